@@ -12,7 +12,8 @@ use smol_str::SmolStr;
 
 use trust_runtime::config::ControlMode;
 use trust_runtime::control::{
-    ControlEndpoint, ControlServer, ControlState, HmiRuntimeDescriptor, SourceRegistry,
+    ControlEndpoint, ControlServer, ControlSessions, ControlState, HmiRuntimeDescriptor,
+    SourceRegistry,
 };
 use trust_runtime::debug::{DebugVariableHandles, RuntimeEvent};
 use trust_runtime::error::RuntimeError;
@@ -67,10 +68,14 @@ impl DebugControlServer {
             io_health: Arc::new(Mutex::new(Vec::<IoDriverStatus>::new())),
             debug_enabled: Arc::new(AtomicBool::new(true)),
             debug_variables: Arc::new(Mutex::new(DebugVariableHandles::new())),
+            sessions: Arc::new(ControlSessions::default()),
             hmi_live: Arc::new(Mutex::new(trust_runtime::hmi::HmiLiveState::default())),
             hmi_descriptor,
             historian: None,
+            event_store: None,
             pairing: None,
+            mesh: None,
+            redundancy: None,
         });
         let server = ControlServer::start(endpoint, state.clone())?;
         let drain = spawn_command_drain(cmd_rx);
@@ -151,7 +156,17 @@ fn spawn_command_drain(rx: std::sync::mpsc::Receiver<ResourceCommand>) -> thread
                 ResourceCommand::MeshSnapshot { respond_to, .. } => {
                     let _ = respond_to.send(IndexMap::<SmolStr, Value>::new());
                 }
-                ResourceCommand::MeshApply { .. } => {}
+                ResourceCommand::MeshApply { .. }
+                | ResourceCommand::RedundancyApply { .. }
+                | ResourceCommand::SetStandby(_) => {}
+                ResourceCommand::RedundancySnapshot { respond_to, .. } => {
+                    let _ = respond_to.send(trust_runtime::RetainSnapshot::default());
+                }
+                ResourceCommand::UpdateSimulationModel { respond_to, .. } => {
+                    let _ = respond_to.send(Err(RuntimeError::ControlError(SmolStr::new(
+                        "simulation models unavailable in debug control",
+                    ))));
+                }
                 ResourceCommand::Snapshot { respond_to } => {
                     let _ = respond_to.send(trust_runtime::debug::DebugSnapshot {
                         storage: trust_runtime::memory::VariableStorage::new(),
@@ -160,6 +175,7 @@ fn spawn_command_drain(rx: std::sync::mpsc::Receiver<ResourceCommand>) -> thread
                 }
                 ResourceCommand::Pause
                 | ResourceCommand::Resume
+                | ResourceCommand::StepCycles { .. }
                 | ResourceCommand::UpdateWatchdog(_)
                 | ResourceCommand::UpdateFaultPolicy(_)
                 | ResourceCommand::UpdateRetainSaveInterval(_)
@@ -30,7 +30,7 @@ use crate::runtime::DebugRuntime;
 use super::io::io_state_from_snapshot;
 use super::protocol_io::{read_message, write_message_locked, write_protocol_log};
 use super::remote::RemoteStop;
-use super::stop::StopCoordinator;
+use super::stop::{stop_description, StopCoordinator};
 use super::util::env_flag;
 use super::{CoordinateConverter, DebugAdapter, DispatchOutcome, LaunchState, StopGate};
 
@@ -49,6 +49,7 @@ impl DebugAdapter {
             variable_handles: HashMap::new(),
             next_variable_ref: 1,
             watch_cache: HashMap::new(),
+            disassembly: None,
             runner: None,
             control_server: None,
             last_io_state: Arc::new(Mutex::new(None)),
@@ -400,6 +401,8 @@ impl DebugAdapter {
             "terminate" => self.handle_terminate(request),
             "setBreakpoints" => self.handle_set_breakpoints(request),
             "setExceptionBreakpoints" => self.handle_set_exception_breakpoints(request),
+            "exceptionInfo" => self.handle_exception_info(request),
+            "disassemble" => self.handle_disassemble(request),
             "breakpointLocations" => self.handle_breakpoint_locations(request),
             "stIoState" => self.handle_io_state(request),
             "stIoWrite" => self.handle_io_write(request),
@@ -418,6 +421,7 @@ impl DebugAdapter {
             "stepIn" => self.handle_step_in(request),
             "stepOut" => self.handle_step_out(request),
             "evaluate" => self.handle_evaluate(request),
+            "completions" => self.handle_completions(request),
             _ => DispatchOutcome {
                 responses: vec![self.error_response(&request, "unsupported command")],
                 ..DispatchOutcome::default()
@@ -631,9 +635,11 @@ impl DebugAdapter {
         let stopped = self.event(
             "stopped",
             Some(StoppedEventBody {
+                description: stop_description(&stop.reason),
                 reason: stop.reason,
                 thread_id,
                 all_threads_stopped: Some(true),
+                text: stop.fault,
             }),
         );
         vec![output, stopped]
@@ -0,0 +1,125 @@
+//! Bytecode disassembly cache for the disassemble request.
+//! - refresh_disassembly: rebuild the listing after a (re)load
+//! - ensure_disassembly: lazily build the listing without blocking a paused runner
+//! - instruction_pointer_for: map a source location onto an instruction address
+
+use trust_runtime::bytecode::{BytecodeModule, DisassembledInstruction};
+use trust_runtime::debug::{location_to_line_col, SourceLocation};
+use trust_runtime::Runtime;
+
+use super::DebugAdapter;
+
+/// One listed instruction with the POU symbol it opens, if any.
+#[derive(Debug, Clone)]
+pub(super) struct DisassemblyEntry {
+    pub(super) symbol: Option<String>,
+    pub(super) instruction: DisassembledInstruction,
+}
+
+/// Render an instruction offset as a DAP memory reference.
+pub(super) fn format_address(offset: u32) -> String {
+    format!("0x{offset:08x}")
+}
+
+/// Parse a DAP memory reference produced by [`format_address`].
+pub(super) fn parse_address(reference: &str) -> Option<i64> {
+    let trimmed = reference.trim();
+    match trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+    {
+        Some(hex) => i64::from_str_radix(hex, 16).ok(),
+        None => trimmed.parse::<i64>().ok(),
+    }
+}
+
+impl DebugAdapter {
+    pub(super) fn refresh_disassembly(&mut self) {
+        let runtime = self.session.runtime_handle();
+        let listing = match runtime.lock() {
+            Ok(guard) => self.build_disassembly(&guard),
+            Err(_) => None,
+        };
+        self.disassembly = listing;
+    }
+
+    pub(super) fn ensure_disassembly(&mut self) -> Option<&[DisassemblyEntry]> {
+        if self.disassembly.is_none() {
+            let runtime = self.session.runtime_handle();
+            let listing = match runtime.try_lock() {
+                Ok(guard) => self.build_disassembly(&guard),
+                Err(_) => None,
+            };
+            self.disassembly = listing;
+        }
+        self.disassembly.as_deref()
+    }
+
+    /// Address of the instruction that starts the statement at `location`.
+    pub(super) fn instruction_pointer_for(&self, location: &SourceLocation) -> Option<String> {
+        let listing = self.disassembly.as_deref()?;
+        let path = self.session.source_for_file_id(location.file_id)?.path?;
+        let text = self.session.source_text_for_file_id(location.file_id)?;
+        let (line, column) = location_to_line_col(text, location);
+        let (line, column) = (line + 1, column + 1);
+        let on_line = |entry: &&DisassemblyEntry| {
+            entry.instruction.statement_start
+                && entry
+                    .instruction
+                    .source
+                    .as_ref()
+                    .is_some_and(|source| source.path == path.as_str() && source.line == line)
+        };
+        listing
+            .iter()
+            .filter(on_line)
+            .find(|entry| {
+                entry
+                    .instruction
+                    .source
+                    .as_ref()
+                    .is_some_and(|source| source.column == column)
+            })
+            .or_else(|| listing.iter().find(on_line))
+            .map(|entry| format_address(entry.instruction.offset))
+    }
+
+    fn build_disassembly(&self, runtime: &Runtime) -> Option<Vec<DisassemblyEntry>> {
+        let mut texts = Vec::new();
+        let mut paths = Vec::new();
+        for file_id in 0.. {
+            let Some(text) = self.session.source_text_for_file_id(file_id) else {
+                break;
+            };
+            let path = self
+                .session
+                .source_for_file_id(file_id)
+                .and_then(|source| source.path)
+                .unwrap_or_else(|| format!("file_{file_id}"));
+            texts.push(text);
+            paths.push(path);
+        }
+        let module = if texts.is_empty() {
+            BytecodeModule::from_runtime(runtime)
+        } else {
+            let paths = paths.iter().map(String::as_str).collect::<Vec<_>>();
+            BytecodeModule::from_runtime_with_sources_and_paths(runtime, &texts, &paths)
+                .or_else(|_| BytecodeModule::from_runtime(runtime))
+        };
+        let pous = module.ok()?.disassemble().ok()?;
+        let listing = pous
+            .into_iter()
+            .flat_map(|pou| {
+                let name = pou.name.to_string();
+                pou.instructions
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(idx, instruction)| DisassemblyEntry {
+                        symbol: (idx == 0).then(|| name.clone()),
+                        instruction,
+                    })
+            })
+            .collect();
+        Some(listing)
+    }
+}
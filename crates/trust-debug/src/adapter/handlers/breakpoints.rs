@@ -1,15 +1,17 @@
 //! Breakpoint-related requests and location queries.
 //! - handle_set_breakpoints: configure source breakpoints
-//! - handle_set_exception_breakpoints: ignore exception breakpoints
+//! - handle_set_exception_breakpoints: map fault filters onto the runtime
+//! - handle_exception_info: describe the fault of an exception stop
 //! - handle_breakpoint_locations: enumerate valid locations
 
 use serde_json::Value;
 
-use trust_runtime::debug::location_to_line_col;
+use trust_runtime::debug::{location_to_line_col, DebugStopReason, FaultBreakpoints};
 
 use crate::protocol::{
-    BreakpointLocation, BreakpointLocationsArguments, BreakpointLocationsResponseBody, Request,
-    SetBreakpointsArguments,
+    BreakpointLocation, BreakpointLocationsArguments, BreakpointLocationsResponseBody,
+    ExceptionBreakpointsFilter, ExceptionInfoArguments, ExceptionInfoResponseBody, Request,
+    SetBreakpointsArguments, SetExceptionBreakpointsArguments,
 };
 
 use super::super::{DebugAdapter, DispatchOutcome};
@@ -68,8 +70,41 @@ impl DebugAdapter {
         &mut self,
         request: Request<Value>,
     ) -> DispatchOutcome {
-        let events =
-            vec![self.debug_output_message("[trust-debug] setExceptionBreakpoints ignored")];
+        let Some(args) = request.arguments.clone().and_then(|value| {
+            serde_json::from_value::<SetExceptionBreakpointsArguments>(value).ok()
+        }) else {
+            return DispatchOutcome {
+                responses: vec![
+                    self.error_response(&request, "invalid setExceptionBreakpoints args")
+                ],
+                ..DispatchOutcome::default()
+            };
+        };
+        // Unknown filters are ignored so newer clients keep working.
+        let filters = args
+            .filters
+            .into_iter()
+            .filter(|filter| matches!(filter.as_str(), "faults" | "unhandled" | "watchdog"))
+            .collect::<Vec<_>>();
+        if let Some(remote) = self.remote_session.as_mut() {
+            if let Err(err) = remote.set_exception_breakpoints(&filters) {
+                return DispatchOutcome {
+                    responses: vec![self.error_response(&request, &err)],
+                    ..DispatchOutcome::default()
+                };
+            }
+        } else {
+            self.session
+                .debug_control()
+                .set_fault_breakpoints(FaultBreakpoints {
+                    all_faults: filters.iter().any(|filter| filter == "faults"),
+                    unhandled_faults: filters.iter().any(|filter| filter == "unhandled"),
+                    watchdog: filters.iter().any(|filter| filter == "watchdog"),
+                });
+        }
+        let events = vec![self.debug_output_message(format!(
+            "[trust-debug] setExceptionBreakpoints: filters={filters:?}"
+        ))];
         DispatchOutcome {
             responses: vec![self.ok_response::<Value>(&request, None)],
             events,
@@ -78,6 +113,61 @@ impl DebugAdapter {
         }
     }
 
+    pub(in crate::adapter) fn handle_exception_info(
+        &mut self,
+        request: Request<Value>,
+    ) -> DispatchOutcome {
+        let Some(_args) = request
+            .arguments
+            .clone()
+            .and_then(|value| serde_json::from_value::<ExceptionInfoArguments>(value).ok())
+        else {
+            return DispatchOutcome {
+                responses: vec![self.error_response(&request, "invalid exceptionInfo args")],
+                ..DispatchOutcome::default()
+            };
+        };
+
+        let fault = if let Some(remote) = self.remote_session.as_mut() {
+            remote
+                .debug_state()
+                .ok()
+                .and_then(|state| state.last_stop)
+                .filter(|stop| stop.reason == "exception")
+                .map(|stop| stop.fault.unwrap_or_default())
+        } else {
+            self.session
+                .debug_control()
+                .last_stop()
+                .filter(|stop| stop.reason == DebugStopReason::Exception)
+                .map(|stop| {
+                    stop.fault
+                        .map(|fault| fault.to_string())
+                        .unwrap_or_default()
+                })
+        };
+        let Some(fault) = fault else {
+            return DispatchOutcome {
+                responses: vec![self.error_response(&request, "not stopped on a fault")],
+                ..DispatchOutcome::default()
+            };
+        };
+        let exception_id = if fault.starts_with("watchdog") {
+            "watchdog"
+        } else {
+            "fault"
+        };
+        let body = ExceptionInfoResponseBody {
+            exception_id: exception_id.to_string(),
+            description: Some(fault),
+            break_mode: "always".to_string(),
+        };
+        DispatchOutcome {
+            responses: vec![self.ok_response(&request, Some(body))],
+            ..DispatchOutcome::default()
+        }
+    }
+
     pub(in crate::adapter) fn handle_breakpoint_locations(
         &mut self,
         request: Request<Value>,
@@ -265,3 +355,36 @@ impl DebugAdapter {
         }
     }
 }
+
+/// Exception breakpoint filters advertised in `initialize`.
+pub(super) fn exception_breakpoint_filters() -> Vec<ExceptionBreakpointsFilter> {
+    vec![
+        ExceptionBreakpointsFilter {
+            filter: "faults".to_string(),
+            label: "All Faults".to_string(),
+            description: Some(
+                "Stop on every runtime fault, including faults the restart policy recovers from"
+                    .to_string(),
+            ),
+            default: Some(false),
+        },
+        ExceptionBreakpointsFilter {
+            filter: "unhandled".to_string(),
+            label: "Unhandled Faults".to_string(),
+            description: Some(
+                "Stop on runtime faults that halt the resource, such as division by zero or an array index out of bounds"
+                    .to_string(),
+            ),
+            default: Some(true),
+        },
+        ExceptionBreakpointsFilter {
+            filter: "watchdog".to_string(),
+            label: "Watchdog Pre-Trip".to_string(),
+            description: Some(
+                "Stop at the statement running when the cycle exceeds the watchdog timeout"
+                    .to_string(),
+            ),
+            default: Some(false),
+        },
+    ]
+}
@@ -0,0 +1,135 @@
+//! Disassemble request handling.
+//! - handle_disassemble: list bytecode instructions around a memory reference
+
+use serde_json::Value;
+
+use crate::protocol::{
+    DisassembleArguments, DisassembleResponseBody, DisassembledInstruction, Request, Source,
+};
+
+use super::super::disassembly::{format_address, parse_address, DisassemblyEntry};
+use super::super::{DebugAdapter, DispatchOutcome};
+
+impl DebugAdapter {
+    pub(in crate::adapter) fn handle_disassemble(
+        &mut self,
+        request: Request<Value>,
+    ) -> DispatchOutcome {
+        if self.remote_session.is_some() {
+            return DispatchOutcome {
+                responses: vec![
+                    self.error_response(&request, "disassemble is not supported in attach mode")
+                ],
+                ..DispatchOutcome::default()
+            };
+        }
+        let Some(args) = request
+            .arguments
+            .clone()
+            .and_then(|value| serde_json::from_value::<DisassembleArguments>(value).ok())
+        else {
+            return DispatchOutcome {
+                responses: vec![self.error_response(&request, "invalid disassemble args")],
+                ..DispatchOutcome::default()
+            };
+        };
+        let Some(address) = parse_address(&args.memory_reference)
+            .map(|address| address.saturating_add(args.offset.unwrap_or(0)))
+        else {
+            return DispatchOutcome {
+                responses: vec![self.error_response(&request, "invalid memory reference")],
+                ..DispatchOutcome::default()
+            };
+        };
+        let count = usize::try_from(args.instruction_count).unwrap_or(0);
+        let listing = self.ensure_disassembly().map(<[DisassemblyEntry]>::to_vec);
+        let Some(listing) = listing else {
+            return DispatchOutcome {
+                responses: vec![self.error_response(&request, "bytecode is not available")],
+                ..DispatchOutcome::default()
+            };
+        };
+
+        // Index of the instruction covering `address` (or the next one after it).
+        let anchor = listing
+            .iter()
+            .position(|entry| {
+                let start = i64::from(entry.instruction.offset);
+                let end = start + entry.instruction.bytes.len() as i64;
+                address < end
+            })
+            .unwrap_or(listing.len()) as i64;
+        let first = anchor + args.instruction_offset.unwrap_or(0);
+
+        let mut instructions = Vec::with_capacity(count);
+        let mut last_path: Option<String> = None;
+        for idx in first..first + count as i64 {
+            let entry = usize::try_from(idx).ok().and_then(|idx| listing.get(idx));
+            let Some(entry) = entry else {
+                instructions.push(DisassembledInstruction {
+                    address: format_address(padding_address(&listing, idx)),
+                    instruction_bytes: None,
+                    instruction: "??".to_string(),
+                    symbol: None,
+                    location: None,
+                    line: None,
+                    column: None,
+                    presentation_hint: Some("invalid".to_string()),
+                });
+                continue;
+            };
+            let source = entry.instruction.source.as_ref();
+            let location = source.and_then(|source| {
+                let path = source.path.to_string();
+                if last_path.as_deref() == Some(path.as_str()) {
+                    return None;
+                }
+                last_path = Some(path.clone());
+                Some(Source {
+                    name: Some(path.clone()),
+                    path: Some(path),
+                    source_reference: None,
+                })
+            });
+            instructions.push(DisassembledInstruction {
+                address: format_address(entry.instruction.offset),
+                instruction_bytes: Some(
+                    entry
+                        .instruction
+                        .bytes
+                        .iter()
+                        .map(|byte| format!("{byte:02x}"))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+                instruction: entry.instruction.text(),
+                symbol: entry.symbol.clone(),
+                location,
+                line: source.map(|source| self.to_client_line(source.line.saturating_sub(1))),
+                column: source.map(|source| self.to_client_column(source.column.saturating_sub(1))),
+                presentation_hint: None,
+            });
+        }
+
+        let body = DisassembleResponseBody { instructions };
+        DispatchOutcome {
+            responses: vec![self.ok_response(&request, Some(body))],
+            ..DispatchOutcome::default()
+        }
+    }
+}
+
+/// Synthetic address for a padding entry outside the listing.
+fn padding_address(listing: &[DisassemblyEntry], idx: i64) -> u32 {
+    let address = if idx < 0 {
+        listing
+            .first()
+            .map_or(0, |entry| i64::from(entry.instruction.offset))
+            + idx
+    } else {
+        listing.last().map_or(0, |entry| {
+            i64::from(entry.instruction.offset) + entry.instruction.bytes.len() as i64
+        }) + (idx - listing.len() as i64)
+    };
+    u32::try_from(address.max(0)).unwrap_or(u32::MAX)
+}
@@ -25,6 +25,7 @@ use super::super::{
     CoordinateConverter, DebugAdapter, DispatchOutcome, LaunchActions, LaunchState, PendingAttach,
     PendingLaunch, PendingStart,
 };
+use super::breakpoints::exception_breakpoint_filters;
 
 const CONFIGURATION_DONE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
 
@@ -57,6 +58,11 @@ impl DebugAdapter {
             supports_set_expression: Some(true),
             supports_pause_request: Some(true),
             supports_terminate_request: Some(true),
+            supports_completions_request: Some(true),
+            supports_exception_info_request: Some(true),
+            exception_breakpoint_filters: Some(exception_breakpoint_filters()),
+            supports_disassemble_request: Some(true),
+            supports_stepping_granularity: Some(true),
         };
 
         let response = self.ok_response(&request, Some(InitializeResponseBody { capabilities }));
@@ -147,6 +153,7 @@ impl DebugAdapter {
                         "[trust-debug] reload_program ok; updated_breakpoints={}",
                         updated.len()
                     )));
+                    self.refresh_disassembly();
                     let mut breakpoint_events = updated
                         .into_iter()
                         .map(|breakpoint| self.breakpoint_event("changed", breakpoint))
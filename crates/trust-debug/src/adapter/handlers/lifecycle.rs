@@ -145,6 +145,11 @@ impl DebugAdapter {
         }
 
         let reload_result = self.session.reload_program(args.program.as_deref());
+        if reload_result.is_ok() {
+            self.refresh_disassembly();
+        } else {
+            self.disassembly = None;
+        }
 
         if was_running {
             self.start_runner();
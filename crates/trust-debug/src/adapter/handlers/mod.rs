@@ -6,8 +6,10 @@
 //! - stack_trace: stackTrace request
 //! - scopes: scope enumeration
 //! - run_control: continue/pause/step
+//! - disassemble: bytecode listing around a memory reference
 
 mod breakpoints;
+mod disassemble;
 mod initialize;
 mod lifecycle;
 mod run_control;
@@ -87,6 +87,11 @@ impl DebugAdapter {
                     column,
                     end_line: None,
                     end_column: None,
+                    instruction_pointer_reference: self
+                        .session
+                        .debug_control()
+                        .last_location()
+                        .and_then(|location| self.instruction_pointer_for(&location)),
                 }]
             } else {
                 Vec::new()
@@ -96,9 +101,8 @@ impl DebugAdapter {
                 .iter()
                 .rev()
                 .map(|frame| {
-                    let resolved = frame_locations
-                        .get(&frame.id)
-                        .and_then(|loc| self.location_to_client(loc));
+                    let frame_location = frame_locations.get(&frame.id);
+                    let resolved = frame_location.and_then(|loc| self.location_to_client(loc));
                     let (source, line, column) = resolved.unwrap_or_else(|| {
                         location
                             .clone()
@@ -112,6 +116,8 @@ impl DebugAdapter {
                         column,
                         end_line: None,
                         end_column: None,
+                        instruction_pointer_reference: frame_location
+                            .and_then(|loc| self.instruction_pointer_for(loc)),
                     }
                 })
                 .collect()
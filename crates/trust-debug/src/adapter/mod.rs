@@ -3,6 +3,7 @@
 //! - handlers: DAP request handlers by area
 //! - variables: variable/evaluate/set logic
 //! - io: IO state/write handling
+//! - disassembly: bytecode listing + source mapping
 //! - protocol_io: message framing + logging
 //! - launch: launch argument helpers
 //! - util: small shared helpers
@@ -10,6 +11,7 @@
 
 mod control_bridge;
 mod core;
+mod disassembly;
 mod handlers;
 mod io;
 mod launch;
@@ -42,6 +44,7 @@ use crate::runtime::DebugRuntime;
 
 use self::control_bridge::DebugControlServer;
 use self::core::DebugRunner;
+use self::disassembly::DisassemblyEntry;
 use self::paused::PausedStateView;
 use self::remote::RemoteSession;
 use self::stop_remote::RemoteStopPoller;
@@ -220,6 +223,7 @@ pub struct DebugAdapter {
     variable_handles: HashMap<u32, VariableHandle>,
     next_variable_ref: u32,
     watch_cache: HashMap<String, Expr>,
+    disassembly: Option<Vec<DisassemblyEntry>>,
     runner: Option<DebugRunner>,
     control_server: Option<DebugControlServer>,
     last_io_state: Arc<Mutex<Option<IoStateEventBody>>>,
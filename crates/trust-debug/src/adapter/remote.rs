@@ -7,10 +7,13 @@ use std::path::PathBuf;
 
 use serde::Deserialize;
 use serde_json::{json, Value};
+use trust_runtime::control::control_error_message;
+use trust_runtime::debug::DebugCompletion;
 
 use crate::protocol::{
     AttachArguments, Breakpoint, BreakpointLocation, BreakpointLocationsResponseBody,
-    EvaluateResponseBody, IoStateEntry, IoStateEventBody, Scope, Source, StackFrame, Variable,
+    EvaluateResponseBody, IoStateEntry, IoStateEventBody, Scope, SetExpressionResponseBody,
+    SetVariableResponseBody, Source, StackFrame, Variable,
 };
 
 type RemoteResult<T> = std::result::Result<T, String>;
@@ -43,8 +46,9 @@ struct ControlResponse {
     ok: bool,
     #[serde(default)]
     result: Option<Value>,
+    /// Error object, or a plain string from runtimes with `legacy_errors`.
     #[serde(default)]
-    error: Option<String>,
+    error: Option<Value>,
 }
 
 #[derive(Debug, Clone)]
@@ -53,6 +57,7 @@ pub struct RemoteStop {
     pub thread_id: Option<u32>,
     pub file_id: Option<u32>,
     pub breakpoint_generation: Option<u64>,
+    pub fault: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -153,6 +158,14 @@ impl RemoteSession {
         Ok((breakpoints, file_id, generation))
     }
 
+    pub fn set_exception_breakpoints(&mut self, filters: &[String]) -> RemoteResult<()> {
+        let _payload = self.request(
+            "breakpoints.exceptions",
+            Some(json!({ "filters": filters })),
+        )?;
+        Ok(())
+    }
+
     pub fn clear_breakpoints(&mut self, source: &str) -> RemoteResult<()> {
         let params = json!({
             "source": source,
@@ -245,6 +258,54 @@ impl RemoteSession {
         serde_json::from_value::<EvaluateResponseBody>(payload).map_err(|err| err.to_string())
     }
 
+    pub fn set_variable(
+        &mut self,
+        variables_reference: u32,
+        name: &str,
+        value: &str,
+        force: bool,
+    ) -> RemoteResult<SetVariableResponseBody> {
+        let params = json!({
+            "variables_reference": variables_reference,
+            "name": name,
+            "value": value,
+            "force": force,
+        });
+        let payload = self.request("debug.set_variable", Some(params))?;
+        serde_json::from_value::<SetVariableResponseBody>(payload).map_err(|err| err.to_string())
+    }
+
+    pub fn set_expression(
+        &mut self,
+        expression: &str,
+        value: &str,
+        frame_id: Option<u32>,
+    ) -> RemoteResult<SetExpressionResponseBody> {
+        let params = json!({
+            "expression": expression,
+            "value": value,
+            "frame_id": frame_id,
+        });
+        let payload = self.request("debug.set_expression", Some(params))?;
+        serde_json::from_value::<SetExpressionResponseBody>(payload).map_err(|err| err.to_string())
+    }
+
+    pub fn completions(
+        &mut self,
+        text: &str,
+        column: usize,
+        frame_id: Option<u32>,
+    ) -> RemoteResult<Vec<DebugCompletion>> {
+        let params = json!({
+            "text": text,
+            "column": column,
+            "frame_id": frame_id,
+        });
+        let payload = self.request("debug.complete", Some(params))?;
+        let targets = payload.get("targets").cloned().unwrap_or_else(|| json!([]));
+        serde_json::from_value::<Vec<DebugCompletion>>(targets).map_err(|err| err.to_string())
+    }
+
     pub fn pause(&mut self) -> RemoteResult<()> {
         let _ = self.request("pause", None)?;
         Ok(())
@@ -347,8 +408,10 @@ impl RemoteSession {
         if !response.ok {
             let message = response
                 .error
-                .unwrap_or_else(|| "request failed".to_string());
-            return Err(message);
+                .as_ref()
+                .and_then(control_error_message)
+                .unwrap_or("request failed");
+            return Err(message.to_string());
         }
         Ok(response.result.unwrap_or_else(|| json!({})))
     }
@@ -471,10 +534,15 @@ fn parse_stop(value: &Value) -> Option<RemoteStop> {
     let breakpoint_generation = value
         .get("breakpoint_generation")
         .and_then(|value| value.as_u64());
+    let fault = value
+        .get("fault")
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string());
     Some(RemoteStop {
         reason,
         thread_id,
         file_id,
         breakpoint_generation,
+        fault,
     })
 }
@@ -70,7 +70,7 @@ impl StopCoordinator {
                     return false;
                 }
             }
-            DebugStopReason::Breakpoint | DebugStopReason::Step => {
+            DebugStopReason::Breakpoint | DebugStopReason::Step | DebugStopReason::Exception => {
                 self.pause_expected.store(false, Ordering::SeqCst);
             }
         }
@@ -114,6 +114,7 @@ impl StopCoordinator {
             DebugStopReason::Step => "step",
             DebugStopReason::Pause => "pause",
             DebugStopReason::Entry => "entry",
+            DebugStopReason::Exception => "exception",
         };
         let thread_id = stop.thread_id.or(Some(1));
         let output_body = OutputEventBody {
@@ -138,8 +139,10 @@ impl StopCoordinator {
         let all_threads_stopped = self.stop_control.target_thread().is_none();
         let body = StoppedEventBody {
             reason: reason.to_string(),
+            description: stop_description(reason),
             thread_id,
             all_threads_stopped: Some(all_threads_stopped),
+            text: stop.fault.as_ref().map(ToString::to_string),
         };
         let event = Event {
             seq: self.seq.fetch_add(1, Ordering::Relaxed),
@@ -197,6 +200,7 @@ impl StopCoordinator {
             DebugStopReason::Step => "step",
             DebugStopReason::Pause => "pause",
             DebugStopReason::Entry => "entry",
+            DebugStopReason::Exception => "exception",
         };
         let location = stop
             .location
@@ -216,6 +220,11 @@ impl StopCoordinator {
     }
 }
 
+/// Label shown by the client for stops that need more than the reason.
+pub(super) fn stop_description(reason: &str) -> Option<String> {
+    (reason == "exception").then(|| "Paused on fault".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -249,6 +258,7 @@ mod tests {
             location: Some(location),
             thread_id: Some(1),
             breakpoint_generation: Some(generation),
+            fault: None,
         };
         assert!(coordinator.should_emit_stop(&stop));
     }
@@ -264,6 +274,7 @@ mod tests {
             location: Some(location),
             thread_id: Some(1),
             breakpoint_generation: Some(999),
+            fault: None,
         };
         assert!(!coordinator.should_emit_stop(&stop));
     }
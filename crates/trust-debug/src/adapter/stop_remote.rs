@@ -12,6 +12,7 @@ use crate::protocol::{Event, MessageType, OutputEventBody, StoppedEventBody};
 
 use super::protocol_io::write_protocol_log;
 use super::remote::{RemoteEndpoint, RemoteSession, RemoteStop};
+use super::stop::stop_description;
 use super::StopGate;
 
 const POLL_INTERVAL: Duration = Duration::from_millis(50);
@@ -76,7 +77,7 @@ fn should_emit_stop(
                 return false;
             }
         }
-        "breakpoint" | "step" => {
+        "breakpoint" | "step" | "exception" => {
             pause_expected.store(false, Ordering::SeqCst);
         }
         _ => {}
@@ -121,8 +122,10 @@ fn emit_stop_event(
     };
     let stopped_body = StoppedEventBody {
         reason: stop.reason.clone(),
+        description: stop_description(&stop.reason),
         thread_id,
         all_threads_stopped: Some(true),
+        text: stop.fault.clone(),
     };
     let stopped_event = Event {
         seq: seq.fetch_add(1, Ordering::Relaxed),
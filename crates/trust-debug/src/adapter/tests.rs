@@ -5,13 +5,15 @@
 use super::protocol_io::{read_message, write_message};
 use super::*;
 use crate::protocol::{
-    BreakpointLocationsArguments, BreakpointLocationsResponseBody, ContinueArguments,
-    EvaluateArguments, EvaluateResponseBody, Event, InitializeArguments, InitializeResponseBody,
-    IoStateEventBody, IoWriteArguments, MessageType, NextArguments, PauseArguments, Request,
-    Response, ScopesArguments, ScopesResponseBody, SetBreakpointsArguments,
-    SetBreakpointsResponseBody, SetExpressionArguments, SetExpressionResponseBody, Source,
-    SourceBreakpoint, StackTraceArguments, StackTraceResponseBody, StepInArguments,
-    StepOutArguments, ThreadsResponseBody, VariablesArguments, VariablesResponseBody,
+    BreakpointLocationsArguments, BreakpointLocationsResponseBody, CompletionsArguments,
+    CompletionsResponseBody, ContinueArguments, DisassembleArguments, DisassembleResponseBody,
+    EvaluateArguments, EvaluateResponseBody, Event, ExceptionInfoResponseBody, InitializeArguments,
+    InitializeResponseBody, IoStateEventBody, IoWriteArguments, MessageType, NextArguments,
+    PauseArguments, Request, Response, ScopesArguments, ScopesResponseBody,
+    SetBreakpointsArguments, SetBreakpointsResponseBody, SetExpressionArguments,
+    SetExpressionResponseBody, Source, SourceBreakpoint, StackTraceArguments,
+    StackTraceResponseBody, StepInArguments, StepOutArguments, ThreadsResponseBody,
+    VariablesArguments, VariablesResponseBody,
 };
 use crate::DebugSession;
 use indexmap::IndexMap;
@@ -446,6 +448,15 @@ fn dispatch_initialize_emits_initialized_event() {
         Some(true)
     );
     assert_eq!(capabilities.supports_log_points, Some(true));
+    assert_eq!(capabilities.supports_exception_info_request, Some(true));
+    assert_eq!(capabilities.supports_disassemble_request, Some(true));
+    let filters = capabilities
+        .exception_breakpoint_filters
+        .unwrap()
+        .into_iter()
+        .map(|filter| filter.filter)
+        .collect::<Vec<_>>();
+    assert_eq!(filters, vec!["faults", "unhandled", "watchdog"]);
     let saw_initialized = outcome.events.iter().any(|value| {
         let event: Event<serde_json::Value> = serde_json::from_value(value.clone()).unwrap();
         event.event == "initialized"
@@ -842,6 +853,140 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn dap_exception_breakpoint_stops_on_fault_with_message() {
+    let source = r#"
+CONFIGURATION Conf
+VAR_GLOBAL
+divisor : INT := 0;
+ratio : INT := 0;
+END_VAR
+PROGRAM P1 : Prog1;
+END_CONFIGURATION
+
+PROGRAM Prog1
+ratio := 100 / divisor;
+END_PROGRAM
+"#;
+
+    let harness = TestHarness::from_source(source).unwrap();
+    let mut session = DebugSession::new(harness.into_runtime());
+    session.register_source("main.st", 0, source);
+    let mut adapter = DebugAdapter::new(session);
+
+    let request = Request {
+        seq: 1,
+        message_type: MessageType::Request,
+        command: "setExceptionBreakpoints".to_string(),
+        arguments: Some(serde_json::json!({ "filters": ["unhandled", "custom"] })),
+    };
+    let outcome = adapter.dispatch_request(request);
+    let response: Response<serde_json::Value> =
+        serde_json::from_value(outcome.responses[0].clone()).unwrap();
+    assert!(response.success);
+
+    let control = adapter.session().debug_control();
+    assert!(control.fault_breakpoints().unhandled_faults);
+    assert!(!control.fault_breakpoints().all_faults);
+    let (stop_tx, stop_rx) = std::sync::mpsc::channel();
+    control.set_stop_sender(stop_tx);
+
+    let runtime = adapter.session().runtime_handle();
+    let handle = std::thread::spawn(move || {
+        let mut guard = runtime.lock().unwrap();
+        guard.execute_cycle()
+    });
+
+    let stop = stop_rx
+        .recv_timeout(std::time::Duration::from_secs(2))
+        .unwrap();
+    assert_eq!(stop.reason, DebugStopReason::Exception);
+    assert_eq!(stop.fault.as_deref(), Some("division by zero"));
+
+    let request = Request {
+        seq: 2,
+        message_type: MessageType::Request,
+        command: "exceptionInfo".to_string(),
+        arguments: Some(serde_json::json!({ "threadId": 1 })),
+    };
+    let outcome = adapter.dispatch_request(request);
+    let response: Response<ExceptionInfoResponseBody> =
+        serde_json::from_value(outcome.responses[0].clone()).unwrap();
+    let body = response.body.unwrap();
+    assert_eq!(body.exception_id, "fault");
+    assert_eq!(body.description.as_deref(), Some("division by zero"));
+
+    control.continue_run();
+    assert!(handle.join().unwrap().is_err());
+}
+
+#[test]
+fn dispatch_disassemble_maps_instructions_to_source() {
+    let source = r#"
+PROGRAM Main
+VAR
+    x : INT := INT#0;
+END_VAR
+x := x + INT#1;
+END_PROGRAM
+"#;
+    let harness = TestHarness::from_source(source).unwrap();
+    let mut session = DebugSession::new(harness.into_runtime());
+    session.register_source("main.st", 0, source);
+    let mut adapter = DebugAdapter::new(session);
+    adapter.refresh_disassembly();
+
+    let request = Request {
+        seq: 1,
+        message_type: MessageType::Request,
+        command: "disassemble".to_string(),
+        arguments: Some(
+            serde_json::to_value(DisassembleArguments {
+                memory_reference: "0x00000000".to_string(),
+                offset: None,
+                instruction_offset: Some(-1),
+                instruction_count: 64,
+                resolve_symbols: Some(true),
+            })
+            .unwrap(),
+        ),
+    };
+    let outcome = adapter.dispatch_request(request);
+    let response: Response<DisassembleResponseBody> =
+        serde_json::from_value(outcome.responses[0].clone()).unwrap();
+    assert!(response.success);
+    let instructions = response.body.unwrap().instructions;
+    assert_eq!(instructions.len(), 64);
+    assert_eq!(
+        instructions[0].presentation_hint.as_deref(),
+        Some("invalid")
+    );
+    assert!(instructions[1].symbol.is_some());
+    assert!(instructions
+        .iter()
+        .any(|instruction| instruction.symbol.as_deref() == Some("Main")));
+
+    let line = source
+        .lines()
+        .position(|line| line.contains("x := x + INT#1;"))
+        .unwrap() as u32
+        + 1;
+    let add = instructions
+        .iter()
+        .find(|instruction| instruction.instruction == "ADD")
+        .expect("ADD instruction");
+    assert_eq!(add.line, Some(line));
+    let located = instructions
+        .iter()
+        .find_map(|instruction| instruction.location.as_ref())
+        .expect("source location");
+    assert!(located.path.as_deref().unwrap().ends_with("main.st"));
+    assert_eq!(
+        instructions.last().unwrap().presentation_hint.as_deref(),
+        Some("invalid")
+    );
+}
+
 #[test]
 fn dispatch_threads_stack_scopes_variables() {
     let mut runtime = Runtime::new();
@@ -1151,6 +1296,50 @@ fn dispatch_evaluate_returns_value() {
     assert_eq!(body.result, "DInt(42)");
 }
 
+#[test]
+fn dispatch_completions_returns_struct_members() {
+    let mut runtime = Runtime::new();
+    let frame_id = runtime.storage_mut().push_frame("MAIN");
+    let mut fields = IndexMap::new();
+    fields.insert(SmolStr::new("X"), RuntimeValue::Int(1));
+    fields.insert(SmolStr::new("Y"), RuntimeValue::Int(2));
+    runtime.storage_mut().set_local(
+        "point",
+        RuntimeValue::Struct(StructValue {
+            type_name: SmolStr::new("Point"),
+            fields,
+        }),
+    );
+
+    let session = DebugSession::new(runtime);
+    let mut adapter = DebugAdapter::new(session);
+
+    let request = Request {
+        seq: 1,
+        message_type: MessageType::Request,
+        command: "completions".to_string(),
+        arguments: Some(
+            serde_json::to_value(CompletionsArguments {
+                text: "point.".to_string(),
+                column: 7,
+                line: None,
+                frame_id: Some(frame_id.0),
+            })
+            .unwrap(),
+        ),
+    };
+    let outcome = adapter.dispatch_request(request);
+    let response: Response<CompletionsResponseBody> =
+        serde_json::from_value(outcome.responses[0].clone()).unwrap();
+    assert!(response.success);
+    let targets = response.body.unwrap().targets;
+    let labels: Vec<_> = targets.iter().map(|item| item.label.as_str()).collect();
+    assert_eq!(labels, vec!["X", "Y"]);
+    assert_eq!(targets[0].item_type.as_deref(), Some("field"));
+    assert_eq!(targets[0].start, Some(7));
+    assert_eq!(targets[0].length, Some(0));
+}
+
 #[test]
 fn dispatch_evaluate_rejects_calls() {
     let runtime = Runtime::new();
@@ -0,0 +1,74 @@
+//! Debug console completions.
+//! - handle_completions: DAP completions request (local snapshot or remote `debug.complete`)
+
+use serde_json::Value;
+
+use trust_runtime::debug::{complete_debug_expression, DebugCompletion};
+use trust_runtime::memory::FrameId;
+
+use crate::protocol::{CompletionItem, CompletionsArguments, CompletionsResponseBody, Request};
+
+use super::super::{DebugAdapter, DispatchOutcome};
+
+impl DebugAdapter {
+    pub(in crate::adapter) fn handle_completions(
+        &mut self,
+        request: Request<Value>,
+    ) -> DispatchOutcome {
+        let Some(args) = request
+            .arguments
+            .clone()
+            .and_then(|value| serde_json::from_value::<CompletionsArguments>(value).ok())
+        else {
+            return DispatchOutcome {
+                responses: vec![self.error_response(&request, "invalid completions args")],
+                ..DispatchOutcome::default()
+            };
+        };
+        let cursor = self
+            .coordinate
+            .to_runtime_column(args.column)
+            .unwrap_or_default() as usize;
+
+        let completions = if let Some(remote) = self.remote_session.as_mut() {
+            match remote.completions(&args.text, cursor, args.frame_id) {
+                Ok(completions) => completions,
+                Err(err) => {
+                    return DispatchOutcome {
+                        responses: vec![self.error_response(&request, &err)],
+                        ..DispatchOutcome::default()
+                    };
+                }
+            }
+        } else if let Some(snapshot) = self.session.debug_control().snapshot() {
+            let frame_id = args
+                .frame_id
+                .map(FrameId)
+                .filter(|frame_id| snapshot.storage.frames().iter().any(|f| f.id == *frame_id));
+            complete_debug_expression(&snapshot.storage, frame_id, &args.text, Some(cursor))
+        } else if let Ok(runtime) = self.session.runtime_handle().lock() {
+            complete_debug_expression(runtime.storage(), None, &args.text, Some(cursor))
+        } else {
+            Vec::new()
+        };
+
+        let targets = completions
+            .into_iter()
+            .map(|completion| self.completion_item(completion))
+            .collect();
+        DispatchOutcome {
+            responses: vec![self.ok_response(&request, Some(CompletionsResponseBody { targets }))],
+            ..DispatchOutcome::default()
+        }
+    }
+
+    fn completion_item(&self, completion: DebugCompletion) -> CompletionItem {
+        CompletionItem {
+            label: completion.label,
+            detail: completion.detail,
+            item_type: Some(completion.kind),
+            start: Some(self.coordinate.to_client_column(completion.start as u32)),
+            length: Some(completion.length as u32),
+        }
+    }
+}
@@ -210,6 +210,12 @@ impl DebugAdapter {
                 loop_depth: 0,
                 pause_requested: false,
                 execution_deadline: None,
+                workers: None,
+                bounds: trust_runtime::task::BoundsPolicy::Fault,
+                overflow: trust_runtime::eval::ops::OverflowPolicy::Wrap,
+                subrange: trust_runtime::task::SubrangePolicy::Off,
+                fault_site: None,
+                pou_code: None,
             };
             trust_runtime::eval::eval_expr(&mut ctx, expr)
         };
@@ -308,6 +314,12 @@ impl DebugAdapter {
                 loop_depth: 0,
                 pause_requested: false,
                 execution_deadline: None,
+                workers: None,
+                bounds: trust_runtime::task::BoundsPolicy::Fault,
+                overflow: trust_runtime::eval::ops::OverflowPolicy::Wrap,
+                subrange: trust_runtime::task::SubrangePolicy::Off,
+                fault_site: None,
+                pou_code: None,
             };
             f(&mut ctx)
         };
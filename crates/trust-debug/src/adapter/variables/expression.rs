@@ -38,15 +38,6 @@ impl DebugAdapter {
             };
         };
 
-        if self.remote_session.is_some() {
-            return DispatchOutcome {
-                responses: vec![
-                    self.error_response(&request, "setExpression not supported in attach mode")
-                ],
-                ..DispatchOutcome::default()
-            };
-        }
-
         let directive = match parse_set_directive(&args.value) {
             Ok(directive) => directive,
             Err(message) => {
@@ -56,6 +47,33 @@ impl DebugAdapter {
                 };
             }
         };
+
+        if let Some(remote) = self.remote_session.as_mut() {
+            let result = match &directive {
+                SetDirective::Write(raw) => {
+                    remote.set_expression(&args.expression, raw, args.frame_id)
+                }
+                _ => Err("force/release is not supported for setExpression".to_string()),
+            };
+            return match result {
+                Ok(body) => DispatchOutcome {
+                    responses: vec![self.ok_response(&request, Some(body))],
+                    events: vec![self.event(
+                        "invalidated",
+                        Some(InvalidatedEventBody {
+                            areas: Some(vec!["variables".to_string()]),
+                            thread_id: None,
+                            stack_frame_id: args.frame_id,
+                        }),
+                    )],
+                    ..DispatchOutcome::default()
+                },
+                Err(err) => DispatchOutcome {
+                    responses: vec![self.error_response(&request, &err)],
+                    ..DispatchOutcome::default()
+                },
+            };
+        }
         let mut frame_id = args.frame_id.map(FrameId);
         let snapshot = self.session.debug_control().snapshot();
         let paused = snapshot.is_some();
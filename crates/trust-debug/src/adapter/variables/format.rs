@@ -3,7 +3,7 @@
 //! - value_type_name/type_id_for_value: primitive mapping
 
 use trust_hir::TypeId;
-use trust_runtime::value::Value as RuntimeValue;
+use trust_runtime::value::{format_time_literal, DateTimeProfile, Value as RuntimeValue};
 
 fn primitive_type_info(value: &RuntimeValue) -> Option<(&'static str, TypeId)> {
     match value {
@@ -69,12 +69,13 @@ pub(in crate::adapter) fn format_value(value: &RuntimeValue) -> String {
         RuntimeValue::WChar(value) => char::from_u32((*value).into()).unwrap_or('?').to_string(),
         RuntimeValue::Array(value) => format!("[{}]", value.elements.len()),
         RuntimeValue::Struct(value) => format!("{} {{...}}", value.type_name),
-        RuntimeValue::Enum(value) => format!("{}::{}", value.type_name, value.variant_name),
+        RuntimeValue::Enum(value) => format!("{}#{}", value.type_name, value.variant_name),
         RuntimeValue::Reference(Some(_)) => "REF".to_string(),
         RuntimeValue::Reference(None) => "NULL_REF".to_string(),
         RuntimeValue::Instance(value) => format!("Instance({})", value.0),
         RuntimeValue::Null => "NULL".to_string(),
-        _ => format!("{value:?}"),
+        _ => format_time_literal(value, DateTimeProfile::default())
+            .unwrap_or_else(|| format!("{value:?}")),
     }
 }
 
@@ -5,8 +5,10 @@
 //! - set: setVariable handling
 //! - expression: setExpression handling
 //! - eval: evaluate + snapshot evaluation
+//! - complete: debug console completions
 //! - format: value formatting + type mapping
 
+mod complete;
 mod eval;
 mod expression;
 mod format;
@@ -1,5 +1,6 @@
 //! setVariable handling for live runtime.
 //! - handle_set_variable: parse args and write values (or delegate to paused)
+//! - handle_set_variable_remote: forward writes to an attached runtime
 
 use serde_json::Value;
 
@@ -33,13 +34,18 @@ impl DebugAdapter {
             };
         };
 
+        let directive = match parse_set_directive(&args.value) {
+            Ok(directive) => directive,
+            Err(message) => {
+                return DispatchOutcome {
+                    responses: vec![self.error_response(&request, &message)],
+                    ..DispatchOutcome::default()
+                };
+            }
+        };
+
         if self.remote_session.is_some() {
-            return DispatchOutcome {
-                responses: vec![
-                    self.error_response(&request, "setVariable not supported in attach mode")
-                ],
-                ..DispatchOutcome::default()
-            };
+            return self.handle_set_variable_remote(request, args, directive);
         }
 
         let Some(handle) = self
@@ -52,16 +58,6 @@ impl DebugAdapter {
                 ..DispatchOutcome::default()
             };
         };
-
-        let directive = match parse_set_directive(&args.value) {
-            Ok(directive) => directive,
-            Err(message) => {
-                return DispatchOutcome {
-                    responses: vec![self.error_response(&request, &message)],
-                    ..DispatchOutcome::default()
-                };
-            }
-        };
         let force_requested = matches!(directive, SetDirective::Force(_));
 
         let snapshot = self.session.debug_control().snapshot();
@@ -507,4 +503,42 @@ impl DebugAdapter {
             stop_gate: None,
         }
     }
+
+    fn handle_set_variable_remote(
+        &mut self,
+        request: Request<Value>,
+        args: SetVariableArguments,
+        directive: SetDirective,
+    ) -> DispatchOutcome {
+        let Some(remote) = self.remote_session.as_mut() else {
+            return DispatchOutcome::default();
+        };
+        let result = match &directive {
+            SetDirective::Write(raw) => {
+                remote.set_variable(args.variables_reference, &args.name, raw, false)
+            }
+            SetDirective::Force(raw) => {
+                remote.set_variable(args.variables_reference, &args.name, raw, true)
+            }
+            SetDirective::Release => Err("release is not supported in attach mode".to_string()),
+        };
+        match result {
+            Ok(body) => DispatchOutcome {
+                responses: vec![self.ok_response(&request, Some(body))],
+                events: vec![self.event(
+                    "invalidated",
+                    Some(InvalidatedEventBody {
+                        areas: Some(vec!["variables".to_string()]),
+                        thread_id: None,
+                        stack_frame_id: None,
+                    }),
+                )],
+                ..DispatchOutcome::default()
+            },
+            Err(err) => DispatchOutcome {
+                responses: vec![self.error_response(&request, &err)],
+                ..DispatchOutcome::default()
+            },
+        }
+    }
 }
@@ -58,9 +58,14 @@ pub struct Event<T> {
 pub struct StoppedEventBody {
     pub reason: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thread_id: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub all_threads_stopped: Option<bool>,
+    /// Extra detail, such as the fault message of an exception stop.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
 }
 
 /// Terminated event body.
@@ -233,6 +238,28 @@ pub struct Capabilities {
     pub supports_pause_request: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub supports_terminate_request: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_completions_request: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_exception_info_request: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exception_breakpoint_filters: Option<Vec<ExceptionBreakpointsFilter>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_disassemble_request: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supports_stepping_granularity: Option<bool>,
+}
+
+/// Exception breakpoint filter offered to the client.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExceptionBreakpointsFilter {
+    pub filter: String,
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<bool>,
 }
 
 #[cfg(test)]
@@ -387,6 +414,40 @@ pub struct EvaluateResponseBody {
     pub indexed_variables: Option<u32>,
 }
 
+/// Arguments for `completions`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionsArguments {
+    pub text: String,
+    pub column: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame_id: Option<u32>,
+}
+
+/// DAP completion candidate.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionItem {
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub item_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<u32>,
+}
+
+/// Response body for `completions`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionsResponseBody {
+    pub targets: Vec<CompletionItem>,
+}
+
 /// DAP thread description.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -427,6 +488,9 @@ pub struct StackFrame {
     pub end_line: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end_column: Option<u32>,
+    /// Bytecode address of the statement the frame is executing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instruction_pointer_reference: Option<String>,
 }
 
 /// Response body for `stackTrace`.
@@ -545,6 +609,47 @@ pub struct SetExpressionResponseBody {
     pub indexed_variables: Option<u32>,
 }
 
+/// Arguments for `disassemble`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DisassembleArguments {
+    pub memory_reference: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instruction_offset: Option<i64>,
+    pub instruction_count: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolve_symbols: Option<bool>,
+}
+
+/// Response body for `disassemble`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DisassembleResponseBody {
+    pub instructions: Vec<DisassembledInstruction>,
+}
+
+/// One bytecode instruction in a `disassemble` response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DisassembledInstruction {
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction_bytes: Option<String>,
+    pub instruction: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Source>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presentation_hint: Option<String>,
+}
+
 /// DAP breakpoint requested by the client.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -653,6 +758,31 @@ pub struct SetBreakpointsArguments {
     pub source_modified: Option<bool>,
 }
 
+/// Arguments for `setExceptionBreakpoints`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SetExceptionBreakpointsArguments {
+    #[serde(default)]
+    pub filters: Vec<String>,
+}
+
+/// Arguments for `exceptionInfo`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExceptionInfoArguments {
+    pub thread_id: u32,
+}
+
+/// Response body for `exceptionInfo`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExceptionInfoResponseBody {
+    pub exception_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub break_mode: String,
+}
+
 /// Arguments for `breakpointLocations`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -318,6 +318,7 @@ pub(in crate::db) fn method_signature_from_table(
         parameters: params,
         visibility: symbol.visibility,
         range: symbol.range,
+        has_default_body: symbol.modifiers.has_default_body,
     })
 }
 
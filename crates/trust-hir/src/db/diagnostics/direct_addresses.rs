@@ -0,0 +1,106 @@
+use super::super::*;
+use crate::direct_address::{direct_address_uses, DirectAddressUse, DirectAddressUseKind};
+
+const MAX_RELATED_SITES: usize = 3;
+
+/// Warns when an output or memory address is assigned in more than one place in the
+/// project: bound by several `AT` declarations or `VAR_CONFIG` entries, written by
+/// several POUs, or both. Writes within one POU count as one place.
+pub(in crate::db) fn check_duplicate_address_assignments(
+    root: &SyntaxNode,
+    project_roots: &[(FileId, SyntaxNode)],
+    file_id: FileId,
+    diagnostics: &mut DiagnosticBuilder,
+) {
+    let local = assignment_sites(root);
+    if local.is_empty() {
+        return;
+    }
+
+    let mut counts: FxHashMap<SmolStr, usize> = FxHashMap::default();
+    for site in &local {
+        *counts.entry(site.address.clone()).or_default() += 1;
+    }
+    for (other_id, other_root) in project_roots {
+        if *other_id == file_id {
+            continue;
+        }
+        for site in assignment_sites(other_root) {
+            if let Some(count) = counts.get_mut(&site.address) {
+                *count += 1;
+            }
+        }
+    }
+
+    for site in &local {
+        let count = counts.get(&site.address).copied().unwrap_or_default();
+        if count < 2 {
+            continue;
+        }
+        let mut diagnostic = Diagnostic::warning(
+            DiagnosticCode::DuplicateAddressAssignment,
+            site.range,
+            format!(
+                "direct address '{}' is assigned in {count} places",
+                site.address
+            ),
+        );
+        for other in local
+            .iter()
+            .filter(|other| other.address == site.address && other.range != site.range)
+            .take(MAX_RELATED_SITES)
+        {
+            diagnostic = diagnostic.with_related(
+                other.range,
+                format!(
+                    "'{}' is also {} here",
+                    other.address,
+                    site_label(other.kind)
+                ),
+            );
+        }
+        diagnostics.add(diagnostic);
+    }
+}
+
+/// Bindings and writes of `%Q` and `%M` addresses, with writes reduced to the first
+/// one per POU.
+fn assignment_sites(root: &SyntaxNode) -> Vec<DirectAddressUse> {
+    let mut written = FxHashSet::default();
+    direct_address_uses(root)
+        .into_iter()
+        .filter(|site| site.kind.is_assignment())
+        .filter(|site| matches!(site.address.as_bytes().get(1), Some(b'Q' | b'M')))
+        .filter(|site| {
+            site.kind != DirectAddressUseKind::Write
+                || written.insert((site.address.clone(), owning_pou(root, site.range)))
+        })
+        .collect()
+}
+
+fn owning_pou(root: &SyntaxNode, range: TextRange) -> Option<TextRange> {
+    root.token_at_offset(range.start())
+        .right_biased()?
+        .parent_ancestors()
+        .find(|node| {
+            matches!(
+                node.kind(),
+                SyntaxKind::Program
+                    | SyntaxKind::Function
+                    | SyntaxKind::FunctionBlock
+                    | SyntaxKind::Class
+                    | SyntaxKind::Method
+                    | SyntaxKind::Action
+                    | SyntaxKind::Property
+            )
+        })
+        .map(|node| node.text_range())
+}
+
+fn site_label(kind: DirectAddressUseKind) -> &'static str {
+    match kind {
+        DirectAddressUseKind::Declaration => "declared",
+        DirectAddressUseKind::Configuration => "mapped by VAR_CONFIG",
+        DirectAddressUseKind::Write | DirectAddressUseKind::Read => "written",
+    }
+}
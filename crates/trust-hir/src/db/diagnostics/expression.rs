@@ -60,5 +60,7 @@ pub(in crate::db) fn is_expression_kind(kind: SyntaxKind) -> bool {
             | SyntaxKind::ThisExpr
             | SyntaxKind::SuperExpr
             | SyntaxKind::SizeOfExpr
+            | SyntaxKind::InitializerList
+            | SyntaxKind::ArrayInitializer
     )
 }
@@ -1,11 +1,13 @@
 mod configuration;
 mod context;
+mod direct_addresses;
 mod expression;
 mod globals;
 mod nondeterminism;
 mod oop;
 mod shared_globals;
 mod type_check;
+mod unions;
 mod unreachable;
 mod unused;
 mod using;
@@ -16,6 +18,7 @@ mod tests;
 pub(super) use complexity::check_cyclomatic_complexity;
 pub(super) use configuration::check_configuration_semantics;
 pub(super) use context::{expression_context, is_pou_kind};
+pub(super) use direct_addresses::check_duplicate_address_assignments;
 pub(super) use expression::{expression_by_id, expression_id_at_offset, is_expression_kind};
 pub(super) use globals::{
     check_global_external_links_with_project, resolve_declared_var_types_with_project,
@@ -28,6 +31,7 @@ pub(super) use oop::{
 };
 pub(super) use shared_globals::check_shared_global_task_hazards;
 pub(super) use type_check::type_check_file;
+pub(super) use unions::check_union_layouts;
 pub(super) use unreachable::check_unreachable_statements;
 pub(super) use unused::{add_unused_symbol_warnings, collect_used_symbols};
 pub(super) use using::check_using_directives;
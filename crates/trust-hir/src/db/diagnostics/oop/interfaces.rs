@@ -205,7 +205,8 @@ fn check_interface_methods_with_table(
 ) {
     for (key, expected_sig) in expected {
         let Some(actual_sig) = provided.get(key) else {
-            if !context.allow_missing {
+            // Interface methods with a default body may be left unimplemented.
+            if !context.allow_missing && !expected_sig.has_default_body {
                 diagnostics.error(
                     DiagnosticCode::InvalidOperation,
                     context.interface_range,
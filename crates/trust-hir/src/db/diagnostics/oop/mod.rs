@@ -20,12 +20,24 @@ pub(super) fn visibility_label(visibility: Visibility) -> &'static str {
     }
 }
 
+/// Lower-case label for a CLASS or FUNCTION_BLOCK owner in diagnostics.
+pub(super) fn owner_kind_label(kind: &SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::FunctionBlock => "function block",
+        _ => "class",
+    }
+}
+
 pub(in crate::db) fn check_class_semantics(
     symbols: &SymbolTable,
     root: &SyntaxNode,
     diagnostics: &mut DiagnosticBuilder,
 ) {
-    for node in root.descendants().filter(|n| n.kind() == SyntaxKind::Class) {
+    for node in root
+        .descendants()
+        .filter(|n| matches!(n.kind(), SyntaxKind::Class | SyntaxKind::FunctionBlock))
+    {
+        let is_class = node.kind() == SyntaxKind::Class;
         let Some((class_name, class_range)) = name_from_node(&node) else {
             continue;
         };
@@ -58,6 +70,7 @@ pub(in crate::db) fn check_class_semantics(
             &declared_methods,
             diagnostics,
         );
+        modifiers::check_abstract_method_bodies(&node, diagnostics);
 
         let extends_clause = node
             .children()
@@ -70,7 +83,8 @@ pub(in crate::db) fn check_class_semantics(
             class_range
         };
 
-        if extends_clause.is_some() && class_inheritance_cycle(symbols, class_id) {
+        // FUNCTION_BLOCK EXTENDS targets are checked by `check_extends_implements_semantics`.
+        if is_class && extends_clause.is_some() && class_inheritance_cycle(symbols, class_id) {
             diagnostics.error(
                 DiagnosticCode::CyclicDependency,
                 extends_range,
@@ -78,7 +92,7 @@ pub(in crate::db) fn check_class_semantics(
             );
         }
 
-        if let Some(base_id) = resolve_extends_symbol(symbols, class_id) {
+        if let Some(base_id) = resolve_extends_symbol(symbols, class_id).filter(|_| is_class) {
             if let Some(base_symbol) = symbols.get(base_id) {
                 if !matches!(base_symbol.kind, SymbolKind::Class) {
                     diagnostics.error(
@@ -140,7 +154,9 @@ pub(in crate::db) fn check_abstract_instantiations(
             let symbol_type = symbols.resolve_alias_type(symbol.type_id);
             let resolved_type = symbol_type;
 
-            let Some(Type::Class { name: class_name }) = symbols.type_by_id(resolved_type) else {
+            let Some(Type::Class { name: class_name } | Type::FunctionBlock { name: class_name }) =
+                symbols.type_by_id(resolved_type)
+            else {
                 continue;
             };
             let Some(class_id) = symbols.resolve_by_name(class_name.as_str()) else {
@@ -164,7 +180,8 @@ pub(in crate::db) fn check_abstract_instantiations(
                 DiagnosticCode::InvalidOperation,
                 range,
                 format!(
-                    "cannot instantiate ABSTRACT class '{}' here",
+                    "cannot instantiate ABSTRACT {} '{}' here",
+                    owner_kind_label(&class_symbol.kind),
                     class_symbol.name
                 ),
             );
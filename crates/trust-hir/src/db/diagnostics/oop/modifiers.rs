@@ -7,11 +7,12 @@ pub(super) fn check_class_modifiers(
     declared_methods: &[SymbolId],
     diagnostics: &mut DiagnosticBuilder,
 ) {
+    let label = super::owner_kind_label(&class_symbol.kind);
     if class_symbol.modifiers.is_final && class_symbol.modifiers.is_abstract {
         diagnostics.error(
             DiagnosticCode::InvalidOperation,
             class_range,
-            "class cannot be FINAL and ABSTRACT",
+            format!("{label} cannot be FINAL and ABSTRACT"),
         );
     }
 
@@ -26,7 +27,7 @@ pub(super) fn check_class_modifiers(
         diagnostics.error(
             DiagnosticCode::InvalidOperation,
             class_range,
-            "abstract class must declare at least one abstract method",
+            format!("abstract {label} must declare at least one abstract method"),
         );
     }
 
@@ -38,7 +39,7 @@ pub(super) fn check_class_modifiers(
             diagnostics.error(
                 DiagnosticCode::InvalidOperation,
                 method_sym.range,
-                "abstract method requires an ABSTRACT class",
+                format!("abstract method requires an ABSTRACT {label}"),
             );
         }
         if method_sym.modifiers.is_abstract && method_sym.modifiers.is_override {
@@ -57,3 +58,38 @@ pub(super) fn check_class_modifiers(
         }
     }
 }
+
+/// Reports ABSTRACT methods that declare statements.
+pub(super) fn check_abstract_method_bodies(
+    owner: &SyntaxNode,
+    diagnostics: &mut DiagnosticBuilder,
+) {
+    for method in owner
+        .children()
+        .filter(|child| child.kind() == SyntaxKind::Method)
+    {
+        let is_abstract = method
+            .children_with_tokens()
+            .any(|element| element.kind() == SyntaxKind::KwAbstract);
+        if !is_abstract {
+            continue;
+        }
+        let has_body = method
+            .children()
+            .filter(|child| child.kind() == SyntaxKind::StmtList)
+            .any(|stmt_list| stmt_list.children().next().is_some());
+        if !has_body {
+            continue;
+        }
+        let range = method
+            .children()
+            .find(|child| child.kind() == SyntaxKind::Name)
+            .map(|name| name.text_range())
+            .unwrap_or_else(|| method.text_range());
+        diagnostics.error(
+            DiagnosticCode::InvalidOperation,
+            range,
+            "ABSTRACT method cannot have a body",
+        );
+    }
+}
@@ -73,14 +73,26 @@ pub(super) fn check_method_overrides(
                 }
             }
         } else if base_method_id.is_some() {
-            diagnostics.error(
-                DiagnosticCode::InvalidOperation,
-                method_sym.range,
-                format!(
-                    "method '{}' overrides a base method and must use OVERRIDE",
-                    method_sym.name
-                ),
-            );
+            // Function blocks accepted implicit overrides before; keep them compiling.
+            if matches!(class_symbol.kind, SymbolKind::FunctionBlock) {
+                diagnostics.warning(
+                    DiagnosticCode::MissingOverride,
+                    method_sym.range,
+                    format!(
+                        "method '{}' overrides a base method and should use OVERRIDE",
+                        method_sym.name
+                    ),
+                );
+            } else {
+                diagnostics.error(
+                    DiagnosticCode::InvalidOperation,
+                    method_sym.range,
+                    format!(
+                        "method '{}' overrides a base method and must use OVERRIDE",
+                        method_sym.name
+                    ),
+                );
+            }
         }
     }
 
@@ -106,8 +118,10 @@ pub(super) fn check_method_overrides(
                     DiagnosticCode::InvalidOperation,
                     class_range,
                     format!(
-                        "class '{}' must implement abstract method '{}'",
-                        class_symbol.name, base_method.name
+                        "{} '{}' must implement abstract method '{}'",
+                        super::owner_kind_label(&class_symbol.kind),
+                        class_symbol.name,
+                        base_method.name
                     ),
                 );
             }
@@ -1,11 +1,17 @@
+use super::super::queries::name_from_node;
 use super::super::*;
-use super::context::{action_context, is_top_level_stmt_list, pou_context, property_type_for_node};
+use super::context::{
+    action_context, expression_context, find_symbol_by_name_range, is_top_level_stmt_list,
+    pou_context, property_type_for_node, PouContext,
+};
 
 pub(in crate::db) fn type_check_file(
     symbols: &mut SymbolTable,
     root: &SyntaxNode,
     diagnostics: &mut DiagnosticBuilder,
 ) {
+    check_declaration_initializers(symbols, root, diagnostics);
+
     // Find all POUs and type-check their bodies
     for node in root.descendants() {
         match node.kind() {
@@ -26,6 +32,41 @@ pub(in crate::db) fn type_check_file(
     }
 }
 
+/// Checks initializer lists and array initializers of variable declarations against
+/// the declared variable type.
+fn check_declaration_initializers(
+    symbols: &mut SymbolTable,
+    root: &SyntaxNode,
+    diagnostics: &mut DiagnosticBuilder,
+) {
+    for var_decl in root
+        .descendants()
+        .filter(|node| node.kind() == SyntaxKind::VarDecl)
+    {
+        let Some(initializer) = var_decl.children().find(|child| {
+            matches!(
+                child.kind(),
+                SyntaxKind::InitializerList | SyntaxKind::ArrayInitializer
+            )
+        }) else {
+            continue;
+        };
+        let type_id = var_decl
+            .children()
+            .find(|child| child.kind() == SyntaxKind::Name)
+            .and_then(|name| name_from_node(&name))
+            .and_then(|(name, range)| find_symbol_by_name_range(symbols, name.as_str(), range))
+            .and_then(|symbol_id| symbols.get(symbol_id))
+            .map(|symbol| symbol.type_id);
+        let Some(type_id) = type_id else {
+            continue;
+        };
+        let scope_id = expression_context(symbols, &var_decl).scope_id;
+        let mut checker = TypeChecker::new(symbols, diagnostics, scope_id);
+        checker.check_initializer(type_id, &initializer);
+    }
+}
+
 /// Type checks a single POU (Program, Function, FunctionBlock, or Method).
 pub(in crate::db) fn type_check_pou(
     symbols: &mut SymbolTable,
@@ -39,7 +80,10 @@ pub(in crate::db) fn type_check_pou(
             if let Some(symbol) = symbols.get(symbol_id) {
                 if let Some(parent_id) = symbol.parent {
                     if let Some(parent) = symbols.get(parent_id) {
-                        if matches!(&parent.kind, SymbolKind::Interface) {
+                        // Only interface methods with a default body have statements to check.
+                        if matches!(&parent.kind, SymbolKind::Interface)
+                            && !symbol.modifiers.has_default_body
+                        {
                             return;
                         }
                     }
@@ -50,7 +94,31 @@ pub(in crate::db) fn type_check_pou(
 
     // Create type checker
     let mut checker = TypeChecker::new(symbols, diagnostics, context.scope_id);
-    checker.set_return_type(context.return_type);
+    let instantiations = context
+        .symbol_id
+        .map(|symbol_id| checker.generic_instantiations(symbol_id))
+        .unwrap_or_default();
+    if instantiations.is_empty() {
+        check_pou_body(&mut checker, &context, node);
+        return;
+    }
+
+    // Generic POUs are checked once per instantiation of their ANY_* classes.
+    let mut runs = Vec::with_capacity(instantiations.len());
+    for bindings in instantiations {
+        let mut run_diagnostics = DiagnosticBuilder::new();
+        let mut checker = TypeChecker::new(symbols, &mut run_diagnostics, context.scope_id);
+        let label = checker.describe_generic_bindings(&bindings);
+        checker.set_generic_bindings(bindings);
+        check_pou_body(&mut checker, &context, node);
+        runs.push((label, run_diagnostics.finish()));
+    }
+    merge_instantiation_diagnostics(runs, diagnostics);
+}
+
+fn check_pou_body(checker: &mut TypeChecker<'_>, context: &PouContext, node: &SyntaxNode) {
+    let return_type = context.return_type.map(|ty| checker.instantiate(ty));
+    checker.set_return_type(return_type);
     checker.set_receiver_types(context.this_type, context.super_type);
     checker.set_current_pou(context.symbol_id);
 
@@ -68,6 +136,50 @@ pub(in crate::db) fn type_check_pou(
     checker.finish_return_checks(node);
 }
 
+/// Reports diagnostics shared by every instantiation once, and errors raised only by
+/// some instantiations tagged with the bindings they fail for.
+fn merge_instantiation_diagnostics(
+    runs: Vec<(String, Vec<Diagnostic>)>,
+    diagnostics: &mut DiagnosticBuilder,
+) {
+    let total = runs.len();
+    let mut order = Vec::new();
+    let mut merged: FxHashMap<(DiagnosticCode, TextRange), (Diagnostic, Vec<String>)> =
+        FxHashMap::default();
+    for (label, run) in runs {
+        let mut seen = FxHashSet::default();
+        for diagnostic in run {
+            let key = (diagnostic.code, diagnostic.range);
+            if !seen.insert(key) {
+                continue;
+            }
+            match merged.get_mut(&key) {
+                Some((_, labels)) => labels.push(label.clone()),
+                None => {
+                    order.push(key);
+                    merged.insert(key, (diagnostic, vec![label.clone()]));
+                }
+            }
+        }
+    }
+
+    for key in order {
+        let Some((mut diagnostic, labels)) = merged.remove(&key) else {
+            continue;
+        };
+        if labels.len() == total {
+            diagnostics.add(diagnostic);
+        } else if diagnostic.is_error() {
+            diagnostic.message = format!(
+                "{} (not supported for {})",
+                diagnostic.message,
+                labels.join("; ")
+            );
+            diagnostics.add(diagnostic);
+        }
+    }
+}
+
 pub(in crate::db) fn type_check_property(
     symbols: &mut SymbolTable,
     node: &SyntaxNode,
@@ -0,0 +1,127 @@
+use super::super::*;
+
+/// Nesting limit when sizing variant types, guarding against cyclic definitions.
+const MAX_LAYOUT_DEPTH: usize = 32;
+
+/// Overlay size of a variant type.
+enum VariantLayout {
+    Bytes(u64),
+    /// Strings, date/time values, references and instances are not overlaid.
+    Unsupported,
+    /// The type is not resolved yet; other passes report it.
+    Unknown,
+}
+
+/// Warns about UNION variants that do not share the union's full byte range:
+/// variants without a fixed byte layout, and variants smaller than the largest one.
+pub(in crate::db) fn check_union_layouts(
+    symbols: &SymbolTable,
+    diagnostics: &mut DiagnosticBuilder,
+) {
+    for symbol in symbols.iter() {
+        if symbol.origin.is_some()
+            || symbol.range.is_empty()
+            || !matches!(symbol.kind, SymbolKind::Type)
+        {
+            continue;
+        }
+        let Some(Type::Union { variants, .. }) = symbols.type_by_id(symbol.type_id) else {
+            continue;
+        };
+        let layouts: Vec<_> = variants
+            .iter()
+            .map(|variant| (variant, variant_layout(symbols, variant.type_id, 0)))
+            .collect();
+        if layouts
+            .iter()
+            .any(|(_, layout)| matches!(layout, VariantLayout::Unknown))
+        {
+            continue;
+        }
+        let union_size = layouts
+            .iter()
+            .filter_map(|(_, layout)| match layout {
+                VariantLayout::Bytes(size) => Some(*size),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+
+        for (variant, layout) in &layouts {
+            match layout {
+                VariantLayout::Unsupported => diagnostics.warning(
+                    DiagnosticCode::UnionLayoutMismatch,
+                    symbol.range,
+                    format!(
+                        "union variant '{}' of '{}' has no fixed byte layout and does not overlay the other variants",
+                        variant.name, symbol.name
+                    ),
+                ),
+                VariantLayout::Bytes(size) if *size < union_size => diagnostics.warning(
+                    DiagnosticCode::UnionLayoutMismatch,
+                    symbol.range,
+                    format!(
+                        "union variant '{}' of '{}' is {size} bytes but the union is {union_size} bytes; it overlays only the first {size} bytes",
+                        variant.name, symbol.name
+                    ),
+                ),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn variant_layout(symbols: &SymbolTable, type_id: TypeId, depth: usize) -> VariantLayout {
+    if depth > MAX_LAYOUT_DEPTH {
+        return VariantLayout::Unknown;
+    }
+    let Some(ty) = symbols.type_by_id(type_id) else {
+        return VariantLayout::Unknown;
+    };
+    match ty {
+        Type::Unknown => VariantLayout::Unknown,
+        Type::Alias { target, .. } => variant_layout(symbols, *target, depth + 1),
+        Type::Enum { base, .. } => variant_layout(symbols, *base, depth + 1),
+        Type::Bool | Type::Char => VariantLayout::Bytes(1),
+        Type::Array {
+            element,
+            dimensions,
+        } => {
+            let count = dimensions.iter().try_fold(1u64, |total, (lower, upper)| {
+                let len = u64::try_from(upper.checked_sub(*lower)?.checked_add(1)?).ok()?;
+                total.checked_mul(len)
+            });
+            match (variant_layout(symbols, *element, depth + 1), count) {
+                (VariantLayout::Bytes(size), Some(count)) => size
+                    .checked_mul(count)
+                    .map_or(VariantLayout::Unsupported, VariantLayout::Bytes),
+                (VariantLayout::Bytes(_), None) => VariantLayout::Unsupported,
+                (layout, _) => layout,
+            }
+        }
+        Type::Struct { fields, .. } => {
+            let mut total = 0u64;
+            for field in fields {
+                match variant_layout(symbols, field.type_id, depth + 1) {
+                    VariantLayout::Bytes(size) => total = total.saturating_add(size),
+                    layout => return layout,
+                }
+            }
+            VariantLayout::Bytes(total)
+        }
+        Type::Union { variants, .. } => {
+            let mut largest = 0u64;
+            for variant in variants {
+                match variant_layout(symbols, variant.type_id, depth + 1) {
+                    VariantLayout::Bytes(size) => largest = largest.max(size),
+                    layout => return layout,
+                }
+            }
+            VariantLayout::Bytes(largest)
+        }
+        Type::Time | Type::LTime | Type::LDate => VariantLayout::Unsupported,
+        _ => ty.bit_size().map_or(VariantLayout::Unsupported, |bits| {
+            VariantLayout::Bytes(u64::from(bits.div_ceil(8)))
+        }),
+    }
+}
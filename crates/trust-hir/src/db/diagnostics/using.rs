@@ -6,6 +6,8 @@ pub(in crate::db) fn check_using_directives(
     diagnostics: &mut DiagnosticBuilder,
 ) {
     for scope in symbols.scopes() {
+        // Imported name -> (symbol, importing path) for the directives seen so far.
+        let mut imported: FxHashMap<SmolStr, (SymbolId, SmolStr)> = FxHashMap::default();
         for using in &scope.using_directives {
             let Some(symbol_id) = symbols.resolve_qualified(&using.path) else {
                 diagnostics.error(
@@ -30,7 +32,99 @@ pub(in crate::db) fn check_using_directives(
                         qualified_name_string(&using.path)
                     ),
                 );
+                continue;
+            }
+
+            let path = qualified_name_string(&using.path);
+            for name in namespace_member_names(symbols, symbol_id) {
+                let mut parts = using.path.clone();
+                parts.push(name.clone());
+                let Some(member_id) = symbols.resolve_qualified(&parts) else {
+                    continue;
+                };
+                if let Some(local_id) = scope.lookup_local(name.as_str()) {
+                    if local_id != member_id {
+                        diagnostics.warning(
+                            DiagnosticCode::ShadowedUsingImport,
+                            using.range,
+                            format!(
+                                "'{}' imported by USING '{}' is shadowed by a local declaration",
+                                name, path
+                            ),
+                        );
+                    }
+                    continue;
+                }
+                let key = SmolStr::new(name.to_ascii_uppercase());
+                match imported.get(&key) {
+                    Some((existing_id, existing_path)) if *existing_id != member_id => {
+                        diagnostics.warning(
+                            DiagnosticCode::AmbiguousUsingImport,
+                            using.range,
+                            format!(
+                                "'{}' is imported by both USING '{}' and USING '{}'; qualify the name",
+                                name, existing_path, path
+                            ),
+                        );
+                    }
+                    Some(_) => {}
+                    None => {
+                        imported.insert(key, (member_id, path.clone()));
+                    }
+                }
             }
         }
     }
 }
+
+/// Names a USING directive for `namespace` brings into scope, including re-exports.
+fn namespace_member_names(symbols: &SymbolTable, namespace: SymbolId) -> Vec<SmolStr> {
+    let mut names = Vec::new();
+    let mut seen = FxHashSet::default();
+    let mut visited = FxHashSet::default();
+    collect_member_names(
+        symbols,
+        namespace,
+        true,
+        &mut visited,
+        &mut seen,
+        &mut names,
+    );
+    names
+}
+
+fn collect_member_names(
+    symbols: &SymbolTable,
+    namespace: SymbolId,
+    direct: bool,
+    visited: &mut FxHashSet<SymbolId>,
+    seen: &mut FxHashSet<SmolStr>,
+    names: &mut Vec<SmolStr>,
+) {
+    if !visited.insert(namespace) {
+        return;
+    }
+    let mut members: Vec<&Symbol> = symbols
+        .iter()
+        .filter(|sym| sym.parent == Some(namespace))
+        .filter(|sym| !matches!(sym.kind, SymbolKind::Namespace))
+        .filter(|sym| direct || !matches!(sym.visibility, Visibility::Internal))
+        .collect();
+    members.sort_by_key(|sym| sym.id.0);
+    for sym in members {
+        if seen.insert(SmolStr::new(sym.name.to_ascii_uppercase())) {
+            names.push(sym.name.clone());
+        }
+    }
+    for path in symbols.namespace_reexports(namespace) {
+        let Some(target) = symbols.resolve_qualified(path) else {
+            continue;
+        };
+        if symbols
+            .get(target)
+            .is_some_and(|sym| matches!(sym.kind, SymbolKind::Namespace))
+        {
+            collect_member_names(symbols, target, false, visited, seen, names);
+        }
+    }
+}
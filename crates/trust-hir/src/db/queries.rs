@@ -120,6 +120,7 @@ pub(super) struct MethodSignature {
     pub(super) parameters: Vec<ParamSignature>,
     pub(super) visibility: Visibility,
     pub(super) range: TextRange,
+    pub(super) has_default_body: bool,
 }
 
 #[derive(Clone)]
@@ -79,6 +79,21 @@ impl SymbolCollector {
     }
 
     fn collect_using_directive(&mut self, node: &SyntaxNode) {
+        let reexport = node
+            .children_with_tokens()
+            .any(|element| element.kind() == SyntaxKind::KwPublic);
+        let namespace = self
+            .table
+            .get_scope(self.table.current_scope())
+            .filter(|scope| scope.kind == ScopeKind::Namespace)
+            .and_then(|scope| scope.owner);
+        if reexport && namespace.is_none() {
+            self.diagnostics.error(
+                DiagnosticCode::InvalidOperation,
+                node.text_range(),
+                "USING PUBLIC is only allowed inside a NAMESPACE",
+            );
+        }
         for child in node.children() {
             if !matches!(child.kind(), SyntaxKind::QualifiedName | SyntaxKind::Name) {
                 continue;
@@ -87,9 +102,13 @@ impl SymbolCollector {
                 continue;
             };
             let path: Vec<SmolStr> = parts.into_iter().map(|(name, _)| name).collect();
-            if !path.is_empty() {
-                self.table.add_using_directive(path, range);
+            if path.is_empty() {
+                continue;
+            }
+            if let Some(namespace) = namespace.filter(|_| reexport) {
+                self.table.add_namespace_reexport(namespace, path.clone());
             }
+            self.table.add_using_directive(path, range);
         }
     }
 
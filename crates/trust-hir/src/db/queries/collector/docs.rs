@@ -0,0 +1,92 @@
+use super::*;
+use crate::doc_comments::{leading_comment_lines, parse_doc_comment, trailing_comment_lines};
+
+impl SymbolCollector {
+    /// Attaches documentation comments to POU, member, type, and variable symbols.
+    pub(super) fn attach_doc_comments(&mut self, root: &SyntaxNode) {
+        let by_range: FxHashMap<TextRange, SymbolId> = self
+            .table
+            .iter()
+            .filter(|symbol| !symbol.range.is_empty())
+            .map(|symbol| (symbol.range, symbol.id))
+            .collect();
+        if by_range.is_empty() {
+            return;
+        }
+
+        for node in root.descendants() {
+            match node.kind() {
+                SyntaxKind::Program
+                | SyntaxKind::Function
+                | SyntaxKind::FunctionBlock
+                | SyntaxKind::Class
+                | SyntaxKind::Interface
+                | SyntaxKind::Method
+                | SyntaxKind::Property => {
+                    let Some((_, range)) = name_from_node(&node) else {
+                        continue;
+                    };
+                    self.attach_doc(&by_range, range, leading_comment_lines(&node));
+                }
+                SyntaxKind::TypeDecl => {
+                    let names: Vec<SyntaxNode> = node
+                        .children()
+                        .filter(|child| child.kind() == SyntaxKind::Name)
+                        .collect();
+                    for (index, name) in names.iter().enumerate() {
+                        let Some(ident) = first_ident_token(name) else {
+                            continue;
+                        };
+                        let mut lines = leading_comment_lines(name);
+                        // The comment above `TYPE` documents the first type.
+                        if lines.is_empty() && index == 0 {
+                            lines = leading_comment_lines(&node);
+                        }
+                        self.attach_doc(&by_range, ident.text_range(), lines);
+                    }
+                }
+                SyntaxKind::VarDecl => {
+                    let mut lines = leading_comment_lines(&node);
+                    if lines.is_empty() {
+                        lines = trailing_comment_lines(&node);
+                    }
+                    if lines.is_empty() {
+                        continue;
+                    }
+                    for name in node
+                        .children()
+                        .filter(|child| child.kind() == SyntaxKind::Name)
+                    {
+                        let Some(ident) = first_ident_token(&name) else {
+                            continue;
+                        };
+                        self.attach_doc(&by_range, ident.text_range(), lines.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn attach_doc(
+        &mut self,
+        by_range: &FxHashMap<TextRange, SymbolId>,
+        range: TextRange,
+        lines: Vec<String>,
+    ) {
+        if lines.is_empty() {
+            return;
+        }
+        let Some(symbol_id) = by_range.get(&range) else {
+            return;
+        };
+        // Tag problems are reported by `trust-runtime docs`, not as diagnostics.
+        let (doc, _) = parse_doc_comment(&lines, "");
+        if doc.is_empty() {
+            return;
+        }
+        if let Some(symbol) = self.table.get_mut(*symbol_id) {
+            symbol.doc = Some(SmolStr::new(doc.to_markdown()));
+        }
+    }
+}
@@ -4,6 +4,7 @@ use super::*;
 mod collect;
 mod const_eval;
 mod const_utils;
+mod docs;
 mod precollect;
 mod types;
 mod validation;
@@ -36,6 +37,7 @@ impl SymbolCollector {
     pub(super) fn collect(mut self, root: &SyntaxNode) -> (SymbolTable, Vec<Diagnostic>) {
         self.phase_precollect(root);
         self.phase_collect_symbols(root);
+        self.attach_doc_comments(root);
         self.phase_access_and_config(root);
         self.phase_resolve_types();
         self.phase_global_links(root);
@@ -50,6 +52,7 @@ impl SymbolCollector {
     ) -> (SymbolTable, Vec<Diagnostic>, Vec<PendingType>) {
         self.phase_precollect(root);
         self.phase_collect_symbols(root);
+        self.attach_doc_comments(root);
         self.phase_access_and_config(root);
         self.phase_var_validation(root);
         self.phase_constants();
@@ -133,6 +133,13 @@ pub(in crate::db) fn modifiers_from_node(node: &SyntaxNode) -> SymbolModifiers {
             _ => {}
         }
     }
+    modifiers.has_default_body = node.kind() == SyntaxKind::Method
+        && node
+            .parent()
+            .is_some_and(|parent| parent.kind() == SyntaxKind::Interface)
+        && node
+            .children()
+            .any(|child| child.kind() == SyntaxKind::StmtList);
     modifiers
 }
 
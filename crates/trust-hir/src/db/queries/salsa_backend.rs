@@ -1,11 +1,13 @@
 use super::collector::SymbolCollector;
 use super::diagnostics::{
     add_unused_symbol_warnings, check_abstract_instantiations, check_class_semantics,
-    check_configuration_semantics, check_cyclomatic_complexity, check_extends_implements_semantics,
+    check_configuration_semantics, check_cyclomatic_complexity,
+    check_duplicate_address_assignments, check_extends_implements_semantics,
     check_global_external_links_with_project, check_interface_conformance, check_nondeterminism,
-    check_property_accessors, check_shared_global_task_hazards, check_unreachable_statements,
-    check_using_directives, collect_used_symbols, expression_by_id, expression_context,
-    resolve_declared_var_types_with_project, resolve_pending_types_with_table, type_check_file,
+    check_property_accessors, check_shared_global_task_hazards, check_union_layouts,
+    check_unreachable_statements, check_using_directives, collect_used_symbols, expression_by_id,
+    expression_context, resolve_declared_var_types_with_project, resolve_pending_types_with_table,
+    type_check_file,
 };
 use super::symbol_import::SymbolImporter;
 use super::*;
@@ -329,9 +331,17 @@ pub(super) fn analyze_query(
     check_unreachable_statements(&root, &mut builder);
     check_cyclomatic_complexity(&root, &mut builder);
     check_nondeterminism(&symbols, &mut builder);
+    check_union_layouts(&symbols, &mut builder);
+    let mut project_roots = None;
     if has_global_variables(&symbols) {
-        let project_roots = project_roots_from_inputs(db, &project_source_inputs);
-        check_shared_global_task_hazards(&symbols, &project_roots, file_id, &mut builder);
+        let project_roots = project_roots
+            .get_or_insert_with(|| project_roots_from_inputs(db, &project_source_inputs));
+        check_shared_global_task_hazards(&symbols, project_roots, file_id, &mut builder);
+    }
+    if has_direct_addresses(&root) {
+        let project_roots = project_roots
+            .get_or_insert_with(|| project_roots_from_inputs(db, &project_source_inputs));
+        check_duplicate_address_assignments(&root, project_roots, file_id, &mut builder);
     }
     add_unused_symbol_warnings(&symbols, file_id, project_used.as_ref(), &mut builder);
     diagnostics.extend(builder.finish());
@@ -435,6 +445,11 @@ fn has_global_variables(symbols: &SymbolTable) -> bool {
     })
 }
 
+fn has_direct_addresses(root: &SyntaxNode) -> bool {
+    root.descendants_with_tokens()
+        .any(|element| element.kind() == SyntaxKind::DirectAddress)
+}
+
 fn project_roots_from_inputs(
     db: &dyn salsa::Database,
     source_inputs: &FxHashMap<FileId, SourceInput>,
@@ -168,6 +168,9 @@ impl<'a> SymbolImporter<'a> {
             if let Some(base) = source.extends_name(*old_id) {
                 self.target.set_extends(*new_id, base.clone());
             }
+            for path in source.namespace_reexports(*old_id) {
+                self.target.add_namespace_reexport(*new_id, path.clone());
+            }
         }
 
         for new_id in id_map.values() {
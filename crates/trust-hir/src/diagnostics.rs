@@ -28,6 +28,8 @@ pub enum DiagnosticCode {
     MissingToken,
     /// Unclosed block.
     UnclosedBlock,
+    /// Vendor syntax extension not enabled by the workspace dialect.
+    UnsupportedExtension,
 
     // Name resolution errors (E100-E199)
     /// Undefined variable.
@@ -74,6 +76,10 @@ pub enum DiagnosticCode {
     InvalidTaskConfig,
     /// Unknown task reference in program configuration.
     UnknownTask,
+    /// Recursive call chain (unbounded call depth).
+    RecursiveCall,
+    /// Constant array index outside the declared bounds.
+    ArrayIndexOutOfBounds,
 
     // Warnings (W001-W099)
     /// Unused variable.
@@ -100,6 +106,34 @@ pub enum DiagnosticCode {
     NondeterministicIo,
     /// Shared global accessed by multiple tasks with writes.
     SharedGlobalTaskHazard,
+    /// Symbol not reachable from any CONFIGURATION/TASK entry point.
+    DeadCode,
+    /// Implicit operand conversion that may lose precision.
+    LossyConversion,
+    /// Arithmetic or comparison mixing signed and unsigned integers.
+    SignedUnsignedMix,
+    /// String assignment whose source may exceed the target's declared length.
+    StringTruncation,
+    /// Two USING directives in one scope import different symbols with the same name.
+    AmbiguousUsingImport,
+    /// A USING import is hidden by a declaration of the same name in its scope.
+    ShadowedUsingImport,
+    /// Two workspace roots export a public symbol with the same name.
+    WorkspaceSymbolConflict,
+    /// An output or memory address is bound or written in more than one place.
+    DuplicateAddressAssignment,
+    /// A FOR loop variable indexes an array beyond its bounds.
+    LoopIndexOutOfBounds,
+    /// CASE over an enumeration misses values and has no ELSE branch.
+    NonExhaustiveCase,
+    /// Integer arithmetic on constants overflows its result type.
+    ConstantOverflow,
+    /// A UNION variant does not overlay the union's full byte range.
+    UnionLayoutMismatch,
+    /// A REF_TO or POINTER variable is dereferenced without a NULL check.
+    PossibleNullDereference,
+    /// A FUNCTION_BLOCK method replaces a base method without OVERRIDE.
+    MissingOverride,
 
     // Info/Hints (I001-I099)
     /// Suggested simplification.
@@ -117,6 +151,7 @@ impl DiagnosticCode {
             Self::UnexpectedToken => "E001",
             Self::MissingToken => "E002",
             Self::UnclosedBlock => "E003",
+            Self::UnsupportedExtension => "E004",
             // Name resolution
             Self::UndefinedVariable => "E101",
             Self::UndefinedType => "E102",
@@ -140,6 +175,8 @@ impl DiagnosticCode {
             Self::CyclicDependency => "E305",
             Self::InvalidTaskConfig => "E306",
             Self::UnknownTask => "E307",
+            Self::RecursiveCall => "E308",
+            Self::ArrayIndexOutOfBounds => "E309",
             // Warnings
             Self::UnusedVariable => "W001",
             Self::UnusedParameter => "W002",
@@ -153,6 +190,20 @@ impl DiagnosticCode {
             Self::NondeterministicTimeDate => "W010",
             Self::NondeterministicIo => "W011",
             Self::SharedGlobalTaskHazard => "W012",
+            Self::DeadCode => "W013",
+            Self::LossyConversion => "W014",
+            Self::SignedUnsignedMix => "W015",
+            Self::StringTruncation => "W016",
+            Self::AmbiguousUsingImport => "W017",
+            Self::ShadowedUsingImport => "W018",
+            Self::WorkspaceSymbolConflict => "W019",
+            Self::DuplicateAddressAssignment => "W020",
+            Self::LoopIndexOutOfBounds => "W021",
+            Self::NonExhaustiveCase => "W022",
+            Self::ConstantOverflow => "W023",
+            Self::UnionLayoutMismatch => "W024",
+            Self::PossibleNullDereference => "W025",
+            Self::MissingOverride => "W026",
             // Info
             Self::Simplification => "I001",
             Self::StyleSuggestion => "I002",
@@ -167,6 +218,7 @@ impl DiagnosticCode {
             Self::UnexpectedToken
             | Self::MissingToken
             | Self::UnclosedBlock
+            | Self::UnsupportedExtension
             | Self::UndefinedVariable
             | Self::UndefinedType
             | Self::UndefinedFunction
@@ -186,7 +238,9 @@ impl DiagnosticCode {
             | Self::OutOfRange
             | Self::CyclicDependency
             | Self::InvalidTaskConfig
-            | Self::UnknownTask => DiagnosticSeverity::Error,
+            | Self::UnknownTask
+            | Self::RecursiveCall
+            | Self::ArrayIndexOutOfBounds => DiagnosticSeverity::Error,
 
             // Warnings
             Self::UnusedVariable
@@ -200,7 +254,21 @@ impl DiagnosticCode {
             | Self::UnusedPou
             | Self::NondeterministicTimeDate
             | Self::NondeterministicIo
-            | Self::SharedGlobalTaskHazard => DiagnosticSeverity::Warning,
+            | Self::SharedGlobalTaskHazard
+            | Self::DeadCode
+            | Self::LossyConversion
+            | Self::SignedUnsignedMix
+            | Self::StringTruncation
+            | Self::AmbiguousUsingImport
+            | Self::ShadowedUsingImport
+            | Self::WorkspaceSymbolConflict
+            | Self::DuplicateAddressAssignment
+            | Self::LoopIndexOutOfBounds
+            | Self::NonExhaustiveCase
+            | Self::ConstantOverflow
+            | Self::UnionLayoutMismatch
+            | Self::PossibleNullDereference
+            | Self::MissingOverride => DiagnosticSeverity::Warning,
 
             // Info/Hints
             Self::Simplification | Self::StyleSuggestion => DiagnosticSeverity::Hint,
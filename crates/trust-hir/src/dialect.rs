@@ -0,0 +1,207 @@
+//! Dialect-gated validation of vendor syntax extensions.
+//!
+//! The lexer accepts vendor extensions regardless of the workspace dialect;
+//! this pass reports extensions the selected [`Dialect`] does not allow and
+//! checks the structure of the ones it does. Extensions that need type
+//! information are reported by the type checker instead and dropped again by
+//! [`retain_dialect_diagnostics`] for dialects that accept them.
+
+use text_size::TextRange;
+use trust_syntax::{lex, Dialect, DialectExtension, TokenKind};
+
+use crate::diagnostics::{Diagnostic, DiagnosticCode};
+
+/// Validates vendor extension usage in `source` against `dialect`.
+#[must_use]
+pub fn check_dialect_extensions(source: &str, dialect: &Dialect) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let regions_allowed = dialect.supports(DialectExtension::Regions);
+    let pointers_allowed = dialect.supports(DialectExtension::Pointers);
+    let mut open_regions: Vec<(TextRange, String)> = Vec::new();
+
+    for token in lex(source) {
+        if matches!(token.kind, TokenKind::KwPointer | TokenKind::KwAdr) {
+            if !pointers_allowed {
+                diagnostics.push(deprecated_pointer(token.range, dialect));
+            }
+            continue;
+        }
+        if token.kind != TokenKind::RegionMarker {
+            continue;
+        }
+        let text = &source[usize::from(token.range.start())..usize::from(token.range.end())];
+        if !regions_allowed {
+            diagnostics.push(unsupported_extension(
+                DialectExtension::Regions,
+                token.range,
+                dialect,
+            ));
+            continue;
+        }
+        if is_region_end(text) {
+            if open_regions.pop().is_none() {
+                diagnostics.push(Diagnostic::error(
+                    DiagnosticCode::UnexpectedToken,
+                    token.range,
+                    "END_REGION without matching REGION",
+                ));
+            }
+        } else {
+            let title = text["REGION".len()..].trim().to_string();
+            open_regions.push((token.range, title));
+        }
+    }
+
+    for (range, title) in open_regions {
+        let message = if title.is_empty() {
+            "REGION is missing END_REGION".to_string()
+        } else {
+            format!("REGION '{title}' is missing END_REGION")
+        };
+        diagnostics.push(Diagnostic::error(
+            DiagnosticCode::UnclosedBlock,
+            range,
+            message,
+        ));
+    }
+    diagnostics
+}
+
+fn is_region_end(text: &str) -> bool {
+    text.get(..10)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("END_REGION"))
+}
+
+fn unsupported_extension(
+    extension: DialectExtension,
+    range: TextRange,
+    dialect: &Dialect,
+) -> Diagnostic {
+    let hint = Dialect::provider_of(extension)
+        .map(|name| format!("; set vendor_profile = \"{name}\" to enable them"))
+        .unwrap_or_default();
+    Diagnostic::new(
+        DiagnosticCode::UnsupportedExtension,
+        range,
+        format!(
+            "{} are not supported by the '{}' dialect{hint}",
+            extension.label(),
+            dialect.name
+        ),
+    )
+}
+
+/// Pointers were accepted in every profile before dialect gating, so they
+/// stay a deprecation warning until the next breaking release.
+fn deprecated_pointer(range: TextRange, dialect: &Dialect) -> Diagnostic {
+    let hint = Dialect::provider_of(DialectExtension::Pointers)
+        .map(|name| format!("; set vendor_profile = \"{name}\" to keep using them"))
+        .unwrap_or_default();
+    Diagnostic::warning(
+        DiagnosticCode::Deprecated,
+        range,
+        format!(
+            "{} are deprecated in the '{}' dialect and will be rejected in a future release{hint}",
+            DialectExtension::Pointers.label(),
+            dialect.name
+        ),
+    )
+}
+
+/// Drops semantic diagnostics about extensions `dialect` accepts.
+pub fn retain_dialect_diagnostics(diagnostics: &mut Vec<Diagnostic>, dialect: &Dialect) {
+    if dialect.supports(DialectExtension::IntegerBitAccess) {
+        let message = integer_bit_access_message();
+        diagnostics
+            .retain(|diag| !(diag.code == DiagnosticCode::Deprecated && diag.message == message));
+    }
+}
+
+/// Bit access on integers was accepted in every profile before dialect gating.
+/// The type checker does not know the dialect, so it always reports the
+/// warning; see [`retain_dialect_diagnostics`].
+pub(crate) fn deprecated_integer_bit_access(range: TextRange) -> Diagnostic {
+    Diagnostic::warning(
+        DiagnosticCode::Deprecated,
+        range,
+        integer_bit_access_message(),
+    )
+}
+
+fn integer_bit_access_message() -> String {
+    let provider = Dialect::provider_of(DialectExtension::IntegerBitAccess).unwrap_or("codesys");
+    format!(
+        "{} is a '{provider}' extension and will be rejected by other dialects in a future release; set vendor_profile = \"{provider}\" to keep using it",
+        DialectExtension::IntegerBitAccess.label()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::DiagnosticSeverity;
+    use trust_syntax::dialect::{CODESYS, IEC, SIEMENS};
+
+    const SOURCE: &str = "PROGRAM Main\nVAR\n    x : INT;\nEND_VAR\nREGION Init\n    x := 1;\nEND_REGION\nEND_PROGRAM\n";
+
+    #[test]
+    fn iec_dialect_rejects_regions() {
+        let diagnostics = check_dialect_extensions(SOURCE, &IEC);
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics
+            .iter()
+            .all(|diag| diag.code == DiagnosticCode::UnsupportedExtension));
+        assert!(diagnostics[0]
+            .message
+            .contains("vendor_profile = \"siemens\""));
+    }
+
+    #[test]
+    fn siemens_dialect_accepts_balanced_regions() {
+        assert!(check_dialect_extensions(SOURCE, &SIEMENS).is_empty());
+
+        let unbalanced = "REGION Setup\n    x := 1;\n";
+        let diagnostics = check_dialect_extensions(unbalanced, &SIEMENS);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, DiagnosticCode::UnclosedBlock);
+        assert!(diagnostics[0].message.contains("'Setup'"));
+
+        let stray = "x := 1;\nEND_REGION\n";
+        let diagnostics = check_dialect_extensions(stray, &SIEMENS);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, DiagnosticCode::UnexpectedToken);
+    }
+
+    #[test]
+    fn pointers_outside_codesys_dialect_are_deprecated() {
+        let source = "PROGRAM Main\nVAR\n    x : INT;\n    p : POINTER TO INT;\nEND_VAR\np := ADR(x);\nEND_PROGRAM\n";
+        let diagnostics = check_dialect_extensions(source, &IEC);
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(|diag| {
+            diag.code == DiagnosticCode::Deprecated && diag.severity == DiagnosticSeverity::Warning
+        }));
+        assert!(diagnostics[0]
+            .message
+            .contains("vendor_profile = \"codesys\""));
+        assert!(check_dialect_extensions(source, &CODESYS).is_empty());
+    }
+
+    #[test]
+    fn integer_bit_access_warning_is_dropped_for_codesys() {
+        let range = TextRange::new(0.into(), 4.into());
+        let diagnostics = vec![
+            deprecated_integer_bit_access(range),
+            Diagnostic::warning(DiagnosticCode::Deprecated, range, "other"),
+        ];
+
+        let mut iec = diagnostics.clone();
+        retain_dialect_diagnostics(&mut iec, &IEC);
+        assert_eq!(iec.len(), 2);
+        assert!(iec[0].message.contains("vendor_profile = \"codesys\""));
+
+        let mut codesys = diagnostics;
+        retain_dialect_diagnostics(&mut codesys, &CODESYS);
+        assert_eq!(codesys.len(), 1);
+        assert_eq!(codesys[0].message, "other");
+    }
+}
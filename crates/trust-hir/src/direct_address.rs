@@ -0,0 +1,149 @@
+//! Direct address (`%IX0.1`, `%QW4`, `%MD10`) uses in Structured Text.
+//!
+//! Addresses are compared in normalized form: upper case, with the implicit bit
+//! size made explicit (`%Q0.3` is `%QX0.3`, IEC 61131-3 Ed.3, 6.5.5.2).
+
+use smol_str::SmolStr;
+use text_size::TextRange;
+use trust_syntax::syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+/// How a direct address is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectAddressUseKind {
+    /// Located variable declaration (`x AT %QX0.3 : BOOL;`).
+    Declaration,
+    /// `VAR_CONFIG` mapping (`P1.x AT %QX0.3 : BOOL;`).
+    Configuration,
+    /// Read in an expression.
+    Read,
+    /// Written by an assignment or an output parameter (`=>`).
+    Write,
+}
+
+impl DirectAddressUseKind {
+    /// Whether this use assigns a value to the address (binds it or writes it).
+    #[must_use]
+    pub fn is_assignment(self) -> bool {
+        !matches!(self, Self::Read)
+    }
+}
+
+/// A use of a fully specified direct address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectAddressUse {
+    /// Normalized address, e.g. `%QX0.3`.
+    pub address: SmolStr,
+    /// Range of the address token.
+    pub range: TextRange,
+    /// How the address is used.
+    pub kind: DirectAddressUseKind,
+}
+
+/// Normalizes a direct address for comparison.
+///
+/// Returns `None` for partly specified addresses (`%Q*`, `%I*`), which are completed
+/// by a `VAR_CONFIG` mapping, and for text that is not a direct address.
+#[must_use]
+pub fn normalize_direct_address(text: &str) -> Option<SmolStr> {
+    let upper = text.trim().to_ascii_uppercase();
+    let rest = upper.strip_prefix('%')?;
+    let mut chars = rest.chars();
+    let location = chars.next().filter(|ch| matches!(ch, 'I' | 'Q' | 'M'))?;
+    let tail = chars.as_str();
+    if tail.contains('*') || tail.is_empty() {
+        return None;
+    }
+    if tail.starts_with(|ch: char| ch.is_ascii_digit()) {
+        return Some(SmolStr::new(format!("%{location}X{tail}")));
+    }
+    Some(SmolStr::new(upper))
+}
+
+/// Collects the uses of fully specified direct addresses in a syntax tree.
+#[must_use]
+pub fn direct_address_uses(root: &SyntaxNode) -> Vec<DirectAddressUse> {
+    root.descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == SyntaxKind::DirectAddress)
+        .filter_map(|token| {
+            Some(DirectAddressUse {
+                address: normalize_direct_address(token.text())?,
+                range: token.text_range(),
+                kind: use_kind(&token),
+            })
+        })
+        .collect()
+}
+
+fn use_kind(token: &SyntaxToken) -> DirectAddressUseKind {
+    let Some(parent) = token.parent() else {
+        return DirectAddressUseKind::Read;
+    };
+    match parent.kind() {
+        SyntaxKind::VarDecl => DirectAddressUseKind::Declaration,
+        SyntaxKind::ConfigInit => DirectAddressUseKind::Configuration,
+        SyntaxKind::NameRef if is_write_target(&parent) => DirectAddressUseKind::Write,
+        _ => DirectAddressUseKind::Read,
+    }
+}
+
+fn is_write_target(name_ref: &SyntaxNode) -> bool {
+    let Some(parent) = name_ref.parent() else {
+        return false;
+    };
+    match parent.kind() {
+        SyntaxKind::AssignStmt => parent.first_child().as_ref() == Some(name_ref),
+        SyntaxKind::Arg => parent
+            .children_with_tokens()
+            .any(|element| element.kind() == SyntaxKind::Arrow),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use trust_syntax::parser::parse;
+
+    #[test]
+    fn normalizes_addresses() {
+        assert_eq!(
+            normalize_direct_address("%qx0.3").as_deref(),
+            Some("%QX0.3")
+        );
+        assert_eq!(normalize_direct_address("%Q0.3").as_deref(), Some("%QX0.3"));
+        assert_eq!(normalize_direct_address("%MW10").as_deref(), Some("%MW10"));
+        assert_eq!(normalize_direct_address("%Q*"), None);
+        assert_eq!(normalize_direct_address("QX0.3"), None);
+    }
+
+    #[test]
+    fn classifies_uses() {
+        let source = r#"
+PROGRAM Main
+VAR
+    Lamp AT %QX0.3 : BOOL;
+    t : TON;
+END_VAR
+%Q0.3 := TRUE;
+Lamp := %IX0.1;
+t(IN := %IX0.2, Q => %QX0.4);
+END_PROGRAM
+"#;
+        let root = parse(source).syntax();
+        let uses: Vec<_> = direct_address_uses(&root)
+            .into_iter()
+            .map(|entry| (entry.address.to_string(), entry.kind))
+            .collect();
+        assert_eq!(
+            uses,
+            vec![
+                ("%QX0.3".to_string(), DirectAddressUseKind::Declaration),
+                ("%QX0.3".to_string(), DirectAddressUseKind::Write),
+                ("%IX0.1".to_string(), DirectAddressUseKind::Read),
+                ("%IX0.2".to_string(), DirectAddressUseKind::Read),
+                ("%QX0.4".to_string(), DirectAddressUseKind::Write),
+            ]
+        );
+    }
+}
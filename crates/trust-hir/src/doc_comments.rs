@@ -0,0 +1,336 @@
+//! Structured documentation comments.
+//!
+//! A comment directly above a declaration, with no blank line in between,
+//! documents that declaration. Lines may start with `@brief`, `@param <name>`,
+//! or `@return`; untagged lines become free-form details. Variables may also
+//! be documented by a comment on the same line after their `;`.
+
+use smol_str::SmolStr;
+use trust_syntax::syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+/// A documented parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocParam {
+    /// Parameter name as written in the tag.
+    pub name: SmolStr,
+    /// Parameter description.
+    pub description: String,
+}
+
+/// A parsed documentation comment.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocComment {
+    /// `@brief` summary.
+    pub brief: Option<String>,
+    /// Untagged lines, in order.
+    pub details: Vec<String>,
+    /// `@param` entries, in order.
+    pub params: Vec<DocParam>,
+    /// `@return` description.
+    pub returns: Option<String>,
+}
+
+enum CurrentTag {
+    Brief,
+    Detail,
+    Param(usize),
+    Return,
+}
+
+impl DocComment {
+    /// Returns true when the comment documents nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.brief.is_none()
+            && self.details.is_empty()
+            && self.params.is_empty()
+            && self.returns.is_none()
+    }
+
+    /// Renders the comment as Markdown for hover and completion.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut sections = Vec::new();
+        if let Some(brief) = &self.brief {
+            sections.push(brief.clone());
+        }
+        if !self.details.is_empty() {
+            sections.push(self.details.join("\n"));
+        }
+        if !self.params.is_empty() {
+            let mut params = String::from("**Parameters**");
+            for param in &self.params {
+                if param.description.is_empty() {
+                    params.push_str(&format!("\n- `{}`", param.name));
+                } else {
+                    params.push_str(&format!("\n- `{}`: {}", param.name, param.description));
+                }
+            }
+            sections.push(params);
+        }
+        if let Some(returns) = &self.returns {
+            sections.push(format!("**Returns**: {returns}"));
+        }
+        sections.join("\n\n")
+    }
+}
+
+/// Parses tagged comment lines.
+///
+/// `subject` names the declaration in the returned issue messages, for
+/// example ``FUNCTION `Add` ``.
+#[must_use]
+pub fn parse_doc_comment(lines: &[String], subject: &str) -> (DocComment, Vec<String>) {
+    let mut doc = DocComment::default();
+    let mut issues = Vec::new();
+    let mut current: Option<CurrentTag> = None;
+
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some(tag_line) = trimmed.strip_prefix('@') {
+            let mut parts = tag_line.splitn(2, char::is_whitespace);
+            let tag = parts.next().unwrap_or_default().to_ascii_lowercase();
+            let remainder = parts.next().map_or("", str::trim_start);
+            match tag.as_str() {
+                "brief" => {
+                    if doc.brief.is_some() {
+                        issues.push(format!("duplicate @brief tag for {subject}"));
+                    }
+                    if remainder.is_empty() {
+                        issues.push(format!("missing description for @brief on {subject}"));
+                    }
+                    doc.brief = (!remainder.is_empty()).then(|| remainder.to_string());
+                    current = Some(CurrentTag::Brief);
+                }
+                "param" => {
+                    let mut param_parts = remainder.splitn(2, char::is_whitespace);
+                    let Some(name) = param_parts.next().filter(|text| !text.trim().is_empty())
+                    else {
+                        issues.push(format!(
+                            "malformed @param tag on {subject} (expected: @param <name> <description>)"
+                        ));
+                        current = None;
+                        continue;
+                    };
+                    let description = param_parts.next().map_or("", str::trim_start);
+                    if description.is_empty() {
+                        issues.push(format!(
+                            "missing description for @param `{name}` on {subject}"
+                        ));
+                    }
+                    doc.params.push(DocParam {
+                        name: SmolStr::new(name),
+                        description: description.to_string(),
+                    });
+                    current = Some(CurrentTag::Param(doc.params.len() - 1));
+                }
+                "return" => {
+                    if doc.returns.is_some() {
+                        issues.push(format!("duplicate @return tag for {subject}"));
+                    }
+                    if remainder.is_empty() {
+                        issues.push(format!("missing description for @return on {subject}"));
+                    }
+                    doc.returns = (!remainder.is_empty()).then(|| remainder.to_string());
+                    current = Some(CurrentTag::Return);
+                }
+                other => {
+                    issues.push(format!("unknown documentation tag `@{other}` on {subject}"));
+                    current = None;
+                }
+            }
+            continue;
+        }
+
+        match current {
+            Some(CurrentTag::Brief) => append_with_space(&mut doc.brief, trimmed),
+            Some(CurrentTag::Param(index)) => {
+                if let Some(param) = doc.params.get_mut(index) {
+                    append_string_with_space(&mut param.description, trimmed);
+                }
+            }
+            Some(CurrentTag::Return) => append_with_space(&mut doc.returns, trimmed),
+            Some(CurrentTag::Detail) | None => {
+                doc.details.push(trimmed.to_string());
+                current = Some(CurrentTag::Detail);
+            }
+        }
+    }
+
+    (doc, issues)
+}
+
+/// Checks `@param` and `@return` tags against the documented declaration.
+#[must_use]
+pub fn check_doc_signature(
+    doc: &DocComment,
+    subject: &str,
+    declared_params: &[SmolStr],
+    has_return: bool,
+) -> Vec<String> {
+    let mut issues = Vec::new();
+    let mut seen = Vec::new();
+    for param in &doc.params {
+        let normalized = param.name.to_ascii_uppercase();
+        if seen.contains(&normalized) {
+            issues.push(format!(
+                "duplicate @param entry for `{}` on {subject}",
+                param.name
+            ));
+        } else {
+            seen.push(normalized);
+        }
+        if !declared_params
+            .iter()
+            .any(|declared| declared.eq_ignore_ascii_case(&param.name))
+        {
+            issues.push(format!(
+                "@param `{}` does not match any declared parameter on {subject}",
+                param.name
+            ));
+        }
+    }
+    if doc.returns.is_some() && !has_return {
+        issues.push(format!("@return used on non-returning {subject}"));
+    }
+    issues
+}
+
+/// Strips comment delimiters and decorative leading `*` from a comment token.
+#[must_use]
+pub fn comment_lines(raw: &str) -> Vec<String> {
+    let raw = raw.trim_end();
+    if let Some(line) = raw.strip_prefix("//") {
+        return vec![line.trim().to_string()];
+    }
+    let body = raw
+        .strip_prefix("(*")
+        .and_then(|text| text.strip_suffix("*)"))
+        .or_else(|| {
+            raw.strip_prefix("/*")
+                .and_then(|text| text.strip_suffix("*/"))
+        })
+        .unwrap_or(raw);
+    body.lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let without_star = trimmed.strip_prefix('*').map_or(trimmed, str::trim_start);
+            without_star.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Returns the comment lines directly above `node`.
+///
+/// A comment that trails code on its own line belongs to that code and ends
+/// the search, as does a blank line.
+#[must_use]
+pub fn leading_comment_lines(node: &SyntaxNode) -> Vec<String> {
+    let Some(first) = first_non_trivia_token(node) else {
+        return Vec::new();
+    };
+    let mut comments: Vec<SyntaxToken> = Vec::new();
+    let mut token = first.prev_token();
+    while let Some(current) = token {
+        match current.kind() {
+            SyntaxKind::Whitespace => {
+                if current.text().matches('\n').count() > 1 {
+                    break;
+                }
+            }
+            SyntaxKind::LineComment | SyntaxKind::BlockComment => {
+                if trails_code(&current) {
+                    break;
+                }
+                comments.push(current.clone());
+            }
+            _ => break,
+        }
+        token = current.prev_token();
+    }
+    comments.reverse();
+    trim_blank_lines(
+        comments
+            .iter()
+            .flat_map(|comment| comment_lines(comment.text()))
+            .collect(),
+    )
+}
+
+/// Returns the lines of a comment on the same line after `node`, such as
+/// `speed : INT; (* rpm *)`.
+#[must_use]
+pub fn trailing_comment_lines(node: &SyntaxNode) -> Vec<String> {
+    let Some(last) = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !token.kind().is_trivia())
+        .last()
+    else {
+        return Vec::new();
+    };
+    let mut token = last.next_token();
+    while let Some(current) = token {
+        match current.kind() {
+            SyntaxKind::Whitespace if !current.text().contains('\n') => {}
+            SyntaxKind::LineComment | SyntaxKind::BlockComment => {
+                return trim_blank_lines(comment_lines(current.text()));
+            }
+            _ => break,
+        }
+        token = current.next_token();
+    }
+    Vec::new()
+}
+
+fn trails_code(comment: &SyntaxToken) -> bool {
+    let mut token = comment.prev_token();
+    while let Some(current) = token {
+        match current.kind() {
+            SyntaxKind::Whitespace => {
+                if current.text().contains('\n') {
+                    return false;
+                }
+            }
+            SyntaxKind::LineComment | SyntaxKind::BlockComment => {}
+            _ => return true,
+        }
+        token = current.prev_token();
+    }
+    false
+}
+
+fn first_non_trivia_token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find(|token| !token.kind().is_trivia())
+}
+
+fn trim_blank_lines(mut lines: Vec<String>) -> Vec<String> {
+    while matches!(lines.first(), Some(line) if line.trim().is_empty()) {
+        lines.remove(0);
+    }
+    while matches!(lines.last(), Some(line) if line.trim().is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+fn append_with_space(target: &mut Option<String>, value: &str) {
+    if let Some(existing) = target {
+        append_string_with_space(existing, value);
+    } else {
+        *target = Some(value.to_string());
+    }
+}
+
+fn append_string_with_space(target: &mut String, value: &str) {
+    if !target.is_empty() {
+        target.push(' ');
+    }
+    target.push_str(value);
+}
@@ -31,6 +31,9 @@
 
 pub mod db;
 pub mod diagnostics;
+pub mod dialect;
+pub mod direct_address;
+pub mod doc_comments;
 pub mod ident;
 /// Project-wide source registry and database helpers.
 pub mod project;
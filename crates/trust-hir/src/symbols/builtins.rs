@@ -71,6 +71,9 @@ impl SymbolTable {
         self.register_timer_function_block("TP_LTIME", TypeId::LTIME);
         self.register_timer_function_block("TON_LTIME", TypeId::LTIME);
         self.register_timer_function_block("TOF_LTIME", TypeId::LTIME);
+
+        self.register_async_function_blocks();
+        self.register_communication_function_blocks();
     }
 
     fn register_bistable_function_blocks(&mut self) {
@@ -90,6 +93,14 @@ impl SymbolTable {
                 ("Q1", TypeId::BOOL, ParamDirection::Out),
             ],
         );
+        self.register_simple_function_block(
+            "SEMA",
+            &[
+                ("CLAIM", TypeId::BOOL, ParamDirection::In),
+                ("RELEASE", TypeId::BOOL, ParamDirection::In),
+                ("BUSY", TypeId::BOOL, ParamDirection::Out),
+            ],
+        );
     }
 
     fn register_edge_detection_function_blocks(&mut self) {
@@ -197,6 +208,152 @@ impl SymbolTable {
         );
     }
 
+    /// Runtime extension: I/O blocks whose work runs on background workers.
+    fn register_async_function_blocks(&mut self) {
+        self.register_simple_function_block(
+            "FILE_READ_ASYNC",
+            &[
+                ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+                ("PATH", TypeId::STRING, ParamDirection::In),
+                ("DONE", TypeId::BOOL, ParamDirection::Out),
+                ("BUSY", TypeId::BOOL, ParamDirection::Out),
+                ("ERROR", TypeId::BOOL, ParamDirection::Out),
+                ("STATUS", TypeId::STRING, ParamDirection::Out),
+                ("DATA", TypeId::STRING, ParamDirection::Out),
+            ],
+        );
+        self.register_simple_function_block(
+            "FILE_WRITE_ASYNC",
+            &[
+                ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+                ("PATH", TypeId::STRING, ParamDirection::In),
+                ("DATA", TypeId::STRING, ParamDirection::In),
+                ("APPEND", TypeId::BOOL, ParamDirection::In),
+                ("DONE", TypeId::BOOL, ParamDirection::Out),
+                ("BUSY", TypeId::BOOL, ParamDirection::Out),
+                ("ERROR", TypeId::BOOL, ParamDirection::Out),
+                ("STATUS", TypeId::STRING, ParamDirection::Out),
+            ],
+        );
+        self.register_simple_function_block(
+            "TCP_CLIENT",
+            &[
+                ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+                ("HOST", TypeId::STRING, ParamDirection::In),
+                ("SEND", TypeId::STRING, ParamDirection::In),
+                ("TIMEOUT", TypeId::TIME, ParamDirection::In),
+                ("DONE", TypeId::BOOL, ParamDirection::Out),
+                ("BUSY", TypeId::BOOL, ParamDirection::Out),
+                ("ERROR", TypeId::BOOL, ParamDirection::Out),
+                ("STATUS", TypeId::STRING, ParamDirection::Out),
+                ("RECEIVED", TypeId::STRING, ParamDirection::Out),
+            ],
+        );
+        self.register_simple_function_block(
+            "FILE_OPEN",
+            &[
+                ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+                ("PATH", TypeId::STRING, ParamDirection::In),
+                ("WRITE", TypeId::BOOL, ParamDirection::In),
+                ("APPEND", TypeId::BOOL, ParamDirection::In),
+                ("DONE", TypeId::BOOL, ParamDirection::Out),
+                ("BUSY", TypeId::BOOL, ParamDirection::Out),
+                ("ERROR", TypeId::BOOL, ParamDirection::Out),
+                ("STATUS", TypeId::STRING, ParamDirection::Out),
+                ("HANDLE", TypeId::UDINT, ParamDirection::Out),
+            ],
+        );
+        self.register_simple_function_block(
+            "FILE_READ",
+            &[
+                ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+                ("HANDLE", TypeId::UDINT, ParamDirection::In),
+                ("DONE", TypeId::BOOL, ParamDirection::Out),
+                ("BUSY", TypeId::BOOL, ParamDirection::Out),
+                ("ERROR", TypeId::BOOL, ParamDirection::Out),
+                ("STATUS", TypeId::STRING, ParamDirection::Out),
+                ("DATA", TypeId::STRING, ParamDirection::Out),
+                ("EOF", TypeId::BOOL, ParamDirection::Out),
+            ],
+        );
+        self.register_simple_function_block(
+            "FILE_WRITE",
+            &[
+                ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+                ("HANDLE", TypeId::UDINT, ParamDirection::In),
+                ("DATA", TypeId::STRING, ParamDirection::In),
+                ("DONE", TypeId::BOOL, ParamDirection::Out),
+                ("BUSY", TypeId::BOOL, ParamDirection::Out),
+                ("ERROR", TypeId::BOOL, ParamDirection::Out),
+                ("STATUS", TypeId::STRING, ParamDirection::Out),
+            ],
+        );
+        self.register_simple_function_block(
+            "FILE_CLOSE",
+            &[
+                ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+                ("HANDLE", TypeId::UDINT, ParamDirection::In),
+                ("DONE", TypeId::BOOL, ParamDirection::Out),
+                ("BUSY", TypeId::BOOL, ParamDirection::Out),
+                ("ERROR", TypeId::BOOL, ParamDirection::Out),
+                ("STATUS", TypeId::STRING, ParamDirection::Out),
+            ],
+        );
+    }
+
+    /// Runtime extension: socket and serial endpoints exchanging byte buffers.
+    fn register_communication_function_blocks(&mut self) {
+        let bytes = self.register_array_type(TypeId::BYTE, vec![(0, i64::MAX)]);
+        type Fields<'a> = &'a [(&'a str, TypeId)];
+        let blocks: [(&str, Fields, Fields); 4] = [
+            (
+                "TCP_STREAM_CLIENT",
+                &[("HOST", TypeId::STRING)],
+                &[("CONNECTED", TypeId::BOOL)],
+            ),
+            (
+                "TCP_STREAM_SERVER",
+                &[("PORT", TypeId::UINT)],
+                &[("CONNECTED", TypeId::BOOL), ("PEER", TypeId::STRING)],
+            ),
+            (
+                "UDP_SOCKET",
+                &[("PORT", TypeId::UINT), ("REMOTE", TypeId::STRING)],
+                &[("OPEN", TypeId::BOOL), ("PEER", TypeId::STRING)],
+            ),
+            (
+                "SERIAL_PORT",
+                &[("DEVICE", TypeId::STRING), ("BAUD", TypeId::UDINT)],
+                &[("OPEN", TypeId::BOOL)],
+            ),
+        ];
+        for (name, inputs, outputs) in blocks {
+            let mut params = vec![("ENABLE", TypeId::BOOL, ParamDirection::In)];
+            params.extend(
+                inputs
+                    .iter()
+                    .map(|(param, type_id)| (*param, *type_id, ParamDirection::In)),
+            );
+            params.extend([
+                ("SEND", TypeId::BOOL, ParamDirection::In),
+                ("TX_LEN", TypeId::UINT, ParamDirection::In),
+                ("TX_BUFFER", bytes, ParamDirection::InOut),
+                ("RX_BUFFER", bytes, ParamDirection::InOut),
+            ]);
+            params.extend(
+                outputs
+                    .iter()
+                    .map(|(param, type_id)| (*param, *type_id, ParamDirection::Out)),
+            );
+            params.extend([
+                ("RX_LEN", TypeId::UINT, ParamDirection::Out),
+                ("ERROR", TypeId::BOOL, ParamDirection::Out),
+                ("STATUS", TypeId::STRING, ParamDirection::Out),
+            ]);
+            self.register_simple_function_block(name, &params);
+        }
+    }
+
     fn register_simple_function_block(
         &mut self,
         name: &str,
@@ -179,6 +179,8 @@ pub struct SymbolModifiers {
     pub is_abstract: bool,
     /// OVERRIDE modifier (method).
     pub is_override: bool,
+    /// Interface method declared with a default body.
+    pub has_default_body: bool,
 }
 
 /// A symbol in the symbol table.
@@ -6,6 +6,9 @@ use text_size::TextRange;
 
 use crate::types::{StructField, Type, TypeId, UnionVariant};
 
+/// Maximum number of `USING PUBLIC` hops followed when resolving a qualified name.
+const MAX_REEXPORT_DEPTH: usize = 8;
+
 /// The symbol table containing all symbols and scopes.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SymbolTable {
@@ -25,6 +28,8 @@ pub struct SymbolTable {
     extends: FxHashMap<SymbolId, SmolStr>,
     /// Implements relationships (symbol -> interface type names).
     implements: FxHashMap<SymbolId, Vec<SmolStr>>,
+    /// Namespaces re-exported by a namespace via `USING PUBLIC`.
+    namespace_reexports: FxHashMap<SymbolId, Vec<Vec<SmolStr>>>,
     /// Constant values by (scope, name).
     const_values: FxHashMap<(Option<SmolStr>, SmolStr), i64>,
     /// Next symbol ID to assign.
@@ -52,6 +57,7 @@ impl SymbolTable {
             types: FxHashMap::default(),
             extends: FxHashMap::default(),
             implements: FxHashMap::default(),
+            namespace_reexports: FxHashMap::default(),
             const_values: FxHashMap::default(),
             next_id: 0,
             next_type_id: TypeId::USER_TYPES_START,
@@ -112,6 +118,23 @@ impl SymbolTable {
         }
     }
 
+    /// Records that `namespace` re-exports the public members of the namespace at `path`.
+    pub fn add_namespace_reexport(&mut self, namespace: SymbolId, path: Vec<SmolStr>) {
+        let paths = self.namespace_reexports.entry(namespace).or_default();
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+
+    /// Returns the namespace paths re-exported by `namespace`.
+    #[must_use]
+    pub fn namespace_reexports(&self, namespace: SymbolId) -> &[Vec<SmolStr>] {
+        self.namespace_reexports
+            .get(&namespace)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
     /// Returns the total number of scopes.
     #[must_use]
     pub fn scope_count(&self) -> usize {
@@ -257,8 +280,15 @@ impl SymbolTable {
     }
 
     /// Resolves a qualified name via namespace symbols.
+    ///
+    /// Members a namespace re-exports with `USING PUBLIC` resolve as if they were
+    /// declared in that namespace.
     #[must_use]
     pub fn resolve_qualified(&self, parts: &[SmolStr]) -> Option<SymbolId> {
+        self.resolve_qualified_with_depth(parts, 0)
+    }
+
+    fn resolve_qualified_with_depth(&self, parts: &[SmolStr], depth: usize) -> Option<SymbolId> {
         if parts.is_empty() {
             return None;
         }
@@ -268,18 +298,37 @@ impl SymbolTable {
             if !matches!(symbol.kind, SymbolKind::Namespace) {
                 return None;
             }
-            let mut next = None;
-            for sym in self.symbols.values() {
-                if sym.parent == Some(current) && sym.name.eq_ignore_ascii_case(part.as_str()) {
-                    next = Some(sym.id);
-                    break;
-                }
-            }
-            current = next?;
+            current = self.namespace_member(current, part, depth)?;
         }
         Some(current)
     }
 
+    fn namespace_member(&self, namespace: SymbolId, name: &str, depth: usize) -> Option<SymbolId> {
+        for sym in self.symbols.values() {
+            if sym.parent == Some(namespace) && sym.name.eq_ignore_ascii_case(name) {
+                return Some(sym.id);
+            }
+        }
+        // Re-export chains are short; the limit only guards against cycles.
+        if depth >= MAX_REEXPORT_DEPTH {
+            return None;
+        }
+        for path in self.namespace_reexports(namespace) {
+            let mut parts = path.clone();
+            parts.push(SmolStr::new(name));
+            let Some(id) = self.resolve_qualified_with_depth(&parts, depth + 1) else {
+                continue;
+            };
+            let exported = self
+                .get(id)
+                .is_some_and(|sym| !matches!(sym.visibility, Visibility::Internal));
+            if exported {
+                return Some(id);
+            }
+        }
+        None
+    }
+
     /// Looks up a symbol by name in a specific scope.
     #[must_use]
     pub fn lookup_in_scope(&self, scope_id: ScopeId, name: &str) -> Option<SymbolId> {
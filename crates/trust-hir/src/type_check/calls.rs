@@ -88,8 +88,7 @@ impl<'a, 'b> CallChecker<'a, 'b> {
                         return TypeId::UNKNOWN;
                     };
 
-                    self.check_call_arguments(call_target.param_owner, &call_target.kind, node);
-                    return call_target.return_type;
+                    return self.check_call(&call_target, node);
                 }
 
                 if let Some(result) = self
@@ -117,8 +116,7 @@ impl<'a, 'b> CallChecker<'a, 'b> {
             {
                 if let Some(call_target) = self.checker.resolve_ref().resolve_call_target(symbol_id)
                 {
-                    self.check_call_arguments(call_target.param_owner, &call_target.kind, node);
-                    return call_target.return_type;
+                    return self.check_call(&call_target, node);
                 }
                 self.checker.diagnostics.error(
                     DiagnosticCode::UndefinedFunction,
@@ -138,6 +136,7 @@ impl<'a, 'b> CallChecker<'a, 'b> {
                         &name,
                         member.text_range(),
                     ) {
+                        self.check_abstract_super_call(base, member, resolved.id);
                         if !resolved.accessible {
                             return TypeId::UNKNOWN;
                         }
@@ -152,8 +151,7 @@ impl<'a, 'b> CallChecker<'a, 'b> {
                             return TypeId::UNKNOWN;
                         };
 
-                        self.check_call_arguments(call_target.param_owner, &call_target.kind, node);
-                        return call_target.return_type;
+                        return self.check_call(&call_target, node);
                     }
                 }
             }
@@ -168,19 +166,31 @@ impl<'a, 'b> CallChecker<'a, 'b> {
             .resolve_ref()
             .resolve_call_target_from_type(callee_type)
         {
-            self.check_call_arguments(call_target.param_owner, &call_target.kind, node);
-            return call_target.return_type;
+            return self.check_call(&call_target, node);
         }
 
         TypeId::UNKNOWN
     }
 
+    /// Checks a call to a resolved target and returns its (instantiated) result type.
+    fn check_call(&mut self, call_target: &CallTargetInfo, node: &SyntaxNode) -> TypeId {
+        let bindings = self.check_call_arguments(call_target.param_owner, &call_target.kind, node);
+        let return_type = self
+            .checker
+            .instantiate_with(&bindings, call_target.return_type);
+        // A generic result that no argument bound leaves the call untyped.
+        if self.checker.generic_class(return_type).is_some() {
+            return TypeId::UNKNOWN;
+        }
+        return_type
+    }
+
     pub(super) fn check_call_arguments(
         &mut self,
         symbol_id: SymbolId,
         kind: &SymbolKind,
         node: &SyntaxNode,
-    ) {
+    ) -> GenericBindings {
         if matches!(kind, SymbolKind::FunctionBlock) {
             let standard_fb = self
                 .checker
@@ -193,13 +203,22 @@ impl<'a, 'b> CallChecker<'a, 'b> {
                     .standard()
                     .check_standard_function_block_call(&name, node)
                 {
-                    return;
+                    return GenericBindings::default();
                 }
             }
         }
 
         let params = self.callable_parameters(symbol_id, kind);
         let bound = self.bind_call_arguments(&params, node);
+        let bindings = self.infer_generic_bindings(symbol_id, &params, &bound);
+        let params: Vec<ParamInfo> = params
+            .into_iter()
+            .map(|param| ParamInfo {
+                type_id: self.checker.instantiate_with(&bindings, param.type_id),
+                ..param
+            })
+            .collect();
         self.check_bound_call_argument_types(&params, &bound);
+        bindings
     }
 }
@@ -119,6 +119,7 @@ impl<'a, 'b> CallChecker<'a, 'b> {
                         &field_name,
                         member.text_range(),
                     ) {
+                        self.check_abstract_super_call(base, member, resolved.id);
                         let Some(symbol) = self.checker.symbols.get(resolved.id) else {
                             return TypeId::UNKNOWN;
                         };
@@ -175,8 +176,32 @@ impl<'a, 'b> CallChecker<'a, 'b> {
             (Type::DWord, PartialAccess::Word(_)) => (TypeId::WORD, 1u8),
             (Type::LWord, PartialAccess::Word(_)) => (TypeId::WORD, 3u8),
             (Type::LWord, PartialAccess::DWord(_)) => (TypeId::DWORD, 1u8),
-            _ => return None,
+            // Bit access on integers is a CODESYS extension over the two's complement bits.
+            (Type::SInt | Type::USInt, PartialAccess::Bit(_)) => (TypeId::BOOL, 7u8),
+            (Type::Int | Type::UInt, PartialAccess::Bit(_)) => (TypeId::BOOL, 15u8),
+            (Type::DInt | Type::UDInt, PartialAccess::Bit(_)) => (TypeId::BOOL, 31u8),
+            (Type::LInt | Type::ULInt, PartialAccess::Bit(_)) => (TypeId::BOOL, 63u8),
+            (Type::Unknown, _) => return None,
+            _ => {
+                self.checker.diagnostics.error(
+                    DiagnosticCode::TypeMismatch,
+                    member.text_range(),
+                    format!(
+                        "partial access '{}' is not supported on '{}'",
+                        member.text().to_string().trim(),
+                        self.checker.type_name(base_type)
+                    ),
+                );
+                return Some(TypeId::UNKNOWN);
+            }
         };
+        if ty.is_integer() {
+            self.checker
+                .diagnostics
+                .add(crate::dialect::deprecated_integer_bit_access(
+                    member.text_range(),
+                ));
+        }
         let index = access.index();
         if index > max_index {
             self.checker.diagnostics.error(
@@ -190,16 +215,18 @@ impl<'a, 'b> CallChecker<'a, 'b> {
     }
 
     pub(in crate::type_check) fn infer_deref_expr(&mut self, node: &SyntaxNode) -> TypeId {
-        let operand = match node.children().next() {
-            Some(child) => self.checker.expr().check_expression(&child),
-            None => return TypeId::UNKNOWN,
+        let Some(operand_node) = node.children().next() else {
+            return TypeId::UNKNOWN;
         };
+        let operand = self.checker.expr().check_expression(&operand_node);
 
         let operand = self.checker.resolve_alias_type(operand);
         if let Some(Type::Pointer { target } | Type::Reference { target }) =
             self.checker.symbols.type_by_id(operand)
         {
-            return *target;
+            let target = *target;
+            self.checker.check_null_dereference(node, &operand_node);
+            return target;
         }
 
         self.checker.diagnostics.error(
@@ -250,10 +277,14 @@ impl<'a, 'b> CallChecker<'a, 'b> {
         lower: i64,
         upper: i64,
     ) {
+        // ARRAY[*] bounds are only known at runtime.
+        if lower == 0 && upper == i64::MAX {
+            return;
+        }
         if let Some(value_int) = self.checker.eval_const_int_expr(expr) {
             if value_int < lower || value_int > upper {
                 self.checker.diagnostics.error(
-                    DiagnosticCode::OutOfRange,
+                    DiagnosticCode::ArrayIndexOutOfBounds,
                     expr.text_range(),
                     format!(
                         "array index {} outside bounds {}..{}",
@@ -264,10 +295,33 @@ impl<'a, 'b> CallChecker<'a, 'b> {
             }
         }
 
+        if let Some(symbol_id) = self.checker.resolve_ref().resolve_simple_symbol(expr) {
+            let control_range = self
+                .checker
+                .loop_stack
+                .iter()
+                .rev()
+                .filter_map(|ctx| ctx.control_range)
+                .find(|(control, _, _)| *control == symbol_id);
+            if let Some((_, first, last)) = control_range {
+                if first < lower || last > upper {
+                    self.checker.diagnostics.warning(
+                        DiagnosticCode::LoopIndexOutOfBounds,
+                        expr.text_range(),
+                        format!(
+                            "FOR loop index runs {}..{}, outside array bounds {}..{}",
+                            first, last, lower, upper
+                        ),
+                    );
+                }
+                return;
+            }
+        }
+
         if let Some((_, idx_lower, idx_upper)) = self.checker.subrange_bounds(idx_type) {
             if idx_lower < lower || idx_upper > upper {
                 self.checker.diagnostics.error(
-                    DiagnosticCode::OutOfRange,
+                    DiagnosticCode::ArrayIndexOutOfBounds,
                     expr.text_range(),
                     format!(
                         "array index subrange {}..{} outside bounds {}..{}",
@@ -277,6 +331,43 @@ impl<'a, 'b> CallChecker<'a, 'b> {
             }
         }
     }
+
+    /// Reports calls of an ABSTRACT method through `SUPER`, which has no body to run.
+    pub(in crate::type_check) fn check_abstract_super_call(
+        &mut self,
+        base: &SyntaxNode,
+        member: &SyntaxNode,
+        symbol_id: SymbolId,
+    ) {
+        let Some(symbol) = self.checker.symbols.get(symbol_id) else {
+            return;
+        };
+        if matches!(symbol.kind, SymbolKind::Method { .. })
+            && symbol.modifiers.is_abstract
+            && is_super_receiver(base)
+        {
+            self.checker.diagnostics.error(
+                DiagnosticCode::InvalidOperation,
+                member.text_range(),
+                format!(
+                    "cannot call ABSTRACT method '{}' through SUPER",
+                    symbol.name
+                ),
+            );
+        }
+    }
+}
+
+/// Returns true for `SUPER` and `SUPER^` receivers.
+fn is_super_receiver(node: &SyntaxNode) -> bool {
+    match node.kind() {
+        SyntaxKind::SuperExpr => true,
+        SyntaxKind::DerefExpr => node
+            .children()
+            .next()
+            .is_some_and(|child| child.kind() == SyntaxKind::SuperExpr),
+        _ => false,
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
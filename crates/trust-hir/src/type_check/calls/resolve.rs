@@ -106,6 +106,7 @@ impl<'a, 'b> ResolveCheckerRef<'a, 'b> {
     ) -> Option<SymbolId> {
         let mut visited = FxHashSet::default();
         let mut current = Some(root_id);
+        let mut interfaces = Vec::new();
 
         while let Some(symbol_id) = current {
             if !visited.insert(symbol_id) {
@@ -118,14 +119,68 @@ impl<'a, 'b> ResolveCheckerRef<'a, 'b> {
                 }
             }
 
-            let base_name = self.checker.symbols.extends_name(symbol_id)?;
-            let base_id = self.checker.symbols.resolve_by_name(base_name.as_str())?;
-            current = Some(base_id);
+            if let Some(names) = self.checker.symbols.implements_names(symbol_id) {
+                interfaces.extend(names.iter().cloned());
+            }
+            current = self
+                .checker
+                .symbols
+                .extends_name(symbol_id)
+                .and_then(|base_name| self.checker.symbols.resolve_by_name(base_name.as_str()));
+        }
+
+        interfaces
+            .iter()
+            .find_map(|name| self.resolve_default_interface_method(name, field_name))
+    }
+
+    /// Finds a method with a default body in `interface` or its base interfaces.
+    fn resolve_default_interface_method(
+        &self,
+        interface: &SmolStr,
+        field_name: &str,
+    ) -> Option<SymbolId> {
+        let mut visited = FxHashSet::default();
+        let mut current = self.resolve_interface_name(interface);
+
+        while let Some(symbol_id) = current {
+            if !visited.insert(symbol_id) {
+                break;
+            }
+
+            let method = self.checker.symbols.iter().find(|sym| {
+                sym.parent == Some(symbol_id)
+                    && sym.modifiers.has_default_body
+                    && sym.name.eq_ignore_ascii_case(field_name)
+            });
+            if let Some(method) = method {
+                return Some(method.id);
+            }
+
+            current = self
+                .checker
+                .symbols
+                .extends_name(symbol_id)
+                .and_then(|base_name| self.resolve_interface_name(base_name));
         }
 
         None
     }
 
+    fn resolve_interface_name(&self, name: &SmolStr) -> Option<SymbolId> {
+        let parts: Vec<SmolStr> = name.split('.').map(SmolStr::new).collect();
+        self.checker
+            .symbols
+            .resolve_qualified(&parts)
+            .or_else(|| self.checker.symbols.resolve_by_name(name.as_str()))
+            .filter(|id| {
+                self.checker
+                    .symbols
+                    .get(*id)
+                    .is_some_and(|sym| matches!(sym.kind, SymbolKind::Interface))
+            })
+    }
+
     pub(in crate::type_check) fn resolve_namespace_qualified_symbol(
         &self,
         node: &SyntaxNode,
@@ -1,7 +1,8 @@
 use super::helpers::direct_address_type;
 use super::literals::{
-    int_literal_info, is_long_date_literal, is_long_dt_literal, is_long_time_literal,
-    is_long_tod_literal, smallest_int_type_for_literal,
+    int_binary_op_from_node, int_literal_info, is_long_date_literal, is_long_dt_literal,
+    is_long_time_literal, is_long_tod_literal, is_untyped_int_literal_expr,
+    smallest_int_type_for_literal, IntBinaryOp,
 };
 use super::*;
 
@@ -23,6 +24,7 @@ impl<'a> TypeChecker<'a> {
             super_type: None,
             loop_stack: Vec::new(),
             label_scopes: Vec::new(),
+            generic_bindings: GenericBindings::default(),
         }
     }
 
@@ -174,7 +176,8 @@ impl<'a, 'b> ExprChecker<'a, 'b> {
                         }
                     }
                 }
-                symbol.type_id
+                let type_id = symbol.type_id;
+                self.checker.instantiate(type_id)
             }
             None => {
                 self.checker.diagnostics.error(
@@ -202,6 +205,9 @@ impl<'a, 'b> ExprChecker<'a, 'b> {
 
         if op.is_comparison() {
             self.check_comparable(lhs_type, rhs_type, node.text_range());
+            if let Some(common) = self.numeric_common_type(lhs_type, rhs_type) {
+                self.warn_operand_conversions(lhs_node, lhs_type, rhs_node, rhs_type, common);
+            }
             TypeId::BOOL
         } else if op.is_logical() {
             self.check_boolean(lhs_type, node.text_range());
@@ -223,12 +229,132 @@ impl<'a, 'b> ExprChecker<'a, 'b> {
                     return rhs_type;
                 }
             }
-            self.common_numeric_type(lhs_type, rhs_type, node.text_range())
+            if let Some(result) = self.check_bool_operand(lhs_node, lhs_type, rhs_node, rhs_type) {
+                return result;
+            }
+            let result = self.common_numeric_type(lhs_type, rhs_type, node.text_range());
+            if result != TypeId::UNKNOWN {
+                self.warn_operand_conversions(lhs_node, lhs_type, rhs_node, rhs_type, result);
+                self.warn_constant_overflow(node, lhs_node, rhs_node, result);
+            }
+            result
         } else {
             TypeId::UNKNOWN
         }
     }
 
+    /// Reports a BOOL operand combined with a numeric operand in arithmetic and
+    /// continues with the numeric type.
+    fn check_bool_operand(
+        &mut self,
+        lhs_node: &SyntaxNode,
+        lhs_type: TypeId,
+        rhs_node: &SyntaxNode,
+        rhs_type: TypeId,
+    ) -> Option<TypeId> {
+        let lhs = self.checker.resolve_subrange_base(lhs_type);
+        let rhs = self.checker.resolve_subrange_base(rhs_type);
+        let (bool_node, numeric) = if lhs == TypeId::BOOL {
+            (lhs_node, rhs)
+        } else if rhs == TypeId::BOOL {
+            (rhs_node, lhs)
+        } else {
+            return None;
+        };
+        if !self.checker.symbols.type_by_id(numeric)?.is_numeric() {
+            return None;
+        }
+        let message = format!(
+            "implicit conversion from 'BOOL' to '{}' is not allowed in arithmetic",
+            self.checker.type_name(numeric)
+        );
+        self.checker.diagnostics.error(
+            DiagnosticCode::TypeMismatch,
+            bool_node.text_range(),
+            message,
+        );
+        Some(numeric)
+    }
+
+    fn numeric_common_type(&self, lhs: TypeId, rhs: TypeId) -> Option<TypeId> {
+        let lhs = self.checker.resolve_subrange_base(lhs);
+        let rhs = self.checker.resolve_subrange_base(rhs);
+        let lhs_numeric = self.checker.symbols.type_by_id(lhs)?.is_numeric();
+        let rhs_numeric = self.checker.symbols.type_by_id(rhs)?.is_numeric();
+        (lhs_numeric && rhs_numeric).then(|| self.checker.wider_numeric(lhs, rhs))
+    }
+
+    fn warn_operand_conversions(
+        &mut self,
+        lhs_node: &SyntaxNode,
+        lhs_type: TypeId,
+        rhs_node: &SyntaxNode,
+        rhs_type: TypeId,
+        common: TypeId,
+    ) {
+        self.checker
+            .warn_operand_conversion(lhs_node, lhs_type, common);
+        self.checker
+            .warn_operand_conversion(rhs_node, rhs_type, common);
+    }
+
+    /// Warns when integer arithmetic on constants leaves the range of its result
+    /// type. Untyped literal operands are skipped because the runtime evaluates
+    /// them as DINT, and operands that already overflow were reported themselves.
+    fn warn_constant_overflow(
+        &mut self,
+        node: &SyntaxNode,
+        lhs_node: &SyntaxNode,
+        rhs_node: &SyntaxNode,
+        result: TypeId,
+    ) {
+        if is_untyped_int_literal_expr(lhs_node) || is_untyped_int_literal_expr(rhs_node) {
+            return;
+        }
+        let result = self.checker.resolve_subrange_base(result);
+        let Some((min, max)) = integer_range(result) else {
+            return;
+        };
+        let Some(op) = int_binary_op_from_node(node) else {
+            return;
+        };
+        let (Some(lhs), Some(rhs)) = (
+            self.checker.eval_const_int_expr(lhs_node),
+            self.checker.eval_const_int_expr(rhs_node),
+        ) else {
+            return;
+        };
+        let (lhs, rhs) = (i128::from(lhs), i128::from(rhs));
+        if !(min..=max).contains(&lhs) || !(min..=max).contains(&rhs) {
+            return;
+        }
+        let value = match op {
+            IntBinaryOp::Add => Some(lhs + rhs),
+            IntBinaryOp::Sub => Some(lhs - rhs),
+            IntBinaryOp::Mul => Some(lhs * rhs),
+            IntBinaryOp::Div if rhs != 0 => Some(lhs / rhs),
+            IntBinaryOp::Power if rhs >= 0 => {
+                u32::try_from(rhs).ok().and_then(|exp| lhs.checked_pow(exp))
+            }
+            IntBinaryOp::Div | IntBinaryOp::Mod | IntBinaryOp::Power => return,
+        };
+        if value.is_some_and(|value| (min..=max).contains(&value)) {
+            return;
+        }
+        let type_name = self.checker.type_name(result);
+        let message = match value {
+            Some(value) => {
+                format!("constant expression result {value} overflows '{type_name}' ({min}..{max})")
+            }
+            None => format!("constant expression overflows '{type_name}' ({min}..{max})"),
+        };
+        self.checker.diagnostics.warning(
+            DiagnosticCode::ConstantOverflow,
+            node.text_range(),
+            message,
+        );
+    }
+
     fn infer_unary_expr(&mut self, node: &SyntaxNode) -> TypeId {
         let operand = match node.children().next() {
             Some(child) => self.check_expression(&child),
@@ -336,3 +462,19 @@ impl<'a, 'b> ExprChecker<'a, 'b> {
         }
     }
 }
+
+/// Value range of an integer type.
+fn integer_range(type_id: TypeId) -> Option<(i128, i128)> {
+    let range = match type_id {
+        TypeId::SINT => (i8::MIN.into(), i8::MAX.into()),
+        TypeId::INT => (i16::MIN.into(), i16::MAX.into()),
+        TypeId::DINT => (i32::MIN.into(), i32::MAX.into()),
+        TypeId::LINT => (i64::MIN.into(), i64::MAX.into()),
+        TypeId::USINT => (0, u8::MAX.into()),
+        TypeId::UINT => (0, u16::MAX.into()),
+        TypeId::UDINT => (0, u32::MAX.into()),
+        TypeId::ULINT => (0, u64::MAX.into()),
+        _ => return None,
+    };
+    Some(range)
+}
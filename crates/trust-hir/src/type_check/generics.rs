@@ -0,0 +1,298 @@
+//! Generic (`ANY_*`) user functions and methods.
+//!
+//! A POU whose parameters use a generic type class is checked once per concrete
+//! member of that class, and every call binds the class from its arguments so the
+//! result gets a concrete type.
+
+use super::calls::{BoundArgs, CallArg, ParamInfo};
+use super::literals::{is_untyped_int_literal_expr, is_untyped_real_literal_expr};
+use super::*;
+
+/// Generic type class to concrete type, for one instantiation.
+pub(crate) type GenericBindings = FxHashMap<TypeId, TypeId>;
+
+/// Concrete types a generic class can be instantiated with.
+const ELEMENTARY_TYPES: &[TypeId] = &[
+    TypeId::SINT,
+    TypeId::INT,
+    TypeId::DINT,
+    TypeId::LINT,
+    TypeId::USINT,
+    TypeId::UINT,
+    TypeId::UDINT,
+    TypeId::ULINT,
+    TypeId::REAL,
+    TypeId::LREAL,
+    TypeId::BOOL,
+    TypeId::BYTE,
+    TypeId::WORD,
+    TypeId::DWORD,
+    TypeId::LWORD,
+    TypeId::TIME,
+    TypeId::LTIME,
+    TypeId::DATE,
+    TypeId::LDATE,
+    TypeId::TOD,
+    TypeId::LTOD,
+    TypeId::DT,
+    TypeId::LDT,
+    TypeId::STRING,
+    TypeId::WSTRING,
+    TypeId::CHAR,
+    TypeId::WCHAR,
+];
+
+impl<'a> TypeChecker<'a> {
+    /// Sets the generic bindings used while checking one instantiation of a POU body.
+    pub(crate) fn set_generic_bindings(&mut self, bindings: GenericBindings) {
+        self.generic_bindings = bindings;
+    }
+
+    /// Substitutes the active generic bindings into `type_id`.
+    pub(crate) fn instantiate(&mut self, type_id: TypeId) -> TypeId {
+        if self.generic_bindings.is_empty() {
+            return type_id;
+        }
+        let bindings = std::mem::take(&mut self.generic_bindings);
+        let instantiated = self.instantiate_with(&bindings, type_id);
+        self.generic_bindings = bindings;
+        instantiated
+    }
+
+    pub(super) fn instantiate_with(
+        &mut self,
+        bindings: &GenericBindings,
+        type_id: TypeId,
+    ) -> TypeId {
+        if let Some(bound) = bindings.get(&type_id) {
+            return *bound;
+        }
+        if let Some(Type::Array {
+            element,
+            dimensions,
+        }) = self.symbols.type_by_id(type_id)
+        {
+            if let Some(bound) = bindings.get(element) {
+                let (bound, dimensions) = (*bound, dimensions.clone());
+                return self.symbols.register_array_type(bound, dimensions);
+            }
+        }
+        type_id
+    }
+
+    /// Generic class used by a parameter type, either directly or as an array element.
+    pub(super) fn generic_class(&self, type_id: TypeId) -> Option<TypeId> {
+        if self.is_generic_type(type_id) {
+            return Some(type_id);
+        }
+        match self.symbols.type_by_id(type_id) {
+            Some(Type::Array { element, .. }) if self.is_generic_type(*element) => Some(*element),
+            _ => None,
+        }
+    }
+
+    /// Infers an expression type without reporting diagnostics.
+    pub(super) fn peek_expression_type(&mut self, node: &SyntaxNode) -> TypeId {
+        let saved = std::mem::take(self.diagnostics);
+        let type_id = self.expr().check_expression(node);
+        *self.diagnostics = saved;
+        type_id
+    }
+
+    /// Returns the instantiations a generic FUNCTION or METHOD body is checked with.
+    ///
+    /// Every generic class is varied over its members while the other classes stay on
+    /// their first member. Returns an empty list for non-generic POUs.
+    pub(crate) fn generic_instantiations(&mut self, pou: SymbolId) -> Vec<GenericBindings> {
+        let Some(symbol) = self.symbols.get(pou) else {
+            return Vec::new();
+        };
+        let (name, range, kind) = (symbol.name.clone(), symbol.range, symbol.kind.clone());
+        let return_type = match &kind {
+            SymbolKind::Function { return_type, .. } => Some(*return_type),
+            SymbolKind::Method { return_type, .. } => *return_type,
+            _ => return Vec::new(),
+        };
+
+        let params = self.calls().callable_parameters(pou, &kind);
+        let mut classes = Vec::new();
+        let mut inferable = FxHashSet::default();
+        for param in params {
+            let Some(generic) = self.generic_class(param.type_id) else {
+                continue;
+            };
+            if !classes.contains(&generic) {
+                classes.push(generic);
+            }
+            if !matches!(param.direction, ParamDirection::Out) {
+                inferable.insert(generic);
+            }
+        }
+        if let Some(generic) = return_type.and_then(|ty| self.generic_class(ty)) {
+            if !inferable.contains(&generic) {
+                self.diagnostics.error(
+                    DiagnosticCode::InvalidReturnType,
+                    range,
+                    format!(
+                        "result type '{}' of '{}' must also be used by an input or in-out parameter",
+                        self.type_name(generic),
+                        name
+                    ),
+                );
+            }
+            if !classes.contains(&generic) {
+                classes.push(generic);
+            }
+        }
+
+        let members: Vec<(TypeId, Vec<TypeId>)> = classes
+            .into_iter()
+            .map(|generic| {
+                let types = ELEMENTARY_TYPES
+                    .iter()
+                    .copied()
+                    .filter(|ty| self.is_assignable(generic, *ty))
+                    .collect::<Vec<_>>();
+                (generic, types)
+            })
+            .filter(|(_, types)| !types.is_empty())
+            .collect();
+        if members.is_empty() {
+            return Vec::new();
+        }
+
+        let base: GenericBindings = members
+            .iter()
+            .map(|(generic, types)| (*generic, types[0]))
+            .collect();
+        let mut instantiations = vec![base.clone()];
+        for (generic, types) in &members {
+            for ty in types.iter().skip(1) {
+                let mut bindings = base.clone();
+                bindings.insert(*generic, *ty);
+                instantiations.push(bindings);
+            }
+        }
+        instantiations
+    }
+
+    /// Formats bindings as `ANY_NUM = REAL` for diagnostics.
+    pub(crate) fn describe_generic_bindings(&self, bindings: &GenericBindings) -> String {
+        let mut parts: Vec<String> = bindings
+            .iter()
+            .map(|(generic, ty)| format!("{} = {}", self.type_name(*generic), self.type_name(*ty)))
+            .collect();
+        parts.sort();
+        parts.join(", ")
+    }
+}
+
+impl<'a, 'b> CallChecker<'a, 'b> {
+    /// Binds the generic classes of a callee from its argument types.
+    ///
+    /// Typed arguments decide the binding; untyped literals only bind classes no typed
+    /// argument did. Classes with conflicting arguments stay unbound.
+    pub(super) fn infer_generic_bindings(
+        &mut self,
+        callee: SymbolId,
+        params: &[ParamInfo],
+        bound: &BoundArgs,
+    ) -> GenericBindings {
+        let mut bindings = GenericBindings::default();
+        let mut conflicts = FxHashSet::default();
+        if params
+            .iter()
+            .all(|param| self.checker.generic_class(param.type_id).is_none())
+        {
+            return bindings;
+        }
+
+        for literal_pass in [false, true] {
+            let typed: FxHashSet<TypeId> = bindings.keys().copied().collect();
+            for (param, arg) in params.iter().zip(bound.assigned.iter()) {
+                let Some(arg) = arg else {
+                    continue;
+                };
+                if matches!(param.direction, ParamDirection::Out) {
+                    continue;
+                }
+                let Some(generic) = self.checker.generic_class(param.type_id) else {
+                    continue;
+                };
+                let literal = is_untyped_int_literal_expr(&arg.expr)
+                    || is_untyped_real_literal_expr(&arg.expr);
+                if literal != literal_pass
+                    || conflicts.contains(&generic)
+                    || (literal_pass && typed.contains(&generic))
+                {
+                    continue;
+                }
+                if !self.bind_generic_argument(callee, generic, param, arg, &mut bindings) {
+                    bindings.remove(&generic);
+                    conflicts.insert(generic);
+                }
+            }
+        }
+        bindings
+    }
+
+    /// Folds one argument into the binding of `generic`; returns false on a conflict.
+    fn bind_generic_argument(
+        &mut self,
+        callee: SymbolId,
+        generic: TypeId,
+        param: &ParamInfo,
+        arg: &CallArg,
+        bindings: &mut GenericBindings,
+    ) -> bool {
+        let arg_type = self.checker.peek_expression_type(&arg.expr);
+        let arg_type = if generic == param.type_id {
+            arg_type
+        } else {
+            match self.checker.resolved_type(arg_type) {
+                Some(Type::Array { element, .. }) => *element,
+                _ => return true,
+            }
+        };
+        let arg_type = self.checker.resolve_subrange_base(arg_type);
+        if arg_type == TypeId::UNKNOWN {
+            return true;
+        }
+        // Non-members are reported against the generic parameter type.
+        if !self.checker.is_assignable(generic, arg_type) {
+            return false;
+        }
+
+        match bindings.get(&generic).copied() {
+            None => {
+                bindings.insert(generic, arg_type);
+                true
+            }
+            Some(current) if self.checker.is_assignable(current, arg_type) => true,
+            Some(current) if self.checker.is_assignable(arg_type, current) => {
+                bindings.insert(generic, arg_type);
+                true
+            }
+            Some(current) => {
+                let callee_name = self
+                    .checker
+                    .symbols
+                    .get(callee)
+                    .map(|symbol| symbol.name.clone())
+                    .unwrap_or_default();
+                self.checker.diagnostics.error(
+                    DiagnosticCode::InvalidArgumentType,
+                    arg.range,
+                    format!(
+                        "conflicting types for {} in call to '{}': '{}' and '{}'",
+                        self.checker.type_name(generic),
+                        callee_name,
+                        self.checker.type_name(current),
+                        self.checker.type_name(arg_type)
+                    ),
+                );
+                false
+            }
+        }
+    }
+}
@@ -88,6 +88,54 @@ impl<'a> TypeChecker<'a> {
         );
     }
 
+    /// Warns when a binary operand is implicitly promoted to `target` in a way
+    /// that mixes signedness or may lose precision.
+    pub(super) fn warn_operand_conversion(
+        &mut self,
+        operand: &SyntaxNode,
+        source: TypeId,
+        target: TypeId,
+    ) {
+        let source = self.resolve_subrange_base(source);
+        let target = self.resolve_subrange_base(target);
+        if source == target || source == TypeId::UNKNOWN || target == TypeId::UNKNOWN {
+            return;
+        }
+        if is_untyped_int_literal_expr(operand) || is_untyped_real_literal_expr(operand) {
+            return;
+        }
+        let (Some(source_ty), Some(target_ty)) = (
+            self.symbols.type_by_id(source),
+            self.symbols.type_by_id(target),
+        ) else {
+            return;
+        };
+
+        let (code, reason) = if (source_ty.is_signed() && target_ty.is_unsigned())
+            || (source_ty.is_unsigned() && target_ty.is_signed())
+        {
+            (
+                DiagnosticCode::SignedUnsignedMix,
+                "mixes signed and unsigned operands",
+            )
+        } else if is_lossy_numeric_promotion(source, target) {
+            (DiagnosticCode::LossyConversion, "may lose precision")
+        } else {
+            return;
+        };
+
+        self.diagnostics.warning(
+            code,
+            operand.text_range(),
+            format!(
+                "implicit conversion from '{}' to '{}' {}",
+                self.type_name(source),
+                self.type_name(target),
+                reason
+            ),
+        );
+    }
+
     fn is_string_family_implicit_ok(&self, target: TypeId, source: TypeId) -> bool {
         let Some(target_ty) = self.symbols.type_by_id(target) else {
             return false;
@@ -129,6 +177,39 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
+    /// Warns when a non-literal string value may not fit a `STRING[n]`/`WSTRING[n]` target.
+    pub(super) fn warn_string_truncation(
+        &mut self,
+        target_type: TypeId,
+        value: &SyntaxNode,
+        value_type: TypeId,
+    ) {
+        let Some((target_is_wide, max_len)) = self.string_max_len(target_type) else {
+            return;
+        };
+        if string_literal_info(value).is_some() {
+            return;
+        }
+        let source_len = match self.symbols.type_by_id(self.resolve_alias_type(value_type)) {
+            Some(Type::String { max_len }) if !target_is_wide => *max_len,
+            Some(Type::WString { max_len }) if target_is_wide => *max_len,
+            _ => return,
+        };
+        if source_len.is_some_and(|len| len <= max_len) {
+            return;
+        }
+        let target_name = self.type_name(target_type);
+        let value_name = self.type_name(value_type);
+        self.diagnostics.warning(
+            DiagnosticCode::StringTruncation,
+            value.text_range(),
+            format!(
+                "'{}' value may be truncated to {} characters when assigned to '{}'",
+                value_name, max_len, target_name
+            ),
+        );
+    }
+
     pub(super) fn string_max_len(&self, type_id: TypeId) -> Option<(bool, u32)> {
         let resolved = self.resolve_alias_type(type_id);
         match self.symbols.type_by_id(resolved)? {
@@ -166,3 +247,16 @@ pub(super) fn direct_address_type(text: &str) -> TypeId {
         None => TypeId::BOOL,
     }
 }
+
+/// Returns true when promoting an integer to a floating-point type cannot
+/// represent every value of the source exactly.
+fn is_lossy_numeric_promotion(source: TypeId, target: TypeId) -> bool {
+    match target {
+        TypeId::REAL => matches!(
+            source,
+            TypeId::DINT | TypeId::LINT | TypeId::UDINT | TypeId::ULINT
+        ),
+        TypeId::LREAL => matches!(source, TypeId::LINT | TypeId::ULINT),
+        _ => false,
+    }
+}
@@ -0,0 +1,185 @@
+//! Structured and array initializers in declarations.
+
+use super::*;
+
+impl<'a> TypeChecker<'a> {
+    /// Checks a declaration initializer against the declared type. Initializer lists
+    /// must name fields of a STRUCT, and each element value must be assignable to its
+    /// field or element type. Array initializers that do not fill the ARRAY exactly
+    /// produce a warning.
+    pub fn check_initializer(&mut self, target_type: TypeId, node: &SyntaxNode) {
+        match node.kind() {
+            SyntaxKind::InitializerList => self.check_initializer_list(target_type, node),
+            SyntaxKind::ArrayInitializer => self.check_array_initializer(target_type, node),
+            _ => self.check_initializer_value(target_type, node),
+        }
+    }
+
+    fn check_initializer_list(&mut self, target_type: TypeId, node: &SyntaxNode) {
+        let resolved = self.resolve_alias_type(target_type);
+        let fields = match self.symbols.type_by_id(resolved) {
+            Some(Type::Struct { fields, .. }) => fields.clone(),
+            Some(Type::Unknown) | None => return,
+            Some(_) => {
+                self.diagnostics.error(
+                    DiagnosticCode::TypeMismatch,
+                    node.text_range(),
+                    format!(
+                        "initializer list requires a STRUCT type, found '{}'",
+                        self.type_name(target_type)
+                    ),
+                );
+                return;
+            }
+        };
+
+        let mut seen = FxHashSet::default();
+        for entry in node
+            .children()
+            .filter(|child| child.kind() == SyntaxKind::FieldInitializer)
+        {
+            let Some(name_node) = entry
+                .children()
+                .find(|child| child.kind() == SyntaxKind::Name)
+            else {
+                continue;
+            };
+            let name = name_node.text().to_string();
+            let name = name.trim();
+            if !seen.insert(SmolStr::new(name.to_ascii_uppercase())) {
+                self.diagnostics.error(
+                    DiagnosticCode::DuplicateDeclaration,
+                    name_node.text_range(),
+                    format!("field '{name}' is initialized more than once"),
+                );
+                continue;
+            }
+            let Some(field) = fields
+                .iter()
+                .find(|field| field.name.eq_ignore_ascii_case(name))
+            else {
+                self.diagnostics.error(
+                    DiagnosticCode::CannotResolve,
+                    name_node.text_range(),
+                    format!(
+                        "no field '{name}' on struct '{}'",
+                        self.type_name(target_type)
+                    ),
+                );
+                continue;
+            };
+            if let Some(value) = entry
+                .children()
+                .find(|child| child.kind() != SyntaxKind::Name)
+            {
+                self.check_initializer(field.type_id, &value);
+            }
+        }
+    }
+
+    fn check_array_initializer(&mut self, target_type: TypeId, node: &SyntaxNode) {
+        let resolved = self.resolve_alias_type(target_type);
+        let (element, dimensions) = match self.symbols.type_by_id(resolved) {
+            Some(Type::Array {
+                element,
+                dimensions,
+            }) => (*element, dimensions.clone()),
+            Some(Type::Unknown) | None => return,
+            Some(_) => {
+                self.diagnostics.error(
+                    DiagnosticCode::TypeMismatch,
+                    node.text_range(),
+                    format!(
+                        "array initializer requires an ARRAY type, found '{}'",
+                        self.type_name(target_type)
+                    ),
+                );
+                return;
+            }
+        };
+
+        let count = self.check_array_elements(element, node.children());
+        let capacity = dimensions.iter().try_fold(1u64, |total, (lower, upper)| {
+            let len = u64::try_from(upper.checked_sub(*lower)?.checked_add(1)?).ok()?;
+            total.checked_mul(len)
+        });
+        let Some((count, capacity)) = count.zip(capacity) else {
+            return;
+        };
+        let message = if count > capacity {
+            format!(
+                "array initializer has {count} elements but '{}' holds {capacity}; excess values are ignored",
+                self.type_name(target_type)
+            )
+        } else if count < capacity {
+            format!(
+                "array initializer has {count} elements but '{}' holds {capacity}; remaining elements use default values",
+                self.type_name(target_type)
+            )
+        } else {
+            return;
+        };
+        self.diagnostics
+            .warning(DiagnosticCode::OutOfRange, node.text_range(), message);
+    }
+
+    /// Checks array initializer elements and returns how many elements they produce,
+    /// when the repetition counts are constant.
+    fn check_array_elements(
+        &mut self,
+        element: TypeId,
+        elements: impl Iterator<Item = SyntaxNode>,
+    ) -> Option<u64> {
+        let mut total = Some(0u64);
+        for child in elements {
+            let count = if child.kind() == SyntaxKind::RepeatInitializer {
+                let times = child
+                    .children()
+                    .next()
+                    .and_then(|count| self.eval_const_int_expr(&count))
+                    .and_then(|count| u64::try_from(count).ok());
+                // `n()` repeats one default element.
+                let inner = self
+                    .check_array_elements(element, child.children().skip(1))
+                    .map(|inner| inner.max(1));
+                times
+                    .zip(inner)
+                    .map(|(times, inner)| times.saturating_mul(inner))
+            } else {
+                self.check_initializer(element, &child);
+                Some(1)
+            };
+            total = total
+                .zip(count)
+                .map(|(total, count)| total.saturating_add(count));
+        }
+        total
+    }
+
+    fn check_initializer_value(&mut self, target_type: TypeId, value: &SyntaxNode) {
+        let value_type = self.check_expression(value);
+        if target_type == TypeId::UNKNOWN || value_type == TypeId::UNKNOWN {
+            return;
+        }
+        let is_context_int = self.is_contextual_int_literal(target_type, value);
+        let is_context_real = self.is_contextual_real_literal(target_type, value);
+        if self.is_assignable(target_type, value_type) || is_context_int || is_context_real {
+            let checked_type = if is_context_int || is_context_real {
+                target_type
+            } else {
+                value_type
+            };
+            self.stmt()
+                .check_subrange_assignment(target_type, value, checked_type);
+            self.check_string_literal_assignment(target_type, value, checked_type);
+        } else {
+            let target_name = self.type_name(target_type);
+            let value_name = self.type_name(value_type);
+            self.diagnostics.error(
+                DiagnosticCode::IncompatibleAssignment,
+                value.text_range(),
+                format!("cannot initialize '{}' with '{}'", target_name, value_name),
+            );
+        }
+    }
+}
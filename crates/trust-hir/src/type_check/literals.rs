@@ -12,10 +12,26 @@ pub(super) fn parse_int_literal_from_node(node: &SyntaxNode) -> Option<i64> {
         }
     }
 
-    node.descendants_with_tokens()
+    // Typed literals keep their sign inside the literal (`INT#-5`).
+    let mut negative = false;
+    for token in node
+        .descendants_with_tokens()
         .filter_map(|e| e.into_token())
-        .find(|token| token.kind() == SyntaxKind::IntLiteral)
-        .and_then(|token| parse_int_literal(token.text()).map(|info| info.value))
+    {
+        match token.kind() {
+            SyntaxKind::Minus => negative = true,
+            SyntaxKind::IntLiteral => {
+                let value = parse_int_literal(token.text())?.value;
+                return if negative {
+                    value.checked_neg()
+                } else {
+                    Some(value)
+                };
+            }
+            _ => {}
+        }
+    }
+    None
 }
 
 #[derive(Clone, Copy)]
@@ -18,14 +18,18 @@ mod calls;
 mod compatibility;
 mod const_eval;
 mod expr;
+mod generics;
 mod helpers;
+mod initializers;
 mod literals;
+mod null_safety;
 mod ops;
 mod standard;
 mod stmt;
 mod symbol_resolve;
 mod validation;
 
+pub(crate) use generics::GenericBindings;
 pub(crate) use literals::string_literal_info;
 pub use ops::{BinaryOp, UnaryOp};
 
@@ -42,6 +46,8 @@ pub struct TypeChecker<'a> {
     super_type: Option<TypeId>,
     loop_stack: Vec<LoopContext>,
     label_scopes: Vec<LabelScope>,
+    /// Concrete types for generic classes while checking a generic POU instantiation.
+    generic_bindings: GenericBindings,
 }
 
 pub(crate) struct ExprChecker<'a, 'b> {
@@ -112,6 +118,9 @@ impl<'a> TypeChecker<'a> {
 #[derive(Debug, Clone)]
 struct LoopContext {
     restricted: FxHashSet<SymbolId>,
+    /// FOR control variable with the first and last value it takes, when the
+    /// bounds are constant.
+    control_range: Option<(SymbolId, i64, i64)>,
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,263 @@
+//! Possible NULL dereference detection.
+//!
+//! A dereference `r^` of a REF_TO or POINTER variable is considered safe when it
+//! is guarded by a check such as `IF r <> NULL THEN`, follows an early exit such
+//! as `IF r = NULL THEN RETURN; END_IF`, or follows an assignment of `REF(...)` or
+//! `ADR(...)` in the same statement list. The analysis is syntactic: changes made
+//! through calls or other references are not tracked.
+
+use super::*;
+
+impl<'a> TypeChecker<'a> {
+    /// Warns when `deref` dereferences the variable `operand` without a NULL guard.
+    pub(super) fn check_null_dereference(&mut self, deref: &SyntaxNode, operand: &SyntaxNode) {
+        if operand.kind() != SyntaxKind::NameRef {
+            return;
+        }
+        let name = operand.text().to_string();
+        let name = name.trim();
+        if is_null_guarded(deref, name) {
+            return;
+        }
+        self.diagnostics.warning(
+            DiagnosticCode::PossibleNullDereference,
+            deref.text_range(),
+            format!("'{name}' may be NULL here; check '{name} <> NULL' before dereferencing"),
+        );
+    }
+}
+
+fn is_null_guarded(deref: &SyntaxNode, name: &str) -> bool {
+    let mut child = deref.clone();
+    while let Some(parent) = child.parent() {
+        if is_statement(child.kind()) {
+            if is_guarded_branch(&parent, name) {
+                return true;
+            }
+            if let Some(guarded) = guard_from_previous_statements(&child, name) {
+                return guarded;
+            }
+        }
+        if is_pou_boundary(parent.kind()) {
+            return false;
+        }
+        child = parent;
+    }
+    false
+}
+
+/// Whether statements directly inside `parent` run only when the conditions of
+/// the enclosing IF or WHILE imply `name <> NULL`.
+fn is_guarded_branch(parent: &SyntaxNode, name: &str) -> bool {
+    match parent.kind() {
+        SyntaxKind::IfStmt | SyntaxKind::WhileStmt => {
+            condition(parent).is_some_and(|cond| implies_non_null(&cond, name))
+        }
+        SyntaxKind::ElsifBranch => {
+            condition(parent).is_some_and(|cond| implies_non_null(&cond, name))
+                || earlier_conditions(parent).any(|cond| implies_null(&cond, name))
+        }
+        SyntaxKind::ElseBranch => earlier_conditions(parent).any(|cond| implies_null(&cond, name)),
+        _ => false,
+    }
+}
+
+/// Scans the statements before `statement` for the most recent one that decides
+/// whether `name` is NULL.
+fn guard_from_previous_statements(statement: &SyntaxNode, name: &str) -> Option<bool> {
+    let mut previous = statement.prev_sibling();
+    while let Some(node) = previous {
+        if let Some(value) = assigned_value(&node, name) {
+            return Some(is_ref_assignment(&node) || is_non_null_value(&value));
+        }
+        if node.kind() == SyntaxKind::IfStmt && is_early_exit_guard(&node, name) {
+            return Some(true);
+        }
+        if assigns_anywhere(&node, name) {
+            return Some(false);
+        }
+        previous = node.prev_sibling();
+    }
+    None
+}
+
+/// `IF name = NULL THEN ... RETURN; END_IF` with no other branches.
+fn is_early_exit_guard(if_stmt: &SyntaxNode, name: &str) -> bool {
+    if if_stmt.children().any(|child| is_branch(child.kind())) {
+        return false;
+    }
+    let Some(cond) = condition(if_stmt) else {
+        return false;
+    };
+    implies_null(&cond, name)
+        && if_stmt
+            .children()
+            .filter(|child| is_statement(child.kind()))
+            .last()
+            .is_some_and(|last| {
+                matches!(
+                    last.kind(),
+                    SyntaxKind::ReturnStmt
+                        | SyntaxKind::ExitStmt
+                        | SyntaxKind::ContinueStmt
+                        | SyntaxKind::JmpStmt
+                )
+            })
+}
+
+/// The value assigned to `name` when `node` is `name := value;`.
+fn assigned_value(node: &SyntaxNode, name: &str) -> Option<SyntaxNode> {
+    if node.kind() != SyntaxKind::AssignStmt {
+        return None;
+    }
+    let mut exprs = node.children();
+    let target = exprs.next()?;
+    if !is_name(&target, name) {
+        return None;
+    }
+    exprs.next()
+}
+
+/// `name REF= target;` always binds a reference.
+fn is_ref_assignment(node: &SyntaxNode) -> bool {
+    node.children_with_tokens()
+        .any(|element| element.kind() == SyntaxKind::RefAssign)
+}
+
+fn assigns_anywhere(node: &SyntaxNode, name: &str) -> bool {
+    node.descendants()
+        .filter(|node| node.kind() == SyntaxKind::AssignStmt)
+        .any(|assign| {
+            assign
+                .children()
+                .next()
+                .is_some_and(|target| is_name(&target, name))
+        })
+}
+
+fn is_non_null_value(value: &SyntaxNode) -> bool {
+    match value.kind() {
+        SyntaxKind::AddrExpr => true,
+        SyntaxKind::ParenExpr => value
+            .children()
+            .next()
+            .is_some_and(|inner| is_non_null_value(&inner)),
+        SyntaxKind::CallExpr => value.children().next().is_some_and(|callee| {
+            let callee = callee.text().to_string();
+            matches!(
+                callee.trim().to_ascii_uppercase().as_str(),
+                "REF" | "NEW" | "__NEW"
+            )
+        }),
+        _ => false,
+    }
+}
+
+/// `cond` can only be TRUE when `name` is not NULL.
+fn implies_non_null(cond: &SyntaxNode, name: &str) -> bool {
+    match cond.kind() {
+        SyntaxKind::ParenExpr => cond
+            .children()
+            .next()
+            .is_some_and(|inner| implies_non_null(&inner, name)),
+        SyntaxKind::BinaryExpr => match BinaryOp::from_node(cond) {
+            BinaryOp::Neq => compares_with_null(cond, name),
+            BinaryOp::And => cond.children().any(|side| implies_non_null(&side, name)),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// `cond` is TRUE whenever `name` is NULL, so `name` is not NULL when it is FALSE.
+fn implies_null(cond: &SyntaxNode, name: &str) -> bool {
+    match cond.kind() {
+        SyntaxKind::ParenExpr => cond
+            .children()
+            .next()
+            .is_some_and(|inner| implies_null(&inner, name)),
+        SyntaxKind::BinaryExpr => match BinaryOp::from_node(cond) {
+            BinaryOp::Eq => compares_with_null(cond, name),
+            BinaryOp::Or => cond.children().any(|side| implies_null(&side, name)),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn compares_with_null(binary: &SyntaxNode, name: &str) -> bool {
+    let sides: Vec<_> = binary.children().collect();
+    let [left, right] = sides.as_slice() else {
+        return false;
+    };
+    (is_name(left, name) && is_null_literal(right))
+        || (is_null_literal(left) && is_name(right, name))
+}
+
+fn is_null_literal(node: &SyntaxNode) -> bool {
+    node.kind() == SyntaxKind::Literal
+        && node
+            .children_with_tokens()
+            .any(|element| element.kind() == SyntaxKind::KwNull)
+}
+
+fn is_name(node: &SyntaxNode, name: &str) -> bool {
+    node.kind() == SyntaxKind::NameRef && node.text().to_string().trim().eq_ignore_ascii_case(name)
+}
+
+fn condition(node: &SyntaxNode) -> Option<SyntaxNode> {
+    node.children()
+        .find(|child| !is_statement(child.kind()) && !is_branch(child.kind()))
+}
+
+/// Conditions of the IF and ELSIF branches before `branch`.
+fn earlier_conditions(branch: &SyntaxNode) -> impl Iterator<Item = SyntaxNode> {
+    let mut conditions = Vec::new();
+    if let Some(if_stmt) = branch.parent() {
+        conditions.extend(condition(&if_stmt));
+        conditions.extend(
+            if_stmt
+                .children()
+                .take_while(|child| child != branch)
+                .filter(|child| child.kind() == SyntaxKind::ElsifBranch)
+                .filter_map(|elsif| condition(&elsif)),
+        );
+    }
+    conditions.into_iter()
+}
+
+fn is_branch(kind: SyntaxKind) -> bool {
+    matches!(kind, SyntaxKind::ElsifBranch | SyntaxKind::ElseBranch)
+}
+
+fn is_statement(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::AssignStmt
+            | SyntaxKind::IfStmt
+            | SyntaxKind::CaseStmt
+            | SyntaxKind::ForStmt
+            | SyntaxKind::WhileStmt
+            | SyntaxKind::RepeatStmt
+            | SyntaxKind::ReturnStmt
+            | SyntaxKind::ExitStmt
+            | SyntaxKind::ContinueStmt
+            | SyntaxKind::JmpStmt
+            | SyntaxKind::LabelStmt
+            | SyntaxKind::EmptyStmt
+            | SyntaxKind::ExprStmt
+    )
+}
+
+fn is_pou_boundary(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::Program
+            | SyntaxKind::Function
+            | SyntaxKind::FunctionBlock
+            | SyntaxKind::Class
+            | SyntaxKind::Method
+            | SyntaxKind::Action
+            | SyntaxKind::Property
+    )
+}
@@ -0,0 +1,52 @@
+use super::super::*;
+use super::helpers::builtin_param;
+
+impl<'a, 'b> StandardChecker<'a, 'b> {
+    /// `LOWER_BOUND(ARR, DIM)` / `UPPER_BOUND(ARR, DIM)` for fixed and `ARRAY[*]` arrays.
+    pub(in crate::type_check) fn infer_array_bound_call(&mut self, node: &SyntaxNode) -> TypeId {
+        let params = vec![
+            builtin_param("ARR", ParamDirection::In),
+            builtin_param("DIM", ParamDirection::In),
+        ];
+        let call = self.builtin_call(node, params);
+        call.check_formal_arg_count(self, node, 2);
+        if call.arg_count() != 2 {
+            return TypeId::UNKNOWN;
+        }
+        let Some((arg_arr, ty_arr)) = call.arg(0) else {
+            return TypeId::UNKNOWN;
+        };
+        let Some((arg_dim, ty_dim)) = call.arg(1) else {
+            return TypeId::UNKNOWN;
+        };
+        let rank = match self.checker.resolved_type(ty_arr) {
+            Some(Type::Array { dimensions, .. }) => dimensions.len(),
+            _ => {
+                self.checker.diagnostics.error(
+                    DiagnosticCode::InvalidArgumentType,
+                    arg_arr.range,
+                    "expected array input",
+                );
+                return TypeId::UNKNOWN;
+            }
+        };
+        if !self.is_integer_type(ty_dim) {
+            self.checker.diagnostics.error(
+                DiagnosticCode::InvalidArgumentType,
+                arg_dim.range,
+                "expected integer dimension",
+            );
+            return TypeId::UNKNOWN;
+        }
+        if let Some(dim) = self.checker.eval_const_int_expr(&arg_dim.expr) {
+            if dim < 1 || dim > rank as i64 {
+                self.checker.diagnostics.error(
+                    DiagnosticCode::OutOfRange,
+                    arg_dim.range,
+                    format!("dimension {} outside 1..{}", dim, rank),
+                );
+            }
+        }
+        TypeId::DINT
+    }
+}
@@ -81,4 +81,27 @@ impl<'a, 'b> StandardChecker<'a, 'b> {
         }
         self.base_type_id(ty)
     }
+
+    /// `BE_TO_HOST`, `HOST_TO_BE`, `LE_TO_HOST` and `HOST_TO_LE` keep the input type.
+    pub(in crate::type_check) fn infer_byte_order_call(&mut self, node: &SyntaxNode) -> TypeId {
+        let params = vec![builtin_param("IN", ParamDirection::In)];
+        let call = self.builtin_call(node, params);
+        call.check_formal_arg_count(self, node, 1);
+        if call.arg_count() != 1 {
+            return TypeId::UNKNOWN;
+        }
+        let Some((arg, ty)) = call.arg(0) else {
+            return TypeId::UNKNOWN;
+        };
+        let base = self.base_type_id(ty);
+        if base == TypeId::BOOL || !(self.is_numeric_type(ty) || self.is_bit_string_type(ty)) {
+            self.checker.diagnostics.error(
+                DiagnosticCode::InvalidArgumentType,
+                arg.range,
+                "expected integer, bit string or real input",
+            );
+            return TypeId::UNKNOWN;
+        }
+        base
+    }
 }
@@ -165,7 +165,7 @@ impl<'a, 'b> StandardChecker<'a, 'b> {
         }
 
         if let Some(dst_name) = upper.strip_prefix("TO_") {
-            let dst = TypeId::from_builtin_name(dst_name)?;
+            let dst = self.conversion_type_id(dst_name)?;
             let Some((arg, arg_type)) = self.collect_single_conversion_arg(node) else {
                 return Some(TypeId::UNKNOWN);
             };
@@ -185,8 +185,8 @@ impl<'a, 'b> StandardChecker<'a, 'b> {
         }
 
         if let Some((src_name, dst_name)) = upper.split_once("_TO_") {
-            let src = TypeId::from_builtin_name(src_name)?;
-            let dst = TypeId::from_builtin_name(dst_name)?;
+            let src = self.conversion_type_id(src_name)?;
+            let dst = self.conversion_type_id(dst_name)?;
             let Some((arg, arg_type)) = self.collect_single_conversion_arg(node) else {
                 return Some(TypeId::UNKNOWN);
             };
@@ -211,6 +211,19 @@ impl<'a, 'b> StandardChecker<'a, 'b> {
         None
     }
 
+    /// Resolves a type named in a `TO_<T>` or `<S>_TO_<T>` function: an elementary
+    /// type or a user-defined enumeration.
+    fn conversion_type_id(&self, name: &str) -> Option<TypeId> {
+        TypeId::from_builtin_name(name).or_else(|| {
+            let type_id = self.checker.symbols.lookup_type(name)?;
+            self.is_enum_type(type_id).then_some(type_id)
+        })
+    }
+
+    fn is_enum_type(&self, type_id: TypeId) -> bool {
+        matches!(self.checker.resolved_type(type_id), Some(Type::Enum { .. }))
+    }
+
     fn collect_single_conversion_arg(&mut self, node: &SyntaxNode) -> Option<(CallArg, TypeId)> {
         let params = vec![builtin_param("IN", ParamDirection::In)];
         let call = self.builtin_call(node, params);
@@ -733,6 +746,13 @@ impl<'a, 'b> StandardChecker<'a, 'b> {
             return true;
         }
 
+        // Enumerations convert to and from integers through their member values.
+        if (self.is_enum_type(src) && self.is_integer_type(dst))
+            || (self.is_integer_type(src) && self.is_enum_type(dst))
+        {
+            return true;
+        }
+
         if self.is_numeric_type(src) && self.is_numeric_type(dst) {
             return true;
         }
@@ -0,0 +1,63 @@
+use super::super::*;
+use super::helpers::{builtin_in_params, builtin_param};
+
+impl<'a, 'b> StandardChecker<'a, 'b> {
+    pub(in crate::type_check) fn infer_to_fix_call(&mut self, node: &SyntaxNode) -> TypeId {
+        let params = vec![builtin_param("IN", ParamDirection::In)];
+        let call = self.builtin_call(node, params);
+        call.check_formal_arg_count(self, node, 1);
+        if call.arg_count() != 1 {
+            return TypeId::UNKNOWN;
+        }
+        let Some((arg, arg_type)) = call.arg(0) else {
+            return TypeId::UNKNOWN;
+        };
+        if !self.is_numeric_type(arg_type) {
+            self.checker.diagnostics.error(
+                DiagnosticCode::InvalidArgumentType,
+                arg.range,
+                "expected numeric type",
+            );
+            return TypeId::UNKNOWN;
+        }
+        TypeId::DINT
+    }
+
+    /// Q16.16 functions whose inputs are raw DINT fixed-point values.
+    pub(in crate::type_check) fn infer_fixed_call(
+        &mut self,
+        node: &SyntaxNode,
+        name: &str,
+    ) -> TypeId {
+        let (params, result) = match name {
+            "FIX_ADD" | "FIX_SUB" | "FIX_MUL" | "FIX_DIV" => {
+                (builtin_in_params("IN", 1, 2), TypeId::DINT)
+            }
+            "FIX_TO_REAL" => (vec![builtin_param("IN", ParamDirection::In)], TypeId::REAL),
+            "FIX_TO_LREAL" => (vec![builtin_param("IN", ParamDirection::In)], TypeId::LREAL),
+            _ => (vec![builtin_param("IN", ParamDirection::In)], TypeId::DINT),
+        };
+        let expected = params.len();
+        let call = self.builtin_call(node, params);
+        call.check_formal_arg_count(self, node, expected);
+        if call.arg_count() != expected {
+            return TypeId::UNKNOWN;
+        }
+        let mut valid = true;
+        for (arg, arg_type) in call.args_from(0) {
+            if !self.checker.is_assignable(TypeId::DINT, arg_type) {
+                self.checker.diagnostics.error(
+                    DiagnosticCode::InvalidArgumentType,
+                    arg.range,
+                    "expected Q16.16 value (DINT)",
+                );
+                valid = false;
+            }
+        }
+        if valid {
+            result
+        } else {
+            TypeId::UNKNOWN
+        }
+    }
+}
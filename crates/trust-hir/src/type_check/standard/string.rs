@@ -122,7 +122,9 @@ impl<'a, 'b> StandardChecker<'a, 'b> {
         let params = builtin_in_params("IN", 1, arg_count);
         let call = self.builtin_call(node, params);
         let inputs = call.args_from(0);
+        // The result can be as long as all inputs together, so it has no declared length.
         self.common_string_type_for_args(&inputs)
+            .map(|common| self.normalize_string_type_id(common))
             .unwrap_or(TypeId::UNKNOWN)
     }
 
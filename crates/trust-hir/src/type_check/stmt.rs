@@ -109,6 +109,8 @@ impl<'a, 'b> StmtChecker<'a, 'b> {
             self.check_subrange_assignment(target_type, value, checked_type);
             self.checker
                 .check_string_literal_assignment(target_type, value, checked_type);
+            self.checker
+                .warn_string_truncation(target_type, value, checked_type);
             if !is_context_int && !is_context_real {
                 self.checker
                     .warn_implicit_conversion(target_type, value_type, node.text_range());
@@ -240,7 +242,13 @@ impl<'a, 'b> StmtChecker<'a, 'b> {
             }
         }
 
-        self.checker.loop_stack.push(LoopContext { restricted });
+        let control_range = control_symbol
+            .zip(self.for_loop_range(&exprs))
+            .map(|(symbol_id, (first, last))| (symbol_id, first.min(last), first.max(last)));
+        self.checker.loop_stack.push(LoopContext {
+            restricted,
+            control_range,
+        });
         self.check_statement_children(node);
         self.checker.loop_stack.pop();
     }
@@ -256,11 +264,29 @@ impl<'a, 'b> StmtChecker<'a, 'b> {
 
         self.checker.loop_stack.push(LoopContext {
             restricted: FxHashSet::default(),
+            control_range: None,
         });
         self.check_statement_children(node);
         self.checker.loop_stack.pop();
     }
 
+    /// First and last value of a FOR loop with constant bounds and step, or `None`
+    /// when a bound is not constant or the body never runs.
+    fn for_loop_range(&self, exprs: &[SyntaxNode]) -> Option<(i64, i64)> {
+        let first = i128::from(self.checker.eval_const_int_expr(exprs.first()?)?);
+        let end = i128::from(self.checker.eval_const_int_expr(exprs.get(1)?)?);
+        let step = match exprs.get(2) {
+            Some(expr) => i128::from(self.checker.eval_const_int_expr(expr)?),
+            None => 1,
+        };
+        let span = end - first;
+        if step == 0 || span.signum() * step.signum() < 0 {
+            return None;
+        }
+        let last = first + span / step * step;
+        Some((i64::try_from(first).ok()?, i64::try_from(last).ok()?))
+    }
+
     fn check_repeat_stmt(&mut self, node: &SyntaxNode) {
         // Check UNTIL condition is boolean
         if let Some(expr) = last_expression_child(node) {
@@ -272,6 +298,7 @@ impl<'a, 'b> StmtChecker<'a, 'b> {
 
         self.checker.loop_stack.push(LoopContext {
             restricted: FxHashSet::default(),
+            control_range: None,
         });
         self.check_statement_children(node);
         self.checker.loop_stack.pop();
@@ -303,12 +330,28 @@ impl<'a, 'b> StmtChecker<'a, 'b> {
         let has_else = node
             .children()
             .any(|child| child.kind() == SyntaxKind::ElseBranch);
-        if !has_else && !self.case_labels_cover_enum(selector_type, &tracker) {
-            self.checker.diagnostics.warning(
-                DiagnosticCode::MissingElse,
-                node.text_range(),
-                "CASE statement has no ELSE branch",
-            );
+        if has_else {
+            return;
+        }
+        match self.missing_enum_values(selector_type, &tracker) {
+            Some((_, missing)) if missing.is_empty() => {}
+            Some((enum_name, missing)) => {
+                self.checker.diagnostics.warning(
+                    DiagnosticCode::NonExhaustiveCase,
+                    node.text_range(),
+                    format!(
+                        "CASE over '{enum_name}' has no ELSE branch and does not handle {}",
+                        missing.join(", ")
+                    ),
+                );
+            }
+            None => {
+                self.checker.diagnostics.warning(
+                    DiagnosticCode::MissingElse,
+                    node.text_range(),
+                    "CASE statement has no ELSE branch",
+                );
+            }
         }
     }
 
@@ -586,15 +629,27 @@ impl<'a, 'b> StmtChecker<'a, 'b> {
         )
     }
 
-    fn case_labels_cover_enum(&self, selector_type: TypeId, tracker: &CaseLabelTracker) -> bool {
+    /// Enum name and the values no CASE label covers, or `None` when the selector
+    /// is not an enumeration.
+    fn missing_enum_values(
+        &self,
+        selector_type: TypeId,
+        tracker: &CaseLabelTracker,
+    ) -> Option<(SmolStr, Vec<SmolStr>)> {
         let resolved = self.checker.resolve_alias_type(selector_type);
-        let Some(Type::Enum { values, .. }) = self.checker.symbols.type_by_id(resolved) else {
-            return false;
+        let Some(Type::Enum { name, values, .. }) = self.checker.symbols.type_by_id(resolved)
+        else {
+            return None;
         };
         if values.is_empty() {
-            return false;
+            return None;
         }
-        values.iter().all(|(_, value)| tracker.covers(*value))
+        let missing = values
+            .iter()
+            .filter(|(_, value)| !tracker.covers(*value))
+            .map(|(value_name, _)| value_name.clone())
+            .collect();
+        Some((name.clone(), missing))
     }
 
     fn check_return_stmt(&mut self, node: &SyntaxNode) {
@@ -791,7 +846,7 @@ impl<'a, 'b> StmtChecker<'a, 'b> {
         }
     }
 
-    fn check_subrange_assignment(
+    pub(super) fn check_subrange_assignment(
         &mut self,
         target_type: TypeId,
         value: &SyntaxNode,
@@ -119,7 +119,26 @@ impl<'a> TypeChecker<'a> {
         };
 
         match symbol.kind {
-            SymbolKind::Variable { .. } => true,
+            SymbolKind::Variable { .. } => {
+                let Some(address) = symbol.direct_address.as_deref() else {
+                    return true;
+                };
+                if !address
+                    .get(..2)
+                    .is_some_and(|area| area.eq_ignore_ascii_case("%I"))
+                {
+                    return true;
+                }
+                self.diagnostics.error(
+                    DiagnosticCode::InvalidAssignmentTarget,
+                    node.text_range(),
+                    format!(
+                        "cannot assign to input-mapped variable '{}' ({address})",
+                        symbol.name
+                    ),
+                );
+                false
+            }
             SymbolKind::Parameter {
                 direction: ParamDirection::Out | ParamDirection::InOut,
             } => true,
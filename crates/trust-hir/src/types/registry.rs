@@ -10,6 +10,8 @@ pub struct TypeRegistry {
     types: FxHashMap<TypeId, Type>,
     /// Name to type ID lookup.
     names: FxHashMap<SmolStr, TypeId>,
+    /// Re-exported qualified names (`USING PUBLIC`) to declared names, uppercase.
+    qualified_aliases: FxHashMap<SmolStr, SmolStr>,
     /// Next type ID to assign.
     next_id: u32,
 }
@@ -21,6 +23,7 @@ impl TypeRegistry {
         let mut registry = Self {
             types: FxHashMap::default(),
             names: FxHashMap::default(),
+            qualified_aliases: FxHashMap::default(),
             next_id: TypeId::USER_TYPES_START,
         };
 
@@ -162,8 +165,54 @@ impl TypeRegistry {
         self.names
             .get(name)
             .or_else(|| self.names.get(&SmolStr::new(name.to_uppercase())))
+            .or_else(|| {
+                self.resolve_qualified_alias(name)
+                    .and_then(|target| self.names.get(&target))
+            })
             .copied()
     }
+
+    /// Registers `alias` as another qualified name for the declared name `target`.
+    ///
+    /// Used for namespace re-exports; aliases only affect lookups, never [`Self::type_name`].
+    pub fn register_qualified_alias(&mut self, alias: &str, target: &str) {
+        let alias = SmolStr::new(alias.to_ascii_uppercase());
+        let target = SmolStr::new(target.to_ascii_uppercase());
+        if alias != target {
+            self.qualified_aliases.entry(alias).or_insert(target);
+        }
+    }
+
+    /// Namespaces whose members are re-exported through `namespace` (uppercase).
+    #[must_use]
+    pub fn reexported_namespaces(&self, namespace: &str) -> Vec<SmolStr> {
+        let prefix = format!("{}.", namespace.to_ascii_uppercase());
+        let mut namespaces = Vec::new();
+        for (alias, target) in &self.qualified_aliases {
+            let Some(member) = alias.strip_prefix(prefix.as_str()) else {
+                continue;
+            };
+            if member.contains('.') {
+                continue;
+            }
+            if let Some((target_namespace, _)) = target.rsplit_once('.') {
+                let target_namespace = SmolStr::new(target_namespace);
+                if !namespaces.contains(&target_namespace) {
+                    namespaces.push(target_namespace);
+                }
+            }
+        }
+        namespaces.sort();
+        namespaces
+    }
+
+    /// Resolves a re-exported qualified name to the declared name it stands for (uppercase).
+    #[must_use]
+    pub fn resolve_qualified_alias(&self, name: &str) -> Option<SmolStr> {
+        self.qualified_aliases
+            .get(&SmolStr::new(name.to_ascii_uppercase()))
+            .cloned()
+    }
 }
 
 #[cfg(test)]
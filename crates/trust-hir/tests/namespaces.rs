@@ -48,3 +48,191 @@ END_PROGRAM
 "#,
     );
 }
+
+#[test]
+fn dotted_namespace_nests_into_existing_namespace() {
+    check_no_errors(
+        r#"
+NAMESPACE Company
+FUNCTION Base : INT
+Base := INT#1;
+END_FUNCTION
+END_NAMESPACE
+
+NAMESPACE Company.Motion
+FUNCTION Speed : INT
+Speed := Company.Base() + INT#1;
+END_FUNCTION
+END_NAMESPACE
+
+USING Company.Motion;
+PROGRAM Main
+VAR
+    a : INT;
+    b : INT;
+END_VAR
+a := Company.Motion.Speed();
+b := Speed();
+END_PROGRAM
+"#,
+    );
+}
+
+const FACADE_LIBRARY: &str = r#"
+NAMESPACE Impl
+FUNCTION_BLOCK Axis
+VAR_OUTPUT
+    ready : BOOL;
+END_VAR
+ready := TRUE;
+END_FUNCTION_BLOCK
+
+FUNCTION Scale : INT
+VAR_INPUT
+    x : INT;
+END_VAR
+Scale := x * INT#2;
+END_FUNCTION
+
+NAMESPACE INTERNAL Detail
+FUNCTION Hidden : INT
+Hidden := INT#0;
+END_FUNCTION
+END_NAMESPACE
+END_NAMESPACE
+
+NAMESPACE Facade
+USING PUBLIC Impl;
+END_NAMESPACE
+"#;
+
+#[test]
+fn using_public_reexports_namespace_members() {
+    check_no_errors(&format!(
+        r#"{FACADE_LIBRARY}
+PROGRAM Main
+VAR
+    axis : Facade.Axis;
+    y : INT;
+END_VAR
+axis();
+y := Facade.Scale(INT#3);
+END_PROGRAM
+"#
+    ));
+    check_no_errors(&format!(
+        r#"{FACADE_LIBRARY}
+USING Facade;
+PROGRAM Main
+VAR
+    axis : Axis;
+    y : INT;
+END_VAR
+axis();
+y := Scale(INT#3);
+END_PROGRAM
+"#
+    ));
+}
+
+#[test]
+fn using_public_does_not_reexport_internal_members() {
+    let errors = check_errors(&format!(
+        r#"{FACADE_LIBRARY}
+PROGRAM Main
+VAR
+    y : INT;
+END_VAR
+y := Facade.Detail.Hidden();
+END_PROGRAM
+"#
+    ));
+    assert!(!errors.is_empty());
+}
+
+#[test]
+fn using_public_outside_namespace_is_error() {
+    check_has_error(
+        r#"
+NAMESPACE Lib
+FUNCTION Inc : INT
+Inc := INT#1;
+END_FUNCTION
+END_NAMESPACE
+
+USING PUBLIC Lib;
+PROGRAM Main
+END_PROGRAM
+"#,
+        DiagnosticCode::InvalidOperation,
+    );
+}
+
+#[test]
+fn using_imports_with_same_name_warn() {
+    let warnings = check_warnings(
+        r#"
+NAMESPACE A
+FUNCTION Foo : INT
+Foo := INT#1;
+END_FUNCTION
+END_NAMESPACE
+
+NAMESPACE B
+FUNCTION Foo : INT
+Foo := INT#2;
+END_FUNCTION
+END_NAMESPACE
+
+USING A;
+USING B;
+PROGRAM Main
+VAR
+    y : INT;
+END_VAR
+y := A.Foo();
+END_PROGRAM
+"#,
+    );
+    assert!(warnings.contains(&DiagnosticCode::AmbiguousUsingImport));
+}
+
+#[test]
+fn using_import_shadowed_by_local_declaration_warns() {
+    let warnings = check_warnings(
+        r#"
+NAMESPACE Lib
+FUNCTION Foo : INT
+Foo := INT#1;
+END_FUNCTION
+END_NAMESPACE
+
+PROGRAM Main
+USING Lib;
+VAR
+    Foo : INT;
+END_VAR
+Foo := Lib.Foo();
+END_PROGRAM
+"#,
+    );
+    assert!(warnings.contains(&DiagnosticCode::ShadowedUsingImport));
+    assert!(!warnings.contains(&DiagnosticCode::AmbiguousUsingImport));
+}
+
+#[test]
+fn using_same_symbol_through_facade_does_not_warn() {
+    let warnings = check_warnings(&format!(
+        r#"{FACADE_LIBRARY}
+USING Impl;
+USING Facade;
+PROGRAM Main
+VAR
+    y : INT;
+END_VAR
+y := Scale(INT#3);
+END_PROGRAM
+"#
+    ));
+    assert!(!warnings.contains(&DiagnosticCode::AmbiguousUsingImport));
+}
@@ -76,6 +76,77 @@ END_INTERFACE
     assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
 }
 
+#[test]
+fn test_interface_inherited_method_must_be_implemented() {
+    check_has_error(
+        r#"
+INTERFACE IBase
+    METHOD Start
+    END_METHOD
+END_INTERFACE
+
+INTERFACE IDerived EXTENDS IBase
+    METHOD Stop
+    END_METHOD
+END_INTERFACE
+
+CLASS Motor IMPLEMENTS IDerived
+    METHOD PUBLIC Stop
+    END_METHOD
+END_CLASS
+"#,
+        DiagnosticCode::InvalidOperation,
+    );
+}
+
+#[test]
+fn test_interface_default_method_is_optional_and_callable() {
+    check_no_errors(
+        r#"
+INTERFACE IBase
+    METHOD Scale : INT
+    VAR_INPUT
+        value : INT;
+    END_VAR
+        Scale := value * 2;
+    END_METHOD
+END_INTERFACE
+
+INTERFACE IDerived EXTENDS IBase
+    METHOD Stop
+    END_METHOD
+END_INTERFACE
+
+CLASS Motor IMPLEMENTS IDerived
+    METHOD PUBLIC Stop
+    END_METHOD
+END_CLASS
+
+PROGRAM Main
+VAR
+    motor : Motor;
+    result : INT;
+END_VAR
+result := motor.Scale(3);
+END_PROGRAM
+"#,
+    );
+}
+
+#[test]
+fn test_interface_default_method_body_is_type_checked() {
+    check_has_error(
+        r#"
+INTERFACE IBase
+    METHOD Scale : INT
+        Scale := TRUE;
+    END_METHOD
+END_INTERFACE
+"#,
+        DiagnosticCode::IncompatibleAssignment,
+    );
+}
+
 #[test]
 fn test_interface_extends_non_interface_error() {
     check_has_error(
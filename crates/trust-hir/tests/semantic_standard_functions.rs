@@ -151,6 +151,49 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_enum_conversion_functions() {
+    check_no_errors(
+        r#"
+TYPE
+    Color : (Red := 1, Green := 2, Blue := 4);
+END_TYPE
+
+PROGRAM Test
+VAR
+    c: Color;
+    i: INT;
+    d: DINT;
+END_VAR
+i := TO_INT(c);
+d := COLOR_TO_DINT(Color#Blue);
+c := TO_Color(i);
+c := INT_TO_COLOR(2);
+END_PROGRAM
+"#,
+    );
+}
+
+#[test]
+fn test_enum_conversion_rejects_non_integer_types() {
+    check_has_error(
+        r#"
+TYPE
+    Color : (Red, Green);
+END_TYPE
+
+PROGRAM Test
+VAR
+    c: Color;
+    r: REAL;
+END_VAR
+r := TO_REAL(c);
+END_PROGRAM
+"#,
+        DiagnosticCode::InvalidArgumentType,
+    );
+}
+
 #[test]
 fn test_using_directive_resolves_type() {
     check_no_errors(
@@ -453,3 +496,136 @@ END_PROGRAM
         DiagnosticCode::WrongArgumentCount,
     );
 }
+
+#[test]
+fn test_fixed_point_functions() {
+    check_no_errors(
+        r#"
+PROGRAM Test
+VAR
+    a: DINT;
+    b: DINT;
+    r: REAL;
+    lr: LREAL;
+END_VAR
+a := TO_FIX(REAL#1.5);
+b := FIX_MUL(a, TO_FIX(2));
+b := FIX_DIV(FIX_SUB(FIX_ADD(a, b), a), b);
+b := FIX_SQRT(FIX_ABS(b));
+a := FIX_TO_DINT(b);
+r := FIX_TO_REAL(b);
+lr := FIX_TO_LREAL(b);
+END_PROGRAM
+"#,
+    );
+}
+
+#[test]
+fn test_fixed_point_rejects_real_input() {
+    check_has_error(
+        r#"
+PROGRAM Test
+VAR
+    a: DINT;
+END_VAR
+a := FIX_ADD(REAL#1.0, 1);
+END_PROGRAM
+"#,
+        DiagnosticCode::InvalidArgumentType,
+    );
+}
+
+#[test]
+fn test_byte_order_functions() {
+    check_no_errors(
+        r#"
+PROGRAM Test
+VAR
+    w: WORD;
+    d: DWORD;
+    i: INT;
+    r: REAL;
+END_VAR
+w := BE_TO_HOST(w);
+d := HOST_TO_BE(d);
+i := LE_TO_HOST(i);
+r := HOST_TO_LE(BE_TO_HOST(r));
+END_PROGRAM
+"#,
+    );
+}
+
+#[test]
+fn test_byte_order_rejects_bool_input() {
+    check_has_error(
+        r#"
+PROGRAM Test
+VAR
+    b: BOOL;
+END_VAR
+b := BE_TO_HOST(b);
+END_PROGRAM
+"#,
+        DiagnosticCode::InvalidArgumentType,
+    );
+}
+
+#[test]
+fn test_array_bound_functions() {
+    check_no_errors(
+        r#"
+FUNCTION Sum : REAL
+VAR_INPUT
+    values : ARRAY[*] OF REAL;
+END_VAR
+VAR
+    i : DINT;
+END_VAR
+FOR i := LOWER_BOUND(values, 1) TO UPPER_BOUND(values, 1) DO
+    Sum := Sum + values[i];
+END_FOR;
+END_FUNCTION
+
+PROGRAM Test
+VAR
+    data : ARRAY[-3..3] OF REAL;
+    grid : ARRAY[1..2, 0..4] OF INT;
+    lo : DINT;
+    hi : DINT;
+    total : REAL;
+END_VAR
+lo := LOWER_BOUND(data, 1);
+hi := UPPER_BOUND(ARR := grid, DIM := 2);
+total := Sum(data);
+END_PROGRAM
+"#,
+    );
+}
+
+#[test]
+fn test_array_bound_rejects_invalid_dimension() {
+    check_has_error(
+        r#"
+PROGRAM Test
+VAR
+    data : ARRAY[0..3] OF INT;
+    hi : DINT;
+END_VAR
+hi := UPPER_BOUND(data, 2);
+END_PROGRAM
+"#,
+        DiagnosticCode::OutOfRange,
+    );
+    check_has_error(
+        r#"
+PROGRAM Test
+VAR
+    value : INT;
+    hi : DINT;
+END_VAR
+hi := UPPER_BOUND(value, 1);
+END_PROGRAM
+"#,
+        DiagnosticCode::InvalidArgumentType,
+    );
+}
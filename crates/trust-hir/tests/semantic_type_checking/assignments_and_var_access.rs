@@ -270,7 +270,7 @@ PROGRAM Test
     arr[4] := 1;
 END_PROGRAM
 "#,
-        DiagnosticCode::OutOfRange,
+        DiagnosticCode::ArrayIndexOutOfBounds,
     );
 }
 
@@ -287,7 +287,7 @@ PROGRAM Test
     arr[i] := 1;
 END_PROGRAM
 "#,
-        DiagnosticCode::OutOfRange,
+        DiagnosticCode::ArrayIndexOutOfBounds,
     );
 }
 
@@ -658,6 +658,36 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_assign_to_input_mapped_variable() {
+    check_has_error(
+        r#"
+PROGRAM Test
+VAR
+    start AT %IX0.0 : BOOL;
+END_VAR
+start := TRUE;
+END_PROGRAM
+"#,
+        DiagnosticCode::InvalidAssignmentTarget,
+    );
+}
+
+#[test]
+fn test_assign_to_output_mapped_variable_ok() {
+    check_no_errors(
+        r#"
+PROGRAM Test
+VAR
+    start AT %IX0.0 : BOOL;
+    motor AT %QX0.0 : BOOL;
+END_VAR
+motor := start;
+END_PROGRAM
+"#,
+    );
+}
+
 #[test]
 fn test_at_wildcard_var_config_mapping_ok() {
     check_no_errors(
@@ -277,6 +277,167 @@ END_PROGRAM
     assert!(warnings.contains(&DiagnosticCode::ImplicitConversion));
 }
 
+#[test]
+fn test_operand_conversion_warnings() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Test
+    VAR
+        i : INT;
+        u : UINT;
+        d : DINT;
+        r : REAL;
+        ok : BOOL;
+    END_VAR
+    u := u + i;
+    r := r * d;
+    ok := i < u;
+END_PROGRAM
+"#,
+    );
+    assert_eq!(
+        warnings
+            .iter()
+            .filter(|code| **code == DiagnosticCode::SignedUnsignedMix)
+            .count(),
+        2
+    );
+    assert!(warnings.contains(&DiagnosticCode::LossyConversion));
+}
+
+#[test]
+fn test_operand_conversion_ignores_literals_and_safe_promotion() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Test
+    VAR
+        i : INT;
+        d : DINT;
+        r : REAL;
+    END_VAR
+    d := d + i;
+    r := r * i + 1.5;
+    d := d * 2;
+END_PROGRAM
+"#,
+    );
+    assert!(!warnings.contains(&DiagnosticCode::SignedUnsignedMix));
+    assert!(!warnings.contains(&DiagnosticCode::LossyConversion));
+}
+
+#[test]
+fn test_constant_overflow_warning() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Test
+    VAR CONSTANT
+        Limit : INT := 30000;
+    END_VAR
+    VAR
+        i : INT;
+        u : USINT;
+    END_VAR
+    i := INT#32767 + INT#1;
+    i := Limit * INT#2;
+    u := USINT#0 - USINT#1;
+END_PROGRAM
+"#,
+    );
+    let overflows = warnings
+        .iter()
+        .filter(|code| **code == DiagnosticCode::ConstantOverflow)
+        .count();
+    assert_eq!(overflows, 3, "got: {:?}", warnings);
+}
+
+#[test]
+fn test_constant_overflow_ignores_untyped_literals_and_in_range_results() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Test
+    VAR CONSTANT
+        Limit : INT := 30000;
+    END_VAR
+    VAR
+        i : INT;
+        d : DINT;
+    END_VAR
+    d := Limit + 5000;
+    d := 32767 + 1;
+    i := INT#-100 + INT#32000;
+    i := Limit + i;
+END_PROGRAM
+"#,
+    );
+    assert!(
+        !warnings.contains(&DiagnosticCode::ConstantOverflow),
+        "got: {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn test_union_layout_warnings() {
+    let warnings = check_warnings(
+        r#"
+TYPE
+    Telegram : UNION
+        raw : DWORD;
+        bytes : ARRAY[0..3] OF BYTE;
+        low : WORD;
+        text : STRING[4];
+    END_UNION;
+END_TYPE
+"#,
+    );
+    let layouts = warnings
+        .iter()
+        .filter(|code| **code == DiagnosticCode::UnionLayoutMismatch)
+        .count();
+    assert_eq!(layouts, 2, "got: {:?}", warnings);
+}
+
+#[test]
+fn test_union_layout_accepts_matching_variants() {
+    let warnings = check_warnings(
+        r#"
+TYPE
+    Header : STRUCT
+        id : BYTE;
+        flags : BYTE;
+        length : WORD;
+    END_STRUCT;
+    Telegram : UNION
+        raw : DWORD;
+        bytes : ARRAY[0..3] OF BYTE;
+        header : Header;
+    END_UNION;
+END_TYPE
+"#,
+    );
+    assert!(
+        !warnings.contains(&DiagnosticCode::UnionLayoutMismatch),
+        "got: {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn test_bool_operand_in_arithmetic() {
+    check_has_error(
+        r#"
+PROGRAM Test
+    VAR
+        flag : BOOL;
+        count : INT;
+    END_VAR
+    count := count + flag;
+END_PROGRAM
+"#,
+        DiagnosticCode::TypeMismatch,
+    );
+}
+
 #[test]
 fn test_cyclomatic_complexity_warning() {
     let mut body = String::new();
@@ -453,6 +614,45 @@ END_PROGRAM
     assert!(!warnings.contains(&DiagnosticCode::SharedGlobalTaskHazard));
 }
 
+#[test]
+fn test_duplicate_address_assignment_warning() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Main
+    VAR
+        Lamp AT %QX0.3 : BOOL;
+    END_VAR
+    Lamp := TRUE;
+END_PROGRAM
+
+FUNCTION_BLOCK Blinker
+    %Q0.3 := NOT %QX0.3;
+END_FUNCTION_BLOCK
+"#,
+    );
+    assert!(warnings.contains(&DiagnosticCode::DuplicateAddressAssignment));
+}
+
+#[test]
+fn test_duplicate_address_assignment_single_pou_no_warning() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Main
+    VAR
+        Sensor AT %IX0.0 : BOOL;
+        Backup AT %IX0.0 : BOOL;
+    END_VAR
+    IF Sensor THEN
+        %QX0.3 := TRUE;
+    ELSE
+        %QX0.3 := FALSE;
+    END_IF;
+END_PROGRAM
+"#,
+    );
+    assert!(!warnings.contains(&DiagnosticCode::DuplicateAddressAssignment));
+}
+
 #[test]
 fn test_used_function_no_unused_pou_warning() {
     let warnings = check_warnings(
@@ -103,6 +103,33 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_case_enum_missing_values_warning() {
+    let warnings = check_warnings(
+        r#"
+TYPE Mode : (Off, Manual, Auto)
+END_TYPE
+
+PROGRAM Test
+    VAR m : Mode; END_VAR
+    CASE m OF
+        Mode#Off: m := Mode#Manual;
+    END_CASE;
+END_PROGRAM
+"#,
+    );
+    assert!(
+        warnings.contains(&DiagnosticCode::NonExhaustiveCase),
+        "Expected NonExhaustiveCase warning, got: {:?}",
+        warnings
+    );
+    assert!(
+        !warnings.contains(&DiagnosticCode::MissingElse),
+        "Expected no MissingElse warning, got: {:?}",
+        warnings
+    );
+}
+
 #[test]
 fn test_case_enum_exhaustive_no_warning() {
     let warnings = check_warnings(
@@ -239,6 +266,47 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_for_loop_index_exceeds_array_bounds_warning() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Test
+    VAR i : INT; arr : ARRAY[0..9] OF INT; END_VAR
+    FOR i := 0 TO 10 DO
+        arr[i] := i;
+    END_FOR;
+END_PROGRAM
+"#,
+    );
+    assert!(
+        warnings.contains(&DiagnosticCode::LoopIndexOutOfBounds),
+        "Expected LoopIndexOutOfBounds warning, got: {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn test_for_loop_index_within_array_bounds_no_warning() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Test
+    VAR i : INT; arr : ARRAY[0..9] OF INT; END_VAR
+    FOR i := 9 TO 0 BY -1 DO
+        arr[i] := i;
+    END_FOR;
+    FOR i := 0 TO 10 BY 3 DO
+        arr[i] := i;
+    END_FOR;
+END_PROGRAM
+"#,
+    );
+    assert!(
+        !warnings.contains(&DiagnosticCode::LoopIndexOutOfBounds),
+        "Expected no LoopIndexOutOfBounds warning, got: {:?}",
+        warnings
+    );
+}
+
 #[test]
 fn test_formal_call_allows_missing_arguments() {
     check_no_errors(
@@ -279,6 +347,125 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_in_out_argument_must_be_assignable() {
+    check_has_error(
+        r#"
+FUNCTION_BLOCK Accumulate
+    VAR_IN_OUT
+        total : DINT;
+    END_VAR
+    total := total + 1;
+END_FUNCTION_BLOCK
+
+PROGRAM Test
+    VAR acc : Accumulate; a : DINT; END_VAR
+    acc(total := a + 1);
+END_PROGRAM
+"#,
+        DiagnosticCode::InvalidArgumentType,
+    );
+}
+
+const GENERIC_MAX2: &str = r#"
+FUNCTION MAX2 : ANY_NUM
+    VAR_INPUT
+        a : ANY_NUM;
+        b : ANY_NUM;
+    END_VAR
+    IF a > b THEN
+        MAX2 := a;
+    ELSE
+        MAX2 := b;
+    END_IF
+END_FUNCTION
+"#;
+
+#[test]
+fn test_generic_function_instantiated_per_call() {
+    let source = format!(
+        "{GENERIC_MAX2}{}",
+        r#"
+PROGRAM Test
+    VAR i : INT; d : DINT; r : REAL; END_VAR
+    i := MAX2(i, 3);
+    d := MAX2(i, d);
+    r := MAX2(r, 2.5);
+    r := MAX2(i, r);
+END_PROGRAM
+"#
+    );
+    check_no_errors(&source);
+}
+
+#[test]
+fn test_generic_function_rejects_non_member_argument() {
+    let source = format!(
+        "{GENERIC_MAX2}{}",
+        r#"
+PROGRAM Test
+    VAR flag : BOOL; END_VAR
+    flag := MAX2(flag, flag);
+END_PROGRAM
+"#
+    );
+    check_has_error(&source, DiagnosticCode::InvalidArgumentType);
+}
+
+#[test]
+fn test_generic_function_conflicting_arguments() {
+    let source = format!(
+        "{GENERIC_MAX2}{}",
+        r#"
+PROGRAM Test
+    VAR i : INT; u : UINT; END_VAR
+    i := MAX2(i, u);
+END_PROGRAM
+"#
+    );
+    check_has_error(&source, DiagnosticCode::InvalidArgumentType);
+}
+
+#[test]
+fn test_generic_function_reports_unsupported_instantiations() {
+    let source = r#"
+FUNCTION ToDint : DINT
+    VAR_INPUT
+        a : ANY_NUM;
+    END_VAR
+    ToDint := a;
+END_FUNCTION
+"#;
+    let mut db = Database::new();
+    let file = FileId(0);
+    db.set_source_text(file, source.to_string());
+    let diagnostics = db.diagnostics(file);
+    let errors: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.severity == DiagnosticSeverity::Error)
+        .collect();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    let message = &errors[0].message;
+    assert!(message.contains("not supported for"), "{message}");
+    assert!(message.contains("ANY_NUM = REAL"), "{message}");
+    assert!(!message.contains("ANY_NUM = INT"), "{message}");
+}
+
+#[test]
+fn test_generic_result_requires_generic_input() {
+    check_has_error(
+        r#"
+FUNCTION Zero : ANY_NUM
+    VAR_INPUT
+        n : INT;
+    END_VAR
+    Zero := n;
+END_FUNCTION
+"#,
+        DiagnosticCode::InvalidReturnType,
+    );
+}
+
 #[test]
 fn test_formal_call_duplicate_parameter_error() {
     check_has_error(
@@ -42,6 +42,97 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_structured_initializers() {
+    check_no_errors(
+        r#"
+TYPE
+    Position : STRUCT
+        x : REAL;
+        y : REAL;
+    END_STRUCT;
+    Waypoint : STRUCT
+        pos : Position;
+        speed : INT;
+    END_STRUCT;
+END_TYPE
+
+PROGRAM Test
+    VAR CONSTANT
+        Slow : INT := 5;
+    END_VAR
+    VAR
+        origin : Position := (x := 1.0, y := -2.0);
+        route : ARRAY[1..4] OF Waypoint := [
+            (pos := (x := 0.0, y := 0.0), speed := Slow),
+            2((speed := 10)),
+            (pos := (y := 5.0))
+        ];
+        limits : ARRAY[0..1, 0..2] OF INT := [3(1), 3(2)];
+    END_VAR
+END_PROGRAM
+"#,
+    );
+}
+
+#[test]
+fn test_structured_initializer_errors() {
+    let source = r#"
+TYPE
+    Position : STRUCT
+        x : REAL;
+        y : REAL;
+    END_STRUCT;
+END_TYPE
+
+PROGRAM Test
+    VAR
+        a : Position := (x := 1.0, z := 2.0);
+        b : Position := (x := 1.0, x := 2.0);
+        c : Position := (x := 'fast');
+        e : INT := (x := 1);
+    END_VAR
+END_PROGRAM
+"#;
+    let errors = check_errors(source);
+    assert!(
+        errors.contains(&DiagnosticCode::CannotResolve),
+        "{errors:?}"
+    );
+    assert!(
+        errors.contains(&DiagnosticCode::DuplicateDeclaration),
+        "{errors:?}"
+    );
+    assert!(
+        errors.contains(&DiagnosticCode::IncompatibleAssignment),
+        "{errors:?}"
+    );
+    assert!(errors.contains(&DiagnosticCode::TypeMismatch), "{errors:?}");
+}
+
+#[test]
+fn test_array_initializer_count_warnings() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Test
+    VAR
+        excess : ARRAY[1..3] OF INT := [1, 2, 2(0)];
+        short : ARRAY[1..3] OF INT := [1];
+        exact : ARRAY[0..1, 0..2] OF INT := [2(1, 2(0))];
+    END_VAR
+END_PROGRAM
+"#,
+    );
+    assert_eq!(
+        warnings
+            .iter()
+            .filter(|code| **code == DiagnosticCode::OutOfRange)
+            .count(),
+        2,
+        "{warnings:?}"
+    );
+}
+
 #[test]
 fn test_string_literal_length_in_assignment() {
     check_has_error(
@@ -70,6 +161,36 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_string_truncation_warning() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Test
+    VAR
+        short : STRING[5];
+        long : STRING[20];
+        tiny : STRING[3];
+        wide : WSTRING[4];
+        text : WSTRING;
+    END_VAR
+    short := long;
+    short := CONCAT(tiny, tiny);
+    long := short;
+    short := tiny;
+    short := 'ABC';
+    wide := text;
+END_PROGRAM
+"#,
+    );
+    assert_eq!(
+        warnings
+            .iter()
+            .filter(|code| **code == DiagnosticCode::StringTruncation)
+            .count(),
+        3
+    );
+}
+
 #[test]
 fn test_type_alias_numeric_ops() {
     check_no_errors(
@@ -144,6 +265,67 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_possible_null_dereference() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Reader
+    VAR_INPUT source : REF_TO INT; END_VAR
+    VAR_OUTPUT value : INT; END_VAR
+    VAR x : INT; r : REF_TO INT; END_VAR
+    value := source^;
+    IF source <> NULL OR x > 0 THEN
+        value := source^;
+    END_IF;
+    r := REF(x);
+    r := source;
+    r^ := 1;
+END_PROGRAM
+"#,
+    );
+    let nulls = warnings
+        .iter()
+        .filter(|code| **code == DiagnosticCode::PossibleNullDereference)
+        .count();
+    assert_eq!(nulls, 3, "got: {:?}", warnings);
+}
+
+#[test]
+fn test_null_checks_guard_dereference() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Reader
+    VAR_INPUT source : REF_TO INT; END_VAR
+    VAR_OUTPUT value : INT; END_VAR
+    VAR x : INT; r : REF_TO INT; p : POINTER TO INT; END_VAR
+    IF source <> NULL AND x > 0 THEN
+        value := source^;
+    ELSIF x < 0 THEN
+        value := 0;
+    END_IF;
+    IF source = NULL THEN
+        value := 0;
+    ELSE
+        value := source^;
+    END_IF;
+    r := REF(x);
+    r^ := 1;
+    p := ADR(x);
+    p^ := 2;
+    IF source = NULL THEN
+        RETURN;
+    END_IF;
+    value := source^;
+END_PROGRAM
+"#,
+    );
+    assert!(
+        !warnings.contains(&DiagnosticCode::PossibleNullDereference),
+        "got: {:?}",
+        warnings
+    );
+}
+
 #[test]
 fn test_ref_requires_lvalue() {
     check_has_error(
@@ -270,3 +452,66 @@ END_PROGRAM
 "#,
     );
 }
+
+#[test]
+fn test_integer_bit_access() {
+    check_no_errors(
+        r#"
+PROGRAM Test
+    VAR status : WORD; code : INT; count : ULINT; flag : BOOL; END_VAR
+    status.5 := TRUE;
+    flag := code.15;
+    code.%X0 := flag;
+    flag := count.63;
+END_PROGRAM
+"#,
+    );
+}
+
+#[test]
+fn test_integer_bit_access_is_deprecated() {
+    let warnings = check_warnings(
+        r#"
+PROGRAM Test
+    VAR status : WORD; code : INT; flag : BOOL; END_VAR
+    status.5 := TRUE;
+    flag := code.15;
+    code.%X0 := flag;
+END_PROGRAM
+"#,
+    );
+    assert_eq!(
+        warnings
+            .iter()
+            .filter(|code| **code == DiagnosticCode::Deprecated)
+            .count(),
+        2,
+        "{warnings:?}"
+    );
+}
+
+#[test]
+fn test_bit_access_index_out_of_range() {
+    check_has_error(
+        r#"
+PROGRAM Test
+    VAR code : INT; flag : BOOL; END_VAR
+    flag := code.16;
+END_PROGRAM
+"#,
+        DiagnosticCode::OutOfRange,
+    );
+}
+
+#[test]
+fn test_bit_access_requires_integer_or_bit_string() {
+    check_has_error(
+        r#"
+PROGRAM Test
+    VAR value : REAL; flag : BOOL; END_VAR
+    flag := value.3;
+END_PROGRAM
+"#,
+        DiagnosticCode::TypeMismatch,
+    );
+}
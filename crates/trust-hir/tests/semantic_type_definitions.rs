@@ -339,6 +339,125 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_abstract_method_body_error() {
+    check_has_error(
+        r#"
+CLASS ABSTRACT Base
+METHOD PUBLIC ABSTRACT DoIt : INT
+DoIt := 1;
+END_METHOD
+END_CLASS
+"#,
+        DiagnosticCode::InvalidOperation,
+    );
+}
+
+#[test]
+fn test_super_call_to_abstract_method_error() {
+    check_has_error(
+        r#"
+CLASS ABSTRACT Base
+METHOD PUBLIC ABSTRACT DoIt
+END_METHOD
+END_CLASS
+
+CLASS Derived EXTENDS Base
+METHOD PUBLIC OVERRIDE DoIt
+SUPER.DoIt();
+END_METHOD
+END_CLASS
+"#,
+        DiagnosticCode::InvalidOperation,
+    );
+}
+
+#[test]
+fn test_function_block_override_without_keyword_warns() {
+    let source = r#"
+FUNCTION_BLOCK Base
+METHOD PUBLIC DoIt
+END_METHOD
+END_FUNCTION_BLOCK
+
+FUNCTION_BLOCK Derived EXTENDS Base
+METHOD PUBLIC DoIt
+END_METHOD
+END_FUNCTION_BLOCK
+"#;
+    check_no_errors(source);
+    assert!(check_warnings(source).contains(&DiagnosticCode::MissingOverride));
+}
+
+#[test]
+fn test_function_block_override_final_method_error() {
+    check_has_error(
+        r#"
+FUNCTION_BLOCK Base
+METHOD PUBLIC FINAL DoIt
+END_METHOD
+END_FUNCTION_BLOCK
+
+FUNCTION_BLOCK Derived EXTENDS Base
+METHOD PUBLIC OVERRIDE DoIt
+END_METHOD
+END_FUNCTION_BLOCK
+"#,
+        DiagnosticCode::InvalidOperation,
+    );
+}
+
+#[test]
+fn test_abstract_function_block_rules() {
+    check_has_error(
+        r#"
+FUNCTION_BLOCK ABSTRACT Base
+METHOD PUBLIC ABSTRACT DoIt
+END_METHOD
+END_FUNCTION_BLOCK
+
+FUNCTION_BLOCK Derived EXTENDS Base
+END_FUNCTION_BLOCK
+"#,
+        DiagnosticCode::InvalidOperation,
+    );
+    check_has_error(
+        r#"
+FUNCTION_BLOCK ABSTRACT Base
+METHOD PUBLIC ABSTRACT DoIt
+END_METHOD
+END_FUNCTION_BLOCK
+
+PROGRAM Test
+VAR
+    x: Base;
+END_VAR
+END_PROGRAM
+"#,
+        DiagnosticCode::InvalidOperation,
+    );
+    check_no_errors(
+        r#"
+FUNCTION_BLOCK ABSTRACT Base
+METHOD PUBLIC ABSTRACT DoIt
+END_METHOD
+END_FUNCTION_BLOCK
+
+FUNCTION_BLOCK Derived EXTENDS Base
+METHOD PUBLIC OVERRIDE DoIt
+END_METHOD
+END_FUNCTION_BLOCK
+
+PROGRAM Test
+VAR
+    x: Derived;
+END_VAR
+x.DoIt();
+END_PROGRAM
+"#,
+    );
+}
+
 #[test]
 fn test_inherited_variable_name_conflict_error() {
     check_has_error(
@@ -8,15 +8,22 @@ use text_size::{TextRange, TextSize};
 
 use trust_hir::db::SemanticDatabase;
 use trust_hir::symbols::{ParamDirection, ScopeId, SymbolId, SymbolTable, Visibility};
-use trust_hir::{Database, SymbolKind, Type, TypeId};
+use trust_hir::{Database, SourceDatabase, SymbolKind, Type, TypeId};
+use trust_syntax::dialect::IEC;
 use trust_syntax::syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
+use trust_syntax::{Dialect, DialectExtension};
 
+use crate::expr_completion::{
+    complete_request, parse_expression_prefix, ExprCompletionRequest, ExprCompletionTarget,
+    ExprMember, ExprScope, ExprShape,
+};
 use crate::signature_help::call_signature_context;
 use crate::stdlib_docs::{self, StdlibFilter};
 use crate::util::{
     is_member_symbol_kind, namespace_path_for_symbol, scope_at_position, type_detail,
     using_path_for_symbol, IdeContext, SymbolFilter,
 };
+use crate::var_decl::var_decl_info_for_symbol;
 
 /// The kind of completion item.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -359,6 +366,17 @@ pub fn complete_with_filter(
     file_id: trust_hir::db::FileId,
     position: TextSize,
     stdlib_filter: &StdlibFilter,
+) -> Vec<CompletionItem> {
+    complete_with_dialect(db, file_id, position, stdlib_filter, &IEC)
+}
+
+/// Computes completions with stdlib filtering and vendor dialect keywords.
+pub fn complete_with_dialect(
+    db: &Database,
+    file_id: trust_hir::db::FileId,
+    position: TextSize,
+    stdlib_filter: &StdlibFilter,
+    dialect: &Dialect,
 ) -> Vec<CompletionItem> {
     let mut items = Vec::new();
 
@@ -370,12 +388,19 @@ pub fn complete_with_filter(
     let typed_literal_context = typed_literal_completion_context(&context, position);
     let scope_id = context.scope_at_position(position);
 
+    // Field names of the struct being initialized in a declaration.
+    let initializer_fields = initializer_field_completions(root, symbols, scope_id, position);
+    if !initializer_fields.is_empty() {
+        return initializer_fields;
+    }
+
     match detect {
         CompletionContext::TopLevel => {
             items.extend(keyword_snippets());
         }
         CompletionContext::Statement => {
             items.extend(keyword_snippets());
+            items.extend(dialect_statement_keywords(dialect));
             items.extend(symbols_in_scope(&filter, scope_id, stdlib_filter));
             items.extend(standard_function_completions(stdlib_filter));
             items.extend(typed_literal_completions_with_context(
@@ -383,7 +408,7 @@ pub fn complete_with_filter(
             ));
         }
         CompletionContext::MemberAccess => {
-            items.extend(member_access_completions(
+            let members = member_access_completions(
                 db,
                 file_id,
                 position,
@@ -391,10 +416,23 @@ pub fn complete_with_filter(
                 symbols,
                 scope_id,
                 stdlib_filter,
-            ));
+            );
+            if members.is_empty() {
+                // Incomplete access chains (e.g. `a.b[i, j].`) may not type-check yet;
+                // fall back to walking the declared types along the typed path.
+                items.extend(expression_path_completions(
+                    &context.source,
+                    position,
+                    symbols,
+                    scope_id,
+                ));
+            } else {
+                items.extend(members);
+            }
         }
         CompletionContext::TypeAnnotation => {
             items.extend(type_keywords());
+            items.extend(dialect_type_keywords(dialect));
             items.extend(type_symbols(&filter));
         }
         CompletionContext::VarBlock => {
@@ -413,6 +451,7 @@ pub fn complete_with_filter(
         _ => {
             // General: include keywords and symbols
             items.extend(keyword_snippets());
+            items.extend(dialect_statement_keywords(dialect));
             items.extend(expression_keywords());
             items.extend(symbols_in_scope(&filter, scope_id, stdlib_filter));
             items.extend(standard_function_completions(stdlib_filter));
@@ -422,12 +461,283 @@ pub fn complete_with_filter(
         }
     }
 
+    if matches!(
+        detect,
+        CompletionContext::Statement | CompletionContext::Argument | CompletionContext::General
+    ) {
+        items.extend(expression_path_completions(
+            &context.source,
+            position,
+            symbols,
+            scope_id,
+        ));
+    }
+
     // Sort by priority
     items.sort_by_key(|item| item.sort_priority);
     items = dedupe_items(items);
     items
 }
 
+/// Expression path scope backed by declared types in the symbol table.
+struct DeclaredTypeScope<'a> {
+    symbols: &'a SymbolTable,
+    scope_id: ScopeId,
+}
+
+impl ExprScope for DeclaredTypeScope<'_> {
+    type Node = TypeId;
+
+    fn roots(&self) -> Vec<ExprMember<TypeId>> {
+        // Root names are completed by the scope-aware symbol completions.
+        Vec::new()
+    }
+
+    fn root(&self, name: &str) -> Option<ExprMember<TypeId>> {
+        let symbol = self
+            .symbols
+            .get(self.symbols.resolve(name, self.scope_id)?)?;
+        Some(ExprMember {
+            name: symbol.name.clone(),
+            detail: type_detail(self.symbols, symbol.type_id),
+            node: symbol.type_id,
+        })
+    }
+
+    fn shape(&self, node: &TypeId) -> ExprShape<TypeId> {
+        let type_id = self.symbols.resolve_alias_type(*node);
+        let member = |name: &SmolStr, type_id: TypeId| ExprMember {
+            name: name.clone(),
+            detail: type_detail(self.symbols, type_id),
+            node: type_id,
+        };
+        match self.symbols.type_by_id(type_id) {
+            Some(Type::Struct { fields, .. }) => ExprShape::Members(
+                fields
+                    .iter()
+                    .map(|field| member(&field.name, field.type_id))
+                    .collect(),
+            ),
+            Some(Type::Union { variants, .. }) => ExprShape::Members(
+                variants
+                    .iter()
+                    .map(|variant| member(&variant.name, variant.type_id))
+                    .collect(),
+            ),
+            Some(Type::Array {
+                element,
+                dimensions,
+            }) => ExprShape::Array {
+                dimensions: dimensions.clone(),
+                element: *element,
+                element_detail: type_detail(self.symbols, *element),
+            },
+            Some(Type::FunctionBlock { .. } | Type::Class { .. }) => {
+                let filter = SymbolFilter::new(self.symbols);
+                let Some(owner_id) = filter.owner_for_type(type_id) else {
+                    return ExprShape::Leaf;
+                };
+                ExprShape::Members(
+                    filter
+                        .members_in_hierarchy(owner_id, |symbol| {
+                            matches!(
+                                symbol.kind,
+                                SymbolKind::Variable { .. } | SymbolKind::Property { .. }
+                            )
+                        })
+                        .into_iter()
+                        .map(|symbol| member(&symbol.name, symbol.type_id))
+                        .collect(),
+                )
+            }
+            _ => ExprShape::Leaf,
+        }
+    }
+}
+
+fn expression_request_at(source: &str, position: TextSize) -> Option<ExprCompletionRequest> {
+    let end = usize::from(position).min(source.len());
+    let line_start = source[..end].rfind('\n').map_or(0, |idx| idx + 1);
+    let statement_start = source[line_start..end]
+        .rfind(';')
+        .map_or(line_start, |idx| line_start + idx + 1);
+    parse_expression_prefix(source.get(statement_start..end)?)
+}
+
+/// Completes struct members and array subscripts by walking the typed access path.
+fn expression_path_completions(
+    source: &str,
+    position: TextSize,
+    symbols: &SymbolTable,
+    scope_id: ScopeId,
+) -> Vec<CompletionItem> {
+    let Some(request) = expression_request_at(source, position) else {
+        return Vec::new();
+    };
+    if request.target == ExprCompletionTarget::Root {
+        return Vec::new();
+    }
+    complete_request(&DeclaredTypeScope { symbols, scope_id }, &request)
+}
+
+/// An open `(` or `[` inside a declaration initializer.
+struct InitializerFrame {
+    type_id: TypeId,
+    /// Whether the frame lists array elements rather than struct fields.
+    elements: bool,
+    /// Field currently being initialized in a struct frame.
+    field: Option<SmolStr>,
+    used: FxHashSet<SmolStr>,
+}
+
+/// Completes field names inside a structured initializer such as
+/// `pos : Position := (x := 1.0, |)`, following nested fields and array elements.
+fn initializer_field_completions(
+    root: &SyntaxNode,
+    symbols: &SymbolTable,
+    scope_id: ScopeId,
+    position: TextSize,
+) -> Vec<CompletionItem> {
+    let Some(token) = root.token_at_offset(position).left_biased() else {
+        return Vec::new();
+    };
+    let Some(var_decl) = token
+        .parent_ancestors()
+        .find(|node| node.kind() == SyntaxKind::VarDecl)
+    else {
+        return Vec::new();
+    };
+    let Some(name) = var_decl
+        .children()
+        .find(|child| child.kind() == SyntaxKind::Name)
+    else {
+        return Vec::new();
+    };
+    let Some(declared) = symbols
+        .resolve(name.text().to_string().trim(), scope_id)
+        .and_then(|id| symbols.get(id))
+    else {
+        return Vec::new();
+    };
+
+    let mut tokens: Vec<SyntaxToken> = var_decl
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !is_trivia(token.kind()) && token.text_range().end() <= position)
+        .collect();
+    // A partially typed field name does not count as written yet.
+    if tokens.last().is_some_and(|token| {
+        token.kind() == SyntaxKind::Ident && token.text_range().end() == position
+    }) {
+        tokens.pop();
+    }
+    let Some(assign) = tokens
+        .iter()
+        .position(|token| token.kind() == SyntaxKind::Assign)
+    else {
+        return Vec::new();
+    };
+
+    let mut frames: Vec<InitializerFrame> = Vec::new();
+    let mut prev_kind = SyntaxKind::Assign;
+    for (idx, token) in tokens.iter().enumerate().skip(assign + 1) {
+        let expected = match frames.last() {
+            None => Some(declared.type_id),
+            Some(frame) if frame.elements => Some(frame.type_id),
+            Some(frame) => frame
+                .field
+                .as_ref()
+                .and_then(|field| struct_field_type(symbols, frame.type_id, field)),
+        };
+        match token.kind() {
+            SyntaxKind::LBracket => {
+                let element = expected.and_then(|type_id| {
+                    match symbols.type_by_id(symbols.resolve_alias_type(type_id)) {
+                        Some(Type::Array { element, .. }) => Some(*element),
+                        _ => None,
+                    }
+                });
+                frames.push(InitializerFrame {
+                    type_id: element.unwrap_or(TypeId::UNKNOWN),
+                    elements: true,
+                    field: None,
+                    used: FxHashSet::default(),
+                });
+            }
+            SyntaxKind::LParen => {
+                // `n(...)` repeats array elements; `name(...)` is a call.
+                let repeat = prev_kind == SyntaxKind::IntLiteral
+                    && frames.last().is_some_and(|frame| frame.elements);
+                let call = prev_kind == SyntaxKind::Ident;
+                frames.push(InitializerFrame {
+                    type_id: expected.filter(|_| !call).unwrap_or(TypeId::UNKNOWN),
+                    elements: repeat,
+                    field: None,
+                    used: FxHashSet::default(),
+                });
+            }
+            SyntaxKind::RParen | SyntaxKind::RBracket => {
+                frames.pop();
+            }
+            SyntaxKind::Comma => {
+                if let Some(frame) = frames.last_mut() {
+                    frame.field = None;
+                }
+            }
+            SyntaxKind::Ident
+                if tokens
+                    .get(idx + 1)
+                    .is_some_and(|next| next.kind() == SyntaxKind::Assign) =>
+            {
+                if let Some(frame) = frames.last_mut().filter(|frame| !frame.elements) {
+                    let field = SmolStr::new(token.text());
+                    frame.used.insert(SmolStr::new(field.to_ascii_uppercase()));
+                    frame.field = Some(field);
+                }
+            }
+            _ => {}
+        }
+        prev_kind = token.kind();
+    }
+
+    let Some(frame) = frames.last().filter(|frame| !frame.elements) else {
+        return Vec::new();
+    };
+    if !matches!(prev_kind, SyntaxKind::LParen | SyntaxKind::Comma) {
+        return Vec::new();
+    }
+    let Some(Type::Struct { fields, .. }) =
+        symbols.type_by_id(symbols.resolve_alias_type(frame.type_id))
+    else {
+        return Vec::new();
+    };
+    fields
+        .iter()
+        .filter(|field| {
+            !frame
+                .used
+                .contains(&SmolStr::new(field.name.to_ascii_uppercase()))
+        })
+        .map(|field| {
+            let mut item =
+                CompletionItem::new(field.name.clone(), CompletionKind::Variable).with_priority(5);
+            item.detail = type_detail(symbols, field.type_id);
+            item.insert_text = Some(SmolStr::new(format!("{} := $0", field.name)));
+            item
+        })
+        .collect()
+}
+
+fn struct_field_type(symbols: &SymbolTable, type_id: TypeId, name: &str) -> Option<TypeId> {
+    match symbols.type_by_id(symbols.resolve_alias_type(type_id))? {
+        Type::Struct { fields, .. } => fields
+            .iter()
+            .find(|field| field.name.eq_ignore_ascii_case(name))
+            .map(|field| field.type_id),
+        _ => None,
+    }
+}
+
 fn dedupe_items(items: Vec<CompletionItem>) -> Vec<CompletionItem> {
     let mut seen: FxHashSet<String> = FxHashSet::default();
     let mut deduped = Vec::new();
@@ -483,6 +793,100 @@ fn parameter_name_completions(
     items
 }
 
+/// Which parameters a call template lists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CallTemplateParams {
+    /// Every input, in-out, and output parameter.
+    #[default]
+    All,
+    /// In-out parameters and inputs declared without an initial value.
+    Mandatory,
+}
+
+/// Completion that fills an empty call argument list with named parameters.
+///
+/// Inputs and in-outs are written as `name := value` and outputs as `name => target`, one
+/// per line, with each value a snippet placeholder showing the parameter type.
+pub fn call_template_completion(
+    db: &Database,
+    file_id: trust_hir::db::FileId,
+    position: TextSize,
+    params: CallTemplateParams,
+) -> Option<CompletionItem> {
+    let context = call_signature_context(db, file_id, position)?;
+    if context.arg_count > 0 {
+        return None;
+    }
+    let symbols = db.file_symbols_with_project(file_id);
+    let selected = context
+        .signature
+        .params
+        .iter()
+        .filter(|param| match params {
+            CallTemplateParams::All => true,
+            CallTemplateParams::Mandatory => match param.direction {
+                ParamDirection::InOut => true,
+                ParamDirection::Out => false,
+                ParamDirection::In => !param
+                    .symbol_id
+                    .is_some_and(|symbol_id| param_has_initializer(db, file_id, symbol_id)),
+            },
+        })
+        .collect::<Vec<_>>();
+    if selected.is_empty() {
+        return None;
+    }
+
+    let lines = selected
+        .iter()
+        .enumerate()
+        .map(|(index, param)| {
+            let op = match param.direction {
+                ParamDirection::Out => "=>",
+                ParamDirection::In | ParamDirection::InOut => ":=",
+            };
+            let type_name = type_detail(&symbols, param.type_id)
+                .map(|name| name.to_string())
+                .or_else(|| param.type_id.builtin_name().map(|name| name.to_string()))
+                .unwrap_or_else(|| param.name.to_string());
+            format!("\t{} {op} ${{{}:{type_name}}}", param.name, index + 1)
+        })
+        .collect::<Vec<_>>();
+    let names = selected
+        .iter()
+        .map(|param| param.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let detail = match params {
+        CallTemplateParams::All => "Named-parameter call (all parameters)",
+        CallTemplateParams::Mandatory => "Named-parameter call (mandatory parameters)",
+    };
+    Some(
+        CompletionItem::new(
+            format!("{}({names})", context.signature.name),
+            CompletionKind::Snippet,
+        )
+        .with_detail(detail)
+        .with_insert_text(format!("\n{}\n", lines.join(",\n")))
+        .with_priority(1),
+    )
+}
+
+fn param_has_initializer(
+    db: &Database,
+    file_id: trust_hir::db::FileId,
+    symbol_id: SymbolId,
+) -> bool {
+    let Some(definition) = crate::goto_def::definition_of_symbol(db, file_id, symbol_id) else {
+        return false;
+    };
+    let source = db.source_text(definition.file_id);
+    let root = trust_syntax::parser::parse(&source).syntax();
+    var_decl_info_for_symbol(&root, &source, definition.range)
+        .initializer
+        .is_some()
+}
+
 fn keyword_snippets() -> Vec<CompletionItem> {
     let mut items = Vec::new();
     items.extend(top_level_keywords());
@@ -558,6 +962,26 @@ fn statement_keywords() -> Vec<CompletionItem> {
     ]
 }
 
+fn dialect_statement_keywords(dialect: &Dialect) -> Vec<CompletionItem> {
+    let mut items = Vec::new();
+    if dialect.supports(DialectExtension::Regions) {
+        items.push(
+            CompletionItem::new("REGION", CompletionKind::Keyword)
+                .with_insert_text("REGION ${1:Name}\n\t$0\nEND_REGION")
+                .with_priority(22),
+        );
+    }
+    items
+}
+
+fn dialect_type_keywords(dialect: &Dialect) -> Vec<CompletionItem> {
+    let mut items = Vec::new();
+    if dialect.supports(DialectExtension::Pointers) {
+        items.push(CompletionItem::new("POINTER TO", CompletionKind::Keyword).with_priority(40));
+    }
+    items
+}
+
 fn type_keywords() -> Vec<CompletionItem> {
     vec![
         // Boolean
@@ -613,7 +1037,6 @@ fn type_keywords() -> Vec<CompletionItem> {
         CompletionItem::new("ARRAY", CompletionKind::Keyword)
             .with_insert_text("ARRAY[${1:0}..${2:10}] OF ${3:INT}")
             .with_priority(30),
-        CompletionItem::new("POINTER TO", CompletionKind::Keyword).with_priority(40),
         CompletionItem::new("REF_TO", CompletionKind::Keyword).with_priority(40),
     ]
 }
@@ -912,15 +1335,17 @@ fn is_member_visible(
     current_owner: Option<SymbolId>,
     current_namespace: &[SmolStr],
 ) -> bool {
+    // Access is decided by the declaring class, which may be a base of `owner_id`.
+    let declaring_id = member.parent.unwrap_or(owner_id);
     match member.visibility {
         Visibility::Public => true,
-        Visibility::Private => current_owner == Some(owner_id),
+        Visibility::Private => current_owner == Some(declaring_id),
         Visibility::Protected => {
-            current_owner.is_some_and(|current| is_same_or_derived(symbols, current, owner_id))
+            current_owner.is_some_and(|current| is_same_or_derived(symbols, current, declaring_id))
         }
         Visibility::Internal => {
             let owner_namespace = symbols
-                .get(owner_id)
+                .get(declaring_id)
                 .map(|symbol| namespace_path_for_symbol(symbols, symbol))
                 .unwrap_or_default();
             owner_namespace == current_namespace
@@ -1498,6 +1923,91 @@ END_PROGRAM
         assert!(insert.contains(":="));
     }
 
+    #[test]
+    fn test_field_completion_in_structured_initializer() {
+        let source = r#"
+TYPE
+    Position : STRUCT
+        x : REAL;
+        y : REAL;
+    END_STRUCT;
+    Segment : STRUCT
+        start : Position;
+        stop : Position;
+    END_STRUCT;
+END_TYPE
+
+PROGRAM Main
+VAR
+    path : ARRAY[1..2] OF Segment := [(start := (x := 1.0, |)), (stop := (x := 2.0))];
+END_VAR
+END_PROGRAM
+"#;
+        let cursor = source.find('|').expect("cursor");
+        let mut cleaned = source.to_string();
+        cleaned.remove(cursor);
+
+        let mut db = Database::new();
+        let file_id = FileId(0);
+        db.set_source_text(file_id, cleaned);
+
+        let items = complete(&db, file_id, TextSize::from(cursor as u32));
+        let labels: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(labels, vec!["y"]);
+        assert_eq!(items[0].insert_text.as_deref(), Some("y := $0"));
+    }
+
+    #[test]
+    fn test_call_template_lists_named_parameters() {
+        let source = r#"
+FUNCTION_BLOCK Valve
+VAR_INPUT
+    Open : BOOL;
+    Timeout : TIME := T#5s;
+END_VAR
+VAR_IN_OUT
+    Cmd : INT;
+END_VAR
+VAR_OUTPUT
+    IsOpen : BOOL;
+END_VAR
+END_FUNCTION_BLOCK
+
+PROGRAM Main
+VAR
+    v : Valve;
+END_VAR
+    v(|);
+END_PROGRAM
+"#;
+        let cursor = source.find('|').expect("cursor");
+        let mut cleaned = source.to_string();
+        cleaned.remove(cursor);
+
+        let mut db = Database::new();
+        let file_id = FileId(0);
+        db.set_source_text(file_id, cleaned);
+        let position = TextSize::from(cursor as u32);
+
+        let all = call_template_completion(&db, file_id, position, CallTemplateParams::All)
+            .expect("call template");
+        assert_eq!(all.label, "Valve(Open, Timeout, Cmd, IsOpen)");
+        assert_eq!(
+            all.insert_text.as_deref(),
+            Some(
+                "\n\tOpen := ${1:BOOL},\n\tTimeout := ${2:TIME},\n\tCmd := ${3:INT},\n\tIsOpen => ${4:BOOL}\n"
+            )
+        );
+
+        let mandatory =
+            call_template_completion(&db, file_id, position, CallTemplateParams::Mandatory)
+                .expect("mandatory call template");
+        assert_eq!(
+            mandatory.insert_text.as_deref(),
+            Some("\n\tOpen := ${1:BOOL},\n\tCmd := ${2:INT}\n")
+        );
+    }
+
     #[test]
     fn test_parameter_name_completion_skips_used_formal() {
         let source = r#"
@@ -1665,6 +2175,50 @@ END_PROGRAM
         assert!(!items.iter().any(|item| item.label == "x"));
     }
 
+    #[test]
+    fn test_array_index_and_element_member_completion() {
+        let source = r#"
+TYPE
+    Axis : STRUCT
+        Position : LREAL;
+        Enabled : BOOL;
+    END_STRUCT;
+END_TYPE
+
+PROGRAM Main
+VAR
+    axes : ARRAY[1..3, 0..1] OF Axis;
+    pos : LREAL;
+END_VAR
+    pos := axes[2, |
+END_PROGRAM
+"#;
+        let cursor = source.find('|').expect("cursor");
+        let mut cleaned = source.to_string();
+        cleaned.remove(cursor);
+
+        let mut db = Database::new();
+        let file_id = FileId(0);
+        db.set_source_text(file_id, cleaned.clone());
+        let items = complete(&db, file_id, TextSize::from(cursor as u32));
+        let hint = items
+            .iter()
+            .find(|item| item.label == "0" && item.kind == CompletionKind::Constant)
+            .expect("index hint");
+        assert!(hint
+            .detail
+            .as_deref()
+            .is_some_and(|detail| detail.contains("dimension 2 of 2: 0..1")));
+        assert!(items.iter().any(|item| item.label == "1"));
+
+        let member_source = cleaned.replace("axes[2, ", "axes[2, 1].");
+        let cursor = member_source.find("].").expect("member cursor") + 2;
+        db.set_source_text(file_id, member_source);
+        let items = complete(&db, file_id, TextSize::from(cursor as u32));
+        assert!(items.iter().any(|item| item.label == "Position"));
+        assert!(items.iter().any(|item| item.label == "Enabled"));
+    }
+
     #[test]
     fn test_completion_recovery_in_statement_context_keeps_scope_symbols() {
         let source = r#"
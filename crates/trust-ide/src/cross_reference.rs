@@ -0,0 +1,398 @@
+//! Project cross-reference listing ("where used").
+//!
+//! Lists every variable and direct address of the workspace with its
+//! declaration and all read and write locations. Each location names the POU it
+//! sits in and the tasks that execute that POU: a POU runs on a task when one of
+//! the task's program instances reaches it through calls.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use smol_str::SmolStr;
+use std::collections::{BTreeMap, VecDeque};
+use text_size::TextRange;
+
+use trust_hir::db::{FileId, SourceDatabase};
+use trust_hir::direct_address::{
+    direct_address_uses, normalize_direct_address, DirectAddressUseKind,
+};
+use trust_hir::symbols::{ScopeId, Symbol, SymbolId, SymbolKind, SymbolTable, VarQualifier};
+use trust_hir::Database;
+use trust_syntax::parser::parse;
+use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
+
+use crate::dead_code::resolve_name_ref;
+use crate::references::{reference_access, resolve_field_expr_member, ReferenceAccess};
+use crate::stack_usage::{symbol_key, CallGraph, SymbolKey};
+use crate::util::scope_at_position;
+
+/// What a cross-reference entry lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossReferenceKind {
+    /// A declared variable, parameter, or constant.
+    Variable,
+    /// A direct address (`%IX0.1`, `%QW4`) used literally or bound by `AT`.
+    Address,
+}
+
+impl CrossReferenceKind {
+    /// Returns a stable lowercase label for reports.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Variable => "variable",
+            Self::Address => "address",
+        }
+    }
+}
+
+/// How a location uses the listed variable or address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossReferenceUse {
+    /// Declaration; for addresses an `AT` declaration or `VAR_CONFIG` mapping.
+    Declaration,
+    /// Read, written, or passed to VAR_IN_OUT.
+    Access(ReferenceAccess),
+}
+
+impl CrossReferenceUse {
+    /// Returns a stable lowercase label for reports.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Declaration => "declaration",
+            Self::Access(access) => access.label(),
+        }
+    }
+}
+
+/// One location in a cross-reference entry.
+#[derive(Debug, Clone)]
+pub struct CrossReferenceLocation {
+    /// How the location uses the entry.
+    pub usage: CrossReferenceUse,
+    /// File containing the location.
+    pub file_id: FileId,
+    /// Range of the name or address token.
+    pub range: TextRange,
+    /// Enclosing POU (`Motor.Start` for methods); `None` in configurations.
+    pub pou: Option<String>,
+    /// Tasks whose program instances reach the POU, sorted by name.
+    pub tasks: Vec<SmolStr>,
+}
+
+/// A variable or address with its locations.
+#[derive(Debug, Clone)]
+pub struct CrossReferenceEntry {
+    /// Qualified variable name (`Main.Count`) or normalized address (`%QX0.3`).
+    pub name: String,
+    /// Entry kind.
+    pub kind: CrossReferenceKind,
+    /// Declared type name, for variables.
+    pub type_name: Option<SmolStr>,
+    /// Address a variable is located at (`AT %QX0.3`).
+    pub address: Option<SmolStr>,
+    /// Declaration first, then accesses in file and source order.
+    pub locations: Vec<CrossReferenceLocation>,
+}
+
+/// Result of the cross-reference analysis.
+#[derive(Debug, Clone, Default)]
+pub struct CrossReferenceReport {
+    /// Variables in file and declaration order, followed by addresses sorted by name.
+    pub entries: Vec<CrossReferenceEntry>,
+}
+
+/// Options for the cross-reference analysis.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrossReferenceOptions<'a> {
+    /// Files that participate in the analysis (defaults to all files).
+    pub files: Option<&'a FxHashSet<FileId>>,
+}
+
+/// Builds the cross-reference listing of the workspace.
+pub fn analyze_cross_references(
+    db: &Database,
+    options: CrossReferenceOptions<'_>,
+) -> CrossReferenceReport {
+    let graph = CallGraph::build(db, options.files);
+    let tasks = tasks_by_pou(&graph);
+    let mut file_ids: Vec<FileId> = graph.tables.keys().copied().collect();
+    file_ids.sort_by_key(|id| id.0);
+
+    let mut accesses: FxHashMap<SymbolKey, Vec<CrossReferenceLocation>> = FxHashMap::default();
+    let mut addresses: BTreeMap<SmolStr, Vec<CrossReferenceLocation>> = BTreeMap::new();
+    for &file_id in &file_ids {
+        let symbols = &graph.tables[&file_id];
+        let source = db.source_text(file_id);
+        let root = parse(&source).syntax();
+        let locate = |range: TextRange, usage: CrossReferenceUse| {
+            let owner = owning_pou(symbols, &root, range, file_id);
+            CrossReferenceLocation {
+                usage,
+                file_id,
+                range,
+                pou: owner.and_then(|key| pou_name(&graph, key)),
+                tasks: owner
+                    .and_then(|key| tasks.get(&key).cloned())
+                    .unwrap_or_default(),
+            }
+        };
+
+        for node in root.descendants() {
+            let target = match node.kind() {
+                SyntaxKind::NameRef => node
+                    .descendants_with_tokens()
+                    .filter_map(|element| element.into_token())
+                    .find(|token| token.kind() == SyntaxKind::Ident)
+                    .zip(resolve_name_ref(symbols, &root, &node))
+                    .map(|(ident, id)| (id, ident.text_range())),
+                SyntaxKind::FieldExpr => {
+                    resolve_field_expr_member(db, file_id, symbols, &root, &node)
+                }
+                _ => None,
+            };
+            let Some((symbol_id, range)) = target else {
+                continue;
+            };
+            if !symbols.get(symbol_id).is_some_and(is_listed) {
+                continue;
+            }
+            let Some(key) = symbol_key(symbols, symbol_id, file_id) else {
+                continue;
+            };
+            let access = reference_access(db, file_id, &source, &root, range);
+            accesses
+                .entry(key)
+                .or_default()
+                .push(locate(range, CrossReferenceUse::Access(access)));
+        }
+
+        for entry in direct_address_uses(&root) {
+            let usage = match entry.kind {
+                DirectAddressUseKind::Declaration | DirectAddressUseKind::Configuration => {
+                    CrossReferenceUse::Declaration
+                }
+                DirectAddressUseKind::Read => CrossReferenceUse::Access(ReferenceAccess::Read),
+                DirectAddressUseKind::Write => CrossReferenceUse::Access(ReferenceAccess::Write),
+            };
+            addresses
+                .entry(entry.address)
+                .or_default()
+                .push(locate(entry.range, usage));
+        }
+    }
+
+    let mut entries = Vec::new();
+    for &file_id in &file_ids {
+        let symbols = &graph.tables[&file_id];
+        let source = db.source_text(file_id);
+        let root = parse(&source).syntax();
+        for symbol in symbols
+            .iter()
+            .filter(|symbol| symbol.origin.is_none() && is_listed(symbol))
+            .filter(|symbol| !symbol.range.is_empty())
+        {
+            let key = SymbolKey {
+                file_id,
+                symbol_id: symbol.id,
+            };
+            let owner = owning_pou(symbols, &root, symbol.range, file_id);
+            let mut locations = vec![CrossReferenceLocation {
+                usage: CrossReferenceUse::Declaration,
+                file_id,
+                range: symbol.range,
+                pou: owner.and_then(|key| pou_name(&graph, key)),
+                tasks: owner
+                    .and_then(|key| tasks.get(&key).cloned())
+                    .unwrap_or_default(),
+            }];
+            locations.extend(accesses.remove(&key).unwrap_or_default());
+            entries.push(CrossReferenceEntry {
+                name: qualified_name(symbols, symbol.id),
+                kind: CrossReferenceKind::Variable,
+                type_name: symbols.type_name(symbol.type_id),
+                address: symbol.direct_address.as_ref().map(|address| {
+                    normalize_direct_address(address).unwrap_or_else(|| address.clone())
+                }),
+                locations,
+            });
+        }
+    }
+    entries.extend(
+        addresses
+            .into_iter()
+            .map(|(address, locations)| CrossReferenceEntry {
+                name: address.to_string(),
+                kind: CrossReferenceKind::Address,
+                type_name: None,
+                address: None,
+                locations,
+            }),
+    );
+
+    CrossReferenceReport { entries }
+}
+
+/// Variables, parameters, and constants; VAR_EXTERNAL re-declarations are folded
+/// into their VAR_GLOBAL by name resolution and not listed on their own.
+fn is_listed(symbol: &Symbol) -> bool {
+    match symbol.kind {
+        SymbolKind::Variable { qualifier } => !matches!(qualifier, VarQualifier::External),
+        SymbolKind::Parameter { .. } | SymbolKind::Constant => true,
+        _ => false,
+    }
+}
+
+/// Tasks reaching each POU from the program instances attached to them.
+fn tasks_by_pou(graph: &CallGraph) -> FxHashMap<SymbolKey, Vec<SmolStr>> {
+    let mut tasks: FxHashMap<SymbolKey, Vec<SmolStr>> = FxHashMap::default();
+    for entry in &graph.entry_points {
+        let Some(task) = &entry.task else {
+            continue;
+        };
+        let mut seen = FxHashSet::default();
+        let mut queue = VecDeque::from([entry.program]);
+        while let Some(key) = queue.pop_front() {
+            if !seen.insert(key) {
+                continue;
+            }
+            let names = tasks.entry(key).or_default();
+            if !names.iter().any(|name| name.eq_ignore_ascii_case(task)) {
+                names.push(task.clone());
+            }
+            queue.extend(
+                graph
+                    .calls
+                    .get(&key)
+                    .into_iter()
+                    .flatten()
+                    .map(|site| site.callee),
+            );
+        }
+    }
+    for names in tasks.values_mut() {
+        names.sort();
+    }
+    tasks
+}
+
+fn owning_pou(
+    symbols: &SymbolTable,
+    root: &SyntaxNode,
+    range: TextRange,
+    file_id: FileId,
+) -> Option<SymbolKey> {
+    let scope_id = scope_at_position(symbols, root, range.start());
+    if scope_id == ScopeId::GLOBAL {
+        return None;
+    }
+    let owner = symbols.get_scope(scope_id)?.owner?;
+    let symbol = symbols.get(owner)?;
+    if matches!(
+        symbol.kind,
+        SymbolKind::Namespace | SymbolKind::Configuration | SymbolKind::Resource
+    ) {
+        return None;
+    }
+    symbol_key(symbols, owner, file_id)
+}
+
+fn pou_name(graph: &CallGraph, key: SymbolKey) -> Option<String> {
+    let symbols = graph.tables.get(&key.file_id)?;
+    Some(qualified_name(symbols, key.symbol_id))
+}
+
+/// Dotted name through the enclosing POUs, types, and namespaces.
+fn qualified_name(symbols: &SymbolTable, symbol_id: SymbolId) -> String {
+    let mut parts = Vec::new();
+    let mut current = Some(symbol_id);
+    while let Some(symbol) = current.and_then(|id| symbols.get(id)) {
+        if !matches!(
+            symbol.kind,
+            SymbolKind::Configuration | SymbolKind::Resource
+        ) {
+            parts.push(symbol.name.as_str());
+        }
+        current = symbol.parent;
+    }
+    parts.reverse();
+    parts.join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cross_references_list_accesses_with_pou_and_task() {
+        let source = r#"
+CONFIGURATION Conf
+VAR_GLOBAL
+    gSpeed : INT;
+END_VAR
+RESOURCE Res ON PLC
+    TASK Fast (INTERVAL := T#10ms, PRIORITY := 1);
+    PROGRAM Inst WITH Fast : Main;
+END_RESOURCE
+END_CONFIGURATION
+
+FUNCTION Scale : INT
+VAR_INPUT
+    raw : INT;
+END_VAR
+    Scale := raw * 2;
+END_FUNCTION
+
+PROGRAM Main
+VAR_EXTERNAL
+    gSpeed : INT;
+END_VAR
+VAR
+    Lamp AT %QX0.3 : BOOL;
+END_VAR
+    gSpeed := Scale(raw := gSpeed);
+    Lamp := %IX0.1;
+END_PROGRAM
+"#;
+        let mut db = Database::new();
+        db.set_source_text(FileId(0), source.to_string());
+
+        let report = analyze_cross_references(&db, CrossReferenceOptions::default());
+        let entry = |name: &str| {
+            report
+                .entries
+                .iter()
+                .find(|entry| entry.name == name)
+                .unwrap_or_else(|| panic!("missing entry {name}: {:?}", report.entries))
+        };
+
+        let speed = entry("gSpeed");
+        let uses: Vec<_> = speed
+            .locations
+            .iter()
+            .map(|location| (location.usage.label(), location.pou.as_deref()))
+            .collect();
+        assert_eq!(
+            uses,
+            vec![
+                ("declaration", None),
+                ("write", Some("Main")),
+                ("read", Some("Main")),
+            ]
+        );
+        assert_eq!(speed.locations[1].tasks, vec![SmolStr::new("Fast")]);
+
+        let raw = entry("Scale.raw");
+        assert_eq!(raw.locations.len(), 2);
+        assert_eq!(raw.locations[1].pou.as_deref(), Some("Scale"));
+        assert_eq!(raw.locations[1].tasks, vec![SmolStr::new("Fast")]);
+
+        assert_eq!(entry("Main.Lamp").address.as_deref(), Some("%QX0.3"));
+        assert!(!report
+            .entries
+            .iter()
+            .any(|entry| entry.name == "Main.gSpeed"));
+        let input = entry("%IX0.1");
+        assert_eq!(input.kind, CrossReferenceKind::Address);
+        assert_eq!(input.locations[0].usage.label(), "read");
+    }
+}
@@ -0,0 +1,871 @@
+//! Cross-POU data-flow tracing ("who drives this output").
+//!
+//! Starting from a variable or a direct address, the tracer collects every
+//! place that writes the value (assignments, `=>` output connections, and
+//! VAR_IN_OUT passes) and recursively follows the values read at those places.
+//! Function block inputs are followed back to the call sites that connect
+//! them, and outputs into the function block body that assigns them.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::sync::Arc;
+use text_size::{TextRange, TextSize};
+
+use trust_hir::db::{FileId, SemanticDatabase};
+use trust_hir::symbols::{ParamDirection, Symbol, SymbolId, SymbolKind, SymbolTable, VarQualifier};
+use trust_hir::Database;
+use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
+
+use crate::references::{
+    classify_references, find_references, reference_consumer, FindReferencesOptions,
+    ReferenceAccess,
+};
+use crate::util::{ident_token_in_name, IdeContext, ResolvedTarget};
+
+/// Maximum label length for statement snippets.
+const MAX_LABEL_LEN: usize = 80;
+
+/// Kind of a node in a driver tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataFlowNodeKind {
+    /// A direct address (`%QX0.0`, `%IW4`, ...).
+    Address,
+    /// A variable, parameter, or constant.
+    Variable,
+    /// A structure field access that is not traced further.
+    Field,
+    /// An assignment statement writing the parent value.
+    Assignment,
+    /// A `=>` output connection writing the parent value.
+    OutputConnection,
+    /// A call-site connection driving an input or in-out parameter.
+    InputConnection,
+    /// The parent value is passed to a VAR_IN_OUT parameter that may write it.
+    InOutPass,
+}
+
+impl DataFlowNodeKind {
+    /// Returns a stable lowercase label for reports.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Address => "address",
+            Self::Variable => "variable",
+            Self::Field => "field",
+            Self::Assignment => "assignment",
+            Self::OutputConnection => "output",
+            Self::InputConnection => "input",
+            Self::InOutPass => "inout",
+        }
+    }
+}
+
+/// A node in a driver tree.
+///
+/// Value nodes (addresses, variables, fields) have driver nodes (assignments,
+/// connections) as children, and driver nodes have the values they read as
+/// children.
+#[derive(Debug, Clone)]
+pub struct DataFlowNode {
+    /// Node kind.
+    pub kind: DataFlowNodeKind,
+    /// Display label (qualified name or statement snippet).
+    pub label: String,
+    /// File containing the node location.
+    pub file_id: FileId,
+    /// Location of the declaration or statement.
+    pub range: TextRange,
+    /// Contributing nodes.
+    pub children: Vec<DataFlowNode>,
+    /// True when the node was not expanded (depth limit or already expanded).
+    pub truncated: bool,
+}
+
+/// Options for driver tracing.
+#[derive(Debug, Clone, Copy)]
+pub struct DataFlowOptions {
+    /// Maximum number of variables followed along a single path.
+    pub max_depth: usize,
+}
+
+impl Default for DataFlowOptions {
+    fn default() -> Self {
+        Self { max_depth: 8 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct SymbolKey {
+    file_id: FileId,
+    symbol_id: SymbolId,
+}
+
+struct Tracer<'a> {
+    db: &'a Database,
+    options: DataFlowOptions,
+    contexts: FxHashMap<FileId, IdeContext<'a>>,
+    local_tables: FxHashMap<FileId, Arc<SymbolTable>>,
+    expanded: FxHashSet<SymbolKey>,
+}
+
+/// Traces the sources driving the variable or direct address at `position`.
+///
+/// Returns `None` when the position is not on a variable, parameter, or
+/// direct address.
+pub fn trace_drivers(
+    db: &Database,
+    file_id: FileId,
+    position: TextSize,
+    options: DataFlowOptions,
+) -> Option<DataFlowNode> {
+    let mut tracer = Tracer {
+        db,
+        options,
+        contexts: FxHashMap::default(),
+        local_tables: FxHashMap::default(),
+        expanded: FxHashSet::default(),
+    };
+
+    let address = {
+        let context = tracer.context(file_id);
+        let offset = context.root.token_at_offset(position);
+        offset
+            .clone()
+            .right_biased()
+            .into_iter()
+            .chain(offset.left_biased())
+            .find(|token| token.kind() == SyntaxKind::DirectAddress)
+    };
+    if let Some(token) = address {
+        return Some(tracer.address_node(file_id, token.text(), token.text_range()));
+    }
+
+    let key = {
+        let context = tracer.context(file_id);
+        let ResolvedTarget::Symbol(symbol_id) = context.resolve_target_at_position(position)?
+        else {
+            return None;
+        };
+        resolve_value_key(&context.symbols, symbol_id, file_id)?
+    };
+    Some(tracer.variable_node(key, 0))
+}
+
+impl<'a> Tracer<'a> {
+    fn context(&mut self, file_id: FileId) -> &IdeContext<'a> {
+        let db = self.db;
+        self.contexts
+            .entry(file_id)
+            .or_insert_with(|| IdeContext::new(db, file_id))
+    }
+
+    fn local_table(&mut self, file_id: FileId) -> Arc<SymbolTable> {
+        let db = self.db;
+        self.local_tables
+            .entry(file_id)
+            .or_insert_with(|| db.file_symbols(file_id))
+            .clone()
+    }
+
+    fn symbol(&mut self, key: SymbolKey) -> Option<Symbol> {
+        self.local_table(key.file_id).get(key.symbol_id).cloned()
+    }
+
+    fn address_node(&mut self, file_id: FileId, address: &str, range: TextRange) -> DataFlowNode {
+        let mut children = Vec::new();
+        let mut file_ids = self.db.file_ids();
+        file_ids.sort_by_key(|id| id.0);
+        for other_file in file_ids {
+            let table = self.local_table(other_file);
+            let bound: Vec<SymbolId> = table
+                .iter()
+                .filter(|symbol| {
+                    symbol.origin.is_none()
+                        && symbol
+                            .direct_address
+                            .as_deref()
+                            .is_some_and(|bound| bound.eq_ignore_ascii_case(address))
+                })
+                .map(|symbol| symbol.id)
+                .collect();
+            for symbol_id in bound {
+                let key = SymbolKey {
+                    file_id: other_file,
+                    symbol_id,
+                };
+                children.push(self.variable_node(key, 1));
+            }
+
+            let context = self.context(other_file);
+            let targets: Vec<SyntaxNode> = context
+                .root
+                .descendants()
+                .filter(|node| node.kind() == SyntaxKind::AssignStmt)
+                .filter(|node| {
+                    node.first_child().is_some_and(|target| {
+                        target
+                            .descendants_with_tokens()
+                            .filter_map(|element| element.into_token())
+                            .any(|token| {
+                                token.kind() == SyntaxKind::DirectAddress
+                                    && token.text().eq_ignore_ascii_case(address)
+                            })
+                    })
+                })
+                .collect();
+            for assign in targets {
+                children.push(self.assignment_node(other_file, &assign, 1));
+            }
+        }
+
+        DataFlowNode {
+            kind: DataFlowNodeKind::Address,
+            label: address.to_string(),
+            file_id,
+            range,
+            children,
+            truncated: false,
+        }
+    }
+
+    fn variable_node(&mut self, key: SymbolKey, depth: usize) -> DataFlowNode {
+        let table = self.local_table(key.file_id);
+        let Some(symbol) = table.get(key.symbol_id).cloned() else {
+            return DataFlowNode {
+                kind: DataFlowNodeKind::Variable,
+                label: String::from("<unknown>"),
+                file_id: key.file_id,
+                range: TextRange::default(),
+                children: Vec::new(),
+                truncated: true,
+            };
+        };
+        let mut label = qualified_label(&table, &symbol);
+        if let Some(address) = symbol.direct_address.as_deref() {
+            label.push_str(&format!(" AT {address}"));
+        }
+        let mut node = DataFlowNode {
+            kind: DataFlowNodeKind::Variable,
+            label,
+            file_id: key.file_id,
+            range: symbol.range,
+            children: Vec::new(),
+            truncated: false,
+        };
+        if matches!(symbol.kind, SymbolKind::Constant) {
+            return node;
+        }
+        if depth >= self.options.max_depth || !self.expanded.insert(key) {
+            node.truncated = true;
+            return node;
+        }
+
+        node.children = self.write_sites(key, &symbol, depth);
+        if let SymbolKind::Parameter {
+            direction: ParamDirection::In | ParamDirection::InOut,
+        } = symbol.kind
+        {
+            if let Some(owner) = symbol.parent {
+                let owner = SymbolKey {
+                    file_id: key.file_id,
+                    symbol_id: owner,
+                };
+                node.children
+                    .extend(self.call_site_connections(owner, &symbol, depth));
+            }
+        }
+        node
+    }
+
+    fn write_sites(&mut self, key: SymbolKey, symbol: &Symbol, depth: usize) -> Vec<DataFlowNode> {
+        let mut anchors = vec![(key.file_id, symbol.range.start())];
+        if matches!(
+            symbol.kind,
+            SymbolKind::Variable {
+                qualifier: VarQualifier::Global
+            }
+        ) {
+            anchors.extend(self.external_declarations(&symbol.name));
+        }
+
+        let mut classified = Vec::new();
+        for (file_id, offset) in anchors {
+            let references =
+                find_references(self.db, file_id, offset, FindReferencesOptions::default());
+            classified.extend(classify_references(self.db, references));
+        }
+        classified.sort_by_key(|item| (item.reference.file_id.0, item.reference.range.start()));
+        classified.dedup_by_key(|item| (item.reference.file_id, item.reference.range));
+
+        let mut sites = Vec::new();
+        for item in classified {
+            if item.access == ReferenceAccess::Read {
+                continue;
+            }
+            let file_id = item.reference.file_id;
+            let Some((consumer, _)) =
+                reference_consumer(&self.context(file_id).root, item.reference.range)
+            else {
+                continue;
+            };
+            match (item.access, consumer.kind()) {
+                (ReferenceAccess::Write, SyntaxKind::AssignStmt) => {
+                    sites.push(self.assignment_node(file_id, &consumer, depth + 1));
+                }
+                (ReferenceAccess::Write, SyntaxKind::Arg) => {
+                    sites.push(self.arg_node(
+                        file_id,
+                        &consumer,
+                        DataFlowNodeKind::OutputConnection,
+                        depth + 1,
+                    ));
+                }
+                (ReferenceAccess::InOut, SyntaxKind::Arg) => {
+                    sites.push(self.arg_node(
+                        file_id,
+                        &consumer,
+                        DataFlowNodeKind::InOutPass,
+                        depth + 1,
+                    ));
+                }
+                _ => {}
+            }
+        }
+        sites
+    }
+
+    fn external_declarations(&mut self, name: &str) -> Vec<(FileId, TextSize)> {
+        let mut anchors = Vec::new();
+        for file_id in self.db.file_ids() {
+            let table = self.local_table(file_id);
+            anchors.extend(
+                table
+                    .iter()
+                    .filter(|candidate| {
+                        candidate.origin.is_none()
+                            && matches!(
+                                candidate.kind,
+                                SymbolKind::Variable {
+                                    qualifier: VarQualifier::External
+                                }
+                            )
+                            && candidate.name.eq_ignore_ascii_case(name)
+                    })
+                    .map(|candidate| (file_id, candidate.range.start())),
+            );
+        }
+        anchors
+    }
+
+    fn assignment_node(
+        &mut self,
+        file_id: FileId,
+        assign: &SyntaxNode,
+        depth: usize,
+    ) -> DataFlowNode {
+        let value = assign.children().nth(1);
+        let children = match value {
+            Some(value) => self.read_sources(file_id, &value, depth),
+            None => Vec::new(),
+        };
+        DataFlowNode {
+            kind: DataFlowNodeKind::Assignment,
+            label: snippet(assign),
+            file_id,
+            range: assign.text_range(),
+            children,
+            truncated: false,
+        }
+    }
+
+    /// Builds a node for an argument writing the traced value (`=>` or VAR_IN_OUT).
+    fn arg_node(
+        &mut self,
+        file_id: FileId,
+        arg: &SyntaxNode,
+        kind: DataFlowNodeKind,
+        depth: usize,
+    ) -> DataFlowNode {
+        let call_expr = arg
+            .parent()
+            .and_then(|arg_list| arg_list.parent())
+            .filter(|node| node.kind() == SyntaxKind::CallExpr);
+        let param = call_expr
+            .as_ref()
+            .and_then(|call_expr| self.param_for_arg(file_id, call_expr, arg));
+        let children = match param {
+            Some(param) => vec![self.variable_node(param, depth)],
+            None => Vec::new(),
+        };
+        DataFlowNode {
+            kind,
+            label: call_expr.as_ref().map_or_else(|| snippet(arg), snippet),
+            file_id,
+            range: call_expr.map_or_else(|| arg.text_range(), |node| node.text_range()),
+            children,
+            truncated: false,
+        }
+    }
+
+    /// Finds call sites of `owner` that connect `param` and traces the connected values.
+    fn call_site_connections(
+        &mut self,
+        owner: SymbolKey,
+        param: &Symbol,
+        depth: usize,
+    ) -> Vec<DataFlowNode> {
+        let mut nodes = Vec::new();
+        let mut file_ids = self.db.file_ids();
+        file_ids.sort_by_key(|id| id.0);
+        for file_id in file_ids {
+            let calls: Vec<SyntaxNode> = self
+                .context(file_id)
+                .root
+                .descendants()
+                .filter(|node| node.kind() == SyntaxKind::CallExpr)
+                .collect();
+            for call_expr in calls {
+                if self.callee_owner(file_id, &call_expr) != Some(owner) {
+                    continue;
+                }
+                let Some(arg) = self.arg_for_param(owner, param, &call_expr) else {
+                    continue;
+                };
+                let is_output = arg
+                    .children_with_tokens()
+                    .filter_map(|element| element.into_token())
+                    .any(|token| token.kind() == SyntaxKind::Arrow);
+                if is_output {
+                    continue;
+                }
+                let children = match arg
+                    .children()
+                    .find(|child| child.kind() != SyntaxKind::Name)
+                {
+                    Some(value) => self.read_sources(file_id, &value, depth + 1),
+                    None => Vec::new(),
+                };
+                nodes.push(DataFlowNode {
+                    kind: DataFlowNodeKind::InputConnection,
+                    label: snippet(&call_expr),
+                    file_id,
+                    range: call_expr.text_range(),
+                    children,
+                    truncated: false,
+                });
+            }
+        }
+        nodes
+    }
+
+    /// Collects the values read by an expression.
+    fn read_sources(
+        &mut self,
+        file_id: FileId,
+        expr: &SyntaxNode,
+        depth: usize,
+    ) -> Vec<DataFlowNode> {
+        let mut anchors = Vec::new();
+        for element in expr.descendants_with_tokens() {
+            if let Some(token) = element.as_token() {
+                if token.kind() == SyntaxKind::DirectAddress {
+                    anchors.push((token.text_range(), Some(token.text().to_string())));
+                }
+                continue;
+            }
+            let Some(node) = element.into_node() else {
+                continue;
+            };
+            match node.kind() {
+                SyntaxKind::NameRef if !is_callee(&node) && !is_field_base(&node) => {
+                    if let Some(token) = node
+                        .descendants_with_tokens()
+                        .filter_map(|element| element.into_token())
+                        .find(|token| token.kind() == SyntaxKind::Ident)
+                    {
+                        anchors.push((token.text_range(), None));
+                    }
+                }
+                SyntaxKind::FieldExpr if !is_callee(&node) && !is_field_base(&node) => {
+                    if let Some(token) = node
+                        .children()
+                        .find(|child| child.kind() == SyntaxKind::Name)
+                        .and_then(|name| ident_token_in_name(&name))
+                    {
+                        anchors.push((token.text_range(), None));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut nodes = Vec::new();
+        let mut seen = FxHashSet::default();
+        for (range, address) in anchors {
+            if let Some(address) = address {
+                if seen.insert(address.to_ascii_uppercase()) {
+                    nodes.push(DataFlowNode {
+                        kind: DataFlowNodeKind::Address,
+                        label: address,
+                        file_id,
+                        range,
+                        children: Vec::new(),
+                        truncated: false,
+                    });
+                }
+                continue;
+            }
+            let (target, symbols) = {
+                let context = self.context(file_id);
+                (
+                    context.resolve_target_at_position(range.start()),
+                    context.symbols.clone(),
+                )
+            };
+            match target {
+                Some(ResolvedTarget::Symbol(symbol_id)) => {
+                    let Some(key) = resolve_value_key(&symbols, symbol_id, file_id) else {
+                        continue;
+                    };
+                    if seen.insert(format!("{}:{}", key.file_id.0, key.symbol_id.0)) {
+                        nodes.push(self.variable_node(key, depth));
+                    }
+                }
+                Some(ResolvedTarget::Field(field)) => {
+                    let source = self.context(file_id).source.clone();
+                    let label = source
+                        .get(usize::from(range.start())..usize::from(range.end()))
+                        .unwrap_or(field.name.as_str())
+                        .to_string();
+                    if seen.insert(format!("field:{}", label.to_ascii_uppercase())) {
+                        nodes.push(DataFlowNode {
+                            kind: DataFlowNodeKind::Field,
+                            label,
+                            file_id,
+                            range,
+                            children: Vec::new(),
+                            truncated: false,
+                        });
+                    }
+                }
+                None => {}
+            }
+        }
+        nodes
+    }
+
+    /// Resolves the POU or function block type invoked by a call expression.
+    fn callee_owner(&mut self, file_id: FileId, call_expr: &SyntaxNode) -> Option<SymbolKey> {
+        let callee = call_expr
+            .children()
+            .find(|child| child.kind() != SyntaxKind::ArgList)?;
+        let ident = callee
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+            .filter(|token| token.kind() == SyntaxKind::Ident)
+            .last()?;
+        let context = self.context(file_id);
+        let ResolvedTarget::Symbol(symbol_id) =
+            context.resolve_target_at_position(ident.text_range().start())?
+        else {
+            return None;
+        };
+        let symbols = context.symbols.clone();
+        let symbol = symbols.get(symbol_id)?;
+        let owner = match symbol.kind {
+            SymbolKind::Function { .. }
+            | SymbolKind::FunctionBlock
+            | SymbolKind::Method { .. }
+            | SymbolKind::Program => symbol,
+            _ => symbols.iter().find(|candidate| {
+                candidate.type_id == symbol.type_id
+                    && matches!(
+                        candidate.kind,
+                        SymbolKind::FunctionBlock | SymbolKind::Class
+                    )
+            })?,
+        };
+        symbol_key(&symbols, owner.id, file_id)
+    }
+
+    /// Finds the parameter of the called POU that an argument binds to.
+    fn param_for_arg(
+        &mut self,
+        file_id: FileId,
+        call_expr: &SyntaxNode,
+        arg: &SyntaxNode,
+    ) -> Option<SymbolKey> {
+        let owner = self.callee_owner(file_id, call_expr)?;
+        let params = self.owner_params(owner);
+        let index = match arg
+            .children()
+            .find(|child| child.kind() == SyntaxKind::Name)
+        {
+            Some(name) => {
+                let name = ident_token_in_name(&name)?;
+                params.iter().position(|param| {
+                    self.symbol(SymbolKey {
+                        file_id: owner.file_id,
+                        symbol_id: *param,
+                    })
+                    .is_some_and(|symbol| symbol.name.eq_ignore_ascii_case(name.text()))
+                })?
+            }
+            None => call_expr
+                .children()
+                .find(|child| child.kind() == SyntaxKind::ArgList)?
+                .children()
+                .filter(|child| child.kind() == SyntaxKind::Arg)
+                .position(|child| child.text_range() == arg.text_range())?,
+        };
+        params.get(index).map(|symbol_id| SymbolKey {
+            file_id: owner.file_id,
+            symbol_id: *symbol_id,
+        })
+    }
+
+    /// Finds the argument of a call that binds to `param`.
+    fn arg_for_param(
+        &mut self,
+        owner: SymbolKey,
+        param: &Symbol,
+        call_expr: &SyntaxNode,
+    ) -> Option<SyntaxNode> {
+        let args: Vec<SyntaxNode> = call_expr
+            .children()
+            .find(|child| child.kind() == SyntaxKind::ArgList)?
+            .children()
+            .filter(|child| child.kind() == SyntaxKind::Arg)
+            .collect();
+        let named = args.iter().find(|arg| {
+            arg.children()
+                .find(|child| child.kind() == SyntaxKind::Name)
+                .and_then(|name| ident_token_in_name(&name))
+                .is_some_and(|token| token.text().eq_ignore_ascii_case(&param.name))
+        });
+        if let Some(arg) = named {
+            return Some(arg.clone());
+        }
+        if args
+            .iter()
+            .any(|arg| arg.children().any(|child| child.kind() == SyntaxKind::Name))
+        {
+            return None;
+        }
+        let index = self
+            .owner_params(owner)
+            .iter()
+            .position(|symbol_id| *symbol_id == param.id)?;
+        args.get(index).cloned()
+    }
+
+    /// Returns the formal parameters of a POU in declaration order, excluding EN/ENO.
+    fn owner_params(&mut self, owner: SymbolKey) -> Vec<SymbolId> {
+        let table = self.local_table(owner.file_id);
+        let mut params: Vec<SymbolId> = table
+            .iter()
+            .filter(|symbol| {
+                symbol.parent == Some(owner.symbol_id)
+                    && matches!(symbol.kind, SymbolKind::Parameter { .. })
+                    && !symbol.name.eq_ignore_ascii_case("EN")
+                    && !symbol.name.eq_ignore_ascii_case("ENO")
+            })
+            .map(|symbol| symbol.id)
+            .collect();
+        params.sort_by_key(|id| id.0);
+        params
+    }
+}
+
+/// Maps a resolved symbol to the declaration key of a traceable value.
+///
+/// VAR_EXTERNAL declarations are mapped to the VAR_GLOBAL they re-declare.
+fn resolve_value_key(
+    symbols: &SymbolTable,
+    symbol_id: SymbolId,
+    file_id: FileId,
+) -> Option<SymbolKey> {
+    let symbol = symbols.get(symbol_id)?;
+    match symbol.kind {
+        SymbolKind::Variable {
+            qualifier: VarQualifier::External,
+        } => {
+            let global = symbols.iter().find(|candidate| {
+                matches!(
+                    candidate.kind,
+                    SymbolKind::Variable {
+                        qualifier: VarQualifier::Global
+                    } | SymbolKind::Constant
+                ) && candidate.name.eq_ignore_ascii_case(&symbol.name)
+            });
+            match global {
+                Some(global) => symbol_key(symbols, global.id, file_id),
+                None => symbol_key(symbols, symbol_id, file_id),
+            }
+        }
+        SymbolKind::Variable { .. } | SymbolKind::Parameter { .. } | SymbolKind::Constant => {
+            symbol_key(symbols, symbol_id, file_id)
+        }
+        _ => None,
+    }
+}
+
+fn symbol_key(symbols: &SymbolTable, symbol_id: SymbolId, file_id: FileId) -> Option<SymbolKey> {
+    let symbol = symbols.get(symbol_id)?;
+    Some(match symbol.origin {
+        Some(origin) => SymbolKey {
+            file_id: origin.file_id,
+            symbol_id: origin.symbol_id,
+        },
+        None => SymbolKey { file_id, symbol_id },
+    })
+}
+
+fn qualified_label(table: &SymbolTable, symbol: &Symbol) -> String {
+    let owner = symbol
+        .parent
+        .and_then(|parent| table.get(parent))
+        .filter(|parent| {
+            matches!(
+                parent.kind,
+                SymbolKind::Program
+                    | SymbolKind::Function { .. }
+                    | SymbolKind::FunctionBlock
+                    | SymbolKind::Class
+                    | SymbolKind::Method { .. }
+            )
+        });
+    match owner {
+        Some(owner) => format!("{}.{}", owner.name, symbol.name),
+        None => symbol.name.to_string(),
+    }
+}
+
+fn is_callee(node: &SyntaxNode) -> bool {
+    node.parent().is_some_and(|parent| {
+        parent.kind() == SyntaxKind::CallExpr
+            && parent
+                .first_child()
+                .is_some_and(|first| first.text_range() == node.text_range())
+    })
+}
+
+fn is_field_base(node: &SyntaxNode) -> bool {
+    node.parent().is_some_and(|parent| {
+        parent.kind() == SyntaxKind::FieldExpr
+            && parent
+                .first_child()
+                .is_some_and(|first| first.text_range() == node.text_range())
+    })
+}
+
+fn snippet(node: &SyntaxNode) -> String {
+    let text = node
+        .text()
+        .to_string()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let text = text.trim_end_matches(';').to_string();
+    if text.chars().count() > MAX_LABEL_LEN {
+        let truncated: String = text.chars().take(MAX_LABEL_LEN - 3).collect();
+        format!("{truncated}...")
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use trust_hir::db::SourceDatabase;
+
+    fn setup(sources: &[&str]) -> Database {
+        let mut db = Database::new();
+        for (index, source) in sources.iter().enumerate() {
+            db.set_source_text(FileId(index as u32), (*source).to_string());
+        }
+        db
+    }
+
+    fn find_label<'n>(node: &'n DataFlowNode, label: &str) -> Option<&'n DataFlowNode> {
+        if node.label == label {
+            return Some(node);
+        }
+        node.children
+            .iter()
+            .find_map(|child| find_label(child, label))
+    }
+
+    #[test]
+    fn data_flow_traces_assignments_and_function_block_connections() {
+        let source = r#"
+FUNCTION_BLOCK Latch
+    VAR_INPUT arm : BOOL; reset : BOOL; END_VAR
+    VAR_OUTPUT q : BOOL; END_VAR
+    q := arm OR (q AND NOT reset);
+END_FUNCTION_BLOCK
+
+PROGRAM Main
+    VAR
+        start AT %IX0.0 : BOOL;
+        stop : BOOL;
+        motor AT %QX0.0 : BOOL;
+        seal : Latch;
+    END_VAR
+    stop := %IX0.1;
+    seal(arm := start, reset := stop, q => motor);
+END_PROGRAM
+"#;
+        let db = setup(&[source]);
+        let offset = source.find("motor AT").unwrap();
+        let tree = trace_drivers(
+            &db,
+            FileId(0),
+            TextSize::from(offset as u32),
+            DataFlowOptions::default(),
+        )
+        .expect("trace");
+
+        assert_eq!(tree.kind, DataFlowNodeKind::Variable);
+        assert_eq!(tree.label, "Main.motor AT %QX0.0");
+        assert_eq!(tree.children.len(), 1);
+        assert_eq!(tree.children[0].kind, DataFlowNodeKind::OutputConnection);
+        assert!(find_label(&tree, "Latch.q").is_some(), "{tree:#?}");
+        assert!(
+            find_label(&tree, "Main.start AT %IX0.0").is_some(),
+            "{tree:#?}"
+        );
+        let stop = find_label(&tree, "Main.stop").expect("stop");
+        assert!(
+            find_label(stop, "%IX0.1").is_some_and(|node| node.kind == DataFlowNodeKind::Address),
+            "{tree:#?}"
+        );
+    }
+
+    #[test]
+    fn data_flow_traces_direct_address_bindings() {
+        let source = r#"
+PROGRAM Main
+    VAR
+        lamp AT %QX1.0 : BOOL;
+        ready : BOOL;
+    END_VAR
+    lamp := ready;
+    %QX1.1 := NOT ready;
+END_PROGRAM
+"#;
+        let db = setup(&[source]);
+        let offset = source.find("%QX1.0").unwrap();
+        let tree = trace_drivers(
+            &db,
+            FileId(0),
+            TextSize::from(offset as u32 + 1),
+            DataFlowOptions::default(),
+        )
+        .expect("trace");
+        assert_eq!(tree.kind, DataFlowNodeKind::Address);
+        assert_eq!(tree.label, "%QX1.0");
+        assert!(find_label(&tree, "Main.ready").is_some(), "{tree:#?}");
+        assert!(find_label(&tree, "%QX1.1 := NOT ready").is_none());
+    }
+}
@@ -0,0 +1,629 @@
+//! Workspace-wide dead code analysis.
+//!
+//! Builds a reference graph across all files and walks it from the
+//! CONFIGURATION/TASK entry points. POUs, methods, and global variables that
+//! are never reached are reported as dead code.
+//!
+//! The analysis is deliberately conservative: references made at global scope
+//! (for example struct fields typed as function blocks) are treated as roots,
+//! and methods of reachable types that take part in inheritance or interface
+//! dispatch are kept alive.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use smol_str::SmolStr;
+use std::sync::Arc;
+use text_size::TextRange;
+
+use trust_hir::db::{FileId, SemanticDatabase, SourceDatabase};
+use trust_hir::symbols::{ScopeId, SymbolId, SymbolKind, SymbolTable, VarQualifier};
+use trust_hir::Database;
+use trust_syntax::parser::parse;
+use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
+
+use crate::util::{
+    is_type_name_node, qualified_name_parts_from_node, resolve_type_symbol_at_node,
+    scope_at_position,
+};
+
+/// Category of a dead code finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeadCodeCategory {
+    /// PROGRAM, FUNCTION, FUNCTION_BLOCK, CLASS, or INTERFACE.
+    Pou,
+    /// METHOD or PROPERTY of a type.
+    Method,
+    /// VAR_GLOBAL variable or constant.
+    Variable,
+}
+
+impl DeadCodeCategory {
+    /// Returns a stable lowercase label for reports.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pou => "pou",
+            Self::Method => "method",
+            Self::Variable => "variable",
+        }
+    }
+}
+
+/// A symbol reported by the dead code analysis.
+#[derive(Debug, Clone)]
+pub struct DeadCodeItem {
+    /// Symbol name.
+    pub name: SmolStr,
+    /// Owning type or POU name for methods and properties.
+    pub container: Option<SmolStr>,
+    /// Symbol kind.
+    pub kind: SymbolKind,
+    /// Finding category.
+    pub category: DeadCodeCategory,
+    /// File containing the declaration.
+    pub file_id: FileId,
+    /// Declaration range.
+    pub range: TextRange,
+}
+
+/// Result of a workspace-wide dead code analysis.
+#[derive(Debug, Clone, Default)]
+pub struct DeadCodeReport {
+    /// Entry points the analysis started from.
+    pub entry_points: Vec<DeadCodeItem>,
+    /// True when no CONFIGURATION was found and every PROGRAM was used as entry point.
+    pub implicit_entry_points: bool,
+    /// Symbols never reached from any entry point.
+    pub items: Vec<DeadCodeItem>,
+}
+
+/// Options for dead code analysis.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeadCodeOptions<'a> {
+    /// Files that participate in the analysis (defaults to all files).
+    pub files: Option<&'a FxHashSet<FileId>>,
+    /// Files whose symbols may be reported (defaults to `files`).
+    ///
+    /// Library sources typically take part in the reference graph but are
+    /// excluded here because their public API is consumed elsewhere.
+    pub report_files: Option<&'a FxHashSet<FileId>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct SymbolKey {
+    file_id: FileId,
+    symbol_id: SymbolId,
+}
+
+#[derive(Debug, Default)]
+struct ReferenceGraph {
+    /// Outgoing references per owning symbol.
+    edges: FxHashMap<SymbolKey, FxHashSet<SymbolKey>>,
+    /// Member names accessed (`x.Name`) per owning symbol, uppercased.
+    member_names: FxHashMap<SymbolKey, FxHashSet<SmolStr>>,
+    /// Symbols referenced from global scope.
+    global_roots: FxHashSet<SymbolKey>,
+}
+
+/// Runs dead code analysis over the workspace.
+pub fn analyze_dead_code(db: &Database, options: DeadCodeOptions<'_>) -> DeadCodeReport {
+    let mut file_ids: Vec<FileId> = match options.files {
+        Some(files) => files.iter().copied().collect(),
+        None => db.file_ids(),
+    };
+    file_ids.sort_by_key(|id| id.0);
+
+    let mut graph = ReferenceGraph::default();
+    let mut local_tables: FxHashMap<FileId, Arc<SymbolTable>> = FxHashMap::default();
+    for &file_id in &file_ids {
+        let symbols = match options.files {
+            Some(files) => db.file_symbols_with_project_filtered(file_id, files),
+            None => db.file_symbols_with_project(file_id),
+        };
+        collect_file_references(db, file_id, &symbols, &mut graph);
+        local_tables.insert(file_id, db.file_symbols(file_id));
+    }
+
+    let mut entry_keys = Vec::new();
+    for (&file_id, symbols) in &local_tables {
+        for symbol in symbols.iter() {
+            if symbol.origin.is_none() && matches!(symbol.kind, SymbolKind::Configuration) {
+                entry_keys.push(SymbolKey {
+                    file_id,
+                    symbol_id: symbol.id,
+                });
+            }
+        }
+    }
+    let implicit_entry_points = entry_keys.is_empty();
+    if implicit_entry_points {
+        for (&file_id, symbols) in &local_tables {
+            for symbol in symbols.iter() {
+                if symbol.origin.is_none() && matches!(symbol.kind, SymbolKind::Program) {
+                    entry_keys.push(SymbolKey {
+                        file_id,
+                        symbol_id: symbol.id,
+                    });
+                }
+            }
+        }
+    }
+
+    let reachable = walk_reachable(&graph, &local_tables, &entry_keys);
+
+    let mut entry_points: Vec<DeadCodeItem> = entry_keys
+        .iter()
+        .filter_map(|key| dead_code_item(&local_tables, *key, DeadCodeCategory::Pou))
+        .collect();
+    sort_items(&mut entry_points);
+
+    let report_files = options.report_files.or(options.files);
+    let mut items = Vec::new();
+    for &file_id in &file_ids {
+        if report_files.is_some_and(|files| !files.contains(&file_id)) {
+            continue;
+        }
+        let Some(symbols) = local_tables.get(&file_id) else {
+            continue;
+        };
+        for symbol in symbols.iter() {
+            if symbol.origin.is_some() || symbol.range.is_empty() {
+                continue;
+            }
+            let Some(category) = dead_code_category(symbols, symbol.id) else {
+                continue;
+            };
+            let key = SymbolKey {
+                file_id,
+                symbol_id: symbol.id,
+            };
+            if reachable.contains(&key) {
+                continue;
+            }
+            if let Some(item) = dead_code_item(&local_tables, key, category) {
+                items.push(item);
+            }
+        }
+    }
+    sort_items(&mut items);
+
+    DeadCodeReport {
+        entry_points,
+        implicit_entry_points,
+        items,
+    }
+}
+
+fn collect_file_references(
+    db: &Database,
+    file_id: FileId,
+    symbols: &SymbolTable,
+    graph: &mut ReferenceGraph,
+) {
+    let source = db.source_text(file_id);
+    let parsed = parse(&source);
+    let root = parsed.syntax();
+
+    for node in root.descendants() {
+        match node.kind() {
+            SyntaxKind::NameRef => {
+                let Some(target) = resolve_name_ref(symbols, &root, &node) else {
+                    continue;
+                };
+                let Some(target) = symbol_key(symbols, target, file_id) else {
+                    continue;
+                };
+                record_edge(graph, symbols, &root, &node, file_id, target);
+            }
+            SyntaxKind::Name => {
+                let target = if is_type_name_node(&node) {
+                    resolve_type_symbol_at_node(symbols, &root, &node)
+                } else if is_program_config_type_name(&node) {
+                    resolve_program_type(symbols, &root, &node)
+                } else {
+                    None
+                };
+                let Some(target) = target.and_then(|id| symbol_key(symbols, id, file_id)) else {
+                    continue;
+                };
+                record_edge(graph, symbols, &root, &node, file_id, target);
+            }
+            SyntaxKind::FieldExpr => {
+                let Some(member) = node.children().nth(1) else {
+                    continue;
+                };
+                let Some(name) = member
+                    .descendants_with_tokens()
+                    .filter_map(|element| element.into_token())
+                    .find(|token| token.kind() == SyntaxKind::Ident)
+                else {
+                    continue;
+                };
+                let Some(owner) = owner_key(symbols, &root, &node, file_id) else {
+                    continue;
+                };
+                graph
+                    .member_names
+                    .entry(owner)
+                    .or_default()
+                    .insert(SmolStr::new(name.text().to_ascii_uppercase()));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn record_edge(
+    graph: &mut ReferenceGraph,
+    symbols: &SymbolTable,
+    root: &SyntaxNode,
+    node: &SyntaxNode,
+    file_id: FileId,
+    target: SymbolKey,
+) {
+    match owner_key(symbols, root, node, file_id) {
+        Some(owner) if owner != target => {
+            graph.edges.entry(owner).or_default().insert(target);
+        }
+        Some(_) => {}
+        None => {
+            graph.global_roots.insert(target);
+        }
+    }
+}
+
+fn owner_key(
+    symbols: &SymbolTable,
+    root: &SyntaxNode,
+    node: &SyntaxNode,
+    file_id: FileId,
+) -> Option<SymbolKey> {
+    let scope_id = scope_at_position(symbols, root, node.text_range().start());
+    if scope_id == ScopeId::GLOBAL {
+        return None;
+    }
+    let owner = symbols.get_scope(scope_id)?.owner?;
+    let symbol = symbols.get(owner)?;
+    if matches!(symbol.kind, SymbolKind::Namespace) {
+        return None;
+    }
+    symbol_key(symbols, owner, file_id)
+}
+
+pub(crate) fn resolve_name_ref(
+    symbols: &SymbolTable,
+    root: &SyntaxNode,
+    node: &SyntaxNode,
+) -> Option<SymbolId> {
+    let ident = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find(|token| token.kind() == SyntaxKind::Ident)?;
+    let scope_id = scope_at_position(symbols, root, ident.text_range().start());
+    let symbol_id = symbols.resolve(ident.text(), scope_id)?;
+    let symbol = symbols.get(symbol_id)?;
+    if matches!(
+        symbol.kind,
+        SymbolKind::Variable {
+            qualifier: VarQualifier::External
+        }
+    ) {
+        // VAR_EXTERNAL only re-declares the global; the reference belongs to VAR_GLOBAL.
+        if let Some(global) = symbols.iter().find(|candidate| {
+            matches!(
+                candidate.kind,
+                SymbolKind::Variable {
+                    qualifier: VarQualifier::Global
+                } | SymbolKind::Constant
+            ) && candidate.name.eq_ignore_ascii_case(&symbol.name)
+        }) {
+            return Some(global.id);
+        }
+    }
+    Some(symbol_id)
+}
+
+fn is_program_config_type_name(node: &SyntaxNode) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    parent.kind() == SyntaxKind::QualifiedName
+        && parent
+            .parent()
+            .is_some_and(|grand| grand.kind() == SyntaxKind::ProgramConfig)
+}
+
+fn resolve_program_type(
+    symbols: &SymbolTable,
+    root: &SyntaxNode,
+    node: &SyntaxNode,
+) -> Option<SymbolId> {
+    let parts = qualified_name_parts_from_node(node)?;
+    let symbol_id = if parts.len() > 1 {
+        symbols.resolve_qualified(&parts)?
+    } else {
+        let scope_id = scope_at_position(symbols, root, node.text_range().start());
+        symbols
+            .resolve(parts[0].as_str(), scope_id)
+            .or_else(|| symbols.lookup_any(parts[0].as_str()))?
+    };
+    symbols
+        .get(symbol_id)
+        .filter(|symbol| matches!(symbol.kind, SymbolKind::Program))
+        .map(|symbol| symbol.id)
+}
+
+fn symbol_key(symbols: &SymbolTable, symbol_id: SymbolId, file_id: FileId) -> Option<SymbolKey> {
+    let symbol = symbols.get(symbol_id)?;
+    Some(match symbol.origin {
+        Some(origin) => SymbolKey {
+            file_id: origin.file_id,
+            symbol_id: origin.symbol_id,
+        },
+        None => SymbolKey { file_id, symbol_id },
+    })
+}
+
+fn walk_reachable(
+    graph: &ReferenceGraph,
+    tables: &FxHashMap<FileId, Arc<SymbolTable>>,
+    entry_keys: &[SymbolKey],
+) -> FxHashSet<SymbolKey> {
+    let mut reachable = FxHashSet::default();
+    let mut used_members: FxHashSet<SmolStr> = FxHashSet::default();
+    let mut worklist: Vec<SymbolKey> = entry_keys.to_vec();
+    worklist.extend(graph.global_roots.iter().copied());
+
+    loop {
+        while let Some(key) = worklist.pop() {
+            if !reachable.insert(key) {
+                continue;
+            }
+            if let Some(targets) = graph.edges.get(&key) {
+                worklist.extend(targets.iter().copied());
+            }
+            if let Some(names) = graph.member_names.get(&key) {
+                used_members.extend(names.iter().cloned());
+            }
+            if let Some(symbols) = tables.get(&key.file_id) {
+                if let Some(parent) = symbols.get(key.symbol_id).and_then(|sym| sym.parent) {
+                    worklist.push(SymbolKey {
+                        file_id: key.file_id,
+                        symbol_id: parent,
+                    });
+                }
+            }
+        }
+
+        // Members are reached through instances, so a method becomes live once its
+        // type is live and its name is accessed from live code (or it may be
+        // dispatched dynamically through EXTENDS/IMPLEMENTS).
+        for (&file_id, symbols) in tables {
+            for symbol in symbols.iter() {
+                if !matches!(
+                    symbol.kind,
+                    SymbolKind::Method { .. } | SymbolKind::Property { .. }
+                ) {
+                    continue;
+                }
+                let key = SymbolKey {
+                    file_id,
+                    symbol_id: symbol.id,
+                };
+                if reachable.contains(&key) {
+                    continue;
+                }
+                let Some(parent) = symbol.parent else {
+                    continue;
+                };
+                let parent_key = SymbolKey {
+                    file_id,
+                    symbol_id: parent,
+                };
+                if !reachable.contains(&parent_key) {
+                    continue;
+                }
+                let dispatched = symbol.modifiers.is_override
+                    || symbols.extends_name(parent).is_some()
+                    || symbols
+                        .implements_names(parent)
+                        .is_some_and(|names| !names.is_empty());
+                let accessed =
+                    used_members.contains(&SmolStr::new(symbol.name.to_ascii_uppercase()));
+                if dispatched || accessed {
+                    worklist.push(key);
+                }
+            }
+        }
+
+        if worklist.is_empty() {
+            break;
+        }
+    }
+
+    reachable
+}
+
+fn dead_code_category(symbols: &SymbolTable, symbol_id: SymbolId) -> Option<DeadCodeCategory> {
+    let symbol = symbols.get(symbol_id)?;
+    match symbol.kind {
+        SymbolKind::Program
+        | SymbolKind::Function { .. }
+        | SymbolKind::FunctionBlock
+        | SymbolKind::Class
+        | SymbolKind::Interface => {
+            let nested_in_type = symbol
+                .parent
+                .and_then(|id| symbols.get(id))
+                .is_some_and(|parent| !matches!(parent.kind, SymbolKind::Namespace));
+            (!nested_in_type).then_some(DeadCodeCategory::Pou)
+        }
+        SymbolKind::Method { .. } | SymbolKind::Property { .. } => {
+            let in_interface = symbol
+                .parent
+                .and_then(|id| symbols.get(id))
+                .is_some_and(|parent| matches!(parent.kind, SymbolKind::Interface));
+            (!in_interface).then_some(DeadCodeCategory::Method)
+        }
+        SymbolKind::Variable {
+            qualifier: VarQualifier::Global,
+        } => Some(DeadCodeCategory::Variable),
+        SymbolKind::Constant => {
+            let global = symbol
+                .parent
+                .and_then(|id| symbols.get(id))
+                .is_none_or(|parent| {
+                    matches!(
+                        parent.kind,
+                        SymbolKind::Configuration | SymbolKind::Resource | SymbolKind::Namespace
+                    )
+                });
+            global.then_some(DeadCodeCategory::Variable)
+        }
+        _ => None,
+    }
+}
+
+fn dead_code_item(
+    tables: &FxHashMap<FileId, Arc<SymbolTable>>,
+    key: SymbolKey,
+    category: DeadCodeCategory,
+) -> Option<DeadCodeItem> {
+    let symbols = tables.get(&key.file_id)?;
+    let symbol = symbols.get(key.symbol_id)?;
+    let container = match category {
+        DeadCodeCategory::Method => symbol
+            .parent
+            .and_then(|id| symbols.get(id))
+            .map(|parent| parent.name.clone()),
+        _ => None,
+    };
+    Some(DeadCodeItem {
+        name: symbol.name.clone(),
+        container,
+        kind: symbol.kind.clone(),
+        category,
+        file_id: key.file_id,
+        range: symbol.range,
+    })
+}
+
+fn sort_items(items: &mut [DeadCodeItem]) {
+    items.sort_by(|a, b| {
+        a.file_id
+            .0
+            .cmp(&b.file_id.0)
+            .then_with(|| a.range.start().cmp(&b.range.start()))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(report: &DeadCodeReport) -> Vec<String> {
+        report
+            .items
+            .iter()
+            .map(|item| match &item.container {
+                Some(container) => format!("{container}.{}", item.name),
+                None => item.name.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dead_code_reports_pous_unreachable_from_configuration() {
+        let source = r#"
+CONFIGURATION Conf
+VAR_GLOBAL
+    gUsed : INT;
+    gUnused : INT;
+END_VAR
+RESOURCE Res ON PLC
+    TASK Fast (INTERVAL := T#10ms, PRIORITY := 1);
+    PROGRAM Inst WITH Fast : Main;
+END_RESOURCE
+END_CONFIGURATION
+
+FUNCTION Helper : INT
+    Helper := 1;
+END_FUNCTION
+
+FUNCTION Orphan : INT
+    Orphan := 2;
+END_FUNCTION
+
+PROGRAM Main
+VAR_EXTERNAL
+    gUsed : INT;
+END_VAR
+    gUsed := Helper();
+END_PROGRAM
+
+PROGRAM Legacy
+END_PROGRAM
+"#;
+        let mut db = Database::new();
+        let file_id = FileId(0);
+        db.set_source_text(file_id, source.to_string());
+
+        let report = analyze_dead_code(&db, DeadCodeOptions::default());
+        let names = names(&report);
+        assert!(!report.implicit_entry_points);
+        assert!(names.iter().any(|name| name == "Orphan"), "{names:?}");
+        assert!(names.iter().any(|name| name == "Legacy"), "{names:?}");
+        assert!(names.iter().any(|name| name == "gUnused"), "{names:?}");
+        assert!(!names.iter().any(|name| name == "Helper"), "{names:?}");
+        assert!(!names.iter().any(|name| name == "Main"), "{names:?}");
+    }
+
+    #[test]
+    fn dead_code_tracks_methods_through_instances_and_respects_report_files() {
+        let library = r#"
+FUNCTION_BLOCK Motor
+METHOD Start : BOOL
+    Start := TRUE;
+END_METHOD
+METHOD Stop : BOOL
+    Stop := FALSE;
+END_METHOD
+END_FUNCTION_BLOCK
+
+FUNCTION LibUnused : INT
+    LibUnused := 0;
+END_FUNCTION
+"#;
+        let app = r#"
+PROGRAM Main
+VAR
+    m : Motor;
+    ok : BOOL;
+END_VAR
+    ok := m.Start();
+END_PROGRAM
+"#;
+        let mut db = Database::new();
+        let lib_file = FileId(0);
+        let app_file = FileId(1);
+        db.set_source_text(lib_file, library.to_string());
+        db.set_source_text(app_file, app.to_string());
+
+        let report = analyze_dead_code(&db, DeadCodeOptions::default());
+        let all = names(&report);
+        assert!(report.implicit_entry_points);
+        assert!(all.iter().any(|name| name == "Motor.Stop"), "{all:?}");
+        assert!(!all.iter().any(|name| name == "Motor.Start"), "{all:?}");
+        assert!(all.iter().any(|name| name == "LibUnused"), "{all:?}");
+
+        let mut report_files = FxHashSet::default();
+        report_files.insert(app_file);
+        let scoped = analyze_dead_code(
+            &db,
+            DeadCodeOptions {
+                files: None,
+                report_files: Some(&report_files),
+            },
+        );
+        assert!(scoped.items.iter().all(|item| item.file_id == app_file));
+    }
+}
@@ -0,0 +1,523 @@
+//! Expression path completion shared by editor and debugger clients.
+//!
+//! The engine works on the raw text of an expression (for example a debug
+//! console line such as `Motor.Axes[1, ` or a watch expression) and walks the
+//! access path through an [`ExprScope`]. Scopes decide what the path resolves
+//! to: the LSP walks declared types, while the runtime walks live values.
+
+use smol_str::SmolStr;
+
+use crate::completion::{CompletionItem, CompletionKind};
+
+/// Upper bound on enumerated index values for a single array dimension.
+const MAX_INDEX_ITEMS: i64 = 32;
+
+/// A segment of an already-typed access path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprPathSegment {
+    /// Named access (`a` or `.field`).
+    Field(SmolStr),
+    /// Subscript group with the given number of comma-separated indices.
+    Index(usize),
+}
+
+/// What the cursor is completing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExprCompletionTarget {
+    /// A root identifier.
+    Root,
+    /// A member after `.`.
+    Member,
+    /// A subscript inside `[...]` for the given zero-based dimension.
+    Index {
+        /// Dimension being completed.
+        dimension: usize,
+    },
+}
+
+/// Parsed completion request for an expression prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExprCompletionRequest {
+    /// Access path leading up to the completion point.
+    pub base: Vec<ExprPathSegment>,
+    /// What kind of completion is requested.
+    pub target: ExprCompletionTarget,
+    /// Partially typed text at the cursor.
+    pub prefix: SmolStr,
+    /// Byte offset where `prefix` starts in the input text.
+    pub replace_start: usize,
+}
+
+/// A named entry reachable through a scope.
+#[derive(Debug, Clone)]
+pub struct ExprMember<N> {
+    /// Member or variable name.
+    pub name: SmolStr,
+    /// Type or value detail shown next to the label.
+    pub detail: Option<SmolStr>,
+    /// Scope-specific handle used to continue the walk.
+    pub node: N,
+}
+
+/// Structure of a resolved path node.
+#[derive(Debug, Clone)]
+pub enum ExprShape<N> {
+    /// Struct, union, or instance members.
+    Members(Vec<ExprMember<N>>),
+    /// Array with per-dimension bounds.
+    Array {
+        /// Lower/upper bounds per dimension.
+        dimensions: Vec<(i64, i64)>,
+        /// Element node.
+        element: N,
+        /// Element type detail.
+        element_detail: Option<SmolStr>,
+    },
+    /// Nothing further to complete.
+    Leaf,
+}
+
+/// Resolves expression paths for completion.
+pub trait ExprScope {
+    /// Handle for a resolved path node (a type ID, a value reference, ...).
+    type Node: Clone;
+
+    /// Returns all root names visible to the expression.
+    fn roots(&self) -> Vec<ExprMember<Self::Node>>;
+
+    /// Resolves a single root name (case-insensitive).
+    fn root(&self, name: &str) -> Option<ExprMember<Self::Node>> {
+        self.roots()
+            .into_iter()
+            .find(|member| member.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Describes the structure of a resolved node.
+    fn shape(&self, node: &Self::Node) -> ExprShape<Self::Node>;
+}
+
+/// Parses the expression text before the cursor into a completion request.
+///
+/// Returns `None` when the cursor is not at a completable position (for
+/// example inside a string literal or after a closing parenthesis).
+#[must_use]
+pub fn parse_expression_prefix(text: &str) -> Option<ExprCompletionRequest> {
+    let bytes = text.as_bytes();
+    if bytes.iter().filter(|b| **b == b'\'' || **b == b'"').count() % 2 == 1 {
+        return None;
+    }
+    let replace_start = ident_start(bytes, bytes.len());
+    let prefix = SmolStr::new(&text[replace_start..]);
+    let before = skip_whitespace_back(bytes, replace_start);
+
+    if before > 0 && bytes[before - 1] == b'.' {
+        let base = parse_path_back(bytes, before - 1)?;
+        return Some(ExprCompletionRequest {
+            base,
+            target: ExprCompletionTarget::Member,
+            prefix,
+            replace_start,
+        });
+    }
+
+    let names_variable = prefix.starts_with(|ch: char| ch.is_ascii_alphabetic() || ch == '_');
+    if let Some((open, dimension)) = enclosing_subscript(bytes, replace_start) {
+        if !names_variable && before > 0 && matches!(bytes[before - 1], b'[' | b',') {
+            let base = parse_path_back(bytes, open)?;
+            return Some(ExprCompletionRequest {
+                base,
+                target: ExprCompletionTarget::Index { dimension },
+                prefix,
+                replace_start,
+            });
+        }
+    }
+
+    if before > 0 && matches!(bytes[before - 1], b')' | b']') {
+        return None;
+    }
+    Some(ExprCompletionRequest {
+        base: Vec::new(),
+        target: ExprCompletionTarget::Root,
+        prefix,
+        replace_start,
+    })
+}
+
+/// Computes completion items for an expression prefix using the given scope.
+#[must_use]
+pub fn complete_expression<S: ExprScope>(scope: &S, text: &str) -> Vec<CompletionItem> {
+    let Some(request) = parse_expression_prefix(text) else {
+        return Vec::new();
+    };
+    complete_request(scope, &request)
+}
+
+/// Computes completion items for an already parsed request.
+#[must_use]
+pub fn complete_request<S: ExprScope>(
+    scope: &S,
+    request: &ExprCompletionRequest,
+) -> Vec<CompletionItem> {
+    let prefix = request.prefix.as_str();
+    match request.target {
+        ExprCompletionTarget::Root => member_items(scope.roots(), prefix, CompletionKind::Variable),
+        ExprCompletionTarget::Member => {
+            let Some(node) = resolve_path(scope, &request.base) else {
+                return Vec::new();
+            };
+            match scope.shape(&node) {
+                ExprShape::Members(members) => {
+                    member_items(members, prefix, CompletionKind::Variable)
+                }
+                _ => Vec::new(),
+            }
+        }
+        ExprCompletionTarget::Index { dimension } => {
+            let Some(node) = resolve_path(scope, &request.base) else {
+                return Vec::new();
+            };
+            match scope.shape(&node) {
+                ExprShape::Array {
+                    dimensions,
+                    element_detail,
+                    ..
+                } => index_items(&dimensions, dimension, element_detail.as_deref(), prefix),
+                _ => Vec::new(),
+            }
+        }
+    }
+}
+
+/// Resolves an access path to a scope node.
+pub fn resolve_path<S: ExprScope>(scope: &S, path: &[ExprPathSegment]) -> Option<S::Node> {
+    let (first, rest) = path.split_first()?;
+    let ExprPathSegment::Field(name) = first else {
+        return None;
+    };
+    let mut node = scope.root(name)?.node;
+    for segment in rest {
+        node = match (segment, scope.shape(&node)) {
+            (ExprPathSegment::Field(name), ExprShape::Members(members)) => {
+                members
+                    .into_iter()
+                    .find(|member| member.name.eq_ignore_ascii_case(name))?
+                    .node
+            }
+            (
+                ExprPathSegment::Index(count),
+                ExprShape::Array {
+                    dimensions,
+                    element,
+                    ..
+                },
+            ) if *count == dimensions.len() => element,
+            _ => return None,
+        };
+    }
+    Some(node)
+}
+
+/// Formats array bounds the way they appear in declarations (`[0..9, 1..5]`).
+#[must_use]
+pub fn format_dimensions(dimensions: &[(i64, i64)]) -> String {
+    let parts = dimensions
+        .iter()
+        .map(|(lower, upper)| format!("{lower}..{upper}"))
+        .collect::<Vec<_>>();
+    format!("[{}]", parts.join(", "))
+}
+
+fn member_items<N>(
+    members: Vec<ExprMember<N>>,
+    prefix: &str,
+    kind: CompletionKind,
+) -> Vec<CompletionItem> {
+    members
+        .into_iter()
+        .filter(|member| starts_with_ignore_case(&member.name, prefix))
+        .map(|member| {
+            let mut item = CompletionItem::new(member.name, kind).with_priority(10);
+            if let Some(detail) = member.detail {
+                item = item.with_detail(detail);
+            }
+            item
+        })
+        .collect()
+}
+
+fn index_items(
+    dimensions: &[(i64, i64)],
+    dimension: usize,
+    element_detail: Option<&str>,
+    prefix: &str,
+) -> Vec<CompletionItem> {
+    let Some(&(lower, upper)) = dimensions.get(dimension) else {
+        return Vec::new();
+    };
+    let mut detail = format!(
+        "dimension {} of {}: {}..{}",
+        dimension + 1,
+        dimensions.len(),
+        lower,
+        upper
+    );
+    if let Some(element) = element_detail {
+        detail.push_str(&format!(
+            " (ARRAY{} OF {element})",
+            format_dimensions(dimensions)
+        ));
+    }
+    let detail = SmolStr::new(detail);
+
+    let values: Vec<i64> = if upper.saturating_sub(lower) < MAX_INDEX_ITEMS {
+        (lower..=upper).collect()
+    } else {
+        vec![lower, upper]
+    };
+    values
+        .into_iter()
+        .enumerate()
+        .filter_map(|(order, value)| {
+            let label = value.to_string();
+            if !label.starts_with(prefix) {
+                return None;
+            }
+            Some(
+                CompletionItem::new(label, CompletionKind::Constant)
+                    .with_detail(detail.clone())
+                    .with_priority(10 + order as u32),
+            )
+        })
+        .collect()
+}
+
+fn starts_with_ignore_case(value: &str, prefix: &str) -> bool {
+    value.len() >= prefix.len()
+        && value.as_bytes()[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+fn ident_start(bytes: &[u8], end: usize) -> usize {
+    let mut start = end;
+    while start > 0 && is_ident_byte(bytes[start - 1]) {
+        start -= 1;
+    }
+    start
+}
+
+fn skip_whitespace_back(bytes: &[u8], mut end: usize) -> usize {
+    while end > 0 && bytes[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+    end
+}
+
+/// Finds the unmatched `[` before `end` and the number of top-level commas after it.
+fn enclosing_subscript(bytes: &[u8], end: usize) -> Option<(usize, usize)> {
+    let mut depth = 0usize;
+    let mut commas = 0usize;
+    let mut idx = end;
+    while idx > 0 {
+        idx -= 1;
+        match bytes[idx] {
+            b']' | b')' => depth += 1,
+            b'(' => {
+                if depth == 0 {
+                    return None;
+                }
+                depth -= 1;
+            }
+            b'[' => {
+                if depth == 0 {
+                    return Some((idx, commas));
+                }
+                depth -= 1;
+            }
+            b',' if depth == 0 => commas += 1,
+            b';' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses an access path that ends right before `end` (exclusive), reading backwards.
+fn parse_path_back(bytes: &[u8], end: usize) -> Option<Vec<ExprPathSegment>> {
+    let mut segments = Vec::new();
+    let mut pos = skip_whitespace_back(bytes, end);
+    loop {
+        if pos == 0 {
+            return None;
+        }
+        if bytes[pos - 1] == b']' {
+            let (open, count) = matching_subscript(bytes, pos - 1)?;
+            segments.push(ExprPathSegment::Index(count));
+            pos = skip_whitespace_back(bytes, open);
+            continue;
+        }
+        let start = ident_start(bytes, pos);
+        if start == pos || bytes[start].is_ascii_digit() {
+            return None;
+        }
+        let name = std::str::from_utf8(&bytes[start..pos]).ok()?;
+        segments.push(ExprPathSegment::Field(SmolStr::new(name)));
+        let before = skip_whitespace_back(bytes, start);
+        if before > 0 && bytes[before - 1] == b'.' {
+            pos = skip_whitespace_back(bytes, before - 1);
+            continue;
+        }
+        break;
+    }
+    segments.reverse();
+    Some(segments)
+}
+
+/// Finds the `[` matching the `]` at `close` and counts its top-level subscripts.
+fn matching_subscript(bytes: &[u8], close: usize) -> Option<(usize, usize)> {
+    let mut depth = 0usize;
+    let mut commas = 0usize;
+    let mut idx = close;
+    while idx > 0 {
+        idx -= 1;
+        match bytes[idx] {
+            b']' | b')' => depth += 1,
+            b'(' => depth = depth.checked_sub(1)?,
+            b'[' => {
+                if depth == 0 {
+                    return Some((idx, commas + 1));
+                }
+                depth -= 1;
+            }
+            b',' if depth == 0 => commas += 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    enum Node {
+        Motor,
+        Axes,
+        Axis,
+        Scalar,
+    }
+
+    struct TestScope;
+
+    impl ExprScope for TestScope {
+        type Node = Node;
+
+        fn roots(&self) -> Vec<ExprMember<Node>> {
+            vec![
+                ExprMember {
+                    name: "Motor".into(),
+                    detail: Some("MotorData".into()),
+                    node: Node::Motor,
+                },
+                ExprMember {
+                    name: "Mode".into(),
+                    detail: Some("INT".into()),
+                    node: Node::Scalar,
+                },
+            ]
+        }
+
+        fn shape(&self, node: &Node) -> ExprShape<Node> {
+            match node {
+                Node::Motor => ExprShape::Members(vec![
+                    ExprMember {
+                        name: "Axes".into(),
+                        detail: None,
+                        node: Node::Axes,
+                    },
+                    ExprMember {
+                        name: "Speed".into(),
+                        detail: Some("REAL".into()),
+                        node: Node::Scalar,
+                    },
+                ]),
+                Node::Axes => ExprShape::Array {
+                    dimensions: vec![(1, 3), (0, 99)],
+                    element: Node::Axis,
+                    element_detail: Some("AxisData".into()),
+                },
+                Node::Axis => ExprShape::Members(vec![ExprMember {
+                    name: "Position".into(),
+                    detail: Some("LREAL".into()),
+                    node: Node::Scalar,
+                }]),
+                Node::Scalar => ExprShape::Leaf,
+            }
+        }
+    }
+
+    fn labels(items: &[CompletionItem]) -> Vec<&str> {
+        items.iter().map(|item| item.label.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_member_and_index_prefixes() {
+        let request = parse_expression_prefix("Motor.Axes[1, 2].Po").expect("request");
+        assert_eq!(request.target, ExprCompletionTarget::Member);
+        assert_eq!(request.prefix, "Po");
+        assert_eq!(
+            request.base,
+            vec![
+                ExprPathSegment::Field("Motor".into()),
+                ExprPathSegment::Field("Axes".into()),
+                ExprPathSegment::Index(2),
+            ]
+        );
+
+        let request = parse_expression_prefix("x + Motor.Axes[Mode, ").expect("request");
+        assert_eq!(request.target, ExprCompletionTarget::Index { dimension: 1 });
+        assert_eq!(request.prefix, "");
+
+        let request = parse_expression_prefix("Motor.Axes[Mo").expect("request");
+        assert_eq!(request.target, ExprCompletionTarget::Root);
+
+        assert!(parse_expression_prefix("'Motor.").is_none());
+    }
+
+    #[test]
+    fn completes_struct_members_through_array_elements() {
+        let items = complete_expression(&TestScope, "motor.");
+        assert_eq!(labels(&items), vec!["Axes", "Speed"]);
+
+        let items = complete_expression(&TestScope, "Motor.Axes[2, 10].");
+        assert_eq!(labels(&items), vec!["Position"]);
+
+        let items = complete_expression(&TestScope, "Motor.Axes[2].");
+        assert!(items.is_empty(), "partial subscripts should not resolve");
+
+        let items = complete_expression(&TestScope, "Mo");
+        assert_eq!(labels(&items), vec!["Motor", "Mode"]);
+    }
+
+    #[test]
+    fn completes_multi_dimensional_index_bounds() {
+        let items = complete_expression(&TestScope, "Motor.Axes[");
+        assert_eq!(labels(&items), vec!["1", "2", "3"]);
+        let detail = items[0].detail.as_deref().unwrap_or_default();
+        assert!(detail.contains("dimension 1 of 2: 1..3"), "{detail}");
+        assert!(
+            detail.contains("ARRAY[1..3, 0..99] OF AxisData"),
+            "{detail}"
+        );
+
+        let items = complete_expression(&TestScope, "Motor.Axes[1, ");
+        assert_eq!(labels(&items), vec!["0", "99"]);
+
+        let items = complete_expression(&TestScope, "Motor.Axes[1, 9");
+        assert_eq!(labels(&items), vec!["99"]);
+    }
+}
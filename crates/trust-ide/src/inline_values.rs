@@ -2,7 +2,7 @@ use rustc_hash::FxHashSet;
 use text_size::TextRange;
 
 use trust_hir::db::{FileId, SemanticDatabase};
-use trust_hir::symbols::{Symbol, VarQualifier};
+use trust_hir::symbols::{Symbol, SymbolTable, VarQualifier};
 use trust_hir::{Database, SourceDatabase, SymbolKind};
 use trust_syntax::parser::parse;
 use trust_syntax::syntax::SyntaxKind;
@@ -124,6 +124,140 @@ pub fn inline_value_hints(
     inline_value_data(db, file_id, range).hints
 }
 
+/// Returns runtime targets for the variables declared in a file.
+///
+/// Covers globals and the variables of PROGRAM, FUNCTION_BLOCK, and CLASS
+/// instances, which keep their storage between cycles and can be forced.
+/// Temporaries, VAR_IN_OUT, VAR_EXTERNAL, constants, and function or method
+/// locals are skipped. Each target's range is the declared name.
+pub fn runtime_declaration_targets(db: &Database, file_id: FileId) -> Vec<InlineValueTarget> {
+    let source = db.source_text(file_id);
+    let root = parse(&source).syntax();
+    let symbols = db.file_symbols(file_id);
+    symbols
+        .iter()
+        .filter(|symbol| symbol.origin.is_none() && !symbol.range.is_empty())
+        .filter_map(|symbol| {
+            let scope = match symbol.kind {
+                SymbolKind::Variable {
+                    qualifier: VarQualifier::Temp | VarQualifier::InOut | VarQualifier::External,
+                } => return None,
+                SymbolKind::Variable { .. } | SymbolKind::Parameter { .. } => {
+                    runtime_scope_for_symbol(&root, &source, symbol)?
+                }
+                _ => return None,
+            };
+            // Method and function locals live on the call stack, not in an instance.
+            let declared_in_instance = symbol
+                .parent
+                .and_then(|parent| symbols.get(parent))
+                .is_some_and(|parent| {
+                    matches!(
+                        parent.kind,
+                        SymbolKind::Program | SymbolKind::FunctionBlock | SymbolKind::Class
+                    )
+                });
+            if scope == InlineValueScope::Local && !declared_in_instance {
+                return None;
+            }
+            let owner = owning_pou_name(symbols.as_ref(), symbol);
+            Some(InlineValueTarget {
+                range: symbol.range,
+                name: symbol.name.clone(),
+                scope,
+                owner,
+            })
+        })
+        .collect()
+}
+
+/// Returns the source text in `range` with references to namespace members
+/// rewritten to their fully qualified names.
+///
+/// The runtime evaluates debug expressions without the file's NAMESPACE and
+/// USING context, so `Speed` inside `NAMESPACE Plant` becomes `Plant.Speed`.
+/// Member accesses and names that are already qualified are left unchanged.
+pub fn qualify_runtime_expression(
+    db: &Database,
+    file_id: FileId,
+    range: TextRange,
+) -> Option<String> {
+    let source = db.source_text(file_id);
+    let start = usize::from(range.start());
+    let end = usize::from(range.end()).min(source.len());
+    let selected = source.get(start..end)?;
+    if selected.trim().is_empty() {
+        return None;
+    }
+    let parsed = parse(&source);
+    let root = parsed.syntax();
+    let symbols = db.file_symbols_with_project(file_id);
+
+    let mut replacements = Vec::new();
+    for node in root
+        .descendants()
+        .filter(|node| node.kind() == SyntaxKind::NameRef)
+    {
+        let Some(ident_range) = ident_range_from_name_ref(&node) else {
+            continue;
+        };
+        if ident_range.start() < range.start() || ident_range.end() > range.end() {
+            continue;
+        }
+        let is_member = node.parent().is_some_and(|parent| {
+            parent.kind() == SyntaxKind::FieldExpr && parent.first_child().as_ref() != Some(&node)
+        });
+        if is_member {
+            continue;
+        }
+        let Some(ResolvedTarget::Symbol(symbol_id)) = resolve_target_at_position_with_context(
+            db,
+            file_id,
+            ident_range.start(),
+            &source,
+            &root,
+            &symbols,
+        ) else {
+            continue;
+        };
+        let Some(symbol) = symbols.get(symbol_id) else {
+            continue;
+        };
+        let Some(path) = namespace_member_path(symbols.as_ref(), symbol) else {
+            continue;
+        };
+        replacements.push((ident_range, path));
+    }
+
+    let mut text = selected.to_string();
+    replacements.sort_by_key(|(range, _)| std::cmp::Reverse(range.start()));
+    for (ident_range, path) in replacements {
+        let local_start = usize::from(ident_range.start()) - start;
+        let local_end = usize::from(ident_range.end()) - start;
+        text.replace_range(local_start..local_end, &path);
+    }
+    Some(text.trim().to_string())
+}
+
+/// Returns `Ns.Name` for symbols declared directly inside namespaces.
+fn namespace_member_path(symbols: &SymbolTable, symbol: &Symbol) -> Option<String> {
+    let mut parts = vec![symbol.name.to_string()];
+    let mut current = symbol.parent;
+    while let Some(parent_id) = current {
+        let parent = symbols.get(parent_id)?;
+        if !matches!(parent.kind, SymbolKind::Namespace) {
+            return None;
+        }
+        parts.push(parent.name.to_string());
+        current = parent.parent;
+    }
+    if parts.len() < 2 {
+        return None;
+    }
+    parts.reverse();
+    Some(parts.join("."))
+}
+
 fn inline_text_for_symbol(db: &Database, file_id: FileId, symbol: &Symbol) -> Option<String> {
     match symbol.kind {
         SymbolKind::Constant => inline_text_for_constant(db, file_id, symbol),
@@ -248,4 +382,83 @@ END_PROGRAM
 
         assert!(hints.iter().any(|hint| hint.text == " = 42"));
     }
+
+    #[test]
+    fn runtime_declaration_targets_cover_globals_and_instance_variables() {
+        let source = r#"
+CONFIGURATION Conf
+VAR_GLOBAL
+    g : INT;
+END_VAR
+VAR_GLOBAL RETAIN
+    r : INT;
+END_VAR
+END_CONFIGURATION
+
+FUNCTION Helper : INT
+VAR
+    scratch : INT;
+END_VAR
+    Helper := scratch;
+END_FUNCTION
+
+PROGRAM Main
+VAR
+    x : INT;
+END_VAR
+VAR_TEMP
+    t : INT;
+END_VAR
+    x := t;
+END_PROGRAM
+"#;
+        let mut db = Database::new();
+        let file_id = FileId(0);
+        db.set_source_text(file_id, source.to_string());
+
+        let targets = runtime_declaration_targets(&db, file_id);
+        let summary: Vec<(&str, InlineValueScope, Option<&str>)> = targets
+            .iter()
+            .map(|target| (target.name.as_str(), target.scope, target.owner.as_deref()))
+            .collect();
+        assert!(summary.contains(&("g", InlineValueScope::Global, None)));
+        assert!(summary.contains(&("r", InlineValueScope::Retain, None)));
+        assert!(summary.contains(&("x", InlineValueScope::Local, Some("Main"))));
+        assert!(!summary
+            .iter()
+            .any(|(name, _, _)| *name == "t" || *name == "scratch"));
+    }
+
+    #[test]
+    fn qualify_runtime_expression_adds_namespace_paths() {
+        let source = r#"
+NAMESPACE Plant
+FUNCTION Scale : INT
+VAR_INPUT
+    A : INT;
+END_VAR
+    Scale := A * 2;
+END_FUNCTION
+END_NAMESPACE
+
+PROGRAM Main
+USING Plant;
+VAR
+    speed : INT;
+END_VAR
+    speed := Scale(speed) + 1;
+END_PROGRAM
+"#;
+        let mut db = Database::new();
+        let file_id = FileId(0);
+        db.set_source_text(file_id, source.to_string());
+
+        let start = source.find("Scale(speed)").unwrap();
+        let end = start + "Scale(speed) + 1".len();
+        let range = TextRange::new(TextSize::from(start as u32), TextSize::from(end as u32));
+        assert_eq!(
+            qualify_runtime_expression(&db, file_id, range).as_deref(),
+            Some("Plant.Scale(speed) + 1")
+        );
+    }
 }
@@ -21,7 +21,11 @@
 
 pub mod call_hierarchy;
 pub mod completion;
+pub mod cross_reference;
+pub mod data_flow;
+pub mod dead_code;
 pub mod diagnostics;
+pub mod expr_completion;
 pub mod goto_def;
 pub mod hover;
 pub mod implementation;
@@ -29,46 +33,80 @@ pub mod inlay_hints;
 /// Inline value hints for constant/enum references.
 pub mod inline_values;
 pub mod linked_editing;
+pub mod lint;
+pub mod project_graph;
 pub mod refactor;
 pub mod references;
 pub mod rename;
 pub mod selection_range;
 pub mod semantic_tokens;
 pub mod signature_help;
+pub mod stack_usage;
 pub mod stdlib_docs;
 pub mod type_hierarchy;
 pub mod util;
 /// Shared helpers for VAR/CONSTANT declaration inspection.
 pub mod var_decl;
+pub mod wcet;
 
 pub use call_hierarchy::{
     incoming_calls, incoming_calls_in_files, outgoing_calls, outgoing_calls_in_files,
     prepare_call_hierarchy, prepare_call_hierarchy_in_files, CallHierarchyIncomingCall,
     CallHierarchyItem, CallHierarchyOutgoingCall,
 };
-pub use completion::{complete, complete_with_filter, CompletionItem, CompletionKind};
+pub use completion::{
+    call_template_completion, complete, complete_with_dialect, complete_with_filter,
+    CallTemplateParams, CompletionItem, CompletionKind,
+};
+pub use cross_reference::{
+    analyze_cross_references, CrossReferenceEntry, CrossReferenceKind, CrossReferenceLocation,
+    CrossReferenceOptions, CrossReferenceReport, CrossReferenceUse,
+};
+pub use data_flow::{trace_drivers, DataFlowNode, DataFlowNodeKind, DataFlowOptions};
+pub use dead_code::{
+    analyze_dead_code, DeadCodeCategory, DeadCodeItem, DeadCodeOptions, DeadCodeReport,
+};
+pub use expr_completion::{
+    complete_expression, parse_expression_prefix, ExprCompletionRequest, ExprCompletionTarget,
+    ExprMember, ExprPathSegment, ExprScope, ExprShape,
+};
 pub use goto_def::{goto_declaration, goto_definition, goto_type_definition, DefinitionResult};
 pub use hover::{hover, hover_with_filter, HoverResult};
 pub use implementation::{goto_implementation, ImplementationResult};
 pub use inlay_hints::{inlay_hints, InlayHint, InlayHintKind};
 pub use inline_values::{
-    inline_value_data, inline_value_hints, InlineValueData, InlineValueHint, InlineValueScope,
-    InlineValueTarget,
+    inline_value_data, inline_value_hints, qualify_runtime_expression, runtime_declaration_targets,
+    InlineValueData, InlineValueHint, InlineValueScope, InlineValueTarget,
 };
 pub use linked_editing::linked_editing_ranges;
+pub use lint::{lint_file, LintFinding, LintOptions, LintRule, NamingConventions, NamingStyle};
+pub use project_graph::{
+    analyze_project_graph, Graph, GraphEdge, GraphEdgeKind, GraphEntryPoint, GraphNode,
+    ProjectGraph, ProjectGraphOptions,
+};
 pub use refactor::{
     convert_function_block_to_function, convert_function_to_function_block, extract_method,
-    extract_pou, extract_property, generate_interface_stubs, inline_symbol, move_namespace_path,
-    ExtractResult, ExtractTargetKind, InlineResult, InlineTargetKind,
+    extract_pou, extract_property, generate_interface_stubs, global_constant_lists, inline_symbol,
+    introduce_constant, move_namespace_path, organize_declarations, ConstantTarget,
+    DeclarationSort, ExtractResult, ExtractTargetKind, GlobalConstantList, InlineResult,
+    InlineTargetKind,
+};
+pub use references::{
+    classify_references, find_direct_address_references, find_references, ClassifiedReference,
+    FindReferencesOptions, Reference, ReferenceAccess,
 };
-pub use references::{find_references, FindReferencesOptions, Reference};
 pub use rename::rename;
 pub use selection_range::{selection_ranges, SelectionRange};
 pub use semantic_tokens::{semantic_tokens, SemanticToken, SemanticTokenType};
 pub use signature_help::{
-    call_signature_info, signature_help, CallSignatureInfo, CallSignatureParam, Signature,
-    SignatureHelpResult, SignatureParameter,
+    call_argument_type, call_signature_info, signature_help, CallSignatureInfo, CallSignatureParam,
+    Signature, SignatureHelpResult, SignatureParameter,
+};
+pub use stack_usage::{
+    analyze_stack_usage, RecursiveCall, StackFrame, StackUsageEntry, StackUsageOptions,
+    StackUsageReport,
 };
 pub use stdlib_docs::StdlibFilter;
 pub use type_hierarchy::{prepare_type_hierarchy, subtypes, supertypes, TypeHierarchyItem};
 pub use util::symbol_name_at_position;
+pub use wcet::{analyze_wcet, MeasuredTiming, WcetOptions, WcetProgram, WcetReport, WcetTask};
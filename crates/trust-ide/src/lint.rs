@@ -0,0 +1,631 @@
+//! Configurable lint rules for Structured Text.
+//!
+//! The lint engine runs a catalogue of coding-standard rules (in the spirit of
+//! MISRA-style guidelines) over the parsed syntax tree and the file symbol
+//! table. Rules are opt-in: callers pass the enabled rules and their options,
+//! and map findings onto their own severity model.
+
+use smol_str::SmolStr;
+use text_size::TextRange;
+
+use trust_hir::db::{FileId, SemanticDatabase, SourceDatabase};
+use trust_hir::symbols::{SymbolKind, VarQualifier};
+use trust_hir::Database;
+use trust_syntax::parser::parse;
+use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
+
+use crate::util::is_pou_kind;
+
+/// A lint rule in the catalogue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LintRule {
+    /// Control statements nested deeper than the configured limit.
+    NestingDepth,
+    /// Loops left through more than one EXIT, and JMP statements.
+    LoopExit,
+    /// Declarations that do not follow the configured naming conventions.
+    Naming,
+    /// Calls to functions listed as forbidden.
+    ForbiddenFunctions,
+    /// Numeric literals in statements that are not in the allowed list.
+    MagicNumbers,
+}
+
+impl LintRule {
+    /// Every rule in the catalogue.
+    pub const ALL: [LintRule; 5] = [
+        LintRule::NestingDepth,
+        LintRule::LoopExit,
+        LintRule::Naming,
+        LintRule::ForbiddenFunctions,
+        LintRule::MagicNumbers,
+    ];
+
+    /// Returns the diagnostic code reported for the rule (e.g., "R001").
+    #[must_use]
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::NestingDepth => "R001",
+            Self::LoopExit => "R002",
+            Self::Naming => "R003",
+            Self::ForbiddenFunctions => "R004",
+            Self::MagicNumbers => "R005",
+        }
+    }
+
+    /// Returns the configuration key for the rule.
+    #[must_use]
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::NestingDepth => "nesting_depth",
+            Self::LoopExit => "loop_exit",
+            Self::Naming => "naming",
+            Self::ForbiddenFunctions => "forbidden_functions",
+            Self::MagicNumbers => "magic_numbers",
+        }
+    }
+
+    /// Looks up a rule by configuration key or diagnostic code.
+    #[must_use]
+    pub fn from_key(value: &str) -> Option<Self> {
+        let value = value.trim();
+        Self::ALL.into_iter().find(|rule| {
+            rule.key().eq_ignore_ascii_case(&value.replace('-', "_"))
+                || rule.code().eq_ignore_ascii_case(value)
+        })
+    }
+}
+
+/// Identifier casing style for the naming rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamingStyle {
+    /// `MotorControl`
+    PascalCase,
+    /// `motorControl`
+    CamelCase,
+    /// `motor_control`
+    SnakeCase,
+    /// `MOTOR_CONTROL`
+    UpperSnakeCase,
+}
+
+impl NamingStyle {
+    /// Parses a style name (`PascalCase`, `camelCase`, `snake_case`, `UPPER_CASE`).
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "pascalcase" | "pascal" => Some(Self::PascalCase),
+            "camelcase" | "camel" => Some(Self::CamelCase),
+            "snake_case" | "snake" => Some(Self::SnakeCase),
+            "upper_case" | "upper_snake_case" | "screaming_snake_case" | "upper" => {
+                Some(Self::UpperSnakeCase)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the display label for the style.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::PascalCase => "PascalCase",
+            Self::CamelCase => "camelCase",
+            Self::SnakeCase => "snake_case",
+            Self::UpperSnakeCase => "UPPER_CASE",
+        }
+    }
+
+    /// Returns true when `name` already follows the style.
+    #[must_use]
+    pub fn matches(self, name: &str) -> bool {
+        let Some(first) = name.chars().next() else {
+            return true;
+        };
+        match self {
+            Self::PascalCase => first.is_ascii_uppercase() && !name.contains('_'),
+            Self::CamelCase => first.is_ascii_lowercase() && !name.contains('_'),
+            Self::SnakeCase => !name.chars().any(|ch| ch.is_ascii_uppercase()),
+            Self::UpperSnakeCase => !name.chars().any(|ch| ch.is_ascii_lowercase()),
+        }
+    }
+
+    /// Converts `name` to the style.
+    #[must_use]
+    pub fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+        match self {
+            Self::PascalCase => words.iter().map(|word| capitalize(word)).collect(),
+            Self::CamelCase => words
+                .iter()
+                .enumerate()
+                .map(|(idx, word)| {
+                    if idx == 0 {
+                        word.to_ascii_lowercase()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            Self::SnakeCase => words
+                .iter()
+                .map(|word| word.to_ascii_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Self::UpperSnakeCase => words
+                .iter()
+                .map(|word| word.to_ascii_uppercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+        }
+    }
+}
+
+/// Naming conventions per declaration category (`None` leaves it unchecked).
+#[derive(Debug, Clone, Default)]
+pub struct NamingConventions {
+    /// Variables and parameters.
+    pub variables: Option<NamingStyle>,
+    /// Constants (`VAR CONSTANT` and global constants).
+    pub constants: Option<NamingStyle>,
+    /// Programs, functions, function blocks, classes, methods, and properties.
+    pub pous: Option<NamingStyle>,
+    /// Type definitions and interfaces.
+    pub types: Option<NamingStyle>,
+}
+
+/// Options for a lint run.
+#[derive(Debug, Clone)]
+pub struct LintOptions {
+    /// Rules to run.
+    pub rules: Vec<LintRule>,
+    /// Maximum nesting depth of IF/CASE/FOR/WHILE/REPEAT statements.
+    pub max_nesting_depth: usize,
+    /// Function names that must not be called (case-insensitive).
+    pub forbidden_functions: Vec<SmolStr>,
+    /// Numeric literals allowed inside statements.
+    pub allowed_numbers: Vec<SmolStr>,
+    /// Naming conventions for the naming rule.
+    pub naming: NamingConventions,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self {
+            rules: LintRule::ALL.to_vec(),
+            max_nesting_depth: 4,
+            forbidden_functions: Vec::new(),
+            allowed_numbers: vec![SmolStr::new("0"), SmolStr::new("1")],
+            naming: NamingConventions::default(),
+        }
+    }
+}
+
+impl LintOptions {
+    fn enabled(&self, rule: LintRule) -> bool {
+        self.rules.contains(&rule)
+    }
+}
+
+/// A single lint finding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    /// Rule that produced the finding.
+    pub rule: LintRule,
+    /// Range of the offending construct.
+    pub range: TextRange,
+    /// Human-readable message.
+    pub message: String,
+    /// Suggested replacement name (naming rule only).
+    pub rename_to: Option<SmolStr>,
+}
+
+/// Runs the enabled lint rules over a file.
+pub fn lint_file(db: &Database, file_id: FileId, options: &LintOptions) -> Vec<LintFinding> {
+    if options.rules.is_empty() {
+        return Vec::new();
+    }
+    let source = db.source_text(file_id);
+    let parsed = parse(&source);
+    let root = parsed.syntax();
+    let mut findings = Vec::new();
+
+    if options.enabled(LintRule::NestingDepth) {
+        check_nesting_depth(&root, options.max_nesting_depth, &mut findings);
+    }
+    if options.enabled(LintRule::LoopExit) {
+        check_loop_exits(&root, &mut findings);
+    }
+    if options.enabled(LintRule::Naming) {
+        check_naming(db, file_id, &options.naming, &mut findings);
+    }
+    if options.enabled(LintRule::ForbiddenFunctions) && !options.forbidden_functions.is_empty() {
+        check_forbidden_functions(&root, &options.forbidden_functions, &mut findings);
+    }
+    if options.enabled(LintRule::MagicNumbers) {
+        check_magic_numbers(&root, &options.allowed_numbers, &mut findings);
+    }
+
+    findings.sort_by_key(|finding| (finding.range.start(), finding.rule));
+    findings
+}
+
+fn finding(rule: LintRule, range: TextRange, message: String) -> LintFinding {
+    LintFinding {
+        rule,
+        range,
+        message,
+        rename_to: None,
+    }
+}
+
+fn is_nesting_kind(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::IfStmt
+            | SyntaxKind::CaseStmt
+            | SyntaxKind::ForStmt
+            | SyntaxKind::WhileStmt
+            | SyntaxKind::RepeatStmt
+    )
+}
+
+fn is_loop_kind(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::ForStmt | SyntaxKind::WhileStmt | SyntaxKind::RepeatStmt
+    )
+}
+
+/// Number of enclosing nodes matching `pred` up to the owning POU.
+fn enclosing_count(node: &SyntaxNode, pred: fn(SyntaxKind) -> bool) -> usize {
+    node.ancestors()
+        .skip(1)
+        .take_while(|ancestor| !is_pou_kind(ancestor.kind()))
+        .filter(|ancestor| pred(ancestor.kind()))
+        .count()
+}
+
+fn keyword_range(node: &SyntaxNode) -> TextRange {
+    node.first_token()
+        .map(|token| token.text_range())
+        .unwrap_or_else(|| node.text_range())
+}
+
+fn check_nesting_depth(root: &SyntaxNode, max_depth: usize, findings: &mut Vec<LintFinding>) {
+    for node in root
+        .descendants()
+        .filter(|node| is_nesting_kind(node.kind()))
+    {
+        // Report only the statement that crosses the limit, not every deeper one.
+        let depth = enclosing_count(&node, is_nesting_kind) + 1;
+        if depth != max_depth + 1 {
+            continue;
+        }
+        findings.push(finding(
+            LintRule::NestingDepth,
+            keyword_range(&node),
+            format!("statement nesting depth {depth} exceeds the maximum of {max_depth}"),
+        ));
+    }
+}
+
+fn check_loop_exits(root: &SyntaxNode, findings: &mut Vec<LintFinding>) {
+    for loop_node in root.descendants().filter(|node| is_loop_kind(node.kind())) {
+        let exits = loop_node
+            .descendants()
+            .filter(|node| node.kind() == SyntaxKind::ExitStmt)
+            .filter(|exit| {
+                exit.ancestors()
+                    .skip(1)
+                    .find(|ancestor| is_loop_kind(ancestor.kind()))
+                    .is_some_and(|owner| owner == loop_node)
+            })
+            .skip(1);
+        for exit in exits {
+            findings.push(finding(
+                LintRule::LoopExit,
+                exit.text_range(),
+                "loop is terminated by more than one EXIT; fold the condition into the loop header"
+                    .to_string(),
+            ));
+        }
+    }
+    for jump in root
+        .descendants()
+        .filter(|node| node.kind() == SyntaxKind::JmpStmt)
+    {
+        findings.push(finding(
+            LintRule::LoopExit,
+            jump.text_range(),
+            "JMP is goto-style control flow; use structured statements instead".to_string(),
+        ));
+    }
+}
+
+fn check_naming(
+    db: &Database,
+    file_id: FileId,
+    naming: &NamingConventions,
+    findings: &mut Vec<LintFinding>,
+) {
+    let symbols = db.file_symbols(file_id);
+    for symbol in symbols.iter() {
+        if symbol.origin.is_some() || symbol.range.is_empty() {
+            continue;
+        }
+        let (category, style) = match &symbol.kind {
+            SymbolKind::Variable {
+                qualifier: VarQualifier::External,
+            } => continue,
+            SymbolKind::Variable { .. } | SymbolKind::Parameter { .. } => {
+                ("variable", naming.variables)
+            }
+            SymbolKind::Constant => ("constant", naming.constants),
+            SymbolKind::Program
+            | SymbolKind::Function { .. }
+            | SymbolKind::FunctionBlock
+            | SymbolKind::Class
+            | SymbolKind::Method { .. }
+            | SymbolKind::Property { .. } => ("POU", naming.pous),
+            SymbolKind::Type | SymbolKind::Interface => ("type", naming.types),
+            _ => continue,
+        };
+        let Some(style) = style else {
+            continue;
+        };
+        if style.matches(&symbol.name) {
+            continue;
+        }
+        let suggestion = style.apply(&symbol.name);
+        let message = if suggestion.is_empty() || suggestion == symbol.name.as_str() {
+            format!(
+                "{category} '{}' does not follow {} naming",
+                symbol.name,
+                style.label()
+            )
+        } else {
+            format!(
+                "{category} '{}' does not follow {} naming; rename to '{suggestion}'",
+                symbol.name,
+                style.label()
+            )
+        };
+        findings.push(LintFinding {
+            rule: LintRule::Naming,
+            range: symbol.range,
+            message,
+            rename_to: (!suggestion.is_empty() && suggestion != symbol.name.as_str())
+                .then(|| SmolStr::new(&suggestion)),
+        });
+    }
+}
+
+fn check_forbidden_functions(
+    root: &SyntaxNode,
+    forbidden: &[SmolStr],
+    findings: &mut Vec<LintFinding>,
+) {
+    for call in root
+        .descendants()
+        .filter(|node| node.kind() == SyntaxKind::CallExpr)
+    {
+        let Some(callee) = call.first_child() else {
+            continue;
+        };
+        if callee.kind() != SyntaxKind::NameRef {
+            continue;
+        }
+        let name = callee.text().to_string();
+        let name = name.trim();
+        if forbidden
+            .iter()
+            .any(|forbidden| forbidden.eq_ignore_ascii_case(name))
+        {
+            findings.push(finding(
+                LintRule::ForbiddenFunctions,
+                callee.text_range(),
+                format!("call to forbidden function '{name}'"),
+            ));
+        }
+    }
+}
+
+fn check_magic_numbers(root: &SyntaxNode, allowed: &[SmolStr], findings: &mut Vec<LintFinding>) {
+    for literal in root
+        .descendants()
+        .filter(|node| node.kind() == SyntaxKind::Literal)
+    {
+        let Some(value) = numeric_literal_text(&literal) else {
+            continue;
+        };
+        let in_statement = literal.ancestors().skip(1).all(|ancestor| {
+            !matches!(
+                ancestor.kind(),
+                SyntaxKind::VarBlock | SyntaxKind::TypeDecl | SyntaxKind::CaseLabel
+            )
+        }) && literal
+            .ancestors()
+            .any(|ancestor| is_pou_kind(ancestor.kind()));
+        if !in_statement || number_allowed(&value, allowed) {
+            continue;
+        }
+        findings.push(finding(
+            LintRule::MagicNumbers,
+            literal.text_range(),
+            format!("magic number '{value}'; introduce a named constant"),
+        ));
+    }
+}
+
+/// Returns the numeric part of an integer/real literal (without typed prefix).
+fn numeric_literal_text(literal: &SyntaxNode) -> Option<String> {
+    literal
+        .children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find(|token| {
+            matches!(
+                token.kind(),
+                SyntaxKind::IntLiteral | SyntaxKind::RealLiteral
+            )
+        })
+        .map(|token| token.text().to_string())
+}
+
+fn number_allowed(value: &str, allowed: &[SmolStr]) -> bool {
+    let normalized = normalize_number(value);
+    let numeric = normalized.parse::<f64>().ok();
+    allowed.iter().any(|candidate| {
+        let candidate = normalize_number(candidate);
+        candidate == normalized
+            || numeric
+                .zip(candidate.parse::<f64>().ok())
+                .is_some_and(|(a, b)| a == b)
+    })
+}
+
+fn normalize_number(value: &str) -> String {
+    value
+        .trim()
+        .chars()
+        .filter(|ch| *ch != '_')
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut current = String::new();
+        for (idx, ch) in chars.iter().enumerate() {
+            // Split `speedLimit` / `Axis2Pos` and the end of acronyms (`PIDLoop`).
+            let boundary = idx > 0
+                && ch.is_ascii_uppercase()
+                && (chars[idx - 1].is_ascii_lowercase()
+                    || chars[idx - 1].is_ascii_digit()
+                    || (chars[idx - 1].is_ascii_uppercase()
+                        && chars
+                            .get(idx + 1)
+                            .is_some_and(|next| next.is_ascii_lowercase())));
+            if boundary && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            current.push(*ch);
+        }
+        if !current.is_empty() {
+            words.push(current);
+        }
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => {
+            first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()
+        }
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(source: &str, options: &LintOptions) -> Vec<LintFinding> {
+        let mut db = Database::new();
+        let file_id = FileId(0);
+        db.set_source_text(file_id, source.to_string());
+        lint_file(&db, file_id, options)
+    }
+
+    fn codes(findings: &[LintFinding]) -> Vec<&'static str> {
+        findings.iter().map(|finding| finding.rule.code()).collect()
+    }
+
+    #[test]
+    fn reports_nesting_exit_forbidden_and_magic_numbers() {
+        let source = r#"
+PROGRAM Main
+VAR
+    i : INT;
+    x : INT := 42;
+END_VAR
+    FOR i := 0 TO 10 DO
+        IF i > 1 THEN
+            IF x > 0 THEN
+                EXIT;
+            END_IF;
+            EXIT;
+        END_IF;
+    END_FOR;
+    x := SEL(TRUE, 0, 1);
+END_PROGRAM
+"#;
+        let options = LintOptions {
+            max_nesting_depth: 2,
+            forbidden_functions: vec![SmolStr::new("sel")],
+            ..LintOptions::default()
+        };
+        let findings = lint(source, &options);
+        let codes = codes(&findings);
+        assert_eq!(
+            codes.iter().filter(|code| **code == "R001").count(),
+            1,
+            "{findings:?}"
+        );
+        assert_eq!(codes.iter().filter(|code| **code == "R002").count(), 1);
+        assert_eq!(codes.iter().filter(|code| **code == "R004").count(), 1);
+        // Only the loop bound 10 is magic: 0/1 are allowed and 42 is a declaration.
+        let magic: Vec<_> = findings
+            .iter()
+            .filter(|finding| finding.rule == LintRule::MagicNumbers)
+            .map(|finding| finding.message.as_str())
+            .collect();
+        assert_eq!(magic, vec!["magic number '10'; introduce a named constant"]);
+    }
+
+    #[test]
+    fn naming_rule_suggests_converted_names() {
+        let source = r#"
+FUNCTION_BLOCK motor_control
+VAR
+    Speed_Setpoint : REAL;
+    lastError : INT;
+END_VAR
+END_FUNCTION_BLOCK
+"#;
+        let options = LintOptions {
+            rules: vec![LintRule::Naming],
+            naming: NamingConventions {
+                variables: Some(NamingStyle::CamelCase),
+                pous: Some(NamingStyle::PascalCase),
+                ..NamingConventions::default()
+            },
+            ..LintOptions::default()
+        };
+        let findings = lint(source, &options);
+        let renames: Vec<_> = findings
+            .iter()
+            .filter_map(|finding| finding.rename_to.as_deref())
+            .collect();
+        assert_eq!(renames, vec!["MotorControl", "speedSetpoint"]);
+    }
+
+    #[test]
+    fn naming_style_conversions() {
+        assert_eq!(
+            NamingStyle::PascalCase.apply("PIDController"),
+            "PidController"
+        );
+        assert_eq!(NamingStyle::UpperSnakeCase.apply("maxSpeed2"), "MAX_SPEED2");
+        assert_eq!(NamingStyle::SnakeCase.apply("MaxSpeed"), "max_speed");
+        assert!(NamingStyle::UpperSnakeCase.matches("MAX_SPEED"));
+        assert_eq!(
+            LintRule::from_key("magic-numbers"),
+            Some(LintRule::MagicNumbers)
+        );
+        assert_eq!(LintRule::from_key("R001"), Some(LintRule::NestingDepth));
+    }
+}
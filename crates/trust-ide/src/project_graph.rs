@@ -0,0 +1,607 @@
+//! Whole-project call graph and POU dependency graph export.
+//!
+//! The call graph has one node per callable POU (programs, functions,
+//! function blocks, methods, and properties) and one edge per distinct
+//! caller/callee pair. The dependency graph folds methods and properties into
+//! their owning type and records how program organization units depend on each
+//! other: calls, FUNCTION_BLOCK/CLASS instances, interface references, EXTENDS,
+//! and IMPLEMENTS.
+//!
+//! Both graphs can be restricted to what the program instances of one task
+//! reach, which is the view architecture reviews use to check layering.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use smol_str::SmolStr;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::sync::Arc;
+use text_size::TextRange;
+
+use trust_hir::db::FileId;
+use trust_hir::symbols::{SymbolId, SymbolKind, SymbolTable};
+use trust_hir::{Database, Type, TypeId};
+
+use crate::stack_usage::{instance_type_symbol, symbol_key, CallGraph, SymbolKey};
+
+/// Nesting limit when looking through array element types.
+const MAX_TYPE_DEPTH: usize = 16;
+
+/// How one node of a graph depends on another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GraphEdgeKind {
+    /// The source calls the target.
+    Call,
+    /// The source declares an instance of the target FUNCTION_BLOCK or CLASS.
+    Instance,
+    /// The source declares a variable of the target INTERFACE type.
+    Reference,
+    /// The source EXTENDS the target.
+    Extends,
+    /// The source IMPLEMENTS the target.
+    Implements,
+}
+
+impl GraphEdgeKind {
+    /// Returns the lowercase label used in DOT and JSON output.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Call => "call",
+            Self::Instance => "instance",
+            Self::Reference => "reference",
+            Self::Extends => "extends",
+            Self::Implements => "implements",
+        }
+    }
+}
+
+/// A POU in an exported graph.
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    /// Dotted name including namespaces and the owning type of methods.
+    pub name: String,
+    /// Symbol kind.
+    pub kind: SymbolKind,
+    /// File containing the declaration.
+    pub file_id: FileId,
+    /// Declaration range.
+    pub range: TextRange,
+}
+
+impl GraphNode {
+    /// Returns the IEC keyword for the node kind.
+    #[must_use]
+    pub fn kind_label(&self) -> &'static str {
+        match self.kind {
+            SymbolKind::Program => "PROGRAM",
+            SymbolKind::Function { .. } => "FUNCTION",
+            SymbolKind::FunctionBlock => "FUNCTION_BLOCK",
+            SymbolKind::Class => "CLASS",
+            SymbolKind::Interface => "INTERFACE",
+            SymbolKind::Method { .. } => "METHOD",
+            SymbolKind::Property { .. } => "PROPERTY",
+            _ => "SYMBOL",
+        }
+    }
+}
+
+/// A directed edge between two nodes of the same graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphEdge {
+    /// Index of the source node.
+    pub from: usize,
+    /// Index of the target node.
+    pub to: usize,
+    /// Relationship kind.
+    pub kind: GraphEdgeKind,
+    /// Number of call sites or declarations behind the edge.
+    pub count: usize,
+}
+
+/// Nodes and edges of one exported graph.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    /// Nodes ordered by file and declaration position.
+    pub nodes: Vec<GraphNode>,
+    /// Edges ordered by source, target, and kind.
+    pub edges: Vec<GraphEdge>,
+}
+
+impl Graph {
+    /// Renders the graph in Graphviz DOT syntax.
+    #[must_use]
+    pub fn to_dot(&self, graph_name: &str) -> String {
+        let mut output = String::new();
+        let _ = writeln!(output, "digraph {} {{", dot_quote(graph_name));
+        let _ = writeln!(output, "  rankdir=LR;");
+        let _ = writeln!(output, "  node [shape=box];");
+        for node in &self.nodes {
+            let _ = writeln!(
+                output,
+                "  {} [label={}];",
+                dot_quote(&node.name),
+                dot_quote(&format!("{}\\n{}", node.name, node.kind_label()))
+            );
+        }
+        for edge in &self.edges {
+            let (Some(from), Some(to)) = (self.nodes.get(edge.from), self.nodes.get(edge.to))
+            else {
+                continue;
+            };
+            let label = match (edge.kind, edge.count) {
+                (GraphEdgeKind::Call, 1) => String::new(),
+                (GraphEdgeKind::Call, count) => format!(" [label=\"{count}\"]"),
+                (kind, _) => format!(" [label=\"{}\"]", kind.label()),
+            };
+            let _ = writeln!(
+                output,
+                "  {} -> {}{label};",
+                dot_quote(&from.name),
+                dot_quote(&to.name)
+            );
+        }
+        output.push_str("}\n");
+        output
+    }
+}
+
+/// A program instance the graphs can be restricted to.
+#[derive(Debug, Clone)]
+pub struct GraphEntryPoint {
+    /// Program instance name (the PROGRAM name for implicit entry points).
+    pub name: SmolStr,
+    /// Task the program instance is attached to, if declared.
+    pub task: Option<SmolStr>,
+    /// Dotted name of the PROGRAM type.
+    pub program: String,
+}
+
+/// Result of a whole-project graph export.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectGraph {
+    /// POU call graph.
+    pub calls: Graph,
+    /// POU dependency graph.
+    pub dependencies: Graph,
+    /// Entry points the graphs were built from (only those of the selected task when filtered).
+    pub entry_points: Vec<GraphEntryPoint>,
+}
+
+/// Options for the project graph export.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProjectGraphOptions<'a> {
+    /// Files that participate in the analysis (defaults to all files).
+    pub files: Option<&'a FxHashSet<FileId>>,
+    /// Keep only what the program instances of this task reach (case-insensitive).
+    pub entry_task: Option<&'a str>,
+}
+
+/// Builds the call graph and dependency graph of the workspace.
+///
+/// When `entry_task` names a task without program instances the result has no
+/// entry points and empty graphs.
+pub fn analyze_project_graph(db: &Database, options: ProjectGraphOptions<'_>) -> ProjectGraph {
+    let graph = CallGraph::build(db, options.files);
+    let tables = &graph.tables;
+
+    let entry_points: Vec<_> = graph
+        .entry_points
+        .iter()
+        .filter(|entry| match options.entry_task {
+            Some(task) => entry
+                .task
+                .as_ref()
+                .is_some_and(|name| name.eq_ignore_ascii_case(task)),
+            None => true,
+        })
+        .collect();
+
+    // Call graph.
+    let mut call_counts: FxHashMap<(SymbolKey, SymbolKey), usize> = FxHashMap::default();
+    for (caller, sites) in &graph.calls {
+        for site in sites {
+            *call_counts.entry((*caller, site.callee)).or_default() += 1;
+        }
+    }
+    let mut call_nodes = local_symbols(tables, |kind| {
+        matches!(
+            kind,
+            SymbolKind::Program
+                | SymbolKind::Function { .. }
+                | SymbolKind::FunctionBlock
+                | SymbolKind::Method { .. }
+                | SymbolKind::Property { .. }
+        )
+    });
+    for (caller, callee) in call_counts.keys() {
+        call_nodes.insert(*caller);
+        call_nodes.insert(*callee);
+    }
+    let call_edges: Vec<(SymbolKey, SymbolKey, GraphEdgeKind, usize)> = call_counts
+        .into_iter()
+        .map(|((caller, callee), count)| (caller, callee, GraphEdgeKind::Call, count))
+        .collect();
+
+    // Dependency graph.
+    let mut dependency_counts: FxHashMap<(SymbolKey, SymbolKey, GraphEdgeKind), usize> =
+        FxHashMap::default();
+    for (caller, sites) in &graph.calls {
+        let Some(from) = owning_unit(tables, *caller) else {
+            continue;
+        };
+        for site in sites {
+            if let Some(to) = owning_unit(tables, site.callee).filter(|to| *to != from) {
+                *dependency_counts
+                    .entry((from, to, GraphEdgeKind::Call))
+                    .or_default() += 1;
+            }
+        }
+    }
+    let mut file_ids: Vec<&FileId> = tables.keys().collect();
+    file_ids.sort_by_key(|id| id.0);
+    for &file_id in file_ids {
+        let symbols = &tables[&file_id];
+        for symbol in symbols.iter().filter(|symbol| symbol.origin.is_none()) {
+            let key = SymbolKey {
+                file_id,
+                symbol_id: symbol.id,
+            };
+            if is_unit_kind(&symbol.kind) {
+                for (base, kind) in symbols
+                    .extends_name(symbol.id)
+                    .map(|name| (name, GraphEdgeKind::Extends))
+                    .into_iter()
+                    .chain(
+                        symbols
+                            .implements_names(symbol.id)
+                            .unwrap_or_default()
+                            .iter()
+                            .map(|name| (name, GraphEdgeKind::Implements)),
+                    )
+                {
+                    let Some(target) = symbols
+                        .resolve_by_name(base.as_str())
+                        .and_then(|id| symbol_key(symbols, id, file_id))
+                    else {
+                        continue;
+                    };
+                    *dependency_counts.entry((key, target, kind)).or_default() += 1;
+                }
+                continue;
+            }
+            if !matches!(
+                symbol.kind,
+                SymbolKind::Variable { .. } | SymbolKind::Parameter { .. }
+            ) {
+                continue;
+            }
+            let Some(from) = owning_unit(tables, key) else {
+                continue;
+            };
+            let Some((target, kind)) = instance_target(symbols, symbol.type_id, 0) else {
+                continue;
+            };
+            let Some(to) = symbol_key(symbols, target, file_id).filter(|to| *to != from) else {
+                continue;
+            };
+            *dependency_counts.entry((from, to, kind)).or_default() += 1;
+        }
+    }
+    let mut dependency_nodes = local_symbols(tables, is_unit_kind);
+    for (from, to, _) in dependency_counts.keys() {
+        dependency_nodes.insert(*from);
+        dependency_nodes.insert(*to);
+    }
+    let dependency_edges: Vec<(SymbolKey, SymbolKey, GraphEdgeKind, usize)> = dependency_counts
+        .into_iter()
+        .map(|((from, to, kind), count)| (from, to, kind, count))
+        .collect();
+
+    if options.entry_task.is_some() {
+        let roots: Vec<SymbolKey> = entry_points.iter().map(|entry| entry.program).collect();
+        call_nodes = reachable(&roots, &call_edges);
+        let unit_roots: Vec<SymbolKey> = roots
+            .iter()
+            .filter_map(|key| owning_unit(tables, *key))
+            .collect();
+        dependency_nodes = reachable(&unit_roots, &dependency_edges);
+    }
+
+    ProjectGraph {
+        calls: build_graph(tables, &call_nodes, &call_edges),
+        dependencies: build_graph(tables, &dependency_nodes, &dependency_edges),
+        entry_points: entry_points
+            .iter()
+            .map(|entry| GraphEntryPoint {
+                name: entry.name.clone(),
+                task: entry.task.clone(),
+                program: qualified_name(tables, entry.program).unwrap_or_default(),
+            })
+            .collect(),
+    }
+}
+
+fn is_unit_kind(kind: &SymbolKind) -> bool {
+    matches!(
+        kind,
+        SymbolKind::Program
+            | SymbolKind::Function { .. }
+            | SymbolKind::FunctionBlock
+            | SymbolKind::Class
+            | SymbolKind::Interface
+    )
+}
+
+fn local_symbols(
+    tables: &FxHashMap<FileId, Arc<SymbolTable>>,
+    include: impl Fn(&SymbolKind) -> bool,
+) -> FxHashSet<SymbolKey> {
+    let mut keys = FxHashSet::default();
+    for (file_id, symbols) in tables {
+        for symbol in symbols.iter() {
+            if symbol.origin.is_none() && include(&symbol.kind) {
+                keys.insert(SymbolKey {
+                    file_id: *file_id,
+                    symbol_id: symbol.id,
+                });
+            }
+        }
+    }
+    keys
+}
+
+/// Returns the PROGRAM, FUNCTION, FUNCTION_BLOCK, CLASS, or INTERFACE that declares `key`.
+fn owning_unit(tables: &FxHashMap<FileId, Arc<SymbolTable>>, key: SymbolKey) -> Option<SymbolKey> {
+    let symbols = tables.get(&key.file_id)?;
+    let mut current = Some(key.symbol_id);
+    while let Some(symbol_id) = current {
+        let symbol = symbols.get(symbol_id)?;
+        if is_unit_kind(&symbol.kind) {
+            return Some(SymbolKey {
+                file_id: key.file_id,
+                symbol_id,
+            });
+        }
+        current = symbol.parent;
+    }
+    None
+}
+
+/// Maps a variable type to the FUNCTION_BLOCK, CLASS, or INTERFACE it depends on.
+fn instance_target(
+    symbols: &SymbolTable,
+    type_id: TypeId,
+    depth: usize,
+) -> Option<(SymbolId, GraphEdgeKind)> {
+    if depth > MAX_TYPE_DEPTH {
+        return None;
+    }
+    let resolved = symbols.resolve_alias_type(type_id);
+    match symbols.type_by_id(resolved)? {
+        Type::FunctionBlock { name } | Type::Class { name } => {
+            instance_type_symbol(symbols, name).map(|id| (id, GraphEdgeKind::Instance))
+        }
+        Type::Interface { name } => symbols
+            .resolve_by_name(name)
+            .filter(|id| {
+                symbols
+                    .get(*id)
+                    .is_some_and(|symbol| matches!(symbol.kind, SymbolKind::Interface))
+            })
+            .map(|id| (id, GraphEdgeKind::Reference)),
+        Type::Array { element, .. } => instance_target(symbols, *element, depth + 1),
+        _ => None,
+    }
+}
+
+fn reachable(
+    roots: &[SymbolKey],
+    edges: &[(SymbolKey, SymbolKey, GraphEdgeKind, usize)],
+) -> FxHashSet<SymbolKey> {
+    let mut seen: FxHashSet<SymbolKey> = roots.iter().copied().collect();
+    let mut queue: VecDeque<SymbolKey> = roots.iter().copied().collect();
+    while let Some(key) = queue.pop_front() {
+        for (from, to, _, _) in edges {
+            if *from == key && seen.insert(*to) {
+                queue.push_back(*to);
+            }
+        }
+    }
+    seen
+}
+
+fn build_graph(
+    tables: &FxHashMap<FileId, Arc<SymbolTable>>,
+    keys: &FxHashSet<SymbolKey>,
+    edges: &[(SymbolKey, SymbolKey, GraphEdgeKind, usize)],
+) -> Graph {
+    let mut nodes: Vec<(SymbolKey, GraphNode)> = keys
+        .iter()
+        .filter_map(|key| {
+            let symbol = tables.get(&key.file_id)?.get(key.symbol_id)?;
+            Some((
+                *key,
+                GraphNode {
+                    name: qualified_name(tables, *key)?,
+                    kind: symbol.kind.clone(),
+                    file_id: key.file_id,
+                    range: symbol.range,
+                },
+            ))
+        })
+        .collect();
+    nodes.sort_by(|(_, a), (_, b)| {
+        a.file_id
+            .0
+            .cmp(&b.file_id.0)
+            .then_with(|| a.range.start().cmp(&b.range.start()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    let index: FxHashMap<SymbolKey, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(position, (key, _))| (*key, position))
+        .collect();
+
+    let mut graph_edges: Vec<GraphEdge> = edges
+        .iter()
+        .filter_map(|(from, to, kind, count)| {
+            Some(GraphEdge {
+                from: *index.get(from)?,
+                to: *index.get(to)?,
+                kind: *kind,
+                count: *count,
+            })
+        })
+        .collect();
+    graph_edges.sort_by_key(|edge| (edge.from, edge.to, edge.kind));
+
+    Graph {
+        nodes: nodes.into_iter().map(|(_, node)| node).collect(),
+        edges: graph_edges,
+    }
+}
+
+/// Returns the dotted name of a symbol including namespaces and owning types.
+fn qualified_name(tables: &FxHashMap<FileId, Arc<SymbolTable>>, key: SymbolKey) -> Option<String> {
+    let symbols = tables.get(&key.file_id)?;
+    let mut parts = Vec::new();
+    let mut current = Some(key.symbol_id);
+    while let Some(symbol_id) = current {
+        let symbol = symbols.get(symbol_id)?;
+        parts.push(symbol.name.to_string());
+        current = symbol.parent;
+    }
+    parts.reverse();
+    Some(parts.join("."))
+}
+
+fn dot_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use trust_hir::db::SourceDatabase;
+
+    const SOURCE: &str = r#"
+CONFIGURATION Conf
+RESOURCE Res ON PLC
+    TASK Fast (INTERVAL := T#10ms, PRIORITY := 1);
+    TASK Slow (INTERVAL := T#100ms, PRIORITY := 2);
+    PROGRAM FastInst WITH Fast : FastMain;
+    PROGRAM SlowInst WITH Slow : SlowMain;
+END_RESOURCE
+END_CONFIGURATION
+
+INTERFACE ILogger
+METHOD Log
+END_METHOD
+END_INTERFACE
+
+FUNCTION Scale : INT
+VAR_INPUT
+    A : INT;
+END_VAR
+    Scale := A * 2;
+END_FUNCTION
+
+FUNCTION_BLOCK BaseDrive
+END_FUNCTION_BLOCK
+
+FUNCTION_BLOCK Drive EXTENDS BaseDrive IMPLEMENTS ILogger
+VAR
+    logger : ILogger;
+END_VAR
+METHOD Log
+    Scale(1);
+END_METHOD
+    Scale(2);
+    Scale(3);
+END_FUNCTION_BLOCK
+
+PROGRAM FastMain
+VAR
+    drives : ARRAY[1..2] OF Drive;
+END_VAR
+    drives[1]();
+END_PROGRAM
+
+PROGRAM SlowMain
+    Scale(4);
+END_PROGRAM
+"#;
+
+    fn graph_for_task(entry_task: Option<&str>) -> ProjectGraph {
+        let mut db = Database::new();
+        db.set_source_text(FileId(0), SOURCE.to_string());
+        analyze_project_graph(
+            &db,
+            ProjectGraphOptions {
+                files: None,
+                entry_task,
+            },
+        )
+    }
+
+    fn edges(graph: &Graph) -> Vec<(String, String, &'static str, usize)> {
+        graph
+            .edges
+            .iter()
+            .map(|edge| {
+                (
+                    graph.nodes[edge.from].name.clone(),
+                    graph.nodes[edge.to].name.clone(),
+                    edge.kind.label(),
+                    edge.count,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn project_graph_collects_calls_and_dependencies() {
+        let graph = graph_for_task(None);
+        assert_eq!(graph.entry_points.len(), 2);
+
+        let calls = edges(&graph.calls);
+        assert!(calls.contains(&("Drive".into(), "Scale".into(), "call", 2)));
+        assert!(calls.contains(&("Drive.Log".into(), "Scale".into(), "call", 1)));
+        assert!(calls.contains(&("SlowMain".into(), "Scale".into(), "call", 1)));
+
+        let dependencies = edges(&graph.dependencies);
+        assert!(dependencies.contains(&("Drive".into(), "Scale".into(), "call", 3)));
+        assert!(dependencies.contains(&("Drive".into(), "BaseDrive".into(), "extends", 1)));
+        assert!(dependencies.contains(&("Drive".into(), "ILogger".into(), "implements", 1)));
+        assert!(dependencies.contains(&("Drive".into(), "ILogger".into(), "reference", 1)));
+        assert!(dependencies.contains(&("FastMain".into(), "Drive".into(), "instance", 1)));
+
+        let dot = graph.dependencies.to_dot("dependencies");
+        assert!(dot.starts_with("digraph \"dependencies\" {"));
+        assert!(dot.contains("\"Drive\" -> \"BaseDrive\" [label=\"extends\"];"));
+    }
+
+    #[test]
+    fn project_graph_filters_by_entry_task() {
+        let graph = graph_for_task(Some("slow"));
+        assert_eq!(graph.entry_points.len(), 1);
+        assert_eq!(graph.entry_points[0].program, "SlowMain");
+
+        let names: Vec<&str> = graph
+            .dependencies
+            .nodes
+            .iter()
+            .map(|node| node.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Scale", "SlowMain"]);
+        assert_eq!(
+            edges(&graph.calls),
+            vec![("SlowMain".into(), "Scale".into(), "call", 1)]
+        );
+
+        let missing = graph_for_task(Some("Missing"));
+        assert!(missing.entry_points.is_empty());
+        assert!(missing.calls.nodes.is_empty());
+    }
+}
@@ -0,0 +1,453 @@
+//! Organize the declaration part of a POU.
+//!
+//! VAR blocks are grouped in IEC order, blocks with the same header are merged, empty
+//! blocks are dropped, and members are aligned on `:` and `:=`. Members of local
+//! sections can be sorted; interface sections keep their order because it defines
+//! positional calls.
+
+use text_size::{TextRange, TextSize};
+
+use trust_hir::db::FileId;
+use trust_hir::{Database, SourceDatabase};
+use trust_syntax::parser::parse;
+use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
+
+use super::utilities::{indent_unit_for, node_token_range};
+use crate::rename::{RenameResult, TextEdit};
+
+/// Sort key for members of local VAR sections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeclarationSort {
+    /// Keep source order.
+    None,
+    /// Sort by the first declared name, ignoring case.
+    #[default]
+    Name,
+    /// Sort by declared type, then by name.
+    Type,
+}
+
+const DECLARATION_OWNERS: &[SyntaxKind] = &[
+    SyntaxKind::Program,
+    SyntaxKind::Function,
+    SyntaxKind::FunctionBlock,
+    SyntaxKind::Class,
+    SyntaxKind::Method,
+];
+
+struct Member {
+    comments: Vec<String>,
+    verbatim: Option<String>,
+    names: String,
+    type_text: String,
+    init: Option<String>,
+    trailing: Option<String>,
+    sort_name: String,
+}
+
+struct Group {
+    key: String,
+    header: String,
+    header_comment: Option<String>,
+    end_keyword: String,
+    rank: u8,
+    leading: Vec<String>,
+    members: Vec<Member>,
+    tail: Vec<String>,
+}
+
+/// Normalize the VAR blocks of the POU enclosing `position`.
+///
+/// Returns a single edit that replaces the declaration part, or `None` when it is
+/// already organized or contains syntax the rewrite cannot preserve.
+pub fn organize_declarations(
+    db: &Database,
+    file_id: FileId,
+    position: TextSize,
+    sort: DeclarationSort,
+) -> Option<RenameResult> {
+    let source = db.source_text(file_id);
+    let parsed = parse(&source);
+    let root = parsed.syntax();
+    let owner = root
+        .token_at_offset(position)
+        .right_biased()?
+        .parent_ancestors()
+        .find(|node| DECLARATION_OWNERS.contains(&node.kind()))?;
+    let blocks = leading_var_blocks(&owner);
+    let first = blocks.first()?;
+    let last = blocks.last()?;
+
+    let first_start = usize::from(node_token_range(first).start());
+    let start = source[..first_start]
+        .rfind('\n')
+        .map_or(0, |index| index + 1);
+    let indent = &source[start..first_start];
+    if !indent.trim().is_empty() {
+        return None;
+    }
+    let end = usize::from(node_token_range(last).end());
+    let stray_token = owner
+        .children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .any(|token| {
+            let offset = usize::from(token.text_range().start());
+            !token.kind().is_trivia() && offset > first_start && offset < end
+        });
+    if stray_token {
+        return None;
+    }
+    let member_indent = blocks
+        .iter()
+        .flat_map(|block| block.children())
+        .find(|child| child.kind() == SyntaxKind::VarDecl)
+        .and_then(|decl| {
+            let offset = usize::from(node_token_range(&decl).start());
+            let line = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+            let text = &source[line..offset];
+            (text.trim().is_empty() && text.len() > indent.len()).then(|| text.to_string())
+        })
+        .unwrap_or_else(|| format!("{indent}{}", indent_unit_for(indent)));
+
+    let mut groups: Vec<Group> = Vec::new();
+    let mut pending = Vec::new();
+    let mut cursor = first_start;
+    for block in &blocks {
+        let range = node_token_range(block);
+        pending.extend(comment_lines(&source[cursor..usize::from(range.start())]));
+        cursor = usize::from(range.end());
+        let mut group = parse_block(&source, block)?;
+        if let Some(existing) = groups.iter_mut().find(|existing| existing.key == group.key) {
+            let mut carried = std::mem::take(&mut pending);
+            carried.extend(group.header_comment.take());
+            match group.members.first_mut() {
+                Some(member) => {
+                    carried.append(&mut member.comments);
+                    member.comments = carried;
+                }
+                None => existing.tail.append(&mut carried),
+            }
+            existing.members.append(&mut group.members);
+            existing.tail.append(&mut group.tail);
+        } else {
+            group.leading = std::mem::take(&mut pending);
+            groups.push(group);
+        }
+    }
+
+    groups.sort_by_key(|group| group.rank);
+    groups.retain(|group| {
+        !group.members.is_empty() || !group.leading.is_empty() || !group.tail.is_empty()
+    });
+    for group in &mut groups {
+        if is_sortable_section(group.rank) {
+            sort_members(&mut group.members, sort);
+        }
+    }
+
+    let newline = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let text = groups
+        .iter()
+        .map(|group| render_group(group, indent, &member_indent, newline))
+        .collect::<Vec<_>>()
+        .join(newline);
+    let text = text.strip_prefix(indent).unwrap_or(&text).to_string();
+    if text == source[first_start..end] {
+        return None;
+    }
+
+    let mut result = RenameResult::new();
+    result.add_edit(
+        file_id,
+        TextEdit {
+            range: TextRange::new(
+                TextSize::from(first_start as u32),
+                TextSize::from(end as u32),
+            ),
+            new_text: text,
+        },
+    );
+    Some(result)
+}
+
+/// VAR blocks that directly follow each other at the start of the POU body.
+fn leading_var_blocks(owner: &SyntaxNode) -> Vec<SyntaxNode> {
+    let mut blocks = Vec::new();
+    for child in owner.children() {
+        if child.kind() == SyntaxKind::VarBlock {
+            blocks.push(child);
+        } else if !blocks.is_empty() {
+            break;
+        }
+    }
+    blocks
+}
+
+fn parse_block(source: &str, block: &SyntaxNode) -> Option<Group> {
+    if block
+        .descendants()
+        .any(|node| node.kind() == SyntaxKind::Error)
+    {
+        return None;
+    }
+    let mut header = Vec::new();
+    let mut keyword = None;
+    let mut header_end = None;
+    let mut end_var = None;
+    for element in block.children_with_tokens() {
+        if let Some(node) = element.as_node() {
+            if node.kind() != SyntaxKind::VarDecl || end_var.is_some() {
+                return None;
+            }
+            continue;
+        }
+        let Some(token) = element.into_token() else {
+            continue;
+        };
+        let kind = token.kind();
+        if kind.is_trivia() {
+            continue;
+        }
+        if kind == SyntaxKind::KwEndVar {
+            end_var = Some(token);
+        } else if end_var.is_none() {
+            keyword.get_or_insert(kind);
+            header.push(token.text().to_string());
+            header_end = Some(token.text_range().end());
+        } else {
+            return None;
+        }
+    }
+    let header_end = usize::from(header_end?);
+    let end_var_token = end_var?;
+    let end_var = usize::from(end_var_token.text_range().start());
+
+    let decls: Vec<_> = block
+        .children()
+        .filter(|child| child.kind() == SyntaxKind::VarDecl)
+        .collect();
+    let mut members = Vec::with_capacity(decls.len());
+    let mut cursor = header_end;
+    let first_item = decls
+        .first()
+        .map_or(end_var, |decl| usize::from(node_token_range(decl).start()));
+    let mut header_comment = None;
+    if let Some(newline) = source[cursor..first_item].find('\n') {
+        header_comment = Some(source[cursor..cursor + newline].trim())
+            .filter(|text| !text.is_empty())
+            .map(str::to_string);
+        cursor += newline;
+    }
+    for (index, decl) in decls.iter().enumerate() {
+        let range = node_token_range(decl);
+        let decl_start = usize::from(range.start());
+        let decl_end = usize::from(range.end());
+        let comments = comment_lines(&source[cursor..decl_start]);
+        let next_start = decls
+            .get(index + 1)
+            .map_or(end_var, |next| usize::from(node_token_range(next).start()));
+        let line_end = source[decl_end..]
+            .find('\n')
+            .map_or(source.len(), |offset| decl_end + offset);
+        let trailing = if next_start > line_end {
+            cursor = line_end;
+            Some(source[decl_end..line_end].trim())
+                .filter(|text| !text.is_empty())
+                .map(str::to_string)
+        } else {
+            cursor = decl_end;
+            None
+        };
+        members.push(parse_member(source, decl, comments, trailing)?);
+    }
+    let tail = comment_lines(&source[cursor..end_var]);
+
+    let header = header.join(" ");
+    Some(Group {
+        key: header.to_ascii_uppercase(),
+        rank: section_rank(keyword?),
+        header,
+        header_comment,
+        end_keyword: end_var_token.text().to_string(),
+        leading: Vec::new(),
+        members,
+        tail,
+    })
+}
+
+fn parse_member(
+    source: &str,
+    decl: &SyntaxNode,
+    comments: Vec<String>,
+    trailing: Option<String>,
+) -> Option<Member> {
+    let range = node_token_range(decl);
+    let text = &source[usize::from(range.start())..usize::from(range.end())];
+    let sort_name = decl
+        .children()
+        .find(|child| child.kind() == SyntaxKind::Name)
+        .and_then(|name| {
+            name.children_with_tokens()
+                .filter_map(|element| element.into_token())
+                .find(|token| !token.kind().is_trivia())
+        })
+        .map(|token| token.text().to_ascii_lowercase())
+        .unwrap_or_default();
+    let type_text = decl
+        .children()
+        .find(|child| child.kind() == SyntaxKind::TypeRef)
+        .map(|type_ref| {
+            source[usize::from(node_token_range(&type_ref).start())
+                ..usize::from(node_token_range(&type_ref).end())]
+                .to_string()
+        })
+        .unwrap_or_default();
+    let mut colon = None;
+    let mut assign = None;
+    let mut semicolon = None;
+    let mut has_comment = false;
+    for token in decl
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+    {
+        match token.kind() {
+            SyntaxKind::LineComment | SyntaxKind::BlockComment | SyntaxKind::Pragma => {
+                has_comment |= range.contains_range(token.text_range());
+            }
+            SyntaxKind::Colon if token.parent().as_ref() == Some(decl) => {
+                colon = Some(usize::from(token.text_range().start()));
+            }
+            SyntaxKind::Assign if token.parent().as_ref() == Some(decl) => {
+                assign = Some(usize::from(token.text_range().end()));
+            }
+            SyntaxKind::Semicolon if token.parent().as_ref() == Some(decl) => {
+                semicolon = Some(usize::from(token.text_range().start()));
+            }
+            _ => {}
+        }
+    }
+
+    let mut member = Member {
+        comments,
+        verbatim: None,
+        names: String::new(),
+        type_text,
+        init: None,
+        trailing,
+        sort_name,
+    };
+    let colon = colon?;
+    let semicolon = semicolon?;
+    if has_comment || text.contains('\n') || member.type_text.is_empty() {
+        member.verbatim = Some(text.to_string());
+        return Some(member);
+    }
+    member.names = source[usize::from(range.start())..colon].trim().to_string();
+    member.init = assign.map(|assign| source[assign..semicolon].trim().to_string());
+    Some(member)
+}
+
+fn comment_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn section_rank(keyword: SyntaxKind) -> u8 {
+    match keyword {
+        SyntaxKind::KwVarInput => 0,
+        SyntaxKind::KwVarOutput => 1,
+        SyntaxKind::KwVarInOut => 2,
+        SyntaxKind::KwVarExternal => 3,
+        SyntaxKind::KwVarGlobal => 4,
+        SyntaxKind::KwVar => 5,
+        SyntaxKind::KwVarStat => 6,
+        SyntaxKind::KwVarTemp => 7,
+        _ => 8,
+    }
+}
+
+fn is_sortable_section(rank: u8) -> bool {
+    (3..=7).contains(&rank)
+}
+
+fn sort_members(members: &mut [Member], sort: DeclarationSort) {
+    match sort {
+        DeclarationSort::None => {}
+        DeclarationSort::Name => members.sort_by(|a, b| a.sort_name.cmp(&b.sort_name)),
+        DeclarationSort::Type => members.sort_by(|a, b| {
+            a.type_text
+                .to_ascii_lowercase()
+                .cmp(&b.type_text.to_ascii_lowercase())
+                .then_with(|| a.sort_name.cmp(&b.sort_name))
+        }),
+    }
+}
+
+fn render_group(group: &Group, indent: &str, member_indent: &str, newline: &str) -> String {
+    let aligned = group
+        .members
+        .iter()
+        .filter(|member| member.verbatim.is_none());
+    let name_width = aligned
+        .clone()
+        .map(|member| member.names.chars().count())
+        .max()
+        .unwrap_or(0);
+    let type_width = aligned
+        .filter(|member| member.init.is_some())
+        .map(|member| member.type_text.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = Vec::new();
+    lines.extend(group.leading.iter().map(|line| format!("{indent}{line}")));
+    match &group.header_comment {
+        Some(comment) => lines.push(format!("{indent}{} {comment}", group.header)),
+        None => lines.push(format!("{indent}{}", group.header)),
+    }
+    for member in &group.members {
+        lines.extend(
+            member
+                .comments
+                .iter()
+                .map(|line| format!("{member_indent}{line}")),
+        );
+        let mut line = match &member.verbatim {
+            Some(text) => format!("{member_indent}{text}"),
+            None => {
+                let mut line = format!(
+                    "{member_indent}{:<name_width$} : {}",
+                    member.names, member.type_text
+                );
+                if let Some(init) = &member.init {
+                    let pad = type_width.saturating_sub(member.type_text.chars().count());
+                    line.push_str(&" ".repeat(pad));
+                    line.push_str(" := ");
+                    line.push_str(init);
+                }
+                line.push(';');
+                line
+            }
+        };
+        if let Some(trailing) = &member.trailing {
+            line.push(' ');
+            line.push_str(trailing);
+        }
+        lines.push(line);
+    }
+    lines.extend(
+        group
+            .tail
+            .iter()
+            .map(|line| format!("{member_indent}{line}")),
+    );
+    lines.push(format!("{indent}{}", group.end_keyword));
+    lines.join(newline)
+}
@@ -2,12 +2,15 @@
 //!
 //! This module provides cross-file refactor primitives that go beyond rename.
 
+mod declarations;
 mod operations;
 mod utilities;
 
+pub use declarations::{organize_declarations, DeclarationSort};
 pub(crate) use operations::namespace_full_path;
 pub use operations::{
     convert_function_block_to_function, convert_function_to_function_block, extract_method,
-    extract_pou, extract_property, generate_interface_stubs, inline_symbol, move_namespace_path,
-    parse_namespace_path, ExtractResult, ExtractTargetKind, InlineResult, InlineTargetKind,
+    extract_pou, extract_property, generate_interface_stubs, global_constant_lists, inline_symbol,
+    introduce_constant, move_namespace_path, parse_namespace_path, ConstantTarget, ExtractResult,
+    ExtractTargetKind, GlobalConstantList, InlineResult, InlineTargetKind,
 };
@@ -8,7 +8,9 @@ use text_size::{TextRange, TextSize};
 
 use trust_hir::db::{FileId, SemanticDatabase};
 use trust_hir::symbols::{SymbolKind, SymbolTable};
-use trust_hir::{is_reserved_keyword, is_valid_identifier, Database, SourceDatabase, SymbolId};
+use trust_hir::{
+    is_reserved_keyword, is_valid_identifier, Database, SourceDatabase, SymbolId, TypeId,
+};
 use trust_syntax::parser::parse;
 use trust_syntax::syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
 
@@ -69,6 +71,28 @@ pub enum ExtractTargetKind {
     Property,
     /// Extract a FUNCTION (POU).
     Function,
+    /// Introduce a named constant from a literal.
+    Constant,
+}
+
+/// Declaration target for an introduced constant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstantTarget {
+    /// A `VAR CONSTANT` block of the enclosing POU (created when missing).
+    Local,
+    /// An existing `VAR_GLOBAL CONSTANT` block.
+    Global(GlobalConstantList),
+}
+
+/// A `VAR_GLOBAL CONSTANT` block that can receive introduced constants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalConstantList {
+    /// File containing the block.
+    pub file_id: FileId,
+    /// Range of the `VAR_GLOBAL CONSTANT ... END_VAR` block.
+    pub range: TextRange,
+    /// Name of the enclosing CONFIGURATION/RESOURCE, if any.
+    pub container: Option<SmolStr>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -575,6 +599,143 @@ pub fn extract_pou(db: &Database, file_id: FileId, range: TextRange) -> Option<E
     })
 }
 
+/// Lists `VAR_GLOBAL CONSTANT` blocks across the project.
+pub fn global_constant_lists(db: &Database) -> Vec<GlobalConstantList> {
+    let mut file_ids = db.file_ids();
+    file_ids.sort_by_key(|id| id.0);
+    let mut lists = Vec::new();
+    for file_id in file_ids {
+        let source = db.source_text(file_id);
+        let root = parse(&source).syntax();
+        for block in root
+            .descendants()
+            .filter(|node| node.kind() == SyntaxKind::VarBlock)
+        {
+            if var_block_kind(&block) != Some(SyntaxKind::KwVarGlobal)
+                || keyword_token(&block, SyntaxKind::KwConstant).is_none()
+            {
+                continue;
+            }
+            let container = block
+                .ancestors()
+                .find(|node| {
+                    matches!(
+                        node.kind(),
+                        SyntaxKind::Resource | SyntaxKind::Configuration
+                    )
+                })
+                .and_then(|node| {
+                    node.children()
+                        .find(|child| child.kind() == SyntaxKind::Name)
+                })
+                .and_then(|name| name_from_name_node(&name));
+            lists.push(GlobalConstantList {
+                file_id,
+                range: block.text_range(),
+                container,
+            });
+        }
+    }
+    lists
+}
+
+/// Introduces a named constant for the literal at `range` and replaces identical literals.
+///
+/// Local constants replace literals in the enclosing POU; global constants
+/// replace literals in every POU of the current file.
+pub fn introduce_constant(
+    db: &Database,
+    file_id: FileId,
+    range: TextRange,
+    target: &ConstantTarget,
+) -> Option<ExtractResult> {
+    let source = db.source_text(file_id);
+    let parsed = parse(&source);
+    let root = parsed.syntax();
+    let literal = literal_expr_for_selection(&source, &root, range)?;
+    let literal_text = literal_expr_text(&literal)?;
+
+    let owner_node = find_enclosing_owner_node(
+        &root,
+        literal.text_range().start(),
+        &[
+            SyntaxKind::Program,
+            SyntaxKind::Function,
+            SyntaxKind::FunctionBlock,
+            SyntaxKind::Method,
+        ],
+    )?;
+    let symbols = db.file_symbols_with_project(file_id);
+    let type_name = constant_type_name(db, file_id, &symbols, &literal)?;
+
+    let mut edits = RenameResult::new();
+    let (name, scope) = match target {
+        ConstantTarget::Local => {
+            let owner_id = owner_symbol_id(&symbols, &owner_node)?;
+            let name = unique_member_name(&symbols, owner_id, "ExtractedConstant");
+            let (offset, text) =
+                local_constant_insert(&source, &owner_node, &name, &type_name, &literal_text)?;
+            edits.add_edit(
+                file_id,
+                TextEdit {
+                    range: TextRange::empty(TextSize::from(offset as u32)),
+                    new_text: text,
+                },
+            );
+            (name, owner_node.clone())
+        }
+        ConstantTarget::Global(list) => {
+            let name = unique_top_level_name(&symbols, "ExtractedConstant");
+            let list_source = db.source_text(list.file_id);
+            let list_root = parse(&list_source).syntax();
+            let block = list_root.descendants().find(|node| {
+                node.kind() == SyntaxKind::VarBlock && node.text_range() == list.range
+            })?;
+            let (offset, text) =
+                var_block_append_decl(&list_source, &block, &name, &type_name, &literal_text)?;
+            edits.add_edit(
+                list.file_id,
+                TextEdit {
+                    range: TextRange::empty(TextSize::from(offset as u32)),
+                    new_text: text,
+                },
+            );
+            (name, root.clone())
+        }
+    };
+
+    for occurrence in scope
+        .descendants()
+        .filter(|node| matches!(node.kind(), SyntaxKind::Literal | SyntaxKind::UnaryExpr))
+    {
+        if !occurrence
+            .ancestors()
+            .any(|node| node.kind() == SyntaxKind::StmtList)
+        {
+            continue;
+        }
+        let Some(text) = literal_expr_text(&occurrence) else {
+            continue;
+        };
+        if !literal_texts_match(&text, &literal_text) {
+            continue;
+        }
+        edits.add_edit(
+            file_id,
+            TextEdit {
+                range: node_token_range(&occurrence),
+                new_text: name.to_string(),
+            },
+        );
+    }
+
+    Some(ExtractResult {
+        edits,
+        name,
+        kind: ExtractTargetKind::Constant,
+    })
+}
+
 /// Converts a FUNCTION to a FUNCTION_BLOCK.
 pub fn convert_function_to_function_block(
     db: &Database,
@@ -726,7 +887,9 @@ pub fn convert_function_block_to_function(
         );
     }
 
-    let name_end = name_node.text_range().end();
+    let name_end = ident_token_in_name(&name_node).map_or(name_node.text_range().end(), |ident| {
+        ident.text_range().end()
+    });
     edits.add_edit(
         file_id,
         TextEdit {
@@ -986,7 +1149,11 @@ fn collect_interface_stubs(
                         else {
                             continue;
                         };
-                        if seen.insert(stub.name_key.clone()) {
+                        // A default body makes the method optional, also over base interfaces.
+                        let has_default = child
+                            .children()
+                            .any(|node| node.kind() == SyntaxKind::StmtList);
+                        if seen.insert(stub.name_key.clone()) && !has_default {
                             out.push(stub);
                         }
                     }
@@ -1596,6 +1763,185 @@ fn expression_node_for_selection(root: &SyntaxNode, selection: TextRange) -> Opt
     None
 }
 
+/// Finds the literal (optionally negated) selected by `range` inside a statement body.
+fn literal_expr_for_selection(
+    source: &str,
+    root: &SyntaxNode,
+    range: TextRange,
+) -> Option<SyntaxNode> {
+    let token = root.token_at_offset(range.start()).right_biased()?;
+    let literal = token
+        .parent_ancestors()
+        .find(|node| node.kind() == SyntaxKind::Literal)?;
+    if !literal
+        .ancestors()
+        .any(|node| node.kind() == SyntaxKind::StmtList)
+    {
+        return None;
+    }
+    let negated = literal
+        .parent()
+        .filter(|parent| literal_expr_text(parent).is_some());
+    if range.is_empty() {
+        return Some(negated.unwrap_or(literal));
+    }
+    let selection = trim_range_to_non_whitespace(source, range)?;
+    if node_token_range(&literal) == selection {
+        return Some(literal);
+    }
+    negated.filter(|node| node_token_range(node) == selection)
+}
+
+/// Returns the normalized text of a literal or negated literal, or `None` for
+/// other expressions and for BOOL/NULL literals.
+fn literal_expr_text(node: &SyntaxNode) -> Option<String> {
+    let literal = match node.kind() {
+        SyntaxKind::Literal => node.clone(),
+        SyntaxKind::UnaryExpr => {
+            let mut tokens = node
+                .children_with_tokens()
+                .filter_map(|element| element.into_token())
+                .filter(|token| !token.kind().is_trivia());
+            if tokens.next()?.kind() != SyntaxKind::Minus {
+                return None;
+            }
+            let literal = node.children().next()?;
+            if literal.kind() != SyntaxKind::Literal {
+                return None;
+            }
+            return literal_expr_text(&literal).map(|text| format!("-{text}"));
+        }
+        _ => return None,
+    };
+    let tokens: Vec<SyntaxToken> = literal
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !token.kind().is_trivia())
+        .collect();
+    if tokens.iter().any(|token| {
+        matches!(
+            token.kind(),
+            SyntaxKind::KwTrue | SyntaxKind::KwFalse | SyntaxKind::KwNull
+        )
+    }) {
+        return None;
+    }
+    let text: String = tokens.iter().map(|token| token.text()).collect();
+    (!text.is_empty()).then_some(text)
+}
+
+fn literal_texts_match(left: &str, right: &str) -> bool {
+    let is_string = |text: &str| text.contains('\'') || text.contains('"');
+    if is_string(left) || is_string(right) {
+        left == right
+    } else {
+        left.eq_ignore_ascii_case(right)
+    }
+}
+
+/// Picks the declared type for a constant, preferring the type of the
+/// variable the literal is assigned to or compared with.
+fn constant_type_name(
+    db: &Database,
+    file_id: FileId,
+    symbols: &SymbolTable,
+    literal: &SyntaxNode,
+) -> Option<SmolStr> {
+    let type_at = |node: &SyntaxNode| {
+        db.expr_id_at_offset(file_id, u32::from(node_token_range(node).start()))
+            .map(|expr_id| symbols.resolve_alias_type(db.type_of(file_id, expr_id)))
+    };
+    let literal_type = type_at(literal)?;
+    let literal_info = symbols.type_by_id(literal_type)?;
+
+    let context = literal.parent().and_then(|parent| match parent.kind() {
+        SyntaxKind::AssignStmt | SyntaxKind::BinaryExpr => parent
+            .children()
+            .find(|child| child.text_range() != literal.text_range())
+            .filter(|child| child.kind() == SyntaxKind::NameRef),
+        _ => None,
+    });
+    if let Some(context_type) = context.as_ref().and_then(type_at) {
+        if let Some(context_info) = symbols.type_by_id(context_type) {
+            let compatible = (literal_info.is_integer()
+                && (context_info.is_numeric() || context_info.is_bit_string()))
+                || (literal_info.is_float() && context_info.is_float())
+                || (literal_info.is_string() && context_info.is_string())
+                || (literal_info.is_time() && context_type == literal_type);
+            if compatible && context_type != TypeId::BOOL {
+                return symbols.type_name(context_type);
+            }
+        }
+    }
+
+    let widened = match literal_type {
+        TypeId::SINT => TypeId::INT,
+        TypeId::USINT => TypeId::UINT,
+        other => other,
+    };
+    symbols.type_name(widened)
+}
+
+fn local_constant_insert(
+    source: &str,
+    owner: &SyntaxNode,
+    name: &str,
+    type_name: &str,
+    value: &str,
+) -> Option<(usize, String)> {
+    if let Some(block) = owner.children().find(|child| {
+        child.kind() == SyntaxKind::VarBlock
+            && var_block_kind(child) == Some(SyntaxKind::KwVar)
+            && keyword_token(child, SyntaxKind::KwConstant).is_some()
+    }) {
+        return var_block_append_decl(source, &block, name, type_name, value);
+    }
+
+    let anchor = owner
+        .children()
+        .find(|child| matches!(child.kind(), SyntaxKind::VarBlock | SyntaxKind::StmtList))?;
+    let anchor_start = node_token_range(&anchor).start();
+    let indent = if anchor.kind() == SyntaxKind::VarBlock {
+        line_indent_at_offset(source, anchor_start)
+    } else {
+        let owner_indent = line_indent_at_offset(source, owner.text_range().start());
+        format!("{owner_indent}{}", indent_unit_for(&owner_indent))
+    };
+    let indent_unit = indent_unit_for(&indent);
+    let offset = line_start(source, usize::from(anchor_start));
+    let text = format!(
+        "{indent}VAR CONSTANT\n{indent}{indent_unit}{name} : {type_name} := {value};\n{indent}END_VAR\n"
+    );
+    Some((offset, text))
+}
+
+fn var_block_append_decl(
+    source: &str,
+    block: &SyntaxNode,
+    name: &str,
+    type_name: &str,
+    value: &str,
+) -> Option<(usize, String)> {
+    let end_var = keyword_token(block, SyntaxKind::KwEndVar)?;
+    let decl_indent = block
+        .children()
+        .find(|child| child.kind() == SyntaxKind::VarDecl)
+        .map(|decl| line_indent_at_offset(source, node_token_range(&decl).start()))
+        .unwrap_or_else(|| {
+            let base = line_indent_at_offset(source, end_var.text_range().start());
+            format!("{base}{}", indent_unit_for(&base))
+        });
+    let offset = line_start(source, usize::from(end_var.text_range().start()));
+    Some((
+        offset,
+        format!("{decl_indent}{name} : {type_name} := {value};\n"),
+    ))
+}
+
+fn line_start(source: &str, offset: usize) -> usize {
+    source[..offset].rfind('\n').map_or(0, |index| index + 1)
+}
+
 fn keyword_token(node: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxToken> {
     node.descendants_with_tokens()
         .filter_map(|element| element.into_token())
@@ -2172,7 +2518,16 @@ fn output_var_info(source: &str, _root: &SyntaxNode, node: &SyntaxNode) -> Optio
     if type_name.is_empty() {
         return None;
     }
-    let removal_range = extend_range_to_line_end(source, block.text_range());
+    // The block node also holds the trivia up to the body, so stop at END_VAR.
+    let end_var = keyword_token(&block, SyntaxKind::KwEndVar)
+        .map_or(block.text_range().end(), |token| token.text_range().end());
+    let removal_range = extend_range_to_line_end(
+        source,
+        TextRange::new(
+            line_indent_start(source, block.text_range().start()),
+            end_var,
+        ),
+    );
     Some(OutputVarInfo {
         name: SmolStr::new(ident.text()),
         type_name,
@@ -2510,6 +2865,19 @@ fn extend_range_to_line_end(source: &str, range: TextRange) -> TextRange {
     TextRange::new(range.start(), TextSize::from(end as u32))
 }
 
+/// Moves `offset` back over the indentation before it when nothing else
+/// precedes it on the line.
+fn line_indent_start(source: &str, offset: TextSize) -> TextSize {
+    let prefix = &source[..usize::from(offset)];
+    let indent = prefix.len() - prefix.trim_end_matches([' ', '\t']).len();
+    let start = prefix.len() - indent;
+    if start == 0 || prefix[..start].ends_with('\n') {
+        TextSize::from(start as u32)
+    } else {
+        offset
+    }
+}
+
 fn text_for_range(source: &str, range: TextRange) -> String {
     utilities::text_for_range(source, range)
 }
@@ -2640,6 +3008,43 @@ END_CLASS
         assert!(insert.new_text.contains("PROPERTY PUBLIC Status"));
     }
 
+    #[test]
+    fn generate_interface_stubs_follows_extends_and_skips_defaults() {
+        let source = r#"
+INTERFACE IBase
+    METHOD Start
+    END_METHOD
+END_INTERFACE
+
+INTERFACE IDerived EXTENDS IBase
+    METHOD Stop
+    END_METHOD
+
+    METHOD Reset : BOOL
+        Reset := TRUE;
+    END_METHOD
+END_INTERFACE
+
+CLASS Pump IMPLEMENTS IDerived
+END_CLASS
+"#;
+        let mut db = Database::new();
+        let file_id = FileId(0);
+        db.set_source_text(file_id, source.to_string());
+
+        let offset = source.find("IMPLEMENTS IDerived").expect("implements");
+        let result =
+            generate_interface_stubs(&db, file_id, TextSize::from(offset as u32)).expect("stubs");
+        let edits = result.edits.get(&file_id).expect("file edits");
+        let insert = edits
+            .iter()
+            .find(|edit| !edit.new_text.is_empty())
+            .expect("insert edit");
+        assert!(insert.new_text.contains("METHOD PUBLIC Start"));
+        assert!(insert.new_text.contains("METHOD PUBLIC Stop"));
+        assert!(!insert.new_text.contains("Reset"));
+    }
+
     #[test]
     fn inline_variable_with_literal_initializer() {
         let source = r#"
@@ -2978,4 +3383,111 @@ END_PROGRAM
         let result = convert_function_block_to_function(&db, fb_id, TextSize::from(offset as u32));
         assert!(result.is_none(), "expected conversion to be unavailable");
     }
+
+    #[test]
+    fn introduce_local_constant_replaces_identical_literals_in_pou() {
+        let source = r#"
+PROGRAM Main
+    VAR
+        speed : INT;
+        limit : INT;
+    END_VAR
+    speed := 1500;
+    IF limit > 1500 THEN
+        limit := 1500;
+    END_IF;
+END_PROGRAM
+
+PROGRAM Other
+    VAR
+        speed : INT;
+    END_VAR
+    speed := 1500;
+END_PROGRAM
+"#;
+        let mut db = Database::new();
+        let file_id = FileId(0);
+        db.set_source_text(file_id, source.to_string());
+
+        let offset = source.find("1500").expect("literal") as u32;
+        let result = introduce_constant(
+            &db,
+            file_id,
+            TextRange::new(TextSize::from(offset), TextSize::from(offset + 4)),
+            &ConstantTarget::Local,
+        )
+        .expect("introduce constant");
+        assert_eq!(result.kind, ExtractTargetKind::Constant);
+        let edits = result.edits.edits.get(&file_id).expect("file edits");
+        let declaration = edits
+            .iter()
+            .find(|edit| edit.new_text.contains("VAR CONSTANT"))
+            .expect("declaration edit");
+        assert!(declaration
+            .new_text
+            .contains("ExtractedConstant : INT := 1500;"));
+        let other_start = source.find("PROGRAM Other").expect("other") as u32;
+        let replacements: Vec<_> = edits
+            .iter()
+            .filter(|edit| edit.new_text == "ExtractedConstant")
+            .collect();
+        assert_eq!(replacements.len(), 3);
+        assert!(replacements
+            .iter()
+            .all(|edit| u32::from(edit.range.start()) < other_start));
+    }
+
+    #[test]
+    fn introduce_global_constant_appends_to_selected_list() {
+        let globals = r#"
+CONFIGURATION Plant
+    VAR_GLOBAL CONSTANT
+        MaxPressure : REAL := 8.5;
+    END_VAR
+END_CONFIGURATION
+"#;
+        let source = r#"
+PROGRAM Main
+    VAR
+        message : STRING;
+    END_VAR
+    message := 'Pump fault';
+END_PROGRAM
+"#;
+        let mut db = Database::new();
+        db.set_source_text(FileId(0), globals.to_string());
+        db.set_source_text(FileId(1), source.to_string());
+
+        let lists = global_constant_lists(&db);
+        assert_eq!(lists.len(), 1);
+        assert_eq!(lists[0].container.as_deref(), Some("Plant"));
+
+        let offset = source.find("Pump").expect("literal") as u32;
+        let result = introduce_constant(
+            &db,
+            FileId(1),
+            TextRange::empty(TextSize::from(offset)),
+            &ConstantTarget::Global(lists[0].clone()),
+        )
+        .expect("introduce constant");
+        let declaration = result
+            .edits
+            .edits
+            .get(&FileId(0))
+            .and_then(|edits| edits.first())
+            .expect("declaration edit");
+        assert_eq!(
+            declaration.new_text,
+            "        ExtractedConstant : STRING := 'Pump fault';\n"
+        );
+        let replacement = result
+            .edits
+            .edits
+            .get(&FileId(1))
+            .and_then(|edits| edits.first())
+            .expect("replacement edit");
+        let start = usize::from(replacement.range.start());
+        let end = usize::from(replacement.range.end());
+        assert_eq!(&source[start..end], "'Pump fault'");
+    }
 }
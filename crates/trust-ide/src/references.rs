@@ -2,14 +2,21 @@
 //!
 //! This module provides functionality to find all references to a symbol.
 
+use rustc_hash::FxHashMap;
+use std::sync::Arc;
 use text_size::{TextRange, TextSize};
 
 use smol_str::SmolStr;
 use trust_hir::db::{FileId, SemanticDatabase};
+use trust_hir::direct_address::{
+    direct_address_uses, normalize_direct_address, DirectAddressUseKind,
+};
+use trust_hir::symbols::ParamDirection;
 use trust_hir::{Database, SourceDatabase, SymbolId, Type, TypeId};
 use trust_syntax::parser::parse;
 use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
 
+use crate::signature_help::{signature_for_call_expr, strip_execution_params};
 use crate::util::{
     field_declaration_ranges, ident_token_in_name, is_type_name_node, is_type_symbol_kind,
     qualified_name_from_field_expr, resolve_target_at_position_with_context,
@@ -27,6 +34,37 @@ pub struct Reference {
     pub is_write: bool,
 }
 
+/// How a reference accesses the referenced variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReferenceAccess {
+    /// The value is read.
+    Read,
+    /// The value is written (assignment target or `=>` output connection).
+    Write,
+    /// The variable is passed to a VAR_IN_OUT parameter (read and possibly written).
+    InOut,
+}
+
+impl ReferenceAccess {
+    /// Returns a stable lowercase label for the access kind.
+    pub fn label(self) -> &'static str {
+        match self {
+            ReferenceAccess::Read => "read",
+            ReferenceAccess::Write => "write",
+            ReferenceAccess::InOut => "inout",
+        }
+    }
+}
+
+/// A reference paired with its access classification.
+#[derive(Debug, Clone)]
+pub struct ClassifiedReference {
+    /// The underlying reference.
+    pub reference: Reference,
+    /// How the reference accesses its target.
+    pub access: ReferenceAccess,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct SymbolIdentity {
     file_id: FileId,
@@ -49,6 +87,10 @@ pub fn find_references(
 ) -> Vec<Reference> {
     let context = IdeContext::new(db, file_id);
 
+    if let Some(address) = direct_address_at(&context.root, position) {
+        return find_direct_address_references(db, &address, options);
+    }
+
     if let Some(target) = resolve_target_at_position_with_context(
         db,
         file_id,
@@ -84,6 +126,55 @@ pub fn find_references(
     Vec::new()
 }
 
+/// Finds every use of a direct address (`%QX0.3`) across the project: located
+/// declarations and `VAR_CONFIG` mappings (reported as declarations), and reads and
+/// writes in code. `%Q0.3` and `%QX0.3` name the same address.
+pub fn find_direct_address_references(
+    db: &Database,
+    address: &str,
+    options: FindReferencesOptions,
+) -> Vec<Reference> {
+    let Some(address) = normalize_direct_address(address) else {
+        return Vec::new();
+    };
+    let mut file_ids = db.file_ids();
+    file_ids.sort_by_key(|file_id| file_id.0);
+
+    let mut references = Vec::new();
+    for file_id in file_ids {
+        let source = db.source_text(file_id);
+        if !source.contains('%') {
+            continue;
+        }
+        let root = parse(&source).syntax();
+        references.extend(
+            direct_address_uses(&root)
+                .into_iter()
+                .filter(|entry| entry.address == address)
+                .filter(|entry| {
+                    options.include_declaration
+                        || matches!(
+                            entry.kind,
+                            DirectAddressUseKind::Read | DirectAddressUseKind::Write
+                        )
+                })
+                .map(|entry| Reference {
+                    file_id,
+                    range: entry.range,
+                    is_write: entry.kind == DirectAddressUseKind::Write,
+                }),
+        );
+    }
+    references
+}
+
+fn direct_address_at(root: &SyntaxNode, position: TextSize) -> Option<SmolStr> {
+    let token = root
+        .token_at_offset(position)
+        .find(|token| token.kind() == SyntaxKind::DirectAddress)?;
+    normalize_direct_address(token.text())
+}
+
 fn find_references_to_symbol_across_project(
     db: &Database,
     identity: SymbolIdentity,
@@ -489,7 +580,7 @@ fn find_type_references_to_symbol(
 
 // Text-based fallback intentionally omitted to keep references strictly symbol-aware.
 
-fn resolve_field_expr_member(
+pub(crate) fn resolve_field_expr_member(
     db: &Database,
     file_id: FileId,
     symbols: &trust_hir::symbols::SymbolTable,
@@ -682,6 +773,137 @@ fn symbol_identity(
 }
 
 /// Checks if a node is in a write context (LHS of assignment).
+/// Classifies references as reads, writes, or VAR_IN_OUT passes.
+///
+/// Call arguments are resolved against the callee signature so that `=>` output
+/// connections count as writes and arguments bound to VAR_IN_OUT parameters
+/// count as in-out passes. Each file is parsed once per call.
+pub fn classify_references(db: &Database, references: Vec<Reference>) -> Vec<ClassifiedReference> {
+    let mut roots: FxHashMap<FileId, (Arc<String>, SyntaxNode)> = FxHashMap::default();
+    references
+        .into_iter()
+        .map(|reference| {
+            let (source, root) = roots.entry(reference.file_id).or_insert_with(|| {
+                let source = db.source_text(reference.file_id);
+                let root = parse(&source).syntax();
+                (source, root)
+            });
+            let access = reference_access(
+                db,
+                reference.file_id,
+                source.as_str(),
+                root,
+                reference.range,
+            );
+            ClassifiedReference { reference, access }
+        })
+        .collect()
+}
+
+pub(crate) fn reference_access(
+    db: &Database,
+    file_id: FileId,
+    source: &str,
+    root: &SyntaxNode,
+    range: TextRange,
+) -> ReferenceAccess {
+    match reference_consumer(root, range) {
+        Some((parent, true)) if parent.kind() == SyntaxKind::AssignStmt => ReferenceAccess::Write,
+        Some((parent, _)) if parent.kind() == SyntaxKind::Arg => {
+            call_arg_access(db, file_id, source, root, &parent)
+        }
+        _ => ReferenceAccess::Read,
+    }
+}
+
+/// Returns the node consuming the access expression that contains the
+/// reference at `range`, and whether the expression is its first child.
+///
+/// Field, dereference, and parenthesized expressions as well as array bases
+/// are part of the access expression; array indices are not.
+pub(crate) fn reference_consumer(
+    root: &SyntaxNode,
+    range: TextRange,
+) -> Option<(SyntaxNode, bool)> {
+    let token = root.token_at_offset(range.start()).right_biased()?;
+    let mut current = token.parent()?;
+    while let Some(parent) = current.parent() {
+        let is_first = parent
+            .first_child()
+            .is_some_and(|first| first.text_range() == current.text_range());
+        let climbs = match parent.kind() {
+            SyntaxKind::FieldExpr | SyntaxKind::DerefExpr | SyntaxKind::ParenExpr => true,
+            SyntaxKind::IndexExpr => is_first,
+            _ => false,
+        };
+        if !climbs {
+            return Some((parent, is_first));
+        }
+        current = parent;
+    }
+    None
+}
+
+fn call_arg_access(
+    db: &Database,
+    file_id: FileId,
+    source: &str,
+    root: &SyntaxNode,
+    arg: &SyntaxNode,
+) -> ReferenceAccess {
+    let mut named = false;
+    for token in arg
+        .children_with_tokens()
+        .filter_map(|element| element.into_token())
+    {
+        match token.kind() {
+            SyntaxKind::Arrow => return ReferenceAccess::Write,
+            SyntaxKind::Assign => named = true,
+            _ => {}
+        }
+    }
+    let Some(arg_list) = arg.parent() else {
+        return ReferenceAccess::Read;
+    };
+    let Some(call_expr) = arg_list
+        .parent()
+        .filter(|node| node.kind() == SyntaxKind::CallExpr)
+    else {
+        return ReferenceAccess::Read;
+    };
+    let Some(signature) = signature_for_call_expr(db, file_id, source, root, &call_expr) else {
+        return ReferenceAccess::Read;
+    };
+    let direction = if named {
+        let Some(name) = arg
+            .children()
+            .find(|child| child.kind() == SyntaxKind::Name)
+            .map(|name| name.text().to_string())
+        else {
+            return ReferenceAccess::Read;
+        };
+        signature
+            .params
+            .iter()
+            .find(|param| param.name.eq_ignore_ascii_case(name.trim()))
+            .map(|param| param.direction)
+    } else {
+        let index = arg_list
+            .children()
+            .filter(|child| child.kind() == SyntaxKind::Arg)
+            .position(|child| child.text_range() == arg.text_range());
+        let signature = strip_execution_params(&signature);
+        index
+            .and_then(|index| signature.params.get(index))
+            .map(|param| param.direction)
+    };
+    match direction {
+        Some(ParamDirection::InOut) => ReferenceAccess::InOut,
+        Some(ParamDirection::Out) => ReferenceAccess::Write,
+        _ => ReferenceAccess::Read,
+    }
+}
+
 fn is_write_context(expr: &SyntaxNode) -> bool {
     let mut current = expr.clone();
     while let Some(parent) = current.parent() {
@@ -1,18 +1,27 @@
 //! Rename for Structured Text.
 //!
 //! This module provides safe symbol renaming functionality.
+//!
+//! Besides references in code, a rename updates the dotted access paths that name the
+//! symbol, e.g. `P1.Count` in a `VAR_CONFIG` block. The same resolution is available for
+//! paths written in configuration files through [`access_path_references`].
 
+use smol_str::SmolStr;
 use std::collections::HashMap;
 use text_size::{TextRange, TextSize};
 
 use crate::refactor::{move_namespace_path, namespace_full_path, parse_namespace_path};
 use crate::references::{find_references, find_references_to_field, FindReferencesOptions};
-use crate::util::{ident_at_offset, resolve_target_at_position, FieldTarget, ResolvedTarget};
+use crate::util::{
+    ident_at_offset, ident_token_in_name, resolve_target_at_position, FieldTarget, ResolvedTarget,
+};
 use trust_hir::db::{FileId, SemanticDatabase};
-use trust_hir::symbols::{ScopeId, SymbolTable};
+use trust_hir::symbols::{ScopeId, SymbolKind, SymbolTable, VarQualifier};
 use trust_hir::{
-    is_reserved_keyword, is_valid_identifier, Database, SourceDatabase, SymbolId, Type,
+    is_reserved_keyword, is_valid_identifier, Database, SourceDatabase, SymbolId, Type, TypeId,
 };
+use trust_syntax::parser::parse;
+use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
 
 /// A text edit representing a change to the source.
 #[derive(Debug, Clone)]
@@ -57,6 +66,15 @@ impl Default for RenameResult {
     }
 }
 
+/// A symbol targeted by a rename, identified by the file that declares it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenameTarget {
+    /// The file declaring the symbol.
+    pub file_id: FileId,
+    /// The symbol ID within that file.
+    pub symbol_id: SymbolId,
+}
+
 /// Prepares a rename operation, checking if rename is valid at the position.
 pub fn prepare_rename(db: &Database, file_id: FileId, position: TextSize) -> Option<TextRange> {
     let source = db.source_text(file_id);
@@ -106,6 +124,37 @@ pub fn rename(
     }
 }
 
+/// Returns the symbol a rename at `position` would change.
+///
+/// Fields of structs and unions are not symbols and return `None`.
+pub fn rename_target(db: &Database, file_id: FileId, position: TextSize) -> Option<RenameTarget> {
+    let ResolvedTarget::Symbol(symbol_id) = resolve_target_at_position(db, file_id, position)?
+    else {
+        return None;
+    };
+    let symbols = db.file_symbols_with_project(file_id);
+    Some(declared_target(file_id, &symbols, symbol_id))
+}
+
+/// Returns the ranges of the segments of a dotted access path that name `target`.
+///
+/// Paths have the form used by `VAR_CONFIG` and the runtime configuration files:
+/// `P1.Count`, `Res.P1.Motor.Speed`, `P1.Values[2].Level`, or a global name. Ranges
+/// are relative to `path`; a path that does not resolve yields no ranges.
+pub fn access_path_references(db: &Database, target: RenameTarget, path: &str) -> Vec<TextRange> {
+    let Some(segments) = parse_access_path_text(path) else {
+        return Vec::new();
+    };
+    for file_id in db.file_ids() {
+        let symbols = db.file_symbols_with_project(file_id);
+        let Some(resolved) = resolve_access_path(db, file_id, &symbols, &segments, true) else {
+            continue;
+        };
+        return matching_segments(file_id, &symbols, &segments, &resolved, target);
+    }
+    Vec::new()
+}
+
 /// Renames a symbol by ID using semantic reference finding.
 pub fn rename_symbol(
     db: &Database,
@@ -154,6 +203,17 @@ pub fn rename_symbol(
         );
     }
 
+    let target = declared_target(file_id, &symbols, symbol_id);
+    for (path_file_id, range) in var_config_references(db, target) {
+        result.add_edit(
+            path_file_id,
+            TextEdit {
+                range,
+                new_text: new_name.to_string(),
+            },
+        );
+    }
+
     Some(result)
 }
 
@@ -199,6 +259,302 @@ fn rename_field(
     Some(result)
 }
 
+/// One segment of an access path, with any array subscripts that follow it.
+struct AccessPathSegment {
+    name: SmolStr,
+    range: TextRange,
+    /// Number of indices in each subscript, e.g. `[1, 2][3]` is `[2, 1]`.
+    subscripts: Vec<usize>,
+}
+
+fn parse_access_path_text(path: &str) -> Option<Vec<AccessPathSegment>> {
+    let mut segments = Vec::new();
+    let mut offset = 0;
+    for part in path.split('.') {
+        let name_end = part.find('[').unwrap_or(part.len());
+        let (name, subscripts) = part.split_at(name_end);
+        if !is_valid_identifier(name) {
+            return None;
+        }
+        segments.push(AccessPathSegment {
+            name: SmolStr::new(name),
+            range: TextRange::at(
+                TextSize::from(offset as u32),
+                TextSize::from(name.len() as u32),
+            ),
+            subscripts: subscripts
+                .split('[')
+                .skip(1)
+                .map(|subscript| subscript.split(',').count())
+                .collect(),
+        });
+        offset += part.len() + 1;
+    }
+    Some(segments)
+}
+
+fn access_path_segments(node: &SyntaxNode) -> Vec<AccessPathSegment> {
+    let mut segments: Vec<AccessPathSegment> = Vec::new();
+    let mut indices = 0;
+    for element in node.children_with_tokens() {
+        match element.kind() {
+            SyntaxKind::Name => {
+                let Some(ident) = element.as_node().and_then(ident_token_in_name) else {
+                    continue;
+                };
+                segments.push(AccessPathSegment {
+                    name: SmolStr::new(ident.text()),
+                    range: ident.text_range(),
+                    subscripts: Vec::new(),
+                });
+            }
+            SyntaxKind::LBracket => indices = 1,
+            SyntaxKind::Comma => indices += 1,
+            SyntaxKind::RBracket => {
+                if let Some(segment) = segments.last_mut() {
+                    segment.subscripts.push(indices);
+                }
+            }
+            _ => {}
+        }
+    }
+    segments
+}
+
+/// Finds the `VAR_CONFIG` access path segments in the project that name `target`.
+fn var_config_references(db: &Database, target: RenameTarget) -> Vec<(FileId, TextRange)> {
+    let mut references = Vec::new();
+    for file_id in db.file_ids() {
+        let source = db.source_text(file_id);
+        let parsed = parse(&source);
+        let root = parsed.syntax();
+        let mut paths = root
+            .descendants()
+            .filter(|node| node.kind() == SyntaxKind::AccessPath)
+            .peekable();
+        if paths.peek().is_none() {
+            continue;
+        }
+        let symbols = db.file_symbols_with_project(file_id);
+        for path in paths {
+            let segments = access_path_segments(&path);
+            let Some(resolved) = resolve_access_path(db, file_id, &symbols, &segments, false)
+            else {
+                continue;
+            };
+            references.extend(
+                matching_segments(file_id, &symbols, &segments, &resolved, target)
+                    .into_iter()
+                    .map(|range| (file_id, range)),
+            );
+        }
+    }
+    references
+}
+
+fn matching_segments(
+    file_id: FileId,
+    symbols: &SymbolTable,
+    segments: &[AccessPathSegment],
+    resolved: &[Option<SymbolId>],
+    target: RenameTarget,
+) -> Vec<TextRange> {
+    segments
+        .iter()
+        .zip(resolved)
+        .filter(|(_, symbol_id)| {
+            symbol_id.is_some_and(|id| declared_target(file_id, symbols, id) == target)
+        })
+        .map(|(segment, _)| segment.range)
+        .collect()
+}
+
+/// Maps a symbol seen from `file_id` back to the file that declares it.
+fn declared_target(file_id: FileId, symbols: &SymbolTable, symbol_id: SymbolId) -> RenameTarget {
+    match symbols.get(symbol_id).and_then(|symbol| symbol.origin) {
+        Some(origin) => RenameTarget {
+            file_id: origin.file_id,
+            symbol_id: origin.symbol_id,
+        },
+        None => RenameTarget { file_id, symbol_id },
+    }
+}
+
+/// Resolves each segment of an access path to the symbol it names, if any.
+///
+/// Leading configuration and resource names are skipped. The root is a program
+/// instance, a program, or a global; later segments are program members, function
+/// block members, or struct fields (which are not symbols). With `local_root`, the
+/// root must be declared in `file_id` so that each path is resolved only once.
+fn resolve_access_path(
+    db: &Database,
+    file_id: FileId,
+    symbols: &SymbolTable,
+    segments: &[AccessPathSegment],
+    local_root: bool,
+) -> Option<Vec<Option<SymbolId>>> {
+    let mut resolved = Vec::with_capacity(segments.len());
+    let mut remaining = segments.iter();
+    let (root_id, root_segment) = loop {
+        let segment = remaining.next()?;
+        let symbol_id = access_path_root(symbols, &segment.name)?;
+        resolved.push(Some(symbol_id));
+        let kind = &symbols.get(symbol_id)?.kind;
+        if !matches!(kind, SymbolKind::Configuration | SymbolKind::Resource) {
+            break (symbol_id, segment);
+        }
+    };
+    let root = symbols.get(root_id)?;
+    if local_root && root.origin.is_some() {
+        return None;
+    }
+
+    let mut owner = match root.kind {
+        SymbolKind::ProgramInstance => {
+            program_of_instance(db, file_id, symbols, root_id).map(PathOwner::Symbol)
+        }
+        SymbolKind::Program | SymbolKind::Namespace => Some(PathOwner::Symbol(root_id)),
+        _ => apply_subscripts(symbols, root.type_id, &root_segment.subscripts).map(PathOwner::Type),
+    };
+    for segment in remaining {
+        let (member, member_type) = match owner {
+            Some(PathOwner::Symbol(owner_id)) => {
+                let member = symbols.resolve_member_symbol_in_hierarchy(owner_id, &segment.name);
+                (
+                    member,
+                    member.and_then(|id| symbols.get(id)).map(|m| m.type_id),
+                )
+            }
+            Some(PathOwner::Type(type_id)) => {
+                match symbols.type_by_id(symbols.resolve_alias_type(type_id)) {
+                    Some(
+                        Type::FunctionBlock { .. } | Type::Class { .. } | Type::Interface { .. },
+                    ) => {
+                        let member = symbols.resolve_member_symbol_in_type(type_id, &segment.name);
+                        (
+                            member,
+                            member.and_then(|id| symbols.get(id)).map(|m| m.type_id),
+                        )
+                    }
+                    Some(Type::Struct { fields, .. }) => (
+                        None,
+                        fields
+                            .iter()
+                            .find(|field| field.name.eq_ignore_ascii_case(&segment.name))
+                            .map(|field| field.type_id),
+                    ),
+                    Some(Type::Union { variants, .. }) => (
+                        None,
+                        variants
+                            .iter()
+                            .find(|variant| variant.name.eq_ignore_ascii_case(&segment.name))
+                            .map(|variant| variant.type_id),
+                    ),
+                    _ => (None, None),
+                }
+            }
+            None => (None, None),
+        };
+        resolved.push(member);
+        owner = member_type
+            .and_then(|type_id| apply_subscripts(symbols, type_id, &segment.subscripts))
+            .map(PathOwner::Type);
+    }
+    Some(resolved)
+}
+
+#[derive(Clone, Copy)]
+enum PathOwner {
+    Symbol(SymbolId),
+    Type(TypeId),
+}
+
+fn access_path_root(symbols: &SymbolTable, name: &str) -> Option<SymbolId> {
+    symbols
+        .iter()
+        .filter(|symbol| symbol.name.eq_ignore_ascii_case(name))
+        .filter_map(|symbol| {
+            let rank = match symbol.kind {
+                SymbolKind::ProgramInstance => 0,
+                SymbolKind::Variable {
+                    qualifier: VarQualifier::Global,
+                } => 1,
+                SymbolKind::Program => 2,
+                SymbolKind::Resource | SymbolKind::Configuration => 3,
+                SymbolKind::Namespace => 4,
+                _ => return None,
+            };
+            Some((rank, symbol.id))
+        })
+        .min_by_key(|(rank, _)| *rank)
+        .map(|(_, symbol_id)| symbol_id)
+}
+
+fn apply_subscripts(
+    symbols: &SymbolTable,
+    type_id: TypeId,
+    subscripts: &[usize],
+) -> Option<TypeId> {
+    let mut current = type_id;
+    for indices in subscripts {
+        let Some(Type::Array {
+            element,
+            dimensions,
+        }) = symbols.type_by_id(symbols.resolve_alias_type(current))
+        else {
+            return None;
+        };
+        if *indices != dimensions.len() {
+            return None;
+        }
+        current = *element;
+    }
+    Some(current)
+}
+
+/// Returns the program a `PROGRAM P1 WITH Task : Main;` instance runs.
+fn program_of_instance(
+    db: &Database,
+    file_id: FileId,
+    symbols: &SymbolTable,
+    instance_id: SymbolId,
+) -> Option<SymbolId> {
+    let instance = symbols.get(instance_id)?;
+    let (file_id, range) = match instance.origin {
+        Some(origin) => {
+            let origin_symbols = db.file_symbols(origin.file_id);
+            (origin.file_id, origin_symbols.get(origin.symbol_id)?.range)
+        }
+        None => (file_id, instance.range),
+    };
+    let source = db.source_text(file_id);
+    let root = parse(&source).syntax();
+    let config = root
+        .descendants()
+        .filter(|node| node.kind() == SyntaxKind::ProgramConfig)
+        .find(|node| {
+            node.children()
+                .find(|child| child.kind() == SyntaxKind::Name)
+                .and_then(|name| ident_token_in_name(&name))
+                .is_some_and(|ident| ident.text_range() == range)
+        })?;
+    let type_parts: Vec<SmolStr> = config
+        .children()
+        .find(|child| child.kind() == SyntaxKind::QualifiedName)?
+        .children()
+        .filter_map(|name| ident_token_in_name(&name))
+        .map(|ident| SmolStr::new(ident.text()))
+        .collect();
+    let program_id =
+        symbols
+            .resolve_qualified(&type_parts)
+            .or_else(|| match type_parts.as_slice() {
+                [name] => symbols.lookup_any(name),
+                _ => None,
+            })?;
+    matches!(symbols.get(program_id)?.kind, SymbolKind::Program).then_some(program_id)
+}
+
 /// Checks if renaming a symbol to the new name would cause a conflict.
 fn has_conflict(symbols: &SymbolTable, symbol_id: SymbolId, new_name: &str) -> bool {
     // Verify symbol exists
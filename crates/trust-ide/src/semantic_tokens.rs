@@ -44,6 +44,12 @@ pub enum SemanticTokenType {
     EnumMember,
     /// A namespace.
     Namespace,
+    /// A directly represented variable (`%IX0.0`, `%QW10`, `%MD4`).
+    DirectAddress,
+    /// A task name in a resource configuration.
+    Task,
+    /// A pragma in braces (`{attribute 'hide'}`).
+    Pragma,
 }
 
 /// Semantic token modifiers.
@@ -59,6 +65,16 @@ pub struct SemanticTokenModifiers {
     pub is_static: bool,
     /// This is a modification (write).
     pub modification: bool,
+    /// Input area address (`%I`).
+    pub input: bool,
+    /// Output area address (`%Q`).
+    pub output: bool,
+    /// Memory area address (`%M`).
+    pub memory: bool,
+    /// Duration literal (`T#1s`, `LTIME#5ms`).
+    pub duration: bool,
+    /// Typed literal (`INT#5`, `D#2024-01-15`, `TOD#12:00:00`).
+    pub typed: bool,
 }
 
 /// A semantic token.
@@ -103,7 +119,7 @@ fn symbol_kind_to_token_type(kind: &SymbolKind) -> SemanticTokenType {
         SymbolKind::Program => SemanticTokenType::Namespace,
         SymbolKind::Configuration => SemanticTokenType::Namespace,
         SymbolKind::Resource => SemanticTokenType::Namespace,
-        SymbolKind::Task => SemanticTokenType::Function,
+        SymbolKind::Task => SemanticTokenType::Task,
         SymbolKind::ProgramInstance => SemanticTokenType::Variable,
         SymbolKind::Namespace => SemanticTokenType::Namespace,
         SymbolKind::Function { .. } => SemanticTokenType::Function,
@@ -272,8 +288,10 @@ pub fn semantic_tokens(db: &Database, file_id: FileId) -> Vec<SemanticToken> {
     let symbols = db.file_symbols(file_id);
 
     let mut result = Vec::new();
+    let mut after_typed_prefix = false;
 
     for token in tokens {
+        let typed_operand = std::mem::take(&mut after_typed_prefix);
         let semantic_type = match token.kind {
             // Keywords
             TokenKind::KwProgram
@@ -422,16 +440,43 @@ pub fn semantic_tokens(db: &Database, file_id: FileId) -> Vec<SemanticToken> {
             | TokenKind::KwAnyDate => Some(SemanticTokenType::Type),
 
             // Literals
+            TokenKind::IntLiteral | TokenKind::RealLiteral if typed_operand => {
+                result.push(typed_literal_token(token.range, SemanticTokenType::Number));
+                continue;
+            }
+            TokenKind::StringLiteral | TokenKind::WideStringLiteral if typed_operand => {
+                result.push(typed_literal_token(token.range, SemanticTokenType::String));
+                continue;
+            }
             TokenKind::IntLiteral | TokenKind::RealLiteral => Some(SemanticTokenType::Number),
-            TokenKind::StringLiteral
-            | TokenKind::WideStringLiteral
-            | TokenKind::TimeLiteral
-            | TokenKind::DateLiteral
+            TokenKind::StringLiteral | TokenKind::WideStringLiteral => {
+                Some(SemanticTokenType::String)
+            }
+            TokenKind::TimeLiteral => {
+                let mut semantic = SemanticToken::new(token.range, SemanticTokenType::Number);
+                semantic.modifiers.duration = true;
+                result.push(semantic);
+                continue;
+            }
+            TokenKind::DateLiteral
             | TokenKind::TimeOfDayLiteral
-            | TokenKind::DateAndTimeLiteral => Some(SemanticTokenType::String),
+            | TokenKind::DateAndTimeLiteral => {
+                result.push(typed_literal_token(token.range, SemanticTokenType::Number));
+                continue;
+            }
+            TokenKind::TypedLiteralPrefix => {
+                after_typed_prefix = true;
+                result.push(typed_literal_token(token.range, SemanticTokenType::Type));
+                continue;
+            }
+            TokenKind::DirectAddress => {
+                result.push(direct_address_token(&source, token.range));
+                continue;
+            }
 
-            // Comments
+            // Comments and pragmas
             TokenKind::LineComment | TokenKind::BlockComment => Some(SemanticTokenType::Comment),
+            TokenKind::Pragma => Some(SemanticTokenType::Pragma),
 
             // Operators
             TokenKind::Assign
@@ -472,6 +517,24 @@ pub fn semantic_tokens(db: &Database, file_id: FileId) -> Vec<SemanticToken> {
     result
 }
 
+fn typed_literal_token(range: TextRange, token_type: SemanticTokenType) -> SemanticToken {
+    let mut token = SemanticToken::new(range, token_type);
+    token.modifiers.typed = true;
+    token
+}
+
+/// Classifies a direct address by its location prefix (IEC 61131-3 Table 16).
+fn direct_address_token(source: &str, range: TextRange) -> SemanticToken {
+    let mut token = SemanticToken::new(range, SemanticTokenType::DirectAddress);
+    match source[range].as_bytes().get(1).map(u8::to_ascii_uppercase) {
+        Some(b'I') => token.modifiers.input = true,
+        Some(b'Q') => token.modifiers.output = true,
+        Some(b'M') => token.modifiers.memory = true,
+        _ => {}
+    }
+    token
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
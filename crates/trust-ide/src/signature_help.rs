@@ -7,7 +7,7 @@ use smol_str::SmolStr;
 use text_size::{TextRange, TextSize};
 
 use trust_hir::db::{FileId, SemanticDatabase};
-use trust_hir::symbols::{ParamDirection, Symbol, SymbolKind, SymbolTable};
+use trust_hir::symbols::{ParamDirection, Symbol, SymbolId, SymbolKind, SymbolTable};
 use trust_hir::{Database, SourceDatabase, TypeId};
 use trust_syntax::parser::parse;
 use trust_syntax::syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
@@ -67,6 +67,8 @@ pub(crate) struct ParamData {
     pub(crate) name: SmolStr,
     pub(crate) type_id: TypeId,
     pub(crate) direction: ParamDirection,
+    /// Declaring symbol; `None` for built-in standard functions.
+    pub(crate) symbol_id: Option<SymbolId>,
 }
 
 #[derive(Debug, Clone)]
@@ -85,6 +87,7 @@ struct ArgInfo {
 pub(crate) struct CallSignatureContext {
     pub(crate) signature: SignatureInfo,
     pub(crate) used_params: FxHashSet<SmolStr>,
+    pub(crate) arg_count: usize,
 }
 
 pub(crate) fn signature_for_call_expr(
@@ -163,6 +166,7 @@ pub(crate) fn call_signature_context(
     } else {
         strip_execution_params(&signature)
     };
+    let arg_count = args.len();
     let mut used_params: FxHashSet<SmolStr> = FxHashSet::default();
     for arg in args {
         if let Some(name) = arg.name {
@@ -173,6 +177,7 @@ pub(crate) fn call_signature_context(
     Some(CallSignatureContext {
         signature,
         used_params,
+        arg_count,
     })
 }
 
@@ -198,6 +203,36 @@ pub fn call_signature_info(
     })
 }
 
+/// Returns the declared type of the parameter that the call argument at `position`
+/// binds to. Generic parameters (`ANY_*`) yield `None`.
+pub fn call_argument_type(db: &Database, file_id: FileId, position: TextSize) -> Option<TypeId> {
+    let source = db.source_text(file_id);
+    let parsed = parse(&source);
+    let root = parsed.syntax();
+    let token = find_token_at_position(&root, position)?;
+    let arg_list = token
+        .parent_ancestors()
+        .find(|node| node.kind() == SyntaxKind::Arg)?
+        .parent()?;
+    let call_expr = arg_list
+        .parent()
+        .filter(|node| node.kind() == SyntaxKind::CallExpr)?;
+
+    let signature = signature_for_call_expr(db, file_id, &source, &root, &call_expr)?;
+    let args = collect_call_args(&arg_list);
+    let formal_call = args.iter().any(|arg| arg.name.is_some());
+    let signature = if formal_call {
+        signature
+    } else {
+        strip_execution_params(&signature)
+    };
+    let active_arg = active_arg_index(&args, &arg_list, position);
+    let param = signature
+        .params
+        .get(active_param_index(&args, active_arg, &signature.params))?;
+    (!is_generic_type(param.type_id)).then_some(param.type_id)
+}
+
 /// Computes signature help information at a given position.
 pub fn signature_help(
     db: &Database,
@@ -371,7 +406,7 @@ fn is_generic_type(type_id: TypeId) -> bool {
     )
 }
 
-fn strip_execution_params(signature: &SignatureInfo) -> SignatureInfo {
+pub(crate) fn strip_execution_params(signature: &SignatureInfo) -> SignatureInfo {
     let mut filtered = signature.clone();
     filtered
         .params
@@ -480,6 +515,7 @@ fn callable_params(symbols: &SymbolTable, symbol: &Symbol) -> Vec<ParamData> {
                     name: sym.name.clone(),
                     type_id: sym.type_id,
                     direction,
+                    symbol_id: Some(id),
                 }),
                 _ => None,
             }
@@ -805,6 +841,26 @@ fn standard_signature(name: &str, arg_count: usize) -> Option<SignatureInfo> {
             Some(TypeId::VOID),
         ),
         "DAY_OF_WEEK" => (vec![param("IN", TypeId::DATE)], Some(TypeId::INT)),
+        "LOWER_BOUND" | "UPPER_BOUND" => (
+            vec![param("ARR", TypeId::ANY), param("DIM", TypeId::ANY_INT)],
+            Some(TypeId::DINT),
+        ),
+
+        // Q16.16 fixed-point extensions
+        "TO_FIX" => (vec![param("IN", TypeId::ANY_NUM)], Some(TypeId::DINT)),
+        "FIX_TO_DINT" | "FIX_ABS" | "FIX_SQRT" => {
+            (vec![param("IN", TypeId::DINT)], Some(TypeId::DINT))
+        }
+        "FIX_TO_REAL" => (vec![param("IN", TypeId::DINT)], Some(TypeId::REAL)),
+        "FIX_TO_LREAL" => (vec![param("IN", TypeId::DINT)], Some(TypeId::LREAL)),
+        "FIX_ADD" | "FIX_SUB" | "FIX_MUL" | "FIX_DIV" => {
+            (fixed_in("IN", 2, TypeId::DINT), Some(TypeId::DINT))
+        }
+
+        // Byte-order extensions
+        "BE_TO_HOST" | "HOST_TO_BE" | "LE_TO_HOST" | "HOST_TO_LE" => {
+            (vec![param("IN", TypeId::ANY_BIT)], None)
+        }
 
         // Special calls
         "REF" => (vec![param("IN", TypeId::ANY)], None),
@@ -911,6 +967,7 @@ fn param(name: &str, type_id: TypeId) -> ParamData {
         name: SmolStr::new(name),
         type_id,
         direction: ParamDirection::In,
+        symbol_id: None,
     }
 }
 
@@ -919,6 +976,7 @@ fn out_param(name: &str, type_id: TypeId) -> ParamData {
         name: SmolStr::new(name),
         type_id,
         direction: ParamDirection::Out,
+        symbol_id: None,
     }
 }
 
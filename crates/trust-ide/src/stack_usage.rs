@@ -0,0 +1,951 @@
+//! Worst-case call depth and stack usage analysis.
+//!
+//! Builds a POU call graph across all files and walks it from every program
+//! instance declared in a CONFIGURATION (or from every PROGRAM when no
+//! program instance exists). Each entry point reports its deepest call chain
+//! together with an estimate of the stack consumed along it.
+//!
+//! Calls that close a cycle are reported as recursion, which IEC 61131-3 does
+//! not permit and which makes the call depth of every affected entry point
+//! unbounded.
+//!
+//! Frame sizes are estimates: FUNCTIONs and METHODs reserve their parameters,
+//! locals, and return value, while PROGRAM and FUNCTION_BLOCK bodies only
+//! reserve VAR_TEMP storage and VAR_IN_OUT references because their other
+//! variables live in instance memory.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use smol_str::SmolStr;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use text_size::TextRange;
+
+use trust_hir::db::{FileId, SourceDatabase};
+use trust_hir::symbols::{ParamDirection, SymbolId, SymbolKind, SymbolTable, VarQualifier};
+use trust_hir::{Database, Type, TypeId};
+use trust_syntax::parser::parse;
+use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
+
+use crate::util::{
+    is_pou_symbol_kind, qualified_name_parts_from_node, resolve_target_at_position_with_context,
+    scope_at_position, ResolvedTarget,
+};
+
+/// Size assumed for references, pointers, and interface values.
+const REFERENCE_BYTES: u64 = 8;
+/// Default STRING/WSTRING length when none is declared.
+const DEFAULT_STRING_LEN: u64 = 80;
+/// Nesting limit when sizing FUNCTION_BLOCK instances and structures.
+const MAX_TYPE_DEPTH: usize = 16;
+
+/// A POU on a call chain.
+#[derive(Debug, Clone)]
+pub struct StackFrame {
+    /// POU name.
+    pub name: SmolStr,
+    /// Owning type name for methods.
+    pub container: Option<SmolStr>,
+    /// Symbol kind.
+    pub kind: SymbolKind,
+    /// File containing the declaration.
+    pub file_id: FileId,
+    /// Declaration range.
+    pub range: TextRange,
+    /// Estimated frame size in bytes.
+    pub frame_bytes: u64,
+}
+
+impl StackFrame {
+    /// Returns the display name (`Type.Method` for methods).
+    #[must_use]
+    pub fn qualified_name(&self) -> String {
+        match &self.container {
+            Some(container) => format!("{container}.{}", self.name),
+            None => self.name.to_string(),
+        }
+    }
+}
+
+/// Worst-case call chain for one entry point.
+#[derive(Debug, Clone)]
+pub struct StackUsageEntry {
+    /// Program instance name (the PROGRAM name for implicit entry points).
+    pub name: SmolStr,
+    /// Task the program instance is attached to, if declared.
+    pub task: Option<SmolStr>,
+    /// File containing the entry declaration.
+    pub file_id: FileId,
+    /// Entry declaration range.
+    pub range: TextRange,
+    /// Number of frames on the deepest call chain (the program counts as one).
+    pub max_depth: usize,
+    /// Estimated worst-case stack usage in bytes.
+    pub stack_bytes: u64,
+    /// Call chain with the highest stack usage, starting at the program.
+    pub worst_path: Vec<StackFrame>,
+    /// True when a recursive call is reachable, so depth and usage are unbounded.
+    pub unbounded: bool,
+}
+
+/// A call that closes a recursion cycle.
+#[derive(Debug, Clone)]
+pub struct RecursiveCall {
+    /// Calling POU.
+    pub caller: StackFrame,
+    /// Called POU.
+    pub callee: StackFrame,
+    /// Names along the cycle, starting and ending at the caller.
+    pub cycle: Vec<String>,
+    /// File containing the call.
+    pub file_id: FileId,
+    /// Call expression range.
+    pub range: TextRange,
+}
+
+/// Result of a workspace-wide stack usage analysis.
+#[derive(Debug, Clone, Default)]
+pub struct StackUsageReport {
+    /// Per entry point call depth and stack usage.
+    pub entries: Vec<StackUsageEntry>,
+    /// True when no program instance was found and every PROGRAM was used as entry point.
+    pub implicit_entry_points: bool,
+    /// Calls that take part in recursion.
+    pub recursive_calls: Vec<RecursiveCall>,
+}
+
+/// Options for stack usage analysis.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StackUsageOptions<'a> {
+    /// Files that participate in the analysis (defaults to all files).
+    pub files: Option<&'a FxHashSet<FileId>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct SymbolKey {
+    pub(crate) file_id: FileId,
+    pub(crate) symbol_id: SymbolId,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CallSite {
+    pub(crate) callee: SymbolKey,
+    pub(crate) file_id: FileId,
+    pub(crate) range: TextRange,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EntryPoint {
+    pub(crate) name: SmolStr,
+    pub(crate) task: Option<SmolStr>,
+    pub(crate) file_id: FileId,
+    pub(crate) range: TextRange,
+    pub(crate) program: SymbolKey,
+}
+
+/// POU call graph and entry points shared by the workspace timing analyses.
+pub(crate) struct CallGraph {
+    pub(crate) calls: FxHashMap<SymbolKey, Vec<CallSite>>,
+    pub(crate) tables: FxHashMap<FileId, Arc<SymbolTable>>,
+    pub(crate) entry_points: Vec<EntryPoint>,
+    pub(crate) implicit_entry_points: bool,
+    /// Component index of every POU that takes part in a call cycle.
+    pub(crate) components: FxHashMap<SymbolKey, usize>,
+}
+
+impl CallGraph {
+    pub(crate) fn build(db: &Database, files: Option<&FxHashSet<FileId>>) -> Self {
+        let mut file_ids: Vec<FileId> = match files {
+            Some(files) => files.iter().copied().collect(),
+            None => db.file_ids(),
+        };
+        file_ids.sort_by_key(|id| id.0);
+
+        let mut calls: FxHashMap<SymbolKey, Vec<CallSite>> = FxHashMap::default();
+        let mut tables: FxHashMap<FileId, Arc<SymbolTable>> = FxHashMap::default();
+        let mut entry_points = Vec::new();
+        for &file_id in &file_ids {
+            let symbols = match files {
+                Some(files) => db.file_symbols_with_project_filtered(file_id, files),
+                None => db.file_symbols_with_project(file_id),
+            };
+            let source = db.source_text(file_id);
+            let root = parse(&source).syntax();
+            collect_calls(db, file_id, &source, &root, &symbols, &mut calls);
+            collect_program_instances(file_id, &root, &symbols, &mut entry_points);
+            tables.insert(file_id, symbols);
+        }
+
+        let implicit_entry_points = entry_points.is_empty();
+        if implicit_entry_points {
+            for &file_id in &file_ids {
+                let Some(symbols) = tables.get(&file_id) else {
+                    continue;
+                };
+                for symbol in symbols.iter() {
+                    if symbol.origin.is_none() && matches!(symbol.kind, SymbolKind::Program) {
+                        entry_points.push(EntryPoint {
+                            name: symbol.name.clone(),
+                            task: None,
+                            file_id,
+                            range: symbol.range,
+                            program: SymbolKey {
+                                file_id,
+                                symbol_id: symbol.id,
+                            },
+                        });
+                    }
+                }
+            }
+        }
+
+        let components = strongly_connected_components(&calls);
+        Self {
+            calls,
+            tables,
+            entry_points,
+            implicit_entry_points,
+            components,
+        }
+    }
+
+    /// Returns true when `caller -> callee` stays inside one recursion cycle.
+    pub(crate) fn in_cycle(&self, caller: SymbolKey, callee: SymbolKey) -> bool {
+        self.components
+            .get(&caller)
+            .is_some_and(|component| self.components.get(&callee) == Some(component))
+    }
+
+    /// Returns the distinct callees of `key`, split into acyclic callees and a
+    /// flag telling whether any call closes a recursion cycle.
+    pub(crate) fn callees(&self, key: SymbolKey) -> (Vec<SymbolKey>, bool) {
+        let mut callees: Vec<SymbolKey> = Vec::new();
+        let mut recursive = false;
+        for site in self.calls.get(&key).into_iter().flatten() {
+            if self.in_cycle(key, site.callee) {
+                recursive = true;
+            } else if !callees.contains(&site.callee) {
+                callees.push(site.callee);
+            }
+        }
+        (callees, recursive)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ChainCost {
+    depth: usize,
+    bytes: u64,
+    next: Option<SymbolKey>,
+    unbounded: bool,
+}
+
+/// Runs call depth, stack usage, and recursion analysis over the workspace.
+pub fn analyze_stack_usage(db: &Database, options: StackUsageOptions<'_>) -> StackUsageReport {
+    let graph = CallGraph::build(db, options.files);
+    let CallGraph {
+        calls,
+        tables,
+        components,
+        ..
+    } = &graph;
+
+    let mut recursive_calls = Vec::new();
+    let mut callers: Vec<&SymbolKey> = calls.keys().collect();
+    callers.sort_by_key(|key| (key.file_id.0, key.symbol_id.0));
+    for &caller in callers {
+        for site in &calls[&caller] {
+            if !graph.in_cycle(caller, site.callee) {
+                continue;
+            }
+            let (Some(caller_frame), Some(callee_frame)) = (
+                stack_frame(tables, caller),
+                stack_frame(tables, site.callee),
+            ) else {
+                continue;
+            };
+            let cycle = cycle_path(calls, components, caller, site.callee)
+                .into_iter()
+                .filter_map(|key| stack_frame(tables, key))
+                .map(|frame| frame.qualified_name())
+                .collect();
+            recursive_calls.push(RecursiveCall {
+                caller: caller_frame,
+                callee: callee_frame,
+                cycle,
+                file_id: site.file_id,
+                range: site.range,
+            });
+        }
+    }
+    recursive_calls.sort_by(|a, b| {
+        a.file_id
+            .0
+            .cmp(&b.file_id.0)
+            .then_with(|| a.range.start().cmp(&b.range.start()))
+    });
+
+    let mut costs: FxHashMap<SymbolKey, ChainCost> = FxHashMap::default();
+    let mut entries = Vec::new();
+    for entry in &graph.entry_points {
+        let cost = chain_cost(&graph, entry.program, &mut costs);
+        let mut worst_path = Vec::new();
+        let mut current = Some(entry.program);
+        while let Some(key) = current {
+            if let Some(frame) = stack_frame(tables, key) {
+                worst_path.push(frame);
+            }
+            current = costs.get(&key).and_then(|cost| cost.next);
+        }
+        entries.push(StackUsageEntry {
+            name: entry.name.clone(),
+            task: entry.task.clone(),
+            file_id: entry.file_id,
+            range: entry.range,
+            max_depth: cost.depth,
+            stack_bytes: cost.bytes,
+            worst_path,
+            unbounded: cost.unbounded,
+        });
+    }
+    entries.sort_by(|a, b| {
+        a.file_id
+            .0
+            .cmp(&b.file_id.0)
+            .then_with(|| a.range.start().cmp(&b.range.start()))
+    });
+
+    StackUsageReport {
+        entries,
+        implicit_entry_points: graph.implicit_entry_points,
+        recursive_calls,
+    }
+}
+
+fn collect_calls(
+    db: &Database,
+    file_id: FileId,
+    source: &str,
+    root: &SyntaxNode,
+    symbols: &SymbolTable,
+    calls: &mut FxHashMap<SymbolKey, Vec<CallSite>>,
+) {
+    for call_expr in root
+        .descendants()
+        .filter(|node| node.kind() == SyntaxKind::CallExpr)
+    {
+        let Some(callee) = call_expr
+            .children()
+            .find(|child| child.kind() != SyntaxKind::ArgList)
+        else {
+            continue;
+        };
+        let Some(name_ref) = (match callee.kind() {
+            SyntaxKind::NameRef => Some(callee),
+            SyntaxKind::FieldExpr => callee
+                .children()
+                .filter(|child| child.kind() == SyntaxKind::NameRef)
+                .last(),
+            _ => None,
+        }) else {
+            continue;
+        };
+        let Some(ident) = name_ref
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+            .find(|token| token.kind() == SyntaxKind::Ident)
+        else {
+            continue;
+        };
+        let resolved = match resolve_target_at_position_with_context(
+            db,
+            file_id,
+            ident.text_range().start(),
+            source,
+            root,
+            symbols,
+        ) {
+            Some(ResolvedTarget::Symbol(target)) => callable_symbol(symbols, target),
+            _ => None,
+        };
+        // Inside a FUNCTION its own name denotes the result variable, so fall
+        // back to the global POU when the name did not resolve to a callable.
+        let Some(callee_id) = resolved.or_else(|| {
+            symbols.lookup(ident.text()).filter(|id| {
+                symbols
+                    .get(*id)
+                    .is_some_and(|symbol| matches!(symbol.kind, SymbolKind::Function { .. }))
+            })
+        }) else {
+            continue;
+        };
+        let Some(callee) = symbol_key(symbols, callee_id, file_id) else {
+            continue;
+        };
+
+        let scope_id = scope_at_position(symbols, root, call_expr.text_range().start());
+        let Some(owner) = symbols.get_scope(scope_id).and_then(|scope| scope.owner) else {
+            continue;
+        };
+        if !symbols
+            .get(owner)
+            .is_some_and(|symbol| is_pou_symbol_kind(&symbol.kind))
+        {
+            continue;
+        }
+        let Some(caller) = symbol_key(symbols, owner, file_id) else {
+            continue;
+        };
+        calls.entry(caller).or_default().push(CallSite {
+            callee,
+            file_id,
+            range: call_expr.text_range(),
+        });
+    }
+}
+
+/// Maps a resolved call target to the POU whose body runs for the call.
+///
+/// Calling a FUNCTION_BLOCK instance runs the body of its type.
+fn callable_symbol(symbols: &SymbolTable, target: SymbolId) -> Option<SymbolId> {
+    let symbol = symbols.get(target)?;
+    match symbol.kind {
+        SymbolKind::Function { .. }
+        | SymbolKind::Method { .. }
+        | SymbolKind::Property { .. }
+        | SymbolKind::Program => Some(target),
+        SymbolKind::Variable { .. } | SymbolKind::Parameter { .. } => {
+            let type_id = symbols.resolve_alias_type(symbol.type_id);
+            match symbols.type_by_id(type_id)? {
+                Type::FunctionBlock { name } | Type::Class { name } => {
+                    instance_type_symbol(symbols, name)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn instance_type_symbol(symbols: &SymbolTable, name: &str) -> Option<SymbolId> {
+    symbols
+        .resolve_by_name(name)
+        .filter(|id| {
+            symbols.get(*id).is_some_and(|symbol| {
+                matches!(symbol.kind, SymbolKind::FunctionBlock | SymbolKind::Class)
+            })
+        })
+        .or_else(|| {
+            symbols
+                .iter()
+                .find(|symbol| {
+                    matches!(symbol.kind, SymbolKind::FunctionBlock | SymbolKind::Class)
+                        && symbol.name.eq_ignore_ascii_case(name)
+                })
+                .map(|symbol| symbol.id)
+        })
+}
+
+fn collect_program_instances(
+    file_id: FileId,
+    root: &SyntaxNode,
+    symbols: &SymbolTable,
+    entry_points: &mut Vec<EntryPoint>,
+) {
+    for program in root
+        .descendants()
+        .filter(|node| node.kind() == SyntaxKind::ProgramConfig)
+    {
+        let mut instance = None;
+        let mut task = None;
+        let mut after_with = false;
+        for element in program.children_with_tokens() {
+            if element.kind() == SyntaxKind::KwWith {
+                after_with = true;
+                continue;
+            }
+            let Some(node) = element.as_node() else {
+                continue;
+            };
+            if node.kind() != SyntaxKind::Name {
+                continue;
+            }
+            let Some(ident) = node
+                .descendants_with_tokens()
+                .filter_map(|element| element.into_token())
+                .find(|token| token.kind() == SyntaxKind::Ident)
+            else {
+                continue;
+            };
+            if after_with && task.is_none() {
+                task = Some(SmolStr::new(ident.text()));
+            } else if instance.is_none() {
+                instance = Some((SmolStr::new(ident.text()), node.text_range()));
+            }
+        }
+        let Some(type_name) = program
+            .children()
+            .find(|node| node.kind() == SyntaxKind::QualifiedName)
+        else {
+            continue;
+        };
+        let Some(parts) = qualified_name_parts_from_node(&type_name) else {
+            continue;
+        };
+        let Some(program_id) = symbols
+            .resolve_qualified(&parts)
+            .or_else(|| {
+                (parts.len() == 1)
+                    .then(|| symbols.lookup_any(parts[0].as_str()))
+                    .flatten()
+            })
+            .filter(|id| {
+                symbols
+                    .get(*id)
+                    .is_some_and(|symbol| matches!(symbol.kind, SymbolKind::Program))
+            })
+        else {
+            continue;
+        };
+        let Some(program_key) = symbol_key(symbols, program_id, file_id) else {
+            continue;
+        };
+        let (name, range) = instance.unwrap_or_else(|| {
+            (
+                parts.last().cloned().unwrap_or_default(),
+                type_name.text_range(),
+            )
+        });
+        entry_points.push(EntryPoint {
+            name,
+            task,
+            file_id,
+            range,
+            program: program_key,
+        });
+    }
+}
+
+pub(crate) fn symbol_key(
+    symbols: &SymbolTable,
+    symbol_id: SymbolId,
+    file_id: FileId,
+) -> Option<SymbolKey> {
+    let symbol = symbols.get(symbol_id)?;
+    Some(match symbol.origin {
+        Some(origin) => SymbolKey {
+            file_id: origin.file_id,
+            symbol_id: origin.symbol_id,
+        },
+        None => SymbolKey { file_id, symbol_id },
+    })
+}
+
+/// Assigns a component index to every POU that takes part in a call cycle.
+///
+/// POUs outside any cycle are omitted, so two keys share a component exactly
+/// when each can reach the other.
+fn strongly_connected_components(
+    calls: &FxHashMap<SymbolKey, Vec<CallSite>>,
+) -> FxHashMap<SymbolKey, usize> {
+    struct Tarjan<'a> {
+        calls: &'a FxHashMap<SymbolKey, Vec<CallSite>>,
+        index: FxHashMap<SymbolKey, usize>,
+        low: FxHashMap<SymbolKey, usize>,
+        stack: Vec<SymbolKey>,
+        on_stack: FxHashSet<SymbolKey>,
+        components: FxHashMap<SymbolKey, usize>,
+        next_component: usize,
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, key: SymbolKey) {
+            let index = self.index.len();
+            self.index.insert(key, index);
+            self.low.insert(key, index);
+            self.stack.push(key);
+            self.on_stack.insert(key);
+
+            let calls = self.calls;
+            for site in calls.get(&key).into_iter().flatten() {
+                let callee = site.callee;
+                if !self.index.contains_key(&callee) {
+                    self.visit(callee);
+                    let low = self.low[&key].min(self.low[&callee]);
+                    self.low.insert(key, low);
+                } else if self.on_stack.contains(&callee) {
+                    let low = self.low[&key].min(self.index[&callee]);
+                    self.low.insert(key, low);
+                }
+            }
+
+            if self.low[&key] != self.index[&key] {
+                return;
+            }
+            let mut members = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(&member);
+                members.push(member);
+                if member == key {
+                    break;
+                }
+            }
+            let self_call = calls
+                .get(&key)
+                .is_some_and(|sites| sites.iter().any(|site| site.callee == key));
+            if members.len() > 1 || self_call {
+                for member in members {
+                    self.components.insert(member, self.next_component);
+                }
+                self.next_component += 1;
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        calls,
+        index: FxHashMap::default(),
+        low: FxHashMap::default(),
+        stack: Vec::new(),
+        on_stack: FxHashSet::default(),
+        components: FxHashMap::default(),
+        next_component: 0,
+    };
+    let mut roots: Vec<SymbolKey> = calls.keys().copied().collect();
+    roots.sort_by_key(|key| (key.file_id.0, key.symbol_id.0));
+    for key in roots {
+        if !tarjan.index.contains_key(&key) {
+            tarjan.visit(key);
+        }
+    }
+    tarjan.components
+}
+
+/// Returns the shortest cycle closed by `caller -> callee`, starting and ending at `caller`.
+fn cycle_path(
+    calls: &FxHashMap<SymbolKey, Vec<CallSite>>,
+    components: &FxHashMap<SymbolKey, usize>,
+    caller: SymbolKey,
+    callee: SymbolKey,
+) -> Vec<SymbolKey> {
+    let component = components.get(&caller);
+    let mut previous: FxHashMap<SymbolKey, SymbolKey> = FxHashMap::default();
+    let mut queue = VecDeque::from([callee]);
+    let mut seen = FxHashSet::from_iter([callee]);
+    while let Some(key) = queue.pop_front() {
+        if key == caller {
+            break;
+        }
+        for site in calls.get(&key).into_iter().flatten() {
+            if components.get(&site.callee) != component || !seen.insert(site.callee) {
+                continue;
+            }
+            previous.insert(site.callee, key);
+            queue.push_back(site.callee);
+        }
+    }
+
+    let mut path = vec![caller];
+    let mut current = caller;
+    while current != callee {
+        let Some(&prev) = previous.get(&current) else {
+            break;
+        };
+        path.push(prev);
+        current = prev;
+    }
+    path.push(caller);
+    path.reverse();
+    path
+}
+
+fn chain_cost(
+    graph: &CallGraph,
+    key: SymbolKey,
+    costs: &mut FxHashMap<SymbolKey, ChainCost>,
+) -> ChainCost {
+    if let Some(cost) = costs.get(&key) {
+        return *cost;
+    }
+    let frame_bytes = graph
+        .tables
+        .get(&key.file_id)
+        .map_or(0, |symbols| frame_bytes(symbols, key.symbol_id));
+    let mut cost = ChainCost {
+        depth: 1,
+        bytes: frame_bytes,
+        next: None,
+        unbounded: false,
+    };
+    let (callees, recursive) = graph.callees(key);
+    cost.unbounded = recursive;
+    for callee in callees {
+        let callee_cost = chain_cost(graph, callee, costs);
+        cost.unbounded |= callee_cost.unbounded;
+        cost.depth = cost.depth.max(callee_cost.depth + 1);
+        if cost.next.is_none() || frame_bytes + callee_cost.bytes > cost.bytes {
+            cost.bytes = frame_bytes + callee_cost.bytes;
+            cost.next = Some(callee);
+        }
+    }
+    costs.insert(key, cost);
+    cost
+}
+
+fn stack_frame(tables: &FxHashMap<FileId, Arc<SymbolTable>>, key: SymbolKey) -> Option<StackFrame> {
+    let symbols = tables.get(&key.file_id)?;
+    let symbol = symbols.get(key.symbol_id)?;
+    let container = match symbol.kind {
+        SymbolKind::Method { .. } | SymbolKind::Property { .. } => symbol
+            .parent
+            .and_then(|id| symbols.get(id))
+            .map(|parent| parent.name.clone()),
+        _ => None,
+    };
+    Some(StackFrame {
+        name: symbol.name.clone(),
+        container,
+        kind: symbol.kind.clone(),
+        file_id: key.file_id,
+        range: symbol.range,
+        frame_bytes: frame_bytes(symbols, key.symbol_id),
+    })
+}
+
+/// Estimates the stack reserved by one activation of a POU.
+fn frame_bytes(symbols: &SymbolTable, pou: SymbolId) -> u64 {
+    let Some(symbol) = symbols.get(pou) else {
+        return 0;
+    };
+    let (stateless, return_bytes) = match &symbol.kind {
+        SymbolKind::Function { return_type, .. } => (true, type_bytes(symbols, *return_type, 0)),
+        SymbolKind::Method { return_type, .. } => {
+            (true, return_type.map_or(0, |ty| type_bytes(symbols, ty, 0)))
+        }
+        SymbolKind::Property { prop_type, .. } => (true, type_bytes(symbols, *prop_type, 0)),
+        SymbolKind::Program | SymbolKind::FunctionBlock | SymbolKind::Class => (false, 0),
+        _ => return 0,
+    };
+    let locals: u64 = symbols
+        .iter()
+        .filter(|child| child.parent == Some(pou) && child.origin.is_none())
+        .map(|child| match child.kind {
+            SymbolKind::Parameter {
+                direction: ParamDirection::InOut,
+            } => REFERENCE_BYTES,
+            SymbolKind::Variable {
+                qualifier: VarQualifier::Temp,
+            } => type_bytes(symbols, child.type_id, 0),
+            SymbolKind::Parameter { .. }
+            | SymbolKind::Variable {
+                qualifier: VarQualifier::Local,
+            } if stateless => type_bytes(symbols, child.type_id, 0),
+            _ => 0,
+        })
+        .sum();
+    return_bytes + locals
+}
+
+fn type_bytes(symbols: &SymbolTable, type_id: TypeId, depth: usize) -> u64 {
+    if depth > MAX_TYPE_DEPTH {
+        return 0;
+    }
+    let type_id = symbols.resolve_alias_type(type_id);
+    let Some(ty) = symbols.type_by_id(type_id) else {
+        return 0;
+    };
+    if let Some(bits) = ty.bit_size() {
+        return u64::from(bits).div_ceil(8);
+    }
+    match ty {
+        Type::Date | Type::Tod | Type::Dt => 4,
+        Type::LTod | Type::Ldt => 8,
+        Type::String { max_len } => max_len.map_or(DEFAULT_STRING_LEN, u64::from) + 1,
+        Type::WString { max_len } => (max_len.map_or(DEFAULT_STRING_LEN, u64::from) + 1) * 2,
+        Type::Array {
+            element,
+            dimensions,
+        } => {
+            let count: u64 = dimensions
+                .iter()
+                .map(|(lower, upper)| u64::try_from(upper - lower + 1).unwrap_or(0))
+                .product();
+            count.saturating_mul(type_bytes(symbols, *element, depth + 1))
+        }
+        Type::Struct { fields, .. } => fields
+            .iter()
+            .map(|field| type_bytes(symbols, field.type_id, depth + 1))
+            .sum(),
+        Type::Union { variants, .. } => variants
+            .iter()
+            .map(|variant| type_bytes(symbols, variant.type_id, depth + 1))
+            .max()
+            .unwrap_or(0),
+        Type::Enum { base, .. } => type_bytes(symbols, *base, depth + 1),
+        Type::Pointer { .. } | Type::Reference { .. } | Type::Interface { .. } => REFERENCE_BYTES,
+        Type::FunctionBlock { name } | Type::Class { name } => {
+            let Some(owner) = instance_type_symbol(symbols, name) else {
+                return 0;
+            };
+            symbols
+                .iter()
+                .filter(|child| child.parent == Some(owner))
+                .map(|child| match child.kind {
+                    SymbolKind::Parameter {
+                        direction: ParamDirection::InOut,
+                    } => REFERENCE_BYTES,
+                    SymbolKind::Parameter { .. }
+                    | SymbolKind::Variable {
+                        qualifier: VarQualifier::Local | VarQualifier::Static,
+                    } => type_bytes(symbols, child.type_id, depth + 1),
+                    _ => 0,
+                })
+                .sum()
+        }
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stack_usage_reports_deepest_chain_per_task_entry() {
+        let source = r#"
+CONFIGURATION Conf
+RESOURCE Res ON PLC
+    TASK Fast (INTERVAL := T#10ms, PRIORITY := 1);
+    PROGRAM Inst WITH Fast : Main;
+END_RESOURCE
+END_CONFIGURATION
+
+FUNCTION Leaf : INT
+VAR_INPUT
+    A : INT;
+END_VAR
+VAR
+    Buffer : ARRAY[1..10] OF INT;
+END_VAR
+    Leaf := A;
+END_FUNCTION
+
+FUNCTION Middle : INT
+VAR_INPUT
+    B : INT;
+END_VAR
+    Middle := Leaf(B);
+END_FUNCTION
+
+FUNCTION_BLOCK Worker
+VAR_TEMP
+    Scratch : INT;
+END_VAR
+    Scratch := Middle(1);
+END_FUNCTION_BLOCK
+
+PROGRAM Main
+VAR
+    w : Worker;
+END_VAR
+    w();
+END_PROGRAM
+"#;
+        let mut db = Database::new();
+        let file_id = FileId(0);
+        db.set_source_text(file_id, source.to_string());
+
+        let report = analyze_stack_usage(&db, StackUsageOptions::default());
+        assert!(!report.implicit_entry_points);
+        assert!(report.recursive_calls.is_empty());
+        assert_eq!(report.entries.len(), 1);
+        let entry = &report.entries[0];
+        assert_eq!(entry.name.as_str(), "Inst");
+        assert_eq!(entry.task.as_deref(), Some("Fast"));
+        assert!(!entry.unbounded);
+        assert_eq!(entry.max_depth, 4);
+        let path: Vec<String> = entry
+            .worst_path
+            .iter()
+            .map(StackFrame::qualified_name)
+            .collect();
+        assert_eq!(path, vec!["Main", "Worker", "Middle", "Leaf"]);
+        // Worker: INT temp (2), Middle: INT return + INT input (4),
+        // Leaf: INT return + INT input + 10 INT buffer (24).
+        assert_eq!(entry.stack_bytes, 30);
+    }
+
+    #[test]
+    fn stack_usage_flags_recursion_as_unbounded() {
+        let source = r#"
+FUNCTION Fact : DINT
+VAR_INPUT
+    N : DINT;
+END_VAR
+    IF N <= 1 THEN
+        Fact := 1;
+    ELSE
+        Fact := N * Fact(N - 1);
+    END_IF;
+END_FUNCTION
+
+FUNCTION Ping : INT
+VAR_INPUT
+    N : INT;
+END_VAR
+    Ping := Pong(N);
+END_FUNCTION
+
+FUNCTION Pong : INT
+VAR_INPUT
+    N : INT;
+END_VAR
+    Pong := Ping(N);
+END_FUNCTION
+
+PROGRAM Main
+VAR
+    x : DINT;
+    y : INT;
+END_VAR
+    x := Fact(5);
+    y := Ping(1);
+END_PROGRAM
+
+PROGRAM Quiet
+END_PROGRAM
+"#;
+        let mut db = Database::new();
+        let file_id = FileId(0);
+        db.set_source_text(file_id, source.to_string());
+
+        let report = analyze_stack_usage(&db, StackUsageOptions::default());
+        assert!(report.implicit_entry_points);
+        let cycles: Vec<String> = report
+            .recursive_calls
+            .iter()
+            .map(|call| call.cycle.join(" -> "))
+            .collect();
+        assert_eq!(
+            cycles,
+            vec![
+                "Fact -> Fact",
+                "Ping -> Pong -> Ping",
+                "Pong -> Ping -> Pong"
+            ]
+        );
+
+        let main = report
+            .entries
+            .iter()
+            .find(|entry| entry.name == "Main")
+            .expect("Main entry");
+        assert!(main.unbounded);
+        let quiet = report
+            .entries
+            .iter()
+            .find(|entry| entry.name == "Quiet")
+            .expect("Quiet entry");
+        assert!(!quiet.unbounded);
+        assert_eq!(quiet.max_depth, 1);
+    }
+}
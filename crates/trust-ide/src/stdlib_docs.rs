@@ -78,11 +78,19 @@ const DOC_COMPARISON: &str = "Standard comparison function (IEC 61131-3 Ed.3, Ta
 const DOC_STRING: &str = "Standard string function (IEC 61131-3 Ed.3, Table 34).";
 const DOC_TIME_NUMERIC: &str = "Standard time arithmetic function (IEC 61131-3 Ed.3, Table 35).";
 const DOC_TIME_SPLIT: &str = "Standard time/date function (IEC 61131-3 Ed.3, Table 36).";
+const DOC_ARRAY_BOUNDS: &str =
+    "Standard array bound function for fixed and ARRAY[*] arrays (IEC 61131-3 Ed.3, Table 15).";
 
 const DOC_FB_BISTABLE: &str = "Standard bistable function block (IEC 61131-3 Ed.3, Table 43).";
+const DOC_FB_SEMA: &str =
+    "Semaphore function block (IEC 61131-3 Ed.1 bistable elements); BUSY is FALSE only for the call that claims it.";
 const DOC_FB_EDGE: &str = "Standard edge detection function block (IEC 61131-3 Ed.3, Table 44).";
 const DOC_FB_COUNTER: &str = "Standard counter function block (IEC 61131-3 Ed.3, Table 45).";
 const DOC_FB_TIMER: &str = "Standard timer function block (IEC 61131-3 Ed.3, Table 46).";
+const DOC_FB_ASYNC: &str =
+    "Asynchronous I/O function block (runtime extension); the work runs on a background worker.";
+const DOC_FB_COMM: &str =
+    "Communication function block (runtime extension); exchanges byte buffers without blocking the scan.";
 
 const NUMERIC_SINGLE: &[&str] = &[
     "ABS", "SQRT", "LN", "LOG", "EXP", "SIN", "COS", "TAN", "ASIN", "ACOS", "ATAN", "ATAN2",
@@ -135,6 +143,8 @@ const TIME_SPLIT: &[&str] = &[
     "DAY_OF_WEEK",
 ];
 
+const ARRAY_BOUNDS: &[&str] = &["LOWER_BOUND", "UPPER_BOUND"];
+
 const NUMERIC_TYPES: &[&str] = &[
     "LREAL", "REAL", "LINT", "DINT", "INT", "SINT", "ULINT", "UDINT", "UINT", "USINT",
 ];
@@ -186,6 +196,9 @@ static STANDARD_FUNCTIONS: Lazy<Vec<StdlibFunctionEntry>> = Lazy::new(|| {
     for name in TIME_SPLIT {
         push((*name).to_string(), DOC_TIME_SPLIT);
     }
+    for name in ARRAY_BOUNDS {
+        push((*name).to_string(), DOC_ARRAY_BOUNDS);
+    }
 
     // Conversion forms (Table 22)
     for dst in NUMERIC_TYPES
@@ -305,6 +318,7 @@ static STANDARD_FUNCTION_SET: Lazy<FxHashSet<SmolStr>> = Lazy::new(|| {
 const STANDARD_FB_NAMES: &[(&str, &str)] = &[
     ("RS", DOC_FB_BISTABLE),
     ("SR", DOC_FB_BISTABLE),
+    ("SEMA", DOC_FB_SEMA),
     ("R_TRIG", DOC_FB_EDGE),
     ("F_TRIG", DOC_FB_EDGE),
     ("CTU", DOC_FB_COUNTER),
@@ -331,6 +345,17 @@ const STANDARD_FB_NAMES: &[(&str, &str)] = &[
     ("TP_LTIME", DOC_FB_TIMER),
     ("TON_LTIME", DOC_FB_TIMER),
     ("TOF_LTIME", DOC_FB_TIMER),
+    ("FILE_READ_ASYNC", DOC_FB_ASYNC),
+    ("FILE_WRITE_ASYNC", DOC_FB_ASYNC),
+    ("TCP_CLIENT", DOC_FB_ASYNC),
+    ("FILE_OPEN", DOC_FB_ASYNC),
+    ("FILE_READ", DOC_FB_ASYNC),
+    ("FILE_WRITE", DOC_FB_ASYNC),
+    ("FILE_CLOSE", DOC_FB_ASYNC),
+    ("TCP_STREAM_CLIENT", DOC_FB_COMM),
+    ("TCP_STREAM_SERVER", DOC_FB_COMM),
+    ("UDP_SOCKET", DOC_FB_COMM),
+    ("SERIAL_PORT", DOC_FB_COMM),
 ];
 
 static STANDARD_FB_SET: Lazy<FxHashSet<SmolStr>> = Lazy::new(|| {
@@ -391,6 +416,7 @@ pub fn standard_fb_names() -> &'static [&'static str] {
     const NAMES: &[&str] = &[
         "RS",
         "SR",
+        "SEMA",
         "R_TRIG",
         "F_TRIG",
         "CTU",
@@ -417,6 +443,17 @@ pub fn standard_fb_names() -> &'static [&'static str] {
         "TP_LTIME",
         "TON_LTIME",
         "TOF_LTIME",
+        "FILE_READ_ASYNC",
+        "FILE_WRITE_ASYNC",
+        "TCP_CLIENT",
+        "FILE_OPEN",
+        "FILE_READ",
+        "FILE_WRITE",
+        "FILE_CLOSE",
+        "TCP_STREAM_CLIENT",
+        "TCP_STREAM_SERVER",
+        "UDP_SOCKET",
+        "SERIAL_PORT",
     ];
     NAMES
 }
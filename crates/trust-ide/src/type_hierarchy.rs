@@ -289,6 +289,40 @@ END_CLASS
         assert!(subs[0].name.eq_ignore_ascii_case("Derived"));
     }
 
+    #[test]
+    fn type_hierarchy_interface_extends() {
+        let source = r#"
+INTERFACE IBase
+END_INTERFACE
+
+INTERFACE IDerived EXTENDS IBase
+END_INTERFACE
+
+CLASS Impl IMPLEMENTS IDerived
+END_CLASS
+"#;
+        let mut db = Database::new();
+        let file_id = FileId(0);
+        db.set_source_text(file_id, source.to_string());
+
+        let derived_offset = source.find("IDerived EXTENDS").unwrap();
+        let derived = prepare_type_hierarchy(&db, file_id, TextSize::from(derived_offset as u32))
+            .expect("derived");
+        let supers = supertypes(&db, &derived);
+        assert_eq!(supers.len(), 1);
+        assert!(supers[0].name.eq_ignore_ascii_case("IBase"));
+        let subs = subtypes(&db, &derived);
+        assert_eq!(subs.len(), 1);
+        assert!(subs[0].name.eq_ignore_ascii_case("Impl"));
+
+        let base_offset = source.find("IBase").unwrap();
+        let base =
+            prepare_type_hierarchy(&db, file_id, TextSize::from(base_offset as u32)).expect("base");
+        let subs = subtypes(&db, &base);
+        assert_eq!(subs.len(), 1);
+        assert!(subs[0].name.eq_ignore_ascii_case("IDerived"));
+    }
+
     #[test]
     fn type_hierarchy_resolves_interfaces_in_namespace() {
         let source = r#"
@@ -81,16 +81,21 @@ pub fn scope_for_pou(symbols: &SymbolTable, pou_node: &SyntaxNode) -> ScopeId {
                 .filter_map(|e| e.into_token())
                 .find(|t| t.kind() == SyntaxKind::Ident)
         })
-        .map(|t| t.text().to_string());
+        .map(|t| (t.text().to_string(), t.text_range()));
 
-    let Some(name) = pou_name else {
+    let Some((name, name_range)) = pou_name else {
         return ScopeId::GLOBAL;
     };
 
-    // Find the symbol for this POU
+    // Find the symbol for this POU, preferring the one declared at this node so that
+    // methods sharing a name across types resolve to their own declaration.
+    let matches_name =
+        |sym: &&Symbol| sym.name.eq_ignore_ascii_case(&name) && is_pou_symbol_kind(&sym.kind);
     let pou_symbol = symbols
         .iter()
-        .find(|sym| sym.name.eq_ignore_ascii_case(&name) && is_pou_symbol_kind(&sym.kind));
+        .filter(matches_name)
+        .find(|sym| sym.origin.is_none() && sym.range == name_range)
+        .or_else(|| symbols.iter().find(matches_name));
 
     let Some(pou_sym) = pou_symbol else {
         return ScopeId::GLOBAL;
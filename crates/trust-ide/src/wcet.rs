@@ -0,0 +1,764 @@
+//! Worst-case execution time (WCET) estimation.
+//!
+//! Counts the statements each POU body executes on its longest path: IF and
+//! CASE take their most expensive branch, FOR loops with literal bounds
+//! multiply their body by the iteration count, and calls add the inclusive
+//! count of the called POU. WHILE, REPEAT, and FOR loops without literal
+//! bounds assume a fixed iteration count and mark the estimate as unbounded.
+//!
+//! Static counts are converted into time with a per-statement cost. When
+//! measured timings from the runtime profiler are supplied, the cost is
+//! calibrated to the slowest measured POU, and a program instance never
+//! estimates below its own measured maximum. Task estimates sum their program
+//! instances and are flagged when they exceed the task INTERVAL.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use smol_str::SmolStr;
+use text_size::TextRange;
+
+use trust_hir::db::{FileId, SourceDatabase};
+use trust_hir::symbols::SymbolKind;
+use trust_hir::Database;
+use trust_syntax::parser::parse;
+use trust_syntax::syntax::{SyntaxElement, SyntaxKind, SyntaxNode};
+
+use crate::stack_usage::{CallGraph, SymbolKey};
+
+/// Default cost of one statement in nanoseconds when nothing was measured.
+pub const DEFAULT_STATEMENT_NS: f64 = 100.0;
+/// Iterations assumed for loops whose bound is not a literal.
+pub const DEFAULT_LOOP_ITERATIONS: u64 = 100;
+
+/// A measured execution time reported by the runtime profiler.
+#[derive(Debug, Clone)]
+pub struct MeasuredTiming {
+    /// Profiler call kind (`program` for program instances, `fb` for FUNCTION_BLOCK types).
+    pub kind: SmolStr,
+    /// Program instance or FUNCTION_BLOCK type name.
+    pub name: SmolStr,
+    /// Maximum measured duration in milliseconds.
+    pub max_ms: f64,
+}
+
+/// Estimate for one program instance.
+#[derive(Debug, Clone)]
+pub struct WcetProgram {
+    /// Program instance name.
+    pub name: SmolStr,
+    /// PROGRAM type name.
+    pub program: SmolStr,
+    /// File containing the instance declaration.
+    pub file_id: FileId,
+    /// Instance declaration range.
+    pub range: TextRange,
+    /// Worst-case statement count including called POUs.
+    pub statements: u64,
+    /// Static estimate in milliseconds.
+    pub static_ms: f64,
+    /// Maximum measured duration in milliseconds, if profiled.
+    pub measured_ms: Option<f64>,
+    /// Final estimate in milliseconds.
+    pub estimate_ms: f64,
+    /// True when a loop without literal bounds or a recursive call was assumed.
+    pub unbounded: bool,
+}
+
+/// Estimate for one task.
+#[derive(Debug, Clone)]
+pub struct WcetTask {
+    /// Task name.
+    pub name: SmolStr,
+    /// File containing the task declaration (or the first program instance).
+    pub file_id: FileId,
+    /// Task declaration range.
+    pub range: TextRange,
+    /// Task INTERVAL in milliseconds, if declared.
+    pub interval_ms: Option<f64>,
+    /// Sum of the program instance estimates in milliseconds.
+    pub estimate_ms: f64,
+    /// Program instances attached to the task.
+    pub programs: Vec<WcetProgram>,
+    /// True when any program estimate is unbounded.
+    pub unbounded: bool,
+    /// True when the estimate exceeds the task INTERVAL.
+    pub overrun: bool,
+}
+
+/// Result of a workspace-wide WCET estimation.
+#[derive(Debug, Clone, Default)]
+pub struct WcetReport {
+    /// Per task estimates.
+    pub tasks: Vec<WcetTask>,
+    /// Program entry points that are not attached to a task.
+    pub unscheduled: Vec<WcetProgram>,
+    /// Statement cost used for static estimates in nanoseconds.
+    pub statement_ns: f64,
+    /// True when the statement cost was derived from measured timings.
+    pub calibrated: bool,
+}
+
+/// Options for WCET estimation.
+#[derive(Debug, Clone, Copy)]
+pub struct WcetOptions<'a> {
+    /// Files that participate in the analysis (defaults to all files).
+    pub files: Option<&'a FxHashSet<FileId>>,
+    /// Measured timings from the runtime profiler.
+    pub measured: &'a [MeasuredTiming],
+    /// Statement cost in nanoseconds used when nothing was measured.
+    pub statement_ns: f64,
+    /// Iterations assumed for loops whose bound is not a literal.
+    pub loop_iterations: u64,
+}
+
+impl Default for WcetOptions<'_> {
+    fn default() -> Self {
+        Self {
+            files: None,
+            measured: &[],
+            statement_ns: DEFAULT_STATEMENT_NS,
+            loop_iterations: DEFAULT_LOOP_ITERATIONS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Cost {
+    statements: u64,
+    unbounded: bool,
+}
+
+impl Cost {
+    fn add(&mut self, other: Cost) {
+        self.statements = self.statements.saturating_add(other.statements);
+        self.unbounded |= other.unbounded;
+    }
+
+    fn max(&mut self, other: Cost) {
+        self.statements = self.statements.max(other.statements);
+        self.unbounded |= other.unbounded;
+    }
+}
+
+struct TaskDecl {
+    name: SmolStr,
+    file_id: FileId,
+    range: TextRange,
+    interval_ms: Option<f64>,
+}
+
+struct Estimator<'a> {
+    graph: &'a CallGraph,
+    roots: FxHashMap<FileId, SyntaxNode>,
+    callees: FxHashMap<(FileId, TextRange), SymbolKey>,
+    loop_iterations: u64,
+    costs: FxHashMap<SymbolKey, Cost>,
+}
+
+/// Estimates the worst-case execution time of every task in the workspace.
+pub fn analyze_wcet(db: &Database, options: WcetOptions<'_>) -> WcetReport {
+    let graph = CallGraph::build(db, options.files);
+    let mut file_ids: Vec<FileId> = graph.tables.keys().copied().collect();
+    file_ids.sort_by_key(|id| id.0);
+
+    let mut roots = FxHashMap::default();
+    let mut task_decls = Vec::new();
+    for &file_id in &file_ids {
+        let root = parse(&db.source_text(file_id)).syntax();
+        collect_tasks(file_id, &root, &mut task_decls);
+        roots.insert(file_id, root);
+    }
+    let callees = graph
+        .calls
+        .values()
+        .flatten()
+        .map(|site| ((site.file_id, site.range), site.callee))
+        .collect();
+    let mut estimator = Estimator {
+        graph: &graph,
+        roots,
+        callees,
+        loop_iterations: options.loop_iterations,
+        costs: FxHashMap::default(),
+    };
+
+    let mut programs = Vec::new();
+    for entry in &graph.entry_points {
+        let cost = estimator.pou_cost(entry.program);
+        let measured_ms = measured_max(options.measured, "program", &entry.name);
+        programs.push((entry, cost, measured_ms));
+    }
+
+    let mut statement_ns: Option<f64> = None;
+    let mut calibrate = |statements: u64, measured_ms: f64| {
+        if statements == 0 || !measured_ms.is_finite() || measured_ms <= 0.0 {
+            return;
+        }
+        let rate = measured_ms * 1_000_000.0 / statements as f64;
+        statement_ns = Some(statement_ns.map_or(rate, |current| current.max(rate)));
+    };
+    for (_, cost, measured_ms) in &programs {
+        if let Some(measured_ms) = measured_ms {
+            calibrate(cost.statements, *measured_ms);
+        }
+    }
+    for timing in options.measured {
+        if !timing.kind.eq_ignore_ascii_case("fb") {
+            continue;
+        }
+        if let Some(key) = estimator.function_block(&timing.name) {
+            let cost = estimator.pou_cost(key);
+            calibrate(cost.statements, timing.max_ms);
+        }
+    }
+    let calibrated = statement_ns.is_some();
+    let statement_ns = statement_ns.unwrap_or(options.statement_ns);
+
+    let mut tasks: Vec<WcetTask> = task_decls
+        .into_iter()
+        .map(|decl| WcetTask {
+            name: decl.name,
+            file_id: decl.file_id,
+            range: decl.range,
+            interval_ms: decl.interval_ms,
+            estimate_ms: 0.0,
+            programs: Vec::new(),
+            unbounded: false,
+            overrun: false,
+        })
+        .collect();
+    let mut unscheduled = Vec::new();
+    for (entry, cost, measured_ms) in programs {
+        let static_ms = cost.statements as f64 * statement_ns / 1_000_000.0;
+        let program = WcetProgram {
+            name: entry.name.clone(),
+            program: estimator.symbol_name(entry.program),
+            file_id: entry.file_id,
+            range: entry.range,
+            statements: cost.statements,
+            static_ms,
+            measured_ms,
+            estimate_ms: measured_ms.map_or(static_ms, |measured| measured.max(static_ms)),
+            unbounded: cost.unbounded,
+        };
+        let Some(task_name) = entry.task.as_ref() else {
+            unscheduled.push(program);
+            continue;
+        };
+        let index = match tasks
+            .iter()
+            .position(|task| task.name.eq_ignore_ascii_case(task_name))
+        {
+            Some(index) => index,
+            None => {
+                tasks.push(WcetTask {
+                    name: task_name.clone(),
+                    file_id: entry.file_id,
+                    range: entry.range,
+                    interval_ms: None,
+                    estimate_ms: 0.0,
+                    programs: Vec::new(),
+                    unbounded: false,
+                    overrun: false,
+                });
+                tasks.len() - 1
+            }
+        };
+        tasks[index].programs.push(program);
+    }
+    for task in &mut tasks {
+        task.estimate_ms = task
+            .programs
+            .iter()
+            .map(|program| program.estimate_ms)
+            .sum();
+        task.unbounded = task.programs.iter().any(|program| program.unbounded);
+        task.overrun = task
+            .interval_ms
+            .is_some_and(|interval| interval > 0.0 && task.estimate_ms > interval);
+    }
+    tasks.retain(|task| !task.programs.is_empty());
+
+    WcetReport {
+        tasks,
+        unscheduled,
+        statement_ns,
+        calibrated,
+    }
+}
+
+fn measured_max(measured: &[MeasuredTiming], kind: &str, name: &str) -> Option<f64> {
+    measured
+        .iter()
+        .filter(|timing| {
+            timing.kind.eq_ignore_ascii_case(kind) && timing.name.eq_ignore_ascii_case(name)
+        })
+        .map(|timing| timing.max_ms)
+        .filter(|max_ms| max_ms.is_finite())
+        .reduce(f64::max)
+}
+
+impl Estimator<'_> {
+    fn symbol_name(&self, key: SymbolKey) -> SmolStr {
+        self.graph
+            .tables
+            .get(&key.file_id)
+            .and_then(|symbols| symbols.get(key.symbol_id))
+            .map(|symbol| symbol.name.clone())
+            .unwrap_or_default()
+    }
+
+    fn function_block(&self, name: &str) -> Option<SymbolKey> {
+        let mut file_ids: Vec<&FileId> = self.graph.tables.keys().collect();
+        file_ids.sort_by_key(|id| id.0);
+        file_ids.into_iter().find_map(|&file_id| {
+            self.graph.tables[&file_id]
+                .iter()
+                .find(|symbol| {
+                    symbol.origin.is_none()
+                        && matches!(symbol.kind, SymbolKind::FunctionBlock)
+                        && symbol.name.eq_ignore_ascii_case(name)
+                })
+                .map(|symbol| SymbolKey {
+                    file_id,
+                    symbol_id: symbol.id,
+                })
+        })
+    }
+
+    /// Inclusive worst-case statement count of a POU body.
+    fn pou_cost(&mut self, key: SymbolKey) -> Cost {
+        if let Some(cost) = self.costs.get(&key) {
+            return *cost;
+        }
+        // Calls inside a cycle are skipped by `call_cost`; the placeholder
+        // keeps any remaining back edge from recursing forever.
+        self.costs.insert(key, Cost::default());
+        let mut cost = Cost::default();
+        if let Some(pou) = self.pou_node(key) {
+            if pou.kind() == SyntaxKind::Property {
+                for accessor in pou.children().filter(|child| {
+                    matches!(
+                        child.kind(),
+                        SyntaxKind::PropertyGet | SyntaxKind::PropertySet
+                    )
+                }) {
+                    let accessor_cost = self.body_cost(key, &accessor);
+                    cost.max(accessor_cost);
+                }
+            } else {
+                cost = self.body_cost(key, &pou);
+            }
+        }
+        cost.unbounded |= self.graph.callees(key).1;
+        self.costs.insert(key, cost);
+        cost
+    }
+
+    fn pou_node(&self, key: SymbolKey) -> Option<SyntaxNode> {
+        let symbol = self.graph.tables.get(&key.file_id)?.get(key.symbol_id)?;
+        let root = self.roots.get(&key.file_id)?;
+        let token = root.token_at_offset(symbol.range.start()).right_biased()?;
+        token.parent_ancestors().find(|node| {
+            matches!(
+                node.kind(),
+                SyntaxKind::Program
+                    | SyntaxKind::Function
+                    | SyntaxKind::FunctionBlock
+                    | SyntaxKind::Method
+                    | SyntaxKind::Property
+                    | SyntaxKind::Action
+            )
+        })
+    }
+
+    fn body_cost(&mut self, owner: SymbolKey, node: &SyntaxNode) -> Cost {
+        let mut cost = Cost::default();
+        for body in node
+            .children()
+            .filter(|child| child.kind() == SyntaxKind::StmtList)
+        {
+            cost.add(self.statements_cost(owner, &body));
+        }
+        cost
+    }
+
+    /// Sums the statements that are direct children of `node`.
+    fn statements_cost(&mut self, owner: SymbolKey, node: &SyntaxNode) -> Cost {
+        let mut cost = Cost::default();
+        for statement in node.children().filter(|child| is_statement(child.kind())) {
+            cost.add(self.statement_cost(owner, &statement));
+        }
+        cost
+    }
+
+    /// Cost of evaluating the expressions that are direct children of `node`.
+    fn expressions_cost(&mut self, owner: SymbolKey, node: &SyntaxNode) -> Cost {
+        let mut cost = Cost::default();
+        for expr in node.children().filter(|child| is_expression(child.kind())) {
+            for call in expr
+                .descendants()
+                .filter(|node| node.kind() == SyntaxKind::CallExpr)
+            {
+                cost.add(self.call_cost(owner, &call));
+            }
+        }
+        cost
+    }
+
+    fn call_cost(&mut self, owner: SymbolKey, call: &SyntaxNode) -> Cost {
+        let Some(&callee) = self.callees.get(&(owner.file_id, call.text_range())) else {
+            return Cost::default();
+        };
+        if self.graph.in_cycle(owner, callee) {
+            return Cost {
+                statements: 0,
+                unbounded: true,
+            };
+        }
+        self.pou_cost(callee)
+    }
+
+    fn statement_cost(&mut self, owner: SymbolKey, node: &SyntaxNode) -> Cost {
+        if node.kind() == SyntaxKind::EmptyStmt {
+            return Cost::default();
+        }
+        let mut cost = Cost {
+            statements: 1,
+            unbounded: false,
+        };
+        cost.add(self.expressions_cost(owner, node));
+        match node.kind() {
+            SyntaxKind::IfStmt => {
+                let mut worst = self.statements_cost(owner, node);
+                for branch in node.children() {
+                    match branch.kind() {
+                        SyntaxKind::ElsifBranch => {
+                            cost.statements = cost.statements.saturating_add(1);
+                            cost.add(self.expressions_cost(owner, &branch));
+                            worst.max(self.statements_cost(owner, &branch));
+                        }
+                        SyntaxKind::ElseBranch => {
+                            worst.max(self.statements_cost(owner, &branch));
+                        }
+                        _ => {}
+                    }
+                }
+                cost.add(worst);
+            }
+            SyntaxKind::CaseStmt => {
+                let mut worst = Cost::default();
+                for branch in node.children().filter(|child| {
+                    matches!(
+                        child.kind(),
+                        SyntaxKind::CaseBranch | SyntaxKind::ElseBranch
+                    )
+                }) {
+                    worst.max(self.statements_cost(owner, &branch));
+                }
+                cost.add(worst);
+            }
+            SyntaxKind::ForStmt => {
+                let (iterations, bounded) = match for_iterations(node) {
+                    Some(iterations) => (iterations, true),
+                    None => (self.loop_iterations, false),
+                };
+                let body = self.statements_cost(owner, node);
+                cost.statements = cost
+                    .statements
+                    .saturating_add(iterations.saturating_mul(body.statements.saturating_add(1)));
+                cost.unbounded |= body.unbounded || !bounded;
+            }
+            SyntaxKind::WhileStmt | SyntaxKind::RepeatStmt => {
+                let body = self.statements_cost(owner, node);
+                let per_iteration = cost.statements.saturating_add(body.statements);
+                cost.statements = cost
+                    .statements
+                    .saturating_add(self.loop_iterations.saturating_mul(per_iteration));
+                cost.unbounded = true;
+            }
+            _ => cost.add(self.statements_cost(owner, node)),
+        }
+        cost
+    }
+}
+
+fn is_statement(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::AssignStmt
+            | SyntaxKind::IfStmt
+            | SyntaxKind::CaseStmt
+            | SyntaxKind::ForStmt
+            | SyntaxKind::WhileStmt
+            | SyntaxKind::RepeatStmt
+            | SyntaxKind::ReturnStmt
+            | SyntaxKind::ExitStmt
+            | SyntaxKind::ContinueStmt
+            | SyntaxKind::JmpStmt
+            | SyntaxKind::LabelStmt
+            | SyntaxKind::EmptyStmt
+            | SyntaxKind::ExprStmt
+    )
+}
+
+fn is_expression(kind: SyntaxKind) -> bool {
+    !is_statement(kind)
+        && !matches!(
+            kind,
+            SyntaxKind::Name
+                | SyntaxKind::ElsifBranch
+                | SyntaxKind::ElseBranch
+                | SyntaxKind::CaseBranch
+                | SyntaxKind::StmtList
+        )
+}
+
+/// Returns the iteration count of a FOR loop whose bounds are integer literals.
+fn for_iterations(node: &SyntaxNode) -> Option<u64> {
+    let mut bounds = node
+        .children()
+        .filter(|child| is_expression(child.kind()))
+        .map(|expr| int_literal(&expr));
+    let start = bounds.next()??;
+    let end = bounds.next()??;
+    let step = match bounds.next() {
+        Some(step) => step?,
+        None => 1,
+    };
+    if step == 0 {
+        return None;
+    }
+    let span = if step > 0 { end - start } else { start - end };
+    if span < 0 {
+        return Some(0);
+    }
+    u64::try_from(span / step.abs() + 1).ok()
+}
+
+fn int_literal(expr: &SyntaxNode) -> Option<i64> {
+    let text: String = expr
+        .text()
+        .to_string()
+        .chars()
+        .filter(|ch| !ch.is_whitespace() && *ch != '_')
+        .collect();
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.as_str()),
+    };
+    let digits = digits.rsplit_once('#').map_or(digits, |(_, value)| value);
+    let value: i64 = digits.parse().ok()?;
+    Some(if negative { -value } else { value })
+}
+
+fn collect_tasks(file_id: FileId, root: &SyntaxNode, tasks: &mut Vec<TaskDecl>) {
+    for task in root
+        .descendants()
+        .filter(|node| node.kind() == SyntaxKind::TaskConfig)
+    {
+        let Some(name) = task
+            .children()
+            .find(|child| child.kind() == SyntaxKind::Name)
+        else {
+            continue;
+        };
+        let interval_ms = task
+            .children()
+            .find(|child| child.kind() == SyntaxKind::TaskInit)
+            .and_then(|init| task_init_value(&init, "INTERVAL"))
+            .and_then(|expr| parse_time_ms(&expr.text().to_string()));
+        tasks.push(TaskDecl {
+            name: SmolStr::new(name.text().to_string().trim()),
+            file_id,
+            range: name.text_range(),
+            interval_ms,
+        });
+    }
+}
+
+fn task_init_value(init: &SyntaxNode, field: &str) -> Option<SyntaxNode> {
+    let elements: Vec<SyntaxElement> = init.children_with_tokens().collect();
+    for (idx, element) in elements.iter().enumerate() {
+        let Some(name) = element
+            .as_node()
+            .filter(|node| node.kind() == SyntaxKind::Name)
+        else {
+            continue;
+        };
+        if !name.text().to_string().trim().eq_ignore_ascii_case(field) {
+            continue;
+        }
+        if elements
+            .get(idx + 1)
+            .and_then(|element| element.as_token())
+            .is_none_or(|token| token.kind() != SyntaxKind::Assign)
+        {
+            continue;
+        }
+        return elements[idx + 2..]
+            .iter()
+            .take_while(|element| !matches!(element.kind(), SyntaxKind::Comma | SyntaxKind::RParen))
+            .find_map(|element| element.as_node().cloned());
+    }
+    None
+}
+
+/// Parses a TIME literal such as `T#1s500ms` into milliseconds.
+pub fn parse_time_ms(text: &str) -> Option<f64> {
+    let text: String = text
+        .trim()
+        .chars()
+        .filter(|ch| *ch != '_')
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let (_, value) = text.split_once('#')?;
+    let (negative, mut rest) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+    if rest.is_empty() {
+        return None;
+    }
+    let mut total = 0.0;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+            .unwrap_or(rest.len());
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|ch: char| !ch.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let nanos_per_unit = match &rest[..unit_len] {
+            "d" => 86_400_000_000_000.0,
+            "h" => 3_600_000_000_000.0,
+            "m" => 60_000_000_000.0,
+            "s" => 1_000_000_000.0,
+            "ms" => 1_000_000.0,
+            "us" => 1_000.0,
+            "ns" => 1.0,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        total += number * nanos_per_unit;
+    }
+    let total = total / 1_000_000.0;
+    Some(if negative { -total } else { total })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+CONFIGURATION Conf
+RESOURCE Res ON PLC
+    TASK Fast (INTERVAL := T#1ms, PRIORITY := 1);
+    PROGRAM Inst WITH Fast : Main;
+END_RESOURCE
+END_CONFIGURATION
+
+FUNCTION_BLOCK Worker
+VAR
+    i : INT;
+    Total : INT;
+END_VAR
+    FOR i := 1 TO 10 DO
+        Total := Total + i;
+    END_FOR;
+END_FUNCTION_BLOCK
+
+PROGRAM Main
+VAR
+    w : Worker;
+    x : INT;
+END_VAR
+    IF x > 0 THEN
+        w();
+        x := 0;
+    ELSE
+        x := 1;
+    END_IF;
+END_PROGRAM
+"#;
+
+    fn database(source: &str) -> Database {
+        let mut db = Database::new();
+        db.set_source_text(FileId(0), source.to_string());
+        db
+    }
+
+    #[test]
+    fn wcet_counts_worst_branch_and_constant_loops() {
+        let db = database(SOURCE);
+        let report = analyze_wcet(&db, WcetOptions::default());
+        assert!(!report.calibrated);
+        assert_eq!(report.tasks.len(), 1);
+        let task = &report.tasks[0];
+        assert_eq!(task.name.as_str(), "Fast");
+        assert_eq!(task.interval_ms, Some(1.0));
+        assert_eq!(task.programs.len(), 1);
+        let program = &task.programs[0];
+        assert_eq!(program.name.as_str(), "Inst");
+        assert_eq!(program.program.as_str(), "Main");
+        // IF (1) + FB call (1 + FOR 1 + 10 * 2) + assignment (1).
+        assert_eq!(program.statements, 24);
+        assert!(!program.unbounded);
+        assert!(!task.overrun);
+    }
+
+    #[test]
+    fn wcet_calibrates_from_measurements_and_flags_overruns() {
+        let db = database(SOURCE);
+        let measured = [MeasuredTiming {
+            kind: "program".into(),
+            name: "inst".into(),
+            max_ms: 2.4,
+        }];
+        let report = analyze_wcet(
+            &db,
+            WcetOptions {
+                measured: &measured,
+                ..WcetOptions::default()
+            },
+        );
+        assert!(report.calibrated);
+        assert!((report.statement_ns - 100_000.0).abs() < 1e-6);
+        let task = &report.tasks[0];
+        assert_eq!(task.programs[0].measured_ms, Some(2.4));
+        assert!((task.estimate_ms - 2.4).abs() < 1e-9);
+        assert!(task.overrun);
+    }
+
+    #[test]
+    fn wcet_marks_unbounded_loops_and_parses_time_literals() {
+        let source = r#"
+PROGRAM Main
+VAR
+    x : INT;
+END_VAR
+    WHILE x < 10 DO
+        x := x + 1;
+    END_WHILE;
+END_PROGRAM
+"#;
+        let db = database(source);
+        let report = analyze_wcet(
+            &db,
+            WcetOptions {
+                loop_iterations: 5,
+                ..WcetOptions::default()
+            },
+        );
+        assert!(report.tasks.is_empty());
+        assert_eq!(report.unscheduled.len(), 1);
+        let program = &report.unscheduled[0];
+        assert!(program.unbounded);
+        assert_eq!(program.statements, 1 + 5 * 2);
+
+        assert_eq!(parse_time_ms("T#1s500ms"), Some(1500.0));
+        assert_eq!(parse_time_ms("TIME#2m"), Some(120_000.0));
+        assert_eq!(parse_time_ms("t#250us"), Some(0.25));
+        assert_eq!(parse_time_ms("T#10"), None);
+    }
+}
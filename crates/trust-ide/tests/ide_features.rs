@@ -6,7 +6,9 @@ use trust_hir::db::{FileId, SourceDatabase};
 use trust_hir::Database;
 use trust_ide::completion::complete;
 use trust_ide::hover;
-use trust_ide::references::{find_references, FindReferencesOptions};
+use trust_ide::references::{
+    classify_references, find_references, FindReferencesOptions, ReferenceAccess,
+};
 use trust_ide::rename::rename;
 use trust_ide::semantic_tokens::{semantic_tokens, SemanticTokenType};
 use trust_ide::{goto_definition, goto_implementation};
@@ -80,6 +82,44 @@ fn test_completion_statement_context() {
     );
 }
 
+#[test]
+fn test_completion_dialect_keywords() {
+    let source = "PROGRAM Test\n\nEND_PROGRAM";
+    let (db, file) = setup(source);
+    let pos = TextSize::from(source.find("\n\n").unwrap() as u32 + 1);
+    let filter = trust_ide::StdlibFilter::allow_all();
+
+    let iec = complete(&db, file, pos);
+    assert!(!iec.iter().any(|c| c.label == "REGION"));
+
+    let siemens =
+        trust_ide::complete_with_dialect(&db, file, pos, &filter, &trust_syntax::dialect::SIEMENS);
+    let region = siemens
+        .iter()
+        .find(|c| c.label == "REGION")
+        .expect("REGION snippet for siemens dialect");
+    assert!(region
+        .insert_text
+        .as_deref()
+        .is_some_and(|text| text.contains("END_REGION")));
+}
+
+#[test]
+fn test_completion_dialect_type_keywords() {
+    let source = "PROGRAM Test VAR p : END_VAR END_PROGRAM";
+    let (db, file) = setup(source);
+    let pos = TextSize::from(source.find(": ").unwrap() as u32 + 2);
+    let filter = trust_ide::StdlibFilter::allow_all();
+
+    let iec = complete(&db, file, pos);
+    assert!(iec.iter().any(|c| c.label == "REF_TO"));
+    assert!(!iec.iter().any(|c| c.label == "POINTER TO"));
+
+    let codesys =
+        trust_ide::complete_with_dialect(&db, file, pos, &filter, &trust_syntax::dialect::CODESYS);
+    assert!(codesys.iter().any(|c| c.label == "POINTER TO"));
+}
+
 #[test]
 fn test_completion_includes_symbols() {
     let source = r#"PROGRAM Test
@@ -130,6 +170,55 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_completion_member_access_respects_visibility() {
+    let source = r#"
+CLASS Base
+VAR PRIVATE
+    secret : INT;
+END_VAR
+VAR PROTECTED
+    shared : INT;
+END_VAR
+VAR PUBLIC
+    open : INT;
+END_VAR
+END_CLASS
+
+CLASS Derived EXTENDS Base
+METHOD PUBLIC Probe
+VAR
+    other : Derived;
+END_VAR
+other.
+END_METHOD
+END_CLASS
+
+PROGRAM Test
+VAR
+    d : Derived;
+END_VAR
+
+d.
+END_PROGRAM
+"#;
+    let (db, file) = setup(source);
+
+    let outside = TextSize::from(source.find("d.\n").unwrap() as u32 + 2);
+    let completions = complete(&db, file, outside);
+    assert!(completions.iter().any(|c| c.label == "open"));
+    assert!(!completions.iter().any(|c| c.label == "shared"));
+    assert!(!completions.iter().any(|c| c.label == "secret"));
+
+    let inside = TextSize::from(source.find("other.\n").unwrap() as u32 + 6);
+    let completions = complete(&db, file, inside);
+    assert!(completions.iter().any(|c| c.label == "shared"));
+    assert!(
+        !completions.iter().any(|c| c.label == "secret"),
+        "PRIVATE members of a base class should not be offered in derived classes"
+    );
+}
+
 // =============================================================================
 // Go To Definition Tests
 // =============================================================================
@@ -382,6 +471,48 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_references_classified_by_access() {
+    let source = r#"
+FUNCTION_BLOCK Worker
+    VAR_INPUT inp : INT; END_VAR
+    VAR_OUTPUT outp : INT; END_VAR
+    VAR_IN_OUT io : INT; END_VAR
+END_FUNCTION_BLOCK
+
+FUNCTION Bump : INT
+    VAR_IN_OUT value : INT; END_VAR
+    value := value + 1;
+END_FUNCTION
+
+PROGRAM Main
+    VAR
+        x : INT;
+        arr : ARRAY[0..3] OF INT;
+        w : Worker;
+    END_VAR
+    x := 1;
+    arr[x] := x;
+    w(inp := x, outp => x, io := x);
+    Bump(x);
+END_PROGRAM
+"#;
+    let (db, file) = setup(source);
+    let pos = TextSize::from(source.find("x : INT").unwrap() as u32);
+    let refs = find_references(&db, file, pos, FindReferencesOptions::default());
+    let classified = classify_references(&db, refs);
+    let count = |access: ReferenceAccess| {
+        classified
+            .iter()
+            .filter(|item| item.access == access)
+            .count()
+    };
+
+    assert_eq!(count(ReferenceAccess::Write), 2, "{classified:?}");
+    assert_eq!(count(ReferenceAccess::InOut), 2, "{classified:?}");
+    assert_eq!(count(ReferenceAccess::Read), 3, "{classified:?}");
+}
+
 // =============================================================================
 // Rename Tests
 // =============================================================================
@@ -458,6 +589,57 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_rename_updates_var_config_access_paths() {
+    let source = r#"
+PROGRAM Main
+VAR
+    Count : INT;
+    Out AT %Q* : BOOL;
+END_VAR
+END_PROGRAM
+
+CONFIGURATION Conf
+RESOURCE Res ON PLC
+TASK Fast (INTERVAL := T#10ms, PRIORITY := 1);
+PROGRAM P1 WITH Fast : Main;
+END_RESOURCE
+VAR_CONFIG
+    P1.Out AT %QX0.0 : BOOL;
+    Res.P1.Count : INT := 3;
+END_VAR
+END_CONFIGURATION
+"#;
+    let (db, file) = setup(source);
+    let pos = TextSize::from(source.find("Count :").unwrap() as u32);
+
+    let result = rename(&db, file, pos, "Total").expect("rename result");
+    let mut starts: Vec<usize> = result.edits[&file]
+        .iter()
+        .map(|edit| usize::from(edit.range.start()))
+        .collect();
+    starts.sort_unstable();
+    assert_eq!(
+        starts,
+        vec![
+            source.find("Count :").unwrap(),
+            source.find("Count : INT :=").unwrap()
+        ]
+    );
+
+    let target = trust_ide::rename::rename_target(&db, file, pos).expect("target");
+    let ranges = trust_ide::rename::access_path_references(&db, target, "P1.Count");
+    assert_eq!(ranges.len(), 1);
+    assert_eq!(usize::from(ranges[0].start()), 3);
+    assert!(trust_ide::rename::access_path_references(&db, target, "P1.Out").is_empty());
+
+    let instance_pos = TextSize::from(source.find("P1 WITH").unwrap() as u32);
+    let instance = trust_ide::rename::rename_target(&db, file, instance_pos).expect("instance");
+    let ranges = trust_ide::rename::access_path_references(&db, instance, "Res.P1.Count");
+    assert_eq!(ranges.len(), 1);
+    assert_eq!(usize::from(ranges[0].start()), 4);
+}
+
 #[test]
 fn test_rename_struct_field() {
     let source = r#"
@@ -817,6 +999,59 @@ fn test_semantic_tokens_keywords() {
     }
 }
 
+#[test]
+fn test_semantic_tokens_config_literals_and_addresses() {
+    let source = r#"
+CONFIGURATION Plant
+RESOURCE Cpu ON PLC
+    TASK Fast(INTERVAL := T#10ms, PRIORITY := 1);
+    PROGRAM Line1 WITH Fast : Main;
+END_RESOURCE
+END_CONFIGURATION
+
+PROGRAM Main
+VAR
+    start AT %IX0.0 : BOOL;
+    lamp AT %QX0.1 : BOOL;
+    count : INT := INT#5;
+END_VAR
+{attribute 'hide'}
+lamp := start;
+END_PROGRAM
+"#;
+    let (db, file) = setup(source);
+    let tokens = semantic_tokens(&db, file);
+    let token_at = |needle: &str| {
+        let offset = source.find(needle).unwrap() as u32;
+        tokens
+            .iter()
+            .find(|t| u32::from(t.range.start()) == offset)
+            .unwrap_or_else(|| panic!("missing semantic token for {needle}"))
+    };
+
+    let input = token_at("%IX0.0");
+    assert_eq!(input.token_type, SemanticTokenType::DirectAddress);
+    assert!(input.modifiers.input && !input.modifiers.output);
+    let output = token_at("%QX0.1");
+    assert_eq!(output.token_type, SemanticTokenType::DirectAddress);
+    assert!(output.modifiers.output);
+
+    let duration = token_at("T#10ms");
+    assert_eq!(duration.token_type, SemanticTokenType::Number);
+    assert!(duration.modifiers.duration);
+
+    let prefix = token_at("INT#");
+    assert_eq!(prefix.token_type, SemanticTokenType::Type);
+    assert!(prefix.modifiers.typed);
+    let typed_value = token_at("5;");
+    assert_eq!(typed_value.token_type, SemanticTokenType::Number);
+    assert!(typed_value.modifiers.typed);
+
+    assert_eq!(token_at("{attribute").token_type, SemanticTokenType::Pragma);
+    assert_eq!(token_at("Fast(").token_type, SemanticTokenType::Task);
+    assert_eq!(token_at("Fast :").token_type, SemanticTokenType::Task);
+}
+
 #[test]
 fn test_semantic_tokens_parameter() {
     let source = r#"
@@ -1043,6 +1278,51 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn test_hover_shows_doc_comments() {
+    let source = r#"
+// @brief Adds two numbers.
+// @param A Left-hand value.
+// @return Sum value.
+FUNCTION Add : INT
+VAR_INPUT
+    A : INT;
+END_VAR
+Add := A;
+END_FUNCTION
+
+PROGRAM Main
+VAR
+    total : INT; (* Running total. *)
+END_VAR
+total := Add(A := 1);
+END_PROGRAM
+"#;
+    let (db, file) = setup(source);
+
+    let call_offset = TextSize::from(source.find("Add(A").unwrap() as u32);
+    let call_hover = hover(&db, file, call_offset).expect("hover");
+    assert!(
+        call_hover.contents.contains("Adds two numbers."),
+        "Hover should include the @brief text"
+    );
+    assert!(
+        call_hover.contents.contains("- `A`: Left-hand value."),
+        "Hover should list documented parameters"
+    );
+    assert!(
+        call_hover.contents.contains("**Returns**: Sum value."),
+        "Hover should include the @return text"
+    );
+
+    let total_offset = TextSize::from(source.find("total :=").unwrap() as u32);
+    let total_hover = hover(&db, file, total_offset).expect("hover");
+    assert!(
+        total_hover.contents.contains("Running total."),
+        "Hover should include a trailing variable comment"
+    );
+}
+
 #[test]
 fn test_hover_task_priority() {
     let source = r#"
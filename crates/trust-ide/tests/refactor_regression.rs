@@ -230,8 +230,9 @@ END_FUNCTION_BLOCK
     expect![[r#"
         file 0:
           [1..15] => "FUNCTION"
-          [23..23] => " : INT"
-          [23..85] => ""
+          [18..18] => " : INT"
+          [19..68] => ""
+          [72..78] => "Fb"
           [85..103] => "END_FUNCTION"
     "#]]
     .assert_eq(&snapshot);
@@ -0,0 +1,419 @@
+//! Headless `trust-lsp check` command.
+//!
+//! Indexes a project the way the language server does and runs the same
+//! diagnostic pass, so CI results match what the editor shows.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Url};
+
+use crate::handlers::{collect_workspace_diagnostics, index_project_blocking};
+use crate::state::{uri_to_path, ServerState};
+
+const USAGE: &str = "\
+Usage: trust-lsp check [--project <dir>] [--format human|json|sarif] [--output <file>]
+                       [--fail-on error|warning|never]
+
+Runs the language server diagnostics over a project without an editor.
+Exits with status 1 when a diagnostic at or above --fail-on (default: error) is found.";
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckFormat {
+    Human,
+    Json,
+    Sarif,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FailOn {
+    Error,
+    Warning,
+    Never,
+}
+
+#[derive(Debug, Clone)]
+struct CheckOptions {
+    project: PathBuf,
+    format: CheckFormat,
+    output: Option<PathBuf>,
+    fail_on: FailOn,
+}
+
+/// One diagnostic with its file path relative to the project root.
+#[derive(Debug, Clone)]
+struct CheckFinding {
+    path: String,
+    diagnostic: Diagnostic,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct CheckSummary {
+    files: usize,
+    errors: usize,
+    warnings: usize,
+    notes: usize,
+}
+
+/// Runs `trust-lsp check` with the arguments after the subcommand name and
+/// returns the process exit code.
+pub fn run_check(args: impl IntoIterator<Item = String>) -> i32 {
+    let options = match parse_args(args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return 0;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            return 2;
+        }
+    };
+    match check_project(&options) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            2
+        }
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> anyhow::Result<Option<CheckOptions>> {
+    let mut options = CheckOptions {
+        project: PathBuf::from("."),
+        format: CheckFormat::Human,
+        output: None,
+        fail_on: FailOn::Error,
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| anyhow::anyhow!("{name} requires a value"))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--project" => options.project = PathBuf::from(value("--project")?),
+            "--output" => options.output = Some(PathBuf::from(value("--output")?)),
+            "--format" => {
+                options.format = match value("--format")?.as_str() {
+                    "human" => CheckFormat::Human,
+                    "json" => CheckFormat::Json,
+                    "sarif" => CheckFormat::Sarif,
+                    other => anyhow::bail!("unknown format '{other}'"),
+                }
+            }
+            "--fail-on" => {
+                options.fail_on = match value("--fail-on")?.as_str() {
+                    "error" => FailOn::Error,
+                    "warning" => FailOn::Warning,
+                    "never" => FailOn::Never,
+                    other => anyhow::bail!("unknown --fail-on level '{other}'"),
+                }
+            }
+            other => anyhow::bail!("unexpected argument '{other}'"),
+        }
+    }
+    Ok(Some(options))
+}
+
+fn check_project(options: &CheckOptions) -> anyhow::Result<i32> {
+    let root = options
+        .project
+        .canonicalize()
+        .map_err(|err| anyhow::anyhow!("project {} not found: {err}", options.project.display()))?;
+    let (findings, files) = collect_findings(&root)?;
+    let summary = summarize(&findings, files);
+    let rendered = match options.format {
+        CheckFormat::Human => render_human(&findings, summary),
+        CheckFormat::Json => serde_json::to_string_pretty(&render_json(&root, &findings, summary))?,
+        CheckFormat::Sarif => serde_json::to_string_pretty(&render_sarif(&root, &findings))?,
+    };
+    match options.output.as_ref() {
+        Some(path) => std::fs::write(path, format!("{rendered}\n"))
+            .map_err(|err| anyhow::anyhow!("failed to write {}: {err}", path.display()))?,
+        None => println!("{rendered}"),
+    }
+    let failed = match options.fail_on {
+        FailOn::Error => summary.errors > 0,
+        FailOn::Warning => summary.errors + summary.warnings > 0,
+        FailOn::Never => false,
+    };
+    Ok(i32::from(failed))
+}
+
+fn collect_findings(root: &Path) -> anyhow::Result<(Vec<CheckFinding>, usize)> {
+    let state = ServerState::new();
+    index_project_blocking(&state, root)?;
+    let reports = collect_workspace_diagnostics(&state);
+    let files = reports.len();
+    let mut findings = Vec::new();
+    for (uri, diagnostics) in reports {
+        let path = display_path(root, &uri);
+        for diagnostic in diagnostics {
+            findings.push(CheckFinding {
+                path: path.clone(),
+                diagnostic,
+            });
+        }
+    }
+    Ok((findings, files))
+}
+
+fn display_path(root: &Path, uri: &Url) -> String {
+    let Some(path) = uri_to_path(uri) else {
+        return uri.to_string();
+    };
+    let path = path.strip_prefix(root).unwrap_or(&path);
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn summarize(findings: &[CheckFinding], files: usize) -> CheckSummary {
+    let mut summary = CheckSummary {
+        files,
+        ..CheckSummary::default()
+    };
+    for finding in findings {
+        match finding.diagnostic.severity {
+            Some(DiagnosticSeverity::ERROR) => summary.errors += 1,
+            Some(DiagnosticSeverity::WARNING) => summary.warnings += 1,
+            _ => summary.notes += 1,
+        }
+    }
+    summary
+}
+
+fn severity_label(diagnostic: &Diagnostic) -> &'static str {
+    match diagnostic.severity {
+        Some(DiagnosticSeverity::ERROR) => "error",
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        Some(DiagnosticSeverity::HINT) => "hint",
+        _ => "info",
+    }
+}
+
+fn sarif_level(diagnostic: &Diagnostic) -> &'static str {
+    match diagnostic.severity {
+        Some(DiagnosticSeverity::ERROR) => "error",
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        _ => "note",
+    }
+}
+
+fn code_label(diagnostic: &Diagnostic) -> Option<String> {
+    match diagnostic.code.as_ref()? {
+        NumberOrString::String(code) => Some(code.clone()),
+        NumberOrString::Number(code) => Some(code.to_string()),
+    }
+}
+
+fn render_human(findings: &[CheckFinding], summary: CheckSummary) -> String {
+    let mut out = String::new();
+    for finding in findings {
+        let diagnostic = &finding.diagnostic;
+        let start = diagnostic.range.start;
+        let code = code_label(diagnostic)
+            .map(|code| format!("[{code}]"))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "{}:{}:{}: {}{}: {}\n",
+            finding.path,
+            start.line + 1,
+            start.character + 1,
+            severity_label(diagnostic),
+            code,
+            diagnostic.message
+        ));
+    }
+    out.push_str(&format!(
+        "checked {} file(s): {} error(s), {} warning(s), {} note(s)",
+        summary.files, summary.errors, summary.warnings, summary.notes
+    ));
+    out
+}
+
+fn render_json(root: &Path, findings: &[CheckFinding], summary: CheckSummary) -> Value {
+    let diagnostics = findings
+        .iter()
+        .map(|finding| {
+            let diagnostic = &finding.diagnostic;
+            json!({
+                "file": finding.path,
+                "line": diagnostic.range.start.line + 1,
+                "column": diagnostic.range.start.character + 1,
+                "end_line": diagnostic.range.end.line + 1,
+                "end_column": diagnostic.range.end.character + 1,
+                "severity": severity_label(diagnostic),
+                "code": code_label(diagnostic),
+                "message": diagnostic.message,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "version": 1,
+        "command": "check",
+        "status": if summary.errors > 0 { "failed" } else { "ok" },
+        "project": root.display().to_string(),
+        "summary": {
+            "files": summary.files,
+            "errors": summary.errors,
+            "warnings": summary.warnings,
+            "notes": summary.notes,
+        },
+        "diagnostics": diagnostics,
+    })
+}
+
+fn render_sarif(root: &Path, findings: &[CheckFinding]) -> Value {
+    let rules = findings
+        .iter()
+        .filter_map(|finding| code_label(&finding.diagnostic))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|code| json!({ "id": code }))
+        .collect::<Vec<_>>();
+    let results = findings
+        .iter()
+        .map(|finding| {
+            let diagnostic = &finding.diagnostic;
+            let range = diagnostic.range;
+            let mut result = json!({
+                "level": sarif_level(diagnostic),
+                "message": { "text": diagnostic.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": finding.path,
+                            "uriBaseId": "PROJECTROOT",
+                        },
+                        "region": {
+                            "startLine": range.start.line + 1,
+                            "startColumn": range.start.character + 1,
+                            "endLine": range.end.line + 1,
+                            "endColumn": range.end.character + 1,
+                        },
+                    },
+                }],
+            });
+            if let Some(code) = code_label(diagnostic) {
+                result["ruleId"] = json!(code);
+            }
+            result
+        })
+        .collect::<Vec<_>>();
+    let root_uri = Url::from_directory_path(root)
+        .map(|uri| uri.to_string())
+        .unwrap_or_else(|_| root.display().to_string());
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "trust-lsp",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "originalUriBaseIds": {
+                "PROJECTROOT": { "uri": root_uri },
+            },
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time before UNIX_EPOCH")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("{prefix}-{stamp}"));
+        std::fs::create_dir_all(path.join("src")).expect("create temp dir");
+        path
+    }
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn check_reports_project_diagnostics_as_sarif_and_json() {
+        let root = temp_dir("trust-lsp-check");
+        std::fs::write(
+            root.join("trust-lsp.toml"),
+            "[project]\ninclude_paths = [\"src\"]\n",
+        )
+        .expect("write config");
+        std::fs::write(
+            root.join("src/main.st"),
+            "PROGRAM Main\nVAR\n    x : INT;\nEND_VAR\nx := missing + 1;\nEND_PROGRAM\n",
+        )
+        .expect("write source");
+        let root = root.canonicalize().expect("canonical root");
+
+        let (findings, files) = collect_findings(&root).expect("collect findings");
+        let summary = summarize(&findings, files);
+        assert!(summary.errors > 0, "expected an undefined-name error");
+        let undefined = findings
+            .iter()
+            .find(|finding| finding.diagnostic.message.contains("missing"))
+            .expect("undefined name diagnostic");
+        assert_eq!(undefined.path, "src/main.st");
+
+        let sarif = render_sarif(&root, &findings);
+        let result = sarif["runs"][0]["results"]
+            .as_array()
+            .and_then(|results| {
+                results.iter().find(|result| {
+                    result["message"]["text"]
+                        .as_str()
+                        .is_some_and(|text| text.contains("missing"))
+                })
+            })
+            .expect("sarif result");
+        assert_eq!(result["level"], "error");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/main.st");
+        assert_eq!(location["region"]["startLine"], 5);
+        let rule_id = result["ruleId"].as_str().expect("rule id");
+        assert!(sarif["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .expect("rules")
+            .iter()
+            .any(|rule| rule["id"] == rule_id));
+
+        let report = render_json(&root, &findings, summary);
+        assert_eq!(report["status"], "failed");
+        assert_eq!(report["summary"]["errors"], summary.errors);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn parse_args_reads_format_and_gate() {
+        let options = parse_args(args(&[
+            "--project",
+            "plc",
+            "--format",
+            "sarif",
+            "--fail-on",
+            "warning",
+        ]))
+        .expect("parse")
+        .expect("options");
+        assert_eq!(options.project, PathBuf::from("plc"));
+        assert_eq!(options.format, CheckFormat::Sarif);
+        assert_eq!(options.fail_on, FailOn::Warning);
+        assert!(parse_args(args(&["--format", "xml"])).is_err());
+        assert!(parse_args(args(&["--help"])).expect("parse").is_none());
+    }
+}
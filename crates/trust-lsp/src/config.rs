@@ -1,12 +1,16 @@
 //! Workspace/project configuration for trust-lsp.
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::{BTreeMap, HashMap};
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use tower_lsp::lsp_types::DiagnosticSeverity;
 use tracing::warn;
+use trust_ide::lint::{LintOptions, LintRule, NamingStyle};
+use trust_ide::CallTemplateParams;
+use trust_runtime::stlib::{is_library_archive, LibraryArchive};
 
 pub(crate) const CONFIG_FILES: &[&str] = &["trust-lsp.toml", ".trust-lsp.toml", "trustlsp.toml"];
 
@@ -25,6 +29,8 @@ pub struct ProjectConfig {
     pub stdlib: StdlibSettings,
     /// External libraries to index.
     pub libraries: Vec<LibrarySpec>,
+    /// Documentation bundles (markdown files or directories) for hover/completion.
+    pub doc_bundles: Vec<PathBuf>,
     /// Local package dependencies declared in `[dependencies]`.
     pub dependencies: Vec<ProjectDependency>,
     /// Resolver issues produced while expanding local dependencies.
@@ -39,12 +45,18 @@ pub struct ProjectConfig {
     pub indexing: IndexingConfig,
     /// Diagnostics configuration.
     pub diagnostics: DiagnosticSettings,
+    /// Lint rule selection and options from `[lint]`.
+    pub lint: LintSettings,
     /// Runtime control configuration for debug-assisted features.
     pub runtime: RuntimeConfig,
     /// Workspace federation settings.
     pub workspace: WorkspaceSettings,
     /// Telemetry configuration (opt-in).
     pub telemetry: TelemetryConfig,
+    /// Formatter style settings.
+    pub format: FormatSettings,
+    /// Completion settings from `[completion]`.
+    pub completion: CompletionSettings,
 }
 
 impl ProjectConfig {
@@ -91,12 +103,16 @@ impl ProjectConfig {
         config.diagnostic_external_paths = resolve_paths(root, &diagnostics_section.external_paths);
         config.diagnostics =
             DiagnosticSettings::from_config(config.vendor_profile.as_deref(), diagnostics_section);
+        config.lint = parsed.lint.into();
         config.runtime = parsed.runtime.into();
         config.workspace = WorkspaceSettings::from(parsed.workspace);
         config.telemetry = TelemetryConfig::from_section(root, parsed.telemetry);
+        config.format = parsed.format.into();
+        config.completion = parsed.completion.into();
 
         let mut include_paths = resolve_paths(root, &parsed.project.include_paths);
         config.include_paths.append(&mut include_paths);
+        config.doc_bundles = resolve_paths(root, &parsed.project.doc_bundles);
 
         let mut libraries = Vec::new();
         for path in resolve_paths(root, &parsed.project.library_paths) {
@@ -140,7 +156,7 @@ impl ProjectConfig {
         }
         let policy = DependencyPolicy::from(parsed.dependency_policy);
         let (dependencies, mut dependency_resolution_issues) =
-            parse_project_dependencies(root, &parsed.dependencies);
+            parse_project_dependencies(root, &parsed.dependencies, &parsed.registries);
         let (dependency_libraries, mut resolver_issues) =
             resolve_manifest_dependencies(root, &dependencies, &config.build, &policy);
         dependency_resolution_issues.append(&mut resolver_issues);
@@ -163,6 +179,13 @@ impl ProjectConfig {
         roots
     }
 
+    /// Returns the persisted index cache size cap in bytes (if configured).
+    pub fn index_cache_max_bytes(&self) -> Option<u64> {
+        self.indexing
+            .cache_max_mb
+            .map(|mb| (mb as u64).saturating_mul(1024 * 1024))
+    }
+
     /// Returns the resolved index cache directory (if enabled).
     pub fn index_cache_dir(&self) -> Option<PathBuf> {
         if !self.indexing.cache_enabled {
@@ -186,6 +209,7 @@ impl ProjectConfig {
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -193,9 +217,12 @@ impl ProjectConfig {
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         }
     }
 }
@@ -219,6 +246,8 @@ pub struct IndexingConfig {
     pub cache_enabled: bool,
     /// Optional cache directory override.
     pub cache_dir: Option<PathBuf>,
+    /// Optional cap on persisted cache size, in MB.
+    pub cache_max_mb: Option<usize>,
     /// Optional memory budget for indexed (closed) documents, in MB.
     pub memory_budget_mb: Option<usize>,
     /// Target percent of the budget to evict down to (0-100).
@@ -240,6 +269,7 @@ impl Default for IndexingConfig {
             max_ms: None,
             cache_enabled: true,
             cache_dir: None,
+            cache_max_mb: None,
             memory_budget_mb: None,
             evict_to_percent: 80,
             throttle_idle_ms: 0,
@@ -250,6 +280,25 @@ impl Default for IndexingConfig {
     }
 }
 
+/// Type-check strictness for implicit conversions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TypeCheckLevel {
+    /// Report only the standard IEC conversion diagnostics.
+    #[default]
+    Standard,
+    /// Also report lossy operand promotion (W014) and signed/unsigned mixing (W015).
+    Strict,
+}
+
+impl TypeCheckLevel {
+    fn from_str(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "strict" => TypeCheckLevel::Strict,
+            _ => TypeCheckLevel::Standard,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DiagnosticSettings {
     /// Toggle unused variable/parameter warnings (W001/W002).
@@ -268,6 +317,16 @@ pub struct DiagnosticSettings {
     pub warn_complexity: bool,
     /// Toggle non-determinism warnings (W010/W011).
     pub warn_nondeterminism: bool,
+    /// Toggle workspace-wide dead code warnings (W013).
+    pub warn_dead_code: bool,
+    /// Toggle FOR loop array bounds warnings (W021).
+    pub warn_loop_bounds: bool,
+    /// Toggle warnings for CASE over an enumeration missing values (W022).
+    pub warn_case_exhaustive: bool,
+    /// Toggle workspace-wide recursion errors (E308).
+    pub check_recursion: bool,
+    /// Type-check strictness for implicit operand conversions (W014/W015).
+    pub type_check: TypeCheckLevel,
     /// Per-code severity overrides (e.g., W010 -> error).
     pub severity_overrides: HashMap<String, DiagnosticSeverity>,
 }
@@ -283,6 +342,11 @@ impl Default for DiagnosticSettings {
             warn_deprecated: true,
             warn_complexity: true,
             warn_nondeterminism: true,
+            warn_dead_code: false,
+            warn_loop_bounds: true,
+            warn_case_exhaustive: true,
+            check_recursion: true,
+            type_check: TypeCheckLevel::Standard,
             severity_overrides: HashMap::new(),
         }
     }
@@ -353,6 +417,21 @@ impl DiagnosticSettings {
         if let Some(value) = section.warn_nondeterminism {
             settings.warn_nondeterminism = value;
         }
+        if let Some(value) = section.warn_dead_code {
+            settings.warn_dead_code = value;
+        }
+        if let Some(value) = section.warn_loop_bounds {
+            settings.warn_loop_bounds = value;
+        }
+        if let Some(value) = section.warn_case_exhaustive {
+            settings.warn_case_exhaustive = value;
+        }
+        if let Some(value) = section.check_recursion {
+            settings.check_recursion = value;
+        }
+        if let Some(value) = section.type_check.as_deref() {
+            settings.type_check = TypeCheckLevel::from_str(value);
+        }
 
         apply_severity_overrides(&mut settings, section.severity_overrides);
         settings
@@ -369,6 +448,10 @@ impl DiagnosticSettings {
         self.warn_deprecated = true;
         self.warn_complexity = true;
         self.warn_nondeterminism = true;
+        self.warn_dead_code = true;
+        self.warn_loop_bounds = true;
+        self.warn_case_exhaustive = true;
+        self.check_recursion = true;
     }
 }
 
@@ -395,11 +478,17 @@ fn apply_rule_pack(settings: &mut DiagnosticSettings, pack: &str) {
 }
 
 fn apply_safety_overrides(settings: &mut DiagnosticSettings) {
+    settings.type_check = TypeCheckLevel::Strict;
     let overrides = [
         ("W004", DiagnosticSeverity::ERROR),
+        ("W021", DiagnosticSeverity::ERROR),
+        ("W022", DiagnosticSeverity::ERROR),
         ("W005", DiagnosticSeverity::ERROR),
         ("W010", DiagnosticSeverity::ERROR),
         ("W011", DiagnosticSeverity::ERROR),
+        ("W014", DiagnosticSeverity::ERROR),
+        ("W015", DiagnosticSeverity::ERROR),
+        ("W026", DiagnosticSeverity::ERROR),
     ];
     for (code, severity) in overrides {
         settings
@@ -426,6 +515,89 @@ fn parse_severity(value: &str) -> Option<DiagnosticSeverity> {
     }
 }
 
+/// Lint rule selection from `[lint]`.
+///
+/// Only rules listed here run; each maps to the severity used for its
+/// diagnostics (R001-R005).
+#[derive(Debug, Clone, Default)]
+pub struct LintSettings {
+    /// Enabled rules and their diagnostic severity.
+    pub rules: HashMap<LintRule, DiagnosticSeverity>,
+    /// Options passed to the lint engine.
+    pub options: LintOptions,
+}
+
+impl LintSettings {
+    /// Returns engine options restricted to the enabled rules.
+    pub fn lint_options(&self) -> LintOptions {
+        let mut rules: Vec<LintRule> = self.rules.keys().copied().collect();
+        rules.sort();
+        LintOptions {
+            rules,
+            ..self.options.clone()
+        }
+    }
+}
+
+impl From<LintSection> for LintSettings {
+    fn from(section: LintSection) -> Self {
+        let mut settings = LintSettings::default();
+        if let Some(preset) = section.preset.as_deref() {
+            apply_lint_preset(&mut settings, preset);
+        }
+        for (key, severity) in section.rules {
+            let Some(rule) = LintRule::from_key(&key) else {
+                warn!("Unknown lint rule '{key}' in [lint.rules]");
+                continue;
+            };
+            if severity.trim().eq_ignore_ascii_case("off") {
+                settings.rules.remove(&rule);
+            } else if let Some(parsed) = parse_severity(&severity) {
+                settings.rules.insert(rule, parsed);
+            }
+        }
+        if let Some(depth) = section.max_nesting_depth {
+            settings.options.max_nesting_depth = depth;
+        }
+        if let Some(functions) = section.forbidden_functions {
+            settings.options.forbidden_functions = functions.into_iter().map(Into::into).collect();
+        }
+        if let Some(numbers) = section.allowed_numbers {
+            settings.options.allowed_numbers = numbers.into_iter().map(Into::into).collect();
+        }
+        let naming = &mut settings.options.naming;
+        for (value, target) in [
+            (section.naming.variables, &mut naming.variables),
+            (section.naming.constants, &mut naming.constants),
+            (section.naming.pous, &mut naming.pous),
+            (section.naming.types, &mut naming.types),
+        ] {
+            if let Some(value) = value {
+                *target = NamingStyle::parse(&value);
+            }
+        }
+        settings
+    }
+}
+
+fn apply_lint_preset(settings: &mut LintSettings, preset: &str) {
+    match preset.trim().to_ascii_lowercase().as_str() {
+        "misra" | "misra-like" | "strict" => {
+            for rule in LintRule::ALL {
+                settings.rules.insert(rule, DiagnosticSeverity::WARNING);
+            }
+            settings
+                .rules
+                .insert(LintRule::LoopExit, DiagnosticSeverity::ERROR);
+            let naming = &mut settings.options.naming;
+            naming.pous = Some(NamingStyle::PascalCase);
+            naming.types = Some(NamingStyle::PascalCase);
+            naming.constants = Some(NamingStyle::UpperSnakeCase);
+        }
+        _ => warn!("Unknown lint preset '{preset}'"),
+    }
+}
+
 /// Runtime control settings for inline values/debug integration.
 #[derive(Debug, Clone, Default)]
 pub struct RuntimeConfig {
@@ -543,6 +715,53 @@ impl From<WorkspaceSection> for WorkspaceSettings {
     }
 }
 
+/// Formatter style settings from `[format]`.
+///
+/// Values are kept as written; the formatting handler resolves them on top of
+/// the vendor profile and client settings.
+#[derive(Debug, Clone, Default)]
+pub struct FormatSettings {
+    /// Named style profile (e.g., codesys, siemens).
+    pub profile: Option<String>,
+    /// Indentation width in spaces.
+    pub indent_width: Option<usize>,
+    /// Use spaces instead of tabs.
+    pub insert_spaces: Option<bool>,
+    /// Keyword casing (`upper`, `lower`, `pascal`, `preserve`).
+    pub keyword_case: Option<String>,
+    /// VAR block placement relative to the POU header (`indented`, `flush`).
+    pub var_block_indent: Option<String>,
+    /// Align type colons inside VAR blocks.
+    pub align_var_decls: Option<bool>,
+    /// Align `:=` initializers inside VAR blocks.
+    pub align_var_assignments: Option<bool>,
+    /// Align `:=`/`=>` in consecutive statements.
+    pub align_assignments: Option<bool>,
+    /// Maximum line length before call arguments are wrapped.
+    pub max_line_length: Option<usize>,
+    /// Operator spacing (`spaced`, `compact`).
+    pub spacing_style: Option<String>,
+    /// END_* keyword placement (`aligned`, `indented`).
+    pub end_keyword_style: Option<String>,
+    /// Member order used by organize declarations (`name`, `type`, `none`).
+    pub declaration_sort: Option<String>,
+}
+
+/// Completion settings from `[completion]`.
+#[derive(Debug, Clone, Copy)]
+pub struct CompletionSettings {
+    /// Parameters listed by the call template offered after `(`; `None` turns it off.
+    pub call_template: Option<CallTemplateParams>,
+}
+
+impl Default for CompletionSettings {
+    fn default() -> Self {
+        Self {
+            call_template: Some(CallTemplateParams::All),
+        }
+    }
+}
+
 /// Telemetry configuration (opt-in).
 #[derive(Debug, Clone)]
 pub struct TelemetryConfig {
@@ -597,6 +816,8 @@ pub struct ProjectDependency {
     pub name: String,
     pub path: Option<PathBuf>,
     pub git: Option<GitDependency>,
+    /// Registry base (URL or local directory) for registry packages.
+    pub registry: Option<String>,
     pub version: Option<String>,
 }
 
@@ -611,6 +832,7 @@ pub struct DependencyResolutionIssue {
 #[derive(Debug, Clone, Default)]
 pub struct DependencyPolicy {
     pub allowed_git_hosts: Vec<String>,
+    pub allowed_registry_hosts: Vec<String>,
     pub allow_http: bool,
     pub allow_ssh: bool,
 }
@@ -620,6 +842,8 @@ struct ConfigFile {
     #[serde(default)]
     dependencies: BTreeMap<String, ManifestDependencyEntry>,
     #[serde(default)]
+    registries: BTreeMap<String, String>,
+    #[serde(default)]
     dependency_policy: DependencyPolicySection,
     #[serde(default)]
     project: ProjectSection,
@@ -634,11 +858,17 @@ struct ConfigFile {
     #[serde(default)]
     diagnostics: DiagnosticSection,
     #[serde(default)]
+    lint: LintSection,
+    #[serde(default)]
     libraries: Vec<LibrarySection>,
     #[serde(default)]
     runtime: RuntimeSection,
     #[serde(default)]
     telemetry: TelemetrySection,
+    #[serde(default)]
+    format: FormatSection,
+    #[serde(default)]
+    completion: CompletionSection,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -650,6 +880,8 @@ struct ProjectSection {
     #[serde(default)]
     stdlib: StdlibSelection,
     vendor_profile: Option<String>,
+    #[serde(default)]
+    doc_bundles: Vec<String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -664,6 +896,7 @@ struct IndexingSection {
     max_ms: Option<u64>,
     cache: Option<bool>,
     cache_dir: Option<String>,
+    cache_max_mb: Option<usize>,
     memory_budget_mb: Option<usize>,
     evict_to_percent: Option<u8>,
     throttle_idle_ms: Option<u64>,
@@ -683,12 +916,37 @@ struct DiagnosticSection {
     warn_deprecated: Option<bool>,
     warn_complexity: Option<bool>,
     warn_nondeterminism: Option<bool>,
+    warn_dead_code: Option<bool>,
+    warn_loop_bounds: Option<bool>,
+    warn_case_exhaustive: Option<bool>,
+    check_recursion: Option<bool>,
+    type_check: Option<String>,
     #[serde(default)]
     external_paths: Vec<String>,
     #[serde(default)]
     severity_overrides: HashMap<String, String>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+struct LintSection {
+    preset: Option<String>,
+    #[serde(default)]
+    rules: HashMap<String, String>,
+    max_nesting_depth: Option<usize>,
+    forbidden_functions: Option<Vec<String>>,
+    allowed_numbers: Option<Vec<String>>,
+    #[serde(default)]
+    naming: LintNamingSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct LintNamingSection {
+    variables: Option<String>,
+    constants: Option<String>,
+    pous: Option<String>,
+    types: Option<String>,
+}
+
 #[derive(Debug, Default, Deserialize)]
 struct RuntimeSection {
     control_endpoint: Option<String>,
@@ -702,10 +960,33 @@ struct TelemetrySection {
     flush_every: Option<usize>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+struct CompletionSection {
+    call_template: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct FormatSection {
+    profile: Option<String>,
+    indent_width: Option<usize>,
+    insert_spaces: Option<bool>,
+    keyword_case: Option<String>,
+    var_block_indent: Option<String>,
+    align_var_decls: Option<bool>,
+    align_var_assignments: Option<bool>,
+    align_assignments: Option<bool>,
+    max_line_length: Option<usize>,
+    spacing_style: Option<String>,
+    end_keyword_style: Option<String>,
+    declaration_sort: Option<String>,
+}
+
 #[derive(Debug, Default, Deserialize)]
 struct DependencyPolicySection {
     #[serde(default)]
     allowed_git_hosts: Vec<String>,
+    #[serde(default)]
+    allowed_registry_hosts: Vec<String>,
     allow_http: Option<bool>,
     allow_ssh: Option<bool>,
 }
@@ -773,6 +1054,7 @@ enum ManifestDependencyEntry {
 struct ManifestDependencySection {
     path: Option<String>,
     git: Option<String>,
+    registry: Option<String>,
     version: Option<String>,
     rev: Option<String>,
     tag: Option<String>,
@@ -785,13 +1067,25 @@ struct DependencyManifestFile {
     package: PackageSection,
     #[serde(default)]
     dependencies: BTreeMap<String, ManifestDependencyEntry>,
+    #[serde(default)]
+    registries: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "source", rename_all = "snake_case")]
 enum DependencyLockEntry {
-    Path { path: String },
-    Git { url: String, rev: String },
+    Path {
+        path: String,
+    },
+    Git {
+        url: String,
+        rev: String,
+    },
+    Registry {
+        registry: String,
+        version: String,
+        checksum: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -802,6 +1096,12 @@ struct DependencyLockFile {
     dependencies: BTreeMap<String, DependencyLockEntry>,
 }
 
+#[derive(Debug, Clone)]
+struct ResolvedRegistryDependency {
+    path: PathBuf,
+    checksum: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 struct ResolvedGitDependency {
     path: PathBuf,
@@ -863,6 +1163,45 @@ impl From<RuntimeSection> for RuntimeConfig {
     }
 }
 
+impl From<FormatSection> for FormatSettings {
+    fn from(section: FormatSection) -> Self {
+        FormatSettings {
+            profile: section.profile,
+            indent_width: section.indent_width,
+            insert_spaces: section.insert_spaces,
+            keyword_case: section.keyword_case,
+            var_block_indent: section.var_block_indent,
+            align_var_decls: section.align_var_decls,
+            align_var_assignments: section.align_var_assignments,
+            align_assignments: section.align_assignments,
+            max_line_length: section.max_line_length,
+            spacing_style: section.spacing_style,
+            end_keyword_style: section.end_keyword_style,
+            declaration_sort: section.declaration_sort,
+        }
+    }
+}
+
+impl From<CompletionSection> for CompletionSettings {
+    fn from(section: CompletionSection) -> Self {
+        let call_template = match section.call_template.as_deref().map(str::trim) {
+            None => Some(CallTemplateParams::All),
+            Some(value) if value.eq_ignore_ascii_case("all") => Some(CallTemplateParams::All),
+            Some(value) if value.eq_ignore_ascii_case("mandatory") => {
+                Some(CallTemplateParams::Mandatory)
+            }
+            Some(value) if value.eq_ignore_ascii_case("off") => None,
+            Some(value) => {
+                warn!(
+                    "Unknown [completion].call_template '{value}', expected all, mandatory, or off"
+                );
+                Some(CallTemplateParams::All)
+            }
+        };
+        CompletionSettings { call_template }
+    }
+}
+
 impl From<BuildSection> for BuildConfig {
     fn from(section: BuildSection) -> Self {
         BuildConfig {
@@ -880,15 +1219,19 @@ impl From<BuildSection> for BuildConfig {
     }
 }
 
+fn normalize_hosts(hosts: Vec<String>) -> Vec<String> {
+    hosts
+        .into_iter()
+        .map(|host| host.trim().to_ascii_lowercase())
+        .filter(|host| !host.is_empty())
+        .collect()
+}
+
 impl From<DependencyPolicySection> for DependencyPolicy {
     fn from(section: DependencyPolicySection) -> Self {
         DependencyPolicy {
-            allowed_git_hosts: section
-                .allowed_git_hosts
-                .into_iter()
-                .map(|host| host.trim().to_ascii_lowercase())
-                .filter(|host| !host.is_empty())
-                .collect(),
+            allowed_git_hosts: normalize_hosts(section.allowed_git_hosts),
+            allowed_registry_hosts: normalize_hosts(section.allowed_registry_hosts),
             allow_http: section.allow_http.unwrap_or(false),
             allow_ssh: section.allow_ssh.unwrap_or(false),
         }
@@ -933,6 +1276,7 @@ impl From<IndexingSection> for IndexingConfig {
             max_ms: section.max_ms,
             cache_enabled: section.cache.unwrap_or(true),
             cache_dir: section.cache_dir.map(PathBuf::from),
+            cache_max_mb: section.cache_max_mb.filter(|mb| *mb > 0),
             memory_budget_mb: section.memory_budget_mb,
             evict_to_percent: section.evict_to_percent.unwrap_or(80),
             throttle_idle_ms: section.throttle_idle_ms.unwrap_or(0),
@@ -969,11 +1313,12 @@ fn resolve_path(root: &Path, entry: &str) -> PathBuf {
 fn parse_project_dependencies(
     root: &Path,
     entries: &BTreeMap<String, ManifestDependencyEntry>,
+    registries: &BTreeMap<String, String>,
 ) -> (Vec<ProjectDependency>, Vec<DependencyResolutionIssue>) {
     let mut dependencies = Vec::new();
     let mut issues = Vec::new();
     for (name, entry) in entries {
-        match parse_project_dependency(root, name, entry) {
+        match parse_project_dependency(root, name, entry, registries) {
             Ok(dependency) => dependencies.push(dependency),
             Err(message) => issues.push(DependencyResolutionIssue {
                 code: "L005",
@@ -1084,7 +1429,8 @@ impl<'a> DependencyResolver<'a> {
                 return;
             }
         };
-        if !path.is_dir() {
+        let is_archive = path.is_file() && is_library_archive(&path);
+        if !path.is_dir() && !is_archive {
             self.issues.push(DependencyResolutionIssue {
                 code: "L001",
                 dependency: dependency.name.clone(),
@@ -1126,10 +1472,15 @@ impl<'a> DependencyResolver<'a> {
         self.states
             .insert(dependency.name.clone(), DependencyVisitState::Visiting);
 
-        let (package, nested_dependencies) = match load_dependency_manifest(&path) {
+        let manifest = if is_archive {
+            load_archive_manifest(&path)
+        } else {
+            load_dependency_manifest(&path)
+        };
+        let (package, nested_dependencies) = match manifest {
             Ok(manifest) => {
                 let (nested, mut parse_issues) =
-                    parse_project_dependencies(&path, &manifest.dependencies);
+                    parse_project_dependencies(&path, &manifest.dependencies, &manifest.registries);
                 self.issues.append(&mut parse_issues);
                 (manifest.package, nested)
             }
@@ -1181,16 +1532,30 @@ impl<'a> DependencyResolver<'a> {
     }
 }
 
+/// Reads the `[package]` identity of a precompiled `.stlib` dependency.
+fn load_archive_manifest(path: &Path) -> Result<DependencyManifestFile, String> {
+    let archive = LibraryArchive::read(path).map_err(|err| format!("{err:#}"))?;
+    Ok(DependencyManifestFile {
+        package: PackageSection {
+            version: archive.manifest.version,
+        },
+        dependencies: BTreeMap::new(),
+        registries: BTreeMap::new(),
+    })
+}
+
 fn parse_project_dependency(
     root: &Path,
     name: &str,
     entry: &ManifestDependencyEntry,
+    registries: &BTreeMap<String, String>,
 ) -> Result<ProjectDependency, String> {
     match entry {
         ManifestDependencyEntry::Path(path) => Ok(ProjectDependency {
             name: name.to_string(),
             path: Some(resolve_path(root, path)),
             git: None,
+            registry: None,
             version: None,
         }),
         ManifestDependencyEntry::Detailed(section) => {
@@ -1202,13 +1567,43 @@ fn parse_project_dependency(
                 .git
                 .as_ref()
                 .is_some_and(|git| !git.trim().is_empty());
+            let has_registry = section
+                .registry
+                .as_ref()
+                .is_some_and(|registry| !registry.trim().is_empty());
 
-            if has_path == has_git {
+            if usize::from(has_path) + usize::from(has_git) + usize::from(has_registry) != 1 {
                 return Err(format!(
-                    "Dependency '{name}' must set exactly one of `path` or `git`"
+                    "Dependency '{name}' must set exactly one of `path`, `git`, or `registry`"
                 ));
             }
 
+            if has_registry {
+                if section.rev.is_some() || section.tag.is_some() || section.branch.is_some() {
+                    return Err(format!(
+                        "Dependency '{name}' registry entries do not support `rev`, `tag`, or `branch`"
+                    ));
+                }
+                let Some(version) = section
+                    .version
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|version| !version.is_empty())
+                else {
+                    return Err(format!(
+                        "Dependency '{name}' registry entries require an exact `version`"
+                    ));
+                };
+                let registry = section.registry.as_deref().unwrap_or_default().trim();
+                return Ok(ProjectDependency {
+                    name: name.to_string(),
+                    path: None,
+                    git: None,
+                    registry: Some(resolve_registry_base(root, registry, registries)),
+                    version: Some(version.to_string()),
+                });
+            }
+
             let selector_count = usize::from(section.rev.is_some())
                 + usize::from(section.tag.is_some())
                 + usize::from(section.branch.is_some());
@@ -1229,6 +1624,7 @@ fn parse_project_dependency(
                     name: name.to_string(),
                     path: Some(resolve_path(root, path)),
                     git: None,
+                    registry: None,
                     version: section.version.clone(),
                 });
             }
@@ -1242,6 +1638,7 @@ fn parse_project_dependency(
                     tag: section.tag.clone(),
                     branch: section.branch.clone(),
                 }),
+                registry: None,
                 version: section.version.clone(),
             })
         }
@@ -1267,6 +1664,28 @@ fn resolve_dependency_source(
         return Ok(resolved);
     }
 
+    if let Some(registry) = dependency.registry.as_ref() {
+        let version = dependency.version.as_deref().unwrap_or_default();
+        let resolved = resolve_registry_dependency(
+            root,
+            build,
+            policy,
+            lock,
+            &dependency.name,
+            registry,
+            version,
+        )?;
+        resolved_lock.insert(
+            dependency.name.clone(),
+            DependencyLockEntry::Registry {
+                registry: registry.clone(),
+                version: version.to_string(),
+                checksum: resolved.checksum,
+            },
+        );
+        return Ok(resolved.path);
+    }
+
     let Some(git) = dependency.git.as_ref() else {
         return Err(DependencyResolutionIssue {
             code: "L005",
@@ -1440,6 +1859,241 @@ fn resolve_git_dependency(
     })
 }
 
+/// Maps a `registry` value to a base URL or directory: names declared in
+/// `[registries]` first, then URLs, then paths relative to the manifest.
+fn resolve_registry_base(
+    root: &Path,
+    registry: &str,
+    registries: &BTreeMap<String, String>,
+) -> String {
+    let base = registries
+        .get(registry)
+        .map(|value| value.trim())
+        .unwrap_or(registry);
+    if base.contains("://") {
+        return base.trim_end_matches('/').to_string();
+    }
+    resolve_path(root, base).to_string_lossy().into_owned()
+}
+
+/// Fetches a registry package into the workspace cache.
+///
+/// Registries serve `<base>/<name>/<version>.tar.gz`; local registries may
+/// also hold an unpacked `<base>/<name>/<version>/` directory.
+fn resolve_registry_dependency(
+    root: &Path,
+    build: &BuildConfig,
+    policy: &DependencyPolicy,
+    lock: &DependencyLockFile,
+    dependency_name: &str,
+    registry: &str,
+    version: &str,
+) -> Result<ResolvedRegistryDependency, DependencyResolutionIssue> {
+    let issue = |code: &'static str, message: String| DependencyResolutionIssue {
+        code,
+        dependency: dependency_name.to_string(),
+        message,
+    };
+
+    let local_base = registry_local_base(registry);
+    if local_base.is_none() {
+        validate_registry_source_policy(registry, policy).map_err(|message| {
+            issue(
+                "L005",
+                format!("Dependency '{dependency_name}' rejected by trust policy: {message}"),
+            )
+        })?;
+    }
+
+    let locked_checksum = match lock.dependencies.get(dependency_name) {
+        Some(DependencyLockEntry::Registry {
+            registry: locked_registry,
+            version: locked_version,
+            checksum,
+        }) if locked_registry == registry && locked_version == version => checksum.clone(),
+        _ => None,
+    };
+
+    if let Some(base) = local_base.as_ref() {
+        let unpacked = base.join(dependency_name).join(version);
+        if unpacked.is_dir() {
+            return Ok(ResolvedRegistryDependency {
+                path: canonicalize_or_self(&unpacked),
+                checksum: None,
+            });
+        }
+    }
+
+    let cache_dir = root
+        .join(".trust-lsp")
+        .join("deps")
+        .join("registry")
+        .join(format!(
+            "{}-{}-{}",
+            sanitize_for_path(dependency_name),
+            sanitize_for_path(version),
+            stable_hash_hex(registry)
+        ));
+    let checksum_path = cache_dir.with_extension("checksum");
+
+    if !cache_dir.is_dir() {
+        if build.dependencies_offline && local_base.is_none() {
+            return Err(issue(
+                "L007",
+                format!(
+                    "Dependency '{dependency_name}' is not available in offline mode (missing cache at {})",
+                    cache_dir.display()
+                ),
+            ));
+        }
+        let archive =
+            fetch_registry_archive(local_base.as_deref(), registry, dependency_name, version)
+                .map_err(|message| {
+                    issue(
+                        "L001",
+                        format!("Dependency '{dependency_name}' registry fetch failed: {message}"),
+                    )
+                })?;
+        let checksum = archive_checksum(&archive);
+        if let Some(expected) = locked_checksum.as_deref() {
+            if expected != checksum {
+                return Err(issue(
+                    "L006",
+                    format!(
+                        "Dependency '{dependency_name}' archive checksum {checksum} does not match lock entry {expected}"
+                    ),
+                ));
+            }
+        }
+        unpack_registry_archive(&archive, &cache_dir).map_err(|message| {
+            issue(
+                "L001",
+                format!("Dependency '{dependency_name}' archive extraction failed: {message}"),
+            )
+        })?;
+        let _ = std::fs::write(&checksum_path, &checksum);
+        return Ok(ResolvedRegistryDependency {
+            path: cache_dir,
+            checksum: Some(checksum),
+        });
+    }
+
+    let cached_checksum = std::fs::read_to_string(&checksum_path)
+        .ok()
+        .map(|value| value.trim().to_string());
+    if let (Some(expected), Some(actual)) = (locked_checksum.as_deref(), cached_checksum.as_deref())
+    {
+        if expected != actual {
+            return Err(issue(
+                "L006",
+                format!(
+                    "Dependency '{dependency_name}' cached archive checksum {actual} does not match lock entry {expected}"
+                ),
+            ));
+        }
+    }
+    Ok(ResolvedRegistryDependency {
+        path: cache_dir,
+        checksum: cached_checksum.or(locked_checksum),
+    })
+}
+
+fn registry_local_base(registry: &str) -> Option<PathBuf> {
+    if let Some(path) = registry.strip_prefix("file://") {
+        return Some(PathBuf::from(path));
+    }
+    if registry.contains("://") {
+        None
+    } else {
+        Some(PathBuf::from(registry))
+    }
+}
+
+fn validate_registry_source_policy(url: &str, policy: &DependencyPolicy) -> Result<(), String> {
+    let authority = if let Some(authority) = url.strip_prefix("https://") {
+        authority
+    } else if let Some(authority) = url.strip_prefix("http://") {
+        if !policy.allow_http {
+            return Err("HTTP registries are disabled".to_string());
+        }
+        authority
+    } else {
+        return Err("unsupported registry scheme".to_string());
+    };
+    let host =
+        extract_git_host(authority).ok_or_else(|| "failed to parse registry host".to_string())?;
+    if policy.allowed_registry_hosts.is_empty() {
+        return Ok(());
+    }
+    let host_lower = host.to_ascii_lowercase();
+    if policy.allowed_registry_hosts.iter().any(|allowed| {
+        host_lower == *allowed || host_lower.ends_with(format!(".{allowed}").as_str())
+    }) {
+        Ok(())
+    } else {
+        Err(format!(
+            "host '{host}' is not in dependency_policy.allowed_registry_hosts"
+        ))
+    }
+}
+
+fn fetch_registry_archive(
+    local_base: Option<&Path>,
+    registry: &str,
+    name: &str,
+    version: &str,
+) -> Result<Vec<u8>, String> {
+    if let Some(base) = local_base {
+        let path = base.join(name).join(format!("{version}.tar.gz"));
+        return std::fs::read(&path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()));
+    }
+    let url = format!("{registry}/{name}/{version}.tar.gz");
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", url.as_str()])
+        .output()
+        .map_err(|err| format!("failed to execute curl: {err}"))?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(format!(
+            "GET {url}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+fn unpack_registry_archive(archive: &[u8], dest: &Path) -> Result<(), String> {
+    let staging = dest.with_extension("partial");
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(&staging)
+        .map_err(|err| format!("failed to create {}: {err}", staging.display()))?;
+    let archive_path = staging.join("package.tar.gz");
+    std::fs::write(&archive_path, archive)
+        .map_err(|err| format!("failed to write {}: {err}", archive_path.display()))?;
+    let output = Command::new("tar")
+        .arg("-xzf")
+        .arg(&archive_path)
+        .arg("-C")
+        .arg(&staging)
+        .output()
+        .map_err(|err| format!("failed to execute tar: {err}"))?;
+    let _ = std::fs::remove_file(&archive_path);
+    if !output.status.success() {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    std::fs::rename(&staging, dest)
+        .map_err(|err| format!("failed to move package into {}: {err}", dest.display()))
+}
+
+/// SHA-256 digest of a package archive, as recorded in lock entries.
+fn archive_checksum(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    let hex: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("sha256:{hex}")
+}
+
 fn validate_git_source_policy(url: &str, policy: &DependencyPolicy) -> Result<(), String> {
     let trimmed = url.trim();
     if trimmed.is_empty() {
@@ -1785,6 +2439,7 @@ max_files = 25
 max_ms = 100
 cache = false
 cache_dir = ".trust-lsp/custom-cache"
+cache_max_mb = 32
 memory_budget_mb = 64
 evict_to_percent = 75
 throttle_idle_ms = 2
@@ -1841,6 +2496,8 @@ docs = ["docs/vendor.md"]
             .cache_dir
             .as_ref()
             .is_some_and(|dir| dir.ends_with("custom-cache")));
+        assert_eq!(config.indexing.cache_max_mb, Some(32));
+        assert_eq!(config.index_cache_max_bytes(), Some(32 * 1024 * 1024));
         assert_eq!(config.indexing.memory_budget_mb, Some(64));
         assert_eq!(config.indexing.evict_to_percent, 75);
         assert_eq!(config.indexing.throttle_idle_ms, 2);
@@ -1916,6 +2573,139 @@ vendor_profile = "siemens"
         fs::remove_dir_all(root).ok();
     }
 
+    #[test]
+    fn loads_type_check_strictness() {
+        let root = temp_dir("trustlsp-config-type-check");
+        let config_path = root.join("trust-lsp.toml");
+        fs::write(
+            &config_path,
+            r#"
+[diagnostics]
+type_check = "strict"
+severity_overrides = { W015 = "error" }
+"#,
+        )
+        .expect("write config");
+
+        let config = ProjectConfig::load(&root);
+        assert_eq!(config.diagnostics.type_check, TypeCheckLevel::Strict);
+        assert_eq!(
+            config.diagnostics.severity_overrides.get("W015"),
+            Some(&DiagnosticSeverity::ERROR)
+        );
+
+        fs::write(&config_path, "[diagnostics]\ntype_check = \"lenient\"\n").expect("write config");
+        let config = ProjectConfig::load(&root);
+        assert_eq!(config.diagnostics.type_check, TypeCheckLevel::Standard);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn loads_lint_rules_and_options() {
+        let root = temp_dir("trustlsp-config-lint");
+        let config = ProjectConfig::from_contents(
+            &root,
+            None,
+            r#"
+[lint]
+max_nesting_depth = 3
+forbidden_functions = ["SEL"]
+allowed_numbers = ["0", "1", "100"]
+
+[lint.rules]
+nesting_depth = "error"
+forbidden-functions = "warning"
+magic_numbers = "off"
+unknown_rule = "error"
+
+[lint.naming]
+variables = "camelCase"
+constants = "UPPER_CASE"
+"#,
+        );
+        let lint = &config.lint;
+        assert_eq!(
+            lint.rules.get(&LintRule::NestingDepth),
+            Some(&DiagnosticSeverity::ERROR)
+        );
+        assert_eq!(
+            lint.rules.get(&LintRule::ForbiddenFunctions),
+            Some(&DiagnosticSeverity::WARNING)
+        );
+        assert!(!lint.rules.contains_key(&LintRule::MagicNumbers));
+        let options = lint.lint_options();
+        assert_eq!(
+            options.rules,
+            vec![LintRule::NestingDepth, LintRule::ForbiddenFunctions]
+        );
+        assert_eq!(options.max_nesting_depth, 3);
+        assert_eq!(options.allowed_numbers.len(), 3);
+        assert_eq!(options.naming.variables, Some(NamingStyle::CamelCase));
+        assert_eq!(options.naming.constants, Some(NamingStyle::UpperSnakeCase));
+        assert_eq!(options.naming.pous, None);
+
+        let preset = ProjectConfig::from_contents(&root, None, "[lint]\npreset = \"misra\"\n");
+        assert_eq!(preset.lint.rules.len(), LintRule::ALL.len());
+        assert_eq!(
+            preset.lint.rules.get(&LintRule::LoopExit),
+            Some(&DiagnosticSeverity::ERROR)
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn loads_format_style_settings() {
+        let root = temp_dir("trustlsp-config-format");
+        let config_path = root.join("trust-lsp.toml");
+        fs::write(
+            &config_path,
+            r#"
+[format]
+profile = "codesys"
+keyword_case = "pascal"
+var_block_indent = "flush"
+align_var_assignments = false
+max_line_length = 100
+"#,
+        )
+        .expect("write config");
+
+        let config = ProjectConfig::load(&root);
+        assert_eq!(config.format.profile.as_deref(), Some("codesys"));
+        assert_eq!(config.format.keyword_case.as_deref(), Some("pascal"));
+        assert_eq!(config.format.var_block_indent.as_deref(), Some("flush"));
+        assert_eq!(config.format.align_var_assignments, Some(false));
+        assert_eq!(config.format.max_line_length, Some(100));
+        assert!(config.format.align_assignments.is_none());
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn loads_doc_bundle_paths() {
+        let root = temp_dir("trustlsp-config-doc-bundles");
+        let config_path = root.join("trust-lsp.toml");
+        fs::write(
+            &config_path,
+            r#"
+[project]
+vendor_profile = "siemens"
+doc_bundles = ["docs/bundle", "docs/extra.md"]
+"#,
+        )
+        .expect("write config");
+
+        let config = ProjectConfig::load(&root);
+        assert_eq!(
+            config.doc_bundles,
+            vec![root.join("docs/bundle"), root.join("docs/extra.md")]
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+
     #[test]
     fn mitsubishi_vendor_profile_keeps_default_diagnostics_enabled() {
         let root = temp_dir("trustlsp-config-diagnostics-mitsubishi");
@@ -2180,4 +2970,144 @@ Vendor = { git = "https://github.com/example/vendor.git", rev = "deadbeef" }
 
         fs::remove_dir_all(root).ok();
     }
+
+    fn write_registry_package(dir: &Path, version: &str) {
+        fs::create_dir_all(dir.join("src")).expect("create registry package");
+        fs::write(
+            dir.join("trust-lsp.toml"),
+            format!("[package]\nversion = \"{version}\"\n"),
+        )
+        .expect("write package manifest");
+        fs::write(
+            dir.join("src/Lib.st"),
+            "FUNCTION RegistryFn : INT\nRegistryFn := 1;\nEND_FUNCTION\n",
+        )
+        .expect("write package source");
+    }
+
+    #[test]
+    fn resolves_registry_dependencies_from_local_registry() {
+        let root = temp_dir("trustlsp-config-registry-local");
+        write_registry_package(&root.join("registry/Utils/1.2.0"), "1.2.0");
+        fs::write(
+            root.join("trust-lsp.toml"),
+            r#"
+[registries]
+local = "registry"
+
+[dependencies]
+Utils = { registry = "local", version = "1.2.0" }
+Missing = { registry = "local", version = "9.9.9" }
+"#,
+        )
+        .expect("write registry config");
+
+        let config = ProjectConfig::load(&root);
+        let utils = config
+            .libraries
+            .iter()
+            .find(|lib| lib.name == "Utils")
+            .expect("Utils library");
+        assert_eq!(utils.version.as_deref(), Some("1.2.0"));
+        assert!(config
+            .dependency_resolution_issues
+            .iter()
+            .any(|issue| issue.code == "L001" && issue.dependency == "Missing"));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn registry_archives_are_cached_and_locked_with_checksum() {
+        let root = temp_dir("trustlsp-config-registry-archive");
+        let staging = root.join("staging");
+        write_registry_package(&staging, "2.0.0");
+        let archive_dir = root.join("registry/Motion");
+        fs::create_dir_all(&archive_dir).expect("create archive dir");
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(archive_dir.join("2.0.0.tar.gz"))
+            .arg("-C")
+            .arg(&staging)
+            .arg(".")
+            .status();
+        if !status.is_ok_and(|status| status.success()) {
+            fs::remove_dir_all(root).ok();
+            return;
+        }
+        fs::write(
+            root.join("trust-lsp.toml"),
+            r#"
+[dependencies]
+Motion = { registry = "registry", version = "2.0.0" }
+"#,
+        )
+        .expect("write registry config");
+
+        let config = ProjectConfig::load(&root);
+        assert!(
+            config.dependency_resolution_issues.is_empty(),
+            "{:?}",
+            config.dependency_resolution_issues
+        );
+        let motion = config
+            .libraries
+            .iter()
+            .find(|lib| lib.name == "Motion")
+            .expect("Motion library");
+        assert!(motion
+            .path
+            .to_string_lossy()
+            .replace('\\', "/")
+            .contains(".trust-lsp/deps/registry/"));
+        let lock = fs::read_to_string(root.join("trust-lsp.lock")).expect("read lockfile");
+        assert!(lock.contains("source = \"registry\""));
+        assert!(lock.contains("checksum = \"sha256:"));
+
+        fs::write(
+            root.join("trust-lsp.toml"),
+            r#"
+[build]
+dependencies_offline = true
+
+[dependencies]
+Motion = { registry = "https://packages.example.com", version = "2.0.0" }
+"#,
+        )
+        .expect("write offline config");
+        let offline = ProjectConfig::load(&root);
+        assert!(offline
+            .dependency_resolution_issues
+            .iter()
+            .any(|issue| issue.code == "L007" && issue.dependency == "Motion"));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn rejects_registry_dependencies_without_version_or_over_http() {
+        let root = temp_dir("trustlsp-config-registry-policy");
+        fs::write(
+            root.join("trust-lsp.toml"),
+            r#"
+[dependencies]
+Unpinned = { registry = "https://packages.example.com" }
+Plain = { registry = "http://packages.example.com", version = "1.0.0" }
+"#,
+        )
+        .expect("write registry policy config");
+
+        let config = ProjectConfig::load(&root);
+        for name in ["Unpinned", "Plain"] {
+            assert!(
+                config
+                    .dependency_resolution_issues
+                    .iter()
+                    .any(|issue| issue.code == "L005" && issue.dependency == name),
+                "expected L005 for {name}"
+            );
+        }
+
+        fs::remove_dir_all(root).ok();
+    }
 }
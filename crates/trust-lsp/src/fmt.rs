@@ -0,0 +1,371 @@
+//! Headless `trust-lsp fmt` command.
+//!
+//! Formats every project source file with the editor formatter and the
+//! `[format]` settings from trust-lsp.toml.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::ProjectConfig;
+use crate::handlers::{format_source, project_source_files};
+use crate::state::{path_to_uri, ServerState};
+
+const USAGE: &str = "\
+Usage: trust-lsp fmt [--project <dir>] [--check]
+
+Formats all .st/.pou files in a project like the editor does.
+With --check nothing is written; a diff is printed and the exit status is 1
+when any file is not formatted.";
+
+/// Lines of unchanged context around each diff hunk.
+const DIFF_CONTEXT: usize = 3;
+/// Above this many line pairs the differing region is shown as one replacement.
+const DIFF_MAX_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone)]
+struct FmtOptions {
+    project: PathBuf,
+    check: bool,
+}
+
+/// A file whose formatted text differs from the text on disk.
+#[derive(Debug, Clone)]
+struct FmtChange {
+    path: PathBuf,
+    original: String,
+    formatted: String,
+}
+
+/// Runs `trust-lsp fmt` with the arguments after the subcommand name and
+/// returns the process exit code.
+pub fn run_fmt(args: impl IntoIterator<Item = String>) -> i32 {
+    let options = match parse_args(args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return 0;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            return 2;
+        }
+    };
+    match fmt_project(&options) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            2
+        }
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> anyhow::Result<Option<FmtOptions>> {
+    let mut options = FmtOptions {
+        project: PathBuf::from("."),
+        check: false,
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--check" => options.check = true,
+            "--project" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("--project requires a value"))?;
+                options.project = PathBuf::from(value);
+            }
+            other => anyhow::bail!("unexpected argument '{other}'"),
+        }
+    }
+    Ok(Some(options))
+}
+
+fn fmt_project(options: &FmtOptions) -> anyhow::Result<i32> {
+    let root = options
+        .project
+        .canonicalize()
+        .map_err(|err| anyhow::anyhow!("project {} not found: {err}", options.project.display()))?;
+    let (changes, total) = collect_changes(&root)?;
+    if options.check {
+        for change in &changes {
+            print!("{}", unified_diff(&relative(&root, &change.path), change));
+        }
+        if changes.is_empty() {
+            println!("{total} file(s) already formatted");
+            return Ok(0);
+        }
+        eprintln!(
+            "{} of {total} file(s) need formatting (run `trust-lsp fmt` to fix)",
+            changes.len()
+        );
+        return Ok(1);
+    }
+    for change in &changes {
+        std::fs::write(&change.path, &change.formatted)
+            .map_err(|err| anyhow::anyhow!("failed to write {}: {err}", change.path.display()))?;
+        println!("formatted {}", relative(&root, &change.path));
+    }
+    println!(
+        "{} file(s) formatted, {} unchanged",
+        changes.len(),
+        total - changes.len()
+    );
+    Ok(0)
+}
+
+fn collect_changes(root: &Path) -> anyhow::Result<(Vec<FmtChange>, usize)> {
+    let folder = path_to_uri(root)
+        .ok_or_else(|| anyhow::anyhow!("invalid project path {}", root.display()))?;
+    let config = ProjectConfig::load(root);
+    let files = project_source_files(&config);
+    let state = ServerState::new();
+    state.set_workspace_folders(vec![folder.clone()]);
+    state.set_workspace_config(folder, config);
+
+    let mut changes = Vec::new();
+    for path in &files {
+        let original = std::fs::read_to_string(path)
+            .map_err(|err| anyhow::anyhow!("failed to read {}: {err}", path.display()))?;
+        let Some(uri) = path_to_uri(path) else {
+            continue;
+        };
+        let formatted = format_source(&state, &uri, &original);
+        if formatted != original {
+            changes.push(FmtChange {
+                path: path.clone(),
+                original,
+                formatted,
+            });
+        }
+    }
+    Ok((changes, files.len()))
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffOp {
+    Keep,
+    Remove,
+    Add,
+}
+
+/// Renders a unified diff between the original and formatted text.
+fn unified_diff(path: &str, change: &FmtChange) -> String {
+    let old: Vec<&str> = change.original.lines().collect();
+    let new: Vec<&str> = change.formatted.lines().collect();
+    let ops = diff_lines(&old, &new);
+
+    let mut out = format!("--- a/{path}\n+++ b/{path}\n");
+    // Walk the edit script, grouping changes that are close enough to share context.
+    let mut index = 0;
+    while index < ops.len() {
+        let Some(first_change) = ops[index..]
+            .iter()
+            .position(|op| *op != DiffOp::Keep)
+            .map(|offset| index + offset)
+        else {
+            break;
+        };
+        let start = first_change.saturating_sub(DIFF_CONTEXT);
+        let mut end = first_change;
+        let mut keep_run = 0;
+        while end < ops.len() {
+            if ops[end] == DiffOp::Keep {
+                keep_run += 1;
+                if keep_run > DIFF_CONTEXT * 2 {
+                    break;
+                }
+            } else {
+                keep_run = 0;
+            }
+            end += 1;
+        }
+        // The keep that stopped the scan is not part of the hunk.
+        let trailing = if end < ops.len() {
+            keep_run - 1
+        } else {
+            keep_run
+        };
+        let end = end - trailing.saturating_sub(DIFF_CONTEXT);
+
+        let (old_start, new_start) = positions_before(&ops, start);
+        let mut old_line = old_start;
+        let mut new_line = new_start;
+        let mut body = String::new();
+        for op in &ops[start..end] {
+            match op {
+                DiffOp::Keep => {
+                    body.push_str(&format!(" {}\n", old[old_line]));
+                    old_line += 1;
+                    new_line += 1;
+                }
+                DiffOp::Remove => {
+                    body.push_str(&format!("-{}\n", old[old_line]));
+                    old_line += 1;
+                }
+                DiffOp::Add => {
+                    body.push_str(&format!("+{}\n", new[new_line]));
+                    new_line += 1;
+                }
+            }
+        }
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n{body}",
+            hunk_range(old_start, old_line - old_start),
+            hunk_range(new_start, new_line - new_start)
+        ));
+        index = end;
+    }
+    if !ops.contains(&DiffOp::Remove) && !ops.contains(&DiffOp::Add) {
+        out.push_str("(only the line endings differ)\n");
+    }
+    out
+}
+
+fn positions_before(ops: &[DiffOp], upto: usize) -> (usize, usize) {
+    ops[..upto].iter().fold((0, 0), |(old, new), op| match op {
+        DiffOp::Keep => (old + 1, new + 1),
+        DiffOp::Remove => (old + 1, new),
+        DiffOp::Add => (old, new + 1),
+    })
+}
+
+fn hunk_range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{len}", start + 1),
+    }
+}
+
+/// Line-level edit script: common prefix/suffix, then an LCS over the rest.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<DiffOp> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(left, right)| left == right)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops = vec![DiffOp::Keep; prefix];
+    if old_mid.len().saturating_mul(new_mid.len()) > DIFF_MAX_CELLS {
+        ops.extend(std::iter::repeat_n(DiffOp::Remove, old_mid.len()));
+        ops.extend(std::iter::repeat_n(DiffOp::Add, new_mid.len()));
+    } else {
+        let width = new_mid.len() + 1;
+        let mut lcs = vec![0u32; (old_mid.len() + 1) * width];
+        for i in (0..old_mid.len()).rev() {
+            for j in (0..new_mid.len()).rev() {
+                lcs[i * width + j] = if old_mid[i] == new_mid[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < old_mid.len() && j < new_mid.len() {
+            if old_mid[i] == new_mid[j] {
+                ops.push(DiffOp::Keep);
+                i += 1;
+                j += 1;
+            } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+                ops.push(DiffOp::Remove);
+                i += 1;
+            } else {
+                ops.push(DiffOp::Add);
+                j += 1;
+            }
+        }
+        ops.extend(std::iter::repeat_n(DiffOp::Remove, old_mid.len() - i));
+        ops.extend(std::iter::repeat_n(DiffOp::Add, new_mid.len() - j));
+    }
+    ops.extend(std::iter::repeat_n(DiffOp::Keep, suffix));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time before UNIX_EPOCH")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("{prefix}-{stamp}"));
+        std::fs::create_dir_all(path.join("src")).expect("create temp dir");
+        path
+    }
+
+    #[test]
+    fn fmt_uses_project_settings_and_reports_diff() {
+        let root = temp_dir("trust-lsp-fmt");
+        std::fs::write(
+            root.join("trust-lsp.toml"),
+            "[format]\nindent_width = 2\nkeyword_case = \"upper\"\n",
+        )
+        .expect("write config");
+        let source = "program Main\nvar\nx : int;\nend_var\nx := 1;\nend_program\n";
+        std::fs::write(root.join("src/main.st"), source).expect("write source");
+        let root = root.canonicalize().expect("canonical root");
+
+        let (changes, total) = collect_changes(&root).expect("collect changes");
+        assert_eq!(total, 1);
+        assert_eq!(changes.len(), 1);
+        let formatted = &changes[0].formatted;
+        assert!(formatted.starts_with("PROGRAM Main\n"), "{formatted}");
+        assert!(formatted.contains("\n  VAR\n    x"), "{formatted}");
+        assert!(formatted.contains("INT;"), "{formatted}");
+
+        let diff = unified_diff("src/main.st", &changes[0]);
+        assert!(diff.starts_with("--- a/src/main.st\n+++ b/src/main.st\n@@ -1"));
+        assert!(diff.contains("\n-program Main\n"), "{diff}");
+        assert!(diff.contains("\n+PROGRAM Main\n"), "{diff}");
+
+        std::fs::write(root.join("src/main.st"), formatted).expect("write formatted");
+        let (changes, _) = collect_changes(&root).expect("collect changes");
+        assert!(changes.is_empty(), "formatting should be idempotent");
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn diff_groups_distant_changes_into_separate_hunks() {
+        let original = (1..=20)
+            .map(|line| format!("line {line}\n"))
+            .collect::<String>();
+        let formatted = original
+            .replace("line 2\n", "LINE 2\n")
+            .replace("line 18\n", "LINE 18\n");
+        let change = FmtChange {
+            path: PathBuf::from("main.st"),
+            original,
+            formatted,
+        };
+        let diff = unified_diff("main.st", &change);
+        assert!(
+            diff.contains("@@ -1,5 +1,5 @@\n line 1\n-line 2\n+LINE 2\n"),
+            "{diff}"
+        );
+        assert!(diff.contains("@@ -15,6 +15,6 @@\n line 15\n"), "{diff}");
+        assert!(
+            diff.ends_with("-line 18\n+LINE 18\n line 19\n line 20\n"),
+            "{diff}"
+        );
+    }
+}
@@ -12,17 +12,35 @@ use tower_lsp::lsp_types::{
 use tower_lsp::Client;
 
 use text_size::{TextRange, TextSize};
+use trust_hir::db::FileId;
+use trust_ide::inline_values::qualify_runtime_expression;
 use trust_ide::refactor::parse_namespace_path;
 use trust_ide::rename::{RenameResult, TextEdit as IdeTextEdit};
+use trust_ide::runtime_declaration_targets;
+use trust_ide::{
+    analyze_cross_references, analyze_project_graph, analyze_wcet, classify_references,
+    find_references, trace_drivers, CrossReferenceOptions, DataFlowNode, DataFlowOptions,
+    DeadCodeItem, FindReferencesOptions, Graph, ProjectGraphOptions, ReferenceAccess, StackFrame,
+    WcetOptions, WcetProgram,
+};
 use trust_runtime::bundle_builder::resolve_sources_root;
 use trust_runtime::debug::DebugSnapshot;
 use trust_runtime::harness::{CompileSession, SourceFile as HarnessSourceFile};
 use trust_runtime::hmi::{self as runtime_hmi, HmiSourceRef};
+use trust_runtime::metrics::measured_timings_from_task_stats;
 use trust_syntax::parser::parse;
 use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
 
 use crate::handlers::context::ServerContext;
+use crate::handlers::diagnostics::{
+    dead_code_message, dead_code_report_for_config, recursive_call_message,
+    stack_usage_report_for_config,
+};
+use crate::handlers::features::{live_values, runtime_control_for_uri};
 use crate::handlers::lsp_utils::{offset_to_position, position_to_offset};
+use crate::handlers::runtime_values::{
+    evaluate_runtime_expression, fetch_runtime_forced, fetch_runtime_task_stats, set_runtime_force,
+};
 use crate::library_graph::build_library_graph;
 use crate::state::{path_to_uri, uri_to_path, ServerState};
 
@@ -30,6 +48,23 @@ pub const MOVE_NAMESPACE_COMMAND: &str = "trust-lsp.moveNamespace";
 pub const PROJECT_INFO_COMMAND: &str = "trust-lsp.projectInfo";
 pub const HMI_INIT_COMMAND: &str = "trust-lsp.hmiInit";
 pub const HMI_BINDINGS_COMMAND: &str = "trust-lsp.hmiBindings";
+pub const DEAD_CODE_COMMAND: &str = "trust-lsp.deadCode";
+pub const STACK_USAGE_COMMAND: &str = "trust-lsp.stackUsage";
+pub const WCET_COMMAND: &str = "trust-lsp.wcet";
+pub const PROJECT_GRAPH_COMMAND: &str = "trust-lsp.projectGraph";
+pub const EVALUATE_SELECTION_COMMAND: &str = "trust-lsp.evaluateSelection";
+pub const LIVE_VALUES_COMMAND: &str = "trust-lsp.liveValues";
+pub const FORCE_VARIABLE_COMMAND: &str = "trust-lsp.forceVariable";
+pub const UNFORCE_VARIABLE_COMMAND: &str = "trust-lsp.unforceVariable";
+pub const FORCED_VARIABLES_COMMAND: &str = "trust-lsp.forcedVariables";
+pub const REFERENCE_ACCESS_COMMAND: &str = "trust-lsp.referenceAccess";
+pub const TRACE_DRIVERS_COMMAND: &str = "trust-lsp.traceDrivers";
+pub const CROSS_REFERENCE_COMMAND: &str = "trust-lsp.crossReference";
+
+/// Columns of the cross-reference CSV and HTML exports.
+const CROSS_REFERENCE_COLUMNS: [&str; 9] = [
+    "Name", "Kind", "Type", "Address", "Use", "POU", "Tasks", "File", "Line",
+];
 
 #[derive(Debug, Deserialize)]
 pub struct MoveNamespaceCommandArgs {
@@ -48,6 +83,113 @@ struct ProjectInfoCommandArgs {
     text_document: Option<TextDocumentIdentifier>,
 }
 
+#[derive(Debug, Deserialize, Default)]
+struct DeadCodeCommandArgs {
+    #[serde(default)]
+    root_uri: Option<Url>,
+    #[serde(default)]
+    text_document: Option<TextDocumentIdentifier>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct StackUsageCommandArgs {
+    #[serde(default)]
+    root_uri: Option<Url>,
+    #[serde(default)]
+    text_document: Option<TextDocumentIdentifier>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct ProjectGraphCommandArgs {
+    #[serde(default)]
+    root_uri: Option<Url>,
+    #[serde(default)]
+    text_document: Option<TextDocumentIdentifier>,
+    /// Restrict both graphs to what the program instances of this task reach.
+    #[serde(default)]
+    entry_task: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct CrossReferenceCommandArgs {
+    #[serde(default)]
+    root_uri: Option<Url>,
+    #[serde(default)]
+    text_document: Option<TextDocumentIdentifier>,
+    /// Keep entries whose name contains this text (case-insensitive).
+    #[serde(default)]
+    filter: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct WcetCommandArgs {
+    #[serde(default)]
+    root_uri: Option<Url>,
+    #[serde(default)]
+    text_document: Option<TextDocumentIdentifier>,
+    /// `tasks.stats` result to use instead of querying the runtime.
+    #[serde(default)]
+    profile: Option<Value>,
+    #[serde(default)]
+    statement_ns: Option<f64>,
+    #[serde(default)]
+    loop_iterations: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReferenceAccessCommandArgs {
+    text_document: TextDocumentIdentifier,
+    position: Position,
+    #[serde(default)]
+    include_declaration: bool,
+    #[serde(default)]
+    access: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EvaluateSelectionCommandArgs {
+    text_document: TextDocumentIdentifier,
+    /// Selected expression; its names are qualified with the file's namespaces.
+    #[serde(default)]
+    range: Option<Range>,
+    /// Expression text sent as-is when no range is given.
+    #[serde(default)]
+    expression: Option<String>,
+    /// Paused stack frame to evaluate in, so POU locals resolve.
+    #[serde(default)]
+    frame_id: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LiveValuesCommandArgs {
+    text_document: TextDocumentIdentifier,
+    /// Visible range to decorate.
+    range: Range,
+}
+
+#[derive(Debug, Deserialize)]
+struct ForceVariableCommandArgs {
+    text_document: TextDocumentIdentifier,
+    /// Position inside the variable's declared name.
+    position: Position,
+    /// Value to force; omitted for `trust-lsp.unforceVariable`.
+    #[serde(default)]
+    value: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ForcedVariablesCommandArgs {
+    text_document: TextDocumentIdentifier,
+}
+
+#[derive(Debug, Deserialize)]
+struct TraceDriversCommandArgs {
+    text_document: TextDocumentIdentifier,
+    position: Position,
+    #[serde(default)]
+    max_depth: Option<usize>,
+}
+
 #[derive(Debug, Deserialize, Default)]
 struct HmiInitCommandArgs {
     #[serde(default)]
@@ -81,6 +223,18 @@ pub async fn execute_command(
         PROJECT_INFO_COMMAND => project_info_value(state, params.arguments),
         HMI_INIT_COMMAND => hmi_init_value(state, params.arguments),
         HMI_BINDINGS_COMMAND => hmi_bindings_value(state, params.arguments),
+        DEAD_CODE_COMMAND => dead_code_value(state, params.arguments),
+        STACK_USAGE_COMMAND => stack_usage_value(state, params.arguments),
+        WCET_COMMAND => wcet_value(state, params.arguments),
+        PROJECT_GRAPH_COMMAND => project_graph_value(state, params.arguments),
+        EVALUATE_SELECTION_COMMAND => evaluate_selection_value(state, params.arguments),
+        LIVE_VALUES_COMMAND => live_values_value(state, params.arguments),
+        FORCE_VARIABLE_COMMAND => force_variable_value(state, params.arguments, true),
+        UNFORCE_VARIABLE_COMMAND => force_variable_value(state, params.arguments, false),
+        FORCED_VARIABLES_COMMAND => forced_variables_value(state, params.arguments),
+        REFERENCE_ACCESS_COMMAND => reference_access_value(state, params.arguments),
+        TRACE_DRIVERS_COMMAND => trace_drivers_value(state, params.arguments),
+        CROSS_REFERENCE_COMMAND => cross_reference_value(state, params.arguments),
         _ => None,
     }
 }
@@ -176,6 +330,1110 @@ fn project_info_for_config(root: &Url, config: &crate::config::ProjectConfig) ->
     })
 }
 
+pub(crate) fn dead_code_value(state: &ServerState, args: Vec<Value>) -> Option<Value> {
+    let parsed = match args.len() {
+        0 => DeadCodeCommandArgs::default(),
+        1 => match serde_json::from_value(args.into_iter().next().unwrap_or(Value::Null)) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return Some(json!({
+                    "ok": false,
+                    "error": format!("invalid trust-lsp.deadCode arguments: {error}"),
+                }));
+            }
+        },
+        _ => {
+            return Some(json!({
+                "ok": false,
+                "error": "trust-lsp.deadCode expects zero or one argument object",
+            }));
+        }
+    };
+
+    let mut configs = state.workspace_configs();
+    if let Some(root_uri) = parsed.root_uri {
+        configs.retain(|(root, _)| root == &root_uri);
+    } else if let Some(text_document) = parsed.text_document {
+        if let Some(config) = state.workspace_config_for_uri(&text_document.uri) {
+            let root_uri = path_to_uri(&config.root).unwrap_or(text_document.uri.clone());
+            configs = vec![(root_uri, config)];
+        }
+    }
+
+    let mut markdown = String::from("# Dead code report\n");
+    let mut projects = Vec::new();
+    for (root, config) in configs {
+        let Some(report) = dead_code_report_for_config(state, &config) else {
+            continue;
+        };
+        let entry_points: Vec<Value> = report
+            .entry_points
+            .iter()
+            .map(|item| dead_code_item_value(state, item))
+            .collect();
+        let items: Vec<Value> = report
+            .items
+            .iter()
+            .map(|item| dead_code_item_value(state, item))
+            .collect();
+
+        markdown.push_str(&format!("\n## {root}\n\n"));
+        if report.implicit_entry_points {
+            markdown.push_str(
+                "No CONFIGURATION found; every PROGRAM is treated as an entry point.\n\n",
+            );
+        }
+        if report.items.is_empty() {
+            markdown.push_str("No unreachable symbols found.\n");
+        }
+        for (item, value) in report.items.iter().zip(items.iter()) {
+            let location = value
+                .get("uri")
+                .and_then(Value::as_str)
+                .unwrap_or("<unknown>");
+            let line = value
+                .pointer("/range/start/line")
+                .and_then(Value::as_u64)
+                .map(|line| line + 1)
+                .unwrap_or(0);
+            markdown.push_str(&format!(
+                "- {} ({location}:{line})\n",
+                dead_code_message(item)
+            ));
+        }
+
+        projects.push(json!({
+            "root": root.to_string(),
+            "implicitEntryPoints": report.implicit_entry_points,
+            "entryPoints": entry_points,
+            "items": items,
+        }));
+    }
+
+    Some(json!({
+        "ok": true,
+        "command": DEAD_CODE_COMMAND,
+        "projects": projects,
+        "markdown": markdown,
+    }))
+}
+
+fn dead_code_item_value(state: &ServerState, item: &DeadCodeItem) -> Value {
+    let (uri, range) = file_location_value(state, item.file_id, item.range);
+    json!({
+        "name": item.name.as_str(),
+        "container": item.container.as_ref().map(|name| name.as_str()),
+        "category": item.category.label(),
+        "uri": uri,
+        "range": range,
+    })
+}
+
+pub(crate) fn stack_usage_value(state: &ServerState, args: Vec<Value>) -> Option<Value> {
+    let parsed = match args.len() {
+        0 => StackUsageCommandArgs::default(),
+        1 => match serde_json::from_value(args.into_iter().next().unwrap_or(Value::Null)) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return Some(json!({
+                    "ok": false,
+                    "error": format!("invalid trust-lsp.stackUsage arguments: {error}"),
+                }));
+            }
+        },
+        _ => {
+            return Some(json!({
+                "ok": false,
+                "error": "trust-lsp.stackUsage expects zero or one argument object",
+            }));
+        }
+    };
+
+    let mut configs = state.workspace_configs();
+    if let Some(root_uri) = parsed.root_uri {
+        configs.retain(|(root, _)| root == &root_uri);
+    } else if let Some(text_document) = parsed.text_document {
+        if let Some(config) = state.workspace_config_for_uri(&text_document.uri) {
+            let root_uri = path_to_uri(&config.root).unwrap_or(text_document.uri.clone());
+            configs = vec![(root_uri, config)];
+        }
+    }
+
+    let mut markdown = String::from("# Stack usage report\n");
+    let mut projects = Vec::new();
+    for (root, config) in configs {
+        let Some(report) = stack_usage_report_for_config(state, &config) else {
+            continue;
+        };
+
+        markdown.push_str(&format!("\n## {root}\n\n"));
+        if report.implicit_entry_points {
+            markdown.push_str(
+                "No program instance found; every PROGRAM is treated as an entry point.\n\n",
+            );
+        }
+        if !report.entries.is_empty() {
+            markdown.push_str("| Entry | Task | Max call depth | Stack (bytes) | Worst path |\n");
+            markdown.push_str("| --- | --- | --- | --- | --- |\n");
+        }
+        let mut entries = Vec::new();
+        for entry in &report.entries {
+            let (uri, range) = file_location_value(state, entry.file_id, entry.range);
+            let path: Vec<String> = entry
+                .worst_path
+                .iter()
+                .map(StackFrame::qualified_name)
+                .collect();
+            let (depth, bytes) = if entry.unbounded {
+                ("unbounded".to_string(), "unbounded".to_string())
+            } else {
+                (entry.max_depth.to_string(), entry.stack_bytes.to_string())
+            };
+            markdown.push_str(&format!(
+                "| {} | {} | {depth} | {bytes} | {} |\n",
+                entry.name,
+                entry.task.as_deref().unwrap_or("-"),
+                path.join(" -> ")
+            ));
+            entries.push(json!({
+                "name": entry.name.as_str(),
+                "task": entry.task.as_ref().map(|task| task.as_str()),
+                "uri": uri,
+                "range": range,
+                "maxDepth": entry.max_depth,
+                "stackBytes": entry.stack_bytes,
+                "unbounded": entry.unbounded,
+                "worstPath": entry
+                    .worst_path
+                    .iter()
+                    .map(|frame| stack_frame_value(state, frame))
+                    .collect::<Vec<_>>(),
+            }));
+        }
+
+        let mut recursive_calls = Vec::new();
+        if !report.recursive_calls.is_empty() {
+            markdown.push_str("\n### Recursion\n\n");
+        }
+        for call in &report.recursive_calls {
+            let (uri, range) = file_location_value(state, call.file_id, call.range);
+            let line = range.map(|range| range.start.line + 1).unwrap_or(0);
+            markdown.push_str(&format!(
+                "- {} ({}:{line})\n",
+                recursive_call_message(call),
+                uri.as_deref().unwrap_or("<unknown>")
+            ));
+            recursive_calls.push(json!({
+                "caller": call.caller.qualified_name(),
+                "callee": call.callee.qualified_name(),
+                "cycle": call.cycle,
+                "uri": uri,
+                "range": range,
+            }));
+        }
+
+        projects.push(json!({
+            "root": root.to_string(),
+            "implicitEntryPoints": report.implicit_entry_points,
+            "entries": entries,
+            "recursiveCalls": recursive_calls,
+        }));
+    }
+
+    Some(json!({
+        "ok": true,
+        "command": STACK_USAGE_COMMAND,
+        "projects": projects,
+        "markdown": markdown,
+    }))
+}
+
+fn stack_frame_value(state: &ServerState, frame: &StackFrame) -> Value {
+    let (uri, range) = file_location_value(state, frame.file_id, frame.range);
+    json!({
+        "name": frame.qualified_name(),
+        "frameBytes": frame.frame_bytes,
+        "uri": uri,
+        "range": range,
+    })
+}
+
+pub(crate) fn project_graph_value(state: &ServerState, args: Vec<Value>) -> Option<Value> {
+    let parsed = match args.len() {
+        0 => ProjectGraphCommandArgs::default(),
+        1 => match serde_json::from_value(args.into_iter().next().unwrap_or(Value::Null)) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return Some(json!({
+                    "ok": false,
+                    "error": format!("invalid trust-lsp.projectGraph arguments: {error}"),
+                }));
+            }
+        },
+        _ => {
+            return Some(json!({
+                "ok": false,
+                "error": "trust-lsp.projectGraph expects zero or one argument object",
+            }));
+        }
+    };
+
+    let mut configs = state.workspace_configs();
+    if let Some(root_uri) = parsed.root_uri {
+        configs.retain(|(root, _)| root == &root_uri);
+    } else if let Some(text_document) = parsed.text_document {
+        if let Some(config) = state.workspace_config_for_uri(&text_document.uri) {
+            let root_uri = path_to_uri(&config.root).unwrap_or(text_document.uri.clone());
+            configs = vec![(root_uri, config)];
+        }
+    }
+
+    let entry_task = parsed.entry_task.as_deref();
+    let mut projects = Vec::new();
+    for (root, config) in configs {
+        let files = state.file_ids_for_config(&config);
+        if files.is_empty() {
+            continue;
+        }
+        let graph = state.with_database(|db| {
+            analyze_project_graph(
+                db,
+                ProjectGraphOptions {
+                    files: Some(&files),
+                    entry_task,
+                },
+            )
+        });
+        if entry_task.is_some() && graph.entry_points.is_empty() {
+            continue;
+        }
+        projects.push(json!({
+            "root": root.to_string(),
+            "entryPoints": graph
+                .entry_points
+                .iter()
+                .map(|entry| {
+                    json!({
+                        "name": entry.name.as_str(),
+                        "task": entry.task.as_ref().map(|task| task.as_str()),
+                        "program": entry.program,
+                    })
+                })
+                .collect::<Vec<_>>(),
+            "callGraph": graph_value(state, &graph.calls),
+            "dependencyGraph": graph_value(state, &graph.dependencies),
+            "dot": {
+                "callGraph": graph.calls.to_dot("calls"),
+                "dependencyGraph": graph.dependencies.to_dot("dependencies"),
+            },
+        }));
+    }
+
+    if let (Some(task), true) = (entry_task, projects.is_empty()) {
+        return Some(json!({
+            "ok": false,
+            "error": format!("no program instance is attached to task '{task}'"),
+        }));
+    }
+
+    Some(json!({
+        "ok": true,
+        "command": PROJECT_GRAPH_COMMAND,
+        "entryTask": entry_task,
+        "projects": projects,
+    }))
+}
+
+fn graph_value(state: &ServerState, graph: &Graph) -> Value {
+    let nodes: Vec<Value> = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            let (uri, range) = file_location_value(state, node.file_id, node.range);
+            json!({
+                "name": node.name,
+                "kind": node.kind_label(),
+                "uri": uri,
+                "range": range,
+            })
+        })
+        .collect();
+    let edges: Vec<Value> = graph
+        .edges
+        .iter()
+        .map(|edge| {
+            json!({
+                "from": graph.nodes[edge.from].name,
+                "to": graph.nodes[edge.to].name,
+                "kind": edge.kind.label(),
+                "count": edge.count,
+            })
+        })
+        .collect();
+    json!({ "nodes": nodes, "edges": edges })
+}
+
+/// Lists every variable and direct address with its declaration and read/write
+/// locations, as JSON and as CSV and HTML for export.
+pub(crate) fn cross_reference_value(state: &ServerState, args: Vec<Value>) -> Option<Value> {
+    let parsed = match args.len() {
+        0 => CrossReferenceCommandArgs::default(),
+        1 => match serde_json::from_value(args.into_iter().next().unwrap_or(Value::Null)) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return Some(json!({
+                    "ok": false,
+                    "error": format!("invalid trust-lsp.crossReference arguments: {error}"),
+                }));
+            }
+        },
+        _ => {
+            return Some(json!({
+                "ok": false,
+                "error": "trust-lsp.crossReference expects zero or one argument object",
+            }));
+        }
+    };
+
+    let mut configs = state.workspace_configs();
+    if let Some(root_uri) = parsed.root_uri {
+        configs.retain(|(root, _)| root == &root_uri);
+    } else if let Some(text_document) = parsed.text_document {
+        if let Some(config) = state.workspace_config_for_uri(&text_document.uri) {
+            let root_uri = path_to_uri(&config.root).unwrap_or(text_document.uri.clone());
+            configs = vec![(root_uri, config)];
+        }
+    }
+
+    let filter = parsed
+        .filter
+        .map(|filter| filter.trim().to_ascii_lowercase())
+        .filter(|filter| !filter.is_empty());
+    let mut rows: Vec<[String; 9]> = Vec::new();
+    let mut projects = Vec::new();
+    for (root, config) in configs {
+        let files = state.file_ids_for_config(&config);
+        if files.is_empty() {
+            continue;
+        }
+        let report = state.with_database(|db| {
+            analyze_cross_references(
+                db,
+                CrossReferenceOptions {
+                    files: Some(&files),
+                },
+            )
+        });
+        let mut entries = Vec::new();
+        for entry in report.entries.iter().filter(|entry| {
+            filter
+                .as_ref()
+                .is_none_or(|filter| entry.name.to_ascii_lowercase().contains(filter))
+        }) {
+            let mut locations = Vec::new();
+            for location in &entry.locations {
+                let (uri, range) = file_location_value(state, location.file_id, location.range);
+                let file = uri
+                    .as_deref()
+                    .and_then(|uri| Url::parse(uri).ok())
+                    .and_then(|uri| uri_to_path(&uri))
+                    .map(|path| {
+                        path.strip_prefix(&config.root)
+                            .unwrap_or(&path)
+                            .display()
+                            .to_string()
+                    })
+                    .or_else(|| uri.clone())
+                    .unwrap_or_default();
+                rows.push([
+                    entry.name.clone(),
+                    entry.kind.label().to_string(),
+                    entry.type_name.as_deref().unwrap_or_default().to_string(),
+                    entry.address.as_deref().unwrap_or_default().to_string(),
+                    location.usage.label().to_string(),
+                    location.pou.clone().unwrap_or_default(),
+                    location.tasks.join(" "),
+                    file,
+                    range
+                        .map(|range| (range.start.line + 1).to_string())
+                        .unwrap_or_default(),
+                ]);
+                locations.push(json!({
+                    "use": location.usage.label(),
+                    "pou": location.pou,
+                    "tasks": location.tasks.iter().map(|task| task.as_str()).collect::<Vec<_>>(),
+                    "uri": uri,
+                    "range": range,
+                }));
+            }
+            entries.push(json!({
+                "name": entry.name,
+                "kind": entry.kind.label(),
+                "type": entry.type_name.as_ref().map(|name| name.as_str()),
+                "address": entry.address.as_ref().map(|address| address.as_str()),
+                "locations": locations,
+            }));
+        }
+        projects.push(json!({
+            "root": root.to_string(),
+            "entries": entries,
+        }));
+    }
+
+    Some(json!({
+        "ok": true,
+        "command": CROSS_REFERENCE_COMMAND,
+        "projects": projects,
+        "csv": cross_reference_csv(&rows),
+        "html": cross_reference_html(&rows),
+    }))
+}
+
+fn cross_reference_csv(rows: &[[String; 9]]) -> String {
+    let mut csv = String::new();
+    for row in
+        std::iter::once(CROSS_REFERENCE_COLUMNS.map(str::to_string)).chain(rows.iter().cloned())
+    {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|field| {
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.clone()
+                }
+            })
+            .collect();
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+fn cross_reference_html(rows: &[[String; 9]]) -> String {
+    let escape = |text: &str| {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    };
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Cross reference</title>\n</head>\n<body>\n<table>\n<thead>\n<tr>",
+    );
+    for column in CROSS_REFERENCE_COLUMNS {
+        html.push_str(&format!("<th>{column}</th>"));
+    }
+    html.push_str("</tr>\n</thead>\n<tbody>\n");
+    for row in rows {
+        html.push_str("<tr>");
+        for field in row {
+            html.push_str(&format!("<td>{}</td>", escape(field)));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+    html
+}
+
+pub(crate) fn evaluate_selection_value(state: &ServerState, args: Vec<Value>) -> Option<Value> {
+    if args.len() != 1 {
+        return Some(json!({
+            "ok": false,
+            "error": "trust-lsp.evaluateSelection expects one argument object",
+        }));
+    }
+    let parsed: EvaluateSelectionCommandArgs =
+        match serde_json::from_value(args.into_iter().next().unwrap_or(Value::Null)) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return Some(json!({
+                    "ok": false,
+                    "error": format!("invalid trust-lsp.evaluateSelection arguments: {error}"),
+                }));
+            }
+        };
+    let uri = parsed.text_document.uri;
+    let Some(doc) = state.get_document(&uri) else {
+        return Some(json!({
+            "ok": false,
+            "error": format!("document '{uri}' is not open"),
+        }));
+    };
+
+    let expression = match (parsed.range, parsed.expression) {
+        (Some(range), _) => {
+            let (Some(start), Some(end)) = (
+                position_to_offset(&doc.content, range.start),
+                position_to_offset(&doc.content, range.end),
+            ) else {
+                return Some(json!({ "ok": false, "error": "selection is outside the document" }));
+            };
+            let range = TextRange::new(TextSize::from(start), TextSize::from(end.max(start)));
+            state.with_database(|db| qualify_runtime_expression(db, doc.file_id, range))
+        }
+        (None, Some(expression)) => Some(expression.trim().to_string()),
+        (None, None) => None,
+    };
+    let Some(expression) = expression.filter(|expression| !expression.is_empty()) else {
+        return Some(json!({ "ok": false, "error": "nothing selected to evaluate" }));
+    };
+
+    let (endpoint, auth) = runtime_control_for_uri(state, &uri);
+    let Some(endpoint) = endpoint else {
+        return Some(json!({
+            "ok": false,
+            "expression": expression,
+            "error": "no runtime control endpoint is configured",
+        }));
+    };
+
+    match evaluate_runtime_expression(&endpoint, auth.as_deref(), &expression, parsed.frame_id) {
+        Ok(evaluation) => {
+            let markdown = match &evaluation.type_name {
+                Some(type_name) => {
+                    format!("`{expression}` = `{}` : {type_name}", evaluation.result)
+                }
+                None => format!("`{expression}` = `{}`", evaluation.result),
+            };
+            Some(json!({
+                "ok": true,
+                "command": EVALUATE_SELECTION_COMMAND,
+                "expression": expression,
+                "result": evaluation.result,
+                "type": evaluation.type_name,
+                "range": parsed.range,
+                "markdown": markdown,
+            }))
+        }
+        Err(error) => Some(json!({
+            "ok": false,
+            "expression": expression,
+            "error": error,
+        })),
+    }
+}
+
+pub(crate) fn live_values_value(state: &ServerState, args: Vec<Value>) -> Option<Value> {
+    if args.len() != 1 {
+        return Some(json!({
+            "ok": false,
+            "error": "trust-lsp.liveValues expects one argument object",
+        }));
+    }
+    let parsed: LiveValuesCommandArgs =
+        match serde_json::from_value(args.into_iter().next().unwrap_or(Value::Null)) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return Some(json!({
+                    "ok": false,
+                    "error": format!("invalid trust-lsp.liveValues arguments: {error}"),
+                }));
+            }
+        };
+    match live_values(state, &parsed.text_document.uri, parsed.range) {
+        Ok(values) => Some(json!({
+            "ok": true,
+            "command": LIVE_VALUES_COMMAND,
+            "values": values
+                .into_iter()
+                .map(|value| json!({ "range": value.range, "text": value.text }))
+                .collect::<Vec<_>>(),
+        })),
+        Err(error) => Some(json!({ "ok": false, "error": error })),
+    }
+}
+
+/// Forces or releases the variable declared at the given position.
+pub(crate) fn force_variable_value(
+    state: &ServerState,
+    args: Vec<Value>,
+    force: bool,
+) -> Option<Value> {
+    let command = if force {
+        FORCE_VARIABLE_COMMAND
+    } else {
+        UNFORCE_VARIABLE_COMMAND
+    };
+    if args.len() != 1 {
+        return Some(json!({
+            "ok": false,
+            "error": format!("{command} expects one argument object"),
+        }));
+    }
+    let parsed: ForceVariableCommandArgs =
+        match serde_json::from_value(args.into_iter().next().unwrap_or(Value::Null)) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return Some(json!({
+                    "ok": false,
+                    "error": format!("invalid {command} arguments: {error}"),
+                }));
+            }
+        };
+    let value = match (force, parsed.value.as_deref().map(str::trim)) {
+        (true, Some(value)) if !value.is_empty() => Some(value),
+        (true, _) => {
+            return Some(json!({ "ok": false, "error": "missing value to force" }));
+        }
+        (false, _) => None,
+    };
+    let uri = parsed.text_document.uri;
+    let Some(doc) = state.get_document(&uri) else {
+        return Some(json!({
+            "ok": false,
+            "error": format!("document '{uri}' is not open"),
+        }));
+    };
+    let Some(offset) = position_to_offset(&doc.content, parsed.position) else {
+        return Some(json!({ "ok": false, "error": "position is outside the document" }));
+    };
+    let offset = TextSize::from(offset);
+    let targets = state.with_database(|db| runtime_declaration_targets(db, doc.file_id));
+    let Some(variable) = targets
+        .into_iter()
+        .find(|target| target.range.contains_inclusive(offset))
+    else {
+        return Some(json!({
+            "ok": false,
+            "error": "no forceable variable is declared at this position",
+        }));
+    };
+    let (Some(endpoint), auth) = runtime_control_for_uri(state, &uri) else {
+        return Some(json!({
+            "ok": false,
+            "error": "no runtime control endpoint is configured",
+        }));
+    };
+
+    match set_runtime_force(&endpoint, auth.as_deref(), &variable, value) {
+        Ok(target) => Some(json!({
+            "ok": true,
+            "command": command,
+            "name": variable.name.as_str(),
+            "target": target,
+            "value": value,
+        })),
+        Err(error) => Some(json!({
+            "ok": false,
+            "name": variable.name.as_str(),
+            "error": error,
+        })),
+    }
+}
+
+/// Lists forced variables and which of them are declared in the document.
+pub(crate) fn forced_variables_value(state: &ServerState, args: Vec<Value>) -> Option<Value> {
+    if args.len() != 1 {
+        return Some(json!({
+            "ok": false,
+            "error": "trust-lsp.forcedVariables expects one argument object",
+        }));
+    }
+    let parsed: ForcedVariablesCommandArgs =
+        match serde_json::from_value(args.into_iter().next().unwrap_or(Value::Null)) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return Some(json!({
+                    "ok": false,
+                    "error": format!("invalid trust-lsp.forcedVariables arguments: {error}"),
+                }));
+            }
+        };
+    let uri = parsed.text_document.uri;
+    let Some(doc) = state.get_document(&uri) else {
+        return Some(json!({
+            "ok": false,
+            "error": format!("document '{uri}' is not open"),
+        }));
+    };
+    let (Some(endpoint), auth) = runtime_control_for_uri(state, &uri) else {
+        return Some(json!({
+            "ok": false,
+            "error": "no runtime control endpoint is configured",
+        }));
+    };
+    let variables = state.with_database(|db| runtime_declaration_targets(db, doc.file_id));
+    let (forced, targets) = match fetch_runtime_forced(&endpoint, auth.as_deref(), &variables) {
+        Ok(result) => result,
+        Err(error) => return Some(json!({ "ok": false, "error": error })),
+    };
+
+    let declarations: Vec<Value> = variables
+        .iter()
+        .zip(&targets)
+        .filter_map(|(variable, target)| {
+            let target = target.as_ref()?;
+            let entry = forced.iter().find(|entry| entry.target == *target)?;
+            Some(json!({
+                "name": variable.name.as_str(),
+                "target": target,
+                "value": entry.value,
+                "range": Range {
+                    start: offset_to_position(&doc.content, variable.range.start().into()),
+                    end: offset_to_position(&doc.content, variable.range.end().into()),
+                },
+            }))
+        })
+        .collect();
+    Some(json!({
+        "ok": true,
+        "command": FORCED_VARIABLES_COMMAND,
+        "forced": forced
+            .iter()
+            .map(|entry| json!({ "target": entry.target, "value": entry.value }))
+            .collect::<Vec<_>>(),
+        "declarations": declarations,
+    }))
+}
+
+pub(crate) fn wcet_value(state: &ServerState, args: Vec<Value>) -> Option<Value> {
+    let parsed = match args.len() {
+        0 => WcetCommandArgs::default(),
+        1 => match serde_json::from_value(args.into_iter().next().unwrap_or(Value::Null)) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return Some(json!({
+                    "ok": false,
+                    "error": format!("invalid trust-lsp.wcet arguments: {error}"),
+                }));
+            }
+        },
+        _ => {
+            return Some(json!({
+                "ok": false,
+                "error": "trust-lsp.wcet expects zero or one argument object",
+            }));
+        }
+    };
+
+    let mut configs = state.workspace_configs();
+    if let Some(root_uri) = parsed.root_uri {
+        configs.retain(|(root, _)| root == &root_uri);
+    } else if let Some(text_document) = parsed.text_document {
+        if let Some(config) = state.workspace_config_for_uri(&text_document.uri) {
+            let root_uri = path_to_uri(&config.root).unwrap_or(text_document.uri.clone());
+            configs = vec![(root_uri, config)];
+        }
+    }
+
+    let mut markdown = String::from("# WCET estimation report\n");
+    let mut projects = Vec::new();
+    for (root, config) in configs {
+        let files = state.file_ids_for_config(&config);
+        if files.is_empty() {
+            continue;
+        }
+        let (measured, source) = match (&parsed.profile, &config.runtime.control_endpoint) {
+            (Some(profile), _) => (measured_timings_from_task_stats(profile), "profile"),
+            (None, Some(endpoint)) => {
+                match fetch_runtime_task_stats(
+                    endpoint,
+                    config.runtime.control_auth_token.as_deref(),
+                ) {
+                    Some(stats) => (measured_timings_from_task_stats(&stats), "runtime"),
+                    None => (Vec::new(), "static"),
+                }
+            }
+            (None, None) => (Vec::new(), "static"),
+        };
+        let defaults = WcetOptions::default();
+        let report = state.with_database(|db| {
+            analyze_wcet(
+                db,
+                WcetOptions {
+                    files: Some(&files),
+                    measured: &measured,
+                    statement_ns: parsed.statement_ns.unwrap_or(defaults.statement_ns),
+                    loop_iterations: parsed.loop_iterations.unwrap_or(defaults.loop_iterations),
+                },
+            )
+        });
+
+        markdown.push_str(&format!("\n## {root}\n\n"));
+        markdown.push_str(&format!(
+            "Statement cost: {:.1} ns ({}).\n\n",
+            report.statement_ns,
+            if report.calibrated {
+                "calibrated from measured timings"
+            } else {
+                "default, no measured timings"
+            }
+        ));
+        if !report.tasks.is_empty() {
+            markdown.push_str("| Task | Interval (ms) | Estimate (ms) | Status | Programs |\n");
+            markdown.push_str("| --- | --- | --- | --- | --- |\n");
+        }
+        let mut tasks = Vec::new();
+        for task in &report.tasks {
+            let (uri, range) = file_location_value(state, task.file_id, task.range);
+            let status = if task.overrun {
+                "overrun"
+            } else if task.unbounded {
+                "unbounded loop"
+            } else {
+                "ok"
+            };
+            let programs: Vec<String> = task
+                .programs
+                .iter()
+                .map(|program| format!("{} ({:.3} ms)", program.name, program.estimate_ms))
+                .collect();
+            markdown.push_str(&format!(
+                "| {} | {} | {:.3} | {status} | {} |\n",
+                task.name,
+                task.interval_ms
+                    .map_or_else(|| "-".to_string(), |interval| format!("{interval:.3}")),
+                task.estimate_ms,
+                programs.join(", ")
+            ));
+            tasks.push(json!({
+                "name": task.name.as_str(),
+                "uri": uri,
+                "range": range,
+                "intervalMs": task.interval_ms,
+                "estimateMs": task.estimate_ms,
+                "unbounded": task.unbounded,
+                "overrun": task.overrun,
+                "programs": task
+                    .programs
+                    .iter()
+                    .map(|program| wcet_program_value(state, program))
+                    .collect::<Vec<_>>(),
+            }));
+        }
+        if !report.unscheduled.is_empty() {
+            markdown.push_str("\n### Programs without task\n\n");
+        }
+        for program in &report.unscheduled {
+            markdown.push_str(&format!(
+                "- {}: {:.3} ms ({} statements)\n",
+                program.name, program.estimate_ms, program.statements
+            ));
+        }
+
+        projects.push(json!({
+            "root": root.to_string(),
+            "measurementSource": source,
+            "statementNs": report.statement_ns,
+            "calibrated": report.calibrated,
+            "tasks": tasks,
+            "unscheduled": report
+                .unscheduled
+                .iter()
+                .map(|program| wcet_program_value(state, program))
+                .collect::<Vec<_>>(),
+        }));
+    }
+
+    Some(json!({
+        "ok": true,
+        "command": WCET_COMMAND,
+        "projects": projects,
+        "markdown": markdown,
+    }))
+}
+
+fn wcet_program_value(state: &ServerState, program: &WcetProgram) -> Value {
+    let (uri, range) = file_location_value(state, program.file_id, program.range);
+    json!({
+        "name": program.name.as_str(),
+        "program": program.program.as_str(),
+        "statements": program.statements,
+        "staticMs": program.static_ms,
+        "measuredMs": program.measured_ms,
+        "estimateMs": program.estimate_ms,
+        "unbounded": program.unbounded,
+        "uri": uri,
+        "range": range,
+    })
+}
+
+pub(crate) fn reference_access_value(state: &ServerState, args: Vec<Value>) -> Option<Value> {
+    if args.len() != 1 {
+        return Some(json!({
+            "ok": false,
+            "error": "trust-lsp.referenceAccess expects one argument object",
+        }));
+    }
+    let parsed: ReferenceAccessCommandArgs =
+        match serde_json::from_value(args.into_iter().next().unwrap_or(Value::Null)) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return Some(json!({
+                    "ok": false,
+                    "error": format!("invalid trust-lsp.referenceAccess arguments: {error}"),
+                }));
+            }
+        };
+    let filter = match parsed.access.as_deref().map(parse_reference_access_filter) {
+        Some(Ok(filter)) => Some(filter),
+        Some(Err(error)) => return Some(json!({ "ok": false, "error": error })),
+        None => None,
+    };
+
+    let doc = state.get_document(&parsed.text_document.uri)?;
+    let offset = position_to_offset(&doc.content, parsed.position)?;
+    let options = FindReferencesOptions {
+        include_declaration: parsed.include_declaration,
+    };
+    let classified = state.with_database(|db| {
+        let references = find_references(db, doc.file_id, TextSize::from(offset), options);
+        classify_references(db, references)
+    });
+
+    let mut counts = [0usize; 3];
+    let mut references = Vec::new();
+    for item in classified {
+        let slot = match item.access {
+            ReferenceAccess::Read => 0,
+            ReferenceAccess::Write => 1,
+            ReferenceAccess::InOut => 2,
+        };
+        counts[slot] += 1;
+        if filter
+            .as_ref()
+            .is_some_and(|filter| !filter.contains(&item.access))
+        {
+            continue;
+        }
+        let Some(target_doc) = state.document_for_file_id(item.reference.file_id) else {
+            continue;
+        };
+        let range = Range {
+            start: offset_to_position(&target_doc.content, item.reference.range.start().into()),
+            end: offset_to_position(&target_doc.content, item.reference.range.end().into()),
+        };
+        references.push(json!({
+            "uri": target_doc.uri.to_string(),
+            "range": range,
+            "access": item.access.label(),
+        }));
+    }
+
+    Some(json!({
+        "ok": true,
+        "command": REFERENCE_ACCESS_COMMAND,
+        "counts": {
+            "read": counts[0],
+            "write": counts[1],
+            "inout": counts[2],
+        },
+        "references": references,
+    }))
+}
+
+fn parse_reference_access_filter(values: &[String]) -> Result<Vec<ReferenceAccess>, String> {
+    values
+        .iter()
+        .map(|value| match value.trim().to_ascii_lowercase().as_str() {
+            "read" => Ok(ReferenceAccess::Read),
+            "write" => Ok(ReferenceAccess::Write),
+            "inout" => Ok(ReferenceAccess::InOut),
+            other => Err(format!(
+                "invalid access filter '{other}' (expected read, write, or inout)"
+            )),
+        })
+        .collect()
+}
+
+pub(crate) fn trace_drivers_value(state: &ServerState, args: Vec<Value>) -> Option<Value> {
+    if args.len() != 1 {
+        return Some(json!({
+            "ok": false,
+            "error": "trust-lsp.traceDrivers expects one argument object",
+        }));
+    }
+    let parsed: TraceDriversCommandArgs =
+        match serde_json::from_value(args.into_iter().next().unwrap_or(Value::Null)) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return Some(json!({
+                    "ok": false,
+                    "error": format!("invalid trust-lsp.traceDrivers arguments: {error}"),
+                }));
+            }
+        };
+
+    let doc = state.get_document(&parsed.text_document.uri)?;
+    let offset = position_to_offset(&doc.content, parsed.position)?;
+    let mut options = DataFlowOptions::default();
+    if let Some(max_depth) = parsed.max_depth {
+        options.max_depth = max_depth.max(1);
+    }
+    let Some(tree) =
+        state.with_database(|db| trace_drivers(db, doc.file_id, TextSize::from(offset), options))
+    else {
+        return Some(json!({
+            "ok": false,
+            "error": "no variable or direct address at position",
+        }));
+    };
+
+    let mut markdown = format!("# Drivers of `{}`\n\n", tree.label);
+    data_flow_markdown(state, &tree, 0, &mut markdown);
+    Some(json!({
+        "ok": true,
+        "command": TRACE_DRIVERS_COMMAND,
+        "tree": data_flow_node_value(state, &tree),
+        "markdown": markdown,
+    }))
+}
+
+fn data_flow_node_value(state: &ServerState, node: &DataFlowNode) -> Value {
+    let (uri, range) = file_location_value(state, node.file_id, node.range);
+    json!({
+        "kind": node.kind.label(),
+        "label": node.label,
+        "uri": uri,
+        "range": range,
+        "truncated": node.truncated,
+        "children": node
+            .children
+            .iter()
+            .map(|child| data_flow_node_value(state, child))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn data_flow_markdown(state: &ServerState, node: &DataFlowNode, depth: usize, out: &mut String) {
+    let (uri, range) = file_location_value(state, node.file_id, node.range);
+    let location = match (uri, range) {
+        (Some(uri), Some(range)) => format!(" ({uri}:{})", range.start.line + 1),
+        (Some(uri), None) => format!(" ({uri})"),
+        _ => String::new(),
+    };
+    let suffix = if node.truncated { " …" } else { "" };
+    out.push_str(&format!(
+        "{}- {}: `{}`{location}{suffix}\n",
+        "  ".repeat(depth),
+        node.kind.label(),
+        node.label,
+    ));
+    for child in &node.children {
+        data_flow_markdown(state, child, depth + 1, out);
+    }
+}
+
+fn file_location_value(
+    state: &ServerState,
+    file_id: FileId,
+    range: TextRange,
+) -> (Option<String>, Option<Range>) {
+    match state.document_for_file_id(file_id) {
+        Some(doc) => (
+            Some(doc.uri.to_string()),
+            Some(Range {
+                start: offset_to_position(&doc.content, range.start().into()),
+                end: offset_to_position(&doc.content, range.end().into()),
+            }),
+        ),
+        None => (
+            state.uri_for_file_id(file_id).map(|uri| uri.to_string()),
+            None,
+        ),
+    }
+}
+
 #[derive(Debug, Clone)]
 struct LoadedSource {
     path: PathBuf,
@@ -758,8 +2016,9 @@ fn line_end_offset(source: &str, offset: usize) -> usize {
 mod tests {
     use super::*;
     use crate::config::{
-        BuildConfig, DiagnosticSettings, IndexingConfig, ProjectConfig, RuntimeConfig,
-        StdlibSettings, TelemetryConfig, WorkspaceSettings,
+        BuildConfig, CompletionSettings, DiagnosticSettings, FormatSettings, IndexingConfig,
+        LintSettings, ProjectConfig, RuntimeConfig, StdlibSettings, TelemetryConfig,
+        WorkspaceSettings,
     };
     use crate::state::Document;
     use serde_json::json;
@@ -825,6 +2084,7 @@ mod tests {
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -835,9 +2095,12 @@ mod tests {
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         }
     }
 
@@ -0,0 +1,775 @@
+//! Editor support for a project folder's `runtime.toml` and `hmi.toml`.
+//!
+//! Completion and hover come from the runtime's key schema
+//! ([`trust_runtime::config::schema`]); diagnostics combine a schema walk, which
+//! pinpoints every unknown key, with the runtime's own validators, so the editor
+//! reports exactly what `trust-runtime` would refuse at startup.
+
+use tower_lsp::lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionTextEdit, Diagnostic, DiagnosticSeverity,
+    Documentation, Hover, HoverContents, InsertTextFormat, MarkupContent, MarkupKind,
+    NumberOrString, Position, Range, TextEdit, Url,
+};
+use trust_runtime::config::schema::{
+    ConfigKey, ConfigSchema, ConfigTable, ConfigValueKind, HMI_TOML, RUNTIME_TOML,
+};
+use trust_runtime::error::RuntimeError;
+
+use crate::state::uri_to_path;
+
+use super::lsp_utils::{offset_to_position, position_to_offset};
+
+const DIAG_PARSE: &str = "CONFIG_TOML_PARSE";
+const DIAG_UNKNOWN_KEY: &str = "CONFIG_UNKNOWN_KEY";
+const DIAG_MISSING_KEY: &str = "CONFIG_MISSING_KEY";
+const DIAG_INVALID_VALUE: &str = "CONFIG_INVALID_VALUE";
+const DIAG_INVALID: &str = "CONFIG_INVALID";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConfigFile {
+    Runtime,
+    Hmi,
+}
+
+impl ConfigFile {
+    fn schema(self) -> &'static ConfigSchema {
+        match self {
+            Self::Runtime => &RUNTIME_TOML,
+            Self::Hmi => &HMI_TOML,
+        }
+    }
+
+    /// The runtime refuses unknown `runtime.toml` keys but ignores unknown `hmi.toml`
+    /// keys and values, which then fall back to defaults.
+    fn schema_severity(self) -> DiagnosticSeverity {
+        match self {
+            Self::Runtime => DiagnosticSeverity::ERROR,
+            Self::Hmi => DiagnosticSeverity::WARNING,
+        }
+    }
+}
+
+pub(crate) fn config_file_kind(uri: &Url) -> Option<ConfigFile> {
+    let path = uri_to_path(uri)?;
+    match path.file_name()?.to_str()? {
+        "runtime.toml" => Some(ConfigFile::Runtime),
+        "hmi.toml" => Some(ConfigFile::Hmi),
+        _ => None,
+    }
+}
+
+pub(crate) fn config_file_diagnostics(file: ConfigFile, content: &str) -> Vec<Diagnostic> {
+    let value = match toml::from_str::<toml::Table>(content) {
+        Ok(value) => value,
+        Err(error) => {
+            let range = error
+                .span()
+                .map(|span| byte_range(content, span.start, span.end))
+                .unwrap_or_else(|| first_line_range(content));
+            return vec![diagnostic(
+                range,
+                DiagnosticSeverity::ERROR,
+                DIAG_PARSE,
+                error.message().to_string(),
+            )];
+        }
+    };
+
+    let lines = ConfigLines::new(content);
+    let mut issues = Vec::new();
+    check_table(file, &mut Vec::new(), 0, &value, &mut issues);
+    let mut diagnostics = issues
+        .into_iter()
+        .map(|issue| {
+            let range = lines.locate(content, &issue.table, issue.index, issue.key.as_deref());
+            diagnostic(range, issue.severity, issue.code, issue.message)
+        })
+        .collect::<Vec<_>>();
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Some(DiagnosticSeverity::ERROR))
+    {
+        return diagnostics;
+    }
+
+    let validation = match file {
+        ConfigFile::Runtime => {
+            trust_runtime::config::validate_runtime_toml_text(content).map_err(|err| match err {
+                RuntimeError::InvalidConfig(message) => message.to_string(),
+                other => other.to_string(),
+            })
+        }
+        ConfigFile::Hmi => {
+            trust_runtime::hmi::validate_hmi_toml_text(content).map_err(|err| err.to_string())
+        }
+    };
+    if let Err(message) = validation {
+        let range = toml_error_position(&message)
+            .map(|position| Range {
+                start: position,
+                end: position,
+            })
+            .unwrap_or_else(|| {
+                locate_message(&lines, content, file.schema(), &validator_message(&message))
+            });
+        let message = validator_message(&message);
+        diagnostics.push(diagnostic(
+            range,
+            DiagnosticSeverity::ERROR,
+            DIAG_INVALID,
+            message,
+        ));
+    }
+    diagnostics
+}
+
+pub(crate) fn config_file_completion(
+    file: ConfigFile,
+    content: &str,
+    position: Position,
+) -> Option<Vec<CompletionItem>> {
+    let schema = file.schema();
+    let cursor = position_to_offset(content, position)? as usize;
+    let lines = ConfigLines::new(content);
+    let line = lines.line_at(cursor)?;
+    let prefix = &content[line.start..cursor];
+    let trimmed = prefix.trim_start();
+
+    if trimmed.starts_with('[') {
+        let name_start = line.start + prefix.len() - trimmed.trim_start_matches('[').len();
+        let range = Range {
+            start: offset_to_position(content, name_start as u32),
+            end: position,
+        };
+        return Some(
+            schema
+                .tables
+                .iter()
+                .filter(|table| !table.path.contains('*'))
+                .map(|table| CompletionItem {
+                    label: table.path.to_string(),
+                    kind: Some(CompletionItemKind::MODULE),
+                    detail: table.array.then(|| "array of tables".to_string()),
+                    documentation: Some(markdown_doc(table.doc.to_string())),
+                    text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                        range,
+                        new_text: table.path.to_string(),
+                    })),
+                    ..Default::default()
+                })
+                .collect(),
+        );
+    }
+
+    let section = lines.section_before(line.index);
+    if let Some(eq) = find_unquoted(prefix, '=') {
+        let key_path = split_dotted(&prefix[..eq]);
+        let (_, key) = resolve_key(schema, &section, &key_path)?;
+        let values: Vec<&str> = match key.kind {
+            ConfigValueKind::Bool => vec!["true", "false"],
+            _ => key.values.to_vec(),
+        };
+        let after_eq = &prefix[eq + 1..];
+        let value_start = line.start + eq + 1 + (after_eq.len() - after_eq.trim_start().len());
+        let mut value_end = cursor;
+        if content[cursor..].starts_with('"') {
+            value_end += 1;
+        }
+        let range = Range {
+            start: offset_to_position(content, value_start as u32),
+            end: offset_to_position(content, value_end as u32),
+        };
+        let quoted = key.kind != ConfigValueKind::Bool;
+        return Some(
+            values
+                .into_iter()
+                .map(|value| {
+                    let text = if quoted {
+                        format!("\"{value}\"")
+                    } else {
+                        value.to_string()
+                    };
+                    CompletionItem {
+                        label: value.to_string(),
+                        kind: Some(CompletionItemKind::ENUM_MEMBER),
+                        filter_text: Some(text.clone()),
+                        text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                            range,
+                            new_text: text,
+                        })),
+                        ..Default::default()
+                    }
+                })
+                .collect(),
+        );
+    }
+
+    let path = section.iter().map(String::as_str).collect::<Vec<_>>();
+    let table = schema.table(&path)?;
+    let present = lines.keys_in_section(line.index);
+    Some(
+        table
+            .keys
+            .iter()
+            .filter(|key| !present.iter().any(|name| name == key.name))
+            .map(|key| CompletionItem {
+                label: key.name.to_string(),
+                kind: Some(CompletionItemKind::PROPERTY),
+                detail: Some(key_detail(key)),
+                documentation: Some(markdown_doc(key_doc(table, key))),
+                insert_text: Some(key_snippet(key)),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            })
+            .collect(),
+    )
+}
+
+pub(crate) fn config_file_hover(
+    file: ConfigFile,
+    content: &str,
+    position: Position,
+) -> Option<Hover> {
+    let schema = file.schema();
+    let cursor = position_to_offset(content, position)? as usize;
+    let lines = ConfigLines::new(content);
+    let line = lines.line_at(cursor)?;
+    let (value, start, end) = match &line.kind {
+        LineKind::Header {
+            path, start, end, ..
+        } => {
+            let path = path.iter().map(String::as_str).collect::<Vec<_>>();
+            let table = schema.table(&path)?;
+            (table_doc(schema, table), *start, *end)
+        }
+        LineKind::Entry { key, start, end } => {
+            let section = lines.section_before(line.index);
+            let (table, key) = resolve_key(schema, &section, key)?;
+            (key_doc(table, key), *start, *end)
+        }
+        LineKind::Other => return None,
+    };
+    if cursor < start || cursor > end {
+        return None;
+    }
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        }),
+        range: Some(byte_range(content, start, end)),
+    })
+}
+
+struct Issue {
+    table: Vec<String>,
+    index: usize,
+    key: Option<String>,
+    severity: DiagnosticSeverity,
+    code: &'static str,
+    message: String,
+}
+
+fn check_table(
+    file: ConfigFile,
+    path: &mut Vec<String>,
+    index: usize,
+    table: &toml::Table,
+    issues: &mut Vec<Issue>,
+) {
+    let schema = file.schema();
+    let parts = path.iter().map(String::as_str).collect::<Vec<_>>();
+    let spec = if parts.is_empty() {
+        None
+    } else {
+        schema.table(&parts)
+    };
+    let severity = file.schema_severity();
+    let mut issue = |table: &[&str], key: Option<&str>, code, message: String| {
+        issues.push(Issue {
+            table: table.iter().map(|part| part.to_string()).collect(),
+            index,
+            key: key.map(str::to_string),
+            severity,
+            code,
+            message,
+        });
+    };
+    let mut nested = Vec::new();
+    for (name, value) in table {
+        let mut child = parts.clone();
+        child.push(name.as_str());
+        match (value, schema.table(&child)) {
+            (toml::Value::Table(_), Some(_)) => nested.push(name),
+            (toml::Value::Array(items), Some(child_spec))
+                if child_spec.array && items.iter().all(toml::Value::is_table) =>
+            {
+                nested.push(name)
+            }
+            _ if spec.is_some_and(|spec| spec.open) => {}
+            _ => match spec.and_then(|spec| spec.key(name)) {
+                Some(key) => {
+                    if let Some(message) = invalid_value(file, key, value) {
+                        issue(&parts, Some(name), DIAG_INVALID_VALUE, message);
+                    }
+                }
+                None if value.is_table() => issue(
+                    &child,
+                    None,
+                    DIAG_UNKNOWN_KEY,
+                    format!("unknown table [{}] in {}", child.join("."), schema.file),
+                ),
+                None if parts.is_empty() => issue(
+                    &parts,
+                    Some(name),
+                    DIAG_UNKNOWN_KEY,
+                    format!("unknown top-level key `{name}` in {}", schema.file),
+                ),
+                None => issue(
+                    &parts,
+                    Some(name),
+                    DIAG_UNKNOWN_KEY,
+                    format!("unknown key `{name}` in [{}]", parts.join(".")),
+                ),
+            },
+        }
+    }
+    // Missing tables are left to the runtime validator; only keys of tables that are
+    // present are checked here.
+    for key in spec
+        .map(|spec| spec.keys)
+        .unwrap_or_default()
+        .iter()
+        .filter(|key| key.required && !table.contains_key(key.name))
+    {
+        issue(
+            &parts,
+            None,
+            DIAG_MISSING_KEY,
+            format!(
+                "missing required key `{}` in [{}]",
+                key.name,
+                parts.join(".")
+            ),
+        );
+    }
+
+    for name in nested {
+        path.push(name.clone());
+        match &table[name] {
+            toml::Value::Table(child) => check_table(file, path, 0, child, issues),
+            toml::Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    if let toml::Value::Table(child) = item {
+                        check_table(file, path, index, child, issues);
+                    }
+                }
+            }
+            _ => {}
+        }
+        path.pop();
+    }
+}
+
+/// Enumerated values are compared the way the runtime parses them: trimmed, case- and
+/// separator-insensitive.
+fn invalid_value(file: ConfigFile, key: &ConfigKey, value: &toml::Value) -> Option<String> {
+    if key.values.is_empty() || file == ConfigFile::Runtime {
+        // runtime.toml values are checked by the runtime's validator.
+        return None;
+    }
+    let accepted = |text: &str| {
+        key.values
+            .iter()
+            .any(|candidate| normalize_choice(candidate) == normalize_choice(text))
+    };
+    let rejected = match value {
+        toml::Value::String(text) => (!accepted(text)).then(|| text.clone()),
+        toml::Value::Array(items) => items
+            .iter()
+            .filter_map(toml::Value::as_str)
+            .find(|&text| !accepted(text))
+            .map(str::to_string),
+        _ => None,
+    }?;
+    Some(format!(
+        "unknown value '{rejected}' for `{}`; expected one of: {}",
+        key.name,
+        key.values.join(", ")
+    ))
+}
+
+fn normalize_choice(text: &str) -> String {
+    text.trim()
+        .chars()
+        .filter(|ch| !matches!(ch, '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Strip the file prefix the runtime adds, and the source excerpt of TOML errors.
+fn validator_message(message: &str) -> String {
+    let message = message.strip_prefix("runtime.toml: ").unwrap_or(message);
+    if message.starts_with("TOML parse error") {
+        if let Some(last) = message.lines().rev().find(|line| !line.trim().is_empty()) {
+            return last.trim().to_string();
+        }
+    }
+    message.trim().to_string()
+}
+
+/// `TOML parse error at line L, column C` of a deserialization error, as a position.
+fn toml_error_position(message: &str) -> Option<Position> {
+    let rest = &message[message.find("at line ")? + "at line ".len()..];
+    let (line, rest) = rest.split_once(", column ")?;
+    let column = rest
+        .split(|ch: char| !ch.is_ascii_digit())
+        .next()?
+        .parse::<u32>()
+        .ok()?;
+    Some(Position::new(
+        line.parse::<u32>().ok()?.checked_sub(1)?,
+        column.saturating_sub(1),
+    ))
+}
+
+/// Place a validator message on the setting it names: a dotted `table.key` path, or
+/// else the first quoted value that appears in the document.
+fn locate_message(
+    lines: &ConfigLines,
+    content: &str,
+    schema: &ConfigSchema,
+    message: &str,
+) -> Range {
+    let words = message
+        .split(|ch: char| !(ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '[' | ']')));
+    for word in words.filter(|word| word.contains('.')) {
+        let segments = word
+            .replace("[]", "")
+            .split('.')
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let parts = segments.iter().map(String::as_str).collect::<Vec<_>>();
+        if schema.table(&parts).is_some() {
+            return lines.locate(content, &segments, 0, None);
+        }
+        if let Some((key, table)) = segments.split_last() {
+            let table_parts = table.iter().map(String::as_str).collect::<Vec<_>>();
+            if schema
+                .table(&table_parts)
+                .is_some_and(|spec| spec.key(key).is_some())
+            {
+                return lines.locate(content, table, 0, Some(key.as_str()));
+            }
+        }
+    }
+    for quoted in message.split('\'').skip(1).step_by(2) {
+        if quoted.is_empty() {
+            continue;
+        }
+        if let Some(pos) = content.find(&format!("\"{quoted}\"")) {
+            return byte_range(content, pos + 1, pos + 1 + quoted.len());
+        }
+    }
+    first_line_range(content)
+}
+
+fn resolve_key<'a>(
+    schema: &'a ConfigSchema,
+    section: &[String],
+    key_path: &[String],
+) -> Option<(&'a ConfigTable, &'a ConfigKey)> {
+    let (name, prefix) = key_path.split_last()?;
+    let path = section
+        .iter()
+        .chain(prefix)
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    let table = schema.table(&path)?;
+    Some((table, table.key(name)?))
+}
+
+fn table_doc(schema: &ConfigSchema, table: &ConfigTable) -> String {
+    let header = if table.array {
+        format!("[[{}]]", table.path)
+    } else {
+        format!("[{}]", table.path)
+    };
+    let mut doc = format!("**{header}** · {}\n\n{}", schema.file, table.doc);
+    if !table.keys.is_empty() {
+        let keys = table
+            .keys
+            .iter()
+            .map(|key| format!("`{}`", key.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        doc.push_str(&format!("\n\nKeys: {keys}"));
+    }
+    doc
+}
+
+fn key_doc(table: &ConfigTable, key: &ConfigKey) -> String {
+    let mut doc = format!(
+        "**{}.{}** · {}\n\n{}",
+        table.path,
+        key.name,
+        key_detail(key),
+        key.doc
+    );
+    if !key.values.is_empty() {
+        let values = key
+            .values
+            .iter()
+            .map(|value| format!("`{value}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        doc.push_str(&format!("\n\nValues: {values}"));
+    }
+    doc
+}
+
+fn key_detail(key: &ConfigKey) -> String {
+    if key.required {
+        format!("{} (required)", key.kind.as_str())
+    } else {
+        key.kind.as_str().to_string()
+    }
+}
+
+fn key_snippet(key: &ConfigKey) -> String {
+    let name = key.name;
+    if !key.values.is_empty() && key.kind == ConfigValueKind::String {
+        return format!("{name} = \"${{1|{}|}}\"", key.values.join(","));
+    }
+    match key.kind {
+        ConfigValueKind::String => format!("{name} = \"$1\""),
+        ConfigValueKind::Bool => format!("{name} = ${{1|true,false|}}"),
+        ConfigValueKind::Array => format!("{name} = [$1]"),
+        _ => format!("{name} = $1"),
+    }
+}
+
+fn markdown_doc(value: String) -> Documentation {
+    Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+    })
+}
+
+fn diagnostic(
+    range: Range,
+    severity: DiagnosticSeverity,
+    code: &str,
+    message: String,
+) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        code: Some(NumberOrString::String(code.to_string())),
+        source: Some("trust-lsp".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+fn byte_range(content: &str, start: usize, end: usize) -> Range {
+    Range {
+        start: offset_to_position(content, start as u32),
+        end: offset_to_position(content, end as u32),
+    }
+}
+
+fn first_line_range(content: &str) -> Range {
+    byte_range(content, 0, content.find('\n').unwrap_or(content.len()))
+}
+
+/// Line-level view of a TOML document: table headers and `key = value` entries with
+/// their byte ranges. Enough to place diagnostics and find the cursor's table without
+/// a span-preserving parser.
+struct ConfigLines {
+    lines: Vec<ConfigLine>,
+}
+
+struct ConfigLine {
+    index: usize,
+    start: usize,
+    kind: LineKind,
+}
+
+enum LineKind {
+    Header {
+        path: Vec<String>,
+        start: usize,
+        end: usize,
+    },
+    Entry {
+        key: Vec<String>,
+        start: usize,
+        end: usize,
+    },
+    Other,
+}
+
+impl ConfigLines {
+    fn new(content: &str) -> Self {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for (index, text) in content.split('\n').enumerate() {
+            let kind = parse_line(text.trim_end_matches('\r'), start);
+            lines.push(ConfigLine { index, start, kind });
+            start += text.len() + 1;
+        }
+        Self { lines }
+    }
+
+    fn line_at(&self, offset: usize) -> Option<&ConfigLine> {
+        self.lines.iter().rev().find(|line| line.start <= offset)
+    }
+
+    /// Table path of the header above `index`.
+    fn section_before(&self, index: usize) -> Vec<String> {
+        self.lines[..index]
+            .iter()
+            .rev()
+            .find_map(|line| match &line.kind {
+                LineKind::Header { path, .. } => Some(path.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Keys already set in the section containing line `index`.
+    fn keys_in_section(&self, index: usize) -> Vec<String> {
+        let first = self.lines[..index]
+            .iter()
+            .rposition(|line| matches!(line.kind, LineKind::Header { .. }))
+            .map_or(0, |pos| pos + 1);
+        self.lines[first..]
+            .iter()
+            .take_while(|line| !matches!(line.kind, LineKind::Header { .. }))
+            .filter_map(|line| match &line.kind {
+                LineKind::Entry { key, .. } if key.len() == 1 => Some(key[0].clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Range of `key` in the `index`-th `[table]` section (or of the header itself).
+    /// Falls back to the first line when the table is written inline or dotted.
+    fn locate(&self, content: &str, table: &[String], index: usize, key: Option<&str>) -> Range {
+        let header = self
+            .lines
+            .iter()
+            .filter(|line| {
+                matches!(&line.kind, LineKind::Header { path, .. } if path.as_slice() == table)
+            })
+            .nth(index);
+        let Some(header) = header else {
+            if table.is_empty() {
+                if let Some(range) = key.and_then(|key| self.find_entry(content, 0, key)) {
+                    return range;
+                }
+            }
+            return first_line_range(content);
+        };
+        if let Some(range) = key.and_then(|key| self.find_entry(content, header.index + 1, key)) {
+            return range;
+        }
+        match header.kind {
+            LineKind::Header { start, end, .. } => byte_range(content, start, end),
+            _ => first_line_range(content),
+        }
+    }
+
+    fn find_entry(&self, content: &str, from: usize, key: &str) -> Option<Range> {
+        self.lines
+            .get(from..)?
+            .iter()
+            .take_while(|line| !matches!(line.kind, LineKind::Header { .. }))
+            .find_map(|line| match &line.kind {
+                LineKind::Entry {
+                    key: path,
+                    start,
+                    end,
+                } if path.first().is_some_and(|first| first == key) => {
+                    Some(byte_range(content, *start, *end))
+                }
+                _ => None,
+            })
+    }
+}
+
+fn parse_line(text: &str, line_start: usize) -> LineKind {
+    let indent = text.len() - text.trim_start().len();
+    let body = text.trim_start();
+    if let Some(rest) = body.strip_prefix('[') {
+        let rest = rest.trim_start_matches('[');
+        let name_start = line_start + indent + (body.len() - rest.len());
+        let Some(close) = find_unquoted(rest, ']') else {
+            return LineKind::Other;
+        };
+        let name = &rest[..close];
+        let lead = name.len() - name.trim_start().len();
+        return LineKind::Header {
+            path: split_dotted(name),
+            start: name_start + lead,
+            end: name_start + name.trim_end().len(),
+        };
+    }
+    if body.is_empty() || body.starts_with('#') {
+        return LineKind::Other;
+    }
+    let Some(eq) = find_unquoted(body, '=') else {
+        return LineKind::Other;
+    };
+    let key = split_dotted(&body[..eq]);
+    if key.is_empty() {
+        return LineKind::Other;
+    }
+    let start = line_start + indent;
+    LineKind::Entry {
+        key,
+        start,
+        end: start + body[..eq].trim_end().len(),
+    }
+}
+
+fn find_unquoted(text: &str, target: char) -> Option<usize> {
+    let mut quote = None;
+    for (pos, ch) in text.char_indices() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == target => return Some(pos),
+            None => {}
+        }
+    }
+    None
+}
+
+/// Split a dotted TOML key (`a."b.c".d`) into its unquoted segments.
+fn split_dotted(text: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let end = find_unquoted(rest, '.').unwrap_or(rest.len());
+        let segment = rest[..end].trim();
+        let segment = segment
+            .strip_prefix('"')
+            .and_then(|inner| inner.strip_suffix('"'))
+            .or_else(|| {
+                segment
+                    .strip_prefix('\'')
+                    .and_then(|inner| inner.strip_suffix('\''))
+            })
+            .unwrap_or(segment);
+        if segment.is_empty() {
+            return Vec::new();
+        }
+        segments.push(segment.to_string());
+        rest = rest.get(end + 1..).unwrap_or_default();
+    }
+    segments
+}
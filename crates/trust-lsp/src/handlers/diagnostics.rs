@@ -1,5 +1,6 @@
 //! Diagnostics publishing helpers.
 
+use rustc_hash::FxHashSet;
 use serde_json::{json, Map, Value};
 use std::collections::{BTreeMap, BTreeSet};
 use std::hash::{Hash, Hasher};
@@ -16,20 +17,31 @@ use tower_lsp::lsp_types::{
 use tower_lsp::Client;
 
 use trust_hir::db::FileId;
+use trust_hir::diagnostics::DiagnosticCode as HirDiagnosticCode;
+use trust_hir::dialect::{check_dialect_extensions, retain_dialect_diagnostics};
 use trust_hir::symbols::SymbolKind;
 use trust_hir::DiagnosticSeverity as HirSeverity;
+use trust_ide::{
+    analyze_dead_code, analyze_stack_usage, lint_file, DeadCodeItem, DeadCodeOptions,
+    DeadCodeReport, RecursiveCall, StackUsageOptions, StackUsageReport,
+};
 use trust_runtime::bundle_builder::resolve_sources_root;
 use trust_runtime::debug::DebugSnapshot;
 use trust_runtime::harness::{CompileSession, SourceFile as HarnessSourceFile};
 use trust_runtime::hmi::{self as runtime_hmi, HmiSourceRef};
 use trust_syntax::parser::parse;
+use trust_syntax::Dialect;
 
-use crate::config::{DiagnosticSettings, ProjectConfig, CONFIG_FILES};
+use crate::config::{DiagnosticSettings, ProjectConfig, TypeCheckLevel, CONFIG_FILES};
 use crate::external_diagnostics::collect_external_diagnostics;
 use crate::library_graph::library_dependency_issues;
-use crate::state::{path_to_uri, uri_to_path, ServerState};
+use crate::state::{path_to_uri, uri_to_path, AnalysisPhase, ServerState};
 
+use super::config_files::{config_file_diagnostics, config_file_kind};
 use super::lsp_utils::{offset_to_position, position_to_offset};
+use super::progress::{send_work_done_begin, send_work_done_end};
+use super::root_conflicts::collect_root_conflict_diagnostics;
+use super::status::enter_phase;
 
 pub(crate) async fn publish_diagnostics(
     client: &Client,
@@ -117,6 +129,7 @@ pub(crate) fn workspace_diagnostic(
 
     let mut items = Vec::new();
     let mut seen = std::collections::HashSet::new();
+    let mut counts = SeverityCounts::default();
     for doc in state.documents() {
         if state.semantic_request_cancelled(request_ticket) {
             break;
@@ -129,6 +142,7 @@ pub(crate) fn workspace_diagnostic(
             doc.file_id,
             Some(request_ticket),
         );
+        counts.add(&diagnostics);
         let content_hash = hash_content(&doc.content);
         let diagnostic_hash = hash_diagnostics(&diagnostics);
         let result_id = state.store_diagnostics(doc.uri.clone(), content_hash, diagnostic_hash);
@@ -174,6 +188,7 @@ pub(crate) fn workspace_diagnostic(
             continue;
         };
         let diagnostics = collect_config_diagnostics(state, &uri, &content, Some(&root));
+        counts.add(&diagnostics);
         let content_hash = hash_content(&content);
         let diagnostic_hash = hash_diagnostics(&diagnostics);
         let result_id = state.store_diagnostics(uri.clone(), content_hash, diagnostic_hash);
@@ -201,9 +216,95 @@ pub(crate) fn workspace_diagnostic(
         }
     }
 
+    if !state.semantic_request_cancelled(request_ticket) {
+        state.set_workspace_diagnostic_counts(counts.errors, counts.warnings);
+    }
+
     WorkspaceDiagnosticReportResult::Report(WorkspaceDiagnosticReport { items })
 }
 
+/// Collects diagnostics for every tracked document and workspace config file.
+///
+/// This is the same pass the workspace pull request reports, without result ids,
+/// so headless checks match what the editor shows.
+pub(crate) fn collect_workspace_diagnostics(state: &ServerState) -> Vec<(Url, Vec<Diagnostic>)> {
+    let mut items = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for doc in state.documents() {
+        seen.insert(doc.uri.clone());
+        let diagnostics =
+            collect_diagnostics_with_ticket(state, &doc.uri, &doc.content, doc.file_id, None);
+        items.push((doc.uri, diagnostics));
+    }
+    for (root, config) in state.workspace_configs() {
+        let Some(uri) = config.config_path.as_deref().and_then(path_to_uri) else {
+            continue;
+        };
+        if seen.contains(&uri) {
+            continue;
+        }
+        let Some(content) = config
+            .config_path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+        else {
+            continue;
+        };
+        let diagnostics = collect_config_diagnostics(state, &uri, &content, Some(&root));
+        items.push((uri, diagnostics));
+    }
+    items.sort_by(|left, right| left.0.as_str().cmp(right.0.as_str()));
+    items
+}
+
+/// Runs a workspace diagnostic pass with work-done progress and `trust/status` updates.
+pub(crate) async fn workspace_diagnostic_with_progress(
+    client: &Client,
+    state: &ServerState,
+    params: WorkspaceDiagnosticParams,
+) -> WorkspaceDiagnosticReportResult {
+    let work_done_token = params.work_done_progress_params.work_done_token.clone();
+    enter_phase(client, state, AnalysisPhase::Diagnosing).await;
+    send_work_done_begin(
+        client,
+        &work_done_token,
+        "Workspace diagnostics",
+        Some(format!("{} documents", state.documents().len())),
+    )
+    .await;
+    let result = workspace_diagnostic(state, params);
+    let status = state.analysis_status();
+    send_work_done_end(
+        client,
+        &work_done_token,
+        Some(format!(
+            "{} error(s), {} warning(s)",
+            status.errors, status.warnings
+        )),
+    )
+    .await;
+    enter_phase(client, state, AnalysisPhase::Idle).await;
+    result
+}
+
+#[derive(Default)]
+struct SeverityCounts {
+    errors: usize,
+    warnings: usize,
+}
+
+impl SeverityCounts {
+    fn add(&mut self, diagnostics: &[Diagnostic]) {
+        for diagnostic in diagnostics {
+            match diagnostic.severity {
+                Some(DiagnosticSeverity::ERROR) => self.errors += 1,
+                Some(DiagnosticSeverity::WARNING) => self.warnings += 1,
+                _ => {}
+            }
+        }
+    }
+}
+
 pub(crate) fn collect_diagnostics_with_ticket(
     state: &ServerState,
     uri: &Url,
@@ -226,6 +327,13 @@ pub(crate) fn collect_diagnostics_with_ticket(
         return diagnostics;
     }
 
+    if let Some(file) = config_file_kind(uri) {
+        let mut diagnostics = config_file_diagnostics(file, content);
+        apply_diagnostic_filters(state, uri, &mut diagnostics);
+        apply_diagnostic_overrides(state, uri, &mut diagnostics);
+        return diagnostics;
+    }
+
     if is_hmi_toml_uri(uri) {
         let mut diagnostics = collect_hmi_toml_diagnostics(state, uri, content);
         apply_diagnostic_filters(state, uri, &mut diagnostics);
@@ -261,7 +369,7 @@ pub(crate) fn collect_diagnostics_with_ticket(
         })
         .collect();
 
-    let semantic = state.with_database(|db| {
+    let mut semantic = state.with_database(|db| {
         if request_ticket.is_some_and(|ticket| state.semantic_request_cancelled(ticket)) {
             Vec::new()
         } else {
@@ -273,6 +381,13 @@ pub(crate) fn collect_diagnostics_with_ticket(
         return diagnostics;
     }
 
+    let vendor_profile = state
+        .workspace_config_for_uri(uri)
+        .and_then(|config| config.vendor_profile);
+    let dialect = Dialect::for_vendor_profile(vendor_profile.as_deref());
+    retain_dialect_diagnostics(&mut semantic, dialect);
+    semantic.extend(check_dialect_extensions(content, dialect));
+
     for diag in semantic {
         let range = Range {
             start: offset_to_position(content, diag.range.start().into()),
@@ -317,7 +432,23 @@ pub(crate) fn collect_diagnostics_with_ticket(
 
     if let Some(config) = state.workspace_config_for_uri(uri) {
         diagnostics.extend(collect_external_diagnostics(&config, uri));
+        if config.diagnostics.warn_dead_code {
+            diagnostics.extend(collect_dead_code_diagnostics(
+                state, &config, content, file_id,
+            ));
+        }
+        if config.diagnostics.check_recursion {
+            diagnostics.extend(collect_recursion_diagnostics(
+                state, &config, content, file_id,
+            ));
+        }
+        if !config.lint.rules.is_empty() {
+            diagnostics.extend(collect_lint_diagnostics(state, &config, content, file_id));
+        }
     }
+    diagnostics.extend(collect_root_conflict_diagnostics(
+        state, uri, content, file_id,
+    ));
 
     let learner_context = build_learner_context(state, file_id);
     apply_diagnostic_filters(state, uri, &mut diagnostics);
@@ -332,6 +463,163 @@ pub(crate) fn collect_diagnostics_with_ticket(
     diagnostics
 }
 
+/// Runs workspace-wide dead code analysis for a project configuration.
+///
+/// Library sources contribute references but are never reported.
+pub(crate) fn dead_code_report_for_config(
+    state: &ServerState,
+    config: &ProjectConfig,
+) -> Option<DeadCodeReport> {
+    let files = state.file_ids_for_config(config);
+    if files.is_empty() {
+        return None;
+    }
+    let library_files = state.library_file_ids_for_config(config);
+    let report_files: FxHashSet<FileId> = files
+        .iter()
+        .copied()
+        .filter(|file_id| !library_files.contains(file_id))
+        .collect();
+    Some(state.with_database(|db| {
+        analyze_dead_code(
+            db,
+            DeadCodeOptions {
+                files: Some(&files),
+                report_files: Some(&report_files),
+            },
+        )
+    }))
+}
+
+pub(crate) fn dead_code_message(item: &DeadCodeItem) -> String {
+    let label = match &item.kind {
+        SymbolKind::Program => "program",
+        SymbolKind::Function { .. } => "function",
+        SymbolKind::FunctionBlock => "function block",
+        SymbolKind::Class => "class",
+        SymbolKind::Interface => "interface",
+        SymbolKind::Method { .. } => "method",
+        SymbolKind::Property { .. } => "property",
+        SymbolKind::Constant => "global constant",
+        _ => "global variable",
+    };
+    let name = match &item.container {
+        Some(container) => format!("{container}.{}", item.name),
+        None => item.name.to_string(),
+    };
+    format!("{label} '{name}' is not reachable from any CONFIGURATION/TASK entry point")
+}
+
+fn collect_dead_code_diagnostics(
+    state: &ServerState,
+    config: &ProjectConfig,
+    content: &str,
+    file_id: FileId,
+) -> Vec<Diagnostic> {
+    let Some(report) = dead_code_report_for_config(state, config) else {
+        return Vec::new();
+    };
+    let code = HirDiagnosticCode::DeadCode;
+    report
+        .items
+        .iter()
+        .filter(|item| item.file_id == file_id)
+        .map(|item| Diagnostic {
+            range: Range {
+                start: offset_to_position(content, item.range.start().into()),
+                end: offset_to_position(content, item.range.end().into()),
+            },
+            severity: Some(DiagnosticSeverity::WARNING),
+            code: Some(NumberOrString::String(code.code().to_string())),
+            source: Some("trust-lsp".to_string()),
+            message: dead_code_message(item),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Runs call depth and recursion analysis for a project configuration.
+pub(crate) fn stack_usage_report_for_config(
+    state: &ServerState,
+    config: &ProjectConfig,
+) -> Option<StackUsageReport> {
+    let files = state.file_ids_for_config(config);
+    if files.is_empty() {
+        return None;
+    }
+    Some(state.with_database(|db| {
+        analyze_stack_usage(
+            db,
+            StackUsageOptions {
+                files: Some(&files),
+            },
+        )
+    }))
+}
+
+pub(crate) fn recursive_call_message(call: &RecursiveCall) -> String {
+    format!(
+        "recursive call to '{}' makes the call depth unbounded (cycle: {})",
+        call.callee.qualified_name(),
+        call.cycle.join(" -> ")
+    )
+}
+
+fn collect_recursion_diagnostics(
+    state: &ServerState,
+    config: &ProjectConfig,
+    content: &str,
+    file_id: FileId,
+) -> Vec<Diagnostic> {
+    let Some(report) = stack_usage_report_for_config(state, config) else {
+        return Vec::new();
+    };
+    let code = HirDiagnosticCode::RecursiveCall;
+    report
+        .recursive_calls
+        .iter()
+        .filter(|call| call.file_id == file_id)
+        .map(|call| Diagnostic {
+            range: Range {
+                start: offset_to_position(content, call.range.start().into()),
+                end: offset_to_position(content, call.range.end().into()),
+            },
+            severity: Some(DiagnosticSeverity::ERROR),
+            code: Some(NumberOrString::String(code.code().to_string())),
+            source: Some("trust-lsp".to_string()),
+            message: recursive_call_message(call),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn collect_lint_diagnostics(
+    state: &ServerState,
+    config: &ProjectConfig,
+    content: &str,
+    file_id: FileId,
+) -> Vec<Diagnostic> {
+    let options = config.lint.lint_options();
+    let findings = state.with_database(|db| lint_file(db, file_id, &options));
+    findings
+        .into_iter()
+        .map(|finding| Diagnostic {
+            range: Range {
+                start: offset_to_position(content, finding.range.start().into()),
+                end: offset_to_position(content, finding.range.end().into()),
+            },
+            severity: config.lint.rules.get(&finding.rule).copied(),
+            code: Some(NumberOrString::String(finding.rule.code().to_string())),
+            source: Some("trust-lsp".to_string()),
+            message: finding.message,
+            data: finding
+                .rename_to
+                .map(|name| json!({ "lintRename": name.as_str() })),
+            ..Default::default()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 pub(crate) fn collect_diagnostics_with_ticket_for_tests(
     state: &ServerState,
@@ -344,10 +632,10 @@ pub(crate) fn collect_diagnostics_with_ticket_for_tests(
 }
 
 fn apply_diagnostic_filters(state: &ServerState, uri: &Url, diagnostics: &mut Vec<Diagnostic>) {
-    let Some(config) = state.workspace_config_for_uri(uri) else {
-        return;
-    };
-    let settings = config.diagnostics;
+    let settings = state
+        .workspace_config_for_uri(uri)
+        .map(|config| config.diagnostics)
+        .unwrap_or_default();
     diagnostics.retain(|diagnostic| diagnostic_allowed(&settings, diagnostic));
 }
 
@@ -378,10 +666,14 @@ fn diagnostic_allowed(settings: &DiagnosticSettings, diagnostic: &Diagnostic) ->
         "W003" => settings.warn_unreachable,
         "W004" => settings.warn_missing_else,
         "W005" => settings.warn_implicit_conversion,
-        "W006" => settings.warn_shadowed,
+        "W006" | "W018" => settings.warn_shadowed,
         "W007" => settings.warn_deprecated,
         "W008" => settings.warn_complexity,
         "W010" | "W011" => settings.warn_nondeterminism,
+        "W013" => settings.warn_dead_code,
+        "W021" => settings.warn_loop_bounds,
+        "W022" => settings.warn_case_exhaustive,
+        "W014" | "W015" => settings.type_check == TypeCheckLevel::Strict,
         _ => true,
     }
 }
@@ -753,7 +1045,7 @@ fn contains_plain_equal(text: &str) -> bool {
 }
 
 fn conversion_guidance_hint(code: &str, message: &str) -> Option<String> {
-    if !matches!(code, "E201" | "E203" | "E207" | "W005") {
+    if !matches!(code, "E201" | "E203" | "E207" | "W005" | "W014" | "W015") {
         return None;
     }
     let quoted = collect_quoted_segments(message);
@@ -876,6 +1168,10 @@ fn diagnostic_explainer(code: &str, message: &str) -> Option<DiagnosticExplainer
             iec_ref: "IEC 61131-3 Ed.3 §7.3",
             spec_path: "docs/specs/06-statements.md",
         }),
+        "E004" => Some(DiagnosticExplainer {
+            iec_ref: "Vendor dialect extension (non-IEC)",
+            spec_path: "docs/specs/10-runtime.md",
+        }),
         "E101" | "E104" | "E105" | "W001" | "W002" | "W006" => Some(DiagnosticExplainer {
             iec_ref: "IEC 61131-3 Ed.3 §6.5.2.2",
             spec_path: "docs/specs/09-semantic-rules.md",
@@ -904,14 +1200,38 @@ fn diagnostic_explainer(code: &str, message: &str) -> Option<DiagnosticExplainer
             iec_ref: "IEC 61131-3 Ed.3 §6.2; §6.8.2; Table 62",
             spec_path: "docs/specs/09-semantic-rules.md",
         }),
-        "E303" | "E304" => Some(DiagnosticExplainer {
+        "E308" => Some(DiagnosticExplainer {
+            iec_ref: "IEC 61131-3 Ed.3 §6.6.1 (recursive POU calls are not permitted)",
+            spec_path: "docs/specs/09-semantic-rules.md",
+        }),
+        "E303" | "E304" | "E309" => Some(DiagnosticExplainer {
             iec_ref: "IEC 61131-3 Ed.3 §6.2.6",
             spec_path: "docs/specs/02-data-types.md",
         }),
-        "W004" => Some(DiagnosticExplainer {
+        "W004" | "W022" => Some(DiagnosticExplainer {
             iec_ref: "IEC 61131-3 Ed.3 §7.3.3.3.3",
             spec_path: "docs/specs/06-statements.md",
         }),
+        "W021" => Some(DiagnosticExplainer {
+            iec_ref: "Tooling lint; ARRAY bounds per IEC 61131-3 Ed.3 §6.4.4.5.1, FOR per §7.3.3.4.2",
+            spec_path: "docs/specs/06-statements.md",
+        }),
+        "W023" => Some(DiagnosticExplainer {
+            iec_ref: "IEC 61131-3 Ed.3 §6.4.2 (integer ranges, Table 10); overflow is implementer specific",
+            spec_path: "docs/specs/05-expressions.md",
+        }),
+        "W024" => Some(DiagnosticExplainer {
+            iec_ref: "Non-IEC UNION extension; byte overlay layout",
+            spec_path: "docs/specs/02-data-types.md",
+        }),
+        "W025" => Some(DiagnosticExplainer {
+            iec_ref: "IEC 61131-3 Ed.3 Table 12 (dereferencing NULL)",
+            spec_path: "docs/specs/02-data-types.md",
+        }),
+        "W026" => Some(DiagnosticExplainer {
+            iec_ref: "IEC 61131-3 Ed.3 §6.6.5 (OVERRIDE, Table 48)",
+            spec_path: "docs/specs/04-pou-declarations.md",
+        }),
         "W003" => Some(DiagnosticExplainer {
             iec_ref: "Tooling quality lint (non-IEC)",
             spec_path: "docs/specs/09-semantic-rules.md",
@@ -920,6 +1240,26 @@ fn diagnostic_explainer(code: &str, message: &str) -> Option<DiagnosticExplainer
             iec_ref: "IEC 61131-3 Ed.3 §6.4.2",
             spec_path: "docs/specs/02-data-types.md",
         }),
+        "W014" | "W015" => Some(DiagnosticExplainer {
+            iec_ref: "Tooling quality lint (non-IEC); conversion functions per IEC 61131-3 Ed.3 Table 22",
+            spec_path: "docs/specs/07-standard-functions.md",
+        }),
+        "W016" => Some(DiagnosticExplainer {
+            iec_ref: "Tooling quality lint (non-IEC); STRING[n]/WSTRING[n] per IEC 61131-3 Ed.3 Table 10",
+            spec_path: "docs/specs/02-data-types.md",
+        }),
+        "W017" | "W018" => Some(DiagnosticExplainer {
+            iec_ref: "Tooling quality lint (non-IEC); USING per IEC 61131-3 Ed.3 Table 66",
+            spec_path: "docs/specs/04-pou-declarations.md",
+        }),
+        "W019" => Some(DiagnosticExplainer {
+            iec_ref: "Tooling workspace lint (non-IEC)",
+            spec_path: "docs/specs/10-runtime.md",
+        }),
+        "W020" => Some(DiagnosticExplainer {
+            iec_ref: "Tooling lint; directly represented variables per IEC 61131-3 Ed.3 §6.5.5 Table 16",
+            spec_path: "docs/specs/09-semantic-rules.md",
+        }),
         "W008" => Some(DiagnosticExplainer {
             iec_ref: "Tooling quality lint (non-IEC)",
             spec_path: "docs/specs/09-semantic-rules.md",
@@ -940,6 +1280,14 @@ fn diagnostic_explainer(code: &str, message: &str) -> Option<DiagnosticExplainer
             iec_ref: "Tooling quality lint (non-IEC); shared globals across tasks (IEC 61131-3 Ed.3 §6.5.2.2 Tables 13-16; §6.2/§6.8.2 Table 62)",
             spec_path: "docs/specs/09-semantic-rules.md",
         }),
+        "W013" => Some(DiagnosticExplainer {
+            iec_ref: "Tooling quality lint (non-IEC); entry points per IEC 61131-3 Ed.3 §6.8.2 (Table 62)",
+            spec_path: "docs/specs/09-semantic-rules.md",
+        }),
+        "R001" | "R002" | "R003" | "R004" | "R005" => Some(DiagnosticExplainer {
+            iec_ref: "Tooling coding-standard lint (non-IEC)",
+            spec_path: "docs/specs/09-semantic-rules.md",
+        }),
         "L001" | "L002" | "L003" | "L005" | "L006" | "L007" => Some(DiagnosticExplainer {
             iec_ref: "Tooling config lint (non-IEC)",
             spec_path: "docs/specs/10-runtime.md",
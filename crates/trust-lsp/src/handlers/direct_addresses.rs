@@ -0,0 +1,84 @@
+//! Workspace symbol search for direct addresses.
+//!
+//! A query starting with `%` lists the located declarations and `VAR_CONFIG`
+//! mappings whose address starts with it, so `%QX0` shows what is wired to each bit
+//! of output byte 0. References to one address come from `trust_ide`.
+
+use smol_str::SmolStr;
+use tower_lsp::lsp_types::{Location, Range, SymbolInformation, SymbolKind, Url};
+use trust_hir::direct_address::{
+    direct_address_uses, normalize_direct_address, DirectAddressUseKind,
+};
+use trust_syntax::parser::parse;
+use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
+
+use super::lsp_utils::offset_to_position;
+
+/// Address bindings in `source` matching a `%...` workspace symbol query.
+pub(crate) fn direct_address_symbols(
+    uri: &Url,
+    source: &str,
+    query: &str,
+) -> Vec<SymbolInformation> {
+    if !source.contains('%') {
+        return Vec::new();
+    }
+    let prefix = normalize_direct_address(query)
+        .unwrap_or_else(|| SmolStr::new(query.trim().to_ascii_uppercase()));
+    let root = parse(source).syntax();
+    direct_address_uses(&root)
+        .into_iter()
+        .filter(|entry| {
+            matches!(
+                entry.kind,
+                DirectAddressUseKind::Declaration | DirectAddressUseKind::Configuration
+            ) && entry.address.starts_with(prefix.as_str())
+        })
+        .map(|entry| {
+            let container_name = root
+                .token_at_offset(entry.range.start())
+                .right_biased()
+                .and_then(|token| token.parent())
+                .and_then(|owner| bound_name(&owner));
+            #[allow(deprecated)]
+            SymbolInformation {
+                name: entry.address.to_string(),
+                kind: SymbolKind::VARIABLE,
+                location: Location {
+                    uri: uri.clone(),
+                    range: Range {
+                        start: offset_to_position(source, entry.range.start().into()),
+                        end: offset_to_position(source, entry.range.end().into()),
+                    },
+                },
+                container_name,
+                tags: None,
+                deprecated: None,
+            }
+        })
+        .collect()
+}
+
+/// The variables of a located declaration or the access path of a `VAR_CONFIG` entry.
+fn bound_name(owner: &SyntaxNode) -> Option<String> {
+    let parts: Vec<String> = match owner.kind() {
+        SyntaxKind::VarDecl => owner
+            .children()
+            .filter(|child| child.kind() == SyntaxKind::Name)
+            .map(|name| name.text().to_string().trim().to_string())
+            .collect(),
+        SyntaxKind::ConfigInit => owner
+            .children()
+            .filter(|child| child.kind() == SyntaxKind::AccessPath)
+            .map(|path| {
+                path.descendants_with_tokens()
+                    .filter_map(|element| element.into_token())
+                    .filter(|token| !token.kind().is_trivia())
+                    .map(|token| token.text().to_string())
+                    .collect()
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
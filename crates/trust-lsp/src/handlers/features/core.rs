@@ -17,8 +17,9 @@ use trust_hir::symbols::{ParamDirection, ScopeId, SymbolKind as HirSymbolKind, S
 use trust_hir::TypeId;
 use trust_syntax::parser::parse;
 use trust_syntax::syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
+use trust_syntax::{Dialect, DialectExtension};
 
-use crate::config::{find_config_file, WorkspaceVisibility, CONFIG_FILES};
+use crate::config::{find_config_file, CompletionSettings, WorkspaceVisibility, CONFIG_FILES};
 use crate::external_diagnostics::ExternalFixData;
 use crate::handlers::diagnostics::collect_diagnostics_with_ticket;
 use crate::library_docs::doc_for_name;
@@ -28,22 +29,35 @@ use trust_ide::goto_def::goto_definition as ide_goto_definition;
 use trust_ide::util::scope_at_position;
 use trust_ide::{
     call_signature_info, convert_function_block_to_function, convert_function_to_function_block,
-    extract_method, extract_pou, extract_property, inline_value_data, InlineTargetKind,
-    InlineValueScope, StdlibFilter,
+    extract_method, extract_pou, extract_property, global_constant_lists, inline_value_data,
+    introduce_constant, organize_declarations, runtime_declaration_targets, ConstantTarget,
+    InlineTargetKind, InlineValueScope, InlineValueTarget, StdlibFilter,
 };
 
 use super::super::config::{bool_with_aliases, lsp_runtime_section, string_with_aliases};
+use super::super::config_files::{config_file_completion, config_file_hover, config_file_kind};
+use super::super::direct_addresses::direct_address_symbols;
+use super::super::formatting::declaration_sort;
 use super::super::lsp_utils::{
     display_symbol_name, is_primary_pou_symbol_kind, lsp_symbol_kind, offset_to_line_col,
     offset_to_position, position_to_offset, rename_result_to_changes, semantic_tokens_to_lsp,
-    st_file_stem, symbol_container_name, text_document_identifier_for_edit,
+    st_file_stem, symbol_container_name,
 };
+use super::super::outline::{outline_symbols, pragma_region_folding_ranges};
 use super::super::progress::{
     send_partial_result, send_work_done_begin, send_work_done_end, send_work_done_report,
 };
-use super::super::runtime_values::{fetch_runtime_inline_values, RuntimeInlineValues};
+use super::super::rename_config::{config_reference_edits, rename_workspace_edit};
+use super::super::root_conflicts::root_conflict_actions;
+use super::super::runtime_values::{
+    fetch_runtime_history, fetch_runtime_inline_values, RuntimeInlineValues,
+};
 
 const PARTIAL_CHUNK_SIZE: usize = 200;
+/// Cycles drawn in the hover history chart.
+const HOVER_HISTORY_CYCLES: usize = 60;
+/// Code action kind of the organize declarations source action.
+const ORGANIZE_DECLARATIONS_KIND: &str = "source.organizeDeclarations";
 
 fn runtime_inline_values_enabled(state: &ServerState) -> bool {
     let value = state.config();
@@ -110,6 +124,9 @@ pub fn hover(state: &ServerState, params: HoverParams) -> Option<Hover> {
     let position = params.text_document_position_params.position;
 
     let doc = state.get_document(uri)?;
+    if let Some(file) = config_file_kind(uri) {
+        return config_file_hover(file, &doc.content, position);
+    }
     let offset = position_to_offset(&doc.content, position)?;
     let stdlib_filter = stdlib_filter_for_uri(state, uri);
 
@@ -133,6 +150,11 @@ pub fn hover(state: &ServerState, params: HoverParams) -> Option<Hover> {
         }
     }
 
+    if let Some(chart) = runtime_history_hover(state, uri, doc.file_id, offset) {
+        result.contents.push_str("\n\n---\n\n");
+        result.contents.push_str(&chart);
+    }
+
     let range = result.range.map(|r| Range {
         start: offset_to_position(&doc.content, r.start().into()),
         end: offset_to_position(&doc.content, r.end().into()),
@@ -147,6 +169,80 @@ pub fn hover(state: &ServerState, params: HoverParams) -> Option<Hover> {
     })
 }
 
+/// Charts the last cycles of the hovered variable from `debug.history`.
+///
+/// Only runs when a runtime control endpoint is configured and live values
+/// are enabled.
+fn runtime_history_hover(
+    state: &ServerState,
+    uri: &Url,
+    file_id: trust_hir::db::FileId,
+    offset: u32,
+) -> Option<String> {
+    if !runtime_inline_values_enabled(state) {
+        return None;
+    }
+    let (Some(endpoint), auth) = runtime_control_for_uri(state, uri) else {
+        return None;
+    };
+    let offset = TextSize::from(offset);
+    let data = state.with_database(|db| {
+        inline_value_data(
+            db,
+            file_id,
+            TextRange::new(offset, offset + TextSize::from(1)),
+        )
+    });
+    let target = data
+        .targets
+        .into_iter()
+        .find(|target| target.range.contains_inclusive(offset))?;
+    let history =
+        match fetch_runtime_history(&endpoint, auth.as_deref(), &target, HOVER_HISTORY_CYCLES) {
+            Ok(history) => history,
+            Err(err) => {
+                debug!("hover history skipped for '{}': {}", target.name, err);
+                return None;
+            }
+        };
+    if history.values.is_empty() {
+        return Some(format!(
+            "**History:** recording `{}` (last {} cycles)",
+            target.name, history.depth
+        ));
+    }
+    let min = history.values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = history
+        .values
+        .iter()
+        .copied()
+        .fold(f64::NEG_INFINITY, f64::max);
+    let last = history.values.last().copied().unwrap_or_default();
+    Some(format!(
+        "**History** (last {} cycles)\n\n`{}`\n\nmin {min} · max {max} · last {last}",
+        history.values.len(),
+        sparkline(&history.values)
+    ))
+}
+
+/// Renders `values` as a unicode block sparkline scaled to their range.
+fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = max - min;
+    values
+        .iter()
+        .map(|value| {
+            if span <= 0.0 {
+                return BARS[0];
+            }
+            let level = ((value - min) / span * (BARS.len() - 1) as f64).round() as usize;
+            BARS[level.min(BARS.len() - 1)]
+        })
+        .collect()
+}
+
 pub fn completion(state: &ServerState, params: CompletionParams) -> Option<CompletionResponse> {
     let request_ticket = state.begin_semantic_request();
     completion_with_ticket(state, params, request_ticket)
@@ -165,12 +261,38 @@ fn completion_with_ticket(
     }
 
     let doc = state.get_document(uri)?;
+    if let Some(file) = config_file_kind(uri) {
+        return config_file_completion(file, &doc.content, position).map(CompletionResponse::Array);
+    }
     let offset = position_to_offset(&doc.content, position)?;
     let stdlib_filter = stdlib_filter_for_uri(state, uri);
+    let workspace_config = state.workspace_config_for_uri(uri);
+    let vendor_profile = workspace_config
+        .as_ref()
+        .and_then(|config| config.vendor_profile.clone());
+    let dialect = Dialect::for_vendor_profile(vendor_profile.as_deref());
+    let call_template = workspace_config
+        .map_or_else(CompletionSettings::default, |config| config.completion)
+        .call_template;
 
     // Get completions from trust_ide
     let items = state.with_database(|db| {
-        trust_ide::complete_with_filter(db, doc.file_id, TextSize::from(offset), &stdlib_filter)
+        let mut items = trust_ide::complete_with_dialect(
+            db,
+            doc.file_id,
+            TextSize::from(offset),
+            &stdlib_filter,
+            dialect,
+        );
+        if let Some(params) = call_template {
+            items.extend(trust_ide::call_template_completion(
+                db,
+                doc.file_id,
+                TextSize::from(offset),
+                params,
+            ));
+        }
+        items
     });
 
     if state.semantic_request_cancelled(request_ticket) {
@@ -305,6 +427,7 @@ pub fn signature_help(state: &ServerState, params: SignatureHelpParams) -> Optio
         return None;
     }
 
+    let docs = state.library_docs_for_uri(uri);
     let signatures = result
         .signatures
         .into_iter()
@@ -322,9 +445,18 @@ pub fn signature_help(state: &ServerState, params: SignatureHelpParams) -> Optio
                         .collect(),
                 )
             };
+            let documentation = docs.as_ref().and_then(|docs| {
+                let name = sig.label.split('(').next().unwrap_or_default().trim();
+                doc_for_name(docs.as_ref(), name).map(|value| {
+                    Documentation::MarkupContent(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value: value.to_string(),
+                    })
+                })
+            });
             SignatureInformation {
                 label: sig.label,
-                documentation: None,
+                documentation,
                 parameters,
                 active_parameter: None,
             }
@@ -607,7 +739,7 @@ pub fn document_symbol(
     let doc = state.get_document(uri)?;
 
     let symbols = state.with_database(|db| db.file_symbols(doc.file_id));
-    let result: Vec<SymbolInformation> = symbols
+    let mut result: Vec<SymbolInformation> = symbols
         .iter()
         .filter(|symbol| is_outline_symbol_kind(&symbol.kind))
         // Exclude builtin symbols (they have empty range at offset 0)
@@ -634,6 +766,14 @@ pub fn document_symbol(
         })
         .collect();
 
+    let parsed = parse(&doc.content);
+    result.extend(outline_symbols(
+        &doc.uri,
+        &doc.content,
+        &parsed.syntax(),
+        vendor_regions_enabled(state, uri),
+    ));
+
     Some(DocumentSymbolResponse::Flat(result))
 }
 
@@ -676,6 +816,7 @@ fn workspace_symbol_with_ticket(
 
     let query = params.query.trim().to_lowercase();
     let query_empty = query.is_empty();
+    let address_query = query.starts_with('%');
 
     let file_ids = state.with_database(|db| db.file_ids());
     let mut result = Vec::new();
@@ -698,6 +839,15 @@ fn workspace_symbol_with_ticket(
             continue;
         }
 
+        if address_query {
+            result.extend(
+                direct_address_symbols(&doc.uri, &doc.content, &query)
+                    .into_iter()
+                    .map(|symbol| (priority, symbol)),
+            );
+            continue;
+        }
+
         let symbols = state.with_database(|db| db.file_symbols(file_id));
         for symbol in symbols.iter() {
             if state.semantic_request_cancelled(request_ticket) {
@@ -905,14 +1055,14 @@ fn code_action_with_ticket(
                 actions.push(CodeActionOrCommand::CodeAction(action));
             }
             Some("E101") => {
-                if let Some(edit) = missing_var_text_edit(state, &doc, &root, diagnostic) {
-                    push_quickfix_action(
-                        &mut actions,
-                        "Create VAR declaration",
-                        diagnostic,
-                        uri,
-                        edit,
-                    );
+                let edits = missing_var_text_edits(state, &doc, &root, diagnostic);
+                for (title, edit, preferred) in edits {
+                    push_quickfix_action(&mut actions, &title, diagnostic, uri, edit);
+                    if !preferred {
+                        if let Some(CodeActionOrCommand::CodeAction(action)) = actions.last_mut() {
+                            action.is_preferred = None;
+                        }
+                    }
                 }
             }
             Some("E102") => {
@@ -969,7 +1119,7 @@ fn code_action_with_ticket(
                     );
                 }
             }
-            Some("W004") => {
+            Some("W004") | Some("W022") => {
                 let edit = match missing_else_text_edit(&doc.content, &root, diagnostic.range) {
                     Some(edit) => edit,
                     None => continue,
@@ -1005,6 +1155,35 @@ fn code_action_with_ticket(
                     );
                 }
             }
+            Some("R003") => {
+                if let Some(action) = lint_rename_action(state, &doc, diagnostic) {
+                    actions.push(action);
+                }
+            }
+            Some("R005") => {
+                if let Some(action) = magic_number_constant_action(state, &doc, diagnostic) {
+                    actions.push(action);
+                }
+            }
+            Some("W017") | Some("W018") => {
+                if let Some((title, edit)) = remove_using_text_edit(&doc, &root, diagnostic) {
+                    push_quickfix_action(&mut actions, &title, diagnostic, uri, edit);
+                }
+            }
+            Some("W019") => {
+                actions.extend(root_conflict_actions(state, &doc, diagnostic));
+            }
+            Some("W014") | Some("W015") | Some("E201") => {
+                if let Some(edit) = operand_conversion_text_edit(&doc, diagnostic) {
+                    push_quickfix_action(
+                        &mut actions,
+                        "Wrap operand with conversion function",
+                        diagnostic,
+                        uri,
+                        edit,
+                    );
+                }
+            }
             _ => continue,
         }
     }
@@ -1028,6 +1207,11 @@ fn code_action_with_ticket(
     }
     actions.extend(extract_actions(state, &doc, &params));
 
+    if state.semantic_request_cancelled(request_ticket) {
+        return None;
+    }
+    actions.extend(introduce_constant_actions(state, &doc, &params));
+
     if state.semantic_request_cancelled(request_ticket) {
         return None;
     }
@@ -1049,6 +1233,28 @@ fn code_action_with_ticket(
         actions.push(action);
     }
 
+    if state.semantic_request_cancelled(request_ticket) {
+        return None;
+    }
+    if let Some(action) = organize_declarations_action(state, &doc, &params) {
+        actions.push(action);
+    }
+
+    // A client diagnostic and the server's own copy can differ only in range
+    // (e.g. trivia), so drop quick fixes that repeat an earlier title and edit.
+    let mut seen_fixes: Vec<(String, Option<WorkspaceEdit>)> = Vec::new();
+    actions.retain(|action| {
+        let CodeActionOrCommand::CodeAction(action) = action else {
+            return true;
+        };
+        let key = (action.title.clone(), action.edit.clone());
+        if seen_fixes.contains(&key) {
+            return false;
+        }
+        seen_fixes.push(key);
+        true
+    });
+
     Some(actions)
 }
 
@@ -1096,40 +1302,26 @@ fn rename_with_ticket(
         return None;
     }
 
-    if let Some(rename_op) = file_rename {
-        let mut document_changes = changes_to_document_operations(state, changes);
-        document_changes.push(DocumentChangeOperation::Op(ResourceOp::Rename(rename_op)));
-        return Some(WorkspaceEdit {
-            changes: None,
-            document_changes: Some(DocumentChanges::Operations(document_changes)),
-            change_annotations: None,
-        });
-    }
-
-    Some(WorkspaceEdit {
-        changes: Some(changes),
-        document_changes: None,
-        change_annotations: None,
-    })
-}
+    let config_changes = (!new_name.contains('.'))
+        .then(|| {
+            state.with_database(|db| {
+                trust_ide::rename::rename_target(db, doc.file_id, TextSize::from(offset))
+            })
+        })
+        .flatten()
+        .map(|target| config_reference_edits(state, uri, target, new_name))
+        .unwrap_or_default();
 
-fn changes_to_document_operations(
-    state: &ServerState,
-    changes: std::collections::HashMap<Url, Vec<TextEdit>>,
-) -> Vec<DocumentChangeOperation> {
-    let mut entries: Vec<_> = changes.into_iter().collect();
-    entries.sort_by_key(|(uri, _)| uri.to_string());
-
-    let mut operations = Vec::new();
-    for (uri, edits) in entries {
-        let text_document = text_document_identifier_for_edit(state, &uri);
-        let text_edits = edits.into_iter().map(OneOf::Left).collect();
-        operations.push(DocumentChangeOperation::Edit(TextDocumentEdit {
-            text_document,
-            edits: text_edits,
-        }));
+    if state.semantic_request_cancelled(request_ticket) {
+        return None;
     }
-    operations
+
+    Some(rename_workspace_edit(
+        state,
+        changes,
+        config_changes,
+        file_rename,
+    ))
 }
 
 fn maybe_rename_pou_file(
@@ -1397,9 +1589,45 @@ pub fn folding_range(state: &ServerState, params: FoldingRangeParams) -> Option<
         }
     }
 
+    ranges.extend(pragma_region_folding_ranges(&doc.content, &root));
+    if vendor_regions_enabled(state, uri) {
+        let mut open_regions = Vec::new();
+        for token in root
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+            .filter(|token| token.kind() == SyntaxKind::RegionMarker)
+        {
+            let (line, _) = offset_to_line_col(&doc.content, token.text_range().start().into());
+            if !token.text().to_ascii_uppercase().starts_with("END_REGION") {
+                open_regions.push(line);
+                continue;
+            }
+            let Some(start_line) = open_regions.pop() else {
+                continue;
+            };
+            if line > start_line {
+                ranges.push(FoldingRange {
+                    start_line,
+                    start_character: None,
+                    end_line: line,
+                    end_character: None,
+                    kind: Some(FoldingRangeKind::Region),
+                    collapsed_text: None,
+                });
+            }
+        }
+    }
+
     Some(ranges)
 }
 
+fn vendor_regions_enabled(state: &ServerState, uri: &Url) -> bool {
+    let vendor_profile = state
+        .workspace_config_for_uri(uri)
+        .and_then(|config| config.vendor_profile);
+    Dialect::for_vendor_profile(vendor_profile.as_deref()).supports(DialectExtension::Regions)
+}
+
 pub fn selection_range(
     state: &ServerState,
     params: SelectionRangeParams,
@@ -1563,59 +1791,16 @@ pub fn inline_value(state: &ServerState, params: InlineValueParams) -> Option<Ve
     }
 
     let frame_id = u32::try_from(params.context.frame_id).ok();
-    let mut owner_hints = Vec::new();
-    for target in &data.targets {
-        if let Some(owner) = target.owner.as_ref() {
-            if !owner_hints
-                .iter()
-                .any(|name: &SmolStr| name.eq_ignore_ascii_case(owner))
-            {
-                owner_hints.push(owner.clone());
-            }
-        }
-    }
-    let (override_endpoint, override_auth) = runtime_control_override(state);
-    let config = state.workspace_config_for_uri(uri);
-    let endpoint = config
-        .as_ref()
-        .and_then(|config| config.runtime.control_endpoint.as_deref())
-        .or(override_endpoint.as_deref());
-    let auth = config
-        .as_ref()
-        .and_then(|config| config.runtime.control_auth_token.as_deref())
-        .or(override_auth.as_deref());
-    if let (Some(frame_id), Some(endpoint)) = (frame_id, endpoint) {
-        debug!(
-            "inlineValue runtime fetch uri={} endpoint={} auth_present={} owner_hints={}",
-            uri,
+    let (endpoint, auth) = runtime_control_for_uri(state, uri);
+    if let (Some(frame_id), Some(endpoint)) = (frame_id, endpoint.as_deref()) {
+        values.extend(runtime_target_values(
+            &doc.content,
+            data.targets,
             endpoint,
-            auth.is_some(),
-            owner_hints.len()
-        );
-        if let Some(runtime_values) =
-            fetch_runtime_inline_values(endpoint, auth, frame_id, &owner_hints)
-        {
-            debug!(
-                "inlineValue runtime values locals={} globals={} retain={}",
-                runtime_values.locals.len(),
-                runtime_values.globals.len(),
-                runtime_values.retain.len()
-            );
-            let normalized_values = NormalizedInlineValues::new(&runtime_values);
-            for target in data.targets {
-                if seen.contains(&target.range) {
-                    continue;
-                }
-                let value = normalized_values.lookup(target.scope, &target.name);
-                if let Some(value) = value {
-                    seen.insert(target.range);
-                    values.push(InlineValue::Text(InlineValueText {
-                        range: text_range_to_lsp(&doc.content, target.range),
-                        text: format!(" = {value}"),
-                    }));
-                }
-            }
-        }
+            auth.as_deref(),
+            Some(frame_id),
+            &mut seen,
+        ));
     } else if frame_id.is_none() {
         warn!(
             "inlineValue skipped: invalid frame_id={} for uri={}",
@@ -1631,6 +1816,134 @@ pub fn inline_value(state: &ServerState, params: InlineValueParams) -> Option<Ve
     Some(values)
 }
 
+/// Returns live values for variables in `range` while the runtime is running.
+///
+/// Unlike [`inline_value`] this needs no paused debug frame: values come from
+/// the globals, retained globals, and program/FB instances of the runtime's
+/// latest cycle snapshot. Only the read-only `debug.scopes` and
+/// `debug.variables` requests are sent, so monitoring never writes to the PLC.
+pub(crate) fn live_values(
+    state: &ServerState,
+    uri: &Url,
+    range: Range,
+) -> Result<Vec<InlineValueText>, String> {
+    let doc = state
+        .get_document(uri)
+        .ok_or_else(|| format!("document '{uri}' is not open"))?;
+    let (Some(start_offset), Some(end_offset)) = (
+        position_to_offset(&doc.content, range.start),
+        position_to_offset(&doc.content, range.end),
+    ) else {
+        return Err("range is outside the document".to_string());
+    };
+    if end_offset < start_offset || !runtime_inline_values_enabled(state) {
+        return Ok(Vec::new());
+    }
+    let (endpoint, auth) = runtime_control_for_uri(state, uri);
+    let endpoint =
+        endpoint.ok_or_else(|| "no runtime control endpoint is configured".to_string())?;
+
+    let data = state.with_database(|db| {
+        inline_value_data(
+            db,
+            doc.file_id,
+            TextRange::new(TextSize::from(start_offset), TextSize::from(end_offset)),
+        )
+    });
+    let mut seen = FxHashSet::default();
+    Ok(runtime_target_values(
+        &doc.content,
+        data.targets,
+        &endpoint,
+        auth.as_deref(),
+        None,
+        &mut seen,
+    )
+    .into_iter()
+    .filter_map(|value| match value {
+        InlineValue::Text(text) => Some(text),
+        _ => None,
+    })
+    .collect())
+}
+
+/// Returns the runtime control endpoint and auth token for `uri`.
+///
+/// `trust-lsp.toml` takes precedence over the client settings override.
+pub(crate) fn runtime_control_for_uri(
+    state: &ServerState,
+    uri: &Url,
+) -> (Option<String>, Option<String>) {
+    let (override_endpoint, override_auth) = runtime_control_override(state);
+    let config = state.workspace_config_for_uri(uri);
+    let endpoint = config
+        .as_ref()
+        .and_then(|config| config.runtime.control_endpoint.clone())
+        .or(override_endpoint);
+    let auth = config
+        .as_ref()
+        .and_then(|config| config.runtime.control_auth_token.clone())
+        .or(override_auth);
+    (endpoint, auth)
+}
+
+/// Fills inline value targets from the runtime, skipping ranges in `seen`.
+///
+/// Without a `frame_id` the paused frame's locals are ignored and locals are
+/// taken from the matching program/FB instance only.
+fn runtime_target_values(
+    content: &str,
+    targets: Vec<InlineValueTarget>,
+    endpoint: &str,
+    auth: Option<&str>,
+    frame_id: Option<u32>,
+    seen: &mut FxHashSet<TextRange>,
+) -> Vec<InlineValue> {
+    let mut owner_hints = Vec::new();
+    for target in &targets {
+        if let Some(owner) = target.owner.as_ref() {
+            if !owner_hints
+                .iter()
+                .any(|name: &SmolStr| name.eq_ignore_ascii_case(owner))
+            {
+                owner_hints.push(owner.clone());
+            }
+        }
+    }
+    debug!(
+        "inlineValue runtime fetch endpoint={} auth_present={} frame_id={:?} owner_hints={}",
+        endpoint,
+        auth.is_some(),
+        frame_id,
+        owner_hints.len()
+    );
+    let Some(runtime_values) = fetch_runtime_inline_values(endpoint, auth, frame_id, &owner_hints)
+    else {
+        return Vec::new();
+    };
+    debug!(
+        "inlineValue runtime values locals={} globals={} retain={}",
+        runtime_values.locals.len(),
+        runtime_values.globals.len(),
+        runtime_values.retain.len()
+    );
+    let normalized_values = NormalizedInlineValues::new(&runtime_values);
+    let mut values = Vec::new();
+    for target in targets {
+        if seen.contains(&target.range) {
+            continue;
+        }
+        if let Some(value) = normalized_values.lookup(target.scope, &target.name) {
+            seen.insert(target.range);
+            values.push(InlineValue::Text(InlineValueText {
+                range: text_range_to_lsp(content, target.range),
+                text: format!(" = {value}"),
+            }));
+        }
+    }
+    values
+}
+
 struct NormalizedInlineValues {
     locals: FxHashMap<SmolStr, String>,
     globals: FxHashMap<SmolStr, String>,
@@ -1994,9 +2307,46 @@ pub fn code_lens(state: &ServerState, params: CodeLensParams) -> Option<Vec<Code
         });
     }
 
+    if runtime_control_for_uri(state, uri).0.is_some() {
+        lenses.extend(force_code_lenses(state, &doc));
+    }
+
     Some(lenses)
 }
 
+/// Force/unforce lenses above forceable variable declarations.
+///
+/// The lenses run client commands, which prompt for a value and refresh the
+/// forced-state decorations around the `trust-lsp.forceVariable`,
+/// `trust-lsp.unforceVariable`, and `trust-lsp.forcedVariables` server commands.
+fn force_code_lenses(state: &ServerState, doc: &crate::state::Document) -> Vec<CodeLens> {
+    let targets = state.with_database(|db| runtime_declaration_targets(db, doc.file_id));
+    let mut lenses = Vec::new();
+    for target in targets {
+        let range = text_range_to_lsp(&doc.content, target.range);
+        let arguments = Some(vec![json!({
+            "text_document": { "uri": doc.uri },
+            "position": range.start,
+        })]);
+        for (title, command) in [
+            ("Force…", "trust-lsp.runtime.forceVariable"),
+            ("Unforce", "trust-lsp.runtime.unforceVariable"),
+            ("Show forced", "trust-lsp.runtime.showForced"),
+        ] {
+            lenses.push(CodeLens {
+                range,
+                command: Some(Command {
+                    title: title.to_string(),
+                    command: command.to_string(),
+                    arguments: arguments.clone(),
+                }),
+                data: None,
+            });
+        }
+    }
+    lenses
+}
+
 pub fn prepare_call_hierarchy(
     state: &ServerState,
     params: CallHierarchyPrepareParams,
@@ -2355,6 +2705,76 @@ fn unused_symbol_removal_range(
     Some(TextRange::new(comma.text_range().start(), end))
 }
 
+/// Removes the USING path flagged by W017/W018, or the whole directive if it is the only path.
+fn remove_using_text_edit(
+    doc: &crate::state::Document,
+    root: &SyntaxNode,
+    diagnostic: &Diagnostic,
+) -> Option<(String, TextEdit)> {
+    let start = position_to_offset(&doc.content, diagnostic.range.start)?;
+    let end = position_to_offset(&doc.content, diagnostic.range.end)?;
+    let diag_range = TextRange::new(TextSize::from(start), TextSize::from(end));
+    let directive = find_enclosing_node_of_kind(root, diag_range, SyntaxKind::UsingDirective)?;
+    let paths: Vec<SyntaxNode> = directive
+        .children()
+        .filter(|node| matches!(node.kind(), SyntaxKind::QualifiedName | SyntaxKind::Name))
+        .collect();
+    let index = paths
+        .iter()
+        .position(|path| path.text_range().contains_range(diag_range))?;
+    let path_range = non_trivia_range(&paths[index])?;
+    let title = format!(
+        "Remove USING '{}'",
+        text_for_range(&doc.content, path_range)
+    );
+
+    let removal = if paths.len() == 1 {
+        let directive_range = non_trivia_range(&directive)?;
+        let line_start = line_start_offset(&doc.content, usize::from(directive_range.start()));
+        let start = if doc.content[line_start..usize::from(directive_range.start())]
+            .trim()
+            .is_empty()
+        {
+            TextSize::from(line_start as u32)
+        } else {
+            directive_range.start()
+        };
+        extend_range_to_line_end(&doc.content, TextRange::new(start, directive_range.end()))
+    } else if index + 1 < paths.len() {
+        let next = non_trivia_range(&paths[index + 1])?;
+        TextRange::new(path_range.start(), next.start())
+    } else {
+        let comma = directive
+            .children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .filter(|token| token.kind() == SyntaxKind::Comma)
+            .filter(|token| token.text_range().end() <= path_range.start())
+            .last()?;
+        TextRange::new(comma.text_range().start(), path_range.end())
+    };
+
+    Some((
+        title,
+        TextEdit {
+            range: text_range_to_lsp(&doc.content, removal),
+            new_text: String::new(),
+        },
+    ))
+}
+
+fn non_trivia_range(node: &SyntaxNode) -> Option<TextRange> {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !token.kind().is_trivia());
+    let first = tokens.next()?;
+    let last = tokens.last().unwrap_or_else(|| first.clone());
+    Some(TextRange::new(
+        first.text_range().start(),
+        last.text_range().end(),
+    ))
+}
+
 fn missing_else_text_edit(source: &str, root: &SyntaxNode, range: Range) -> Option<TextEdit> {
     let start = position_to_offset(source, range.start)?;
     let end = position_to_offset(source, range.end)?;
@@ -2535,6 +2955,67 @@ fn push_quickfix_action(
     actions.push(CodeActionOrCommand::CodeAction(action));
 }
 
+/// Renames a symbol flagged by the naming lint (R003) to the suggested name.
+fn lint_rename_action(
+    state: &ServerState,
+    doc: &crate::state::Document,
+    diagnostic: &Diagnostic,
+) -> Option<CodeActionOrCommand> {
+    let new_name = diagnostic
+        .data
+        .as_ref()
+        .and_then(|data| data.get("lintRename"))
+        .and_then(|value| value.as_str())
+        .map(ToString::to_string)
+        .or_else(|| {
+            let (_, suggestion) = diagnostic.message.split_once("rename to ")?;
+            extract_quoted_name(suggestion)
+        })?;
+    let offset = position_to_offset(&doc.content, diagnostic.range.start)?;
+    let result = state.with_database(|db| {
+        trust_ide::rename(db, doc.file_id, TextSize::from(offset), &new_name)
+    })?;
+    let changes = rename_result_to_changes(state, result)?;
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title: format!("Rename to '{new_name}'"),
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            document_changes: None,
+            change_annotations: None,
+        }),
+        is_preferred: Some(true),
+        ..Default::default()
+    }))
+}
+
+/// Replaces a magic number (R005) with a new local constant.
+fn magic_number_constant_action(
+    state: &ServerState,
+    doc: &crate::state::Document,
+    diagnostic: &Diagnostic,
+) -> Option<CodeActionOrCommand> {
+    let start = position_to_offset(&doc.content, diagnostic.range.start)?;
+    let end = position_to_offset(&doc.content, diagnostic.range.end)?;
+    let range = TextRange::new(TextSize::from(start), TextSize::from(end));
+    let result = state
+        .with_database(|db| introduce_constant(db, doc.file_id, range, &ConstantTarget::Local))?;
+    let changes = rename_result_to_changes(state, result.edits)?;
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title: "Introduce named constant".to_string(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            document_changes: None,
+            change_annotations: None,
+        }),
+        is_preferred: Some(true),
+        ..Default::default()
+    }))
+}
+
 fn extract_quoted_name(message: &str) -> Option<String> {
     if let Some(start) = message.find('\'') {
         let rest = &message[start + 1..];
@@ -2614,74 +3095,190 @@ fn ranges_intersect(a: Range, b: Range) -> bool {
     position_leq(a.start, b.end) && position_leq(b.start, a.end)
 }
 
-fn missing_var_text_edit(
+/// Declaration sections offered by the undefined-identifier quick fix, in menu order.
+const MISSING_VAR_SECTIONS: [(&str, SyntaxKind); 4] = [
+    ("VAR", SyntaxKind::KwVar),
+    ("VAR_INPUT", SyntaxKind::KwVarInput),
+    ("VAR_OUTPUT", SyntaxKind::KwVarOutput),
+    ("VAR_GLOBAL", SyntaxKind::KwVarGlobal),
+];
+
+/// One edit per section the undefined identifier can be declared in, titled for the menu.
+/// The flag marks the local VAR declaration, which is the preferred fix.
+fn missing_var_text_edits(
     state: &ServerState,
     doc: &crate::state::Document,
     root: &SyntaxNode,
     diagnostic: &Diagnostic,
-) -> Option<TextEdit> {
-    let name = extract_quoted_name(&diagnostic.message)?;
-    let start = position_to_offset(&doc.content, diagnostic.range.start)?;
+) -> Vec<(String, TextEdit, bool)> {
+    let Some(name) = extract_quoted_name(&diagnostic.message) else {
+        return Vec::new();
+    };
+    let Some(start) = position_to_offset(&doc.content, diagnostic.range.start) else {
+        return Vec::new();
+    };
     let text_range = TextRange::new(TextSize::from(start), TextSize::from(start));
-    let pou = trust_ide::util::find_enclosing_pou(root, text_range.start())?;
+    let Some(pou) = trust_ide::util::find_enclosing_pou(root, text_range.start()) else {
+        return Vec::new();
+    };
 
     let type_name =
         infer_missing_var_type(state, doc, root, text_range).unwrap_or_else(|| "INT".to_string());
-    let newline = newline_for_source(&doc.content);
+    let decl = format!("{name} : {type_name}");
+    MISSING_VAR_SECTIONS
+        .iter()
+        .filter_map(|(keyword, kind)| {
+            let container = match kind {
+                SyntaxKind::KwVar => Some(pou.clone()),
+                SyntaxKind::KwVarInput | SyntaxKind::KwVarOutput => matches!(
+                    pou.kind(),
+                    SyntaxKind::Program
+                        | SyntaxKind::Function
+                        | SyntaxKind::FunctionBlock
+                        | SyntaxKind::Method
+                )
+                .then(|| pou.clone()),
+                _ => global_var_container(root, &pou),
+            }?;
+            let edit = var_decl_text_edit(&doc.content, &container, keyword, *kind, &decl)?;
+            Some((
+                format!("Declare '{decl}' in {keyword}"),
+                edit,
+                *kind == SyntaxKind::KwVar,
+            ))
+        })
+        .collect()
+}
+
+/// Where a new VAR_GLOBAL goes: a configuration or resource in the same file that already
+/// has a VAR_GLOBAL block, otherwise the first configuration, otherwise the enclosing
+/// PROGRAM.
+fn global_var_container(root: &SyntaxNode, pou: &SyntaxNode) -> Option<SyntaxNode> {
+    let configurations = root
+        .descendants()
+        .filter(|node| {
+            matches!(
+                node.kind(),
+                SyntaxKind::Configuration | SyntaxKind::Resource
+            )
+        })
+        .collect::<Vec<_>>();
+    configurations
+        .iter()
+        .find(|node| find_var_block(node, SyntaxKind::KwVarGlobal).is_some())
+        .or_else(|| configurations.first())
+        .cloned()
+        .or_else(|| (pou.kind() == SyntaxKind::Program).then(|| pou.clone()))
+}
+
+/// Insert `decl;` into the `keyword` block of `container`, creating the block when there
+/// is none. New blocks follow the IEC order: inputs first, then outputs, then the rest.
+fn var_decl_text_edit(
+    source: &str,
+    container: &SyntaxNode,
+    keyword: &str,
+    kind: SyntaxKind,
+    decl: &str,
+) -> Option<TextEdit> {
+    let newline = newline_for_source(source);
+    let insert_edit = |offset: usize, new_text: String| {
+        let position = offset_to_position(source, offset as u32);
+        TextEdit {
+            range: Range {
+                start: position,
+                end: position,
+            },
+            new_text,
+        }
+    };
 
-    if let Some(var_block) = find_var_block(&pou) {
+    if let Some(var_block) = find_var_block(container, kind) {
         let end_var_token = var_block
-            .descendants_with_tokens()
+            .children_with_tokens()
             .filter_map(|element| element.into_token())
             .find(|token| token.kind() == SyntaxKind::KwEndVar)?;
-        let insert_offset = line_start_offset(
-            &doc.content,
-            usize::from(end_var_token.text_range().start()),
-        );
-        let decl_indent = indent_for_var_block(&doc.content, &var_block, &end_var_token)
-            .unwrap_or_else(|| {
-                let base = indent_at_offset(&doc.content, insert_offset);
-                format!("{base}{}", infer_indent_unit(&doc.content))
+        let insert_offset =
+            line_start_offset(source, usize::from(end_var_token.text_range().start()));
+        let decl_indent =
+            indent_for_var_block(source, &var_block, &end_var_token).unwrap_or_else(|| {
+                let base = indent_at_offset(source, insert_offset);
+                format!("{base}{}", infer_indent_unit(source))
             });
-        let insert_text = format!("{decl_indent}{name} : {type_name};{newline}");
-        let insert_pos = offset_to_position(&doc.content, insert_offset as u32);
-        return Some(TextEdit {
-            range: Range {
-                start: insert_pos,
-                end: insert_pos,
-            },
-            new_text: insert_text,
-        });
+        return Some(insert_edit(
+            insert_offset,
+            format!("{decl_indent}{decl};{newline}"),
+        ));
     }
 
-    let header_indent = indent_at_offset(&doc.content, usize::from(pou.text_range().start()));
-    let indent_unit = infer_indent_unit(&doc.content);
-    let body_indent = format!("{header_indent}{indent_unit}");
-    let insert_offset = line_end_offset(&doc.content, usize::from(pou.text_range().start()));
-    let insert_text = format!(
-        "{newline}{header_indent}VAR{newline}{body_indent}{name} : {type_name};{newline}{header_indent}END_VAR{newline}"
-    );
-    let insert_pos = offset_to_position(&doc.content, insert_offset as u32);
-    Some(TextEdit {
-        range: Range {
-            start: insert_pos,
-            end: insert_pos,
-        },
-        new_text: insert_text,
-    })
+    let blocks = container
+        .children()
+        .filter(|node| node.kind() == SyntaxKind::VarBlock)
+        .collect::<Vec<_>>();
+    let anchor = match kind {
+        SyntaxKind::KwVarInput => blocks.first().map(|block| (block, false)),
+        SyntaxKind::KwVarOutput => blocks
+            .iter()
+            .rev()
+            .find(|block| var_block_keyword(block) == Some(SyntaxKind::KwVarInput))
+            .map(|block| (block, true))
+            .or_else(|| blocks.first().map(|block| (block, false))),
+        _ => blocks.last().map(|block| (block, true)),
+    };
+    if let Some((block, after)) = anchor {
+        let tokens = block
+            .children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .filter(|token| !token.kind().is_trivia())
+            .collect::<Vec<_>>();
+        let block_start = usize::from(tokens.first()?.text_range().start());
+        let indent = indent_at_offset(source, block_start);
+        let body_indent = format!("{indent}{}", infer_indent_unit(source));
+        let insert_offset = if after {
+            line_end_offset(source, usize::from(tokens.last()?.text_range().end()))
+        } else {
+            line_start_offset(source, block_start)
+        };
+        return Some(insert_edit(
+            insert_offset,
+            format!(
+                "{indent}{keyword}{newline}{body_indent}{decl};{newline}{indent}END_VAR{newline}"
+            ),
+        ));
+    }
+
+    let header_indent = indent_at_offset(source, usize::from(container.text_range().start()));
+    let body_indent = format!("{header_indent}{}", infer_indent_unit(source));
+    let insert_offset = line_end_offset(source, usize::from(container.text_range().start()));
+    Some(insert_edit(
+        insert_offset,
+        format!(
+            "{newline}{header_indent}{keyword}{newline}{body_indent}{decl};{newline}{header_indent}END_VAR{newline}"
+        ),
+    ))
 }
 
-fn find_var_block(pou: &SyntaxNode) -> Option<SyntaxNode> {
-    pou.descendants()
+/// The first writable (non-CONSTANT) block of `kind` declared directly in `container`.
+fn find_var_block(container: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxNode> {
+    container
+        .children()
         .filter(|node| node.kind() == SyntaxKind::VarBlock)
         .find(|block| {
-            block
-                .children_with_tokens()
-                .filter_map(|element| element.into_token())
-                .any(|token| token.kind() == SyntaxKind::KwVar)
+            var_block_keyword(block) == Some(kind)
+                && !block
+                    .children_with_tokens()
+                    .filter_map(|element| element.into_token())
+                    .any(|token| token.kind() == SyntaxKind::KwConstant)
         })
 }
 
+fn var_block_keyword(block: &SyntaxNode) -> Option<SyntaxKind> {
+    block
+        .children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .map(|token| token.kind())
+        .find(|kind| !kind.is_trivia())
+}
+
 fn indent_for_var_block(
     source: &str,
     block: &SyntaxNode,
@@ -2718,6 +3315,17 @@ fn infer_missing_var_type(
             .find(|node| matches!(node.kind(), SyntaxKind::NameRef | SyntaxKind::Name))
     })?;
 
+    // `F(IN := name)` or `F(name)` takes the type of the bound parameter.
+    if name_node
+        .parent()
+        .is_some_and(|parent| parent.kind() == SyntaxKind::Arg)
+    {
+        let offset = name_node.text_range().start();
+        let type_id =
+            state.with_database(|db| trust_ide::call_argument_type(db, doc.file_id, offset))?;
+        return type_name_for_type_id(state, doc, type_id);
+    }
+
     if name_node
         .ancestors()
         .any(|node| node.kind() == SyntaxKind::Condition)
@@ -2745,6 +3353,14 @@ fn infer_missing_var_type(
                     })?;
                     return type_name_for_type_id(state, doc, type_id);
                 }
+            } else if assign_stmt.children().last().as_ref() == Some(&name_node) {
+                // `target := name;` takes the type of the assignment target.
+                let target_offset = u32::from(lhs.text_range().start());
+                let type_id = state.with_database(|db| {
+                    let expr_id = db.expr_id_at_offset(doc.file_id, target_offset)?;
+                    Some(db.type_of(doc.file_id, expr_id))
+                })?;
+                return type_name_for_type_id(state, doc, type_id);
             }
         }
     }
@@ -2988,6 +3604,30 @@ fn implicit_conversion_text_edit(
     })
 }
 
+/// Wraps the operand reported by an implicit operand conversion diagnostic.
+fn operand_conversion_text_edit(
+    doc: &crate::state::Document,
+    diagnostic: &Diagnostic,
+) -> Option<TextEdit> {
+    if !diagnostic.message.starts_with("implicit conversion from '") {
+        return None;
+    }
+    let (source, target) = parse_conversion_types(&diagnostic.message)?;
+    let start = position_to_offset(&doc.content, diagnostic.range.start)?;
+    let end = position_to_offset(&doc.content, diagnostic.range.end)?;
+    let operand = text_for_range(
+        &doc.content,
+        TextRange::new(TextSize::from(start), TextSize::from(end)),
+    );
+    if operand.is_empty() {
+        return None;
+    }
+    Some(TextEdit {
+        range: diagnostic.range,
+        new_text: format!("{source}_TO_{target}({operand})"),
+    })
+}
+
 fn parse_conversion_types(message: &str) -> Option<(String, String)> {
     let message = message.trim();
     let start = message.find('\'')?;
@@ -3317,6 +3957,66 @@ fn extract_actions(
     actions
 }
 
+fn introduce_constant_actions(
+    state: &ServerState,
+    doc: &crate::state::Document,
+    params: &CodeActionParams,
+) -> Vec<CodeActionOrCommand> {
+    if !allows_refactor_action(&params.context.only) {
+        return Vec::new();
+    }
+    let Some(start) = position_to_offset(&doc.content, params.range.start) else {
+        return Vec::new();
+    };
+    let end = position_to_offset(&doc.content, params.range.end).unwrap_or(start);
+    let range = TextRange::new(TextSize::from(start), TextSize::from(end.max(start)));
+
+    let mut targets = vec![(
+        "Introduce local constant".to_string(),
+        ConstantTarget::Local,
+    )];
+    for list in state.with_database(global_constant_lists) {
+        let location = list
+            .container
+            .as_ref()
+            .map(ToString::to_string)
+            .or_else(|| {
+                state
+                    .uri_for_file_id(list.file_id)
+                    .and_then(|uri| uri.path_segments()?.next_back().map(ToString::to_string))
+            });
+        let title = match location {
+            Some(location) => format!("Introduce global constant in {location}"),
+            None => "Introduce global constant".to_string(),
+        };
+        targets.push((title, ConstantTarget::Global(list)));
+    }
+
+    let mut actions = Vec::new();
+    for (title, target) in targets {
+        let Some(result) =
+            state.with_database(|db| introduce_constant(db, doc.file_id, range, &target))
+        else {
+            continue;
+        };
+        let Some(changes) = rename_result_to_changes(state, result.edits) else {
+            continue;
+        };
+        let action = CodeAction {
+            title,
+            kind: Some(CodeActionKind::REFACTOR_EXTRACT),
+            edit: Some(WorkspaceEdit {
+                changes: Some(changes),
+                document_changes: None,
+                change_annotations: None,
+            }),
+            ..Default::default()
+        };
+        actions.push(CodeActionOrCommand::CodeAction(action));
+    }
+    actions
+}
+
 fn convert_function_action(
     state: &ServerState,
     doc: &crate::state::Document,
@@ -3371,6 +4071,43 @@ fn convert_function_block_action(
     Some(CodeActionOrCommand::CodeAction(action))
 }
 
+fn organize_declarations_action(
+    state: &ServerState,
+    doc: &crate::state::Document,
+    params: &CodeActionParams,
+) -> Option<CodeActionOrCommand> {
+    if !allows_source_action(&params.context.only) {
+        return None;
+    }
+    let offset = position_to_offset(&doc.content, params.range.start)?;
+    let sort = declaration_sort(state, &doc.uri);
+    let result = state
+        .with_database(|db| organize_declarations(db, doc.file_id, TextSize::from(offset), sort))?;
+    let changes = rename_result_to_changes(state, result)?;
+
+    let action = CodeAction {
+        title: "Organize declarations".to_string(),
+        kind: Some(CodeActionKind::new(ORGANIZE_DECLARATIONS_KIND)),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            document_changes: None,
+            change_annotations: None,
+        }),
+        ..Default::default()
+    };
+    Some(CodeActionOrCommand::CodeAction(action))
+}
+
+fn allows_source_action(only: &Option<Vec<CodeActionKind>>) -> bool {
+    let Some(only) = only else {
+        return true;
+    };
+    only.iter().any(|kind| {
+        let value = kind.as_str();
+        value == CodeActionKind::SOURCE.as_str() || value == ORGANIZE_DECLARATIONS_KIND
+    })
+}
+
 fn allows_refactor_action(only: &Option<Vec<CodeActionKind>>) -> bool {
     let Some(only) = only else {
         return true;
@@ -1 +1,2 @@
 pub use super::core::inline_value;
+pub(crate) use super::core::{live_values, runtime_control_for_uri};
@@ -18,6 +18,7 @@ pub use hierarchy::{
     type_hierarchy_subtypes, type_hierarchy_supertypes,
 };
 pub use inline_values::inline_value;
+pub(crate) use inline_values::{live_values, runtime_control_for_uri};
 pub use links::document_link;
 pub use navigation::{
     document_highlight, goto_declaration, goto_definition, goto_implementation,
@@ -2,12 +2,14 @@
 
 use tower_lsp::lsp_types::{
     DocumentFormattingParams, DocumentOnTypeFormattingParams, DocumentRangeFormattingParams,
-    Position, Range, TextEdit, Url,
+    FormattingOptions, Position, Range, TextEdit, Url,
 };
 
 use serde_json::Value;
-use trust_syntax::{lex, Token, TokenKind};
+use trust_ide::DeclarationSort;
+use trust_syntax::{lex, Dialect, DialectExtension, Token, TokenKind};
 
+use crate::config::FormatSettings;
 use crate::state::ServerState;
 
 use super::config::{bool_with_aliases, lsp_section, string_with_aliases, value_with_aliases};
@@ -18,6 +20,7 @@ enum KeywordCase {
     Preserve,
     Upper,
     Lower,
+    Pascal,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -32,16 +35,25 @@ enum EndKeywordStyle {
     Indented,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum VarBlockIndent {
+    Indented,
+    Flush,
+}
+
 #[derive(Clone, Debug)]
 struct FormatConfig {
     indent_width: usize,
     insert_spaces: bool,
     keyword_case: KeywordCase,
+    var_block_indent: VarBlockIndent,
     align_var_decl_colons: bool,
+    align_var_assignments: bool,
     align_assignments: bool,
     max_line_length: Option<usize>,
     spacing_style: SpacingStyle,
     end_keyword_style: EndKeywordStyle,
+    indent_regions: bool,
 }
 
 fn format_config(
@@ -53,16 +65,22 @@ fn format_config(
         indent_width: options.tab_size as usize,
         insert_spaces: options.insert_spaces,
         keyword_case: KeywordCase::Preserve,
+        var_block_indent: VarBlockIndent::Indented,
         align_var_decl_colons: true,
+        align_var_assignments: true,
         align_assignments: true,
         max_line_length: None,
         spacing_style: SpacingStyle::Spaced,
         end_keyword_style: EndKeywordStyle::Aligned,
+        indent_regions: false,
     };
 
-    if let Some(workspace_config) = state.workspace_config_for_uri(uri) {
-        let overrides = format_profile_overrides(workspace_config.vendor_profile.as_deref());
-        apply_format_overrides(&mut config, overrides);
+    let workspace_config = state.workspace_config_for_uri(uri);
+    if let Some(workspace_config) = workspace_config.as_ref() {
+        let vendor_profile = workspace_config.vendor_profile.as_deref();
+        apply_format_overrides(&mut config, format_profile_overrides(vendor_profile));
+        config.indent_regions =
+            Dialect::for_vendor_profile(vendor_profile).supports(DialectExtension::Regions);
     }
 
     let value = state.config();
@@ -70,6 +88,9 @@ fn format_config(
         .and_then(|section| value_with_aliases(section, &["format", "formatting"]));
 
     if let Some(format) = format {
+        if let Some(profile) = string_with_aliases(format, &["profile", "styleProfile"]) {
+            apply_format_overrides(&mut config, format_profile_overrides(Some(profile)));
+        }
         if let Some(width) =
             value_with_aliases(format, &["indentWidth", "indent_width"]).and_then(Value::as_u64)
         {
@@ -79,15 +100,19 @@ fn format_config(
             config.insert_spaces = insert;
         }
         if let Some(case) = string_with_aliases(format, &["keywordCase", "keyword_case"]) {
-            config.keyword_case = match case.to_ascii_lowercase().as_str() {
-                "upper" => KeywordCase::Upper,
-                "lower" => KeywordCase::Lower,
-                _ => KeywordCase::Preserve,
-            };
+            config.keyword_case = parse_keyword_case(case);
+        }
+        if let Some(style) = string_with_aliases(format, &["varBlockIndent", "var_block_indent"]) {
+            config.var_block_indent = parse_var_block_indent(style);
         }
         if let Some(align) = bool_with_aliases(format, &["alignVarDecls", "align_var_decls"]) {
             config.align_var_decl_colons = align;
         }
+        if let Some(align) =
+            bool_with_aliases(format, &["alignVarAssignments", "align_var_assignments"])
+        {
+            config.align_var_assignments = align;
+        }
         if let Some(align) = bool_with_aliases(format, &["alignAssignments", "align_assignments"]) {
             config.align_assignments = align;
         }
@@ -99,29 +124,94 @@ fn format_config(
             }
         }
         if let Some(style) = string_with_aliases(format, &["spacingStyle", "spacing_style"]) {
-            config.spacing_style = match style.to_ascii_lowercase().as_str() {
-                "compact" | "tight" => SpacingStyle::Compact,
-                _ => SpacingStyle::Spaced,
-            };
+            config.spacing_style = parse_spacing_style(style);
         }
         if let Some(style) = string_with_aliases(format, &["endKeywordStyle", "end_keyword_style"])
         {
-            config.end_keyword_style = match style.to_ascii_lowercase().as_str() {
-                "indented" | "indent" => EndKeywordStyle::Indented,
-                _ => EndKeywordStyle::Aligned,
-            };
+            config.end_keyword_style = parse_end_keyword_style(style);
         }
     }
 
+    // Project style settings in trust-lsp.toml win over editor settings so the
+    // whole team formats the same way.
+    if let Some(workspace_config) = workspace_config.as_ref() {
+        apply_format_settings(&mut config, &workspace_config.format);
+    }
+
     config
 }
 
+fn apply_format_settings(config: &mut FormatConfig, settings: &FormatSettings) {
+    if let Some(profile) = settings.profile.as_deref() {
+        apply_format_overrides(config, format_profile_overrides(Some(profile)));
+    }
+    let overrides = FormatOverrides {
+        indent_width: settings.indent_width,
+        insert_spaces: settings.insert_spaces,
+        keyword_case: settings.keyword_case.as_deref().map(parse_keyword_case),
+        var_block_indent: settings
+            .var_block_indent
+            .as_deref()
+            .map(parse_var_block_indent),
+        align_var_decl_colons: settings.align_var_decls,
+        align_var_assignments: settings.align_var_assignments,
+        align_assignments: settings.align_assignments,
+        max_line_length: settings.max_line_length,
+        spacing_style: settings.spacing_style.as_deref().map(parse_spacing_style),
+        end_keyword_style: settings
+            .end_keyword_style
+            .as_deref()
+            .map(parse_end_keyword_style),
+    };
+    apply_format_overrides(config, overrides);
+}
+
+fn parse_keyword_case(value: &str) -> KeywordCase {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "upper" => KeywordCase::Upper,
+        "lower" => KeywordCase::Lower,
+        "pascal" | "pascalcase" | "pascal_case" => KeywordCase::Pascal,
+        _ => KeywordCase::Preserve,
+    }
+}
+
+fn parse_var_block_indent(value: &str) -> VarBlockIndent {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "flush" | "none" | "aligned" => VarBlockIndent::Flush,
+        _ => VarBlockIndent::Indented,
+    }
+}
+
+fn parse_spacing_style(value: &str) -> SpacingStyle {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "compact" | "tight" => SpacingStyle::Compact,
+        _ => SpacingStyle::Spaced,
+    }
+}
+
+fn parse_declaration_sort(value: &str) -> DeclarationSort {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "type" => DeclarationSort::Type,
+        "none" | "off" | "source" => DeclarationSort::None,
+        _ => DeclarationSort::Name,
+    }
+}
+
+fn parse_end_keyword_style(value: &str) -> EndKeywordStyle {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "indented" | "indent" => EndKeywordStyle::Indented,
+        _ => EndKeywordStyle::Aligned,
+    }
+}
+
 #[derive(Default)]
 struct FormatOverrides {
     indent_width: Option<usize>,
     insert_spaces: Option<bool>,
     keyword_case: Option<KeywordCase>,
+    var_block_indent: Option<VarBlockIndent>,
     align_var_decl_colons: Option<bool>,
+    align_var_assignments: Option<bool>,
     align_assignments: Option<bool>,
     max_line_length: Option<usize>,
     spacing_style: Option<SpacingStyle>,
@@ -138,9 +228,15 @@ fn apply_format_overrides(config: &mut FormatConfig, overrides: FormatOverrides)
     if let Some(case) = overrides.keyword_case {
         config.keyword_case = case;
     }
+    if let Some(style) = overrides.var_block_indent {
+        config.var_block_indent = style;
+    }
     if let Some(align) = overrides.align_var_decl_colons {
         config.align_var_decl_colons = align;
     }
+    if let Some(align) = overrides.align_var_assignments {
+        config.align_var_assignments = align;
+    }
     if let Some(align) = overrides.align_assignments {
         config.align_assignments = align;
     }
@@ -167,7 +263,9 @@ fn format_profile_overrides(profile: Option<&str>) -> FormatOverrides {
             indent_width: Some(4),
             insert_spaces: Some(true),
             keyword_case: Some(KeywordCase::Upper),
+            var_block_indent: Some(VarBlockIndent::Indented),
             align_var_decl_colons: Some(true),
+            align_var_assignments: Some(true),
             align_assignments: Some(true),
             max_line_length: Some(120),
             spacing_style: Some(SpacingStyle::Spaced),
@@ -177,7 +275,9 @@ fn format_profile_overrides(profile: Option<&str>) -> FormatOverrides {
             indent_width: Some(2),
             insert_spaces: Some(true),
             keyword_case: Some(KeywordCase::Upper),
+            var_block_indent: Some(VarBlockIndent::Indented),
             align_var_decl_colons: Some(true),
+            align_var_assignments: Some(true),
             align_assignments: Some(true),
             max_line_length: Some(120),
             spacing_style: Some(SpacingStyle::Compact),
@@ -206,6 +306,42 @@ pub fn formatting(state: &ServerState, params: DocumentFormattingParams) -> Opti
     }])
 }
 
+/// Formats a whole file the way an editor with default options would.
+///
+/// Indentation defaults to four spaces; `[format]` in trust-lsp.toml still wins.
+pub(crate) fn format_source(state: &ServerState, uri: &Url, source: &str) -> String {
+    let options = FormattingOptions {
+        tab_size: 4,
+        insert_spaces: true,
+        ..FormattingOptions::default()
+    };
+    format_document(source, &format_config(state, uri, &options))
+}
+
+/// Member order for the organize declarations action.
+///
+/// `[format].declaration_sort` in trust-lsp.toml wins over the editor's
+/// `format.declarationSort`; members are sorted by name when neither is set.
+pub(crate) fn declaration_sort(state: &ServerState, uri: &Url) -> DeclarationSort {
+    let project = state
+        .workspace_config_for_uri(uri)
+        .and_then(|config| config.format.declaration_sort);
+    let client = || {
+        let value = state.config();
+        lsp_section(&value)
+            .and_then(|section| value_with_aliases(section, &["format", "formatting"]))
+            .and_then(|format| {
+                string_with_aliases(format, &["declarationSort", "declaration_sort"])
+                    .map(str::to_string)
+            })
+    };
+    project
+        .or_else(client)
+        .map_or(DeclarationSort::Name, |value| {
+            parse_declaration_sort(&value)
+        })
+}
+
 pub fn range_formatting(
     state: &ServerState,
     params: DocumentRangeFormattingParams,
@@ -265,6 +401,10 @@ fn format_document(source: &str, config: &FormatConfig) -> String {
     let mut line_has_line_comment = vec![false; line_count];
     let mut line_has_pragma = vec![false; line_count];
     let mut line_has_string_literal = vec![false; line_count];
+    let mut line_fmt_off_marker = vec![false; line_count];
+    let mut line_fmt_on_marker = vec![false; line_count];
+    let mut line_region_start = vec![false; line_count];
+    let mut line_region_end = vec![false; line_count];
 
     for token in tokens {
         if token.kind == TokenKind::BlockComment {
@@ -290,6 +430,24 @@ fn format_document(source: &str, config: &FormatConfig) -> String {
             if let Some(line) = line_has_pragma.get_mut(line_idx) {
                 *line = true;
             }
+            let text = &source[usize::from(token.range.start())..usize::from(token.range.end())];
+            match format_region_pragma(text) {
+                Some(false) => line_fmt_off_marker[line_idx] = true,
+                Some(true) => line_fmt_on_marker[line_idx] = true,
+                None => {}
+            }
+            continue;
+        }
+        if token.kind == TokenKind::RegionMarker {
+            // Region markers are kept verbatim and skip alignment, like pragmas.
+            let line_idx = line_index(&line_starts, usize::from(token.range.start()));
+            line_has_pragma[line_idx] = true;
+            let text = &source[usize::from(token.range.start())..usize::from(token.range.end())];
+            if text.to_ascii_uppercase().starts_with("END_REGION") {
+                line_region_end[line_idx] = true;
+            } else {
+                line_region_start[line_idx] = true;
+            }
             continue;
         }
         if matches!(
@@ -311,6 +469,8 @@ fn format_document(source: &str, config: &FormatConfig) -> String {
         }
     }
 
+    let line_fmt_off = format_off_lines(&line_fmt_off_marker, &line_fmt_on_marker);
+
     let indent_unit = if config.insert_spaces {
         " ".repeat(config.indent_width.max(1))
     } else {
@@ -321,7 +481,11 @@ fn format_document(source: &str, config: &FormatConfig) -> String {
     let mut output_lines = Vec::with_capacity(line_count);
     let mut line_in_var_block = vec![false; line_count];
     let mut line_colon_index: Vec<Option<usize>> = vec![None; line_count];
+    let mut line_at_index: Vec<Option<usize>> = vec![None; line_count];
+    let mut line_config_decl = vec![false; line_count];
     let mut in_var_block = false;
+    let mut var_block_offset: i32 = 0;
+    let mut configuration_depth = 0usize;
 
     for i in 0..line_count {
         let line_start = line_starts[i];
@@ -340,6 +504,7 @@ fn format_document(source: &str, config: &FormatConfig) -> String {
 
         if line_in_block_comment[i] {
             output_lines.push(line_text.to_string());
+            configuration_depth = update_configuration_depth(configuration_depth, tokens);
             if has_var_start {
                 in_var_block = true;
             }
@@ -351,7 +516,11 @@ fn format_document(source: &str, config: &FormatConfig) -> String {
 
         let trimmed = line_text.trim();
         if trimmed.is_empty() {
-            output_lines.push(String::new());
+            if line_fmt_off[i] {
+                output_lines.push(line_text.to_string());
+            } else {
+                output_lines.push(String::new());
+            }
             if has_var_start {
                 in_var_block = true;
             }
@@ -376,22 +545,52 @@ fn format_document(source: &str, config: &FormatConfig) -> String {
                 }
             }
         }
+        if config.indent_regions && line_region_end[i] {
+            current_indent = (current_indent - 1).max(0);
+        }
+
+        if has_var_start && !in_var_block {
+            var_block_offset = match config.var_block_indent {
+                VarBlockIndent::Flush if current_indent > 0 => 1,
+                _ => 0,
+            };
+        }
+        let line_indent = if has_var_start || in_var_block {
+            (current_indent - var_block_offset).max(0)
+        } else {
+            current_indent
+        };
 
-        let indent_prefix = indent_unit.repeat(current_indent as usize);
-        let formatted_line = if line_has_line_comment[i] || line_has_pragma[i] {
+        let indent_prefix = indent_unit.repeat(line_indent as usize);
+        let formatted_line = if line_fmt_off[i] {
+            line_text.to_string()
+        } else if line_has_line_comment[i] || line_has_pragma[i] {
             format!("{}{}", indent_prefix, trimmed)
         } else {
             let content =
                 format_line_tokens(tokens, source, config.keyword_case, config.spacing_style);
             format!("{}{}", indent_prefix, content)
         };
-        if line_in_var_block[i] && !line_has_line_comment[i] && !line_has_pragma[i] {
+        if line_in_var_block[i]
+            && !line_has_line_comment[i]
+            && !line_has_pragma[i]
+            && !line_fmt_off[i]
+        {
             line_colon_index[i] = find_type_colon(&formatted_line);
+            line_at_index[i] = find_at_keyword(&formatted_line);
         }
+        let in_configuration = configuration_depth > 0;
+        line_config_decl[i] = in_configuration
+            && !in_var_block
+            && tokens.first().is_some_and(|token| {
+                matches!(token.kind, TokenKind::KwTask | TokenKind::KwProgram)
+            });
 
         output_lines.push(formatted_line);
 
-        if line_has_indent_start(tokens) {
+        if line_has_indent_start(tokens, in_configuration)
+            || (config.indent_regions && line_region_start[i])
+        {
             indent_level = current_indent + 1;
         } else {
             indent_level = current_indent;
@@ -399,6 +598,7 @@ fn format_document(source: &str, config: &FormatConfig) -> String {
         if dedent_after {
             indent_level = indent_level.saturating_sub(1);
         }
+        configuration_depth = update_configuration_depth(configuration_depth, tokens);
         if has_var_start {
             in_var_block = true;
         }
@@ -408,7 +608,14 @@ fn format_document(source: &str, config: &FormatConfig) -> String {
     }
 
     if config.align_var_decl_colons {
-        align_var_block_colons(&mut output_lines, &line_in_var_block, &line_colon_index);
+        if align_var_block_columns(&mut output_lines, &line_in_var_block, &line_at_index) {
+            for (idx, colon) in line_colon_index.iter_mut().enumerate() {
+                if colon.is_some() {
+                    *colon = find_type_colon(&output_lines[idx]);
+                }
+            }
+        }
+        align_var_block_columns(&mut output_lines, &line_in_var_block, &line_colon_index);
     }
     let line_masks = LineFormatMasks {
         in_var_block: &line_in_var_block,
@@ -416,9 +623,16 @@ fn format_document(source: &str, config: &FormatConfig) -> String {
         has_line_comment: &line_has_line_comment,
         has_pragma: &line_has_pragma,
         has_string_literal: &line_has_string_literal,
+        fmt_off: &line_fmt_off,
+        config_decl: &line_config_decl,
     };
-    if config.align_assignments {
-        align_assignment_ops(&mut output_lines, &line_masks);
+    if config.align_assignments || config.align_var_assignments {
+        align_assignment_ops(
+            &mut output_lines,
+            &line_masks,
+            config.align_assignments,
+            config.align_var_assignments,
+        );
     }
 
     let output_lines = if let Some(max) = config.max_line_length {
@@ -439,6 +653,40 @@ fn format_document(source: &str, config: &FormatConfig) -> String {
     result
 }
 
+/// Returns `Some(false)` for `{fmt:off}` and `Some(true)` for `{fmt:on}`.
+fn format_region_pragma(text: &str) -> Option<bool> {
+    let inner = text.strip_prefix('{')?.strip_suffix('}')?;
+    let normalized: String = inner
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    match normalized.as_str() {
+        "fmt:off" => Some(false),
+        "fmt:on" => Some(true),
+        _ => None,
+    }
+}
+
+/// Marks lines inside `{fmt:off}` ... `{fmt:on}` regions, marker lines included.
+fn format_off_lines(off_markers: &[bool], on_markers: &[bool]) -> Vec<bool> {
+    let mut disabled = false;
+    off_markers
+        .iter()
+        .zip(on_markers)
+        .map(|(&off, &on)| {
+            if off {
+                disabled = true;
+            }
+            let line_disabled = disabled || on;
+            if on {
+                disabled = false;
+            }
+            line_disabled
+        })
+        .collect()
+}
+
 fn format_lines_edit(
     source: &str,
     formatted: &str,
@@ -565,6 +813,14 @@ fn block_spans(source: &str) -> Vec<BlockSpan> {
             continue;
         }
         let line = line_index(&line_starts, usize::from(token.range.start()));
+        if token.kind == TokenKind::KwProgram
+            && stack
+                .iter()
+                .any(|(open_kind, _)| *open_kind == BlockKind::Configuration)
+        {
+            // Program configuration inside CONFIGURATION/RESOURCE, not a POU.
+            continue;
+        }
         if let Some(kind) = block_start_kind(token.kind) {
             stack.push((kind, line));
             continue;
@@ -676,6 +932,8 @@ fn is_dedent_token(kind: TokenKind) -> bool {
             | TokenKind::KwEndRepeat
             | TokenKind::KwEndGet
             | TokenKind::KwEndSet
+            | TokenKind::KwEndConfiguration
+            | TokenKind::KwEndResource
             | TokenKind::KwElse
             | TokenKind::KwElsif
             | TokenKind::KwUntil
@@ -704,15 +962,31 @@ fn is_end_keyword(kind: TokenKind) -> bool {
             | TokenKind::KwEndRepeat
             | TokenKind::KwEndGet
             | TokenKind::KwEndSet
+            | TokenKind::KwEndConfiguration
+            | TokenKind::KwEndResource
     )
 }
 
-fn line_has_indent_start(tokens: &[Token]) -> bool {
+/// Tracks CONFIGURATION nesting so program configurations
+/// (`PROGRAM inst WITH task : Type;`) are not mistaken for POU headers.
+fn update_configuration_depth(depth: usize, tokens: &[Token]) -> usize {
+    tokens.iter().fold(depth, |depth, token| match token.kind {
+        TokenKind::KwConfiguration => depth + 1,
+        TokenKind::KwEndConfiguration => depth.saturating_sub(1),
+        _ => depth,
+    })
+}
+
+fn line_has_indent_start(tokens: &[Token], in_configuration: bool) -> bool {
     tokens.iter().any(|token| {
         let kind = token.kind;
+        if kind == TokenKind::KwProgram {
+            return !in_configuration;
+        }
         matches!(
             kind,
-            TokenKind::KwProgram
+            TokenKind::KwConfiguration
+                | TokenKind::KwResource
                 | TokenKind::KwFunction
                 | TokenKind::KwFunctionBlock
                 | TokenKind::KwMethod
@@ -765,12 +1039,15 @@ fn format_line_tokens(
         let start = usize::from(token.range.start());
         let end = usize::from(token.range.end());
         let text = &source[start..end];
-        if keyword_case == KeywordCase::Preserve || !kind.is_keyword() {
+        if !kind.is_keyword() {
             out.push_str(text);
-        } else if keyword_case == KeywordCase::Upper {
-            out.push_str(&text.to_ascii_uppercase());
         } else {
-            out.push_str(&text.to_ascii_lowercase());
+            match keyword_case {
+                KeywordCase::Preserve => out.push_str(text),
+                KeywordCase::Upper => out.push_str(&text.to_ascii_uppercase()),
+                KeywordCase::Lower => out.push_str(&text.to_ascii_lowercase()),
+                KeywordCase::Pascal => out.push_str(&pascal_case_keyword(text)),
+            }
         }
         prev_kind = Some(kind);
     }
@@ -778,6 +1055,24 @@ fn format_line_tokens(
     out
 }
 
+/// Capitalizes each `_`-separated segment (`END_IF` -> `End_If`).
+fn pascal_case_keyword(text: &str) -> String {
+    text.split('_')
+        .map(|segment| {
+            let mut chars = segment.chars();
+            match chars.next() {
+                Some(first) => {
+                    let mut part = first.to_ascii_uppercase().to_string();
+                    part.push_str(&chars.as_str().to_ascii_lowercase());
+                    part
+                }
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
 fn should_glue(prev: TokenKind, current: TokenKind, spacing_style: SpacingStyle) -> bool {
     if spacing_style == SpacingStyle::Compact
         && (is_symbolic_operator(prev) || is_symbolic_operator(current))
@@ -829,6 +1124,11 @@ fn should_glue(prev: TokenKind, current: TokenKind, spacing_style: SpacingStyle)
         return true;
     }
 
+    // Repeated array initializer elements: `3(0)`.
+    if current == TokenKind::LParen && prev == TokenKind::IntLiteral {
+        return true;
+    }
+
     false
 }
 
@@ -869,11 +1169,26 @@ fn find_type_colon(line: &str) -> Option<usize> {
     None
 }
 
-fn align_var_block_colons(
+/// Finds the `AT` keyword of a located declaration (`name AT %IX0.0 : BOOL`).
+fn find_at_keyword(line: &str) -> Option<usize> {
+    let bytes = line.as_bytes();
+    (1..bytes.len().saturating_sub(3)).find(|&idx| {
+        bytes[idx - 1] == b' '
+            && bytes[idx].eq_ignore_ascii_case(&b'a')
+            && bytes[idx + 1].eq_ignore_ascii_case(&b't')
+            && bytes[idx + 2] == b' '
+            && bytes[idx + 3] == b'%'
+    })
+}
+
+/// Pads consecutive VAR block lines so the marked columns line up. Returns
+/// `true` when any line changed.
+fn align_var_block_columns(
     lines: &mut [String],
     line_in_var_block: &[bool],
-    line_colon_index: &[Option<usize>],
-) {
+    line_column_index: &[Option<usize>],
+) -> bool {
+    let mut changed = false;
     let mut i = 0usize;
     while i < lines.len() {
         if !line_in_var_block[i] {
@@ -881,7 +1196,7 @@ fn align_var_block_colons(
             continue;
         }
 
-        while i < lines.len() && line_in_var_block[i] && line_colon_index[i].is_none() {
+        while i < lines.len() && line_in_var_block[i] && line_column_index[i].is_none() {
             i += 1;
         }
         if i >= lines.len() || !line_in_var_block[i] {
@@ -889,38 +1204,40 @@ fn align_var_block_colons(
         }
 
         let start = i;
-        let mut max_colon = 0usize;
+        let mut max_column = 0usize;
         while i < lines.len() && line_in_var_block[i] {
-            let Some(colon_idx) = line_colon_index[i] else {
+            let Some(column_idx) = line_column_index[i] else {
                 break;
             };
-            max_colon = max_colon.max(colon_idx);
+            max_column = max_column.max(column_idx);
             i += 1;
         }
 
-        if max_colon == 0 {
+        if max_column == 0 {
             continue;
         }
 
         for idx in start..i {
-            let Some(colon_idx) = line_colon_index[idx] else {
+            let Some(column_idx) = line_column_index[idx] else {
                 continue;
             };
-            if colon_idx >= max_colon {
+            if column_idx >= max_column {
                 continue;
             }
-            let pad = max_colon - colon_idx;
+            let pad = max_column - column_idx;
             let line = &lines[idx];
-            if colon_idx > line.len() {
+            if column_idx > line.len() {
                 continue;
             }
             let mut updated = String::with_capacity(line.len() + pad);
-            updated.push_str(&line[..colon_idx]);
+            updated.push_str(&line[..column_idx]);
             updated.extend(std::iter::repeat_n(' ', pad));
-            updated.push_str(&line[colon_idx..]);
+            updated.push_str(&line[column_idx..]);
             lines[idx] = updated;
+            changed = true;
         }
     }
+    changed
 }
 
 struct LineFormatMasks<'a> {
@@ -929,6 +1246,8 @@ struct LineFormatMasks<'a> {
     has_line_comment: &'a [bool],
     has_pragma: &'a [bool],
     has_string_literal: &'a [bool],
+    fmt_off: &'a [bool],
+    config_decl: &'a [bool],
 }
 
 impl<'a> LineFormatMasks<'a> {
@@ -937,18 +1256,24 @@ impl<'a> LineFormatMasks<'a> {
     }
 
     fn skip_alignment(&self, idx: usize) -> bool {
+        self.skip_wrapping(idx) || self.config_decl.get(idx).copied().unwrap_or(false)
+    }
+
+    fn skip_wrapping(&self, idx: usize) -> bool {
         self.in_block_comment.get(idx).copied().unwrap_or(false)
             || self.has_line_comment.get(idx).copied().unwrap_or(false)
             || self.has_pragma.get(idx).copied().unwrap_or(false)
             || self.has_string_literal.get(idx).copied().unwrap_or(false)
-    }
-
-    fn skip_wrapping(&self, idx: usize) -> bool {
-        self.skip_alignment(idx)
+            || self.fmt_off.get(idx).copied().unwrap_or(false)
     }
 }
 
-fn align_assignment_ops(lines: &mut [String], masks: &LineFormatMasks<'_>) {
+fn align_assignment_ops(
+    lines: &mut [String],
+    masks: &LineFormatMasks<'_>,
+    align_statements: bool,
+    align_var_blocks: bool,
+) {
     let mut i = 0usize;
     while i < lines.len() {
         if masks.skip_alignment(i) {
@@ -962,10 +1287,11 @@ fn align_assignment_ops(lines: &mut [String], masks: &LineFormatMasks<'_>) {
         };
 
         let start = i;
+        let in_var_block = masks.in_var_block(start);
         i += 1;
         while i < lines.len() {
             let line = &lines[i];
-            if masks.skip_alignment(i) {
+            if masks.skip_alignment(i) || masks.in_var_block(i) != in_var_block {
                 break;
             }
             if leading_whitespace(line) != indent {
@@ -978,6 +1304,14 @@ fn align_assignment_ops(lines: &mut [String], masks: &LineFormatMasks<'_>) {
             i += 1;
         }
 
+        let enabled = if in_var_block {
+            align_var_blocks
+        } else {
+            align_statements
+        };
+        if !enabled {
+            continue;
+        }
         for line in lines.iter_mut().take(i).skip(start) {
             if let Some(op_idx) = find_assignment_op(line) {
                 if op_idx < max_op {
@@ -1029,6 +1363,10 @@ fn wrap_long_lines(
             output.push(line.clone());
             continue;
         }
+        if let Some(wrapped) = wrap_call_arguments(line, indent_unit, max_len) {
+            output.extend(wrapped);
+            continue;
+        }
         let indent = leading_whitespace(line);
         let continuation = format!("{indent}{indent_unit}");
         let parts: Vec<&str> = line.split(',').collect();
@@ -1046,9 +1384,91 @@ fn wrap_long_lines(
     output
 }
 
+/// Wraps the first call on `line` with two or more arguments, one argument
+/// per line. Arguments are aligned after the open parenthesis when that fits
+/// within `max_len`; otherwise they move to a continuation indent and the
+/// closing parenthesis goes on its own line.
+fn wrap_call_arguments(line: &str, indent_unit: &str, max_len: usize) -> Option<Vec<String>> {
+    let bytes = line.as_bytes();
+    let (open, close, args) = (0..bytes.len()).find_map(|idx| {
+        if bytes[idx] != b'(' || idx == 0 {
+            return None;
+        }
+        let prev = bytes[idx - 1];
+        if !(prev.is_ascii_alphanumeric() || prev == b'_') {
+            return None;
+        }
+        let (close, args) = split_call_arguments(line, idx)?;
+        (args.len() > 1).then_some((idx, close, args))
+    })?;
+
+    let head = &line[..=open];
+    let tail = &line[close..];
+    let last = args.len() - 1;
+
+    let align = " ".repeat(head.len());
+    let aligned: Vec<String> = args
+        .iter()
+        .enumerate()
+        .map(|(idx, arg)| {
+            let prefix = if idx == 0 { head } else { align.as_str() };
+            let suffix = if idx == last { tail } else { "," };
+            format!("{prefix}{arg}{suffix}")
+        })
+        .collect();
+    if aligned.iter().all(|wrapped| wrapped.len() <= max_len) {
+        return Some(aligned);
+    }
+
+    let indent = leading_whitespace(line);
+    let mut wrapped = Vec::with_capacity(args.len() + 2);
+    wrapped.push(head.to_string());
+    for (idx, arg) in args.iter().enumerate() {
+        let suffix = if idx == last { "" } else { "," };
+        wrapped.push(format!("{indent}{indent_unit}{arg}{suffix}"));
+    }
+    wrapped.push(format!("{indent}{tail}"));
+    Some(wrapped)
+}
+
+/// Splits the arguments of the parenthesized list opening at `open`, returning
+/// the offset of the matching `)` and the trimmed top-level arguments.
+fn split_call_arguments(line: &str, open: usize) -> Option<(usize, Vec<&str>)> {
+    let bytes = line.as_bytes();
+    let mut depth = 0usize;
+    let mut arg_start = open + 1;
+    let mut args = Vec::new();
+    for (idx, &byte) in bytes.iter().enumerate().skip(open) {
+        match byte {
+            b'(' | b'[' => depth += 1,
+            b')' | b']' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    if byte != b')' {
+                        return None;
+                    }
+                    let arg = line[arg_start..idx].trim();
+                    if !arg.is_empty() {
+                        args.push(arg);
+                    }
+                    return Some((idx, args));
+                }
+            }
+            b',' if depth == 1 => {
+                args.push(line[arg_start..idx].trim());
+                arg_start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{format_document, EndKeywordStyle, FormatConfig, KeywordCase, SpacingStyle};
+    use super::{
+        format_document, EndKeywordStyle, FormatConfig, KeywordCase, SpacingStyle, VarBlockIndent,
+    };
 
     #[test]
     fn format_document_normalizes_spacing() {
@@ -1057,11 +1477,14 @@ mod tests {
             indent_width: 4,
             insert_spaces: true,
             keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
             align_var_decl_colons: true,
+            align_var_assignments: true,
             align_assignments: true,
             max_line_length: None,
             spacing_style: SpacingStyle::Spaced,
             end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
         };
         let formatted = format_document(source, &config);
         assert!(formatted.contains("x := 1 + 2;"));
@@ -1077,11 +1500,14 @@ mod tests {
             indent_width: 4,
             insert_spaces: true,
             keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
             align_var_decl_colons: true,
+            align_var_assignments: true,
             align_assignments: true,
             max_line_length: None,
             spacing_style: SpacingStyle::Spaced,
             end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
         };
         let formatted = format_document(source, &config);
         let mut lines = formatted.lines();
@@ -1103,11 +1529,14 @@ mod tests {
             indent_width: 4,
             insert_spaces: true,
             keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
             align_var_decl_colons: true,
+            align_var_assignments: true,
             align_assignments: true,
             max_line_length: None,
             spacing_style: SpacingStyle::Spaced,
             end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
         };
         let formatted = format_document(source, &config);
         println!("{formatted}");
@@ -1129,11 +1558,14 @@ mod tests {
             indent_width: 4,
             insert_spaces: true,
             keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
             align_var_decl_colons: true,
+            align_var_assignments: true,
             align_assignments: true,
             max_line_length: None,
             spacing_style: SpacingStyle::Compact,
             end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
         };
         let formatted = format_document(source, &config);
         assert!(formatted.contains("x:INT;"));
@@ -1147,11 +1579,14 @@ mod tests {
             indent_width: 2,
             insert_spaces: true,
             keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
             align_var_decl_colons: true,
+            align_var_assignments: true,
             align_assignments: true,
             max_line_length: None,
             spacing_style: SpacingStyle::Spaced,
             end_keyword_style: EndKeywordStyle::Indented,
+            indent_regions: false,
         };
         let formatted = format_document(source, &config);
         let lines: Vec<&str> = formatted.lines().collect();
@@ -1172,11 +1607,14 @@ mod tests {
             indent_width: 4,
             insert_spaces: true,
             keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
             align_var_decl_colons: true,
+            align_var_assignments: true,
             align_assignments: true,
             max_line_length: None,
             spacing_style: SpacingStyle::Spaced,
             end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
         };
         let formatted = format_document(source, &config);
         assert!(formatted.contains("    x:=1  {PRAGMA}  y:=2;"));
@@ -1189,11 +1627,14 @@ mod tests {
             indent_width: 4,
             insert_spaces: true,
             keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
             align_var_decl_colons: true,
+            align_var_assignments: true,
             align_assignments: true,
             max_line_length: Some(20),
             spacing_style: SpacingStyle::Spaced,
             end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
         };
         let formatted = format_document(source, &config);
         assert!(formatted.contains("msg := 'a,b,c,d,e,f';"));
@@ -1211,4 +1652,199 @@ mod tests {
             longer_line.find(":=").unwrap()
         );
     }
+
+    #[test]
+    fn format_document_preserves_fmt_off_regions() {
+        let source = "PROGRAM Test\nVAR\n    x: INT;\nEND_VAR\n{fmt:off}\nx:=1+2;   // keep\n  y  :=  3;\n{fmt:on}\nx:=4;\nEND_PROGRAM\n";
+        let config = FormatConfig {
+            indent_width: 4,
+            insert_spaces: true,
+            keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
+            align_var_decl_colons: true,
+            align_var_assignments: true,
+            align_assignments: true,
+            max_line_length: None,
+            spacing_style: SpacingStyle::Spaced,
+            end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
+        };
+        let formatted = format_document(source, &config);
+        assert!(formatted.contains("\n{fmt:off}\nx:=1+2;   // keep\n  y  :=  3;\n{fmt:on}\n"));
+        assert!(formatted.contains("\n    x := 4;\n"));
+    }
+
+    #[test]
+    fn format_document_pascal_case_keywords() {
+        let source = "program Test\nif x then\nx := 1;\nend_if\nend_program\n";
+        let config = FormatConfig {
+            indent_width: 4,
+            insert_spaces: true,
+            keyword_case: KeywordCase::Pascal,
+            var_block_indent: VarBlockIndent::Indented,
+            align_var_decl_colons: true,
+            align_var_assignments: true,
+            align_assignments: true,
+            max_line_length: None,
+            spacing_style: SpacingStyle::Spaced,
+            end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
+        };
+        let formatted = format_document(source, &config);
+        assert!(formatted.starts_with("Program Test\n"));
+        assert!(formatted.contains("    If x Then\n"));
+        assert!(formatted.contains("    End_If\n"));
+        assert!(formatted.ends_with("End_Program\n"));
+    }
+
+    #[test]
+    fn format_document_flush_var_blocks() {
+        let source = "PROGRAM Test\nVAR\nx : INT;\nEND_VAR\nx := 1;\nEND_PROGRAM\n";
+        let config = FormatConfig {
+            indent_width: 4,
+            insert_spaces: true,
+            keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Flush,
+            align_var_decl_colons: true,
+            align_var_assignments: true,
+            align_assignments: true,
+            max_line_length: None,
+            spacing_style: SpacingStyle::Spaced,
+            end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
+        };
+        let formatted = format_document(source, &config);
+        assert_eq!(
+            formatted,
+            "PROGRAM Test\nVAR\n    x: INT;\nEND_VAR\n    x := 1;\nEND_PROGRAM\n"
+        );
+    }
+
+    #[test]
+    fn format_document_can_skip_var_initializer_alignment() {
+        let source = "PROGRAM Test\nVAR\n    a : INT := 1;\n    b : INT := 2;\n    longer : LREAL := 3.0;\nEND_VAR\n    a := 1;\n    longer := 2;\nEND_PROGRAM\n";
+        let config = FormatConfig {
+            indent_width: 4,
+            insert_spaces: true,
+            keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
+            align_var_decl_colons: true,
+            align_var_assignments: false,
+            align_assignments: true,
+            max_line_length: None,
+            spacing_style: SpacingStyle::Spaced,
+            end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
+        };
+        let formatted = format_document(source, &config);
+        let lines: Vec<&str> = formatted.lines().collect();
+        assert_eq!(lines[2], "        a     : INT := 1;");
+        assert_eq!(lines[6], "    a      := 1;");
+    }
+
+    #[test]
+    fn format_document_spaces_structured_initializers() {
+        let source = "PROGRAM Test\nVAR\npos:Position:=(x:=1.0,y:=2.0);\ngrid:ARRAY[1..4] OF INT:=[2 (1),2(0)];\nEND_VAR\nEND_PROGRAM\n";
+        let config = FormatConfig {
+            indent_width: 4,
+            insert_spaces: true,
+            keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
+            align_var_decl_colons: false,
+            align_var_assignments: false,
+            align_assignments: false,
+            max_line_length: None,
+            spacing_style: SpacingStyle::Spaced,
+            end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
+        };
+        let formatted = format_document(source, &config);
+        assert!(formatted.contains(":= (x := 1.0, y := 2.0);"));
+        assert!(formatted.contains(":= [2(1), 2(0)];"));
+    }
+
+    #[test]
+    fn format_document_wraps_call_arguments_smartly() {
+        let source = "PROGRAM Test\n    result := Scale(RawValue, MinRaw, MaxRaw, Limit(0, Value, 10));\nEND_PROGRAM\n";
+        let config = FormatConfig {
+            indent_width: 4,
+            insert_spaces: true,
+            keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
+            align_var_decl_colons: true,
+            align_var_assignments: true,
+            align_assignments: true,
+            max_line_length: Some(50),
+            spacing_style: SpacingStyle::Spaced,
+            end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
+        };
+        let formatted = format_document(source, &config);
+        let lines: Vec<&str> = formatted.lines().collect();
+        assert_eq!(lines[1], "    result := Scale(RawValue,");
+        assert_eq!(lines[2], "                    MinRaw,");
+        assert_eq!(lines[4], "                    Limit(0, Value, 10));");
+
+        let narrow = FormatConfig {
+            max_line_length: Some(30),
+            ..config
+        };
+        let formatted = format_document(source, &narrow);
+        let lines: Vec<&str> = formatted.lines().collect();
+        assert_eq!(lines[1], "    result := Scale(");
+        assert_eq!(lines[2], "        RawValue,");
+        assert_eq!(lines[5], "        Limit(0, Value, 10)");
+        assert_eq!(lines[6], "    );");
+    }
+
+    #[test]
+    fn format_document_configuration_with_var_config() {
+        let source = "CONFIGURATION Plant\nRESOURCE Cpu ON PLC\nTASK Fast(INTERVAL:=T#10ms,PRIORITY:=1);\nTASK Background(INTERVAL:=T#100ms,PRIORITY:=5);\nPROGRAM Line1 WITH Fast:Conveyor;\nPROGRAM Logger WITH Background:Log;\nEND_RESOURCE\nVAR_GLOBAL\nstart AT %IX0.0:BOOL;\nmotor_speed AT %QW10:INT;\nEND_VAR\nVAR_CONFIG\nCpu.Line1.run AT %QX0.1:BOOL;\nCpu.Logger.count AT %MW100:DINT;\nEND_VAR\nEND_CONFIGURATION\n";
+        let config = FormatConfig {
+            indent_width: 4,
+            insert_spaces: true,
+            keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
+            align_var_decl_colons: true,
+            align_var_assignments: true,
+            align_assignments: true,
+            max_line_length: None,
+            spacing_style: SpacingStyle::Spaced,
+            end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: false,
+        };
+        let formatted = format_document(source, &config);
+        let expected = "CONFIGURATION Plant\n    RESOURCE Cpu ON PLC\n        TASK Fast(INTERVAL := T#10ms, PRIORITY := 1);\n        TASK Background(INTERVAL := T#100ms, PRIORITY := 5);\n        PROGRAM Line1 WITH Fast: Conveyor;\n        PROGRAM Logger WITH Background: Log;\n    END_RESOURCE\n    VAR_GLOBAL\n        start       AT %IX0.0: BOOL;\n        motor_speed AT %QW10 : INT;\n    END_VAR\n    VAR_CONFIG\n        Cpu.Line1.run    AT %QX0.1: BOOL;\n        Cpu.Logger.count AT %MW100: DINT;\n    END_VAR\nEND_CONFIGURATION\n";
+        assert_eq!(formatted, expected);
+        assert_eq!(format_document(&formatted, &config), formatted);
+    }
+
+    #[test]
+    fn format_document_indents_regions_when_dialect_allows() {
+        let source =
+            "PROGRAM Test\nVAR\nx:INT;\nEND_VAR\nREGION Init\nx:=1;\nEND_REGION\nEND_PROGRAM\n";
+        let mut config = FormatConfig {
+            indent_width: 4,
+            insert_spaces: true,
+            keyword_case: KeywordCase::Preserve,
+            var_block_indent: VarBlockIndent::Indented,
+            align_var_decl_colons: true,
+            align_var_assignments: true,
+            align_assignments: true,
+            max_line_length: None,
+            spacing_style: SpacingStyle::Spaced,
+            end_keyword_style: EndKeywordStyle::Aligned,
+            indent_regions: true,
+        };
+        let formatted = format_document(source, &config);
+        assert_eq!(
+            formatted,
+            "PROGRAM Test\n    VAR\n        x: INT;\n    END_VAR\n    REGION Init\n        x := 1;\n    END_REGION\nEND_PROGRAM\n"
+        );
+        assert_eq!(format_document(&formatted, &config), formatted);
+
+        config.indent_regions = false;
+        let formatted = format_document(source, &config);
+        assert!(formatted.contains("\n    REGION Init\n    x := 1;\n    END_REGION\n"));
+    }
 }
@@ -99,6 +99,9 @@ pub(crate) fn semantic_tokens_to_lsp(
             trust_ide::SemanticTokenType::Operator => 10,
             trust_ide::SemanticTokenType::EnumMember => 11,
             trust_ide::SemanticTokenType::Namespace => 12,
+            trust_ide::SemanticTokenType::DirectAddress => 13,
+            trust_ide::SemanticTokenType::Task => 14,
+            trust_ide::SemanticTokenType::Pragma => 15,
         };
 
         let mut modifiers = 0u32;
@@ -117,6 +120,21 @@ pub(crate) fn semantic_tokens_to_lsp(
         if token.modifiers.modification {
             modifiers |= 1 << 4;
         }
+        if token.modifiers.input {
+            modifiers |= 1 << 5;
+        }
+        if token.modifiers.output {
+            modifiers |= 1 << 6;
+        }
+        if token.modifiers.memory {
+            modifiers |= 1 << 7;
+        }
+        if token.modifiers.duration {
+            modifiers |= 1 << 8;
+        }
+        if token.modifiers.typed {
+            modifiers |= 1 << 9;
+        }
 
         let delta_line = line - prev_line;
         let delta_start = if delta_line == 0 {
@@ -4,24 +4,37 @@
 
 mod commands;
 mod config;
+mod config_files;
 mod context;
 mod diagnostics;
+mod direct_addresses;
 mod features;
 mod formatting;
 mod lsp_utils;
+mod outline;
 mod progress;
 mod refresh;
+mod rename_config;
+mod root_conflicts;
 mod runtime_values;
+mod status;
 mod sync;
 mod workspace;
 
 #[cfg(test)]
 pub(crate) use commands::namespace_move_workspace_edit;
 pub use commands::{
-    execute_command, HMI_BINDINGS_COMMAND, HMI_INIT_COMMAND, MOVE_NAMESPACE_COMMAND,
-    PROJECT_INFO_COMMAND,
+    execute_command, CROSS_REFERENCE_COMMAND, DEAD_CODE_COMMAND, EVALUATE_SELECTION_COMMAND,
+    FORCED_VARIABLES_COMMAND, FORCE_VARIABLE_COMMAND, HMI_BINDINGS_COMMAND, HMI_INIT_COMMAND,
+    LIVE_VALUES_COMMAND, MOVE_NAMESPACE_COMMAND, PROJECT_GRAPH_COMMAND, PROJECT_INFO_COMMAND,
+    REFERENCE_ACCESS_COMMAND, STACK_USAGE_COMMAND, TRACE_DRIVERS_COMMAND, UNFORCE_VARIABLE_COMMAND,
+    WCET_COMMAND,
+};
+#[cfg(test)]
+pub(crate) use diagnostics::workspace_diagnostic;
+pub(crate) use diagnostics::{
+    collect_workspace_diagnostics, document_diagnostic, workspace_diagnostic_with_progress,
 };
-pub(crate) use diagnostics::{document_diagnostic, workspace_diagnostic};
 #[cfg(test)]
 pub(crate) use features::completion_with_ticket_for_tests;
 pub use features::{
@@ -33,6 +46,7 @@ pub use features::{
     semantic_tokens_full_delta, semantic_tokens_range, signature_help, type_hierarchy_subtypes,
     type_hierarchy_supertypes, workspace_symbol_with_progress,
 };
+pub(crate) use formatting::format_source;
 pub use formatting::{formatting, on_type_formatting, range_formatting};
 pub use refresh::{refresh_diagnostics, refresh_semantic_tokens};
 pub use sync::{did_change, did_close, did_open, did_save};
@@ -41,6 +55,7 @@ pub use workspace::{
     index_workspace_background_with_refresh, register_file_watchers, register_type_hierarchy,
     will_rename_files,
 };
+pub(crate) use workspace::{index_project_blocking, project_source_files};
 
 #[cfg(test)]
 pub use workspace::index_workspace;
@@ -0,0 +1,280 @@
+//! Outline and folding entries that come from the syntax tree rather than the
+//! symbol table: ACTION blocks, regions, and the branches of large CASE statements.
+//!
+//! Symbols are returned flat with a container name; clients nest them by range, so a
+//! CASE branch shows up under its CASE statement, which sits under its POU.
+
+use tower_lsp::lsp_types::{
+    FoldingRange, FoldingRangeKind, Location, Range, SymbolInformation, SymbolKind, Url,
+};
+use trust_syntax::syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+use super::lsp_utils::{offset_to_line_col, offset_to_position};
+
+/// CASE statements with at least this many branches get outline entries.
+const OUTLINE_CASE_MIN_BRANCHES: usize = 4;
+
+/// Syntax-only outline symbols for `uri`. `vendor_regions` enables `REGION` /
+/// `END_REGION` markers; `{region}` / `{endregion}` pragmas are always recognized.
+pub(crate) fn outline_symbols(
+    uri: &Url,
+    source: &str,
+    root: &SyntaxNode,
+    vendor_regions: bool,
+) -> Vec<SymbolInformation> {
+    let mut symbols = Vec::new();
+    let mut push = |name: String, kind: SymbolKind, start: u32, end: u32, container| {
+        #[allow(deprecated)]
+        symbols.push(SymbolInformation {
+            name,
+            kind,
+            location: Location {
+                uri: uri.clone(),
+                range: Range {
+                    start: offset_to_position(source, start),
+                    end: offset_to_position(source, end),
+                },
+            },
+            container_name: container,
+            tags: None,
+            deprecated: None,
+        });
+    };
+
+    for node in root.descendants() {
+        match node.kind() {
+            SyntaxKind::Action => {
+                let Some(name) = node_name(&node) else {
+                    continue;
+                };
+                let (start, end) = token_bounds(&node);
+                push(
+                    format!("{name} (ACTION)"),
+                    SymbolKind::METHOD,
+                    start,
+                    end,
+                    container_name(&node),
+                );
+            }
+            SyntaxKind::CaseStmt => {
+                let branches: Vec<_> = node
+                    .children()
+                    .filter(|child| {
+                        matches!(
+                            child.kind(),
+                            SyntaxKind::CaseBranch | SyntaxKind::ElseBranch
+                        )
+                    })
+                    .collect();
+                if branches.len() < OUTLINE_CASE_MIN_BRANCHES {
+                    continue;
+                }
+                let selector = node
+                    .children()
+                    .next()
+                    .filter(|expr| expr.kind() != SyntaxKind::CaseBranch)
+                    .map(|expr| collapse_whitespace(&expr.text().to_string()))
+                    .unwrap_or_default();
+                let case_name = format!("CASE {selector}");
+                let (start, end) = token_bounds(&node);
+                push(
+                    case_name.clone(),
+                    SymbolKind::ENUM,
+                    start,
+                    end,
+                    container_name(&node),
+                );
+                for branch in branches {
+                    let (start, end) = token_bounds(&branch);
+                    push(
+                        case_branch_label(&branch),
+                        SymbolKind::KEY,
+                        start,
+                        end,
+                        Some(case_name.clone()),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for region in regions(root, vendor_regions) {
+        let container = region.start.parent().and_then(|node| container_name(&node));
+        push(
+            region.title,
+            SymbolKind::PACKAGE,
+            u32::from(region.start.text_range().start()),
+            u32::from(region.end.text_range().end()),
+            container,
+        );
+    }
+
+    symbols
+}
+
+/// Folding ranges for `{region}` / `{endregion}` pragmas.
+pub(crate) fn pragma_region_folding_ranges(source: &str, root: &SyntaxNode) -> Vec<FoldingRange> {
+    regions(root, false)
+        .into_iter()
+        .filter_map(|region| {
+            let (start_line, _) =
+                offset_to_line_col(source, region.start.text_range().start().into());
+            let (end_line, _) = offset_to_line_col(source, region.end.text_range().start().into());
+            (end_line > start_line).then_some(FoldingRange {
+                start_line,
+                start_character: None,
+                end_line,
+                end_character: None,
+                kind: Some(FoldingRangeKind::Region),
+                collapsed_text: None,
+            })
+        })
+        .collect()
+}
+
+struct Region {
+    title: String,
+    start: SyntaxToken,
+    end: SyntaxToken,
+}
+
+enum RegionMarker {
+    Open(String),
+    Close,
+}
+
+/// Matched region markers, innermost first. Unbalanced markers are ignored.
+fn regions(root: &SyntaxNode, vendor_regions: bool) -> Vec<Region> {
+    let mut open: Vec<(String, SyntaxToken)> = Vec::new();
+    let mut regions = Vec::new();
+    for token in root
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+    {
+        let marker = match token.kind() {
+            SyntaxKind::Pragma => pragma_region_marker(token.text()),
+            SyntaxKind::RegionMarker if vendor_regions => Some(vendor_region_marker(token.text())),
+            _ => None,
+        };
+        match marker {
+            Some(RegionMarker::Open(title)) => open.push((title, token)),
+            Some(RegionMarker::Close) => {
+                if let Some((title, start)) = open.pop() {
+                    regions.push(Region {
+                        title,
+                        start,
+                        end: token,
+                    });
+                }
+            }
+            None => {}
+        }
+    }
+    regions
+}
+
+fn pragma_region_marker(text: &str) -> Option<RegionMarker> {
+    let inner = text.strip_prefix('{')?.strip_suffix('}')?.trim();
+    let keyword_end = inner
+        .find(|ch: char| ch.is_whitespace())
+        .unwrap_or(inner.len());
+    let (keyword, title) = inner.split_at(keyword_end);
+    if keyword.eq_ignore_ascii_case("region") {
+        Some(RegionMarker::Open(region_title(title)))
+    } else if keyword.eq_ignore_ascii_case("endregion") {
+        Some(RegionMarker::Close)
+    } else {
+        None
+    }
+}
+
+fn vendor_region_marker(text: &str) -> RegionMarker {
+    if text.to_ascii_uppercase().starts_with("END_REGION") {
+        return RegionMarker::Close;
+    }
+    RegionMarker::Open(region_title(text.get("REGION".len()..).unwrap_or_default()))
+}
+
+fn region_title(text: &str) -> String {
+    let title = text.trim().trim_matches('"').trim();
+    if title.is_empty() {
+        "region".to_string()
+    } else {
+        title.to_string()
+    }
+}
+
+/// Branch labels as written, e.g. `10, 20..25` or `ELSE`.
+fn case_branch_label(branch: &SyntaxNode) -> String {
+    if branch.kind() == SyntaxKind::ElseBranch {
+        return "ELSE".to_string();
+    }
+    let mut label = String::new();
+    for token in branch
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .take_while(|token| {
+            token.kind() != SyntaxKind::Colon || token.parent().as_ref() != Some(branch)
+        })
+        .filter(|token| !token.kind().is_trivia())
+    {
+        label.push_str(token.text());
+        if token.kind() == SyntaxKind::Comma {
+            label.push(' ');
+        }
+    }
+    label
+}
+
+/// Name of the closest enclosing POU, method, property, or action.
+fn container_name(node: &SyntaxNode) -> Option<String> {
+    node.ancestors()
+        .skip(1)
+        .find(|ancestor| {
+            matches!(
+                ancestor.kind(),
+                SyntaxKind::Program
+                    | SyntaxKind::Function
+                    | SyntaxKind::FunctionBlock
+                    | SyntaxKind::Class
+                    | SyntaxKind::Method
+                    | SyntaxKind::Property
+                    | SyntaxKind::Action
+            )
+        })
+        .and_then(|ancestor| node_name(&ancestor))
+}
+
+fn node_name(node: &SyntaxNode) -> Option<String> {
+    node.children()
+        .find(|child| child.kind() == SyntaxKind::Name)?
+        .children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find(|token| !token.kind().is_trivia())
+        .map(|token| token.text().to_string())
+}
+
+/// Offsets of the first and last non-trivia tokens of `node`.
+fn token_bounds(node: &SyntaxNode) -> (u32, u32) {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !token.kind().is_trivia());
+    let first = tokens.next();
+    let last = tokens.last().or_else(|| first.clone());
+    match (first, last) {
+        (Some(first), Some(last)) => (
+            u32::from(first.text_range().start()),
+            u32::from(last.text_range().end()),
+        ),
+        _ => {
+            let range = node.text_range();
+            (u32::from(range.start()), u32::from(range.end()))
+        }
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
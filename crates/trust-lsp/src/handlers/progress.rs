@@ -3,12 +3,15 @@
 use serde::Serialize;
 use serde_json::json;
 use tower_lsp::lsp_types::notification::Notification;
+use tower_lsp::lsp_types::request::WorkDoneProgressCreate;
 use tower_lsp::lsp_types::{
     ProgressParams, ProgressParamsValue, ProgressToken, WorkDoneProgress, WorkDoneProgressBegin,
-    WorkDoneProgressEnd, WorkDoneProgressReport,
+    WorkDoneProgressCreateParams, WorkDoneProgressEnd, WorkDoneProgressReport,
 };
 use tower_lsp::{lsp_types::notification::Progress, Client};
 
+use crate::state::ServerState;
+
 #[derive(Debug)]
 enum RawProgress {}
 
@@ -17,6 +20,28 @@ impl Notification for RawProgress {
     const METHOD: &'static str = "$/progress";
 }
 
+/// Asks the client to create a server-initiated progress token.
+///
+/// Returns `None` when the client does not support work-done progress or
+/// rejects the request.
+pub async fn create_work_done_token(
+    client: &Client,
+    state: &ServerState,
+    id: String,
+) -> Option<ProgressToken> {
+    if !state.work_done_progress() {
+        return None;
+    }
+    let token = ProgressToken::String(id);
+    client
+        .send_request::<WorkDoneProgressCreate>(WorkDoneProgressCreateParams {
+            token: token.clone(),
+        })
+        .await
+        .ok()?;
+    Some(token)
+}
+
 pub async fn send_work_done_begin(
     client: &Client,
     token: &Option<ProgressToken>,
@@ -0,0 +1,370 @@
+//! Rename support for variable paths in a project's configuration files.
+//!
+//! `runtime.toml`, `hmi.toml`, and the page files under `hmi/` name variables by
+//! access path (`P1.Count`). A rename updates those strings in the same workspace
+//! edit; the edits carry a change annotation so clients preview them before applying.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use text_size::TextRange;
+use tower_lsp::lsp_types::{
+    AnnotatedTextEdit, ChangeAnnotation, DocumentChangeOperation, DocumentChanges, OneOf, Range,
+    RenameFile, ResourceOp, TextDocumentEdit, TextEdit, Url, WorkspaceEdit,
+};
+use trust_ide::rename::{access_path_references, RenameTarget};
+
+use crate::state::{path_to_uri, ServerState};
+
+use super::lsp_utils::{offset_to_position, text_document_identifier_for_edit};
+
+const CONFIG_REFERENCES_ANNOTATION: &str = "config-references";
+
+/// Keys whose string (or array of strings) values are access paths.
+const PATH_VALUE_KEYS: &[&str] = &[
+    "bind", "signals", "allow", "publish", "include", "expose", "variable", "single", "done",
+    "program", "programs",
+];
+
+/// Tables whose keys are access paths.
+const PATH_KEY_TABLES: &[&str] = &["publish_qos", "widgets"];
+
+/// Tables whose string values are access paths.
+const PATH_VALUE_TABLES: &[&str] = &["subscribe"];
+
+/// Edits to the configuration files of the project containing `uri` that rename
+/// `target` to `new_name`.
+pub(crate) fn config_reference_edits(
+    state: &ServerState,
+    uri: &Url,
+    target: RenameTarget,
+    new_name: &str,
+) -> HashMap<Url, Vec<TextEdit>> {
+    let mut changes = HashMap::new();
+    let Some(config) = state.workspace_config_for_uri(uri) else {
+        return changes;
+    };
+    let mut resolved: FxHashMap<String, Vec<TextRange>> = FxHashMap::default();
+    for path in config_files(config.root) {
+        let Some(file_uri) = path_to_uri(&path) else {
+            continue;
+        };
+        let content = match state.get_document(&file_uri) {
+            Some(doc) => doc.content,
+            None => match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(_) => continue,
+            },
+        };
+        let Ok(table) = toml::from_str::<toml::Table>(&content) else {
+            continue;
+        };
+        let mut paths = FxHashSet::default();
+        collect_paths(&table, &mut paths);
+        if paths.is_empty() {
+            continue;
+        }
+
+        let mut edits = Vec::new();
+        for (start, text) in path_literals(&content) {
+            if !paths.contains(text) {
+                continue;
+            }
+            let ranges = resolved.entry(text.to_string()).or_insert_with(|| {
+                state.with_database(|db| access_path_references(db, target, text))
+            });
+            for range in ranges.iter() {
+                let offset = start as u32;
+                edits.push(TextEdit {
+                    range: Range {
+                        start: offset_to_position(&content, offset + u32::from(range.start())),
+                        end: offset_to_position(&content, offset + u32::from(range.end())),
+                    },
+                    new_text: new_name.to_string(),
+                });
+            }
+        }
+        if !edits.is_empty() {
+            changes.insert(file_uri, edits);
+        }
+    }
+    changes
+}
+
+/// Builds the workspace edit of a rename.
+///
+/// Plain `changes` are used when only source files change; configuration file edits
+/// and file renames need `documentChanges`.
+pub(crate) fn rename_workspace_edit(
+    state: &ServerState,
+    changes: HashMap<Url, Vec<TextEdit>>,
+    config_changes: HashMap<Url, Vec<TextEdit>>,
+    file_rename: Option<RenameFile>,
+) -> WorkspaceEdit {
+    if config_changes.is_empty() && file_rename.is_none() {
+        return WorkspaceEdit {
+            changes: Some(changes),
+            document_changes: None,
+            change_annotations: None,
+        };
+    }
+
+    let mut document_changes = document_operations(state, changes, None);
+    let annotations = (!config_changes.is_empty()).then(|| {
+        HashMap::from([(
+            CONFIG_REFERENCES_ANNOTATION.to_string(),
+            ChangeAnnotation {
+                label: "Update configuration references".to_string(),
+                needs_confirmation: Some(true),
+                description: Some(
+                    "Variable paths in runtime.toml and HMI configuration files.".to_string(),
+                ),
+            },
+        )])
+    });
+    document_changes.extend(document_operations(
+        state,
+        config_changes,
+        Some(CONFIG_REFERENCES_ANNOTATION),
+    ));
+    if let Some(rename_op) = file_rename {
+        document_changes.push(DocumentChangeOperation::Op(ResourceOp::Rename(rename_op)));
+    }
+    WorkspaceEdit {
+        changes: None,
+        document_changes: Some(DocumentChanges::Operations(document_changes)),
+        change_annotations: annotations,
+    }
+}
+
+fn document_operations(
+    state: &ServerState,
+    changes: HashMap<Url, Vec<TextEdit>>,
+    annotation: Option<&str>,
+) -> Vec<DocumentChangeOperation> {
+    let mut entries: Vec<_> = changes.into_iter().collect();
+    entries.sort_by_key(|(uri, _)| uri.to_string());
+
+    let mut operations = Vec::new();
+    for (uri, edits) in entries {
+        let text_document = text_document_identifier_for_edit(state, &uri);
+        let edits = edits
+            .into_iter()
+            .map(|text_edit| match annotation {
+                Some(annotation_id) => OneOf::Right(AnnotatedTextEdit {
+                    text_edit,
+                    annotation_id: annotation_id.to_string(),
+                }),
+                None => OneOf::Left(text_edit),
+            })
+            .collect();
+        operations.push(DocumentChangeOperation::Edit(TextDocumentEdit {
+            text_document,
+            edits,
+        }));
+    }
+    operations
+}
+
+/// `runtime.toml`, `hmi.toml`, and `hmi/*.toml` under the project root, in that order.
+fn config_files(root: PathBuf) -> Vec<PathBuf> {
+    let mut files = vec![root.join("runtime.toml"), root.join("hmi.toml")];
+    if let Ok(entries) = std::fs::read_dir(root.join("hmi")) {
+        let mut pages: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        pages.sort();
+        files.extend(pages);
+    }
+    files.retain(|path| path.is_file());
+    files
+}
+
+fn collect_paths(table: &toml::Table, paths: &mut FxHashSet<String>) {
+    for (key, value) in table {
+        if let Some(nested) = value.as_table() {
+            if PATH_KEY_TABLES.contains(&key.as_str()) {
+                paths.extend(nested.keys().cloned());
+            }
+            if PATH_VALUE_TABLES.contains(&key.as_str()) {
+                nested
+                    .values()
+                    .for_each(|value| collect_strings(value, paths));
+            }
+            collect_paths(nested, paths);
+        } else if PATH_VALUE_KEYS.contains(&key.as_str()) {
+            collect_strings(value, paths);
+        } else if let Some(items) = value.as_array() {
+            for nested in items.iter().filter_map(toml::Value::as_table) {
+                collect_paths(nested, paths);
+            }
+        }
+    }
+}
+
+fn collect_strings(value: &toml::Value, paths: &mut FxHashSet<String>) {
+    match value {
+        toml::Value::String(text) => {
+            paths.insert(text.clone());
+        }
+        toml::Value::Array(items) => items.iter().for_each(|item| collect_strings(item, paths)),
+        _ => {}
+    }
+}
+
+/// Single-line TOML strings in path positions as `(content offset, content)`: values
+/// of [`PATH_VALUE_KEYS`] and of [`PATH_VALUE_TABLES`], and keys of
+/// [`PATH_KEY_TABLES`]. Strings with escapes are skipped because their content does
+/// not match the source text.
+fn path_literals(content: &str) -> Vec<(usize, &str)> {
+    let bytes = content.as_bytes();
+    let mut literals = Vec::new();
+    let mut table = "";
+    let mut key = "";
+    let mut last_token = "";
+    let mut depth = 0usize;
+    let mut line_start = true;
+    let mut index = 0;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        match byte {
+            b'\n' => {
+                line_start = true;
+                index += 1;
+                continue;
+            }
+            b' ' | b'\t' | b'\r' => {
+                index += 1;
+                continue;
+            }
+            b'#' => {
+                index = content[index..]
+                    .find('\n')
+                    .map_or(bytes.len(), |end| index + end);
+                continue;
+            }
+            b'[' if depth == 0 && line_start => {
+                let end = content[index..]
+                    .find('\n')
+                    .map_or(bytes.len(), |end| index + end);
+                table = table_header(content, index, end, &mut literals);
+                key = "";
+                index = end;
+                continue;
+            }
+            _ => {}
+        }
+        line_start = false;
+        match byte {
+            b'[' | b'{' => depth += 1,
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            b'=' => key = last_token,
+            b'"' | b'\'' => {
+                let delimiter = if byte == b'"' { "\"\"\"" } else { "'''" };
+                if content[index..].starts_with(delimiter) {
+                    index = content[index + 3..]
+                        .find(delimiter)
+                        .map_or(bytes.len(), |end| index + 3 + end + 3);
+                    continue;
+                }
+                let (start, end, escaped) = literal_bounds(bytes, index);
+                if bytes.get(end) == Some(&byte) {
+                    let text = &content[start..end];
+                    let is_key = content[end + 1..]
+                        .trim_start_matches([' ', '\t'])
+                        .starts_with(['=', '.']);
+                    let is_path = if is_key {
+                        last_token = text;
+                        PATH_KEY_TABLES.contains(&table)
+                            || (depth > 0 && PATH_KEY_TABLES.contains(&key))
+                    } else {
+                        PATH_VALUE_KEYS.contains(&key) || PATH_VALUE_TABLES.contains(&table)
+                    };
+                    if is_path && !escaped {
+                        literals.push((start, text));
+                    }
+                }
+                index = end + 1;
+                continue;
+            }
+            _ if is_bare_key_byte(byte) => {
+                let start = index;
+                while index < bytes.len() && is_bare_key_byte(bytes[index]) {
+                    index += 1;
+                }
+                last_token = &content[start..index];
+                continue;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    literals
+}
+
+/// Parses the table header in `content[start..end]`, returning the last segment of
+/// its name. Quoted segments naming entries of a [`PATH_KEY_TABLES`] table are pushed
+/// to `literals`.
+fn table_header<'a>(
+    content: &'a str,
+    start: usize,
+    end: usize,
+    literals: &mut Vec<(usize, &'a str)>,
+) -> &'a str {
+    let bytes = content.as_bytes();
+    let mut segment = "";
+    let mut index = start;
+    while index < end {
+        let byte = bytes[index];
+        if byte == b'[' {
+            index += 1;
+            continue;
+        }
+        if byte == b']' || byte == b'#' {
+            break;
+        }
+        if byte == b'"' || byte == b'\'' {
+            let (text_start, text_end, escaped) = literal_bounds(bytes, index);
+            if bytes.get(text_end) != Some(&byte) {
+                break;
+            }
+            let text = &content[text_start..text_end];
+            if !escaped && PATH_KEY_TABLES.contains(&segment) {
+                literals.push((text_start, text));
+            }
+            segment = text;
+            index = text_end + 1;
+        } else if is_bare_key_byte(byte) {
+            let text_start = index;
+            while index < end && is_bare_key_byte(bytes[index]) {
+                index += 1;
+            }
+            segment = &content[text_start..index];
+        } else {
+            index += 1;
+        }
+    }
+    segment
+}
+
+/// The content bounds of the single-line string opening at `quote`, and whether it
+/// contains escapes. The end is the closing quote, or the line end if unterminated.
+fn literal_bounds(bytes: &[u8], quote: usize) -> (usize, usize, bool) {
+    let delimiter = bytes[quote];
+    let start = quote + 1;
+    let mut end = start;
+    let mut escaped = false;
+    while end < bytes.len() && bytes[end] != delimiter && bytes[end] != b'\n' {
+        if delimiter == b'"' && bytes[end] == b'\\' {
+            escaped = true;
+            end += 1;
+        }
+        end += 1;
+    }
+    (start, end.min(bytes.len()), escaped)
+}
+
+fn is_bare_key_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-'
+}
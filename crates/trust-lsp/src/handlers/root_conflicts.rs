@@ -0,0 +1,415 @@
+//! Conflicts between public symbols exported by different workspace roots.
+//!
+//! Every file in the workspace shares one symbol database, so two public roots
+//! that declare the same POU or type name silently shadow each other. The
+//! `[workspace].priority` of each root decides which declaration is intended.
+
+use serde_json::{json, Value};
+use smol_str::SmolStr;
+use text_size::TextRange;
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, DiagnosticRelatedInformation,
+    DiagnosticSeverity, Location, NumberOrString, Range, TextEdit, Url, WorkspaceEdit,
+};
+use trust_hir::db::{FileId, SemanticDatabase};
+use trust_hir::diagnostics::DiagnosticCode as HirDiagnosticCode;
+use trust_hir::symbols::{Symbol, SymbolKind, SymbolTable, Visibility};
+use trust_syntax::parser::parse;
+use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
+
+use crate::config::WorkspaceVisibility;
+use crate::state::{Document, ServerState};
+
+use super::lsp_utils::offset_to_position;
+
+/// A public declaration exported by a workspace root.
+#[derive(Debug, Clone)]
+struct RootExport {
+    uri: Url,
+    root: Url,
+    root_label: String,
+    priority: i32,
+    name: SmolStr,
+    qualified: String,
+    range: Range,
+}
+
+/// Reports public declarations in `uri` that another public workspace root also exports.
+pub(crate) fn collect_root_conflict_diagnostics(
+    state: &ServerState,
+    uri: &Url,
+    content: &str,
+    file_id: FileId,
+) -> Vec<Diagnostic> {
+    if state.workspace_configs().len() < 2 {
+        return Vec::new();
+    }
+    let Some((root, config)) = state.workspace_root_for_uri(uri) else {
+        return Vec::new();
+    };
+    if config.workspace.visibility != WorkspaceVisibility::Public {
+        return Vec::new();
+    }
+    let label = root_label(&root, &config.root);
+    let local = file_exports(
+        state,
+        uri,
+        content,
+        file_id,
+        &root,
+        &label,
+        config.workspace.priority,
+    );
+    if local.is_empty() {
+        return Vec::new();
+    }
+
+    let mut others: Vec<RootExport> = Vec::new();
+    for doc in state.documents() {
+        if doc.uri == *uri {
+            continue;
+        }
+        let Some((other_root, other_config)) = state.workspace_root_for_uri(&doc.uri) else {
+            continue;
+        };
+        if other_root == root || other_config.workspace.visibility != WorkspaceVisibility::Public {
+            continue;
+        }
+        let other_label = root_label(&other_root, &other_config.root);
+        others.extend(
+            file_exports(
+                state,
+                &doc.uri,
+                &doc.content,
+                doc.file_id,
+                &other_root,
+                &other_label,
+                other_config.workspace.priority,
+            )
+            .into_iter()
+            .filter(|export| {
+                local
+                    .iter()
+                    .any(|own| own.name.eq_ignore_ascii_case(&export.name))
+            }),
+        );
+    }
+
+    let mut diagnostics = Vec::new();
+    for own in &local {
+        let mut conflicting: Vec<&RootExport> = others
+            .iter()
+            .filter(|other| other.name.eq_ignore_ascii_case(&own.name))
+            .collect();
+        if conflicting.is_empty() {
+            continue;
+        }
+        conflicting.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then_with(|| a.root_label.cmp(&b.root_label))
+                .then_with(|| a.uri.as_str().cmp(b.uri.as_str()))
+        });
+        diagnostics.push(conflict_diagnostic(own, &conflicting));
+    }
+    diagnostics
+}
+
+fn conflict_diagnostic(own: &RootExport, conflicting: &[&RootExport]) -> Diagnostic {
+    let mut roots: Vec<String> = Vec::new();
+    for other in conflicting {
+        let quoted = format!("'{}'", other.root_label);
+        if !roots.contains(&quoted) {
+            roots.push(quoted);
+        }
+    }
+    let top = conflicting[0];
+    let (message, winner) = if own.priority > top.priority {
+        (
+            format!(
+                "'{}' is also exported by workspace root {}; this root (priority {}) takes precedence",
+                own.name,
+                roots.join(", "),
+                own.priority
+            ),
+            Some(own),
+        )
+    } else if own.priority < top.priority {
+        (
+            format!(
+                "'{}' is also exported by workspace root {}; root '{}' (priority {}) takes precedence over this root (priority {})",
+                own.name,
+                roots.join(", "),
+                top.root_label,
+                top.priority,
+                own.priority
+            ),
+            Some(top),
+        )
+    } else {
+        (
+            format!(
+                "'{}' is also exported by workspace root {} with the same priority {}; references are ambiguous",
+                own.name,
+                roots.join(", "),
+                own.priority
+            ),
+            None,
+        )
+    };
+
+    let related = conflicting
+        .iter()
+        .map(|other| DiagnosticRelatedInformation {
+            location: Location {
+                uri: other.uri.clone(),
+                range: other.range,
+            },
+            message: format!(
+                "declared in workspace root '{}' (priority {})",
+                other.root_label, other.priority
+            ),
+        })
+        .collect();
+
+    let candidates: Vec<Value> = std::iter::once(own)
+        .chain(conflicting.iter().copied())
+        .map(|export| {
+            json!({
+                "qualified": export.qualified,
+                "root": export.root_label,
+                "preferred": winner.is_some_and(|winner| winner.root == export.root),
+            })
+        })
+        .collect();
+
+    Diagnostic {
+        range: own.range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(
+            HirDiagnosticCode::WorkspaceSymbolConflict
+                .code()
+                .to_string(),
+        )),
+        source: Some("trust-lsp".to_string()),
+        message,
+        related_information: Some(related),
+        data: Some(json!({
+            "rootConflict": { "name": own.name.as_str(), "candidates": candidates }
+        })),
+        ..Default::default()
+    }
+}
+
+/// Offers to qualify references in the diagnostic's workspace root with the
+/// namespace path of one of the conflicting declarations.
+///
+/// Declarations that share their qualified name cannot be told apart by
+/// qualification, so only candidates with a unique namespaced path are offered.
+pub(crate) fn root_conflict_actions(
+    state: &ServerState,
+    doc: &Document,
+    diagnostic: &Diagnostic,
+) -> Vec<CodeActionOrCommand> {
+    let Some(conflict) = diagnostic
+        .data
+        .as_ref()
+        .and_then(|data| data.get("rootConflict"))
+    else {
+        return Vec::new();
+    };
+    let Some(name) = conflict.get("name").and_then(Value::as_str) else {
+        return Vec::new();
+    };
+    let candidates: Vec<(String, String, bool)> = conflict
+        .get("candidates")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    Some((
+                        item.get("qualified")?.as_str()?.to_string(),
+                        item.get("root")?.as_str()?.to_string(),
+                        item.get("preferred")
+                            .and_then(Value::as_bool)
+                            .unwrap_or(false),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let Some((root, _)) = state.workspace_root_for_uri(&doc.uri) else {
+        return Vec::new();
+    };
+
+    let mut actions = Vec::new();
+    for (qualified, root_label, preferred) in &candidates {
+        let unique = candidates
+            .iter()
+            .filter(|(other, _, _)| other.eq_ignore_ascii_case(qualified))
+            .count()
+            == 1;
+        if !unique || !qualified.contains('.') {
+            continue;
+        }
+        let mut changes: std::collections::HashMap<Url, Vec<TextEdit>> =
+            std::collections::HashMap::new();
+        for other in state.documents() {
+            if state
+                .workspace_root_for_uri(&other.uri)
+                .is_none_or(|(other_root, _)| other_root != root)
+            {
+                continue;
+            }
+            let edits = qualify_reference_edits(state, &other, name, qualified);
+            if !edits.is_empty() {
+                changes.insert(other.uri.clone(), edits);
+            }
+        }
+        if changes.is_empty() {
+            continue;
+        }
+        actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+            title: format!("Qualify references with {qualified} (workspace root '{root_label}')"),
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: Some(vec![diagnostic.clone()]),
+            edit: Some(WorkspaceEdit {
+                changes: Some(changes),
+                document_changes: None,
+                change_annotations: None,
+            }),
+            is_preferred: Some(*preferred),
+            ..Default::default()
+        }));
+    }
+    actions
+}
+
+/// Rewrites unqualified type references and calls to `name` in `doc`.
+///
+/// Files that declare a local symbol with the same name are left alone, since
+/// a plain call there may refer to that local instead.
+fn qualify_reference_edits(
+    state: &ServerState,
+    doc: &Document,
+    name: &str,
+    qualified: &str,
+) -> Vec<TextEdit> {
+    let symbols = state.with_database(|db| db.file_symbols(doc.file_id));
+    let shadowed = symbols.iter().any(|symbol| {
+        symbol.name.eq_ignore_ascii_case(name)
+            && !symbol.range.is_empty()
+            && !is_exported_kind(&symbol.kind)
+    });
+
+    let root = parse(&doc.content).syntax();
+    let mut edits = Vec::new();
+    for node in root.descendants() {
+        let is_reference = match node.kind() {
+            SyntaxKind::Name => node
+                .parent()
+                .is_some_and(|parent| parent.kind() == SyntaxKind::TypeRef),
+            SyntaxKind::NameRef => {
+                !shadowed
+                    && node.parent().is_some_and(|parent| {
+                        parent.kind() == SyntaxKind::CallExpr
+                            && parent.first_child().as_ref() == Some(&node)
+                    })
+            }
+            _ => false,
+        };
+        if !is_reference {
+            continue;
+        }
+        let Some(range) = ident_range(&node, name) else {
+            continue;
+        };
+        edits.push(TextEdit {
+            range: Range {
+                start: offset_to_position(&doc.content, range.start().into()),
+                end: offset_to_position(&doc.content, range.end().into()),
+            },
+            new_text: qualified.to_string(),
+        });
+    }
+    edits
+}
+
+fn ident_range(node: &SyntaxNode, name: &str) -> Option<TextRange> {
+    let mut idents = node
+        .children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == SyntaxKind::Ident);
+    let ident = idents.next()?;
+    if idents.next().is_some() || !ident.text().eq_ignore_ascii_case(name) {
+        return None;
+    }
+    Some(ident.text_range())
+}
+
+fn file_exports(
+    state: &ServerState,
+    uri: &Url,
+    content: &str,
+    file_id: FileId,
+    root: &Url,
+    root_label: &str,
+    priority: i32,
+) -> Vec<RootExport> {
+    let symbols = state.with_database(|db| db.file_symbols(file_id));
+    symbols
+        .iter()
+        .filter(|symbol| !symbol.range.is_empty() && is_exported_kind(&symbol.kind))
+        .filter(|symbol| matches!(symbol.visibility, Visibility::Public))
+        .filter_map(|symbol| {
+            let qualified = qualified_path(&symbols, symbol)?;
+            Some(RootExport {
+                uri: uri.clone(),
+                root: root.clone(),
+                root_label: root_label.to_string(),
+                priority,
+                name: symbol.name.clone(),
+                qualified,
+                range: Range {
+                    start: offset_to_position(content, symbol.range.start().into()),
+                    end: offset_to_position(content, symbol.range.end().into()),
+                },
+            })
+        })
+        .collect()
+}
+
+fn is_exported_kind(kind: &SymbolKind) -> bool {
+    matches!(
+        kind,
+        SymbolKind::Program
+            | SymbolKind::Function { .. }
+            | SymbolKind::FunctionBlock
+            | SymbolKind::Class
+            | SymbolKind::Interface
+            | SymbolKind::Type
+    )
+}
+
+/// Returns the dotted path of a top-level or namespace-level declaration.
+fn qualified_path(symbols: &SymbolTable, symbol: &Symbol) -> Option<String> {
+    let mut parts = vec![symbol.name.to_string()];
+    let mut parent = symbol.parent;
+    while let Some(parent_id) = parent {
+        let namespace = symbols.get(parent_id)?;
+        if !matches!(namespace.kind, SymbolKind::Namespace) {
+            return None;
+        }
+        parts.push(namespace.name.to_string());
+        parent = namespace.parent;
+    }
+    parts.reverse();
+    Some(parts.join("."))
+}
+
+fn root_label(root: &Url, path: &std::path::Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| root.to_string())
+}
@@ -5,6 +5,8 @@ use smol_str::SmolStr;
 use std::io::{BufRead, BufReader, Read, Write};
 use std::net::TcpStream;
 use tracing::{debug, warn};
+use trust_ide::{InlineValueScope, InlineValueTarget};
+use trust_runtime::control::control_error_message;
 
 #[cfg(unix)]
 use std::os::unix::net::UnixStream;
@@ -41,6 +43,8 @@ struct ControlClient {
     seq: u64,
     reader: BufReader<ControlStream>,
     auth: Option<String>,
+    /// Error message of the last rejected request.
+    last_error: Option<String>,
 }
 
 impl ControlClient {
@@ -54,6 +58,7 @@ impl ControlClient {
             seq: 1,
             reader: BufReader::new(stream),
             auth: auth.map(|value| value.to_string()),
+            last_error: None,
         })
     }
 
@@ -110,6 +115,11 @@ impl ControlClient {
         };
         if !response.ok {
             warn!("inlineValue control request kind={} failed", kind);
+            self.last_error = response
+                .error
+                .as_ref()
+                .and_then(control_error_message)
+                .map(str::to_string);
             return None;
         }
         response.result
@@ -155,6 +165,8 @@ impl Write for ControlStream {
 struct ControlResponse {
     ok: bool,
     result: Option<Value>,
+    #[serde(default)]
+    error: Option<Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -171,10 +183,288 @@ struct DebugVariable {
     value: String,
 }
 
+pub(crate) fn fetch_runtime_task_stats(endpoint: &str, auth: Option<&str>) -> Option<Value> {
+    let Some(parsed) = ControlEndpoint::parse(endpoint) else {
+        warn!("tasks.stats control endpoint parse failed: {}", endpoint);
+        return None;
+    };
+    let Some(mut client) = ControlClient::connect(parsed, auth) else {
+        warn!("tasks.stats control connect failed");
+        return None;
+    };
+    client.request("tasks.stats", None)
+}
+
+/// Result of evaluating an expression on the attached runtime.
+#[derive(Debug, Clone)]
+pub(crate) struct RuntimeEvaluation {
+    pub(crate) result: String,
+    pub(crate) type_name: Option<String>,
+}
+
+/// Sends `debug.evaluate` to the runtime control endpoint.
+///
+/// Without a `frame_id` the expression is evaluated against globals and
+/// program instances of the latest debug snapshot.
+pub(crate) fn evaluate_runtime_expression(
+    endpoint: &str,
+    auth: Option<&str>,
+    expression: &str,
+    frame_id: Option<u32>,
+) -> Result<RuntimeEvaluation, String> {
+    let parsed = ControlEndpoint::parse(endpoint)
+        .ok_or_else(|| format!("unsupported control endpoint '{endpoint}'"))?;
+    let mut client = ControlClient::connect(parsed, auth)
+        .ok_or_else(|| format!("failed to connect to runtime control endpoint '{endpoint}'"))?;
+    let Some(result) = client.request(
+        "debug.evaluate",
+        Some(serde_json::json!({ "expression": expression, "frame_id": frame_id })),
+    ) else {
+        return Err(client
+            .last_error
+            .take()
+            .unwrap_or_else(|| "runtime did not answer debug.evaluate".to_string()));
+    };
+    Ok(RuntimeEvaluation {
+        result: result
+            .get("result")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        type_name: result
+            .get("type")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    })
+}
+
+/// A variable reported by `var.forced`.
+#[derive(Debug, Clone)]
+pub(crate) struct ForcedVariable {
+    pub(crate) target: String,
+    pub(crate) value: String,
+}
+
+/// Forces `variable` to `value` with `var.force`, or releases it with
+/// `var.unforce` when `value` is `None`.
+///
+/// Returns the control target used, such as `global:g` or `instance:1:x`.
+pub(crate) fn set_runtime_force(
+    endpoint: &str,
+    auth: Option<&str>,
+    variable: &InlineValueTarget,
+    value: Option<&str>,
+) -> Result<String, String> {
+    let mut client = connect_control(endpoint, auth)?;
+    let mut instances = None;
+    let target = control_target(&mut client, variable, &mut instances)?;
+    let (kind, params) = match value {
+        Some(value) => (
+            "var.force",
+            serde_json::json!({ "target": target, "value": value }),
+        ),
+        None => ("var.unforce", serde_json::json!({ "target": target })),
+    };
+    if client.request(kind, Some(params)).is_none() {
+        return Err(client
+            .last_error
+            .take()
+            .unwrap_or_else(|| format!("runtime did not answer {kind}")));
+    }
+    Ok(target)
+}
+
+/// Lists forced variables and resolves the control target of each of
+/// `variables`, so forced entries can be matched to their declarations.
+///
+/// Targets that cannot be resolved (for example an FB with several instances)
+/// are `None`.
+pub(crate) fn fetch_runtime_forced(
+    endpoint: &str,
+    auth: Option<&str>,
+    variables: &[InlineValueTarget],
+) -> Result<(Vec<ForcedVariable>, Vec<Option<String>>), String> {
+    let mut client = connect_control(endpoint, auth)?;
+    let Some(result) = client.request("var.forced", None) else {
+        return Err(client
+            .last_error
+            .take()
+            .unwrap_or_else(|| "runtime did not answer var.forced".to_string()));
+    };
+    let forced: Vec<ForcedVariable> = result
+        .get("vars")
+        .and_then(Value::as_array)
+        .map(|vars| {
+            vars.iter()
+                .filter_map(|entry| {
+                    Some(ForcedVariable {
+                        target: entry.get("target")?.as_str()?.to_string(),
+                        value: entry
+                            .get("value")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if forced.is_empty() {
+        return Ok((forced, vec![None; variables.len()]));
+    }
+    let mut instances = None;
+    let targets = variables
+        .iter()
+        .map(|variable| control_target(&mut client, variable, &mut instances).ok())
+        .collect();
+    Ok((forced, targets))
+}
+
+/// Numeric samples of one variable over the last cycles, oldest first.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RuntimeHistory {
+    pub(crate) depth: usize,
+    pub(crate) values: Vec<f64>,
+}
+
+/// Sends `debug.history` for `variable`.
+///
+/// The first request only starts recording, so it usually returns no samples.
+/// Locals are addressed as `<program>.<variable>`; FB locals have no such path
+/// and stay empty.
+pub(crate) fn fetch_runtime_history(
+    endpoint: &str,
+    auth: Option<&str>,
+    variable: &InlineValueTarget,
+    limit: usize,
+) -> Result<RuntimeHistory, String> {
+    let path = match (variable.scope, variable.owner.as_ref()) {
+        (InlineValueScope::Local, Some(owner)) => format!("{owner}.{}", variable.name),
+        _ => variable.name.to_string(),
+    };
+    let mut client = connect_control(endpoint, auth)?;
+    let Some(result) = client.request(
+        "debug.history",
+        Some(serde_json::json!({ "variables": [path], "limit": limit })),
+    ) else {
+        return Err(client
+            .last_error
+            .take()
+            .unwrap_or_else(|| "runtime did not answer debug.history".to_string()));
+    };
+    let values = result
+        .get("variables")
+        .and_then(Value::as_array)
+        .and_then(|variables| variables.first())
+        .and_then(|entry| entry.get("samples"))
+        .and_then(Value::as_array)
+        .map(|samples| {
+            samples
+                .iter()
+                .filter_map(|sample| sample.get("number").and_then(Value::as_f64))
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(RuntimeHistory {
+        depth: result
+            .get("depth")
+            .and_then(Value::as_u64)
+            .and_then(|depth| usize::try_from(depth).ok())
+            .unwrap_or_default(),
+        values,
+    })
+}
+
+fn connect_control(endpoint: &str, auth: Option<&str>) -> Result<ControlClient, String> {
+    let parsed = ControlEndpoint::parse(endpoint)
+        .ok_or_else(|| format!("unsupported control endpoint '{endpoint}'"))?;
+    ControlClient::connect(parsed, auth)
+        .ok_or_else(|| format!("failed to connect to runtime control endpoint '{endpoint}'"))
+}
+
+/// Maps a declared variable onto a `var.force` target.
+///
+/// Instance variables need the runtime instance id of their owning POU, which
+/// must be unique.
+fn control_target(
+    client: &mut ControlClient,
+    variable: &InlineValueTarget,
+    instances: &mut Option<Vec<(String, u32)>>,
+) -> Result<String, String> {
+    match variable.scope {
+        InlineValueScope::Global => return Ok(format!("global:{}", variable.name)),
+        InlineValueScope::Retain => return Ok(format!("retain:{}", variable.name)),
+        InlineValueScope::Local => {}
+    }
+    let owner = variable
+        .owner
+        .as_ref()
+        .ok_or_else(|| format!("'{}' is not stored in an instance", variable.name))?;
+    let instances = instances.get_or_insert_with(|| fetch_instance_ids(client));
+    let matching: Vec<u32> = instances
+        .iter()
+        .filter(|(type_name, _)| {
+            let base = type_name.rsplit('.').next().unwrap_or(type_name);
+            type_name.eq_ignore_ascii_case(owner) || base.eq_ignore_ascii_case(owner)
+        })
+        .map(|(_, id)| *id)
+        .collect();
+    match matching.as_slice() {
+        [id] => Ok(format!("instance:{id}:{}", variable.name)),
+        [] => Err(format!("no running instance of '{owner}'")),
+        _ => Err(format!(
+            "'{owner}' has {} instances; force '{}' from the runtime panel",
+            matching.len(),
+            variable.name
+        )),
+    }
+}
+
+/// Returns `(type name, instance id)` for every instance in the debug snapshot.
+fn fetch_instance_ids(client: &mut ControlClient) -> Vec<(String, u32)> {
+    let Some(scopes_value) =
+        client.request("debug.scopes", Some(serde_json::json!({ "frame_id": 0 })))
+    else {
+        return Vec::new();
+    };
+    let Some(reference) = scopes_value
+        .get("scopes")
+        .and_then(|value| serde_json::from_value::<Vec<DebugScope>>(value.clone()).ok())
+        .and_then(|scopes| {
+            scopes
+                .into_iter()
+                .find(|scope| scope.name.eq_ignore_ascii_case("instances"))
+        })
+        .map(|scope| scope.variables_reference)
+    else {
+        return Vec::new();
+    };
+    let Some(instances_value) = client.request(
+        "debug.variables",
+        Some(serde_json::json!({ "variables_reference": reference })),
+    ) else {
+        return Vec::new();
+    };
+    instances_value
+        .get("variables")
+        .and_then(|value| serde_json::from_value::<Vec<DebugVariableRef>>(value.clone()).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| {
+            let (type_name, id) = entry.name.rsplit_once('#')?;
+            Some((type_name.to_string(), id.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Fetches variable values for inline display.
+///
+/// With `frame_id` set the paused frame's locals are included; without it only
+/// globals, retained globals, and instance variables are read.
 pub(crate) fn fetch_runtime_inline_values(
     endpoint: &str,
     auth: Option<&str>,
-    frame_id: u32,
+    frame_id: Option<u32>,
     owner_hints: &[SmolStr],
 ) -> Option<RuntimeInlineValues> {
     let endpoint = match ControlEndpoint::parse(endpoint) {
@@ -192,13 +482,13 @@ pub(crate) fn fetch_runtime_inline_values(
         }
     };
     debug!(
-        "inlineValue control connected frame_id={} owner_hints={}",
+        "inlineValue control connected frame_id={:?} owner_hints={}",
         frame_id,
         owner_hints.len()
     );
     let scopes_value = client.request(
         "debug.scopes",
-        Some(serde_json::json!({ "frame_id": frame_id })),
+        Some(serde_json::json!({ "frame_id": frame_id.unwrap_or_default() })),
     )?;
     let scopes = scopes_value
         .get("scopes")
@@ -218,7 +508,7 @@ pub(crate) fn fetch_runtime_inline_values(
     let mut instances_ref = None;
     for scope in scopes {
         match scope.name.to_ascii_lowercase().as_str() {
-            "locals" => locals_ref = Some(scope.variables_reference),
+            "locals" if frame_id.is_some() => locals_ref = Some(scope.variables_reference),
             "globals" => globals_ref = Some(scope.variables_reference),
             "retain" => retain_ref = Some(scope.variables_reference),
             "instances" => instances_ref = Some(scope.variables_reference),
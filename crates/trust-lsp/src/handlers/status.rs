@@ -0,0 +1,28 @@
+//! Custom `trust/status` notification for editor status bar items.
+
+use tower_lsp::lsp_types::notification::Notification;
+use tower_lsp::Client;
+
+use crate::state::{AnalysisPhase, AnalysisStatus, ServerState};
+
+/// Notification carrying background analysis counts.
+#[derive(Debug)]
+pub enum StatusNotification {}
+
+impl Notification for StatusNotification {
+    type Params = AnalysisStatus;
+    const METHOD: &'static str = "trust/status";
+}
+
+/// Sends the current analysis status snapshot to the client.
+pub async fn publish_status(client: &Client, state: &ServerState) {
+    let _ = client
+        .send_notification::<StatusNotification>(state.analysis_status())
+        .await;
+}
+
+/// Records a new analysis phase and notifies the client.
+pub async fn enter_phase(client: &Client, state: &ServerState, phase: AnalysisPhase) {
+    state.set_analysis_phase(phase);
+    publish_status(client, state).await;
+}
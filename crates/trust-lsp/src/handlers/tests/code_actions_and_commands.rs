@@ -116,6 +116,117 @@ END_PROGRAM
     assert!(has_var_action, "expected VAR creation code action");
 }
 
+#[test]
+fn lsp_code_action_declare_missing_var_in_each_section_with_inferred_type() {
+    let source = r#"
+FUNCTION_BLOCK Motor
+VAR_INPUT
+    enable : BOOL;
+END_VAR
+VAR
+    speed : REAL;
+    timer : TON;
+END_VAR
+    target := speed;
+    timer(IN := enable, PT := delay);
+END_FUNCTION_BLOCK
+
+CONFIGURATION Plant
+VAR_GLOBAL
+    mode : INT;
+END_VAR
+END_CONFIGURATION
+"#;
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///test.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let declare_actions = |name: &str| {
+        let offset = source
+            .find(&format!("{name} "))
+            .or_else(|| source.find(&format!("{name})")))
+            .unwrap();
+        let start = super::lsp_utils::offset_to_position(source, offset as u32);
+        let end = super::lsp_utils::offset_to_position(source, (offset + name.len()) as u32);
+        let diagnostic = tower_lsp::lsp_types::Diagnostic {
+            range: tower_lsp::lsp_types::Range { start, end },
+            severity: Some(tower_lsp::lsp_types::DiagnosticSeverity::ERROR),
+            code: Some(tower_lsp::lsp_types::NumberOrString::String(
+                "E101".to_string(),
+            )),
+            source: Some("trust-lsp".to_string()),
+            message: format!("undefined identifier '{name}'"),
+            ..Default::default()
+        };
+        let params = tower_lsp::lsp_types::CodeActionParams {
+            text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+            range: diagnostic.range,
+            context: tower_lsp::lsp_types::CodeActionContext {
+                diagnostics: vec![diagnostic],
+                only: None,
+                trigger_kind: None,
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        code_action(&state, params)
+            .expect("code actions")
+            .into_iter()
+            .filter_map(|action| match action {
+                tower_lsp::lsp_types::CodeActionOrCommand::CodeAction(action)
+                    if action.title.starts_with("Declare") =>
+                {
+                    let edit = action.edit?.changes?.remove(&uri)?.remove(0);
+                    Some((action.title, action.is_preferred, edit))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let target = declare_actions("target");
+    let titles = target
+        .iter()
+        .map(|(title, _, _)| title.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        titles,
+        [
+            "Declare 'target : REAL' in VAR",
+            "Declare 'target : REAL' in VAR_INPUT",
+            "Declare 'target : REAL' in VAR_OUTPUT",
+            "Declare 'target : REAL' in VAR_GLOBAL",
+        ]
+    );
+    assert_eq!(target[0].1, Some(true));
+    assert!(target[1..]
+        .iter()
+        .all(|(_, preferred, _)| preferred.is_none()));
+
+    let line_of = |text: &str| source[..source.find(text).unwrap()].matches('\n').count() as u32;
+    let (_, _, var_edit) = &target[0];
+    assert_eq!(var_edit.new_text, "    target : REAL;\n");
+    assert_eq!(var_edit.range.start.line, line_of("END_VAR\n    target"));
+    let (_, _, input_edit) = &target[1];
+    assert_eq!(input_edit.new_text, "    target : REAL;\n");
+    assert_eq!(input_edit.range.start.line, line_of("END_VAR\nVAR\n"));
+    let (_, _, output_edit) = &target[2];
+    assert_eq!(
+        output_edit.new_text,
+        "VAR_OUTPUT\n    target : REAL;\nEND_VAR\n"
+    );
+    assert_eq!(output_edit.range.start.line, line_of("VAR\n    speed"));
+    let (_, _, global_edit) = &target[3];
+    assert_eq!(global_edit.new_text, "    target : REAL;\n");
+    assert_eq!(
+        global_edit.range.start.line,
+        line_of("END_VAR\nEND_CONFIGURATION")
+    );
+
+    let delay = declare_actions("delay");
+    assert_eq!(delay[0].0, "Declare 'delay : TIME' in VAR");
+}
+
 #[test]
 fn lsp_code_action_create_type() {
     let source = r#"
@@ -235,6 +346,142 @@ END_PROGRAM
     assert!(has_conversion_action, "expected conversion code action");
 }
 
+#[test]
+fn lsp_code_action_signed_unsigned_operand_conversion() {
+    let source = r#"
+PROGRAM Test
+VAR
+    i : INT;
+    u : UINT;
+END_VAR
+    u := u + i;
+END_PROGRAM
+"#;
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///test.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let offset = source.find("i;\nEND_PROGRAM").unwrap();
+    let start = super::lsp_utils::offset_to_position(source, offset as u32);
+    let end = super::lsp_utils::offset_to_position(source, (offset + 1) as u32);
+
+    let diagnostic = tower_lsp::lsp_types::Diagnostic {
+        range: tower_lsp::lsp_types::Range { start, end },
+        severity: Some(tower_lsp::lsp_types::DiagnosticSeverity::WARNING),
+        code: Some(tower_lsp::lsp_types::NumberOrString::String(
+            "W015".to_string(),
+        )),
+        source: Some("trust-lsp".to_string()),
+        message: "implicit conversion from 'INT' to 'UINT' mixes signed and unsigned operands"
+            .to_string(),
+        ..Default::default()
+    };
+
+    let params = tower_lsp::lsp_types::CodeActionParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+        range: diagnostic.range,
+        context: tower_lsp::lsp_types::CodeActionContext {
+            diagnostics: vec![diagnostic],
+            only: None,
+            trigger_kind: None,
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    let actions = code_action(&state, params).expect("code actions");
+    let edit_text = actions.iter().find_map(|action| match action {
+        tower_lsp::lsp_types::CodeActionOrCommand::CodeAction(code_action)
+            if code_action.title.contains("operand") =>
+        {
+            code_action
+                .edit
+                .as_ref()
+                .and_then(|edit| edit.changes.as_ref())
+                .and_then(|changes| changes.values().next())
+                .and_then(|edits| edits.first())
+                .map(|edit| edit.new_text.clone())
+        }
+        _ => None,
+    });
+    assert_eq!(edit_text.as_deref(), Some("INT_TO_UINT(i)"));
+}
+
+#[test]
+fn lsp_code_action_remove_ambiguous_using_import() {
+    let source = r#"
+NAMESPACE A
+FUNCTION Foo : INT
+Foo := INT#1;
+END_FUNCTION
+END_NAMESPACE
+
+NAMESPACE B
+FUNCTION Foo : INT
+Foo := INT#2;
+END_FUNCTION
+END_NAMESPACE
+
+USING A, B;
+PROGRAM Test
+END_PROGRAM
+"#;
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///test.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let offset = source.find("B;").unwrap();
+    let start = super::lsp_utils::offset_to_position(source, offset as u32);
+    let end = super::lsp_utils::offset_to_position(source, (offset + 1) as u32);
+
+    let diagnostic = tower_lsp::lsp_types::Diagnostic {
+        range: tower_lsp::lsp_types::Range { start, end },
+        severity: Some(tower_lsp::lsp_types::DiagnosticSeverity::WARNING),
+        code: Some(tower_lsp::lsp_types::NumberOrString::String(
+            "W017".to_string(),
+        )),
+        source: Some("trust-lsp".to_string()),
+        message: "'Foo' is imported by both USING 'A' and USING 'B'; qualify the name".to_string(),
+        ..Default::default()
+    };
+
+    let params = tower_lsp::lsp_types::CodeActionParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+        range: diagnostic.range,
+        context: tower_lsp::lsp_types::CodeActionContext {
+            diagnostics: vec![diagnostic],
+            only: None,
+            trigger_kind: None,
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    let actions = code_action(&state, params).expect("code actions");
+    let edit = actions.iter().find_map(|action| match action {
+        tower_lsp::lsp_types::CodeActionOrCommand::CodeAction(code_action)
+            if code_action.title == "Remove USING 'B'" =>
+        {
+            code_action
+                .edit
+                .as_ref()
+                .and_then(|edit| edit.changes.as_ref())
+                .and_then(|changes| changes.values().next())
+                .and_then(|edits| edits.first())
+                .cloned()
+        }
+        _ => None,
+    });
+    let edit = edit.expect("remove USING action");
+    let comma = source.find(", B;").unwrap();
+    assert_eq!(
+        edit.range.start,
+        super::lsp_utils::offset_to_position(source, comma as u32)
+    );
+    assert_eq!(edit.range.end, end);
+    assert!(edit.new_text.is_empty());
+}
+
 #[test]
 fn lsp_code_action_incompatible_assignment_conversion() {
     let source = r#"
@@ -616,6 +863,134 @@ END_CLASS
         .any(|edit| edit.new_text.contains("METHOD ExtractedMethod")));
 }
 
+#[test]
+fn lsp_code_action_introduce_constant() {
+    let source = r#"
+PROGRAM Main
+VAR
+    speed : INT;
+END_VAR
+    speed := 1500;
+    IF speed > 1500 THEN
+        speed := 0;
+    END_IF;
+END_PROGRAM
+"#;
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///test.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let offset = source.find("1500").expect("literal") + 1;
+    let position = super::lsp_utils::offset_to_position(source, offset as u32);
+    let params = tower_lsp::lsp_types::CodeActionParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+        range: tower_lsp::lsp_types::Range {
+            start: position,
+            end: position,
+        },
+        context: tower_lsp::lsp_types::CodeActionContext {
+            diagnostics: Vec::new(),
+            only: Some(vec![tower_lsp::lsp_types::CodeActionKind::REFACTOR_EXTRACT]),
+            trigger_kind: None,
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    let actions = code_action(&state, params).expect("code actions");
+    let constant_action = actions.iter().find_map(|action| match action {
+        tower_lsp::lsp_types::CodeActionOrCommand::CodeAction(code_action)
+            if code_action.title == "Introduce local constant" =>
+        {
+            Some(code_action)
+        }
+        _ => None,
+    });
+    let constant_action = constant_action.expect("introduce constant action");
+    let edits = constant_action
+        .edit
+        .as_ref()
+        .and_then(|edit| edit.changes.as_ref())
+        .and_then(|changes| changes.get(&uri))
+        .expect("constant edits");
+    assert!(edits
+        .iter()
+        .any(|edit| edit.new_text.contains("ExtractedConstant : INT := 1500;")));
+    assert_eq!(
+        edits
+            .iter()
+            .filter(|edit| edit.new_text == "ExtractedConstant")
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn lsp_code_action_introduce_constant_in_function_block_body() {
+    let source = r#"
+FUNCTION_BLOCK Limiter
+VAR_INPUT
+    value : INT;
+END_VAR
+VAR_OUTPUT
+    limited : INT;
+END_VAR
+    limited := value;
+    IF value > 250 THEN
+        limited := 250;
+    END_IF;
+END_FUNCTION_BLOCK
+"#;
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///test.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let position = position_at(source, "250 THEN");
+    let params = tower_lsp::lsp_types::CodeActionParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+        range: tower_lsp::lsp_types::Range {
+            start: position,
+            end: position,
+        },
+        context: tower_lsp::lsp_types::CodeActionContext {
+            diagnostics: Vec::new(),
+            only: Some(vec![tower_lsp::lsp_types::CodeActionKind::REFACTOR_EXTRACT]),
+            trigger_kind: None,
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    let actions = code_action(&state, params).expect("code actions");
+    let constant_action = actions
+        .iter()
+        .find_map(|action| match action {
+            tower_lsp::lsp_types::CodeActionOrCommand::CodeAction(code_action)
+                if code_action.title == "Introduce local constant" =>
+            {
+                Some(code_action)
+            }
+            _ => None,
+        })
+        .expect("introduce constant action");
+    let edits = constant_action
+        .edit
+        .as_ref()
+        .and_then(|edit| edit.changes.as_ref())
+        .and_then(|changes| changes.get(&uri))
+        .expect("constant edits");
+    assert!(edits
+        .iter()
+        .any(|edit| edit.new_text.contains("ExtractedConstant : INT := 250;")));
+    assert_eq!(
+        edits
+            .iter()
+            .filter(|edit| edit.new_text == "ExtractedConstant")
+            .count(),
+        2
+    );
+}
+
 #[test]
 fn lsp_code_action_convert_function_to_function_block() {
     let source = r#"
@@ -725,6 +1100,82 @@ END_FUNCTION_BLOCK
     assert!(edits.iter().any(|edit| edit.new_text.contains(": INT")));
 }
 
+#[test]
+fn lsp_code_action_organize_declarations() {
+    let source = r#"
+FUNCTION_BLOCK Fb
+    VAR
+        speed : REAL := 1.0;
+        alpha : INT; // first
+    END_VAR
+    VAR_OUTPUT
+        done : BOOL;
+    END_VAR
+    VAR_INPUT
+        enable : BOOL;
+        setpoint : REAL := 0.0;
+    END_VAR
+    VAR
+    END_VAR
+    VAR
+        counter : DINT;
+    END_VAR
+    done := enable;
+END_FUNCTION_BLOCK
+"#;
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///test.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let position = position_at(source, "done := enable");
+    let params = tower_lsp::lsp_types::CodeActionParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+        range: tower_lsp::lsp_types::Range {
+            start: position,
+            end: position,
+        },
+        context: tower_lsp::lsp_types::CodeActionContext {
+            diagnostics: Vec::new(),
+            only: Some(vec![tower_lsp::lsp_types::CodeActionKind::SOURCE]),
+            trigger_kind: None,
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    let actions = code_action(&state, params).expect("code actions");
+    let organize_action = actions.iter().find_map(|action| match action {
+        tower_lsp::lsp_types::CodeActionOrCommand::CodeAction(code_action)
+            if code_action.title == "Organize declarations" =>
+        {
+            Some(code_action)
+        }
+        _ => None,
+    });
+    let organize_action = organize_action.expect("organize action");
+    assert_eq!(
+        organize_action.kind.as_ref().map(|kind| kind.as_str()),
+        Some("source.organizeDeclarations")
+    );
+    let edits = organize_action
+        .edit
+        .as_ref()
+        .and_then(|edit| edit.changes.as_ref())
+        .and_then(|changes| changes.get(&uri))
+        .expect("organize edits");
+    assert_eq!(edits.len(), 1);
+    assert_eq!(
+        edits[0].range.start,
+        position_at(source, "VAR\n        speed")
+    );
+    assert_eq!(
+        edits[0].new_text,
+        "VAR_INPUT\n        enable   : BOOL;\n        setpoint : REAL := 0.0;\n    END_VAR\n    \
+         VAR_OUTPUT\n        done : BOOL;\n    END_VAR\n    \
+         VAR\n        alpha   : INT; // first\n        counter : DINT;\n        speed   : REAL := 1.0;\n    END_VAR"
+    );
+}
+
 #[test]
 fn lsp_execute_command_namespace_move_workspace_edit() {
     let source = r#"
@@ -853,6 +1304,7 @@ fn lsp_project_info_exposes_build_and_targets() {
                 }],
                 docs: Vec::new(),
             }],
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -873,9 +1325,12 @@ fn lsp_project_info_exposes_build_and_targets() {
             }],
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -1063,3 +1518,690 @@ END_PROGRAM
         "expected LibB qualification quick fix"
     );
 }
+
+#[test]
+fn lsp_dead_code_command_reports_unreachable_pous() {
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(
+        root_uri.clone(),
+        ProjectConfig {
+            root: PathBuf::from("/workspace"),
+            config_path: None,
+            include_paths: Vec::new(),
+            vendor_profile: None,
+            stdlib: StdlibSettings::default(),
+            libraries: Vec::new(),
+            doc_bundles: Vec::new(),
+            dependencies: Vec::new(),
+            dependency_resolution_issues: Vec::new(),
+            diagnostic_external_paths: Vec::new(),
+            build: BuildConfig::default(),
+            targets: Vec::new(),
+            indexing: IndexingConfig::default(),
+            diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
+            runtime: RuntimeConfig::default(),
+            workspace: WorkspaceSettings::default(),
+            telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
+        },
+    );
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/main.st").unwrap();
+    let source = r#"
+CONFIGURATION Cell
+    RESOURCE Cpu ON PLC
+        TASK Fast (INTERVAL := T#10ms, PRIORITY := 1);
+        PROGRAM Main WITH Fast : MainProgram;
+    END_RESOURCE
+END_CONFIGURATION
+
+PROGRAM MainProgram
+END_PROGRAM
+
+FUNCTION Unused : INT
+    Unused := 0;
+END_FUNCTION
+"#;
+    state.open_document(uri, 1, source.to_string());
+
+    let result = super::commands::dead_code_value(&state, Vec::new()).expect("dead code");
+    assert_eq!(result.get("ok").and_then(Value::as_bool), Some(true));
+    let items = result
+        .pointer("/projects/0/items")
+        .and_then(Value::as_array)
+        .expect("items");
+    let names: Vec<&str> = items
+        .iter()
+        .filter_map(|item| item.get("name").and_then(Value::as_str))
+        .collect();
+    assert_eq!(names, vec!["Unused"]);
+}
+
+#[test]
+fn lsp_stack_usage_command_reports_depth_and_recursion() {
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(
+        root_uri.clone(),
+        ProjectConfig {
+            root: PathBuf::from("/workspace"),
+            config_path: None,
+            include_paths: Vec::new(),
+            vendor_profile: None,
+            stdlib: StdlibSettings::default(),
+            libraries: Vec::new(),
+            doc_bundles: Vec::new(),
+            dependencies: Vec::new(),
+            dependency_resolution_issues: Vec::new(),
+            diagnostic_external_paths: Vec::new(),
+            build: BuildConfig::default(),
+            targets: Vec::new(),
+            indexing: IndexingConfig::default(),
+            diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
+            runtime: RuntimeConfig::default(),
+            workspace: WorkspaceSettings::default(),
+            telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
+        },
+    );
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/main.st").unwrap();
+    let source = r#"
+CONFIGURATION Cell
+    RESOURCE Cpu ON PLC
+        TASK Fast (INTERVAL := T#10ms, PRIORITY := 1);
+        PROGRAM Main WITH Fast : MainProgram;
+    END_RESOURCE
+END_CONFIGURATION
+
+FUNCTION Fact : DINT
+VAR_INPUT
+    N : DINT;
+END_VAR
+    Fact := N * Fact(N - 1);
+END_FUNCTION
+
+PROGRAM MainProgram
+VAR
+    x : DINT;
+END_VAR
+    x := Fact(3);
+END_PROGRAM
+"#;
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let result = super::commands::stack_usage_value(&state, Vec::new()).expect("stack usage");
+    assert_eq!(result.get("ok").and_then(Value::as_bool), Some(true));
+    assert_eq!(
+        result
+            .pointer("/projects/0/entries/0/name")
+            .and_then(Value::as_str),
+        Some("Main")
+    );
+    assert_eq!(
+        result
+            .pointer("/projects/0/entries/0/task")
+            .and_then(Value::as_str),
+        Some("Fast")
+    );
+    assert_eq!(
+        result
+            .pointer("/projects/0/entries/0/unbounded")
+            .and_then(Value::as_bool),
+        Some(true)
+    );
+    assert_eq!(
+        result
+            .pointer("/projects/0/recursiveCalls/0/callee")
+            .and_then(Value::as_str),
+        Some("Fact")
+    );
+
+    let file_id = state.get_document(&uri).expect("document").file_id;
+    let ticket = state.begin_semantic_request();
+    let diagnostics = super::diagnostics::collect_diagnostics_with_ticket_for_tests(
+        &state, &uri, source, file_id, ticket,
+    );
+    let recursion: Vec<_> = diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            diagnostic.code
+                == Some(tower_lsp::lsp_types::NumberOrString::String(
+                    "E308".to_string(),
+                ))
+        })
+        .collect();
+    assert_eq!(recursion.len(), 1);
+    assert_eq!(
+        recursion[0].severity,
+        Some(tower_lsp::lsp_types::DiagnosticSeverity::ERROR)
+    );
+    assert!(recursion[0].message.contains("Fact -> Fact"));
+}
+
+#[test]
+fn lsp_project_graph_command_exports_json_and_dot() {
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(
+        root_uri.clone(),
+        ProjectConfig {
+            root: PathBuf::from("/workspace"),
+            config_path: None,
+            include_paths: Vec::new(),
+            vendor_profile: None,
+            stdlib: StdlibSettings::default(),
+            libraries: Vec::new(),
+            doc_bundles: Vec::new(),
+            dependencies: Vec::new(),
+            dependency_resolution_issues: Vec::new(),
+            diagnostic_external_paths: Vec::new(),
+            build: BuildConfig::default(),
+            targets: Vec::new(),
+            indexing: IndexingConfig::default(),
+            diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
+            runtime: RuntimeConfig::default(),
+            workspace: WorkspaceSettings::default(),
+            telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
+        },
+    );
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/main.st").unwrap();
+    let source = r#"
+CONFIGURATION Cell
+    RESOURCE Cpu ON PLC
+        TASK Fast (INTERVAL := T#10ms, PRIORITY := 1);
+        TASK Slow (INTERVAL := T#100ms, PRIORITY := 2);
+        PROGRAM FastMain WITH Fast : FastProgram;
+        PROGRAM SlowMain WITH Slow : SlowProgram;
+    END_RESOURCE
+END_CONFIGURATION
+
+FUNCTION Scale : INT
+VAR_INPUT
+    A : INT;
+END_VAR
+    Scale := A * 2;
+END_FUNCTION
+
+FUNCTION_BLOCK Motor
+    Scale(1);
+END_FUNCTION_BLOCK
+
+PROGRAM FastProgram
+VAR
+    m : Motor;
+END_VAR
+    m();
+END_PROGRAM
+
+PROGRAM SlowProgram
+    Scale(2);
+END_PROGRAM
+"#;
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let result = super::commands::project_graph_value(&state, Vec::new()).expect("graph");
+    assert_eq!(result.get("ok").and_then(Value::as_bool), Some(true));
+    let edges = result
+        .pointer("/projects/0/dependencyGraph/edges")
+        .and_then(Value::as_array)
+        .expect("edges");
+    assert!(edges.iter().any(|edge| {
+        edge.get("from").and_then(Value::as_str) == Some("FastProgram")
+            && edge.get("to").and_then(Value::as_str) == Some("Motor")
+            && edge.get("kind").and_then(Value::as_str) == Some("instance")
+    }));
+    let dot = result
+        .pointer("/projects/0/dot/callGraph")
+        .and_then(Value::as_str)
+        .expect("dot");
+    assert!(dot.contains("\"FastProgram\" -> \"Motor\";"));
+
+    let filtered =
+        super::commands::project_graph_value(&state, vec![json!({ "entry_task": "Slow" })])
+            .expect("filtered graph");
+    let nodes: Vec<&str> = filtered
+        .pointer("/projects/0/callGraph/nodes")
+        .and_then(Value::as_array)
+        .expect("nodes")
+        .iter()
+        .filter_map(|node| node.get("name").and_then(Value::as_str))
+        .collect();
+    assert_eq!(nodes, vec!["Scale", "SlowProgram"]);
+
+    let missing =
+        super::commands::project_graph_value(&state, vec![json!({ "entry_task": "Nope" })])
+            .expect("missing task");
+    assert_eq!(missing.get("ok").and_then(Value::as_bool), Some(false));
+}
+
+#[test]
+fn lsp_cross_reference_command_lists_accesses_and_exports() {
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(
+        root_uri.clone(),
+        ProjectConfig {
+            root: PathBuf::from("/workspace"),
+            config_path: None,
+            include_paths: Vec::new(),
+            vendor_profile: None,
+            stdlib: StdlibSettings::default(),
+            libraries: Vec::new(),
+            doc_bundles: Vec::new(),
+            dependencies: Vec::new(),
+            dependency_resolution_issues: Vec::new(),
+            diagnostic_external_paths: Vec::new(),
+            build: BuildConfig::default(),
+            targets: Vec::new(),
+            indexing: IndexingConfig::default(),
+            diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
+            runtime: RuntimeConfig::default(),
+            workspace: WorkspaceSettings::default(),
+            telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
+        },
+    );
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/main.st").unwrap();
+    let source = r#"
+CONFIGURATION Cell
+    RESOURCE Cpu ON PLC
+        TASK Fast (INTERVAL := T#10ms, PRIORITY := 1);
+        PROGRAM FastMain WITH Fast : FastProgram;
+    END_RESOURCE
+END_CONFIGURATION
+
+PROGRAM FastProgram
+VAR
+    Lamp AT %QX0.3 : BOOL;
+    Start : BOOL;
+END_VAR
+    Lamp := Start AND %IX0.1;
+END_PROGRAM
+"#;
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let result = super::commands::cross_reference_value(&state, vec![json!({ "filter": "lamp" })])
+        .expect("cross reference");
+    assert_eq!(result.get("ok").and_then(Value::as_bool), Some(true));
+    let entries = result
+        .pointer("/projects/0/entries")
+        .and_then(Value::as_array)
+        .expect("entries");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(
+        entries[0].get("address").and_then(Value::as_str),
+        Some("%QX0.3")
+    );
+    let uses: Vec<&str> = entries[0]
+        .get("locations")
+        .and_then(Value::as_array)
+        .expect("locations")
+        .iter()
+        .filter_map(|location| location.get("use").and_then(Value::as_str))
+        .collect();
+    assert_eq!(uses, vec!["declaration", "write"]);
+
+    let csv = result.get("csv").and_then(Value::as_str).expect("csv");
+    assert!(csv.starts_with("Name,Kind,Type,Address,Use,POU,Tasks,File,Line\r\n"));
+    assert!(
+        csv.contains("FastProgram.Lamp,variable,BOOL,%QX0.3,write,FastProgram,Fast,main.st,14\r\n"),
+        "{csv}"
+    );
+    let html = result.get("html").and_then(Value::as_str).expect("html");
+    assert!(html.contains("<td>FastProgram.Lamp</td>"));
+
+    let all = super::commands::cross_reference_value(&state, Vec::new()).expect("all");
+    let names: Vec<&str> = all
+        .pointer("/projects/0/entries")
+        .and_then(Value::as_array)
+        .expect("entries")
+        .iter()
+        .filter_map(|entry| entry.get("name").and_then(Value::as_str))
+        .collect();
+    assert!(names.contains(&"FastProgram.Start"), "{names:?}");
+    assert!(names.contains(&"%IX0.1"), "{names:?}");
+}
+
+#[test]
+fn lsp_wcet_command_flags_task_overruns_from_profile() {
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(
+        root_uri.clone(),
+        ProjectConfig {
+            root: PathBuf::from("/workspace"),
+            config_path: None,
+            include_paths: Vec::new(),
+            vendor_profile: None,
+            stdlib: StdlibSettings::default(),
+            libraries: Vec::new(),
+            doc_bundles: Vec::new(),
+            dependencies: Vec::new(),
+            dependency_resolution_issues: Vec::new(),
+            diagnostic_external_paths: Vec::new(),
+            build: BuildConfig::default(),
+            targets: Vec::new(),
+            indexing: IndexingConfig::default(),
+            diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
+            runtime: RuntimeConfig::default(),
+            workspace: WorkspaceSettings::default(),
+            telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
+        },
+    );
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/main.st").unwrap();
+    let source = r#"
+CONFIGURATION Cell
+    RESOURCE Cpu ON PLC
+        TASK Fast (INTERVAL := T#10ms, PRIORITY := 1);
+        PROGRAM Main WITH Fast : MainProgram;
+    END_RESOURCE
+END_CONFIGURATION
+
+PROGRAM MainProgram
+VAR
+    x : DINT;
+END_VAR
+    x := x + 1;
+END_PROGRAM
+"#;
+    state.open_document(uri, 1, source.to_string());
+
+    let args = json!({
+        "root_uri": root_uri,
+        "profile": {
+            "calls": [{ "kind": "program", "name": "Main", "max_ms": 12.5 }],
+        },
+    });
+    let result = super::commands::wcet_value(&state, vec![args]).expect("wcet");
+    assert_eq!(result.get("ok").and_then(Value::as_bool), Some(true));
+    assert_eq!(
+        result
+            .pointer("/projects/0/measurementSource")
+            .and_then(Value::as_str),
+        Some("profile")
+    );
+    assert_eq!(
+        result
+            .pointer("/projects/0/tasks/0/name")
+            .and_then(Value::as_str),
+        Some("Fast")
+    );
+    assert_eq!(
+        result
+            .pointer("/projects/0/tasks/0/intervalMs")
+            .and_then(Value::as_f64),
+        Some(10.0)
+    );
+    assert_eq!(
+        result
+            .pointer("/projects/0/tasks/0/estimateMs")
+            .and_then(Value::as_f64),
+        Some(12.5)
+    );
+    assert_eq!(
+        result
+            .pointer("/projects/0/tasks/0/overrun")
+            .and_then(Value::as_bool),
+        Some(true)
+    );
+    assert!(result
+        .get("markdown")
+        .and_then(Value::as_str)
+        .is_some_and(|markdown| markdown.contains("| Fast | 10.000 | 12.500 | overrun |")));
+}
+
+#[test]
+fn lsp_reference_access_command_filters_writers() {
+    let source = r#"
+PROGRAM Main
+    VAR
+        x : INT;
+        y : INT;
+    END_VAR
+    x := 1;
+    y := x;
+END_PROGRAM
+"#;
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///test.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let result = super::commands::reference_access_value(
+        &state,
+        vec![json!({
+            "text_document": { "uri": uri.as_str() },
+            "position": position_at(source, "x : INT"),
+            "access": ["write"],
+        })],
+    )
+    .expect("reference access");
+    assert_eq!(result.get("ok").and_then(Value::as_bool), Some(true));
+    assert_eq!(
+        result.pointer("/counts/read").and_then(Value::as_u64),
+        Some(1)
+    );
+    let references = result
+        .get("references")
+        .and_then(Value::as_array)
+        .expect("references");
+    assert_eq!(references.len(), 1);
+    assert_eq!(
+        references[0].get("access").and_then(Value::as_str),
+        Some("write")
+    );
+    assert_eq!(
+        references[0]
+            .pointer("/range/start/line")
+            .and_then(Value::as_u64),
+        Some(6)
+    );
+}
+
+#[test]
+fn lsp_trace_drivers_command_returns_driver_tree() {
+    let source = r#"
+PROGRAM Main
+    VAR
+        start AT %IX0.0 : BOOL;
+        motor AT %QX0.0 : BOOL;
+    END_VAR
+    motor := start;
+END_PROGRAM
+"#;
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///test.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let result = super::commands::trace_drivers_value(
+        &state,
+        vec![json!({
+            "text_document": { "uri": uri.as_str() },
+            "position": position_at(source, "motor AT"),
+        })],
+    )
+    .expect("trace drivers");
+    assert_eq!(result.get("ok").and_then(Value::as_bool), Some(true));
+    assert_eq!(
+        result.pointer("/tree/label").and_then(Value::as_str),
+        Some("Main.motor AT %QX0.0")
+    );
+    assert_eq!(
+        result
+            .pointer("/tree/children/0/kind")
+            .and_then(Value::as_str),
+        Some("assignment")
+    );
+    assert_eq!(
+        result
+            .pointer("/tree/children/0/children/0/label")
+            .and_then(Value::as_str),
+        Some("Main.start AT %IX0.0")
+    );
+    let markdown = result
+        .get("markdown")
+        .and_then(Value::as_str)
+        .expect("markdown");
+    assert!(markdown.contains("assignment: `motor := start`"));
+}
+
+#[test]
+fn lsp_root_conflict_reports_priority_and_qualifies_references() {
+    let state = ServerState::new();
+    let root_one = temp_dir("trustlsp-conflict-alpha");
+    let root_two = temp_dir("trustlsp-conflict-beta");
+    let root_one_uri = tower_lsp::lsp_types::Url::from_file_path(&root_one).unwrap();
+    let root_two_uri = tower_lsp::lsp_types::Url::from_file_path(&root_two).unwrap();
+    state.set_workspace_folders(vec![root_one_uri.clone(), root_two_uri.clone()]);
+    for (root_uri, root, priority) in [
+        (root_one_uri.clone(), root_one.clone(), 10),
+        (root_two_uri.clone(), root_two.clone(), 1),
+    ] {
+        state.set_workspace_config(
+            root_uri,
+            ProjectConfig {
+                root,
+                config_path: None,
+                include_paths: Vec::new(),
+                vendor_profile: None,
+                stdlib: StdlibSettings::default(),
+                libraries: Vec::new(),
+                doc_bundles: Vec::new(),
+                dependencies: Vec::new(),
+                dependency_resolution_issues: Vec::new(),
+                diagnostic_external_paths: Vec::new(),
+                build: BuildConfig::default(),
+                targets: Vec::new(),
+                indexing: IndexingConfig::default(),
+                diagnostics: DiagnosticSettings::default(),
+                lint: LintSettings::default(),
+                runtime: RuntimeConfig::default(),
+                workspace: WorkspaceSettings {
+                    priority,
+                    visibility: crate::config::WorkspaceVisibility::Public,
+                },
+                telemetry: TelemetryConfig::default(),
+                format: FormatSettings::default(),
+                completion: CompletionSettings::default(),
+            },
+        );
+    }
+
+    let alpha_source = r#"
+NAMESPACE Alpha
+FUNCTION_BLOCK Counter
+END_FUNCTION_BLOCK
+END_NAMESPACE
+"#;
+    let beta_source = r#"
+FUNCTION_BLOCK Counter
+END_FUNCTION_BLOCK
+"#;
+    let user_source = r#"
+PROGRAM Main
+VAR
+    c : Counter;
+END_VAR
+END_PROGRAM
+"#;
+    let alpha_uri = tower_lsp::lsp_types::Url::from_file_path(root_one.join("alpha.st")).unwrap();
+    let beta_uri = tower_lsp::lsp_types::Url::from_file_path(root_two.join("beta.st")).unwrap();
+    let user_uri = tower_lsp::lsp_types::Url::from_file_path(root_two.join("main.st")).unwrap();
+    state.open_document(alpha_uri.clone(), 1, alpha_source.to_string());
+    state.open_document(beta_uri.clone(), 1, beta_source.to_string());
+    state.open_document(user_uri.clone(), 1, user_source.to_string());
+
+    let conflicts = |uri: &tower_lsp::lsp_types::Url, source: &str| {
+        let file_id = state.get_document(uri).expect("document").file_id;
+        let ticket = state.begin_semantic_request();
+        super::diagnostics::collect_diagnostics_with_ticket_for_tests(
+            &state, uri, source, file_id, ticket,
+        )
+        .into_iter()
+        .filter(|diagnostic| {
+            diagnostic.code
+                == Some(tower_lsp::lsp_types::NumberOrString::String(
+                    "W019".to_string(),
+                ))
+        })
+        .collect::<Vec<_>>()
+    };
+
+    let alpha = conflicts(&alpha_uri, alpha_source);
+    assert_eq!(alpha.len(), 1);
+    assert!(alpha[0]
+        .message
+        .contains("this root (priority 10) takes precedence"));
+
+    let beta = conflicts(&beta_uri, beta_source);
+    assert_eq!(beta.len(), 1);
+    let diagnostic = beta[0].clone();
+    assert!(diagnostic
+        .message
+        .contains("(priority 10) takes precedence over this root (priority 1)"));
+    let related = diagnostic
+        .related_information
+        .as_ref()
+        .expect("related information");
+    assert_eq!(related[0].location.uri, alpha_uri);
+    assert_eq!(
+        related[0].location.range.start,
+        position_at(alpha_source, "Counter")
+    );
+    assert!(conflicts(&user_uri, user_source).is_empty());
+
+    let params = tower_lsp::lsp_types::CodeActionParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier {
+            uri: beta_uri.clone(),
+        },
+        range: diagnostic.range,
+        context: tower_lsp::lsp_types::CodeActionContext {
+            diagnostics: vec![diagnostic],
+            only: None,
+            trigger_kind: None,
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let actions = code_action(&state, params).expect("code actions");
+    let action = actions
+        .iter()
+        .find_map(|action| match action {
+            tower_lsp::lsp_types::CodeActionOrCommand::CodeAction(action)
+                if action
+                    .title
+                    .starts_with("Qualify references with Alpha.Counter") =>
+            {
+                Some(action)
+            }
+            _ => None,
+        })
+        .expect("qualify action");
+    assert_eq!(action.is_preferred, Some(true));
+    let changes = action
+        .edit
+        .as_ref()
+        .and_then(|edit| edit.changes.as_ref())
+        .expect("changes");
+    assert!(!changes.contains_key(&alpha_uri));
+    let edits = changes.get(&user_uri).expect("edits for main.st");
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].new_text, "Alpha.Counter");
+    assert_eq!(edits[0].range.start, position_at(user_source, "Counter;"));
+}
@@ -59,6 +59,7 @@ END_PROGRAM
                 allow: Some(vec!["ABS".to_string()]),
             },
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -66,9 +67,12 @@ END_PROGRAM
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -98,6 +102,77 @@ END_PROGRAM
         .any(|label| label.eq_ignore_ascii_case("SQRT")));
 }
 
+#[test]
+fn lsp_completion_offers_fb_call_template_with_configurable_params() {
+    let source = r#"
+FUNCTION_BLOCK Valve
+VAR_INPUT
+    Open : BOOL;
+    Timeout : TIME := T#5s;
+END_VAR
+VAR_OUTPUT
+    IsOpen : BOOL;
+END_VAR
+END_FUNCTION_BLOCK
+
+PROGRAM Test
+VAR
+    v : Valve;
+END_VAR
+    v();
+END_PROGRAM
+"#;
+    let template_for = |config: Option<&str>| {
+        let state = ServerState::new();
+        let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+        state.set_workspace_folders(vec![root_uri.clone()]);
+        if let Some(contents) = config {
+            state.set_workspace_config(
+                root_uri,
+                ProjectConfig::from_contents(std::path::Path::new("/workspace"), None, contents),
+            );
+        }
+        let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/test.st").unwrap();
+        state.open_document(uri.clone(), 1, source.to_string());
+        let params = tower_lsp::lsp_types::CompletionParams {
+            text_document_position: tower_lsp::lsp_types::TextDocumentPositionParams {
+                text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri },
+                position: position_at(source, ");\nEND_PROGRAM"),
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: None,
+        };
+        let items = match completion(&state, params).expect("completion response") {
+            tower_lsp::lsp_types::CompletionResponse::Array(items) => items,
+            tower_lsp::lsp_types::CompletionResponse::List(list) => list.items,
+        };
+        items
+            .into_iter()
+            .find(|item| item.label.starts_with("Valve("))
+    };
+
+    let all = template_for(None).expect("call template");
+    assert_eq!(all.label, "Valve(Open, Timeout, IsOpen)");
+    assert_eq!(
+        all.insert_text.as_deref(),
+        Some("\n\tOpen := ${1:BOOL},\n\tTimeout := ${2:TIME},\n\tIsOpen => ${3:BOOL}\n")
+    );
+    assert_eq!(
+        all.insert_text_format,
+        Some(tower_lsp::lsp_types::InsertTextFormat::SNIPPET)
+    );
+
+    let mandatory = template_for(Some("[completion]\ncall_template = \"mandatory\"\n"))
+        .expect("mandatory call template");
+    assert_eq!(
+        mandatory.insert_text.as_deref(),
+        Some("\n\tOpen := ${1:BOOL}\n")
+    );
+
+    assert!(template_for(Some("[completion]\ncall_template = \"off\"\n")).is_none());
+}
+
 #[test]
 fn lsp_completion_respects_stdlib_profile_none() {
     let source = r#"
@@ -123,6 +198,7 @@ END_PROGRAM
                 allow: None,
             },
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -130,9 +206,12 @@ END_PROGRAM
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -271,6 +350,7 @@ END_PROGRAM
                 allow: None,
             },
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -278,9 +358,12 @@ END_PROGRAM
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -298,3 +381,52 @@ END_PROGRAM
     let hover = hover(&state, params);
     assert!(hover.is_none(), "expected stdlib hover to be filtered");
 }
+
+#[test]
+fn lsp_runtime_toml_completes_keys_and_values_and_hovers_settings() {
+    let source = "[runtime.watchdog]\nenabled = true\naction = \"\"\n\n[runtime.fault]\n";
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/runtime.toml").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+    let complete_at = |position| {
+        let params = tower_lsp::lsp_types::CompletionParams {
+            text_document_position: tower_lsp::lsp_types::TextDocumentPositionParams {
+                text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+                position,
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: None,
+        };
+        let items = match completion(&state, params).expect("completion") {
+            tower_lsp::lsp_types::CompletionResponse::Array(items) => items,
+            tower_lsp::lsp_types::CompletionResponse::List(list) => list.items,
+        };
+        items.into_iter().map(|item| item.label).collect::<Vec<_>>()
+    };
+
+    let mut value_position = position_at(source, "\"\"\n");
+    value_position.character += 1;
+    assert_eq!(
+        complete_at(value_position),
+        vec!["halt", "safe_halt", "restart"]
+    );
+    let keys = complete_at(tower_lsp::lsp_types::Position::new(3, 0));
+    assert_eq!(keys, vec!["timeout_ms"], "keys already set are not offered");
+    let fault_keys = complete_at(tower_lsp::lsp_types::Position::new(5, 0));
+    assert_eq!(fault_keys, vec!["policy", "bounds", "overflow", "subrange"]);
+
+    let params = tower_lsp::lsp_types::HoverParams {
+        text_document_position_params: tower_lsp::lsp_types::TextDocumentPositionParams {
+            text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri },
+            position: position_at(source, "action"),
+        },
+        work_done_progress_params: Default::default(),
+    };
+    let hover = hover(&state, params).expect("hover action");
+    let tower_lsp::lsp_types::HoverContents::Markup(markup) = hover.contents else {
+        panic!("expected markdown hover");
+    };
+    assert!(markup.value.contains("runtime.watchdog.action"));
+    assert!(markup.value.contains("`safe_halt`"));
+}
@@ -193,6 +193,110 @@ END_FUNCTION_BLOCK
     assert!(has_text_edit, "expected text edits for new POU name");
 }
 
+#[test]
+fn lsp_rename_updates_config_file_paths() {
+    let root = temp_dir("trustlsp-rename-config");
+    std::fs::create_dir_all(root.join("hmi")).expect("create hmi dir");
+    std::fs::write(
+        root.join("runtime.toml"),
+        r#"[runtime.mesh]
+publish = ["P1.Count", "P1.Other"]
+
+[runtime.observability]
+include = ["P1.Count"] # P1.Count
+"#,
+    )
+    .expect("write runtime.toml");
+    std::fs::write(
+        root.join("hmi/overview.toml"),
+        r#"[[widget]]
+bind = "P1.Count"
+label = "P1.Count"
+"#,
+    )
+    .expect("write hmi page");
+    let source = r#"
+PROGRAM Main
+VAR
+    Count : INT;
+    Other : INT;
+END_VAR
+Count := Count + 1;
+END_PROGRAM
+
+CONFIGURATION Conf
+RESOURCE Res ON PLC
+TASK Fast (INTERVAL := T#10ms, PRIORITY := 1);
+PROGRAM P1 WITH Fast : Main;
+END_RESOURCE
+VAR_CONFIG
+    P1.Count : INT := 3;
+END_VAR
+END_CONFIGURATION
+"#;
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::from_file_path(&root).expect("root uri");
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(root_uri, ProjectConfig::load(&root));
+    let uri = tower_lsp::lsp_types::Url::from_file_path(root.join("main.st")).expect("uri");
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let params = tower_lsp::lsp_types::RenameParams {
+        text_document_position: tower_lsp::lsp_types::TextDocumentPositionParams {
+            text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+            position: position_at(source, "Count : INT"),
+        },
+        new_name: "Total".to_string(),
+        work_done_progress_params: Default::default(),
+    };
+    let edit = rename(&state, params).expect("rename edits");
+    let annotation = edit
+        .change_annotations
+        .as_ref()
+        .and_then(|annotations| annotations.get("config-references"))
+        .expect("config annotation");
+    assert_eq!(annotation.needs_confirmation, Some(true));
+
+    let Some(tower_lsp::lsp_types::DocumentChanges::Operations(operations)) = edit.document_changes
+    else {
+        panic!("expected document change operations");
+    };
+    let edits_for = |name: &str| -> Vec<(u32, u32, bool)> {
+        operations
+            .iter()
+            .filter_map(|operation| match operation {
+                tower_lsp::lsp_types::DocumentChangeOperation::Edit(edit)
+                    if edit.text_document.uri.path().ends_with(name) =>
+                {
+                    Some(edit.edits.iter().map(|edit| match edit {
+                        tower_lsp::lsp_types::OneOf::Left(edit) => {
+                            (edit.range.start.line, edit.range.start.character, false)
+                        }
+                        tower_lsp::lsp_types::OneOf::Right(edit) => (
+                            edit.text_edit.range.start.line,
+                            edit.text_edit.range.start.character,
+                            true,
+                        ),
+                    }))
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    };
+
+    let mut source_edits = edits_for("main.st");
+    source_edits.sort_unstable();
+    assert_eq!(
+        source_edits,
+        vec![(3, 4, false), (6, 0, false), (6, 9, false), (15, 7, false)]
+    );
+    let mut runtime_edits = edits_for("runtime.toml");
+    runtime_edits.sort_unstable();
+    assert_eq!(runtime_edits, vec![(1, 15, true), (4, 15, true)]);
+    assert_eq!(edits_for("overview.toml"), vec![(1, 11, true)]);
+}
+
 #[test]
 fn lsp_pull_diagnostics_returns_unchanged_and_explainer() {
     let source = r#"
@@ -324,6 +428,7 @@ END_PROGRAM
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -339,11 +444,19 @@ END_PROGRAM
                 warn_deprecated: true,
                 warn_complexity: false,
                 warn_nondeterminism: true,
+                warn_dead_code: false,
+                warn_loop_bounds: true,
+                warn_case_exhaustive: true,
+                check_recursion: true,
+                type_check: TypeCheckLevel::Standard,
                 severity_overrides: Default::default(),
             },
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -391,6 +504,242 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn lsp_lint_rules_report_configured_severity_and_rename_fix() {
+    let source = r#"
+PROGRAM main_program
+    VAR
+        x : INT;
+    END_VAR
+    x := x + 42;
+END_PROGRAM
+"#;
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(
+        root_uri,
+        ProjectConfig::from_contents(
+            Path::new("/workspace"),
+            None,
+            r#"
+[lint]
+preset = "misra"
+
+[lint.rules]
+magic_numbers = "error"
+loop_exit = "off"
+"#,
+        ),
+    );
+
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/lint.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+    let params = tower_lsp::lsp_types::DocumentDiagnosticParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+        identifier: None,
+        previous_result_id: None,
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let full = match document_diagnostic(&state, params) {
+        tower_lsp::lsp_types::DocumentDiagnosticReportResult::Report(
+            tower_lsp::lsp_types::DocumentDiagnosticReport::Full(full),
+        ) => full,
+        _ => panic!("expected full diagnostic report"),
+    };
+    let items = full.full_document_diagnostic_report.items;
+    let by_code = |code: &str| {
+        items
+            .iter()
+            .find(|diag| {
+                diag.code == Some(tower_lsp::lsp_types::NumberOrString::String(code.into()))
+            })
+            .cloned()
+    };
+
+    let magic = by_code("R005").expect("magic number diagnostic");
+    assert_eq!(
+        magic.severity,
+        Some(tower_lsp::lsp_types::DiagnosticSeverity::ERROR)
+    );
+    let naming = by_code("R003").expect("naming diagnostic");
+    assert_eq!(
+        naming.severity,
+        Some(tower_lsp::lsp_types::DiagnosticSeverity::WARNING)
+    );
+    assert!(naming.message.contains("MainProgram"), "{}", naming.message);
+
+    let params = tower_lsp::lsp_types::CodeActionParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri },
+        range: naming.range,
+        context: tower_lsp::lsp_types::CodeActionContext {
+            diagnostics: vec![naming],
+            only: None,
+            trigger_kind: None,
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let actions = code_action(&state, params).expect("code actions");
+    let rename_edits = actions.iter().find_map(|action| match action {
+        tower_lsp::lsp_types::CodeActionOrCommand::CodeAction(action)
+            if action.title == "Rename to 'MainProgram'" =>
+        {
+            action
+                .edit
+                .as_ref()
+                .and_then(|edit| edit.changes.as_ref())
+                .and_then(|changes| changes.values().next())
+                .cloned()
+        }
+        _ => None,
+    });
+    let rename_edits = rename_edits.expect("rename quick fix");
+    assert!(rename_edits
+        .iter()
+        .all(|edit| edit.new_text == "MainProgram"));
+}
+
+fn region_dialect_state(
+    vendor_profile: Option<&str>,
+    source: &str,
+) -> (ServerState, tower_lsp::lsp_types::Url) {
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(
+        root_uri,
+        ProjectConfig {
+            root: PathBuf::from("/workspace"),
+            config_path: None,
+            include_paths: Vec::new(),
+            vendor_profile: vendor_profile.map(str::to_string),
+            stdlib: StdlibSettings::default(),
+            libraries: Vec::new(),
+            doc_bundles: Vec::new(),
+            dependencies: Vec::new(),
+            dependency_resolution_issues: Vec::new(),
+            diagnostic_external_paths: Vec::new(),
+            build: BuildConfig::default(),
+            targets: Vec::new(),
+            indexing: IndexingConfig::default(),
+            diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
+            runtime: RuntimeConfig::default(),
+            workspace: WorkspaceSettings::default(),
+            telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
+        },
+    );
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/regions.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+    (state, uri)
+}
+
+fn error_codes(state: &ServerState, uri: &tower_lsp::lsp_types::Url) -> Vec<String> {
+    diagnostic_codes(state, uri, tower_lsp::lsp_types::DiagnosticSeverity::ERROR)
+}
+
+fn diagnostic_codes(
+    state: &ServerState,
+    uri: &tower_lsp::lsp_types::Url,
+    severity: tower_lsp::lsp_types::DiagnosticSeverity,
+) -> Vec<String> {
+    let params = tower_lsp::lsp_types::DocumentDiagnosticParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+        identifier: None,
+        previous_result_id: None,
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let tower_lsp::lsp_types::DocumentDiagnosticReportResult::Report(
+        tower_lsp::lsp_types::DocumentDiagnosticReport::Full(full),
+    ) = document_diagnostic(state, params)
+    else {
+        panic!("expected full diagnostic report");
+    };
+    full.full_document_diagnostic_report
+        .items
+        .iter()
+        .filter(|diag| diag.severity == Some(severity))
+        .filter_map(|diag| match diag.code.as_ref()? {
+            tower_lsp::lsp_types::NumberOrString::String(value) => Some(value.clone()),
+            tower_lsp::lsp_types::NumberOrString::Number(value) => Some(value.to_string()),
+        })
+        .collect()
+}
+
+#[test]
+fn lsp_vendor_dialect_gates_region_blocks() {
+    let source = r#"
+PROGRAM Main
+VAR
+    x : INT;
+END_VAR
+REGION Init
+    x := 1;
+END_REGION
+
+END_PROGRAM
+"#;
+
+    let (state, uri) = region_dialect_state(None, source);
+    assert_eq!(error_codes(&state, &uri), vec!["E004", "E004"]);
+
+    let (state, uri) = region_dialect_state(Some("siemens"), source);
+    assert!(error_codes(&state, &uri).is_empty());
+
+    let params = tower_lsp::lsp_types::FoldingRangeParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let folds = folding_range(&state, params).expect("folding ranges");
+    assert!(folds
+        .iter()
+        .any(|range| range.start_line == 5 && range.end_line == 7));
+
+    let mut position = position_at(source, "END_REGION\n\n");
+    position.line += 1;
+    position.character = 0;
+    let params = tower_lsp::lsp_types::CompletionParams {
+        text_document_position: tower_lsp::lsp_types::TextDocumentPositionParams {
+            text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri },
+            position,
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+        context: None,
+    };
+    let Some(tower_lsp::lsp_types::CompletionResponse::Array(items)) = completion(&state, params)
+    else {
+        panic!("expected completion items");
+    };
+    assert!(items.iter().any(|item| item.label == "REGION"));
+}
+
+#[test]
+fn lsp_vendor_dialect_gates_integer_bit_access() {
+    let source = r#"
+PROGRAM Main
+VAR
+    code : INT;
+    flag : BOOL;
+END_VAR
+flag := code.15;
+END_PROGRAM
+"#;
+    let warning = tower_lsp::lsp_types::DiagnosticSeverity::WARNING;
+
+    let (state, uri) = region_dialect_state(None, source);
+    assert!(diagnostic_codes(&state, &uri, warning).contains(&"W007".to_string()));
+
+    let (state, uri) = region_dialect_state(Some("twincat"), source);
+    assert!(!diagnostic_codes(&state, &uri, warning).contains(&"W007".to_string()));
+}
+
 #[test]
 fn lsp_learner_diagnostics_include_did_you_mean_and_conversion_guidance() {
     let source = r#"
@@ -672,6 +1021,84 @@ dependencies = [{ name = "Core", version = "2.0" }, { name = "Missing" }]
     assert!(codes.contains(&"L002".to_string()));
 }
 
+#[test]
+fn lsp_runtime_toml_diagnostics_use_schema_and_runtime_validator() {
+    let valid = r#"
+[bundle]
+version = 1
+
+[resource]
+name = "RESOURCE"
+cycle_interval_ms = 100
+
+[runtime.control]
+endpoint = "unix:///tmp/trust-runtime.sock"
+
+[runtime.log]
+level = "info"
+
+[runtime.retain]
+mode = "none"
+save_interval_ms = 1000
+
+[runtime.watchdog]
+enabled = false
+timeout_ms = 5000
+action = "halt"
+
+[runtime.fault]
+policy = "halt"
+"#;
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/runtime.toml").unwrap();
+    let diagnostics_for = |content: &str| {
+        state.open_document(uri.clone(), 1, content.to_string());
+        let params = tower_lsp::lsp_types::DocumentDiagnosticParams {
+            text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+            identifier: None,
+            previous_result_id: None,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        match document_diagnostic(&state, params) {
+            tower_lsp::lsp_types::DocumentDiagnosticReportResult::Report(
+                tower_lsp::lsp_types::DocumentDiagnosticReport::Full(full),
+            ) => full.full_document_diagnostic_report.items,
+            _ => panic!("expected full diagnostic report"),
+        }
+    };
+    let code = |diagnostic: &tower_lsp::lsp_types::Diagnostic| match diagnostic.code.as_ref() {
+        Some(tower_lsp::lsp_types::NumberOrString::String(value)) => value.clone(),
+        _ => String::new(),
+    };
+
+    assert!(diagnostics_for(valid).is_empty());
+
+    let typo = valid.replace("timeout_ms = 5000", "timeout = 5000");
+    let diagnostics = diagnostics_for(&typo);
+    let codes = diagnostics.iter().map(code).collect::<Vec<_>>();
+    assert_eq!(codes, ["CONFIG_UNKNOWN_KEY", "CONFIG_MISSING_KEY"]);
+    let typo_line = typo
+        .lines()
+        .position(|line| line.starts_with("timeout ="))
+        .unwrap() as u32;
+    assert_eq!(diagnostics[0].range.start.line, typo_line);
+
+    let bad_action = valid.replace("action = \"halt\"", "action = \"reboot\"");
+    let diagnostics = diagnostics_for(&bad_action);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(code(&diagnostics[0]), "CONFIG_INVALID");
+    assert!(diagnostics[0].message.contains("invalid watchdog action"));
+    let action_line = bad_action
+        .lines()
+        .position(|line| line.starts_with("action ="))
+        .unwrap() as u32;
+    assert_eq!(diagnostics[0].range.start.line, action_line);
+
+    let diagnostics = diagnostics_for("[runtime\n");
+    assert_eq!(code(&diagnostics[0]), "CONFIG_TOML_PARSE");
+}
+
 #[test]
 fn lsp_config_diagnostics_report_dependency_cycle_issues() {
     let root = temp_dir("trustlsp-cycle-config");
@@ -841,6 +1268,92 @@ END_FUNCTION
     std::fs::remove_dir_all(root).ok();
 }
 
+#[test]
+fn lsp_indexes_precompiled_library_archive_interface() {
+    let root = temp_dir("trustlsp-stlib-dependency");
+    std::fs::create_dir_all(root.join("libs")).expect("create libs dir");
+    let archive = trust_runtime::stlib::LibraryArchive::from_sources(
+        "Valves",
+        Some("0.3.0".to_string()),
+        vec![trust_runtime::harness::SourceFile::with_path(
+            "valve.st",
+            r#"
+FUNCTION_BLOCK ValveCtrl
+VAR_INPUT
+    Open : BOOL;
+END_VAR
+VAR
+    Hidden : INT;
+END_VAR
+Hidden := Hidden + 1;
+END_FUNCTION_BLOCK
+"#,
+        )],
+    )
+    .expect("build archive");
+    let archive_path = root.join("libs/Valves.stlib");
+    std::fs::write(&archive_path, archive.encode().expect("encode archive"))
+        .expect("write archive");
+    std::fs::write(
+        root.join("trust-lsp.toml"),
+        r#"
+[dependencies]
+Valves = { path = "libs/Valves.stlib", version = "0.3.0" }
+"#,
+    )
+    .expect("write root config");
+
+    let config = crate::config::ProjectConfig::load(&root);
+    assert!(config.dependency_resolution_issues.is_empty());
+    assert!(config
+        .libraries
+        .iter()
+        .any(|lib| lib.name == "Valves" && lib.version.as_deref() == Some("0.3.0")));
+
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::from_file_path(&root).expect("root uri");
+    state.set_workspace_folders(vec![root_uri]);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("tokio runtime");
+    runtime.block_on(async {
+        let client = test_client();
+        index_workspace(&client, &state).await;
+    });
+
+    let archive_norm =
+        normalize_path_for_assert(&archive_path.canonicalize().expect("archive path"));
+    let mut found_interface_symbol = false;
+    for _ in 0..40 {
+        let symbols = workspace_symbol(
+            &state,
+            tower_lsp::lsp_types::WorkspaceSymbolParams {
+                query: "ValveCtrl".to_string(),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            },
+        )
+        .expect("workspace symbols");
+        found_interface_symbol = symbols.iter().any(|symbol| {
+            let Some(path) = symbol.location.uri.to_file_path().ok() else {
+                return false;
+            };
+            let path = path.canonicalize().unwrap_or(path);
+            symbol.name.starts_with("ValveCtrl") && normalize_path_for_assert(&path) == archive_norm
+        });
+        if found_interface_symbol {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(25));
+    }
+    assert!(
+        found_interface_symbol,
+        "expected archive interface symbol to be indexed"
+    );
+    std::fs::remove_dir_all(root).ok();
+}
+
 #[test]
 fn lsp_external_diagnostics_provide_quick_fixes() {
     let root = temp_dir("trustlsp-external-diag");
@@ -878,6 +1391,7 @@ fn lsp_external_diagnostics_provide_quick_fixes() {
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: vec![lint_path.clone()],
@@ -885,9 +1399,12 @@ fn lsp_external_diagnostics_provide_quick_fixes() {
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -1043,6 +1560,95 @@ END_FUNCTION_BLOCK
     assert!(has_next_in_fb, "expected Next under CounterFb");
 }
 
+#[test]
+fn lsp_outline_includes_actions_regions_and_case_branches() {
+    let source = r#"
+FUNCTION_BLOCK Sequencer
+VAR
+    phase : INT;
+END_VAR
+{region Sequence}
+CASE phase OF
+    0:
+        phase := 10;
+    10, 20..25:
+        phase := 30;
+    30:
+        phase := 0;
+ELSE
+    phase := 0;
+END_CASE
+{endregion}
+
+ACTION Reset
+    phase := 0;
+END_ACTION
+END_FUNCTION_BLOCK
+"#;
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///sequencer.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let params = tower_lsp::lsp_types::DocumentSymbolParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let response = document_symbol(&state, params).expect("document symbols");
+    let symbols = match response {
+        tower_lsp::lsp_types::DocumentSymbolResponse::Flat(symbols) => symbols,
+        tower_lsp::lsp_types::DocumentSymbolResponse::Nested(_) => {
+            panic!("expected flat document symbols")
+        }
+    };
+    let entry = |name: &str| {
+        symbols
+            .iter()
+            .find(|symbol| symbol.name == name)
+            .map(|symbol| (symbol.kind, symbol.container_name.clone()))
+    };
+    assert_eq!(
+        entry("Reset (ACTION)"),
+        Some((
+            tower_lsp::lsp_types::SymbolKind::METHOD,
+            Some("Sequencer".to_string())
+        ))
+    );
+    assert_eq!(
+        entry("Sequence"),
+        Some((
+            tower_lsp::lsp_types::SymbolKind::PACKAGE,
+            Some("Sequencer".to_string())
+        ))
+    );
+    assert_eq!(
+        entry("CASE phase"),
+        Some((
+            tower_lsp::lsp_types::SymbolKind::ENUM,
+            Some("Sequencer".to_string())
+        ))
+    );
+    let branches: Vec<_> = symbols
+        .iter()
+        .filter(|symbol| symbol.container_name.as_deref() == Some("CASE phase"))
+        .map(|symbol| (symbol.name.as_str(), symbol.location.range.start.line))
+        .collect();
+    assert_eq!(
+        branches,
+        vec![("0", 7), ("10, 20..25", 9), ("30", 11), ("ELSE", 13)]
+    );
+
+    let params = tower_lsp::lsp_types::FoldingRangeParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let folds = folding_range(&state, params).expect("folding ranges");
+    assert!(folds
+        .iter()
+        .any(|range| range.start_line == 5 && range.end_line == 16));
+}
+
 #[test]
 fn lsp_oop_access_diagnostics_include_explainer_and_hint() {
     let source = r#"
@@ -1179,6 +1785,39 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn lsp_workspace_diagnostics_update_status_counts() {
+    let source = r#"
+PROGRAM Test
+    VAR
+        x : INT;
+    END_VAR
+    x := missing_name;
+END_PROGRAM
+"#;
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///status-counts.st").unwrap();
+    state.open_document(uri, 1, source.to_string());
+    assert_eq!(state.analysis_status().errors, 0);
+
+    let params = tower_lsp::lsp_types::WorkspaceDiagnosticParams {
+        identifier: None,
+        previous_result_ids: Vec::new(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let _ = workspace_diagnostic(&state, params);
+
+    let status = state.analysis_status();
+    assert!(status.errors >= 1, "expected error count, got {status:?}");
+    assert_eq!(status.files_open, 1);
+    assert_eq!(status.queue_depth, 0);
+    let value = serde_json::to_value(&status).expect("serialize status");
+    assert_eq!(value["phase"], "idle");
+    assert!(value.get("filesIndexed").is_some());
+    assert!(value.get("queueDepth").is_some());
+}
+
 #[test]
 fn lsp_will_rename_files_updates_pou_name() {
     let source_decl = r#"
@@ -1287,6 +1926,61 @@ END_PROGRAM
     );
 }
 
+#[test]
+fn lsp_direct_address_references_and_symbols_span_documents() {
+    let source_one = r#"
+PROGRAM Main
+VAR
+    Lamp AT %QX0.3 : BOOL;
+END_VAR
+Lamp := TRUE;
+END_PROGRAM
+"#;
+    let source_two = r#"
+FUNCTION_BLOCK Blinker
+%Q0.3 := NOT %QX0.3;
+END_FUNCTION_BLOCK
+"#;
+    let state = ServerState::new();
+    let uri_one = tower_lsp::lsp_types::Url::parse("file:///one.st").unwrap();
+    let uri_two = tower_lsp::lsp_types::Url::parse("file:///two.st").unwrap();
+    state.open_document(uri_one.clone(), 1, source_one.to_string());
+    state.open_document(uri_two.clone(), 1, source_two.to_string());
+
+    let params = tower_lsp::lsp_types::ReferenceParams {
+        text_document_position: tower_lsp::lsp_types::TextDocumentPositionParams {
+            text_document: tower_lsp::lsp_types::TextDocumentIdentifier {
+                uri: uri_two.clone(),
+            },
+            position: position_at(source_two, "%Q0.3"),
+        },
+        context: tower_lsp::lsp_types::ReferenceContext {
+            include_declaration: true,
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let refs = references(&state, params).expect("references");
+    assert_eq!(refs.len(), 3, "declaration, write, and read: {refs:?}");
+    assert_eq!(
+        refs.iter()
+            .filter(|location| location.uri == uri_one)
+            .count(),
+        1
+    );
+
+    let params = tower_lsp::lsp_types::WorkspaceSymbolParams {
+        query: "%qx0".to_string(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let items = workspace_symbol(&state, params).expect("workspace symbols");
+    assert_eq!(items.len(), 1, "only AT bindings are listed: {items:?}");
+    assert_eq!(items[0].name, "%QX0.3");
+    assert_eq!(items[0].container_name.as_deref(), Some("Lamp"));
+    assert_eq!(items[0].location.uri, uri_one);
+}
+
 #[test]
 fn lsp_workspace_symbols_respect_root_visibility_and_priority() {
     let source = r#"
@@ -1309,6 +2003,7 @@ END_FUNCTION_BLOCK
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -1316,12 +2011,15 @@ END_FUNCTION_BLOCK
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings {
                 priority: 10,
                 visibility: crate::config::WorkspaceVisibility::Public,
             },
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
     state.set_workspace_config(
@@ -1333,6 +2031,7 @@ END_FUNCTION_BLOCK
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -1340,12 +2039,15 @@ END_FUNCTION_BLOCK
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings {
                 priority: 1,
                 visibility: crate::config::WorkspaceVisibility::Private,
             },
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -1627,6 +2329,7 @@ fn lsp_inline_values_fetch_runtime_values_from_control_stub() {
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -1634,12 +2337,15 @@ fn lsp_inline_values_fetch_runtime_values_from_control_stub() {
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig {
                 control_endpoint: Some(endpoint),
                 control_auth_token: None,
             },
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -1664,6 +2370,273 @@ fn lsp_inline_values_fetch_runtime_values_from_control_stub() {
     handle.join().expect("control stub thread");
 }
 
+#[test]
+fn lsp_live_values_read_runtime_snapshot_without_paused_frame() {
+    let (endpoint, handle) = spawn_control_stub();
+    let source = runtime_inline_values_source();
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(
+        root_uri,
+        ProjectConfig {
+            root: PathBuf::from("/workspace"),
+            config_path: None,
+            include_paths: Vec::new(),
+            vendor_profile: None,
+            stdlib: StdlibSettings::default(),
+            libraries: Vec::new(),
+            doc_bundles: Vec::new(),
+            dependencies: Vec::new(),
+            dependency_resolution_issues: Vec::new(),
+            diagnostic_external_paths: Vec::new(),
+            build: BuildConfig::default(),
+            targets: Vec::new(),
+            indexing: IndexingConfig::default(),
+            diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
+            runtime: RuntimeConfig {
+                control_endpoint: Some(endpoint),
+                control_auth_token: None,
+            },
+            workspace: WorkspaceSettings::default(),
+            telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
+        },
+    );
+
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/runtime.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let result = super::commands::live_values_value(
+        &state,
+        vec![json!({
+            "text_document": { "uri": uri },
+            "range": {
+                "start": position_at(source, "x := x"),
+                "end": position_at(source, "END_PROGRAM"),
+            },
+        })],
+    )
+    .expect("live values response");
+    assert_eq!(result["ok"], json!(true), "{result}");
+    let texts: Vec<&str> = result["values"]
+        .as_array()
+        .expect("values array")
+        .iter()
+        .filter_map(|value| value["text"].as_str())
+        .collect();
+
+    assert!(texts.contains(&" = DInt(11)"));
+    assert!(texts.contains(&" = DInt(42)"));
+    // Without a paused frame, the stub's frame locals must not be used.
+    assert!(!texts.contains(&" = DInt(7)"));
+
+    handle.join().expect("control stub thread");
+}
+
+#[test]
+fn lsp_hover_charts_runtime_history_of_variable() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind control stub");
+    let endpoint = format!(
+        "tcp://{}",
+        listener.local_addr().expect("control stub addr")
+    );
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("accept control stub");
+        let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+        let mut writer = std::io::BufWriter::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line).expect("read line");
+        let payload: serde_json::Value = serde_json::from_str(line.trim()).expect("payload");
+        assert_eq!(
+            payload.get("type").and_then(Value::as_str),
+            Some("debug.history")
+        );
+        let samples = [0.0, 5.0, 10.0, 5.0]
+            .iter()
+            .enumerate()
+            .map(|(cycle, number)| json!({ "cycle": cycle, "time_ns": 0, "value": "", "number": number }))
+            .collect::<Vec<_>>();
+        let response = json!({
+            "id": payload.get("id").cloned().unwrap_or(Value::Null),
+            "ok": true,
+            "result": { "depth": 100, "variables": [{ "name": "Test.x", "samples": samples }] },
+        });
+        writeln!(writer, "{response}").expect("write response");
+        writer.flush().expect("flush response");
+        payload
+            .pointer("/params/variables/0")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    });
+
+    let source = runtime_inline_values_source();
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(
+        root_uri,
+        ProjectConfig {
+            root: PathBuf::from("/workspace"),
+            config_path: None,
+            include_paths: Vec::new(),
+            vendor_profile: None,
+            stdlib: StdlibSettings::default(),
+            libraries: Vec::new(),
+            doc_bundles: Vec::new(),
+            dependencies: Vec::new(),
+            dependency_resolution_issues: Vec::new(),
+            diagnostic_external_paths: Vec::new(),
+            build: BuildConfig::default(),
+            targets: Vec::new(),
+            indexing: IndexingConfig::default(),
+            diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
+            runtime: RuntimeConfig {
+                control_endpoint: Some(endpoint),
+                control_auth_token: None,
+            },
+            workspace: WorkspaceSettings::default(),
+            telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
+        },
+    );
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/runtime.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let params = tower_lsp::lsp_types::HoverParams {
+        text_document_position_params: tower_lsp::lsp_types::TextDocumentPositionParams {
+            text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri },
+            position: position_at(source, "x := x"),
+        },
+        work_done_progress_params: Default::default(),
+    };
+    let hover = hover(&state, params).expect("hover result");
+    let tower_lsp::lsp_types::HoverContents::Markup(markup) = hover.contents else {
+        panic!("expected markup hover");
+    };
+    assert!(markup.value.contains("**History** (last 4 cycles)"));
+    assert!(markup.value.contains("`▁▅█▅`"));
+    assert!(markup.value.contains("min 0 · max 10 · last 5"));
+    assert_eq!(handle.join().expect("control stub thread"), "Test.x");
+}
+
+#[test]
+fn lsp_evaluate_selection_qualifies_namespaces_and_queries_runtime() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind control stub");
+    let endpoint = format!(
+        "tcp://{}",
+        listener.local_addr().expect("control stub addr")
+    );
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("accept control stub");
+        let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+        let mut writer = std::io::BufWriter::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line).expect("read line");
+        let payload: serde_json::Value = serde_json::from_str(line.trim()).expect("payload");
+        assert_eq!(
+            payload.get("type").and_then(Value::as_str),
+            Some("debug.evaluate")
+        );
+        let expression = payload
+            .pointer("/params/expression")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let response = json!({
+            "id": payload.get("id").cloned().unwrap_or(Value::Null),
+            "ok": true,
+            "result": { "result": "INT(14)", "type": "INT", "variables_reference": 0 },
+        });
+        writeln!(writer, "{response}").expect("write response");
+        writer.flush().expect("flush response");
+        expression
+    });
+
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(
+        root_uri,
+        ProjectConfig {
+            root: PathBuf::from("/workspace"),
+            config_path: None,
+            include_paths: Vec::new(),
+            vendor_profile: None,
+            stdlib: StdlibSettings::default(),
+            libraries: Vec::new(),
+            doc_bundles: Vec::new(),
+            dependencies: Vec::new(),
+            dependency_resolution_issues: Vec::new(),
+            diagnostic_external_paths: Vec::new(),
+            build: BuildConfig::default(),
+            targets: Vec::new(),
+            indexing: IndexingConfig::default(),
+            diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
+            runtime: RuntimeConfig {
+                control_endpoint: Some(endpoint),
+                control_auth_token: None,
+            },
+            workspace: WorkspaceSettings::default(),
+            telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
+        },
+    );
+    let source = r#"
+NAMESPACE Plant
+FUNCTION Double : INT
+VAR_INPUT
+    A : INT;
+END_VAR
+    Double := A * 2;
+END_FUNCTION
+END_NAMESPACE
+
+PROGRAM Main
+USING Plant;
+VAR
+    x : INT;
+END_VAR
+    x := Double(7);
+END_PROGRAM
+"#;
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/main.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let start = position_at(source, "Double(7)");
+    let mut end = start;
+    end.character += "Double(7)".len() as u32;
+    let result = super::commands::evaluate_selection_value(
+        &state,
+        vec![json!({
+            "text_document": { "uri": uri },
+            "range": { "start": start, "end": end },
+        })],
+    )
+    .expect("evaluate result");
+
+    assert_eq!(result.get("ok").and_then(Value::as_bool), Some(true));
+    assert_eq!(
+        result.get("expression").and_then(Value::as_str),
+        Some("Plant.Double(7)")
+    );
+    assert_eq!(
+        result.get("result").and_then(Value::as_str),
+        Some("INT(14)")
+    );
+    assert_eq!(
+        handle.join().expect("control stub thread"),
+        "Plant.Double(7)"
+    );
+}
+
 #[test]
 fn lsp_inline_values_runtime_override_accepts_camel_case_client_settings() {
     let (endpoint, handle) = spawn_control_stub();
@@ -1680,6 +2653,7 @@ fn lsp_inline_values_runtime_override_accepts_camel_case_client_settings() {
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -1687,9 +2661,12 @@ fn lsp_inline_values_runtime_override_accepts_camel_case_client_settings() {
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
     state.set_config(json!({
@@ -1735,6 +2712,7 @@ fn lsp_inline_values_runtime_override_accepts_snake_case_client_settings() {
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -1742,9 +2720,12 @@ fn lsp_inline_values_runtime_override_accepts_snake_case_client_settings() {
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
     state.set_config(json!({
@@ -1794,6 +2775,7 @@ fn lsp_inline_values_runtime_override_prefers_camel_case_when_aliases_conflict()
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -1801,9 +2783,12 @@ fn lsp_inline_values_runtime_override_prefers_camel_case_when_aliases_conflict()
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
     state.set_config(json!({
@@ -1867,6 +2852,7 @@ END_PROGRAM
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -1874,12 +2860,15 @@ END_PROGRAM
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig {
                 control_endpoint: Some(endpoint),
                 control_auth_token: None,
             },
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -1941,6 +2930,7 @@ END_NAMESPACE
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -1948,12 +2938,15 @@ END_NAMESPACE
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig {
                 control_endpoint: Some(endpoint),
                 control_auth_token: None,
             },
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -1990,6 +2983,94 @@ END_NAMESPACE
     handle.join().expect("control stub thread");
 }
 
+#[test]
+fn lsp_force_code_lenses_map_declarations_to_control_targets() {
+    let (endpoint, requests, handle) = spawn_force_control_stub("TestProgram#4");
+    let source = r#"
+PROGRAM TestProgram
+VAR
+    x : DINT;
+END_VAR
+    x := x + 1;
+END_PROGRAM
+"#;
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(
+        root_uri,
+        ProjectConfig {
+            root: PathBuf::from("/workspace"),
+            config_path: None,
+            include_paths: Vec::new(),
+            vendor_profile: None,
+            stdlib: StdlibSettings::default(),
+            libraries: Vec::new(),
+            doc_bundles: Vec::new(),
+            dependencies: Vec::new(),
+            dependency_resolution_issues: Vec::new(),
+            diagnostic_external_paths: Vec::new(),
+            build: BuildConfig::default(),
+            targets: Vec::new(),
+            indexing: IndexingConfig::default(),
+            diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
+            runtime: RuntimeConfig {
+                control_endpoint: Some(endpoint),
+                control_auth_token: None,
+            },
+            workspace: WorkspaceSettings::default(),
+            telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
+        },
+    );
+
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/runtime.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let lenses = code_lens(
+        &state,
+        tower_lsp::lsp_types::CodeLensParams {
+            text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        },
+    )
+    .expect("code lenses");
+    let declaration = position_at(source, "x : DINT");
+    let titles: Vec<&str> = lenses
+        .iter()
+        .filter(|lens| lens.range.start == declaration)
+        .filter_map(|lens| lens.command.as_ref().map(|command| command.title.as_str()))
+        .collect();
+    assert!(titles.contains(&"Force…"), "{titles:?}");
+    assert!(titles.contains(&"Unforce"), "{titles:?}");
+    assert!(titles.contains(&"Show forced"), "{titles:?}");
+
+    let result = super::commands::force_variable_value(
+        &state,
+        vec![json!({
+            "text_document": { "uri": uri },
+            "position": declaration,
+            "value": "5",
+        })],
+        true,
+    )
+    .expect("force response");
+    assert_eq!(result["ok"], json!(true), "{result}");
+    assert_eq!(result["target"], json!("instance:4:x"));
+
+    handle.join().expect("control stub thread");
+    let requests = requests.lock().expect("requests lock");
+    let force = requests
+        .iter()
+        .find(|request| request["type"] == "var.force")
+        .expect("var.force request");
+    assert_eq!(force["params"]["target"], json!("instance:4:x"));
+    assert_eq!(force["params"]["value"], json!("5"));
+}
+
 #[test]
 fn lsp_tutorial_examples_no_unexpected_diagnostics_snapshot() {
     let tutorials = [
@@ -2043,6 +3124,7 @@ fn lsp_tutorial_examples_no_unexpected_diagnostics_snapshot() {
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -2058,11 +3140,19 @@ fn lsp_tutorial_examples_no_unexpected_diagnostics_snapshot() {
                 warn_deprecated: false,
                 warn_complexity: false,
                 warn_nondeterminism: false,
+                warn_dead_code: false,
+                warn_loop_bounds: true,
+                warn_case_exhaustive: true,
+                check_recursion: true,
+                type_check: TypeCheckLevel::Standard,
                 severity_overrides: Default::default(),
             },
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
     let mut output = serde_json::Map::new();
@@ -2140,6 +3230,7 @@ fn lsp_siemens_hash_prefixed_example_has_no_unexpected_diagnostics() {
             vendor_profile: Some("siemens".to_string()),
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -2155,11 +3246,19 @@ fn lsp_siemens_hash_prefixed_example_has_no_unexpected_diagnostics() {
                 warn_deprecated: false,
                 warn_complexity: false,
                 warn_nondeterminism: false,
+                warn_dead_code: false,
+                warn_loop_bounds: true,
+                warn_case_exhaustive: true,
+                check_recursion: true,
+                type_check: TypeCheckLevel::Standard,
                 severity_overrides: Default::default(),
             },
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -2215,6 +3314,7 @@ fn lsp_mitsubishi_gxworks3_example_has_no_unexpected_diagnostics() {
             vendor_profile: Some("mitsubishi".to_string()),
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -2230,11 +3330,19 @@ fn lsp_mitsubishi_gxworks3_example_has_no_unexpected_diagnostics() {
                 warn_deprecated: false,
                 warn_complexity: false,
                 warn_nondeterminism: false,
+                warn_dead_code: false,
+                warn_loop_bounds: true,
+                warn_case_exhaustive: true,
+                check_recursion: true,
+                type_check: TypeCheckLevel::Standard,
                 severity_overrides: Default::default(),
             },
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -2428,3 +3536,70 @@ fn spawn_control_stub_with_instances(instance_name: &str) -> (String, thread::Jo
 
     (format!("tcp://{addr}"), handle)
 }
+
+fn spawn_force_control_stub(
+    instance_name: &str,
+) -> (
+    String,
+    Arc<std::sync::Mutex<Vec<serde_json::Value>>>,
+    thread::JoinHandle<()>,
+) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind control stub");
+    let addr = listener.local_addr().expect("control stub addr");
+    let instance_name = instance_name.to_string();
+    let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&requests);
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("accept control stub");
+        let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+        let mut writer = std::io::BufWriter::new(stream);
+
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).expect("read line") == 0 {
+                break;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let payload: serde_json::Value =
+                serde_json::from_str(line.trim()).expect("parse payload");
+            let id = payload
+                .get("id")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(0);
+            let kind = payload
+                .get("type")
+                .and_then(|value| value.as_str())
+                .unwrap_or("")
+                .to_string();
+            recorded.lock().expect("requests lock").push(payload);
+            let response = match kind.as_str() {
+                "debug.scopes" => json!({
+                    "id": id,
+                    "ok": true,
+                    "result": {
+                        "scopes": [{ "name": "Instances", "variablesReference": 2 }]
+                    }
+                }),
+                "debug.variables" => json!({
+                    "id": id,
+                    "ok": true,
+                    "result": {
+                        "variables": [{
+                            "name": instance_name.clone(),
+                            "value": "Instance(4)",
+                            "variablesReference": 10
+                        }]
+                    }
+                }),
+                "var.force" => json!({ "id": id, "ok": true, "result": { "status": "forced" } }),
+                _ => json!({ "id": id, "ok": false, "error": "unknown request" }),
+            };
+            writeln!(writer, "{response}").expect("write response");
+            writer.flush().expect("flush response");
+        }
+    });
+
+    (format!("tcp://{addr}"), requests, handle)
+}
@@ -59,6 +59,103 @@ END_PROGRAM
     .assert_eq(&json);
 }
 
+#[test]
+fn lsp_signature_help_and_hover_include_doc_bundles() {
+    let root = temp_dir("trustlsp-doc-bundles");
+    let bundle = root.join("docs");
+    std::fs::create_dir_all(bundle.join("siemens")).expect("create bundle dir");
+    std::fs::write(bundle.join("Scale.md"), "Scales a raw analog value.\n").expect("write doc");
+    std::fs::write(
+        bundle.join("siemens").join("Scale.md"),
+        "# SCALE\n\nMatches the TIA Portal SCALE_X conventions.\n",
+    )
+    .expect("write vendor doc");
+
+    let source = r#"
+FUNCTION Scale : REAL
+VAR_INPUT
+    raw : INT;
+END_VAR
+    Scale := INT_TO_REAL(raw);
+END_FUNCTION
+
+PROGRAM Main
+VAR
+    delay : TON;
+    value : REAL;
+END_VAR
+    value := Scale(1|);
+END_PROGRAM
+"#;
+    let cursor = source.find('|').expect("cursor");
+    let mut cleaned = source.to_string();
+    cleaned.remove(cursor);
+
+    let state = ServerState::new();
+    let root_uri = tower_lsp::lsp_types::Url::from_file_path(&root).unwrap();
+    state.set_workspace_folders(vec![root_uri.clone()]);
+    state.set_workspace_config(
+        root_uri,
+        ProjectConfig {
+            root: root.clone(),
+            config_path: None,
+            include_paths: Vec::new(),
+            vendor_profile: Some("siemens".to_string()),
+            stdlib: StdlibSettings::default(),
+            libraries: Vec::new(),
+            doc_bundles: vec![bundle],
+            dependencies: Vec::new(),
+            dependency_resolution_issues: Vec::new(),
+            diagnostic_external_paths: Vec::new(),
+            build: BuildConfig::default(),
+            targets: Vec::new(),
+            indexing: IndexingConfig::default(),
+            diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
+            runtime: RuntimeConfig::default(),
+            workspace: WorkspaceSettings::default(),
+            telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
+        },
+    );
+    let uri = tower_lsp::lsp_types::Url::from_file_path(root.join("main.st")).unwrap();
+    state.open_document(uri.clone(), 1, cleaned.clone());
+
+    let params = tower_lsp::lsp_types::SignatureHelpParams {
+        text_document_position_params: tower_lsp::lsp_types::TextDocumentPositionParams {
+            text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+            position: super::lsp_utils::offset_to_position(&cleaned, cursor as u32),
+        },
+        work_done_progress_params: Default::default(),
+        context: None,
+    };
+    let result = signature_help(&state, params).expect("signature help");
+    let Some(tower_lsp::lsp_types::Documentation::MarkupContent(doc)) =
+        result.signatures[0].documentation.as_ref()
+    else {
+        panic!("expected markdown signature documentation");
+    };
+    assert!(doc.value.contains("Scales a raw analog value."));
+    assert!(doc.value.contains("SCALE_X"));
+
+    let params = tower_lsp::lsp_types::HoverParams {
+        text_document_position_params: tower_lsp::lsp_types::TextDocumentPositionParams {
+            text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri },
+            position: position_at(&cleaned, "TON;"),
+        },
+        work_done_progress_params: Default::default(),
+    };
+    let hover = hover(&state, params).expect("hover");
+    let tower_lsp::lsp_types::HoverContents::Markup(markup) = hover.contents else {
+        panic!("expected markdown hover");
+    };
+    assert!(markup.value.contains("On-delay timer"));
+    assert!(markup.value.contains("IEC_TIMER"));
+
+    std::fs::remove_dir_all(root).ok();
+}
+
 #[test]
 fn lsp_formatting_snapshot() {
     let source = "PROGRAM Test\nVAR\nx:INT;\nEND_VAR\nx:=1;\nEND_PROGRAM\n";
@@ -106,6 +203,7 @@ fn lsp_formatting_vendor_profile_applies_keyword_case() {
             vendor_profile: Some("siemens".to_string()),
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -113,9 +211,12 @@ fn lsp_formatting_vendor_profile_applies_keyword_case() {
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -153,6 +254,7 @@ fn lsp_formatting_siemens_profile_preserves_hash_prefixed_references() {
             vendor_profile: Some("siemens".to_string()),
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -160,9 +262,12 @@ fn lsp_formatting_siemens_profile_preserves_hash_prefixed_references() {
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -200,6 +305,7 @@ fn lsp_formatting_mitsubishi_profile_keeps_spaced_style() {
             vendor_profile: Some("mitsubishi".to_string()),
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -207,9 +313,12 @@ fn lsp_formatting_mitsubishi_profile_keeps_spaced_style() {
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings::default(),
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -233,6 +342,102 @@ fn lsp_formatting_mitsubishi_profile_keeps_spaced_style() {
     assert_eq!(formatted, expected);
 }
 
+#[test]
+fn lsp_formatting_project_style_profile_overrides_client_settings() {
+    let source = "program Test\nvar\nx:int;\nend_var\nx:=1+2;\nend_program\n";
+    let state = ServerState::new();
+    state.set_config(serde_json::json!({
+        "trust_lsp": {
+            "format": {
+                "keywordCase": "lower"
+            }
+        }
+    }));
+    let root_uri = tower_lsp::lsp_types::Url::parse("file:///workspace/").unwrap();
+    state.set_workspace_config(
+        root_uri,
+        ProjectConfig {
+            root: PathBuf::from("/workspace"),
+            config_path: None,
+            include_paths: Vec::new(),
+            vendor_profile: None,
+            stdlib: StdlibSettings::default(),
+            libraries: Vec::new(),
+            doc_bundles: Vec::new(),
+            dependencies: Vec::new(),
+            dependency_resolution_issues: Vec::new(),
+            diagnostic_external_paths: Vec::new(),
+            build: BuildConfig::default(),
+            targets: Vec::new(),
+            indexing: IndexingConfig::default(),
+            diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
+            runtime: RuntimeConfig::default(),
+            workspace: WorkspaceSettings::default(),
+            telemetry: TelemetryConfig::default(),
+            format: FormatSettings {
+                profile: Some("siemens".to_string()),
+                keyword_case: Some("pascal".to_string()),
+                var_block_indent: Some("flush".to_string()),
+                ..FormatSettings::default()
+            },
+            completion: CompletionSettings::default(),
+        },
+    );
+
+    let uri = tower_lsp::lsp_types::Url::parse("file:///workspace/test.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let params = tower_lsp::lsp_types::DocumentFormattingParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri },
+        options: tower_lsp::lsp_types::FormattingOptions {
+            tab_size: 4,
+            insert_spaces: true,
+            ..Default::default()
+        },
+        work_done_progress_params: Default::default(),
+    };
+
+    let edits = formatting(&state, params).expect("formatting edits");
+    assert!(!edits.is_empty());
+    let formatted = edits[0].new_text.as_str();
+    let expected = "Program Test\nVar\n  x:Int;\nEnd_Var\n  x:=1+2;\nEnd_Program\n";
+    assert_eq!(formatted, expected);
+}
+
+#[test]
+fn lsp_range_formatting_honors_fmt_off_regions() {
+    let source =
+        "PROGRAM Test\nVAR\nx:INT;\nEND_VAR\n{fmt:off}\nx:=1+2;\n{fmt:on}\nx:=3+4;\nEND_PROGRAM\n";
+    let state = ServerState::new();
+    let uri = tower_lsp::lsp_types::Url::parse("file:///fmt-off.st").unwrap();
+    state.open_document(uri.clone(), 1, source.to_string());
+
+    let start = position_at(source, "x:=1+2");
+    let end = super::lsp_utils::offset_to_position(
+        source,
+        (source.find("x:=3+4").unwrap() + "x:=3+4;".len()) as u32,
+    );
+
+    let params = tower_lsp::lsp_types::DocumentRangeFormattingParams {
+        text_document: tower_lsp::lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+        range: tower_lsp::lsp_types::Range { start, end },
+        options: tower_lsp::lsp_types::FormattingOptions {
+            tab_size: 4,
+            insert_spaces: true,
+            ..Default::default()
+        },
+        work_done_progress_params: Default::default(),
+    };
+
+    let edits = range_formatting(&state, params).expect("range formatting");
+    assert_eq!(edits.len(), 1);
+    assert!(edits[0]
+        .new_text
+        .contains("\n{fmt:off}\nx:=1+2;\n{fmt:on}\n"));
+    assert!(edits[0].new_text.contains("    x := 3 + 4;"));
+}
+
 #[test]
 fn lsp_formatting_accepts_snake_case_client_keys() {
     let source = "PROGRAM Test\nVAR\nx:INT;\nEND_VAR\nx:=1;\nEND_PROGRAM\n";
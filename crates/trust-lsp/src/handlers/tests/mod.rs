@@ -2,8 +2,9 @@ use super::features::{references, workspace_symbol};
 use super::namespace_move_workspace_edit;
 use super::*;
 use crate::config::{
-    BuildConfig, DiagnosticSettings, IndexingConfig, LibraryDependency, LibrarySpec, ProjectConfig,
-    RuntimeConfig, StdlibSettings, TargetProfile, TelemetryConfig, WorkspaceSettings,
+    BuildConfig, CompletionSettings, DiagnosticSettings, FormatSettings, IndexingConfig,
+    LibraryDependency, LibrarySpec, LintSettings, ProjectConfig, RuntimeConfig, StdlibSettings,
+    TargetProfile, TelemetryConfig, TypeCheckLevel, WorkspaceSettings,
 };
 use crate::state::ServerState;
 use crate::test_support::test_client;
@@ -145,6 +146,7 @@ path = "vendor"
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -152,12 +154,15 @@ path = "vendor"
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings {
                 priority: 10,
                 visibility: crate::config::WorkspaceVisibility::Public,
             },
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
     state.set_workspace_config(
@@ -169,6 +174,7 @@ path = "vendor"
             vendor_profile: None,
             stdlib: StdlibSettings::default(),
             libraries: Vec::new(),
+            doc_bundles: Vec::new(),
             dependencies: Vec::new(),
             dependency_resolution_issues: Vec::new(),
             diagnostic_external_paths: Vec::new(),
@@ -176,12 +182,15 @@ path = "vendor"
             targets: Vec::new(),
             indexing: IndexingConfig::default(),
             diagnostics: DiagnosticSettings::default(),
+            lint: LintSettings::default(),
             runtime: RuntimeConfig::default(),
             workspace: WorkspaceSettings {
                 priority: 1,
                 visibility: crate::config::WorkspaceVisibility::Private,
             },
             telemetry: TelemetryConfig::default(),
+            format: FormatSettings::default(),
+            completion: CompletionSettings::default(),
         },
     );
 
@@ -8,29 +8,32 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tower_lsp::lsp_types::notification::Progress;
-use tower_lsp::lsp_types::request::WorkDoneProgressCreate;
 use tower_lsp::lsp_types::{
     DidChangeConfigurationParams, DidChangeWatchedFilesParams, FileChangeType, MessageType,
     ProgressParams, ProgressParamsValue, ProgressToken, Registration, RenameFilesParams, TextEdit,
-    Url, WorkDoneProgress, WorkDoneProgressBegin, WorkDoneProgressCreateParams,
-    WorkDoneProgressEnd, WorkDoneProgressReport, WorkspaceEdit,
+    Url, WorkDoneProgress, WorkDoneProgressBegin, WorkDoneProgressEnd, WorkDoneProgressReport,
+    WorkspaceEdit,
 };
 use tower_lsp::Client;
 use tracing::info;
 
 use crate::config::{ProjectConfig, CONFIG_FILES};
 use crate::index_cache::IndexCache;
-use crate::state::{path_to_uri, uri_to_path, ServerState};
+use crate::state::{path_to_uri, uri_to_path, AnalysisPhase, ServerState};
 use trust_hir::db::SemanticDatabase;
 use trust_hir::symbols::{ScopeId, SymbolId, SymbolTable};
 use trust_hir::{is_reserved_keyword, is_valid_identifier, SymbolKind};
+use trust_runtime::stlib::{is_library_archive, LibraryArchive};
 
 use super::lsp_utils;
+use super::progress::{create_work_done_token, send_work_done_begin, send_work_done_end};
 use super::refresh::{refresh_diagnostics, refresh_semantic_tokens};
+use super::rename_config::{config_reference_edits, rename_workspace_edit};
+use super::status::enter_phase;
 
 pub async fn register_file_watchers(client: &Client) {
     let mut watchers = Vec::new();
-    watchers.push(json!({ "globPattern": "**/*.{st,ST,pou,POU}" }));
+    watchers.push(json!({ "globPattern": "**/*.{st,ST,pou,POU,stlib}" }));
     for name in CONFIG_FILES {
         watchers.push(json!({ "globPattern": format!("**/{name}") }));
     }
@@ -83,8 +86,10 @@ pub async fn index_workspace(client: &Client, state: &ServerState) {
         let Some(root) = uri_to_path(&folder) else {
             continue;
         };
-        let config = ProjectConfig::load(&root);
+        enter_phase(client, state, AnalysisPhase::ResolvingDependencies).await;
+        let config = resolve_workspace_config(client, state, &folder, &root).await;
         state.set_workspace_config(folder.clone(), config.clone());
+        enter_phase(client, state, AnalysisPhase::Indexing).await;
         let summary = index_workspace_root(client, state, &config, &folder, &mut seen).await;
         indexed_total += summary.indexed;
         skipped_total += summary.skipped;
@@ -107,6 +112,32 @@ pub async fn index_workspace(client: &Client, state: &ServerState) {
         }
         client.log_message(MessageType::INFO, message).await;
     }
+    enter_phase(client, state, AnalysisPhase::Idle).await;
+}
+
+/// Loads the project configuration, reporting dependency resolution progress.
+async fn resolve_workspace_config(
+    client: &Client,
+    state: &ServerState,
+    folder: &Url,
+    root: &Path,
+) -> ProjectConfig {
+    let progress = create_work_done_token(client, state, format!("trustlsp-deps-{}", folder)).await;
+    send_work_done_begin(
+        client,
+        &progress,
+        &format!("Resolving dependencies for {}", root_display_name(folder)),
+        None,
+    )
+    .await;
+    let config = ProjectConfig::load(root);
+    let issues = config.dependency_resolution_issues.len();
+    let mut message = format!("Resolved {} dependencies", config.dependencies.len());
+    if issues > 0 {
+        message.push_str(&format!(" ({issues} issue(s))"));
+    }
+    send_work_done_end(client, &progress, Some(message)).await;
+    config
 }
 
 pub fn index_workspace_background_with_refresh(client: Client, state: Arc<ServerState>) {
@@ -117,6 +148,52 @@ pub fn index_workspace_background_with_refresh(client: Client, state: Arc<Server
     });
 }
 
+/// Indexes a project root without a client, for headless diagnostics.
+///
+/// Every indexable file is loaded regardless of the indexing budget so a
+/// headless run sees the whole project. Returns the number of indexed files.
+pub(crate) fn index_project_blocking(state: &ServerState, root: &Path) -> anyhow::Result<usize> {
+    let folder = path_to_uri(root)
+        .ok_or_else(|| anyhow::anyhow!("invalid project path {}", root.display()))?;
+    let config = ProjectConfig::load(root);
+    state.set_workspace_folders(vec![folder.clone()]);
+    state.set_workspace_config(folder, config.clone());
+
+    let mut files = Vec::new();
+    collect_workspace_files(&config, &mut files);
+    files.sort();
+    files.dedup();
+    let mut indexed = 0usize;
+    for path in files {
+        let (Some(uri), Some(content)) = (path_to_uri(&path), read_indexable_source(&path)) else {
+            continue;
+        };
+        if state.index_document_deferred_budget(uri, content).is_some() {
+            indexed += 1;
+        }
+    }
+    Ok(indexed)
+}
+
+/// Lists the project's own Structured Text files, leaving out configured libraries.
+pub(crate) fn project_source_files(config: &ProjectConfig) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_st_files(&config.root, &mut files);
+    for include in &config.include_paths {
+        collect_st_files(include, &mut files);
+    }
+    files.retain(|path| {
+        is_st_file(path)
+            && !config
+                .libraries
+                .iter()
+                .any(|lib| lib.path != config.root && path.starts_with(&lib.path))
+    });
+    files.sort();
+    files.dedup();
+    files
+}
+
 pub fn did_change_configuration(state: &ServerState, params: DidChangeConfigurationParams) {
     state.set_config(params.settings);
     state.record_activity();
@@ -125,6 +202,7 @@ pub fn did_change_configuration(state: &ServerState, params: DidChangeConfigurat
 
 pub fn will_rename_files(state: &ServerState, params: RenameFilesParams) -> Option<WorkspaceEdit> {
     let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+    let mut config_changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
     let documents = state.documents();
 
     for change in params.files {
@@ -196,16 +274,23 @@ pub fn will_rename_files(state: &ServerState, params: RenameFilesParams) -> Opti
                     },
                 );
             }
-            Some(result)
+            let target = trust_ide::rename::RenameTarget {
+                file_id: doc.file_id,
+                symbol_id: symbol.id,
+            };
+            Some((result, target))
         });
 
-        if let Some(rename_result) = rename_result {
+        if let Some((rename_result, target)) = rename_result {
             if let Some(rename_changes) = lsp_utils::rename_result_to_changes(state, rename_result)
             {
                 for (uri, edits) in rename_changes {
                     changes.entry(uri).or_default().extend(edits);
                 }
             }
+            for (uri, edits) in config_reference_edits(state, &old_uri, target, &new_stem) {
+                config_changes.entry(uri).or_default().extend(edits);
+            }
         }
 
         let namespace_rename = state.with_database(|db| {
@@ -257,14 +342,10 @@ pub fn will_rename_files(state: &ServerState, params: RenameFilesParams) -> Opti
         }
     }
 
-    if changes.is_empty() {
+    if changes.is_empty() && config_changes.is_empty() {
         None
     } else {
-        Some(WorkspaceEdit {
-            changes: Some(changes),
-            document_changes: None,
-            change_annotations: None,
-        })
+        Some(rename_workspace_edit(state, changes, config_changes, None))
     }
 }
 
@@ -348,13 +429,9 @@ async fn index_workspace_root(
     let cache_dir = config.index_cache_dir();
     let mut cache = cache_dir
         .as_ref()
-        .map(|dir| IndexCache::load_or_default(dir));
+        .map(|dir| load_index_cache(Some(config), dir));
 
-    let progress = if state.work_done_progress() {
-        start_progress(client, root_uri, total).await
-    } else {
-        None
-    };
+    let progress = start_progress(client, state, root_uri, total).await;
 
     let start = Instant::now();
     let mut indexed = 0usize;
@@ -402,7 +479,7 @@ async fn index_workspace_root(
         }
 
         let step_start = Instant::now();
-        if let Some(cache) = cache.as_ref() {
+        if let Some(cache) = cache.as_mut() {
             if let Some(cached) = cache.content_for_path(path) {
                 if state
                     .index_document_deferred_budget(uri, cached.to_string())
@@ -423,7 +500,7 @@ async fn index_workspace_root(
             }
         }
 
-        let Ok(content) = std::fs::read_to_string(path) else {
+        let Some(content) = read_indexable_source(path) else {
             skipped += 1;
             report_progress(client, &progress, idx + 1, total, &mut last_percent).await;
             continue;
@@ -462,8 +539,18 @@ async fn index_workspace_root(
     }
 }
 
+fn load_index_cache(config: Option<&ProjectConfig>, dir: &Path) -> IndexCache {
+    let mut cache = IndexCache::load_or_default(dir);
+    cache.set_max_bytes(config.and_then(ProjectConfig::index_cache_max_bytes));
+    cache
+}
+
 fn collect_workspace_files(config: &ProjectConfig, out: &mut Vec<PathBuf>) {
     for root in config.indexing_roots() {
+        if root.is_file() && is_library_archive(&root) {
+            out.push(root);
+            continue;
+        }
         collect_st_files(&root, out);
     }
 }
@@ -490,7 +577,7 @@ fn collect_st_files(root: &Path, out: &mut Vec<PathBuf>) {
                     continue;
                 }
                 stack.push(path);
-            } else if file_type.is_file() && is_st_file(&path) {
+            } else if file_type.is_file() && is_indexable_file(&path) {
                 out.push(path);
             }
         }
@@ -504,28 +591,15 @@ fn should_skip_dir(path: &Path) -> bool {
     matches!(name, ".git" | ".hg" | ".svn" | "node_modules" | "target")
 }
 
-async fn start_progress(client: &Client, root_uri: &Url, total: usize) -> Option<ProgressToken> {
-    let token = ProgressToken::String(format!("trustlsp-index-{}", root_uri));
-    let params = WorkDoneProgressCreateParams {
-        token: token.clone(),
-    };
-    if client
-        .send_request::<WorkDoneProgressCreate>(params)
-        .await
-        .is_err()
-    {
-        return None;
-    }
-
-    let title = uri_to_path(root_uri)
-        .and_then(|path| {
-            path.file_name()
-                .and_then(|name| name.to_str())
-                .map(|name| name.to_string())
-        })
-        .map(|name| format!("Indexing {name}"))
-        .unwrap_or_else(|| "Indexing workspace".to_string());
-
+async fn start_progress(
+    client: &Client,
+    state: &ServerState,
+    root_uri: &Url,
+    total: usize,
+) -> Option<ProgressToken> {
+    let token =
+        create_work_done_token(client, state, format!("trustlsp-index-{}", root_uri)).await?;
+    let title = format!("Indexing {}", root_display_name(root_uri));
     let begin = WorkDoneProgressBegin {
         title,
         cancellable: Some(false),
@@ -541,6 +615,16 @@ async fn start_progress(client: &Client, root_uri: &Url, total: usize) -> Option
     Some(token)
 }
 
+fn root_display_name(root_uri: &Url) -> String {
+    uri_to_path(root_uri)
+        .and_then(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.to_string())
+        })
+        .unwrap_or_else(|| "workspace".to_string())
+}
+
 async fn report_progress(
     client: &Client,
     token: &Option<ProgressToken>,
@@ -633,14 +717,15 @@ pub async fn did_rename_files(
         }
 
         if let (Some(old_uri), Some(path)) = (old_uri.as_ref(), old_path.as_ref()) {
-            if is_st_file(path) {
-                let cache_dir = state
-                    .workspace_config_for_uri(old_uri)
-                    .and_then(|config| config.index_cache_dir());
+            if is_indexable_file(path) {
+                let cache_config = state.workspace_config_for_uri(old_uri);
+                let cache_dir = cache_config
+                    .as_ref()
+                    .and_then(ProjectConfig::index_cache_dir);
                 if let Some(dir) = cache_dir.clone() {
                     let cache = cache_by_dir
                         .entry(dir.clone())
-                        .or_insert_with(|| IndexCache::load_or_default(&dir));
+                        .or_insert_with(|| load_index_cache(cache_config.as_ref(), &dir));
                     cache.remove_path(path);
                     dirty_cache_dirs.insert(dir);
                 }
@@ -651,14 +736,15 @@ pub async fn did_rename_files(
         }
 
         if let (Some(new_uri), Some(path)) = (new_uri.as_ref(), new_path.as_ref()) {
-            if is_st_file(path) {
-                let cache_dir = state
-                    .workspace_config_for_uri(new_uri)
-                    .and_then(|config| config.index_cache_dir());
+            if is_indexable_file(path) {
+                let cache_config = state.workspace_config_for_uri(new_uri);
+                let cache_dir = cache_config
+                    .as_ref()
+                    .and_then(ProjectConfig::index_cache_dir);
                 let content = if let Some(content) = open_content.clone() {
                     content
                 } else {
-                    let Ok(content) = std::fs::read_to_string(path) else {
+                    let Some(content) = read_indexable_source(path) else {
                         continue;
                     };
                     content
@@ -666,7 +752,7 @@ pub async fn did_rename_files(
                 if let Some(dir) = cache_dir.clone() {
                     let cache = cache_by_dir
                         .entry(dir.clone())
-                        .or_insert_with(|| IndexCache::load_or_default(&dir));
+                        .or_insert_with(|| load_index_cache(cache_config.as_ref(), &dir));
                     cache.update_from_content(path, content.clone());
                     dirty_cache_dirs.insert(dir);
                 }
@@ -682,7 +768,7 @@ pub async fn did_rename_files(
     }
 
     for dir in dirty_cache_dirs {
-        if let Some(cache) = cache_by_dir.get(&dir) {
+        if let Some(cache) = cache_by_dir.get_mut(&dir) {
             let _ = cache.save(&dir);
         }
     }
@@ -731,22 +817,23 @@ pub async fn did_change_watched_files(
             config_changed = true;
             continue;
         }
-        if !is_st_file(&path) {
+        if !is_indexable_file(&path) {
             continue;
         }
-        let cache_dir = state
-            .workspace_config_for_uri(&change.uri)
-            .and_then(|config| config.index_cache_dir());
+        let cache_config = state.workspace_config_for_uri(&change.uri);
+        let cache_dir = cache_config
+            .as_ref()
+            .and_then(ProjectConfig::index_cache_dir);
 
         match change.typ {
             FileChangeType::CREATED | FileChangeType::CHANGED => {
-                let Ok(content) = std::fs::read_to_string(&path) else {
+                let Some(content) = read_indexable_source(&path) else {
                     continue;
                 };
                 if let Some(dir) = cache_dir.clone() {
                     let cache = cache_by_dir
                         .entry(dir.clone())
-                        .or_insert_with(|| IndexCache::load_or_default(&dir));
+                        .or_insert_with(|| load_index_cache(cache_config.as_ref(), &dir));
                     cache.update_from_content(&path, content.clone());
                     dirty_cache_dirs.insert(dir);
                 }
@@ -758,7 +845,7 @@ pub async fn did_change_watched_files(
                 if let Some(dir) = cache_dir.clone() {
                     let cache = cache_by_dir
                         .entry(dir.clone())
-                        .or_insert_with(|| IndexCache::load_or_default(&dir));
+                        .or_insert_with(|| load_index_cache(cache_config.as_ref(), &dir));
                     cache.remove_path(&path);
                     dirty_cache_dirs.insert(dir);
                 }
@@ -771,7 +858,7 @@ pub async fn did_change_watched_files(
     }
 
     for dir in dirty_cache_dirs {
-        if let Some(cache) = cache_by_dir.get(&dir) {
+        if let Some(cache) = cache_by_dir.get_mut(&dir) {
             let _ = cache.save(&dir);
         }
     }
@@ -809,6 +896,21 @@ fn is_st_file(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+fn is_indexable_file(path: &Path) -> bool {
+    is_st_file(path) || is_library_archive(path)
+}
+
+/// Reads the text to index for `path`; `.stlib` archives contribute their
+/// declaration-only interface.
+fn read_indexable_source(path: &Path) -> Option<String> {
+    if is_library_archive(path) {
+        return LibraryArchive::read(path)
+            .map(|archive| archive.interface)
+            .ok();
+    }
+    std::fs::read_to_string(path).ok()
+}
+
 fn is_config_file(path: &Path) -> bool {
     path.file_name()
         .and_then(|name| name.to_str())
@@ -7,20 +7,28 @@ use std::hash::Hasher;
 use std::path::Path;
 use std::time::UNIX_EPOCH;
 
-const CACHE_VERSION: u32 = 1;
+const CACHE_VERSION: u32 = 2;
 const CACHE_FILE: &str = "index.json";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct IndexCache {
     version: u32,
+    /// Logical clock used to order entries for LRU eviction.
+    #[serde(default)]
+    clock: u64,
     entries: HashMap<String, CacheEntry>,
+    #[serde(skip)]
+    max_bytes: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct CacheEntry {
     hash: u64,
     size: u64,
+    /// Modification time in nanoseconds since the Unix epoch.
     mtime: Option<u64>,
+    #[serde(default)]
+    last_used: u64,
     content: String,
 }
 
@@ -28,7 +36,9 @@ impl Default for IndexCache {
     fn default() -> Self {
         Self {
             version: CACHE_VERSION,
+            clock: 0,
             entries: HashMap::new(),
+            max_bytes: None,
         }
     }
 }
@@ -49,31 +59,79 @@ impl IndexCache {
         cache
     }
 
-    pub(crate) fn save(&self, dir: &Path) -> std::io::Result<()> {
+    /// Caps the total cached content size; least recently used entries are
+    /// dropped on save once the cap is exceeded.
+    pub(crate) fn set_max_bytes(&mut self, max_bytes: Option<u64>) {
+        self.max_bytes = max_bytes;
+    }
+
+    pub(crate) fn save(&mut self, dir: &Path) -> std::io::Result<()> {
+        if let Some(max_bytes) = self.max_bytes {
+            self.evict_to(max_bytes);
+        }
         fs::create_dir_all(dir)?;
         let path = dir.join(CACHE_FILE);
-        let payload = serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string());
-        fs::write(path, payload)
+        let tmp_path = dir.join(format!("{CACHE_FILE}.tmp"));
+        let payload = serde_json::to_vec(self).unwrap_or_else(|_| b"{}".to_vec());
+        fs::write(&tmp_path, payload)?;
+        fs::rename(tmp_path, path)
     }
 
-    pub(crate) fn content_for_path(&self, path: &Path) -> Option<&str> {
+    pub(crate) fn content_for_path(&mut self, path: &Path) -> Option<&str> {
         let key = cache_key(path);
-        let entry = self.entries.get(&key)?;
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(&key)?;
         if entry.matches_metadata(path) {
+            entry.last_used = tick;
             Some(entry.content.as_str())
         } else {
             None
         }
     }
 
+    pub(crate) fn total_bytes(&self) -> u64 {
+        self.entries
+            .values()
+            .map(|entry| entry.content.len() as u64)
+            .sum()
+    }
+
+    fn evict_to(&mut self, max_bytes: u64) {
+        let mut total = self.total_bytes();
+        if total <= max_bytes {
+            return;
+        }
+        let mut order = self
+            .entries
+            .iter()
+            .map(|(key, entry)| (entry.last_used, key.clone()))
+            .collect::<Vec<_>>();
+        order.sort();
+        for (_, key) in order {
+            if total <= max_bytes {
+                break;
+            }
+            if let Some(entry) = self.entries.remove(&key) {
+                total = total.saturating_sub(entry.content.len() as u64);
+            }
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.clock = self.clock.wrapping_add(1);
+        self.clock
+    }
+
     pub(crate) fn update_from_content(&mut self, path: &Path, content: String) -> u64 {
         let (size, mtime) = metadata_signature(path).unwrap_or((content.len() as u64, None));
         let hash = hash_content(&content);
         let key = cache_key(path);
+        let tick = self.next_tick();
         if let Some(entry) = self.entries.get_mut(&key) {
             if entry.hash == hash {
                 entry.size = size;
                 entry.mtime = mtime;
+                entry.last_used = tick;
                 return hash;
             }
         }
@@ -81,6 +139,7 @@ impl IndexCache {
             hash,
             size,
             mtime,
+            last_used: tick,
             content,
         };
         self.entries.insert(key, entry);
@@ -120,7 +179,7 @@ fn metadata_signature(path: &Path) -> Option<(u64, Option<u64>)> {
         .modified()
         .ok()
         .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
-        .map(|duration| duration.as_secs());
+        .and_then(|duration| u64::try_from(duration.as_nanos()).ok());
     Some((size, mtime))
 }
 
@@ -160,7 +219,7 @@ mod tests {
         cache.update_from_content(&file_path, content.clone());
         cache.save(&cache_dir).expect("save cache");
 
-        let cache = IndexCache::load_or_default(&cache_dir);
+        let mut cache = IndexCache::load_or_default(&cache_dir);
         let cached = cache.content_for_path(&file_path).expect("cached content");
         assert_eq!(cached, content);
 
@@ -170,9 +229,36 @@ mod tests {
         )
         .expect("write update");
 
-        let cache = IndexCache::load_or_default(&cache_dir);
+        let mut cache = IndexCache::load_or_default(&cache_dir);
         assert!(cache.content_for_path(&file_path).is_none());
 
         fs::remove_dir_all(root).ok();
     }
+
+    #[test]
+    fn cache_size_limit_evicts_least_recently_used_entries() {
+        let root = temp_dir("trustlsp-index-cache-limit");
+        let cache_dir = root.join(".trust-lsp/index-cache");
+        let paths = ["a.st", "b.st", "c.st"].map(|name| root.join(name));
+        let mut cache = IndexCache::load_or_default(&cache_dir);
+        for path in &paths {
+            let content = format!("PROGRAM P\n// {}\nEND_PROGRAM\n", "x".repeat(64));
+            fs::write(path, &content).expect("write file");
+            cache.update_from_content(path, content);
+        }
+        // Touch `a.st` so `b.st` becomes the least recently used entry.
+        assert!(cache.content_for_path(&paths[0]).is_some());
+
+        let entry_bytes = cache.total_bytes() / 3;
+        cache.set_max_bytes(Some(entry_bytes * 2));
+        cache.save(&cache_dir).expect("save cache");
+
+        let mut cache = IndexCache::load_or_default(&cache_dir);
+        assert!(cache.total_bytes() <= entry_bytes * 2);
+        assert!(cache.content_for_path(&paths[0]).is_some());
+        assert!(cache.content_for_path(&paths[1]).is_none());
+        assert!(cache.content_for_path(&paths[2]).is_some());
+
+        fs::remove_dir_all(root).ok();
+    }
 }
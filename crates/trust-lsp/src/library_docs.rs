@@ -1,12 +1,56 @@
 //! External library documentation ingestion (markdown headings).
+//!
+//! Documentation comes from three places, merged in this order:
+//! - built-in bundles for the IEC standard library and the configured `vendor_profile`,
+//! - `[project].doc_bundles` paths (markdown files or bundle directories),
+//! - `[[libraries]].docs` files, which replace bundle text for the same name.
 
 use rustc_hash::FxHashMap;
 use std::fs;
+use std::path::Path;
 
 use crate::config::ProjectConfig;
 
+const STDLIB_BUNDLE: &str = "stdlib";
+
+const BUILTIN_BUNDLES: &[(&str, &str)] = &[
+    (
+        STDLIB_BUNDLE,
+        include_str!("../assets/doc-bundles/stdlib.md"),
+    ),
+    (
+        "mitsubishi",
+        include_str!("../assets/doc-bundles/mitsubishi.md"),
+    ),
+    ("siemens", include_str!("../assets/doc-bundles/siemens.md")),
+];
+
 pub(crate) fn library_doc_map(config: &ProjectConfig) -> FxHashMap<String, String> {
     let mut docs = FxHashMap::default();
+    let vendor = config
+        .vendor_profile
+        .as_deref()
+        .and_then(vendor_bundle_name);
+
+    if stdlib_docs_enabled(config) {
+        merge_builtin_bundle(STDLIB_BUNDLE, &mut docs);
+        for path in &config.doc_bundles {
+            merge_bundle_path(path, Some(STDLIB_BUNDLE), &mut docs);
+        }
+    } else {
+        for path in &config.doc_bundles {
+            merge_bundle_path(path, None, &mut docs);
+        }
+    }
+    if let Some(vendor) = vendor {
+        merge_builtin_bundle(vendor, &mut docs);
+        for path in &config.doc_bundles {
+            if path.is_dir() {
+                merge_bundle_dir(&path.join(vendor), &mut docs);
+            }
+        }
+    }
+
     for lib in &config.libraries {
         for path in &lib.docs {
             if let Ok(contents) = fs::read_to_string(path) {
@@ -17,6 +61,101 @@ pub(crate) fn library_doc_map(config: &ProjectConfig) -> FxHashMap<String, Strin
     docs
 }
 
+/// Maps a `vendor_profile` value to the documentation bundle it ships with.
+fn vendor_bundle_name(profile: &str) -> Option<&'static str> {
+    match profile.trim().to_ascii_lowercase().as_str() {
+        "mitsubishi" | "gxworks3" => Some("mitsubishi"),
+        "siemens" => Some("siemens"),
+        _ => None,
+    }
+}
+
+fn stdlib_docs_enabled(config: &ProjectConfig) -> bool {
+    !config
+        .stdlib
+        .profile
+        .as_deref()
+        .is_some_and(|profile| profile.trim().eq_ignore_ascii_case("none"))
+}
+
+fn merge_builtin_bundle(name: &str, docs: &mut FxHashMap<String, String>) {
+    if let Some((_, contents)) = BUILTIN_BUNDLES
+        .iter()
+        .find(|(bundle, _)| bundle.eq_ignore_ascii_case(name))
+    {
+        let mut bundle = FxHashMap::default();
+        parse_markdown_docs(contents, &mut bundle);
+        merge_docs(bundle, docs);
+    }
+}
+
+/// Loads a bundle path: a markdown file, or a directory whose top-level `*.md`
+/// files (plus the optional `subdir` folder) hold one function/FB each.
+fn merge_bundle_path(path: &Path, subdir: Option<&str>, docs: &mut FxHashMap<String, String>) {
+    if path.is_dir() {
+        merge_bundle_dir(path, docs);
+        if let Some(subdir) = subdir {
+            merge_bundle_dir(&path.join(subdir), docs);
+        }
+        return;
+    }
+    if let Ok(contents) = fs::read_to_string(path) {
+        let mut bundle = FxHashMap::default();
+        parse_markdown_docs(&contents, &mut bundle);
+        merge_docs(bundle, docs);
+    }
+}
+
+fn merge_bundle_dir(dir: &Path, docs: &mut FxHashMap<String, String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+        })
+        .collect();
+    files.sort();
+
+    for path in files {
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let mut bundle = FxHashMap::default();
+        parse_markdown_docs(&contents, &mut bundle);
+        if bundle.is_empty() {
+            let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let text = contents.trim();
+            if !text.is_empty() {
+                bundle.insert(stem.to_ascii_uppercase(), text.to_string());
+            }
+        }
+        merge_docs(bundle, docs);
+    }
+}
+
+/// Appends bundle text so vendor notes extend the standard description.
+fn merge_docs(bundle: FxHashMap<String, String>, docs: &mut FxHashMap<String, String>) {
+    for (name, text) in bundle {
+        match docs.get_mut(&name) {
+            Some(existing) if !existing.contains(text.as_str()) => {
+                existing.push_str("\n\n");
+                existing.push_str(&text);
+            }
+            Some(_) => {}
+            None => {
+                docs.insert(name, text);
+            }
+        }
+    }
+}
+
 fn parse_markdown_docs(contents: &str, docs: &mut FxHashMap<String, String>) {
     let mut current: Option<String> = None;
     let mut buffer: Vec<String> = Vec::new();
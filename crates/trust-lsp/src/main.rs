@@ -2,8 +2,10 @@
 //!
 //! This is the main entry point for the ST language server.
 
+mod check;
 mod config;
 mod external_diagnostics;
+mod fmt;
 mod handlers;
 mod index_cache;
 mod library_docs;
@@ -28,7 +30,11 @@ use tower_lsp::{Client, LanguageServer, LspService, Server};
 use tracing::info;
 
 use crate::handlers::{
-    HMI_BINDINGS_COMMAND, HMI_INIT_COMMAND, MOVE_NAMESPACE_COMMAND, PROJECT_INFO_COMMAND,
+    CROSS_REFERENCE_COMMAND, DEAD_CODE_COMMAND, EVALUATE_SELECTION_COMMAND,
+    FORCED_VARIABLES_COMMAND, FORCE_VARIABLE_COMMAND, HMI_BINDINGS_COMMAND, HMI_INIT_COMMAND,
+    LIVE_VALUES_COMMAND, MOVE_NAMESPACE_COMMAND, PROJECT_GRAPH_COMMAND, PROJECT_INFO_COMMAND,
+    REFERENCE_ACCESS_COMMAND, STACK_USAGE_COMMAND, TRACE_DRIVERS_COMMAND, UNFORCE_VARIABLE_COMMAND,
+    WCET_COMMAND,
 };
 use crate::state::ServerState;
 use crate::telemetry::TelemetryEvent;
@@ -137,6 +143,18 @@ impl LanguageServer for StLanguageServer {
                         PROJECT_INFO_COMMAND.to_string(),
                         HMI_INIT_COMMAND.to_string(),
                         HMI_BINDINGS_COMMAND.to_string(),
+                        DEAD_CODE_COMMAND.to_string(),
+                        STACK_USAGE_COMMAND.to_string(),
+                        WCET_COMMAND.to_string(),
+                        PROJECT_GRAPH_COMMAND.to_string(),
+                        EVALUATE_SELECTION_COMMAND.to_string(),
+                        LIVE_VALUES_COMMAND.to_string(),
+                        FORCE_VARIABLE_COMMAND.to_string(),
+                        UNFORCE_VARIABLE_COMMAND.to_string(),
+                        FORCED_VARIABLES_COMMAND.to_string(),
+                        REFERENCE_ACCESS_COMMAND.to_string(),
+                        TRACE_DRIVERS_COMMAND.to_string(),
+                        CROSS_REFERENCE_COMMAND.to_string(),
                     ],
                     ..Default::default()
                 }),
@@ -218,6 +236,9 @@ impl LanguageServer for StLanguageServer {
                                     SemanticTokenType::OPERATOR,
                                     SemanticTokenType::ENUM_MEMBER,
                                     SemanticTokenType::NAMESPACE,
+                                    SemanticTokenType::new("directAddress"),
+                                    SemanticTokenType::new("task"),
+                                    SemanticTokenType::new("pragma"),
                                 ],
                                 token_modifiers: vec![
                                     SemanticTokenModifier::DECLARATION,
@@ -225,6 +246,11 @@ impl LanguageServer for StLanguageServer {
                                     SemanticTokenModifier::READONLY,
                                     SemanticTokenModifier::STATIC,
                                     SemanticTokenModifier::MODIFICATION,
+                                    SemanticTokenModifier::new("input"),
+                                    SemanticTokenModifier::new("output"),
+                                    SemanticTokenModifier::new("memory"),
+                                    SemanticTokenModifier::new("duration"),
+                                    SemanticTokenModifier::new("typed"),
                                 ],
                             },
                             full: Some(SemanticTokensFullOptions::Delta { delta: Some(true) }),
@@ -417,7 +443,11 @@ impl LanguageServer for StLanguageServer {
         let start = Instant::now();
         let result = self
             .state
-            .run_background(async { handlers::workspace_diagnostic(&self.state, params) })
+            .run_background(handlers::workspace_diagnostic_with_progress(
+                &self.client,
+                &self.state,
+                params,
+            ))
             .await;
         self.state
             .record_telemetry(TelemetryEvent::WorkspaceDiagnostic, start.elapsed(), None);
@@ -771,6 +801,13 @@ impl LanguageServer for StLanguageServer {
 
 #[tokio::main]
 async fn main() {
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("check") => std::process::exit(check::run_check(args)),
+        Some("fmt") => std::process::exit(fmt::run_fmt(args)),
+        _ => {}
+    }
+
     // Initialize logging
     tracing_subscriber::fmt()
         .with_env_filter(
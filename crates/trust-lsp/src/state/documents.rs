@@ -213,6 +213,31 @@ pub(super) fn file_ids_for_config(
     ids
 }
 
+pub(super) fn library_file_ids_for_config(
+    state: &ServerState,
+    config: &ProjectConfig,
+) -> FxHashSet<FileId> {
+    let roots = config
+        .libraries
+        .iter()
+        .map(|lib| canonicalize_path(lib.path.clone()))
+        .collect::<Vec<_>>();
+    if roots.is_empty() {
+        return FxHashSet::default();
+    }
+    let project = state.project.read();
+    let mut ids = FxHashSet::default();
+    for (key, file_id) in project.sources().iter() {
+        let SourceKey::Path(path) = key else {
+            continue;
+        };
+        if roots.iter().any(|root| path.starts_with(root)) {
+            ids.insert(file_id);
+        }
+    }
+    ids
+}
+
 pub(super) fn apply_memory_budget(state: &ServerState) {
     enforce_memory_budget(state);
 }
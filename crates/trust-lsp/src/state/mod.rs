@@ -24,8 +24,10 @@ const BACKGROUND_REQUEST_LIMIT: usize = 1;
 mod cache;
 mod documents;
 mod path;
+mod status;
 
 pub(crate) use path::{path_to_uri, uri_to_path};
+pub use status::{AnalysisPhase, AnalysisStatus};
 
 /// A document managed by the server.
 #[derive(Debug, Clone)]
@@ -143,6 +145,8 @@ pub struct ServerState {
     telemetry: TelemetryCollector,
     /// Limits concurrency for background workspace scans.
     request_limiter: RequestLimiter,
+    /// Background analysis phase, diagnostic counts, and queue depth.
+    analysis: status::AnalysisTracker,
 }
 
 impl ServerState {
@@ -168,6 +172,7 @@ impl ServerState {
             library_docs: RwLock::new(FxHashMap::default()),
             telemetry: TelemetryCollector::new(),
             request_limiter: RequestLimiter::new(BACKGROUND_REQUEST_LIMIT),
+            analysis: status::AnalysisTracker::new(),
         }
     }
 
@@ -259,6 +264,11 @@ impl ServerState {
         path::workspace_config_for_uri(self, uri)
     }
 
+    /// Returns the workspace root that owns `uri` together with its configuration.
+    pub fn workspace_root_for_uri(&self, uri: &Url) -> Option<(Url, ProjectConfig)> {
+        path::workspace_config_match_for_uri(self, uri)
+    }
+
     /// Returns cached library docs for the workspace that owns `uri`.
     pub fn library_docs_for_uri(&self, uri: &Url) -> Option<Arc<FxHashMap<String, String>>> {
         let (root, config) = path::workspace_config_match_for_uri(self, uri)?;
@@ -367,6 +377,11 @@ impl ServerState {
         documents::file_ids_for_config(self, config)
     }
 
+    /// Returns file IDs that come from library roots of the given configuration.
+    pub fn library_file_ids_for_config(&self, config: &ProjectConfig) -> FxHashSet<FileId> {
+        documents::library_file_ids_for_config(self, config)
+    }
+
     /// Stores updated client configuration settings.
     pub fn set_config(&self, config: Value) {
         *self.config.write() = config;
@@ -382,9 +397,38 @@ impl ServerState {
     where
         F: Future<Output = T>,
     {
+        let _queued = self.analysis.enqueue();
         self.request_limiter.run_background(fut).await
     }
 
+    /// Records the current background analysis phase.
+    pub fn set_analysis_phase(&self, phase: AnalysisPhase) {
+        self.analysis.set_phase(phase);
+    }
+
+    /// Records error and warning totals from the latest workspace diagnostic pass.
+    pub fn set_workspace_diagnostic_counts(&self, errors: usize, warnings: usize) {
+        self.analysis.set_diagnostic_counts(errors, warnings);
+    }
+
+    /// Returns a snapshot of background analysis status for `trust/status`.
+    pub fn analysis_status(&self) -> AnalysisStatus {
+        let (files_indexed, files_open) = {
+            let documents = self.documents.read();
+            let open = documents.values().filter(|doc| doc.is_open).count();
+            (documents.len(), open)
+        };
+        let (errors, warnings) = self.analysis.diagnostic_counts();
+        AnalysisStatus {
+            phase: self.analysis.phase(),
+            files_indexed,
+            files_open,
+            errors,
+            warnings,
+            queue_depth: self.analysis.queue_depth(),
+        }
+    }
+
     pub fn semantic_tokens_cache(&self, uri: &Url) -> Option<SemanticTokensCache> {
         cache::semantic_tokens_cache(self, uri)
     }
@@ -515,6 +559,42 @@ mod tests {
         assert_eq!(result, 42);
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn analysis_status_tracks_queue_depth_and_counts() {
+        let state = Arc::new(ServerState::new());
+        let uri = Url::parse("file:///status.st").unwrap();
+        state.open_document(uri, 1, "PROGRAM Main\nEND_PROGRAM\n".to_string());
+        state.set_workspace_diagnostic_counts(2, 5);
+        state.set_analysis_phase(AnalysisPhase::Indexing);
+
+        let (started_tx, mut started_rx) = mpsc::channel(1);
+        let (release_tx, mut release_rx) = mpsc::channel(1);
+        let task = tokio::spawn({
+            let state = Arc::clone(&state);
+            async move {
+                state
+                    .run_background(async move {
+                        let _ = started_tx.send(()).await;
+                        let _ = release_rx.recv().await;
+                    })
+                    .await;
+            }
+        });
+        let _ = started_rx.recv().await;
+
+        let status = state.analysis_status();
+        assert_eq!(status.phase, AnalysisPhase::Indexing);
+        assert_eq!(status.files_indexed, 1);
+        assert_eq!(status.files_open, 1);
+        assert_eq!(status.errors, 2);
+        assert_eq!(status.warnings, 5);
+        assert_eq!(status.queue_depth, 1);
+
+        let _ = release_tx.send(()).await;
+        let _ = task.await;
+        assert_eq!(state.analysis_status().queue_depth, 0);
+    }
+
     #[test]
     fn evicts_closed_documents_over_budget() {
         let root = temp_dir("trustlsp-budget");
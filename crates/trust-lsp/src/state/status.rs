@@ -0,0 +1,96 @@
+//! Background analysis status tracking for `trust/status` notifications.
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Current background analysis phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AnalysisPhase {
+    /// No background work is running.
+    Idle,
+    /// Loading project configuration and resolving dependencies.
+    ResolvingDependencies,
+    /// Indexing workspace files.
+    Indexing,
+    /// Computing workspace diagnostics.
+    Diagnosing,
+}
+
+/// Snapshot sent to clients via the `trust/status` notification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisStatus {
+    /// Current background phase.
+    pub phase: AnalysisPhase,
+    /// Number of known documents (open + indexed).
+    pub files_indexed: usize,
+    /// Number of documents open in the editor.
+    pub files_open: usize,
+    /// Error count from the most recent workspace diagnostic pass.
+    pub errors: usize,
+    /// Warning count from the most recent workspace diagnostic pass.
+    pub warnings: usize,
+    /// Background tasks waiting for or holding the background permit.
+    pub queue_depth: usize,
+}
+
+#[derive(Debug)]
+pub(super) struct AnalysisTracker {
+    phase: RwLock<AnalysisPhase>,
+    errors: AtomicUsize,
+    warnings: AtomicUsize,
+    queue_depth: AtomicUsize,
+}
+
+impl AnalysisTracker {
+    pub(super) fn new() -> Self {
+        Self {
+            phase: RwLock::new(AnalysisPhase::Idle),
+            errors: AtomicUsize::new(0),
+            warnings: AtomicUsize::new(0),
+            queue_depth: AtomicUsize::new(0),
+        }
+    }
+
+    pub(super) fn phase(&self) -> AnalysisPhase {
+        *self.phase.read()
+    }
+
+    pub(super) fn set_phase(&self, phase: AnalysisPhase) {
+        *self.phase.write() = phase;
+    }
+
+    pub(super) fn set_diagnostic_counts(&self, errors: usize, warnings: usize) {
+        self.errors.store(errors, Ordering::Relaxed);
+        self.warnings.store(warnings, Ordering::Relaxed);
+    }
+
+    pub(super) fn diagnostic_counts(&self) -> (usize, usize) {
+        (
+            self.errors.load(Ordering::Relaxed),
+            self.warnings.load(Ordering::Relaxed),
+        )
+    }
+
+    pub(super) fn enqueue(&self) -> QueueGuard<'_> {
+        self.queue_depth.fetch_add(1, Ordering::Relaxed);
+        QueueGuard { tracker: self }
+    }
+
+    pub(super) fn queue_depth(&self) -> usize {
+        self.queue_depth.load(Ordering::Relaxed)
+    }
+}
+
+/// Decrements the queue depth when background work finishes or is dropped.
+pub(super) struct QueueGuard<'a> {
+    tracker: &'a AnalysisTracker,
+}
+
+impl Drop for QueueGuard<'_> {
+    fn drop(&mut self) {
+        self.tracker.queue_depth.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,62 @@
+//! Cycle-time comparison between the bytecode VM and the statement interpreter.
+//!
+//! Run with `cargo bench -p trust-runtime --bench vm_cycle`. Pass a cycle count
+//! as the first argument to override the default.
+
+use std::time::{Duration, Instant};
+
+use trust_runtime::eval::vm::ExecutionEngine;
+use trust_runtime::harness::TestHarness;
+
+const DEFAULT_CYCLES: u32 = 2_000;
+
+const ARITHMETIC: &str = r#"
+    PROGRAM Filter
+    VAR
+        i : INT;
+        acc : LREAL := 0.0;
+        gain : LREAL := 0.5;
+        state : DINT := 1;
+        hits : DINT := 0;
+    END_VAR
+    FOR i := 1 TO 200 DO
+        acc := acc * 0.9 + gain * (2.0 * 3.0 - 1.0) / (1.0 + 4.0);
+        state := (state * 1103 + 12345) MOD 65536;
+        IF state > 32768 AND state MOD 3 = 0 THEN
+            hits := hits + 1;
+        ELSIF state < 1024 OR state = 4096 THEN
+            hits := hits - 1;
+        END_IF;
+    END_FOR;
+    END_PROGRAM
+"#;
+
+fn measure(engine: ExecutionEngine, cycles: u32) -> Duration {
+    let mut harness = TestHarness::from_source(ARITHMETIC).expect("benchmark program compiles");
+    harness.runtime_mut().set_execution_engine(engine);
+    // Warm up allocations and caches before timing.
+    harness.run_cycles(10);
+    let start = Instant::now();
+    for _ in 0..cycles {
+        let result = harness.cycle();
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let cycles = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse::<u32>().ok())
+        .unwrap_or(DEFAULT_CYCLES);
+    let interpreter = measure(ExecutionEngine::Interpreter, cycles);
+    let vm = measure(ExecutionEngine::Vm, cycles);
+    let per_cycle = |elapsed: Duration| elapsed.as_secs_f64() * 1e6 / f64::from(cycles);
+    println!("cycles: {cycles}");
+    println!("interpreter: {:.2} us/cycle", per_cycle(interpreter));
+    println!("vm:          {:.2} us/cycle", per_cycle(vm));
+    println!(
+        "speedup:     {:.2}x",
+        interpreter.as_secs_f64() / vm.as_secs_f64()
+    );
+}
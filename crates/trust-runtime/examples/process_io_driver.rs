@@ -0,0 +1,75 @@
+//! Skeleton out-of-tree I/O driver.
+//!
+//! Build it, then declare it in `runtime.toml` and use it from `io.toml`:
+//!
+//! ```toml
+//! # runtime.toml
+//! [[runtime.io_drivers]]
+//! name = "echo"
+//! command = "drivers/process_io_driver"
+//!
+//! # io.toml
+//! [[io.drivers]]
+//! name = "echo"
+//! params = { invert = true }
+//! ```
+//!
+//! The driver copies each output byte back to the matching input byte,
+//! optionally inverted. Replace `EchoDriver` with the vendor hardware access.
+
+use smol_str::SmolStr;
+use trust_runtime::error::RuntimeError;
+use trust_runtime::io::{serve_process_driver, IoAddress, IoChannel, IoDriver};
+
+struct EchoDriver {
+    invert: bool,
+    last_outputs: Vec<u8>,
+}
+
+impl EchoDriver {
+    fn from_params(params: &toml::Value) -> Result<Self, RuntimeError> {
+        let invert = match params.get("invert") {
+            None => false,
+            Some(toml::Value::Boolean(flag)) => *flag,
+            Some(_) => {
+                return Err(RuntimeError::InvalidConfig(
+                    "io.params.invert must be a boolean".into(),
+                ))
+            }
+        };
+        Ok(Self {
+            invert,
+            last_outputs: Vec::new(),
+        })
+    }
+}
+
+impl IoDriver for EchoDriver {
+    fn read_inputs(&mut self, inputs: &mut [u8]) -> Result<(), RuntimeError> {
+        for (input, output) in inputs.iter_mut().zip(&self.last_outputs) {
+            *input = if self.invert { !output } else { *output };
+        }
+        Ok(())
+    }
+
+    fn write_outputs(&mut self, outputs: &[u8]) -> Result<(), RuntimeError> {
+        self.last_outputs.clear();
+        self.last_outputs.extend_from_slice(outputs);
+        Ok(())
+    }
+
+    fn channels(&self) -> Vec<IoChannel> {
+        vec![IoChannel {
+            address: IoAddress::parse("%IB0").expect("static address"),
+            label: SmolStr::new("echo byte 0"),
+        }]
+    }
+}
+
+fn main() {
+    let result = serve_process_driver(|params| Ok(Box::new(EchoDriver::from_params(params)?)));
+    if let Err(err) = result {
+        eprintln!("echo driver: {err}");
+        std::process::exit(1);
+    }
+}
@@ -20,8 +20,14 @@ mod deploy;
 mod docs;
 #[path = "trust-runtime/git.rs"]
 mod git;
+#[path = "trust-runtime/graph.rs"]
+mod graph;
 #[path = "trust-runtime/hmi.rs"]
 mod hmi;
+#[path = "trust-runtime/library.rs"]
+mod library;
+#[path = "trust-runtime/new.rs"]
+mod new;
 #[path = "trust-runtime/plcopen.rs"]
 mod plcopen;
 #[path = "trust-runtime/prompt.rs"]
@@ -36,6 +42,8 @@ mod setup;
 mod style;
 #[path = "trust-runtime/test.rs"]
 mod test;
+#[path = "trust-runtime/wcet.rs"]
+mod wcet;
 #[path = "trust-runtime/wizard.rs"]
 mod wizard;
 
@@ -47,11 +55,15 @@ use cli::{Cli, Command};
 fn main() -> anyhow::Result<()> {
     let raw_args: Vec<String> = std::env::args().collect();
     let ci_mode = raw_args.iter().any(|arg| arg == "--ci");
-    let ci_command = raw_args
-        .iter()
-        .skip(1)
-        .find(|arg| !arg.starts_with('-'))
-        .map(|arg| arg.as_str());
+    let ci_command = if raw_args.iter().any(|arg| arg == "--check") {
+        Some("check")
+    } else {
+        raw_args
+            .iter()
+            .skip(1)
+            .find(|arg| !arg.starts_with('-'))
+            .map(|arg| arg.as_str())
+    };
     if let Err(err) = run() {
         let message = format_error_with_tip(&err);
         eprintln!("{}", style::error(format!("Error: {message}")));
@@ -66,6 +78,21 @@ fn main() -> anyhow::Result<()> {
 }
 
 fn run() -> anyhow::Result<()> {
+    // Bundles produced by `build --self-extracting` carry their project and skip the CLI.
+    // A failed probe must not keep the CLI from starting.
+    if let Ok(executable) = std::env::current_exe() {
+        match trust_runtime::self_extracting::SelfExtractingImage::read_embedded(&executable) {
+            Ok(Some(image)) => return run::run_embedded(image, &executable),
+            Ok(None) => {}
+            Err(err) => eprintln!(
+                "{}",
+                style::warning(format!(
+                    "Ignoring embedded project in {}: {err:#}",
+                    executable.display()
+                ))
+            ),
+        }
+    }
     let raw_args: Vec<String> = std::env::args().collect();
     let used_bundle_flag = raw_args
         .iter()
@@ -89,6 +116,9 @@ fn run() -> anyhow::Result<()> {
             style::warning("Warning: --bundle is deprecated. Use --project instead.")
         );
     }
+    if cli.check {
+        return run::run_check(cli.project, cli.ci);
+    }
     match cli.command {
         None => run::run_default(cli.verbose),
         Some(Command::Run {
@@ -98,6 +128,7 @@ fn run() -> anyhow::Result<()> {
             restart,
             simulation,
             time_scale,
+            watch,
         }) => run::run_runtime(
             project,
             config,
@@ -109,6 +140,7 @@ fn run() -> anyhow::Result<()> {
             false,
             simulation,
             time_scale,
+            watch,
         ),
         Some(Command::Play {
             project,
@@ -143,7 +175,15 @@ fn run() -> anyhow::Result<()> {
             refresh,
             no_input,
             beginner,
-        }) => trust_runtime::ui::run_ui(project, endpoint, token, refresh, no_input, beginner),
+            fleet,
+            discover,
+        }) => {
+            if fleet.is_some() || discover {
+                trust_runtime::ui::run_fleet_ui(fleet, discover, token, refresh, no_input, beginner)
+            } else {
+                trust_runtime::ui::run_ui(project, endpoint, token, refresh, no_input, beginner)
+            }
+        }
         Some(Command::Ctl {
             project,
             endpoint,
@@ -155,7 +195,20 @@ fn run() -> anyhow::Result<()> {
             project,
             sources,
             ci,
-        }) => build::run_build(project, sources, ci),
+            self_extracting,
+            output,
+            with_control,
+            runner,
+        }) => build::run_build(
+            project,
+            sources,
+            ci,
+            self_extracting.then_some(build::SelfExtractingOptions {
+                output,
+                with_control,
+                runner,
+            }),
+        ),
         Some(Command::Test {
             project,
             filter,
@@ -169,8 +222,24 @@ fn run() -> anyhow::Result<()> {
             out_dir,
             format,
         }) => docs::run_docs(project, out_dir, format),
+        Some(Command::Wcet {
+            project,
+            endpoint,
+            token,
+            profile,
+            statement_ns,
+            json,
+        }) => wcet::run_wcet(project, endpoint, token, profile, statement_ns, json),
+        Some(Command::Graph {
+            project,
+            format,
+            graph,
+            entry_task,
+            output,
+        }) => graph::run_graph(project, format, graph, entry_task, output),
         Some(Command::Hmi { project, action }) => hmi::run_hmi(project, action),
         Some(Command::Plcopen { action }) => plcopen::run_plcopen(action),
+        Some(Command::Library { action }) => library::run_library(action),
         Some(Command::Registry { action }) => registry::run_registry(action),
         Some(Command::Setup {
             mode,
@@ -197,6 +266,12 @@ fn run() -> anyhow::Result<()> {
             path,
             force,
         }),
+        Some(Command::New {
+            path,
+            template,
+            force,
+            no_git,
+        }) => new::run_new(path, template, force, no_git),
         Some(Command::Wizard { path, start }) => wizard::run_wizard(path, start),
         Some(Command::Commit {
             project,
@@ -208,7 +283,26 @@ fn run() -> anyhow::Result<()> {
             root,
             label,
             restart,
+            target,
+            token,
+            sign_key,
+            sign_secret_file,
+            probation,
         }) => {
+            if let Some(target) = target {
+                return deploy::run_remote_deploy(
+                    project,
+                    deploy::RemoteDeploy {
+                        target,
+                        token,
+                        label,
+                        sign_key,
+                        sign_secret_file,
+                        probation,
+                        restart,
+                    },
+                );
+            }
             let result = deploy::run_deploy(project, root, label)?;
             if let Some(mode) = restart {
                 ctl::run_control(
@@ -1,18 +1,29 @@
 //! Bundle build command (compile sources to program.stbc).
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use indicatif::{ProgressBar, ProgressStyle};
 use serde_json::json;
 use trust_runtime::bundle::detect_bundle_path;
 use trust_runtime::bundle_builder::build_program_stbc;
+use trust_runtime::config::RuntimeConfig;
+use trust_runtime::self_extracting::SelfExtractingImage;
 
 use crate::style;
 
+/// `--self-extracting` settings for the build command.
+#[derive(Debug, Clone)]
+pub struct SelfExtractingOptions {
+    pub output: Option<PathBuf>,
+    pub with_control: bool,
+    pub runner: Option<PathBuf>,
+}
+
 pub fn run_build(
     bundle: Option<PathBuf>,
     sources: Option<PathBuf>,
     ci: bool,
+    self_extracting: Option<SelfExtractingOptions>,
 ) -> anyhow::Result<()> {
     let bundle_root = match bundle {
         Some(path) => path,
@@ -29,6 +40,9 @@ pub fn run_build(
         spinner.finish_and_clear();
         report
     };
+    let self_extracting = self_extracting
+        .map(|options| build_self_extracting(&bundle_root, sources.as_deref(), options))
+        .transpose()?;
     if ci {
         let payload = json!({
             "version": 1,
@@ -38,6 +52,16 @@ pub fn run_build(
             "program": report.program_path.display().to_string(),
             "source_count": report.sources.len(),
             "sources": report.sources.iter().map(|path| path.display().to_string()).collect::<Vec<_>>(),
+            "build": {
+                "version": report.build_info.version.as_deref(),
+                "git_hash": report.build_info.git_hash.as_deref(),
+                "built_at": report.build_info.built_at.as_deref(),
+                "author": report.build_info.author.as_deref(),
+            },
+            "self_extracting": self_extracting.as_ref().map(|(path, size)| json!({
+                "path": path.display().to_string(),
+                "bytes": size,
+            })),
         });
         println!("{}", serde_json::to_string_pretty(&payload)?);
         return Ok(());
@@ -46,6 +70,14 @@ pub fn run_build(
         "{}",
         style::success(format!("Wrote {}", report.program_path.display()))
     );
+    let build = &report.build_info;
+    println!(
+        "Program: {} ({}), built {} by {}",
+        build.version.as_deref().unwrap_or("unversioned"),
+        build.git_hash.as_deref().unwrap_or("no git"),
+        build.built_at.as_deref().unwrap_or("-"),
+        build.author.as_deref().unwrap_or("unknown")
+    );
     println!("Sources: {} file(s)", report.sources.len());
     for path in report.sources.iter().take(5) {
         println!(" - {}", path.display());
@@ -53,5 +85,38 @@ pub fn run_build(
     if report.sources.len() > 5 {
         println!(" - ... +{}", report.sources.len() - 5);
     }
+    if let Some((path, size)) = self_extracting {
+        println!(
+            "{}",
+            style::success(format!(
+                "Wrote self-extracting bundle {} ({} KiB)",
+                path.display(),
+                size.div_ceil(1024)
+            ))
+        );
+    }
     Ok(())
 }
+
+/// Pack the built project into a copy of the runner executable.
+fn build_self_extracting(
+    bundle_root: &Path,
+    sources: Option<&Path>,
+    options: SelfExtractingOptions,
+) -> anyhow::Result<(PathBuf, u64)> {
+    let image = SelfExtractingImage::from_project(bundle_root, sources, options.with_control)?;
+    let runner = match options.runner {
+        Some(path) => path,
+        None => std::env::current_exe()?,
+    };
+    let output = match options.output {
+        Some(path) => path,
+        None => {
+            let config = RuntimeConfig::load(bundle_root.join("runtime.toml"))?;
+            let name = format!("{}{}", config.resource_name, std::env::consts::EXE_SUFFIX);
+            bundle_root.join("build").join(name)
+        }
+    };
+    let size = image.write_executable(&runner, &output)?;
+    Ok((output, size))
+}
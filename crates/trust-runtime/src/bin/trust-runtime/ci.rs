@@ -57,7 +57,7 @@ pub fn classify_error_with_command(message: &str, command: Option<&str>) -> i32
     match command {
         Some("build") => EXIT_BUILD_FAILED,
         Some("test") => EXIT_TEST_FAILED,
-        Some("validate") => EXIT_INVALID_CONFIG,
+        Some("validate" | "check") => EXIT_INVALID_CONFIG,
         _ => EXIT_INTERNAL,
     }
 }
@@ -120,5 +120,9 @@ mod tests {
             classify_error_with_command("bad bundle", Some("validate")),
             EXIT_INVALID_CONFIG
         );
+        assert_eq!(
+            classify_error_with_command("check failed: 1 of 6 steps failed", Some("check")),
+            EXIT_INVALID_CONFIG
+        );
     }
 }
@@ -11,12 +11,21 @@ use std::path::PathBuf;
     about = "Structured Text runtime CLI",
     infer_subcommands = true,
     arg_required_else_help = false,
-    after_help = "Examples:\n  trust-runtime                       # start (first run opens setup)\n  trust-runtime --verbose             # show startup details\n  trust-runtime ui --project ./my-plc # terminal UI\n  trust-runtime play --project ./my-plc # compatibility"
+    after_help = "Examples:\n  trust-runtime                       # start (first run opens setup)\n  trust-runtime --verbose             # show startup details\n  trust-runtime ui --project ./my-plc # terminal UI\n  trust-runtime play --project ./my-plc # compatibility\n  trust-runtime --check --project ./my-plc # dry-run self-test"
 )]
 pub struct Cli {
     /// Show verbose startup details.
     #[arg(long, short, global = true)]
     pub verbose: bool,
+    /// Dry-run the project (config, I/O drivers, retain layout, one simulated cycle) and exit.
+    #[arg(long)]
+    pub check: bool,
+    /// Project folder directory for --check (auto-detect if omitted).
+    #[arg(long = "project", alias = "bundle", requires = "check")]
+    pub project: Option<PathBuf>,
+    /// Print the --check report as JSON.
+    #[arg(long, requires = "check")]
+    pub ci: bool,
     #[command(subcommand)]
     pub command: Option<Command>,
 }
@@ -43,6 +52,9 @@ pub enum Command {
         /// Simulation time acceleration factor (>= 1).
         #[arg(long, default_value_t = 1)]
         time_scale: u32,
+        /// Rebuild and hot-reload the program when sources change (implies --simulation).
+        #[arg(long, action = ArgAction::SetTrue)]
+        watch: bool,
     },
     /// Start the runtime with project auto-detection (production UX).
     #[command(
@@ -91,6 +103,12 @@ pub enum Command {
         /// Beginner mode (Play/Stop/Download/Debug only).
         #[arg(long)]
         beginner: bool,
+        /// Fleet file (TOML with [[plc]] name/endpoint/token) for the multi-PLC dashboard.
+        #[arg(long, conflicts_with_all = ["project", "endpoint"])]
+        fleet: Option<PathBuf>,
+        /// Add runtimes found via local discovery (mDNS) to the multi-PLC dashboard.
+        #[arg(long, conflicts_with_all = ["project", "endpoint"])]
+        discover: bool,
     },
     /// Send control commands to a running runtime.
     Ctl {
@@ -116,6 +134,9 @@ pub enum Command {
         ci: bool,
     },
     /// Build program.stbc from project sources.
+    #[command(
+        after_help = "Examples:\n  trust-runtime build\n  trust-runtime build --self-extracting --output ./dist/plc\n  trust-runtime build --self-extracting --with-control"
+    )]
     Build {
         /// Project folder directory (defaults to auto-detect or current directory).
         #[arg(long = "project", alias = "bundle")]
@@ -123,6 +144,18 @@ pub enum Command {
         /// Sources directory override (defaults to <project>/src).
         #[arg(long)]
         sources: Option<PathBuf>,
+        /// Also emit a self-extracting bundle: this runtime with the project appended.
+        #[arg(long, action = ArgAction::SetTrue)]
+        self_extracting: bool,
+        /// Executable path for --self-extracting (defaults to <project>/build/<resource name>).
+        #[arg(long, requires = "self_extracting")]
+        output: Option<PathBuf>,
+        /// Keep the control server and configured network services in the executable.
+        #[arg(long = "with-control", action = ArgAction::SetTrue, requires = "self_extracting")]
+        with_control: bool,
+        /// Runtime executable to bundle the project with (defaults to this binary).
+        #[arg(long, requires = "self_extracting")]
+        runner: Option<PathBuf>,
         /// Enable CI-friendly behavior and machine-readable output.
         #[arg(long, action = ArgAction::SetTrue)]
         ci: bool,
@@ -160,6 +193,45 @@ pub enum Command {
         #[arg(long, value_enum, default_value_t = DocsFormat::Both)]
         format: DocsFormat,
     },
+    /// Estimate worst-case execution time per task from static counts and profiler timings.
+    Wcet {
+        /// Project folder directory (defaults to auto-detect or current directory).
+        #[arg(long = "project", alias = "bundle")]
+        project: Option<PathBuf>,
+        /// Control endpoint to read measured timings from (tcp://host:port or unix://path).
+        #[arg(long)]
+        endpoint: Option<String>,
+        /// Control auth token.
+        #[arg(long)]
+        token: Option<String>,
+        /// Saved `tasks.stats` JSON result to read measured timings from.
+        #[arg(long)]
+        profile: Option<PathBuf>,
+        /// Statement cost in nanoseconds used when no timings were measured.
+        #[arg(long = "statement-ns")]
+        statement_ns: Option<f64>,
+        /// Print the report as JSON.
+        #[arg(long, action = ArgAction::SetTrue)]
+        json: bool,
+    },
+    /// Export the project call graph and POU dependency graph.
+    Graph {
+        /// Project folder directory (defaults to auto-detect or current directory).
+        #[arg(long = "project", alias = "bundle")]
+        project: Option<PathBuf>,
+        /// Output format (`json`, `dot`).
+        #[arg(long, value_enum, default_value_t = GraphFormat::Json)]
+        format: GraphFormat,
+        /// Graphs to export (`calls`, `dependencies`, `both`).
+        #[arg(long, value_enum, default_value_t = GraphSelection::Both)]
+        graph: GraphSelection,
+        /// Keep only POUs reachable from the program instances of this task.
+        #[arg(long = "entry-task")]
+        entry_task: Option<String>,
+        /// Write the graph to this file instead of stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
     /// Human-machine-interface scaffold workflows.
     Hmi {
         /// Project folder directory (defaults to auto-detect or current directory).
@@ -173,6 +245,11 @@ pub enum Command {
         #[command(subcommand)]
         action: PlcopenAction,
     },
+    /// Precompiled library archive (.stlib) workflows.
+    Library {
+        #[command(subcommand)]
+        action: LibraryAction,
+    },
     /// Package registry workflows.
     Registry {
         #[command(subcommand)]
@@ -217,6 +294,23 @@ pub enum Command {
         #[arg(long)]
         force: bool,
     },
+    /// Create a ready-to-run project folder from a starter template.
+    #[command(
+        after_help = "Examples:\n  trust-runtime new my-plc\n  trust-runtime new line-2 --template traffic-light\n  trust-runtime new cell-io --template modbus-io --no-git"
+    )]
+    New {
+        /// Directory to create the project in.
+        path: PathBuf,
+        /// Starter program and I/O profile.
+        #[arg(long, value_enum, default_value_t = ProjectTemplateArg::Counter)]
+        template: ProjectTemplateArg,
+        /// Write into an existing non-empty directory, overwriting template files.
+        #[arg(long, action = ArgAction::SetTrue)]
+        force: bool,
+        /// Skip `git init` in the new project.
+        #[arg(long, action = ArgAction::SetTrue)]
+        no_git: bool,
+    },
     /// Guided wizard to create a new project folder.
     #[command(alias = "init")]
     Wizard {
@@ -240,12 +334,15 @@ pub enum Command {
         dry_run: bool,
     },
     /// Deploy a project folder into a versioned store with rollback support.
+    ///
+    /// With --target the project is pushed to a running runtime instead, activated
+    /// in place, and rolled back automatically if it faults during probation.
     Deploy {
         /// Source project folder directory.
         #[arg(long = "project", alias = "bundle")]
         project: PathBuf,
         /// Deployment root (defaults to current directory).
-        #[arg(long)]
+        #[arg(long, conflicts_with = "target")]
         root: Option<PathBuf>,
         /// Custom deployment label (defaults to project-<timestamp>).
         #[arg(long)]
@@ -253,6 +350,22 @@ pub enum Command {
         /// Restart mode after deployment (optional).
         #[arg(long)]
         restart: Option<String>,
+        /// Web URL of a remote runtime to deploy to (e.g. http://plc-1:8080).
+        #[arg(long)]
+        target: Option<String>,
+        /// Access token for the remote runtime.
+        #[arg(long, requires = "target")]
+        token: Option<String>,
+        /// Deploy signing key id from the target's keyring.
+        #[arg(long, requires_all = ["target", "sign_secret_file"])]
+        sign_key: Option<String>,
+        /// File holding the secret for --sign-key.
+        #[arg(long, requires = "sign_key")]
+        sign_secret_file: Option<PathBuf>,
+        /// Seconds to watch the new program for faults before keeping it
+        /// (defaults to runtime.deploy.probation_s on the target; 0 disables).
+        #[arg(long, requires = "target")]
+        probation: Option<u64>,
     },
     /// Roll back to the previous project version in a deployment root.
     Rollback {
@@ -301,6 +414,26 @@ pub enum DocsFormat {
     Both,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    Json,
+    Dot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphSelection {
+    Calls,
+    Dependencies,
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProjectTemplateArg {
+    Counter,
+    TrafficLight,
+    ModbusIo,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum HmiStyleArg {
     Industrial,
@@ -414,6 +547,37 @@ pub enum RegistryVisibilityArg {
     Private,
 }
 
+#[derive(Debug, Subcommand)]
+pub enum LibraryAction {
+    /// Compile a library project into a .stlib archive (bytecode + interface, no source).
+    Pack {
+        /// Library project folder (defaults to auto-detect or current directory).
+        #[arg(long = "project", alias = "bundle")]
+        project: Option<PathBuf>,
+        /// Sources directory override (defaults to <project>/src).
+        #[arg(long)]
+        sources: Option<PathBuf>,
+        /// Output archive path (defaults to <project>/<name>.stlib).
+        #[arg(long = "output")]
+        output: Option<PathBuf>,
+        /// Print machine-readable JSON report.
+        #[arg(long, action = ArgAction::SetTrue)]
+        json: bool,
+    },
+    /// Print the interface manifest of a .stlib archive.
+    Inspect {
+        /// Archive file path.
+        #[arg(long = "archive")]
+        archive: PathBuf,
+        /// Print the declaration-only ST interface.
+        #[arg(long, action = ArgAction::SetTrue)]
+        interface: bool,
+        /// Print machine-readable JSON.
+        #[arg(long, action = ArgAction::SetTrue)]
+        json: bool,
+    },
+}
+
 #[derive(Debug, Subcommand)]
 pub enum RegistryAction {
     /// Print package registry API contract and metadata model.
@@ -508,24 +672,60 @@ pub enum ControlAction {
     Status,
     Health,
     Stats,
-    Pause,
+    Pause {
+        /// `immediate` or `cycle_end` (default: runtime.control.pause).
+        #[arg(long)]
+        policy: Option<String>,
+    },
     Resume,
     StepIn,
     StepOver,
     StepOut,
-    BreakpointsSet { source: String, lines: Vec<u32> },
-    BreakpointsClear { source: String },
+    /// Run N scan cycles on a paused resource, then pause again.
+    Cycle {
+        #[arg(default_value_t = 1)]
+        cycles: u32,
+    },
+    BreakpointsSet {
+        source: String,
+        lines: Vec<u32>,
+    },
+    BreakpointsClear {
+        source: String,
+    },
     BreakpointsList,
     IoRead,
-    IoWrite { address: String, value: String },
-    IoForce { address: String, value: String },
-    IoUnforce { address: String },
-    Eval { expr: String },
-    Set { target: String, value: String },
-    Restart { mode: String },
+    IoMap,
+    IoWrite {
+        address: String,
+        value: String,
+    },
+    IoForce {
+        address: String,
+        value: String,
+    },
+    IoUnforce {
+        address: String,
+    },
+    Eval {
+        expr: String,
+    },
+    Set {
+        target: String,
+        value: String,
+    },
+    Restart {
+        mode: String,
+    },
     Shutdown,
     ConfigGet,
-    ConfigSet { key: String, value: String },
+    ConfigSet {
+        key: String,
+        value: String,
+    },
+    MeshStatus,
+    /// Print a diagnostic bundle (events, metrics, breakpoints) for a support ticket.
+    Diagnostics,
 }
 
 #[cfg(test)]
@@ -542,6 +742,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_build_self_extracting_flags() {
+        let cli = Cli::parse_from([
+            "trust-runtime",
+            "build",
+            "--self-extracting",
+            "--output",
+            "dist/plc",
+            "--with-control",
+        ]);
+        match cli.command.expect("command") {
+            Command::Build {
+                self_extracting,
+                output,
+                with_control,
+                runner,
+                ..
+            } => {
+                assert!(self_extracting);
+                assert_eq!(output, Some(PathBuf::from("dist/plc")));
+                assert!(with_control);
+                assert!(runner.is_none());
+            }
+            other => panic!("expected build command, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["trust-runtime", "build", "--with-control"]).is_err());
+    }
+
     #[test]
     fn parse_validate_ci_flag() {
         let cli = Cli::parse_from(["trust-runtime", "validate", "--project", "project", "--ci"]);
@@ -551,6 +779,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_check_flag() {
+        let cli = Cli::parse_from(["trust-runtime", "--check", "--project", "project", "--ci"]);
+        assert!(cli.check && cli.ci);
+        assert_eq!(cli.project, Some(PathBuf::from("project")));
+        assert!(cli.command.is_none());
+        assert!(Cli::try_parse_from(["trust-runtime", "--ci"]).is_err());
+    }
+
     #[test]
     fn parse_test_ci_flag() {
         let cli = Cli::parse_from(["trust-runtime", "test", "--project", "project", "--ci"]);
@@ -586,6 +823,71 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_wcet_command() {
+        let cli = Cli::parse_from([
+            "trust-runtime",
+            "wcet",
+            "--project",
+            "project",
+            "--profile",
+            "stats.json",
+            "--statement-ns",
+            "50",
+            "--json",
+        ]);
+        match cli.command.expect("command") {
+            Command::Wcet {
+                project,
+                endpoint,
+                token,
+                profile,
+                statement_ns,
+                json,
+            } => {
+                assert_eq!(project, Some(PathBuf::from("project")));
+                assert_eq!(endpoint, None);
+                assert_eq!(token, None);
+                assert_eq!(profile, Some(PathBuf::from("stats.json")));
+                assert_eq!(statement_ns, Some(50.0));
+                assert!(json);
+            }
+            other => panic!("expected wcet command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_graph_command() {
+        let cli = Cli::parse_from([
+            "trust-runtime",
+            "graph",
+            "--project",
+            "project",
+            "--format",
+            "dot",
+            "--graph",
+            "dependencies",
+            "--entry-task",
+            "Fast",
+        ]);
+        match cli.command.expect("command") {
+            Command::Graph {
+                project,
+                format,
+                graph,
+                entry_task,
+                output,
+            } => {
+                assert_eq!(project, Some(PathBuf::from("project")));
+                assert_eq!(format, GraphFormat::Dot);
+                assert_eq!(graph, GraphSelection::Dependencies);
+                assert_eq!(entry_task.as_deref(), Some("Fast"));
+                assert_eq!(output, None);
+            }
+            other => panic!("expected graph command, got {other:?}"),
+        }
+    }
+
     #[test]
     fn parse_plcopen_export_command() {
         let cli = Cli::parse_from([
@@ -617,6 +919,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_library_pack_command() {
+        let cli = Cli::parse_from([
+            "trust-runtime",
+            "library",
+            "pack",
+            "--project",
+            "motion",
+            "--output",
+            "dist/Motion.stlib",
+        ]);
+        match cli.command.expect("command") {
+            Command::Library { action } => match action {
+                LibraryAction::Pack {
+                    project,
+                    sources,
+                    output,
+                    json,
+                } => {
+                    assert_eq!(project, Some(PathBuf::from("motion")));
+                    assert_eq!(sources, None);
+                    assert_eq!(output, Some(PathBuf::from("dist/Motion.stlib")));
+                    assert!(!json);
+                }
+                other => panic!("expected library pack action, got {other:?}"),
+            },
+            other => panic!("expected library command, got {other:?}"),
+        }
+    }
+
     #[test]
     fn parse_plcopen_export_target_command() {
         let cli = Cli::parse_from([
@@ -668,6 +1000,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_new_template_flags() {
+        let cli = Cli::parse_from([
+            "trust-runtime",
+            "new",
+            "line-2",
+            "--template",
+            "traffic-light",
+            "--no-git",
+        ]);
+        match cli.command.expect("command") {
+            Command::New {
+                path,
+                template,
+                force,
+                no_git,
+            } => {
+                assert_eq!(path, PathBuf::from("line-2"));
+                assert_eq!(template, ProjectTemplateArg::TrafficLight);
+                assert!(!force);
+                assert!(no_git);
+            }
+            other => panic!("expected new command, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["trust-runtime", "new"]).is_err());
+    }
+
+    #[test]
+    fn parse_run_watch_flag() {
+        let cli = Cli::parse_from(["trust-runtime", "run", "--project", "plc", "--watch"]);
+        match cli.command.expect("command") {
+            Command::Run { project, watch, .. } => {
+                assert_eq!(project, Some(PathBuf::from("plc")));
+                assert!(watch);
+            }
+            other => panic!("expected run command, got {other:?}"),
+        }
+    }
+
     #[test]
     fn parse_play_simulation_flags() {
         let cli = Cli::parse_from(["trust-runtime", "play", "--simulation", "--time-scale", "8"]);
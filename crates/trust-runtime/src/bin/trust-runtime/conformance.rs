@@ -8,6 +8,7 @@ use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use anyhow::{anyhow, bail, Context};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use trust_runtime::eval::ops::OverflowPolicy;
 use trust_runtime::harness::TestHarness;
 use trust_runtime::value::{Duration, Value};
 use trust_runtime::RestartMode;
@@ -52,6 +53,9 @@ struct CaseManifest {
     input_series: BTreeMap<String, Vec<String>>,
     direct_input_series: BTreeMap<String, Vec<String>>,
     restarts: Vec<RestartDirective>,
+    /// Integer overflow policy (`wrap`, `saturate`, `fault`); the runtime default
+    /// when omitted.
+    overflow: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -433,6 +437,10 @@ fn execute_runtime_case(case: &CaseDefinition, sources: &[String]) -> anyhow::Re
     let source_refs = sources.iter().map(String::as_str).collect::<Vec<_>>();
     let mut harness =
         TestHarness::from_sources(&source_refs).map_err(|err| anyhow!(err.to_string()))?;
+    if let Some(policy) = case.manifest.overflow.as_deref() {
+        let policy = OverflowPolicy::parse(policy).map_err(|err| anyhow!(err.to_string()))?;
+        harness.runtime_mut().set_overflow_policy(policy);
+    }
 
     let mut trace = Vec::with_capacity(cycles as usize);
     for cycle_idx in 0..(cycles as usize) {
@@ -126,11 +126,25 @@ fn build_request(action: &ControlAction, auth_token: Option<&str>) -> serde_json
         ControlAction::Status => json!({"id": 1, "type": "status", "auth": auth}),
         ControlAction::Health => json!({"id": 1, "type": "health", "auth": auth}),
         ControlAction::Stats => json!({"id": 1, "type": "tasks.stats", "auth": auth}),
-        ControlAction::Pause => json!({"id": 1, "type": "pause", "auth": auth}),
+        ControlAction::Pause { policy } => match policy {
+            Some(policy) => json!({
+                "id": 1,
+                "type": "pause",
+                "auth": auth,
+                "params": { "policy": policy }
+            }),
+            None => json!({"id": 1, "type": "pause", "auth": auth}),
+        },
         ControlAction::Resume => json!({"id": 1, "type": "resume", "auth": auth}),
         ControlAction::StepIn => json!({"id": 1, "type": "step_in", "auth": auth}),
         ControlAction::StepOver => json!({"id": 1, "type": "step_over", "auth": auth}),
         ControlAction::StepOut => json!({"id": 1, "type": "step_out", "auth": auth}),
+        ControlAction::Cycle { cycles } => json!({
+            "id": 1,
+            "type": "cycle.step",
+            "auth": auth,
+            "params": { "cycles": cycles }
+        }),
         ControlAction::BreakpointsSet { source, lines } => json!({
             "id": 1,
             "type": "breakpoints.set",
@@ -147,6 +161,7 @@ fn build_request(action: &ControlAction, auth_token: Option<&str>) -> serde_json
             json!({"id": 1, "type": "breakpoints.list", "auth": auth})
         }
         ControlAction::IoRead => json!({"id": 1, "type": "io.read", "auth": auth}),
+        ControlAction::IoMap => json!({"id": 1, "type": "io.map.get", "auth": auth}),
         ControlAction::IoWrite { address, value } => json!({
             "id": 1,
             "type": "io.write",
@@ -185,6 +200,10 @@ fn build_request(action: &ControlAction, auth_token: Option<&str>) -> serde_json
         }),
         ControlAction::Shutdown => json!({"id": 1, "type": "shutdown", "auth": auth}),
         ControlAction::ConfigGet => json!({"id": 1, "type": "config.get", "auth": auth}),
+        ControlAction::MeshStatus => json!({"id": 1, "type": "mesh.status", "auth": auth}),
+        ControlAction::Diagnostics => {
+            json!({"id": 1, "type": "diagnostics.collect", "auth": auth})
+        }
         ControlAction::ConfigSet { key, value } => {
             let mut params = serde_json::Map::new();
             params.insert(key.clone(), parse_config_value(value));
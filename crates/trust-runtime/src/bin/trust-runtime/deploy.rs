@@ -3,12 +3,19 @@
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use indicatif::{ProgressBar, ProgressStyle};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
 use trust_runtime::config::{IoConfig, RuntimeBundle, RuntimeConfig};
-use trust_runtime::io::{IoAddress, IoDriverRegistry};
+use trust_runtime::io::IoAddress;
 use trust_runtime::watchdog::WatchdogPolicy;
+use trust_runtime::web::{
+    sign_deploy_request, DeployActivation, DeployPhase, DeployRequest, DeploySource,
+};
 
 use crate::style;
 
@@ -108,8 +115,169 @@ pub fn run_rollback(root: Option<PathBuf>) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Options for pushing a project to a running runtime over its web API.
+pub struct RemoteDeploy {
+    pub target: String,
+    pub token: Option<String>,
+    pub label: Option<String>,
+    pub sign_key: Option<String>,
+    pub sign_secret_file: Option<PathBuf>,
+    pub probation: Option<u64>,
+    pub restart: Option<String>,
+}
+
+/// Extra time allowed past the probation window before giving up on the target.
+const PROBATION_GRACE: Duration = Duration::from_secs(30);
+
+pub fn run_remote_deploy(bundle: PathBuf, remote: RemoteDeploy) -> anyhow::Result<()> {
+    let source_bundle = RuntimeBundle::load(&bundle)?;
+    validate_bundle(&source_bundle)?;
+    let mut request = package_bundle(&source_bundle)?;
+    request.activate = true;
+    request.probation_s = remote.probation;
+    request.label = Some(remote.label.unwrap_or_else(default_bundle_label));
+    request.restart = remote.restart;
+    if let (Some(key_id), Some(secret_file)) = (&remote.sign_key, &remote.sign_secret_file) {
+        let secret = fs::read_to_string(secret_file)
+            .map_err(|err| anyhow::anyhow!("read {}: {err}", secret_file.display()))?;
+        sign_deploy_request(&mut request, key_id, &secret);
+    }
+    let expected_sha = hex_digest(&source_bundle.bytecode);
+    let label = request.label.clone().unwrap_or_default();
+
+    let target = remote.target.trim_end_matches('/');
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(5))
+        .timeout_read(Duration::from_secs(30))
+        .build();
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(ProgressStyle::default_spinner().template("{spinner} {msg}")?);
+    spinner.enable_steady_tick(Duration::from_millis(120));
+    spinner.set_message(format!("Deploying project {label} to {target}..."));
+
+    let mut post = agent
+        .post(&format!("{target}/api/deploy"))
+        .set("Content-Type", "application/json");
+    if let Some(token) = remote.token.as_deref() {
+        post = post.set("X-Trust-Token", token);
+    }
+    let response = read_json(post.send_string(&serde_json::to_string(&request)?))?;
+    if response.get("ok").and_then(Value::as_bool) != Some(true) {
+        spinner.finish_and_clear();
+        let error = response
+            .get("error")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        anyhow::bail!("deploy rejected by {target}: {error}");
+    }
+    let mut activation: DeployActivation =
+        serde_json::from_value(response.get("activation").cloned().unwrap_or_default())
+            .map_err(|err| anyhow::anyhow!("unexpected deploy response from {target}: {err}"))?;
+    if activation.program_sha256 != expected_sha {
+        spinner.finish_and_clear();
+        anyhow::bail!(
+            "{target} activated program {} but {} was pushed",
+            activation.program_sha256,
+            expected_sha
+        );
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(activation.probation_s) + PROBATION_GRACE;
+    while activation.phase == DeployPhase::Probation {
+        if Instant::now() >= deadline {
+            spinner.finish_and_clear();
+            anyhow::bail!("probation on {target} did not finish; check /api/deploy/status");
+        }
+        spinner.set_message(format!(
+            "Watching {label} on {target} for faults ({}s probation)...",
+            activation.probation_s
+        ));
+        std::thread::sleep(Duration::from_secs(1));
+        let mut get = agent.get(&format!("{target}/api/deploy/status"));
+        if let Some(token) = remote.token.as_deref() {
+            get = get.set("X-Trust-Token", token);
+        }
+        let status = read_json(get.call())?;
+        let Some(current) = status
+            .get("activation")
+            .cloned()
+            .and_then(|value| serde_json::from_value::<DeployActivation>(value).ok())
+        else {
+            continue;
+        };
+        if current.started_ms != activation.started_ms {
+            spinner.finish_and_clear();
+            anyhow::bail!("deploy on {target} was superseded by another deploy");
+        }
+        activation = current;
+    }
+    spinner.finish_and_clear();
+
+    if activation.phase == DeployPhase::RolledBack {
+        anyhow::bail!(
+            "deploy {label} rolled back on {target}: {}",
+            activation.reason.as_deref().unwrap_or("program faulted")
+        );
+    }
+    println!(
+        "{}",
+        style::success(format!("Deployed project {label} -> {target}"))
+    );
+    println!("Program sha256: {}", activation.program_sha256);
+    Ok(())
+}
+
+/// Builds the deploy payload the runtime web API accepts from a project folder.
+fn package_bundle(bundle: &RuntimeBundle) -> anyhow::Result<DeployRequest> {
+    let io_path = bundle.root.join("io.toml");
+    let io_toml = if io_path.is_file() {
+        Some(fs::read_to_string(io_path)?)
+    } else {
+        None
+    };
+    let sources = collect_sources(&bundle.root)?
+        .into_iter()
+        .map(|(path, bytes)| -> anyhow::Result<DeploySource> {
+            let content = String::from_utf8(bytes)
+                .map_err(|_| anyhow::anyhow!("source src/{path} is not UTF-8"))?;
+            Ok(DeploySource {
+                path: path.replace('\\', "/"),
+                content,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(DeployRequest {
+        runtime_toml: Some(fs::read_to_string(bundle.root.join("runtime.toml"))?),
+        io_toml,
+        program_stbc_b64: Some(STANDARD.encode(&bundle.bytecode)),
+        sources: Some(sources),
+        ..DeployRequest::default()
+    })
+}
+
+fn read_json(response: Result<ureq::Response, ureq::Error>) -> anyhow::Result<Value> {
+    match response {
+        Ok(response) => Ok(serde_json::from_str(&response.into_string()?)?),
+        Err(ureq::Error::Status(401, _)) => {
+            anyhow::bail!("target requires an access token (use --token)")
+        }
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            anyhow::bail!("target returned HTTP {code}: {}", body.trim())
+        }
+        Err(err) => anyhow::bail!("target unreachable: {err}"),
+    }
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
 fn validate_bundle(bundle: &RuntimeBundle) -> anyhow::Result<()> {
-    let registry = IoDriverRegistry::default_registry();
+    let registry = bundle.io_driver_registry();
     for driver in &bundle.io.drivers {
         registry
             .validate(driver.name.as_str(), &driver.params)
@@ -435,6 +603,19 @@ fn diff_retain(changes: &mut Vec<String>, prev: &RuntimeConfig, next: &RuntimeCo
             next.retain_save_interval.as_millis()
         ));
     }
+    if prev.retention.unqualified != next.retention.unqualified {
+        changes.push(format!(
+            "retain.unqualified: {} -> {}",
+            prev.retention.unqualified.as_str(),
+            next.retention.unqualified.as_str()
+        ));
+    }
+    if prev.retention.persistent_on_cold != next.retention.persistent_on_cold {
+        changes.push(format!(
+            "retain.persistent_on_cold: {} -> {}",
+            prev.retention.persistent_on_cold, next.retention.persistent_on_cold
+        ));
+    }
 }
 
 fn diff_watchdog(changes: &mut Vec<String>, prev: &WatchdogPolicy, next: &WatchdogPolicy) {
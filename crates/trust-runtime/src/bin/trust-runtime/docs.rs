@@ -1,11 +1,12 @@
 //! API documentation generation from tagged ST comments.
 
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::BTreeSet;
 use std::fmt::Write;
 use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 use smol_str::SmolStr;
+use trust_hir::doc_comments::{check_doc_signature, comment_lines, parse_doc_comment, DocComment};
 use trust_runtime::bundle::detect_bundle_path;
 use trust_runtime::bundle_builder::resolve_sources_root;
 use trust_syntax::lexer::{self, Token, TokenKind};
@@ -26,6 +27,8 @@ enum ApiItemKind {
     Interface,
     Method,
     Property,
+    Type,
+    GlobalVariable,
 }
 
 impl ApiItemKind {
@@ -40,8 +43,15 @@ impl ApiItemKind {
             Self::Interface => "INTERFACE",
             Self::Method => "METHOD",
             Self::Property => "PROPERTY",
+            Self::Type => "TYPE",
+            Self::GlobalVariable => "VAR_GLOBAL",
         }
     }
+
+    /// Types and global variables have no parameters or return value.
+    fn has_signature(self) -> bool {
+        !matches!(self, Self::Type | Self::GlobalVariable)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -50,27 +60,13 @@ struct LoadedSource {
     text: String,
 }
 
-#[derive(Debug, Clone)]
-struct ApiParamDoc {
-    name: SmolStr,
-    description: String,
-}
-
-#[derive(Debug, Clone, Default)]
-struct ApiDocTags {
-    brief: Option<String>,
-    details: Vec<String>,
-    params: Vec<ApiParamDoc>,
-    returns: Option<String>,
-}
-
 #[derive(Debug, Clone)]
 struct ApiItem {
     kind: ApiItemKind,
     qualified_name: SmolStr,
     file: PathBuf,
     line: usize,
-    tags: ApiDocTags,
+    tags: DocComment,
     declared_params: Vec<SmolStr>,
     has_return: bool,
 }
@@ -88,13 +84,6 @@ struct CommentBlock {
     start_line: usize,
 }
 
-enum CurrentTag {
-    Brief,
-    Detail,
-    Param(usize),
-    Return,
-}
-
 pub fn run_docs(
     project: Option<PathBuf>,
     out_dir: Option<PathBuf>,
@@ -212,41 +201,46 @@ fn collect_api_items(sources: &[LoadedSource]) -> (Vec<ApiItem>, Vec<DocDiagnost
             let Some(kind) = declaration_kind(&node) else {
                 continue;
             };
-            let Some(name) = declaration_name(&node) else {
-                continue;
-            };
-
             let declared_params = declared_param_names(&node);
             let has_return = declaration_has_return(&node, kind);
-            let qualified_name = qualified_name(&node, &name);
-            let Some(decl_offset) = first_non_trivia_token_start(&node) else {
-                continue;
-            };
-            let decl_line = line_for_offset(&source.text, decl_offset);
+            for site in declaration_sites(&node, kind) {
+                let qualified_name = qualified_name(&node, &site.name);
+                let decl_line = line_for_offset(&source.text, site.offset);
+
+                let comment = leading_comment_block(&source.text, &tokens, site.offset)
+                    .or_else(|| {
+                        site.fallback_offset
+                            .and_then(|offset| leading_comment_block(&source.text, &tokens, offset))
+                    })
+                    .or_else(|| {
+                        site.trailing_offset.and_then(|offset| {
+                            trailing_comment_block(&source.text, &tokens, offset)
+                        })
+                    });
+                let mut tags = DocComment::default();
+                if let Some(comment) = comment {
+                    let (parsed, issues) = parse_doc_tags(
+                        &comment,
+                        &source.path,
+                        kind,
+                        qualified_name.as_str(),
+                        &declared_params,
+                        has_return,
+                    );
+                    tags = parsed;
+                    diagnostics.extend(issues);
+                }
 
-            let mut tags = ApiDocTags::default();
-            if let Some(comment) = leading_comment_block(&source.text, &tokens, decl_offset) {
-                let (parsed, issues) = parse_doc_tags(
-                    &comment,
-                    &source.path,
+                items.push(ApiItem {
                     kind,
-                    qualified_name.as_str(),
-                    &declared_params,
+                    qualified_name,
+                    file: source.path.clone(),
+                    line: decl_line,
+                    tags,
+                    declared_params: declared_params.clone(),
                     has_return,
-                );
-                tags = parsed;
-                diagnostics.extend(issues);
+                });
             }
-
-            items.push(ApiItem {
-                kind,
-                qualified_name,
-                file: source.path.clone(),
-                line: decl_line,
-                tags,
-                declared_params,
-                has_return,
-            });
         }
     }
 
@@ -283,10 +277,79 @@ fn declaration_kind(node: &SyntaxNode) -> Option<ApiItemKind> {
         SyntaxKind::Interface => Some(ApiItemKind::Interface),
         SyntaxKind::Method => Some(ApiItemKind::Method),
         SyntaxKind::Property => Some(ApiItemKind::Property),
+        SyntaxKind::TypeDecl => Some(ApiItemKind::Type),
+        SyntaxKind::VarDecl => {
+            let block = node.parent()?;
+            (block.kind() == SyntaxKind::VarBlock
+                && first_non_trivia_token(&block) == Some(SyntaxKind::KwVarGlobal))
+            .then_some(ApiItemKind::GlobalVariable)
+        }
         _ => None,
     }
 }
 
+/// A documented name within a declaration node.
+struct DeclarationSite {
+    name: SmolStr,
+    /// Where a leading doc comment ends.
+    offset: usize,
+    /// Where to look for a leading comment when there is none at `offset`.
+    fallback_offset: Option<usize>,
+    /// Where a same-line trailing comment may start.
+    trailing_offset: Option<usize>,
+}
+
+fn declaration_sites(node: &SyntaxNode, kind: ApiItemKind) -> Vec<DeclarationSite> {
+    let Some(node_offset) = first_non_trivia_token_start(node) else {
+        return Vec::new();
+    };
+    match kind {
+        ApiItemKind::Type => node
+            .children()
+            .filter(|child| child.kind() == SyntaxKind::Name)
+            .enumerate()
+            .filter_map(|(index, name)| {
+                let ident = name
+                    .children_with_tokens()
+                    .filter_map(|element| element.into_token())
+                    .find(|token| token.kind() == SyntaxKind::Ident)?;
+                Some(DeclarationSite {
+                    name: SmolStr::new(ident.text()),
+                    offset: usize::from(ident.text_range().start()),
+                    // The comment above `TYPE` documents the first type.
+                    fallback_offset: (index == 0).then_some(node_offset),
+                    trailing_offset: None,
+                })
+            })
+            .collect(),
+        ApiItemKind::GlobalVariable => node
+            .children()
+            .filter(|child| child.kind() == SyntaxKind::Name)
+            .filter_map(|name| {
+                let ident = name
+                    .children_with_tokens()
+                    .filter_map(|element| element.into_token())
+                    .find(|token| token.kind() == SyntaxKind::Ident)?;
+                Some(DeclarationSite {
+                    name: SmolStr::new(ident.text()),
+                    offset: usize::from(ident.text_range().start()),
+                    fallback_offset: None,
+                    trailing_offset: last_non_trivia_token_end(node),
+                })
+            })
+            .collect(),
+        _ => declaration_name(node)
+            .map(|name| DeclarationSite {
+                name,
+                offset: node_offset,
+                fallback_offset: None,
+                trailing_offset: None,
+            })
+            .into_iter()
+            .collect(),
+    }
+}
+
 fn first_non_trivia_token(node: &SyntaxNode) -> Option<SyntaxKind> {
     node.children_with_tokens()
         .filter_map(|element| element.into_token())
@@ -301,6 +364,14 @@ fn first_non_trivia_token_start(node: &SyntaxNode) -> Option<usize> {
         .map(|token| usize::from(token.text_range().start()))
 }
 
+fn last_non_trivia_token_end(node: &SyntaxNode) -> Option<usize> {
+    node.descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !token.kind().is_trivia())
+        .last()
+        .map(|token| usize::from(token.text_range().end()))
+}
+
 fn declaration_name(node: &SyntaxNode) -> Option<SmolStr> {
     node.children()
         .find(|child| child.kind() == SyntaxKind::Name)
@@ -409,6 +480,9 @@ fn leading_comment_block(
                 }
             }
             TokenKind::LineComment | TokenKind::BlockComment => {
+                if comment_trails_code(source, &tokens[..idx]) {
+                    break;
+                }
                 seen_comment = true;
                 collected.push(token);
             }
@@ -424,7 +498,7 @@ fn leading_comment_block(
     let mut lines = Vec::new();
     for token in &collected {
         let raw = token_text(source, *token);
-        lines.extend(normalize_comment_lines(token.kind, raw));
+        lines.extend(comment_lines(raw));
     }
 
     while matches!(lines.first(), Some(line) if line.trim().is_empty()) {
@@ -441,39 +515,6 @@ fn leading_comment_block(
     Some(CommentBlock { lines, start_line })
 }
 
-fn normalize_comment_lines(kind: TokenKind, raw: &str) -> Vec<String> {
-    match kind {
-        TokenKind::LineComment => vec![raw
-            .strip_prefix("//")
-            .unwrap_or(raw)
-            .trim_start()
-            .trim_end()
-            .to_string()],
-        TokenKind::BlockComment => {
-            let mut body = raw.trim_end();
-            if let Some(stripped) = body
-                .strip_prefix("(*")
-                .and_then(|text| text.strip_suffix("*)"))
-            {
-                body = stripped;
-            } else if let Some(stripped) = body
-                .strip_prefix("/*")
-                .and_then(|text| text.strip_suffix("*/"))
-            {
-                body = stripped;
-            }
-            body.lines()
-                .map(|line| {
-                    let trimmed = line.trim_start();
-                    let without_star = trimmed.strip_prefix('*').map_or(trimmed, str::trim_start);
-                    without_star.trim_end().to_string()
-                })
-                .collect()
-        }
-        _ => Vec::new(),
-    }
-}
-
 fn parse_doc_tags(
     comment: &CommentBlock,
     file: &Path,
@@ -481,215 +522,26 @@ fn parse_doc_tags(
     symbol_name: &str,
     declared_params: &[SmolStr],
     has_return: bool,
-) -> (ApiDocTags, Vec<DocDiagnostic>) {
-    let mut tags = ApiDocTags::default();
-    let mut diagnostics = Vec::new();
-    let mut current: Option<CurrentTag> = None;
-
-    for line in &comment.lines {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        if let Some(tag_line) = trimmed.strip_prefix('@') {
-            let mut parts = tag_line.splitn(2, char::is_whitespace);
-            let tag = parts.next().unwrap_or_default().to_ascii_lowercase();
-            let remainder = parts.next().map_or("", str::trim_start);
-            match tag.as_str() {
-                "brief" => {
-                    if tags.brief.is_some() {
-                        diagnostics.push(DocDiagnostic {
-                            file: file.to_path_buf(),
-                            line: comment.start_line,
-                            message: format!(
-                                "duplicate @brief tag for {} `{}`",
-                                kind.label(),
-                                symbol_name
-                            ),
-                        });
-                    }
-                    if remainder.is_empty() {
-                        diagnostics.push(DocDiagnostic {
-                            file: file.to_path_buf(),
-                            line: comment.start_line,
-                            message: format!(
-                                "missing description for @brief on {} `{}`",
-                                kind.label(),
-                                symbol_name
-                            ),
-                        });
-                    }
-                    tags.brief = if remainder.is_empty() {
-                        None
-                    } else {
-                        Some(remainder.to_string())
-                    };
-                    current = Some(CurrentTag::Brief);
-                }
-                "param" => {
-                    let mut param_parts = remainder.splitn(2, char::is_whitespace);
-                    let Some(name) = param_parts.next().filter(|text| !text.trim().is_empty())
-                    else {
-                        diagnostics.push(DocDiagnostic {
-                            file: file.to_path_buf(),
-                            line: comment.start_line,
-                            message: format!(
-                                "malformed @param tag on {} `{}` (expected: @param <name> <description>)",
-                                kind.label(),
-                                symbol_name
-                            ),
-                        });
-                        current = None;
-                        continue;
-                    };
-                    let description = param_parts.next().map_or("", str::trim_start);
-                    if description.is_empty() {
-                        diagnostics.push(DocDiagnostic {
-                            file: file.to_path_buf(),
-                            line: comment.start_line,
-                            message: format!(
-                                "missing description for @param `{}` on {} `{}`",
-                                name,
-                                kind.label(),
-                                symbol_name
-                            ),
-                        });
-                    }
-                    tags.params.push(ApiParamDoc {
-                        name: SmolStr::new(name),
-                        description: description.to_string(),
-                    });
-                    current = Some(CurrentTag::Param(tags.params.len() - 1));
-                }
-                "return" => {
-                    if tags.returns.is_some() {
-                        diagnostics.push(DocDiagnostic {
-                            file: file.to_path_buf(),
-                            line: comment.start_line,
-                            message: format!(
-                                "duplicate @return tag for {} `{}`",
-                                kind.label(),
-                                symbol_name
-                            ),
-                        });
-                    }
-                    if remainder.is_empty() {
-                        diagnostics.push(DocDiagnostic {
-                            file: file.to_path_buf(),
-                            line: comment.start_line,
-                            message: format!(
-                                "missing description for @return on {} `{}`",
-                                kind.label(),
-                                symbol_name
-                            ),
-                        });
-                    }
-                    tags.returns = if remainder.is_empty() {
-                        None
-                    } else {
-                        Some(remainder.to_string())
-                    };
-                    current = Some(CurrentTag::Return);
-                }
-                other => {
-                    diagnostics.push(DocDiagnostic {
-                        file: file.to_path_buf(),
-                        line: comment.start_line,
-                        message: format!(
-                            "unknown documentation tag `@{}` on {} `{}`",
-                            other,
-                            kind.label(),
-                            symbol_name
-                        ),
-                    });
-                    current = None;
-                }
-            }
-            continue;
-        }
-
-        match current {
-            Some(CurrentTag::Brief) => append_with_space(&mut tags.brief, trimmed),
-            Some(CurrentTag::Param(index)) => {
-                if let Some(param) = tags.params.get_mut(index) {
-                    append_string_with_space(&mut param.description, trimmed);
-                }
-            }
-            Some(CurrentTag::Return) => append_with_space(&mut tags.returns, trimmed),
-            Some(CurrentTag::Detail) | None => {
-                tags.details.push(trimmed.to_string());
-                current = Some(CurrentTag::Detail);
-            }
-        }
-    }
-
-    let mut seen_params = HashSet::new();
-    let declared: HashMap<String, &SmolStr> = declared_params
-        .iter()
-        .map(|name| (name.as_str().to_ascii_uppercase(), name))
-        .collect();
-
-    for param in &tags.params {
-        let normalized = param.name.as_str().to_ascii_uppercase();
-        if !seen_params.insert(normalized.clone()) {
-            diagnostics.push(DocDiagnostic {
-                file: file.to_path_buf(),
-                line: comment.start_line,
-                message: format!(
-                    "duplicate @param entry for `{}` on {} `{}`",
-                    param.name,
-                    kind.label(),
-                    symbol_name
-                ),
-            });
-        }
-        if !declared.contains_key(&normalized) {
-            diagnostics.push(DocDiagnostic {
-                file: file.to_path_buf(),
-                line: comment.start_line,
-                message: format!(
-                    "@param `{}` does not match any declared parameter on {} `{}`",
-                    param.name,
-                    kind.label(),
-                    symbol_name
-                ),
-            });
-        }
-    }
-
-    if tags.returns.is_some() && !has_return {
-        diagnostics.push(DocDiagnostic {
+) -> (DocComment, Vec<DocDiagnostic>) {
+    let subject = format!("{} `{}`", kind.label(), symbol_name);
+    let (tags, mut issues) = parse_doc_comment(&comment.lines, &subject);
+    issues.extend(check_doc_signature(
+        &tags,
+        &subject,
+        declared_params,
+        has_return,
+    ));
+    let diagnostics = issues
+        .into_iter()
+        .map(|message| DocDiagnostic {
             file: file.to_path_buf(),
             line: comment.start_line,
-            message: format!(
-                "@return used on non-returning {} `{}`",
-                kind.label(),
-                symbol_name
-            ),
-        });
-    }
-
+            message,
+        })
+        .collect();
     (tags, diagnostics)
 }
 
-fn append_with_space(target: &mut Option<String>, value: &str) {
-    if let Some(existing) = target {
-        append_string_with_space(existing, value);
-    } else {
-        *target = Some(value.to_string());
-    }
-}
-
-fn append_string_with_space(target: &mut String, value: &str) {
-    if target.is_empty() {
-        target.push_str(value);
-    } else {
-        target.push(' ');
-        target.push_str(value);
-    }
-}
-
 fn render_markdown(items: &[ApiItem], diagnostics: &[DocDiagnostic]) -> String {
     let mut output = String::new();
     let _ = writeln!(output, "# ST API Documentation");
@@ -725,11 +577,13 @@ fn render_markdown(items: &[ApiItem], diagnostics: &[DocDiagnostic]) -> String {
             item.qualified_name
         );
         let _ = writeln!(output, "- Source: `{}`:{}", item.file.display(), item.line);
-        let _ = writeln!(
-            output,
-            "- Return Value: {}",
-            if item.has_return { "yes" } else { "no" }
-        );
+        if item.kind.has_signature() {
+            let _ = writeln!(
+                output,
+                "- Return Value: {}",
+                if item.has_return { "yes" } else { "no" }
+            );
+        }
         if !item.declared_params.is_empty() {
             let names = item
                 .declared_params
@@ -817,13 +671,22 @@ fn render_html(items: &[ApiItem], diagnostics: &[DocDiagnostic]) -> String {
             item.kind.label(),
             html_escape(item.qualified_name.as_str())
         );
-        let _ = writeln!(
-            output,
-            "      <p><strong>Source:</strong> <code>{}</code>:{}<br><strong>Return Value:</strong> {}</p>",
-            html_escape(&item.file.display().to_string()),
-            item.line,
-            if item.has_return { "yes" } else { "no" }
-        );
+        if item.kind.has_signature() {
+            let _ = writeln!(
+                output,
+                "      <p><strong>Source:</strong> <code>{}</code>:{}<br><strong>Return Value:</strong> {}</p>",
+                html_escape(&item.file.display().to_string()),
+                item.line,
+                if item.has_return { "yes" } else { "no" }
+            );
+        } else {
+            let _ = writeln!(
+                output,
+                "      <p><strong>Source:</strong> <code>{}</code>:{}</p>",
+                html_escape(&item.file.display().to_string()),
+                item.line
+            );
+        }
         if !item.declared_params.is_empty() {
             let params = item
                 .declared_params
@@ -894,6 +757,52 @@ fn html_escape(text: &str) -> String {
     escaped
 }
 
+/// Returns true when the comment after `before` shares its line with code.
+fn comment_trails_code(source: &str, before: &[Token]) -> bool {
+    for token in before.iter().rev() {
+        match token.kind {
+            TokenKind::Whitespace => {
+                if token_text(source, *token).contains('\n') {
+                    return false;
+                }
+            }
+            TokenKind::LineComment | TokenKind::BlockComment => {}
+            _ => return true,
+        }
+    }
+    false
+}
+
+/// Returns a comment on the same line after `declaration_end`.
+fn trailing_comment_block(
+    source: &str,
+    tokens: &[Token],
+    declaration_end: usize,
+) -> Option<CommentBlock> {
+    let token_pos =
+        tokens.partition_point(|token| usize::from(token.range.start()) < declaration_end);
+    for token in &tokens[token_pos..] {
+        match token.kind {
+            TokenKind::Whitespace if !token_text(source, *token).contains('\n') => {}
+            TokenKind::LineComment | TokenKind::BlockComment => {
+                let lines: Vec<String> = comment_lines(token_text(source, *token))
+                    .into_iter()
+                    .filter(|line| !line.trim().is_empty())
+                    .collect();
+                if lines.is_empty() {
+                    return None;
+                }
+                return Some(CommentBlock {
+                    lines,
+                    start_line: line_for_offset(source, usize::from(token.range.start())),
+                });
+            }
+            _ => return None,
+        }
+    }
+    None
+}
+
 fn token_text(source: &str, token: Token) -> &str {
     &source[usize::from(token.range.start())..usize::from(token.range.end())]
 }
@@ -910,6 +819,7 @@ fn line_for_offset(source: &str, offset: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use trust_hir::doc_comments::DocParam;
 
     #[test]
     fn parser_extraction_for_tagged_comments() {
@@ -945,6 +855,62 @@ END_FUNCTION
         assert_eq!(function.tags.returns.as_deref(), Some("Sum value."));
     }
 
+    #[test]
+    fn types_and_globals_are_documented() {
+        let sources = vec![LoadedSource {
+            path: PathBuf::from("src/plant.st"),
+            text: r#"
+// @brief Motor operating mode.
+TYPE
+    Mode : (Off, Run);
+    // @brief Speed setpoint in rpm.
+    Speed : INT;
+    Unused : BOOL;
+END_TYPE
+
+CONFIGURATION Plant
+VAR_GLOBAL
+    // @brief Line pressure in bar.
+    Pressure : REAL;
+    Flow : REAL; // @brief Flow rate in l/min.
+    Level : REAL;
+END_VAR
+END_CONFIGURATION
+"#
+            .to_string(),
+        }];
+
+        let (items, diagnostics) = collect_api_items(&sources);
+        assert_eq!(diagnostics.len(), 0);
+        let brief = |name: &str, kind: ApiItemKind| {
+            items
+                .iter()
+                .find(|item| item.qualified_name == name && item.kind == kind)
+                .unwrap_or_else(|| panic!("missing {name}"))
+                .tags
+                .brief
+                .clone()
+        };
+        assert_eq!(
+            brief("Mode", ApiItemKind::Type).as_deref(),
+            Some("Motor operating mode.")
+        );
+        assert_eq!(
+            brief("Speed", ApiItemKind::Type).as_deref(),
+            Some("Speed setpoint in rpm.")
+        );
+        assert_eq!(brief("Unused", ApiItemKind::Type), None);
+        assert_eq!(
+            brief("Pressure", ApiItemKind::GlobalVariable).as_deref(),
+            Some("Line pressure in bar.")
+        );
+        assert_eq!(
+            brief("Flow", ApiItemKind::GlobalVariable).as_deref(),
+            Some("Flow rate in l/min.")
+        );
+        assert_eq!(brief("Level", ApiItemKind::GlobalVariable), None);
+    }
+
     #[test]
     fn broken_tag_diagnostics_are_reported() {
         let sources = vec![LoadedSource {
@@ -1027,15 +993,15 @@ Generated by `trust-runtime docs` from tagged ST comments.
             qualified_name: "Calc.Add".into(),
             file: PathBuf::from("src/math.st"),
             line: 7,
-            tags: ApiDocTags {
+            tags: DocComment {
                 brief: Some("Adds two INT values.".to_string()),
                 details: vec!["Overflow behavior follows IEC arithmetic.".to_string()],
                 params: vec![
-                    ApiParamDoc {
+                    DocParam {
                         name: "A".into(),
                         description: "First operand.".to_string(),
                     },
-                    ApiParamDoc {
+                    DocParam {
                         name: "B".into(),
                         description: "Second operand.".to_string(),
                     },
@@ -0,0 +1,158 @@
+//! Call graph and POU dependency graph export.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde_json::json;
+use trust_hir::db::{FileId, SourceDatabase};
+use trust_ide::{analyze_project_graph, Graph, ProjectGraphOptions};
+use trust_runtime::bundle::detect_bundle_path;
+use trust_runtime::bundle_builder::resolve_sources_root;
+
+use crate::cli::{GraphFormat, GraphSelection};
+use crate::style;
+use crate::wcet::source_paths;
+
+pub fn run_graph(
+    project: Option<PathBuf>,
+    format: GraphFormat,
+    selection: GraphSelection,
+    entry_task: Option<String>,
+    output: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let project_root = match project {
+        Some(path) => path,
+        None => match detect_bundle_path(None) {
+            Ok(path) => path,
+            Err(_) => std::env::current_dir().context("failed to resolve current directory")?,
+        },
+    };
+    let sources_root = resolve_sources_root(&project_root, None)?;
+    let paths = source_paths(&sources_root)?;
+    if paths.is_empty() {
+        anyhow::bail!("no ST sources found under {}", sources_root.display());
+    }
+
+    let mut db = trust_hir::Database::new();
+    let mut display_paths = Vec::with_capacity(paths.len());
+    for (index, path) in paths.iter().enumerate() {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read source '{}'", path.display()))?;
+        db.set_source_text(FileId(index as u32), text);
+        display_paths.push(
+            path.strip_prefix(&project_root)
+                .map_or_else(|_| path.clone(), Path::to_path_buf),
+        );
+    }
+
+    let graph = analyze_project_graph(
+        &db,
+        ProjectGraphOptions {
+            files: None,
+            entry_task: entry_task.as_deref(),
+        },
+    );
+    if let Some(task) = &entry_task {
+        if graph.entry_points.is_empty() {
+            anyhow::bail!("no program instance is attached to task '{task}'");
+        }
+    }
+
+    let include_calls = selection != GraphSelection::Dependencies;
+    let include_dependencies = selection != GraphSelection::Calls;
+    let rendered = match format {
+        GraphFormat::Dot => {
+            let mut text = String::new();
+            if include_calls {
+                text.push_str(&graph.calls.to_dot("calls"));
+            }
+            if include_dependencies {
+                text.push_str(&graph.dependencies.to_dot("dependencies"));
+            }
+            text
+        }
+        GraphFormat::Json => {
+            let location = |file_id: FileId, offset: u32| {
+                let text = db.source_text(file_id);
+                let line = text[..(offset as usize).min(text.len())]
+                    .matches('\n')
+                    .count()
+                    + 1;
+                format!(
+                    "{}:{line}",
+                    display_paths
+                        .get(file_id.0 as usize)
+                        .map_or_else(String::new, |path| path.display().to_string())
+                )
+            };
+            let graph_value = |graph: &Graph| {
+                json!({
+                    "nodes": graph
+                        .nodes
+                        .iter()
+                        .map(|node| {
+                            json!({
+                                "name": node.name,
+                                "kind": node.kind_label(),
+                                "location": location(node.file_id, node.range.start().into()),
+                            })
+                        })
+                        .collect::<Vec<_>>(),
+                    "edges": graph
+                        .edges
+                        .iter()
+                        .map(|edge| {
+                            json!({
+                                "from": graph.nodes[edge.from].name,
+                                "to": graph.nodes[edge.to].name,
+                                "kind": edge.kind.label(),
+                                "count": edge.count,
+                            })
+                        })
+                        .collect::<Vec<_>>(),
+                })
+            };
+            let mut value = json!({
+                "entry_task": entry_task,
+                "entry_points": graph
+                    .entry_points
+                    .iter()
+                    .map(|entry| {
+                        json!({
+                            "name": entry.name.as_str(),
+                            "task": entry.task.as_ref().map(|task| task.as_str()),
+                            "program": entry.program,
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            });
+            if include_calls {
+                value["call_graph"] = graph_value(&graph.calls);
+            }
+            if include_dependencies {
+                value["dependency_graph"] = graph_value(&graph.dependencies);
+            }
+            format!("{}\n", serde_json::to_string_pretty(&value)?)
+        }
+    };
+
+    match output {
+        Some(path) => {
+            if let Some(parent) = path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+            {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("failed to create '{}'", parent.display()))?;
+            }
+            std::fs::write(&path, rendered)
+                .with_context(|| format!("failed to write '{}'", path.display()))?;
+            println!(
+                "{}",
+                style::success(format!("Wrote graph to {}", path.display()))
+            );
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
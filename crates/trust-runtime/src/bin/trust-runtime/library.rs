@@ -0,0 +1,98 @@
+//! Precompiled library archive command handlers.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use serde_json::json;
+
+use trust_runtime::bundle::detect_bundle_path;
+use trust_runtime::stlib::{pack_library, LibraryArchive, LibraryManifest};
+
+use crate::cli::LibraryAction;
+use crate::style;
+
+pub fn run_library(action: LibraryAction) -> anyhow::Result<()> {
+    match action {
+        LibraryAction::Pack {
+            project,
+            sources,
+            output,
+            json,
+        } => run_pack(project, sources, output, json),
+        LibraryAction::Inspect {
+            archive,
+            interface,
+            json,
+        } => run_inspect(archive, interface, json),
+    }
+}
+
+fn run_pack(
+    project: Option<PathBuf>,
+    sources: Option<PathBuf>,
+    output: Option<PathBuf>,
+    json: bool,
+) -> anyhow::Result<()> {
+    let project_root = match project {
+        Some(path) => path,
+        None => match detect_bundle_path(None) {
+            Ok(path) => path,
+            Err(_) => std::env::current_dir().context("failed to resolve current directory")?,
+        },
+    };
+    let report = pack_library(&project_root, sources.as_deref(), output.as_deref())?;
+    if json {
+        let payload = json!({
+            "archive": report.archive_path.display().to_string(),
+            "manifest": report.manifest,
+            "source_count": report.sources.len(),
+        });
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(());
+    }
+    println!(
+        "{}",
+        style::success(format!("Wrote {}", report.archive_path.display()))
+    );
+    print_manifest(&report.manifest);
+    println!("Sources: {} file(s) (not included)", report.sources.len());
+    Ok(())
+}
+
+fn run_inspect(archive: PathBuf, interface: bool, json: bool) -> anyhow::Result<()> {
+    let archive = LibraryArchive::read(&archive)?;
+    if json {
+        let payload = json!({
+            "manifest": archive.manifest,
+            "interface": archive.interface,
+            "bytecode_bytes": archive.bytecode.len(),
+        });
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(());
+    }
+    print_manifest(&archive.manifest);
+    println!("Bytecode: {} byte(s)", archive.bytecode.len());
+    if interface {
+        println!();
+        print!("{}", archive.interface);
+    }
+    Ok(())
+}
+
+fn print_manifest(manifest: &LibraryManifest) {
+    println!(
+        "{}",
+        style::accent(format!(
+            "{} {}",
+            manifest.name,
+            manifest.version.as_deref().unwrap_or("(unversioned)")
+        ))
+    );
+    println!("POUs: {}", manifest.pous.len());
+    for pou in &manifest.pous {
+        println!(" - {} ({:?})", pou.name, pou.kind);
+    }
+    if !manifest.types.is_empty() {
+        println!("Types: {}", manifest.types.join(", "));
+    }
+}
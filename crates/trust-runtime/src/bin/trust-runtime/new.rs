@@ -0,0 +1,93 @@
+//! Project scaffolding from starter templates.
+
+use std::fs;
+use std::path::PathBuf;
+
+use trust_runtime::bundle_builder::build_program_stbc;
+use trust_runtime::bundle_template::{render_project_scaffold, ProjectTemplate};
+
+use crate::cli::ProjectTemplateArg;
+use crate::git::git_init;
+use crate::style;
+use crate::wizard::{default_resource_name, ensure_gitignore};
+
+const DEFAULT_CYCLE_MS: u64 = 100;
+
+pub fn run_new(
+    path: PathBuf,
+    template: ProjectTemplateArg,
+    force: bool,
+    no_git: bool,
+) -> anyhow::Result<()> {
+    let template = ProjectTemplate::from(template);
+    let existing = path.is_dir() && fs::read_dir(&path)?.next().is_some();
+    if existing && !force {
+        anyhow::bail!(
+            "{} is not empty (use --force to write the template files into it)",
+            path.display()
+        );
+    }
+    fs::create_dir_all(&path)?;
+    let root = path.canonicalize()?;
+    let project_name = root
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("trust-plc")
+        .to_string();
+    let resource_name = default_resource_name(&root);
+
+    let files = render_project_scaffold(template, &project_name, &resource_name, DEFAULT_CYCLE_MS)?;
+    for file in &files {
+        let target = root.join(file.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, &file.contents)?;
+    }
+    ensure_gitignore(&root)?;
+    if !no_git {
+        if let Err(err) = git_init(&root) {
+            eprintln!("{err}; continuing without a repository.");
+        }
+    }
+    let report = build_program_stbc(&root, None)?;
+
+    println!(
+        "{}",
+        style::success(format!(
+            "✓ Created {} project '{}' at {}",
+            template.as_str(),
+            project_name,
+            root.display()
+        ))
+    );
+    for file in &files {
+        println!("  {}", file.path);
+    }
+    println!(
+        "  {}",
+        report
+            .program_path
+            .strip_prefix(&root)
+            .unwrap_or(&report.program_path)
+            .display()
+    );
+    println!(
+        "{}",
+        style::accent(format!(
+            "Next: trust-runtime play --project {}",
+            path.display()
+        ))
+    );
+    Ok(())
+}
+
+impl From<ProjectTemplateArg> for ProjectTemplate {
+    fn from(value: ProjectTemplateArg) -> Self {
+        match value {
+            ProjectTemplateArg::Counter => Self::Counter,
+            ProjectTemplateArg::TrafficLight => Self::TrafficLight,
+            ProjectTemplateArg::ModbusIo => Self::ModbusIo,
+        }
+    }
+}
@@ -3,32 +3,37 @@
 use std::collections::VecDeque;
 use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use serde_json::json;
 use smol_str::SmolStr;
 use trust_runtime::bundle::detect_bundle_path;
 use trust_runtime::bundle_builder::resolve_sources_root;
 use trust_runtime::bytecode::BytecodeModule;
-use trust_runtime::config::{RuntimeBundle, WebAuthMode, WebConfig};
+use trust_runtime::config::{RuntimeBundle, ServerLimits, WebAuthMode, WebConfig};
 use trust_runtime::control::{
-    spawn_hmi_descriptor_watcher, ControlEndpoint, ControlServer, ControlState,
-    HmiRuntimeDescriptor, SourceFile, SourceRegistry,
+    install_crash_handler, spawn_hmi_descriptor_watcher, spawn_source_watcher, ControlEndpoint,
+    ControlServer, ControlSessions, ControlState, HmiRuntimeDescriptor, SourceFile, SourceRegistry,
+    SourceWatchEvent,
 };
 use trust_runtime::discovery::{start_discovery, DiscoveryState};
+use trust_runtime::event_store::EventStore;
 use trust_runtime::harness::CompileSession;
 use trust_runtime::historian::HistorianService;
 use trust_runtime::hmi::{HmiScaffoldMode, HmiSourceRef};
-use trust_runtime::io::IoDriverRegistry;
-use trust_runtime::mesh::start_mesh;
+use trust_runtime::logging::{LogLevel, LogRecord, LogSinks};
+use trust_runtime::mesh::{start_mesh, MeshLinks};
 use trust_runtime::metrics::RuntimeMetrics;
+use trust_runtime::notify::NotificationService;
 use trust_runtime::opcua::{start_wire_server, OpcUaWireServer};
+use trust_runtime::redundancy::RedundancyPair;
 use trust_runtime::retain::FileRetainStore;
 use trust_runtime::scheduler::{ResourceCommand, ResourceRunner, StartGate, StdClock};
 use trust_runtime::security::load_tls_materials;
+use trust_runtime::self_extracting::SelfExtractingImage;
 use trust_runtime::settings::{
-    BaseSettings, DiscoverySettings, MeshSettings, OpcUaSettings, RuntimeSettings,
-    SimulationSettings, WebSettings,
+    BaseSettings, DiscoverySettings, MeshSettings, OpcUaSettings, RealtimeSettings,
+    RuntimeSettings, SimulationSettings, WebSettings,
 };
 use trust_runtime::value::Duration;
 use trust_runtime::web::pairing::PairingStore;
@@ -134,6 +139,7 @@ pub fn run_play(
         beginner,
         simulation,
         time_scale,
+        false,
     )
 }
 
@@ -146,7 +152,7 @@ pub fn run_validate(bundle: PathBuf, ci: bool) -> anyhow::Result<()> {
     {
         anyhow::bail!("tcp control endpoint requires runtime.control.auth_token");
     }
-    let registry = IoDriverRegistry::default_registry();
+    let registry = bundle.io_driver_registry();
     for driver in &bundle.io.drivers {
         registry
             .validate(driver.name.as_str(), &driver.params)
@@ -183,6 +189,308 @@ pub fn run_validate(bundle: PathBuf, ci: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Outcome of one `--check` step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckStatus {
+    Ok,
+    Warning,
+    Failed,
+    Skipped,
+}
+
+impl CheckStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Warning => "warning",
+            Self::Failed => "failed",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CheckStep {
+    name: &'static str,
+    status: CheckStatus,
+    details: Vec<String>,
+}
+
+impl CheckStep {
+    fn new(name: &'static str, status: CheckStatus, details: Vec<String>) -> Self {
+        Self {
+            name,
+            status,
+            details,
+        }
+    }
+
+    fn from_result(name: &'static str, result: anyhow::Result<Vec<String>>) -> Self {
+        match result {
+            Ok(details) => Self::new(name, CheckStatus::Ok, details),
+            Err(err) => Self::new(name, CheckStatus::Failed, vec![format!("{err:#}")]),
+        }
+    }
+}
+
+/// Dry-run a project: load and validate it, then execute one cycle against simulated I/O.
+///
+/// No I/O driver is opened, retained values are only read, and file function blocks work
+/// in a scratch directory, so the check never touches outputs.
+pub fn run_check(project: Option<PathBuf>, ci: bool) -> anyhow::Result<()> {
+    let project = detect_bundle_path(project)?;
+    let bundle = RuntimeBundle::load(&project)?;
+    let mut steps = vec![CheckStep::new(
+        "bundle",
+        CheckStatus::Ok,
+        vec![format!(
+            "resource {} (bundle version {})",
+            bundle.runtime.resource_name, bundle.runtime.bundle_version
+        )],
+    )];
+    steps.push(CheckStep::from_result(
+        "config",
+        check_runtime_config(&bundle),
+    ));
+
+    let registry = bundle.io_driver_registry();
+    let mut driver_details = Vec::new();
+    let mut driver_failed = false;
+    for driver in &bundle.io.drivers {
+        match registry.validate(driver.name.as_str(), &driver.params) {
+            Ok(()) => driver_details.push(format!("{} resolved", driver.name)),
+            Err(err) => {
+                driver_failed = true;
+                driver_details.push(format!("{}: {err}", driver.name));
+            }
+        }
+    }
+    if driver_details.is_empty() {
+        driver_details.push("no drivers configured".to_string());
+    }
+    steps.push(CheckStep::new(
+        "io_drivers",
+        if driver_failed {
+            CheckStatus::Failed
+        } else {
+            CheckStatus::Ok
+        },
+        driver_details,
+    ));
+
+    let scratch = std::env::temp_dir().join(format!("trust-runtime-check-{}", std::process::id()));
+    let runtime = check_program(&bundle, &scratch);
+    let runtime = match runtime {
+        Ok((runtime, details)) => {
+            steps.push(CheckStep::new("program", CheckStatus::Ok, details));
+            Some(runtime)
+        }
+        Err(err) => {
+            steps.push(CheckStep::new(
+                "program",
+                CheckStatus::Failed,
+                vec![format!("{err:#}")],
+            ));
+            None
+        }
+    };
+    match runtime {
+        Some(mut runtime) => {
+            steps.push(check_retain_layout_step(&bundle, &runtime));
+            steps.push(CheckStep::from_result(
+                "cycle",
+                check_cycle(&bundle, &mut runtime),
+            ));
+        }
+        None => {
+            for name in ["retain", "cycle"] {
+                steps.push(CheckStep::new(
+                    name,
+                    CheckStatus::Skipped,
+                    vec!["program did not load".to_string()],
+                ));
+            }
+        }
+    }
+    let _ = std::fs::remove_dir_all(&scratch);
+
+    let failed = steps
+        .iter()
+        .filter(|step| step.status == CheckStatus::Failed)
+        .count();
+    if ci {
+        let payload = json!({
+            "version": 1,
+            "command": "check",
+            "status": if failed == 0 { "ok" } else { "failed" },
+            "project": bundle.root.display().to_string(),
+            "resource": bundle.runtime.resource_name.to_string(),
+            "steps": steps
+                .iter()
+                .map(|step| json!({
+                    "name": step.name,
+                    "status": step.status.as_str(),
+                    "details": step.details,
+                }))
+                .collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+    } else {
+        for step in &steps {
+            let label = format!("{:<10} {}", step.name, step.status.as_str());
+            let label = match step.status {
+                CheckStatus::Ok => style::success(label),
+                CheckStatus::Warning | CheckStatus::Skipped => style::warning(label),
+                CheckStatus::Failed => style::error(label),
+            };
+            println!("{label}");
+            for detail in &step.details {
+                println!("  {detail}");
+            }
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!("check failed: {failed} of {} steps failed", steps.len());
+    }
+    if !ci {
+        println!("{}", style::success("Project ok"));
+    }
+    Ok(())
+}
+
+fn check_runtime_config(bundle: &RuntimeBundle) -> anyhow::Result<Vec<String>> {
+    if bundle.runtime.bundle_version != 1 {
+        anyhow::bail!(
+            "unsupported bundle version {}",
+            bundle.runtime.bundle_version
+        );
+    }
+    let tls = load_tls_materials(&bundle.runtime.tls, Some(bundle.root.as_path()))?;
+    let control_endpoint = ControlEndpoint::parse(bundle.runtime.control_endpoint.as_str())?;
+    if matches!(control_endpoint, ControlEndpoint::Tcp(_))
+        && bundle.runtime.control_auth_token.is_none()
+    {
+        anyhow::bail!("tcp control endpoint requires runtime.control.auth_token");
+    }
+    Ok(vec![
+        format!("control endpoint {}", bundle.runtime.control_endpoint),
+        format!(
+            "cycle interval {} ms",
+            bundle.runtime.cycle_interval.as_millis()
+        ),
+        format!("tls {}", if tls.is_some() { "enabled" } else { "disabled" }),
+    ])
+}
+
+/// Compile the sources and apply the bundle like `run_runtime` does, minus I/O drivers
+/// and the retain store.
+fn check_program(bundle: &RuntimeBundle, scratch: &Path) -> anyhow::Result<(Runtime, Vec<String>)> {
+    let sources_path = resolve_sources_root(bundle.root.as_path(), None)?;
+    let sources = load_sources(&sources_path)?;
+    let mut runtime = compile_runtime(&sources)?;
+    runtime.set_watchdog_policy(bundle.runtime.watchdog);
+    runtime.set_fault_policy(bundle.runtime.fault_policy);
+    runtime.set_task_catch_up(bundle.runtime.task_catch_up);
+    runtime.set_bounds_settings(bundle.runtime.bounds.clone());
+    runtime.set_overflow_policy(bundle.runtime.overflow);
+    runtime.set_subrange_policy(bundle.runtime.subrange);
+    runtime.set_retention_rules(bundle.runtime.retention);
+    std::fs::create_dir_all(scratch)?;
+    runtime.set_worker_data_dir(Some(scratch.to_path_buf()));
+    runtime.set_jit_settings(bundle.runtime.jit);
+    runtime.set_io_safe_state(bundle.io.safe_state.clone());
+    runtime.set_output_safe_state(bundle.runtime.safety_state.clone());
+    runtime
+        .apply_bytecode_bytes(&bundle.bytecode, Some(&bundle.runtime.resource_name))
+        .map_err(|err| anyhow::anyhow!("failed to apply bytecode metadata: {err}"))?;
+    runtime.bind_io_symbols(&bundle.io.map)?;
+    runtime.validate_io_map()?;
+    runtime.set_shutdown_hook(bundle.runtime.shutdown.clone())?;
+    runtime.restart(RestartMode::Cold)?;
+    let details = vec![
+        format!("{} source file(s) compiled", sources.files().len()),
+        format!("{} I/O binding(s) validated", bundle.io.map.symbols.len()),
+    ];
+    Ok((runtime, details))
+}
+
+fn check_retain_layout_step(bundle: &RuntimeBundle, runtime: &Runtime) -> CheckStep {
+    use trust_runtime::retain::{check_retain_layout, RetainStore};
+
+    if bundle.runtime.retain_mode != trust_runtime::watchdog::RetainMode::File {
+        return CheckStep::new(
+            "retain",
+            CheckStatus::Skipped,
+            vec!["retain disabled".into()],
+        );
+    }
+    let Some(path) = bundle.runtime.retain_path.as_ref() else {
+        return CheckStep::new(
+            "retain",
+            CheckStatus::Skipped,
+            vec!["no retain path".into()],
+        );
+    };
+    let path = if path.is_relative() {
+        bundle.root.join(path)
+    } else {
+        path.clone()
+    };
+    if !path.exists() {
+        return CheckStep::new(
+            "retain",
+            CheckStatus::Ok,
+            vec![format!("{} not written yet", path.display())],
+        );
+    }
+    let stored = match FileRetainStore::new(&path).load() {
+        Ok(stored) => stored,
+        Err(err) => {
+            return CheckStep::new(
+                "retain",
+                CheckStatus::Failed,
+                vec![format!("{}: {err}", path.display())],
+            )
+        }
+    };
+    let issues = check_retain_layout(&runtime.retain_snapshot(), &stored);
+    let status = if issues.iter().any(|issue| issue.is_error()) {
+        CheckStatus::Failed
+    } else if issues.is_empty() {
+        CheckStatus::Ok
+    } else {
+        CheckStatus::Warning
+    };
+    let mut details = vec![format!(
+        "{} value(s) in {}",
+        stored.values().len(),
+        path.display()
+    )];
+    details.extend(issues.iter().map(ToString::to_string));
+    CheckStep::new("retain", status, details)
+}
+
+fn check_cycle(bundle: &RuntimeBundle, runtime: &mut Runtime) -> anyhow::Result<Vec<String>> {
+    let mut simulation_config = bundle.simulation.clone().unwrap_or_default();
+    simulation_config.enabled = true;
+    let mut simulation = trust_runtime::simulation::SimulationController::new(simulation_config);
+    let now = runtime.current_time();
+    let started = std::time::Instant::now();
+    simulation.apply_pre_cycle(now, runtime)?;
+    runtime.execute_cycle()?;
+    simulation.apply_post_cycle(now, runtime)?;
+    let elapsed = started.elapsed();
+    let mut details = vec![format!(
+        "one cycle in {:.3} ms against simulated I/O",
+        elapsed.as_secs_f64() * 1000.0
+    )];
+    let budget = bundle.runtime.cycle_interval.as_millis();
+    if budget > 0 && elapsed.as_millis() > u128::try_from(budget).unwrap_or(u128::MAX) {
+        details.push(format!("cycle took longer than the {budget} ms interval"));
+    }
+    Ok(details)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn run_runtime(
     project: Option<PathBuf>,
@@ -195,6 +503,7 @@ pub fn run_runtime(
     beginner: bool,
     simulation: bool,
     time_scale: u32,
+    watch: bool,
 ) -> anyhow::Result<()> {
     let ide_shell_mode = project.is_none() && config.is_none();
     let restart_mode = match restart.to_ascii_lowercase().as_str() {
@@ -205,23 +514,14 @@ pub fn run_runtime(
         ),
     };
 
+    if watch && project.is_none() {
+        anyhow::bail!("--watch requires --project");
+    }
     let (bundle, mut runtime, sources) = if let Some(project_path) = project {
         let bundle = RuntimeBundle::load(&project_path)?;
         let sources_path = resolve_sources_root(bundle.root.as_path(), None)?;
         let sources = load_sources(&sources_path)?;
-        let session = CompileSession::from_sources(
-            sources
-                .files()
-                .iter()
-                .map(|file| {
-                    trust_runtime::harness::SourceFile::with_path(
-                        file.path.to_string_lossy().as_ref(),
-                        file.text.clone(),
-                    )
-                })
-                .collect(),
-        );
-        let runtime = session.build_runtime()?;
+        let runtime = compile_runtime(&sources)?;
         (Some(bundle), runtime, sources)
     } else if let Some(config_path) = config {
         let runtime_root = runtime_root.unwrap_or_else(|| {
@@ -231,19 +531,7 @@ pub fn run_runtime(
                 .unwrap_or_else(|| PathBuf::from("."))
         });
         let sources = load_sources(&runtime_root)?;
-        let session = CompileSession::from_sources(
-            sources
-                .files()
-                .iter()
-                .map(|file| {
-                    trust_runtime::harness::SourceFile::with_path(
-                        file.path.to_string_lossy().as_ref(),
-                        file.text.clone(),
-                    )
-                })
-                .collect(),
-        );
-        let runtime = session.build_runtime()?;
+        let runtime = compile_runtime(&sources)?;
         (None, runtime, sources)
     } else {
         let runtime_root = runtime_root
@@ -271,7 +559,8 @@ pub fn run_runtime(
         .as_ref()
         .and_then(|bundle| bundle.simulation.clone())
         .unwrap_or_default();
-    if simulation || time_scale > 1 {
+    // Watch mode reloads on every save, so it never drives real I/O.
+    if simulation || time_scale > 1 || watch {
         simulation_config.enabled = true;
     }
     if time_scale > 1 {
@@ -288,8 +577,12 @@ pub fn run_runtime(
         .then(|| trust_runtime::simulation::SimulationController::new(simulation_config));
 
     let debug = runtime.enable_debug();
+    if let Some(bundle) = &bundle {
+        debug.set_history_depth(bundle.runtime.control_history_depth);
+    }
     let metrics = Arc::new(Mutex::new(RuntimeMetrics::new()));
     runtime.set_metrics_sink(metrics.clone());
+    let time_base = runtime.time_base();
     let io_health = Arc::new(Mutex::new(Vec::new()));
     runtime.set_io_health_sink(Some(io_health.clone()));
     let io_snapshot = Arc::new(Mutex::new(None));
@@ -306,47 +599,7 @@ pub fn run_runtime(
         });
     }
     if let Some(bundle) = &bundle {
-        if bundle.runtime.bundle_version != 1 {
-            anyhow::bail!(
-                "unsupported bundle version {}",
-                bundle.runtime.bundle_version
-            );
-        }
-        runtime.set_watchdog_policy(bundle.runtime.watchdog);
-        runtime.set_fault_policy(bundle.runtime.fault_policy);
-        runtime.set_io_safe_state(bundle.io.safe_state.clone());
-        let registry = IoDriverRegistry::default_registry();
-        for driver in &bundle.io.drivers {
-            if let Some(spec) = registry
-                .build(driver.name.as_str(), &driver.params)
-                .map_err(anyhow::Error::from)?
-            {
-                runtime.add_io_driver(spec.name, spec.driver);
-            }
-        }
-        match bundle.runtime.retain_mode {
-            trust_runtime::watchdog::RetainMode::File => {
-                let store = bundle.runtime.retain_path.as_ref().map(|path| {
-                    let path = if path.is_relative() {
-                        bundle.root.join(path)
-                    } else {
-                        path.clone()
-                    };
-                    Box::new(FileRetainStore::new(path)) as _
-                });
-                runtime.set_retain_store(store, Some(bundle.runtime.retain_save_interval));
-            }
-            trust_runtime::watchdog::RetainMode::None => {
-                runtime.set_retain_store(None, None);
-            }
-        }
-        if let Err(err) =
-            runtime.apply_bytecode_bytes(&bundle.bytecode, Some(&bundle.runtime.resource_name))
-        {
-            anyhow::bail!(
-                "failed to apply bytecode metadata: {err} (project folder may require sources)"
-            );
-        }
+        apply_bundle_config(&mut runtime, bundle)?;
     }
 
     runtime.restart(restart_mode)?;
@@ -356,21 +609,57 @@ pub fn run_runtime(
         .as_ref()
         .and_then(|bundle| auto_scaffold_hmi_update(bundle, &runtime, &sources));
 
-    let logger = RuntimeLogger::new(match &bundle {
-        Some(bundle) => LogLevel::parse(bundle.runtime.log_level.as_str()),
-        None => LogLevel::Info,
+    let logger = match &bundle {
+        Some(bundle) => RuntimeLogger::new(
+            LogLevel::parse(bundle.runtime.log_level.as_str()),
+            LogSinks::open(
+                &bundle.runtime.log_sinks,
+                bundle.runtime.resource_name.as_str(),
+            )?,
+        ),
+        None => RuntimeLogger::new(LogLevel::Info, LogSinks::stdout_only()),
+    };
+    let notifier = bundle.as_ref().and_then(|bundle| {
+        NotificationService::start(
+            &bundle.runtime.notify,
+            bundle.runtime.resource_name.as_str(),
+        )
     });
+    if let Some(notifier) = notifier.as_ref() {
+        notifier.watch_io_health(io_health.clone());
+    }
 
     let metadata = Arc::new(Mutex::new(runtime.metadata_snapshot()));
     let events = Arc::new(Mutex::new(VecDeque::new()));
+    let event_store = match &bundle {
+        Some(bundle) if bundle.runtime.events.enabled => Some(Arc::new(EventStore::open(
+            bundle.runtime.events.clone(),
+            Some(bundle.root.as_path()),
+        )?)),
+        _ => None,
+    };
     {
         let events = events.clone();
+        let event_store = event_store.clone();
         let (event_tx, event_rx) = std::sync::mpsc::channel();
         debug.set_runtime_sender(event_tx);
         let event_logger = logger.clone();
+        let event_notifier = notifier.clone();
         std::thread::spawn(move || {
             for event in event_rx {
                 log_runtime_event(&event_logger, &event);
+                if let Some(notifier) = event_notifier.as_ref() {
+                    notifier.observe_runtime_event(&event);
+                }
+                if let Some(store) = event_store.as_ref() {
+                    if let Err(err) = store.append(&event) {
+                        event_logger.log(
+                            LogLevel::Warn,
+                            "event_store_write_failed",
+                            json!({ "error": err.to_string() }),
+                        );
+                    }
+                }
                 if let Ok(mut guard) = events.lock() {
                     guard.push_back(event);
                     while guard.len() > 200 {
@@ -415,8 +704,18 @@ pub fn run_runtime(
     if let Some(simulation) = simulation_controller {
         runner = runner.with_simulation(simulation);
     }
+    if let Some(realtime) = bundle
+        .as_ref()
+        .map(|bundle| &bundle.runtime.realtime)
+        .filter(|realtime| realtime.is_enabled())
+    {
+        runner = runner.with_realtime(realtime.clone());
+    }
     let mut handle = runner.spawn("trust-runtime")?;
     let control = handle.control();
+    for warning in handle.realtime_warnings() {
+        eprintln!("{}", style::warning(format!("Warning: realtime {warning}")));
+    }
 
     let mut settings = if let Some(bundle) = &bundle {
         RuntimeSettings::new(
@@ -516,6 +815,16 @@ pub fn run_runtime(
             allow_anonymous: bundle.runtime.opcua.security.allow_anonymous,
             username_set: bundle.runtime.opcua.username.is_some(),
         };
+        settings.realtime = RealtimeSettings {
+            priority: bundle.runtime.realtime.priority,
+            cpu_affinity: bundle.runtime.realtime.cpu_affinity.clone(),
+            lock_memory: bundle.runtime.realtime.lock_memory,
+            warnings: handle.realtime_warnings().to_vec(),
+        };
+        settings.pause_policy = Some(bundle.runtime.control_pause);
+        settings.legacy_errors = bundle.runtime.control_legacy_errors;
+        settings.safety_state = bundle.runtime.safety_state.clone();
+        settings.io_map = bundle.io.map.clone();
     }
     let auth_token_value = bundle
         .as_ref()
@@ -534,6 +843,7 @@ pub fn run_runtime(
             listen: settings.web.listen.clone(),
             auth,
             tls: settings.web.tls,
+            limits: ServerLimits::default(),
         }
     };
     let auth_token = Arc::new(Mutex::new(
@@ -550,7 +860,9 @@ pub fn run_runtime(
                 bundle.runtime.observability.clone(),
                 Some(bundle.root.as_path()),
             )?;
-            service.clone().start_sampler(debug.clone());
+            service
+                .clone()
+                .start_sampler(debug.clone(), time_base.clone());
             Some(service)
         } else {
             None
@@ -570,6 +882,21 @@ pub fn run_runtime(
         bundle.as_ref().map(|bundle| bundle.root.as_path()),
         &sources,
     )));
+    let settings = Arc::new(Mutex::new(settings));
+    logger.follow_settings(settings.clone());
+    let mesh_links = bundle
+        .as_ref()
+        .filter(|bundle| bundle.runtime.mesh.enabled)
+        .map(|bundle| Arc::new(MeshLinks::new(&bundle.runtime.mesh)));
+    let redundancy = bundle
+        .as_ref()
+        .filter(|bundle| bundle.runtime.redundancy.enabled)
+        .map(|bundle| {
+            Arc::new(RedundancyPair::new(
+                &bundle.runtime.redundancy,
+                bundle.runtime.resource_name.clone(),
+            ))
+        });
     let state = Arc::new(ControlState {
         debug: debug.clone(),
         resource: control.clone(),
@@ -588,7 +915,7 @@ pub fn run_runtime(
         audit_tx: Some(audit_tx),
         metrics: metrics.clone(),
         events: events.clone(),
-        settings: Arc::new(Mutex::new(settings)),
+        settings,
         project_root: bundle.as_ref().map(|bundle| bundle.root.clone()),
         resource_name: bundle
             .as_ref()
@@ -602,12 +929,33 @@ pub fn run_runtime(
                 .unwrap_or(true),
         )),
         debug_variables: Arc::new(Mutex::new(trust_runtime::debug::DebugVariableHandles::new())),
+        sessions: Arc::new(ControlSessions::default()),
         hmi_live: Arc::new(Mutex::new(trust_runtime::hmi::HmiLiveState::default())),
         hmi_descriptor,
         historian: historian.clone(),
+        event_store: event_store.clone(),
         pairing: pairing.clone(),
+        mesh: mesh_links.clone(),
+        redundancy: redundancy.clone(),
     });
     spawn_hmi_descriptor_watcher(state.clone());
+    {
+        let crash_logger = logger.clone();
+        let diagnostics_dir = bundle
+            .as_ref()
+            .map_or_else(|| PathBuf::from("."), |bundle| bundle.root.clone())
+            .join("diagnostics");
+        install_crash_handler(state.clone(), diagnostics_dir, move |path| {
+            crash_logger.log(
+                LogLevel::Error,
+                "runtime_crash",
+                json!({
+                    "event_id": "TRUST-RT-CRASH-001",
+                    "diagnostics": path.display().to_string(),
+                }),
+            );
+        });
+    }
 
     let mut opcua_server: Option<OpcUaWireServer> = None;
     if let Some(bundle) = &bundle {
@@ -633,7 +981,12 @@ pub fn run_runtime(
         )?;
     }
 
-    let _server = ControlServer::start(control_endpoint.clone(), state.clone())?;
+    let control_limits = bundle
+        .as_ref()
+        .map(|bundle| bundle.runtime.control_limits)
+        .unwrap_or_default();
+    let _server =
+        ControlServer::start_with_limits(control_endpoint.clone(), state.clone(), control_limits)?;
     let _discovery_handle = if let Some(bundle) = &bundle {
         if bundle.runtime.discovery.enabled {
             let web_listen = bundle.runtime.web.listen.as_str();
@@ -643,7 +996,7 @@ pub fn run_runtime(
                 &bundle.runtime.resource_name,
                 &control_endpoint,
                 Some(web_listen),
-                Some(mesh_listen),
+                bundle.runtime.mesh.enabled.then_some(mesh_listen),
             )?;
             Some(handle)
         } else {
@@ -668,16 +1021,17 @@ pub fn run_runtime(
     } else {
         None
     };
-    let _mesh = if let Some(bundle) = &bundle {
-        start_mesh(
+    let _mesh = match (&bundle, mesh_links) {
+        (Some(bundle), Some(links)) => start_mesh(
             &bundle.runtime.mesh,
             bundle.runtime.resource_name.clone(),
             control.clone(),
             Some(discovery_state.clone()),
             tls_materials.clone(),
-        )?
-    } else {
-        None
+            links,
+            redundancy,
+        )?,
+        _ => None,
     };
     start_gate.open();
 
@@ -694,6 +1048,23 @@ pub fn run_runtime(
         );
     }
 
+    if let (true, Some(bundle)) = (watch, &bundle) {
+        let sources_root = resolve_sources_root(bundle.root.as_path(), None)?;
+        spawn_source_watcher(
+            state.clone(),
+            bundle.root.clone(),
+            sources_root.clone(),
+            print_watch_event,
+        )?;
+        println!(
+            "{}",
+            style::accent(format!(
+                "Watching {} for changes (simulation mode)",
+                sources_root.display()
+            ))
+        );
+    }
+
     let wants_console = match console {
         ConsoleMode::Auto => std::io::stdin().is_terminal() && std::io::stdout().is_terminal(),
         ConsoleMode::Enabled => true,
@@ -757,6 +1128,7 @@ pub fn run_runtime(
                 "watchdog_timeout_ms": bundle.runtime.watchdog.timeout.as_millis(),
                 "watchdog_action": format!("{:?}", bundle.runtime.watchdog.action),
                 "fault_policy": format!("{:?}", bundle.runtime.fault_policy),
+                "task_catch_up": bundle.runtime.task_catch_up.as_str(),
                 "control_endpoint": format_endpoint(&control_endpoint),
                 "control_auth_token_set": bundle.runtime.control_auth_token.is_some(),
                 "control_auth_token_length": bundle.runtime.control_auth_token.as_ref().map(|t| t.len()),
@@ -798,6 +1170,177 @@ pub fn run_runtime(
     Ok(())
 }
 
+/// Run the project embedded in this executable by `trust-runtime build --self-extracting`.
+///
+/// The image is extracted next to the executable (`<name>.runtime/`, or
+/// `TRUST_RUNTIME_ROOT`) so retain files and logs have a home. Images built
+/// without `--with-control` skip the control server and all network services.
+pub fn run_embedded(image: SelfExtractingImage, executable: &Path) -> anyhow::Result<()> {
+    let root = std::env::var_os("TRUST_RUNTIME_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            let name = executable
+                .file_stem()
+                .map_or_else(|| "trust".into(), |stem| stem.to_string_lossy());
+            executable
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .join(format!("{name}.runtime"))
+        });
+    image.extract(&root)?;
+    if image.control {
+        return run_runtime(
+            Some(root),
+            None,
+            None,
+            "cold".to_string(),
+            false,
+            false,
+            ConsoleMode::Disabled,
+            false,
+            false,
+            1,
+            false,
+        );
+    }
+    run_headless(&root)
+}
+
+/// Cycle-only runtime: no control server, web UI, discovery, mesh, or OPC UA.
+fn run_headless(root: &Path) -> anyhow::Result<()> {
+    let bundle = RuntimeBundle::load(root)?;
+    let sources = load_sources(&resolve_sources_root(bundle.root.as_path(), None)?)?;
+    let mut runtime = compile_runtime(&sources)?;
+    apply_bundle_config(&mut runtime, &bundle)?;
+    runtime.restart(RestartMode::Cold)?;
+    runtime.load_retain_store()?;
+
+    let logger = RuntimeLogger::new(
+        LogLevel::parse(bundle.runtime.log_level.as_str()),
+        LogSinks::open(
+            &bundle.runtime.log_sinks,
+            bundle.runtime.resource_name.as_str(),
+        )?,
+    );
+    logger.log(
+        LogLevel::Info,
+        "runtime_start",
+        json!({
+            "project": bundle.root.display().to_string(),
+            "resource": bundle.runtime.resource_name.to_string(),
+            "mode": "headless",
+            "cycle_interval_ms": bundle.runtime.cycle_interval.as_millis(),
+        }),
+    );
+    let mut runner = ResourceRunner::new(runtime, StdClock::new(), bundle.runtime.cycle_interval);
+    if bundle.runtime.realtime.is_enabled() {
+        runner = runner.with_realtime(bundle.runtime.realtime.clone());
+    }
+    let mut handle = runner.spawn("trust-runtime")?;
+    for warning in handle.realtime_warnings() {
+        logger.log(
+            LogLevel::Warn,
+            "realtime_degraded",
+            json!({ "warning": warning.as_str() }),
+        );
+    }
+    handle
+        .join()
+        .map_err(|_| anyhow::anyhow!("runtime thread panicked"))?;
+    logger.log(
+        LogLevel::Debug,
+        "runtime_exit",
+        json!({ "status": "stopped" }),
+    );
+    Ok(())
+}
+
+fn compile_runtime(sources: &SourceRegistry) -> anyhow::Result<Runtime> {
+    let session = CompileSession::from_sources(
+        sources
+            .files()
+            .iter()
+            .map(|file| {
+                trust_runtime::harness::SourceFile::with_path(
+                    file.path.to_string_lossy().as_ref(),
+                    file.text.clone(),
+                )
+            })
+            .collect(),
+    );
+    Ok(session.build_runtime()?)
+}
+
+/// Apply `runtime.toml`/`io.toml` settings and bytecode metadata.
+fn apply_bundle_config(runtime: &mut Runtime, bundle: &RuntimeBundle) -> anyhow::Result<()> {
+    if bundle.runtime.bundle_version != 1 {
+        anyhow::bail!(
+            "unsupported bundle version {}",
+            bundle.runtime.bundle_version
+        );
+    }
+    runtime.set_watchdog_policy(bundle.runtime.watchdog);
+
+    runtime.set_fault_policy(bundle.runtime.fault_policy);
+    runtime.set_task_catch_up(bundle.runtime.task_catch_up);
+    runtime.set_bounds_settings(bundle.runtime.bounds.clone());
+    runtime.set_overflow_policy(bundle.runtime.overflow);
+    runtime.set_subrange_policy(bundle.runtime.subrange);
+    // File FBs only see the bundle's data directory.
+    runtime.set_worker_data_dir(Some(bundle.root.join(&bundle.runtime.files.data_dir)));
+    if bundle.runtime.jit.enabled && !trust_runtime::eval::vm::JIT_AVAILABLE {
+        eprintln!(
+            "{}",
+            style::warning(
+                "Warning: runtime.jit.enabled is set but this build has no `jit` feature; programs run on the bytecode VM"
+            )
+        );
+    }
+    runtime.set_jit_settings(bundle.runtime.jit);
+    runtime.set_io_safe_state(bundle.io.safe_state.clone());
+    runtime.set_output_safe_state(bundle.runtime.safety_state.clone());
+    // A redundant runtime stays standby until it has agreed roles with its partner.
+    runtime.set_standby(bundle.runtime.redundancy.enabled);
+    let registry = bundle.io_driver_registry();
+    for driver in &bundle.io.drivers {
+        if let Some(spec) = registry
+            .build(driver.name.as_str(), &driver.params)
+            .map_err(anyhow::Error::from)?
+        {
+            runtime.add_io_driver(spec.name, spec.driver);
+        }
+    }
+    runtime.set_retention_rules(bundle.runtime.retention);
+    match bundle.runtime.retain_mode {
+        trust_runtime::watchdog::RetainMode::File => {
+            let store = bundle.runtime.retain_path.as_ref().map(|path| {
+                let path = if path.is_relative() {
+                    bundle.root.join(path)
+                } else {
+                    path.clone()
+                };
+                Box::new(FileRetainStore::new(path)) as _
+            });
+            runtime.set_retain_store(store, Some(bundle.runtime.retain_save_interval));
+        }
+        trust_runtime::watchdog::RetainMode::None => {
+            runtime.set_retain_store(None, None);
+        }
+    }
+    if let Err(err) =
+        runtime.apply_bytecode_bytes(&bundle.bytecode, Some(&bundle.runtime.resource_name))
+    {
+        anyhow::bail!(
+            "failed to apply bytecode metadata: {err} (project folder may require sources)"
+        );
+    }
+    runtime.bind_io_symbols(&bundle.io.map)?;
+    // Fail before the first cycle instead of letting two writers share output bits.
+    runtime.validate_io_map()?;
+    runtime.set_shutdown_hook(bundle.runtime.shutdown.clone())?;
+    Ok(())
+}
+
 fn print_trust_banner(
     bundle: Option<&RuntimeBundle>,
     web_url: Option<&str>,
@@ -900,6 +1443,28 @@ fn auto_scaffold_hmi_update(
     }
 }
 
+fn print_watch_event(event: SourceWatchEvent) {
+    match event {
+        SourceWatchEvent::Reloaded { sources, elapsed } => println!(
+            "{}",
+            style::success(format!(
+                "✓ Rebuilt {sources} source file(s) and reloaded in {} ms",
+                elapsed.as_millis()
+            ))
+        ),
+        SourceWatchEvent::BuildFailed(error) => {
+            eprintln!(
+                "{}",
+                style::error("✗ Build failed; previous program keeps running")
+            );
+            eprintln!("{error}");
+        }
+        SourceWatchEvent::ReloadFailed(error) => {
+            eprintln!("{}", style::error(format!("✗ Reload failed: {error}")));
+        }
+    }
+}
+
 fn format_endpoint(endpoint: &ControlEndpoint) -> String {
     match endpoint {
         ControlEndpoint::Tcp(addr) => format!("tcp://{addr}"),
@@ -994,6 +1559,7 @@ fn print_startup_summary(
         bundle.runtime.watchdog.action
     );
     println!("fault policy: {:?}", bundle.runtime.fault_policy);
+    println!("task catch-up: {}", bundle.runtime.task_catch_up.as_str());
     println!("control endpoint: {}", format_endpoint(endpoint));
     println!(
         "web ui: {} ({})",
@@ -1022,6 +1588,13 @@ fn print_startup_summary(
         },
         bundle.runtime.mesh.listen
     );
+    if bundle.runtime.redundancy.enabled {
+        println!(
+            "redundancy: {} (partner {})",
+            bundle.runtime.redundancy.role.as_str(),
+            bundle.runtime.redundancy.partner
+        );
+    }
     println!(
         "opc ua: {} ({})",
         if bundle.runtime.opcua.enabled {
@@ -1091,66 +1664,55 @@ mod tests {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum LogLevel {
-    Error,
-    Warn,
-    Info,
-    Debug,
-    Trace,
+#[derive(Clone)]
+struct RuntimeLogger {
+    level: LogLevel,
+    /// Live settings once the control server is up, so `config.set log.level`
+    /// applies to the next record.
+    settings: Arc<OnceLock<Arc<Mutex<RuntimeSettings>>>>,
+    sinks: Arc<LogSinks>,
 }
 
-impl LogLevel {
-    fn parse(text: &str) -> Self {
-        match text.trim().to_ascii_lowercase().as_str() {
-            "error" => Self::Error,
-            "warn" | "warning" => Self::Warn,
-            "debug" => Self::Debug,
-            "trace" => Self::Trace,
-            _ => Self::Info,
+impl RuntimeLogger {
+    fn new(level: LogLevel, sinks: LogSinks) -> Self {
+        Self {
+            level,
+            settings: Arc::new(OnceLock::new()),
+            sinks: Arc::new(sinks),
         }
     }
 
-    fn as_str(self) -> &'static str {
-        match self {
-            Self::Error => "error",
-            Self::Warn => "warn",
-            Self::Info => "info",
-            Self::Debug => "debug",
-            Self::Trace => "trace",
-        }
+    fn follow_settings(&self, settings: Arc<Mutex<RuntimeSettings>>) {
+        let _ = self.settings.set(settings);
     }
-}
 
-#[derive(Debug, Clone)]
-struct RuntimeLogger {
-    level: LogLevel,
-}
-
-impl RuntimeLogger {
-    fn new(level: LogLevel) -> Self {
-        Self { level }
+    fn level(&self) -> LogLevel {
+        self.settings
+            .get()
+            .and_then(|settings| settings.lock().ok())
+            .map_or(self.level, |settings| {
+                LogLevel::parse(settings.log_level.as_str())
+            })
     }
 
     fn enabled(&self, level: LogLevel) -> bool {
-        level <= self.level
+        level <= self.level()
     }
 
     fn log(&self, level: LogLevel, event: &str, data: serde_json::Value) {
         if !self.enabled(level) {
             return;
         }
-        let timestamp = std::time::SystemTime::now()
+        let timestamp_ms = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis();
-        let payload = json!({
-            "ts": timestamp,
-            "level": level.as_str(),
-            "event": event,
-            "data": data,
+        self.sinks.write(&LogRecord {
+            timestamp_ms,
+            level,
+            event,
+            data: &data,
         });
-        println!("{payload}");
     }
 }
 
@@ -1168,13 +1730,45 @@ fn log_runtime_event(logger: &RuntimeLogger, event: &trust_runtime::debug::Runti
                 }),
             );
         }
-        trust_runtime::debug::RuntimeEvent::Fault { error, time } => {
+        trust_runtime::debug::RuntimeEvent::Fault {
+            error,
+            location,
+            time,
+        } => {
             logger.log(
                 LogLevel::Error,
                 "runtime_fault",
                 json!({
                     "event_id": "TRUST-RT-FAULT-001",
                     "error": error,
+                    "location": location.as_ref().map(ToString::to_string),
+                    "time_ms": time.as_millis(),
+                }),
+            );
+        }
+        trust_runtime::debug::RuntimeEvent::Shutdown {
+            program,
+            cycles,
+            outcome,
+            time,
+        } => {
+            let incomplete = matches!(
+                outcome,
+                trust_runtime::watchdog::ShutdownOutcome::TimedOut
+                    | trust_runtime::watchdog::ShutdownOutcome::Faulted
+            );
+            logger.log(
+                if incomplete {
+                    LogLevel::Warn
+                } else {
+                    LogLevel::Info
+                },
+                "runtime_shutdown_hook",
+                json!({
+                    "event_id": if incomplete { "TRUST-RT-SHUTDOWN-002" } else { "TRUST-RT-SHUTDOWN-001" },
+                    "program": program.as_str(),
+                    "cycles": cycles,
+                    "outcome": outcome.as_str(),
                     "time_ms": time.as_millis(),
                 }),
             );
@@ -724,8 +724,8 @@ fn qualified_pou_name(node: &SyntaxNode) -> Option<SmolStr> {
         }
         if let Some(ns_name) = ancestor
             .children()
-            .find(|child| child.kind() == SyntaxKind::Name)
-            .and_then(|name_node| name_part_from_name_node(&name_node))
+            .find(|child| matches!(child.kind(), SyntaxKind::Name | SyntaxKind::QualifiedName))
+            .and_then(|name_node| namespace_name_from_node(&name_node))
         {
             parts.push(ns_name);
         }
@@ -735,6 +735,18 @@ fn qualified_pou_name(node: &SyntaxNode) -> Option<SmolStr> {
     Some(parts.join(".").into())
 }
 
+fn namespace_name_from_node(node: &SyntaxNode) -> Option<String> {
+    if node.kind() != SyntaxKind::QualifiedName {
+        return name_part_from_name_node(node);
+    }
+    let parts = node
+        .children()
+        .filter(|child| child.kind() == SyntaxKind::Name)
+        .map(|child| name_part_from_name_node(&child))
+        .collect::<Option<Vec<_>>>()?;
+    (!parts.is_empty()).then(|| parts.join("."))
+}
+
 fn name_part_from_name_node(node: &SyntaxNode) -> Option<String> {
     let text = first_ident_token(node)?.text().trim().to_string();
     if text.is_empty() {
@@ -826,7 +838,7 @@ END_NAMESPACE
 
         let discovered = discover_tests(&sources);
         assert_eq!(discovered.len(), 2);
-        assert_eq!(discovered[0].name, "CaseOne");
+        assert_eq!(discovered[0].name, "NS.Core.CaseOne");
         assert_eq!(discovered[0].kind, TestKind::FunctionBlock);
         assert_eq!(
             discovered[0].source_line.as_deref(),
@@ -0,0 +1,254 @@
+//! Worst-case execution time estimation report.
+
+use std::collections::BTreeSet;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde_json::json;
+use trust_hir::db::{FileId, SourceDatabase};
+use trust_ide::{analyze_wcet, WcetOptions, WcetProgram};
+use trust_runtime::bundle::detect_bundle_path;
+use trust_runtime::bundle_builder::resolve_sources_root;
+use trust_runtime::control::{control_error_message, ControlEndpoint};
+use trust_runtime::metrics::measured_timings_from_task_stats;
+
+use crate::style;
+
+pub fn run_wcet(
+    project: Option<PathBuf>,
+    endpoint: Option<String>,
+    token: Option<String>,
+    profile: Option<PathBuf>,
+    statement_ns: Option<f64>,
+    json_output: bool,
+) -> anyhow::Result<()> {
+    let project_root = match project {
+        Some(path) => path,
+        None => match detect_bundle_path(None) {
+            Ok(path) => path,
+            Err(_) => std::env::current_dir().context("failed to resolve current directory")?,
+        },
+    };
+    let sources_root = resolve_sources_root(&project_root, None)?;
+    let paths = source_paths(&sources_root)?;
+    if paths.is_empty() {
+        anyhow::bail!("no ST sources found under {}", sources_root.display());
+    }
+
+    let mut db = trust_hir::Database::new();
+    let mut display_paths = Vec::with_capacity(paths.len());
+    for (index, path) in paths.iter().enumerate() {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read source '{}'", path.display()))?;
+        db.set_source_text(FileId(index as u32), text);
+        display_paths.push(
+            path.strip_prefix(&project_root)
+                .map_or_else(|_| path.clone(), Path::to_path_buf),
+        );
+    }
+
+    let stats = match (profile, endpoint) {
+        (Some(path), _) => {
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read profile '{}'", path.display()))?;
+            let value: serde_json::Value = serde_json::from_str(&text)
+                .with_context(|| format!("invalid profile JSON '{}'", path.display()))?;
+            // Accept both a raw `tasks.stats` result and a full control response.
+            Some(value.get("result").cloned().unwrap_or(value))
+        }
+        (None, Some(endpoint)) => {
+            let token = token.or_else(|| std::env::var("TRUST_CTL_TOKEN").ok());
+            Some(fetch_task_stats(&endpoint, token.as_deref())?)
+        }
+        (None, None) => None,
+    };
+    let measured = stats
+        .as_ref()
+        .map(measured_timings_from_task_stats)
+        .unwrap_or_default();
+
+    let defaults = WcetOptions::default();
+    let report = analyze_wcet(
+        &db,
+        WcetOptions {
+            measured: &measured,
+            statement_ns: statement_ns.unwrap_or(defaults.statement_ns),
+            ..defaults
+        },
+    );
+    let location = |file_id: FileId, offset: u32| {
+        let text = db.source_text(file_id);
+        let line = text[..(offset as usize).min(text.len())]
+            .matches('\n')
+            .count()
+            + 1;
+        format!(
+            "{}:{line}",
+            display_paths
+                .get(file_id.0 as usize)
+                .map_or_else(String::new, |path| path.display().to_string())
+        )
+    };
+    let overruns = report.tasks.iter().filter(|task| task.overrun).count();
+
+    if json_output {
+        let program_value = |program: &WcetProgram| {
+            json!({
+                "name": program.name.as_str(),
+                "program": program.program.as_str(),
+                "location": location(program.file_id, program.range.start().into()),
+                "statements": program.statements,
+                "static_ms": program.static_ms,
+                "measured_ms": program.measured_ms,
+                "estimate_ms": program.estimate_ms,
+                "unbounded": program.unbounded,
+            })
+        };
+        let tasks = report
+            .tasks
+            .iter()
+            .map(|task| {
+                json!({
+                    "name": task.name.as_str(),
+                    "location": location(task.file_id, task.range.start().into()),
+                    "interval_ms": task.interval_ms,
+                    "estimate_ms": task.estimate_ms,
+                    "unbounded": task.unbounded,
+                    "overrun": task.overrun,
+                    "programs": task.programs.iter().map(program_value).collect::<Vec<_>>(),
+                })
+            })
+            .collect::<Vec<_>>();
+        let value = json!({
+            "statement_ns": report.statement_ns,
+            "calibrated": report.calibrated,
+            "tasks": tasks,
+            "unscheduled": report.unscheduled.iter().map(program_value).collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&value)?);
+    } else {
+        println!(
+            "Statement cost: {:.1} ns ({})",
+            report.statement_ns,
+            if report.calibrated {
+                "calibrated from measured timings"
+            } else {
+                "default, no measured timings"
+            }
+        );
+        for task in &report.tasks {
+            let interval = task.interval_ms.map_or_else(
+                || "no interval".to_string(),
+                |ms| format!("interval {ms:.3} ms"),
+            );
+            let line = format!(
+                "TASK {} ({interval}): estimate {:.3} ms",
+                task.name, task.estimate_ms
+            );
+            if task.overrun {
+                println!("{}", style::error(format!("{line} exceeds INTERVAL")));
+            } else if task.unbounded {
+                println!(
+                    "{}",
+                    style::warning(format!("{line} (unbounded loop assumed)"))
+                );
+            } else {
+                println!("{}", style::success(line));
+            }
+            for program in &task.programs {
+                print_program(
+                    program,
+                    &location(program.file_id, program.range.start().into()),
+                );
+            }
+        }
+        if !report.unscheduled.is_empty() {
+            println!("Programs without task:");
+            for program in &report.unscheduled {
+                print_program(
+                    program,
+                    &location(program.file_id, program.range.start().into()),
+                );
+            }
+        }
+    }
+
+    if overruns > 0 {
+        anyhow::bail!("{overruns} task(s) exceed their INTERVAL");
+    }
+    Ok(())
+}
+
+fn print_program(program: &WcetProgram, location: &str) {
+    let measured = program
+        .measured_ms
+        .map_or_else(String::new, |ms| format!(", measured {ms:.3} ms"));
+    println!(
+        "  {} : {} ({location}): {} statements, static {:.3} ms{measured}, estimate {:.3} ms{}",
+        program.name,
+        program.program,
+        program.statements,
+        program.static_ms,
+        program.estimate_ms,
+        if program.unbounded {
+            " (unbounded)"
+        } else {
+            ""
+        }
+    );
+}
+
+pub(crate) fn source_paths(root: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut paths = BTreeSet::new();
+    for pattern in ["**/*.st", "**/*.ST", "**/*.pou", "**/*.POU"] {
+        for entry in glob::glob(&format!("{}/{}", root.display(), pattern))
+            .with_context(|| format!("invalid glob pattern for '{}'", root.display()))?
+        {
+            paths.insert(entry?);
+        }
+    }
+    Ok(paths.into_iter().collect())
+}
+
+fn fetch_task_stats(endpoint: &str, token: Option<&str>) -> anyhow::Result<serde_json::Value> {
+    let request = json!({"id": 1, "type": "tasks.stats", "auth": token});
+    let response = match ControlEndpoint::parse(endpoint)? {
+        ControlEndpoint::Tcp(addr) => {
+            let mut stream = std::net::TcpStream::connect(addr)?;
+            let mut reader = BufReader::new(stream.try_clone()?);
+            exchange(&mut stream, &mut reader, &request)?
+        }
+        #[cfg(unix)]
+        ControlEndpoint::Unix(path) => {
+            let mut stream = std::os::unix::net::UnixStream::connect(path)?;
+            let mut reader = BufReader::new(stream.try_clone()?);
+            exchange(&mut stream, &mut reader, &request)?
+        }
+    };
+    let response: serde_json::Value =
+        serde_json::from_str(response.trim_end()).context("invalid control response")?;
+    if response.get("ok").and_then(serde_json::Value::as_bool) != Some(true) {
+        let error = response
+            .get("error")
+            .and_then(control_error_message)
+            .unwrap_or("unknown error");
+        anyhow::bail!("tasks.stats failed: {error}");
+    }
+    Ok(response
+        .get("result")
+        .cloned()
+        .unwrap_or(serde_json::Value::Null))
+}
+
+fn exchange<S: Write, R: BufRead>(
+    stream: &mut S,
+    reader: &mut R,
+    request: &serde_json::Value,
+) -> anyhow::Result<String> {
+    writeln!(stream, "{}", serde_json::to_string(request)?)?;
+    stream.flush()?;
+    let mut response = String::new();
+    reader.read_line(&mut response)?;
+    Ok(response)
+}
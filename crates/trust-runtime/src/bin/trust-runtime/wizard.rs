@@ -50,6 +50,7 @@ pub fn run_wizard(path: Option<PathBuf>, start: bool) -> anyhow::Result<()> {
             false,
             false,
             1,
+            false,
         )?;
     }
     Ok(())
@@ -230,7 +231,7 @@ const DEFAULT_GITIGNORE: &str = "\
 *.swo\n\
 ";
 
-fn ensure_gitignore(root: &Path) -> anyhow::Result<()> {
+pub(crate) fn ensure_gitignore(root: &Path) -> anyhow::Result<()> {
     let path = root.join(".gitignore");
     if path.exists() {
         return Ok(());
@@ -262,6 +263,7 @@ fn build_io_config(driver: &str) -> anyhow::Result<IoConfigTemplate> {
                 params: toml::Value::Table(params),
             }],
             safe_state,
+            map: Vec::new(),
         });
     }
     build_io_config_auto(driver)
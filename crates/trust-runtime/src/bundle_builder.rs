@@ -2,10 +2,14 @@
 
 use anyhow::Context;
 use serde::Deserialize;
+use smol_str::SmolStr;
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::bytecode::{BuildInfo, Section, SectionData, SectionId};
 use crate::harness::{CompileSession, SourceFile};
 
 const DEPENDENCY_MANIFEST_FILES: &[&str] = &["trust-lsp.toml", ".trust-lsp.toml", "trustlsp.toml"];
@@ -21,6 +25,8 @@ pub struct BundleBuildReport {
     pub dependency_roots: Vec<PathBuf>,
     /// Resolved dependency names in deterministic order.
     pub resolved_dependencies: Vec<String>,
+    /// Provenance stamped into the program's BUILD_INFO section.
+    pub build_info: BuildInfo,
 }
 
 /// Compile bundle sources into `program.stbc`.
@@ -45,7 +51,14 @@ pub fn build_program_stbc(
     }
 
     let session = CompileSession::from_sources(sources);
-    let bytes = session.build_bytecode_bytes()?;
+    let mut module = session.build_bytecode_module()?;
+    let build_info = collect_build_info(bundle_root, &sources_root);
+    module.sections.push(Section {
+        id: SectionId::BuildInfo.as_raw(),
+        flags: 0,
+        data: SectionData::BuildInfo(build_info.clone()),
+    });
+    let bytes = module.encode()?;
     fs::create_dir_all(bundle_root)?;
     let program_path = bundle_root.join("program.stbc");
     fs::write(&program_path, bytes)?;
@@ -61,9 +74,67 @@ pub fn build_program_stbc(
             .iter()
             .map(|dependency| dependency.name.clone())
             .collect(),
+        build_info,
     })
 }
 
+/// Gathers what operators need to identify a build on the PLC.
+///
+/// The version comes from `[package]` in the project manifest, the commit and
+/// author from git when the project is in a repository. `SOURCE_DATE_EPOCH`
+/// pins the timestamp and `TRUST_BUILD_AUTHOR` overrides the author.
+fn collect_build_info(bundle_root: &Path, sources_root: &Path) -> BuildInfo {
+    let version = load_package_info(bundle_root)
+        .ok()
+        .and_then(|package| package.version);
+    let git_hash = git_output(bundle_root, &["rev-parse", "--short=12", "HEAD"]).map(|hash| {
+        let sources = sources_root.to_string_lossy();
+        let dirty = git_output(bundle_root, &["status", "--porcelain", "--", &sources]).is_some();
+        if dirty {
+            format!("{hash}-dirty")
+        } else {
+            hash
+        }
+    });
+    let built_at_ms = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.trim().parse::<u128>().ok())
+        .map(|secs| secs * 1000)
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis()
+        });
+    let author = std::env::var("TRUST_BUILD_AUTHOR")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .or_else(|| git_output(bundle_root, &["config", "user.name"]))
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok());
+    BuildInfo {
+        version: version.map(SmolStr::new),
+        git_hash: git_hash.map(SmolStr::new),
+        built_at: Some(crate::logging::format_rfc3339_ms(built_at_ms).into()),
+        author: author.map(SmolStr::new),
+    }
+}
+
+/// Trimmed stdout of a successful git command, or `None` if it failed or printed nothing.
+fn git_output(root: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
 /// Resolve the effective project source root for bundle operations.
 ///
 /// Behavior:
@@ -122,6 +193,19 @@ fn resolve_dependency_recursive(
     resolved: &mut BTreeMap<String, ResolvedDependency>,
 ) -> anyhow::Result<()> {
     let path = canonicalize_or_self(&dependency.path);
+    if crate::stlib::is_library_archive(&path) {
+        let archive = crate::stlib::LibraryArchive::read(&path)?;
+        ensure_dependency_version(
+            dependency.name.as_str(),
+            dependency.version.as_deref(),
+            archive.manifest.version.as_deref(),
+        )?;
+        anyhow::bail!(
+            "dependency '{}' is a precompiled library archive ({}); the source interpreter cannot execute archived bytecode, build against the library sources instead",
+            dependency.name,
+            path.display()
+        );
+    }
     if !path.is_dir() {
         anyhow::bail!(
             "dependency '{}' path does not exist: {}",
@@ -224,11 +308,26 @@ fn parse_dependency_specs(
         .collect()
 }
 
+/// Source roots compiled for a bundle: project sources first, then local
+/// dependencies in resolution order.
+pub(crate) fn bundle_source_roots(
+    bundle_root: &Path,
+    sources_root: Option<&Path>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let mut roots = vec![resolve_sources_root(bundle_root, sources_root)?];
+    for dependency in resolve_local_dependencies(bundle_root)? {
+        roots.push(preferred_dependency_sources_root(&dependency.path));
+    }
+    Ok(roots)
+}
+
 fn preferred_dependency_sources_root(path: &Path) -> PathBuf {
     path.join("src")
 }
 
-fn collect_sources(source_roots: &[PathBuf]) -> anyhow::Result<(Vec<SourceFile>, Vec<PathBuf>)> {
+pub(crate) fn collect_sources(
+    source_roots: &[PathBuf],
+) -> anyhow::Result<(Vec<SourceFile>, Vec<PathBuf>)> {
     let patterns = ["**/*.st", "**/*.ST", "**/*.pou", "**/*.POU"];
     let mut seen = BTreeSet::new();
     let mut source_map = BTreeMap::new();
@@ -263,6 +362,20 @@ fn collect_sources(source_roots: &[PathBuf]) -> anyhow::Result<(Vec<SourceFile>,
     Ok((sources, paths))
 }
 
+/// `[package]` identity of a project.
+pub(crate) struct PackageInfo {
+    pub(crate) name: Option<String>,
+    pub(crate) version: Option<String>,
+}
+
+pub(crate) fn load_package_info(root: &Path) -> anyhow::Result<PackageInfo> {
+    let manifest = load_dependency_manifest(root)?;
+    Ok(PackageInfo {
+        name: manifest.package.name,
+        version: manifest.package.version,
+    })
+}
+
 fn load_dependency_manifest(root: &Path) -> anyhow::Result<DependencyManifestFile> {
     let Some(path) = find_dependency_manifest(root) else {
         return Ok(DependencyManifestFile::default());
@@ -324,6 +437,7 @@ struct DependencyManifestFile {
 
 #[derive(Debug, Default, Deserialize)]
 struct PackageSection {
+    name: Option<String>,
     version: Option<String>,
 }
 
@@ -392,6 +506,17 @@ END_PROGRAM
         );
     }
 
+    fn without_build_time(bytes: &[u8]) -> Vec<u8> {
+        let mut module = crate::bytecode::BytecodeModule::decode(bytes).expect("decode program");
+        for section in &mut module.sections {
+            if let SectionData::BuildInfo(info) = &mut section.data {
+                assert!(info.built_at.is_some());
+                info.built_at = None;
+            }
+        }
+        module.encode().expect("encode program")
+    }
+
     fn write_dependency_source(root: &Path, name: &str) {
         write_file(
             &root.join("src/lib.st"),
@@ -453,6 +578,37 @@ version = "2.0.0"
         fs::remove_dir_all(root).ok();
     }
 
+    #[test]
+    fn build_rejects_precompiled_dependency_archives() {
+        let root = temp_dir("trust-runtime-build-stlib");
+        let dep = root.join("deps/lib-a");
+        write_root_source(&root);
+        write_dependency_source(&dep, "DepDouble");
+        write_file(
+            &dep.join("trust-lsp.toml"),
+            r#"
+[package]
+name = "LibA"
+version = "1.0.0"
+"#,
+        );
+        let packed = crate::stlib::pack_library(&dep, None, Some(&root.join("libs/LibA.stlib")))
+            .expect("pack library");
+        assert_eq!(packed.manifest.name, "LibA");
+        write_file(
+            &root.join("trust-lsp.toml"),
+            r#"
+[dependencies]
+LibA = { path = "libs/LibA.stlib", version = "1.0.0" }
+"#,
+        );
+
+        let err = build_program_stbc(&root, None).expect_err("build should fail");
+        assert!(err.to_string().contains("precompiled library archive"));
+
+        fs::remove_dir_all(root).ok();
+    }
+
     #[test]
     fn build_fails_for_missing_dependency_path() {
         let root = temp_dir("trust-runtime-build-missing");
@@ -587,7 +743,34 @@ version = "1.0.0"
 
         assert_eq!(first.resolved_dependencies, second.resolved_dependencies);
         assert_eq!(first.sources, second.sources);
-        assert_eq!(first_bytes, second_bytes);
+        // Only the build timestamp may differ between the two programs.
+        assert_eq!(
+            without_build_time(&first_bytes),
+            without_build_time(&second_bytes)
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn build_stamps_program_with_build_info() {
+        let root = temp_dir("trust-runtime-build-info");
+        write_file(&root.join("src/main.st"), "PROGRAM Main END_PROGRAM");
+        write_file(
+            &root.join("trust-lsp.toml"),
+            r#"
+[package]
+name = "Line1"
+version = "2.1.0"
+"#,
+        );
+
+        let report = build_program_stbc(&root, None).expect("build should pass");
+        assert_eq!(report.build_info.version.as_deref(), Some("2.1.0"));
+        assert!(report.build_info.built_at.is_some());
+        let bytes = fs::read(&report.program_path).expect("read program");
+        let module = crate::bytecode::BytecodeModule::decode(&bytes).expect("decode program");
+        assert_eq!(module.build_info(), Some(report.build_info));
 
         fs::remove_dir_all(root).ok();
     }
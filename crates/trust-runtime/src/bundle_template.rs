@@ -9,6 +9,8 @@ pub struct IoConfigTemplate {
     pub drivers: Vec<IoDriverTemplate>,
     /// Optional safe state entries.
     pub safe_state: Vec<(String, String)>,
+    /// Optional `[io.map]` symbol to address entries.
+    pub map: Vec<(String, String)>,
 }
 
 /// Single I/O driver template.
@@ -48,6 +50,7 @@ pub fn build_io_config_auto(driver: &str) -> anyhow::Result<IoConfigTemplate> {
                 params: toml::Value::Table(params),
             }],
             safe_state,
+            map: Vec::new(),
         });
     }
     if driver.eq_ignore_ascii_case("modbus-tcp") {
@@ -67,6 +70,7 @@ pub fn build_io_config_auto(driver: &str) -> anyhow::Result<IoConfigTemplate> {
                 params: toml::Value::Table(params),
             }],
             safe_state,
+            map: Vec::new(),
         });
     }
     if driver.eq_ignore_ascii_case("simulated") {
@@ -76,6 +80,7 @@ pub fn build_io_config_auto(driver: &str) -> anyhow::Result<IoConfigTemplate> {
                 params: toml::Value::Table(toml::map::Map::new()),
             }],
             safe_state,
+            map: Vec::new(),
         });
     }
     if driver.eq_ignore_ascii_case("mqtt") {
@@ -101,6 +106,7 @@ pub fn build_io_config_auto(driver: &str) -> anyhow::Result<IoConfigTemplate> {
                 params: toml::Value::Table(params),
             }],
             safe_state,
+            map: Vec::new(),
         });
     }
     if driver.eq_ignore_ascii_case("ethercat") {
@@ -134,6 +140,7 @@ pub fn build_io_config_auto(driver: &str) -> anyhow::Result<IoConfigTemplate> {
                 params: toml::Value::Table(params),
             }],
             safe_state,
+            map: Vec::new(),
         });
     }
     Ok(IoConfigTemplate {
@@ -142,6 +149,7 @@ pub fn build_io_config_auto(driver: &str) -> anyhow::Result<IoConfigTemplate> {
             params: toml::Value::Table(toml::map::Map::new()),
         }],
         safe_state,
+        map: Vec::new(),
     })
 }
 
@@ -189,6 +197,233 @@ pub fn render_io_toml(config: &IoConfigTemplate) -> String {
             .collect::<Vec<_>>();
         io.insert("safe_state".into(), toml::Value::Array(entries));
     }
+    if !config.map.is_empty() {
+        let entries = config
+            .map
+            .iter()
+            .map(|(name, address)| (name.clone(), toml::Value::String(address.clone())))
+            .collect::<toml::map::Map<_, _>>();
+        io.insert("map".into(), toml::Value::Table(entries));
+    }
     root.insert("io".into(), toml::Value::Table(io));
     toml::to_string(&toml::Value::Table(root)).unwrap_or_default()
 }
+
+/// Starter programs offered by `trust-runtime new`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectTemplate {
+    /// Cycle counter with a reset input and a blinking output (loopback I/O).
+    Counter,
+    /// Timer-driven traffic light sequence (simulated I/O).
+    TrafficLight,
+    /// Start/stop motor latch over a Modbus/TCP I/O coupler.
+    ModbusIo,
+}
+
+impl ProjectTemplate {
+    /// Template name as used on the command line.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::TrafficLight => "traffic-light",
+            Self::ModbusIo => "modbus-io",
+        }
+    }
+
+    /// I/O driver written to the template's io.toml.
+    #[must_use]
+    pub fn io_driver(self) -> &'static str {
+        match self {
+            Self::Counter => "loopback",
+            Self::TrafficLight => "simulated",
+            Self::ModbusIo => "modbus-tcp",
+        }
+    }
+
+    fn main_source(self) -> &'static str {
+        match self {
+            Self::Counter => COUNTER_MAIN,
+            Self::TrafficLight => TRAFFIC_LIGHT_MAIN,
+            Self::ModbusIo => MODBUS_IO_MAIN,
+        }
+    }
+
+    fn io_globals(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Counter => &[("ResetButton", "%IX0.0"), ("BlinkLamp", "%QX0.0")],
+            Self::TrafficLight => &[
+                ("RedLamp", "%QX0.0"),
+                ("YellowLamp", "%QX0.1"),
+                ("GreenLamp", "%QX0.2"),
+            ],
+            Self::ModbusIo => &[
+                ("StartButton", "%IX0.0"),
+                ("StopButton", "%IX0.1"),
+                ("MotorRun", "%QX0.0"),
+            ],
+        }
+    }
+}
+
+/// Single file of a rendered project, relative to the project root.
+#[derive(Debug, Clone)]
+pub struct ScaffoldFile {
+    /// Path relative to the project root.
+    pub path: &'static str,
+    /// File contents.
+    pub contents: String,
+}
+
+/// Render every file of a new project from a template.
+///
+/// The control endpoint in runtime.toml is repeated in trust-lsp.toml so the
+/// editor, debugger, and `trust-runtime ctl` reach the runtime without extra setup.
+pub fn render_project_scaffold(
+    template: ProjectTemplate,
+    project_name: &str,
+    resource_name: &SmolStr,
+    cycle_ms: u64,
+) -> anyhow::Result<Vec<ScaffoldFile>> {
+    let mut io_config = build_io_config_auto(template.io_driver())?;
+    io_config.safe_state = template
+        .io_globals()
+        .iter()
+        .filter(|(_, address)| address.starts_with("%Q"))
+        .map(|(_, address)| (address.to_string(), "FALSE".to_string()))
+        .collect();
+    Ok(vec![
+        ScaffoldFile {
+            path: "src/main.st",
+            contents: template.main_source().to_string(),
+        },
+        ScaffoldFile {
+            path: "src/configuration.st",
+            contents: render_configuration_source(template, resource_name, cycle_ms),
+        },
+        ScaffoldFile {
+            path: "runtime.toml",
+            contents: render_runtime_toml(resource_name, cycle_ms),
+        },
+        ScaffoldFile {
+            path: "io.toml",
+            contents: render_io_toml(&io_config),
+        },
+        ScaffoldFile {
+            path: "trust-lsp.toml",
+            contents: render_lsp_toml(project_name),
+        },
+        ScaffoldFile {
+            path: ".vscode/launch.json",
+            contents: LAUNCH_JSON.to_string(),
+        },
+    ])
+}
+
+/// Control endpoint written by [`render_runtime_toml`].
+pub const DEFAULT_CONTROL_ENDPOINT: &str = "unix:///tmp/trust-runtime.sock";
+
+fn render_configuration_source(
+    template: ProjectTemplate,
+    resource_name: &SmolStr,
+    cycle_ms: u64,
+) -> String {
+    let mut out = String::from("CONFIGURATION Config\nVAR_GLOBAL\n");
+    for (name, address) in template.io_globals() {
+        out.push_str(&format!("    {name} AT {address} : BOOL;\n"));
+    }
+    out.push_str(&format!(
+        "END_VAR\nRESOURCE {resource_name} ON PLC\n    TASK MainTask (INTERVAL := T#{cycle_ms}ms, PRIORITY := 1);\n    PROGRAM P1 WITH MainTask : Main;\nEND_RESOURCE\nEND_CONFIGURATION\n"
+    ));
+    out
+}
+
+fn render_lsp_toml(project_name: &str) -> String {
+    let name = project_name.replace(['"', '\\'], "");
+    format!(
+        "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n\n[project]\ninclude_paths = [\"src\"]\nstdlib = \"iec\"\n\n[runtime]\ncontrol_endpoint = \"{DEFAULT_CONTROL_ENDPOINT}\"\n"
+    )
+}
+
+const LAUNCH_JSON: &str = r#"{
+    "version": "0.2.0",
+    "configurations": [
+        {
+            "type": "structured-text",
+            "request": "launch",
+            "name": "Debug Structured Text",
+            "program": "${workspaceFolder}/src/configuration.st",
+            "stopOnEntry": false
+        }
+    ]
+}
+"#;
+
+const COUNTER_MAIN: &str = r#"PROGRAM Main
+VAR_EXTERNAL
+    ResetButton : BOOL;
+    BlinkLamp : BOOL;
+END_VAR
+VAR
+    Count : DINT := 0;
+END_VAR
+
+IF ResetButton THEN
+    Count := 0;
+ELSE
+    Count := Count + 1;
+END_IF;
+BlinkLamp := (Count MOD 10) < 5;
+END_PROGRAM
+"#;
+
+const TRAFFIC_LIGHT_MAIN: &str = r#"PROGRAM Main
+VAR_EXTERNAL
+    RedLamp : BOOL;
+    YellowLamp : BOOL;
+    GreenLamp : BOOL;
+END_VAR
+VAR
+    Phase : INT := 0;
+    PhaseTimer : TON;
+    PhaseTime : TIME := T#2s;
+END_VAR
+
+PhaseTimer(IN := TRUE, PT := PhaseTime);
+IF PhaseTimer.Q THEN
+    Phase := (Phase + 1) MOD 4;
+    PhaseTimer(IN := FALSE, PT := PhaseTime);
+END_IF;
+
+CASE Phase OF
+    0:
+        RedLamp := TRUE;
+        YellowLamp := FALSE;
+        GreenLamp := FALSE;
+    1:
+        RedLamp := TRUE;
+        YellowLamp := TRUE;
+        GreenLamp := FALSE;
+    2:
+        RedLamp := FALSE;
+        YellowLamp := FALSE;
+        GreenLamp := TRUE;
+    3:
+        RedLamp := FALSE;
+        YellowLamp := TRUE;
+        GreenLamp := FALSE;
+END_CASE;
+END_PROGRAM
+"#;
+
+const MODBUS_IO_MAIN: &str = r#"PROGRAM Main
+VAR_EXTERNAL
+    StartButton : BOOL;
+    StopButton : BOOL;
+    MotorRun : BOOL;
+END_VAR
+
+(* Stop wins over start; the motor holds itself in once started. *)
+MotorRun := (StartButton OR MotorRun) AND NOT StopButton;
+END_PROGRAM
+"#;
@@ -7,8 +7,8 @@ use smol_str::SmolStr;
 use super::reader::BytecodeReader;
 use super::util::align4;
 use super::{
-    BytecodeError, BytecodeModule, BytecodeVersion, ConstEntry, ConstPool, DebugEntry, DebugMap,
-    EnumVariant, Field, InterfaceImpl, InterfaceMethod, IoBinding, IoMap, MethodEntry,
+    BuildInfo, BytecodeError, BytecodeModule, BytecodeVersion, ConstEntry, ConstPool, DebugEntry,
+    DebugMap, EnumVariant, Field, InterfaceImpl, InterfaceMethod, IoBinding, IoMap, MethodEntry,
     PouClassMeta, PouEntry, PouIndex, PouKind, RefEntry, RefLocation, RefSegment, RefTable,
     ResourceEntry, ResourceMeta, RetainInit, RetainInitEntry, Section, SectionData, SectionEntry,
     SectionId, StringTable, TypeData, TypeEntry, TypeKind, TypeTable, VarMeta, VarMetaEntry,
@@ -420,6 +420,10 @@ fn decode_section_data(
             }
             SectionData::RetainInit(RetainInit { entries })
         }
+        SectionId::BuildInfo => {
+            let table = decode_string_table(version, &mut reader)?;
+            SectionData::BuildInfo(BuildInfo::from_entries(&table.entries)?)
+        }
     };
     Ok(data)
 }
@@ -0,0 +1,304 @@
+//! Bytecode disassembly with source mapping.
+
+#![allow(missing_docs)]
+
+use smol_str::SmolStr;
+
+use super::reader::BytecodeReader;
+use super::{
+    BytecodeError, BytecodeModule, DebugMap, PouEntry, PouIndex, PouKind, SectionData, SectionId,
+    StringTable, TypeTable, VarMeta,
+};
+
+/// Disassembled POU body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisassembledPou {
+    pub id: u32,
+    pub name: SmolStr,
+    pub kind: PouKind,
+    pub instructions: Vec<DisassembledInstruction>,
+}
+
+/// One decoded instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisassembledInstruction {
+    /// Absolute offset into POU_BODIES.
+    pub offset: u32,
+    pub bytes: Vec<u8>,
+    pub mnemonic: &'static str,
+    /// Rendered operands (jump targets are absolute offsets).
+    pub operands: String,
+    /// Source statement the instruction belongs to, when the module has a DEBUG_MAP.
+    pub source: Option<DisassemblySource>,
+    /// Whether a statement starts at this instruction.
+    pub statement_start: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisassemblySource {
+    pub path: SmolStr,
+    /// 1-based line.
+    pub line: u32,
+    /// 1-based column.
+    pub column: u32,
+}
+
+impl DisassembledInstruction {
+    /// Mnemonic followed by its operands.
+    #[must_use]
+    pub fn text(&self) -> String {
+        if self.operands.is_empty() {
+            self.mnemonic.to_string()
+        } else {
+            format!("{} {}", self.mnemonic, self.operands)
+        }
+    }
+}
+
+impl BytecodeModule {
+    /// Decode every POU body into instructions, in code order.
+    pub fn disassemble(&self) -> Result<Vec<DisassembledPou>, BytecodeError> {
+        let strings = match self.section(SectionId::StringTable) {
+            Some(SectionData::StringTable(table)) => table,
+            _ => return Err(BytecodeError::MissingSection("STRING_TABLE".into())),
+        };
+        let index = match self.section(SectionId::PouIndex) {
+            Some(SectionData::PouIndex(index)) => index,
+            _ => return Err(BytecodeError::MissingSection("POU_INDEX".into())),
+        };
+        let bodies = match self.section(SectionId::PouBodies) {
+            Some(SectionData::PouBodies(bodies)) => bodies,
+            _ => return Err(BytecodeError::MissingSection("POU_BODIES".into())),
+        };
+        let names = OperandNames {
+            strings,
+            index,
+            types: match self.section(SectionId::TypeTable) {
+                Some(SectionData::TypeTable(types)) => Some(types),
+                _ => None,
+            },
+            vars: match self.section(SectionId::VarMeta) {
+                Some(SectionData::VarMeta(vars)) => Some(vars),
+                _ => None,
+            },
+        };
+        let debug_map = match self.section(SectionId::DebugMap) {
+            Some(SectionData::DebugMap(map)) => Some(map),
+            _ => None,
+        };
+        let debug_strings = match self.section(SectionId::DebugStringTable) {
+            Some(SectionData::DebugStringTable(table)) => Some(table),
+            _ => None,
+        };
+
+        let mut entries = index.entries.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.code_offset);
+        entries
+            .into_iter()
+            .map(|entry| {
+                let start = entry.code_offset as usize;
+                let end = start + entry.code_length as usize;
+                let code = bodies.get(start..end).ok_or_else(|| {
+                    BytecodeError::InvalidSection("POU code out of bounds".into())
+                })?;
+                let statements = statement_map(entry, debug_map, debug_strings);
+                Ok(DisassembledPou {
+                    id: entry.id,
+                    name: lookup_string(strings, entry.name_idx),
+                    kind: entry.kind,
+                    instructions: decode_instructions(
+                        entry.code_offset,
+                        code,
+                        &names,
+                        &statements,
+                    )?,
+                })
+            })
+            .collect()
+    }
+}
+
+struct OperandNames<'a> {
+    strings: &'a StringTable,
+    index: &'a PouIndex,
+    types: Option<&'a TypeTable>,
+    vars: Option<&'a VarMeta>,
+}
+
+impl OperandNames<'_> {
+    fn pou(&self, id: u32) -> String {
+        self.index
+            .entries
+            .iter()
+            .find(|entry| entry.id == id)
+            .map(|entry| lookup_string(self.strings, entry.name_idx).to_string())
+            .unwrap_or_else(|| format!("pou#{id}"))
+    }
+
+    fn type_name(&self, id: u32) -> String {
+        self.types
+            .and_then(|types| types.entries.get(id as usize))
+            .and_then(|entry| entry.name_idx)
+            .map(|idx| lookup_string(self.strings, idx).to_string())
+            .unwrap_or_else(|| format!("type#{id}"))
+    }
+
+    fn reference(&self, ref_idx: u32) -> String {
+        self.vars
+            .and_then(|vars| vars.entries.iter().find(|entry| entry.ref_idx == ref_idx))
+            .map(|entry| {
+                format!(
+                    "{} ; ref#{ref_idx}",
+                    lookup_string(self.strings, entry.name_idx)
+                )
+            })
+            .unwrap_or_else(|| format!("ref#{ref_idx}"))
+    }
+}
+
+/// Statement start offsets of one POU with their source positions.
+fn statement_map(
+    entry: &PouEntry,
+    debug_map: Option<&DebugMap>,
+    debug_strings: Option<&StringTable>,
+) -> Vec<(u32, DisassemblySource)> {
+    let Some(debug_map) = debug_map else {
+        return Vec::new();
+    };
+    let mut statements = debug_map
+        .entries
+        .iter()
+        .filter(|debug| debug.pou_id == entry.id)
+        .map(|debug| {
+            let path = debug_strings
+                .and_then(|table| table.entries.get(debug.file_idx as usize))
+                .cloned()
+                .unwrap_or_else(|| SmolStr::new(format!("file_{}", debug.file_idx)));
+            (
+                debug.code_offset,
+                DisassemblySource {
+                    path,
+                    line: debug.line,
+                    column: debug.column,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+    statements.sort_by_key(|(offset, _)| *offset);
+    statements
+}
+
+fn decode_instructions(
+    base: u32,
+    code: &[u8],
+    names: &OperandNames<'_>,
+    statements: &[(u32, DisassemblySource)],
+) -> Result<Vec<DisassembledInstruction>, BytecodeError> {
+    let mut reader = BytecodeReader::new(code);
+    let mut instructions = Vec::new();
+    let mut current: Option<&DisassemblySource> = None;
+    while reader.remaining() > 0 {
+        let pc = reader.pos();
+        let offset = base + pc as u32;
+        let opcode = reader.read_u8()?;
+        let mnemonic = opcode_mnemonic(opcode).ok_or(BytecodeError::InvalidOpcode(opcode))?;
+        let operands = match opcode {
+            0x02..=0x04 => {
+                let relative = reader.read_i32()?;
+                let target = i64::from(offset) + 5 + i64::from(relative);
+                format!("0x{target:08x}")
+            }
+            0x05 => names.pou(reader.read_u32()?),
+            0x07 => format!("slot {}", reader.read_u32()?),
+            0x08 => {
+                let interface = reader.read_u32()?;
+                let slot = reader.read_u32()?;
+                format!("{} slot {slot}", names.type_name(interface))
+            }
+            0x10 => format!("const#{}", reader.read_u32()?),
+            0x16 => reader.read_u8()?.to_string(),
+            0x20..=0x22 => names.reference(reader.read_u32()?),
+            0x30 => lookup_string(names.strings, reader.read_u32()?).to_string(),
+            0x60 => names.type_name(reader.read_u32()?),
+            0x70 => format!("std#{}", reader.read_u32()?),
+            _ => String::new(),
+        };
+        let statement = statements.iter().find(|(start, _)| *start == offset);
+        if let Some((_, source)) = statement {
+            current = Some(source);
+        }
+        instructions.push(DisassembledInstruction {
+            offset,
+            bytes: code[pc..reader.pos()].to_vec(),
+            mnemonic,
+            operands,
+            source: current.cloned(),
+            statement_start: statement.is_some(),
+        });
+    }
+    Ok(instructions)
+}
+
+/// Mnemonic for a baseline opcode.
+#[must_use]
+pub fn opcode_mnemonic(opcode: u8) -> Option<&'static str> {
+    let mnemonic = match opcode {
+        0x00 => "NOP",
+        0x01 => "HALT",
+        0x02 => "JMP",
+        0x03 => "JMP_TRUE",
+        0x04 => "JMP_FALSE",
+        0x05 => "CALL",
+        0x06 => "RET",
+        0x07 => "CALL_METHOD",
+        0x08 => "CALL_VIRTUAL",
+        0x10 => "CONST",
+        0x11 => "DUP",
+        0x12 => "POP",
+        0x13 => "SWAP",
+        0x14 => "OVER",
+        0x15 => "ROT",
+        0x16 => "PICK",
+        0x20 => "LOAD_REF",
+        0x21 => "STORE_REF",
+        0x22 => "PUSH_REF",
+        0x23 => "PUSH_SELF",
+        0x30 => "REF_FIELD",
+        0x31 => "REF_INDEX",
+        0x32 => "LOAD",
+        0x33 => "STORE",
+        0x40 => "ADD",
+        0x41 => "SUB",
+        0x42 => "MUL",
+        0x43 => "DIV",
+        0x44 => "MOD",
+        0x45 => "NEG",
+        0x46 => "AND",
+        0x47 => "OR",
+        0x48 => "XOR",
+        0x49 => "NOT",
+        0x4A => "SHL",
+        0x4B => "SHR",
+        0x4C => "EXPT",
+        0x4D => "ROL",
+        0x4E => "ROR",
+        0x50 => "EQ",
+        0x51 => "NE",
+        0x52 => "LT",
+        0x53 => "LE",
+        0x54 => "GT",
+        0x55 => "GE",
+        0x60 => "CAST",
+        0x70 => "CALL_STD",
+        _ => return None,
+    };
+    Some(mnemonic)
+}
+
+fn lookup_string(strings: &StringTable, idx: u32) -> SmolStr {
+    strings
+        .entries
+        .get(idx as usize)
+        .cloned()
+        .unwrap_or_else(|| SmolStr::new(format!("str#{idx}")))
+}
@@ -2,6 +2,8 @@
 
 #![allow(missing_docs)]
 
+use smol_str::SmolStr;
+
 use super::util::{align4, pad_to};
 use super::{
     BytecodeError, BytecodeModule, BytecodeVersion, SectionData, SectionEntry, TypeData, TypeEntry,
@@ -74,18 +76,7 @@ fn encode_section_data(
     let mut out = Vec::new();
     match data {
         SectionData::StringTable(table) | SectionData::DebugStringTable(table) => {
-            out.extend_from_slice(&(table.entries.len() as u32).to_le_bytes());
-            for entry in &table.entries {
-                let bytes = entry.as_bytes();
-                out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
-                out.extend_from_slice(bytes);
-                if version.minor >= 1 {
-                    let entry_len = 4usize + bytes.len();
-                    let padded = align4(entry_len);
-                    let target = out.len() + padded - entry_len;
-                    pad_to(&mut out, target);
-                }
-            }
+            encode_strings(version, &table.entries, &mut out);
         }
         SectionData::TypeTable(table) => {
             out = encode_type_table(version, table)?;
@@ -243,11 +234,27 @@ fn encode_section_data(
                 out.extend_from_slice(&entry.const_idx.to_le_bytes());
             }
         }
+        SectionData::BuildInfo(info) => encode_strings(version, &info.to_entries(), &mut out),
         SectionData::Raw(raw) => out.extend_from_slice(raw),
     }
     Ok(out)
 }
 
+fn encode_strings(version: BytecodeVersion, entries: &[SmolStr], out: &mut Vec<u8>) {
+    out.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+    for entry in entries {
+        let bytes = entry.as_bytes();
+        out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        out.extend_from_slice(bytes);
+        if version.minor >= 1 {
+            let entry_len = 4usize + bytes.len();
+            let padded = align4(entry_len);
+            let target = out.len() + padded - entry_len;
+            pad_to(out, target);
+        }
+    }
+}
+
 fn encode_type_table(
     version: BytecodeVersion,
     table: &TypeTable,
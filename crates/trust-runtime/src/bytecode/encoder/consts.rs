@@ -1,5 +1,6 @@
 use crate::value::Value;
-use trust_hir::TypeId;
+use trust_hir::types::TypeRegistry;
+use trust_hir::{Type, TypeId};
 
 use super::{BytecodeEncoder, BytecodeError, ConstEntry};
 
@@ -20,12 +21,29 @@ impl<'a> BytecodeEncoder<'a> {
         };
         let type_idx = self.type_index(type_id)?;
         let payload = encode_const_payload(value)?;
+        Ok(self.push_const(type_idx, payload))
+    }
+
+    /// Adds a constant of a declared type. ARRAY and STRUCT values take their type
+    /// from the declaration; other values keep the type derived from the value.
+    pub(super) fn const_index_for_type(
+        &mut self,
+        value: &Value,
+        type_id: TypeId,
+    ) -> Result<u32, BytecodeError> {
+        if !matches!(value, Value::Array(_) | Value::Struct(_)) {
+            return self.const_index_for(value);
+        }
+        let type_idx = self.type_index(type_id)?;
+        let mut payload = Vec::new();
+        encode_typed_const_payload(value, type_id, self.runtime.registry(), &mut payload)?;
+        Ok(self.push_const(type_idx, payload))
+    }
+
+    fn push_const(&mut self, type_id: u32, payload: Vec<u8>) -> u32 {
         let idx = self.const_pool.len() as u32;
-        self.const_pool.push(ConstEntry {
-            type_id: type_idx,
-            payload,
-        });
-        Ok(idx)
+        self.const_pool.push(ConstEntry { type_id, payload });
+        idx
     }
 
     pub(super) fn const_value_from_expr(
@@ -56,6 +74,12 @@ impl<'a> BytecodeEncoder<'a> {
             loop_depth: 0,
             pause_requested: false,
             execution_deadline: None,
+            workers: None,
+            bounds: crate::task::BoundsPolicy::Fault,
+            overflow: crate::eval::ops::OverflowPolicy::Wrap,
+            subrange: crate::task::SubrangePolicy::Off,
+            fault_site: None,
+            pou_code: None,
         };
         crate::eval::expr::eval_expr(&mut ctx, expr)
             .map_err(|_| BytecodeError::InvalidSection("unsupported const expression".into()))
@@ -70,7 +94,8 @@ fn const_expr_supported(expr: &crate::eval::expr::Expr) -> bool {
             if matches!(value, Value::String(_) | Value::WString(_)) {
                 return false;
             }
-            type_id_for_value(value).is_some()
+            matches!(value, Value::Array(_) | Value::Struct(_))
+                || type_id_for_value(value).is_some()
         }
         Expr::Unary { op, expr } => {
             matches!(op, UnaryOp::Neg | UnaryOp::Not | UnaryOp::Pos) && const_expr_supported(expr)
@@ -134,6 +159,58 @@ pub(super) fn type_id_for_value(value: &Value) -> Option<TypeId> {
     }
 }
 
+/// Encodes aggregates as a `u32` element or field count followed by the entries;
+/// struct fields follow the declaration order of the type.
+fn encode_typed_const_payload(
+    value: &Value,
+    type_id: TypeId,
+    registry: &TypeRegistry,
+    payload: &mut Vec<u8>,
+) -> Result<(), BytecodeError> {
+    let ty = registry
+        .get(type_id)
+        .ok_or_else(|| BytecodeError::InvalidSection("unknown const type".into()))?;
+    match (value, ty) {
+        (_, Type::Alias { target, .. }) => {
+            encode_typed_const_payload(value, *target, registry, payload)
+        }
+        (_, Type::Subrange { base, .. }) => {
+            encode_typed_const_payload(value, *base, registry, payload)
+        }
+        (Value::Array(array), Type::Array { element, .. }) => {
+            payload.extend_from_slice(&(array.elements.len() as u32).to_le_bytes());
+            for element_value in &array.elements {
+                encode_typed_const_payload(element_value, *element, registry, payload)?;
+            }
+            Ok(())
+        }
+        (Value::Struct(struct_value), Type::Struct { fields, .. }) => {
+            payload.extend_from_slice(&(fields.len() as u32).to_le_bytes());
+            for field in fields {
+                let field_value = struct_value
+                    .fields
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(&field.name))
+                    .map(|(_, value)| value)
+                    .ok_or_else(|| {
+                        BytecodeError::InvalidSection(
+                            format!("missing const field '{}'", field.name).into(),
+                        )
+                    })?;
+                encode_typed_const_payload(field_value, field.type_id, registry, payload)?;
+            }
+            Ok(())
+        }
+        (Value::Array(_) | Value::Struct(_), _) => Err(BytecodeError::InvalidSection(
+            "const aggregate does not match its type".into(),
+        )),
+        _ => {
+            payload.extend_from_slice(&encode_const_payload(value)?);
+            Ok(())
+        }
+    }
+}
+
 fn encode_const_payload(value: &Value) -> Result<Vec<u8>, BytecodeError> {
     let mut payload = Vec::new();
     match value {
@@ -97,7 +97,9 @@ impl<'a> BytecodeEncoder<'a> {
                 crate::RetainPolicy::Persistent => 3,
             };
             let init_const_idx = match &meta.init {
-                crate::GlobalInitValue::Value(value) => self.const_index_for(value).ok(),
+                crate::GlobalInitValue::Value(value) => {
+                    self.const_index_for_type(value, meta.type_id).ok()
+                }
                 _ => None,
             };
             entries.push(VarMetaEntry {
@@ -115,7 +115,7 @@ impl<'a> BytecodeEncoder<'a> {
                 local_ref_count,
                 for_temp_pairs,
             } = self.local_scope_for_body(
-                Some(&func.name),
+                Some(&func.result_name()),
                 &func.params,
                 &func.locals,
                 &func.body,
@@ -379,7 +379,7 @@ impl<'a> BytecodeEncoder<'a> {
             let default_const_idx = match (&param.default, param.direction) {
                 (Some(expr), ParamDirection::In) => {
                     let value = self.const_value_from_expr(expr)?;
-                    Some(self.const_index_for(&value)?)
+                    Some(self.const_index_for_type(&value, param.type_id)?)
                 }
                 _ => None,
             };
@@ -118,6 +118,7 @@ pub enum SectionId {
     DebugStringTable = 0x000A,
     VarMeta = 0x000B,
     RetainInit = 0x000C,
+    BuildInfo = 0x000D,
 }
 
 impl SectionId {
@@ -136,6 +137,7 @@ impl SectionId {
             0x000A => Some(Self::DebugStringTable),
             0x000B => Some(Self::VarMeta),
             0x000C => Some(Self::RetainInit),
+            0x000D => Some(Self::BuildInfo),
             _ => None,
         }
     }
@@ -175,6 +177,7 @@ pub enum SectionData {
     DebugMap(DebugMap),
     VarMeta(VarMeta),
     RetainInit(RetainInit),
+    BuildInfo(BuildInfo),
     Raw(Vec<u8>),
 }
 
@@ -407,6 +410,55 @@ pub struct VarMetaEntry {
     pub init_const_idx: Option<u32>,
 }
 
+/// Build provenance written by the bundle builder (BUILD_INFO section).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BuildInfo {
+    /// Project version from the `[package]` manifest.
+    pub version: Option<SmolStr>,
+    /// Source commit, suffixed with `-dirty` for uncommitted changes.
+    pub git_hash: Option<SmolStr>,
+    /// Build time as RFC 3339 UTC.
+    pub built_at: Option<SmolStr>,
+    pub author: Option<SmolStr>,
+}
+
+impl BuildInfo {
+    /// Flattens the set fields into alternating key and value strings.
+    pub(crate) fn to_entries(&self) -> Vec<SmolStr> {
+        [
+            ("version", &self.version),
+            ("git_hash", &self.git_hash),
+            ("built_at", &self.built_at),
+            ("author", &self.author),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some([SmolStr::new(key), value.clone()?]))
+        .flatten()
+        .collect()
+    }
+
+    /// Inverse of [`BuildInfo::to_entries`]; unknown keys are skipped.
+    pub(crate) fn from_entries(entries: &[SmolStr]) -> Result<Self, BytecodeError> {
+        if entries.len() % 2 != 0 {
+            return Err(BytecodeError::InvalidSection(
+                "build info key without value".into(),
+            ));
+        }
+        let mut info = Self::default();
+        for pair in entries.chunks(2) {
+            let value = Some(pair[1].clone());
+            match pair[0].as_str() {
+                "version" => info.version = value,
+                "git_hash" => info.git_hash = value,
+                "built_at" => info.built_at = value,
+                "author" => info.author = value,
+                _ => {}
+            }
+        }
+        Ok(info)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct RetainInit {
     pub entries: Vec<RetainInitEntry>,
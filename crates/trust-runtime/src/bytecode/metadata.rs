@@ -9,8 +9,9 @@ use crate::task::TaskConfig;
 use crate::value::{Duration, RefSegment as ValueRefSegment, ValueRef};
 
 use super::{
-    BytecodeError, BytecodeMetadata, BytecodeModule, ProcessImageConfig, RefEntry, RefLocation,
-    RefSegment, RefTable, ResourceEntry, ResourceMetadata, SectionData, SectionId, StringTable,
+    BuildInfo, BytecodeError, BytecodeMetadata, BytecodeModule, ProcessImageConfig, RefEntry,
+    RefLocation, RefSegment, RefTable, ResourceEntry, ResourceMetadata, SectionData, SectionId,
+    StringTable,
 };
 
 impl BytecodeModule {
@@ -39,6 +40,15 @@ impl BytecodeModule {
             resources,
         })
     }
+
+    /// Build provenance from the optional BUILD_INFO section.
+    #[must_use]
+    pub fn build_info(&self) -> Option<BuildInfo> {
+        match self.section(SectionId::BuildInfo) {
+            Some(SectionData::BuildInfo(info)) => Some(info.clone()),
+            _ => None,
+        }
+    }
 }
 
 fn resource_to_metadata(
@@ -3,6 +3,7 @@
 #![allow(missing_docs)]
 
 mod decode;
+mod disasm;
 mod encode;
 mod encoder;
 mod format;
@@ -11,4 +12,5 @@ mod reader;
 mod util;
 mod validate;
 
+pub use disasm::{opcode_mnemonic, DisassembledInstruction, DisassembledPou, DisassemblySource};
 pub use format::*;
@@ -2,6 +2,8 @@
 
 #![allow(missing_docs)]
 
+pub mod schema;
+
 use std::path::{Path, PathBuf};
 
 use glob::Pattern;
@@ -10,15 +12,25 @@ use serde::Deserialize;
 use smol_str::SmolStr;
 
 use crate::error::RuntimeError;
+use crate::eval::ops::OverflowPolicy;
+use crate::eval::vm::JitSettings;
+use crate::event_store::{EventSeverity, EventStoreConfig};
 use crate::historian::{AlertRule, HistorianConfig, RecordingMode};
-use crate::io::{IoAddress, IoSafeState, IoSize};
+use crate::io::{
+    IoAddress, IoDriverRegistry, IoSafeState, IoSize, IoSymbol, IoSymbolMap, OutputSafeState,
+    ProcessDriverSpec, SafeOutput,
+};
+use crate::logging::{LogSinkConfig, SyslogSinkConfig};
+use crate::notify::{NotificationConfig, NotifyKind, SmtpConfig};
 use crate::opcua::{
     OpcUaMessageSecurityMode, OpcUaRuntimeConfig, OpcUaSecurityPolicy, OpcUaSecurityProfile,
 };
 use crate::simulation::SimulationConfig;
+use crate::task::{BoundsPolicy, BoundsSettings, SubrangePolicy, TaskCatchUp};
 use crate::value::Duration;
 use crate::value::Value;
-use crate::watchdog::{FaultPolicy, RetainMode, WatchdogAction, WatchdogPolicy};
+use crate::watchdog::{FaultPolicy, RetainMode, ShutdownHook, WatchdogAction, WatchdogPolicy};
+use crate::{RetainPolicy, RetentionRules};
 
 #[cfg(unix)]
 pub const SYSTEM_IO_CONFIG_PATH: &str = "/etc/trust/io.toml";
@@ -30,16 +42,33 @@ pub struct RuntimeConfig {
     pub bundle_version: u32,
     pub resource_name: SmolStr,
     pub cycle_interval: Duration,
+    pub task_catch_up: TaskCatchUp,
     pub control_endpoint: SmolStr,
     pub control_auth_token: Option<SmolStr>,
     pub control_debug_enabled: bool,
     pub control_mode: ControlMode,
+    pub control_pause: PausePolicy,
+    /// Cycles of `debug.history` kept per watched variable.
+    pub control_history_depth: usize,
+    pub control_limits: ServerLimits,
+    /// Send control errors as plain strings for clients that predate error codes.
+    pub control_legacy_errors: bool,
     pub log_level: SmolStr,
+    pub log_sinks: LogSinkConfig,
     pub retain_mode: RetainMode,
     pub retain_path: Option<PathBuf>,
     pub retain_save_interval: Duration,
+    /// Which retention classes survive warm and cold restarts.
+    pub retention: RetentionRules,
     pub watchdog: WatchdogPolicy,
     pub fault_policy: FaultPolicy,
+    /// Array index and string position handling, per task.
+    pub bounds: BoundsSettings,
+    /// Integer overflow behavior of arithmetic.
+    pub overflow: OverflowPolicy,
+    /// Writes outside the range of subrange-typed variables.
+    pub subrange: SubrangePolicy,
+    pub safety_state: OutputSafeState,
     pub web: WebConfig,
     pub tls: TlsConfig,
     pub deploy: DeployConfig,
@@ -47,6 +76,16 @@ pub struct RuntimeConfig {
     pub mesh: MeshConfig,
     pub observability: HistorianConfig,
     pub opcua: OpcUaRuntimeConfig,
+    pub jit: JitSettings,
+    pub notify: NotificationConfig,
+    pub redundancy: RedundancyConfig,
+    pub realtime: RealtimeConfig,
+    pub files: FilesConfig,
+    pub events: EventStoreConfig,
+    /// Program run for a bounded number of cycles before stop or restart.
+    pub shutdown: Option<ShutdownHook>,
+    /// Out-of-tree drivers from `[[runtime.io_drivers]]`, usable by name in io.toml.
+    pub io_drivers: Vec<ProcessDriverSpec>,
     pub tasks: Option<Vec<TaskOverride>>,
 }
 
@@ -74,6 +113,59 @@ pub struct WebConfig {
     pub listen: SmolStr,
     pub auth: WebAuthMode,
     pub tls: bool,
+    pub limits: ServerLimits,
+}
+
+/// Request limits for the control and web servers.
+///
+/// They keep a misbehaving client from flooding the JSON servers and taking CPU time away
+/// from the cyclic tasks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerLimits {
+    /// Largest accepted request (control line or HTTP body) in bytes.
+    pub max_request_bytes: usize,
+    /// Requests per second allowed per client, with bursts up to the same amount.
+    pub max_requests_per_sec: u32,
+    /// Open connections served at once (control sockets, web UI websockets).
+    pub max_connections: usize,
+}
+
+impl Default for ServerLimits {
+    fn default() -> Self {
+        Self {
+            max_request_bytes: 16 * 1024 * 1024,
+            max_requests_per_sec: 1000,
+            max_connections: 32,
+        }
+    }
+}
+
+impl ServerLimits {
+    fn parse(
+        prefix: &str,
+        max_request_bytes: Option<usize>,
+        max_requests_per_sec: Option<u32>,
+        max_connections: Option<usize>,
+    ) -> Result<Self, RuntimeError> {
+        let defaults = Self::default();
+        let limits = Self {
+            max_request_bytes: max_request_bytes.unwrap_or(defaults.max_request_bytes),
+            max_requests_per_sec: max_requests_per_sec.unwrap_or(defaults.max_requests_per_sec),
+            max_connections: max_connections.unwrap_or(defaults.max_connections),
+        };
+        for (key, value) in [
+            ("max_request_bytes", limits.max_request_bytes),
+            ("max_requests_per_sec", limits.max_requests_per_sec as usize),
+            ("max_connections", limits.max_connections),
+        ] {
+            if value == 0 {
+                return Err(RuntimeError::InvalidConfig(
+                    format!("{prefix}.{key} must be at least 1").into(),
+                ));
+            }
+        }
+        Ok(limits)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -114,6 +206,8 @@ pub struct TlsConfig {
 pub struct DeployConfig {
     pub require_signed: bool,
     pub keyring_path: Option<PathBuf>,
+    /// Seconds an activated remote deploy is watched for faults before it is kept.
+    pub probation_s: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -132,12 +226,146 @@ pub struct MeshConfig {
     pub auth_token: Option<SmolStr>,
     pub publish: Vec<SmolStr>,
     pub subscribe: IndexMap<SmolStr, SmolStr>,
+    /// Keyed like `subscribe` (`peer:variable`).
+    pub subscribe_qos: IndexMap<SmolStr, MeshSubscribeQos>,
+    /// Keyed by published variable name.
+    pub publish_qos: IndexMap<SmolStr, MeshPublishQos>,
+}
+
+/// What a subscription does once its peer has been silent for `timeout_ms`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeshTimeoutAction {
+    HoldLast,
+    Substitute(serde_json::Value),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshSubscribeQos {
+    pub timeout_ms: Option<u64>,
+    pub on_timeout: MeshTimeoutAction,
+}
+
+impl Default for MeshSubscribeQos {
+    fn default() -> Self {
+        Self {
+            timeout_ms: None,
+            on_timeout: MeshTimeoutAction::HoldLast,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshPublishQos {
+    /// Numeric changes up to this size are not sent before the next refresh.
+    pub deadband: f64,
+    pub min_interval_ms: u64,
+}
+
+impl Default for MeshPublishQos {
+    fn default() -> Self {
+        Self {
+            deadband: 0.0,
+            min_interval_ms: 1_000,
+        }
+    }
+}
+
+/// Role a redundant runtime prefers when both partners are healthy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedundancyPreference {
+    Primary,
+    Secondary,
+}
+
+impl RedundancyPreference {
+    fn parse(text: &str) -> Result<Self, RuntimeError> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "primary" => Ok(Self::Primary),
+            "secondary" => Ok(Self::Secondary),
+            _ => Err(RuntimeError::InvalidConfig(
+                format!("invalid runtime.redundancy.role '{text}'").into(),
+            )),
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Primary => "primary",
+            Self::Secondary => "secondary",
+        }
+    }
+}
+
+/// Hot-standby pairing with a partner runtime over the mesh channel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedundancyConfig {
+    pub enabled: bool,
+    pub role: RedundancyPreference,
+    /// Mesh listen address of the partner runtime.
+    pub partner: SmolStr,
+    pub heartbeat_ms: u64,
+    /// The standby takes over once the partner has been silent this long.
+    pub failover_timeout_ms: u64,
+    pub failover_on_fault: bool,
+    pub sync_interval_ms: u64,
+    /// Globals synced in addition to RETAIN variables and output-bound globals.
+    pub sync: Vec<SmolStr>,
+}
+
+impl Default for RedundancyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            role: RedundancyPreference::Primary,
+            partner: SmolStr::default(),
+            heartbeat_ms: 100,
+            failover_timeout_ms: 1_000,
+            failover_on_fault: true,
+            sync_interval_ms: 200,
+            sync: Vec::new(),
+        }
+    }
+}
+
+/// Real-time scheduling for the resource thread (Linux only).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RealtimeConfig {
+    /// `SCHED_FIFO` priority (1-99); `None` keeps the default scheduler.
+    pub priority: Option<u8>,
+    /// CPU cores the resource thread (and so every task) is pinned to.
+    pub cpu_affinity: Vec<usize>,
+    /// Lock current and future process memory with `mlockall`.
+    pub lock_memory: bool,
+}
+
+impl RealtimeConfig {
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.priority.is_some() || !self.cpu_affinity.is_empty() || self.lock_memory
+    }
+}
+
+/// Sandbox for file access from ST programs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilesConfig {
+    /// Directory inside the bundle that file function blocks are confined to.
+    pub data_dir: PathBuf,
+}
+
+impl Default for FilesConfig {
+    fn default() -> Self {
+        Self {
+            data_dir: PathBuf::from("data"),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct IoConfig {
     pub drivers: Vec<IoDriverConfig>,
     pub safe_state: IoSafeState,
+    pub map: IoSymbolMap,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -226,6 +454,19 @@ impl RuntimeBundle {
     }
 }
 
+impl RuntimeBundle {
+    /// Built-in drivers plus the process drivers declared in runtime.toml,
+    /// with relative commands resolved against the project folder.
+    #[must_use]
+    pub fn io_driver_registry(&self) -> IoDriverRegistry {
+        let mut registry = IoDriverRegistry::default_registry();
+        for spec in &self.runtime.io_drivers {
+            registry.register_process(spec.resolve(&self.root));
+        }
+        registry
+    }
+}
+
 impl RuntimeConfig {
     pub fn load(path: impl AsRef<Path>) -> Result<Self, RuntimeError> {
         let text = std::fs::read_to_string(path.as_ref())
@@ -308,6 +549,7 @@ struct BundleSection {
 struct ResourceSection {
     name: String,
     cycle_interval_ms: u64,
+    task_catch_up: Option<String>,
     tasks: Option<Vec<TaskSection>>,
 }
 
@@ -329,6 +571,7 @@ struct RuntimeSection {
     retain: RetainSection,
     watchdog: WatchdogSection,
     fault: FaultSection,
+    safety: Option<SafetySection>,
     web: Option<WebSection>,
     tls: Option<TlsSection>,
     deploy: Option<DeploySection>,
@@ -336,6 +579,14 @@ struct RuntimeSection {
     mesh: Option<MeshSection>,
     observability: Option<ObservabilitySection>,
     opcua: Option<OpcUaSection>,
+    jit: Option<JitSection>,
+    notify: Option<NotifySection>,
+    redundancy: Option<RedundancySection>,
+    realtime: Option<RealtimeSection>,
+    files: Option<FilesSection>,
+    events: Option<EventsSection>,
+    shutdown: Option<ShutdownSection>,
+    io_drivers: Option<Vec<ProcessDriverSection>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -345,6 +596,12 @@ struct ControlSection {
     auth_token: Option<String>,
     debug_enabled: Option<bool>,
     mode: Option<String>,
+    pause: Option<String>,
+    history_depth: Option<usize>,
+    max_request_bytes: Option<usize>,
+    max_requests_per_sec: Option<u32>,
+    max_connections: Option<usize>,
+    legacy_errors: Option<bool>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -365,10 +622,60 @@ impl ControlMode {
     }
 }
 
+/// Where a `pause` request stops the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PausePolicy {
+    /// Stop at the next statement, possibly mid-cycle (debugger stepping).
+    Immediate,
+    /// Finish the current cycle and hold at the boundary, so outputs are
+    /// always the committed result of a full scan.
+    CycleEnd,
+}
+
+impl PausePolicy {
+    fn parse(text: &str) -> Result<Self, RuntimeError> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "immediate" => Ok(Self::Immediate),
+            "cycle_end" | "cycle-end" => Ok(Self::CycleEnd),
+            _ => Err(RuntimeError::InvalidConfig(
+                format!("invalid runtime.control.pause '{text}'").into(),
+            )),
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Immediate => "immediate",
+            Self::CycleEnd => "cycle_end",
+        }
+    }
+
+    /// Production never leaves outputs half-updated; debug mode stops at once.
+    #[must_use]
+    pub fn default_for(mode: ControlMode) -> Self {
+        match mode {
+            ControlMode::Production => Self::CycleEnd,
+            ControlMode::Debug => Self::Immediate,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct LogSection {
     level: String,
+    stdout: Option<bool>,
+    journald: Option<bool>,
+    syslog: Option<SyslogSection>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SyslogSection {
+    address: String,
+    facility: Option<String>,
+    app_name: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -377,6 +684,8 @@ struct RetainSection {
     mode: String,
     path: Option<String>,
     save_interval_ms: u64,
+    unqualified: Option<String>,
+    persistent_on_cold: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -391,6 +700,23 @@ struct WatchdogSection {
 #[serde(deny_unknown_fields)]
 struct FaultSection {
     policy: String,
+    bounds: Option<String>,
+    task_bounds: Option<IndexMap<String, String>>,
+    overflow: Option<String>,
+    subrange: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SafetySection {
+    state: Option<SafetyStateSection>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SafetyStateSection {
+    default: Option<String>,
+    outputs: Option<IndexMap<String, String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -400,6 +726,9 @@ struct WebSection {
     listen: Option<String>,
     auth: Option<String>,
     tls: Option<bool>,
+    max_request_bytes: Option<usize>,
+    max_requests_per_sec: Option<u32>,
+    max_connections: Option<usize>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -417,6 +746,7 @@ struct TlsSection {
 struct DeploySection {
     require_signed: Option<bool>,
     keyring_path: Option<String>,
+    probation_s: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -428,6 +758,14 @@ struct DiscoverySection {
     interfaces: Option<Vec<String>>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JitSection {
+    enabled: Option<bool>,
+    warmup_cycles: Option<u64>,
+    hot_programs: Option<usize>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct MeshSection {
@@ -437,6 +775,23 @@ struct MeshSection {
     auth_token: Option<String>,
     publish: Option<Vec<String>>,
     subscribe: Option<IndexMap<String, String>>,
+    subscribe_qos: Option<IndexMap<String, MeshSubscribeQosSection>>,
+    publish_qos: Option<IndexMap<String, MeshPublishQosSection>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MeshSubscribeQosSection {
+    timeout_ms: Option<u64>,
+    on_timeout: Option<String>,
+    substitute: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MeshPublishQosSection {
+    deadband: Option<f64>,
+    min_interval_ms: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -464,6 +819,90 @@ struct AlertSection {
     hook: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NotifySection {
+    enabled: Option<bool>,
+    events: Option<Vec<String>>,
+    min_interval_s: Option<u64>,
+    recent_events: Option<usize>,
+    subject: Option<String>,
+    template: Option<String>,
+    webhook: Option<NotifyWebhookSection>,
+    smtp: Option<NotifySmtpSection>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NotifyWebhookSection {
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NotifySmtpSection {
+    server: String,
+    from: String,
+    to: Vec<String>,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RedundancySection {
+    enabled: Option<bool>,
+    role: Option<String>,
+    partner: Option<String>,
+    heartbeat_ms: Option<u64>,
+    failover_timeout_ms: Option<u64>,
+    failover_on_fault: Option<bool>,
+    sync_interval_ms: Option<u64>,
+    sync: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RealtimeSection {
+    priority: Option<u8>,
+    cpu_affinity: Option<Vec<usize>>,
+    lock_memory: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FilesSection {
+    data_dir: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EventsSection {
+    enabled: Option<bool>,
+    path: Option<String>,
+    min_severity: Option<String>,
+    max_age_s: Option<u64>,
+    max_bytes: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShutdownSection {
+    program: String,
+    cycles: Option<u32>,
+    timeout_ms: Option<u64>,
+    done: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProcessDriverSection {
+    name: String,
+    command: String,
+    args: Option<Vec<String>>,
+    timeout_ms: Option<u64>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct OpcUaSection {
@@ -494,6 +933,7 @@ struct IoSection {
     params: Option<toml::Value>,
     drivers: Option<Vec<IoDriverSection>>,
     safe_state: Option<Vec<IoSafeEntry>>,
+    map: Option<IndexMap<String, String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -537,6 +977,34 @@ impl RuntimeToml {
                 "runtime.log.level must not be empty".into(),
             ));
         }
+        let log_sinks = LogSinkConfig {
+            stdout: self.runtime.log.stdout.unwrap_or(true),
+            journald: self.runtime.log.journald.unwrap_or(false),
+            syslog: self
+                .runtime
+                .log
+                .syslog
+                .as_ref()
+                .map(|section| {
+                    let (transport, address) = SyslogSinkConfig::parse_address(&section.address)?;
+                    let facility = SyslogSinkConfig::parse_facility(
+                        section.facility.as_deref().unwrap_or("daemon"),
+                    )?;
+                    let app_name = section
+                        .app_name
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .unwrap_or("trust-runtime");
+                    Ok::<_, RuntimeError>(SyslogSinkConfig {
+                        transport,
+                        address,
+                        facility,
+                        app_name: SmolStr::new(app_name),
+                    })
+                })
+                .transpose()?,
+        };
         if self.runtime.retain.save_interval_ms == 0 {
             return Err(RuntimeError::InvalidConfig(
                 "runtime.retain.save_interval_ms must be >= 1".into(),
@@ -548,6 +1016,23 @@ impl RuntimeToml {
             ));
         }
         let retain_mode = RetainMode::parse(&self.runtime.retain.mode)?;
+        let retention = RetentionRules {
+            unqualified: match self.runtime.retain.unqualified.as_deref().map(str::trim) {
+                None => RetentionRules::default().unqualified,
+                Some(text) if text.eq_ignore_ascii_case("non_retain") => RetainPolicy::NonRetain,
+                Some(text) if text.eq_ignore_ascii_case("retain") => RetainPolicy::Retain,
+                Some(text) => {
+                    return Err(RuntimeError::InvalidConfig(
+                        format!("invalid runtime.retain.unqualified '{text}'").into(),
+                    ))
+                }
+            },
+            persistent_on_cold: self
+                .runtime
+                .retain
+                .persistent_on_cold
+                .unwrap_or(RetentionRules::default().persistent_on_cold),
+        };
         if matches!(retain_mode, RetainMode::File)
             && self
                 .runtime
@@ -562,6 +1047,55 @@ impl RuntimeToml {
         }
         let watchdog_action = WatchdogAction::parse(&self.runtime.watchdog.action)?;
         let fault_policy = FaultPolicy::parse(&self.runtime.fault.policy)?;
+        let overflow = self
+            .runtime
+            .fault
+            .overflow
+            .as_deref()
+            .map(OverflowPolicy::parse)
+            .transpose()?
+            .unwrap_or_default();
+        let subrange = self
+            .runtime
+            .fault
+            .subrange
+            .as_deref()
+            .map(SubrangePolicy::parse)
+            .transpose()?
+            .unwrap_or_default();
+        let bounds = BoundsSettings {
+            default: self
+                .runtime
+                .fault
+                .bounds
+                .as_deref()
+                .map(BoundsPolicy::parse)
+                .transpose()?
+                .unwrap_or_default(),
+            tasks: self
+                .runtime
+                .fault
+                .task_bounds
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(task, policy)| {
+                    let policy = BoundsPolicy::parse(&policy).map_err(|_| {
+                        RuntimeError::InvalidConfig(
+                            format!("runtime.fault.task_bounds.{task}: invalid bounds policy '{policy}'")
+                                .into(),
+                        )
+                    })?;
+                    Ok((SmolStr::new(task), policy))
+                })
+                .collect::<Result<_, RuntimeError>>()?,
+        };
+        let task_catch_up = self
+            .resource
+            .task_catch_up
+            .as_deref()
+            .map(TaskCatchUp::parse)
+            .transpose()?
+            .unwrap_or_default();
         let tasks = self
             .resource
             .tasks
@@ -614,6 +1148,30 @@ impl RuntimeToml {
         });
         let control_mode =
             ControlMode::parse(self.runtime.control.mode.as_deref().unwrap_or("production"))?;
+        let control_pause = match self.runtime.control.pause.as_deref() {
+            Some(text) => PausePolicy::parse(text)?,
+            None => PausePolicy::default_for(control_mode),
+        };
+        let control_history_depth = self
+            .runtime
+            .control
+            .history_depth
+            .unwrap_or(crate::debug::DEFAULT_HISTORY_DEPTH);
+        if !(1..=crate::debug::MAX_HISTORY_DEPTH).contains(&control_history_depth) {
+            return Err(RuntimeError::InvalidConfig(
+                format!(
+                    "runtime.control.history_depth must be between 1 and {}",
+                    crate::debug::MAX_HISTORY_DEPTH
+                )
+                .into(),
+            ));
+        }
+        let control_limits = ServerLimits::parse(
+            "runtime.control",
+            self.runtime.control.max_request_bytes,
+            self.runtime.control.max_requests_per_sec,
+            self.runtime.control.max_connections,
+        )?;
         let debug_enabled = match self.runtime.control.debug_enabled {
             Some(value) => value,
             None => matches!(control_mode, ControlMode::Debug),
@@ -628,6 +1186,9 @@ impl RuntimeToml {
             listen: Some("0.0.0.0:8080".into()),
             auth: Some("local".into()),
             tls: Some(false),
+            max_request_bytes: None,
+            max_requests_per_sec: None,
+            max_connections: None,
         });
         if web_section
             .listen
@@ -647,6 +1208,12 @@ impl RuntimeToml {
         let web_enabled = web_section.enabled.unwrap_or(true);
         let web_listen = web_section.listen.unwrap_or_else(|| "0.0.0.0:8080".into());
         let web_tls = web_section.tls.unwrap_or(false);
+        let web_limits = ServerLimits::parse(
+            "runtime.web",
+            web_section.max_request_bytes,
+            web_section.max_requests_per_sec,
+            web_section.max_connections,
+        )?;
 
         let tls_section = self.runtime.tls.unwrap_or(TlsSection {
             mode: Some("disabled".into()),
@@ -692,6 +1259,7 @@ impl RuntimeToml {
         let deploy_section = self.runtime.deploy.unwrap_or(DeploySection {
             require_signed: Some(false),
             keyring_path: None,
+            probation_s: Some(30),
         });
         if deploy_section
             .keyring_path
@@ -730,6 +1298,26 @@ impl RuntimeToml {
             ));
         }
 
+        let jit_defaults = JitSettings::default();
+        let jit = self
+            .runtime
+            .jit
+            .map_or(jit_defaults, |section| JitSettings {
+                enabled: section.enabled.unwrap_or(jit_defaults.enabled),
+                warmup_cycles: section.warmup_cycles.unwrap_or(jit_defaults.warmup_cycles),
+                hot_programs: section.hot_programs.unwrap_or(jit_defaults.hot_programs),
+            });
+        if jit.hot_programs == 0 {
+            return Err(RuntimeError::InvalidConfig(
+                "runtime.jit.hot_programs must be >= 1".into(),
+            ));
+        }
+
+        let notify = match self.runtime.notify {
+            Some(section) => section.into_config()?,
+            None => NotificationConfig::default(),
+        };
+
         let mesh_section = self.runtime.mesh.unwrap_or(MeshSection {
             enabled: Some(false),
             listen: Some("0.0.0.0:5200".into()),
@@ -737,6 +1325,8 @@ impl RuntimeToml {
             auth_token: None,
             publish: None,
             subscribe: None,
+            subscribe_qos: None,
+            publish_qos: None,
         });
         if mesh_section
             .listen
@@ -760,6 +1350,133 @@ impl RuntimeToml {
                 "runtime.mesh.tls must be true when runtime.tls.require_remote=true and runtime.mesh.listen is remote".into(),
             ));
         }
+        let mesh_publish = mesh_section
+            .publish
+            .unwrap_or_default()
+            .into_iter()
+            .map(SmolStr::new)
+            .collect::<Vec<_>>();
+        let mesh_subscribe = mesh_section
+            .subscribe
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(k, v)| (SmolStr::new(k), SmolStr::new(v)))
+            .collect::<IndexMap<_, _>>();
+        let mesh_subscribe_qos = mesh_section
+            .subscribe_qos
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(remote, qos)| {
+                if !mesh_subscribe.contains_key(remote.as_str()) {
+                    return Err(RuntimeError::InvalidConfig(
+                        format!(
+                            "runtime.mesh.subscribe_qos '{remote}' is not in \
+                             runtime.mesh.subscribe"
+                        )
+                        .into(),
+                    ));
+                }
+                if qos.timeout_ms == Some(0) {
+                    return Err(RuntimeError::InvalidConfig(
+                        format!("runtime.mesh.subscribe_qos '{remote}' timeout_ms must be >= 1")
+                            .into(),
+                    ));
+                }
+                let on_timeout = match (
+                    qos.on_timeout.as_deref().map(str::trim).unwrap_or("hold"),
+                    qos.substitute,
+                ) {
+                    ("hold", None) => MeshTimeoutAction::HoldLast,
+                    ("substitute", Some(value)) => MeshTimeoutAction::Substitute(value),
+                    ("hold", Some(_)) => {
+                        return Err(RuntimeError::InvalidConfig(
+                            format!(
+                                "runtime.mesh.subscribe_qos '{remote}' substitute requires \
+                                 on_timeout = 'substitute'"
+                            )
+                            .into(),
+                        ))
+                    }
+                    ("substitute", None) => {
+                        return Err(RuntimeError::InvalidConfig(
+                            format!("runtime.mesh.subscribe_qos '{remote}' requires substitute")
+                                .into(),
+                        ))
+                    }
+                    (other, _) => {
+                        return Err(RuntimeError::InvalidConfig(
+                            format!(
+                                "invalid runtime.mesh.subscribe_qos '{remote}' on_timeout '{other}'"
+                            )
+                            .into(),
+                        ))
+                    }
+                };
+                Ok((
+                    SmolStr::new(remote),
+                    MeshSubscribeQos {
+                        timeout_ms: qos.timeout_ms,
+                        on_timeout,
+                    },
+                ))
+            })
+            .collect::<Result<IndexMap<_, _>, RuntimeError>>()?;
+        let publish_defaults = MeshPublishQos::default();
+        let mesh_publish_qos = mesh_section
+            .publish_qos
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, qos)| {
+                if !mesh_publish.iter().any(|entry| entry == name.as_str()) {
+                    return Err(RuntimeError::InvalidConfig(
+                        format!("runtime.mesh.publish_qos '{name}' is not in runtime.mesh.publish")
+                            .into(),
+                    ));
+                }
+                let deadband = qos.deadband.unwrap_or(publish_defaults.deadband);
+                if !deadband.is_finite() || deadband < 0.0 {
+                    return Err(RuntimeError::InvalidConfig(
+                        format!("runtime.mesh.publish_qos '{name}' deadband must be >= 0").into(),
+                    ));
+                }
+                Ok((
+                    SmolStr::new(name),
+                    MeshPublishQos {
+                        deadband,
+                        min_interval_ms: qos
+                            .min_interval_ms
+                            .unwrap_or(publish_defaults.min_interval_ms),
+                    },
+                ))
+            })
+            .collect::<Result<IndexMap<_, _>, RuntimeError>>()?;
+
+        let redundancy = match self.runtime.redundancy {
+            Some(section) => section.into_config(mesh_enabled)?,
+            None => RedundancyConfig::default(),
+        };
+        let realtime = match self.runtime.realtime {
+            Some(section) => section.into_config()?,
+            None => RealtimeConfig::default(),
+        };
+        let files = match self.runtime.files {
+            Some(section) => section.into_config()?,
+            None => FilesConfig::default(),
+        };
+        let events = match self.runtime.events {
+            Some(section) => section.into_config()?,
+            None => EventStoreConfig::default(),
+        };
+        let shutdown = self
+            .runtime
+            .shutdown
+            .map(ShutdownSection::into_config)
+            .transpose()?;
+        let io_drivers = process_drivers_into_config(self.runtime.io_drivers.unwrap_or_default())?;
+        let safety_state = match self.runtime.safety.and_then(|section| section.state) {
+            Some(section) => section.into_config()?,
+            None => OutputSafeState::default(),
+        };
 
         let observability_section = self.runtime.observability.unwrap_or(ObservabilitySection {
             enabled: Some(false),
@@ -1051,27 +1768,39 @@ impl RuntimeToml {
             bundle_version: self.bundle.version,
             resource_name: SmolStr::new(self.resource.name),
             cycle_interval: Duration::from_millis(self.resource.cycle_interval_ms as i64),
+            task_catch_up,
             control_endpoint: SmolStr::new(self.runtime.control.endpoint),
             control_auth_token,
             control_debug_enabled: debug_enabled,
             control_mode,
+            control_pause,
+            control_history_depth,
+            control_limits,
+            control_legacy_errors: self.runtime.control.legacy_errors.unwrap_or(false),
             log_level: SmolStr::new(self.runtime.log.level),
+            log_sinks,
             retain_mode,
             retain_path: self.runtime.retain.path.map(PathBuf::from),
             retain_save_interval: Duration::from_millis(
                 self.runtime.retain.save_interval_ms as i64,
             ),
+            retention,
             watchdog: WatchdogPolicy {
                 enabled: self.runtime.watchdog.enabled,
                 timeout: Duration::from_millis(self.runtime.watchdog.timeout_ms as i64),
                 action: watchdog_action,
             },
             fault_policy,
+            bounds,
+            overflow,
+            subrange,
+            safety_state,
             web: WebConfig {
                 enabled: web_enabled,
                 listen: SmolStr::new(web_listen),
                 auth: web_auth,
                 tls: web_tls,
+                limits: web_limits,
             },
             tls: TlsConfig {
                 mode: tls_mode,
@@ -1090,6 +1819,7 @@ impl RuntimeToml {
                         Some(PathBuf::from(path))
                     }
                 }),
+                probation_s: deploy_section.probation_s.unwrap_or(30),
             },
             discovery: DiscoveryConfig {
                 enabled: discovery_section.enabled.unwrap_or(true),
@@ -1118,18 +1848,10 @@ impl RuntimeToml {
                         Some(SmolStr::new(trimmed))
                     }
                 }),
-                publish: mesh_section
-                    .publish
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(SmolStr::new)
-                    .collect(),
-                subscribe: mesh_section
-                    .subscribe
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|(k, v)| (SmolStr::new(k), SmolStr::new(v)))
-                    .collect(),
+                publish: mesh_publish,
+                subscribe: mesh_subscribe,
+                subscribe_qos: mesh_subscribe_qos,
+                publish_qos: mesh_publish_qos,
             },
             observability: HistorianConfig {
                 enabled: observability_section.enabled.unwrap_or(false),
@@ -1143,25 +1865,397 @@ impl RuntimeToml {
                 alerts,
             },
             opcua,
+            jit,
+            notify,
+            redundancy,
+            realtime,
+            files,
+            events,
+            shutdown,
+            io_drivers,
             tasks,
         })
     }
 }
 
-impl IoToml {
-    fn into_config(self) -> Result<IoConfig, RuntimeError> {
-        let legacy_driver = self
-            .io
-            .driver
-            .as_deref()
-            .map(str::trim)
-            .filter(|text| !text.is_empty())
-            .map(ToOwned::to_owned);
-        let legacy_params = self
-            .io
-            .params
-            .unwrap_or_else(|| toml::Value::Table(toml::map::Map::new()));
-        let explicit_drivers = self.io.drivers.unwrap_or_default();
+impl RedundancySection {
+    fn into_config(self, mesh_enabled: bool) -> Result<RedundancyConfig, RuntimeError> {
+        let defaults = RedundancyConfig::default();
+        let enabled = self.enabled.unwrap_or(true);
+        let role = match self.role.as_deref() {
+            Some(role) => RedundancyPreference::parse(role)?,
+            None => defaults.role,
+        };
+        let partner = self.partner.unwrap_or_default().trim().to_string();
+        let heartbeat_ms = self.heartbeat_ms.unwrap_or(defaults.heartbeat_ms);
+        let failover_timeout_ms = self
+            .failover_timeout_ms
+            .unwrap_or(defaults.failover_timeout_ms);
+        let sync_interval_ms = self.sync_interval_ms.unwrap_or(defaults.sync_interval_ms);
+        if enabled {
+            if !mesh_enabled {
+                return Err(RuntimeError::InvalidConfig(
+                    "runtime.redundancy requires runtime.mesh.enabled=true".into(),
+                ));
+            }
+            if partner.parse::<std::net::SocketAddr>().is_err() {
+                return Err(RuntimeError::InvalidConfig(
+                    format!("runtime.redundancy.partner must be ip:port, got '{partner}'").into(),
+                ));
+            }
+            if heartbeat_ms == 0 || sync_interval_ms == 0 {
+                return Err(RuntimeError::InvalidConfig(
+                    "runtime.redundancy.heartbeat_ms and sync_interval_ms must be >= 1".into(),
+                ));
+            }
+            if failover_timeout_ms < heartbeat_ms.saturating_mul(3) {
+                return Err(RuntimeError::InvalidConfig(
+                    "runtime.redundancy.failover_timeout_ms must be at least 3x heartbeat_ms"
+                        .into(),
+                ));
+            }
+        }
+        let sync = self
+            .sync
+            .unwrap_or_default()
+            .iter()
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .map(SmolStr::new)
+            .collect();
+        Ok(RedundancyConfig {
+            enabled,
+            role,
+            partner: SmolStr::new(partner),
+            heartbeat_ms,
+            failover_timeout_ms,
+            failover_on_fault: self.failover_on_fault.unwrap_or(defaults.failover_on_fault),
+            sync_interval_ms,
+            sync,
+        })
+    }
+}
+
+impl RealtimeSection {
+    fn into_config(self) -> Result<RealtimeConfig, RuntimeError> {
+        if let Some(priority) = self.priority {
+            if !(1..=99).contains(&priority) {
+                return Err(RuntimeError::InvalidConfig(
+                    format!("runtime.realtime.priority must be 1-99, got {priority}").into(),
+                ));
+            }
+        }
+        let mut cpu_affinity = self.cpu_affinity.unwrap_or_default();
+        cpu_affinity.sort_unstable();
+        cpu_affinity.dedup();
+        if let Some(cpu) = cpu_affinity.iter().find(|cpu| **cpu >= 1024) {
+            return Err(RuntimeError::InvalidConfig(
+                format!("runtime.realtime.cpu_affinity core {cpu} is out of range").into(),
+            ));
+        }
+        Ok(RealtimeConfig {
+            priority: self.priority,
+            cpu_affinity,
+            lock_memory: self.lock_memory.unwrap_or(false),
+        })
+    }
+}
+
+impl FilesSection {
+    fn into_config(self) -> Result<FilesConfig, RuntimeError> {
+        let Some(data_dir) = self.data_dir else {
+            return Ok(FilesConfig::default());
+        };
+        let path = PathBuf::from(data_dir.trim());
+        let inside_bundle = path
+            .components()
+            .all(|component| matches!(component, std::path::Component::Normal(_)));
+        if path.as_os_str().is_empty() || !inside_bundle {
+            return Err(RuntimeError::InvalidConfig(
+                format!(
+                    "runtime.files.data_dir must be a relative path inside the bundle, got '{data_dir}'"
+                )
+                .into(),
+            ));
+        }
+        Ok(FilesConfig { data_dir: path })
+    }
+}
+
+impl ShutdownSection {
+    fn into_config(self) -> Result<ShutdownHook, RuntimeError> {
+        let program = self.program.trim();
+        if program.is_empty() {
+            return Err(RuntimeError::InvalidConfig(
+                "runtime.shutdown.program must not be empty".into(),
+            ));
+        }
+        let cycles = self.cycles.unwrap_or(10);
+        if cycles == 0 {
+            return Err(RuntimeError::InvalidConfig(
+                "runtime.shutdown.cycles must be >= 1".into(),
+            ));
+        }
+        let timeout_ms = self.timeout_ms.unwrap_or(5_000);
+        if timeout_ms == 0 {
+            return Err(RuntimeError::InvalidConfig(
+                "runtime.shutdown.timeout_ms must be >= 1".into(),
+            ));
+        }
+        let done = self
+            .done
+            .map(|done| done.trim().to_string())
+            .filter(|done| !done.is_empty())
+            .map(SmolStr::new);
+        Ok(ShutdownHook {
+            program: SmolStr::new(program),
+            cycles,
+            timeout: Duration::from_millis(i64::try_from(timeout_ms).unwrap_or(i64::MAX)),
+            done,
+        })
+    }
+}
+
+impl EventsSection {
+    fn into_config(self) -> Result<EventStoreConfig, RuntimeError> {
+        let defaults = EventStoreConfig::default();
+        let path = match self.path {
+            Some(path) if path.trim().is_empty() => {
+                return Err(RuntimeError::InvalidConfig(
+                    "runtime.events.path must not be empty".into(),
+                ))
+            }
+            Some(path) => PathBuf::from(path.trim()),
+            None => defaults.path,
+        };
+        let min_severity = match self.min_severity.as_deref() {
+            Some(text) => EventSeverity::parse(text).ok_or_else(|| {
+                RuntimeError::InvalidConfig(
+                    format!("invalid runtime.events.min_severity '{text}'").into(),
+                )
+            })?,
+            None => defaults.min_severity,
+        };
+        let max_age = match self.max_age_s {
+            Some(0) => {
+                return Err(RuntimeError::InvalidConfig(
+                    "runtime.events.max_age_s must be >= 1".into(),
+                ))
+            }
+            Some(secs) => std::time::Duration::from_secs(secs),
+            None => defaults.max_age,
+        };
+        let max_bytes = self.max_bytes.unwrap_or(defaults.max_bytes);
+        if max_bytes < 4096 {
+            return Err(RuntimeError::InvalidConfig(
+                "runtime.events.max_bytes must be >= 4096".into(),
+            ));
+        }
+        Ok(EventStoreConfig {
+            enabled: self.enabled.unwrap_or(true),
+            path,
+            min_severity,
+            max_age,
+            max_bytes,
+        })
+    }
+}
+
+fn process_drivers_into_config(
+    sections: Vec<ProcessDriverSection>,
+) -> Result<Vec<ProcessDriverSpec>, RuntimeError> {
+    let builtin = IoDriverRegistry::default_registry();
+    let mut drivers: Vec<ProcessDriverSpec> = Vec::with_capacity(sections.len());
+    for section in sections {
+        let name = section.name.trim();
+        if name.is_empty() {
+            return Err(RuntimeError::InvalidConfig(
+                "runtime.io_drivers name must not be empty".into(),
+            ));
+        }
+        if builtin.is_builtin(name) || name.eq_ignore_ascii_case("none") {
+            return Err(RuntimeError::InvalidConfig(
+                format!("runtime.io_drivers '{name}' shadows a built-in driver").into(),
+            ));
+        }
+        if drivers
+            .iter()
+            .any(|driver| driver.name.eq_ignore_ascii_case(name))
+        {
+            return Err(RuntimeError::InvalidConfig(
+                format!("runtime.io_drivers '{name}' is declared more than once").into(),
+            ));
+        }
+        if section.command.trim().is_empty() {
+            return Err(RuntimeError::InvalidConfig(
+                format!("runtime.io_drivers '{name}' command must not be empty").into(),
+            ));
+        }
+        let timeout_ms = section.timeout_ms.unwrap_or(1000);
+        if !(1..=60_000).contains(&timeout_ms) {
+            return Err(RuntimeError::InvalidConfig(
+                format!("runtime.io_drivers '{name}' timeout_ms must be 1-60000, got {timeout_ms}")
+                    .into(),
+            ));
+        }
+        drivers.push(ProcessDriverSpec {
+            name: SmolStr::new(name),
+            command: PathBuf::from(section.command.trim()),
+            args: section.args.unwrap_or_default(),
+            timeout: std::time::Duration::from_millis(timeout_ms),
+        });
+    }
+    Ok(drivers)
+}
+
+impl SafetyStateSection {
+    fn into_config(self) -> Result<OutputSafeState, RuntimeError> {
+        let default = match self
+            .default
+            .as_deref()
+            .map(|text| text.trim().to_ascii_lowercase())
+        {
+            None => SafeOutput::Hold,
+            Some(text) if text == "hold" => SafeOutput::Hold,
+            Some(text) if text == "zero" => SafeOutput::Zero,
+            Some(text) => {
+                return Err(RuntimeError::InvalidConfig(
+                    format!("invalid runtime.safety.state.default '{text}' (expected hold/zero)")
+                        .into(),
+                ))
+            }
+        };
+        let mut outputs = Vec::new();
+        for (address_text, value) in self.outputs.unwrap_or_default() {
+            let address = IoAddress::parse(&address_text)?;
+            if address.area != crate::memory::IoArea::Output || address.wildcard {
+                return Err(RuntimeError::InvalidConfig(
+                    format!(
+                        "runtime.safety.state.outputs '{address_text}' must be a single %Q address"
+                    )
+                    .into(),
+                ));
+            }
+            if outputs.iter().any(|(existing, _)| *existing == address) {
+                return Err(RuntimeError::InvalidConfig(
+                    format!("runtime.safety.state.outputs lists '{address_text}' twice").into(),
+                ));
+            }
+            let output = match value.trim().to_ascii_lowercase().as_str() {
+                "hold" => SafeOutput::Hold,
+                "zero" => SafeOutput::Zero,
+                _ => SafeOutput::Value(parse_io_value(&value, address.size)?),
+            };
+            outputs.push((address, output));
+        }
+        Ok(OutputSafeState { default, outputs })
+    }
+}
+
+impl NotifySection {
+    fn into_config(self) -> Result<NotificationConfig, RuntimeError> {
+        let defaults = NotificationConfig::default();
+        let events = match self.events {
+            Some(events) => events
+                .iter()
+                .map(|event| {
+                    NotifyKind::parse(event).ok_or_else(|| {
+                        RuntimeError::InvalidConfig(
+                            format!("invalid runtime.notify.events entry '{event}'").into(),
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => defaults.events.clone(),
+        };
+        let webhook = match self.webhook {
+            Some(webhook) => {
+                let url = webhook.url.trim();
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(RuntimeError::InvalidConfig(
+                        format!("runtime.notify.webhook.url must be http(s), got '{url}'").into(),
+                    ));
+                }
+                Some(SmolStr::new(url))
+            }
+            None => None,
+        };
+        let smtp = match self.smtp {
+            Some(smtp) => {
+                let server = smtp.server.trim();
+                if server
+                    .rsplit_once(':')
+                    .is_none_or(|(host, port)| host.is_empty() || port.parse::<u16>().is_err())
+                {
+                    return Err(RuntimeError::InvalidConfig(
+                        format!("runtime.notify.smtp.server must be host:port, got '{server}'")
+                            .into(),
+                    ));
+                }
+                if smtp.from.trim().is_empty() {
+                    return Err(RuntimeError::InvalidConfig(
+                        "runtime.notify.smtp.from must not be empty".into(),
+                    ));
+                }
+                let to = smtp
+                    .to
+                    .iter()
+                    .map(|to| to.trim())
+                    .filter(|to| !to.is_empty())
+                    .map(SmolStr::new)
+                    .collect::<Vec<_>>();
+                if to.is_empty() {
+                    return Err(RuntimeError::InvalidConfig(
+                        "runtime.notify.smtp.to must not be empty".into(),
+                    ));
+                }
+                Some(SmtpConfig {
+                    server: SmolStr::new(server),
+                    from: SmolStr::new(smtp.from.trim()),
+                    to,
+                    username: smtp.username.map(SmolStr::new),
+                    password: smtp.password.map(SmolStr::new),
+                })
+            }
+            None => None,
+        };
+        let enabled = self.enabled.unwrap_or(true);
+        if enabled && webhook.is_none() && smtp.is_none() {
+            return Err(RuntimeError::InvalidConfig(
+                "runtime.notify requires [runtime.notify.webhook] and/or [runtime.notify.smtp]"
+                    .into(),
+            ));
+        }
+        let min_interval_s = self
+            .min_interval_s
+            .unwrap_or(defaults.min_interval.as_secs());
+        Ok(NotificationConfig {
+            enabled,
+            events,
+            min_interval: std::time::Duration::from_secs(min_interval_s),
+            recent_events: self.recent_events.unwrap_or(defaults.recent_events),
+            subject: self.subject.map(SmolStr::new).unwrap_or(defaults.subject),
+            template: self.template.map(SmolStr::new).unwrap_or(defaults.template),
+            webhook,
+            smtp,
+        })
+    }
+}
+
+impl IoToml {
+    fn into_config(self) -> Result<IoConfig, RuntimeError> {
+        let legacy_driver = self
+            .io
+            .driver
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(ToOwned::to_owned);
+        let legacy_params = self
+            .io
+            .params
+            .unwrap_or_else(|| toml::Value::Table(toml::map::Map::new()));
+        let explicit_drivers = self.io.drivers.unwrap_or_default();
 
         if legacy_driver.is_some() && !explicit_drivers.is_empty() {
             return Err(RuntimeError::InvalidConfig(
@@ -1214,13 +2308,52 @@ impl IoToml {
         if let Some(entries) = self.io.safe_state {
             for entry in entries {
                 let address = IoAddress::parse(&entry.address)?;
+                if address.area != crate::memory::IoArea::Output || address.wildcard {
+                    return Err(RuntimeError::InvalidConfig(
+                        format!(
+                            "io.safe_state address '{}' must be a single %Q address",
+                            entry.address
+                        )
+                        .into(),
+                    ));
+                }
                 let value = parse_io_value(&entry.value, address.size)?;
                 safe_state.outputs.push((address, value));
             }
         }
+        let mut map = IoSymbolMap::default();
+        for (name, address_text) in self.io.map.unwrap_or_default() {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(RuntimeError::InvalidConfig(
+                    "io.map symbol names must not be empty".into(),
+                ));
+            }
+            if map
+                .symbols
+                .iter()
+                .any(|symbol| symbol.name.eq_ignore_ascii_case(name))
+            {
+                return Err(RuntimeError::InvalidConfig(
+                    format!("io.map symbol '{name}' is mapped more than once").into(),
+                ));
+            }
+            let address = IoAddress::parse(&address_text)?;
+            if address.wildcard {
+                return Err(RuntimeError::InvalidConfig(
+                    format!("io.map '{name}' must map to a concrete address, not '{address_text}'")
+                        .into(),
+                ));
+            }
+            map.symbols.push(IoSymbol {
+                name: SmolStr::new(name),
+                address,
+            });
+        }
         Ok(IoConfig {
             drivers,
             safe_state,
+            map,
         })
     }
 }
@@ -1289,7 +2422,11 @@ fn listen_is_remote(listen: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{validate_io_toml_text, validate_runtime_toml_text};
+    use super::{
+        parse_io_toml_from_text, parse_runtime_toml_from_text, validate_io_toml_text,
+        validate_runtime_toml_text, IoAddress, OverflowPolicy, RetainPolicy, RetentionRules,
+        SubrangePolicy,
+    };
 
     fn runtime_toml() -> String {
         r#"
@@ -1367,6 +2504,224 @@ params = {}
             .contains("resource.cycle_interval_ms must be >= 1"));
     }
 
+    #[test]
+    fn runtime_schema_parses_task_catch_up_policy() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert_eq!(config.task_catch_up, crate::task::TaskCatchUp::Skip);
+        let text = runtime_toml().replace(
+            "cycle_interval_ms = 100",
+            "cycle_interval_ms = 100\ntask_catch_up = \"burst\"",
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").unwrap();
+        assert_eq!(config.task_catch_up, crate::task::TaskCatchUp::Burst);
+        let text = runtime_toml().replace(
+            "cycle_interval_ms = 100",
+            "cycle_interval_ms = 100\ntask_catch_up = \"later\"",
+        );
+        let err = validate_runtime_toml_text(&text).expect_err("catch-up policy should fail");
+        assert!(err
+            .to_string()
+            .contains("invalid task catch-up policy 'later'"));
+    }
+
+    #[test]
+    fn runtime_schema_parses_bounds_policy() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert_eq!(config.bounds, crate::task::BoundsSettings::default());
+        let text = runtime_toml().replace(
+            "policy = \"halt\"",
+            "policy = \"halt\"\nbounds = \"clamp\"\n\n[runtime.fault.task_bounds]\nFast = \"fault\"",
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").unwrap();
+        assert_eq!(config.bounds.default, crate::task::BoundsPolicy::Clamp);
+        assert_eq!(
+            config.bounds.for_task("fast"),
+            crate::task::BoundsPolicy::Fault
+        );
+        assert_eq!(
+            config.bounds.for_task("Slow"),
+            crate::task::BoundsPolicy::Clamp
+        );
+        let text = runtime_toml().replace(
+            "policy = \"halt\"",
+            "policy = \"halt\"\n\n[runtime.fault.task_bounds]\nFast = \"wrap\"",
+        );
+        let err = validate_runtime_toml_text(&text).expect_err("bounds policy should fail");
+        assert!(err
+            .to_string()
+            .contains("runtime.fault.task_bounds.Fast: invalid bounds policy 'wrap'"));
+    }
+
+    #[test]
+    fn runtime_schema_parses_overflow_policy() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert_eq!(config.overflow, OverflowPolicy::Wrap);
+        let text = runtime_toml().replace(
+            "policy = \"halt\"",
+            "policy = \"halt\"\noverflow = \"saturate\"",
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").unwrap();
+        assert_eq!(config.overflow, OverflowPolicy::Saturate);
+        let text = runtime_toml().replace(
+            "policy = \"halt\"",
+            "policy = \"halt\"\noverflow = \"trap\"",
+        );
+        let err = validate_runtime_toml_text(&text).expect_err("overflow policy should fail");
+        assert!(err.to_string().contains("invalid overflow policy 'trap'"));
+    }
+
+    #[test]
+    fn runtime_schema_parses_subrange_policy() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert_eq!(config.subrange, SubrangePolicy::Off);
+        let text = runtime_toml().replace(
+            "policy = \"halt\"",
+            "policy = \"halt\"\nsubrange = \"clamp\"",
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").unwrap();
+        assert_eq!(config.subrange, SubrangePolicy::Clamp);
+        let text = runtime_toml().replace(
+            "policy = \"halt\"",
+            "policy = \"halt\"\nsubrange = \"wrap\"",
+        );
+        let err = validate_runtime_toml_text(&text).expect_err("subrange policy should fail");
+        assert!(err.to_string().contains("invalid subrange policy 'wrap'"));
+    }
+
+    #[test]
+    fn runtime_schema_parses_pause_policy() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert_eq!(config.control_pause, super::PausePolicy::CycleEnd);
+        let text = runtime_toml().replace("mode = \"production\"", "mode = \"debug\"");
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").unwrap();
+        assert_eq!(config.control_pause, super::PausePolicy::Immediate);
+        let text = runtime_toml().replace(
+            "mode = \"production\"",
+            "mode = \"debug\"\npause = \"cycle_end\"",
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").unwrap();
+        assert_eq!(config.control_pause, super::PausePolicy::CycleEnd);
+        let text = runtime_toml().replace(
+            "mode = \"production\"",
+            "mode = \"production\"\npause = \"soon\"",
+        );
+        let err = validate_runtime_toml_text(&text).expect_err("pause policy should fail");
+        assert!(err
+            .to_string()
+            .contains("invalid runtime.control.pause 'soon'"));
+    }
+
+    #[test]
+    fn runtime_schema_parses_history_depth() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert_eq!(
+            config.control_history_depth,
+            crate::debug::DEFAULT_HISTORY_DEPTH
+        );
+        let text = runtime_toml().replace(
+            "mode = \"production\"",
+            "mode = \"production\"\nhistory_depth = 500",
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").unwrap();
+        assert_eq!(config.control_history_depth, 500);
+        let text = runtime_toml().replace(
+            "mode = \"production\"",
+            "mode = \"production\"\nhistory_depth = 0",
+        );
+        let err = validate_runtime_toml_text(&text).expect_err("zero depth should fail");
+        assert!(err.to_string().contains("runtime.control.history_depth"));
+    }
+
+    #[test]
+    fn runtime_schema_parses_server_limits() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert_eq!(config.control_limits, super::ServerLimits::default());
+        assert_eq!(config.web.limits, super::ServerLimits::default());
+        let text = runtime_toml()
+            .replace(
+                "mode = \"production\"",
+                "mode = \"production\"\nmax_request_bytes = 4096\nmax_requests_per_sec = 20",
+            )
+            .replace("[runtime.web]", "[runtime.web]\nmax_connections = 4");
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").unwrap();
+        assert_eq!(config.control_limits.max_request_bytes, 4096);
+        assert_eq!(config.control_limits.max_requests_per_sec, 20);
+        assert_eq!(config.web.limits.max_connections, 4);
+        let text =
+            runtime_toml().replace("[runtime.web]", "[runtime.web]\nmax_requests_per_sec = 0");
+        let err = validate_runtime_toml_text(&text).expect_err("zero rate should fail");
+        assert!(err
+            .to_string()
+            .contains("runtime.web.max_requests_per_sec must be at least 1"));
+    }
+
+    #[test]
+    fn runtime_schema_parses_legacy_errors() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert!(!config.control_legacy_errors);
+        let text = runtime_toml().replace(
+            "mode = \"production\"",
+            "mode = \"production\"\nlegacy_errors = true",
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").unwrap();
+        assert!(config.control_legacy_errors);
+    }
+
+    #[test]
+    fn runtime_schema_parses_safety_state() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert!(config.safety_state.is_hold_all());
+
+        let text = format!(
+            "{}\n[runtime.safety.state]\ndefault = \"zero\"\noutputs = {{ \"%QX0.1\" = \"hold\", \"%QW2\" = \"0x10\", \"%QX0.2\" = \"TRUE\" }}\n",
+            runtime_toml()
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").unwrap();
+        let state = config.safety_state;
+        assert_eq!(state.default, super::SafeOutput::Zero);
+        assert_eq!(state.outputs.len(), 3);
+        assert_eq!(state.outputs[0].1, super::SafeOutput::Hold);
+        assert_eq!(
+            state.outputs[1].1,
+            super::SafeOutput::Value(super::Value::Word(16))
+        );
+        assert_eq!(
+            state.outputs[2].1,
+            super::SafeOutput::Value(super::Value::Bool(true))
+        );
+
+        for (table, expected) in [
+            (
+                "default = \"off\"",
+                "invalid runtime.safety.state.default 'off'",
+            ),
+            (
+                "outputs = { \"%IX0.0\" = \"zero\" }",
+                "'%IX0.0' must be a single %Q address",
+            ),
+            (
+                "outputs = { \"%QX0.0\" = \"maybe\" }",
+                "invalid BOOL safe_state value",
+            ),
+        ] {
+            let text = format!("{}\n[runtime.safety.state]\n{table}\n", runtime_toml());
+            let err = validate_runtime_toml_text(&text).expect_err("safety state should fail");
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn runtime_schema_rejects_empty_jit_selection() {
+        let text = format!(
+            "{}\n[runtime.jit]\nenabled = true\nhot_programs = 0\n",
+            runtime_toml()
+        );
+        let err = validate_runtime_toml_text(&text).expect_err("jit selection should fail");
+        assert!(err
+            .to_string()
+            .contains("runtime.jit.hot_programs must be >= 1"));
+    }
+
     #[test]
     fn runtime_schema_requires_control_auth_for_tcp_endpoints() {
         let text = runtime_toml().replace(
@@ -1391,6 +2746,15 @@ params = {}
         ));
     }
 
+    #[test]
+    fn runtime_schema_parses_deploy_probation_window() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert_eq!(config.deploy.probation_s, 30);
+        let text = format!("{}\n[runtime.deploy]\nprobation_s = 5\n", runtime_toml());
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").expect("probation");
+        assert_eq!(config.deploy.probation_s, 5);
+    }
+
     #[test]
     fn runtime_schema_requires_tls_credentials_when_tls_enabled() {
         let text = format!(
@@ -1491,6 +2855,273 @@ params = {}
         validate_runtime_toml_text(&text).expect("opcua secure profile should be valid");
     }
 
+    #[test]
+    fn runtime_schema_parses_mesh_qos() {
+        let text = format!(
+            "{}\n[runtime.mesh.subscribe_qos.\"peer:Temp\"]\ntimeout_ms = 3000\n\
+             on_timeout = \"substitute\"\nsubstitute = 20.5\n\n\
+             [runtime.mesh.publish_qos.Speed]\ndeadband = 0.5\nmin_interval_ms = 250\n",
+            runtime_toml().replace(
+                "publish = []\nsubscribe = {}\n",
+                "publish = [\"Speed\"]\nsubscribe = { \"peer:Temp\" = \"RemoteTemp\" }\n",
+            )
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").expect("mesh qos");
+        let qos = &config.mesh.subscribe_qos["peer:Temp"];
+        assert_eq!(qos.timeout_ms, Some(3000));
+        assert_eq!(
+            qos.on_timeout,
+            super::MeshTimeoutAction::Substitute(serde_json::json!(20.5))
+        );
+        let publish = config.mesh.publish_qos["Speed"];
+        assert_eq!(publish.deadband, 0.5);
+        assert_eq!(publish.min_interval_ms, 250);
+
+        let missing = text.replace("substitute = 20.5\n", "");
+        let err = validate_runtime_toml_text(&missing).expect_err("substitute required");
+        assert!(err.to_string().contains("requires substitute"));
+        let unknown = text.replace(
+            "[runtime.mesh.publish_qos.Speed]",
+            "[runtime.mesh.publish_qos.Other]",
+        );
+        let err = validate_runtime_toml_text(&unknown).expect_err("unknown publication");
+        assert!(err.to_string().contains("is not in runtime.mesh.publish"));
+    }
+
+    #[test]
+    fn runtime_schema_parses_process_drivers() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").expect("base");
+        assert!(config.io_drivers.is_empty());
+        let text = format!(
+            "{}\n[[runtime.io_drivers]]\nname = \"acme-io\"\ncommand = \"drivers/acme-io\"\nargs = [\"--bus\", \"1\"]\ntimeout_ms = 250\n",
+            runtime_toml()
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").expect("io_drivers");
+        assert_eq!(config.io_drivers.len(), 1);
+        let driver = &config.io_drivers[0];
+        assert_eq!(driver.name.as_str(), "acme-io");
+        assert_eq!(driver.args, vec!["--bus".to_string(), "1".to_string()]);
+        assert_eq!(driver.timeout, std::time::Duration::from_millis(250));
+        assert_eq!(
+            driver.resolve(std::path::Path::new("/srv/plc")).command,
+            std::path::PathBuf::from("/srv/plc/drivers/acme-io")
+        );
+
+        for (name, message) in [("gpio", "shadows a built-in"), ("", "must not be empty")] {
+            let text = format!(
+                "{}\n[[runtime.io_drivers]]\nname = \"{name}\"\ncommand = \"acme\"\n",
+                runtime_toml()
+            );
+            let err = parse_runtime_toml_from_text(&text, "runtime.toml").unwrap_err();
+            assert!(err.to_string().contains(message), "{err}");
+        }
+    }
+
+    #[test]
+    fn runtime_schema_parses_realtime_section() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert!(!config.realtime.is_enabled());
+        let text = format!(
+            "{}\n[runtime.realtime]\npriority = 80\ncpu_affinity = [3, 2, 3]\nlock_memory = true\n",
+            runtime_toml()
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").expect("realtime");
+        assert_eq!(config.realtime.priority, Some(80));
+        assert_eq!(config.realtime.cpu_affinity, vec![2, 3]);
+        assert!(config.realtime.lock_memory);
+
+        let text = format!("{}\n[runtime.realtime]\npriority = 0\n", runtime_toml());
+        let err = validate_runtime_toml_text(&text).expect_err("priority range should fail");
+        assert!(err
+            .to_string()
+            .contains("runtime.realtime.priority must be 1-99"));
+    }
+
+    #[test]
+    fn runtime_schema_parses_files_section() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert_eq!(config.files.data_dir, std::path::PathBuf::from("data"));
+        let text = format!(
+            "{}\n[runtime.files]\ndata_dir = \"recipes/line1\"\n",
+            runtime_toml()
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").expect("files");
+        assert_eq!(
+            config.files.data_dir,
+            std::path::PathBuf::from("recipes/line1")
+        );
+
+        for data_dir in ["../outside", "/var/data", ""] {
+            let text = format!(
+                "{}\n[runtime.files]\ndata_dir = \"{data_dir}\"\n",
+                runtime_toml()
+            );
+            let err = validate_runtime_toml_text(&text).expect_err("data_dir should fail");
+            assert!(err.to_string().contains("runtime.files.data_dir"));
+        }
+    }
+
+    #[test]
+    fn runtime_schema_parses_events_section() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert!(!config.events.enabled);
+        let text = format!(
+            "{}\n[runtime.events]\nmin_severity = \"warning\"\nmax_age_s = 3600\nmax_bytes = 65536\n",
+            runtime_toml()
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").expect("events");
+        assert!(config.events.enabled);
+        assert_eq!(
+            config.events.min_severity,
+            crate::event_store::EventSeverity::Warning
+        );
+        assert_eq!(config.events.max_age, std::time::Duration::from_secs(3600));
+        assert_eq!(config.events.max_bytes, 65_536);
+
+        for (line, message) in [
+            ("min_severity = \"loud\"", "runtime.events.min_severity"),
+            ("max_age_s = 0", "runtime.events.max_age_s"),
+            ("max_bytes = 100", "runtime.events.max_bytes"),
+        ] {
+            let text = format!("{}\n[runtime.events]\n{line}\n", runtime_toml());
+            let err = validate_runtime_toml_text(&text).expect_err("events should fail");
+            assert!(err.to_string().contains(message));
+        }
+    }
+
+    #[test]
+    fn runtime_schema_parses_shutdown_section() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert!(config.shutdown.is_none());
+        let text = format!(
+            "{}\n[runtime.shutdown]\nprogram = \"Park\"\ntimeout_ms = 2000\ndone = \"Park.Done\"\n",
+            runtime_toml()
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").expect("shutdown");
+        let hook = config.shutdown.expect("shutdown hook");
+        assert_eq!(hook.program, "Park");
+        assert_eq!(hook.cycles, 10);
+        assert_eq!(hook.timeout, crate::value::Duration::from_millis(2_000));
+        assert_eq!(hook.done.as_deref(), Some("Park.Done"));
+
+        for (line, message) in [
+            ("program = \" \"", "runtime.shutdown.program"),
+            ("program = \"Park\"\ncycles = 0", "runtime.shutdown.cycles"),
+            (
+                "program = \"Park\"\ntimeout_ms = 0",
+                "runtime.shutdown.timeout_ms",
+            ),
+        ] {
+            let text = format!("{}\n[runtime.shutdown]\n{line}\n", runtime_toml());
+            let err = validate_runtime_toml_text(&text).expect_err("shutdown should fail");
+            assert!(err.to_string().contains(message));
+        }
+    }
+
+    #[test]
+    fn runtime_schema_parses_retention_rules() {
+        let config = parse_runtime_toml_from_text(&runtime_toml(), "runtime.toml").unwrap();
+        assert_eq!(config.retention, RetentionRules::default());
+        let text = runtime_toml().replace(
+            "save_interval_ms = 1000\n",
+            "save_interval_ms = 1000\nunqualified = \"retain\"\npersistent_on_cold = false\n",
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").expect("retention");
+        assert_eq!(config.retention.unqualified, RetainPolicy::Retain);
+        assert!(!config.retention.persistent_on_cold);
+
+        let text = runtime_toml().replace(
+            "save_interval_ms = 1000\n",
+            "save_interval_ms = 1000\nunqualified = \"persistent\"\n",
+        );
+        let err = validate_runtime_toml_text(&text).expect_err("unqualified should fail");
+        assert!(err.to_string().contains("runtime.retain.unqualified"));
+    }
+
+    #[test]
+    fn runtime_schema_parses_redundancy_section() {
+        let text = format!(
+            "{}\n[runtime.redundancy]\nrole = \"secondary\"\npartner = \"10.0.0.2:5200\"\n\
+             failover_timeout_ms = 500\nsync = [\"Recipe\"]\n",
+            runtime_toml().replace(
+                "[runtime.mesh]\nenabled = false",
+                "[runtime.mesh]\nenabled = true"
+            )
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").expect("redundancy");
+        let redundancy = &config.redundancy;
+        assert!(redundancy.enabled);
+        assert_eq!(redundancy.role, super::RedundancyPreference::Secondary);
+        assert_eq!(redundancy.partner, "10.0.0.2:5200");
+        assert_eq!(redundancy.heartbeat_ms, 100);
+        assert_eq!(redundancy.failover_timeout_ms, 500);
+        assert!(redundancy.failover_on_fault);
+        assert_eq!(redundancy.sync, vec!["Recipe"]);
+
+        let short = text.replace("failover_timeout_ms = 500", "failover_timeout_ms = 200");
+        let err = validate_runtime_toml_text(&short).expect_err("timeout too short");
+        assert!(err.to_string().contains("at least 3x heartbeat_ms"));
+        let without_mesh = format!(
+            "{}\n[runtime.redundancy]\npartner = \"10.0.0.2:5200\"\n",
+            runtime_toml()
+        );
+        let err = validate_runtime_toml_text(&without_mesh).expect_err("mesh required");
+        assert!(err.to_string().contains("requires runtime.mesh.enabled"));
+    }
+
+    #[test]
+    fn runtime_schema_parses_notify_section() {
+        let text = format!(
+            "{}\n[runtime.notify]\nevents = [\"fault\", \"driver\"]\nmin_interval_s = 300\n\n\
+             [runtime.notify.webhook]\nurl = \"https://hooks.local/plc\"\n\n\
+             [runtime.notify.smtp]\nserver = \"relay.local:25\"\nfrom = \"plc@local\"\n\
+             to = [\"ops@local\"]\n",
+            runtime_toml()
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").expect("notify config");
+        assert!(config.notify.enabled);
+        assert_eq!(
+            config.notify.events,
+            vec![
+                crate::notify::NotifyKind::Fault,
+                crate::notify::NotifyKind::Driver
+            ]
+        );
+        assert_eq!(config.notify.min_interval.as_secs(), 300);
+        assert_eq!(
+            config.notify.webhook.as_deref(),
+            Some("https://hooks.local/plc")
+        );
+        assert_eq!(config.notify.smtp.expect("smtp").to, vec!["ops@local"]);
+
+        let missing_target = format!("{}\n[runtime.notify]\nenabled = true\n", runtime_toml());
+        let err = validate_runtime_toml_text(&missing_target).expect_err("target required");
+        assert!(err.to_string().contains("runtime.notify requires"));
+    }
+
+    #[test]
+    fn runtime_schema_parses_syslog_and_journald_log_sinks() {
+        let text = runtime_toml().replace(
+            "[runtime.log]\nlevel = \"info\"\n",
+            "[runtime.log]\nlevel = \"info\"\nstdout = false\njournald = true\n\n[runtime.log.syslog]\naddress = \"tcp://logs.local:601\"\nfacility = \"local0\"\n",
+        );
+        let config = parse_runtime_toml_from_text(&text, "runtime.toml").expect("log sinks");
+        assert!(!config.log_sinks.stdout);
+        assert!(config.log_sinks.journald);
+        let syslog = config.log_sinks.syslog.expect("syslog sink");
+        assert_eq!(syslog.transport, crate::logging::SyslogTransport::Tcp);
+        assert_eq!(syslog.address, "logs.local:601");
+        assert_eq!(syslog.facility, 16);
+        assert_eq!(syslog.app_name, "trust-runtime");
+
+        let text = runtime_toml().replace(
+            "[runtime.log]\nlevel = \"info\"\n",
+            "[runtime.log]\nlevel = \"info\"\n\n[runtime.log.syslog]\naddress = \"logs.local:514\"\n",
+        );
+        let err = validate_runtime_toml_text(&text).expect_err("syslog scheme required");
+        assert!(err.to_string().contains("runtime.log.syslog.address"));
+    }
+
     #[test]
     fn io_schema_rejects_unknown_keys() {
         let text = io_toml().replace("params = {}", "params = {}\nunknown = true");
@@ -1505,6 +3136,54 @@ params = {}
         assert!(err.to_string().contains("io.params must be a table"));
     }
 
+    #[test]
+    fn io_schema_rejects_input_safe_state_address() {
+        let text = format!(
+            "{}\nsafe_state = [{{ address = \"%IX0.0\", value = \"FALSE\" }}]\n",
+            io_toml()
+        );
+        let err = validate_io_toml_text(&text).expect_err("input safe_state should fail");
+        assert!(err.to_string().contains("must be a single %Q address"));
+    }
+
+    #[test]
+    fn io_schema_parses_symbol_map() {
+        let text = format!(
+            "{}\n[io.map]\nStartButton = \"%IX0.0\"\n\"P1.Speed\" = \"%QW2\"\n",
+            io_toml()
+        );
+        let config = parse_io_toml_from_text(&text, "io.toml").expect("parse io.toml");
+        let symbols = config
+            .map
+            .symbols
+            .iter()
+            .map(|symbol| (symbol.name.as_str(), symbol.address.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            symbols,
+            vec![
+                ("StartButton", IoAddress::parse("%IX0.0").unwrap()),
+                ("P1.Speed", IoAddress::parse("%QW2").unwrap()),
+            ]
+        );
+
+        for (entries, expected) in [
+            ("StartButton = \"%I*\"", "must map to a concrete address"),
+            (
+                "StartButton = \"%IX0.0\"\nstartbutton = \"%IX0.1\"",
+                "is mapped more than once",
+            ),
+            ("StartButton = \"%ZX0.0\"", "invalid I/O address"),
+        ] {
+            let text = format!("{}\n[io.map]\n{entries}\n", io_toml());
+            let err = validate_io_toml_text(&text).expect_err("invalid io.map should fail");
+            assert!(
+                err.to_string().contains(expected),
+                "expected '{expected}' in '{err}'"
+            );
+        }
+    }
+
     #[test]
     fn io_schema_accepts_multiple_drivers() {
         let text = r#"
@@ -0,0 +1,1032 @@
+//! Key schema of `runtime.toml` and `hmi.toml` for editor tooling.
+//!
+//! The deserializers in [`crate::config`] and [`crate::hmi`] remain the source of truth
+//! for validation; this table only describes the keys so the language server can complete
+//! and document them. Keep it in step with the `*Section` structs when adding settings.
+
+use self::ConfigValueKind::{Any, Array, Bool, Float, Integer, String as Str};
+
+/// Value type of a setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigValueKind {
+    String,
+    Integer,
+    Float,
+    Bool,
+    Array,
+    Table,
+    Any,
+}
+
+impl ConfigValueKind {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::Bool => "boolean",
+            Self::Array => "array",
+            Self::Table => "table",
+            Self::Any => "any",
+        }
+    }
+}
+
+/// One key of a table.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigKey {
+    pub name: &'static str,
+    pub kind: ConfigValueKind,
+    pub required: bool,
+    /// Accepted values of an enumerated string (or of each array entry).
+    pub values: &'static [&'static str],
+    pub doc: &'static str,
+}
+
+/// One table. `path` segments of `*` match any name, e.g. a mesh remote.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigTable {
+    pub path: &'static str,
+    /// Declared as an array of tables (`[[path]]`).
+    pub array: bool,
+    /// Keys are user-chosen names (maps such as `runtime.mesh.subscribe`).
+    pub open: bool,
+    pub doc: &'static str,
+    pub keys: &'static [ConfigKey],
+}
+
+/// Tables of one configuration file.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigSchema {
+    pub file: &'static str,
+    pub tables: &'static [ConfigTable],
+}
+
+impl ConfigSchema {
+    /// Table declared at `path` (segments without quotes).
+    #[must_use]
+    pub fn table(&self, path: &[&str]) -> Option<&ConfigTable> {
+        self.tables.iter().find(|table| {
+            let mut segments = table.path.split('.').filter(|segment| !segment.is_empty());
+            path.iter().all(|part| {
+                segments
+                    .next()
+                    .is_some_and(|segment| segment == "*" || segment == *part)
+            }) && segments.next().is_none()
+        })
+    }
+
+    /// Tables directly below `path`.
+    pub fn children<'a>(&'a self, path: &'a [&'a str]) -> impl Iterator<Item = &'a ConfigTable> {
+        self.tables.iter().filter(move |table| {
+            let segments = table
+                .path
+                .split('.')
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<_>>();
+            segments.len() == path.len() + 1
+                && segments
+                    .iter()
+                    .zip(path)
+                    .all(|(segment, part)| *segment == "*" || segment == part)
+        })
+    }
+}
+
+impl ConfigTable {
+    #[must_use]
+    pub fn key(&self, name: &str) -> Option<&ConfigKey> {
+        self.keys.iter().find(|key| key.name == name)
+    }
+
+    /// Last segment of the path.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.path.rsplit('.').next().unwrap_or(self.path)
+    }
+}
+
+const fn key(
+    name: &'static str,
+    kind: ConfigValueKind,
+    required: bool,
+    doc: &'static str,
+) -> ConfigKey {
+    ConfigKey {
+        name,
+        kind,
+        required,
+        values: &[],
+        doc,
+    }
+}
+
+const fn choice(
+    name: &'static str,
+    required: bool,
+    values: &'static [&'static str],
+    doc: &'static str,
+) -> ConfigKey {
+    ConfigKey {
+        name,
+        kind: ConfigValueKind::String,
+        required,
+        values,
+        doc,
+    }
+}
+
+const fn table(path: &'static str, doc: &'static str, keys: &'static [ConfigKey]) -> ConfigTable {
+    ConfigTable {
+        path,
+        array: false,
+        open: false,
+        doc,
+        keys,
+    }
+}
+
+const fn table_array(
+    path: &'static str,
+    doc: &'static str,
+    keys: &'static [ConfigKey],
+) -> ConfigTable {
+    ConfigTable {
+        path,
+        array: true,
+        open: false,
+        doc,
+        keys,
+    }
+}
+
+const fn map(path: &'static str, doc: &'static str) -> ConfigTable {
+    ConfigTable {
+        path,
+        array: false,
+        open: true,
+        doc,
+        keys: &[],
+    }
+}
+
+/// `runtime.toml` in a project folder.
+pub const RUNTIME_TOML: ConfigSchema = ConfigSchema {
+    file: "runtime.toml",
+    tables: &[
+        table(
+            "bundle",
+            "Bundle format metadata.",
+            &[key(
+                "version",
+                Integer,
+                true,
+                "Bundle format version. Must be `1`.",
+            )],
+        ),
+        table(
+            "resource",
+            "The resource this runtime executes.",
+            &[
+                key(
+                    "name",
+                    Str,
+                    true,
+                    "Resource name reported to tools and the mesh.",
+                ),
+                key(
+                    "cycle_interval_ms",
+                    Integer,
+                    true,
+                    "Base scan cycle in milliseconds.",
+                ),
+                choice(
+                    "task_catch_up",
+                    false,
+                    &["skip", "burst"],
+                    "What a periodic task does after missed activations: `skip` drops them \
+                     (default), `burst` runs them back to back.",
+                ),
+            ],
+        ),
+        table_array(
+            "resource.tasks",
+            "Task overrides. Each entry replaces the task of the same name.",
+            &[
+                key("name", Str, true, "Task name."),
+                key(
+                    "interval_ms",
+                    Integer,
+                    true,
+                    "Task interval in milliseconds.",
+                ),
+                key("priority", Integer, true, "Task priority (0 is highest)."),
+                key("programs", Array, true, "Programs the task runs, in order."),
+                key(
+                    "single",
+                    Str,
+                    false,
+                    "BOOL variable whose rising edge triggers the task (SINGLE input).",
+                ),
+            ],
+        ),
+        table("runtime", "Runtime services.", &[]),
+        table(
+            "runtime.control",
+            "Control endpoint used by the CLI, the IDE, and the web UI.",
+            &[
+                key(
+                    "endpoint",
+                    Str,
+                    true,
+                    "Control endpoint, `unix:///path.sock` or `tcp://host:port`.",
+                ),
+                key(
+                    "auth_token",
+                    Str,
+                    false,
+                    "Token clients must present. Required for TCP endpoints.",
+                ),
+                key(
+                    "debug_enabled",
+                    Bool,
+                    false,
+                    "Allow debug requests (breakpoints, stepping, forcing).",
+                ),
+                choice(
+                    "mode",
+                    false,
+                    &["production", "debug"],
+                    "`production` (default) or `debug`; debug mode enables debug requests.",
+                ),
+                choice(
+                    "pause",
+                    false,
+                    &["immediate", "cycle_end"],
+                    "Where `pause` stops: `cycle_end` (production default) finishes the scan, \
+                     `immediate` (debug default) stops at the next statement.",
+                ),
+                key(
+                    "history_depth",
+                    Integer,
+                    false,
+                    "Cycles of variable history kept for `debug.history`.",
+                ),
+                key(
+                    "max_request_bytes",
+                    Integer,
+                    false,
+                    "Largest accepted request in bytes (default 16 MiB).",
+                ),
+                key(
+                    "max_requests_per_sec",
+                    Integer,
+                    false,
+                    "Requests per second allowed per client (default 1000).",
+                ),
+                key(
+                    "max_connections",
+                    Integer,
+                    false,
+                    "Concurrent connections (default 32).",
+                ),
+                key(
+                    "legacy_errors",
+                    Bool,
+                    false,
+                    "Send errors as plain strings instead of structured error objects.",
+                ),
+            ],
+        ),
+        table(
+            "runtime.log",
+            "Runtime logging.",
+            &[
+                choice(
+                    "level",
+                    true,
+                    &["error", "warn", "info", "debug", "trace"],
+                    "Minimum level logged.",
+                ),
+                key(
+                    "stdout",
+                    Bool,
+                    false,
+                    "Log to standard output (default true).",
+                ),
+                key("journald", Bool, false, "Log to the systemd journal."),
+            ],
+        ),
+        table(
+            "runtime.log.syslog",
+            "Forward logs to a syslog server.",
+            &[
+                key(
+                    "address",
+                    Str,
+                    true,
+                    "Syslog server, `host:port` (UDP) or `tcp://host:port`.",
+                ),
+                key(
+                    "facility",
+                    Str,
+                    false,
+                    "Syslog facility, e.g. `daemon` (default) or `local0`..`local7`.",
+                ),
+                key(
+                    "app_name",
+                    Str,
+                    false,
+                    "Application name in syslog records.",
+                ),
+            ],
+        ),
+        table(
+            "runtime.retain",
+            "Retain storage and restart retention rules.",
+            &[
+                choice(
+                    "mode",
+                    true,
+                    &["none", "file"],
+                    "`file` persists RETAIN variables to `path`; `none` keeps them in memory.",
+                ),
+                key(
+                    "path",
+                    Str,
+                    false,
+                    "Retain file, relative to the bundle. Required when `mode = \"file\"`.",
+                ),
+                key(
+                    "save_interval_ms",
+                    Integer,
+                    true,
+                    "Minimum time between retain saves while values change.",
+                ),
+                choice(
+                    "unqualified",
+                    false,
+                    &["non_retain", "retain"],
+                    "How variables without RETAIN/NON_RETAIN/PERSISTENT are handled on restart.",
+                ),
+                key(
+                    "persistent_on_cold",
+                    Bool,
+                    false,
+                    "Keep PERSISTENT variables across cold restarts (default true).",
+                ),
+            ],
+        ),
+        table(
+            "runtime.watchdog",
+            "Cycle watchdog.",
+            &[
+                key("enabled", Bool, true, "Enable the watchdog."),
+                key(
+                    "timeout_ms",
+                    Integer,
+                    true,
+                    "Longest allowed cycle in milliseconds.",
+                ),
+                choice(
+                    "action",
+                    true,
+                    &["halt", "safe_halt", "restart"],
+                    "What a watchdog trip does: `halt`, `safe_halt` (apply safe outputs, then \
+                     halt), or `restart` (warm restart).",
+                ),
+            ],
+        ),
+        table(
+            "runtime.fault",
+            "Runtime fault handling.",
+            &[
+                choice(
+                    "policy",
+                    true,
+                    &["halt", "safe_halt", "restart"],
+                    "What a runtime fault does: `halt`, `safe_halt` (apply safe outputs, then \
+                     halt), or `restart` (warm restart).",
+                ),
+                choice(
+                    "bounds",
+                    false,
+                    &["fault", "clamp"],
+                    "Out-of-range array indices and string positions: `fault` (default) or \
+                     `clamp` to the nearest valid element.",
+                ),
+                choice(
+                    "overflow",
+                    false,
+                    &["wrap", "saturate", "fault"],
+                    "Integer arithmetic overflow: `wrap` (default), `saturate` to the type's \
+                     limits, or `fault`.",
+                ),
+                choice(
+                    "subrange",
+                    false,
+                    &["off", "fault", "clamp"],
+                    "Writes outside a subrange type's range: `off` (default, unchecked), \
+                     `fault`, or `clamp` to the nearest bound.",
+                ),
+            ],
+        ),
+        map(
+            "runtime.fault.task_bounds",
+            "Bounds policy per task name, e.g. `Slow = \"clamp\"`.",
+        ),
+        table("runtime.safety", "Safe output state.", &[]),
+        table(
+            "runtime.safety.state",
+            "Outputs applied on `safe_halt`.",
+            &[choice(
+                "default",
+                false,
+                &["hold", "zero"],
+                "Outputs not listed in `outputs`: `hold` the last value (default) or `zero`.",
+            )],
+        ),
+        map(
+            "runtime.safety.state.outputs",
+            "Safe value per output address, e.g. `\"%QX0.0\" = \"FALSE\"`.",
+        ),
+        table(
+            "runtime.web",
+            "Built-in web UI and REST API.",
+            &[
+                key("enabled", Bool, false, "Serve the web UI."),
+                key("listen", Str, false, "Listen address, `host:port`."),
+                choice(
+                    "auth",
+                    false,
+                    &["local", "token"],
+                    "`local` trusts loopback clients; `token` requires the control token.",
+                ),
+                key("tls", Bool, false, "Serve HTTPS using `[runtime.tls]`."),
+                key(
+                    "max_request_bytes",
+                    Integer,
+                    false,
+                    "Largest accepted request in bytes.",
+                ),
+                key(
+                    "max_requests_per_sec",
+                    Integer,
+                    false,
+                    "Requests per second allowed per client.",
+                ),
+                key("max_connections", Integer, false, "Concurrent connections."),
+            ],
+        ),
+        table(
+            "runtime.tls",
+            "Certificates for the web server and the mesh.",
+            &[
+                choice(
+                    "mode",
+                    false,
+                    &["disabled", "self-managed", "provisioned"],
+                    "`disabled` (default), `self-managed` (generated certificate), or \
+                     `provisioned` (certificate files below).",
+                ),
+                key("cert_path", Str, false, "Certificate chain (PEM)."),
+                key("key_path", Str, false, "Private key (PEM)."),
+                key("ca_path", Str, false, "CA bundle used to verify peers."),
+                key(
+                    "require_remote",
+                    Bool,
+                    false,
+                    "Refuse to listen on non-loopback addresses without TLS.",
+                ),
+            ],
+        ),
+        table(
+            "runtime.deploy",
+            "Bundle deployment.",
+            &[
+                key(
+                    "require_signed",
+                    Bool,
+                    false,
+                    "Accept only bundles signed by a key in `keyring_path`.",
+                ),
+                key(
+                    "keyring_path",
+                    Str,
+                    false,
+                    "Directory of trusted public keys.",
+                ),
+                key(
+                    "probation_s",
+                    Integer,
+                    false,
+                    "Seconds a new deployment runs before it is confirmed (default 30).",
+                ),
+            ],
+        ),
+        table(
+            "runtime.discovery",
+            "mDNS discovery.",
+            &[
+                key("enabled", Bool, false, "Advertise and browse for runtimes."),
+                key("service_name", Str, false, "Advertised instance name."),
+                key("advertise", Bool, false, "Advertise this runtime."),
+                key("interfaces", Array, false, "Network interfaces to use."),
+            ],
+        ),
+        table(
+            "runtime.mesh",
+            "Variable exchange with other runtimes.",
+            &[
+                key("enabled", Bool, false, "Enable the mesh."),
+                key("listen", Str, false, "Listen address, `host:port`."),
+                key("tls", Bool, false, "Use TLS for mesh connections."),
+                key("auth_token", Str, false, "Token peers must present."),
+                key("publish", Array, false, "Variables published to peers."),
+            ],
+        ),
+        map(
+            "runtime.mesh.subscribe",
+            "Remote variable (`runtime:Variable`) to local variable.",
+        ),
+        map(
+            "runtime.mesh.subscribe_qos",
+            "Quality-of-service per subscribed remote variable.",
+        ),
+        table(
+            "runtime.mesh.subscribe_qos.*",
+            "Quality of service of one subscription.",
+            &[
+                key(
+                    "timeout_ms",
+                    Integer,
+                    false,
+                    "Time without updates before the value is stale.",
+                ),
+                choice(
+                    "on_timeout",
+                    false,
+                    &["hold", "substitute"],
+                    "Stale value handling: `hold` the last value or `substitute`.",
+                ),
+                key(
+                    "substitute",
+                    Any,
+                    false,
+                    "Value used when `on_timeout = \"substitute\"`.",
+                ),
+            ],
+        ),
+        map(
+            "runtime.mesh.publish_qos",
+            "Quality-of-service per published variable.",
+        ),
+        table(
+            "runtime.mesh.publish_qos.*",
+            "Quality of service of one published variable.",
+            &[
+                key(
+                    "deadband",
+                    Float,
+                    false,
+                    "Smallest change that is published.",
+                ),
+                key(
+                    "min_interval_ms",
+                    Integer,
+                    false,
+                    "Minimum time between updates.",
+                ),
+            ],
+        ),
+        table(
+            "runtime.observability",
+            "Variable history sampling and alerts.",
+            &[
+                key("enabled", Bool, false, "Record variable history."),
+                key("sample_interval_ms", Integer, false, "Sampling interval."),
+                choice(
+                    "mode",
+                    false,
+                    &["all", "allowlist"],
+                    "Record `all` variables or only those in `include`.",
+                ),
+                key(
+                    "include",
+                    Array,
+                    false,
+                    "Variables recorded in `allowlist` mode.",
+                ),
+                key(
+                    "history_path",
+                    Str,
+                    false,
+                    "History file, relative to the bundle.",
+                ),
+                key(
+                    "max_entries",
+                    Integer,
+                    false,
+                    "Samples kept in the history file.",
+                ),
+                key(
+                    "prometheus_enabled",
+                    Bool,
+                    false,
+                    "Serve Prometheus metrics.",
+                ),
+                key(
+                    "prometheus_path",
+                    Str,
+                    false,
+                    "HTTP path of the metrics endpoint.",
+                ),
+            ],
+        ),
+        table_array(
+            "runtime.observability.alerts",
+            "Threshold alerts on recorded variables.",
+            &[
+                key("name", Str, true, "Alert name."),
+                key("variable", Str, true, "Variable watched."),
+                key(
+                    "above",
+                    Float,
+                    false,
+                    "Alert when the value is above this limit.",
+                ),
+                key(
+                    "below",
+                    Float,
+                    false,
+                    "Alert when the value is below this limit.",
+                ),
+                key(
+                    "debounce_samples",
+                    Integer,
+                    false,
+                    "Samples the condition must hold before the alert fires.",
+                ),
+                key(
+                    "hook",
+                    Str,
+                    false,
+                    "Webhook URL called when the alert fires.",
+                ),
+            ],
+        ),
+        table(
+            "runtime.opcua",
+            "OPC UA server.",
+            &[
+                key("enabled", Bool, false, "Serve OPC UA."),
+                key("listen", Str, false, "Listen address, `host:port`."),
+                key("endpoint_path", Str, false, "Endpoint path."),
+                key(
+                    "namespace_uri",
+                    Str,
+                    false,
+                    "Namespace URI of exposed variables.",
+                ),
+                key(
+                    "publish_interval_ms",
+                    Integer,
+                    false,
+                    "Value publish interval.",
+                ),
+                key(
+                    "max_nodes",
+                    Integer,
+                    false,
+                    "Largest number of exposed nodes.",
+                ),
+                key("expose", Array, false, "Variables exposed as nodes."),
+                choice(
+                    "security_policy",
+                    false,
+                    &["none", "basic256sha256", "aes128sha256rsaoaep"],
+                    "Security policy.",
+                ),
+                choice(
+                    "security_mode",
+                    false,
+                    &["none", "sign", "sign_and_encrypt"],
+                    "Message security mode.",
+                ),
+                key("allow_anonymous", Bool, false, "Accept anonymous sessions."),
+                key(
+                    "username",
+                    Str,
+                    false,
+                    "User name for authenticated sessions.",
+                ),
+                key(
+                    "password",
+                    Str,
+                    false,
+                    "Password for authenticated sessions.",
+                ),
+            ],
+        ),
+        table(
+            "runtime.jit",
+            "Native code for hot programs.",
+            &[
+                key(
+                    "enabled",
+                    Bool,
+                    false,
+                    "Compile hot programs to native code.",
+                ),
+                key(
+                    "warmup_cycles",
+                    Integer,
+                    false,
+                    "Cycles profiled before programs are compiled.",
+                ),
+                key("hot_programs", Integer, false, "Programs compiled at most."),
+            ],
+        ),
+        table(
+            "runtime.notify",
+            "Notifications on faults, watchdog trips, and driver failures.",
+            &[
+                key("enabled", Bool, false, "Send notifications."),
+                ConfigKey {
+                    name: "events",
+                    kind: Array,
+                    required: false,
+                    values: &["fault", "watchdog", "driver"],
+                    doc: "Event kinds that notify.",
+                },
+                key(
+                    "min_interval_s",
+                    Integer,
+                    false,
+                    "Minimum time between notifications.",
+                ),
+                key(
+                    "recent_events",
+                    Integer,
+                    false,
+                    "Recent runtime events included in a notification.",
+                ),
+                key("subject", Str, false, "Subject template."),
+                key("template", Str, false, "Body template."),
+            ],
+        ),
+        table(
+            "runtime.notify.webhook",
+            "Webhook notifications.",
+            &[key("url", Str, true, "URL the notification is POSTed to.")],
+        ),
+        table(
+            "runtime.notify.smtp",
+            "E-mail notifications.",
+            &[
+                key("server", Str, true, "SMTP server, `host:port`."),
+                key("from", Str, true, "Sender address."),
+                key("to", Array, true, "Recipient addresses."),
+                key("username", Str, false, "SMTP user name."),
+                key("password", Str, false, "SMTP password."),
+            ],
+        ),
+        table(
+            "runtime.redundancy",
+            "Hot-standby pairing with a second runtime over the mesh.",
+            &[
+                key("enabled", Bool, false, "Enable redundancy."),
+                choice(
+                    "role",
+                    false,
+                    &["primary", "secondary"],
+                    "Preferred role of this runtime.",
+                ),
+                key("partner", Str, false, "Partner mesh address, `host:port`."),
+                key("heartbeat_ms", Integer, false, "Heartbeat interval."),
+                key(
+                    "failover_timeout_ms",
+                    Integer,
+                    false,
+                    "Missed heartbeats time before the standby takes over (at least 3x \
+                     `heartbeat_ms`).",
+                ),
+                key(
+                    "failover_on_fault",
+                    Bool,
+                    false,
+                    "Fail over when the active runtime faults.",
+                ),
+                key("sync_interval_ms", Integer, false, "State sync interval."),
+                key(
+                    "sync",
+                    Array,
+                    false,
+                    "Variables synchronized to the standby.",
+                ),
+            ],
+        ),
+        table(
+            "runtime.realtime",
+            "Real-time scheduling of the resource thread (Linux).",
+            &[
+                key("priority", Integer, false, "`SCHED_FIFO` priority, 1-99."),
+                key(
+                    "cpu_affinity",
+                    Array,
+                    false,
+                    "CPU cores the resource thread is pinned to.",
+                ),
+                key(
+                    "lock_memory",
+                    Bool,
+                    false,
+                    "Lock process memory with `mlockall`.",
+                ),
+            ],
+        ),
+        table(
+            "runtime.files",
+            "File access from ST programs.",
+            &[key(
+                "data_dir",
+                Str,
+                false,
+                "Directory inside the bundle that file function blocks are confined to.",
+            )],
+        ),
+        table(
+            "runtime.events",
+            "On-disk runtime event store.",
+            &[
+                key("enabled", Bool, false, "Persist runtime events."),
+                key("path", Str, false, "Event file, relative to the bundle."),
+                choice(
+                    "min_severity",
+                    false,
+                    &["debug", "info", "warning", "error"],
+                    "Lowest severity stored.",
+                ),
+                key(
+                    "max_age_s",
+                    Integer,
+                    false,
+                    "Events older than this are pruned.",
+                ),
+                key(
+                    "max_bytes",
+                    Integer,
+                    false,
+                    "Size at which the file is rotated.",
+                ),
+            ],
+        ),
+        table(
+            "runtime.shutdown",
+            "PROGRAM run for a bounded number of cycles before stop or restart.",
+            &[
+                key("program", Str, true, "PROGRAM run during shutdown."),
+                key("cycles", Integer, false, "Cycles run at most (default 10)."),
+                key(
+                    "timeout_ms",
+                    Integer,
+                    false,
+                    "Time limit in milliseconds (default 5000).",
+                ),
+                key(
+                    "done",
+                    Str,
+                    false,
+                    "BOOL variable that ends the sequence early when TRUE.",
+                ),
+            ],
+        ),
+        table_array(
+            "runtime.io_drivers",
+            "External I/O driver processes.",
+            &[
+                key("name", Str, true, "Driver name used in `io.toml`."),
+                key("command", Str, true, "Executable started for the driver."),
+                key("args", Array, false, "Command line arguments."),
+                key(
+                    "timeout_ms",
+                    Integer,
+                    false,
+                    "Time to wait for each exchange.",
+                ),
+            ],
+        ),
+    ],
+};
+
+/// Legacy single-file `hmi.toml` in a project folder.
+pub const HMI_TOML: ConfigSchema = ConfigSchema {
+    file: "hmi.toml",
+    tables: &[
+        table(
+            "theme",
+            "HMI theme.",
+            &[
+                choice(
+                    "style",
+                    false,
+                    &["classic", "industrial", "mint", "control-room"],
+                    "Color theme.",
+                ),
+                key("accent", Str, false, "Accent color, e.g. `#0ea5e9`."),
+            ],
+        ),
+        table(
+            "responsive",
+            "Layout on small screens.",
+            &[choice(
+                "mode",
+                false,
+                &["auto", "mobile", "tablet", "kiosk"],
+                "Layout mode (default `auto`).",
+            )],
+        ),
+        table(
+            "export",
+            "HMI export.",
+            &[key("enabled", Bool, false, "Allow exporting the HMI.")],
+        ),
+        table(
+            "write",
+            "Writes from the HMI.",
+            &[
+                key("enabled", Bool, false, "Allow writes (off by default)."),
+                key(
+                    "allow",
+                    Array,
+                    false,
+                    "Widget ids or variable paths that may be written.",
+                ),
+            ],
+        ),
+        table_array(
+            "pages",
+            "HMI pages.",
+            &[
+                key("id", Str, true, "Page id used by `widgets.*.page`."),
+                key("title", Str, false, "Page title."),
+                key("order", Integer, false, "Position in the navigation."),
+                choice(
+                    "kind",
+                    false,
+                    &["dashboard", "trend", "alarm", "table", "process"],
+                    "Page layout (default `dashboard`).",
+                ),
+                key("duration_s", Integer, false, "Time window of trend pages."),
+                key("signals", Array, false, "Variables shown on the page."),
+            ],
+        ),
+        map("widgets", "Widget overrides keyed by variable path."),
+        table(
+            "widgets.*",
+            "Override of one widget.",
+            &[
+                key("label", Str, false, "Widget label."),
+                key("unit", Str, false, "Engineering unit."),
+                key("min", Float, false, "Scale minimum."),
+                key("max", Float, false, "Scale maximum."),
+                key(
+                    "widget",
+                    Str,
+                    false,
+                    "Widget kind, e.g. `gauge` or `toggle`.",
+                ),
+                key("page", Str, false, "Page id the widget is placed on."),
+                key("group", Str, false, "Group on the page."),
+                key("order", Integer, false, "Position in the group."),
+            ],
+        ),
+    ],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_resolve_with_wildcards() {
+        let qos = RUNTIME_TOML
+            .table(&["runtime", "mesh", "subscribe_qos", "plant:Temp"])
+            .expect("qos table");
+        assert!(qos.key("on_timeout").is_some());
+        assert!(RUNTIME_TOML.table(&["runtime", "unknown"]).is_none());
+        let children = RUNTIME_TOML
+            .children(&["runtime", "log"])
+            .map(ConfigTable::name)
+            .collect::<Vec<_>>();
+        assert_eq!(children, ["syslog"]);
+        assert_eq!(
+            RUNTIME_TOML
+                .table(&["runtime", "watchdog"])
+                .and_then(|table| table.key("action"))
+                .map(|key| key.values),
+            Some(&["halt", "safe_halt", "restart"][..])
+        );
+    }
+}
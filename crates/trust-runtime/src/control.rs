@@ -2,8 +2,13 @@
 
 #![allow(missing_docs)]
 
+mod diagnostics;
+mod errors;
 mod handlers;
+pub(crate) mod limits;
+mod session;
 mod transport;
+mod watch;
 
 use std::collections::{BTreeMap, VecDeque};
 use std::net::SocketAddr;
@@ -13,19 +18,20 @@ use std::sync::mpsc::{RecvTimeoutError, Sender};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use crate::config::ControlMode;
+use crate::config::{ControlMode, PausePolicy, ServerLimits};
 use crate::debug::{
     location_to_line_col, DebugBreakpoint, DebugControl, DebugScope, DebugSource, DebugVariable,
     DebugVariableHandles, VariableHandle,
 };
 use crate::error::RuntimeError;
+use crate::event_store::{EventQuery, EventSeverity};
 use crate::io::{IoAddress, IoDriverHealth, IoDriverStatus, IoSnapshot};
 use crate::metrics::RuntimeMetrics;
 use crate::runtime::RuntimeMetadata;
 use crate::scheduler::{ResourceCommand, ResourceControl};
 use crate::security::AccessRole;
 use crate::settings::RuntimeSettings;
-use crate::value::Value;
+use crate::value::{parse_time_literal, DateTimeProfile, Value};
 use crate::web::pairing::PairingStore;
 use crate::RestartMode;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
@@ -36,6 +42,11 @@ use serde_json::json;
 use smol_str::SmolStr;
 use tracing::{debug, warn};
 
+pub use diagnostics::{collect_diagnostics, install_crash_handler, write_diagnostics};
+pub use errors::{control_error_code, control_error_message, ControlErrorCode};
+pub use session::{ControlSession, ControlSessions};
+pub use watch::{spawn_source_watcher, SourceWatchEvent};
+
 const HMI_DESCRIPTOR_WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
 #[cfg(test)]
 const HMI_DESCRIPTOR_WATCH_STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
@@ -92,10 +103,14 @@ pub struct ControlState {
     pub io_health: Arc<Mutex<Vec<IoDriverStatus>>>,
     pub debug_enabled: Arc<AtomicBool>,
     pub debug_variables: Arc<Mutex<DebugVariableHandles>>,
+    pub sessions: Arc<ControlSessions>,
     pub hmi_live: Arc<Mutex<crate::hmi::HmiLiveState>>,
     pub hmi_descriptor: Arc<Mutex<HmiRuntimeDescriptor>>,
     pub historian: Option<Arc<crate::historian::HistorianService>>,
+    pub event_store: Option<Arc<crate::event_store::EventStore>>,
     pub pairing: Option<Arc<PairingStore>>,
+    pub mesh: Option<Arc<crate::mesh::MeshLinks>>,
+    pub redundancy: Option<Arc<crate::redundancy::RedundancyPair>>,
 }
 
 #[derive(Debug, Clone)]
@@ -178,7 +193,16 @@ impl ControlServer {
         endpoint: ControlEndpoint,
         state: Arc<ControlState>,
     ) -> Result<Self, RuntimeError> {
-        transport::spawn_control_server(&endpoint, state.clone())?;
+        Self::start_with_limits(endpoint, state, ServerLimits::default())
+    }
+
+    /// Start the server with request size, rate, and connection limits.
+    pub fn start_with_limits(
+        endpoint: ControlEndpoint,
+        state: Arc<ControlState>,
+        limits: ServerLimits,
+    ) -> Result<Self, RuntimeError> {
+        transport::spawn_control_server(&endpoint, state.clone(), limits)?;
         Ok(Self { endpoint, state })
     }
 
@@ -291,10 +315,16 @@ pub(crate) fn handle_request_line(
     line: &str,
     state: &ControlState,
     client: Option<&str>,
+    session: Option<&ControlSession>,
 ) -> Option<String> {
     let response = match serde_json::from_str::<serde_json::Value>(line) {
-        Ok(value) => handle_request_value(value, state, client),
-        Err(err) => ControlResponse::error(0, format!("invalid request: {err}")),
+        Ok(value) => handle_session_request_value(value, state, client, session),
+        Err(err) => ControlResponse::error_code(
+            0,
+            ControlErrorCode::InvalidRequest,
+            format!("invalid request: {err}"),
+        )
+        .with_legacy_errors(legacy_errors(state)),
     };
     serde_json::to_string(&response).ok()
 }
@@ -303,11 +333,46 @@ pub(crate) fn handle_request_value(
     value: serde_json::Value,
     state: &ControlState,
     client: Option<&str>,
+) -> ControlResponse {
+    handle_session_request_value(value, state, client, None)
+}
+
+/// Handle a request on behalf of a connection session. Without a session the request
+/// uses the shared debug state.
+pub(crate) fn handle_session_request_value(
+    value: serde_json::Value,
+    state: &ControlState,
+    client: Option<&str>,
+    session: Option<&ControlSession>,
+) -> ControlResponse {
+    if let Some(session) = session {
+        session.record_request();
+    }
+    dispatch_request_value(value, state, client, session).with_legacy_errors(legacy_errors(state))
+}
+
+fn legacy_errors(state: &ControlState) -> bool {
+    state
+        .settings
+        .lock()
+        .map(|settings| settings.legacy_errors)
+        .unwrap_or(false)
+}
+
+fn dispatch_request_value(
+    value: serde_json::Value,
+    state: &ControlState,
+    client: Option<&str>,
+    session: Option<&ControlSession>,
 ) -> ControlResponse {
     let request: ControlRequest = match serde_json::from_value(value) {
         Ok(req) => req,
         Err(err) => {
-            let response = ControlResponse::error(0, format!("invalid request: {err}"));
+            let response = ControlResponse::error_code(
+                0,
+                ControlErrorCode::InvalidRequest,
+                format!("invalid request: {err}"),
+            );
             record_audit(
                 state,
                 0,
@@ -323,7 +388,11 @@ pub(crate) fn handle_request_value(
     let request_role = match resolve_request_role(&request, state) {
         Ok(role) => role,
         Err(error) => {
-            let response = ControlResponse::error(request.id, error.to_string());
+            let response = ControlResponse::error_code(
+                request.id,
+                ControlErrorCode::Unauthorized,
+                error.into(),
+            );
             record_audit(
                 state,
                 request.id,
@@ -340,7 +409,9 @@ pub(crate) fn handle_request_value(
         required_role_for_control_request(request.r#type.as_str(), request.params.as_ref());
     if !request_role.allows(required_role) {
         let error = format!("forbidden: requires role {}", required_role.as_str());
-        let response = ControlResponse::error(request.id, error.clone());
+        let response =
+            ControlResponse::error_code(request.id, ControlErrorCode::Forbidden, error.clone())
+                .with_details(json!({ "required_role": required_role.as_str() }));
         record_audit(
             state,
             request.id,
@@ -353,7 +424,11 @@ pub(crate) fn handle_request_value(
         return response;
     }
     if !state.debug_enabled.load(Ordering::Relaxed) && is_debug_request(request.r#type.as_str()) {
-        let response = ControlResponse::error(request.id, "debug disabled".into());
+        let response = ControlResponse::error_code(
+            request.id,
+            ControlErrorCode::DebugDisabled,
+            "debug disabled".into(),
+        );
         record_audit(
             state,
             request.id,
@@ -365,8 +440,13 @@ pub(crate) fn handle_request_value(
         );
         return response;
     }
-    let response = handlers::dispatch(&request, state)
-        .unwrap_or_else(|| ControlResponse::error(request.id, "unsupported request".into()));
+    let response = handlers::dispatch(&request, state, session).unwrap_or_else(|| {
+        ControlResponse::error_code(
+            request.id,
+            ControlErrorCode::UnsupportedRequest,
+            "unsupported request".into(),
+        )
+    });
     record_audit(
         state,
         request.id,
@@ -407,6 +487,24 @@ fn record_audit(
     let _ = sender.send(event);
 }
 
+/// Audit a request or connection refused by a server limit.
+pub(crate) fn record_limit_audit(
+    state: &ControlState,
+    kind: &str,
+    error: &str,
+    client: Option<&str>,
+) {
+    record_audit(
+        state,
+        0,
+        SmolStr::new(kind),
+        false,
+        Some(SmolStr::new(error)),
+        false,
+        client,
+    );
+}
+
 fn is_debug_request(kind: &str) -> bool {
     matches!(
         kind,
@@ -420,6 +518,7 @@ fn is_debug_request(kind: &str) -> bool {
             | "breakpoints.clear_all"
             | "breakpoints.clear_id"
             | "breakpoints.list"
+            | "breakpoints.exceptions"
             | "eval"
             | "set"
             | "var.force"
@@ -431,6 +530,9 @@ fn is_debug_request(kind: &str) -> bool {
             | "debug.scopes"
             | "debug.variables"
             | "debug.evaluate"
+            | "debug.set_variable"
+            | "debug.set_expression"
+            | "debug.complete"
             | "debug.breakpoint_locations"
     )
 }
@@ -464,17 +566,22 @@ fn resolve_request_role(
     Ok(AccessRole::Admin)
 }
 
-fn required_role_for_control_request(kind: &str, params: Option<&serde_json::Value>) -> AccessRole {
+pub(crate) fn required_role_for_control_request(
+    kind: &str,
+    params: Option<&serde_json::Value>,
+) -> AccessRole {
     match kind {
         "status"
         | "health"
         | "tasks.stats"
         | "events.tail"
         | "events"
+        | "events.query"
         | "faults"
         | "config.get"
         | "io.list"
         | "io.read"
+        | "io.map.get"
         | "hmi.schema.get"
         | "hmi.values.get"
         | "hmi.trends.get"
@@ -482,15 +589,24 @@ fn required_role_for_control_request(kind: &str, params: Option<&serde_json::Val
         | "hmi.descriptor.get"
         | "historian.query"
         | "historian.alerts"
+        | "mesh.status"
+        | "mesh.topology"
         | "debug.state"
         | "debug.stops"
         | "debug.stack"
         | "debug.scopes"
         | "debug.variables"
+        | "debug.complete"
         | "debug.breakpoint_locations"
+        | "debug.history"
+        | "session.info"
+        | "diagnostics.collect"
+        | "restart.preview"
         | "breakpoints.list"
         | "var.forced" => AccessRole::Viewer,
-        "pause" | "resume" | "restart" | "hmi.alarm.ack" | "pair.claim" => AccessRole::Operator,
+        "pause" | "resume" | "cycle.step" | "restart" | "hmi.alarm.ack" | "pair.claim" => {
+            AccessRole::Operator
+        }
         "step_in"
         | "step_over"
         | "step_out"
@@ -498,6 +614,7 @@ fn required_role_for_control_request(kind: &str, params: Option<&serde_json::Val
         | "breakpoints.clear"
         | "breakpoints.clear_all"
         | "breakpoints.clear_id"
+        | "breakpoints.exceptions"
         | "eval"
         | "set"
         | "var.force"
@@ -505,7 +622,10 @@ fn required_role_for_control_request(kind: &str, params: Option<&serde_json::Val
         | "io.write"
         | "io.force"
         | "io.unforce"
+        | "sim.model.set"
         | "debug.evaluate"
+        | "debug.set_variable"
+        | "debug.set_expression"
         | "hmi.write"
         | "hmi.descriptor.update"
         | "hmi.scaffold.reset" => AccessRole::Engineer,
@@ -578,6 +698,8 @@ fn handle_status(id: u64, state: &ControlState) -> ControlResponse {
                 .as_ref()
                 .map(|cfg| cfg.warning.as_str())
                 .unwrap_or(""),
+            "realtime": realtime_status_json(state),
+            "safety": safety_status_json(state, status),
             "hmi_read_only": true,
             "metrics": {
                 "cycle_ms": {
@@ -609,10 +731,98 @@ fn handle_status(id: u64, state: &ControlState) -> ControlResponse {
                 },
             },
             "io_drivers": io_health,
+            "mesh": mesh_summary_json(state),
+            "redundancy_role": state
+                .redundancy
+                .as_ref()
+                .map(|pair| pair.role().as_str()),
+            "redundancy": state.redundancy.as_ref().map(|pair| pair.status()),
+            "program": program_build_json(state),
         }),
     )
 }
 
+fn realtime_status_json(state: &ControlState) -> serde_json::Value {
+    let Some(realtime) = state
+        .settings
+        .lock()
+        .ok()
+        .map(|guard| guard.realtime.clone())
+    else {
+        return serde_json::Value::Null;
+    };
+    json!({
+        "priority": realtime.priority,
+        "cpu_affinity": realtime.cpu_affinity,
+        "lock_memory": realtime.lock_memory,
+        "warnings": realtime
+            .warnings
+            .iter()
+            .map(SmolStr::as_str)
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn safety_status_json(
+    state: &ControlState,
+    status: crate::scheduler::ResourceState,
+) -> serde_json::Value {
+    let Some(safety) = state
+        .settings
+        .lock()
+        .ok()
+        .map(|guard| guard.safety_state.clone())
+    else {
+        return serde_json::Value::Null;
+    };
+    json!({
+        "default": safety.default.label(),
+        "outputs": safety
+            .outputs
+            .iter()
+            .map(|(address, output)| {
+                json!({ "address": format_address(address), "action": output.label() })
+            })
+            .collect::<Vec<_>>(),
+        // Outputs are held in the safe state while the resource is paused or faulted.
+        "active": !safety.is_hold_all()
+            && matches!(
+                status,
+                crate::scheduler::ResourceState::Paused | crate::scheduler::ResourceState::Faulted
+            ),
+    })
+}
+
+fn program_build_json(state: &ControlState) -> serde_json::Value {
+    let build = state
+        .metadata
+        .lock()
+        .ok()
+        .and_then(|metadata| metadata.build_info().cloned());
+    let Some(build) = build else {
+        return serde_json::Value::Null;
+    };
+    json!({
+        "version": build.version.as_deref(),
+        "git_hash": build.git_hash.as_deref(),
+        "built_at": build.built_at.as_deref(),
+        "author": build.author.as_deref(),
+    })
+}
+
+fn mesh_summary_json(state: &ControlState) -> serde_json::Value {
+    let Some(mesh) = state.mesh.as_ref() else {
+        return json!({ "enabled": false });
+    };
+    let status = mesh.status();
+    json!({
+        "enabled": true,
+        "peers": status.peers.len(),
+        "subscriptions": status.subscriptions.len(),
+        "stale": status.stale_count(),
+    })
+}
+
 fn handle_health(id: u64, state: &ControlState) -> ControlResponse {
     let status = state.resource.state();
     let error = state.resource.last_error().map(|err| err.to_string());
@@ -661,6 +871,9 @@ fn handle_task_stats(id: u64, state: &ControlState) -> ControlResponse {
                 "max_ms": task.max_ms,
                 "last_ms": task.last_ms,
                 "overruns": task.overruns,
+                "jitter_max_ms": task.jitter_max_ms,
+                "jitter_avg_ms": task.jitter_avg_ms,
+                "jitter_last_ms": task.jitter_last_ms,
             })
         })
         .collect::<Vec<_>>();
@@ -680,12 +893,30 @@ fn handle_task_stats(id: u64, state: &ControlState) -> ControlResponse {
             })
         })
         .collect::<Vec<_>>();
+    let calls = metrics
+        .profiling
+        .calls
+        .iter()
+        .map(|entry| {
+            json!({
+                "key": entry.key.as_str(),
+                "kind": entry.kind.as_str(),
+                "name": entry.name.as_str(),
+                "min_ms": entry.min_ms,
+                "avg_ms": entry.avg_ms,
+                "max_ms": entry.max_ms,
+                "last_ms": entry.last_ms,
+                "calls": entry.calls,
+            })
+        })
+        .collect::<Vec<_>>();
     ControlResponse::ok(
         id,
         json!({
             "tasks": tasks,
             "profiling_enabled": metrics.profiling.enabled,
             "top_contributors": top_contributors,
+            "calls": calls,
         }),
     )
 }
@@ -697,14 +928,14 @@ fn handle_io_list(id: u64, state: &ControlState) -> ControlResponse {
     };
     match snapshot {
         Some(snapshot) => ControlResponse::ok(id, snapshot.into_json()),
-        None => ControlResponse::error(id, "no snapshot available".into()),
+        None => ControlResponse::unavailable(id, "no snapshot available".into()),
     }
 }
 
 fn handle_hmi_schema_get(id: u64, state: &ControlState) -> ControlResponse {
     let metadata = match state.metadata.lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "metadata unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
     };
     let snapshot = load_runtime_snapshot(state);
     let descriptor = hmi_descriptor_snapshot(state);
@@ -731,13 +962,15 @@ fn handle_hmi_values_get(
     let params = match params {
         Some(value) => match serde_json::from_value::<HmiValuesParams>(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
         None => HmiValuesParams { ids: None },
     };
     let metadata = match state.metadata.lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "metadata unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
     };
     let snapshot = load_runtime_snapshot(state);
     let descriptor = hmi_descriptor_snapshot(state);
@@ -754,6 +987,7 @@ fn handle_hmi_values_get(
         snapshot.as_ref(),
         true,
         params.ids.as_deref(),
+        resource_unix_ms(state, snapshot.as_ref()),
     );
     if let Ok(mut live) = state.hmi_live.lock() {
         crate::hmi::update_live_state(&mut live, &schema, &result);
@@ -772,13 +1006,15 @@ fn handle_hmi_trends_get(
     let params = match params {
         Some(value) => match serde_json::from_value::<HmiTrendsParams>(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
         None => HmiTrendsParams::default(),
     };
     let metadata = match state.metadata.lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "metadata unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
     };
     let snapshot = load_runtime_snapshot(state);
     let descriptor = hmi_descriptor_snapshot(state);
@@ -795,19 +1031,22 @@ fn handle_hmi_trends_get(
         snapshot.as_ref(),
         true,
         params.ids.as_deref(),
+        resource_unix_ms(state, snapshot.as_ref()),
     );
     let result = match state.hmi_live.lock() {
         Ok(mut live) => {
             crate::hmi::update_live_state(&mut live, &schema, &values);
-            crate::hmi::build_trends(
+            crate::hmi::build_trends_with_history(
                 &live,
                 &schema,
                 params.ids.as_deref(),
+                params.until_ms,
                 params.duration_ms.unwrap_or(10 * 60 * 1_000),
                 params.buckets.unwrap_or(120),
+                state.historian.as_deref(),
             )
         }
-        Err(_) => return ControlResponse::error(id, "hmi state unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "hmi state unavailable".into()),
     };
     ControlResponse::ok(
         id,
@@ -823,13 +1062,15 @@ fn handle_hmi_alarms_get(
     let params = match params {
         Some(value) => match serde_json::from_value::<HmiAlarmsParams>(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
         None => HmiAlarmsParams::default(),
     };
     let metadata = match state.metadata.lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "metadata unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
     };
     let snapshot = load_runtime_snapshot(state);
     let descriptor = hmi_descriptor_snapshot(state);
@@ -846,13 +1087,14 @@ fn handle_hmi_alarms_get(
         snapshot.as_ref(),
         true,
         None,
+        resource_unix_ms(state, snapshot.as_ref()),
     );
     let result = match state.hmi_live.lock() {
         Ok(mut live) => {
             crate::hmi::update_live_state(&mut live, &schema, &values);
             crate::hmi::build_alarm_view(&live, params.limit.unwrap_or(100))
         }
-        Err(_) => return ControlResponse::error(id, "hmi state unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "hmi state unavailable".into()),
     };
     ControlResponse::ok(
         id,
@@ -871,7 +1113,7 @@ fn handle_hmi_descriptor_get(id: u64, state: &ControlState) -> ControlResponse {
 
     let metadata = match state.metadata.lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "metadata unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
     };
     let snapshot = load_runtime_snapshot(state);
     let schema = crate::hmi::build_schema(
@@ -896,14 +1138,16 @@ fn handle_hmi_descriptor_update(
     let params = match params {
         Some(value) => match serde_json::from_value::<HmiDescriptorUpdateParams>(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let project_root = match state.project_root.as_ref() {
         Some(path) => path,
         None => {
-            return ControlResponse::error(
+            return ControlResponse::unavailable(
                 id,
                 "hmi.descriptor.update requires a project bundle".into(),
             )
@@ -912,7 +1156,7 @@ fn handle_hmi_descriptor_update(
 
     let metadata = match state.metadata.lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "metadata unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
     };
     let snapshot = load_runtime_snapshot(state);
     let diagnostics = crate::hmi::validate_hmi_bindings(
@@ -962,14 +1206,16 @@ fn handle_hmi_scaffold_reset(
     let params = match params {
         Some(value) => match serde_json::from_value::<HmiScaffoldResetParams>(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
         None => HmiScaffoldResetParams::default(),
     };
     let project_root = match state.project_root.as_ref() {
         Some(path) => path,
         None => {
-            return ControlResponse::error(
+            return ControlResponse::unavailable(
                 id,
                 "hmi.scaffold.reset requires a project bundle".into(),
             )
@@ -984,7 +1230,7 @@ fn handle_hmi_scaffold_reset(
         Some(mode) if mode == "update" => crate::hmi::HmiScaffoldMode::Update,
         Some(mode) if mode == "reset" || mode.is_empty() => crate::hmi::HmiScaffoldMode::Reset,
         Some(mode) => {
-            return ControlResponse::error(
+            return ControlResponse::invalid_params(
                 id,
                 format!("invalid scaffold mode '{mode}' (expected update|reset)"),
             )
@@ -1007,7 +1253,7 @@ fn handle_hmi_scaffold_reset(
 
     let metadata = match state.metadata.lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "metadata unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
     };
     let snapshot = load_runtime_snapshot(state);
     let source_refs = state
@@ -1060,14 +1306,13 @@ fn handle_hmi_alarm_ack(
     let params = match params {
         Some(value) => match serde_json::from_value::<HmiAlarmAckParams>(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
-    let timestamp_ms = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis();
+    let timestamp_ms = resource_unix_ms(state, None);
     let result = match state.hmi_live.lock() {
         Ok(mut live) => {
             match crate::hmi::acknowledge_alarm(&mut live, params.id.as_str(), timestamp_ms) {
@@ -1075,7 +1320,7 @@ fn handle_hmi_alarm_ack(
                 Err(err) => return ControlResponse::error(id, err),
             }
         }
-        Err(_) => return ControlResponse::error(id, "hmi state unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "hmi state unavailable".into()),
     };
     ControlResponse::ok(
         id,
@@ -1091,31 +1336,41 @@ fn handle_hmi_write(
     let params = match params {
         Some(value) => match serde_json::from_value::<HmiWriteParams>(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let target = params.id.trim();
     if target.is_empty() {
-        return ControlResponse::error(id, "missing params.id".into());
+        return ControlResponse::invalid_params(id, "missing params.id".into());
     }
 
     let descriptor = hmi_descriptor_snapshot(state);
     let customization = descriptor.customization;
     if !customization.write_enabled() {
-        return ControlResponse::error(id, "hmi.write disabled in read-only mode".into());
+        return ControlResponse::error_code(
+            id,
+            ControlErrorCode::ReadOnly,
+            "hmi.write disabled in read-only mode".into(),
+        );
     }
     if customization.write_allowlist().is_empty() {
-        return ControlResponse::error(id, "hmi.write allowlist is empty".into());
+        return ControlResponse::error_code(
+            id,
+            ControlErrorCode::Forbidden,
+            "hmi.write allowlist is empty".into(),
+        );
     }
 
     let metadata = match state.metadata.lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "metadata unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
     };
     let snapshot = match load_runtime_snapshot(state) {
         Some(snapshot) => snapshot,
-        None => return ControlResponse::error(id, "runtime snapshot unavailable".into()),
+        None => return ControlResponse::unavailable(id, "runtime snapshot unavailable".into()),
     };
     let point = match crate::hmi::resolve_write_point(
         state.resource_name.as_str(),
@@ -1124,17 +1379,21 @@ fn handle_hmi_write(
         target,
     ) {
         Some(point) => point,
-        None => return ControlResponse::error(id, format!("unknown hmi target '{target}'")),
+        None => return ControlResponse::not_found(id, format!("unknown hmi target '{target}'")),
     };
     let allowed = customization.write_target_allowed(point.id.as_str())
         || customization.write_target_allowed(point.path.as_str());
     if !allowed {
-        return ControlResponse::error(id, "hmi.write target is not in allowlist".into());
+        return ControlResponse::error_code(
+            id,
+            ControlErrorCode::Forbidden,
+            "hmi.write target is not in allowlist".into(),
+        );
     }
     let template = match crate::hmi::resolve_write_value_template(&point, &snapshot) {
         Some(value) => value,
         None => {
-            return ControlResponse::error(
+            return ControlResponse::unavailable(
                 id,
                 format!("hmi.write target '{}' is currently unavailable", point.id),
             )
@@ -1143,19 +1402,34 @@ fn handle_hmi_write(
     let value = match parse_hmi_write_value(&params.value, &template) {
         Some(value) => value,
         None => {
-            return ControlResponse::error(
+            return ControlResponse::invalid_params(
                 id,
                 format!("invalid hmi.write value for target '{}'", point.id),
             )
         }
     };
+    let (program, variable) = match &point.binding {
+        crate::hmi::HmiWriteBinding::ProgramVar { program, variable } => {
+            (Some(program.as_str()), variable.as_str())
+        }
+        crate::hmi::HmiWriteBinding::Global { name } => (None, name.as_str()),
+    };
+    let value = match metadata.check_external_write(program, variable, value) {
+        Ok(value) => value,
+        Err(err) => {
+            return ControlResponse::invalid_params(
+                id,
+                format!("invalid hmi.write value for target '{}': {err}", point.id),
+            )
+        }
+    };
 
     match &point.binding {
         crate::hmi::HmiWriteBinding::ProgramVar { program, variable } => {
             let instance_id = match snapshot.storage.get_global(program.as_str()) {
                 Some(Value::Instance(instance_id)) => *instance_id,
                 _ => {
-                    return ControlResponse::error(
+                    return ControlResponse::unavailable(
                         id,
                         format!("hmi.write target '{}' is currently unavailable", point.id),
                     )
@@ -1291,6 +1565,20 @@ fn load_runtime_snapshot(state: &ControlState) -> Option<crate::debug::DebugSnap
     state.debug.snapshot()
 }
 
+/// Unix milliseconds on the resource clock, so HMI stamps line up with the
+/// historian even under a simulation time scale.
+fn resource_unix_ms(state: &ControlState, snapshot: Option<&crate::debug::DebugSnapshot>) -> u128 {
+    let time_base = state
+        .metrics
+        .lock()
+        .map(|metrics| metrics.time_base().clone())
+        .unwrap_or_default();
+    snapshot.map_or_else(
+        || time_base.now_unix_ms(),
+        |snapshot| time_base.unix_ms(snapshot.now),
+    )
+}
+
 fn descriptor_from_schema(schema: &crate::hmi::HmiSchemaResult) -> crate::hmi::HmiDirDescriptor {
     let mut pages = Vec::new();
     let widgets_by_page = schema.widgets.iter().fold(
@@ -1433,21 +1721,94 @@ fn handle_events_tail(
     state: &ControlState,
 ) -> ControlResponse {
     let limit = params
-        .and_then(|value| value.get("limit").cloned())
-        .and_then(|value| value.as_u64())
+        .as_ref()
+        .and_then(|value| value.get("limit"))
+        .and_then(serde_json::Value::as_u64)
         .unwrap_or(50) as usize;
+    let min_severity = match params
+        .as_ref()
+        .and_then(|value| value.get("min_severity"))
+        .map(parse_event_severity)
+        .transpose()
+    {
+        Ok(severity) => severity.unwrap_or(EventSeverity::Debug),
+        Err(err) => return ControlResponse::invalid_params(id, err),
+    };
     let events = state
         .events
         .lock()
-        .map(|guard| guard.iter().rev().take(limit).cloned().collect::<Vec<_>>())
+        .map(|guard| {
+            guard
+                .iter()
+                .rev()
+                .filter(|event| EventSeverity::of(event) >= min_severity)
+                .take(limit)
+                .cloned()
+                .collect::<Vec<_>>()
+        })
         .unwrap_or_default();
     let payload = events
-        .into_iter()
-        .map(runtime_event_to_json)
+        .iter()
+        .map(|event| {
+            let mut value = crate::event_store::runtime_event_json(event);
+            value["severity"] = json!(EventSeverity::of(event).as_str());
+            value
+        })
         .collect::<Vec<_>>();
     ControlResponse::ok(id, json!({ "events": payload }))
 }
 
+fn handle_events_query(
+    id: u64,
+    params: Option<serde_json::Value>,
+    state: &ControlState,
+) -> ControlResponse {
+    let Some(store) = state.event_store.as_ref() else {
+        return ControlResponse::unavailable(id, "event store disabled".into());
+    };
+    let params = match params {
+        Some(value) => match serde_json::from_value::<EventsQueryParams>(value) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
+        },
+        None => EventsQueryParams::default(),
+    };
+    let min_severity = match params.min_severity.as_deref() {
+        Some(text) => match EventSeverity::parse(text) {
+            Some(severity) => Some(severity),
+            None => {
+                return ControlResponse::invalid_params(
+                    id,
+                    format!("invalid min_severity '{text}'"),
+                )
+            }
+        },
+        None => None,
+    };
+    let codes = match params.code {
+        Some(EventCodes::One(code)) => vec![SmolStr::new(code)],
+        Some(EventCodes::Many(codes)) => codes.into_iter().map(SmolStr::new).collect(),
+        None => Vec::new(),
+    };
+    let events = store.query(&EventQuery {
+        since_ms: params.since_ms,
+        until_ms: params.until_ms,
+        codes,
+        min_severity,
+        limit: params.limit.unwrap_or(200),
+    });
+    ControlResponse::ok(id, json!({ "events": events }))
+}
+
+fn parse_event_severity(value: &serde_json::Value) -> Result<EventSeverity, String> {
+    value
+        .as_str()
+        .and_then(EventSeverity::parse)
+        .ok_or_else(|| format!("invalid min_severity '{value}'"))
+}
+
 fn handle_faults(
     id: u64,
     params: Option<serde_json::Value>,
@@ -1465,7 +1826,7 @@ fn handle_faults(
     let faults = events
         .into_iter()
         .filter(|event| matches!(event, crate::debug::RuntimeEvent::Fault { .. }))
-        .map(runtime_event_to_json)
+        .map(|event| crate::event_store::runtime_event_json(&event))
         .collect::<Vec<_>>();
     ControlResponse::ok(id, json!({ "faults": faults }))
 }
@@ -1476,35 +1837,54 @@ fn handle_historian_query(
     state: &ControlState,
 ) -> ControlResponse {
     let Some(historian) = state.historian.as_ref() else {
-        return ControlResponse::error(id, "historian disabled".into());
+        return ControlResponse::unavailable(id, "historian disabled".into());
     };
     let params = match params {
         Some(value) => match serde_json::from_value::<HistorianQueryParams>(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
         None => HistorianQueryParams::default(),
     };
-    let items = historian.query(
+    let items = historian.query_window(
         params.variable.as_deref(),
         params.since_ms,
+        params.until_ms,
         params.limit.unwrap_or(250),
     );
     ControlResponse::ok(id, json!({ "items": items }))
 }
 
+fn handle_mesh_status(id: u64, state: &ControlState) -> ControlResponse {
+    let Some(mesh) = state.mesh.as_ref() else {
+        return ControlResponse::unavailable(id, "mesh disabled".into());
+    };
+    ControlResponse::ok(id, json!(mesh.status()))
+}
+
+fn handle_mesh_topology(id: u64, state: &ControlState) -> ControlResponse {
+    let Some(mesh) = state.mesh.as_ref() else {
+        return ControlResponse::unavailable(id, "mesh disabled".into());
+    };
+    ControlResponse::ok(id, json!(mesh.topology(state.resource_name.as_str())))
+}
+
 fn handle_historian_alerts(
     id: u64,
     params: Option<serde_json::Value>,
     state: &ControlState,
 ) -> ControlResponse {
     let Some(historian) = state.historian.as_ref() else {
-        return ControlResponse::error(id, "historian disabled".into());
+        return ControlResponse::unavailable(id, "historian disabled".into());
     };
     let params = match params {
         Some(value) => match serde_json::from_value::<HistorianAlertsParams>(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
         None => HistorianAlertsParams::default(),
     };
@@ -1515,7 +1895,7 @@ fn handle_historian_alerts(
 fn handle_config_get(id: u64, state: &ControlState) -> ControlResponse {
     let settings = match state.settings.lock() {
         Ok(guard) => guard.clone(),
-        Err(_) => return ControlResponse::error(id, "settings unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "settings unavailable".into()),
     };
     let auth = state.auth_token.lock().ok();
     let auth_set = auth
@@ -1622,6 +2002,8 @@ fn handle_config_get(id: u64, state: &ControlState) -> ControlResponse {
                 .lock()
                 .map(|mode| format!("{:?}", *mode))
                 .unwrap_or_else(|_| "Production".to_string()),
+            "control.pause": configured_pause_policy(state).as_str(),
+            "control.history_depth": state.debug.history_depth(),
             "simulation.enabled": settings.simulation.enabled,
             "simulation.time_scale": settings.simulation.time_scale,
             "simulation.mode": settings.simulation.mode_label.as_str(),
@@ -1655,19 +2037,19 @@ fn handle_config_set(
         ($expr:expr) => {
             match $expr {
                 Ok(value) => value,
-                Err(error) => return ControlResponse::error(id, error),
+                Err(error) => return ControlResponse::invalid_params(id, error),
             }
         };
     }
 
     let params = match params {
         Some(params) => params,
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let params = match params.as_object() {
         Some(params) => params,
         None => {
-            return ControlResponse::error(
+            return ControlResponse::invalid_params(
                 id,
                 "invalid config payload: params must be an object".into(),
             )
@@ -1675,20 +2057,23 @@ fn handle_config_set(
     };
     let mut settings_guard = match state.settings.lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "settings unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "settings unavailable".into()),
     };
     let mut settings = settings_guard.clone();
     let mut updated = Vec::new();
     let mut restart_required = Vec::new();
     let mut auth_token = match state.auth_token.lock() {
         Ok(guard) => guard.clone(),
-        Err(_) => return ControlResponse::error(id, "auth token unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "auth token unavailable".into()),
     };
     let mut auth_changed = false;
     if let Some(value) = params.get("control.auth_token") {
         if value.is_null() {
             if state.control_requires_auth {
-                return ControlResponse::error(id, "auth token required for tcp endpoints".into());
+                return ControlResponse::invalid_params(
+                    id,
+                    "auth token required for tcp endpoints".into(),
+                );
             }
             auth_token = None;
             auth_changed = true;
@@ -1696,7 +2081,7 @@ fn handle_config_set(
         } else if let Some(token) = value.as_str() {
             let token = token.trim();
             if token.is_empty() {
-                return ControlResponse::error(
+                return ControlResponse::invalid_params(
                     id,
                     config_value_error("control.auth_token", "must not be empty"),
                 );
@@ -1705,7 +2090,7 @@ fn handle_config_set(
             auth_changed = true;
             updated.push("control.auth_token");
         } else {
-            return ControlResponse::error(
+            return ControlResponse::invalid_params(
                 id,
                 config_type_error("control.auth_token", "string or null"),
             );
@@ -1714,11 +2099,12 @@ fn handle_config_set(
 
     let mut control_mode = match state.control_mode.lock() {
         Ok(guard) => *guard,
-        Err(_) => return ControlResponse::error(id, "control mode unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "control mode unavailable".into()),
     };
     let mut control_mode_changed = false;
     let mut debug_enabled = state.debug_enabled.load(Ordering::Relaxed);
     let mut debug_enabled_changed = false;
+    let mut history_depth = None;
 
     for (key, value) in params {
         match key.as_str() {
@@ -1777,13 +2163,13 @@ fn handle_config_set(
             "web.auth" => {
                 let auth = parse_or_error!(expect_non_empty_string(key, value));
                 if auth.eq_ignore_ascii_case("token") && auth_token.is_none() {
-                    return ControlResponse::error(
+                    return ControlResponse::invalid_params(
                         id,
                         config_value_error("web.auth", "token mode requires control.auth_token"),
                     );
                 }
                 if !(auth.eq_ignore_ascii_case("local") || auth.eq_ignore_ascii_case("token")) {
-                    return ControlResponse::error(
+                    return ControlResponse::invalid_params(
                         id,
                         config_value_error("web.auth", "expected 'local' or 'token'"),
                     );
@@ -1859,14 +2245,14 @@ fn handle_config_set(
                 } else if let Some(token) = value.as_str() {
                     let token = token.trim();
                     if token.is_empty() {
-                        return ControlResponse::error(
+                        return ControlResponse::invalid_params(
                             id,
                             config_value_error("mesh.auth_token", "must not be empty"),
                         );
                     }
                     settings.mesh.auth_token = Some(SmolStr::new(token));
                 } else {
-                    return ControlResponse::error(
+                    return ControlResponse::invalid_params(
                         id,
                         config_type_error("mesh.auth_token", "string or null"),
                     );
@@ -1885,7 +2271,7 @@ fn handle_config_set(
                     "production" => ControlMode::Production,
                     "debug" => ControlMode::Debug,
                     _ => {
-                        return ControlResponse::error(
+                        return ControlResponse::invalid_params(
                             id,
                             config_value_error("control.mode", "expected 'production' or 'debug'"),
                         )
@@ -1895,8 +2281,40 @@ fn handle_config_set(
                 updated.push("control.mode");
                 restart_required.push("control.mode");
             }
+            "control.pause" => {
+                if value.is_null() {
+                    settings.pause_policy = None;
+                } else {
+                    let text = parse_or_error!(expect_non_empty_string(key, value));
+                    let Some(policy) = parse_pause_policy(text) else {
+                        return ControlResponse::invalid_params(
+                            id,
+                            config_value_error(
+                                "control.pause",
+                                "expected 'immediate', 'cycle_end', or null",
+                            ),
+                        );
+                    };
+                    settings.pause_policy = Some(policy);
+                }
+                updated.push("control.pause");
+            }
+            "control.history_depth" => {
+                let depth = parse_or_error!(expect_positive_i64(key, value));
+                if depth > crate::debug::MAX_HISTORY_DEPTH as i64 {
+                    return ControlResponse::invalid_params(
+                        id,
+                        config_value_error(
+                            "control.history_depth",
+                            &format!("expected at most {}", crate::debug::MAX_HISTORY_DEPTH),
+                        ),
+                    );
+                }
+                history_depth = Some(depth as usize);
+                updated.push("control.history_depth");
+            }
             _ => {
-                return ControlResponse::error(id, format!("unknown config key '{key}'"));
+                return ControlResponse::invalid_params(id, format!("unknown config key '{key}'"));
             }
         }
     }
@@ -1907,19 +2325,22 @@ fn handle_config_set(
         if let Ok(mut guard) = state.auth_token.lock() {
             *guard = auth_token;
         } else {
-            return ControlResponse::error(id, "auth token unavailable".into());
+            return ControlResponse::unavailable(id, "auth token unavailable".into());
         }
     }
     if control_mode_changed {
         if let Ok(mut guard) = state.control_mode.lock() {
             *guard = control_mode;
         } else {
-            return ControlResponse::error(id, "control mode unavailable".into());
+            return ControlResponse::unavailable(id, "control mode unavailable".into());
         }
     }
     if debug_enabled_changed {
         state.debug_enabled.store(debug_enabled, Ordering::Relaxed);
     }
+    if let Some(depth) = history_depth {
+        state.debug.set_history_depth(depth);
+    }
 
     let _ = state
         .resource
@@ -2034,20 +2455,43 @@ fn expect_string_map(
     Ok(output)
 }
 
-fn handle_pause(id: u64, state: &ControlState) -> ControlResponse {
-    let mode = state
-        .control_mode
-        .lock()
-        .map(|value| *value)
-        .unwrap_or(ControlMode::Production);
-    if matches!(mode, ControlMode::Debug) {
-        let _ = state
-            .debug
-            .apply_action(crate::debug::ControlAction::Pause(None));
-    } else {
-        let _ = state.resource.pause();
+fn handle_pause(
+    id: u64,
+    params: Option<serde_json::Value>,
+    state: &ControlState,
+) -> ControlResponse {
+    let params: PauseParams = match params {
+        Some(value) => match serde_json::from_value(value) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
+        },
+        None => PauseParams::default(),
+    };
+    let policy = match params.policy.as_deref() {
+        Some(text) => match parse_pause_policy(text) {
+            Some(policy) => policy,
+            None => {
+                return ControlResponse::invalid_params(
+                    id,
+                    "invalid pause policy (expected 'immediate' or 'cycle_end')".into(),
+                )
+            }
+        },
+        None => configured_pause_policy(state),
+    };
+    match policy {
+        PausePolicy::Immediate => {
+            let _ = state
+                .debug
+                .apply_action(crate::debug::ControlAction::Pause(None));
+        }
+        PausePolicy::CycleEnd => {
+            let _ = state.resource.pause();
+        }
     }
-    ControlResponse::ok(id, json!({"status": "paused"}))
+    ControlResponse::ok(id, json!({"status": "paused", "policy": policy.as_str()}))
 }
 
 fn handle_resume(id: u64, state: &ControlState) -> ControlResponse {
@@ -2056,16 +2500,46 @@ fn handle_resume(id: u64, state: &ControlState) -> ControlResponse {
         .lock()
         .map(|value| *value)
         .unwrap_or(ControlMode::Production);
-    if matches!(mode, ControlMode::Debug) {
+    // Resume whichever layer holds the program: the debugger mid-cycle or the
+    // resource at a cycle boundary.
+    if matches!(mode, ControlMode::Debug) || state.debug.is_paused() {
         let _ = state
             .debug
             .apply_action(crate::debug::ControlAction::Continue);
-    } else {
+    }
+    if matches!(mode, ControlMode::Production)
+        || state.resource.state() == crate::scheduler::ResourceState::Paused
+    {
         let _ = state.resource.resume();
     }
     ControlResponse::ok(id, json!({"status": "running"}))
 }
 
+/// `runtime.control.pause`, or the default for the current control mode.
+fn configured_pause_policy(state: &ControlState) -> PausePolicy {
+    let configured = state
+        .settings
+        .lock()
+        .ok()
+        .and_then(|settings| settings.pause_policy);
+    configured.unwrap_or_else(|| {
+        let mode = state
+            .control_mode
+            .lock()
+            .map(|value| *value)
+            .unwrap_or(ControlMode::Production);
+        PausePolicy::default_for(mode)
+    })
+}
+
+fn parse_pause_policy(text: &str) -> Option<PausePolicy> {
+    match text.trim().to_ascii_lowercase().as_str() {
+        "immediate" => Some(PausePolicy::Immediate),
+        "cycle_end" | "cycle-end" => Some(PausePolicy::CycleEnd),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum StepKind {
     In,
@@ -2098,27 +2572,195 @@ fn handle_debug_state(id: u64, state: &ControlState) -> ControlResponse {
     )
 }
 
-fn handle_debug_stops(id: u64, state: &ControlState) -> ControlResponse {
-    let stops = state
-        .debug
-        .drain_stops()
-        .into_iter()
-        .filter_map(|stop| debug_stop_to_json(stop, state))
-        .collect::<Vec<_>>();
-    ControlResponse::ok(id, json!({ "stops": stops }))
-}
-
-fn handle_debug_stack(id: u64, state: &ControlState) -> ControlResponse {
-    let snapshot = match state.debug.snapshot() {
-        Some(snapshot) => snapshot,
-        None => return ControlResponse::error(id, "no snapshot available".into()),
+fn handle_debug_history(
+    id: u64,
+    params: Option<serde_json::Value>,
+    state: &ControlState,
+    session: Option<&ControlSession>,
+) -> ControlResponse {
+    let params: DebugHistoryParams = match params {
+        Some(value) => match serde_json::from_value(value) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
+        },
+        None => DebugHistoryParams::default(),
     };
-    let frames = snapshot.storage.frames();
-    let frame_locations = state.debug.frame_locations();
-    let fallback = state.debug.last_stop().and_then(|stop| stop.location);
-    let mut stack_frames = Vec::new();
-    if frames.is_empty() {
-        if let Some(location) = fallback {
+    if params.clear {
+        if let Some(session) = session {
+            // Only this session's subscriptions; others keep recording.
+            let names = if params.variables.is_empty() {
+                session.history_names()
+            } else {
+                params
+                    .variables
+                    .iter()
+                    .map(|name| SmolStr::new(name.trim()))
+                    .collect()
+            };
+            state
+                .sessions
+                .release_history(session, &names, &state.debug);
+            return ControlResponse::ok(id, json!({ "status": "cleared" }));
+        }
+        if params.variables.is_empty() {
+            state.debug.clear_history();
+        }
+        for name in &params.variables {
+            state.debug.unwatch_history(name);
+        }
+        return ControlResponse::ok(id, json!({ "status": "cleared" }));
+    }
+    let names = if params.variables.is_empty() {
+        session.map_or_else(
+            || state.debug.history_names(),
+            ControlSession::history_names,
+        )
+    } else {
+        params
+            .variables
+            .iter()
+            .map(|name| SmolStr::new(name.trim()))
+            .filter(|name| !name.is_empty())
+            .collect()
+    };
+    let variables = names
+        .iter()
+        .map(|name| {
+            // Asking for a variable starts recording it from the next cycle end.
+            state.debug.watch_history(name);
+            if let Some(session) = session {
+                session.subscribe_history(name);
+            }
+            let history = state.debug.history(name, params.limit);
+            let samples = history
+                .as_ref()
+                .map(|history| {
+                    history
+                        .samples
+                        .iter()
+                        .map(|sample| {
+                            json!({
+                                "cycle": sample.cycle,
+                                "time_ns": sample.time.as_nanos(),
+                                "value": crate::debug::dap::format_value(&sample.value),
+                                "number": history_number(&sample.value),
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            json!({
+                "name": history.as_ref().map_or(name.as_str(), |history| history.name.as_str()),
+                "samples": samples,
+            })
+        })
+        .collect::<Vec<_>>();
+    ControlResponse::ok(
+        id,
+        json!({ "depth": state.debug.history_depth(), "variables": variables }),
+    )
+}
+
+/// Numeric view of a history sample for charting; BOOL maps to 0/1.
+fn history_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Bool(flag) => Some(if *flag { 1.0 } else { 0.0 }),
+        Value::SInt(v) => Some(f64::from(*v)),
+        Value::Int(v) => Some(f64::from(*v)),
+        Value::DInt(v) => Some(f64::from(*v)),
+        Value::LInt(v) => Some(*v as f64),
+        Value::USInt(v) => Some(f64::from(*v)),
+        Value::UInt(v) => Some(f64::from(*v)),
+        Value::UDInt(v) => Some(f64::from(*v)),
+        Value::ULInt(v) => Some(*v as f64),
+        Value::Real(v) => Some(f64::from(*v)),
+        Value::LReal(v) => Some(*v),
+        Value::Byte(v) => Some(f64::from(*v)),
+        Value::Word(v) => Some(f64::from(*v)),
+        Value::DWord(v) => Some(f64::from(*v)),
+        Value::LWord(v) => Some(*v as f64),
+        Value::Time(v) | Value::LTime(v) => Some(v.as_millis() as f64),
+        _ => None,
+    }
+}
+
+/// Variable handles of the session, or the shared handles for sessionless requests.
+fn debug_variable_handles<'a>(
+    state: &'a ControlState,
+    session: Option<&'a ControlSession>,
+) -> &'a Mutex<DebugVariableHandles> {
+    session.map_or(&*state.debug_variables, |session| &session.variables)
+}
+
+fn handle_diagnostics_collect(id: u64, state: &ControlState) -> ControlResponse {
+    ControlResponse::ok(
+        id,
+        collect_diagnostics(state, json!({ "kind": "on_demand" })),
+    )
+}
+
+fn handle_session_info(
+    id: u64,
+    state: &ControlState,
+    session: Option<&ControlSession>,
+) -> ControlResponse {
+    let sessions = state
+        .sessions
+        .list()
+        .iter()
+        .map(|open| {
+            json!({
+                "id": open.id(),
+                "transport": open.transport(),
+                "client": open.client(),
+                "requests": open.requests(),
+            })
+        })
+        .collect::<Vec<_>>();
+    ControlResponse::ok(
+        id,
+        json!({
+            "session": session.map(ControlSession::info_json),
+            "sessions": sessions,
+        }),
+    )
+}
+
+fn handle_debug_stops(
+    id: u64,
+    state: &ControlState,
+    session: Option<&ControlSession>,
+) -> ControlResponse {
+    // Sessions follow the stop log with their own cursor so one client polling does not
+    // consume stops meant for another.
+    let stops = match session {
+        Some(session) => {
+            let (stops, cursor) = state.debug.stops_since(session.stop_cursor());
+            session.set_stop_cursor(cursor);
+            stops
+        }
+        None => state.debug.drain_stops(),
+    };
+    let stops = stops
+        .into_iter()
+        .filter_map(|stop| debug_stop_to_json(stop, state))
+        .collect::<Vec<_>>();
+    ControlResponse::ok(id, json!({ "stops": stops }))
+}
+
+fn handle_debug_stack(id: u64, state: &ControlState) -> ControlResponse {
+    let snapshot = match state.debug.snapshot() {
+        Some(snapshot) => snapshot,
+        None => return ControlResponse::unavailable(id, "no snapshot available".into()),
+    };
+    let frames = snapshot.storage.frames();
+    let frame_locations = state.debug.frame_locations();
+    let fallback = state.debug.last_stop().and_then(|stop| stop.location);
+    let mut stack_frames = Vec::new();
+    if frames.is_empty() {
+        if let Some(location) = fallback {
             if let Some(frame) = location_to_stack_frame(0, "Main", &location, state) {
                 stack_frames.push(frame);
             }
@@ -2149,18 +2791,21 @@ fn handle_debug_scopes(
     id: u64,
     params: Option<serde_json::Value>,
     state: &ControlState,
+    session: Option<&ControlSession>,
 ) -> ControlResponse {
     let params: DebugScopesParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     debug!("control debug.scopes frame_id={}", params.frame_id);
     let snapshot = match state.debug.snapshot() {
         Some(snapshot) => snapshot,
-        None => return ControlResponse::error(id, "no snapshot available".into()),
+        None => return ControlResponse::unavailable(id, "no snapshot available".into()),
     };
     let requested_frame = crate::memory::FrameId(params.frame_id);
     let current_frame = snapshot.storage.current_frame().map(|frame| frame.id);
@@ -2201,9 +2846,9 @@ fn handle_debug_scopes(
         .and_then(|guard| guard.clone());
     let has_io = crate::debug::dap::io_scope_available(io_snapshot.as_ref());
 
-    let mut handles = match state.debug_variables.lock() {
+    let mut handles = match debug_variable_handles(state, session).lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "debug variables unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "debug variables unavailable".into()),
     };
     handles.clear();
 
@@ -2288,13 +2933,16 @@ fn handle_debug_variables(
     id: u64,
     params: Option<serde_json::Value>,
     state: &ControlState,
+    session: Option<&ControlSession>,
 ) -> ControlResponse {
     let params: DebugVariablesParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     debug!(
         "control debug.variables reference={}",
@@ -2302,16 +2950,16 @@ fn handle_debug_variables(
     );
     let snapshot = match state.debug.snapshot() {
         Some(snapshot) => snapshot,
-        None => return ControlResponse::error(id, "no snapshot available".into()),
+        None => return ControlResponse::unavailable(id, "no snapshot available".into()),
     };
     let io_snapshot = state
         .io_snapshot
         .lock()
         .ok()
         .and_then(|guard| guard.clone());
-    let mut handles = match state.debug_variables.lock() {
+    let mut handles = match debug_variable_handles(state, session).lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "debug variables unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "debug variables unavailable".into()),
     };
     let Some(handle) = handles.get(params.variables_reference).cloned() else {
         return ControlResponse::ok(id, json!({ "variables": [] }));
@@ -2465,13 +3113,15 @@ fn handle_debug_evaluate(
     let params: DebugEvaluateParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let snapshot = match state.debug.snapshot() {
         Some(snapshot) => snapshot,
-        None => return ControlResponse::error(id, "no snapshot available".into()),
+        None => return ControlResponse::unavailable(id, "no snapshot available".into()),
     };
     let frame_id = params.frame_id.map(crate::memory::FrameId);
     if let Some(frame_id) = frame_id {
@@ -2481,12 +3131,12 @@ fn handle_debug_evaluate(
             .iter()
             .any(|frame| frame.id == frame_id)
         {
-            return ControlResponse::error(id, "unknown frame id".into());
+            return ControlResponse::not_found(id, "unknown frame id".into());
         }
     }
     let metadata = match state.metadata.lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "metadata unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
     };
     let using = frame_id
         .and_then(|frame_id| metadata.using_for_frame(&snapshot.storage, frame_id))
@@ -2501,6 +3151,7 @@ fn handle_debug_evaluate(
         Ok(expr) => expr,
         Err(err) => return ControlResponse::error(id, err.to_string()),
     };
+    drop(metadata);
     let value = match evaluate_with_snapshot(&expr, &registry, frame_id, &snapshot, &using, state) {
         Ok(value) => value,
         Err(err) => return ControlResponse::error(id, err.to_string()),
@@ -2517,6 +3168,368 @@ fn handle_debug_evaluate(
     )
 }
 
+/// Reject debugger variable writes unless the runtime runs in debug control mode.
+fn ensure_debug_writes_allowed(id: u64, state: &ControlState) -> Result<(), ControlResponse> {
+    let mode = state
+        .control_mode
+        .lock()
+        .map(|value| *value)
+        .unwrap_or(ControlMode::Production);
+    if matches!(mode, ControlMode::Production) {
+        return Err(ControlResponse::error_code(
+            id,
+            ControlErrorCode::ReadOnly,
+            "variables are read-only in production control mode".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Coerce a debugger-provided value to the type of the variable it replaces.
+fn coerce_debug_write(value: Value, current: &Value) -> Result<Value, String> {
+    let Some(type_id) = crate::debug::dap::value_type_id(current) else {
+        return Err("this variable cannot be edited".to_string());
+    };
+    crate::harness::coerce_value_to_type(value, type_id).map_err(|err| err.to_string())
+}
+
+fn debug_write_result(id: u64, value: &Value) -> ControlResponse {
+    ControlResponse::ok(
+        id,
+        json!({
+            "value": crate::debug::dap::format_value(value),
+            "type": crate::debug::dap::value_type_name(value),
+            "variablesReference": 0,
+        }),
+    )
+}
+
+/// Parse and evaluate the right-hand side of a debugger write against the snapshot.
+fn evaluate_debug_write_value(
+    text: &str,
+    frame_id: Option<crate::memory::FrameId>,
+    snapshot: &crate::debug::DebugSnapshot,
+    state: &ControlState,
+) -> Result<(Value, trust_hir::types::TypeRegistry, Vec<SmolStr>), String> {
+    let (mut registry, profile, using) = {
+        let metadata = state
+            .metadata
+            .lock()
+            .map_err(|_| "metadata unavailable".to_string())?;
+        let using = frame_id
+            .and_then(|frame_id| metadata.using_for_frame(&snapshot.storage, frame_id))
+            .unwrap_or_default();
+        (metadata.registry().clone(), metadata.profile(), using)
+    };
+    let expr = crate::harness::parse_debug_expression(text, &mut registry, profile, &using)
+        .map_err(|err| err.to_string())?;
+    let value = evaluate_with_snapshot(&expr, &registry, frame_id, snapshot, &using, state)
+        .map_err(|err| match err {
+            RuntimeError::InvalidFrame(_) => "unknown frame id".to_string(),
+            _ => err.to_string(),
+        })?;
+    Ok((value, registry, using))
+}
+
+fn handle_debug_set_variable(
+    id: u64,
+    params: Option<serde_json::Value>,
+    state: &ControlState,
+    session: Option<&ControlSession>,
+) -> ControlResponse {
+    let params: DebugSetVariableParams = match params {
+        Some(value) => match serde_json::from_value(value) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
+        },
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
+    };
+    if let Err(response) = ensure_debug_writes_allowed(id, state) {
+        return response;
+    }
+    let snapshot = match state.debug.snapshot() {
+        Some(snapshot) => snapshot,
+        None => return ControlResponse::unavailable(id, "no snapshot available".into()),
+    };
+    let handle = match debug_variable_handles(state, session).lock() {
+        Ok(handles) => handles.get(params.variables_reference).cloned(),
+        Err(_) => return ControlResponse::unavailable(id, "debug variables unavailable".into()),
+    };
+    let Some(handle) = handle else {
+        return ControlResponse::not_found(id, "unknown variables reference".into());
+    };
+    let frame_id = match &handle {
+        VariableHandle::Locals(frame_id) => Some(*frame_id),
+        _ => None,
+    };
+    let name = params.name.as_str();
+    let current = match &handle {
+        VariableHandle::Globals => snapshot.storage.get_global(name).cloned(),
+        VariableHandle::Retain => snapshot.storage.get_retain(name).cloned(),
+        VariableHandle::Instance(instance_id) => {
+            if name == "parent" {
+                return ControlResponse::error_code(
+                    id,
+                    ControlErrorCode::ReadOnly,
+                    "parent instance is read-only".into(),
+                );
+            }
+            snapshot
+                .storage
+                .get_instance_var(*instance_id, name)
+                .cloned()
+        }
+        VariableHandle::Locals(frame_id) => {
+            let Some(frame) = snapshot
+                .storage
+                .frames()
+                .iter()
+                .find(|frame| frame.id == *frame_id)
+            else {
+                return ControlResponse::not_found(id, "unknown frame id".into());
+            };
+            frame.variables.get(name).cloned().or_else(|| {
+                frame
+                    .instance_id
+                    .and_then(|instance_id| snapshot.storage.get_instance_var(instance_id, name))
+                    .cloned()
+            })
+        }
+        _ => {
+            return ControlResponse::error_code(
+                id,
+                ControlErrorCode::ReadOnly,
+                "this variable cannot be edited".into(),
+            )
+        }
+    };
+    let Some(current) = current else {
+        return ControlResponse::not_found(id, format!("unknown variable '{name}'"));
+    };
+    let value = match evaluate_debug_write_value(&params.value, frame_id, &snapshot, state) {
+        Ok((value, _, _)) => value,
+        Err(err) => return ControlResponse::error(id, err),
+    };
+    let value = match coerce_debug_write(value, &current) {
+        Ok(value) => value,
+        Err(err) => return ControlResponse::error(id, err),
+    };
+
+    let target = match handle {
+        VariableHandle::Globals => VarTarget::Global(params.name.clone()),
+        VariableHandle::Retain => VarTarget::Retain(params.name.clone()),
+        VariableHandle::Instance(instance_id) => {
+            VarTarget::Instance(instance_id.0, params.name.clone())
+        }
+        VariableHandle::Locals(frame_id) => {
+            let frame = snapshot
+                .storage
+                .frames()
+                .iter()
+                .find(|frame| frame.id == frame_id);
+            match frame {
+                Some(frame) if frame.variables.contains_key(name) => {
+                    if params.force {
+                        return ControlResponse::error_code(
+                            id,
+                            ControlErrorCode::ReadOnly,
+                            "local variables cannot be forced".into(),
+                        );
+                    }
+                    state
+                        .debug
+                        .enqueue_local_write(frame_id, name, value.clone());
+                    let _ = state.debug.with_snapshot(|snapshot| {
+                        snapshot.storage.with_frame(frame_id, |storage| {
+                            storage.set_local(name, value.clone());
+                        })
+                    });
+                    return debug_write_result(id, &value);
+                }
+                Some(frame) => match frame.instance_id {
+                    Some(instance_id) => VarTarget::Instance(instance_id.0, params.name.clone()),
+                    None => return ControlResponse::not_found(id, "unknown local variable".into()),
+                },
+                None => return ControlResponse::not_found(id, "unknown frame id".into()),
+            }
+        }
+        _ => {
+            return ControlResponse::error_code(
+                id,
+                ControlErrorCode::ReadOnly,
+                "this variable cannot be edited".into(),
+            )
+        }
+    };
+    match target {
+        VarTarget::Global(name) => {
+            if params.force {
+                state.debug.force_global(name.as_str(), value.clone());
+            } else {
+                state
+                    .debug
+                    .enqueue_global_write(name.as_str(), value.clone());
+            }
+            let _ = state.debug.with_snapshot(|snapshot| {
+                snapshot.storage.set_global(name.as_str(), value.clone());
+            });
+        }
+        VarTarget::Retain(name) => {
+            if params.force {
+                state.debug.force_retain(name.as_str(), value.clone());
+            } else {
+                state
+                    .debug
+                    .enqueue_retain_write(name.as_str(), value.clone());
+            }
+            let _ = state.debug.with_snapshot(|snapshot| {
+                snapshot.storage.set_retain(name.as_str(), value.clone());
+            });
+        }
+        VarTarget::Instance(instance_id, name) => {
+            let instance_id = crate::memory::InstanceId(instance_id);
+            if params.force {
+                state
+                    .debug
+                    .force_instance(instance_id, name.as_str(), value.clone());
+            } else {
+                state
+                    .debug
+                    .enqueue_instance_write(instance_id, name.as_str(), value.clone());
+            }
+            let _ = state.debug.with_snapshot(|snapshot| {
+                snapshot
+                    .storage
+                    .set_instance_var(instance_id, name.as_str(), value.clone());
+            });
+        }
+    }
+    debug_write_result(id, &value)
+}
+
+fn handle_debug_set_expression(
+    id: u64,
+    params: Option<serde_json::Value>,
+    state: &ControlState,
+) -> ControlResponse {
+    let params: DebugSetExpressionParams = match params {
+        Some(value) => match serde_json::from_value(value) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
+        },
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
+    };
+    if let Err(response) = ensure_debug_writes_allowed(id, state) {
+        return response;
+    }
+    let snapshot = match state.debug.snapshot() {
+        Some(snapshot) => snapshot,
+        None => return ControlResponse::unavailable(id, "no snapshot available".into()),
+    };
+    let mut frame_id = params.frame_id.map(crate::memory::FrameId);
+    if frame_id == Some(crate::memory::FrameId(0)) && snapshot.storage.frames().is_empty() {
+        frame_id = None;
+    }
+    let (value, mut registry, using) =
+        match evaluate_debug_write_value(&params.value, frame_id, &snapshot, state) {
+            Ok(result) => result,
+            Err(err) => return ControlResponse::error(id, err),
+        };
+    let profile = match state.metadata.lock() {
+        Ok(metadata) => metadata.profile(),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
+    };
+    let target = match crate::harness::parse_debug_lvalue(
+        &params.expression,
+        &mut registry,
+        profile,
+        &using,
+    ) {
+        Ok(target) => target,
+        Err(err) => return ControlResponse::error(id, err.to_string()),
+    };
+    let mut storage = snapshot.storage.clone();
+    let current = match with_snapshot_eval(
+        &mut storage,
+        snapshot.now,
+        &registry,
+        frame_id,
+        &using,
+        state,
+        |ctx| crate::eval::expr::read_lvalue(ctx, &target),
+    ) {
+        Ok(current) => current,
+        Err(RuntimeError::InvalidFrame(_)) => {
+            return ControlResponse::not_found(id, "unknown frame id".into())
+        }
+        Err(err) => return ControlResponse::error(id, err.to_string()),
+    };
+    let value = match coerce_debug_write(value, &current) {
+        Ok(value) => value,
+        Err(err) => return ControlResponse::error(id, err),
+    };
+    let written = state.debug.with_snapshot(|paused| {
+        with_snapshot_eval(
+            &mut paused.storage,
+            snapshot.now,
+            &registry,
+            frame_id,
+            &using,
+            state,
+            |ctx| crate::eval::expr::write_lvalue(ctx, &target, value.clone()),
+        )
+    });
+    if let Some(Err(err)) = written {
+        return ControlResponse::error(id, err.to_string());
+    }
+    state
+        .debug
+        .enqueue_lvalue_write(frame_id, using, target, value.clone());
+    debug_write_result(id, &value)
+}
+
+fn handle_debug_complete(
+    id: u64,
+    params: Option<serde_json::Value>,
+    state: &ControlState,
+) -> ControlResponse {
+    let params: DebugCompleteParams = match params {
+        Some(value) => match serde_json::from_value(value) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
+        },
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
+    };
+    let snapshot = match state.debug.snapshot() {
+        Some(snapshot) => snapshot,
+        None => return ControlResponse::unavailable(id, "no snapshot available".into()),
+    };
+    let frame_id = params.frame_id.map(crate::memory::FrameId);
+    if let Some(frame_id) = frame_id {
+        if !snapshot
+            .storage
+            .frames()
+            .iter()
+            .any(|frame| frame.id == frame_id)
+        {
+            return ControlResponse::not_found(id, "unknown frame id".into());
+        }
+    }
+    let targets = crate::debug::complete_debug_expression(
+        &snapshot.storage,
+        frame_id,
+        &params.text,
+        params.column,
+    );
+    ControlResponse::ok(id, json!({ "targets": targets }))
+}
+
 fn handle_debug_breakpoint_locations(
     id: u64,
     params: Option<serde_json::Value>,
@@ -2525,22 +3538,24 @@ fn handle_debug_breakpoint_locations(
     let params: DebugBreakpointLocationsParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let path = PathBuf::from(params.source);
     let file_id = match state.sources.file_id_for_path(&path) {
         Some(id) => id,
-        None => return ControlResponse::error(id, "unknown source path".into()),
+        None => return ControlResponse::not_found(id, "unknown source path".into()),
     };
     let source_text = match state.sources.source_text(file_id) {
         Some(text) => text,
-        None => return ControlResponse::error(id, "source text not loaded".into()),
+        None => return ControlResponse::unavailable(id, "source text not loaded".into()),
     };
     let metadata = match state.metadata.lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "metadata unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
     };
     let mut breakpoints = Vec::new();
     if let Some(locations) = metadata.statement_locations(file_id) {
@@ -2575,11 +3590,13 @@ fn debug_stop_to_json(
         crate::debug::DebugStopReason::Step => "step",
         crate::debug::DebugStopReason::Pause => "pause",
         crate::debug::DebugStopReason::Entry => "entry",
+        crate::debug::DebugStopReason::Exception => "exception",
     };
     let mut payload = json!({
         "reason": reason,
         "thread_id": stop.thread_id,
         "breakpoint_generation": stop.breakpoint_generation,
+        "fault": stop.fault.as_deref(),
     });
     if let Some(location) = stop.location {
         if let Some(text) = state.sources.source_text(location.file_id) {
@@ -2649,22 +3666,42 @@ fn evaluate_with_snapshot(
     using: &[smol_str::SmolStr],
     state: &ControlState,
 ) -> Result<Value, RuntimeError> {
+    let mut storage = snapshot.storage.clone();
+    with_snapshot_eval(
+        &mut storage,
+        snapshot.now,
+        registry,
+        frame_id,
+        using,
+        state,
+        |ctx| crate::eval::eval_expr(ctx, expr),
+    )
+}
+
+/// Run `f` in an evaluation context over paused snapshot storage.
+fn with_snapshot_eval<T>(
+    storage: &mut crate::memory::VariableStorage,
+    now: crate::value::Duration,
+    registry: &trust_hir::types::TypeRegistry,
+    frame_id: Option<crate::memory::FrameId>,
+    using: &[smol_str::SmolStr],
+    state: &ControlState,
+    f: impl FnOnce(&mut crate::eval::EvalContext<'_>) -> Result<T, RuntimeError>,
+) -> Result<T, RuntimeError> {
     let metadata = state
         .metadata
         .lock()
         .map_err(|_| RuntimeError::ControlError("metadata unavailable".into()))?;
     let profile = metadata.profile();
-    let now = snapshot.now;
     let functions = metadata.functions();
     let stdlib = metadata.stdlib();
     let function_blocks = metadata.function_blocks();
     let classes = metadata.classes();
     let access = metadata.access_map();
 
-    let mut storage = snapshot.storage.clone();
     let eval = |storage: &mut crate::memory::VariableStorage,
                 instance_id: Option<crate::memory::InstanceId>|
-     -> Result<Value, RuntimeError> {
+     -> Result<T, RuntimeError> {
         let mut ctx = crate::eval::EvalContext {
             storage,
             registry,
@@ -2683,8 +3720,14 @@ fn evaluate_with_snapshot(
             loop_depth: 0,
             pause_requested: false,
             execution_deadline: None,
+            workers: None,
+            bounds: crate::task::BoundsPolicy::Fault,
+            overflow: crate::eval::ops::OverflowPolicy::Wrap,
+            subrange: crate::task::SubrangePolicy::Off,
+            fault_site: None,
+            pou_code: None,
         };
-        crate::eval::eval_expr(&mut ctx, expr)
+        f(&mut ctx)
     };
 
     if let Some(frame_id) = frame_id {
@@ -2695,7 +3738,7 @@ fn evaluate_with_snapshot(
             })
             .ok_or(RuntimeError::InvalidFrame(frame_id.0))?
     } else {
-        eval(&mut storage, None)
+        eval(storage, None)
     }
 }
 
@@ -2705,27 +3748,29 @@ fn handle_breakpoints_set(
     state: &ControlState,
 ) -> ControlResponse {
     if state.sources.is_empty() {
-        return ControlResponse::error(id, "no sources registered".into());
+        return ControlResponse::unavailable(id, "no sources registered".into());
     }
     let params: BreakpointsParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let path = PathBuf::from(params.source);
     let file_id = match state.sources.file_id_for_path(&path) {
         Some(id) => id,
-        None => return ControlResponse::error(id, "unknown source path".into()),
+        None => return ControlResponse::not_found(id, "unknown source path".into()),
     };
     let source_text = match state.sources.source_text(file_id) {
         Some(text) => text,
-        None => return ControlResponse::error(id, "source text not loaded".into()),
+        None => return ControlResponse::unavailable(id, "source text not loaded".into()),
     };
     let metadata = match state.metadata.lock() {
         Ok(guard) => guard,
-        Err(_) => return ControlResponse::error(id, "metadata unavailable".into()),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
     };
     let mut breakpoints = Vec::new();
     let mut resolved = Vec::new();
@@ -2758,14 +3803,16 @@ fn handle_breakpoints_clear(
     let params: BreakpointsParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let path = PathBuf::from(params.source);
     let file_id = match state.sources.file_id_for_path(&path) {
         Some(id) => id,
-        None => return ControlResponse::error(id, "unknown source path".into()),
+        None => return ControlResponse::not_found(id, "unknown source path".into()),
     };
     state.debug.set_breakpoints_for_file(file_id, Vec::new());
     ControlResponse::ok(id, json!({"status": "cleared"}))
@@ -2787,6 +3834,40 @@ fn handle_breakpoints_list(id: u64, state: &ControlState) -> ControlResponse {
     ControlResponse::ok(id, json!({ "breakpoints": breakpoints }))
 }
 
+fn handle_breakpoints_exceptions(
+    id: u64,
+    params: Option<serde_json::Value>,
+    state: &ControlState,
+) -> ControlResponse {
+    let params: BreakpointsExceptionsParams = match params {
+        Some(value) => match serde_json::from_value(value) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
+        },
+        None => BreakpointsExceptionsParams::default(),
+    };
+    let mut filters = crate::debug::FaultBreakpoints::default();
+    for filter in &params.filters {
+        match filter.as_str() {
+            "faults" => filters.all_faults = true,
+            "unhandled" => filters.unhandled_faults = true,
+            "watchdog" => filters.watchdog = true,
+            other => {
+                return ControlResponse::invalid_params(
+                    id,
+                    format!(
+                        "unknown exception filter '{other}' (expected faults/unhandled/watchdog)"
+                    ),
+                )
+            }
+        }
+    }
+    state.debug.set_fault_breakpoints(filters);
+    ControlResponse::ok(id, json!({ "filters": params.filters }))
+}
+
 fn handle_breakpoints_clear_all(id: u64, state: &ControlState) -> ControlResponse {
     state.debug.clear_breakpoints();
     ControlResponse::ok(id, json!({ "status": "cleared" }))
@@ -2800,12 +3881,14 @@ fn handle_breakpoints_clear_id(
     let params: BreakpointsClearIdParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     if state.sources.source_text(params.file_id).is_none() {
-        return ControlResponse::error(id, "unknown file id".into());
+        return ControlResponse::not_found(id, "unknown file id".into());
     }
     state
         .debug
@@ -2830,6 +3913,53 @@ fn handle_io_read(id: u64, state: &ControlState) -> ControlResponse {
     )
 }
 
+fn handle_io_map_get(id: u64, state: &ControlState) -> ControlResponse {
+    let map = state
+        .settings
+        .lock()
+        .ok()
+        .map(|guard| guard.io_map.clone())
+        .unwrap_or_default();
+    let snapshot = state
+        .io_snapshot
+        .lock()
+        .ok()
+        .and_then(|guard| guard.clone());
+    let symbols = map
+        .symbols
+        .iter()
+        .map(|symbol| {
+            let value = snapshot.as_ref().and_then(|snapshot| {
+                snapshot
+                    .inputs
+                    .iter()
+                    .chain(&snapshot.outputs)
+                    .chain(&snapshot.memory)
+                    .find(|entry| {
+                        entry.address == symbol.address
+                            && entry
+                                .name
+                                .as_ref()
+                                .is_some_and(|name| name.eq_ignore_ascii_case(&symbol.name))
+                    })
+                    .map(|entry| format_snapshot_value(&entry.value))
+            });
+            let direction = match symbol.address.area {
+                crate::memory::IoArea::Input => "input",
+                crate::memory::IoArea::Output => "output",
+                crate::memory::IoArea::Memory => "memory",
+            };
+            json!({
+                "name": symbol.name.as_str(),
+                "address": format_address(&symbol.address),
+                "direction": direction,
+                "value": value,
+            })
+        })
+        .collect::<Vec<_>>();
+    ControlResponse::ok(id, json!({ "symbols": symbols }))
+}
+
 fn handle_io_write(
     id: u64,
     params: Option<serde_json::Value>,
@@ -2838,17 +3968,19 @@ fn handle_io_write(
     let params: IoWriteParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let address = match IoAddress::parse(&params.address) {
         Ok(addr) => addr,
-        Err(err) => return ControlResponse::error(id, err.to_string()),
+        Err(err) => return ControlResponse::invalid_params(id, err.to_string()),
     };
     let value = match parse_value(&params.value) {
         Ok(value) => value,
-        Err(err) => return ControlResponse::error(id, err.to_string()),
+        Err(err) => return ControlResponse::invalid_params(id, err.to_string()),
     };
     state.debug.enqueue_io_write(address, value);
     ControlResponse::ok(id, json!({"status": "queued"}))
@@ -2862,17 +3994,19 @@ fn handle_io_force(
     let params: IoWriteParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let address = match IoAddress::parse(&params.address) {
         Ok(addr) => addr,
-        Err(err) => return ControlResponse::error(id, err.to_string()),
+        Err(err) => return ControlResponse::invalid_params(id, err.to_string()),
     };
     let value = match parse_value(&params.value) {
         Ok(value) => value,
-        Err(err) => return ControlResponse::error(id, err.to_string()),
+        Err(err) => return ControlResponse::invalid_params(id, err.to_string()),
     };
     state.debug.force_io(address, value);
     ControlResponse::ok(id, json!({"status": "forced"}))
@@ -2886,13 +4020,15 @@ fn handle_io_unforce(
     let params: IoAddressParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let address = match IoAddress::parse(&params.address) {
         Ok(addr) => addr,
-        Err(err) => return ControlResponse::error(id, err.to_string()),
+        Err(err) => return ControlResponse::invalid_params(id, err.to_string()),
     };
     state.debug.release_io(&address);
     ControlResponse::ok(id, json!({"status": "released"}))
@@ -2906,13 +4042,15 @@ fn handle_eval(
     let params: EvalParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let snapshot = match state.debug.snapshot() {
         Some(snapshot) => snapshot,
-        None => return ControlResponse::error(id, "no snapshot available".into()),
+        None => return ControlResponse::unavailable(id, "no snapshot available".into()),
     };
     let name = params.expr.trim();
     let value = snapshot
@@ -2922,7 +4060,7 @@ fn handle_eval(
         .or_else(|| snapshot.storage.get_retain(name).cloned());
     match value {
         Some(value) => ControlResponse::ok(id, json!({ "value": format!("{value:?}") })),
-        None => ControlResponse::error(id, "unknown identifier".into()),
+        None => ControlResponse::not_found(id, "unknown identifier".into()),
     }
 }
 
@@ -2930,23 +4068,43 @@ fn handle_set(id: u64, params: Option<serde_json::Value>, state: &ControlState)
     let params: SetParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let value = match parse_value(&params.value) {
         Ok(value) => value,
-        Err(err) => return ControlResponse::error(id, err.to_string()),
+        Err(err) => return ControlResponse::invalid_params(id, err.to_string()),
     };
     if let Some(name) = params.target.strip_prefix("global:") {
+        let value = match subrange_checked_write(state, name.trim(), value) {
+            Ok(value) => value,
+            Err(err) => return ControlResponse::invalid_params(id, err),
+        };
         state.debug.enqueue_global_write(name.trim(), value);
         return ControlResponse::ok(id, json!({"status": "queued"}));
     }
     if let Some(name) = params.target.strip_prefix("retain:") {
+        let value = match subrange_checked_write(state, name.trim(), value) {
+            Ok(value) => value,
+            Err(err) => return ControlResponse::invalid_params(id, err),
+        };
         state.debug.enqueue_retain_write(name.trim(), value);
         return ControlResponse::ok(id, json!({"status": "queued"}));
     }
-    ControlResponse::error(id, "unsupported target".into())
+    ControlResponse::invalid_params(id, "unsupported target".into())
+}
+
+/// Applies the subrange policy to a global write before it is queued.
+fn subrange_checked_write(state: &ControlState, name: &str, value: Value) -> Result<Value, String> {
+    match state.metadata.lock() {
+        Ok(metadata) => metadata
+            .check_external_write(None, name, value)
+            .map_err(|err| err.to_string()),
+        Err(_) => Ok(value),
+    }
 }
 
 fn parse_var_target(target: &str) -> Result<VarTarget, String> {
@@ -2985,17 +4143,19 @@ fn handle_var_force(
     let params: VarForceParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let target = match parse_var_target(&params.target) {
         Ok(target) => target,
-        Err(err) => return ControlResponse::error(id, err),
+        Err(err) => return ControlResponse::invalid_params(id, err),
     };
     let value = match parse_value(&params.value) {
         Ok(value) => value,
-        Err(err) => return ControlResponse::error(id, err.to_string()),
+        Err(err) => return ControlResponse::invalid_params(id, err.to_string()),
     };
     match target {
         VarTarget::Global(name) => state.debug.force_global(name, value),
@@ -3017,13 +4177,15 @@ fn handle_var_unforce(
     let params: VarTargetParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let target = match parse_var_target(&params.target) {
         Ok(target) => target,
-        Err(err) => return ControlResponse::error(id, err),
+        Err(err) => return ControlResponse::invalid_params(id, err),
     };
     match target {
         VarTarget::Global(name) => state.debug.release_global(&name),
@@ -3066,22 +4228,40 @@ fn handle_shutdown(id: u64, state: &ControlState) -> ControlResponse {
     ControlResponse::ok(id, json!({"status": "stopping"}))
 }
 
-fn handle_restart(
+fn parse_restart_mode(
     id: u64,
     params: Option<serde_json::Value>,
-    state: &ControlState,
-) -> ControlResponse {
+) -> Result<RestartMode, ControlResponse> {
     let params: RestartParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return Err(ControlResponse::invalid_params(
+                    id,
+                    format!("invalid params: {err}"),
+                ))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return Err(ControlResponse::invalid_params(id, "missing params".into())),
     };
-    let mode = match params.mode.to_ascii_lowercase().as_str() {
-        "cold" => RestartMode::Cold,
-        "warm" => RestartMode::Warm,
-        _ => return ControlResponse::error(id, "invalid restart mode".into()),
+    match params.mode.to_ascii_lowercase().as_str() {
+        "cold" => Ok(RestartMode::Cold),
+        "warm" => Ok(RestartMode::Warm),
+        _ => Err(ControlResponse::invalid_params(
+            id,
+            "invalid restart mode".into(),
+        )),
+    }
+}
+
+fn handle_restart(
+    id: u64,
+    params: Option<serde_json::Value>,
+    state: &ControlState,
+) -> ControlResponse {
+    let mode = match parse_restart_mode(id, params) {
+        Ok(mode) => mode,
+        Err(response) => return response,
     };
     if let Ok(mut guard) = state.pending_restart.lock() {
         *guard = Some(mode);
@@ -3089,6 +4269,149 @@ fn handle_restart(
     ControlResponse::ok(id, json!({"status": "restart queued"}))
 }
 
+fn handle_restart_preview(
+    id: u64,
+    params: Option<serde_json::Value>,
+    state: &ControlState,
+) -> ControlResponse {
+    let mode = match parse_restart_mode(id, params) {
+        Ok(mode) => mode,
+        Err(response) => return response,
+    };
+    let (entries, rules) = match state.metadata.lock() {
+        Ok(metadata) => (metadata.restart_preview(mode), metadata.retention_rules()),
+        Err(_) => return ControlResponse::unavailable(id, "metadata unavailable".into()),
+    };
+    let (kept, reinitialized): (Vec<_>, Vec<_>) = entries.into_iter().partition(|entry| entry.kept);
+    let entry_json = |entry: &crate::RestartPreviewEntry| {
+        json!({
+            "name": entry.name.as_str(),
+            "declared": entry.declared.as_str(),
+            "class": entry.class.as_str(),
+        })
+    };
+    ControlResponse::ok(
+        id,
+        json!({
+            "mode": mode.as_str(),
+            "rules": {
+                "unqualified": rules.unqualified.as_str(),
+                "persistent_on_cold": rules.persistent_on_cold,
+            },
+            "kept": kept.iter().map(entry_json).collect::<Vec<_>>(),
+            "reinitialized": reinitialized.iter().map(entry_json).collect::<Vec<_>>(),
+        }),
+    )
+}
+
+fn handle_cycle_step(
+    id: u64,
+    params: Option<serde_json::Value>,
+    state: &ControlState,
+) -> ControlResponse {
+    let params: CycleStepParams = match params {
+        Some(value) => match serde_json::from_value(value) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
+        },
+        None => CycleStepParams::default(),
+    };
+    let cycles = params.cycles.unwrap_or(1);
+    if cycles == 0 {
+        return ControlResponse::invalid_params(id, "cycles must be at least 1".into());
+    }
+    if state.resource.state() != crate::scheduler::ResourceState::Paused {
+        return ControlResponse::error_code(
+            id,
+            ControlErrorCode::InvalidState,
+            "resource is not paused".into(),
+        );
+    }
+    let rx = match state.resource.step_cycles(cycles) {
+        Ok(rx) => rx,
+        Err(err) => return ControlResponse::error(id, err.to_string()),
+    };
+    match rx.recv_timeout(std::time::Duration::from_secs(5)) {
+        Ok(cycle) => ControlResponse::ok(
+            id,
+            json!({ "status": "paused", "cycles": cycles, "cycle": cycle }),
+        ),
+        Err(RecvTimeoutError::Timeout) => {
+            ControlResponse::error_code(id, ControlErrorCode::Timeout, "cycle step timeout".into())
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            let reason = state
+                .resource
+                .last_error()
+                .map_or_else(|| "resource stopped".to_string(), |err| err.to_string());
+            ControlResponse::error(id, reason)
+        }
+    }
+}
+
+fn handle_sim_model_set(
+    id: u64,
+    params: Option<serde_json::Value>,
+    state: &ControlState,
+) -> ControlResponse {
+    let Some(serde_json::Value::Object(mut params)) = params else {
+        return ControlResponse::invalid_params(id, "missing params".into());
+    };
+    let name = match params.remove("name") {
+        Some(serde_json::Value::String(name)) if !name.trim().is_empty() => name,
+        _ => return ControlResponse::invalid_params(id, "missing model name".into()),
+    };
+    let update: crate::simulation::SimulationModelUpdate =
+        match serde_json::from_value(serde_json::Value::Object(params)) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
+        };
+    let rx = match state
+        .resource
+        .update_simulation_model(SmolStr::new(name.trim()), update)
+    {
+        Ok(rx) => rx,
+        Err(err) => return ControlResponse::error(id, err.to_string()),
+    };
+    match rx.recv_timeout(std::time::Duration::from_secs(5)) {
+        Ok(Ok(status)) => ControlResponse::ok(id, simulation_model_json(&status)),
+        Ok(Err(err)) => ControlResponse::error(id, err.to_string()),
+        Err(RecvTimeoutError::Timeout) => ControlResponse::error_code(
+            id,
+            ControlErrorCode::Timeout,
+            "simulation model update timeout".into(),
+        ),
+        Err(RecvTimeoutError::Disconnected) => ControlResponse::error_code(
+            id,
+            ControlErrorCode::InvalidState,
+            "resource stopped".into(),
+        ),
+    }
+}
+
+fn simulation_model_json(status: &crate::simulation::SimulationModelStatus) -> serde_json::Value {
+    let model = &status.model;
+    let params = &model.params;
+    json!({
+        "name": model.name.as_str(),
+        "kind": model.kind.as_str(),
+        "source": model.source.as_ref().map(format_address),
+        "target": format_address(&model.target),
+        "gain": params.gain,
+        "offset": params.offset,
+        "time_constant_ms": params.time_constant.as_millis(),
+        "delay_ms": params.delay.as_millis(),
+        "noise": params.noise,
+        "min": params.min,
+        "max": params.max,
+        "value": status.value,
+    })
+}
+
 fn handle_bytecode_reload(
     id: u64,
     params: Option<serde_json::Value>,
@@ -3097,13 +4420,15 @@ fn handle_bytecode_reload(
     let params: BytecodeReloadParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let bytes = match BASE64_STANDARD.decode(params.bytes.as_bytes()) {
         Ok(bytes) => bytes,
-        Err(err) => return ControlResponse::error(id, format!("invalid bytecode: {err}")),
+        Err(err) => return ControlResponse::invalid_params(id, format!("invalid bytecode: {err}")),
     };
     let (tx, rx) = std::sync::mpsc::channel();
     if let Err(err) = state
@@ -3123,13 +4448,15 @@ fn handle_bytecode_reload(
             ControlResponse::ok(id, json!({ "status": "reloaded" }))
         }
         Ok(Err(err)) => ControlResponse::error(id, err.to_string()),
-        Err(_) => ControlResponse::error(id, "reload timeout".into()),
+        Err(_) => {
+            ControlResponse::error_code(id, ControlErrorCode::Timeout, "reload timeout".into())
+        }
     }
 }
 
 fn handle_pair_start(id: u64, state: &ControlState) -> ControlResponse {
     let Some(store) = state.pairing.as_ref() else {
-        return ControlResponse::error(id, "pairing unavailable".into());
+        return ControlResponse::unavailable(id, "pairing unavailable".into());
     };
     let code = store.start_pairing();
     ControlResponse::ok(
@@ -3146,29 +4473,35 @@ fn handle_pair_claim(
     let params: PairClaimParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let Some(store) = state.pairing.as_ref() else {
-        return ControlResponse::error(id, "pairing unavailable".into());
+        return ControlResponse::unavailable(id, "pairing unavailable".into());
     };
     let requested_role = match params.role.as_deref() {
         Some(text) => match AccessRole::parse(text) {
             Some(role) => Some(role),
-            None => return ControlResponse::error(id, "invalid role".into()),
+            None => return ControlResponse::invalid_params(id, "invalid role".into()),
         },
         None => None,
     };
     match store.claim(&params.code, requested_role) {
         Some(token) => ControlResponse::ok(id, json!({ "token": token })),
-        None => ControlResponse::error(id, "invalid or expired code".into()),
+        None => ControlResponse::error_code(
+            id,
+            ControlErrorCode::Unauthorized,
+            "invalid or expired code".into(),
+        ),
     }
 }
 
 fn handle_pair_list(id: u64, state: &ControlState) -> ControlResponse {
     let Some(store) = state.pairing.as_ref() else {
-        return ControlResponse::error(id, "pairing unavailable".into());
+        return ControlResponse::unavailable(id, "pairing unavailable".into());
     };
     let tokens = store.list();
     ControlResponse::ok(id, json!({ "tokens": tokens }))
@@ -3182,12 +4515,14 @@ fn handle_pair_revoke(
     let params: PairRevokeParams = match params {
         Some(value) => match serde_json::from_value(value) {
             Ok(parsed) => parsed,
-            Err(err) => return ControlResponse::error(id, format!("invalid params: {err}")),
+            Err(err) => {
+                return ControlResponse::invalid_params(id, format!("invalid params: {err}"))
+            }
         },
-        None => return ControlResponse::error(id, "missing params".into()),
+        None => return ControlResponse::invalid_params(id, "missing params".into()),
     };
     let Some(store) = state.pairing.as_ref() else {
-        return ControlResponse::error(id, "pairing unavailable".into());
+        return ControlResponse::unavailable(id, "pairing unavailable".into());
     };
     if params.id == "all" {
         let count = store.revoke_all();
@@ -3196,7 +4531,7 @@ fn handle_pair_revoke(
     if store.revoke(&params.id) {
         ControlResponse::ok(id, json!({ "status": "revoked", "id": params.id }))
     } else {
-        ControlResponse::error(id, "unknown token id".into())
+        ControlResponse::not_found(id, "unknown token id".into())
     }
 }
 
@@ -3317,6 +4652,17 @@ fn parse_hmi_write_from_text(text: &str, template: &Value) -> Option<Value> {
         Value::WChar(_) => Some(Value::WChar(
             u16::try_from(single_char(trimmed)? as u32).ok()?,
         )),
+        Value::Time(_)
+        | Value::LTime(_)
+        | Value::Date(_)
+        | Value::LDate(_)
+        | Value::Tod(_)
+        | Value::LTod(_)
+        | Value::Dt(_)
+        | Value::Ldt(_) => {
+            let value = parse_time_literal(trimmed, DateTimeProfile::default()).ok()?;
+            (std::mem::discriminant(&value) == std::mem::discriminant(template)).then_some(value)
+        }
         _ => None,
     }
 }
@@ -3344,14 +4690,17 @@ struct ControlRequest {
     auth: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug)]
 pub(crate) struct ControlResponse {
     id: u64,
     ok: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
     result: Option<serde_json::Value>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Human-readable error message.
     error: Option<String>,
+    error_code: Option<ControlErrorCode>,
+    error_details: Option<serde_json::Value>,
+    /// Serialize `error` as a plain string (`runtime.control.legacy_errors`).
+    legacy_errors: bool,
 }
 
 impl ControlResponse {
@@ -3361,16 +4710,92 @@ impl ControlResponse {
             ok: true,
             result: Some(result),
             error: None,
+            error_code: None,
+            error_details: None,
+            legacy_errors: false,
         }
     }
 
+    /// Failure without a more specific code.
     fn error(id: u64, error: String) -> Self {
+        Self::error_code(id, ControlErrorCode::Failed, error)
+    }
+
+    pub(crate) fn error_code(id: u64, code: ControlErrorCode, error: String) -> Self {
         Self {
             id,
             ok: false,
             result: None,
             error: Some(error),
+            error_code: Some(code),
+            error_details: None,
+            legacy_errors: false,
+        }
+    }
+
+    fn invalid_params(id: u64, error: String) -> Self {
+        Self::error_code(id, ControlErrorCode::InvalidParams, error)
+    }
+
+    fn not_found(id: u64, error: String) -> Self {
+        Self::error_code(id, ControlErrorCode::NotFound, error)
+    }
+
+    fn unavailable(id: u64, error: String) -> Self {
+        Self::error_code(id, ControlErrorCode::Unavailable, error)
+    }
+
+    fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.error_details = Some(details);
+        self
+    }
+
+    pub(crate) fn is_ok(&self) -> bool {
+        self.ok
+    }
+
+    pub(crate) fn error_message(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub(crate) fn code(&self) -> Option<ControlErrorCode> {
+        self.error_code
+    }
+
+    fn with_legacy_errors(mut self, legacy: bool) -> Self {
+        self.legacy_errors = legacy;
+        self
+    }
+}
+
+impl Serialize for ControlResponse {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("id", &self.id)?;
+        map.serialize_entry("ok", &self.ok)?;
+        if let Some(result) = &self.result {
+            map.serialize_entry("result", result)?;
+        }
+        if let Some(message) = &self.error {
+            if self.legacy_errors {
+                map.serialize_entry("error", message)?;
+            } else {
+                let code = self.error_code.unwrap_or(ControlErrorCode::Failed);
+                let mut error = json!({
+                    "code": code.code(),
+                    "name": code.name(),
+                    "category": code.category(),
+                    "message": message,
+                });
+                if let Some(details) = &self.error_details {
+                    error["details"] = details.clone();
+                }
+                map.serialize_entry("error", &error)?;
+            }
         }
+        map.end()
     }
 }
 
@@ -3380,6 +4805,12 @@ struct BreakpointsParams {
     lines: Vec<u32>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+struct BreakpointsExceptionsParams {
+    #[serde(default)]
+    filters: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct BreakpointsClearIdParams {
     file_id: u32,
@@ -3401,6 +4832,30 @@ struct DebugEvaluateParams {
     frame_id: Option<u32>,
 }
 
+#[derive(Debug, Deserialize)]
+struct DebugSetVariableParams {
+    variables_reference: u32,
+    name: String,
+    value: String,
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct DebugSetExpressionParams {
+    expression: String,
+    value: String,
+    frame_id: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DebugCompleteParams {
+    text: String,
+    frame_id: Option<u32>,
+    /// Zero-based character offset of the cursor (defaults to the end of `text`).
+    column: Option<usize>,
+}
+
 #[derive(Debug, Deserialize)]
 struct DebugBreakpointLocationsParams {
     source: String,
@@ -3418,6 +4873,7 @@ struct HmiValuesParams {
 #[derive(Debug, Default, Deserialize)]
 struct HmiTrendsParams {
     ids: Option<Vec<String>>,
+    until_ms: Option<u128>,
     duration_ms: Option<u64>,
     buckets: Option<usize>,
 }
@@ -3454,9 +4910,26 @@ struct HmiScaffoldResetParams {
 struct HistorianQueryParams {
     variable: Option<String>,
     since_ms: Option<u128>,
+    until_ms: Option<u128>,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct EventsQueryParams {
+    since_ms: Option<u64>,
+    until_ms: Option<u64>,
+    code: Option<EventCodes>,
+    min_severity: Option<String>,
     limit: Option<usize>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum EventCodes {
+    One(String),
+    Many(Vec<String>),
+}
+
 #[derive(Debug, Default, Deserialize)]
 struct HistorianAlertsParams {
     limit: Option<usize>,
@@ -3478,11 +4951,30 @@ struct RestartParams {
     mode: String,
 }
 
+#[derive(Debug, Default, Deserialize)]
+struct PauseParams {
+    policy: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CycleStepParams {
+    cycles: Option<u32>,
+}
+
 #[derive(Debug, Deserialize)]
 struct BytecodeReloadParams {
     bytes: String,
 }
 
+#[derive(Debug, Default, Deserialize)]
+struct DebugHistoryParams {
+    #[serde(default)]
+    variables: Vec<String>,
+    limit: Option<usize>,
+    #[serde(default)]
+    clear: bool,
+}
+
 #[derive(Debug, Deserialize)]
 struct EvalParams {
     expr: String,
@@ -3575,52 +5067,6 @@ fn format_address(address: &IoAddress) -> String {
     }
 }
 
-fn runtime_event_to_json(event: crate::debug::RuntimeEvent) -> serde_json::Value {
-    match event {
-        crate::debug::RuntimeEvent::CycleStart { cycle, time } => json!({
-            "type": "cycle_start",
-            "cycle": cycle,
-            "time_ns": time.as_nanos(),
-        }),
-        crate::debug::RuntimeEvent::CycleEnd { cycle, time } => json!({
-            "type": "cycle_end",
-            "cycle": cycle,
-            "time_ns": time.as_nanos(),
-        }),
-        crate::debug::RuntimeEvent::TaskStart {
-            name,
-            priority,
-            time,
-        } => json!({
-            "type": "task_start",
-            "name": name.as_str(),
-            "priority": priority,
-            "time_ns": time.as_nanos(),
-        }),
-        crate::debug::RuntimeEvent::TaskEnd {
-            name,
-            priority,
-            time,
-        } => json!({
-            "type": "task_end",
-            "name": name.as_str(),
-            "priority": priority,
-            "time_ns": time.as_nanos(),
-        }),
-        crate::debug::RuntimeEvent::TaskOverrun { name, missed, time } => json!({
-            "type": "task_overrun",
-            "name": name.as_str(),
-            "missed": missed,
-            "time_ns": time.as_nanos(),
-        }),
-        crate::debug::RuntimeEvent::Fault { error, time } => json!({
-            "type": "fault",
-            "error": error,
-            "time_ns": time.as_nanos(),
-        }),
-    }
-}
-
 fn io_health_to_json(entry: &IoDriverStatus) -> serde_json::Value {
     match &entry.health {
         IoDriverHealth::Ok => json!({
@@ -3747,15 +5193,25 @@ mod tests {
                         let _ = respond_to
                             .send(Err(RuntimeError::ControlError(SmolStr::new("unsupported"))));
                     }
+                    ResourceCommand::UpdateSimulationModel { respond_to, .. } => {
+                        let _ = respond_to
+                            .send(Err(RuntimeError::ControlError(SmolStr::new("unsupported"))));
+                    }
                     ResourceCommand::MeshSnapshot { respond_to, .. } => {
                         let _ = respond_to.send(IndexMap::new());
                     }
                     ResourceCommand::Snapshot { respond_to } => {
                         let _ = respond_to.send(snapshot.clone());
                     }
+                    ResourceCommand::RedundancySnapshot { respond_to, .. } => {
+                        let _ = respond_to.send(crate::RetainSnapshot::default());
+                    }
                     ResourceCommand::MeshApply { .. }
+                    | ResourceCommand::RedundancyApply { .. }
+                    | ResourceCommand::SetStandby(_)
                     | ResourceCommand::Pause
                     | ResourceCommand::Resume
+                    | ResourceCommand::StepCycles { .. }
                     | ResourceCommand::UpdateWatchdog(_)
                     | ResourceCommand::UpdateFaultPolicy(_)
                     | ResourceCommand::UpdateRetainSaveInterval(_)
@@ -3790,10 +5246,14 @@ mod tests {
             io_health: Arc::new(Mutex::new(Vec::new())),
             debug_enabled: Arc::new(AtomicBool::new(true)),
             debug_variables: Arc::new(Mutex::new(DebugVariableHandles::new())),
+            sessions: Arc::new(ControlSessions::default()),
             hmi_live: Arc::new(Mutex::new(crate::hmi::HmiLiveState::default())),
             hmi_descriptor,
             historian: None,
+            event_store: None,
             pairing: None,
+            mesh: None,
+            redundancy: None,
         }
     }
 
@@ -4236,6 +5696,67 @@ allow = ["Main.run"]
         fs::remove_dir_all(root).ok();
     }
 
+    #[test]
+    fn hmi_write_applies_subrange_policy() {
+        let source = r#"
+TYPE Percent : INT (0..100); END_TYPE
+
+PROGRAM Main
+VAR
+    level : Percent;
+END_VAR
+END_PROGRAM
+"#;
+        let root = temp_dir("hmi-write-subrange");
+        write_file(
+            &root.join("hmi.toml"),
+            r#"
+[write]
+enabled = true
+allow = ["Main.level"]
+"#,
+        );
+
+        let mut state = hmi_test_state(source);
+        set_hmi_project_root(&mut state, &root);
+        let write = |state: &ControlState, value: i64| {
+            handle_request_value(
+                json!({
+                    "id": 6,
+                    "type": "hmi.write",
+                    "params": { "path": "Main.level", "value": value }
+                }),
+                state,
+                None,
+            )
+        };
+
+        let mut harness = TestHarness::from_source(source).expect("build harness");
+        harness
+            .runtime_mut()
+            .set_subrange_policy(crate::task::SubrangePolicy::Clamp);
+        *state.metadata.lock().expect("metadata lock") = harness.runtime().metadata_snapshot();
+        let response = write(&state, 150);
+        assert!(response.ok, "hmi.write failed: {:?}", response.error);
+        let writes = state.debug.drain_var_writes();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].value, Value::Int(100));
+
+        harness
+            .runtime_mut()
+            .set_subrange_policy(crate::task::SubrangePolicy::Fault);
+        *state.metadata.lock().expect("metadata lock") = harness.runtime().metadata_snapshot();
+        let response = write(&state, 150);
+        assert!(!response.ok);
+        assert!(response
+            .error
+            .as_deref()
+            .is_some_and(|error| error.contains("outside subrange [0..100]")));
+        assert!(state.debug.drain_var_writes().is_empty());
+
+        fs::remove_dir_all(root).ok();
+    }
+
     #[test]
     fn hmi_write_rejects_non_allowlisted_target() {
         let source = r#"
@@ -4838,77 +6359,454 @@ VAR_OUTPUT
 END_VAR
 END_PROGRAM
 "#;
-        let root = temp_dir("hmi-scaffold-reset-endpoint");
-        write_file(
-            &root.join("hmi/overview.toml"),
-            r#"
-title = "Overview"
+        let root = temp_dir("hmi-scaffold-reset-endpoint");
+        write_file(
+            &root.join("hmi/overview.toml"),
+            r#"
+title = "Overview"
+
+[[section]]
+title = "Custom"
+span = 12
+"#,
+        );
+        let mut state = hmi_test_state(source);
+        set_hmi_project_root(&mut state, &root);
+
+        let response = handle_request_value(
+            json!({
+                "id": 702,
+                "type": "hmi.scaffold.reset",
+                "params": { "mode": "reset", "style": "industrial" }
+            }),
+            &state,
+            None,
+        );
+        assert!(
+            response.ok,
+            "hmi.scaffold.reset failed: {:?}",
+            response.error
+        );
+        let revision = response
+            .result
+            .as_ref()
+            .and_then(|value| value.get("schema_revision"))
+            .and_then(serde_json::Value::as_u64)
+            .expect("schema revision");
+        assert!(revision >= 1, "schema revision should increment");
+        assert!(root.join("hmi/overview.toml").is_file());
+        assert!(root.join("hmi/process.toml").is_file());
+        assert!(root.join("hmi/control.toml").is_file());
+        assert!(root.join("hmi/trends.toml").is_file());
+        assert!(root.join("hmi/alarms.toml").is_file());
+        let config = fs::read_to_string(root.join("hmi/_config.toml")).expect("read config");
+        assert!(config.contains("version = 1"));
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn request_routing_contract_dispatches_core_handler_modules() {
+        let source = r#"
+PROGRAM Main
+VAR
+    run : BOOL := TRUE;
+END_VAR
+END_PROGRAM
+"#;
+        let state = hmi_test_state(source);
+        let requests = vec![
+            json!({"id": 1, "type": "status"}),
+            json!({"id": 2, "type": "io.list"}),
+            json!({"id": 3, "type": "debug.state"}),
+            json!({"id": 4, "type": "var.forced"}),
+            json!({"id": 5, "type": "restart", "params": { "mode": "warm" }}),
+            json!({"id": 6, "type": "cycle.step", "params": { "cycles": 2 }}),
+        ];
+
+        for request in requests {
+            let response = handle_request_value(request.clone(), &state, None);
+            assert_ne!(
+                response.error.as_deref(),
+                Some("unsupported request"),
+                "request should be routed by module split: {request}"
+            );
+        }
+    }
+
+    #[test]
+    fn pause_policy_chooses_debugger_or_cycle_boundary() {
+        let source = r#"
+PROGRAM Main
+VAR
+    run : BOOL := TRUE;
+END_VAR
+END_PROGRAM
+"#;
+        let state = hmi_test_state(source);
+        let policy_of = |response: &ControlResponse| {
+            response
+                .result
+                .as_ref()
+                .and_then(|result| result.get("policy"))
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string)
+        };
+
+        // Debug mode stops at the next statement by default.
+        let pause = handle_request_value(json!({"id": 1, "type": "pause"}), &state, None);
+        assert_eq!(policy_of(&pause).as_deref(), Some("immediate"));
+        assert!(state.debug.is_paused());
+        let resume = handle_request_value(json!({"id": 2, "type": "resume"}), &state, None);
+        assert!(resume.ok, "resume should succeed: {:?}", resume.error);
+        assert!(!state.debug.is_paused());
+
+        // Production mode finishes the cycle instead of stopping mid-scan.
+        *state.control_mode.lock().unwrap() = ControlMode::Production;
+        let pause = handle_request_value(json!({"id": 3, "type": "pause"}), &state, None);
+        assert_eq!(policy_of(&pause).as_deref(), Some("cycle_end"));
+        assert!(!state.debug.is_paused());
+
+        // The request and runtime.control.pause override the mode default.
+        let pause = handle_request_value(
+            json!({"id": 4, "type": "pause", "params": { "policy": "immediate" }}),
+            &state,
+            None,
+        );
+        assert_eq!(policy_of(&pause).as_deref(), Some("immediate"));
+        let _ = handle_request_value(json!({"id": 5, "type": "resume"}), &state, None);
+        let set = handle_request_value(
+            json!({"id": 6, "type": "config.set", "params": { "control.pause": "immediate" }}),
+            &state,
+            None,
+        );
+        assert!(set.ok, "config.set should succeed: {:?}", set.error);
+        let pause = handle_request_value(json!({"id": 7, "type": "pause"}), &state, None);
+        assert_eq!(policy_of(&pause).as_deref(), Some("immediate"));
+
+        let invalid = handle_request_value(
+            json!({"id": 8, "type": "pause", "params": { "policy": "later" }}),
+            &state,
+            None,
+        );
+        assert!(!invalid.ok);
+    }
+
+    #[test]
+    fn debug_history_watches_and_returns_recorded_cycles() {
+        let source = r#"
+PROGRAM Main
+VAR
+    level : INT;
+END_VAR
+END_PROGRAM
+"#;
+        let state = hmi_test_state(source);
+        let first = handle_request_value(
+            json!({"id": 1, "type": "debug.history", "params": { "variables": ["Main.level"] }}),
+            &state,
+            None,
+        );
+        assert!(first.ok, "debug.history should succeed: {:?}", first.error);
+        assert_eq!(
+            state.debug.history_names(),
+            vec![SmolStr::new("Main.level")]
+        );
+
+        for cycle in 1..=4u64 {
+            state.debug.record_history(
+                cycle,
+                crate::value::Duration::from_millis(cycle as i64 * 10),
+                vec![(SmolStr::new("Main.level"), Value::Int(cycle as i16 * 5))],
+            );
+        }
+        let history = handle_request_value(
+            json!({"id": 2, "type": "debug.history", "params": { "variables": ["main.LEVEL"], "limit": 2 }}),
+            &state,
+            None,
+        );
+        let result = history.result.expect("history result");
+        assert_eq!(result["depth"], json!(crate::debug::DEFAULT_HISTORY_DEPTH));
+        let samples = result["variables"][0]["samples"]
+            .as_array()
+            .expect("samples");
+        assert_eq!(result["variables"][0]["name"], json!("Main.level"));
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0]["cycle"], json!(3));
+        assert_eq!(samples[1]["number"], json!(20.0));
+        assert_eq!(samples[1]["time_ns"], json!(40_000_000));
+
+        let set = handle_request_value(
+            json!({"id": 3, "type": "config.set", "params": { "control.history_depth": 1 }}),
+            &state,
+            None,
+        );
+        assert!(set.ok, "config.set should succeed: {:?}", set.error);
+        let history = handle_request_value(json!({"id": 4, "type": "debug.history"}), &state, None);
+        let result = history.result.expect("history result");
+        assert_eq!(result["depth"], json!(1));
+        assert_eq!(
+            result["variables"][0]["samples"].as_array().unwrap().len(),
+            1
+        );
+
+        let clear = handle_request_value(
+            json!({"id": 5, "type": "debug.history", "params": { "clear": true }}),
+            &state,
+            None,
+        );
+        assert!(clear.ok);
+        assert!(state.debug.history_names().is_empty());
+    }
+
+    #[test]
+    fn breakpoints_exceptions_sets_fault_filters() {
+        let source = r#"
+PROGRAM Main
+VAR
+    run : BOOL := TRUE;
+END_VAR
+END_PROGRAM
+"#;
+        let state = hmi_test_state(source);
+        let response = handle_request_value(
+            json!({"id": 1, "type": "breakpoints.exceptions", "params": { "filters": ["unhandled", "watchdog"] }}),
+            &state,
+            None,
+        );
+        assert!(
+            response.ok,
+            "breakpoints.exceptions failed: {:?}",
+            response.error
+        );
+        assert_eq!(
+            state.debug.fault_breakpoints(),
+            crate::debug::FaultBreakpoints {
+                all_faults: false,
+                unhandled_faults: true,
+                watchdog: true,
+            }
+        );
+        assert!(!state.debug.is_idle());
+
+        let invalid = handle_request_value(
+            json!({"id": 2, "type": "breakpoints.exceptions", "params": { "filters": ["overflow"] }}),
+            &state,
+            None,
+        );
+        assert!(!invalid.ok);
+        let cleared = handle_request_value(
+            json!({"id": 3, "type": "breakpoints.exceptions", "params": { "filters": [] }}),
+            &state,
+            None,
+        );
+        assert!(cleared.ok);
+        assert!(state.debug.fault_breakpoints().is_empty());
+    }
+
+    #[test]
+    fn debug_set_variable_and_expression_coerce_and_queue_writes() {
+        let source = r#"
+CONFIGURATION Conf
+VAR_GLOBAL
+    limit : INT := 10;
+END_VAR
+PROGRAM P1 : Main;
+END_CONFIGURATION
+
+PROGRAM Main
+VAR
+    count : INT := 0;
+END_VAR
+END_PROGRAM
+"#;
+        let state = hmi_test_state(source);
+        let mut harness = TestHarness::from_source(source).expect("build harness");
+        harness.cycle();
+        harness
+            .runtime_mut()
+            .with_eval_context(None, None, |ctx| {
+                state.debug.refresh_snapshot(ctx);
+                Ok(())
+            })
+            .expect("refresh snapshot");
+        let snapshot = state.debug.snapshot().expect("snapshot");
+        let instance_id = snapshot
+            .storage
+            .instances()
+            .iter()
+            .find(|(_, data)| data.type_name.eq_ignore_ascii_case("P1"))
+            .map(|(id, _)| *id)
+            .expect("program instance");
+        let reference = state
+            .debug_variables
+            .lock()
+            .unwrap()
+            .alloc(VariableHandle::Instance(instance_id));
+
+        let written = handle_request_value(
+            json!({"id": 1, "type": "debug.set_variable", "params": {
+                "variables_reference": reference, "name": "count", "value": "40 + 2"
+            }}),
+            &state,
+            None,
+        );
+        assert!(written.ok, "debug.set_variable failed: {:?}", written.error);
+        let result = written.result.expect("result");
+        assert_eq!(result["value"], "Int(42)");
+        assert_eq!(result["type"], "INT");
+        let writes = state.debug.drain_var_writes();
+        assert_eq!(writes.len(), 1);
+        assert!(matches!(
+            &writes[0].target,
+            PendingVarTarget::Instance(id, name) if *id == instance_id && name == "count"
+        ));
+        assert_eq!(writes[0].value, Value::Int(42));
+        let snapshot = state.debug.snapshot().expect("snapshot");
+        assert_eq!(
+            snapshot.storage.get_instance_var(instance_id, "count"),
+            Some(&Value::Int(42))
+        );
 
-[[section]]
-title = "Custom"
-span = 12
-"#,
+        let mismatched = handle_request_value(
+            json!({"id": 2, "type": "debug.set_variable", "params": {
+                "variables_reference": reference, "name": "count", "value": "'text'"
+            }}),
+            &state,
+            None,
         );
-        let mut state = hmi_test_state(source);
-        set_hmi_project_root(&mut state, &root);
+        assert!(!mismatched.ok);
+        assert!(state.debug.drain_var_writes().is_empty());
 
-        let response = handle_request_value(
-            json!({
-                "id": 702,
-                "type": "hmi.scaffold.reset",
-                "params": { "mode": "reset", "style": "industrial" }
-            }),
+        let expression = handle_request_value(
+            json!({"id": 3, "type": "debug.set_expression", "params": {
+                "expression": "limit", "value": "25"
+            }}),
             &state,
             None,
         );
         assert!(
-            response.ok,
-            "hmi.scaffold.reset failed: {:?}",
-            response.error
+            expression.ok,
+            "debug.set_expression failed: {:?}",
+            expression.error
         );
-        let revision = response
-            .result
-            .as_ref()
-            .and_then(|value| value.get("schema_revision"))
-            .and_then(serde_json::Value::as_u64)
-            .expect("schema revision");
-        assert!(revision >= 1, "schema revision should increment");
-        assert!(root.join("hmi/overview.toml").is_file());
-        assert!(root.join("hmi/process.toml").is_file());
-        assert!(root.join("hmi/control.toml").is_file());
-        assert!(root.join("hmi/trends.toml").is_file());
-        assert!(root.join("hmi/alarms.toml").is_file());
-        let config = fs::read_to_string(root.join("hmi/_config.toml")).expect("read config");
-        assert!(config.contains("version = 1"));
-        fs::remove_dir_all(root).ok();
+        assert_eq!(expression.result.expect("result")["value"], "Int(25)");
+        assert_eq!(state.debug.drain_lvalue_writes().len(), 1);
+        let snapshot = state.debug.snapshot().expect("snapshot");
+        assert_eq!(snapshot.storage.get_global("limit"), Some(&Value::Int(25)));
+
+        *state.control_mode.lock().unwrap() = ControlMode::Production;
+        let read_only = handle_request_value(
+            json!({"id": 4, "type": "debug.set_variable", "params": {
+                "variables_reference": reference, "name": "count", "value": "1"
+            }}),
+            &state,
+            None,
+        );
+        assert!(!read_only.ok);
+        assert!(read_only
+            .error
+            .unwrap_or_default()
+            .contains("read-only in production"));
+        assert!(state.debug.drain_var_writes().is_empty());
     }
 
     #[test]
-    fn request_routing_contract_dispatches_core_handler_modules() {
+    fn control_sessions_keep_variable_handles_and_history_apart() {
         let source = r#"
+CONFIGURATION Conf
+VAR_GLOBAL
+    limit : INT := 10;
+END_VAR
+PROGRAM P1 : Main;
+END_CONFIGURATION
+
 PROGRAM Main
 VAR
-    run : BOOL := TRUE;
+    count : INT := 0;
 END_VAR
+count := count + 1;
 END_PROGRAM
 "#;
         let state = hmi_test_state(source);
-        let requests = vec![
-            json!({"id": 1, "type": "status"}),
-            json!({"id": 2, "type": "io.list"}),
-            json!({"id": 3, "type": "debug.state"}),
-            json!({"id": 4, "type": "var.forced"}),
-            json!({"id": 5, "type": "restart", "params": { "mode": "warm" }}),
-        ];
+        let mut harness = TestHarness::from_source(source).expect("build harness");
+        harness.cycle();
+        harness
+            .runtime_mut()
+            .with_eval_context(None, None, |ctx| {
+                state.debug.refresh_snapshot(ctx);
+                Ok(())
+            })
+            .expect("refresh snapshot");
+        let lsp = state.sessions.open("tcp", Some("127.0.0.1:5001"), 0);
+        let tui = state.sessions.open("unix", None, 0);
 
-        for request in requests {
-            let response = handle_request_value(request.clone(), &state, None);
-            assert_ne!(
-                response.error.as_deref(),
-                Some("unsupported request"),
-                "request should be routed by module split: {request}"
+        let scopes = handle_session_request_value(
+            json!({"id": 1, "type": "debug.scopes", "params": { "frame_id": 0 }}),
+            &state,
+            None,
+            Some(&lsp),
+        );
+        assert!(scopes.ok, "debug.scopes failed: {:?}", scopes.error);
+        let globals = scopes.result.expect("result")["scopes"]
+            .as_array()
+            .expect("scopes")
+            .iter()
+            .find(|scope| scope["name"] == "Globals")
+            .map(|scope| scope["variablesReference"].as_u64().expect("reference"))
+            .expect("globals scope");
+
+        let variables = |session: &ControlSession| {
+            handle_session_request_value(
+                json!({"id": 2, "type": "debug.variables", "params": {
+                    "variables_reference": globals
+                }}),
+                &state,
+                None,
+                Some(session),
+            )
+            .result
+            .expect("result")["variables"]
+                .as_array()
+                .expect("variables")
+                .len()
+        };
+        assert!(variables(&lsp) > 0);
+        assert_eq!(variables(&tui), 0, "handles must not leak across sessions");
+
+        for session in [&lsp, &tui] {
+            let watched = handle_session_request_value(
+                json!({"id": 3, "type": "debug.history", "params": { "variables": ["limit"] }}),
+                &state,
+                None,
+                Some(session),
             );
+            assert!(watched.ok);
         }
+        let cleared = handle_session_request_value(
+            json!({"id": 4, "type": "debug.history", "params": { "clear": true }}),
+            &state,
+            None,
+            Some(&tui),
+        );
+        assert!(cleared.ok);
+        assert!(tui.history_names().is_empty());
+        assert_eq!(state.debug.history_names(), vec![SmolStr::new("limit")]);
+
+        let info = handle_session_request_value(
+            json!({"id": 5, "type": "session.info"}),
+            &state,
+            None,
+            Some(&lsp),
+        );
+        let info = info.result.expect("result");
+        assert_eq!(info["session"]["id"], lsp.id());
+        assert_eq!(info["session"]["client"], "127.0.0.1:5001");
+        assert_eq!(info["session"]["history"], json!(["limit"]));
+        assert_eq!(info["sessions"].as_array().expect("sessions").len(), 2);
+
+        state.sessions.close(lsp.id(), &state.debug);
+        assert!(state.debug.history_names().is_empty());
+        assert_eq!(state.sessions.list().len(), 1);
     }
 
     #[test]
@@ -4944,6 +6842,9 @@ END_PROGRAM
             Some(RestartMode::Warm)
         );
 
+        let step = handle_request_value(json!({"id": 9, "type": "cycle.step"}), &state, None);
+        assert_eq!(step.error.as_deref(), Some("resource is not paused"));
+
         let io_write = handle_request_value(
             json!({
                 "id": 4,
@@ -5043,20 +6944,25 @@ END_PROGRAM
 "#;
         let state = hmi_test_state(source);
 
-        let invalid_line = handle_request_line("{invalid-json", &state, None)
+        let invalid_line = handle_request_line("{invalid-json", &state, None, None)
             .expect("invalid request should still return response line");
         let invalid_json: serde_json::Value =
             serde_json::from_str(&invalid_line).expect("parse invalid response");
-        let invalid_error = invalid_json
-            .get("error")
-            .and_then(serde_json::Value::as_str)
-            .unwrap_or_default();
-        assert!(invalid_error.starts_with("invalid request:"));
+        let invalid_error = &invalid_json["error"];
+        assert_eq!(invalid_error["code"], json!(100));
+        assert_eq!(invalid_error["name"], json!("INVALID_REQUEST"));
+        assert!(control_error_message(invalid_error)
+            .unwrap_or_default()
+            .starts_with("invalid request:"));
 
         let unsupported =
             handle_request_value(json!({"id": 10, "type": "does.not.exist"}), &state, None);
         assert!(!unsupported.ok);
         assert_eq!(unsupported.error.as_deref(), Some("unsupported request"));
+        assert_eq!(
+            unsupported.code(),
+            Some(ControlErrorCode::UnsupportedRequest)
+        );
 
         let malformed_io = handle_request_value(
             json!({"id": 11, "type": "io.write", "params": { "address": "%QX0.0" }}),
@@ -5080,6 +6986,52 @@ END_PROGRAM
             invalid_restart.error.as_deref(),
             Some("invalid restart mode")
         );
+        assert_eq!(
+            invalid_restart.code(),
+            Some(ControlErrorCode::InvalidParams)
+        );
+    }
+
+    #[test]
+    fn control_errors_carry_codes_unless_legacy_errors_is_set() {
+        let source = r#"
+PROGRAM Main
+VAR
+    run : BOOL := TRUE;
+END_VAR
+END_PROGRAM
+"#;
+        let mut state = hmi_test_state(source);
+        state.auth_token = Arc::new(Mutex::new(Some(SmolStr::new("admin-token"))));
+        state.control_requires_auth = true;
+
+        let unauthorized = handle_request_value(json!({"id": 1, "type": "status"}), &state, None);
+        let body = serde_json::to_value(&unauthorized).expect("serialize response");
+        assert_eq!(
+            body["error"],
+            json!({
+                "code": 200,
+                "name": "UNAUTHORIZED",
+                "category": "auth",
+                "message": "unauthorized",
+            })
+        );
+
+        state.debug_enabled.store(false, Ordering::Relaxed);
+        let disabled = handle_request_value(
+            json!({"id": 2, "type": "pause", "auth": "admin-token"}),
+            &state,
+            None,
+        );
+        let body = serde_json::to_value(&disabled).expect("serialize response");
+        assert_eq!(body["error"]["name"], json!("DEBUG_DISABLED"));
+        assert_eq!(body["error"]["message"], json!("debug disabled"));
+
+        state.settings.lock().unwrap().legacy_errors = true;
+        let legacy = handle_request_value(json!({"id": 3, "type": "status"}), &state, None);
+        let body = serde_json::to_value(&legacy).expect("serialize response");
+        assert_eq!(body["error"], json!("unauthorized"));
+        assert_eq!(legacy.code(), Some(ControlErrorCode::Unauthorized));
     }
 
     #[test]
@@ -5319,4 +7271,268 @@ END_PROGRAM
         let _ = std::fs::remove_file(history_path);
         let _ = std::fs::remove_file(hook_path);
     }
+
+    #[test]
+    fn diagnostics_collect_bundles_events_metrics_and_runtime_info() {
+        let state = hmi_test_state("PROGRAM Main\nEND_PROGRAM\n");
+        state
+            .events
+            .lock()
+            .unwrap()
+            .push_back(crate::debug::RuntimeEvent::Fault {
+                error: "divide by zero".to_string(),
+                location: None,
+                time: crate::value::Duration::from_millis(3),
+            });
+        assert_eq!(
+            required_role_for_control_request("diagnostics.collect", None),
+            AccessRole::Viewer
+        );
+        let response = handle_request_value(
+            json!({ "id": 1, "type": "diagnostics.collect" }),
+            &state,
+            None,
+        );
+        assert!(response.ok, "diagnostics.collect: {:?}", response.error);
+        let bundle = response.result.expect("bundle");
+        assert_eq!(bundle["reason"]["kind"], json!("on_demand"));
+        assert_eq!(
+            bundle["runtime"]["resource"],
+            json!(state.resource_name.as_str())
+        );
+        assert_eq!(bundle["events"][0]["type"], json!("fault"));
+        assert_eq!(bundle["events"][0]["severity"], json!("error"));
+        assert!(bundle["metrics"]["cycle_ms"].is_object());
+        assert_eq!(bundle["breakpoints"], json!([]));
+    }
+
+    #[test]
+    fn restart_preview_lists_kept_and_reinitialized_variables() {
+        let source = r#"
+CONFIGURATION Conf
+VAR_GLOBAL RETAIN
+    Counter : DINT;
+END_VAR
+PROGRAM P1 : Main;
+END_CONFIGURATION
+
+PROGRAM Main
+VAR PERSISTENT
+    Total : DINT;
+END_VAR
+VAR
+    Phase : INT;
+END_VAR
+END_PROGRAM
+"#;
+        let state = hmi_test_state(source);
+        assert_eq!(
+            required_role_for_control_request("restart.preview", None),
+            AccessRole::Viewer
+        );
+        let names = |result: &serde_json::Value, key: &str| {
+            result[key]
+                .as_array()
+                .expect("entries")
+                .iter()
+                .map(|entry| entry["name"].as_str().expect("name").to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let response = handle_request_value(
+            json!({ "id": 1, "type": "restart.preview", "params": { "mode": "cold" } }),
+            &state,
+            None,
+        );
+        assert!(response.ok, "restart.preview: {:?}", response.error);
+        let result = response.result.expect("result");
+        assert_eq!(result["mode"], json!("cold"));
+        assert_eq!(result["rules"]["unqualified"], json!("non_retain"));
+        assert_eq!(names(&result, "kept"), ["P1.Total"]);
+        assert_eq!(names(&result, "reinitialized"), ["Counter", "P1.Phase"]);
+
+        let response = handle_request_value(
+            json!({ "id": 2, "type": "restart.preview", "params": { "mode": "warm" } }),
+            &state,
+            None,
+        );
+        let result = response.result.expect("result");
+        assert_eq!(names(&result, "kept"), ["Counter", "P1.Total"]);
+        assert_eq!(result["reinitialized"][0]["declared"], json!("unspecified"));
+
+        let response = handle_request_value(
+            json!({ "id": 3, "type": "restart.preview", "params": { "mode": "hot" } }),
+            &state,
+            None,
+        );
+        assert!(!response.ok);
+    }
+
+    #[test]
+    fn events_filter_by_severity_and_query_the_persistent_store() {
+        let mut state = hmi_test_state("PROGRAM Main\nEND_PROGRAM\n");
+        let overrun = crate::debug::RuntimeEvent::TaskOverrun {
+            name: SmolStr::new("Fast"),
+            missed: 1,
+            time: crate::value::Duration::from_millis(2),
+        };
+        let fault = crate::debug::RuntimeEvent::Fault {
+            error: "divide by zero".to_string(),
+            location: None,
+            time: crate::value::Duration::from_millis(3),
+        };
+        {
+            let mut events = state.events.lock().unwrap();
+            events.push_back(crate::debug::RuntimeEvent::CycleStart {
+                cycle: 1,
+                time: crate::value::Duration::from_millis(1),
+            });
+            events.push_back(overrun.clone());
+            events.push_back(fault.clone());
+        }
+
+        let tail = handle_request_value(
+            json!({ "id": 1, "type": "events.tail", "params": { "min_severity": "warning" } }),
+            &state,
+            None,
+        );
+        let events = tail.result.as_ref().unwrap()["events"].as_array().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["type"], json!("fault"));
+        assert_eq!(events[0]["severity"], json!("error"));
+        let bad = handle_request_value(
+            json!({ "id": 2, "type": "events.tail", "params": { "min_severity": "loud" } }),
+            &state,
+            None,
+        );
+        assert_eq!(bad.code(), Some(ControlErrorCode::InvalidParams));
+
+        let disabled =
+            handle_request_value(json!({ "id": 3, "type": "events.query" }), &state, None);
+        assert_eq!(disabled.code(), Some(ControlErrorCode::Unavailable));
+
+        let path = temp_history_path("events");
+        let store = crate::event_store::EventStore::open(
+            crate::event_store::EventStoreConfig {
+                enabled: true,
+                path: path.clone(),
+                ..crate::event_store::EventStoreConfig::default()
+            },
+            None,
+        )
+        .expect("event store");
+        store.append_at(&overrun, 1_000).expect("append overrun");
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        store
+            .append_at(&overrun, now - 1_000)
+            .expect("append overrun");
+        store.append_at(&fault, now).expect("append fault");
+        state.event_store = Some(Arc::new(store));
+
+        let query = handle_request_value(
+            json!({
+                "id": 4,
+                "type": "events.query",
+                "params": { "since_ms": now - 5_000, "code": ["task_overrun"] }
+            }),
+            &state,
+            None,
+        );
+        assert!(query.ok, "events.query should succeed: {:?}", query.error);
+        let events = query.result.as_ref().unwrap()["events"].as_array().unwrap();
+        assert_eq!(events.len(), 1, "aged and filtered events are skipped");
+        assert_eq!(events[0]["code"], json!("task_overrun"));
+        assert_eq!(events[0]["severity"], json!("warning"));
+        assert_eq!(events[0]["event"]["name"], json!("Fast"));
+
+        let query = handle_request_value(
+            json!({ "id": 5, "type": "events.query", "params": { "min_severity": "error" } }),
+            &state,
+            None,
+        );
+        let events = query.result.as_ref().unwrap()["events"].as_array().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["event"]["error"], json!("divide by zero"));
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn hmi_trends_backfill_panned_windows_from_historian() {
+        let source = r#"
+PROGRAM Main
+VAR
+    speed : REAL := 42.5;
+END_VAR
+END_PROGRAM
+"#;
+        let mut state = hmi_test_state(source);
+        let history_path = temp_history_path("trend-backfill");
+        let historian = HistorianService::new(
+            HistorianConfig {
+                enabled: true,
+                sample_interval_ms: 1,
+                mode: RecordingMode::All,
+                include: Vec::new(),
+                history_path: history_path.clone(),
+                max_entries: 500,
+                prometheus_enabled: false,
+                prometheus_path: SmolStr::new("/metrics"),
+                alerts: Vec::new(),
+            },
+            None,
+        )
+        .expect("historian");
+        let (snapshot_tx, snapshot_rx) = std::sync::mpsc::channel();
+        state
+            .resource
+            .send_command(ResourceCommand::Snapshot {
+                respond_to: snapshot_tx,
+            })
+            .expect("request runtime snapshot");
+        let snapshot = snapshot_rx
+            .recv_timeout(std::time::Duration::from_millis(250))
+            .expect("snapshot");
+        for timestamp_ms in [10_000, 20_000, 30_000] {
+            historian
+                .capture_snapshot_at(&snapshot, timestamp_ms)
+                .expect("capture");
+        }
+
+        let request = json!({
+            "id": 90,
+            "type": "hmi.trends.get",
+            "params": { "until_ms": 60_000, "duration_ms": 60_000, "buckets": 24 }
+        });
+        let without_history = handle_request_value(request.clone(), &state, None);
+        assert!(without_history.ok, "{:?}", without_history.error);
+        let result = without_history.result.expect("result");
+        assert_eq!(result["until_ms"], json!(60_000));
+        assert_eq!(result["historian"], json!(false));
+        assert_eq!(
+            result["series"],
+            json!([]),
+            "live samples are newer than the window"
+        );
+
+        state.historian = Some(historian);
+        let with_history = handle_request_value(request, &state, None);
+        assert!(with_history.ok, "{:?}", with_history.error);
+        let result = with_history.result.expect("result");
+        assert_eq!(result["historian"], json!(true));
+        let series = result["series"].as_array().expect("series");
+        assert_eq!(series.len(), 1);
+        let points = series[0]["points"].as_array().expect("points");
+        let timestamps = points
+            .iter()
+            .map(|point| point["ts_ms"].as_u64().expect("ts_ms"))
+            .collect::<Vec<_>>();
+        assert_eq!(timestamps, vec![10_000, 20_000, 30_000]);
+        assert_eq!(points[0]["value"], json!(42.5));
+
+        let _ = std::fs::remove_file(history_path);
+    }
 }
@@ -0,0 +1,251 @@
+//! Diagnostic bundles for crash reports and support tickets.
+//!
+//! A bundle is one JSON document with the most recent runtime events, a metrics snapshot,
+//! a summary of variable storage, the active breakpoints, and the hash of the loaded
+//! bytecode. `diagnostics.collect` returns it on demand; [`install_crash_handler`] writes
+//! it to `diagnostics/` when the runtime panics.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, TryLockError};
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::debug::{location_to_line_col, DebugBreakpoint, DebugSnapshot};
+use crate::event_store::{runtime_event_json, EventSeverity};
+
+use super::ControlState;
+
+/// Runtime events included in a bundle, newest first.
+const DIAGNOSTIC_EVENTS: usize = 100;
+
+/// Set while the crash handler runs, so a panic inside it does not recurse.
+static DUMPING: AtomicBool = AtomicBool::new(false);
+
+/// Collect a diagnostic bundle. `reason` says why it was taken.
+#[must_use]
+pub fn collect_diagnostics(state: &ControlState, reason: Value) -> Value {
+    collect(state, reason, false)
+}
+
+/// Write `bundle` to `dir` as `<prefix>-<ms>.json` and return its path.
+pub fn write_diagnostics(dir: &Path, prefix: &str, bundle: &Value) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{prefix}-{}.json", now_ms()));
+    let text = serde_json::to_string_pretty(bundle).map_err(std::io::Error::other)?;
+    std::fs::write(&path, text)?;
+    Ok(path)
+}
+
+/// Install a panic hook that writes a crash bundle to `dir` before the default hook runs.
+///
+/// The hook only reads state it can lock without waiting, so a panic on a thread that
+/// holds a runtime lock still produces a (partial) bundle. `on_dump` receives the path of
+/// each bundle written.
+pub fn install_crash_handler(
+    state: Arc<ControlState>,
+    dir: PathBuf,
+    on_dump: impl Fn(&Path) + Send + Sync + 'static,
+) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if !DUMPING.swap(true, Ordering::AcqRel) {
+            let reason = json!({
+                "kind": "panic",
+                "message": panic_message(info.payload()),
+                "location": info.location().map(ToString::to_string),
+                "thread": std::thread::current().name().unwrap_or("unnamed"),
+            });
+            let bundle = collect(&state, reason, true);
+            match write_diagnostics(&dir, "crash", &bundle) {
+                Ok(path) => on_dump(&path),
+                Err(err) => eprintln!("trust-runtime: failed to write crash diagnostics: {err}"),
+            }
+            DUMPING.store(false, Ordering::Release);
+        }
+        previous(info);
+    }));
+}
+
+fn collect(state: &ControlState, reason: Value, crashing: bool) -> Value {
+    let (breakpoints, snapshot) = if crashing {
+        state.debug.try_inspect().unwrap_or_default()
+    } else {
+        (state.debug.breakpoints(), state.debug.snapshot())
+    };
+    let events = read_locked(&state.events, crashing, |events| {
+        events
+            .iter()
+            .rev()
+            .take(DIAGNOSTIC_EVENTS)
+            .map(|event| {
+                let mut value = runtime_event_json(event);
+                value["severity"] = json!(EventSeverity::of(event).as_str());
+                value
+            })
+            .collect::<Vec<_>>()
+    });
+    let metrics = read_locked(&state.metrics, crashing, |metrics| metrics.snapshot());
+    let build = read_locked(&state.metadata, crashing, |metadata| {
+        metadata.build_info().cloned()
+    })
+    .flatten();
+    json!({
+        "created_ms": now_ms(),
+        "reason": reason,
+        "runtime": {
+            "version": env!("CARGO_PKG_VERSION"),
+            "resource": state.resource_name.as_str(),
+            "state": format!("{:?}", state.resource.state()).to_ascii_lowercase(),
+            "fault": state.resource.last_error().map(|err| err.to_string()),
+            "pid": std::process::id(),
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
+        },
+        "bundle": bundle_json(state.project_root.as_deref(), build.as_ref()),
+        "events": events,
+        "metrics": metrics.map(|metrics| json!({
+            "uptime_ms": metrics.uptime_ms,
+            "cycle_ms": {
+                "min": metrics.cycle.min_ms,
+                "avg": metrics.cycle.avg_ms,
+                "max": metrics.cycle.max_ms,
+                "last": metrics.cycle.last_ms,
+            },
+            "overruns": metrics.overruns,
+            "faults": metrics.faults,
+            "tasks": metrics.tasks.iter().map(|task| json!({
+                "name": task.name.as_str(),
+                "avg_ms": task.avg_ms,
+                "max_ms": task.max_ms,
+                "overruns": task.overruns,
+            })).collect::<Vec<_>>(),
+        })),
+        "variables": snapshot.as_ref().map(storage_summary),
+        "breakpoints": breakpoints
+            .iter()
+            .map(|breakpoint| breakpoint_json(state, breakpoint))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn bundle_json(root: Option<&Path>, build: Option<&crate::bytecode::BuildInfo>) -> Value {
+    let Some(root) = root else {
+        return Value::Null;
+    };
+    let program = root.join("program.stbc");
+    let bytecode = std::fs::read(&program).ok();
+    json!({
+        "root": root.display().to_string(),
+        "program_sha256": bytecode.as_ref().map(|bytes| hex(&Sha256::digest(bytes))),
+        "program_bytes": bytecode.as_ref().map(Vec::len),
+        "version": build.and_then(|build| build.version.as_deref()),
+        "git_hash": build.and_then(|build| build.git_hash.as_deref()),
+        "built_at": build.and_then(|build| build.built_at.as_deref()),
+    })
+}
+
+fn storage_summary(snapshot: &DebugSnapshot) -> Value {
+    let storage = &snapshot.storage;
+    json!({
+        "time_ns": snapshot.now.as_nanos(),
+        "globals": storage.globals().len(),
+        "retain": storage.retain().len(),
+        "instances": storage.instances().len(),
+        "frames": storage.frames().len(),
+    })
+}
+
+fn breakpoint_json(state: &ControlState, breakpoint: &DebugBreakpoint) -> Value {
+    let location = &breakpoint.location;
+    let file = state
+        .sources
+        .files()
+        .iter()
+        .find(|file| file.id == location.file_id);
+    let (line, column) = file.map_or((0, 0), |file| location_to_line_col(&file.text, location));
+    json!({
+        "path": file.map(|file| file.path.display().to_string()),
+        "file_id": location.file_id,
+        "line": line + 1,
+        "column": column + 1,
+        "hits": breakpoint.hits,
+        "conditional": breakpoint.condition.is_some() || breakpoint.hit_condition.is_some(),
+        "logpoint": breakpoint.log_message.is_some(),
+    })
+}
+
+/// Read through a mutex. While crashing, give up instead of waiting on a lock the
+/// panicking thread might hold.
+fn read_locked<T, R>(mutex: &Mutex<T>, crashing: bool, read: impl FnOnce(&T) -> R) -> Option<R> {
+    let guard = if crashing {
+        match mutex.try_lock() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        }
+    } else {
+        mutex
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    };
+    Some(read(&guard))
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crash_reads_skip_held_locks_and_accept_poisoned_ones() {
+        let mutex = Arc::new(Mutex::new(7));
+        {
+            let _held = mutex.lock().unwrap();
+            assert_eq!(read_locked(&mutex, true, |value| *value), None);
+        }
+        let poisoner = Arc::clone(&mutex);
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("poison");
+        })
+        .join();
+        assert!(mutex.is_poisoned());
+        assert_eq!(read_locked(&mutex, true, |value| *value), Some(7));
+        assert_eq!(read_locked(&mutex, false, |value| *value), Some(7));
+    }
+
+    #[test]
+    fn bundles_are_written_with_a_timestamped_name() {
+        let dir = std::env::temp_dir().join(format!("trust-diagnostics-{}", now_ms()));
+        let path = write_diagnostics(&dir, "crash", &json!({ "reason": "test" })).unwrap();
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("crash-") && name.ends_with(".json"));
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&text).unwrap(),
+            json!({ "reason": "test" })
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
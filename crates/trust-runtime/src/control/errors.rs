@@ -0,0 +1,214 @@
+//! Structured control error codes.
+//!
+//! Failed control responses carry an error object:
+//!
+//! ```json
+//! { "code": 202, "name": "DEBUG_DISABLED", "category": "auth", "message": "debug disabled" }
+//! ```
+//!
+//! `code` and `name` are stable; `message` is for humans and may change. Runtimes
+//! configured with `runtime.control.legacy_errors = true` send the message as a plain
+//! string instead.
+
+use serde_json::Value;
+
+/// Machine-readable reason a control request failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ControlErrorCode {
+    /// The request was not valid JSON or not a control request.
+    InvalidRequest,
+    /// The request type is not known to this runtime.
+    UnsupportedRequest,
+    /// Parameters were missing or malformed.
+    InvalidParams,
+    /// No valid auth token was presented.
+    Unauthorized,
+    /// The caller's role does not allow the request.
+    Forbidden,
+    /// Debug requests are disabled on this runtime.
+    DebugDisabled,
+    /// The target is read-only or writes are disabled.
+    ReadOnly,
+    /// The request was valid but could not be carried out.
+    Failed,
+    /// A named variable, frame, source, or other target does not exist.
+    NotFound,
+    /// A service or piece of state the request needs is not available.
+    Unavailable,
+    /// The resource is not in a state that allows the request.
+    InvalidState,
+    /// The runtime did not answer in time.
+    Timeout,
+    /// The client exceeded `max_requests_per_sec`.
+    RateLimited,
+    /// The request exceeded `max_request_bytes`.
+    RequestTooLarge,
+    /// The server already serves `max_connections`.
+    TooManyConnections,
+}
+
+impl ControlErrorCode {
+    pub const ALL: [Self; 15] = [
+        Self::InvalidRequest,
+        Self::UnsupportedRequest,
+        Self::InvalidParams,
+        Self::Unauthorized,
+        Self::Forbidden,
+        Self::DebugDisabled,
+        Self::ReadOnly,
+        Self::Failed,
+        Self::NotFound,
+        Self::Unavailable,
+        Self::InvalidState,
+        Self::Timeout,
+        Self::RateLimited,
+        Self::RequestTooLarge,
+        Self::TooManyConnections,
+    ];
+
+    /// Stable numeric code; the hundreds digit is the category.
+    #[must_use]
+    pub const fn code(self) -> u16 {
+        match self {
+            Self::InvalidRequest => 100,
+            Self::UnsupportedRequest => 101,
+            Self::InvalidParams => 102,
+            Self::Unauthorized => 200,
+            Self::Forbidden => 201,
+            Self::DebugDisabled => 202,
+            Self::ReadOnly => 203,
+            Self::Failed => 300,
+            Self::NotFound => 301,
+            Self::Unavailable => 302,
+            Self::InvalidState => 303,
+            Self::Timeout => 304,
+            Self::RateLimited => 400,
+            Self::RequestTooLarge => 401,
+            Self::TooManyConnections => 402,
+        }
+    }
+
+    /// Stable symbolic name, e.g. `UNAUTHORIZED`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::InvalidRequest => "INVALID_REQUEST",
+            Self::UnsupportedRequest => "UNSUPPORTED_REQUEST",
+            Self::InvalidParams => "INVALID_PARAMS",
+            Self::Unauthorized => "UNAUTHORIZED",
+            Self::Forbidden => "FORBIDDEN",
+            Self::DebugDisabled => "DEBUG_DISABLED",
+            Self::ReadOnly => "READ_ONLY",
+            Self::Failed => "FAILED",
+            Self::NotFound => "NOT_FOUND",
+            Self::Unavailable => "UNAVAILABLE",
+            Self::InvalidState => "INVALID_STATE",
+            Self::Timeout => "TIMEOUT",
+            Self::RateLimited => "RATE_LIMITED",
+            Self::RequestTooLarge => "REQUEST_TOO_LARGE",
+            Self::TooManyConnections => "TOO_MANY_CONNECTIONS",
+        }
+    }
+
+    #[must_use]
+    pub const fn category(self) -> &'static str {
+        match self.code() / 100 {
+            1 => "request",
+            2 => "auth",
+            3 => "runtime",
+            _ => "limit",
+        }
+    }
+
+    #[must_use]
+    pub fn from_code(code: u64) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|candidate| u64::from(candidate.code()) == code)
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|candidate| candidate.name().eq_ignore_ascii_case(name))
+    }
+
+    /// HTTP status used when the error is returned over the REST API.
+    #[must_use]
+    pub const fn http_status(self) -> u16 {
+        match self {
+            Self::Unauthorized => 401,
+            Self::Forbidden | Self::DebugDisabled | Self::ReadOnly => 403,
+            Self::NotFound => 404,
+            Self::Timeout => 504,
+            Self::RateLimited => 429,
+            Self::RequestTooLarge => 413,
+            Self::TooManyConnections | Self::Unavailable => 503,
+            Self::InvalidRequest
+            | Self::UnsupportedRequest
+            | Self::InvalidParams
+            | Self::Failed
+            | Self::InvalidState => 400,
+        }
+    }
+}
+
+/// Human-readable message of a response `error`, in either the structured or the
+/// legacy string form.
+#[must_use]
+pub fn control_error_message(error: &Value) -> Option<&str> {
+    match error {
+        Value::String(message) => Some(message),
+        Value::Object(fields) => fields.get("message").and_then(Value::as_str),
+        _ => None,
+    }
+}
+
+/// Code of a response `error`. Legacy string errors carry no code.
+#[must_use]
+pub fn control_error_code(error: &Value) -> Option<ControlErrorCode> {
+    let fields = error.as_object()?;
+    fields
+        .get("code")
+        .and_then(Value::as_u64)
+        .and_then(ControlErrorCode::from_code)
+        .or_else(|| {
+            fields
+                .get("name")
+                .and_then(Value::as_str)
+                .and_then(ControlErrorCode::from_name)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn codes_and_names_round_trip() {
+        for code in ControlErrorCode::ALL {
+            assert_eq!(
+                ControlErrorCode::from_code(u64::from(code.code())),
+                Some(code)
+            );
+            assert_eq!(ControlErrorCode::from_name(code.name()), Some(code));
+        }
+        assert_eq!(ControlErrorCode::DebugDisabled.category(), "auth");
+        assert_eq!(ControlErrorCode::RateLimited.category(), "limit");
+    }
+
+    #[test]
+    fn error_helpers_accept_structured_and_legacy_errors() {
+        let structured = json!({ "code": 200, "name": "UNAUTHORIZED", "message": "unauthorized" });
+        assert_eq!(control_error_message(&structured), Some("unauthorized"));
+        assert_eq!(
+            control_error_code(&structured),
+            Some(ControlErrorCode::Unauthorized)
+        );
+        let legacy = json!("unauthorized");
+        assert_eq!(control_error_message(&legacy), Some("unauthorized"));
+        assert_eq!(control_error_code(&legacy), None);
+    }
+}
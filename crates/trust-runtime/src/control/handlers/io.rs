@@ -25,9 +25,13 @@ pub(super) fn dispatch(request: &ControlRequest, state: &ControlState) -> Option
         }
         "hmi.write" => super::super::handle_hmi_write(request.id, request.params.clone(), state),
         "io.read" => super::super::handle_io_read(request.id, state),
+        "io.map.get" => super::super::handle_io_map_get(request.id, state),
         "io.write" => super::super::handle_io_write(request.id, request.params.clone(), state),
         "io.force" => super::super::handle_io_force(request.id, request.params.clone(), state),
         "io.unforce" => super::super::handle_io_unforce(request.id, request.params.clone(), state),
+        "sim.model.set" => {
+            super::super::handle_sim_model_set(request.id, request.params.clone(), state)
+        }
         _ => return None,
     };
     Some(response)
@@ -1,4 +1,4 @@
-use super::{ControlRequest, ControlResponse, ControlState};
+use super::{ControlRequest, ControlResponse, ControlSession, ControlState};
 
 mod debug;
 mod io;
@@ -6,10 +6,14 @@ mod program;
 mod status;
 mod variables;
 
-pub(super) fn dispatch(request: &ControlRequest, state: &ControlState) -> Option<ControlResponse> {
-    status::dispatch(request, state)
+pub(super) fn dispatch(
+    request: &ControlRequest,
+    state: &ControlState,
+    session: Option<&ControlSession>,
+) -> Option<ControlResponse> {
+    status::dispatch(request, state, session)
         .or_else(|| io::dispatch(request, state))
-        .or_else(|| debug::dispatch(request, state))
+        .or_else(|| debug::dispatch(request, state, session))
         .or_else(|| variables::dispatch(request, state))
         .or_else(|| program::dispatch(request, state))
 }
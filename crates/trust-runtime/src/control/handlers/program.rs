@@ -4,6 +4,10 @@ pub(super) fn dispatch(request: &ControlRequest, state: &ControlState) -> Option
     let response = match request.r#type.as_str() {
         "shutdown" => super::super::handle_shutdown(request.id, state),
         "restart" => super::super::handle_restart(request.id, request.params.clone(), state),
+        "restart.preview" => {
+            super::super::handle_restart_preview(request.id, request.params.clone(), state)
+        }
+        "cycle.step" => super::super::handle_cycle_step(request.id, request.params.clone(), state),
         "bytecode.reload" => {
             super::super::handle_bytecode_reload(request.id, request.params.clone(), state)
         }
@@ -1,6 +1,10 @@
-use super::{ControlRequest, ControlResponse, ControlState};
+use super::{ControlRequest, ControlResponse, ControlSession, ControlState};
 
-pub(super) fn dispatch(request: &ControlRequest, state: &ControlState) -> Option<ControlResponse> {
+pub(super) fn dispatch(
+    request: &ControlRequest,
+    state: &ControlState,
+    session: Option<&ControlSession>,
+) -> Option<ControlResponse> {
     let response = match request.r#type.as_str() {
         "status" => super::super::handle_status(request.id, state),
         "health" => super::super::handle_health(request.id, state),
@@ -8,6 +12,9 @@ pub(super) fn dispatch(request: &ControlRequest, state: &ControlState) -> Option
         "events.tail" | "events" => {
             super::super::handle_events_tail(request.id, request.params.clone(), state)
         }
+        "events.query" => {
+            super::super::handle_events_query(request.id, request.params.clone(), state)
+        }
         "faults" => super::super::handle_faults(request.id, request.params.clone(), state),
         "config.get" => super::super::handle_config_get(request.id, state),
         "config.set" => super::super::handle_config_set(request.id, request.params.clone(), state),
@@ -17,6 +24,10 @@ pub(super) fn dispatch(request: &ControlRequest, state: &ControlState) -> Option
         "historian.alerts" => {
             super::super::handle_historian_alerts(request.id, request.params.clone(), state)
         }
+        "mesh.status" => super::super::handle_mesh_status(request.id, state),
+        "mesh.topology" => super::super::handle_mesh_topology(request.id, state),
+        "diagnostics.collect" => super::super::handle_diagnostics_collect(request.id, state),
+        "session.info" => super::super::handle_session_info(request.id, state, session),
         _ => return None,
     };
     Some(response)
@@ -0,0 +1,194 @@
+//! Request limits shared by the control and web servers.
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use smol_str::SmolStr;
+
+use crate::config::ServerLimits;
+
+/// Clients tracked before idle buckets are dropped.
+const RATE_LIMIT_MAX_CLIENTS: usize = 1024;
+
+/// Enforces [`ServerLimits`] for one server.
+#[derive(Debug)]
+pub(crate) struct LimitGuard {
+    limits: ServerLimits,
+    open: Arc<AtomicUsize>,
+    buckets: Mutex<HashMap<SmolStr, TokenBucket>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    refilled: Instant,
+}
+
+/// Held for as long as a connection is served; releases its slot on drop.
+#[derive(Debug)]
+pub(crate) struct ConnectionPermit {
+    open: Arc<AtomicUsize>,
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        self.open.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl LimitGuard {
+    pub(crate) fn new(limits: ServerLimits) -> Self {
+        Self {
+            limits,
+            open: Arc::new(AtomicUsize::new(0)),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn max_request_bytes(&self) -> usize {
+        self.limits.max_request_bytes
+    }
+
+    /// Claim a connection slot, or `None` when `max_connections` are already open.
+    pub(crate) fn try_connect(&self) -> Option<ConnectionPermit> {
+        let max = self.limits.max_connections;
+        self.open
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |open| {
+                (open < max).then_some(open + 1)
+            })
+            .ok()?;
+        Some(ConnectionPermit {
+            open: Arc::clone(&self.open),
+        })
+    }
+
+    /// Take one request from the client's token bucket.
+    pub(crate) fn allow_request(&self, client: &str) -> bool {
+        let rate = f64::from(self.limits.max_requests_per_sec);
+        let now = Instant::now();
+        let Ok(mut buckets) = self.buckets.lock() else {
+            return true;
+        };
+        if buckets.len() >= RATE_LIMIT_MAX_CLIENTS && !buckets.contains_key(client) {
+            // Buckets that have refilled carry no state worth keeping.
+            buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.refilled).as_secs_f64() * rate < rate
+            });
+        }
+        let bucket = buckets.entry(SmolStr::new(client)).or_insert(TokenBucket {
+            tokens: rate,
+            refilled: now,
+        });
+        let elapsed = now.duration_since(bucket.refilled).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(rate);
+        bucket.refilled = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// One newline-terminated request read with a size cap.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum BoundedLine {
+    Line(String),
+    /// The line was longer than the cap; its bytes were skipped.
+    TooLarge,
+    Eof,
+}
+
+/// Read the next line without buffering more than `max` bytes of it.
+pub(crate) fn read_bounded_line(
+    reader: &mut impl BufRead,
+    max: usize,
+) -> std::io::Result<BoundedLine> {
+    let mut line = Vec::new();
+    let mut too_large = false;
+    let mut read_any = false;
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            break;
+        }
+        read_any = true;
+        let (chunk, done) = match available.iter().position(|byte| *byte == b'\n') {
+            Some(end) => (&available[..end], end + 1),
+            None => (available, available.len()),
+        };
+        let found_newline = done > chunk.len();
+        if !too_large {
+            if line.len() + chunk.len() > max {
+                too_large = true;
+                line = Vec::new();
+            } else {
+                line.extend_from_slice(chunk);
+            }
+        }
+        reader.consume(done);
+        if found_newline {
+            break;
+        }
+    }
+    if too_large {
+        return Ok(BoundedLine::TooLarge);
+    }
+    if !read_any {
+        return Ok(BoundedLine::Eof);
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    String::from_utf8(line)
+        .map(BoundedLine::Line)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_lines_skip_oversized_requests() {
+        let input = b"{\"id\":1}\n0123456789abcdef\n{\"id\":2}\r\n".to_vec();
+        let mut reader = std::io::Cursor::new(input);
+        assert_eq!(
+            read_bounded_line(&mut reader, 10).unwrap(),
+            BoundedLine::Line("{\"id\":1}".to_string())
+        );
+        assert_eq!(
+            read_bounded_line(&mut reader, 10).unwrap(),
+            BoundedLine::TooLarge
+        );
+        assert_eq!(
+            read_bounded_line(&mut reader, 10).unwrap(),
+            BoundedLine::Line("{\"id\":2}".to_string())
+        );
+        assert_eq!(
+            read_bounded_line(&mut reader, 10).unwrap(),
+            BoundedLine::Eof
+        );
+    }
+
+    #[test]
+    fn limit_guard_caps_rate_per_client_and_connections() {
+        let guard = LimitGuard::new(ServerLimits {
+            max_request_bytes: 64,
+            max_requests_per_sec: 3,
+            max_connections: 1,
+        });
+        assert!((0..3).all(|_| guard.allow_request("10.0.0.1")));
+        assert!(!guard.allow_request("10.0.0.1"));
+        assert!(guard.allow_request("10.0.0.2"));
+
+        let permit = guard.try_connect().expect("first connection");
+        assert!(guard.try_connect().is_none());
+        drop(permit);
+        assert!(guard.try_connect().is_some());
+    }
+}
@@ -0,0 +1,177 @@
+//! Per-connection control sessions.
+//!
+//! Every control connection gets its own session holding the state that must not leak
+//! between clients: debug variable handles, the debug stop cursor, and variable history
+//! subscriptions. Requests without a session (one-shot HTTP calls, tests) use the shared
+//! state on [`ControlState`](super::ControlState).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use smol_str::SmolStr;
+
+use crate::debug::{DebugControl, DebugVariableHandles};
+
+/// State owned by one control connection.
+#[derive(Debug)]
+pub struct ControlSession {
+    id: u64,
+    transport: SmolStr,
+    client: Option<SmolStr>,
+    opened_at_ms: u128,
+    requests: AtomicU64,
+    pub(super) variables: Mutex<DebugVariableHandles>,
+    stop_cursor: AtomicU64,
+    history: Mutex<BTreeSet<SmolStr>>,
+}
+
+impl ControlSession {
+    #[must_use]
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    #[must_use]
+    pub fn transport(&self) -> &str {
+        self.transport.as_str()
+    }
+
+    #[must_use]
+    pub fn client(&self) -> Option<&str> {
+        self.client.as_deref()
+    }
+
+    #[must_use]
+    pub fn requests(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    /// Variables this session records history for.
+    #[must_use]
+    pub fn history_names(&self) -> Vec<SmolStr> {
+        self.history
+            .lock()
+            .map(|names| names.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub(super) fn record_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn stop_cursor(&self) -> u64 {
+        self.stop_cursor.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn set_stop_cursor(&self, cursor: u64) {
+        self.stop_cursor.store(cursor, Ordering::Relaxed);
+    }
+
+    pub(super) fn subscribe_history(&self, name: &SmolStr) {
+        if let Ok(mut names) = self.history.lock() {
+            names.insert(name.clone());
+        }
+    }
+
+    pub(super) fn info_json(&self) -> serde_json::Value {
+        let history = self.history_names();
+        serde_json::json!({
+            "id": self.id,
+            "transport": self.transport.as_str(),
+            "client": self.client.as_deref(),
+            "opened_at_ms": self.opened_at_ms,
+            "requests": self.requests(),
+            "history": history.iter().map(SmolStr::as_str).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Registry of open control sessions.
+#[derive(Debug, Default)]
+pub struct ControlSessions {
+    next_id: AtomicU64,
+    sessions: Mutex<BTreeMap<u64, Arc<ControlSession>>>,
+}
+
+impl ControlSessions {
+    /// Open a session. Its stop cursor starts at `stop_cursor`, so earlier stops are not
+    /// replayed to the new client.
+    pub fn open(
+        &self,
+        transport: &str,
+        client: Option<&str>,
+        stop_cursor: u64,
+    ) -> Arc<ControlSession> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let opened_at_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let session = Arc::new(ControlSession {
+            id,
+            transport: SmolStr::new(transport),
+            client: client.map(SmolStr::new),
+            opened_at_ms,
+            requests: AtomicU64::new(0),
+            variables: Mutex::new(DebugVariableHandles::new()),
+            stop_cursor: AtomicU64::new(stop_cursor),
+            history: Mutex::new(BTreeSet::new()),
+        });
+        if let Ok(mut sessions) = self.sessions.lock() {
+            sessions.insert(id, Arc::clone(&session));
+        }
+        session
+    }
+
+    /// Close a session and stop recording history nobody else subscribed to.
+    pub fn close(&self, id: u64, debug: &DebugControl) {
+        let removed = self
+            .sessions
+            .lock()
+            .ok()
+            .and_then(|mut sessions| sessions.remove(&id));
+        if let Some(session) = removed {
+            let names = session.history_names();
+            self.release_history(&session, &names, debug);
+        }
+    }
+
+    /// Open sessions, oldest first.
+    #[must_use]
+    pub fn list(&self) -> Vec<Arc<ControlSession>> {
+        self.sessions
+            .lock()
+            .map(|sessions| sessions.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Drop `names` from the session's subscriptions. Recording stops only for names no
+    /// other open session still follows.
+    pub(super) fn release_history(
+        &self,
+        session: &ControlSession,
+        names: &[SmolStr],
+        debug: &DebugControl,
+    ) {
+        if let Ok(mut owned) = session.history.lock() {
+            for name in names {
+                owned.remove(name);
+            }
+        }
+        let others = self.list();
+        for name in names {
+            let shared = others.iter().any(|other| {
+                other.id != session.id
+                    && other
+                        .history
+                        .lock()
+                        .map(|owned| owned.contains(name))
+                        .unwrap_or(false)
+            });
+            if !shared {
+                debug.unwatch_history(name);
+            }
+        }
+    }
+}
@@ -1,18 +1,25 @@
 //! Control server transport (TCP/Unix).
 
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufReader, Read, Write};
 use std::net::TcpListener;
 use std::sync::Arc;
 use std::thread;
 
+use crate::config::ServerLimits;
 use crate::error::RuntimeError;
 
-use super::{handle_request_line, ControlEndpoint, ControlState};
+use super::limits::{read_bounded_line, BoundedLine, LimitGuard};
+use super::{
+    handle_request_line, record_limit_audit, ControlEndpoint, ControlErrorCode, ControlResponse,
+    ControlSession, ControlState,
+};
 
 pub(crate) fn spawn_control_server(
     endpoint: &ControlEndpoint,
     state: Arc<ControlState>,
+    limits: ServerLimits,
 ) -> Result<(), RuntimeError> {
+    let guard = Arc::new(LimitGuard::new(limits));
     match endpoint {
         ControlEndpoint::Tcp(addr) => {
             let listener = TcpListener::bind(addr)
@@ -20,9 +27,38 @@ pub(crate) fn spawn_control_server(
             let state = state.clone();
             thread::spawn(move || {
                 for stream in listener.incoming().map_while(Result::ok) {
-                    let client = stream.peer_addr().map(|addr| addr.to_string()).ok();
+                    let peer = stream.peer_addr().ok();
+                    let client = peer.map(|addr| addr.to_string());
+                    // Rate limits follow the host so reconnecting does not reset them.
+                    let rate_key =
+                        peer.map_or_else(|| "tcp".to_string(), |addr| addr.ip().to_string());
+                    let Some(permit) = guard.try_connect() else {
+                        reject_connection(stream, &state, client.as_deref());
+                        continue;
+                    };
                     let state = state.clone();
-                    thread::spawn(move || handle_client(stream, state, client));
+                    let guard = guard.clone();
+                    thread::spawn(move || {
+                        let _permit = permit;
+                        let Ok(reader) = stream.try_clone() else {
+                            return;
+                        };
+                        let session = state.sessions.open(
+                            "tcp",
+                            client.as_deref(),
+                            state.debug.stop_cursor(),
+                        );
+                        serve_connection(
+                            reader,
+                            stream,
+                            &state,
+                            &session,
+                            client.as_deref(),
+                            &rate_key,
+                            &guard,
+                        );
+                        state.sessions.close(session.id(), &state.debug);
+                    });
                 }
             });
         }
@@ -38,8 +74,31 @@ pub(crate) fn spawn_control_server(
             let state = state.clone();
             thread::spawn(move || {
                 for stream in listener.incoming().map_while(Result::ok) {
+                    let Some(permit) = guard.try_connect() else {
+                        reject_connection(stream, &state, Some("unix"));
+                        continue;
+                    };
                     let state = state.clone();
-                    thread::spawn(move || handle_unix_client(stream, state));
+                    let guard = guard.clone();
+                    thread::spawn(move || {
+                        let _permit = permit;
+                        let Ok(reader) = stream.try_clone() else {
+                            return;
+                        };
+                        let session = state.sessions.open("unix", None, state.debug.stop_cursor());
+                        // Local sockets carry no peer address; limit each connection.
+                        let rate_key = format!("unix#{}", session.id());
+                        serve_connection(
+                            reader,
+                            stream,
+                            &state,
+                            &session,
+                            Some("unix"),
+                            &rate_key,
+                            &guard,
+                        );
+                        state.sessions.close(session.id(), &state.debug);
+                    });
                 }
             });
         }
@@ -47,30 +106,77 @@ pub(crate) fn spawn_control_server(
     Ok(())
 }
 
-fn handle_client(stream: std::net::TcpStream, state: Arc<ControlState>, client: Option<String>) {
-    let reader = match stream.try_clone() {
-        Ok(clone) => BufReader::new(clone),
-        Err(_) => return,
-    };
-    let mut writer = stream;
-    for line in reader.lines().map_while(Result::ok) {
-        if let Some(response) = handle_request_line(&line, &state, client.as_deref()) {
+fn serve_connection(
+    reader: impl Read,
+    mut writer: impl Write,
+    state: &ControlState,
+    session: &ControlSession,
+    client: Option<&str>,
+    rate_key: &str,
+    guard: &LimitGuard,
+) {
+    let mut reader = BufReader::new(reader);
+    loop {
+        let line = match read_bounded_line(&mut reader, guard.max_request_bytes()) {
+            Ok(BoundedLine::Line(line)) => line,
+            Ok(BoundedLine::TooLarge) => {
+                let error = format!(
+                    "request too large (max {} bytes)",
+                    guard.max_request_bytes()
+                );
+                reply_limit_error(
+                    &mut writer,
+                    state,
+                    ControlErrorCode::RequestTooLarge,
+                    "limit.request_size",
+                    error,
+                    client,
+                );
+                continue;
+            }
+            Ok(BoundedLine::Eof) | Err(_) => break,
+        };
+        if !guard.allow_request(rate_key) {
+            reply_limit_error(
+                &mut writer,
+                state,
+                ControlErrorCode::RateLimited,
+                "limit.rate",
+                "rate limit exceeded".to_string(),
+                client,
+            );
+            continue;
+        }
+        if let Some(response) = handle_request_line(&line, state, client, Some(session)) {
             let _ = writeln!(writer, "{response}");
         }
     }
 }
 
-#[cfg(unix)]
-fn handle_unix_client(stream: std::os::unix::net::UnixStream, state: Arc<ControlState>) {
-    let reader = match stream.try_clone() {
-        Ok(clone) => BufReader::new(clone),
-        Err(_) => return,
-    };
-    let mut writer = stream;
-    for line in reader.lines().map_while(Result::ok) {
-        if let Some(response) = handle_request_line(&line, &state, Some("unix")) {
-            let _ = writeln!(writer, "{response}");
-        }
+fn reject_connection(mut stream: impl Write, state: &ControlState, client: Option<&str>) {
+    reply_limit_error(
+        &mut stream,
+        state,
+        ControlErrorCode::TooManyConnections,
+        "limit.connections",
+        "too many connections".to_string(),
+        client,
+    );
+}
+
+fn reply_limit_error(
+    writer: &mut impl Write,
+    state: &ControlState,
+    code: ControlErrorCode,
+    kind: &str,
+    error: String,
+    client: Option<&str>,
+) {
+    record_limit_audit(state, kind, &error, client);
+    let response =
+        ControlResponse::error_code(0, code, error).with_legacy_errors(super::legacy_errors(state));
+    if let Ok(response) = serde_json::to_string(&response) {
+        let _ = writeln!(writer, "{response}");
     }
 }
 
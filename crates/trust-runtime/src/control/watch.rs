@@ -0,0 +1,160 @@
+//! Watch-mode rebuild and hot reload of project sources.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde_json::json;
+use tracing::warn;
+
+use super::{handle_request_value, ControlState};
+
+/// Quiet period after the last source change before rebuilding.
+const SOURCE_WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Outcome of one watch-mode rebuild.
+#[derive(Debug, Clone)]
+pub enum SourceWatchEvent {
+    /// Sources compiled and the new program is running.
+    Reloaded {
+        /// Number of source files in the build.
+        sources: usize,
+        /// Time from change detection to completed reload.
+        elapsed: Duration,
+    },
+    /// Sources failed to compile; the previous program keeps running.
+    BuildFailed(String),
+    /// The program built but `bytecode.reload` rejected it.
+    ReloadFailed(String),
+}
+
+/// Rebuilds `program.stbc` whenever a `.st` file under `sources_root` changes and
+/// hot-reloads it through the `bytecode.reload` control request.
+///
+/// Changes are debounced so an editor saving several files triggers one rebuild.
+/// `on_event` is called from the watcher thread after every rebuild attempt.
+pub fn spawn_source_watcher<F>(
+    state: Arc<ControlState>,
+    bundle_root: PathBuf,
+    sources_root: PathBuf,
+    on_event: F,
+) -> anyhow::Result<()>
+where
+    F: Fn(SourceWatchEvent) + Send + 'static,
+{
+    let (tx, rx) = std::sync::mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(move |result| {
+        let _ = tx.send(result);
+    })?;
+    watcher.watch(sources_root.as_path(), RecursiveMode::Recursive)?;
+    std::thread::spawn(move || {
+        // Keep the watcher alive for as long as the thread runs.
+        let _watcher = watcher;
+        loop {
+            match rx.recv() {
+                Ok(Ok(event)) if source_event_matches(&event) => {}
+                Ok(Ok(_)) => continue,
+                Ok(Err(err)) => {
+                    warn!("source watcher event error: {err}");
+                    continue;
+                }
+                Err(_) => return,
+            }
+            let started = Instant::now();
+            let mut deadline = started + SOURCE_WATCH_DEBOUNCE;
+            while let Some(timeout) = deadline.checked_duration_since(Instant::now()) {
+                match rx.recv_timeout(timeout) {
+                    Ok(Ok(event)) if source_event_matches(&event) => {
+                        deadline = Instant::now() + SOURCE_WATCH_DEBOUNCE;
+                    }
+                    Ok(Ok(_)) => {}
+                    Ok(Err(err)) => warn!("source watcher event error: {err}"),
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+            on_event(rebuild_and_reload(
+                &state,
+                &bundle_root,
+                &sources_root,
+                started,
+            ));
+        }
+    });
+    Ok(())
+}
+
+fn rebuild_and_reload(
+    state: &ControlState,
+    bundle_root: &Path,
+    sources_root: &Path,
+    started: Instant,
+) -> SourceWatchEvent {
+    let report = match crate::bundle_builder::build_program_stbc(bundle_root, Some(sources_root)) {
+        Ok(report) => report,
+        Err(err) => return SourceWatchEvent::BuildFailed(format!("{err:#}")),
+    };
+    let program = match std::fs::read(&report.program_path) {
+        Ok(program) => program,
+        Err(err) => {
+            return SourceWatchEvent::ReloadFailed(format!(
+                "failed to read {}: {err}",
+                report.program_path.display()
+            ))
+        }
+    };
+    let auth = state.auth_token.lock().ok().and_then(|guard| guard.clone());
+    let response = handle_request_value(
+        json!({
+            "id": 0,
+            "type": "bytecode.reload",
+            "auth": auth.as_deref(),
+            "params": { "bytes": BASE64_STANDARD.encode(program) },
+        }),
+        state,
+        Some("watch"),
+    );
+    if response.is_ok() {
+        return SourceWatchEvent::Reloaded {
+            sources: report.sources.len(),
+            elapsed: started.elapsed(),
+        };
+    }
+    let error = response.error_message().unwrap_or("bytecode reload failed");
+    SourceWatchEvent::ReloadFailed(error.to_string())
+}
+
+fn source_event_matches(event: &Event) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) && event.paths.iter().any(|path| {
+        path.extension()
+            .and_then(|value| value.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("st") || ext.eq_ignore_ascii_case("pou"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, ModifyKind};
+
+    #[test]
+    fn source_events_only_match_structured_text_files() {
+        let st = Event::new(EventKind::Modify(ModifyKind::Any)).add_path("src/main.st".into());
+        assert!(source_event_matches(&st));
+        let created = Event::new(EventKind::Create(CreateKind::File)).add_path("src/FB.POU".into());
+        assert!(source_event_matches(&created));
+        let program =
+            Event::new(EventKind::Modify(ModifyKind::Any)).add_path("program.stbc".into());
+        assert!(!source_event_matches(&program));
+        let access = Event::new(EventKind::Access(notify::event::AccessKind::Any))
+            .add_path("src/main.st".into());
+        assert!(!source_event_matches(&access));
+    }
+}
@@ -32,6 +32,20 @@ pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> Result<i64, Da
     Ok(era * 146097 + doe - 719468)
 }
 
+pub(crate) fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = mp + if mp < 10 { 3 } else { -9 };
+    let year = y + if m <= 2 { 1 } else { 0 };
+    (year, m, d)
+}
+
 pub(crate) fn ticks_per_day(profile: DateTimeProfile) -> Result<i64, DateTimeCalcError> {
     let res = profile.resolution.as_nanos();
     if res <= 0 {
@@ -5,7 +5,7 @@
 use std::sync::mpsc::Sender;
 
 use crate::eval::{eval_expr, EvalContext};
-use crate::value::Value;
+use crate::value::{format_time_literal, DateTimeProfile, Value};
 
 use super::{DebugBreakpoint, DebugLog, LogFragment, SourceLocation};
 
@@ -99,12 +99,13 @@ fn format_log_value(value: &Value) -> String {
         Value::WChar(value) => char::from_u32((*value).into()).unwrap_or('?').to_string(),
         Value::Array(value) => format!("[{}]", value.elements.len()),
         Value::Struct(value) => format!("{} {{...}}", value.type_name),
-        Value::Enum(value) => format!("{}::{}", value.type_name, value.variant_name),
+        Value::Enum(value) => format!("{}#{}", value.type_name, value.variant_name),
         Value::Reference(Some(_)) => "REF".to_string(),
         Value::Reference(None) => "NULL_REF".to_string(),
         Value::Instance(value) => format!("Instance({})", value.0),
         Value::Null => "NULL".to_string(),
-        _ => format!("{value:?}"),
+        _ => format_time_literal(value, DateTimeProfile::default())
+            .unwrap_or_else(|| format!("{value:?}")),
     }
 }
 
@@ -0,0 +1,239 @@
+//! Debug console completion over runtime variable storage.
+
+use serde::{Deserialize, Serialize};
+use trust_ide::completion::CompletionKind;
+use trust_ide::expr_completion::{
+    complete_request, parse_expression_prefix, ExprMember, ExprScope, ExprShape,
+};
+
+use crate::memory::{FrameId, InstanceId, LocalFrame, VariableStorage};
+use crate::value::Value;
+
+use super::dap::{format_value, value_type_name};
+
+static NULL_VALUE: Value = Value::Null;
+
+/// A completion candidate for a debug expression.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebugCompletion {
+    /// Text inserted for the candidate.
+    pub label: String,
+    /// Candidate category (`variable`, `field`, or `value`).
+    pub kind: String,
+    /// Type and current value shown next to the label.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Zero-based character offset where the replaced text starts.
+    pub start: usize,
+    /// Number of characters replaced.
+    pub length: usize,
+}
+
+/// Completes a debug console expression against paused (or live) storage.
+///
+/// `cursor` is a zero-based character offset into `text`; `None` completes at
+/// the end. Root names come from the frame locals, the owning instance chain,
+/// globals, and retain variables (in that order).
+#[must_use]
+pub fn complete_debug_expression(
+    storage: &VariableStorage,
+    frame_id: Option<FrameId>,
+    text: &str,
+    cursor: Option<usize>,
+) -> Vec<DebugCompletion> {
+    let end = cursor
+        .and_then(|cursor| text.char_indices().nth(cursor).map(|(idx, _)| idx))
+        .unwrap_or(text.len());
+    let text = &text[..end];
+    let Some(request) = parse_expression_prefix(text) else {
+        return Vec::new();
+    };
+    let frame = match frame_id {
+        Some(frame_id) => storage.frames().iter().find(|frame| frame.id == frame_id),
+        None => storage.current_frame(),
+    };
+    let scope = StorageScope { storage, frame };
+    let start = text[..request.replace_start].chars().count();
+    let length = request.prefix.chars().count();
+    let member = !request.base.is_empty();
+    complete_request(&scope, &request)
+        .into_iter()
+        .map(|item| DebugCompletion {
+            label: item.label.to_string(),
+            kind: match item.kind {
+                CompletionKind::Constant => "value",
+                _ if member => "field",
+                _ => "variable",
+            }
+            .to_string(),
+            detail: item.detail.map(|detail| detail.to_string()),
+            start,
+            length,
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+enum StorageNode<'a> {
+    Value(&'a Value),
+    Instance(InstanceId),
+}
+
+struct StorageScope<'a> {
+    storage: &'a VariableStorage,
+    frame: Option<&'a LocalFrame>,
+}
+
+impl<'a> StorageScope<'a> {
+    fn member(&self, name: &str, value: &'a Value) -> ExprMember<StorageNode<'a>> {
+        let node = match value {
+            Value::Instance(id) => StorageNode::Instance(*id),
+            _ => StorageNode::Value(value),
+        };
+        let detail = match value {
+            Value::Instance(id) => self
+                .storage
+                .get_instance(*id)
+                .map(|instance| instance.type_name.to_string()),
+            _ => value_type_name(value).map(|ty| format!("{ty} = {}", format_value(value))),
+        };
+        ExprMember {
+            name: name.into(),
+            detail: detail.map(Into::into),
+            node,
+        }
+    }
+
+    fn instance_members(&self, id: InstanceId) -> Vec<ExprMember<StorageNode<'a>>> {
+        let mut members: Vec<ExprMember<StorageNode<'a>>> = Vec::new();
+        let mut current = Some(id);
+        while let Some(instance_id) = current {
+            let Some(instance) = self.storage.get_instance(instance_id) else {
+                break;
+            };
+            for (name, value) in &instance.variables {
+                if !members
+                    .iter()
+                    .any(|member| member.name.eq_ignore_ascii_case(name))
+                {
+                    members.push(self.member(name, value));
+                }
+            }
+            current = instance.parent;
+        }
+        members
+    }
+}
+
+impl<'a> ExprScope for StorageScope<'a> {
+    type Node = StorageNode<'a>;
+
+    fn roots(&self) -> Vec<ExprMember<StorageNode<'a>>> {
+        let mut roots = Vec::new();
+        if let Some(frame) = self.frame {
+            roots.extend(
+                frame
+                    .variables
+                    .iter()
+                    .map(|(name, value)| self.member(name, value)),
+            );
+            if let Some(instance_id) = frame.instance_id {
+                roots.extend(self.instance_members(instance_id));
+            }
+        }
+        roots.extend(
+            self.storage
+                .globals()
+                .iter()
+                .chain(self.storage.retain().iter())
+                .map(|(name, value)| self.member(name, value)),
+        );
+        let mut seen = std::collections::HashSet::new();
+        roots.retain(|member| seen.insert(member.name.to_ascii_uppercase()));
+        roots
+    }
+
+    fn shape(&self, node: &StorageNode<'a>) -> ExprShape<StorageNode<'a>> {
+        let value = match node {
+            StorageNode::Instance(id) => return ExprShape::Members(self.instance_members(*id)),
+            StorageNode::Value(value) => *value,
+        };
+        match value {
+            Value::Struct(value) => ExprShape::Members(
+                value
+                    .fields
+                    .iter()
+                    .map(|(name, field)| self.member(name, field))
+                    .collect(),
+            ),
+            Value::Array(array) => {
+                let element = array.elements.first().unwrap_or(&NULL_VALUE);
+                ExprShape::Array {
+                    dimensions: array.dimensions.clone(),
+                    element: match element {
+                        Value::Instance(id) => StorageNode::Instance(*id),
+                        _ => StorageNode::Value(element),
+                    },
+                    element_detail: value_type_name(element).map(Into::into),
+                }
+            }
+            _ => ExprShape::Leaf,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::{ArrayValue, StructValue};
+    use indexmap::IndexMap;
+
+    fn storage() -> VariableStorage {
+        let mut storage = VariableStorage::new();
+        let mut fields = IndexMap::new();
+        fields.insert("Position".into(), Value::LReal(1.5));
+        fields.insert("Enabled".into(), Value::Bool(true));
+        let axis = Value::Struct(StructValue {
+            type_name: "Axis".into(),
+            fields,
+        });
+        storage.set_global(
+            "Axes",
+            Value::Array(ArrayValue {
+                elements: vec![axis; 6],
+                dimensions: vec![(1, 3), (0, 1)],
+            }),
+        );
+        storage.set_global("Count", Value::Int(4));
+        storage
+    }
+
+    #[test]
+    fn completes_struct_members_of_array_elements() {
+        let storage = storage();
+        let items = complete_debug_expression(&storage, None, "Axes[2, 1].Po", None);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "Position");
+        assert_eq!(items[0].kind, "field");
+        assert!(items[0]
+            .detail
+            .as_deref()
+            .is_some_and(|detail| detail.starts_with("LREAL = ")));
+        assert_eq!(items[0].start, 11);
+        assert_eq!(items[0].length, 2);
+    }
+
+    #[test]
+    fn completes_index_bounds_and_roots() {
+        let storage = storage();
+        let items = complete_debug_expression(&storage, None, "Axes[1, ", None);
+        let labels: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(labels, vec!["0", "1"]);
+        assert!(items.iter().all(|item| item.kind == "value"));
+
+        let items = complete_debug_expression(&storage, None, "co + 1", Some(2));
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "Count");
+        assert_eq!(items[0].kind, "variable");
+    }
+}
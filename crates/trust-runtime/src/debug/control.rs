@@ -2,12 +2,14 @@
 
 #![allow(missing_docs)]
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, Condvar, Mutex};
+use std::time::Instant;
 
 use smol_str::SmolStr;
 
+use crate::error::RuntimeError;
 use crate::eval::expr::{Expr, LValue};
 use crate::eval::{eval_expr, EvalContext};
 use crate::io::{IoAddress, IoSnapshot};
@@ -15,13 +17,17 @@ use crate::memory::{FrameId, InstanceId};
 use crate::value::Value;
 
 use super::breakpoints::matches_breakpoint;
+use super::history::{HistoryRing, VariableHistory};
 use super::hook::DebugHook;
 use super::trace::trace_debug;
 use super::{
-    DebugBreakpoint, DebugLog, DebugSnapshot, DebugStop, DebugStopReason, RuntimeEvent,
-    SourceLocation,
+    DebugBreakpoint, DebugLog, DebugSnapshot, DebugStop, DebugStopReason, FaultBreakpoints,
+    RuntimeEvent, SourceLocation,
 };
 
+/// Stops kept for cursor-based readers (see [`DebugControl::stops_since`]).
+const STOP_LOG_CAPACITY: usize = 256;
+
 /// Debugger execution mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DebugMode {
@@ -82,11 +88,19 @@ struct DebugState {
     target_thread: Option<u32>,
     breakpoints: Vec<DebugBreakpoint>,
     breakpoint_generation: HashMap<u32, u64>,
+    fault_breakpoints: FaultBreakpoints,
+    /// Whether the `restart` fault policy recovers from faults this cycle.
+    fault_recovers: bool,
+    /// Set once a fault was reported, so enclosing statements do not report it again.
+    fault_reported: bool,
+    /// Wall-clock deadline and timeout of the watchdog for the current cycle.
+    watchdog_deadline: Option<(Instant, crate::value::Duration)>,
     frame_locations: HashMap<FrameId, SourceLocation>,
     logs: Vec<DebugLog>,
     snapshot: Option<DebugSnapshot>,
     watches: Vec<WatchEntry>,
     watch_changed: bool,
+    history: HistoryRing,
     log_tx: Option<Sender<DebugLog>>,
     io_tx: Option<Sender<IoSnapshot>>,
     stop_tx: Option<Sender<DebugStop>>,
@@ -94,6 +108,8 @@ struct DebugState {
     runtime_events: Vec<RuntimeEvent>,
     pending_stop: Option<DebugStopReason>,
     stops: Vec<DebugStop>,
+    stop_log: VecDeque<(u64, DebugStop)>,
+    stop_seq: u64,
     last_stop: Option<DebugStop>,
     steps: HashMap<u32, StepState>,
     io_writes: Vec<(IoAddress, Value)>,
@@ -171,11 +187,16 @@ impl DebugControl {
                     target_thread: None,
                     breakpoints: Vec::new(),
                     breakpoint_generation: HashMap::new(),
+                    fault_breakpoints: FaultBreakpoints::default(),
+                    fault_recovers: false,
+                    fault_reported: false,
+                    watchdog_deadline: None,
                     frame_locations: HashMap::new(),
                     logs: Vec::new(),
                     snapshot: None,
                     watches: Vec::new(),
                     watch_changed: false,
+                    history: HistoryRing::default(),
                     log_tx: None,
                     io_tx: None,
                     stop_tx: None,
@@ -183,6 +204,8 @@ impl DebugControl {
                     runtime_events: Vec::new(),
                     pending_stop: None,
                     stops: Vec::new(),
+                    stop_log: VecDeque::new(),
+                    stop_seq: 0,
                     last_stop: None,
                     steps: HashMap::new(),
                     io_writes: Vec::new(),
@@ -338,6 +361,45 @@ impl DebugControl {
         trace_debug(&format!("breakpoints.clear prev_total={prev_total}"));
     }
 
+    /// Select which runtime faults stop execution.
+    pub fn set_fault_breakpoints(&self, filters: FaultBreakpoints) {
+        let (lock, _) = &*self.state;
+        let mut state = lock.lock().expect("debug state poisoned");
+        state.fault_breakpoints = filters;
+        trace_debug(&format!("breakpoints.faults filters={filters:?}"));
+    }
+
+    /// Current fault breakpoint filters.
+    #[must_use]
+    pub fn fault_breakpoints(&self) -> FaultBreakpoints {
+        let (lock, _) = &*self.state;
+        let state = lock.lock().expect("debug state poisoned");
+        state.fault_breakpoints
+    }
+
+    /// Arm fault breakpoints for the next cycle.
+    ///
+    /// `restart_on_fault` marks faults as handled, and `watchdog_timeout`
+    /// starts the watchdog pre-trip clock.
+    pub fn begin_fault_cycle(
+        &self,
+        restart_on_fault: bool,
+        watchdog_timeout: Option<crate::value::Duration>,
+    ) {
+        let (lock, _) = &*self.state;
+        let mut state = lock.lock().expect("debug state poisoned");
+        let watch = state.fault_breakpoints.watchdog;
+        state.fault_recovers = restart_on_fault;
+        state.fault_reported = false;
+        state.watchdog_deadline = watchdog_timeout.filter(|_| watch).map(|timeout| {
+            let nanos = u64::try_from(timeout.as_nanos()).unwrap_or_default();
+            (
+                Instant::now() + std::time::Duration::from_nanos(nanos),
+                timeout,
+            )
+        });
+    }
+
     /// Returns the number of active breakpoints (primarily for tests).
     #[doc(hidden)]
     pub fn breakpoint_count(&self) -> usize {
@@ -508,6 +570,21 @@ impl DebugControl {
         state.snapshot.clone()
     }
 
+    /// Breakpoints and the last snapshot without blocking, for crash handlers.
+    ///
+    /// Returns `None` when another thread holds the debug state. A poisoned lock is read
+    /// anyway, since the state is only inspected.
+    #[must_use]
+    pub fn try_inspect(&self) -> Option<(Vec<DebugBreakpoint>, Option<DebugSnapshot>)> {
+        let (lock, _) = &*self.state;
+        let state = match lock.try_lock() {
+            Ok(state) => state,
+            Err(std::sync::TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(std::sync::TryLockError::WouldBlock) => return None,
+        };
+        Some((state.breakpoints.clone(), state.snapshot.clone()))
+    }
+
     /// Return whether execution is currently paused.
     #[must_use]
     pub fn is_paused(&self) -> bool {
@@ -516,6 +593,18 @@ impl DebugControl {
         matches!(state.mode, DebugMode::Paused)
     }
 
+    /// Return whether no breakpoint, step, or pause request needs statement hooks.
+    #[must_use]
+    pub fn is_idle(&self) -> bool {
+        let (lock, _) = &*self.state;
+        let state = lock.lock().expect("debug state poisoned");
+        matches!(state.mode, DebugMode::Running)
+            && state.pending_stop.is_none()
+            && state.breakpoints.is_empty()
+            && state.fault_breakpoints.is_empty()
+            && state.steps.is_empty()
+    }
+
     /// Return the most recent stop, if any.
     #[must_use]
     pub fn last_stop(&self) -> Option<DebugStop> {
@@ -532,6 +621,31 @@ impl DebugControl {
         std::mem::take(&mut state.stops)
     }
 
+    /// Sequence number of the most recent stop.
+    #[must_use]
+    pub fn stop_cursor(&self) -> u64 {
+        let (lock, _) = &*self.state;
+        let state = lock.lock().expect("debug state poisoned");
+        state.stop_seq
+    }
+
+    /// Stops recorded after `cursor`, with the cursor to pass next time.
+    ///
+    /// Unlike [`Self::drain_stops`] this leaves the stops in place, so several readers can
+    /// each follow them with their own cursor. Only the most recent stops are kept.
+    #[must_use]
+    pub fn stops_since(&self, cursor: u64) -> (Vec<DebugStop>, u64) {
+        let (lock, _) = &*self.state;
+        let state = lock.lock().expect("debug state poisoned");
+        let stops = state
+            .stop_log
+            .iter()
+            .filter(|(seq, _)| *seq > cursor)
+            .map(|(_, stop)| stop.clone())
+            .collect();
+        (stops, state.stop_seq)
+    }
+
     /// Mutate the stored snapshot, if one exists.
     pub fn with_snapshot<T>(&self, f: impl FnOnce(&mut DebugSnapshot) -> T) -> Option<T> {
         let (lock, _) = &*self.state;
@@ -746,6 +860,69 @@ impl DebugControl {
         changed
     }
 
+    /// Set how many cycles of history are kept per variable.
+    pub fn set_history_depth(&self, depth: usize) {
+        let (lock, _) = &*self.state;
+        let mut state = lock.lock().expect("debug state poisoned");
+        state.history.set_depth(depth);
+    }
+
+    #[must_use]
+    pub fn history_depth(&self) -> usize {
+        let (lock, _) = &*self.state;
+        let state = lock.lock().expect("debug state poisoned");
+        state.history.depth()
+    }
+
+    /// Start recording a variable (global or `<program>.<variable>`) at every cycle end.
+    pub fn watch_history(&self, name: &str) {
+        let (lock, _) = &*self.state;
+        let mut state = lock.lock().expect("debug state poisoned");
+        state.history.watch(name);
+    }
+
+    /// Stop recording a variable and drop its history.
+    pub fn unwatch_history(&self, name: &str) {
+        let (lock, _) = &*self.state;
+        let mut state = lock.lock().expect("debug state poisoned");
+        state.history.unwatch(name);
+    }
+
+    /// Stop recording all variables.
+    pub fn clear_history(&self) {
+        let (lock, _) = &*self.state;
+        let mut state = lock.lock().expect("debug state poisoned");
+        state.history.clear();
+    }
+
+    /// Names of the variables recorded at each cycle end.
+    #[must_use]
+    pub fn history_names(&self) -> Vec<SmolStr> {
+        let (lock, _) = &*self.state;
+        let state = lock.lock().expect("debug state poisoned");
+        state.history.names()
+    }
+
+    /// Append one cycle's values for watched variables.
+    pub fn record_history(
+        &self,
+        cycle: u64,
+        time: crate::value::Duration,
+        values: Vec<(SmolStr, Value)>,
+    ) {
+        let (lock, _) = &*self.state;
+        let mut state = lock.lock().expect("debug state poisoned");
+        state.history.record(cycle, time, values);
+    }
+
+    /// Recorded history of a watched variable, limited to the newest `limit` samples.
+    #[must_use]
+    pub fn history(&self, name: &str, limit: Option<usize>) -> Option<VariableHistory> {
+        let (lock, _) = &*self.state;
+        let state = lock.lock().expect("debug state poisoned");
+        state.history.history(name, limit)
+    }
+
     /// Stream log output to a sender instead of buffering.
     pub fn set_log_sender(&self, sender: Sender<DebugLog>) {
         let (lock, _) = &*self.state;
@@ -850,6 +1027,41 @@ impl DebugHook for DebugControl {
     ) {
         self.on_statement_inner(location, call_depth, Some(ctx));
     }
+
+    fn on_fault(
+        &mut self,
+        ctx: &mut EvalContext<'_>,
+        location: Option<&SourceLocation>,
+        error: &RuntimeError,
+    ) {
+        let (lock, cvar) = &*self.state;
+        let mut state = lock.lock().expect("debug state poisoned");
+        if state.fault_reported {
+            return;
+        }
+        state.fault_reported = true;
+        let filters = state.fault_breakpoints;
+        if !(filters.all_faults || (filters.unhandled_faults && !state.fault_recovers)) {
+            return;
+        }
+        update_watch_snapshot(&mut state, ctx);
+        update_snapshot(&mut state, ctx);
+        state.mode = DebugMode::Paused;
+        state.steps.clear();
+        state.pending_stop = None;
+        state.target_thread = None;
+        emit_stop(
+            &mut state,
+            DebugStopReason::Exception,
+            location.copied(),
+            None,
+            Some(SmolStr::new(error.to_string())),
+        );
+        // Hold the faulting statement until resumed; the fault then propagates.
+        while matches!(state.mode, DebugMode::Paused) {
+            state = cvar.wait(state).expect("debug state poisoned");
+        }
+    }
 }
 
 impl DebugControl {
@@ -874,6 +1086,7 @@ impl DebugControl {
         ));
         state.last_location = location.copied();
         state.last_call_depth = call_depth;
+        state.fault_reported = false;
         if let Some(thread_id) = state.current_thread {
             state.last_call_depths.insert(thread_id, call_depth);
         }
@@ -899,7 +1112,7 @@ impl DebugControl {
                     update_watch_snapshot(&mut state, eval_ctx);
                     update_snapshot(&mut state, eval_ctx);
                 }
-                emit_stop(&mut state, reason, location.copied(), None);
+                emit_stop(&mut state, reason, location.copied(), None, None);
             }
         }
         let effective_mode = if is_target_thread {
@@ -916,7 +1129,20 @@ impl DebugControl {
             let mut should_pause = false;
             let mut stop_reason = None;
             let mut stop_generation = None;
-            if is_target_thread {
+            let mut stop_fault = None;
+            if let Some((deadline, timeout)) = state.watchdog_deadline {
+                if is_target_thread && Instant::now() >= deadline {
+                    state.watchdog_deadline = None;
+                    state.steps.clear();
+                    should_pause = true;
+                    stop_reason = Some(DebugStopReason::Exception);
+                    stop_fault = Some(SmolStr::new(format!(
+                        "watchdog pre-trip: cycle exceeded the watchdog timeout of {} ms",
+                        timeout.as_millis()
+                    )));
+                }
+            }
+            if is_target_thread && !should_pause {
                 let step_key = state
                     .current_thread
                     .filter(|id| state.steps.contains_key(id))
@@ -984,7 +1210,13 @@ impl DebugControl {
                         update_watch_snapshot(&mut state, eval_ctx);
                         update_snapshot(&mut state, eval_ctx);
                     }
-                    emit_stop(&mut state, reason, Some(*location), stop_generation);
+                    emit_stop(
+                        &mut state,
+                        reason,
+                        Some(*location),
+                        stop_generation,
+                        stop_fault,
+                    );
                 }
             }
         }
@@ -1002,7 +1234,7 @@ impl DebugControl {
                         update_watch_snapshot(&mut state, eval_ctx);
                         update_snapshot(&mut state, eval_ctx);
                     }
-                    emit_stop(&mut state, reason, location.copied(), None);
+                    emit_stop(&mut state, reason, location.copied(), None, None);
                 }
             }
             match state.mode {
@@ -1055,21 +1287,29 @@ fn emit_stop(
     reason: DebugStopReason,
     location: Option<SourceLocation>,
     breakpoint_generation: Option<u64>,
+    fault: Option<SmolStr>,
 ) {
     trace_debug(&format!(
-        "stop reason={reason:?} location={:?} thread={:?}",
-        location, state.current_thread
+        "stop reason={reason:?} location={:?} thread={:?} fault={:?}",
+        location, state.current_thread, fault
     ));
     let stop = DebugStop {
         reason,
         location,
         thread_id: state.current_thread,
         breakpoint_generation,
+        fault,
     };
     if let Some(sender) = &state.stop_tx {
         let _ = sender.send(stop.clone());
     }
     state.last_stop = Some(stop.clone());
+    state.stop_seq += 1;
+    let seq = state.stop_seq;
+    if state.stop_log.len() == STOP_LOG_CAPACITY {
+        state.stop_log.pop_front();
+    }
+    state.stop_log.push_back((seq, stop.clone()));
     state.stops.push(stop);
 }
 
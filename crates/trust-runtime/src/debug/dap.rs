@@ -6,10 +6,13 @@ use std::collections::HashMap;
 
 use serde::Serialize;
 use smol_str::SmolStr;
+use trust_hir::TypeId;
 
 use crate::io::{IoAddress, IoSize, IoSnapshot, IoSnapshotEntry, IoSnapshotValue};
 use crate::memory::{FrameId, InstanceId, IoArea};
-use crate::value::{ArrayValue, StructValue, Value, ValueRef};
+use crate::value::{
+    format_time_literal, ArrayValue, DateTimeProfile, StructValue, Value, ValueRef,
+};
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -133,6 +136,41 @@ pub fn value_type_name(value: &Value) -> Option<String> {
     Some(name.to_string())
 }
 
+/// Elementary type of a value, used to coerce debugger writes to the target type.
+pub fn value_type_id(value: &Value) -> Option<TypeId> {
+    let type_id = match value {
+        Value::Bool(_) => TypeId::BOOL,
+        Value::SInt(_) => TypeId::SINT,
+        Value::Int(_) => TypeId::INT,
+        Value::DInt(_) => TypeId::DINT,
+        Value::LInt(_) => TypeId::LINT,
+        Value::USInt(_) => TypeId::USINT,
+        Value::UInt(_) => TypeId::UINT,
+        Value::UDInt(_) => TypeId::UDINT,
+        Value::ULInt(_) => TypeId::ULINT,
+        Value::Real(_) => TypeId::REAL,
+        Value::LReal(_) => TypeId::LREAL,
+        Value::Byte(_) => TypeId::BYTE,
+        Value::Word(_) => TypeId::WORD,
+        Value::DWord(_) => TypeId::DWORD,
+        Value::LWord(_) => TypeId::LWORD,
+        Value::Time(_) => TypeId::TIME,
+        Value::LTime(_) => TypeId::LTIME,
+        Value::Date(_) => TypeId::DATE,
+        Value::LDate(_) => TypeId::LDATE,
+        Value::Tod(_) => TypeId::TOD,
+        Value::LTod(_) => TypeId::LTOD,
+        Value::Dt(_) => TypeId::DT,
+        Value::Ldt(_) => TypeId::LDT,
+        Value::String(_) => TypeId::STRING,
+        Value::WString(_) => TypeId::WSTRING,
+        Value::Char(_) => TypeId::CHAR,
+        Value::WChar(_) => TypeId::WCHAR,
+        _ => return None,
+    };
+    Some(type_id)
+}
+
 pub fn format_value(value: &Value) -> String {
     match value {
         Value::Bool(value) => {
@@ -148,12 +186,13 @@ pub fn format_value(value: &Value) -> String {
         Value::WChar(value) => char::from_u32((*value).into()).unwrap_or('?').to_string(),
         Value::Array(value) => format!("[{}]", value.elements.len()),
         Value::Struct(value) => format!("{} {{...}}", value.type_name),
-        Value::Enum(value) => format!("{}::{}", value.type_name, value.variant_name),
+        Value::Enum(value) => format!("{}#{}", value.type_name, value.variant_name),
         Value::Reference(Some(_)) => "REF".to_string(),
         Value::Reference(None) => "NULL_REF".to_string(),
         Value::Instance(value) => format!("Instance({})", value.0),
         Value::Null => "NULL".to_string(),
-        _ => format!("{value:?}"),
+        _ => format_time_literal(value, DateTimeProfile::default())
+            .unwrap_or_else(|| format!("{value:?}")),
     }
 }
 
@@ -0,0 +1,151 @@
+//! Per-variable value history over the last scan cycles.
+
+use std::collections::VecDeque;
+
+use indexmap::IndexMap;
+use smol_str::SmolStr;
+
+use crate::value::{Duration, Value};
+
+/// Cycles kept per variable unless `runtime.control.history_depth` says otherwise.
+pub const DEFAULT_HISTORY_DEPTH: usize = 100;
+/// Upper bound for `runtime.control.history_depth`.
+pub const MAX_HISTORY_DEPTH: usize = 10_000;
+
+/// Value of a watched variable at the end of one scan cycle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistorySample {
+    pub cycle: u64,
+    pub time: Duration,
+    pub value: Value,
+}
+
+/// History of one watched variable, oldest sample first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableHistory {
+    pub name: SmolStr,
+    pub samples: Vec<HistorySample>,
+}
+
+#[derive(Debug)]
+pub(super) struct HistoryRing {
+    depth: usize,
+    tracks: IndexMap<SmolStr, VecDeque<HistorySample>>,
+}
+
+impl Default for HistoryRing {
+    fn default() -> Self {
+        Self {
+            depth: DEFAULT_HISTORY_DEPTH,
+            tracks: IndexMap::new(),
+        }
+    }
+}
+
+impl HistoryRing {
+    pub(super) fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub(super) fn set_depth(&mut self, depth: usize) {
+        self.depth = depth.clamp(1, MAX_HISTORY_DEPTH);
+        for samples in self.tracks.values_mut() {
+            while samples.len() > self.depth {
+                samples.pop_front();
+            }
+        }
+    }
+
+    pub(super) fn watch(&mut self, name: &str) {
+        let name = name.trim();
+        if !name.is_empty() && self.key(name).is_none() {
+            self.tracks.insert(SmolStr::new(name), VecDeque::new());
+        }
+    }
+
+    pub(super) fn unwatch(&mut self, name: &str) {
+        if let Some(key) = self.key(name.trim()) {
+            self.tracks.shift_remove(&key);
+        }
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.tracks.clear();
+    }
+
+    pub(super) fn names(&self) -> Vec<SmolStr> {
+        self.tracks.keys().cloned().collect()
+    }
+
+    pub(super) fn record(&mut self, cycle: u64, time: Duration, values: Vec<(SmolStr, Value)>) {
+        for (name, value) in values {
+            let Some(samples) = self.tracks.get_mut(&name) else {
+                continue;
+            };
+            if samples.len() == self.depth {
+                samples.pop_front();
+            }
+            samples.push_back(HistorySample { cycle, time, value });
+        }
+    }
+
+    pub(super) fn history(&self, name: &str, limit: Option<usize>) -> Option<VariableHistory> {
+        let key = self.key(name.trim())?;
+        let samples = self.tracks.get(&key)?;
+        let skip = limit.map_or(0, |limit| samples.len().saturating_sub(limit));
+        Some(VariableHistory {
+            name: key,
+            samples: samples.iter().skip(skip).cloned().collect(),
+        })
+    }
+
+    fn key(&self, name: &str) -> Option<SmolStr> {
+        self.tracks
+            .keys()
+            .find(|key| key.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_keeps_last_depth_samples_per_variable() {
+        let mut ring = HistoryRing::default();
+        ring.set_depth(3);
+        ring.watch("Main.Level");
+        ring.watch("main.level");
+        assert_eq!(ring.names(), vec![SmolStr::new("Main.Level")]);
+
+        for cycle in 0..5u64 {
+            ring.record(
+                cycle,
+                Duration::from_millis(cycle as i64 * 10),
+                vec![
+                    (SmolStr::new("Main.Level"), Value::Int(cycle as i16)),
+                    (SmolStr::new("Other"), Value::Int(0)),
+                ],
+            );
+        }
+        let history = ring.history("MAIN.LEVEL", None).expect("history");
+        let cycles = history
+            .samples
+            .iter()
+            .map(|sample| sample.cycle)
+            .collect::<Vec<_>>();
+        assert_eq!(cycles, vec![2, 3, 4]);
+        assert_eq!(history.samples[2].value, Value::Int(4));
+        assert_eq!(history.samples[2].time, Duration::from_millis(40));
+
+        let last = ring.history("Main.Level", Some(1)).expect("history");
+        assert_eq!(last.samples.len(), 1);
+        assert!(ring.history("Other", None).is_none());
+
+        ring.set_depth(1);
+        assert_eq!(ring.history("Main.Level", None).unwrap().samples.len(), 1);
+        ring.unwatch("main.level");
+        assert!(ring.names().is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 
 #![allow(missing_docs)]
 
+use crate::error::RuntimeError;
 use crate::eval::EvalContext;
 
 use super::SourceLocation;
@@ -20,6 +21,16 @@ pub trait DebugHook {
     ) {
         self.on_statement(location, call_depth);
     }
+
+    /// Called when a statement fails with a runtime fault, before the error
+    /// propagates out of the statement.
+    fn on_fault(
+        &mut self,
+        _ctx: &mut EvalContext<'_>,
+        _location: Option<&SourceLocation>,
+        _error: &RuntimeError,
+    ) {
+    }
 }
 
 /// No-op debug hook.
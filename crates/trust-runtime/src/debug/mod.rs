@@ -3,19 +3,23 @@
 #![allow(missing_docs)]
 
 mod breakpoints;
+mod completion;
 mod control;
 pub mod dap;
+mod history;
 mod hook;
 mod resolve;
 mod trace;
 mod types;
 
+pub use completion::{complete_debug_expression, DebugCompletion};
 pub use control::{ControlAction, ControlOutcome, DebugControl, DebugMode, StepKind};
 pub(crate) use control::{ForcedVarTarget, PendingVarTarget};
 pub use dap::{DebugScope, DebugSource, DebugVariable, DebugVariableHandles, VariableHandle};
+pub use history::{HistorySample, VariableHistory, DEFAULT_HISTORY_DEPTH, MAX_HISTORY_DEPTH};
 pub use hook::{DebugHook, NoopDebugHook};
 pub use resolve::{location_to_line_col, offset_to_line_col, resolve_breakpoint_location};
 pub use types::{
-    DebugBreakpoint, DebugLog, DebugSnapshot, DebugStop, DebugStopReason, HitCondition,
-    LogFragment, RuntimeEvent, SourceLocation,
+    DebugBreakpoint, DebugLog, DebugSnapshot, DebugStop, DebugStopReason, FaultBreakpoints,
+    HitCondition, LogFragment, RuntimeEvent, SourceLocation,
 };
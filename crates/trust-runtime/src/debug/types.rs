@@ -161,9 +161,22 @@ pub enum RuntimeEvent {
     Fault {
         /// Fault message.
         error: String,
+        /// POU and source line of the failing statement, when known.
+        location: Option<crate::error::FaultLocation>,
         /// Time when the fault was recorded.
         time: Duration,
     },
+    /// Shutdown hook finished.
+    Shutdown {
+        /// Shutdown program name.
+        program: SmolStr,
+        /// Shutdown cycles executed.
+        cycles: u32,
+        /// How the sequence ended.
+        outcome: crate::watchdog::ShutdownOutcome,
+        /// Time when the sequence ended.
+        time: Duration,
+    },
 }
 
 /// Stop reason for debugger events.
@@ -177,6 +190,27 @@ pub enum DebugStopReason {
     Pause,
     /// Paused due to stopOnEntry.
     Entry,
+    /// Paused on a runtime fault or watchdog pre-trip (exception breakpoint).
+    Exception,
+}
+
+/// Runtime faults that stop the debugger, set through DAP exception breakpoints.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FaultBreakpoints {
+    /// Stop on every fault, including ones the `restart` fault policy recovers from.
+    pub all_faults: bool,
+    /// Stop on faults that halt the resource.
+    pub unhandled_faults: bool,
+    /// Stop at the first statement that runs after the cycle exceeded the
+    /// watchdog timeout, before the watchdog trips at the end of the cycle.
+    pub watchdog: bool,
+}
+
+impl FaultBreakpoints {
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        !(self.all_faults || self.unhandled_faults || self.watchdog)
+    }
 }
 
 /// Notification emitted when execution stops.
@@ -190,4 +224,6 @@ pub struct DebugStop {
     pub thread_id: Option<u32>,
     /// Breakpoint generation when the stop was emitted.
     pub breakpoint_generation: Option<u64>,
+    /// Fault message for exception stops.
+    pub fault: Option<SmolStr>,
 }
@@ -6,6 +6,7 @@ use std::collections::HashMap;
 use std::net::IpAddr;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
 use indexmap::IndexMap;
 use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
@@ -46,6 +47,14 @@ impl DiscoveryState {
             .map(|guard| guard.values().cloned().collect())
             .unwrap_or_default()
     }
+
+    /// Discovered runtimes other than `own_name` that accept mesh connections.
+    pub fn mesh_peers(&self, own_name: &str) -> Vec<DiscoveryEntry> {
+        self.snapshot()
+            .into_iter()
+            .filter(|entry| entry.mesh_port.is_some() && entry.name != own_name)
+            .collect()
+    }
 }
 
 pub struct DiscoveryHandle {
@@ -108,17 +117,23 @@ pub fn start_discovery(
         .map_err(|err| RuntimeError::ControlError(format!("mdns browse: {err}").into()))?;
     let state_clone = state.clone();
     thread::spawn(move || {
+        // Removal events carry the service fullname, not the advertised id.
+        let mut ids_by_fullname = HashMap::<String, SmolStr>::new();
         for event in receiver {
             match event {
                 ServiceEvent::ServiceResolved(info) => {
                     let entry = info_to_entry(&info);
+                    ids_by_fullname.insert(info.get_fullname().to_string(), entry.id.clone());
                     if let Ok(mut guard) = state_clone.entries.lock() {
                         guard.insert(entry.id.clone(), entry);
                     }
                 }
-                ServiceEvent::ServiceRemoved(id, _) => {
+                ServiceEvent::ServiceRemoved(_, fullname) => {
+                    let Some(id) = ids_by_fullname.remove(&fullname) else {
+                        continue;
+                    };
                     if let Ok(mut guard) = state_clone.entries.lock() {
-                        guard.retain(|_, v| v.name.as_str() != id);
+                        guard.shift_remove(&id);
                     }
                 }
                 _ => {}
@@ -129,6 +144,29 @@ pub fn start_discovery(
     Ok(DiscoveryHandle { daemon, state })
 }
 
+/// Browses mDNS for `duration` without advertising and returns the runtimes that resolved.
+pub fn browse_runtimes(duration: Duration) -> Result<Vec<DiscoveryEntry>, RuntimeError> {
+    let daemon = ServiceDaemon::new()
+        .map_err(|err| RuntimeError::ControlError(format!("mdns start: {err}").into()))?;
+    let receiver = daemon
+        .browse(SERVICE_TYPE)
+        .map_err(|err| RuntimeError::ControlError(format!("mdns browse: {err}").into()))?;
+    let deadline = Instant::now() + duration;
+    let mut entries = IndexMap::new();
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        match receiver.recv_timeout(remaining) {
+            Ok(ServiceEvent::ServiceResolved(info)) => {
+                let entry = info_to_entry(&info);
+                entries.insert(entry.id.clone(), entry);
+            }
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+    let _ = daemon.shutdown();
+    Ok(entries.into_values().collect())
+}
+
 fn info_to_entry(info: &ServiceInfo) -> DiscoveryEntry {
     let props = info.get_properties();
     let id = props
@@ -204,4 +242,30 @@ mod tests {
         assert_eq!(entry.mesh_port, Some(5200));
         assert_eq!(entry.control.as_deref(), Some("unix:///tmp/test.sock"));
     }
+
+    #[test]
+    fn mesh_peers_skip_self_and_runtimes_without_mesh() {
+        let state = DiscoveryState::new();
+        let entry = |name: &str, mesh_port: Option<u16>| DiscoveryEntry {
+            id: SmolStr::new(format!("{name}-1")),
+            name: SmolStr::new(name),
+            addresses: Vec::new(),
+            web_port: None,
+            mesh_port,
+            control: None,
+        };
+        {
+            let mut guard = state.entries.lock().unwrap();
+            for entry in [
+                entry("self", Some(5200)),
+                entry("line-1", Some(5200)),
+                entry("viewer", None),
+            ] {
+                guard.insert(entry.id.clone(), entry);
+            }
+        }
+        let peers = state.mesh_peers("self");
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].name, "line-1");
+    }
 }
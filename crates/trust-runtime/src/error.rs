@@ -47,6 +47,14 @@ pub enum RuntimeError {
     #[error("invalid I/O address '{0}'")]
     InvalidIoAddress(SmolStr),
 
+    /// Conflicting I/O bindings or driver channels.
+    #[error("I/O map conflict: {0}")]
+    IoMapConflict(SmolStr),
+
+    /// `[io.map]` symbols that name no program variable.
+    #[error("io.map symbols not found in the program: {0}")]
+    UnboundIoSymbols(SmolStr),
+
     /// Type mismatch between values.
     #[error("type mismatch")]
     TypeMismatch,
@@ -79,6 +87,22 @@ pub enum RuntimeError {
     #[error("array index {index} out of bounds [{lower}..{upper}]")]
     IndexOutOfBounds { index: i64, lower: i64, upper: i64 },
 
+    /// String position out of bounds.
+    #[error("string position {position} out of bounds [{lower}..{upper}]")]
+    StringIndexOutOfBounds {
+        position: i64,
+        lower: i64,
+        upper: i64,
+    },
+
+    /// Value written to a subrange-typed variable lies outside its range.
+    #[error("value {value} outside subrange [{lower}..{upper}]")]
+    SubrangeViolation { value: i64, lower: i64, upper: i64 },
+
+    /// Integer converted to an enumerated type matches none of its members.
+    #[error("value {value} is not a member of enum '{type_name}'")]
+    InvalidEnumValue { type_name: SmolStr, value: i64 },
+
     /// Null reference dereference.
     #[error("null reference dereference")]
     NullReference,
@@ -160,6 +184,32 @@ pub enum RuntimeError {
     ControlError(SmolStr),
 }
 
+/// Source position of the statement that raised a runtime fault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaultLocation {
+    /// POU executing the statement.
+    pub pou: SmolStr,
+    /// Source file path, when the program was compiled from files.
+    pub file: Option<SmolStr>,
+    /// 1-based line.
+    pub line: u32,
+    /// 1-based column.
+    pub column: u32,
+}
+
+impl std::fmt::Display for FaultLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{} ({file}:{}:{})", self.pou, self.line, self.column),
+            None => write!(
+                f,
+                "{} (line {}, column {})",
+                self.pou, self.line, self.column
+            ),
+        }
+    }
+}
+
 impl From<DateTimeError> for RuntimeError {
     fn from(value: DateTimeError) -> Self {
         Self::DateTimeRange(value)
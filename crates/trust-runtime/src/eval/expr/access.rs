@@ -1,10 +1,12 @@
 use smol_str::SmolStr;
 
 use crate::error::RuntimeError;
+use crate::eval::ops::{fit_integer, integer_kind, OverflowPolicy};
 use crate::eval::EvalContext;
+use crate::task::BoundsPolicy;
 use crate::value::{
-    parse_partial_access, read_partial_access, write_partial_access, ArrayValue,
-    PartialAccessError, StructValue, Value, ValueRef,
+    parse_partial_access, read_partial_access, sync_union_variants, write_partial_access,
+    ArrayValue, PartialAccessError, StructValue, Value, ValueRef,
 };
 
 use super::ast::Expr;
@@ -14,6 +16,12 @@ pub(super) fn write_name(
     name: &SmolStr,
     value: Value,
 ) -> Result<(), RuntimeError> {
+    if let Some(reference) = ctx.storage.get_alias(name.as_ref()).cloned() {
+        if ctx.storage.write_by_ref(reference, value) {
+            return Ok(());
+        }
+        return Err(RuntimeError::NullReference);
+    }
     if ctx.storage.get_local(name.as_ref()).is_some() {
         ctx.storage.set_local(name.clone(), value.clone());
         return Ok(());
@@ -54,7 +62,41 @@ pub(super) fn write_name(
     Ok(())
 }
 
-pub(super) fn read_name(ctx: &EvalContext<'_>, name: &SmolStr) -> Result<Value, RuntimeError> {
+/// Fits an integer `value` to the variable stored at `reference`; see [`fit_integer`].
+pub(super) fn fit_to_reference(
+    ctx: &EvalContext<'_>,
+    reference: &ValueRef,
+    value: Value,
+) -> Result<Value, RuntimeError> {
+    match ctx.storage.read_by_ref(reference.clone()) {
+        Some(slot) => fit_integer(slot, value, ctx.overflow),
+        None => Ok(value),
+    }
+}
+
+/// Fits an integer `value` to the variable `name`; see [`fit_integer`].
+pub(super) fn fit_to_name(
+    ctx: &EvalContext<'_>,
+    name: &SmolStr,
+    value: Value,
+) -> Result<Value, RuntimeError> {
+    if integer_kind(&value).is_none() {
+        return Ok(value);
+    }
+    match read_name(ctx, name) {
+        Ok(slot) => fit_integer(&slot, value, ctx.overflow),
+        Err(_) => Ok(value),
+    }
+}
+
+pub(crate) fn read_name(ctx: &EvalContext<'_>, name: &SmolStr) -> Result<Value, RuntimeError> {
+    if let Some(reference) = ctx.storage.get_alias(name.as_ref()) {
+        return ctx
+            .storage
+            .read_by_ref(reference.clone())
+            .cloned()
+            .ok_or(RuntimeError::NullReference);
+    }
     if let Some(value) = ctx.storage.get_local(name.as_ref()) {
         return Ok(value.clone());
     }
@@ -89,7 +131,31 @@ pub(super) fn read_name(ctx: &EvalContext<'_>, name: &SmolStr) -> Result<Value,
     Err(RuntimeError::UndefinedVariable(name.clone()))
 }
 
+/// Returns true when `name` resolves to a partial-access (`%X0`, `%B1`, ...) binding.
+pub(super) fn is_partial_access_binding(ctx: &EvalContext<'_>, name: &SmolStr) -> bool {
+    if ctx.storage.get_alias(name.as_ref()).is_some()
+        || ctx.storage.get_local(name.as_ref()).is_some()
+    {
+        return false;
+    }
+    if let Some(instance_id) = ctx.current_instance {
+        if ctx
+            .storage
+            .get_instance_var_recursive(instance_id, name.as_ref())
+            .is_some()
+        {
+            return false;
+        }
+    }
+    ctx.access
+        .and_then(|access| access.get(name.as_ref()))
+        .is_some_and(|binding| binding.partial.is_some())
+}
+
 pub(super) fn resolve_reference(ctx: &EvalContext<'_>, name: &SmolStr) -> Option<ValueRef> {
+    if let Some(reference) = ctx.storage.get_alias(name.as_ref()) {
+        return Some(reference.clone());
+    }
     if let Some(value_ref) = ctx.storage.ref_for_local(name.as_ref()) {
         return Some(value_ref);
     }
@@ -109,13 +175,17 @@ pub(super) fn resolve_reference(ctx: &EvalContext<'_>, name: &SmolStr) -> Option
     ctx.storage.ref_for_global(name.as_ref())
 }
 
-pub(super) fn read_indices(target: Value, indices: &[Value]) -> Result<Value, RuntimeError> {
+pub(super) fn read_indices(
+    target: Value,
+    indices: &[Value],
+    bounds: BoundsPolicy,
+) -> Result<Value, RuntimeError> {
     match target {
         Value::Array(ArrayValue {
             elements,
             dimensions,
         }) => {
-            let offset = array_offset(&dimensions, indices)?;
+            let offset = array_offset(&dimensions, indices, bounds)?;
             elements
                 .get(offset)
                 .cloned()
@@ -129,12 +199,14 @@ pub(super) fn write_indices(
     target: Value,
     indices: &[Value],
     value: Value,
+    bounds: BoundsPolicy,
+    overflow: OverflowPolicy,
 ) -> Result<Value, RuntimeError> {
     match target {
         Value::Array(mut array) => {
-            let offset = array_offset(&array.dimensions, indices)?;
+            let offset = array_offset(&array.dimensions, indices, bounds)?;
             if let Some(slot) = array.elements.get_mut(offset) {
-                *slot = value;
+                *slot = fit_integer(slot, value, overflow)?;
                 Ok(Value::Array(array))
             } else {
                 Err(RuntimeError::TypeMismatch)
@@ -178,8 +250,9 @@ pub(super) fn write_field(
     }
     match target {
         Value::Struct(mut struct_value) => {
-            if struct_value.fields.contains_key(field) {
-                struct_value.fields.insert(field.clone(), value);
+            if let Some(slot) = struct_value.fields.get_mut(field) {
+                *slot = fit_integer(slot, value, ctx.overflow)?;
+                sync_union_variants(&mut struct_value, field, ctx.registry);
                 Ok(Value::Struct(struct_value))
             } else {
                 Err(RuntimeError::UndefinedField(field.clone()))
@@ -189,6 +262,7 @@ pub(super) fn write_field(
             let Some(reference) = ctx.storage.ref_for_instance_recursive(id, field.as_ref()) else {
                 return Err(RuntimeError::UndefinedField(field.clone()));
             };
+            let value = fit_to_reference(ctx, &reference, value)?;
             if ctx.storage.write_by_ref(reference, value) {
                 Ok(Value::Instance(id))
             } else {
@@ -213,24 +287,45 @@ pub(super) fn index_to_i64(value: Value) -> Result<i64, RuntimeError> {
     }
 }
 
-pub(super) fn array_offset(
+/// Checks subscripts against the array dimensions. Under [`BoundsPolicy::Clamp`]
+/// an index outside its dimension is moved to the nearest bound.
+pub(super) fn checked_indices(
     dimensions: &[(i64, i64)],
     indices: &[Value],
-) -> Result<usize, RuntimeError> {
+    bounds: BoundsPolicy,
+) -> Result<Vec<i64>, RuntimeError> {
     if dimensions.len() != indices.len() {
         return Err(RuntimeError::TypeMismatch);
     }
+    dimensions
+        .iter()
+        .zip(indices)
+        .map(|(&(lower, upper), index_value)| {
+            let idx = index_to_i64(index_value.clone())?;
+            if (lower..=upper).contains(&idx) {
+                return Ok(idx);
+            }
+            match bounds {
+                BoundsPolicy::Clamp => Ok(idx.clamp(lower, upper)),
+                BoundsPolicy::Fault => Err(RuntimeError::IndexOutOfBounds {
+                    index: idx,
+                    lower,
+                    upper,
+                }),
+            }
+        })
+        .collect()
+}
+
+pub(super) fn array_offset(
+    dimensions: &[(i64, i64)],
+    indices: &[Value],
+    bounds: BoundsPolicy,
+) -> Result<usize, RuntimeError> {
+    let indices = checked_indices(dimensions, indices, bounds)?;
     let mut offset: i128 = 0;
     let mut stride: i128 = 1;
-    for ((lower, upper), index_value) in dimensions.iter().zip(indices).rev() {
-        let idx = index_to_i64(index_value.clone())?;
-        if idx < *lower || idx > *upper {
-            return Err(RuntimeError::IndexOutOfBounds {
-                index: idx,
-                lower: *lower,
-                upper: *upper,
-            });
-        }
+    for ((lower, upper), idx) in dimensions.iter().zip(indices).rev() {
         let len = (*upper - *lower + 1) as i128;
         offset += (idx - *lower) as i128 * stride;
         stride *= len;
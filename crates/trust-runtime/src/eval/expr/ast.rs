@@ -34,6 +34,25 @@ pub enum Expr {
     },
     Ref(LValue),
     Deref(Box<Expr>),
+    /// Value assigned to a subrange-typed variable, checked against `lower..=upper`.
+    Subrange {
+        expr: Box<Expr>,
+        lower: i64,
+        upper: i64,
+    },
+    /// Initializer list `(x := 1.0, y := 2.0)` of a STRUCT, with the type of each named
+    /// field; fields not listed keep their default values.
+    StructInit {
+        type_id: trust_hir::TypeId,
+        fields: Vec<(SmolStr, trust_hir::TypeId, Expr)>,
+    },
+    /// Array initializer `[1, 2, 3(0)]` with repetitions expanded; elements past the
+    /// end of the list keep their default values.
+    ArrayInit {
+        type_id: trust_hir::TypeId,
+        element_type: trust_hir::TypeId,
+        elements: Vec<Expr>,
+    },
 }
 
 /// SIZEOF target.
@@ -552,6 +552,12 @@ mod tests {
             loop_depth: 0,
             pause_requested: false,
             execution_deadline: None,
+            workers: None,
+            bounds: crate::task::BoundsPolicy::Fault,
+            overflow: crate::eval::ops::OverflowPolicy::Wrap,
+            subrange: crate::task::SubrangePolicy::Off,
+            fault_site: None,
+            pou_code: None,
         }
     }
 
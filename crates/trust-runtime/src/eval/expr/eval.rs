@@ -1,10 +1,11 @@
 use smol_str::SmolStr;
 
 use crate::error::RuntimeError;
-use crate::eval::ops::{apply_binary, apply_unary, BinaryOp};
+use crate::eval::ops::{apply_binary_with, apply_unary_with, BinaryOp, OverflowPolicy};
 use crate::eval::EvalContext;
-use crate::stdlib::{conversions, time, StdParams};
-use crate::value::{size_of_type, size_of_value, SizeOfError, Value};
+use crate::stdlib::{conversions, numeric, string, time, StdParams};
+use crate::task::BoundsPolicy;
+use crate::value::{default_value_for_type_id, size_of_type, size_of_value, SizeOfError, Value};
 
 use super::access::{eval_indices, read_field, read_indices, read_name};
 use super::ast::{Expr, SizeOfTarget};
@@ -34,6 +35,37 @@ pub fn eval_expr(ctx: &mut EvalContext<'_>, expr: &Expr) -> Result<Value, Runtim
                 .ok_or(RuntimeError::TypeMismatch)
         }
         Expr::SizeOf(target) => eval_size_of(ctx, target),
+        Expr::Subrange { expr, lower, upper } => {
+            let value = eval_expr(ctx, expr)?;
+            ctx.subrange.apply(value, *lower, *upper)
+        }
+        Expr::StructInit { type_id, fields } => {
+            let mut value = default_value_for_type_id(*type_id, ctx.registry, &ctx.profile)
+                .map_err(|_| RuntimeError::TypeMismatch)?;
+            let Value::Struct(target) = &mut value else {
+                return Err(RuntimeError::TypeMismatch);
+            };
+            for (name, field_type, expr) in fields {
+                let field_value = eval_initializer_value(ctx, expr, *field_type)?;
+                target.fields.insert(name.clone(), field_value);
+            }
+            Ok(value)
+        }
+        Expr::ArrayInit {
+            type_id,
+            element_type,
+            elements,
+        } => {
+            let mut value = default_value_for_type_id(*type_id, ctx.registry, &ctx.profile)
+                .map_err(|_| RuntimeError::TypeMismatch)?;
+            let Value::Array(target) = &mut value else {
+                return Err(RuntimeError::TypeMismatch);
+            };
+            for (slot, expr) in target.elements.iter_mut().zip(elements) {
+                *slot = eval_initializer_value(ctx, expr, *element_type)?;
+            }
+            Ok(value)
+        }
         Expr::Name(name) => read_name(ctx, name),
         Expr::Call { target, args } => {
             if let Some(name) = call_target_name(target) {
@@ -66,15 +98,34 @@ pub fn eval_expr(ctx: &mut EvalContext<'_>, expr: &Expr) -> Result<Value, Runtim
                         } else {
                             eval_positional_args(ctx, args)?
                         };
+                        if ctx.bounds == BoundsPolicy::Clamp {
+                            if let Some(result) = string::call_clamped(&key, &values) {
+                                return result;
+                            }
+                        }
+                        if ctx.overflow != OverflowPolicy::Fault {
+                            if let Some(result) =
+                                numeric::call_with_overflow(&key, &values, ctx.overflow)
+                            {
+                                return result;
+                            }
+                        }
                         return (entry.func)(&values);
                     }
-                    if conversions::is_conversion_name(key.as_str()) {
+                    let enum_conversion =
+                        conversions::is_enum_conversion_name(key.as_str(), ctx.registry);
+                    if enum_conversion || conversions::is_conversion_name(key.as_str()) {
                         let params = StdParams::Fixed(vec![SmolStr::new("IN")]);
                         let values = if has_named {
                             bind_stdlib_named_args(ctx, &params, args)?
                         } else {
                             eval_positional_args(ctx, args)?
                         };
+                        if let Some(result) =
+                            conversions::call_enum_conversion(&key, &values, ctx.registry)
+                        {
+                            return result;
+                        }
                         return stdlib.call(&key, &values);
                     }
                 }
@@ -119,7 +170,7 @@ pub fn eval_expr(ctx: &mut EvalContext<'_>, expr: &Expr) -> Result<Value, Runtim
         }
         Expr::Unary { op, expr } => {
             let value = eval_expr(ctx, expr)?;
-            apply_unary(*op, value)
+            apply_unary_with(*op, value, ctx.overflow)
         }
         Expr::Binary { op, left, right } => {
             if *op == BinaryOp::And {
@@ -128,7 +179,7 @@ pub fn eval_expr(ctx: &mut EvalContext<'_>, expr: &Expr) -> Result<Value, Runtim
                     return Ok(Value::Bool(false));
                 }
                 let right_value = eval_expr(ctx, right)?;
-                return apply_binary(*op, left_value, right_value, &ctx.profile);
+                return apply_binary_with(*op, left_value, right_value, &ctx.profile, ctx.overflow);
             }
             if *op == BinaryOp::Or {
                 let left_value = eval_expr(ctx, left)?;
@@ -136,16 +187,16 @@ pub fn eval_expr(ctx: &mut EvalContext<'_>, expr: &Expr) -> Result<Value, Runtim
                     return Ok(Value::Bool(true));
                 }
                 let right_value = eval_expr(ctx, right)?;
-                return apply_binary(*op, left_value, right_value, &ctx.profile);
+                return apply_binary_with(*op, left_value, right_value, &ctx.profile, ctx.overflow);
             }
             let left_value = eval_expr(ctx, left)?;
             let right_value = eval_expr(ctx, right)?;
-            apply_binary(*op, left_value, right_value, &ctx.profile)
+            apply_binary_with(*op, left_value, right_value, &ctx.profile, ctx.overflow)
         }
         Expr::Index { target, indices } => {
             let target_value = eval_expr(ctx, target)?;
             let index_values = eval_indices(ctx, indices)?;
-            read_indices(target_value, &index_values)
+            read_indices(target_value, &index_values, ctx.bounds)
         }
         Expr::Field { target, field } => {
             let target_value = eval_expr(ctx, target)?;
@@ -170,6 +221,15 @@ pub fn eval_expr(ctx: &mut EvalContext<'_>, expr: &Expr) -> Result<Value, Runtim
     }
 }
 
+fn eval_initializer_value(
+    ctx: &mut EvalContext<'_>,
+    expr: &Expr,
+    type_id: trust_hir::TypeId,
+) -> Result<Value, RuntimeError> {
+    let value = eval_expr(ctx, expr)?;
+    crate::harness::coerce_value_to_type(value, type_id).map_err(|_| RuntimeError::TypeMismatch)
+}
+
 fn eval_size_of(ctx: &mut EvalContext<'_>, target: &SizeOfTarget) -> Result<Value, RuntimeError> {
     let size = match target {
         SizeOfTarget::Type(type_id) => {
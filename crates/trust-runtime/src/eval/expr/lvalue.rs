@@ -2,11 +2,11 @@ use smol_str::SmolStr;
 
 use crate::error::RuntimeError;
 use crate::eval::EvalContext;
-use crate::value::{ArrayValue, RefSegment, StructValue, Value, ValueRef};
+use crate::value::{parse_partial_access, ArrayValue, RefSegment, StructValue, Value, ValueRef};
 
 use super::access::{
-    array_offset, eval_indices, index_to_i64, read_field, read_indices, read_name,
-    resolve_reference, write_field, write_indices,
+    checked_indices, eval_indices, fit_to_name, fit_to_reference, is_partial_access_binding,
+    read_field, read_indices, read_name, resolve_reference, write_field, write_indices,
 };
 use super::ast::LValue;
 
@@ -25,11 +25,7 @@ pub(super) fn resolve_reference_for_lvalue(
                 return Err(RuntimeError::TypeMismatch);
             };
             let index_values = eval_indices(ctx, indices)?;
-            array_offset(dimensions, &index_values)?;
-            let mut index_path = Vec::with_capacity(index_values.len());
-            for value in index_values {
-                index_path.push(index_to_i64(value)?);
-            }
+            let index_path = checked_indices(dimensions, &index_values, ctx.bounds)?;
             let mut value_ref = base;
             value_ref.path.push(RefSegment::Index(index_path));
             Ok(value_ref)
@@ -64,6 +60,25 @@ pub(super) fn resolve_reference_for_lvalue(
     }
 }
 
+/// Resolves a VAR_IN_OUT argument to the storage location it aliases.
+///
+/// Returns `None` for partial bit/byte access, which has no addressable storage and is
+/// passed by copy-in/copy-out instead.
+pub(crate) fn resolve_in_out_reference(
+    ctx: &mut EvalContext<'_>,
+    target: &LValue,
+) -> Result<Option<ValueRef>, RuntimeError> {
+    let partial = match target {
+        LValue::Name(name) => is_partial_access_binding(ctx, name),
+        LValue::Field { field, .. } => parse_partial_access(field.as_str()).is_some(),
+        LValue::Index { .. } | LValue::Deref(_) => false,
+    };
+    if partial {
+        return Ok(None);
+    }
+    resolve_reference_for_lvalue(ctx, target).map(Some)
+}
+
 /// Read a value from an assignment target.
 pub fn read_lvalue(ctx: &mut EvalContext<'_>, target: &LValue) -> Result<Value, RuntimeError> {
     match target {
@@ -71,7 +86,7 @@ pub fn read_lvalue(ctx: &mut EvalContext<'_>, target: &LValue) -> Result<Value,
         LValue::Index { name, indices } => {
             let array_value = read_name(ctx, name)?;
             let index_values = eval_indices(ctx, indices)?;
-            read_indices(array_value, &index_values)
+            read_indices(array_value, &index_values, ctx.bounds)
         }
         LValue::Field { name, field } => {
             let struct_value = read_name(ctx, name)?;
@@ -99,11 +114,15 @@ pub fn write_lvalue(
     value: Value,
 ) -> Result<(), RuntimeError> {
     match target {
-        LValue::Name(name) => write_name(ctx, name, value),
+        LValue::Name(name) => {
+            let value = fit_to_name(ctx, name, value)?;
+            write_name(ctx, name, value)
+        }
         LValue::Index { name, indices } => {
             let array_value = read_name(ctx, name)?;
             let index_values = eval_indices(ctx, indices)?;
-            let updated = write_indices(array_value, &index_values, value)?;
+            let updated =
+                write_indices(array_value, &index_values, value, ctx.bounds, ctx.overflow)?;
             write_name(ctx, name, updated)
         }
         LValue::Field { name, field } => {
@@ -113,6 +132,7 @@ pub fn write_lvalue(
                 else {
                     return Err(RuntimeError::UndefinedField(field.clone()));
                 };
+                let value = fit_to_reference(ctx, &reference, value)?;
                 if ctx.storage.write_by_ref(reference, value) {
                     Ok(())
                 } else {
@@ -127,6 +147,7 @@ pub fn write_lvalue(
             let reference_value = super::eval::eval_expr(ctx, expr)?;
             match reference_value {
                 Value::Reference(Some(reference)) => {
+                    let value = fit_to_reference(ctx, &reference, value)?;
                     if ctx.storage.write_by_ref(reference, value) {
                         Ok(())
                     } else {
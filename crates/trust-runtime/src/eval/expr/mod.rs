@@ -12,4 +12,6 @@ pub use ast::{Expr, LValue, SizeOfTarget};
 pub use eval::eval_expr;
 pub use lvalue::{read_lvalue, write_lvalue, write_name};
 
+pub(crate) use access::read_name;
 pub(crate) use call::read_arg_value;
+pub(crate) use lvalue::resolve_in_out_reference;
@@ -13,11 +13,12 @@ use crate::instance::{create_class_instance, create_fb_instance};
 use crate::io::IoAddress;
 use crate::memory::{InstanceId, VariableStorage};
 use crate::stdlib::{fbs, StandardLibrary};
-use crate::value::{default_value_for_type_id, DateTimeProfile, Duration, Value};
+use crate::value::{default_value_for_type_id, DateTimeProfile, Duration, Value, ValueRef};
 
 pub mod expr;
 pub mod ops;
 pub mod stmt;
+pub mod vm;
 
 /// Evaluation context shared across expression and statement execution.
 pub struct EvalContext<'a> {
@@ -38,6 +39,43 @@ pub struct EvalContext<'a> {
     pub loop_depth: u32,
     pub pause_requested: bool,
     pub execution_deadline: Option<std::time::Instant>,
+    pub workers: Option<&'a crate::workers::WorkerPool>,
+    /// What out-of-bounds array indices and string positions do.
+    pub bounds: crate::task::BoundsPolicy,
+    /// What integer arithmetic does on overflow.
+    pub overflow: crate::eval::ops::OverflowPolicy,
+    /// What writes outside a subrange type's range do.
+    pub subrange: crate::task::SubrangePolicy,
+    /// Innermost statement that failed, recorded as the error propagates.
+    pub fault_site: Option<FaultSite>,
+    /// Compiled POU bodies; `None` runs every call on the interpreter.
+    pub pou_code: Option<&'a vm::PouCode>,
+}
+
+/// Statement that raised a runtime error and the POU frame it ran in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaultSite {
+    /// Owner of the innermost frame; `None` for a program body without a frame.
+    pub pou: Option<SmolStr>,
+    pub location: crate::debug::SourceLocation,
+}
+
+impl EvalContext<'_> {
+    /// Records where an error was raised unless an inner statement already did.
+    pub fn note_fault_site(&mut self, location: Option<&crate::debug::SourceLocation>) {
+        let Some(location) = location else {
+            return;
+        };
+        if self.fault_site.is_none() {
+            self.fault_site = Some(FaultSite {
+                pou: self
+                    .storage
+                    .current_frame()
+                    .map(|frame| frame.owner.clone()),
+                location: *location,
+            });
+        }
+    }
 }
 
 /// Parameter declaration for POUs.
@@ -73,6 +111,18 @@ pub struct FunctionDef {
     pub body: Vec<stmt::Stmt>,
 }
 
+impl FunctionDef {
+    /// The variable the body assigns the result to: the function name without
+    /// its namespace qualification.
+    #[must_use]
+    pub fn result_name(&self) -> SmolStr {
+        match self.name.rsplit_once('.') {
+            Some((_, name)) => SmolStr::new(name),
+            None => self.name.clone(),
+        }
+    }
+}
+
 /// Base type for a function block.
 #[derive(Debug, Clone)]
 pub enum FunctionBlockBase {
@@ -153,6 +203,8 @@ struct PreparedBindings {
     should_execute: bool,
     param_values: Vec<(SmolStr, Value)>,
     out_targets: Vec<OutputBinding>,
+    /// VAR_IN_OUT parameters bound by reference to the caller's variables.
+    aliases: Vec<(SmolStr, ValueRef)>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -182,6 +234,18 @@ pub fn exec_block(
     stmt::exec_block(ctx, stmts)
 }
 
+/// Execute a POU body, from its bytecode when the runtime compiled it.
+pub(crate) fn exec_pou_body(
+    ctx: &mut EvalContext<'_>,
+    compiled: Option<&vm::PouBody>,
+    body: &[stmt::Stmt],
+) -> Result<stmt::StmtResult, RuntimeError> {
+    match compiled {
+        Some(compiled) => compiled.exec(ctx),
+        None => exec_block(ctx, body),
+    }
+}
+
 /// Call a function definition.
 pub fn call_function<'a>(
     ctx: &mut EvalContext<'a>,
@@ -194,6 +258,7 @@ pub fn call_function<'a>(
         should_execute,
         param_values,
         out_targets,
+        aliases,
     } = match prepare_bindings(ctx, &func.params, args, BindingMode::Function) {
         Ok(value) => value,
         Err(err) => {
@@ -205,13 +270,16 @@ pub fn call_function<'a>(
 
     ctx.using = Some(&func.using);
     ctx.storage.push_frame(func.name.clone());
-    ctx.return_name = Some(func.name.clone());
+    ctx.return_name = Some(func.result_name());
     let return_default = default_value_for_type_id(func.return_type, ctx.registry, &ctx.profile)
         .unwrap_or(Value::Null);
-    ctx.storage.set_local(func.name.clone(), return_default);
+    ctx.storage.set_local(func.result_name(), return_default);
     for (name, value) in param_values {
         ctx.storage.set_local(name, value);
     }
+    for (name, reference) in aliases {
+        ctx.storage.bind_alias(name, reference);
+    }
 
     if !should_execute {
         let output_values = collect_outputs(ctx, &out_targets)?;
@@ -235,7 +303,8 @@ pub fn call_function<'a>(
         ctx.using = saved_using;
         return Err(err);
     }
-    let result = match exec_block(ctx, &func.body) {
+    let compiled = ctx.pou_code.and_then(|code| code.function(&func.name));
+    let result = match exec_pou_body(ctx, compiled, &func.body) {
         Ok(result) => result,
         Err(err) => {
             ctx.call_depth = saved_call_depth;
@@ -294,6 +363,7 @@ pub fn call_method(
         should_execute,
         param_values,
         out_targets,
+        aliases,
     } = match prepare_bindings(ctx, &method.params, args, BindingMode::Function) {
         Ok(value) => value,
         Err(err) => {
@@ -315,6 +385,9 @@ pub fn call_method(
     for (name, value) in param_values {
         ctx.storage.set_local(name, value);
     }
+    for (name, reference) in aliases {
+        ctx.storage.bind_alias(name, reference);
+    }
 
     if !should_execute {
         let output_values = collect_outputs(ctx, &out_targets)?;
@@ -340,7 +413,11 @@ pub fn call_method(
         ctx.current_instance = saved_instance;
         return Err(err);
     }
-    let result = match exec_block(ctx, &method.body) {
+    let compiled = ctx.pou_code.and_then(|code| {
+        let instance = ctx.storage.get_instance(instance_id)?;
+        code.method(&instance.type_name, &method.name)
+    });
+    let result = match exec_pou_body(ctx, compiled, &method.body) {
         Ok(result) => result,
         Err(err) => {
             ctx.call_depth = saved_call_depth;
@@ -404,6 +481,7 @@ pub fn call_function_block<'a>(
         should_execute,
         param_values,
         out_targets,
+        aliases,
     } = match prepare_bindings(ctx, &fb.params, args, BindingMode::FunctionBlock) {
         Ok(value) => value,
         Err(err) => {
@@ -419,6 +497,9 @@ pub fn call_function_block<'a>(
     for (name, value) in param_values {
         ctx.storage.set_instance_var(instance_id, name, value);
     }
+    for (name, reference) in aliases {
+        ctx.storage.bind_alias(name, reference);
+    }
 
     if !should_execute {
         let output_values = collect_outputs(ctx, &out_targets)?;
@@ -441,7 +522,8 @@ pub fn call_function_block<'a>(
             ctx.using = saved_using;
             return Err(err);
         }
-        exec_block(ctx, &fb.body)
+        let compiled = ctx.pou_code.and_then(|code| code.function_block(&fb.name));
+        exec_pou_body(ctx, compiled, &fb.body)
     };
     let result = match result {
         Ok(result) => result,
@@ -492,7 +574,8 @@ fn prepare_bindings(
     args: &[CallArg],
     mode: BindingMode,
 ) -> Result<PreparedBindings, RuntimeError> {
-    let positional = args.iter().all(|arg| arg.name.is_none());
+    // `fb();` binds nothing, like a formal call without assignments.
+    let positional = !args.is_empty() && args.iter().all(|arg| arg.name.is_none());
     let mut positional_iter = if positional { Some(args.iter()) } else { None };
     if positional {
         let expected = params.iter().filter(|param| !is_en_eno(param)).count();
@@ -506,6 +589,7 @@ fn prepare_bindings(
 
     let mut param_values = Vec::new();
     let mut out_targets = Vec::new();
+    let mut aliases = Vec::new();
 
     for param in params {
         if param.name.eq_ignore_ascii_case("EN") && matches!(param.direction, ParamDirection::In) {
@@ -534,6 +618,7 @@ fn prepare_bindings(
                     should_execute: false,
                     param_values,
                     out_targets,
+                    aliases,
                 });
             }
             continue;
@@ -588,6 +673,10 @@ fn prepare_bindings(
                     let ArgValue::Target(target) = &arg.value else {
                         return Err(RuntimeError::TypeMismatch);
                     };
+                    if let Some(reference) = expr::resolve_in_out_reference(ctx, target)? {
+                        aliases.push((param.name.clone(), reference));
+                        continue;
+                    }
                     let value = expr::read_lvalue(ctx, target)?;
                     param_values.push((param.name.clone(), value.clone()));
                     out_targets.push(OutputBinding::Param {
@@ -602,6 +691,7 @@ fn prepare_bindings(
         should_execute: true,
         param_values,
         out_targets,
+        aliases,
     })
 }
 
@@ -40,13 +40,57 @@ pub enum BinaryOp {
     Ge,
 }
 
+/// What integer arithmetic does when a result leaves the range of its type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Keep the low bits of the result (two's complement wrap-around).
+    #[default]
+    Wrap,
+    /// Clamp the result to the minimum or maximum of its type.
+    Saturate,
+    /// Raise `RuntimeError::Overflow`.
+    Fault,
+}
+
+impl OverflowPolicy {
+    pub fn parse(text: &str) -> Result<Self, RuntimeError> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "wrap" => Ok(Self::Wrap),
+            "saturate" => Ok(Self::Saturate),
+            "fault" => Ok(Self::Fault),
+            _ => Err(RuntimeError::InvalidConfig(
+                format!("invalid overflow policy '{text}'").into(),
+            )),
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Wrap => "wrap",
+            Self::Saturate => "saturate",
+            Self::Fault => "fault",
+        }
+    }
+}
+
+/// Applies a unary operator, faulting on integer overflow.
 pub fn apply_unary(op: UnaryOp, value: Value) -> Result<Value, RuntimeError> {
+    apply_unary_with(op, value, OverflowPolicy::Fault)
+}
+
+/// Applies a unary operator with the given integer overflow policy.
+pub fn apply_unary_with(
+    op: UnaryOp,
+    value: Value,
+    overflow: OverflowPolicy,
+) -> Result<Value, RuntimeError> {
     match op {
         UnaryOp::Neg => match value {
-            Value::SInt(v) => Ok(Value::SInt(-v)),
-            Value::Int(v) => Ok(Value::Int(-v)),
-            Value::DInt(v) => Ok(Value::DInt(-v)),
-            Value::LInt(v) => Ok(Value::LInt(-v)),
+            Value::SInt(v) => narrow_signed(NumericKind::SInt, -i128::from(v), overflow),
+            Value::Int(v) => narrow_signed(NumericKind::Int, -i128::from(v), overflow),
+            Value::DInt(v) => narrow_signed(NumericKind::DInt, -i128::from(v), overflow),
+            Value::LInt(v) => narrow_signed(NumericKind::LInt, -i128::from(v), overflow),
             Value::Real(v) => Ok(Value::Real(-v)),
             Value::LReal(v) => Ok(Value::LReal(-v)),
             _ => Err(RuntimeError::TypeMismatch),
@@ -63,11 +107,26 @@ pub fn apply_unary(op: UnaryOp, value: Value) -> Result<Value, RuntimeError> {
     }
 }
 
+/// Applies a binary operator, faulting on integer overflow.
 pub fn apply_binary(
     op: BinaryOp,
     left: Value,
     right: Value,
     profile: &DateTimeProfile,
+) -> Result<Value, RuntimeError> {
+    apply_binary_with(op, left, right, profile, OverflowPolicy::Fault)
+}
+
+/// Applies a binary operator with the given integer overflow policy.
+///
+/// The policy covers integer `+`, `-`, `*`, `**` and the `MIN / -1` quotient.
+/// Floating point and time arithmetic always fault on overflow.
+pub fn apply_binary_with(
+    op: BinaryOp,
+    left: Value,
+    right: Value,
+    profile: &DateTimeProfile,
+    overflow: OverflowPolicy,
 ) -> Result<Value, RuntimeError> {
     if let Some(result) = time_arith(op, &left, &right, profile) {
         return result;
@@ -79,12 +138,12 @@ pub fn apply_binary(
         BinaryOp::And | BinaryOp::Or | BinaryOp::Xor => logical_or_bitwise(op, left, right),
         BinaryOp::Eq => numeric_eq(left, right, true),
         BinaryOp::Ne => numeric_eq(left, right, false),
-        BinaryOp::Add => numeric_arith(op, left, right),
-        BinaryOp::Sub => numeric_arith(op, left, right),
-        BinaryOp::Mul => numeric_arith(op, left, right),
-        BinaryOp::Div => numeric_arith(op, left, right),
-        BinaryOp::Mod => numeric_arith(op, left, right),
-        BinaryOp::Pow => numeric_arith(op, left, right),
+        BinaryOp::Add => numeric_arith(op, left, right, overflow),
+        BinaryOp::Sub => numeric_arith(op, left, right, overflow),
+        BinaryOp::Mul => numeric_arith(op, left, right, overflow),
+        BinaryOp::Div => numeric_arith(op, left, right, overflow),
+        BinaryOp::Mod => numeric_arith(op, left, right, overflow),
+        BinaryOp::Pow => numeric_arith(op, left, right, overflow),
         BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
             if let Some(result) = non_numeric_cmp(op, &left, &right) {
                 return result;
@@ -561,7 +620,12 @@ fn numeric_cmp(op: BinaryOp, left: Value, right: Value) -> Result<Value, Runtime
     Ok(Value::Bool(result))
 }
 
-fn numeric_arith(op: BinaryOp, left: Value, right: Value) -> Result<Value, RuntimeError> {
+fn numeric_arith(
+    op: BinaryOp,
+    left: Value,
+    right: Value,
+    overflow: OverflowPolicy,
+) -> Result<Value, RuntimeError> {
     let left_kind = numeric_kind(&left).ok_or(RuntimeError::TypeMismatch)?;
     let right_kind = numeric_kind(&right).ok_or(RuntimeError::TypeMismatch)?;
     let target = wider_numeric(left_kind, right_kind);
@@ -616,18 +680,33 @@ fn numeric_arith(op: BinaryOp, left: Value, right: Value) -> Result<Value, Runti
                         return Err(RuntimeError::TypeMismatch);
                     }
                     let exp = u32::try_from(b).map_err(|_| RuntimeError::Overflow)?;
-                    a.checked_pow(exp).ok_or(RuntimeError::Overflow)?
+                    match a.checked_pow(exp) {
+                        Some(result) => result,
+                        None => match overflow {
+                            OverflowPolicy::Fault => return Err(RuntimeError::Overflow),
+                            OverflowPolicy::Wrap => a.wrapping_pow(exp),
+                            OverflowPolicy::Saturate if a < 0 && exp % 2 == 1 => i128::MIN,
+                            OverflowPolicy::Saturate => i128::MAX,
+                        },
+                    }
                 }
                 _ => return Err(RuntimeError::TypeMismatch),
             };
-            signed_from_i128(target, result)
+            narrow_signed(target, result, overflow)
         }
         NumericKind::USInt | NumericKind::UInt | NumericKind::UDInt | NumericKind::ULInt => {
             let a = u128::from(to_u64(&left)?);
             let b = u128::from(to_u64(&right)?);
             let result = match op {
                 BinaryOp::Add => a + b,
-                BinaryOp::Sub => a.checked_sub(b).ok_or(RuntimeError::Overflow)?,
+                BinaryOp::Sub => match a.checked_sub(b) {
+                    Some(result) => result,
+                    None => match overflow {
+                        OverflowPolicy::Fault => return Err(RuntimeError::Overflow),
+                        OverflowPolicy::Wrap => a.wrapping_sub(b),
+                        OverflowPolicy::Saturate => 0,
+                    },
+                },
                 BinaryOp::Mul => a * b,
                 BinaryOp::Div => {
                     if b == 0 {
@@ -643,11 +722,113 @@ fn numeric_arith(op: BinaryOp, left: Value, right: Value) -> Result<Value, Runti
                 }
                 BinaryOp::Pow => {
                     let exp = u32::try_from(b).map_err(|_| RuntimeError::Overflow)?;
-                    a.checked_pow(exp).ok_or(RuntimeError::Overflow)?
+                    match a.checked_pow(exp) {
+                        Some(result) => result,
+                        None => match overflow {
+                            OverflowPolicy::Fault => return Err(RuntimeError::Overflow),
+                            OverflowPolicy::Wrap => a.wrapping_pow(exp),
+                            OverflowPolicy::Saturate => u128::MAX,
+                        },
+                    }
                 }
                 _ => return Err(RuntimeError::TypeMismatch),
             };
-            unsigned_from_u128(target, result)
+            narrow_unsigned(target, result, overflow)
+        }
+    }
+}
+
+/// Fits a signed result into `target`. Wrapping keeps the low bits, which is exact
+/// because every intermediate result wraps modulo 2^128.
+fn narrow_signed(
+    target: NumericKind,
+    value: i128,
+    overflow: OverflowPolicy,
+) -> Result<Value, RuntimeError> {
+    match overflow {
+        OverflowPolicy::Fault => signed_from_i128(target, value),
+        OverflowPolicy::Wrap => match target {
+            NumericKind::SInt => Ok(Value::SInt(value as i8)),
+            NumericKind::Int => Ok(Value::Int(value as i16)),
+            NumericKind::DInt => Ok(Value::DInt(value as i32)),
+            NumericKind::LInt => Ok(Value::LInt(value as i64)),
+            _ => Err(RuntimeError::TypeMismatch),
+        },
+        OverflowPolicy::Saturate => match target {
+            NumericKind::SInt => Ok(Value::SInt(
+                value.clamp(i8::MIN.into(), i8::MAX.into()) as i8
+            )),
+            NumericKind::Int => Ok(Value::Int(
+                value.clamp(i16::MIN.into(), i16::MAX.into()) as i16
+            )),
+            NumericKind::DInt => Ok(Value::DInt(
+                value.clamp(i32::MIN.into(), i32::MAX.into()) as i32
+            )),
+            NumericKind::LInt => Ok(Value::LInt(
+                value.clamp(i64::MIN.into(), i64::MAX.into()) as i64
+            )),
+            _ => Err(RuntimeError::TypeMismatch),
+        },
+    }
+}
+
+fn narrow_unsigned(
+    target: NumericKind,
+    value: u128,
+    overflow: OverflowPolicy,
+) -> Result<Value, RuntimeError> {
+    match overflow {
+        OverflowPolicy::Fault => unsigned_from_u128(target, value),
+        OverflowPolicy::Wrap => match target {
+            NumericKind::USInt => Ok(Value::USInt(value as u8)),
+            NumericKind::UInt => Ok(Value::UInt(value as u16)),
+            NumericKind::UDInt => Ok(Value::UDInt(value as u32)),
+            NumericKind::ULInt => Ok(Value::ULInt(value as u64)),
+            _ => Err(RuntimeError::TypeMismatch),
+        },
+        OverflowPolicy::Saturate => match target {
+            NumericKind::USInt => Ok(Value::USInt(value.min(u8::MAX.into()) as u8)),
+            NumericKind::UInt => Ok(Value::UInt(value.min(u16::MAX.into()) as u16)),
+            NumericKind::UDInt => Ok(Value::UDInt(value.min(u32::MAX.into()) as u32)),
+            NumericKind::ULInt => Ok(Value::ULInt(value.min(u64::MAX.into()) as u64)),
+            _ => Err(RuntimeError::TypeMismatch),
+        },
+    }
+}
+
+/// Converts an integer `value` stored into a variable that currently holds `slot` to
+/// the variable's integer type, so `c := c + 1` on an INT stores an INT. Out-of-range
+/// values follow `overflow`. Anything other than an integer store is returned as is.
+pub(crate) fn fit_integer(
+    slot: &Value,
+    value: Value,
+    overflow: OverflowPolicy,
+) -> Result<Value, RuntimeError> {
+    let (Some(target), Some(source)) = (integer_kind(slot), integer_kind(&value)) else {
+        return Ok(value);
+    };
+    if target == source {
+        return Ok(value);
+    }
+    let wide = match source {
+        NumericKind::SInt | NumericKind::Int | NumericKind::DInt | NumericKind::LInt => {
+            i128::from(to_i64(&value)?)
         }
+        _ => i128::from(to_u64(&value)?),
+    };
+    match target {
+        NumericKind::SInt | NumericKind::Int | NumericKind::DInt | NumericKind::LInt => {
+            narrow_signed(target, wide, overflow)
+        }
+        // Negative values become huge as u128: they fault or wrap like any other
+        // out-of-range value, but saturate to zero.
+        _ if wide < 0 && overflow == OverflowPolicy::Saturate => {
+            narrow_unsigned(target, 0, overflow)
+        }
+        _ => narrow_unsigned(target, wide as u128, overflow),
     }
 }
+
+pub(crate) fn integer_kind(value: &Value) -> Option<NumericKind> {
+    numeric_kind(value).filter(|kind| !matches!(kind, NumericKind::Real | NumericKind::LReal))
+}
@@ -125,6 +125,21 @@ pub fn exec_stmt(ctx: &mut EvalContext<'_>, stmt: &Stmt) -> Result<StmtResult, R
         hook.on_statement_with_context(ctx, stmt.location(), ctx.call_depth);
         ctx.debug = Some(hook);
     }
+    let result = exec_stmt_kind(ctx, stmt);
+    if result.is_err() {
+        ctx.note_fault_site(stmt.location());
+    }
+    #[cfg(feature = "debug")]
+    if let Err(err) = &result {
+        if let Some(hook) = ctx.debug.take() {
+            hook.on_fault(ctx, stmt.location(), err);
+            ctx.debug = Some(hook);
+        }
+    }
+    result
+}
+
+fn exec_stmt_kind(ctx: &mut EvalContext<'_>, stmt: &Stmt) -> Result<StmtResult, RuntimeError> {
     match stmt {
         Stmt::Assign { target, value, .. } => {
             let value = eval_expr(ctx, value)?;
@@ -320,7 +335,7 @@ pub fn exec_stmt(ctx: &mut EvalContext<'_>, stmt: &Stmt) -> Result<StmtResult, R
     }
 }
 
-fn check_execution_budget(ctx: &EvalContext<'_>) -> Result<(), RuntimeError> {
+pub(crate) fn check_execution_budget(ctx: &EvalContext<'_>) -> Result<(), RuntimeError> {
     if let Some(deadline) = ctx.execution_deadline {
         if std::time::Instant::now() >= deadline {
             return Err(RuntimeError::ExecutionTimeout);
@@ -365,7 +380,7 @@ fn eval_bool(ctx: &mut EvalContext<'_>, expr: &Expr) -> Result<bool, RuntimeErro
     }
 }
 
-fn int_value(value: Value) -> Result<i64, RuntimeError> {
+pub(crate) fn int_value(value: Value) -> Result<i64, RuntimeError> {
     match value {
         Value::SInt(v) => Ok(v as i64),
         Value::Int(v) => Ok(v as i64),
@@ -379,14 +394,14 @@ fn int_value(value: Value) -> Result<i64, RuntimeError> {
     }
 }
 
-fn is_unsigned_int(value: &Value) -> bool {
+pub(crate) fn is_unsigned_int(value: &Value) -> bool {
     matches!(
         value,
         Value::USInt(_) | Value::UInt(_) | Value::UDInt(_) | Value::ULInt(_)
     )
 }
 
-fn coerce_loop_value(template: &Value, value: i64) -> Result<Value, RuntimeError> {
+pub(crate) fn coerce_loop_value(template: &Value, value: i64) -> Result<Value, RuntimeError> {
     match template {
         Value::SInt(_) => i8::try_from(value)
             .map(Value::SInt)
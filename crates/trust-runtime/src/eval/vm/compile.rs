@@ -0,0 +1,388 @@
+//! Lowering from statements to register bytecode.
+
+use smol_str::SmolStr;
+
+use crate::debug::SourceLocation;
+use crate::eval::expr::{Expr, LValue};
+use crate::eval::ops::BinaryOp;
+use crate::eval::stmt::Stmt;
+use crate::value::Value;
+
+use super::{optimize, CaseTable, CompiledBlock, Instr, Reg};
+
+/// Placeholder target patched once the destination is known.
+const UNRESOLVED: u32 = u32::MAX;
+
+/// Compile a statement list into optimized bytecode.
+///
+/// Returns `None` for bodies that use labels or `JMP`; those keep running on
+/// the interpreter, which resolves labels per block.
+#[must_use]
+pub fn compile_block(stmts: &[Stmt]) -> Option<CompiledBlock> {
+    if stmts.iter().any(uses_labels) {
+        return None;
+    }
+    let mut compiler = Compiler::default();
+    compiler.block(stmts);
+    let mut block = compiler.block;
+    optimize::optimize(&mut block);
+    Some(block)
+}
+
+fn uses_labels(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Label { .. } | Stmt::Jmp { .. } => true,
+        Stmt::If {
+            then_block,
+            else_if,
+            else_block,
+            ..
+        } => {
+            then_block.iter().any(uses_labels)
+                || else_if
+                    .iter()
+                    .any(|(_, block)| block.iter().any(uses_labels))
+                || else_block.iter().any(uses_labels)
+        }
+        Stmt::Case {
+            branches,
+            else_block,
+            ..
+        } => {
+            branches
+                .iter()
+                .any(|(_, block)| block.iter().any(uses_labels))
+                || else_block.iter().any(uses_labels)
+        }
+        Stmt::For { body, .. } | Stmt::While { body, .. } | Stmt::Repeat { body, .. } => {
+            body.iter().any(uses_labels)
+        }
+        _ => false,
+    }
+}
+
+/// Pending EXIT/CONTINUE jumps of the innermost loop.
+#[derive(Default)]
+struct LoopJumps {
+    exits: Vec<usize>,
+    continues: Vec<usize>,
+}
+
+#[derive(Default)]
+struct Compiler {
+    block: CompiledBlock,
+    loops: Vec<LoopJumps>,
+    /// Locations of the statements being lowered, innermost last.
+    enclosing: Vec<SourceLocation>,
+}
+
+impl Compiler {
+    fn here(&self) -> u32 {
+        self.block.instrs.len() as u32
+    }
+
+    fn emit(&mut self, instr: Instr) -> usize {
+        self.block.instrs.push(instr);
+        self.block.instrs.len() - 1
+    }
+
+    fn patch(&mut self, at: usize, target: u32) {
+        if let Some(slot) = self.block.instrs[at].target_mut() {
+            *slot = target;
+        }
+    }
+
+    fn register(&mut self) -> Reg {
+        self.block.registers += 1;
+        (self.block.registers - 1) as Reg
+    }
+
+    fn constant(&mut self, value: Value) -> u32 {
+        self.block.consts.push(value);
+        (self.block.consts.len() - 1) as u32
+    }
+
+    fn name(&mut self, name: &SmolStr) -> u32 {
+        if let Some(index) = self.block.names.iter().position(|known| known == name) {
+            return index as u32;
+        }
+        self.block.names.push(name.clone());
+        (self.block.names.len() - 1) as u32
+    }
+
+    fn lvalue(&mut self, target: LValue) -> u32 {
+        self.block.lvalues.push(target);
+        (self.block.lvalues.len() - 1) as u32
+    }
+
+    fn block(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            self.stmt(stmt);
+            // Code after a nested statement (loop steps, ELSIF tests) belongs to
+            // the statement that encloses it.
+            if let Some(outer) = self.enclosing.last() {
+                self.block.locations.push((self.here(), *outer));
+            }
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        let start = self.emit(Instr::CheckBudget);
+        let location = stmt.location().copied();
+        if let Some(location) = location {
+            self.block.locations.push((start as u32, location));
+            self.enclosing.push(location);
+        }
+        self.lower_stmt(stmt);
+        if location.is_some() {
+            self.enclosing.pop();
+        }
+    }
+
+    fn lower_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Assign { target, value, .. } => {
+                let src = self.expr(value);
+                let target = self.lvalue(target.clone());
+                self.emit(Instr::Store { target, src });
+            }
+            Stmt::Expr { expr, .. } => {
+                self.expr(expr);
+            }
+            Stmt::If {
+                condition,
+                then_block,
+                else_if,
+                else_block,
+                ..
+            } => {
+                let mut ends = Vec::new();
+                let branches = std::iter::once((condition, then_block))
+                    .chain(else_if.iter().map(|branch| (&branch.0, &branch.1)));
+                for (condition, block) in branches {
+                    let cond = self.expr(condition);
+                    let skip = self.emit(Instr::JumpIfNot {
+                        cond,
+                        target: UNRESOLVED,
+                    });
+                    self.block(block);
+                    ends.push(self.emit(Instr::Jump { target: UNRESOLVED }));
+                    let next = self.here();
+                    self.patch(skip, next);
+                }
+                self.block(else_block);
+                let end = self.here();
+                for at in ends {
+                    self.patch(at, end);
+                }
+            }
+            Stmt::Case {
+                selector,
+                branches,
+                else_block,
+                ..
+            } => {
+                let selector = self.expr(selector);
+                let table = self.block.cases.len();
+                self.block.cases.push(CaseTable::default());
+                self.emit(Instr::Case {
+                    selector,
+                    table: table as u32,
+                });
+                let mut ends = Vec::new();
+                for (labels, block) in branches {
+                    let start = self.here();
+                    self.block.cases[table].arms.push((labels.clone(), start));
+                    self.block(block);
+                    ends.push(self.emit(Instr::Jump { target: UNRESOLVED }));
+                }
+                self.block.cases[table].default = self.here();
+                self.block(else_block);
+                let end = self.here();
+                for at in ends {
+                    self.patch(at, end);
+                }
+            }
+            Stmt::For {
+                control,
+                start,
+                end,
+                step,
+                body,
+                ..
+            } => {
+                let start = self.expr(start);
+                let end = self.expr(end);
+                let step = self.expr(step);
+                let slot = self.block.for_slots as u32;
+                self.block.for_slots += 1;
+                let control = self.lvalue(LValue::Name(control.clone()));
+                self.emit(Instr::ForInit {
+                    slot,
+                    control,
+                    start,
+                    end,
+                    step,
+                });
+                let head = self.here();
+                self.emit(Instr::CheckBudget);
+                let test = self.emit(Instr::ForTest {
+                    slot,
+                    exit: UNRESOLVED,
+                });
+                let jumps = self.loop_body(body);
+                let next = self.here();
+                self.emit(Instr::LeaveLoop);
+                self.emit(Instr::ForStep {
+                    slot,
+                    control,
+                    head,
+                });
+                self.finish_loop(jumps, next);
+                let done = self.here();
+                self.patch(test, done);
+            }
+            Stmt::While {
+                condition, body, ..
+            } => {
+                let head = self.here();
+                self.emit(Instr::CheckBudget);
+                let cond = self.expr(condition);
+                let test = self.emit(Instr::JumpIfNot {
+                    cond,
+                    target: UNRESOLVED,
+                });
+                let jumps = self.loop_body(body);
+                let next = self.here();
+                self.emit(Instr::LeaveLoop);
+                self.emit(Instr::Jump { target: head });
+                self.finish_loop(jumps, next);
+                let done = self.here();
+                self.patch(test, done);
+            }
+            Stmt::Repeat { body, until, .. } => {
+                let head = self.here();
+                self.emit(Instr::CheckBudget);
+                let jumps = self.loop_body(body);
+                let next = self.here();
+                self.emit(Instr::LeaveLoop);
+                let cond = self.expr(until);
+                self.emit(Instr::JumpIfNot { cond, target: head });
+                let leave = self.emit(Instr::Jump { target: UNRESOLVED });
+                self.finish_loop(jumps, next);
+                let done = self.here();
+                self.patch(leave, done);
+            }
+            Stmt::Exit { .. } | Stmt::Continue { .. } => {
+                let exit = matches!(stmt, Stmt::Exit { .. });
+                if self.loops.is_empty() {
+                    self.emit(Instr::Escape { exit });
+                    return;
+                }
+                let at = self.emit(Instr::Jump { target: UNRESOLVED });
+                if let Some(jumps) = self.loops.last_mut() {
+                    if exit {
+                        jumps.exits.push(at);
+                    } else {
+                        jumps.continues.push(at);
+                    }
+                }
+            }
+            Stmt::Return { expr, .. } => {
+                let src = expr.as_ref().map(|expr| self.expr(expr));
+                self.emit(Instr::Return { src });
+            }
+            Stmt::AssignAttempt { .. } | Stmt::Label { .. } | Stmt::Jmp { .. } => {
+                self.block.stmts.push(stmt.clone());
+                let index = (self.block.stmts.len() - 1) as u32;
+                self.emit(Instr::Exec { stmt: index });
+            }
+        }
+    }
+
+    /// Compiles a loop body bracketed by loop-depth bookkeeping.
+    fn loop_body(&mut self, body: &[Stmt]) -> LoopJumps {
+        self.emit(Instr::EnterLoop);
+        self.loops.push(LoopJumps::default());
+        self.block(body);
+        self.loops.pop().unwrap_or_default()
+    }
+
+    /// Emits the EXIT landing pad and resolves pending loop jumps.
+    fn finish_loop(&mut self, jumps: LoopJumps, next: u32) {
+        let exit = self.here();
+        self.emit(Instr::LeaveLoop);
+        for at in jumps.exits {
+            self.patch(at, exit);
+        }
+        for at in jumps.continues {
+            self.patch(at, next);
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Reg {
+        match expr {
+            Expr::Literal(value) => {
+                let dst = self.register();
+                let index = self.constant(value.clone());
+                self.emit(Instr::Const { dst, index });
+                dst
+            }
+            Expr::Name(name) => {
+                let dst = self.register();
+                let name = self.name(name);
+                self.emit(Instr::Load { dst, name });
+                dst
+            }
+            Expr::Unary { op, expr } => {
+                let src = self.expr(expr);
+                let dst = self.register();
+                self.emit(Instr::Unary { dst, op: *op, src });
+                dst
+            }
+            Expr::Binary {
+                op: op @ (BinaryOp::And | BinaryOp::Or),
+                left,
+                right,
+            } => {
+                // The left register doubles as the result so the short-circuit
+                // path leaves the deciding operand in place.
+                let left = self.expr(left);
+                let skip = self.emit(Instr::BranchIfBool {
+                    cond: left,
+                    value: *op == BinaryOp::Or,
+                    target: UNRESOLVED,
+                });
+                let right = self.expr(right);
+                self.emit(Instr::Binary {
+                    dst: left,
+                    op: *op,
+                    left,
+                    right,
+                });
+                let end = self.here();
+                self.patch(skip, end);
+                left
+            }
+            Expr::Binary { op, left, right } => {
+                let left = self.expr(left);
+                let right = self.expr(right);
+                let dst = self.register();
+                self.emit(Instr::Binary {
+                    dst,
+                    op: *op,
+                    left,
+                    right,
+                });
+                dst
+            }
+            _ => {
+                let dst = self.register();
+                self.block.exprs.push(expr.clone());
+                let index = (self.block.exprs.len() - 1) as u32;
+                self.emit(Instr::Eval { dst, expr: index });
+                dst
+            }
+        }
+    }
+}
@@ -0,0 +1,430 @@
+//! Bytecode dispatch loop.
+
+use crate::error::RuntimeError;
+use crate::eval::expr::{eval_expr, read_lvalue, read_name, write_lvalue, LValue};
+use crate::eval::ops::{apply_binary_with, apply_unary_with, fit_integer, integer_kind, BinaryOp};
+use crate::eval::stmt::{
+    check_execution_budget, coerce_loop_value, exec_stmt, int_value, is_unsigned_int, StmtResult,
+};
+use crate::eval::EvalContext;
+use crate::value::{Value, ValueRef};
+
+use super::{CompiledBlock, Instr};
+
+/// Runtime state of one FOR loop.
+#[derive(Debug, Clone)]
+pub(super) struct ForState {
+    pub(super) current: i64,
+    pub(super) end: i64,
+    pub(super) step: i64,
+    /// Value of the control variable when the loop started; only its type is used.
+    pub(super) template: Value,
+}
+
+impl Default for ForState {
+    fn default() -> Self {
+        Self {
+            current: 0,
+            end: 0,
+            step: 1,
+            template: Value::Null,
+        }
+    }
+}
+
+/// Registers and FOR loops of a block that is being executed.
+pub(super) struct VmState {
+    pub(super) regs: Vec<Value>,
+    pub(super) loops: Vec<ForState>,
+    /// Loop depth of the context when the block was entered.
+    pub(super) base_depth: u32,
+}
+
+impl VmState {
+    pub(super) fn new(block: &CompiledBlock, base_depth: u32) -> Self {
+        Self {
+            regs: vec![Value::Null; block.registers],
+            loops: vec![ForState::default(); block.for_slots],
+            base_depth,
+        }
+    }
+}
+
+/// Storage locations of the plain names a block has looked up.
+///
+/// Resolution cannot change while the block runs: calls pop the frames they
+/// push and storage offsets are stable, so the first lookup of a name is reused
+/// for the rest of the execution instead of hashing the name every time.
+/// Names behind access bindings or only known to retain storage are not cached.
+struct Resolved {
+    names: Vec<Option<ValueRef>>,
+    lvalues: Vec<Option<ValueRef>>,
+}
+
+impl Resolved {
+    fn new(block: &CompiledBlock) -> Self {
+        Self {
+            names: vec![None; block.names.len()],
+            lvalues: vec![None; block.lvalues.len()],
+        }
+    }
+}
+
+/// Location `read_name` and `write_name` would use for `name`, if it is a
+/// plain storage slot.
+fn resolve(ctx: &EvalContext<'_>, name: &str) -> Option<ValueRef> {
+    if let Some(reference) = ctx.storage.get_alias(name) {
+        return Some(reference.clone());
+    }
+    if let Some(reference) = ctx.storage.ref_for_local(name) {
+        return Some(reference);
+    }
+    if let Some(instance_id) = ctx.current_instance {
+        if let Some(reference) = ctx.storage.ref_for_instance_recursive(instance_id, name) {
+            return Some(reference);
+        }
+    }
+    if ctx.access.is_some_and(|access| access.get(name).is_some()) {
+        return None;
+    }
+    ctx.storage.ref_for_global(name)
+}
+
+fn cached(ctx: &EvalContext<'_>, cache: &mut Option<ValueRef>, name: &str) -> Option<ValueRef> {
+    if cache.is_none() {
+        *cache = resolve(ctx, name);
+    }
+    cache.clone()
+}
+
+fn load(
+    ctx: &EvalContext<'_>,
+    block: &CompiledBlock,
+    resolved: &mut Resolved,
+    index: u32,
+) -> Result<Value, RuntimeError> {
+    let name = &block.names[index as usize];
+    match cached(ctx, &mut resolved.names[index as usize], name) {
+        Some(reference) => ctx
+            .storage
+            .read_by_ref(reference)
+            .cloned()
+            .ok_or(RuntimeError::NullReference),
+        None => read_name(ctx, name),
+    }
+}
+
+///
+/// Results and errors match [`crate::eval::stmt::exec_block`] on the source
+/// statements, except that the debug hook is only invoked for fallback
+/// statements.
+pub fn exec_compiled(
+    ctx: &mut EvalContext<'_>,
+    block: &CompiledBlock,
+) -> Result<StmtResult, RuntimeError> {
+    let state = VmState::new(block, ctx.loop_depth);
+    resume(ctx, block, 0, state, |_, _| {})
+}
+
+/// Continues a block at `pc`; `observe` sees the result of every fallback
+/// expression by its index in `CompiledBlock::exprs`.
+pub(super) fn resume(
+    ctx: &mut EvalContext<'_>,
+    block: &CompiledBlock,
+    mut pc: usize,
+    state: VmState,
+    observe: impl FnMut(u32, &Value),
+) -> Result<StmtResult, RuntimeError> {
+    let result = run(ctx, block, &mut pc, state, observe);
+    if result.is_err() {
+        ctx.note_fault_site(block.location_at(pc.saturating_sub(1)));
+    }
+    result
+}
+
+fn run(
+    ctx: &mut EvalContext<'_>,
+    block: &CompiledBlock,
+    pc: &mut usize,
+    state: VmState,
+    mut observe: impl FnMut(u32, &Value),
+) -> Result<StmtResult, RuntimeError> {
+    let VmState {
+        mut regs,
+        mut loops,
+        base_depth,
+    } = state;
+    let mut resolved = Resolved::new(block);
+    let instrs = block.instrs.as_slice();
+
+    while let Some(instr) = instrs.get(*pc) {
+        *pc += 1;
+        match *instr {
+            Instr::Nop => {}
+            Instr::CheckBudget => check_execution_budget(ctx)?,
+            Instr::Const { dst, index } => {
+                regs[dst as usize] = block.consts[index as usize].clone();
+            }
+            Instr::Load { dst, name } => {
+                regs[dst as usize] = load(ctx, block, &mut resolved, name)?;
+            }
+            Instr::Move { dst, src } => {
+                regs[dst as usize] = regs[src as usize].clone();
+            }
+            Instr::Unary { dst, op, src } => {
+                regs[dst as usize] =
+                    apply_unary_with(op, regs[src as usize].clone(), ctx.overflow)?;
+            }
+            Instr::Binary {
+                dst,
+                op,
+                left,
+                right,
+            } => {
+                let (left, right) = (&regs[left as usize], &regs[right as usize]);
+                regs[dst as usize] = match scalar_binary(op, left, right) {
+                    Some(value) => value,
+                    None => apply_binary_with(
+                        op,
+                        left.clone(),
+                        right.clone(),
+                        &ctx.profile,
+                        ctx.overflow,
+                    )?,
+                };
+            }
+            Instr::BranchIfBool {
+                cond,
+                value,
+                target,
+            } => {
+                if matches!(regs[cond as usize], Value::Bool(held) if held == value) {
+                    *pc = target as usize;
+                }
+            }
+            Instr::Eval { dst, expr } => {
+                let value = eval_expr(ctx, &block.exprs[expr as usize])?;
+                observe(expr, &value);
+                regs[dst as usize] = value;
+            }
+            Instr::Store { target, src } => {
+                store_resolved(
+                    ctx,
+                    block,
+                    &mut resolved,
+                    target,
+                    regs[src as usize].clone(),
+                )?;
+            }
+            Instr::Exec { stmt } => match exec_stmt(ctx, &block.stmts[stmt as usize])? {
+                StmtResult::Continue => {}
+                result => {
+                    ctx.loop_depth = base_depth;
+                    return Ok(result);
+                }
+            },
+            Instr::Jump { target } => *pc = target as usize,
+            Instr::JumpIfNot { cond, target } => match regs[cond as usize] {
+                Value::Bool(true) => {}
+                Value::Bool(false) => *pc = target as usize,
+                _ => return Err(RuntimeError::ConditionNotBool),
+            },
+            Instr::Case { selector, table } => {
+                let selector = match regs[selector as usize] {
+                    Value::SInt(v) => v as i64,
+                    Value::Int(v) => v as i64,
+                    Value::DInt(v) => v as i64,
+                    Value::LInt(v) => v,
+                    _ => return Err(RuntimeError::CaseSelectorType),
+                };
+                *pc = block.cases[table as usize].target(selector) as usize;
+            }
+            Instr::ForInit {
+                slot,
+                control: control_index,
+                start,
+                end,
+                step,
+            } => {
+                let start = int_value(regs[start as usize].clone())?;
+                let end = int_value(regs[end as usize].clone())?;
+                let step = int_value(regs[step as usize].clone())?;
+                if step == 0 {
+                    return Err(RuntimeError::ForStepZero);
+                }
+                let template = read_lvalue(ctx, &block.lvalues[control_index as usize])?;
+                if is_unsigned_int(&template) && step < 0 {
+                    return Err(RuntimeError::TypeMismatch);
+                }
+                let value = coerce_loop_value(&template, start)?;
+                store_resolved(ctx, block, &mut resolved, control_index, value)?;
+                loops[slot as usize] = ForState {
+                    current: start,
+                    end,
+                    step,
+                    template,
+                };
+            }
+            Instr::ForTest { slot, exit } => {
+                let state = &loops[slot as usize];
+                if (state.step > 0 && state.current > state.end)
+                    || (state.step < 0 && state.current < state.end)
+                {
+                    *pc = exit as usize;
+                }
+            }
+            Instr::ForStep {
+                slot,
+                control,
+                head,
+            } => {
+                let state = &mut loops[slot as usize];
+                state.current += state.step;
+                let value = coerce_loop_value(&state.template, state.current)?;
+                store_resolved(ctx, block, &mut resolved, control, value)?;
+                *pc = head as usize;
+            }
+            Instr::EnterLoop => ctx.loop_depth += 1,
+            Instr::LeaveLoop => ctx.loop_depth -= 1,
+            Instr::Escape { exit } => {
+                if ctx.loop_depth == 0 {
+                    return Err(RuntimeError::InvalidControlFlow);
+                }
+                return Ok(if exit {
+                    StmtResult::Exit
+                } else {
+                    StmtResult::LoopContinue
+                });
+            }
+            Instr::Return { src } => {
+                ctx.loop_depth = base_depth;
+                let value = src.map(|src| regs[src as usize].clone());
+                return Ok(StmtResult::Return(value));
+            }
+        }
+    }
+    Ok(StmtResult::Continue)
+}
+
+/// [`apply_binary_with`] for two operands of the same scalar kind.
+///
+/// Returns `None` for other operands and whenever the result is not a plain
+/// in-range value (overflow, division by zero, non-finite floats), so the
+/// general path decides the overflow policy and reports the error.
+fn scalar_binary(op: BinaryOp, left: &Value, right: &Value) -> Option<Value> {
+    match (left, right) {
+        (Value::Bool(a), Value::Bool(b)) => Some(Value::Bool(match op {
+            BinaryOp::And => a & b,
+            BinaryOp::Or => a | b,
+            BinaryOp::Xor => a ^ b,
+            BinaryOp::Eq => a == b,
+            BinaryOp::Ne => a != b,
+            _ => return None,
+        })),
+        (Value::SInt(a), Value::SInt(b)) => int_binary(op, (*a).into(), (*b).into(), |v| {
+            i8::try_from(v).ok().map(Value::SInt)
+        }),
+        (Value::Int(a), Value::Int(b)) => int_binary(op, (*a).into(), (*b).into(), |v| {
+            i16::try_from(v).ok().map(Value::Int)
+        }),
+        (Value::DInt(a), Value::DInt(b)) => int_binary(op, (*a).into(), (*b).into(), |v| {
+            i32::try_from(v).ok().map(Value::DInt)
+        }),
+        (Value::LInt(a), Value::LInt(b)) => int_binary(op, *a, *b, |v| Some(Value::LInt(v))),
+        // REAL arithmetic is done in LREAL and rounded, like the general path.
+        (Value::Real(a), Value::Real(b)) => {
+            float_binary(op, (*a).into(), (*b).into(), |v| Value::Real(v as f32))
+        }
+        (Value::LReal(a), Value::LReal(b)) => float_binary(op, *a, *b, Value::LReal),
+        _ => None,
+    }
+}
+
+fn int_binary(
+    op: BinaryOp,
+    a: i64,
+    b: i64,
+    narrow: impl FnOnce(i64) -> Option<Value>,
+) -> Option<Value> {
+    let result = match op {
+        BinaryOp::Add => a.checked_add(b)?,
+        BinaryOp::Sub => a.checked_sub(b)?,
+        BinaryOp::Mul => a.checked_mul(b)?,
+        BinaryOp::Div if b != 0 => a.checked_div(b)?,
+        BinaryOp::Mod if b != 0 => a.checked_rem(b)?,
+        BinaryOp::Eq => return Some(Value::Bool(a == b)),
+        BinaryOp::Ne => return Some(Value::Bool(a != b)),
+        BinaryOp::Lt => return Some(Value::Bool(a < b)),
+        BinaryOp::Le => return Some(Value::Bool(a <= b)),
+        BinaryOp::Gt => return Some(Value::Bool(a > b)),
+        BinaryOp::Ge => return Some(Value::Bool(a >= b)),
+        _ => return None,
+    };
+    narrow(result)
+}
+
+fn float_binary(op: BinaryOp, a: f64, b: f64, wrap: impl FnOnce(f64) -> Value) -> Option<Value> {
+    let result = match op {
+        BinaryOp::Add => a + b,
+        BinaryOp::Sub => a - b,
+        BinaryOp::Mul => a * b,
+        BinaryOp::Div if b != 0.0 => a / b,
+        BinaryOp::Eq => return Some(Value::Bool(a == b)),
+        BinaryOp::Ne => return Some(Value::Bool(a != b)),
+        BinaryOp::Lt => return Some(Value::Bool(a < b)),
+        BinaryOp::Le => return Some(Value::Bool(a <= b)),
+        BinaryOp::Gt => return Some(Value::Bool(a > b)),
+        BinaryOp::Ge => return Some(Value::Bool(a >= b)),
+        _ => return None,
+    };
+    result.is_finite().then(|| wrap(result))
+}
+
+/// [`store`] through the cached location of a plain name.
+fn store_resolved(
+    ctx: &mut EvalContext<'_>,
+    block: &CompiledBlock,
+    resolved: &mut Resolved,
+    index: u32,
+    value: Value,
+) -> Result<(), RuntimeError> {
+    let target = &block.lvalues[index as usize];
+    let LValue::Name(name) = target else {
+        return store(ctx, target, value);
+    };
+    let Some(reference) = cached(ctx, &mut resolved.lvalues[index as usize], name) else {
+        return store(ctx, target, value);
+    };
+    let value = match ctx.storage.read_by_ref(reference.clone()) {
+        Some(slot) if integer_kind(&value).is_some() => fit_integer(slot, value, ctx.overflow)?,
+        _ => value,
+    };
+    if !ctx.storage.write_by_ref(reference.clone(), value) {
+        return Err(RuntimeError::NullReference);
+    }
+    if ctx.return_name.as_ref() == Some(name) {
+        if let Some(value) = ctx.storage.read_by_ref(reference).cloned() {
+            if let Some(frame) = ctx.storage.current_frame_mut() {
+                frame.return_value = Some(value);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn store(
+    ctx: &mut EvalContext<'_>,
+    target: &LValue,
+    value: Value,
+) -> Result<(), RuntimeError> {
+    write_lvalue(ctx, target, value)?;
+    if let Some(return_name) = &ctx.return_name {
+        if target.name() == return_name {
+            let value = read_lvalue(ctx, target)?;
+            if let Some(frame) = ctx.storage.current_frame_mut() {
+                frame.return_value = Some(value);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,1571 @@
+//! Cranelift backend for hot compiled blocks.
+//!
+//! Scalar arithmetic, comparisons, stores to plain names, and structured
+//! control flow are translated to machine code. Operands must be `BOOL`,
+//! signed integers, `REAL`, or `LREAL`. Native code works on a private copy of
+//! the variables it touches. Fallback expressions and statements (calls,
+//! member and subscript access, and the like) call back into the interpreter:
+//! the copy is written back first and reloaded afterwards. Result kinds of
+//! fallback expressions come from one observed VM run before compilation.
+//!
+//! Every other instruction, and every operation the interpreter would turn
+//! into a runtime error, leaves native code. The copy and the registers that
+//! are still live are handed to the bytecode VM, which continues at that
+//! instruction and so reproduces the exact error.
+
+// Executing generated machine code needs function pointer casts, and the
+// interpreter callback receives raw pointers; it is the only unsafe code in
+// the crate.
+#![allow(unsafe_code)]
+
+use std::any::Any;
+use std::ffi::c_void;
+use std::panic::AssertUnwindSafe;
+
+use cranelift_codegen::entity::EntityRef;
+use cranelift_codegen::ir::condcodes::{FloatCC, IntCC};
+use cranelift_codegen::ir::{types, AbiParam, Block, FuncRef, InstBuilder, MemFlags, Type};
+use cranelift_codegen::settings::{self, Configurable};
+use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext, Variable};
+use cranelift_jit::{JITBuilder, JITModule};
+use cranelift_module::{default_libcall_names, Linkage, Module};
+use smol_str::SmolStr;
+
+use crate::error::RuntimeError;
+use crate::eval::expr::{eval_expr, read_name, LValue};
+use crate::eval::ops::{BinaryOp, UnaryOp};
+use crate::eval::stmt::{exec_stmt, CaseLabel, StmtResult};
+use crate::eval::EvalContext;
+use crate::value::Value;
+
+use super::exec::{exec_compiled, resume, store, ForState, VmState};
+use super::{CompiledBlock, Instr, Reg};
+
+/// Native code reached the end of the block.
+const DONE: u32 = 0;
+/// Native code left the block; the VM continues at the pc in the frame.
+const EXIT: u32 = 1;
+/// Callback status telling native code to hand over to the VM.
+const STOP: u32 = 1;
+/// Symbol of [`fallback`] in generated code.
+const FALLBACK: &str = "trust_jit_fallback";
+
+type Entry = extern "C" fn(*mut u64, *mut c_void) -> u32;
+type Callback = extern "C" fn(*mut c_void, *mut u64, u64, u64) -> u32;
+
+/// Scalar kinds the backend can represent in machine registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Bool,
+    SInt,
+    Int,
+    DInt,
+    LInt,
+    Real,
+    LReal,
+}
+
+impl Kind {
+    fn of(value: &Value) -> Option<Self> {
+        Some(match value {
+            Value::Bool(_) => Kind::Bool,
+            Value::SInt(_) => Kind::SInt,
+            Value::Int(_) => Kind::Int,
+            Value::DInt(_) => Kind::DInt,
+            Value::LInt(_) => Kind::LInt,
+            Value::Real(_) => Kind::Real,
+            Value::LReal(_) => Kind::LReal,
+            _ => return None,
+        })
+    }
+
+    fn is_int(self) -> bool {
+        matches!(self, Kind::SInt | Kind::Int | Kind::DInt | Kind::LInt)
+    }
+
+    fn is_float(self) -> bool {
+        matches!(self, Kind::Real | Kind::LReal)
+    }
+
+    fn is_numeric(self) -> bool {
+        self.is_int() || self.is_float()
+    }
+
+    /// Promotion order used by the interpreter's numeric operators.
+    fn wider(self, other: Kind) -> Kind {
+        let rank = |kind: Kind| match kind {
+            Kind::Bool | Kind::SInt => 0,
+            Kind::Int => 1,
+            Kind::DInt => 2,
+            Kind::LInt => 3,
+            Kind::Real => 4,
+            Kind::LReal => 5,
+        };
+        if rank(self) >= rank(other) {
+            self
+        } else {
+            other
+        }
+    }
+
+    fn ty(self) -> Type {
+        match self {
+            Kind::Bool => types::I8,
+            Kind::Real => types::F32,
+            Kind::LReal => types::F64,
+            _ => types::I64,
+        }
+    }
+
+    fn range(self) -> Option<(i64, i64)> {
+        match self {
+            Kind::SInt => Some((i8::MIN.into(), i8::MAX.into())),
+            Kind::Int => Some((i16::MIN.into(), i16::MAX.into())),
+            Kind::DInt => Some((i32::MIN.into(), i32::MAX.into())),
+            _ => None,
+        }
+    }
+
+    fn min(self) -> i64 {
+        self.range().map_or(i64::MIN, |(min, _)| min)
+    }
+
+    /// Slots hold integers and booleans as `i64` and floats as `f64` bits.
+    fn encode(value: &Value) -> u64 {
+        match *value {
+            Value::Bool(value) => u64::from(value),
+            Value::SInt(value) => i64::from(value) as u64,
+            Value::Int(value) => i64::from(value) as u64,
+            Value::DInt(value) => i64::from(value) as u64,
+            Value::LInt(value) => value as u64,
+            Value::Real(value) => f64::from(value).to_bits(),
+            Value::LReal(value) => value.to_bits(),
+            _ => 0,
+        }
+    }
+
+    fn decode(self, bits: u64) -> Value {
+        match self {
+            Kind::Bool => Value::Bool(bits != 0),
+            Kind::SInt => Value::SInt(bits as i64 as i8),
+            Kind::Int => Value::Int(bits as i64 as i16),
+            Kind::DInt => Value::DInt(bits as i64 as i32),
+            Kind::LInt => Value::LInt(bits as i64),
+            Kind::Real => Value::Real(f64::from_bits(bits) as f32),
+            Kind::LReal => Value::LReal(f64::from_bits(bits)),
+        }
+    }
+}
+
+/// Result kinds of fallback expressions seen while a block ran on the VM.
+struct Feedback {
+    exprs: Vec<Observed>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Observed {
+    Never,
+    Kind(Kind),
+    /// Several kinds, or a value the backend cannot hold.
+    Mixed,
+}
+
+impl Feedback {
+    fn new(block: &CompiledBlock) -> Self {
+        Self {
+            exprs: vec![Observed::Never; block.exprs.len()],
+        }
+    }
+
+    fn observe(&mut self, expr: u32, value: &Value) {
+        let seen = &mut self.exprs[expr as usize];
+        *seen = match (*seen, Kind::of(value)) {
+            (Observed::Never, Some(kind)) => Observed::Kind(kind),
+            (Observed::Kind(known), Some(kind)) if known == kind => Observed::Kind(kind),
+            _ => Observed::Mixed,
+        };
+    }
+
+    fn kind(&self, expr: u32) -> Option<Kind> {
+        match self.exprs[expr as usize] {
+            Observed::Kind(kind) => Some(kind),
+            _ => None,
+        }
+    }
+}
+
+/// Variable copied into the native frame.
+#[derive(Debug)]
+struct Slot {
+    target: LValue,
+    kind: Kind,
+    stored: bool,
+}
+
+/// Offsets into the native frame buffer, in 8-byte words.
+///
+/// The buffer holds the slots, every register, the current/end/step triple of
+/// each FOR loop, the loop depth, and the pc to continue at after an exit.
+#[derive(Debug, Clone, Copy)]
+struct Layout {
+    slots: usize,
+    registers: usize,
+    for_slots: usize,
+}
+
+impl Layout {
+    fn register(self, reg: Reg) -> usize {
+        self.slots + reg as usize
+    }
+
+    fn loop_state(self, slot: usize) -> usize {
+        self.slots + self.registers + 3 * slot
+    }
+
+    fn depth(self) -> usize {
+        self.loop_state(self.for_slots)
+    }
+
+    fn pc(self) -> usize {
+        self.depth() + 1
+    }
+
+    fn len(self) -> usize {
+        self.pc() + 1
+    }
+}
+
+/// Native code for one compiled block.
+pub struct NativeBlock {
+    module: Option<JITModule>,
+    entry: Entry,
+    slots: Vec<Slot>,
+    registers: Vec<Option<Kind>>,
+    /// Kind of the control variable of each FOR loop started natively.
+    loops: Vec<Option<Kind>>,
+    layout: Layout,
+}
+
+// SAFETY: the module owns finalized, read-only code that is never patched
+// after `compile_native` returns, and the entry point only touches the frame
+// buffer and host state passed by the caller.
+unsafe impl Send for NativeBlock {}
+// SAFETY: see `Send`; running the code does not mutate the module.
+unsafe impl Sync for NativeBlock {}
+
+impl std::fmt::Debug for NativeBlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NativeBlock")
+            .field("slots", &self.slots)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for NativeBlock {
+    fn drop(&mut self) {
+        if let Some(module) = self.module.take() {
+            // SAFETY: `entry` is owned by this block and cannot be called
+            // once the block is dropped.
+            unsafe { module.free_memory() };
+        }
+    }
+}
+
+impl NativeBlock {
+    /// Number of variables copied in and out of the native frame.
+    #[must_use]
+    pub fn slot_count(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Runs `block`, the block this code was compiled from, natively.
+    ///
+    /// The VM takes over where native code stops, and runs the whole block
+    /// when a variable changed type since compilation.
+    pub fn run(
+        &self,
+        ctx: &mut EvalContext<'_>,
+        block: &CompiledBlock,
+    ) -> Result<StmtResult, RuntimeError> {
+        let mut frame = vec![0; self.layout.len()];
+        for (bits, slot) in frame.iter_mut().zip(&self.slots) {
+            match read_name(ctx, slot.target.name()) {
+                Ok(value) if Kind::of(&value) == Some(slot.kind) => *bits = Kind::encode(&value),
+                _ => return exec_compiled(ctx, block),
+            }
+        }
+        let base_depth = ctx.loop_depth;
+        let mut host = Host {
+            synced: frame[..self.slots.len()].to_vec(),
+            ctx,
+            native: self,
+            block,
+            base_depth,
+            pending: None,
+            stop: None,
+            panic: None,
+        };
+        let status = (self.entry)(
+            frame.as_mut_ptr(),
+            std::ptr::from_mut(&mut host).cast::<c_void>(),
+        );
+        let Host {
+            ctx,
+            mut synced,
+            pending,
+            stop,
+            panic,
+            ..
+        } = host;
+        if let Some(payload) = panic {
+            std::panic::resume_unwind(payload);
+        }
+        let pending = match stop {
+            Some(Stop::Error(err)) => return Err(err),
+            Some(Stop::Finished(result)) => {
+                ctx.loop_depth = base_depth;
+                return Ok(result);
+            }
+            // The callback already left the variables in the context.
+            Some(Stop::Resume) => pending,
+            None => {
+                self.write_back(ctx, &frame, &mut synced)?;
+                if status == DONE {
+                    return Ok(StmtResult::Continue);
+                }
+                None
+            }
+        };
+        let mut state = self.vm_state(&frame, base_depth);
+        if let Some((dst, value)) = pending {
+            state.regs[dst as usize] = value;
+        }
+        ctx.loop_depth = base_depth + frame[self.layout.depth()] as u32;
+        resume(
+            ctx,
+            block,
+            frame[self.layout.pc()] as usize,
+            state,
+            |_, _| {},
+        )
+    }
+
+    /// Stores the slots native code changed since the last sync.
+    fn write_back(
+        &self,
+        ctx: &mut EvalContext<'_>,
+        frame: &[u64],
+        synced: &mut [u64],
+    ) -> Result<(), RuntimeError> {
+        for (index, slot) in self.slots.iter().enumerate() {
+            if slot.stored && frame[index] != synced[index] {
+                store(ctx, &slot.target, slot.kind.decode(frame[index]))?;
+                synced[index] = frame[index];
+            }
+        }
+        Ok(())
+    }
+
+    /// Registers and FOR loops as native code left them.
+    fn vm_state(&self, frame: &[u64], base_depth: u32) -> VmState {
+        let regs = self
+            .registers
+            .iter()
+            .enumerate()
+            .map(|(reg, kind)| {
+                kind.map_or(Value::Null, |kind| {
+                    kind.decode(frame[self.layout.register(reg as Reg)])
+                })
+            })
+            .collect();
+        let loops = self
+            .loops
+            .iter()
+            .enumerate()
+            .map(|(slot, kind)| match kind {
+                Some(kind) => {
+                    let offset = self.layout.loop_state(slot);
+                    ForState {
+                        current: frame[offset] as i64,
+                        end: frame[offset + 1] as i64,
+                        step: frame[offset + 2] as i64,
+                        template: kind.decode(0),
+                    }
+                }
+                None => ForState::default(),
+            })
+            .collect();
+        VmState {
+            regs,
+            loops,
+            base_depth,
+        }
+    }
+}
+
+/// Why a callback asked native code to stop.
+enum Stop {
+    Error(RuntimeError),
+    /// A fallback statement ended the block (RETURN, EXIT, CONTINUE).
+    Finished(StmtResult),
+    /// A result or a variable no longer fits the compiled kinds; the VM
+    /// continues after the fallback instruction.
+    Resume,
+}
+
+/// State shared with [`fallback`] while native code runs.
+struct Host<'h, 'a> {
+    ctx: &'h mut EvalContext<'a>,
+    native: &'h NativeBlock,
+    block: &'h CompiledBlock,
+    base_depth: u32,
+    /// Slot values as of the last write-back or reload.
+    synced: Vec<u64>,
+    /// Result of the last fallback expression or load.
+    pending: Option<(Reg, Value)>,
+    stop: Option<Stop>,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl Host<'_, '_> {
+    /// Runs the fallback instruction at `pc` on the interpreter.
+    fn fallback(&mut self, frame: &mut [u64], pc: usize, depth: u32) -> Option<Stop> {
+        let (native, block) = (self.native, self.block);
+        self.ctx.loop_depth = self.base_depth + depth;
+        self.pending = None;
+        if let Err(err) = native.write_back(self.ctx, frame, &mut self.synced) {
+            return Some(Stop::Error(err));
+        }
+        let stop = match block.instrs[pc] {
+            Instr::Eval { dst, expr } => {
+                let result = eval_expr(self.ctx, &block.exprs[expr as usize]);
+                self.define(frame, dst, result)
+            }
+            Instr::Load { dst, name } => {
+                let result = read_name(self.ctx, &block.names[name as usize]);
+                self.define(frame, dst, result)
+            }
+            Instr::Store { target, src } => {
+                let kind = native.registers[src as usize].expect("planned store source");
+                let value = kind.decode(frame[native.layout.register(src)]);
+                store(self.ctx, &block.lvalues[target as usize], value)
+                    .err()
+                    .map(Stop::Error)
+            }
+            Instr::Exec { stmt } => match exec_stmt(self.ctx, &block.stmts[stmt as usize]) {
+                Ok(StmtResult::Continue) => None,
+                Ok(result) => Some(Stop::Finished(result)),
+                Err(err) => Some(Stop::Error(err)),
+            },
+            _ => unreachable!("only fallback instructions call back"),
+        };
+        if let Some(stop) = stop {
+            if matches!(stop, Stop::Error(_)) {
+                self.ctx.note_fault_site(block.location_at(pc));
+            }
+            return Some(stop);
+        }
+        for (index, slot) in native.slots.iter().enumerate() {
+            match read_name(self.ctx, slot.target.name()) {
+                Ok(value) if Kind::of(&value) == Some(slot.kind) => {
+                    frame[index] = Kind::encode(&value);
+                    self.synced[index] = frame[index];
+                }
+                _ => return Some(Stop::Resume),
+            }
+        }
+        None
+    }
+
+    fn define(
+        &mut self,
+        frame: &mut [u64],
+        dst: Reg,
+        result: Result<Value, RuntimeError>,
+    ) -> Option<Stop> {
+        let value = match result {
+            Ok(value) => value,
+            Err(err) => return Some(Stop::Error(err)),
+        };
+        let fits = Kind::of(&value) == self.native.registers[dst as usize];
+        if fits {
+            frame[self.native.layout.register(dst)] = Kind::encode(&value);
+        }
+        self.pending = Some((dst, value));
+        (!fits).then_some(Stop::Resume)
+    }
+}
+
+/// Interpreter entry point called from native code for fallback instructions.
+extern "C" fn fallback(host: *mut c_void, frame: *mut u64, pc: u64, depth: u64) -> u32 {
+    // SAFETY: native code passes on the host state and frame buffer it got
+    // from `NativeBlock::run`, which outlive the call and are not otherwise
+    // accessed while it runs.
+    let host = unsafe { &mut *host.cast::<Host<'_, '_>>() };
+    // SAFETY: as above; the buffer was allocated with `layout.len()` words.
+    let frame = unsafe { std::slice::from_raw_parts_mut(frame, host.native.layout.len()) };
+    // Unwinding through generated code is not possible; the panic is resumed
+    // once native code has returned.
+    let stop = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        host.fallback(frame, pc as usize, depth as u32)
+    }));
+    match stop {
+        Ok(None) => 0,
+        Ok(Some(stop)) => {
+            host.stop = Some(stop);
+            STOP
+        }
+        Err(payload) => {
+            host.stop = Some(Stop::Resume);
+            host.panic = Some(payload);
+            STOP
+        }
+    }
+}
+
+/// Runs `block` on the VM, then translates it to native code using the result
+/// kinds of fallback expressions seen during that run.
+///
+/// Variable types are taken from the values visible through `ctx`, so the
+/// context must still be set up the way the block runs (instance and frame).
+/// There is no native code when almost nothing in the block would run natively
+/// or the host ISA is not available.
+pub fn exec_and_compile(
+    ctx: &mut EvalContext<'_>,
+    block: &CompiledBlock,
+) -> (Result<StmtResult, RuntimeError>, Option<NativeBlock>) {
+    let mut feedback = Feedback::new(block);
+    let state = VmState::new(block, ctx.loop_depth);
+    let result = resume(ctx, block, 0, state, |expr, value| {
+        feedback.observe(expr, value);
+    });
+    let native = compile_native(block, ctx, &feedback);
+    (result, native)
+}
+
+fn compile_native(
+    block: &CompiledBlock,
+    ctx: &EvalContext<'_>,
+    feedback: &Feedback,
+) -> Option<NativeBlock> {
+    let plan = Plan::build(block, ctx, feedback)?;
+    let mut flags = settings::builder();
+    flags.set("opt_level", "speed").ok()?;
+    let isa = cranelift_native::builder()
+        .ok()?
+        .finish(settings::Flags::new(flags))
+        .ok()?;
+    let mut jit_builder = JITBuilder::with_isa(isa, default_libcall_names());
+    jit_builder.symbol(FALLBACK, fallback as Callback as *const u8);
+    let mut module = JITModule::new(jit_builder);
+    let pointer = module.target_config().pointer_type();
+
+    let mut callback = module.make_signature();
+    callback.params.extend([
+        AbiParam::new(pointer),
+        AbiParam::new(pointer),
+        AbiParam::new(types::I64),
+        AbiParam::new(types::I64),
+    ]);
+    callback.returns.push(AbiParam::new(types::I32));
+    let callback = module
+        .declare_function(FALLBACK, Linkage::Import, &callback)
+        .ok()?;
+
+    let mut context = module.make_context();
+    context
+        .func
+        .signature
+        .params
+        .extend([AbiParam::new(pointer), AbiParam::new(pointer)]);
+    context
+        .func
+        .signature
+        .returns
+        .push(AbiParam::new(types::I32));
+    let callback = module.declare_func_in_func(callback, &mut context.func);
+    let mut builder_context = FunctionBuilderContext::new();
+    {
+        let builder = FunctionBuilder::new(&mut context.func, &mut builder_context);
+        Translator::new(block, &plan, builder, callback).translate();
+    }
+    let id = module
+        .declare_function("block", Linkage::Export, &context.func.signature)
+        .ok()?;
+    module.define_function(id, &mut context).ok()?;
+    module.clear_context(&mut context);
+    module.finalize_definitions().ok()?;
+    let code = module.get_finalized_function(id);
+    // SAFETY: the function was declared with the `Entry` signature using the
+    // host's default calling convention, which is the C ABI.
+    let entry = unsafe { std::mem::transmute::<*const u8, Entry>(code) };
+    Some(NativeBlock {
+        module: Some(module),
+        entry,
+        layout: plan.layout(block),
+        slots: plan.slots,
+        registers: plan.registers,
+        loops: plan.loops,
+    })
+}
+
+/// How an instruction is translated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Native,
+    /// Runs on the interpreter through [`fallback`].
+    Call,
+    /// Hands over to the VM at this instruction.
+    Exit,
+}
+
+/// Type and slot assignment for a block.
+struct Plan {
+    slots: Vec<Slot>,
+    /// Slot for each entry of `CompiledBlock::names`.
+    names: Vec<Option<usize>>,
+    /// Slot for each entry of `CompiledBlock::lvalues`.
+    lvalues: Vec<Option<usize>>,
+    registers: Vec<Option<Kind>>,
+    loops: Vec<Option<Kind>>,
+    modes: Vec<Mode>,
+    /// Registers the VM may read after native code stops.
+    spilled: Vec<bool>,
+}
+
+impl Plan {
+    fn build(block: &CompiledBlock, ctx: &EvalContext<'_>, feedback: &Feedback) -> Option<Self> {
+        let mut plan = Plan {
+            slots: Vec::new(),
+            names: vec![None; block.names.len()],
+            lvalues: vec![None; block.lvalues.len()],
+            registers: vec![None; block.registers],
+            loops: vec![None; block.for_slots],
+            modes: Vec::with_capacity(block.instrs.len()),
+            spilled: Vec::new(),
+        };
+        for instr in &block.instrs {
+            let mode = plan.mode(block, ctx, feedback, *instr);
+            plan.modes.push(mode.unwrap_or(Mode::Exit));
+        }
+        // Leaving native code right away, or only calling back, costs more
+        // than running the block on the VM.
+        let computes = block.instrs.iter().zip(&plan.modes).any(|(instr, mode)| {
+            *mode == Mode::Native
+                && matches!(
+                    instr,
+                    Instr::Unary { .. } | Instr::Binary { .. } | Instr::ForInit { .. }
+                )
+        });
+        if matches!(plan.modes.first(), None | Some(Mode::Exit)) || !computes {
+            return None;
+        }
+        plan.spilled = plan.live_at_exits(block);
+        Some(plan)
+    }
+
+    fn mode(
+        &mut self,
+        block: &CompiledBlock,
+        ctx: &EvalContext<'_>,
+        feedback: &Feedback,
+        instr: Instr,
+    ) -> Option<Mode> {
+        match instr {
+            Instr::Nop
+            | Instr::CheckBudget
+            | Instr::Jump { .. }
+            | Instr::EnterLoop
+            | Instr::LeaveLoop
+            | Instr::ForTest { .. } => {}
+            Instr::Const { dst, index } => {
+                let kind = Kind::of(&block.consts[index as usize])?;
+                self.define(dst, kind)?;
+            }
+            Instr::Load { dst, name } => {
+                let name_ref = &block.names[name as usize];
+                let Some(slot) = self.slot(ctx, name_ref) else {
+                    // Aliases and access bindings are read by the interpreter.
+                    let kind = Kind::of(&read_name(ctx, name_ref).ok()?)?;
+                    self.define(dst, kind)?;
+                    return Some(Mode::Call);
+                };
+                self.names[name as usize] = Some(slot);
+                self.define(dst, self.slots[slot].kind)?;
+            }
+            Instr::Move { dst, src } => {
+                let kind = self.kind(src)?;
+                self.define(dst, kind)?;
+            }
+            Instr::Unary { dst, op, src } => {
+                let kind = self.kind(src)?;
+                let supported = match op {
+                    UnaryOp::Neg | UnaryOp::Pos => kind.is_numeric(),
+                    UnaryOp::Not => kind == Kind::Bool,
+                };
+                if !supported {
+                    return None;
+                }
+                self.define(dst, kind)?;
+            }
+            Instr::Binary {
+                dst,
+                op,
+                left,
+                right,
+            } => {
+                let kind = binary_kind(op, self.kind(left)?, self.kind(right)?)?;
+                self.define(dst, kind)?;
+            }
+            Instr::BranchIfBool { cond, .. } | Instr::JumpIfNot { cond, .. } => {
+                (self.kind(cond)? == Kind::Bool).then_some(())?;
+            }
+            Instr::Eval { dst, expr } => {
+                self.define(dst, feedback.kind(expr)?)?;
+                return Some(Mode::Call);
+            }
+            Instr::Store { target, src } => {
+                let kind = self.kind(src)?;
+                let slot = match &block.lvalues[target as usize] {
+                    LValue::Name(name) => self.slot(ctx, name),
+                    _ => None,
+                };
+                match slot {
+                    Some(slot) if self.slots[slot].kind == kind => {
+                        self.slots[slot].stored = true;
+                        self.lvalues[target as usize] = Some(slot);
+                    }
+                    // Element and field stores, and stores that convert.
+                    _ => return Some(Mode::Call),
+                }
+            }
+            Instr::Exec { .. } => return Some(Mode::Call),
+            Instr::Case { selector, .. } => {
+                self.kind(selector)?.is_int().then_some(())?;
+            }
+            Instr::ForInit {
+                slot,
+                control,
+                start,
+                end,
+                step,
+            } => {
+                for reg in [start, end, step] {
+                    self.kind(reg)?.is_int().then_some(())?;
+                }
+                let control = self.lvalue(ctx, block, control)?;
+                let kind = self.slots[control].kind;
+                kind.is_int().then_some(())?;
+                // Sequential loops may share a slot.
+                match self.loops[slot as usize] {
+                    Some(known) if known != kind => return None,
+                    _ => self.loops[slot as usize] = Some(kind),
+                }
+            }
+            Instr::ForStep { control, .. } => {
+                self.lvalue(ctx, block, control)?;
+            }
+            Instr::Escape { .. } | Instr::Return { .. } => return None,
+        }
+        Some(Mode::Native)
+    }
+
+    fn kind(&self, reg: u32) -> Option<Kind> {
+        self.registers[reg as usize]
+    }
+
+    /// Registers keep one kind for the whole block.
+    fn define(&mut self, reg: u32, kind: Kind) -> Option<()> {
+        match self.registers[reg as usize] {
+            Some(known) if known != kind => None,
+            _ => {
+                self.registers[reg as usize] = Some(kind);
+                Some(())
+            }
+        }
+    }
+
+    fn slot(&mut self, ctx: &EvalContext<'_>, name: &SmolStr) -> Option<usize> {
+        if let Some(index) = self
+            .slots
+            .iter()
+            .position(|slot| slot.target.name() == name)
+        {
+            return Some(index);
+        }
+        // VAR_IN_OUT parameters and access bindings may alias other slots.
+        if ctx.storage.get_alias(name).is_some()
+            || ctx.access.is_some_and(|access| access.get(name).is_some())
+        {
+            return None;
+        }
+        let kind = Kind::of(&read_name(ctx, name).ok()?)?;
+        self.slots.push(Slot {
+            target: LValue::Name(name.clone()),
+            kind,
+            stored: false,
+        });
+        Some(self.slots.len() - 1)
+    }
+
+    fn lvalue(
+        &mut self,
+        ctx: &EvalContext<'_>,
+        block: &CompiledBlock,
+        index: u32,
+    ) -> Option<usize> {
+        let LValue::Name(name) = &block.lvalues[index as usize] else {
+            return None;
+        };
+        let slot = self.slot(ctx, name)?;
+        self.slots[slot].stored = true;
+        self.lvalues[index as usize] = Some(slot);
+        Some(slot)
+    }
+
+    fn layout(&self, block: &CompiledBlock) -> Layout {
+        Layout {
+            slots: self.slots.len(),
+            registers: block.registers,
+            for_slots: block.for_slots,
+        }
+    }
+
+    /// Whether native code can leave the block at `pc`, before or after it.
+    fn exits_at(&self, block: &CompiledBlock, pc: usize) -> bool {
+        // Callbacks stop before the next instruction.
+        let after_call = pc > 0 && self.modes[pc - 1] == Mode::Call;
+        after_call
+            || match self.modes.get(pc) {
+                Some(Mode::Exit) => true,
+                Some(Mode::Native) => matches!(
+                    block.instrs[pc],
+                    Instr::Unary {
+                        op: UnaryOp::Neg,
+                        ..
+                    } | Instr::Binary { .. }
+                        | Instr::ForInit { .. }
+                        | Instr::ForStep { .. }
+                ),
+                _ => false,
+            }
+    }
+
+    /// Registers live on entry to any instruction native code can exit at.
+    fn live_at_exits(&self, block: &CompiledBlock) -> Vec<bool> {
+        let words = block.registers.div_ceil(64);
+        let len = block.instrs.len();
+        let mut live = vec![0u64; (len + 1) * words];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for pc in (0..len).rev() {
+                let instr = block.instrs[pc];
+                let mut set = vec![0u64; words];
+                let mut join = |target: u32| {
+                    let start = target as usize * words;
+                    for (word, succ) in set.iter_mut().zip(&live[start..start + words]) {
+                        *word |= succ;
+                    }
+                };
+                if instr.falls_through() {
+                    join(pc as u32 + 1);
+                }
+                block.for_each_target(pc, &mut join);
+                if let Some(dst) = instr.written() {
+                    set[dst as usize / 64] &= !(1 << (dst % 64));
+                }
+                instr.for_each_read(|src| set[src as usize / 64] |= 1 << (src % 64));
+                let current = &mut live[pc * words..(pc + 1) * words];
+                if current != set.as_slice() {
+                    current.copy_from_slice(&set);
+                    changed = true;
+                }
+            }
+        }
+        let mut spilled = vec![false; block.registers];
+        for pc in 0..=len {
+            if !self.exits_at(block, pc) {
+                continue;
+            }
+            for (reg, spill) in spilled.iter_mut().enumerate() {
+                *spill |= live[pc * words + reg / 64] & (1 << (reg % 64)) != 0;
+            }
+        }
+        spilled
+    }
+}
+
+/// Result kind of a binary operator, or `None` when unsupported.
+fn binary_kind(op: BinaryOp, left: Kind, right: Kind) -> Option<Kind> {
+    let numeric = left.is_numeric() && right.is_numeric();
+    let bools = left == Kind::Bool && right == Kind::Bool;
+    match op {
+        BinaryOp::And | BinaryOp::Or | BinaryOp::Xor if bools => Some(Kind::Bool),
+        BinaryOp::Eq | BinaryOp::Ne if bools || numeric => Some(Kind::Bool),
+        BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge if numeric => Some(Kind::Bool),
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div if numeric => {
+            Some(left.wider(right))
+        }
+        BinaryOp::Mod if left.is_int() && right.is_int() => Some(left.wider(right)),
+        _ => None,
+    }
+}
+
+/// Cranelift variables for registers, slots, FOR loop state, and loop depth.
+struct Translator<'a> {
+    block: &'a CompiledBlock,
+    plan: &'a Plan,
+    layout: Layout,
+    builder: FunctionBuilder<'a>,
+    frame: cranelift_codegen::ir::Value,
+    host: cranelift_codegen::ir::Value,
+    callback: FuncRef,
+    blocks: Vec<Option<Block>>,
+    /// Spills the state for the VM; takes the pc to continue at.
+    exit: Block,
+    /// Instruction being translated.
+    pc: usize,
+}
+
+impl<'a> Translator<'a> {
+    fn new(
+        block: &'a CompiledBlock,
+        plan: &'a Plan,
+        mut builder: FunctionBuilder<'a>,
+        callback: FuncRef,
+    ) -> Self {
+        let entry = builder.create_block();
+        builder.append_block_params_for_function_params(entry);
+        builder.switch_to_block(entry);
+        let frame = builder.block_params(entry)[0];
+        let host = builder.block_params(entry)[1];
+
+        let mut blocks = vec![None; block.instrs.len() + 1];
+        blocks[0] = Some(builder.create_block());
+        blocks[block.instrs.len()] = Some(builder.create_block());
+        for pc in 0..block.instrs.len() {
+            block.for_each_target(pc, |target| {
+                blocks[target as usize].get_or_insert_with(|| builder.create_block());
+            });
+            // Conditional branches fall through into a fresh block.
+            if matches!(
+                block.instrs[pc],
+                Instr::BranchIfBool { .. } | Instr::JumpIfNot { .. } | Instr::ForTest { .. }
+            ) {
+                blocks[pc + 1].get_or_insert_with(|| builder.create_block());
+            }
+        }
+        let exit = builder.create_block();
+        builder.append_block_param(exit, types::I64);
+
+        let mut translator = Self {
+            block,
+            plan,
+            layout: plan.layout(block),
+            builder,
+            frame,
+            host,
+            callback,
+            blocks,
+            exit,
+            pc: 0,
+        };
+        translator.declare();
+        translator
+    }
+
+    fn register_var(reg: u32) -> Variable {
+        Variable::new(reg as usize)
+    }
+
+    fn slot_var(&self, slot: usize) -> Variable {
+        Variable::new(self.block.registers + slot)
+    }
+
+    /// Current, end, and step of a FOR loop.
+    fn loop_vars(&self, slot: u32) -> [Variable; 3] {
+        let base = self.block.registers + self.plan.slots.len() + 3 * slot as usize;
+        [
+            Variable::new(base),
+            Variable::new(base + 1),
+            Variable::new(base + 2),
+        ]
+    }
+
+    /// Number of enclosing compiled loops, relative to the block entry.
+    fn depth_var(&self) -> Variable {
+        Variable::new(self.block.registers + self.plan.slots.len() + 3 * self.block.for_slots)
+    }
+
+    /// Declares every variable and loads the slots from the frame buffer.
+    fn declare(&mut self) {
+        let plan = self.plan;
+        for (reg, kind) in plan.registers.iter().enumerate() {
+            let kind = kind.unwrap_or(Kind::LInt);
+            let var = Self::register_var(reg as u32);
+            self.builder.declare_var(var, kind.ty());
+            let zero = self.zero(kind);
+            self.builder.def_var(var, zero);
+        }
+        for (index, slot) in plan.slots.iter().enumerate() {
+            let var = self.slot_var(index);
+            self.builder.declare_var(var, slot.kind.ty());
+            let value = self.load(slot.kind, index);
+            self.builder.def_var(var, value);
+        }
+        for slot in 0..self.block.for_slots {
+            for var in self.loop_vars(slot as u32) {
+                self.builder.declare_var(var, types::I64);
+                let zero = self.builder.ins().iconst(types::I64, 0);
+                self.builder.def_var(var, zero);
+            }
+        }
+        let depth = self.depth_var();
+        self.builder.declare_var(depth, types::I64);
+        let zero = self.builder.ins().iconst(types::I64, 0);
+        self.builder.def_var(depth, zero);
+    }
+
+    fn zero(&mut self, kind: Kind) -> cranelift_codegen::ir::Value {
+        match kind {
+            Kind::Real => self.builder.ins().f32const(0.0),
+            Kind::LReal => self.builder.ins().f64const(0.0),
+            kind => self.builder.ins().iconst(kind.ty(), 0),
+        }
+    }
+
+    /// Reads a frame word in the encoding of [`Kind::encode`].
+    fn load(&mut self, kind: Kind, word: usize) -> cranelift_codegen::ir::Value {
+        let offset = (word * 8) as i32;
+        match kind {
+            Kind::Real | Kind::LReal => {
+                let raw =
+                    self.builder
+                        .ins()
+                        .load(types::F64, MemFlags::trusted(), self.frame, offset);
+                if kind == Kind::Real {
+                    self.builder.ins().fdemote(types::F32, raw)
+                } else {
+                    raw
+                }
+            }
+            kind => {
+                let raw =
+                    self.builder
+                        .ins()
+                        .load(types::I64, MemFlags::trusted(), self.frame, offset);
+                if kind == Kind::Bool {
+                    self.builder.ins().ireduce(types::I8, raw)
+                } else {
+                    raw
+                }
+            }
+        }
+    }
+
+    /// Writes a frame word in the encoding of [`Kind::encode`].
+    fn store(&mut self, kind: Kind, value: cranelift_codegen::ir::Value, word: usize) {
+        let raw = match kind {
+            Kind::Bool => self.builder.ins().uextend(types::I64, value),
+            Kind::Real => self.builder.ins().fpromote(types::F64, value),
+            _ => value,
+        };
+        self.builder
+            .ins()
+            .store(MemFlags::trusted(), raw, self.frame, (word * 8) as i32);
+    }
+
+    /// Writes the slots native code may have changed to the frame buffer.
+    fn flush_slots(&mut self) {
+        let plan = self.plan;
+        for (index, slot) in plan.slots.iter().enumerate() {
+            if slot.stored {
+                let value = self.builder.use_var(self.slot_var(index));
+                self.store(slot.kind, value, index);
+            }
+        }
+    }
+
+    fn target(&self, pc: u32) -> Block {
+        self.blocks[pc as usize].expect("jump targets have blocks")
+    }
+
+    fn translate(mut self) {
+        let first = self.target(0);
+        self.builder.ins().jump(first, &[]);
+        let mut terminated = true;
+        for pc in 0..self.block.instrs.len() {
+            if let Some(start) = self.blocks[pc] {
+                if !terminated {
+                    self.builder.ins().jump(start, &[]);
+                }
+                self.builder.switch_to_block(start);
+            } else if terminated {
+                continue;
+            }
+            self.pc = pc;
+            terminated = match self.plan.modes[pc] {
+                Mode::Native => self.instr(pc),
+                Mode::Call => {
+                    self.call(pc);
+                    false
+                }
+                Mode::Exit => {
+                    let pc = self.builder.ins().iconst(types::I64, pc as i64);
+                    self.builder.ins().jump(self.exit, &[pc]);
+                    true
+                }
+            };
+        }
+        let done = self.target(self.block.instrs.len() as u32);
+        if !terminated {
+            self.builder.ins().jump(done, &[]);
+        }
+
+        self.builder.switch_to_block(done);
+        self.flush_slots();
+        let status = self.builder.ins().iconst(types::I32, i64::from(DONE));
+        self.builder.ins().return_(&[status]);
+
+        self.translate_exit();
+        self.builder.seal_all_blocks();
+        self.builder.finalize();
+    }
+
+    /// Leaves everything the VM needs to continue in the frame buffer.
+    fn translate_exit(&mut self) {
+        let (plan, layout) = (self.plan, self.layout);
+        self.builder.switch_to_block(self.exit);
+        let pc = self.builder.block_params(self.exit)[0];
+        self.flush_slots();
+        for (reg, kind) in plan.registers.iter().enumerate() {
+            if let (Some(kind), true) = (kind, plan.spilled[reg]) {
+                let value = self.builder.use_var(Self::register_var(reg as u32));
+                self.store(*kind, value, layout.register(reg as u32));
+            }
+        }
+        for slot in 0..self.block.for_slots {
+            for (index, var) in self.loop_vars(slot as u32).into_iter().enumerate() {
+                let value = self.builder.use_var(var);
+                self.store(Kind::LInt, value, layout.loop_state(slot) + index);
+            }
+        }
+        let depth = self.builder.use_var(self.depth_var());
+        self.store(Kind::LInt, depth, layout.depth());
+        self.store(Kind::LInt, pc, layout.pc());
+        let status = self.builder.ins().iconst(types::I32, i64::from(EXIT));
+        self.builder.ins().return_(&[status]);
+    }
+
+    /// Runs a fallback instruction through the interpreter callback.
+    fn call(&mut self, pc: usize) {
+        let (plan, layout) = (self.plan, self.layout);
+        let instr = self.block.instrs[pc];
+        self.flush_slots();
+        if let Instr::Store { src, .. } = instr {
+            let kind = plan.kind(src).expect("planned store source");
+            let value = self.builder.use_var(Self::register_var(src));
+            self.store(kind, value, layout.register(src));
+        }
+        let at = self.builder.ins().iconst(types::I64, pc as i64);
+        let depth = self.builder.use_var(self.depth_var());
+        let call = self
+            .builder
+            .ins()
+            .call(self.callback, &[self.host, self.frame, at, depth]);
+        let status = self.builder.inst_results(call)[0];
+        let next = self.builder.ins().iconst(types::I64, pc as i64 + 1);
+        let continue_block = self.builder.create_block();
+        self.builder
+            .ins()
+            .brif(status, self.exit, &[next], continue_block, &[]);
+        self.builder.switch_to_block(continue_block);
+        for (index, slot) in plan.slots.iter().enumerate() {
+            let value = self.load(slot.kind, index);
+            self.builder.def_var(self.slot_var(index), value);
+        }
+        if let Instr::Eval { dst, .. } | Instr::Load { dst, .. } = instr {
+            let kind = plan.kind(dst).expect("planned fallback result");
+            let value = self.load(kind, layout.register(dst));
+            self.builder.def_var(Self::register_var(dst), value);
+        }
+    }
+
+    /// Translates one native instruction; returns whether it ended the block.
+    fn instr(&mut self, pc: usize) -> bool {
+        let (block, plan) = (self.block, self.plan);
+        let next = pc as u32 + 1;
+        match block.instrs[pc] {
+            Instr::Nop | Instr::CheckBudget => {}
+            Instr::EnterLoop | Instr::LeaveLoop => {
+                let delta = if block.instrs[pc] == Instr::EnterLoop {
+                    1
+                } else {
+                    -1
+                };
+                let var = self.depth_var();
+                let depth = self.builder.use_var(var);
+                let depth = self.builder.ins().iadd_imm(depth, delta);
+                self.builder.def_var(var, depth);
+            }
+            Instr::Eval { .. }
+            | Instr::Exec { .. }
+            | Instr::Escape { .. }
+            | Instr::Return { .. } => {
+                unreachable!("planned as a callback or an exit")
+            }
+            Instr::Const { dst, index } => {
+                let value = match block.consts[index as usize] {
+                    Value::Real(value) => self.builder.ins().f32const(value),
+                    Value::LReal(value) => self.builder.ins().f64const(value),
+                    ref value => {
+                        let kind = Kind::of(value).unwrap_or(Kind::LInt);
+                        self.builder
+                            .ins()
+                            .iconst(kind.ty(), Kind::encode(value) as i64)
+                    }
+                };
+                self.builder.def_var(Self::register_var(dst), value);
+            }
+            Instr::Load { dst, name } => {
+                let var = self.slot_var(plan.names[name as usize].expect("planned load"));
+                let value = self.builder.use_var(var);
+                self.builder.def_var(Self::register_var(dst), value);
+            }
+            Instr::Move { dst, src } => {
+                let value = self.builder.use_var(Self::register_var(src));
+                self.builder.def_var(Self::register_var(dst), value);
+            }
+            Instr::Unary { dst, op, src } => {
+                let kind = plan.kind(src).unwrap_or(Kind::LInt);
+                let value = self.builder.use_var(Self::register_var(src));
+                let result = match op {
+                    UnaryOp::Pos => value,
+                    UnaryOp::Not => self.builder.ins().bxor_imm(value, 1),
+                    UnaryOp::Neg if kind.is_float() => self.builder.ins().fneg(value),
+                    UnaryOp::Neg => {
+                        let overflow = self.builder.ins().icmp_imm(IntCC::Equal, value, kind.min());
+                        self.guard(overflow);
+                        self.builder.ins().ineg(value)
+                    }
+                };
+                self.builder.def_var(Self::register_var(dst), result);
+            }
+            Instr::Binary {
+                dst,
+                op,
+                left,
+                right,
+            } => {
+                let result = self.binary(op, left, right);
+                self.builder.def_var(Self::register_var(dst), result);
+            }
+            Instr::BranchIfBool {
+                cond,
+                value,
+                target,
+            } => {
+                let cond = self.builder.use_var(Self::register_var(cond));
+                let (taken, fallthrough) = (self.target(target), self.target(next));
+                if value {
+                    self.builder.ins().brif(cond, taken, &[], fallthrough, &[]);
+                } else {
+                    self.builder.ins().brif(cond, fallthrough, &[], taken, &[]);
+                }
+                return true;
+            }
+            Instr::Store { target, src } => {
+                let var = self.slot_var(plan.lvalues[target as usize].expect("planned store"));
+                let value = self.builder.use_var(Self::register_var(src));
+                self.builder.def_var(var, value);
+            }
+            Instr::Jump { target } => {
+                let target = self.target(target);
+                self.builder.ins().jump(target, &[]);
+                return true;
+            }
+            Instr::JumpIfNot { cond, target } => {
+                let cond = self.builder.use_var(Self::register_var(cond));
+                let (taken, fallthrough) = (self.target(target), self.target(next));
+                self.builder.ins().brif(cond, fallthrough, &[], taken, &[]);
+                return true;
+            }
+            Instr::Case { selector, table } => {
+                self.case(selector, table);
+                return true;
+            }
+            Instr::ForInit {
+                slot,
+                control,
+                start,
+                end,
+                step,
+            } => {
+                let [current_var, end_var, step_var] = self.loop_vars(slot);
+                let start = self.builder.use_var(Self::register_var(start));
+                let end = self.builder.use_var(Self::register_var(end));
+                let step = self.builder.use_var(Self::register_var(step));
+                let zero_step = self.builder.ins().icmp_imm(IntCC::Equal, step, 0);
+                self.guard(zero_step);
+                let control = plan.lvalues[control as usize].expect("planned control");
+                self.check_range(start, plan.slots[control].kind);
+                let control_var = self.slot_var(control);
+                self.builder.def_var(control_var, start);
+                self.builder.def_var(current_var, start);
+                self.builder.def_var(end_var, end);
+                self.builder.def_var(step_var, step);
+            }
+            Instr::ForTest { slot, exit } => {
+                let [current_var, end_var, step_var] = self.loop_vars(slot);
+                let current = self.builder.use_var(current_var);
+                let end = self.builder.use_var(end_var);
+                let step = self.builder.use_var(step_var);
+                let rising = self
+                    .builder
+                    .ins()
+                    .icmp_imm(IntCC::SignedGreaterThan, step, 0);
+                let above = self
+                    .builder
+                    .ins()
+                    .icmp(IntCC::SignedGreaterThan, current, end);
+                let falling = self.builder.ins().icmp_imm(IntCC::SignedLessThan, step, 0);
+                let below = self.builder.ins().icmp(IntCC::SignedLessThan, current, end);
+                let past_end = self.builder.ins().band(rising, above);
+                let past_start = self.builder.ins().band(falling, below);
+                let done = self.builder.ins().bor(past_end, past_start);
+                let (taken, fallthrough) = (self.target(exit), self.target(next));
+                self.builder.ins().brif(done, taken, &[], fallthrough, &[]);
+                return true;
+            }
+            Instr::ForStep {
+                slot,
+                control,
+                head,
+            } => {
+                let [current_var, _, step_var] = self.loop_vars(slot);
+                let current = self.builder.use_var(current_var);
+                let step = self.builder.use_var(step_var);
+                let current = self.checked_add(current, step);
+                let control = plan.lvalues[control as usize].expect("planned control");
+                self.check_range(current, plan.slots[control].kind);
+                let control_var = self.slot_var(control);
+                self.builder.def_var(control_var, current);
+                self.builder.def_var(current_var, current);
+                let head = self.target(head);
+                self.builder.ins().jump(head, &[]);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Leaves native code at the current instruction when `failed` is set.
+    fn guard(&mut self, failed: cranelift_codegen::ir::Value) {
+        let pc = self.builder.ins().iconst(types::I64, self.pc as i64);
+        let continue_block = self.builder.create_block();
+        self.builder
+            .ins()
+            .brif(failed, self.exit, &[pc], continue_block, &[]);
+        self.builder.switch_to_block(continue_block);
+    }
+
+    fn check_range(&mut self, value: cranelift_codegen::ir::Value, kind: Kind) {
+        if let Some((min, max)) = kind.range() {
+            let below = self
+                .builder
+                .ins()
+                .icmp_imm(IntCC::SignedLessThan, value, min);
+            let above = self
+                .builder
+                .ins()
+                .icmp_imm(IntCC::SignedGreaterThan, value, max);
+            let outside = self.builder.ins().bor(below, above);
+            self.guard(outside);
+        }
+    }
+
+    fn checked_add(
+        &mut self,
+        left: cranelift_codegen::ir::Value,
+        right: cranelift_codegen::ir::Value,
+    ) -> cranelift_codegen::ir::Value {
+        let result = self.builder.ins().iadd(left, right);
+        let left_flip = self.builder.ins().bxor(left, result);
+        let right_flip = self.builder.ins().bxor(right, result);
+        let both = self.builder.ins().band(left_flip, right_flip);
+        let overflow = self.builder.ins().icmp_imm(IntCC::SignedLessThan, both, 0);
+        self.guard(overflow);
+        result
+    }
+
+    fn checked_sub(
+        &mut self,
+        left: cranelift_codegen::ir::Value,
+        right: cranelift_codegen::ir::Value,
+    ) -> cranelift_codegen::ir::Value {
+        let result = self.builder.ins().isub(left, right);
+        let operands = self.builder.ins().bxor(left, right);
+        let flip = self.builder.ins().bxor(left, result);
+        let both = self.builder.ins().band(operands, flip);
+        let overflow = self.builder.ins().icmp_imm(IntCC::SignedLessThan, both, 0);
+        self.guard(overflow);
+        result
+    }
+
+    fn checked_mul(
+        &mut self,
+        left: cranelift_codegen::ir::Value,
+        right: cranelift_codegen::ir::Value,
+    ) -> cranelift_codegen::ir::Value {
+        let result = self.builder.ins().imul(left, right);
+        let high = self.builder.ins().smulhi(left, right);
+        let sign = self.builder.ins().sshr_imm(result, 63);
+        let overflow = self.builder.ins().icmp(IntCC::NotEqual, high, sign);
+        self.guard(overflow);
+        result
+    }
+
+    fn promote_f64(
+        &mut self,
+        value: cranelift_codegen::ir::Value,
+        kind: Kind,
+    ) -> cranelift_codegen::ir::Value {
+        match kind {
+            Kind::LReal => value,
+            Kind::Real => self.builder.ins().fpromote(types::F64, value),
+            _ => self.builder.ins().fcvt_from_sint(types::F64, value),
+        }
+    }
+
+    fn binary(&mut self, op: BinaryOp, left: u32, right: u32) -> cranelift_codegen::ir::Value {
+        let left_kind = self.plan.kind(left).unwrap_or(Kind::LInt);
+        let right_kind = self.plan.kind(right).unwrap_or(Kind::LInt);
+        let mut lhs = self.builder.use_var(Self::register_var(left));
+        let mut rhs = self.builder.use_var(Self::register_var(right));
+        let wide = left_kind.wider(right_kind);
+        if wide.is_float() {
+            lhs = self.promote_f64(lhs, left_kind);
+            rhs = self.promote_f64(rhs, right_kind);
+        }
+        match op {
+            BinaryOp::And => self.builder.ins().band(lhs, rhs),
+            BinaryOp::Or => self.builder.ins().bor(lhs, rhs),
+            BinaryOp::Xor => self.builder.ins().bxor(lhs, rhs),
+            BinaryOp::Eq
+            | BinaryOp::Ne
+            | BinaryOp::Lt
+            | BinaryOp::Le
+            | BinaryOp::Gt
+            | BinaryOp::Ge => {
+                if wide.is_float() {
+                    let cc = match op {
+                        BinaryOp::Eq => FloatCC::Equal,
+                        BinaryOp::Ne => FloatCC::NotEqual,
+                        BinaryOp::Lt => FloatCC::LessThan,
+                        BinaryOp::Le => FloatCC::LessThanOrEqual,
+                        BinaryOp::Gt => FloatCC::GreaterThan,
+                        _ => FloatCC::GreaterThanOrEqual,
+                    };
+                    self.builder.ins().fcmp(cc, lhs, rhs)
+                } else {
+                    let cc = match op {
+                        BinaryOp::Eq => IntCC::Equal,
+                        BinaryOp::Ne => IntCC::NotEqual,
+                        BinaryOp::Lt => IntCC::SignedLessThan,
+                        BinaryOp::Le => IntCC::SignedLessThanOrEqual,
+                        BinaryOp::Gt => IntCC::SignedGreaterThan,
+                        _ => IntCC::SignedGreaterThanOrEqual,
+                    };
+                    self.builder.ins().icmp(cc, lhs, rhs)
+                }
+            }
+            _ if wide.is_float() => {
+                if op == BinaryOp::Div {
+                    let zero = self.builder.ins().f64const(0.0);
+                    let by_zero = self.builder.ins().fcmp(FloatCC::Equal, rhs, zero);
+                    self.guard(by_zero);
+                }
+                let result = match op {
+                    BinaryOp::Add => self.builder.ins().fadd(lhs, rhs),
+                    BinaryOp::Sub => self.builder.ins().fsub(lhs, rhs),
+                    BinaryOp::Mul => self.builder.ins().fmul(lhs, rhs),
+                    _ => self.builder.ins().fdiv(lhs, rhs),
+                };
+                // NaN and infinities are reported as overflow by the interpreter.
+                let magnitude = self.builder.ins().fabs(result);
+                let infinity = self.builder.ins().f64const(f64::INFINITY);
+                let finite = self
+                    .builder
+                    .ins()
+                    .fcmp(FloatCC::LessThan, magnitude, infinity);
+                let overflow = self.builder.ins().bxor_imm(finite, 1);
+                self.guard(overflow);
+                if wide == Kind::Real {
+                    self.builder.ins().fdemote(types::F32, result)
+                } else {
+                    result
+                }
+            }
+            _ => {
+                let result = match op {
+                    BinaryOp::Add => self.checked_add(lhs, rhs),
+                    BinaryOp::Sub => self.checked_sub(lhs, rhs),
+                    BinaryOp::Mul => self.checked_mul(lhs, rhs),
+                    _ => {
+                        // Division by zero, and MIN / -1 which overflows `i64`.
+                        let by_zero = self.builder.ins().icmp_imm(IntCC::Equal, rhs, 0);
+                        let minus_one = self.builder.ins().icmp_imm(IntCC::Equal, rhs, -1);
+                        let min = self.builder.ins().icmp_imm(IntCC::Equal, lhs, i64::MIN);
+                        let overflow = self.builder.ins().band(minus_one, min);
+                        let failed = self.builder.ins().bor(by_zero, overflow);
+                        self.guard(failed);
+                        if op == BinaryOp::Div {
+                            self.builder.ins().sdiv(lhs, rhs)
+                        } else {
+                            self.builder.ins().srem(lhs, rhs)
+                        }
+                    }
+                };
+                self.check_range(result, wide);
+                result
+            }
+        }
+    }
+
+    fn case(&mut self, selector: u32, table: u32) {
+        let selector = self.builder.use_var(Self::register_var(selector));
+        let block = self.block;
+        let table = &block.cases[table as usize];
+        for (labels, target) in &table.arms {
+            let mut matched = self.builder.ins().iconst(types::I8, 0);
+            for label in labels {
+                let hit = match *label {
+                    CaseLabel::Single(value) => {
+                        self.builder.ins().icmp_imm(IntCC::Equal, selector, value)
+                    }
+                    CaseLabel::Range(lower, upper) => {
+                        let low = self.builder.ins().icmp_imm(
+                            IntCC::SignedGreaterThanOrEqual,
+                            selector,
+                            lower,
+                        );
+                        let high = self.builder.ins().icmp_imm(
+                            IntCC::SignedLessThanOrEqual,
+                            selector,
+                            upper,
+                        );
+                        self.builder.ins().band(low, high)
+                    }
+                };
+                matched = self.builder.ins().bor(matched, hit);
+            }
+            let arm = self.target(*target);
+            let next = self.builder.create_block();
+            self.builder.ins().brif(matched, arm, &[], next, &[]);
+            self.builder.switch_to_block(next);
+        }
+        let default = self.target(table.default);
+        self.builder.ins().jump(default, &[]);
+    }
+}
@@ -0,0 +1,294 @@
+//! Register bytecode for program and POU bodies.
+//!
+//! Statement lists are lowered into a flat instruction stream that operates on
+//! numbered registers instead of walking the statement tree every cycle. A
+//! peephole pass folds constants, replaces repeated variable loads with register
+//! moves, and resolves constant branches before the block is cached on the
+//! runtime.
+//!
+//! Only control flow and scalar arithmetic are lowered natively. Calls, member
+//! and subscript access, `?=` assignments, and other constructs are kept as
+//! fallback instructions that defer to [`crate::eval::expr::eval_expr`] and
+//! [`crate::eval::stmt::exec_stmt`], so both engines share one set of semantics.
+
+#![allow(missing_docs)]
+
+mod compile;
+mod exec;
+#[cfg(feature = "jit")]
+mod jit;
+mod optimize;
+mod pou;
+
+use smol_str::SmolStr;
+
+use crate::debug::SourceLocation;
+use crate::eval::expr::{Expr, LValue};
+use crate::eval::ops::{BinaryOp, UnaryOp};
+use crate::eval::stmt::{CaseLabel, Stmt};
+use crate::value::Value;
+
+pub use compile::compile_block;
+pub use exec::exec_compiled;
+#[cfg(feature = "jit")]
+pub use jit::{exec_and_compile, NativeBlock};
+pub use pou::{PouBody, PouCode};
+
+/// Register index inside a compiled block.
+pub(crate) type Reg = u32;
+
+/// Engine used to execute program bodies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExecutionEngine {
+    /// Compiled register bytecode. Programs fall back to the interpreter while
+    /// a debugger has breakpoints, pending steps, or a pause request.
+    #[default]
+    Vm,
+    /// Tree-walking statement interpreter.
+    Interpreter,
+}
+
+/// Whether this build includes the Cranelift backend (`jit` feature).
+pub const JIT_AVAILABLE: bool = cfg!(feature = "jit");
+
+/// Selection of code compiled to native code.
+///
+/// The runtime profiles program bodies for `warmup_cycles` cycles and then
+/// compiles up to `hot_programs` of the most expensive ones. Function,
+/// function block, and method bodies are compiled once they have been called
+/// `warmup_cycles` times. Constructs the backend does not cover call back into
+/// the interpreter or hand the rest of the body to the VM. Without the `jit`
+/// feature the settings are accepted and ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JitSettings {
+    pub enabled: bool,
+    pub warmup_cycles: u64,
+    pub hot_programs: usize,
+}
+
+impl Default for JitSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            warmup_cycles: 100,
+            hot_programs: 4,
+        }
+    }
+}
+
+/// Bytecode instruction.
+///
+/// Jump targets are instruction indices within the owning block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Instr {
+    Nop,
+    CheckBudget,
+    Const {
+        dst: Reg,
+        index: u32,
+    },
+    Load {
+        dst: Reg,
+        name: u32,
+    },
+    Move {
+        dst: Reg,
+        src: Reg,
+    },
+    Unary {
+        dst: Reg,
+        op: UnaryOp,
+        src: Reg,
+    },
+    Binary {
+        dst: Reg,
+        op: BinaryOp,
+        left: Reg,
+        right: Reg,
+    },
+    /// Jumps when `cond` holds exactly `BOOL#value` (AND/OR short-circuit).
+    BranchIfBool {
+        cond: Reg,
+        value: bool,
+        target: u32,
+    },
+    /// Fallback expression evaluated by the interpreter.
+    Eval {
+        dst: Reg,
+        expr: u32,
+    },
+    Store {
+        target: u32,
+        src: Reg,
+    },
+    /// Fallback statement executed by the interpreter.
+    Exec {
+        stmt: u32,
+    },
+    Jump {
+        target: u32,
+    },
+    JumpIfNot {
+        cond: Reg,
+        target: u32,
+    },
+    Case {
+        selector: Reg,
+        table: u32,
+    },
+    ForInit {
+        slot: u32,
+        control: u32,
+        start: Reg,
+        end: Reg,
+        step: Reg,
+    },
+    ForTest {
+        slot: u32,
+        exit: u32,
+    },
+    ForStep {
+        slot: u32,
+        control: u32,
+        head: u32,
+    },
+    EnterLoop,
+    LeaveLoop,
+    /// EXIT/CONTINUE that is not enclosed by a compiled loop.
+    Escape {
+        exit: bool,
+    },
+    Return {
+        src: Option<Reg>,
+    },
+}
+
+impl Instr {
+    /// Whether execution can continue with the next instruction.
+    fn falls_through(&self) -> bool {
+        !matches!(
+            self,
+            Instr::Jump { .. }
+                | Instr::Case { .. }
+                | Instr::ForStep { .. }
+                | Instr::Escape { .. }
+                | Instr::Return { .. }
+        )
+    }
+
+    /// Register written by the instruction, if any.
+    fn written(&self) -> Option<Reg> {
+        match *self {
+            Instr::Const { dst, .. }
+            | Instr::Load { dst, .. }
+            | Instr::Move { dst, .. }
+            | Instr::Unary { dst, .. }
+            | Instr::Binary { dst, .. }
+            | Instr::Eval { dst, .. } => Some(dst),
+            _ => None,
+        }
+    }
+
+    /// Calls `f` for every register read by the instruction.
+    fn for_each_read(&self, mut f: impl FnMut(Reg)) {
+        match *self {
+            Instr::Move { src, .. } | Instr::Unary { src, .. } | Instr::Store { src, .. } => f(src),
+            Instr::Binary { left, right, .. } => {
+                f(left);
+                f(right);
+            }
+            Instr::BranchIfBool { cond, .. } | Instr::JumpIfNot { cond, .. } => f(cond),
+            Instr::Case { selector, .. } => f(selector),
+            Instr::ForInit {
+                start, end, step, ..
+            } => {
+                f(start);
+                f(end);
+                f(step);
+            }
+            Instr::Return { src: Some(src) } => f(src),
+            _ => {}
+        }
+    }
+
+    /// Mutable access to the instruction's jump target, if any.
+    fn target_mut(&mut self) -> Option<&mut u32> {
+        match self {
+            Instr::BranchIfBool { target, .. }
+            | Instr::Jump { target }
+            | Instr::JumpIfNot { target, .. } => Some(target),
+            Instr::ForTest { exit, .. } => Some(exit),
+            Instr::ForStep { head, .. } => Some(head),
+            _ => None,
+        }
+    }
+}
+
+/// CASE dispatch table; arms are tested in source order.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CaseTable {
+    pub(crate) arms: Vec<(Vec<CaseLabel>, u32)>,
+    pub(crate) default: u32,
+}
+
+impl CaseTable {
+    fn target(&self, selector: i64) -> u32 {
+        for (labels, target) in &self.arms {
+            let matches = labels.iter().any(|label| match label {
+                CaseLabel::Single(value) => *value == selector,
+                CaseLabel::Range(lower, upper) => selector >= *lower && selector <= *upper,
+            });
+            if matches {
+                return *target;
+            }
+        }
+        self.default
+    }
+}
+
+/// Compiled statement block.
+#[derive(Debug, Clone, Default)]
+pub struct CompiledBlock {
+    pub(crate) instrs: Vec<Instr>,
+    pub(crate) consts: Vec<Value>,
+    pub(crate) names: Vec<SmolStr>,
+    pub(crate) exprs: Vec<Expr>,
+    pub(crate) stmts: Vec<Stmt>,
+    pub(crate) lvalues: Vec<LValue>,
+    pub(crate) cases: Vec<CaseTable>,
+    /// Source location of each statement by its first instruction, in order.
+    pub(crate) locations: Vec<(u32, SourceLocation)>,
+    pub(crate) registers: usize,
+    pub(crate) for_slots: usize,
+}
+
+impl CompiledBlock {
+    /// Number of instructions after optimization.
+    #[must_use]
+    pub fn instruction_count(&self) -> usize {
+        self.instrs.len()
+    }
+
+    /// Location of the statement the instruction at `pc` belongs to.
+    fn location_at(&self, pc: usize) -> Option<&SourceLocation> {
+        let next = self
+            .locations
+            .partition_point(|(start, _)| *start as usize <= pc);
+        next.checked_sub(1).map(|index| &self.locations[index].1)
+    }
+
+    /// Calls `f` for every jump target of the instruction at `pc`.
+    fn for_each_target(&self, pc: usize, mut f: impl FnMut(u32)) {
+        let mut instr = self.instrs[pc];
+        if let Some(target) = instr.target_mut() {
+            f(*target);
+        }
+        if let Instr::Case { table, .. } = instr {
+            let table = &self.cases[table as usize];
+            for (_, target) in &table.arms {
+                f(*target);
+            }
+            f(table.default);
+        }
+    }
+}
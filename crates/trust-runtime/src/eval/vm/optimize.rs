@@ -0,0 +1,255 @@
+//! Peephole optimizer for compiled blocks.
+
+use rustc_hash::FxHashMap;
+
+use crate::eval::ops::{apply_binary, apply_unary, BinaryOp, UnaryOp};
+use crate::value::{DateTimeProfile, Value};
+
+use super::{CompiledBlock, Instr, Reg};
+
+pub(super) fn optimize(block: &mut CompiledBlock) {
+    propagate(block);
+    remove_dead(block);
+    compact(block);
+    // Compaction can leave jumps that land on the next instruction.
+    let mut threaded = false;
+    for pc in 0..block.instrs.len() {
+        if block.instrs[pc]
+            == (Instr::Jump {
+                target: pc as u32 + 1,
+            })
+        {
+            block.instrs[pc] = Instr::Nop;
+            threaded = true;
+        }
+    }
+    if threaded {
+        compact(block);
+    }
+}
+
+/// Constant propagation and redundant load elimination over straight-line
+/// regions. Knowledge is dropped at jump targets and after any instruction
+/// that may write variables.
+fn propagate(block: &mut CompiledBlock) {
+    let leaders = jump_targets(block);
+    let mut consts: FxHashMap<Reg, u32> = FxHashMap::default();
+    let mut loads: FxHashMap<u32, Reg> = FxHashMap::default();
+
+    for (pc, &leader) in leaders.iter().enumerate().take(block.instrs.len()) {
+        if leader {
+            consts.clear();
+            loads.clear();
+        }
+        let instr = block.instrs[pc];
+        let folded = match instr {
+            Instr::Unary { op, src, .. } => consts
+                .get(&src)
+                .and_then(|index| fold_unary(op, &block.consts[*index as usize])),
+            Instr::Binary {
+                op, left, right, ..
+            } => match (consts.get(&left), consts.get(&right)) {
+                (Some(left), Some(right)) => fold_binary(
+                    op,
+                    &block.consts[*left as usize],
+                    &block.consts[*right as usize],
+                ),
+                _ => None,
+            },
+            _ => None,
+        };
+        let replacement = match instr {
+            Instr::Load { dst, name } => loads
+                .get(&name)
+                .copied()
+                .filter(|src| *src != dst)
+                .map(|src| Instr::Move { dst, src }),
+            Instr::Move { dst, src } => consts
+                .get(&src)
+                .map(|index| Instr::Const { dst, index: *index }),
+            Instr::Unary { dst, .. } | Instr::Binary { dst, .. } => folded.map(|value| {
+                block.consts.push(value);
+                Instr::Const {
+                    dst,
+                    index: (block.consts.len() - 1) as u32,
+                }
+            }),
+            Instr::BranchIfBool {
+                cond,
+                value,
+                target,
+            } => consts.get(&cond).map(|index| {
+                if block.consts[*index as usize] == Value::Bool(value) {
+                    Instr::Jump { target }
+                } else {
+                    Instr::Nop
+                }
+            }),
+            Instr::JumpIfNot { cond, target } => {
+                consts
+                    .get(&cond)
+                    .and_then(|index| match block.consts[*index as usize] {
+                        Value::Bool(true) => Some(Instr::Nop),
+                        Value::Bool(false) => Some(Instr::Jump { target }),
+                        _ => None,
+                    })
+            }
+            _ => None,
+        };
+        let instr = replacement.unwrap_or(instr);
+        block.instrs[pc] = instr;
+
+        if let Some(dst) = instr.written() {
+            consts.remove(&dst);
+            loads.retain(|_, reg| *reg != dst);
+        }
+        match instr {
+            Instr::Const { dst, index } => {
+                consts.insert(dst, index);
+            }
+            Instr::Load { dst, name } => {
+                loads.insert(name, dst);
+            }
+            Instr::Move { dst, src } => {
+                if let Some(name) = loads
+                    .iter()
+                    .find_map(|(name, reg)| (*reg == src).then_some(*name))
+                {
+                    loads.insert(name, dst);
+                }
+            }
+            Instr::Eval { .. }
+            | Instr::Store { .. }
+            | Instr::Exec { .. }
+            | Instr::ForInit { .. }
+            | Instr::ForStep { .. } => loads.clear(),
+            _ => {}
+        }
+        if !instr.falls_through() {
+            consts.clear();
+            loads.clear();
+        }
+    }
+}
+
+/// Drops unreachable instructions and constants or moves nobody reads.
+fn remove_dead(block: &mut CompiledBlock) {
+    let mut reachable = vec![false; block.instrs.len()];
+    let mut pending = vec![0usize];
+    while let Some(pc) = pending.pop() {
+        if pc >= block.instrs.len() || reachable[pc] {
+            continue;
+        }
+        reachable[pc] = true;
+        if block.instrs[pc].falls_through() {
+            pending.push(pc + 1);
+        }
+        block.for_each_target(pc, |target| pending.push(target as usize));
+    }
+    for (instr, live) in block.instrs.iter_mut().zip(reachable) {
+        if !live {
+            *instr = Instr::Nop;
+        }
+    }
+
+    loop {
+        let mut read = vec![false; block.registers];
+        for instr in &block.instrs {
+            instr.for_each_read(|reg| read[reg as usize] = true);
+        }
+        let mut changed = false;
+        for instr in &mut block.instrs {
+            if let Instr::Const { dst, .. } | Instr::Move { dst, .. } = *instr {
+                if !read[dst as usize] {
+                    *instr = Instr::Nop;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+}
+
+/// Removes `Nop`s and rewrites jump targets.
+fn compact(block: &mut CompiledBlock) {
+    let mut remap = Vec::with_capacity(block.instrs.len() + 1);
+    let mut next = 0u32;
+    for instr in &block.instrs {
+        remap.push(next);
+        if *instr != Instr::Nop {
+            next += 1;
+        }
+    }
+    remap.push(next);
+
+    block.instrs.retain(|instr| *instr != Instr::Nop);
+    for instr in &mut block.instrs {
+        if let Some(target) = instr.target_mut() {
+            *target = remap[*target as usize];
+        }
+    }
+    for table in &mut block.cases {
+        for (_, target) in &mut table.arms {
+            *target = remap[*target as usize];
+        }
+        table.default = remap[table.default as usize];
+    }
+    for (start, _) in &mut block.locations {
+        *start = remap[*start as usize];
+    }
+}
+
+fn jump_targets(block: &CompiledBlock) -> Vec<bool> {
+    let mut leaders = vec![false; block.instrs.len() + 1];
+    for pc in 0..block.instrs.len() {
+        block.for_each_target(pc, |target| leaders[target as usize] = true);
+    }
+    leaders
+}
+
+/// Values whose operators never depend on the date/time profile.
+fn foldable(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::Bool(_)
+            | Value::SInt(_)
+            | Value::Int(_)
+            | Value::DInt(_)
+            | Value::LInt(_)
+            | Value::USInt(_)
+            | Value::UInt(_)
+            | Value::UDInt(_)
+            | Value::ULInt(_)
+            | Value::Real(_)
+            | Value::LReal(_)
+            | Value::Byte(_)
+            | Value::Word(_)
+            | Value::DWord(_)
+            | Value::LWord(_)
+    )
+}
+
+fn fold_unary(op: UnaryOp, value: &Value) -> Option<Value> {
+    let overflows = op == UnaryOp::Neg
+        && matches!(
+            value,
+            Value::SInt(i8::MIN)
+                | Value::Int(i16::MIN)
+                | Value::DInt(i32::MIN)
+                | Value::LInt(i64::MIN)
+        );
+    if !foldable(value) || overflows {
+        return None;
+    }
+    // Errors are left for the runtime to report at the original instruction.
+    apply_unary(op, value.clone()).ok()
+}
+
+fn fold_binary(op: BinaryOp, left: &Value, right: &Value) -> Option<Value> {
+    if !foldable(left) || !foldable(right) {
+        return None;
+    }
+    apply_binary(op, left.clone(), right.clone(), &DateTimeProfile::default()).ok()
+}
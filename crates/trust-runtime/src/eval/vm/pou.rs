@@ -0,0 +1,207 @@
+//! Bytecode for function, function block, and method bodies.
+
+#[cfg(feature = "jit")]
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    OnceLock,
+};
+
+use indexmap::IndexMap;
+use smol_str::SmolStr;
+
+use crate::error::RuntimeError;
+use crate::eval::stmt::{Stmt, StmtResult};
+use crate::eval::{ClassDef, EvalContext, FunctionBlockDef, FunctionDef, MethodDef};
+
+use super::{compile_block, exec_compiled, CompiledBlock, JitSettings};
+
+/// Compiled bodies of the POUs a runtime calls.
+///
+/// Built from the registered definitions and rebuilt whenever one of them
+/// changes. Bodies the compiler does not accept keep running on the
+/// interpreter.
+#[derive(Debug, Default)]
+pub struct PouCode {
+    functions: IndexMap<SmolStr, PouBody>,
+    function_blocks: IndexMap<SmolStr, PouBody>,
+    /// Keyed by `TYPE.METHOD` for every FB and class type, including the
+    /// methods it inherits.
+    methods: IndexMap<SmolStr, PouBody>,
+}
+
+impl PouCode {
+    #[must_use]
+    pub fn build(
+        functions: &IndexMap<SmolStr, FunctionDef>,
+        function_blocks: &IndexMap<SmolStr, FunctionBlockDef>,
+        classes: &IndexMap<SmolStr, ClassDef>,
+        jit: JitSettings,
+    ) -> Self {
+        let mut code = Self::default();
+        for (key, func) in functions {
+            if let Some(body) = PouBody::compile(&func.body, jit) {
+                code.functions.insert(key.clone(), body);
+            }
+        }
+        for (key, fb) in function_blocks {
+            if let Some(body) = PouBody::compile(&fb.body, jit) {
+                code.function_blocks.insert(key.clone(), body);
+            }
+            let mut methods = Vec::new();
+            collect_fb_methods(function_blocks, classes, fb, &mut methods);
+            code.insert_methods(key, methods, jit);
+        }
+        for (key, class_def) in classes {
+            let mut methods = Vec::new();
+            collect_class_methods(classes, class_def, &mut methods);
+            code.insert_methods(key, methods, jit);
+        }
+        code
+    }
+
+    /// Methods in resolution order: a type's own before inherited ones.
+    fn insert_methods(&mut self, type_key: &str, methods: Vec<&MethodDef>, jit: JitSettings) {
+        for method in methods {
+            let key = method_key(type_key, &method.name);
+            if self.methods.contains_key(&key) {
+                continue;
+            }
+            if let Some(body) = PouBody::compile(&method.body, jit) {
+                self.methods.insert(key, body);
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn function(&self, name: &str) -> Option<&PouBody> {
+        self.functions.get(name.to_ascii_uppercase().as_str())
+    }
+
+    #[must_use]
+    pub fn function_block(&self, name: &str) -> Option<&PouBody> {
+        self.function_blocks.get(name.to_ascii_uppercase().as_str())
+    }
+
+    /// Body of `method` as resolved on an instance of `type_name`.
+    #[must_use]
+    pub fn method(&self, type_name: &str, method: &str) -> Option<&PouBody> {
+        self.methods
+            .get(method_key(&type_name.to_ascii_uppercase(), method).as_str())
+    }
+
+    /// POUs currently running as native code, as `NAME` or `TYPE.METHOD`.
+    #[must_use]
+    pub fn native_pous(&self) -> Vec<SmolStr> {
+        self.functions
+            .iter()
+            .chain(&self.function_blocks)
+            .chain(&self.methods)
+            .filter(|(_, body)| body.is_native())
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+}
+
+fn method_key(type_key: &str, method: &str) -> SmolStr {
+    SmolStr::new(format!("{type_key}.{}", method.to_ascii_uppercase()))
+}
+
+fn collect_fb_methods<'a>(
+    function_blocks: &'a IndexMap<SmolStr, FunctionBlockDef>,
+    classes: &'a IndexMap<SmolStr, ClassDef>,
+    fb: &'a FunctionBlockDef,
+    methods: &mut Vec<&'a MethodDef>,
+) {
+    let mut current = Some(fb);
+    while let Some(def) = current {
+        methods.extend(&def.methods);
+        current = match &def.base {
+            Some(crate::eval::FunctionBlockBase::FunctionBlock(base)) => {
+                function_blocks.get(base.to_ascii_uppercase().as_str())
+            }
+            Some(crate::eval::FunctionBlockBase::Class(base)) => {
+                if let Some(class_def) = classes.get(base.to_ascii_uppercase().as_str()) {
+                    collect_class_methods(classes, class_def, methods);
+                }
+                None
+            }
+            None => None,
+        };
+    }
+}
+
+fn collect_class_methods<'a>(
+    classes: &'a IndexMap<SmolStr, ClassDef>,
+    class_def: &'a ClassDef,
+    methods: &mut Vec<&'a MethodDef>,
+) {
+    let mut current = Some(class_def);
+    while let Some(def) = current {
+        methods.extend(&def.methods);
+        current = def
+            .base
+            .as_ref()
+            .and_then(|base| classes.get(base.to_ascii_uppercase().as_str()));
+    }
+}
+
+/// Compiled body of one POU.
+///
+/// With the `jit` feature, a body called `warmup_cycles` times is compiled to
+/// native code the same way as a hot program.
+#[derive(Debug)]
+pub struct PouBody {
+    block: CompiledBlock,
+    #[cfg(feature = "jit")]
+    jit: JitSettings,
+    #[cfg(feature = "jit")]
+    calls: AtomicU64,
+    #[cfg(feature = "jit")]
+    native: OnceLock<Option<super::NativeBlock>>,
+}
+
+impl PouBody {
+    #[cfg_attr(not(feature = "jit"), allow(unused_variables))]
+    fn compile(body: &[Stmt], jit: JitSettings) -> Option<Self> {
+        Some(Self {
+            block: compile_block(body)?,
+            #[cfg(feature = "jit")]
+            jit,
+            #[cfg(feature = "jit")]
+            calls: AtomicU64::new(0),
+            #[cfg(feature = "jit")]
+            native: OnceLock::new(),
+        })
+    }
+
+    /// Executes the body in the POU frame set up by the caller.
+    pub fn exec(&self, ctx: &mut EvalContext<'_>) -> Result<StmtResult, RuntimeError> {
+        // Native code has no statement budget checks.
+        #[cfg(feature = "jit")]
+        if self.jit.enabled && ctx.execution_deadline.is_none() {
+            if let Some(native) = self.native.get() {
+                return match native {
+                    Some(native) => native.run(ctx, &self.block),
+                    None => exec_compiled(ctx, &self.block),
+                };
+            }
+            if self.calls.fetch_add(1, Ordering::Relaxed) + 1 >= self.jit.warmup_cycles {
+                let (result, native) = super::exec_and_compile(ctx, &self.block);
+                // A recursive call may have compiled the body first.
+                let _ = self.native.set(native);
+                return result;
+            }
+        }
+        exec_compiled(ctx, &self.block)
+    }
+
+    #[cfg(feature = "jit")]
+    fn is_native(&self) -> bool {
+        matches!(self.native.get(), Some(Some(_)))
+    }
+
+    #[cfg(not(feature = "jit"))]
+    fn is_native(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,518 @@
+//! Persistent runtime event store.
+//!
+//! Runtime events at or above a configured severity are appended to a JSONL file so they
+//! survive a crash or restart. The file is rotated once it reaches half of `max_bytes`
+//! (the previous segment is kept as `<path>.1`), and records older than `max_age` are
+//! dropped on open, on rotation, and from query results.
+
+#![allow(missing_docs)]
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use smol_str::SmolStr;
+
+use crate::debug::RuntimeEvent;
+use crate::error::RuntimeError;
+use crate::watchdog::ShutdownOutcome;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventSeverity {
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl EventSeverity {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "debug" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warning" | "warn" => Some(Self::Warning),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+
+    /// Severity of a runtime event: cycle and task boundaries are debug noise,
+    /// overruns are warnings, and faults are errors.
+    #[must_use]
+    pub fn of(event: &RuntimeEvent) -> Self {
+        match event {
+            RuntimeEvent::CycleStart { .. }
+            | RuntimeEvent::CycleEnd { .. }
+            | RuntimeEvent::TaskStart { .. }
+            | RuntimeEvent::TaskEnd { .. } => Self::Debug,
+            RuntimeEvent::TaskOverrun { .. } => Self::Warning,
+            RuntimeEvent::Fault { .. } => Self::Error,
+            RuntimeEvent::Shutdown { outcome, .. } => match outcome {
+                ShutdownOutcome::Done | ShutdownOutcome::CyclesElapsed => Self::Info,
+                ShutdownOutcome::TimedOut | ShutdownOutcome::Faulted => Self::Warning,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EventStoreConfig {
+    pub enabled: bool,
+    pub path: PathBuf,
+    /// Events below this severity are not persisted.
+    pub min_severity: EventSeverity,
+    pub max_age: Duration,
+    /// Disk budget for the current and the rotated segment together.
+    pub max_bytes: u64,
+}
+
+impl Default for EventStoreConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: PathBuf::from("events/events.jsonl"),
+            min_severity: EventSeverity::Info,
+            max_age: Duration::from_secs(7 * 24 * 60 * 60),
+            max_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// One persisted event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredEvent {
+    pub seq: u64,
+    /// Wall-clock time the event was stored.
+    pub timestamp_ms: u64,
+    pub severity: EventSeverity,
+    /// Event type, e.g. `fault` or `task_overrun`.
+    pub code: String,
+    /// The event as returned by `events.tail`.
+    pub event: serde_json::Value,
+}
+
+/// Filter for [`EventStore::query`].
+#[derive(Debug, Clone, Default)]
+pub struct EventQuery {
+    pub since_ms: Option<u64>,
+    pub until_ms: Option<u64>,
+    /// Event types to include; empty means all.
+    pub codes: Vec<SmolStr>,
+    pub min_severity: Option<EventSeverity>,
+    pub limit: usize,
+}
+
+impl EventQuery {
+    fn matches(&self, event: &StoredEvent) -> bool {
+        self.since_ms
+            .is_none_or(|since| event.timestamp_ms >= since)
+            && self
+                .until_ms
+                .is_none_or(|until| event.timestamp_ms <= until)
+            && (self.codes.is_empty() || self.codes.iter().any(|code| *code == event.code))
+            && self
+                .min_severity
+                .is_none_or(|severity| event.severity >= severity)
+    }
+}
+
+#[derive(Debug)]
+struct StoreState {
+    next_seq: u64,
+    bytes: u64,
+}
+
+#[derive(Debug)]
+pub struct EventStore {
+    config: EventStoreConfig,
+    rotated_path: PathBuf,
+    state: Mutex<StoreState>,
+}
+
+impl EventStore {
+    /// Open the store, dropping records that have aged out since the last run.
+    pub fn open(
+        mut config: EventStoreConfig,
+        bundle_root: Option<&Path>,
+    ) -> Result<Self, RuntimeError> {
+        if !config.path.is_absolute() {
+            if let Some(root) = bundle_root {
+                config.path = root.join(&config.path);
+            }
+        }
+        if let Some(parent) = config.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| store_error("path setup", err))?;
+        }
+        let mut rotated = config.path.clone().into_os_string();
+        rotated.push(".1");
+        let store = Self {
+            rotated_path: PathBuf::from(rotated),
+            state: Mutex::new(StoreState {
+                next_seq: 1,
+                bytes: 0,
+            }),
+            config,
+        };
+        let cutoff = store.cutoff_ms(now_ms());
+        prune_segment(&store.rotated_path, cutoff)?;
+        let current = prune_segment(&store.config.path, cutoff)?;
+        let last_seq = read_segment(&store.rotated_path)
+            .into_iter()
+            .chain(current.iter().cloned())
+            .map(|event| event.seq)
+            .max()
+            .unwrap_or(0);
+        if let Ok(mut state) = store.state.lock() {
+            state.next_seq = last_seq + 1;
+            state.bytes = file_len(&store.config.path);
+        }
+        Ok(store)
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &EventStoreConfig {
+        &self.config
+    }
+
+    /// Persist `event` if it meets the configured severity. Returns whether it was stored.
+    pub fn append(&self, event: &RuntimeEvent) -> Result<bool, RuntimeError> {
+        self.append_at(event, now_ms())
+    }
+
+    pub fn append_at(&self, event: &RuntimeEvent, timestamp_ms: u64) -> Result<bool, RuntimeError> {
+        let severity = EventSeverity::of(event);
+        if severity < self.config.min_severity {
+            return Ok(false);
+        }
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| RuntimeError::ControlError("event store lock poisoned".into()))?;
+        let event = runtime_event_json(event);
+        let record = StoredEvent {
+            seq: state.next_seq,
+            timestamp_ms,
+            severity,
+            code: event["type"].as_str().unwrap_or("event").to_string(),
+            event,
+        };
+        let mut line = serde_json::to_string(&record).map_err(|err| {
+            RuntimeError::ControlError(format!("event store serialization failed: {err}").into())
+        })?;
+        line.push('\n');
+        let line_bytes = line.len() as u64;
+        if state.bytes > 0 && state.bytes + line_bytes > self.config.max_bytes / 2 {
+            self.rotate(timestamp_ms)?;
+            state.bytes = 0;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.config.path)
+            .map_err(|err| store_error("write", err))?;
+        file.write_all(line.as_bytes())
+            .and_then(|()| file.flush())
+            .map_err(|err| store_error("write", err))?;
+        state.bytes += line_bytes;
+        state.next_seq += 1;
+        Ok(true)
+    }
+
+    /// Stored events matching `query`, newest first.
+    #[must_use]
+    pub fn query(&self, query: &EventQuery) -> Vec<StoredEvent> {
+        self.query_at(query, now_ms())
+    }
+
+    fn query_at(&self, query: &EventQuery, now_ms: u64) -> Vec<StoredEvent> {
+        // Hold the lock so a concurrent rotation cannot hide a segment mid-read.
+        let _state = self.state.lock();
+        let cutoff = self.cutoff_ms(now_ms);
+        let mut events = read_segment(&self.rotated_path);
+        events.extend(read_segment(&self.config.path));
+        events
+            .into_iter()
+            .rev()
+            .filter(|event| event.timestamp_ms >= cutoff && query.matches(event))
+            .take(query.limit)
+            .collect()
+    }
+
+    fn rotate(&self, now_ms: u64) -> Result<(), RuntimeError> {
+        std::fs::rename(&self.config.path, &self.rotated_path)
+            .map_err(|err| store_error("rotation", err))?;
+        prune_segment(&self.rotated_path, self.cutoff_ms(now_ms))?;
+        Ok(())
+    }
+
+    fn cutoff_ms(&self, now_ms: u64) -> u64 {
+        let max_age = u64::try_from(self.config.max_age.as_millis()).unwrap_or(u64::MAX);
+        now_ms.saturating_sub(max_age)
+    }
+}
+
+/// JSON form of a runtime event, as listed by `events.tail`.
+#[must_use]
+pub fn runtime_event_json(event: &RuntimeEvent) -> serde_json::Value {
+    match event {
+        RuntimeEvent::CycleStart { cycle, time } => json!({
+            "type": "cycle_start",
+            "cycle": cycle,
+            "time_ns": time.as_nanos(),
+        }),
+        RuntimeEvent::CycleEnd { cycle, time } => json!({
+            "type": "cycle_end",
+            "cycle": cycle,
+            "time_ns": time.as_nanos(),
+        }),
+        RuntimeEvent::TaskStart {
+            name,
+            priority,
+            time,
+        } => json!({
+            "type": "task_start",
+            "name": name.as_str(),
+            "priority": priority,
+            "time_ns": time.as_nanos(),
+        }),
+        RuntimeEvent::TaskEnd {
+            name,
+            priority,
+            time,
+        } => json!({
+            "type": "task_end",
+            "name": name.as_str(),
+            "priority": priority,
+            "time_ns": time.as_nanos(),
+        }),
+        RuntimeEvent::TaskOverrun { name, missed, time } => json!({
+            "type": "task_overrun",
+            "name": name.as_str(),
+            "missed": missed,
+            "time_ns": time.as_nanos(),
+        }),
+        RuntimeEvent::Fault {
+            error,
+            location,
+            time,
+        } => json!({
+            "type": "fault",
+            "error": error,
+            "location": location.as_ref().map(|location| json!({
+                "pou": location.pou.as_str(),
+                "file": location.file.as_deref(),
+                "line": location.line,
+                "column": location.column,
+            })),
+            "time_ns": time.as_nanos(),
+        }),
+        RuntimeEvent::Shutdown {
+            program,
+            cycles,
+            outcome,
+            time,
+        } => json!({
+            "type": "shutdown",
+            "program": program.as_str(),
+            "cycles": cycles,
+            "outcome": outcome.as_str(),
+            "time_ns": time.as_nanos(),
+        }),
+    }
+}
+
+fn read_segment(path: &Path) -> Vec<StoredEvent> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<StoredEvent>(&line).ok())
+        .collect()
+}
+
+/// Rewrite a segment without records older than `cutoff_ms`; returns what is left.
+fn prune_segment(path: &Path, cutoff_ms: u64) -> Result<Vec<StoredEvent>, RuntimeError> {
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let events = read_segment(path);
+    let kept = events
+        .iter()
+        .filter(|event| event.timestamp_ms >= cutoff_ms)
+        .cloned()
+        .collect::<Vec<_>>();
+    if kept.len() == events.len() {
+        return Ok(kept);
+    }
+    let mut text = String::new();
+    for event in &kept {
+        if let Ok(line) = serde_json::to_string(event) {
+            text.push_str(&line);
+            text.push('\n');
+        }
+    }
+    std::fs::write(path, text).map_err(|err| store_error("prune", err))?;
+    Ok(kept)
+}
+
+fn file_len(path: &Path) -> u64 {
+    std::fs::metadata(path).map_or(0, |meta| meta.len())
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+        })
+}
+
+fn store_error(action: &str, err: std::io::Error) -> RuntimeError {
+    RuntimeError::ControlError(format!("event store {action} failed: {err}").into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("clock")
+            .as_nanos();
+        std::env::temp_dir()
+            .join(format!("trust-events-{name}-{stamp}"))
+            .join("events.jsonl")
+    }
+
+    fn config(path: PathBuf) -> EventStoreConfig {
+        EventStoreConfig {
+            enabled: true,
+            path,
+            ..EventStoreConfig::default()
+        }
+    }
+
+    fn fault(message: &str) -> RuntimeEvent {
+        RuntimeEvent::Fault {
+            error: message.to_string(),
+            location: None,
+            time: crate::value::Duration::from_millis(5),
+        }
+    }
+
+    fn overrun() -> RuntimeEvent {
+        RuntimeEvent::TaskOverrun {
+            name: SmolStr::new("Fast"),
+            missed: 2,
+            time: crate::value::Duration::from_millis(7),
+        }
+    }
+
+    #[test]
+    fn events_survive_reopen_and_filter_by_time_code_and_severity() {
+        let path = temp_path("reopen");
+        let now = now_ms();
+        {
+            let store = EventStore::open(config(path.clone()), None).expect("open");
+            let cycle = RuntimeEvent::CycleStart {
+                cycle: 1,
+                time: crate::value::Duration::from_millis(1),
+            };
+            assert!(!store.append_at(&cycle, now).unwrap(), "debug is filtered");
+            assert!(store.append_at(&overrun(), now - 2_000).unwrap());
+            assert!(store
+                .append_at(&fault("divide by zero"), now - 1_000)
+                .unwrap());
+        }
+        let store = EventStore::open(config(path.clone()), None).expect("reopen");
+        assert!(store.append_at(&fault("index out of bounds"), now).unwrap());
+
+        let all = store.query(&EventQuery {
+            limit: 10,
+            ..EventQuery::default()
+        });
+        assert_eq!(all.iter().map(|e| e.seq).collect::<Vec<_>>(), [3, 2, 1]);
+        assert_eq!(all[0].event["error"], json!("index out of bounds"));
+
+        let faults = store.query(&EventQuery {
+            codes: vec![SmolStr::new("fault")],
+            since_ms: Some(now - 1_500),
+            until_ms: Some(now - 500),
+            limit: 10,
+            ..EventQuery::default()
+        });
+        assert_eq!(faults.len(), 1);
+        assert_eq!(faults[0].event["error"], json!("divide by zero"));
+
+        let errors = store.query(&EventQuery {
+            min_severity: Some(EventSeverity::Error),
+            limit: 10,
+            ..EventQuery::default()
+        });
+        assert_eq!(errors.len(), 2);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn retention_rotates_by_size_and_drops_aged_events() {
+        let path = temp_path("retention");
+        let mut limited = config(path.clone());
+        limited.max_bytes = 600;
+        limited.max_age = Duration::from_secs(60);
+        let now = now_ms();
+        {
+            let store = EventStore::open(limited.clone(), None).expect("open");
+            store.append_at(&fault("stale"), now - 120_000).unwrap();
+            for idx in 0..10 {
+                store
+                    .append_at(&fault(&format!("fault {idx}")), now)
+                    .unwrap();
+            }
+            assert!(store.rotated_path.is_file());
+            assert!(file_len(&path) + file_len(&store.rotated_path) <= limited.max_bytes);
+            let kept = store.query(&EventQuery {
+                limit: 100,
+                ..EventQuery::default()
+            });
+            assert!(kept.len() < 10, "oldest segment is dropped on rotation");
+            assert_eq!(kept[0].event["error"], json!("fault 9"));
+            assert!(kept
+                .iter()
+                .all(|event| event.event["error"] != json!("stale")));
+        }
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+
+        let path = temp_path("age");
+        let store = EventStore::open(config(path.clone()), None).expect("open");
+        store.append_at(&fault("old"), now - 120_000).unwrap();
+        store.append_at(&fault("new"), now).unwrap();
+        drop(store);
+        let mut aged = config(path.clone());
+        aged.max_age = Duration::from_secs(60);
+        let store = EventStore::open(aged, None).expect("reopen");
+        let kept = read_segment(&store.config.path);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].event["error"], json!("new"));
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -11,6 +11,7 @@ use crate::Runtime;
 pub struct CompileSession {
     sources: Vec<SourceFile>,
     label_errors: bool,
+    library: bool,
 }
 
 impl CompileSession {
@@ -19,6 +20,7 @@ impl CompileSession {
         Self {
             sources: vec![SourceFile::new(source)],
             label_errors: false,
+            library: false,
         }
     }
 
@@ -28,6 +30,7 @@ impl CompileSession {
         Self {
             sources,
             label_errors,
+            library: false,
         }
     }
 
@@ -37,6 +40,12 @@ impl CompileSession {
         self
     }
 
+    /// Enable/disable library builds, which do not require a PROGRAM declaration.
+    pub fn library(mut self, library: bool) -> Self {
+        self.library = library;
+        self
+    }
+
     /// Access the registered sources.
     pub fn sources(&self) -> &[SourceFile] {
         &self.sources
@@ -44,12 +53,16 @@ impl CompileSession {
 
     /// Compile sources into a runtime.
     pub fn build_runtime(&self) -> Result<Runtime, CompileError> {
-        build::build_runtime_from_source_files(&self.sources, self.label_errors)
+        build::build_runtime_from_source_files(&self.sources, self.label_errors, self.library)
     }
 
     /// Compile sources into a bytecode module.
     pub fn build_bytecode_module(&self) -> Result<crate::bytecode::BytecodeModule, CompileError> {
-        build::build_bytecode_module_from_source_files(&self.sources, self.label_errors)
+        build::build_bytecode_module_from_source_files(
+            &self.sources,
+            self.label_errors,
+            self.library,
+        )
     }
 
     /// Compile sources into bytecode bytes.
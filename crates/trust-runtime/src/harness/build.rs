@@ -2,6 +2,7 @@ use indexmap::IndexMap;
 use smol_str::SmolStr;
 
 use crate::debug::SourceLocation;
+use crate::eval::FunctionBlockBase;
 use crate::task::ProgramDef;
 use crate::Runtime;
 use std::path::Path;
@@ -19,6 +20,7 @@ use super::types::{CompileError, SourceFile};
 pub(super) fn build_runtime_from_source_files(
     sources: &[SourceFile],
     label_errors: bool,
+    library: bool,
 ) -> Result<Runtime, CompileError> {
     let mut parses = Vec::with_capacity(sources.len());
     let mut parse_errors = Vec::new();
@@ -69,6 +71,12 @@ pub(super) fn build_runtime_from_source_files(
     let profile = runtime.profile();
     let mut statement_locations: Vec<Vec<SourceLocation>> = vec![Vec::new(); sources.len()];
 
+    let syntaxes = parses
+        .iter()
+        .map(|parse| parse.syntax())
+        .collect::<Vec<_>>();
+    super::register_namespace_reexports(&syntaxes, runtime.registry_mut());
+
     for (idx, parse) in parses.iter().enumerate() {
         let syntax = parse.syntax();
         super::lower_type_decls(
@@ -153,6 +161,28 @@ pub(super) fn build_runtime_from_source_files(
         }
     }
 
+    let interface_defaults =
+        super::interface_default_methods(&syntaxes, runtime.registry(), runtime.interfaces())?;
+    for (key, methods) in interface_defaults {
+        if let Some(mut class_def) = runtime.classes().get(key.as_str()).cloned() {
+            let base = class_def.base.clone().map(FunctionBlockBase::Class);
+            class_def.methods.extend(
+                methods
+                    .into_iter()
+                    .filter(|method| !base_declares_method(&runtime, base.clone(), &method.name)),
+            );
+            runtime.register_class(class_def);
+        } else if let Some(mut fb) = runtime.function_blocks().get(key.as_str()).cloned() {
+            let base = fb.base.clone();
+            fb.methods.extend(
+                methods
+                    .into_iter()
+                    .filter(|method| !base_declares_method(&runtime, base.clone(), &method.name)),
+            );
+            runtime.register_function_block(fb);
+        }
+    }
+
     let mut function_names = std::collections::HashSet::new();
     for (idx, parse) in parses.iter().enumerate() {
         let syntax = parse.syntax();
@@ -244,7 +274,7 @@ pub(super) fn build_runtime_from_source_files(
             runtime.register_task(task);
         }
     } else {
-        if program_defs.is_empty() {
+        if program_defs.is_empty() && !library {
             return Err(CompileError::new("missing PROGRAM declaration"));
         }
         let mut wildcards = apply_globals(&mut runtime, &globals)?;
@@ -272,6 +302,11 @@ pub(super) fn build_runtime_from_source_files(
 
     for (idx, locations) in statement_locations.into_iter().enumerate() {
         runtime.register_statement_locations(file_ids[idx].0, locations);
+        runtime.register_source_text(
+            file_ids[idx].0,
+            sources[idx].path.as_deref(),
+            &sources[idx].text,
+        );
     }
 
     Ok(runtime)
@@ -280,8 +315,9 @@ pub(super) fn build_runtime_from_source_files(
 pub(super) fn build_bytecode_module_from_source_files(
     sources: &[SourceFile],
     label_errors: bool,
+    library: bool,
 ) -> Result<crate::bytecode::BytecodeModule, CompileError> {
-    let runtime = build_runtime_from_source_files(sources, label_errors)?;
+    let runtime = build_runtime_from_source_files(sources, label_errors, library)?;
     let source_refs = sources
         .iter()
         .map(|source| source.text.as_str())
@@ -303,6 +339,49 @@ pub(super) fn build_bytecode_module_from_source_files(
     }
 }
 
+/// Returns true when a base of a CLASS or FUNCTION_BLOCK declares `name`, which then
+/// takes precedence over an interface default method.
+fn base_declares_method(
+    runtime: &Runtime,
+    mut base: Option<FunctionBlockBase>,
+    name: &str,
+) -> bool {
+    let mut visited = std::collections::HashSet::new();
+    while let Some(current) = base.take() {
+        let (name_key, methods, next) = match &current {
+            FunctionBlockBase::FunctionBlock(base_name) => {
+                let key = base_name.to_ascii_uppercase();
+                let Some(def) = runtime.function_blocks().get(key.as_str()) else {
+                    return false;
+                };
+                (key, &def.methods, def.base.clone())
+            }
+            FunctionBlockBase::Class(base_name) => {
+                let key = base_name.to_ascii_uppercase();
+                let Some(def) = runtime.classes().get(key.as_str()) else {
+                    return false;
+                };
+                (
+                    key,
+                    &def.methods,
+                    def.base.clone().map(FunctionBlockBase::Class),
+                )
+            }
+        };
+        if !visited.insert(name_key) {
+            return false;
+        }
+        if methods
+            .iter()
+            .any(|method| method.name.eq_ignore_ascii_case(name))
+        {
+            return true;
+        }
+        base = next;
+    }
+    false
+}
+
 fn source_label(source: &SourceFile, idx: usize) -> String {
     source
         .path
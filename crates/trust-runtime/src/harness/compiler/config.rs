@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use indexmap::IndexMap;
 use smol_str::SmolStr;
 
@@ -6,7 +8,7 @@ use crate::task::ProgramDef;
 use crate::value::Duration;
 use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
 
-use super::super::lower::{const_duration_from_node, const_int_from_node, lower_expr};
+use super::super::lower::{const_duration_from_node, const_int_from_node, lower_initializer};
 use super::super::types::CompileError;
 use super::super::util::{
     collect_using_directives, extract_name_from_expr, is_expression_kind, node_text,
@@ -38,13 +40,15 @@ pub(crate) fn lower_configuration(
         ));
     }
     let config = configs[0].clone();
-    let using = collect_using_directives(&config);
+    let using = collect_using_directives(&config, registry);
     let mut ctx = LoweringContext {
         registry,
         profile,
         using,
         file_id,
         statement_locations,
+        string_bounds: HashMap::new(),
+        subrange_bounds: HashMap::new(),
     };
     let mut globals = Vec::new();
     let mut tasks = Vec::new();
@@ -117,7 +121,9 @@ fn lower_global_var_block(
     {
         let (names, type_ref, initializer, address) = parse_var_decl(&var_decl)?;
         let type_id = lower_type_ref(&type_ref, ctx)?;
-        let init_expr = initializer.map(|expr| lower_expr(&expr, ctx)).transpose()?;
+        let init_expr = initializer
+            .map(|expr| lower_initializer(&expr, type_id, ctx))
+            .transpose()?;
         match kind {
             VarBlockKind::Global
             | VarBlockKind::Var
@@ -220,7 +226,7 @@ fn lower_var_config_block(
         let initializer = config_init
             .children()
             .find(|child| is_expression_kind(child.kind()))
-            .map(|expr| lower_expr(&expr, ctx))
+            .map(|expr| lower_initializer(&expr, type_id, ctx))
             .transpose()?;
         let address = config_init_address(&config_init)?;
         inits.push(ConfigInit {
@@ -14,11 +14,11 @@ pub(super) use model::{
     ProgramInstanceConfig, ResolvedAccess, WildcardRequirement,
 };
 pub(super) use pou::{
-    lower_classes, lower_function_blocks, lower_functions, lower_interfaces, lower_programs,
-    qualified_pou_name,
+    interface_default_methods, lower_classes, lower_function_blocks, lower_functions,
+    lower_interfaces, lower_programs, qualified_pou_name,
 };
 pub(super) use types::{
     class_type_name, function_block_type_name, interface_type_name, lower_type_decls,
     lower_type_ref, predeclare_classes, predeclare_function_blocks, predeclare_interfaces,
-    resolve_named_type, resolve_type_name,
+    register_namespace_reexports, resolve_named_type, resolve_type_name,
 };
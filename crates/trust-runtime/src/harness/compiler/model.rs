@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use smol_str::SmolStr;
 
 use crate::debug::SourceLocation;
@@ -102,4 +104,50 @@ pub(crate) struct LoweringContext<'a> {
     pub(crate) using: Vec<SmolStr>,
     pub(crate) file_id: u32,
     pub(crate) statement_locations: &'a mut Vec<SourceLocation>,
+    /// Declared capacity of bounded STRING/WSTRING variables in scope (uppercase names).
+    pub(crate) string_bounds: HashMap<SmolStr, u32>,
+    /// Declared range of subrange-typed variables in scope (uppercase names).
+    pub(crate) subrange_bounds: HashMap<SmolStr, (i64, i64)>,
+}
+
+impl LoweringContext<'_> {
+    /// Records (or clears) the string capacity and subrange of freshly declared
+    /// variables.
+    pub(crate) fn declare_vars(&mut self, names: &[SmolStr], type_id: TypeId) {
+        let bound = self.string_capacity(type_id);
+        let range = crate::task::subrange_of_type(self.registry, type_id);
+        for name in names {
+            let key = SmolStr::new(name.to_ascii_uppercase());
+            match bound {
+                Some(bound) => self.string_bounds.insert(key.clone(), bound),
+                None => self.string_bounds.remove(&key),
+            };
+            match range {
+                Some(range) => self.subrange_bounds.insert(key, range),
+                None => self.subrange_bounds.remove(&key),
+            };
+        }
+    }
+
+    /// Returns the declared capacity of a bounded string variable.
+    pub(crate) fn string_bound(&self, name: &str) -> Option<u32> {
+        self.string_bounds
+            .get(name.to_ascii_uppercase().as_str())
+            .copied()
+    }
+
+    /// Returns the declared range of a subrange-typed variable.
+    pub(crate) fn subrange_bound(&self, name: &str) -> Option<(i64, i64)> {
+        self.subrange_bounds
+            .get(name.to_ascii_uppercase().as_str())
+            .copied()
+    }
+
+    fn string_capacity(&self, type_id: TypeId) -> Option<u32> {
+        match self.registry.get(type_id)? {
+            trust_hir::Type::String { max_len } | trust_hir::Type::WString { max_len } => *max_len,
+            trust_hir::Type::Alias { target, .. } => self.string_capacity(*target),
+            _ => None,
+        }
+    }
 }
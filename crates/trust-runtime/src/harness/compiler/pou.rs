@@ -1,3 +1,6 @@
+use std::collections::{HashMap, HashSet};
+
+use indexmap::IndexMap;
 use smol_str::SmolStr;
 use trust_hir::symbols::ParamDirection;
 use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
@@ -10,7 +13,7 @@ use crate::io::IoAddress;
 use crate::task::ProgramDef;
 use crate::value::DateTimeProfile;
 
-use super::super::lower::{lower_expr, lower_stmt_list};
+use super::super::lower::{lower_initializer, lower_stmt_list};
 use super::super::types::CompileError;
 use super::super::util::{collect_using_directives, node_text};
 use super::model::{GlobalInit, LoweredProgram, LoweringContext, ProgramVars};
@@ -53,13 +56,15 @@ pub(crate) fn lower_functions(
         .descendants()
         .filter(|child| child.kind() == SyntaxKind::Function)
     {
-        let using = collect_using_directives(&func_node);
+        let using = collect_using_directives(&func_node, registry);
         let mut ctx = LoweringContext {
             registry,
             profile,
             using,
             file_id,
             statement_locations,
+            string_bounds: HashMap::new(),
+            subrange_bounds: HashMap::new(),
         };
         functions.push(lower_function_node(&func_node, &mut ctx)?);
     }
@@ -78,13 +83,15 @@ pub(crate) fn lower_function_blocks(
         .descendants()
         .filter(|child| child.kind() == SyntaxKind::FunctionBlock)
     {
-        let using = collect_using_directives(&fb_node);
+        let using = collect_using_directives(&fb_node, registry);
         let mut ctx = LoweringContext {
             registry,
             profile,
             using,
             file_id,
             statement_locations,
+            string_bounds: HashMap::new(),
+            subrange_bounds: HashMap::new(),
         };
         function_blocks.push(lower_function_block_node(&fb_node, &mut ctx)?);
     }
@@ -103,13 +110,15 @@ pub(crate) fn lower_classes(
         .descendants()
         .filter(|child| child.kind() == SyntaxKind::Class)
     {
-        let using = collect_using_directives(&class_node);
+        let using = collect_using_directives(&class_node, registry);
         let mut ctx = LoweringContext {
             registry,
             profile,
             using,
             file_id,
             statement_locations,
+            string_bounds: HashMap::new(),
+            subrange_bounds: HashMap::new(),
         };
         classes.push(lower_class_node(&class_node, &mut ctx)?);
     }
@@ -128,19 +137,123 @@ pub(crate) fn lower_interfaces(
         .descendants()
         .filter(|child| child.kind() == SyntaxKind::Interface)
     {
-        let using = collect_using_directives(&interface_node);
+        let using = collect_using_directives(&interface_node, registry);
         let mut ctx = LoweringContext {
             registry,
             profile,
             using,
             file_id,
             statement_locations,
+            string_bounds: HashMap::new(),
+            subrange_bounds: HashMap::new(),
         };
         interfaces.push(lower_interface_node(&interface_node, &mut ctx)?);
     }
     Ok(interfaces)
 }
 
+/// Collects the interface methods with a default body that each CLASS or FUNCTION_BLOCK
+/// inherits through IMPLEMENTS, keyed by the upper-case owner name.
+///
+/// Methods the owner declares itself are left out; base interfaces are followed.
+pub(crate) fn interface_default_methods(
+    syntaxes: &[SyntaxNode],
+    registry: &trust_hir::types::TypeRegistry,
+    interfaces: &IndexMap<SmolStr, InterfaceDef>,
+) -> Result<HashMap<SmolStr, Vec<MethodDef>>, CompileError> {
+    let mut defaults = HashSet::new();
+    for method_node in syntaxes
+        .iter()
+        .flat_map(|syntax| syntax.descendants())
+        .filter(|node| node.kind() == SyntaxKind::Method)
+    {
+        let Some(interface_node) = method_node
+            .parent()
+            .filter(|parent| parent.kind() == SyntaxKind::Interface)
+        else {
+            continue;
+        };
+        if !method_node
+            .children()
+            .any(|child| child.kind() == SyntaxKind::StmtList)
+        {
+            continue;
+        }
+        let Some(name_node) = method_node
+            .children()
+            .find(|child| child.kind() == SyntaxKind::Name)
+        else {
+            continue;
+        };
+        let interface = qualified_pou_name(&interface_node)?;
+        let method = qualify_with_namespaces(&method_node, &node_text(&name_node));
+        defaults.insert((interface.to_ascii_uppercase(), method.to_ascii_uppercase()));
+    }
+
+    let mut inherited = HashMap::new();
+    if defaults.is_empty() {
+        return Ok(inherited);
+    }
+    for owner_node in syntaxes
+        .iter()
+        .flat_map(|syntax| syntax.descendants())
+        .filter(|node| matches!(node.kind(), SyntaxKind::Class | SyntaxKind::FunctionBlock))
+    {
+        let Some(clause) = owner_node
+            .children()
+            .find(|child| child.kind() == SyntaxKind::ImplementsClause)
+        else {
+            continue;
+        };
+        let using = collect_using_directives(&owner_node, registry);
+        let mut seen: HashSet<String> = owner_node
+            .children()
+            .filter(|child| child.kind() == SyntaxKind::Method)
+            .filter_map(|child| {
+                let name_node = child
+                    .children()
+                    .find(|name| name.kind() == SyntaxKind::Name)?;
+                Some(qualify_with_namespaces(&child, &node_text(&name_node)).to_ascii_uppercase())
+            })
+            .collect();
+        let mut methods = Vec::new();
+        for name_node in clause
+            .children()
+            .filter(|child| matches!(child.kind(), SyntaxKind::Name | SyntaxKind::QualifiedName))
+        {
+            let mut current = Some(resolve_named_type(
+                registry,
+                &node_text(&name_node),
+                &using,
+            )?);
+            let mut visited = HashSet::new();
+            while let Some(interface_name) = current.take() {
+                let key = interface_name.to_ascii_uppercase();
+                if !visited.insert(key.clone()) {
+                    break;
+                }
+                let Some(interface_def) = interfaces.get(key.as_str()) else {
+                    break;
+                };
+                for method in &interface_def.methods {
+                    let method_key = method.name.to_ascii_uppercase();
+                    if defaults.contains(&(key.clone(), method_key.clone()))
+                        && seen.insert(method_key)
+                    {
+                        methods.push(method.clone());
+                    }
+                }
+                current = interface_def.base.clone();
+            }
+        }
+        if !methods.is_empty() {
+            let owner = qualified_pou_name(&owner_node)?;
+            inherited.insert(SmolStr::new(owner.to_ascii_uppercase()), methods);
+        }
+    }
+    Ok(inherited)
+}
+
 fn lower_program_node(
     program_node: &SyntaxNode,
     registry: &mut trust_hir::types::TypeRegistry,
@@ -149,13 +262,15 @@ fn lower_program_node(
     statement_locations: &mut Vec<crate::debug::SourceLocation>,
 ) -> Result<LoweredProgram, CompileError> {
     let name = qualified_pou_name(program_node)?;
-    let using = collect_using_directives(program_node);
+    let using = collect_using_directives(program_node, registry);
     let mut ctx = LoweringContext {
         registry,
         profile,
         using,
         file_id,
         statement_locations,
+        string_bounds: HashMap::new(),
+        subrange_bounds: HashMap::new(),
     };
     let vars = lower_program_var_blocks(program_node, &mut ctx)?;
     let body = lower_stmt_list(program_node, &mut ctx)?;
@@ -334,13 +449,15 @@ fn lower_method_node(
     let raw = node_text(&name_node);
     let name = qualify_with_namespaces(node, &raw);
 
-    let using = collect_using_directives(node);
+    let using = collect_using_directives(node, ctx.registry);
     let mut method_ctx = LoweringContext {
         registry: ctx.registry,
         profile: ctx.profile,
         using,
         file_id: ctx.file_id,
         statement_locations: ctx.statement_locations,
+        string_bounds: ctx.string_bounds.clone(),
+        subrange_bounds: ctx.subrange_bounds.clone(),
     };
 
     let return_type = node
@@ -373,9 +490,10 @@ pub(crate) fn qualified_pou_name(node: &SyntaxNode) -> Result<SmolStr, CompileEr
         if ancestor.kind() != SyntaxKind::Namespace {
             continue;
         }
+        // `NAMESPACE A.B` contributes both parts through its qualified name.
         if let Some(ns_name) = ancestor
             .children()
-            .find(|child| child.kind() == SyntaxKind::Name)
+            .find(|child| matches!(child.kind(), SyntaxKind::Name | SyntaxKind::QualifiedName))
         {
             parts.push(node_text(&ns_name));
         }
@@ -403,7 +521,10 @@ fn lower_program_var_blocks(
         {
             let (names, type_ref, initializer, address) = parse_var_decl(&var_decl)?;
             let type_id = lower_type_ref(&type_ref, ctx)?;
-            let init_expr = initializer.map(|expr| lower_expr(&expr, ctx)).transpose()?;
+            ctx.declare_vars(&names, type_id);
+            let init_expr = initializer
+                .map(|expr| lower_initializer(&expr, type_id, ctx))
+                .transpose()?;
             let address_info = address
                 .as_ref()
                 .map(|text| IoAddress::parse(text))
@@ -497,7 +618,10 @@ fn lower_function_var_blocks(
         {
             let (names, type_ref, initializer, address) = parse_var_decl(&var_decl)?;
             let type_id = lower_type_ref(&type_ref, ctx)?;
-            let init_expr = initializer.map(|expr| lower_expr(&expr, ctx)).transpose()?;
+            ctx.declare_vars(&names, type_id);
+            let init_expr = initializer
+                .map(|expr| lower_initializer(&expr, type_id, ctx))
+                .transpose()?;
             let address_info = address
                 .as_ref()
                 .map(|text| IoAddress::parse(text))
@@ -591,7 +715,10 @@ fn lower_class_var_blocks(
         {
             let (names, type_ref, initializer, address) = parse_var_decl(&var_decl)?;
             let type_id = lower_type_ref(&type_ref, ctx)?;
-            let init_expr = initializer.map(|expr| lower_expr(&expr, ctx)).transpose()?;
+            ctx.declare_vars(&names, type_id);
+            let init_expr = initializer
+                .map(|expr| lower_initializer(&expr, type_id, ctx))
+                .transpose()?;
             let address_info = address
                 .as_ref()
                 .map(|text| IoAddress::parse(text))
@@ -655,7 +782,10 @@ fn lower_function_block_var_blocks(
         {
             let (names, type_ref, initializer, address) = parse_var_decl(&var_decl)?;
             let type_id = lower_type_ref(&type_ref, ctx)?;
-            let init_expr = initializer.map(|expr| lower_expr(&expr, ctx)).transpose()?;
+            ctx.declare_vars(&names, type_id);
+            let init_expr = initializer
+                .map(|expr| lower_initializer(&expr, type_id, ctx))
+                .transpose()?;
             let address_info = address
                 .as_ref()
                 .map(|text| IoAddress::parse(text))
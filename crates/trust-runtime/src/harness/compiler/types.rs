@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use smol_str::SmolStr;
 use trust_hir::{Type, TypeId};
 use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
@@ -9,6 +11,7 @@ use super::super::lower::{const_int_from_node, parse_subrange};
 use super::super::types::CompileError;
 use super::super::util::{
     builtin_type_name, collect_using_directives, is_expression_kind, node_text,
+    using_directive_names,
 };
 use super::model::LoweringContext;
 use super::qualified_pou_name;
@@ -92,13 +95,15 @@ fn lower_type_decl_node(
     file_id: u32,
     statement_locations: &mut Vec<SourceLocation>,
 ) -> Result<(), CompileError> {
-    let using = collect_using_directives(node);
+    let using = collect_using_directives(node, registry);
     let mut ctx = LoweringContext {
         registry,
         profile,
         using,
         file_id,
         statement_locations,
+        string_bounds: HashMap::new(),
+        subrange_bounds: HashMap::new(),
     };
     let mut pending_name: Option<SmolStr> = None;
     for child in node.children() {
@@ -270,9 +275,10 @@ pub(crate) fn qualify_with_namespaces(node: &SyntaxNode, name: &str) -> SmolStr
         if ancestor.kind() != SyntaxKind::Namespace {
             continue;
         }
+        // `NAMESPACE A.B` contributes both parts through its qualified name.
         if let Some(ns_name) = ancestor
             .children()
-            .find(|child| child.kind() == SyntaxKind::Name)
+            .find(|child| matches!(child.kind(), SyntaxKind::Name | SyntaxKind::QualifiedName))
         {
             parts.push(node_text(&ns_name));
         }
@@ -281,6 +287,91 @@ pub(crate) fn qualify_with_namespaces(node: &SyntaxNode, name: &str) -> SmolStr
     parts.join(".").into()
 }
 
+/// Registers the type and POU names re-exported by `USING PUBLIC` as qualified aliases.
+///
+/// `NAMESPACE Facade USING PUBLIC Impl;` makes `Facade.X` an alias of every declared
+/// `Impl.X`; chains of re-exports are followed.
+pub(crate) fn register_namespace_reexports(
+    syntaxes: &[SyntaxNode],
+    registry: &mut trust_hir::types::TypeRegistry,
+) {
+    let mut declared = Vec::new();
+    let mut reexports = Vec::new();
+    for syntax in syntaxes {
+        for node in syntax.descendants() {
+            match node.kind() {
+                SyntaxKind::Function
+                | SyntaxKind::FunctionBlock
+                | SyntaxKind::Class
+                | SyntaxKind::Interface => {
+                    if let Ok(name) = qualified_pou_name(&node) {
+                        declared.push(name.to_ascii_uppercase());
+                    }
+                }
+                SyntaxKind::TypeDecl => {
+                    for name in node
+                        .children()
+                        .filter(|child| child.kind() == SyntaxKind::Name)
+                    {
+                        let name = qualify_with_namespaces(&node, &node_text(&name));
+                        declared.push(name.to_ascii_uppercase());
+                    }
+                }
+                SyntaxKind::UsingDirective => {
+                    let public = node
+                        .children_with_tokens()
+                        .any(|element| element.kind() == SyntaxKind::KwPublic);
+                    let Some(namespace) = node.parent() else {
+                        continue;
+                    };
+                    if !public || namespace.kind() != SyntaxKind::Namespace {
+                        continue;
+                    }
+                    let alias = qualify_with_namespaces(&namespace, "");
+                    let alias = alias.trim_end_matches('.').to_ascii_uppercase();
+                    for target in using_directive_names(&node) {
+                        reexports.push((alias.clone(), target.to_ascii_uppercase()));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let declared_set: std::collections::HashSet<String> = declared.iter().cloned().collect();
+    let mut names = declared;
+    // Each pass follows one more re-export hop; the bound only guards against cycles.
+    for _ in 0..8 {
+        let mut added = Vec::new();
+        for name in &names {
+            for (alias, target) in &reexports {
+                let Some(member) = name
+                    .strip_prefix(target.as_str())
+                    .and_then(|rest| rest.strip_prefix('.'))
+                else {
+                    continue;
+                };
+                let aliased = format!("{alias}.{member}");
+                if declared_set.contains(&aliased)
+                    || registry.resolve_qualified_alias(&aliased).is_some()
+                {
+                    continue;
+                }
+                let declared_name = registry
+                    .resolve_qualified_alias(name)
+                    .map(|name| name.to_string())
+                    .unwrap_or_else(|| name.clone());
+                registry.register_qualified_alias(&aliased, &declared_name);
+                added.push(aliased);
+            }
+        }
+        if added.is_empty() {
+            break;
+        }
+        names.extend(added);
+    }
+}
+
 pub(crate) fn lower_type_ref(
     node: &SyntaxNode,
     ctx: &mut LoweringContext<'_>,
@@ -396,6 +396,12 @@ pub(super) fn apply_globals(
             loop_depth: 0,
             pause_requested: false,
             execution_deadline: None,
+            workers: None,
+            bounds: crate::task::BoundsPolicy::Fault,
+            overflow: crate::eval::ops::OverflowPolicy::Wrap,
+            subrange: crate::task::SubrangePolicy::Off,
+            fault_site: None,
+            pou_code: None,
         };
 
         for init in globals {
@@ -676,6 +682,12 @@ pub(super) fn apply_config_inits(
                 loop_depth: 0,
                 pause_requested: false,
                 execution_deadline: None,
+                workers: None,
+                bounds: crate::task::BoundsPolicy::Fault,
+                overflow: crate::eval::ops::OverflowPolicy::Wrap,
+                subrange: crate::task::SubrangePolicy::Off,
+                fault_site: None,
+                pou_code: None,
             };
             let value = eval_expr(&mut ctx, expr)
                 .map_err(|err| CompileError::new(format!("VAR_CONFIG initializer error: {err}")))?;
@@ -42,6 +42,19 @@ pub(super) fn bind_value_ref_to_address(
     address: &IoAddress,
     display_name: Option<SmolStr>,
 ) -> Result<(), CompileError> {
+    // An elementary variable takes its size from its type, so a narrower
+    // address would spill into the neighbouring channels.
+    if let Ok(size) = io_size_for_type(type_id, registry) {
+        if !address.wildcard && size != address.size {
+            return Err(CompileError::new(format!(
+                "'{}' at {} is declared as a {} address but its type needs a {}",
+                display_name.as_deref().unwrap_or("<unnamed>"),
+                crate::io::format_io_address(address),
+                io_size_name(address.size),
+                io_size_name(size),
+            )));
+        }
+    }
     let mut bindings = Vec::new();
     collect_io_bindings(registry, type_id, reference, 0, 0, &mut bindings)?;
     for binding in bindings {
@@ -709,6 +722,16 @@ fn io_size_for_type(
     }
 }
 
+fn io_size_name(size: crate::io::IoSize) -> &'static str {
+    match size {
+        crate::io::IoSize::Bit => "BOOL (X)",
+        crate::io::IoSize::Byte => "BYTE (B)",
+        crate::io::IoSize::Word => "WORD (W)",
+        crate::io::IoSize::DWord => "DWORD (D)",
+        crate::io::IoSize::LWord => "LWORD (L)",
+    }
+}
+
 fn leaf_value_type(type_id: TypeId, registry: &TypeRegistry) -> Result<TypeId, CompileError> {
     let ty = registry
         .get(type_id)
@@ -1,18 +1,15 @@
 use smol_str::SmolStr;
 
-use crate::datetime::{
-    days_from_civil, days_to_ticks, nanos_to_ticks, DateTimeCalcError, DivisionMode, NANOS_PER_DAY,
-};
 use crate::eval::expr::{Expr, LValue};
 use crate::eval::ops::{BinaryOp, UnaryOp};
 use crate::eval::{eval_expr, ArgValue, CallArg, EvalContext};
 use crate::memory::VariableStorage;
 use crate::value::{
-    DateTimeProfile, DateTimeValue, DateValue, Duration, EnumValue, LDateTimeValue, LDateValue,
-    LTimeOfDayValue, TimeOfDayValue, Value,
+    default_value_for_type_id, parse_date_literal, parse_dt_literal, parse_duration_literal,
+    parse_tod_literal, Duration, EnumValue, TimeLiteralError, Value,
 };
 use trust_hir::types::TypeRegistry;
-use trust_hir::TypeId;
+use trust_hir::{Type, TypeId};
 use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
 
 use super::super::util::{direct_expr_children, first_expr_child, is_expression_kind, node_text};
@@ -154,13 +151,204 @@ pub(in crate::harness) fn lower_expr(
         }
         SyntaxKind::CallExpr => lower_call_expr(node, ctx),
         SyntaxKind::SizeOfExpr => lower_sizeof_expr(node, ctx),
-        SyntaxKind::ArrayInitializer | SyntaxKind::InitializerList => {
-            Err(CompileError::new("initializer lists are not supported yet"))
-        }
+        SyntaxKind::ArrayInitializer | SyntaxKind::InitializerList => Err(CompileError::new(
+            "initializer lists are only allowed in declarations",
+        )),
         _ => Err(CompileError::new("unsupported expression")),
     }
 }
 
+/// Lowers a declaration initializer for a variable of `type_id`. Structured and
+/// array initializers whose values are all constant are folded into one literal.
+pub(in crate::harness) fn lower_initializer(
+    node: &SyntaxNode,
+    type_id: TypeId,
+    ctx: &mut LoweringContext<'_>,
+) -> Result<Expr, CompileError> {
+    if !matches!(
+        node.kind(),
+        SyntaxKind::InitializerList | SyntaxKind::ArrayInitializer
+    ) {
+        return lower_expr(node, ctx);
+    }
+    let expr = lower_aggregate_initializer(node, type_id, ctx)?;
+    Ok(fold_constant_initializer(expr, ctx))
+}
+
+fn lower_aggregate_initializer(
+    node: &SyntaxNode,
+    type_id: TypeId,
+    ctx: &mut LoweringContext<'_>,
+) -> Result<Expr, CompileError> {
+    match node.kind() {
+        SyntaxKind::InitializerList => lower_initializer_list(node, type_id, ctx),
+        SyntaxKind::ArrayInitializer => lower_array_initializer(node, type_id, ctx),
+        _ => lower_expr(node, ctx),
+    }
+}
+
+fn lower_initializer_list(
+    node: &SyntaxNode,
+    type_id: TypeId,
+    ctx: &mut LoweringContext<'_>,
+) -> Result<Expr, CompileError> {
+    let fields = match resolve_alias(type_id, ctx.registry) {
+        Some(Type::Struct { fields, .. }) => fields.clone(),
+        _ => return Err(CompileError::new("initializer list requires a STRUCT type")),
+    };
+    let mut lowered: Vec<(SmolStr, TypeId, Expr)> = Vec::new();
+    for entry in node
+        .children()
+        .filter(|child| child.kind() == SyntaxKind::FieldInitializer)
+    {
+        let name_node = entry
+            .children()
+            .find(|child| child.kind() == SyntaxKind::Name)
+            .ok_or_else(|| CompileError::new("missing field name in initializer"))?;
+        let name = node_text(&name_node);
+        let field = fields
+            .iter()
+            .find(|field| field.name.eq_ignore_ascii_case(&name))
+            .ok_or_else(|| CompileError::new(format!("unknown field '{name}' in initializer")))?;
+        if lowered.iter().any(|(existing, ..)| *existing == field.name) {
+            return Err(CompileError::new(format!(
+                "field '{name}' is initialized more than once"
+            )));
+        }
+        let value = entry
+            .children()
+            .find(|child| child.kind() != SyntaxKind::Name)
+            .ok_or_else(|| CompileError::new(format!("missing value for field '{name}'")))?;
+        let value = lower_aggregate_initializer(&value, field.type_id, ctx)?;
+        lowered.push((field.name.clone(), field.type_id, value));
+    }
+    Ok(Expr::StructInit {
+        type_id,
+        fields: lowered,
+    })
+}
+
+fn lower_array_initializer(
+    node: &SyntaxNode,
+    type_id: TypeId,
+    ctx: &mut LoweringContext<'_>,
+) -> Result<Expr, CompileError> {
+    let (element_type, capacity) = match resolve_alias(type_id, ctx.registry) {
+        Some(Type::Array {
+            element,
+            dimensions,
+        }) => {
+            let capacity = dimensions
+                .iter()
+                .map(|(lower, upper)| usize::try_from(upper - lower + 1).unwrap_or(0))
+                .product::<usize>();
+            (*element, capacity)
+        }
+        _ => {
+            return Err(CompileError::new(
+                "array initializer requires an ARRAY type",
+            ))
+        }
+    };
+    let mut elements = Vec::new();
+    lower_array_elements(node.children(), element_type, capacity, ctx, &mut elements)?;
+    Ok(Expr::ArrayInit {
+        type_id,
+        element_type,
+        elements,
+    })
+}
+
+/// Flattens array initializer elements, expanding `n(...)` repetitions. Values
+/// beyond `capacity` are ignored.
+fn lower_array_elements(
+    elements: impl Iterator<Item = SyntaxNode>,
+    element_type: TypeId,
+    capacity: usize,
+    ctx: &mut LoweringContext<'_>,
+    out: &mut Vec<Expr>,
+) -> Result<(), CompileError> {
+    for child in elements {
+        if out.len() >= capacity {
+            break;
+        }
+        if child.kind() != SyntaxKind::RepeatInitializer {
+            out.push(lower_aggregate_initializer(&child, element_type, ctx)?);
+            continue;
+        }
+        let count_node = child
+            .children()
+            .next()
+            .ok_or_else(|| CompileError::new("missing repetition count"))?;
+        let count = usize::try_from(const_int_from_node(&count_node, ctx)?)
+            .map_err(|_| CompileError::new("negative repetition count in array initializer"))?;
+        let mut repeated = Vec::new();
+        lower_array_elements(
+            child.children().skip(1),
+            element_type,
+            capacity,
+            ctx,
+            &mut repeated,
+        )?;
+        if repeated.is_empty() {
+            let value = default_value_for_type_id(element_type, ctx.registry, &ctx.profile)
+                .map_err(|err| CompileError::new(format!("default value error: {err:?}")))?;
+            repeated.push(Expr::Literal(value));
+        }
+        let remaining = capacity - out.len();
+        out.extend(
+            repeated
+                .iter()
+                .cycle()
+                .take(count.saturating_mul(repeated.len()).min(remaining))
+                .cloned(),
+        );
+    }
+    Ok(())
+}
+
+/// Evaluates an initializer up front when it only uses constants, so it is stored
+/// as a literal and can be emitted into the bytecode constant pool.
+fn fold_constant_initializer(expr: Expr, ctx: &mut LoweringContext<'_>) -> Expr {
+    let mut storage = VariableStorage::default();
+    let mut eval_ctx = EvalContext {
+        storage: &mut storage,
+        registry: ctx.registry,
+        profile: ctx.profile,
+        now: Duration::ZERO,
+        debug: None,
+        call_depth: 0,
+        functions: None,
+        stdlib: None,
+        function_blocks: None,
+        classes: None,
+        using: Some(&ctx.using),
+        access: None,
+        current_instance: None,
+        return_name: None,
+        loop_depth: 0,
+        pause_requested: false,
+        execution_deadline: None,
+        workers: None,
+        bounds: crate::task::BoundsPolicy::Fault,
+        overflow: crate::eval::ops::OverflowPolicy::Wrap,
+        subrange: crate::task::SubrangePolicy::Off,
+        fault_site: None,
+        pou_code: None,
+    };
+    match eval_expr(&mut eval_ctx, &expr) {
+        Ok(value) => Expr::Literal(value),
+        Err(_) => expr,
+    }
+}
+
+fn resolve_alias(type_id: TypeId, registry: &TypeRegistry) -> Option<&Type> {
+    match registry.get(type_id)? {
+        Type::Alias { target, .. } => resolve_alias(*target, registry),
+        ty => Some(ty),
+    }
+}
+
 fn lower_sizeof_expr(
     node: &SyntaxNode,
     ctx: &mut LoweringContext<'_>,
@@ -186,7 +374,14 @@ fn lower_sizeof_expr(
 
 fn lower_call_expr(node: &SyntaxNode, ctx: &mut LoweringContext<'_>) -> Result<Expr, CompileError> {
     let target = first_expr_child(node).ok_or_else(|| CompileError::new("missing call target"))?;
-    let target = lower_expr(&target, ctx)?;
+    // Calls through a re-exporting namespace go straight to the declared POU.
+    let reexported = (target.kind() == SyntaxKind::FieldExpr)
+        .then(|| ctx.registry.resolve_qualified_alias(&node_text(&target)))
+        .flatten();
+    let target = match reexported {
+        Some(declared) => Expr::Name(declared),
+        None => lower_expr(&target, ctx)?,
+    };
     let args = lower_call_args(node, ctx)?;
     Ok(Expr::Call {
         target: Box::new(target),
@@ -293,16 +488,19 @@ fn lower_literal(node: &SyntaxNode, ctx: &LoweringContext<'_>) -> Result<Expr, C
                 string_literal = Some((parsed, true));
             }
             SyntaxKind::TimeLiteral => {
-                value_literal = Some(parse_time_literal(token.text())?);
+                value_literal = Some(parse_duration_literal(token.text()).map_err(literal_error)?);
             }
             SyntaxKind::DateLiteral => {
-                value_literal = Some(parse_date_literal(token.text(), ctx.profile)?);
+                value_literal =
+                    Some(parse_date_literal(token.text(), ctx.profile).map_err(literal_error)?);
             }
             SyntaxKind::TimeOfDayLiteral => {
-                value_literal = Some(parse_tod_literal(token.text(), ctx.profile)?);
+                value_literal =
+                    Some(parse_tod_literal(token.text(), ctx.profile).map_err(literal_error)?);
             }
             SyntaxKind::DateAndTimeLiteral => {
-                value_literal = Some(parse_dt_literal(token.text(), ctx.profile)?);
+                value_literal =
+                    Some(parse_dt_literal(token.text(), ctx.profile).map_err(literal_error)?);
             }
             SyntaxKind::Ident => {
                 ident_literal = Some(token.text().to_string());
@@ -396,8 +594,12 @@ fn parse_string_literal(text: &str, is_wide: bool) -> Result<String, CompileErro
     let end = bytes.len() - 1;
     while i < end {
         if bytes[i] != b'$' {
-            result.push(bytes[i] as char);
-            i += 1;
+            let ch = text[i..]
+                .chars()
+                .next()
+                .ok_or_else(|| CompileError::new("invalid string literal"))?;
+            result.push(ch);
+            i += ch.len_utf8();
             continue;
         }
         if i + 1 >= end {
@@ -451,263 +653,8 @@ fn parse_string_literal(text: &str, is_wide: bool) -> Result<String, CompileErro
     Ok(result)
 }
 
-fn parse_time_literal(text: &str) -> Result<Value, CompileError> {
-    let is_long = is_long_time_literal(text);
-    let nanos = parse_duration_nanos(text)?;
-    let duration = Duration::from_nanos(nanos);
-    Ok(if is_long {
-        Value::LTime(duration)
-    } else {
-        Value::Time(duration)
-    })
-}
-
-fn parse_date_literal(text: &str, profile: DateTimeProfile) -> Result<Value, CompileError> {
-    let is_long = is_long_date_literal(text);
-    let (year, month, day) = parse_date_parts(text)?;
-    let days = days_from_civil_checked(year, month, day)?;
-    if is_long {
-        let nanos = days
-            .checked_mul(NANOS_PER_DAY)
-            .ok_or_else(|| CompileError::new("date out of range"))?;
-        return Ok(Value::LDate(LDateValue::new(nanos)));
-    }
-    let ticks = days_to_ticks_checked(days, profile)?;
-    Ok(Value::Date(DateValue::new(ticks)))
-}
-
-fn parse_tod_literal(text: &str, profile: DateTimeProfile) -> Result<Value, CompileError> {
-    let is_long = is_long_tod_literal(text);
-    let nanos = parse_time_of_day_nanos(text)?;
-    if is_long {
-        return Ok(Value::LTod(LTimeOfDayValue::new(nanos)));
-    }
-    let ticks = nanos_to_ticks_checked(nanos, profile)?;
-    Ok(Value::Tod(TimeOfDayValue::new(ticks)))
-}
-
-fn parse_dt_literal(text: &str, profile: DateTimeProfile) -> Result<Value, CompileError> {
-    let is_long = is_long_dt_literal(text);
-    let (date_part, tod_part) = parse_dt_parts(text)?;
-    let (year, month, day) = parse_date_parts(date_part)?;
-    let days = days_from_civil_checked(year, month, day)?;
-    let nanos_tod = parse_time_of_day_nanos(tod_part)?;
-    if is_long {
-        let date_nanos = days
-            .checked_mul(NANOS_PER_DAY)
-            .ok_or_else(|| CompileError::new("date out of range"))?;
-        let nanos = date_nanos
-            .checked_add(nanos_tod)
-            .ok_or_else(|| CompileError::new("date/time out of range"))?;
-        return Ok(Value::Ldt(LDateTimeValue::new(nanos)));
-    }
-    let date_ticks = days_to_ticks_checked(days, profile)?;
-    let tod_ticks = nanos_to_ticks_checked(nanos_tod, profile)?;
-    let ticks = date_ticks
-        .checked_add(tod_ticks)
-        .ok_or_else(|| CompileError::new("date/time out of range"))?;
-    Ok(Value::Dt(DateTimeValue::new(ticks)))
-}
-
-fn days_from_civil_checked(year: i64, month: i64, day: i64) -> Result<i64, CompileError> {
-    match days_from_civil(year, month, day) {
-        Ok(days) => Ok(days),
-        Err(DateTimeCalcError::InvalidDate) => Err(CompileError::new("invalid date")),
-        Err(_) => Err(CompileError::new("invalid date")),
-    }
-}
-
-fn days_to_ticks_checked(days: i64, profile: DateTimeProfile) -> Result<i64, CompileError> {
-    match days_to_ticks(days, profile) {
-        Ok(ticks) => Ok(ticks),
-        Err(DateTimeCalcError::InvalidResolution) => {
-            Err(CompileError::new("invalid time resolution"))
-        }
-        Err(DateTimeCalcError::Overflow) => Err(CompileError::new("date out of range")),
-        Err(DateTimeCalcError::InvalidDate) => Err(CompileError::new("invalid date")),
-    }
-}
-
-fn nanos_to_ticks_checked(nanos: i64, profile: DateTimeProfile) -> Result<i64, CompileError> {
-    match nanos_to_ticks(nanos, profile, DivisionMode::Trunc) {
-        Ok(ticks) => Ok(ticks),
-        Err(DateTimeCalcError::InvalidResolution) => {
-            Err(CompileError::new("invalid time resolution"))
-        }
-        Err(_) => Err(CompileError::new("invalid time resolution")),
-    }
-}
-
-fn parse_duration_nanos(text: &str) -> Result<i64, CompileError> {
-    let upper = text.to_ascii_uppercase();
-    let (_, raw) = upper
-        .split_once('#')
-        .ok_or_else(|| CompileError::new("invalid TIME literal"))?;
-    let mut rest = raw.trim();
-    let mut sign: f64 = 1.0;
-    if let Some(stripped) = rest.strip_prefix('-') {
-        sign = -1.0;
-        rest = stripped;
-    } else if let Some(stripped) = rest.strip_prefix('+') {
-        rest = stripped;
-    }
-
-    let bytes = rest.as_bytes();
-    let mut idx = 0usize;
-    let mut total: f64 = 0.0;
-    while idx < bytes.len() {
-        let start = idx;
-        while idx < bytes.len()
-            && (bytes[idx].is_ascii_digit() || bytes[idx] == b'_' || bytes[idx] == b'.')
-        {
-            idx += 1;
-        }
-        if start == idx {
-            return Err(CompileError::new("invalid TIME literal"));
-        }
-        let num_str: String = rest[start..idx].chars().filter(|c| *c != '_').collect();
-        let value = num_str
-            .parse::<f64>()
-            .map_err(|_| CompileError::new("invalid TIME literal"))?;
-        let unit_start = idx;
-        while idx < bytes.len() && bytes[idx].is_ascii_alphabetic() {
-            idx += 1;
-        }
-        let unit = &rest[unit_start..idx];
-        let nanos_per = match unit {
-            "D" => 86_400_000_000_000.0,
-            "H" => 3_600_000_000_000.0,
-            "M" => 60_000_000_000.0,
-            "S" => 1_000_000_000.0,
-            "MS" => 1_000_000.0,
-            "US" => 1_000.0,
-            "NS" => 1.0,
-            _ => return Err(CompileError::new("invalid TIME literal unit")),
-        };
-        total += value * nanos_per;
-        while idx < bytes.len() && bytes[idx] == b'_' {
-            idx += 1;
-        }
-    }
-    let nanos = (total * sign).round();
-    let nanos =
-        i64::try_from(nanos as i128).map_err(|_| CompileError::new("TIME literal out of range"))?;
-    Ok(nanos)
-}
-
-fn parse_date_parts(text: &str) -> Result<(i64, i64, i64), CompileError> {
-    let rest = match text.split_once('#') {
-        Some((_, rest)) => rest,
-        None => text,
-    };
-    let mut parts = rest.split('-');
-    let year = parts
-        .next()
-        .ok_or_else(|| CompileError::new("invalid DATE literal"))?
-        .parse::<i64>()
-        .map_err(|_| CompileError::new("invalid DATE literal"))?;
-    let month = parts
-        .next()
-        .ok_or_else(|| CompileError::new("invalid DATE literal"))?
-        .parse::<i64>()
-        .map_err(|_| CompileError::new("invalid DATE literal"))?;
-    let day = parts
-        .next()
-        .ok_or_else(|| CompileError::new("invalid DATE literal"))?
-        .parse::<i64>()
-        .map_err(|_| CompileError::new("invalid DATE literal"))?;
-    Ok((year, month, day))
-}
-
-fn parse_time_of_day_nanos(text: &str) -> Result<i64, CompileError> {
-    let rest = match text.split_once('#') {
-        Some((_, rest)) => rest,
-        None => text,
-    };
-    let mut parts = rest.split(':');
-    let hours = parts
-        .next()
-        .ok_or_else(|| CompileError::new("invalid TOD literal"))?
-        .parse::<i64>()
-        .map_err(|_| CompileError::new("invalid TOD literal"))?;
-    let minutes = parts
-        .next()
-        .ok_or_else(|| CompileError::new("invalid TOD literal"))?
-        .parse::<i64>()
-        .map_err(|_| CompileError::new("invalid TOD literal"))?;
-    let seconds_part = parts
-        .next()
-        .ok_or_else(|| CompileError::new("invalid TOD literal"))?;
-    let (seconds, nanos) = parse_seconds_fraction(seconds_part)?;
-    let total = hours
-        .checked_mul(3_600)
-        .and_then(|v| v.checked_add(minutes.checked_mul(60)?))
-        .and_then(|v| v.checked_add(seconds))
-        .ok_or_else(|| CompileError::new("invalid TOD literal"))?;
-    let total_nanos = total
-        .checked_mul(1_000_000_000)
-        .and_then(|v| v.checked_add(nanos))
-        .ok_or_else(|| CompileError::new("invalid TOD literal"))?;
-    Ok(total_nanos)
-}
-
-fn parse_dt_parts(text: &str) -> Result<(&str, &str), CompileError> {
-    let (_, rest) = text
-        .split_once('#')
-        .ok_or_else(|| CompileError::new("invalid DT literal"))?;
-    let (date_part, time_part) = rest
-        .rsplit_once('-')
-        .ok_or_else(|| CompileError::new("invalid DT literal"))?;
-    Ok((date_part, time_part))
-}
-
-fn parse_seconds_fraction(text: &str) -> Result<(i64, i64), CompileError> {
-    let mut parts = text.split('.');
-    let secs = parts
-        .next()
-        .ok_or_else(|| CompileError::new("invalid time literal"))?
-        .parse::<i64>()
-        .map_err(|_| CompileError::new("invalid time literal"))?;
-    let nanos = if let Some(frac) = parts.next() {
-        let digits: String = frac.chars().filter(|c| *c != '_').collect();
-        if digits.is_empty() {
-            0
-        } else {
-            let mut padded = digits;
-            if padded.len() > 9 {
-                padded.truncate(9);
-            }
-            while padded.len() < 9 {
-                padded.push('0');
-            }
-            padded
-                .parse::<i64>()
-                .map_err(|_| CompileError::new("invalid time fraction"))?
-        }
-    } else {
-        0
-    };
-    Ok((secs, nanos))
-}
-
-fn is_long_time_literal(text: &str) -> bool {
-    let upper = text.to_ascii_uppercase();
-    upper.starts_with("LT#") || upper.starts_with("LTIME#")
-}
-
-fn is_long_date_literal(text: &str) -> bool {
-    let upper = text.to_ascii_uppercase();
-    upper.starts_with("LDATE#") || upper.starts_with("LD#")
-}
-
-fn is_long_tod_literal(text: &str) -> bool {
-    let upper = text.to_ascii_uppercase();
-    upper.starts_with("LTOD#") || upper.starts_with("LTIME_OF_DAY#")
-}
-
-fn is_long_dt_literal(text: &str) -> bool {
-    let upper = text.to_ascii_uppercase();
-    upper.starts_with("LDT#") || upper.starts_with("LDATE_AND_TIME#")
+fn literal_error(err: TimeLiteralError) -> CompileError {
+    CompileError::new(err.0)
 }
 
 fn enum_literal_value(name: &str, type_id: TypeId, registry: &TypeRegistry) -> Option<Value> {
@@ -828,6 +775,12 @@ pub(in crate::harness) fn const_int_from_node(
         loop_depth: 0,
         pause_requested: false,
         execution_deadline: None,
+        workers: None,
+        bounds: crate::task::BoundsPolicy::Fault,
+        overflow: crate::eval::ops::OverflowPolicy::Wrap,
+        subrange: crate::task::SubrangePolicy::Off,
+        fault_site: None,
+        pou_code: None,
     };
     let value =
         eval_expr(&mut eval_ctx, &expr).map_err(|err| CompileError::new(err.to_string()))?;
@@ -877,6 +830,12 @@ pub(in crate::harness) fn const_duration_from_node(
         loop_depth: 0,
         pause_requested: false,
         execution_deadline: None,
+        workers: None,
+        bounds: crate::task::BoundsPolicy::Fault,
+        overflow: crate::eval::ops::OverflowPolicy::Wrap,
+        subrange: crate::task::SubrangePolicy::Off,
+        fault_site: None,
+        pou_code: None,
     };
     let value =
         eval_expr(&mut eval_ctx, &expr).map_err(|err| CompileError::new(err.to_string()))?;
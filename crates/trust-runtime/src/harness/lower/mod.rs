@@ -2,6 +2,7 @@ mod expr;
 mod stmt;
 
 pub(super) use expr::{
-    const_duration_from_node, const_int_from_node, lower_expr, lower_lvalue, parse_subrange,
+    const_duration_from_node, const_int_from_node, lower_expr, lower_initializer, lower_lvalue,
+    parse_subrange,
 };
 pub(super) use stmt::lower_stmt_list;
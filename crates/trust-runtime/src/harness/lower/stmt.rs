@@ -1,7 +1,10 @@
 use crate::debug::SourceLocation;
-use crate::eval::expr::Expr;
+use crate::eval::expr::{Expr, LValue};
 use crate::eval::stmt::{CaseLabel, Stmt};
+use crate::eval::{ArgValue, CallArg};
+use crate::stdlib::string::truncate_string;
 use crate::value::Value;
+use smol_str::SmolStr;
 use trust_syntax::syntax::{SyntaxKind, SyntaxNode};
 
 use super::super::util::{direct_expr_children, first_expr_child, is_statement_kind, node_text};
@@ -13,14 +16,13 @@ pub(in crate::harness) fn lower_stmt_list(
     ctx: &mut LoweringContext<'_>,
 ) -> Result<Vec<Stmt>, CompileError> {
     let mut stmts = Vec::new();
-    let stmt_nodes: Vec<SyntaxNode> = if let Some(stmt_list) = program
+    // Function block bodies split by METHOD or ACTION declarations have one
+    // statement list per run of statements.
+    let stmt_nodes: Vec<SyntaxNode> = program
         .children()
-        .find(|child| child.kind() == SyntaxKind::StmtList)
-    {
-        stmt_list.children().collect()
-    } else {
-        program.children().collect()
-    };
+        .filter(|child| child.kind() == SyntaxKind::StmtList)
+        .flat_map(|stmt_list| stmt_list.children())
+        .collect();
 
     for stmt_node in stmt_nodes {
         if !is_statement_kind(stmt_node.kind()) {
@@ -86,7 +88,19 @@ fn lower_assign(node: &SyntaxNode, ctx: &mut LoweringContext<'_>) -> Result<Stmt
         return Err(CompileError::new("invalid assignment"));
     }
     let target = lower_lvalue(&exprs[0], ctx)?;
-    let value = lower_expr(&exprs[1], ctx)?;
+    let mut value = lower_expr(&exprs[1], ctx)?;
+    if let LValue::Name(name) = &target {
+        if let Some(bound) = ctx.string_bound(name) {
+            value = bounded_string(value, bound);
+        }
+        if let Some((lower, upper)) = ctx.subrange_bound(name) {
+            value = Expr::Subrange {
+                expr: Box::new(value),
+                lower,
+                upper,
+            };
+        }
+    }
     let location = stmt_location(node, ctx);
     if assignment_is_attempt(node) {
         Ok(Stmt::AssignAttempt {
@@ -103,6 +117,26 @@ fn lower_assign(node: &SyntaxNode, ctx: &mut LoweringContext<'_>) -> Result<Stmt
     }
 }
 
+/// Truncates values assigned to a `STRING[n]`/`WSTRING[n]` variable to its capacity.
+fn bounded_string(value: Expr, bound: u32) -> Expr {
+    if let Expr::Literal(literal) = value {
+        return Expr::Literal(truncate_string(literal, bound as usize));
+    }
+    Expr::Call {
+        target: Box::new(Expr::Name(SmolStr::new_static("LEFT"))),
+        args: vec![
+            CallArg {
+                name: None,
+                value: ArgValue::Expr(value),
+            },
+            CallArg {
+                name: None,
+                value: ArgValue::Expr(Expr::Literal(Value::UDInt(bound))),
+            },
+        ],
+    }
+}
+
 fn assignment_is_attempt(node: &SyntaxNode) -> bool {
     node.children_with_tokens()
         .filter_map(|child| child.into_token())
@@ -27,10 +27,11 @@ pub use parse::{parse_debug_expression, parse_debug_lvalue};
 pub use types::{CompileError, CycleResult, SourceFile};
 
 use compiler::{
-    class_type_name, function_block_type_name, interface_type_name, lower_classes,
-    lower_configuration, lower_function_blocks, lower_functions, lower_interfaces, lower_programs,
-    lower_type_decls, lower_type_ref, predeclare_classes, predeclare_function_blocks,
-    predeclare_interfaces, resolve_program_type_name, resolve_type_name, LoweringContext,
+    class_type_name, function_block_type_name, interface_default_methods, interface_type_name,
+    lower_classes, lower_configuration, lower_function_blocks, lower_functions, lower_interfaces,
+    lower_programs, lower_type_decls, lower_type_ref, predeclare_classes,
+    predeclare_function_blocks, predeclare_interfaces, register_namespace_reexports,
+    resolve_program_type_name, resolve_type_name, LoweringContext,
 };
 use compiler::{
     AccessDecl, AccessPart, AccessPath, ConfigInit, GlobalInit, ProgramInstanceConfig,
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use smol_str::SmolStr;
 
 use crate::eval::expr::{Expr, LValue};
@@ -57,6 +59,8 @@ pub fn parse_debug_expression(
         using: using.to_vec(),
         file_id: 0,
         statement_locations: &mut statement_locations,
+        string_bounds: HashMap::new(),
+        subrange_bounds: HashMap::new(),
     };
     super::lower_expr(expr, &mut ctx)
 }
@@ -109,6 +113,8 @@ pub fn parse_debug_lvalue(
         using: using.to_vec(),
         file_id: 0,
         statement_locations: &mut statement_locations,
+        string_bounds: HashMap::new(),
+        subrange_bounds: HashMap::new(),
     };
     super::lower::lower_lvalue(target, &mut ctx)
 }
@@ -29,7 +29,11 @@ pub(super) fn node_text(node: &SyntaxNode) -> String {
     }
 }
 
-pub(super) fn collect_using_directives(node: &SyntaxNode) -> Vec<SmolStr> {
+/// USING namespaces in scope of `node`, followed by the namespaces they re-export.
+pub(super) fn collect_using_directives(
+    node: &SyntaxNode,
+    registry: &trust_hir::types::TypeRegistry,
+) -> Vec<SmolStr> {
     let mut ancestors: Vec<SyntaxNode> = node.ancestors().collect();
     ancestors.reverse();
     let mut names = Vec::new();
@@ -41,6 +45,19 @@ pub(super) fn collect_using_directives(node: &SyntaxNode) -> Vec<SmolStr> {
             names.extend(using_directive_names(&using));
         }
     }
+    let mut reexported = Vec::new();
+    for name in &names {
+        for namespace in registry.reexported_namespaces(name) {
+            if !names
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(&namespace))
+                && !reexported.contains(&namespace)
+            {
+                reexported.push(namespace);
+            }
+        }
+    }
+    names.extend(reexported);
     names
 }
 
@@ -85,6 +102,21 @@ pub(super) fn builtin_type_name(kind: SyntaxKind) -> Option<&'static str> {
         SyntaxKind::KwWString => Some("WSTRING"),
         SyntaxKind::KwChar => Some("CHAR"),
         SyntaxKind::KwWChar => Some("WCHAR"),
+        SyntaxKind::KwAny => Some("ANY"),
+        SyntaxKind::KwAnyDerived => Some("ANY_DERIVED"),
+        SyntaxKind::KwAnyElementary => Some("ANY_ELEMENTARY"),
+        SyntaxKind::KwAnyMagnitude => Some("ANY_MAGNITUDE"),
+        SyntaxKind::KwAnyInt => Some("ANY_INT"),
+        SyntaxKind::KwAnyUnsigned => Some("ANY_UNSIGNED"),
+        SyntaxKind::KwAnySigned => Some("ANY_SIGNED"),
+        SyntaxKind::KwAnyReal => Some("ANY_REAL"),
+        SyntaxKind::KwAnyNum => Some("ANY_NUM"),
+        SyntaxKind::KwAnyDuration => Some("ANY_DURATION"),
+        SyntaxKind::KwAnyBit => Some("ANY_BIT"),
+        SyntaxKind::KwAnyChars => Some("ANY_CHARS"),
+        SyntaxKind::KwAnyString => Some("ANY_STRING"),
+        SyntaxKind::KwAnyChar => Some("ANY_CHAR"),
+        SyntaxKind::KwAnyDate => Some("ANY_DATE"),
         _ => None,
     }
 }
@@ -105,6 +137,8 @@ pub(super) fn is_expression_kind(kind: SyntaxKind) -> bool {
             | SyntaxKind::ThisExpr
             | SyntaxKind::SuperExpr
             | SyntaxKind::SizeOfExpr
+            | SyntaxKind::InitializerList
+            | SyntaxKind::ArrayInitializer
     )
 }
 
@@ -19,6 +19,7 @@ use tracing::warn;
 use crate::debug::DebugSnapshot;
 use crate::error::RuntimeError;
 use crate::metrics::RuntimeMetricsSnapshot;
+use crate::scheduler::TimeBase;
 use crate::value::Value;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -85,7 +86,8 @@ pub enum HistorianValue {
 }
 
 impl HistorianValue {
-    fn as_f64(&self) -> Option<f64> {
+    #[must_use]
+    pub fn as_f64(&self) -> Option<f64> {
         match self {
             Self::Bool(value) => Some(if *value { 1.0 } else { 0.0 }),
             Self::Integer(value) => Some(*value as f64),
@@ -195,12 +197,16 @@ impl HistorianService {
         &self.config
     }
 
-    pub fn start_sampler(self: Arc<Self>, debug: crate::debug::DebugControl) {
+    /// Sample the runtime in the background. Samples are stamped from the
+    /// resource clock and polled in step with its time scale, so a scaled
+    /// simulation records the same series as a slowed-down wall clock.
+    pub fn start_sampler(self: Arc<Self>, debug: crate::debug::DebugControl, time_base: TimeBase) {
         let interval = self.config.sample_interval_ms.max(1);
-        let poll_ms = (interval / 2).clamp(10, 1_000);
         thread::spawn(move || loop {
+            let scale = u64::from(time_base.time_scale());
+            let poll_ms = (interval / 2 / scale).clamp(10, 1_000);
             if let Some(snapshot) = debug.snapshot() {
-                let now_ms = unix_ms();
+                let now_ms = time_base.unix_ms(snapshot.now);
                 let _ = self.capture_snapshot_at(&snapshot, now_ms);
             }
             thread::sleep(Duration::from_millis(poll_ms));
@@ -287,6 +293,18 @@ impl HistorianService {
         variable: Option<&str>,
         since_ms: Option<u128>,
         limit: usize,
+    ) -> Vec<HistorianSample> {
+        self.query_window(variable, since_ms, None, limit)
+    }
+
+    /// Like [`Self::query`], limited to samples taken at or before `until_ms`.
+    #[must_use]
+    pub fn query_window(
+        &self,
+        variable: Option<&str>,
+        since_ms: Option<u128>,
+        until_ms: Option<u128>,
+        limit: usize,
     ) -> Vec<HistorianSample> {
         let limit = limit.clamp(1, 5_000);
         let Ok(inner) = self.inner.lock() else {
@@ -298,6 +316,7 @@ impl HistorianService {
             .rev()
             .filter(|sample| variable.is_none_or(|name| sample.variable.as_str() == name))
             .filter(|sample| since_ms.is_none_or(|value| sample.timestamp_ms >= value))
+            .filter(|sample| until_ms.is_none_or(|value| sample.timestamp_ms <= value))
             .take(limit)
             .cloned()
             .collect::<Vec<_>>();
@@ -678,6 +697,14 @@ fn dispatch_hook(target: &HookTarget, event: &HistorianAlertEvent) {
                 }
             }
         }
+        #[cfg(not(feature = "services"))]
+        HookTarget::Webhook(url) => {
+            warn!(
+                "historian webhook {url} for '{}' requires feature 'services'",
+                event.rule
+            );
+        }
+        #[cfg(feature = "services")]
         HookTarget::Webhook(url) => {
             let payload = match serde_json::to_string(event) {
                 Ok(payload) => payload,
@@ -711,13 +738,6 @@ fn resolve_path(path: &Path, bundle_root: Option<&Path>) -> PathBuf {
     }
 }
 
-fn unix_ms() -> u128 {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis()
-}
-
 #[must_use]
 pub fn render_prometheus(
     runtime: &RuntimeMetricsSnapshot,
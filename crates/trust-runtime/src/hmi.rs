@@ -13,8 +13,9 @@ use trust_hir::types::Type;
 
 use crate::debug::dap::value_type_name;
 use crate::debug::DebugSnapshot;
+use crate::historian::HistorianService;
 use crate::runtime::RuntimeMetadata;
-use crate::value::Value;
+use crate::value::{format_time_literal, DateTimeProfile, Value};
 
 const HMI_SCHEMA_VERSION: u32 = 1;
 const HMI_DESCRIPTOR_VERSION: u32 = 1;
@@ -206,8 +207,10 @@ pub struct HmiLiveState {
 pub struct HmiTrendResult {
     pub connected: bool,
     pub timestamp_ms: u128,
+    pub until_ms: u128,
     pub duration_ms: u64,
     pub buckets: usize,
+    pub historian: bool,
     pub series: Vec<HmiTrendSeries>,
 }
 
@@ -1244,16 +1247,17 @@ pub fn build_schema(
     }
 }
 
+/// Resolves the current HMI values, stamped with `now_ms`.
 pub fn build_values(
     resource_name: &str,
     metadata: &RuntimeMetadata,
     snapshot: Option<&DebugSnapshot>,
     read_only: bool,
     ids: Option<&[String]>,
+    now_ms: u128,
 ) -> HmiValuesResult {
     let requested = ids.map(|entries| entries.iter().map(String::as_str).collect::<HashSet<_>>());
     let points = collect_points(resource_name, metadata, snapshot, read_only);
-    let now_ms = now_unix_ms();
     let mut values = IndexMap::new();
 
     for point in points {
@@ -1379,15 +1383,32 @@ pub fn build_trends(
     ids: Option<&[String]>,
     duration_ms: u64,
     buckets: usize,
+) -> HmiTrendResult {
+    build_trends_with_history(state, schema, ids, None, duration_ms, buckets, None)
+}
+
+/// Builds trend series for the window ending at `until_ms` (default: now).
+///
+/// The live buffer only covers recent samples, so older parts of the window
+/// are filled from the historian when one is configured.
+pub fn build_trends_with_history(
+    state: &HmiLiveState,
+    schema: &HmiSchemaResult,
+    ids: Option<&[String]>,
+    until_ms: Option<u128>,
+    duration_ms: u64,
+    buckets: usize,
+    historian: Option<&HistorianService>,
 ) -> HmiTrendResult {
     let now_ms = if state.last_timestamp_ms > 0 {
         state.last_timestamp_ms
     } else {
         now_unix_ms()
     };
+    let until_ms = until_ms.map_or(now_ms, |until| until.min(now_ms));
     let duration_ms = duration_ms.max(5_000);
     let buckets = buckets.clamp(8, 480);
-    let cutoff = now_ms.saturating_sub(u128::from(duration_ms));
+    let cutoff = until_ms.saturating_sub(u128::from(duration_ms));
     let allowed_ids = ids
         .filter(|entries| !entries.is_empty())
         .map(|entries| entries.iter().map(String::as_str).collect::<HashSet<_>>());
@@ -1402,12 +1423,24 @@ pub fn build_trends(
                 .is_none_or(|entries| entries.contains(widget.id.as_str()))
         })
         .filter_map(|widget| {
-            let samples = state.trend_samples.get(widget.id.as_str())?;
-            let scoped = samples
-                .iter()
-                .filter(|sample| sample.ts_ms >= cutoff)
-                .cloned()
-                .collect::<Vec<_>>();
+            let live = state
+                .trend_samples
+                .get(widget.id.as_str())
+                .map(|samples| {
+                    samples
+                        .iter()
+                        .filter(|sample| sample.ts_ms >= cutoff && sample.ts_ms <= until_ms)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let mut scoped = historian
+                .map(|historian| {
+                    let before = live.first().map_or(until_ms, |sample| sample.ts_ms);
+                    historian_trend_samples(historian, widget.path.as_str(), cutoff, before)
+                })
+                .unwrap_or_default();
+            scoped.extend(live);
             let points = downsample_trend_samples(&scoped, buckets);
             if points.is_empty() {
                 return None;
@@ -1424,12 +1457,43 @@ pub fn build_trends(
     HmiTrendResult {
         connected: state.last_connected,
         timestamp_ms: now_ms,
+        until_ms,
         duration_ms,
         buckets,
+        historian: historian.is_some(),
         series,
     }
 }
 
+/// Historian samples for an HMI point taken in `since_ms..before_ms`.
+/// Globals are recorded by their bare name, everything else by its path.
+fn historian_trend_samples(
+    historian: &HistorianService,
+    path: &str,
+    since_ms: u128,
+    before_ms: u128,
+) -> Vec<HmiTrendSample> {
+    if before_ms <= since_ms {
+        return Vec::new();
+    }
+    let variable = path.strip_prefix("global.").unwrap_or(path);
+    historian
+        .query_window(
+            Some(variable),
+            Some(since_ms),
+            Some(before_ms - 1),
+            TREND_HISTORY_LIMIT,
+        )
+        .into_iter()
+        .filter_map(|sample| {
+            Some(HmiTrendSample {
+                ts_ms: sample.timestamp_ms,
+                value: sample.value.as_f64()?,
+            })
+        })
+        .collect()
+}
+
 pub fn build_alarm_view(state: &HmiLiveState, history_limit: usize) -> HmiAlarmResult {
     let mut active = state
         .alarms
@@ -1833,8 +1897,8 @@ fn widget_for_type(ty: &Type, writable: bool) -> &'static str {
         | Type::FunctionBlock { .. }
         | Type::Class { .. }
         | Type::Interface { .. } => "tree",
-        ty if ty.is_string() || ty.is_char() => "text",
-        ty if ty.is_numeric() || ty.is_bit_string() || ty.is_time() => {
+        ty if ty.is_string() || ty.is_char() || ty.is_date() => "text",
+        ty if ty.is_numeric() || ty.is_bit_string() || ty.is_duration() => {
             if writable {
                 "slider"
             } else {
@@ -1863,7 +1927,16 @@ fn widget_for_value(value: &Value, writable: bool) -> &'static str {
         }
         Value::Array(_) => "table",
         Value::Struct(_) | Value::Instance(_) => "tree",
-        Value::String(_) | Value::WString(_) | Value::Char(_) | Value::WChar(_) => "text",
+        Value::String(_)
+        | Value::WString(_)
+        | Value::Char(_)
+        | Value::WChar(_)
+        | Value::Date(_)
+        | Value::LDate(_)
+        | Value::Tod(_)
+        | Value::LTod(_)
+        | Value::Dt(_)
+        | Value::Ldt(_) => "text",
         Value::SInt(_)
         | Value::Int(_)
         | Value::DInt(_)
@@ -1879,13 +1952,7 @@ fn widget_for_value(value: &Value, writable: bool) -> &'static str {
         | Value::DWord(_)
         | Value::LWord(_)
         | Value::Time(_)
-        | Value::LTime(_)
-        | Value::Date(_)
-        | Value::LDate(_)
-        | Value::Tod(_)
-        | Value::LTod(_)
-        | Value::Dt(_)
-        | Value::Ldt(_) => {
+        | Value::LTime(_) => {
             if writable {
                 "slider"
             } else {
@@ -1914,12 +1981,13 @@ fn value_to_json(value: &Value) -> serde_json::Value {
         Value::DWord(value) => serde_json::json!(*value),
         Value::LWord(value) => serde_json::json!(*value),
         Value::Time(value) | Value::LTime(value) => serde_json::json!(value.as_nanos()),
-        Value::Date(value) => serde_json::json!(value.ticks()),
-        Value::LDate(value) => serde_json::json!(value.nanos()),
-        Value::Tod(value) => serde_json::json!(value.ticks()),
-        Value::LTod(value) => serde_json::json!(value.nanos()),
-        Value::Dt(value) => serde_json::json!(value.ticks()),
-        Value::Ldt(value) => serde_json::json!(value.nanos()),
+        Value::Date(_)
+        | Value::LDate(_)
+        | Value::Tod(_)
+        | Value::LTod(_)
+        | Value::Dt(_)
+        | Value::Ldt(_) => format_time_literal(value, DateTimeProfile::default())
+            .map_or(serde_json::Value::Null, serde_json::Value::String),
         Value::String(value) => serde_json::json!(value.as_str()),
         Value::WString(value) => serde_json::json!(value),
         Value::Char(value) => {
@@ -2449,12 +2517,6 @@ fn is_numeric_data_type(data_type: &str) -> bool {
             | "LREAL"
             | "TIME"
             | "LTIME"
-            | "DATE"
-            | "LDATE"
-            | "TOD"
-            | "LTOD"
-            | "DT"
-            | "LDT"
     )
 }
 
@@ -2467,6 +2529,12 @@ fn load_hmi_toml(root: &Path) -> anyhow::Result<HmiTomlFile> {
     Ok(toml::from_str::<HmiTomlFile>(&text)?)
 }
 
+/// Check that `text` parses as a legacy `hmi.toml`.
+pub fn validate_hmi_toml_text(text: &str) -> anyhow::Result<()> {
+    toml::from_str::<HmiTomlFile>(text)?;
+    Ok(())
+}
+
 pub fn load_hmi_dir(root: &Path) -> Option<HmiDirDescriptor> {
     load_hmi_dir_impl(root).ok()
 }
@@ -5086,8 +5154,10 @@ fn enum_values_for_type(ty: &Type) -> Vec<String> {
 fn scaffold_type_bucket_for_type(ty: &Type) -> ScaffoldTypeBucket {
     match ty {
         Type::Bool => ScaffoldTypeBucket::Bool,
-        ty if ty.is_numeric() || ty.is_bit_string() || ty.is_time() => ScaffoldTypeBucket::Numeric,
-        ty if ty.is_string() || ty.is_char() => ScaffoldTypeBucket::Text,
+        ty if ty.is_numeric() || ty.is_bit_string() || ty.is_duration() => {
+            ScaffoldTypeBucket::Numeric
+        }
+        ty if ty.is_string() || ty.is_char() || ty.is_date() => ScaffoldTypeBucket::Text,
         Type::Array { .. }
         | Type::Struct { .. }
         | Type::Union { .. }
@@ -5116,16 +5186,17 @@ fn scaffold_type_bucket_for_value(value: &Value, data_type: &str) -> ScaffoldTyp
         | Value::Real(_)
         | Value::LReal(_)
         | Value::Time(_)
-        | Value::LTime(_)
+        | Value::LTime(_) => ScaffoldTypeBucket::Numeric,
+        Value::String(_)
+        | Value::WString(_)
+        | Value::Char(_)
+        | Value::WChar(_)
         | Value::Date(_)
         | Value::LDate(_)
         | Value::Tod(_)
         | Value::LTod(_)
         | Value::Dt(_)
-        | Value::Ldt(_) => ScaffoldTypeBucket::Numeric,
-        Value::String(_) | Value::WString(_) | Value::Char(_) | Value::WChar(_) => {
-            ScaffoldTypeBucket::Text
-        }
+        | Value::Ldt(_) => ScaffoldTypeBucket::Text,
         Value::Array(_) | Value::Struct(_) => ScaffoldTypeBucket::Composite,
         _ if is_numeric_data_type(data_type) => ScaffoldTypeBucket::Numeric,
         _ => ScaffoldTypeBucket::Other,
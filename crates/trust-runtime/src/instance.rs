@@ -257,6 +257,12 @@ fn init_var_defaults(
         loop_depth: 0,
         pause_requested: false,
         execution_deadline: None,
+        workers: None,
+        bounds: crate::task::BoundsPolicy::Fault,
+        overflow: crate::eval::ops::OverflowPolicy::Wrap,
+        subrange: crate::task::SubrangePolicy::Off,
+        fault_site: None,
+        pou_code: None,
     };
     for var in vars {
         if function_block_type_name(var.type_id, registry).is_some() {
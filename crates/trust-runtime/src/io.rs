@@ -11,11 +11,25 @@ pub use mqtt::MqttIoDriver;
 mod ethercat;
 pub use ethercat::EthercatIoDriver;
 mod gpio;
+mod i2c;
 mod loopback;
+mod process;
 mod registry;
+mod spi;
+mod symbols;
+mod validate;
 pub use gpio::GpioDriver;
+pub use i2c::I2cIoDriver;
 pub use loopback::LoopbackIoDriver;
+pub use process::{
+    serve_process_driver, serve_process_driver_io, ProcessDriverSpec, ProcessIoDriver,
+    PROCESS_DRIVER_PROTOCOL_VERSION,
+};
 pub use registry::IoDriverRegistry;
+pub use spi::SpiIoDriver;
+pub(crate) use symbols::symbol_value_type;
+pub use symbols::{IoSymbol, IoSymbolMap};
+pub use validate::{format_io_address, validate_io_map, DriverChannel, IoChannel};
 
 use crate::error::RuntimeError;
 use crate::memory::IoArea;
@@ -36,6 +50,12 @@ pub trait IoDriver: Send {
     fn health(&self) -> IoDriverHealth {
         IoDriverHealth::Ok
     }
+
+    /// Process-image points this driver maps to individual hardware channels.
+    /// Drivers that exchange whole image ranges return none.
+    fn channels(&self) -> Vec<IoChannel> {
+        Vec::new()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -66,6 +86,29 @@ impl IoDriverErrorPolicy {
     }
 }
 
+/// What a hardware driver does when its device cannot be opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IoDriverFallback {
+    /// Fail the load.
+    #[default]
+    Fault,
+    /// Run against an in-memory stand-in and report degraded health.
+    Simulated,
+}
+
+impl IoDriverFallback {
+    pub fn parse(value: &str) -> Result<Self, RuntimeError> {
+        let value = value.trim().to_ascii_lowercase();
+        match value.as_str() {
+            "fault" | "none" => Ok(Self::Fault),
+            "simulated" | "simulate" | "sim" => Ok(Self::Simulated),
+            _ => Err(RuntimeError::InvalidConfig(
+                format!("invalid io.params.fallback '{value}' (expected fault/simulated)").into(),
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IoDriverStatus {
     pub name: SmolStr,
@@ -249,6 +292,83 @@ impl IoSafeState {
     }
 }
 
+/// What an output does when the safe state is applied.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SafeOutput {
+    /// Keep the last value written by the program.
+    #[default]
+    Hold,
+    /// Write zero (FALSE for bits).
+    Zero,
+    /// Write a fixed value.
+    Value(Value),
+}
+
+impl SafeOutput {
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            Self::Hold => "hold".to_string(),
+            Self::Zero => "zero".to_string(),
+            Self::Value(value) => format!("{value:?}"),
+        }
+    }
+}
+
+/// `[runtime.safety.state]` output policy, applied whenever the resource
+/// pauses, faults, or the watchdog trips.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutputSafeState {
+    /// Policy for %Q outputs without an entry (`Hold` or `Zero`).
+    pub default: SafeOutput,
+    pub outputs: Vec<(IoAddress, SafeOutput)>,
+}
+
+impl OutputSafeState {
+    /// True when applying the policy leaves every output unchanged.
+    #[must_use]
+    pub fn is_hold_all(&self) -> bool {
+        self.default == SafeOutput::Hold
+            && self
+                .outputs
+                .iter()
+                .all(|(_, output)| *output == SafeOutput::Hold)
+    }
+
+    pub fn apply(&self, io: &mut IoInterface) -> Result<(), RuntimeError> {
+        if self.default == SafeOutput::Zero {
+            let held = self
+                .outputs
+                .iter()
+                .filter(|(_, output)| *output == SafeOutput::Hold)
+                .map(|(address, _)| Ok((address, io.read(address)?)))
+                .collect::<Result<Vec<_>, RuntimeError>>()?;
+            io.outputs_mut().fill(0);
+            for (address, value) in held {
+                io.write(address, value)?;
+            }
+        }
+        for (address, output) in &self.outputs {
+            match output {
+                SafeOutput::Hold => {}
+                SafeOutput::Zero => io.write(address, zero_value(address.size))?,
+                SafeOutput::Value(value) => io.write(address, value.clone())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn zero_value(size: IoSize) -> Value {
+    match size {
+        IoSize::Bit => Value::Bool(false),
+        IoSize::Byte => Value::Byte(0),
+        IoSize::Word => Value::Word(0),
+        IoSize::DWord => Value::DWord(0),
+        IoSize::LWord => Value::LWord(0),
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct IoInterface {
     inputs: Vec<u8>,
@@ -276,6 +396,31 @@ impl IoInterface {
         self.memory.resize(memory, 0);
     }
 
+    /// Grow the process image buffers so every flat binding fits.
+    pub fn fit_bindings(&mut self) {
+        for binding in &self.bindings {
+            let address = &binding.address;
+            if address.wildcard || address.path.len() > 1 {
+                continue;
+            }
+            let span = match address.size {
+                IoSize::Bit | IoSize::Byte => 1,
+                IoSize::Word => 2,
+                IoSize::DWord => 4,
+                IoSize::LWord => 8,
+            };
+            let required = address.byte as usize + span;
+            let buffer = match address.area {
+                IoArea::Input => &mut self.inputs,
+                IoArea::Output => &mut self.outputs,
+                IoArea::Memory => &mut self.memory,
+            };
+            if buffer.len() < required {
+                buffer.resize(required, 0);
+            }
+        }
+    }
+
     /// Access the raw input image.
     #[must_use]
     pub fn inputs(&self) -> &[u8] {
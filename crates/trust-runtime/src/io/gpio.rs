@@ -1,5 +1,6 @@
 //! Raspberry Pi GPIO driver (configurable backend).
 
+use std::collections::HashMap;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -8,12 +9,18 @@ use std::time::Instant;
 use smol_str::SmolStr;
 
 use crate::error::RuntimeError;
-use crate::io::{IoAddress, IoDriver, IoSize};
+use crate::io::{
+    IoAddress, IoChannel, IoDriver, IoDriverErrorPolicy, IoDriverFallback, IoDriverHealth, IoSize,
+};
+use crate::memory::IoArea;
 
 pub struct GpioDriver {
     backend: Box<dyn GpioBackend>,
     inputs: Vec<GpioInput>,
     outputs: Vec<GpioOutput>,
+    on_error: IoDriverErrorPolicy,
+    simulated: Option<SmolStr>,
+    health: IoDriverHealth,
 }
 
 impl std::fmt::Debug for GpioDriver {
@@ -21,6 +28,7 @@ impl std::fmt::Debug for GpioDriver {
         f.debug_struct("GpioDriver")
             .field("inputs", &self.inputs.len())
             .field("outputs", &self.outputs.len())
+            .field("simulated", &self.simulated.is_some())
             .finish()
     }
 }
@@ -28,25 +36,40 @@ impl std::fmt::Debug for GpioDriver {
 impl GpioDriver {
     pub fn from_params(params: &toml::Value) -> Result<Self, RuntimeError> {
         let config = GpioConfig::parse(params)?;
-        let mut backend: Box<dyn GpioBackend> = match config.backend {
-            GpioBackendKind::Sysfs => Box::new(SysfsBackend::new(config.sysfs_base)),
+        let (backend, simulated) = match open_backend(&config) {
+            Ok(backend) => (backend, None),
+            Err(err) if config.fallback == IoDriverFallback::Simulated => {
+                let mut backend: Box<dyn GpioBackend> = Box::new(SimulatedBackend::default());
+                configure_lines(backend.as_mut(), &config)?;
+                (backend, Some(SmolStr::new(format!("simulated: {err}"))))
+            }
+            Err(err) => return Err(err),
         };
 
-        let mut inputs = Vec::new();
-        let mut outputs = Vec::new();
-        for entry in config.inputs {
-            backend.configure_input(entry.line)?;
-            inputs.push(GpioInput::from_entry(entry)?);
-        }
-        for entry in config.outputs {
-            backend.configure_output(entry.line, entry.initial)?;
-            outputs.push(GpioOutput::from_entry(entry)?);
-        }
+        let inputs = config
+            .inputs
+            .into_iter()
+            .map(GpioInput::from_entry)
+            .collect::<Result<Vec<_>, _>>()?;
+        let outputs = config
+            .outputs
+            .into_iter()
+            .map(GpioOutput::from_entry)
+            .collect::<Result<Vec<_>, _>>()?;
+        let health = match &simulated {
+            Some(reason) => IoDriverHealth::Degraded {
+                error: reason.clone(),
+            },
+            None => IoDriverHealth::Ok,
+        };
 
         Ok(Self {
             backend,
             inputs,
             outputs,
+            on_error: config.on_error,
+            simulated,
+            health,
         })
     }
 
@@ -56,8 +79,8 @@ impl GpioDriver {
     }
 }
 
-impl IoDriver for GpioDriver {
-    fn read_inputs(&mut self, inputs: &mut [u8]) -> Result<(), RuntimeError> {
+impl GpioDriver {
+    fn read_lines(&mut self, inputs: &mut [u8]) -> Result<(), RuntimeError> {
         let now = Instant::now();
         for entry in &mut self.inputs {
             let raw = self.backend.read(entry.line)?;
@@ -86,7 +109,7 @@ impl IoDriver for GpioDriver {
         Ok(())
     }
 
-    fn write_outputs(&mut self, outputs: &[u8]) -> Result<(), RuntimeError> {
+    fn write_lines(&mut self, outputs: &[u8]) -> Result<(), RuntimeError> {
         for entry in &mut self.outputs {
             let raw = read_bit(outputs, entry.byte, entry.bit)?;
             let value = if entry.invert { !raw } else { raw };
@@ -97,6 +120,78 @@ impl IoDriver for GpioDriver {
         }
         Ok(())
     }
+
+    fn handle_error(&mut self, err: RuntimeError) -> Result<(), RuntimeError> {
+        let message = SmolStr::new(err.to_string());
+        if matches!(self.on_error, IoDriverErrorPolicy::Fault) {
+            self.health = IoDriverHealth::Faulted { error: message };
+            return Err(err);
+        }
+        self.health = IoDriverHealth::Degraded { error: message };
+        Ok(())
+    }
+
+    fn mark_ok(&mut self) {
+        self.health = match &self.simulated {
+            Some(reason) => IoDriverHealth::Degraded {
+                error: reason.clone(),
+            },
+            None => IoDriverHealth::Ok,
+        };
+    }
+}
+
+impl IoDriver for GpioDriver {
+    fn read_inputs(&mut self, inputs: &mut [u8]) -> Result<(), RuntimeError> {
+        match self.read_lines(inputs) {
+            Ok(()) => {
+                self.mark_ok();
+                Ok(())
+            }
+            Err(err) => self.handle_error(err),
+        }
+    }
+
+    fn write_outputs(&mut self, outputs: &[u8]) -> Result<(), RuntimeError> {
+        match self.write_lines(outputs) {
+            Ok(()) => {
+                self.mark_ok();
+                Ok(())
+            }
+            Err(err) => self.handle_error(err),
+        }
+    }
+
+    fn health(&self) -> IoDriverHealth {
+        self.health.clone()
+    }
+
+    fn channels(&self) -> Vec<IoChannel> {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|entry| gpio_channel(IoArea::Input, entry.byte, entry.bit, entry.line));
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|entry| gpio_channel(IoArea::Output, entry.byte, entry.bit, entry.line));
+        inputs.chain(outputs).collect()
+    }
+}
+
+fn gpio_channel(area: IoArea, byte: usize, bit: u8, line: u32) -> IoChannel {
+    let byte = byte as u32;
+    IoChannel {
+        address: IoAddress {
+            area,
+            size: IoSize::Bit,
+            byte,
+            bit,
+            path: vec![byte],
+            wildcard: false,
+        },
+        label: SmolStr::new(format!("line {line}")),
+    }
 }
 
 #[derive(Debug)]
@@ -147,15 +242,20 @@ impl GpioOutput {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum GpioBackendKind {
     Sysfs,
+    /// Linux GPIO character device (`/dev/gpiochipN`).
+    Chip,
 }
 
 #[derive(Debug)]
 struct GpioConfig {
     backend: GpioBackendKind,
     sysfs_base: PathBuf,
+    chip: PathBuf,
+    on_error: IoDriverErrorPolicy,
+    fallback: IoDriverFallback,
     inputs: Vec<GpioInputEntry>,
     outputs: Vec<GpioOutputEntry>,
 }
@@ -184,6 +284,11 @@ impl GpioConfig {
         let backend = match table.get("backend").and_then(|v| v.as_str()) {
             None => GpioBackendKind::Sysfs,
             Some(name) if name.eq_ignore_ascii_case("sysfs") => GpioBackendKind::Sysfs,
+            Some(name)
+                if name.eq_ignore_ascii_case("gpiochip") || name.eq_ignore_ascii_case("cdev") =>
+            {
+                GpioBackendKind::Chip
+            }
             Some(name) => return Err(invalid_gpio(format!("unsupported gpio backend '{name}'"))),
         };
         let sysfs_base = table
@@ -191,6 +296,23 @@ impl GpioConfig {
             .and_then(|v| v.as_str())
             .map(PathBuf::from)
             .unwrap_or_else(|| PathBuf::from("/sys/class/gpio"));
+        let chip = table
+            .get("chip")
+            .and_then(|v| v.as_str())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("/dev/gpiochip0"));
+        let on_error = table
+            .get("on_error")
+            .and_then(|v| v.as_str())
+            .map(IoDriverErrorPolicy::parse)
+            .transpose()?
+            .unwrap_or(IoDriverErrorPolicy::Fault);
+        let fallback = table
+            .get("fallback")
+            .and_then(|v| v.as_str())
+            .map(IoDriverFallback::parse)
+            .transpose()?
+            .unwrap_or_default();
 
         let inputs = parse_gpio_inputs(table.get("inputs"))?;
         let outputs = parse_gpio_outputs(table.get("outputs"))?;
@@ -198,6 +320,9 @@ impl GpioConfig {
         Ok(Self {
             backend,
             sysfs_base,
+            chip,
+            on_error,
+            fallback,
             inputs,
             outputs,
         })
@@ -365,6 +490,38 @@ fn invalid_gpio(msg: impl Into<String>) -> RuntimeError {
     RuntimeError::InvalidConfig(SmolStr::new(msg.into()))
 }
 
+fn open_backend(config: &GpioConfig) -> Result<Box<dyn GpioBackend>, RuntimeError> {
+    let mut backend: Box<dyn GpioBackend> = match config.backend {
+        GpioBackendKind::Sysfs => Box::new(SysfsBackend::new(config.sysfs_base.clone())),
+        GpioBackendKind::Chip => open_chip(&config.chip)?,
+    };
+    configure_lines(backend.as_mut(), config)?;
+    Ok(backend)
+}
+
+fn configure_lines(backend: &mut dyn GpioBackend, config: &GpioConfig) -> Result<(), RuntimeError> {
+    for entry in &config.inputs {
+        backend.configure_input(entry.line)?;
+    }
+    for entry in &config.outputs {
+        backend.configure_output(entry.line, entry.initial)?;
+    }
+    Ok(())
+}
+
+#[cfg(all(feature = "pi-wire", target_os = "linux"))]
+fn open_chip(path: &Path) -> Result<Box<dyn GpioBackend>, RuntimeError> {
+    Ok(Box::new(ChipBackend::new(path)?))
+}
+
+#[cfg(not(all(feature = "pi-wire", target_os = "linux")))]
+fn open_chip(path: &Path) -> Result<Box<dyn GpioBackend>, RuntimeError> {
+    let _ = path;
+    Err(invalid_gpio(
+        "gpio backend 'gpiochip' requires feature 'pi-wire' on Linux",
+    ))
+}
+
 trait GpioBackend: Send {
     fn configure_input(&mut self, line: u32) -> Result<(), RuntimeError>;
     fn configure_output(&mut self, line: u32, initial: bool) -> Result<(), RuntimeError>;
@@ -446,6 +603,103 @@ impl GpioBackend for SysfsBackend {
     }
 }
 
+#[cfg(all(feature = "pi-wire", target_os = "linux"))]
+struct ChipBackend {
+    chip: gpio_cdev::Chip,
+    lines: HashMap<u32, gpio_cdev::LineHandle>,
+}
+
+#[cfg(all(feature = "pi-wire", target_os = "linux"))]
+impl ChipBackend {
+    const CONSUMER: &'static str = "trust-runtime";
+
+    fn new(path: &Path) -> Result<Self, RuntimeError> {
+        let chip = gpio_cdev::Chip::new(path).map_err(|err| {
+            RuntimeError::IoDriver(SmolStr::new(format!("gpio open {path:?} failed: {err}")))
+        })?;
+        Ok(Self {
+            chip,
+            lines: HashMap::new(),
+        })
+    }
+
+    fn request(
+        &mut self,
+        line: u32,
+        flags: gpio_cdev::LineRequestFlags,
+        initial: u8,
+    ) -> Result<(), RuntimeError> {
+        let handle = self
+            .chip
+            .get_line(line)
+            .and_then(|handle| handle.request(flags, initial, Self::CONSUMER))
+            .map_err(|err| {
+                RuntimeError::IoDriver(SmolStr::new(format!("gpio request {line} failed: {err}")))
+            })?;
+        self.lines.insert(line, handle);
+        Ok(())
+    }
+
+    fn handle(&self, line: u32) -> Result<&gpio_cdev::LineHandle, RuntimeError> {
+        self.lines.get(&line).ok_or_else(|| {
+            RuntimeError::IoDriver(SmolStr::new(format!("gpio line {line} not requested")))
+        })
+    }
+}
+
+#[cfg(all(feature = "pi-wire", target_os = "linux"))]
+impl GpioBackend for ChipBackend {
+    fn configure_input(&mut self, line: u32) -> Result<(), RuntimeError> {
+        self.request(line, gpio_cdev::LineRequestFlags::INPUT, 0)
+    }
+
+    fn configure_output(&mut self, line: u32, initial: bool) -> Result<(), RuntimeError> {
+        self.request(line, gpio_cdev::LineRequestFlags::OUTPUT, u8::from(initial))
+    }
+
+    fn read(&mut self, line: u32) -> Result<bool, RuntimeError> {
+        let value = self.handle(line)?.get_value().map_err(|err| {
+            RuntimeError::IoDriver(SmolStr::new(format!("gpio read {line} failed: {err}")))
+        })?;
+        Ok(value != 0)
+    }
+
+    fn write(&mut self, line: u32, value: bool) -> Result<(), RuntimeError> {
+        self.handle(line)?
+            .set_value(u8::from(value))
+            .map_err(|err| {
+                RuntimeError::IoDriver(SmolStr::new(format!("gpio write {line} failed: {err}")))
+            })
+    }
+}
+
+/// Stand-in lines used when `fallback = "simulated"` and the hardware is unavailable.
+#[derive(Debug, Default)]
+struct SimulatedBackend {
+    lines: HashMap<u32, bool>,
+}
+
+impl GpioBackend for SimulatedBackend {
+    fn configure_input(&mut self, line: u32) -> Result<(), RuntimeError> {
+        self.lines.insert(line, false);
+        Ok(())
+    }
+
+    fn configure_output(&mut self, line: u32, initial: bool) -> Result<(), RuntimeError> {
+        self.lines.insert(line, initial);
+        Ok(())
+    }
+
+    fn read(&mut self, line: u32) -> Result<bool, RuntimeError> {
+        Ok(self.lines.get(&line).copied().unwrap_or(false))
+    }
+
+    fn write(&mut self, line: u32, value: bool) -> Result<(), RuntimeError> {
+        self.lines.insert(line, value);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -472,4 +726,58 @@ outputs = [ { address = "%QX0.1", line = 27, invert = false, initial = true } ]
         let err = GpioConfig::parse(&params).unwrap_err();
         assert!(format!("{err}").contains("bit"));
     }
+
+    #[test]
+    fn parse_gpio_config_accepts_gpiochip_backend() {
+        let params: toml::Value = toml::from_str(
+            r#"
+backend = "gpiochip"
+chip = "/dev/gpiochip4"
+on_error = "warn"
+outputs = [ { address = "%QX0.0", line = 17 } ]
+"#,
+        )
+        .unwrap();
+        let config = GpioConfig::parse(&params).expect("config");
+        assert_eq!(config.backend, GpioBackendKind::Chip);
+        assert_eq!(config.chip, PathBuf::from("/dev/gpiochip4"));
+        assert_eq!(config.on_error, IoDriverErrorPolicy::Warn);
+    }
+
+    #[test]
+    fn missing_chip_falls_back_to_simulated_lines() {
+        let params: toml::Value = toml::from_str(
+            r#"
+backend = "gpiochip"
+chip = "/nonexistent/gpiochip9"
+fallback = "simulated"
+inputs = [ { address = "%IX0.0", line = 5 } ]
+outputs = [ { address = "%QX0.1", line = 6, initial = true } ]
+"#,
+        )
+        .unwrap();
+        let mut driver = GpioDriver::from_params(&params).expect("simulated gpio");
+        assert!(matches!(
+            driver.health(),
+            IoDriverHealth::Degraded { ref error } if error.starts_with("simulated:")
+        ));
+        driver.write_outputs(&[0b10]).expect("write");
+        let mut inputs = [0xFF];
+        driver.read_inputs(&mut inputs).expect("read");
+        assert_eq!(inputs[0] & 1, 0);
+        assert!(matches!(driver.health(), IoDriverHealth::Degraded { .. }));
+    }
+
+    #[test]
+    fn missing_chip_without_fallback_fails_to_load() {
+        let params: toml::Value = toml::from_str(
+            r#"
+backend = "gpiochip"
+chip = "/nonexistent/gpiochip9"
+outputs = [ { address = "%QX0.1", line = 6 } ]
+"#,
+        )
+        .unwrap();
+        assert!(GpioDriver::from_params(&params).is_err());
+    }
 }
@@ -0,0 +1,610 @@
+//! Linux I2C register I/O driver (`/dev/i2c-N`).
+
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use smol_str::SmolStr;
+
+use crate::error::RuntimeError;
+use crate::io::{
+    IoAddress, IoChannel, IoDriver, IoDriverErrorPolicy, IoDriverFallback, IoDriverHealth, IoSize,
+};
+use crate::memory::IoArea;
+
+pub struct I2cIoDriver {
+    bus: Box<dyn I2cBus>,
+    inputs: Vec<I2cRegister>,
+    outputs: Vec<I2cRegister>,
+    on_error: IoDriverErrorPolicy,
+    simulated: Option<SmolStr>,
+    health: IoDriverHealth,
+}
+
+impl std::fmt::Debug for I2cIoDriver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("I2cIoDriver")
+            .field("inputs", &self.inputs.len())
+            .field("outputs", &self.outputs.len())
+            .field("simulated", &self.simulated.is_some())
+            .finish()
+    }
+}
+
+impl I2cIoDriver {
+    pub fn from_params(params: &toml::Value) -> Result<Self, RuntimeError> {
+        let config = I2cConfig::from_params(params)?;
+        let (bus, simulated) = match open_bus(&config) {
+            Ok(bus) => (bus, None),
+            Err(err) if config.fallback == IoDriverFallback::Simulated => {
+                let mut bus: Box<dyn I2cBus> = Box::new(SimulatedI2cBus::default());
+                write_init(bus.as_mut(), &config.init)?;
+                (bus, Some(SmolStr::new(format!("simulated: {err}"))))
+            }
+            Err(err) => return Err(err),
+        };
+        let health = match &simulated {
+            Some(reason) => IoDriverHealth::Degraded {
+                error: reason.clone(),
+            },
+            None => IoDriverHealth::Ok,
+        };
+        Ok(Self {
+            bus,
+            inputs: config.inputs,
+            outputs: config.outputs,
+            on_error: config.on_error,
+            simulated,
+            health,
+        })
+    }
+
+    pub fn validate_params(params: &toml::Value) -> Result<(), RuntimeError> {
+        let _ = I2cConfig::from_params(params)?;
+        Ok(())
+    }
+
+    fn read_registers(&mut self, inputs: &mut [u8]) -> Result<(), RuntimeError> {
+        for register in &self.inputs {
+            let value = self.bus.read_byte(register.device, register.register)?;
+            for slot in &register.slots {
+                let Some(target) = inputs.get_mut(slot.byte) else {
+                    return Err(invalid_i2c("i2c mapping outside input buffer"));
+                };
+                match slot.bits {
+                    None => *target = value,
+                    Some((image_bit, register_bit)) => {
+                        if value & (1 << register_bit) != 0 {
+                            *target |= 1 << image_bit;
+                        } else {
+                            *target &= !(1 << image_bit);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_registers(&mut self, outputs: &[u8]) -> Result<(), RuntimeError> {
+        for register in &mut self.outputs {
+            let mut value = 0u8;
+            for slot in &register.slots {
+                let Some(source) = outputs.get(slot.byte) else {
+                    return Err(invalid_i2c("i2c mapping outside output buffer"));
+                };
+                match slot.bits {
+                    None => value = *source,
+                    Some((image_bit, register_bit)) => {
+                        if source & (1 << image_bit) != 0 {
+                            value |= 1 << register_bit;
+                        }
+                    }
+                }
+            }
+            if register.last_written != Some(value) {
+                self.bus
+                    .write_byte(register.device, register.register, value)?;
+                register.last_written = Some(value);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_error(&mut self, err: RuntimeError) -> Result<(), RuntimeError> {
+        let message = SmolStr::new(err.to_string());
+        if matches!(self.on_error, IoDriverErrorPolicy::Fault) {
+            self.health = IoDriverHealth::Faulted { error: message };
+            return Err(err);
+        }
+        self.health = IoDriverHealth::Degraded { error: message };
+        Ok(())
+    }
+
+    fn mark_ok(&mut self) {
+        self.health = match &self.simulated {
+            Some(reason) => IoDriverHealth::Degraded {
+                error: reason.clone(),
+            },
+            None => IoDriverHealth::Ok,
+        };
+    }
+}
+
+impl IoDriver for I2cIoDriver {
+    fn read_inputs(&mut self, inputs: &mut [u8]) -> Result<(), RuntimeError> {
+        match self.read_registers(inputs) {
+            Ok(()) => {
+                self.mark_ok();
+                Ok(())
+            }
+            Err(err) => self.handle_error(err),
+        }
+    }
+
+    fn write_outputs(&mut self, outputs: &[u8]) -> Result<(), RuntimeError> {
+        match self.write_registers(outputs) {
+            Ok(()) => {
+                self.mark_ok();
+                Ok(())
+            }
+            Err(err) => self.handle_error(err),
+        }
+    }
+
+    fn health(&self) -> IoDriverHealth {
+        self.health.clone()
+    }
+
+    fn channels(&self) -> Vec<IoChannel> {
+        let inputs = self
+            .inputs
+            .iter()
+            .flat_map(|register| register.channels(IoArea::Input));
+        let outputs = self
+            .outputs
+            .iter()
+            .flat_map(|register| register.channels(IoArea::Output));
+        inputs.chain(outputs).collect()
+    }
+}
+
+/// One device register and the process-image points it feeds or is built from.
+#[derive(Debug)]
+struct I2cRegister {
+    device: u16,
+    register: u8,
+    slots: Vec<I2cSlot>,
+    last_written: Option<u8>,
+}
+
+impl I2cRegister {
+    fn channels(&self, area: IoArea) -> impl Iterator<Item = IoChannel> + '_ {
+        self.slots.iter().map(move |slot| {
+            let byte = slot.byte as u32;
+            let (size, bit, label) = match slot.bits {
+                None => (
+                    IoSize::Byte,
+                    0,
+                    format!(
+                        "device {:#04x} register {:#04x}",
+                        self.device, self.register
+                    ),
+                ),
+                Some((image_bit, register_bit)) => (
+                    IoSize::Bit,
+                    image_bit,
+                    format!(
+                        "device {:#04x} register {:#04x} bit {register_bit}",
+                        self.device, self.register
+                    ),
+                ),
+            };
+            IoChannel {
+                address: IoAddress {
+                    area,
+                    size,
+                    byte,
+                    bit,
+                    path: vec![byte],
+                    wildcard: false,
+                },
+                label: SmolStr::new(label),
+            }
+        })
+    }
+}
+
+/// Whole-byte slot, or `(image bit, register bit)` for a single bit.
+#[derive(Debug, Clone, Copy)]
+struct I2cSlot {
+    byte: usize,
+    bits: Option<(u8, u8)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct I2cInit {
+    device: u16,
+    register: u8,
+    value: u8,
+}
+
+#[derive(Debug)]
+struct I2cConfig {
+    bus: String,
+    on_error: IoDriverErrorPolicy,
+    fallback: IoDriverFallback,
+    init: Vec<I2cInit>,
+    inputs: Vec<I2cRegister>,
+    outputs: Vec<I2cRegister>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct I2cToml {
+    bus: Option<String>,
+    on_error: Option<String>,
+    fallback: Option<String>,
+    #[serde(default)]
+    init: Vec<I2cInitToml>,
+    #[serde(default)]
+    channels: Vec<I2cChannelToml>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct I2cInitToml {
+    device: u16,
+    register: u8,
+    value: u8,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct I2cChannelToml {
+    address: String,
+    device: u16,
+    register: u8,
+    bit: Option<u8>,
+}
+
+impl I2cConfig {
+    fn from_params(value: &toml::Value) -> Result<Self, RuntimeError> {
+        let params: I2cToml = value
+            .clone()
+            .try_into()
+            .map_err(|err| RuntimeError::InvalidConfig(format!("io.params: {err}").into()))?;
+        let on_error = params
+            .on_error
+            .as_deref()
+            .map(IoDriverErrorPolicy::parse)
+            .transpose()?
+            .unwrap_or(IoDriverErrorPolicy::Fault);
+        let fallback = params
+            .fallback
+            .as_deref()
+            .map(IoDriverFallback::parse)
+            .transpose()?
+            .unwrap_or_default();
+        let mut init = Vec::with_capacity(params.init.len());
+        for entry in params.init {
+            check_device(entry.device)?;
+            init.push(I2cInit {
+                device: entry.device,
+                register: entry.register,
+                value: entry.value,
+            });
+        }
+
+        let mut inputs: Vec<I2cRegister> = Vec::new();
+        let mut outputs: Vec<I2cRegister> = Vec::new();
+        for entry in params.channels {
+            check_device(entry.device)?;
+            let address = IoAddress::parse(&entry.address)?;
+            if address.wildcard || address.path.len() != 1 {
+                return Err(invalid_i2c(format!(
+                    "i2c channel address '{}' must be a simple %I/%Q address",
+                    entry.address
+                )));
+            }
+            let bits = match (address.size, entry.bit) {
+                (IoSize::Byte, None) => None,
+                (IoSize::Bit, bit) => Some((address.bit, bit.unwrap_or(address.bit))),
+                (IoSize::Byte, Some(_)) => {
+                    return Err(invalid_i2c(format!(
+                        "i2c channel '{}' sets 'bit' on a byte address",
+                        entry.address
+                    )))
+                }
+                _ => {
+                    return Err(invalid_i2c(format!(
+                        "i2c channel address '{}' must be a bit (X) or byte (B) address",
+                        entry.address
+                    )))
+                }
+            };
+            if let Some((_, register_bit)) = bits {
+                if register_bit > 7 {
+                    return Err(invalid_i2c(format!(
+                        "i2c channel '{}' register bit {register_bit} is out of range (0..=7)",
+                        entry.address
+                    )));
+                }
+            }
+            let registers = match address.area {
+                IoArea::Input => &mut inputs,
+                IoArea::Output => &mut outputs,
+                IoArea::Memory => {
+                    return Err(invalid_i2c(format!(
+                        "i2c channel address '{}' must be %I or %Q",
+                        entry.address
+                    )))
+                }
+            };
+            let slot = I2cSlot {
+                byte: address.byte as usize,
+                bits,
+            };
+            match registers
+                .iter_mut()
+                .find(|r| r.device == entry.device && r.register == entry.register)
+            {
+                Some(register) => register.slots.push(slot),
+                None => registers.push(I2cRegister {
+                    device: entry.device,
+                    register: entry.register,
+                    slots: vec![slot],
+                    last_written: None,
+                }),
+            }
+        }
+        for register in &outputs {
+            check_output_slots(register)?;
+        }
+
+        Ok(Self {
+            bus: params.bus.unwrap_or_else(|| "/dev/i2c-1".to_string()),
+            on_error,
+            fallback,
+            init,
+            inputs,
+            outputs,
+        })
+    }
+}
+
+fn check_device(device: u16) -> Result<(), RuntimeError> {
+    if device > 0x7F {
+        return Err(invalid_i2c(format!(
+            "i2c device address {device:#04x} is not a 7-bit address"
+        )));
+    }
+    Ok(())
+}
+
+/// An output register is written whole, so its slots must not compete for bits.
+fn check_output_slots(register: &I2cRegister) -> Result<(), RuntimeError> {
+    let mut used = 0u8;
+    for slot in &register.slots {
+        let mask = match slot.bits {
+            None => 0xFF,
+            Some((_, register_bit)) => 1 << register_bit,
+        };
+        if used & mask != 0 {
+            return Err(invalid_i2c(format!(
+                "i2c device {:#04x} register {:#04x} is written by more than one output channel",
+                register.device, register.register
+            )));
+        }
+        used |= mask;
+    }
+    Ok(())
+}
+
+fn write_init(bus: &mut dyn I2cBus, init: &[I2cInit]) -> Result<(), RuntimeError> {
+    for entry in init {
+        bus.write_byte(entry.device, entry.register, entry.value)?;
+    }
+    Ok(())
+}
+
+fn invalid_i2c(msg: impl Into<String>) -> RuntimeError {
+    RuntimeError::InvalidConfig(SmolStr::new(msg.into()))
+}
+
+trait I2cBus: Send {
+    fn read_byte(&mut self, device: u16, register: u8) -> Result<u8, RuntimeError>;
+    fn write_byte(&mut self, device: u16, register: u8, value: u8) -> Result<(), RuntimeError>;
+}
+
+fn open_bus(config: &I2cConfig) -> Result<Box<dyn I2cBus>, RuntimeError> {
+    let mut bus = open_linux_bus(config)?;
+    write_init(bus.as_mut(), &config.init)?;
+    Ok(bus)
+}
+
+#[cfg(all(feature = "pi-wire", target_os = "linux"))]
+fn open_linux_bus(config: &I2cConfig) -> Result<Box<dyn I2cBus>, RuntimeError> {
+    let mut devices = config
+        .init
+        .iter()
+        .map(|entry| entry.device)
+        .chain(config.inputs.iter().map(|register| register.device))
+        .chain(config.outputs.iter().map(|register| register.device))
+        .collect::<Vec<_>>();
+    devices.sort_unstable();
+    devices.dedup();
+    let mut bus = LinuxI2cBus {
+        devices: HashMap::new(),
+    };
+    for device in devices {
+        let handle = i2cdev::linux::LinuxI2CDevice::new(&config.bus, device).map_err(|err| {
+            RuntimeError::IoDriver(SmolStr::new(format!(
+                "i2c open {} device {device:#04x} failed: {err}",
+                config.bus
+            )))
+        })?;
+        bus.devices.insert(device, handle);
+    }
+    Ok(Box::new(bus))
+}
+
+#[cfg(not(all(feature = "pi-wire", target_os = "linux")))]
+fn open_linux_bus(config: &I2cConfig) -> Result<Box<dyn I2cBus>, RuntimeError> {
+    Err(invalid_i2c(format!(
+        "i2c bus {} requires feature 'pi-wire' on Linux",
+        config.bus
+    )))
+}
+
+#[cfg(all(feature = "pi-wire", target_os = "linux"))]
+struct LinuxI2cBus {
+    devices: HashMap<u16, i2cdev::linux::LinuxI2CDevice>,
+}
+
+#[cfg(all(feature = "pi-wire", target_os = "linux"))]
+impl LinuxI2cBus {
+    fn device(&mut self, device: u16) -> Result<&mut i2cdev::linux::LinuxI2CDevice, RuntimeError> {
+        self.devices.get_mut(&device).ok_or_else(|| {
+            RuntimeError::IoDriver(SmolStr::new(format!(
+                "i2c device {device:#04x} is not open"
+            )))
+        })
+    }
+}
+
+#[cfg(all(feature = "pi-wire", target_os = "linux"))]
+impl I2cBus for LinuxI2cBus {
+    fn read_byte(&mut self, device: u16, register: u8) -> Result<u8, RuntimeError> {
+        use i2cdev::core::I2CDevice;
+        self.device(device)?
+            .smbus_read_byte_data(register)
+            .map_err(|err| {
+                RuntimeError::IoDriver(SmolStr::new(format!(
+                    "i2c read device {device:#04x} register {register:#04x} failed: {err}"
+                )))
+            })
+    }
+
+    fn write_byte(&mut self, device: u16, register: u8, value: u8) -> Result<(), RuntimeError> {
+        use i2cdev::core::I2CDevice;
+        self.device(device)?
+            .smbus_write_byte_data(register, value)
+            .map_err(|err| {
+                RuntimeError::IoDriver(SmolStr::new(format!(
+                    "i2c write device {device:#04x} register {register:#04x} failed: {err}"
+                )))
+            })
+    }
+}
+
+/// In-memory register file used when `fallback = "simulated"` and the bus is unavailable.
+#[derive(Debug, Default)]
+struct SimulatedI2cBus {
+    registers: HashMap<(u16, u8), u8>,
+}
+
+impl I2cBus for SimulatedI2cBus {
+    fn read_byte(&mut self, device: u16, register: u8) -> Result<u8, RuntimeError> {
+        Ok(self
+            .registers
+            .get(&(device, register))
+            .copied()
+            .unwrap_or(0))
+    }
+
+    fn write_byte(&mut self, device: u16, register: u8, value: u8) -> Result<(), RuntimeError> {
+        self.registers.insert((device, register), value);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simulated_driver(channels: &str) -> I2cIoDriver {
+        let params: toml::Value = toml::from_str(&format!(
+            r#"
+bus = "/nonexistent/i2c-9"
+fallback = "simulated"
+channels = {channels}
+"#
+        ))
+        .unwrap();
+        I2cIoDriver::from_params(&params).expect("simulated i2c")
+    }
+
+    #[test]
+    fn parse_groups_channels_by_register() {
+        let params: toml::Value = toml::from_str(
+            r#"
+init = [ { device = 0x20, register = 0x00, value = 0xFF } ]
+channels = [
+  { address = "%IB0", device = 0x20, register = 0x12 },
+  { address = "%QX0.0", device = 0x20, register = 0x13 },
+  { address = "%QX0.1", device = 0x20, register = 0x13, bit = 7 },
+]
+"#,
+        )
+        .unwrap();
+        let config = I2cConfig::from_params(&params).expect("config");
+        assert_eq!(config.bus, "/dev/i2c-1");
+        assert_eq!(config.init.len(), 1);
+        assert_eq!(config.inputs.len(), 1);
+        assert_eq!(config.outputs.len(), 1);
+        assert_eq!(config.outputs[0].slots.len(), 2);
+    }
+
+    #[test]
+    fn rejects_word_addresses_and_shared_output_bits() {
+        let word: toml::Value = toml::from_str(
+            r#"channels = [ { address = "%IW0", device = 0x20, register = 0x12 } ]"#,
+        )
+        .unwrap();
+        let err = I2cConfig::from_params(&word).unwrap_err();
+        assert!(err.to_string().contains("bit (X) or byte (B)"));
+
+        let shared: toml::Value = toml::from_str(
+            r#"channels = [
+  { address = "%QB0", device = 0x20, register = 0x13 },
+  { address = "%QX1.0", device = 0x20, register = 0x13 },
+]"#,
+        )
+        .unwrap();
+        let err = I2cConfig::from_params(&shared).unwrap_err();
+        assert!(err.to_string().contains("more than one output channel"));
+    }
+
+    #[test]
+    fn missing_bus_without_fallback_fails_to_load() {
+        let params: toml::Value = toml::from_str(
+            r#"
+bus = "/nonexistent/i2c-9"
+channels = [ { address = "%IB0", device = 0x20, register = 0x12 } ]
+"#,
+        )
+        .unwrap();
+        assert!(I2cIoDriver::from_params(&params).is_err());
+    }
+
+    #[test]
+    fn simulated_bus_reads_back_written_registers() {
+        let mut driver = simulated_driver(
+            r#"[
+  { address = "%QX0.0", device = 0x20, register = 0x14, bit = 3 },
+  { address = "%IB1", device = 0x20, register = 0x14 },
+]"#,
+        );
+        assert!(matches!(
+            driver.health(),
+            IoDriverHealth::Degraded { ref error } if error.starts_with("simulated:")
+        ));
+        driver.write_outputs(&[0b1, 0]).expect("write");
+        let mut inputs = [0, 0];
+        driver.read_inputs(&mut inputs).expect("read");
+        assert_eq!(inputs[1], 0b1000);
+        assert_eq!(driver.channels().len(), 2);
+    }
+}
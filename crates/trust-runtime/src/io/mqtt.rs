@@ -2,11 +2,16 @@
 
 #![allow(missing_docs)]
 
+#[cfg(feature = "mqtt-wire")]
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+#[cfg(feature = "mqtt-wire")]
+use std::sync::Mutex;
+#[cfg(feature = "mqtt-wire")]
 use std::thread;
 use std::time::{Duration as StdDuration, Instant};
 
+#[cfg(feature = "mqtt-wire")]
 use rumqttc::{Client, Event, MqttOptions, Packet, QoS};
 use serde::Deserialize;
 use smol_str::SmolStr;
@@ -14,12 +19,15 @@ use smol_str::SmolStr;
 use crate::error::RuntimeError;
 use crate::io::{IoDriver, IoDriverHealth};
 
+// Without `mqtt-wire` the configuration is only validated, never connected.
+#[cfg_attr(not(feature = "mqtt-wire"), allow(dead_code))]
 #[derive(Debug, Clone)]
 struct BrokerEndpoint {
     host: SmolStr,
     port: u16,
 }
 
+#[cfg_attr(not(feature = "mqtt-wire"), allow(dead_code))]
 #[derive(Debug, Clone)]
 struct MqttIoConfig {
     endpoint: BrokerEndpoint,
@@ -121,6 +129,7 @@ trait MqttSessionFactory: Send + Sync {
 #[derive(Debug, Default)]
 struct RumqttSessionFactory;
 
+#[cfg(feature = "mqtt-wire")]
 struct RumqttSession {
     client: Client,
     incoming: Arc<Mutex<Option<Vec<u8>>>>,
@@ -129,6 +138,16 @@ struct RumqttSession {
     _worker: thread::JoinHandle<()>,
 }
 
+#[cfg(not(feature = "mqtt-wire"))]
+impl MqttSessionFactory for RumqttSessionFactory {
+    fn connect(&self, _config: &MqttIoConfig) -> Result<Box<dyn MqttSession>, RuntimeError> {
+        Err(RuntimeError::IoDriver(
+            "mqtt transport requires feature 'mqtt-wire'".into(),
+        ))
+    }
+}
+
+#[cfg(feature = "mqtt-wire")]
 impl MqttSessionFactory for RumqttSessionFactory {
     fn connect(&self, config: &MqttIoConfig) -> Result<Box<dyn MqttSession>, RuntimeError> {
         let mut options = MqttOptions::new(
@@ -193,6 +212,7 @@ impl MqttSessionFactory for RumqttSessionFactory {
     }
 }
 
+#[cfg(feature = "mqtt-wire")]
 impl MqttSession for RumqttSession {
     fn is_connected(&self) -> bool {
         self.connected.load(Ordering::SeqCst)
@@ -380,7 +400,9 @@ fn is_local_host(host: &str) -> bool {
 mod tests {
     use super::*;
     use std::collections::VecDeque;
-    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::thread;
 
     #[derive(Default)]
     struct MockState {
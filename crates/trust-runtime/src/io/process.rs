@@ -0,0 +1,508 @@
+//! Out-of-tree I/O drivers run as subprocesses.
+//!
+//! The runtime talks to the driver over its stdin/stdout, one JSON object per
+//! line. Each request carries an `id` and an `op` (`init`, `read`, `write`,
+//! `shutdown`). The driver answers with the same `id`, `ok`, and, depending on
+//! the op, `version`, `channels`, `inputs`, `health`, or `error`. Driver
+//! authors implement [`IoDriver`] as for an in-tree driver and hand a
+//! constructor to [`serve_process_driver`].
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+use crate::error::RuntimeError;
+use crate::io::{format_io_address, IoAddress, IoChannel, IoDriver, IoDriverHealth};
+
+/// Version of the line protocol spoken between the runtime and a process driver.
+pub const PROCESS_DRIVER_PROTOCOL_VERSION: u32 = 1;
+
+/// Largest image a process driver may be asked to read, in bytes.
+const MAX_IMAGE_LEN: usize = 64 * 1024;
+
+/// A process driver declared in `[[runtime.io_drivers]]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessDriverSpec {
+    /// Driver name used in io.toml.
+    pub name: SmolStr,
+    /// Executable; relative paths with a directory resolve against the project folder.
+    pub command: PathBuf,
+    pub args: Vec<String>,
+    /// How long to wait for each reply before the driver is treated as faulted.
+    pub timeout: Duration,
+}
+
+impl ProcessDriverSpec {
+    /// Resolve a relative `command` such as `drivers/acme-io` against `root`.
+    /// Bare names are left for the `PATH` lookup.
+    #[must_use]
+    pub fn resolve(&self, root: &Path) -> Self {
+        let mut spec = self.clone();
+        if self.command.is_relative() && self.command.components().count() > 1 {
+            spec.command = root.join(&self.command);
+        }
+        spec
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WireRequest {
+    id: u64,
+    #[serde(flatten)]
+    op: WireOp,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum WireOp {
+    Init {
+        version: u32,
+        params: serde_json::Value,
+    },
+    Read {
+        len: usize,
+    },
+    Write {
+        outputs: Vec<u8>,
+    },
+    Shutdown,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WireReply {
+    id: u64,
+    ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    channels: Option<Vec<WireChannel>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    inputs: Option<Vec<u8>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    health: Option<WireHealth>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WireChannel {
+    address: String,
+    label: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WireHealth {
+    state: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl WireHealth {
+    fn from_health(health: &IoDriverHealth) -> Self {
+        match health {
+            IoDriverHealth::Ok => Self {
+                state: "ok".to_string(),
+                error: None,
+            },
+            IoDriverHealth::Degraded { error } => Self {
+                state: "degraded".to_string(),
+                error: Some(error.to_string()),
+            },
+            IoDriverHealth::Faulted { error } => Self {
+                state: "faulted".to_string(),
+                error: Some(error.to_string()),
+            },
+        }
+    }
+
+    fn into_health(self) -> IoDriverHealth {
+        let error = SmolStr::new(self.error.unwrap_or_default());
+        match self.state.as_str() {
+            "degraded" => IoDriverHealth::Degraded { error },
+            "faulted" => IoDriverHealth::Faulted { error },
+            _ => IoDriverHealth::Ok,
+        }
+    }
+}
+
+/// Runtime side of a process driver.
+pub struct ProcessIoDriver {
+    name: SmolStr,
+    child: Option<Child>,
+    writer: Box<dyn Write + Send>,
+    replies: Receiver<Result<String, String>>,
+    timeout: Duration,
+    next_id: u64,
+    channels: Vec<IoChannel>,
+    health: IoDriverHealth,
+}
+
+impl std::fmt::Debug for ProcessIoDriver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProcessIoDriver")
+            .field("name", &self.name)
+            .field("pid", &self.child.as_ref().map(Child::id))
+            .field("channels", &self.channels.len())
+            .finish()
+    }
+}
+
+impl ProcessIoDriver {
+    /// Start the driver executable and run the `init` handshake with `params`.
+    pub fn spawn(spec: &ProcessDriverSpec, params: &toml::Value) -> Result<Self, RuntimeError> {
+        let mut child = Command::new(&spec.command)
+            .args(&spec.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|err| {
+                RuntimeError::IoDriver(SmolStr::new(format!(
+                    "{}: failed to start {}: {err}",
+                    spec.name,
+                    spec.command.display()
+                )))
+            })?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            return Err(RuntimeError::IoDriver(SmolStr::new(format!(
+                "{}: driver pipes unavailable",
+                spec.name
+            ))));
+        };
+        let mut driver = match Self::from_io(spec.name.clone(), stdout, stdin, spec.timeout, params)
+        {
+            Ok(driver) => driver,
+            Err(err) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(err);
+            }
+        };
+        driver.child = Some(child);
+        Ok(driver)
+    }
+
+    /// Run the protocol over an already connected stream pair, for drivers
+    /// reached through a socket rather than a child process.
+    pub fn from_io(
+        name: impl Into<SmolStr>,
+        reader: impl Read + Send + 'static,
+        writer: impl Write + Send + 'static,
+        timeout: Duration,
+        params: &toml::Value,
+    ) -> Result<Self, RuntimeError> {
+        let name = name.into();
+        let (sender, replies) = mpsc::channel();
+        std::thread::Builder::new()
+            .name(format!("io-driver-{name}"))
+            .spawn(move || {
+                for line in BufReader::new(reader).lines() {
+                    let message = line.map_err(|err| err.to_string());
+                    let failed = message.is_err();
+                    if sender.send(message).is_err() || failed {
+                        return;
+                    }
+                }
+                let _ = sender.send(Err("driver closed its output".to_string()));
+            })
+            .map_err(|err| {
+                RuntimeError::IoDriver(SmolStr::new(format!("{name}: reader thread: {err}")))
+            })?;
+
+        let mut driver = Self {
+            name,
+            child: None,
+            writer: Box::new(writer),
+            replies,
+            timeout,
+            next_id: 1,
+            channels: Vec::new(),
+            health: IoDriverHealth::Ok,
+        };
+        let params = serde_json::to_value(params)
+            .map_err(|err| RuntimeError::InvalidConfig(format!("io.params: {err}").into()))?;
+        let reply = driver.request(WireOp::Init {
+            version: PROCESS_DRIVER_PROTOCOL_VERSION,
+            params,
+        })?;
+        if reply.version != Some(PROCESS_DRIVER_PROTOCOL_VERSION) {
+            return Err(driver.error(format!(
+                "driver speaks protocol version {}, runtime expects {PROCESS_DRIVER_PROTOCOL_VERSION}",
+                reply
+                    .version
+                    .map_or_else(|| "<none>".to_string(), |v| v.to_string())
+            )));
+        }
+        for channel in reply.channels.unwrap_or_default() {
+            let address = IoAddress::parse(&channel.address)
+                .map_err(|err| driver.error(format!("channel '{}': {err}", channel.label)))?;
+            driver.channels.push(IoChannel {
+                address,
+                label: SmolStr::new(channel.label),
+            });
+        }
+        if let Some(health) = reply.health {
+            driver.health = health.into_health();
+        }
+        Ok(driver)
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> RuntimeError {
+        RuntimeError::IoDriver(SmolStr::new(format!("{}: {message}", self.name)))
+    }
+
+    fn request(&mut self, op: WireOp) -> Result<WireReply, RuntimeError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut line = serde_json::to_string(&WireRequest { id, op })
+            .map_err(|err| self.error(format!("encode request: {err}")))?;
+        line.push('\n');
+        self.writer
+            .write_all(line.as_bytes())
+            .and_then(|()| self.writer.flush())
+            .map_err(|err| self.error(format!("write request: {err}")))?;
+
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let line = match self.replies.recv_timeout(remaining) {
+                Ok(Ok(line)) => line,
+                Ok(Err(err)) => return Err(self.error(err)),
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(
+                        self.error(format!("no reply within {} ms", self.timeout.as_millis()))
+                    )
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(self.error("driver closed its output"))
+                }
+            };
+            let reply: WireReply = serde_json::from_str(&line)
+                .map_err(|err| self.error(format!("invalid reply '{line}': {err}")))?;
+            // Replies to requests that already timed out are dropped.
+            if reply.id < id {
+                continue;
+            }
+            if !reply.ok {
+                return Err(self.error(reply.error.unwrap_or_else(|| "request failed".into())));
+            }
+            return Ok(reply);
+        }
+    }
+
+    fn exchange(&mut self, op: WireOp) -> Result<WireReply, RuntimeError> {
+        let mut reply = match self.request(op) {
+            Ok(reply) => reply,
+            Err(err) => {
+                self.health = IoDriverHealth::Faulted {
+                    error: SmolStr::new(err.to_string()),
+                };
+                return Err(err);
+            }
+        };
+        self.health = reply
+            .health
+            .take()
+            .map_or(IoDriverHealth::Ok, WireHealth::into_health);
+        if let IoDriverHealth::Faulted { error } = &self.health {
+            return Err(self.error(error));
+        }
+        Ok(reply)
+    }
+}
+
+impl IoDriver for ProcessIoDriver {
+    fn read_inputs(&mut self, inputs: &mut [u8]) -> Result<(), RuntimeError> {
+        let reply = self.exchange(WireOp::Read { len: inputs.len() })?;
+        let data = reply.inputs.unwrap_or_default();
+        let len = inputs.len().min(data.len());
+        inputs[..len].copy_from_slice(&data[..len]);
+        Ok(())
+    }
+
+    fn write_outputs(&mut self, outputs: &[u8]) -> Result<(), RuntimeError> {
+        self.exchange(WireOp::Write {
+            outputs: outputs.to_vec(),
+        })?;
+        Ok(())
+    }
+
+    fn health(&self) -> IoDriverHealth {
+        self.health.clone()
+    }
+
+    fn channels(&self) -> Vec<IoChannel> {
+        self.channels.clone()
+    }
+}
+
+impl Drop for ProcessIoDriver {
+    fn drop(&mut self) {
+        let Some(mut child) = self.child.take() else {
+            return;
+        };
+        let _ = self.request(WireOp::Shutdown);
+        let deadline = Instant::now() + self.timeout;
+        while Instant::now() < deadline {
+            if matches!(child.try_wait(), Ok(Some(_))) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+/// Serve the process driver protocol on stdin/stdout.
+///
+/// Call this from the `main` of an out-of-tree driver. `create` receives the
+/// driver's `params` table from io.toml, like an in-tree `from_params`.
+pub fn serve_process_driver<F>(create: F) -> Result<(), RuntimeError>
+where
+    F: FnMut(&toml::Value) -> Result<Box<dyn IoDriver>, RuntimeError>,
+{
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    serve_process_driver_io(create, stdin.lock(), stdout.lock())
+}
+
+/// [`serve_process_driver`] over arbitrary streams. Returns when the runtime
+/// sends `shutdown` or closes the input.
+pub fn serve_process_driver_io<F, R, W>(
+    mut create: F,
+    reader: R,
+    mut writer: W,
+) -> Result<(), RuntimeError>
+where
+    F: FnMut(&toml::Value) -> Result<Box<dyn IoDriver>, RuntimeError>,
+    R: BufRead,
+    W: Write,
+{
+    let mut driver: Option<Box<dyn IoDriver>> = None;
+    for line in reader.lines() {
+        let line = line.map_err(|err| RuntimeError::IoDriver(format!("read: {err}").into()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request: WireRequest = match serde_json::from_str(&line) {
+            Ok(request) => request,
+            Err(err) => {
+                send_reply(&mut writer, failure(0, format!("invalid request: {err}")))?;
+                continue;
+            }
+        };
+        let id = request.id;
+        let reply = match request.op {
+            WireOp::Init { version, params } => {
+                if version != PROCESS_DRIVER_PROTOCOL_VERSION {
+                    failure(
+                        id,
+                        format!(
+                            "unsupported protocol version {version} (driver speaks {PROCESS_DRIVER_PROTOCOL_VERSION})"
+                        ),
+                    )
+                } else {
+                    match serde_json::from_value::<toml::Value>(params)
+                        .map_err(|err| {
+                            RuntimeError::InvalidConfig(format!("io.params: {err}").into())
+                        })
+                        .and_then(|params| create(&params))
+                    {
+                        Ok(created) => {
+                            let reply = WireReply {
+                                id,
+                                ok: true,
+                                version: Some(PROCESS_DRIVER_PROTOCOL_VERSION),
+                                channels: Some(
+                                    created
+                                        .channels()
+                                        .into_iter()
+                                        .map(|channel| WireChannel {
+                                            address: format_io_address(&channel.address),
+                                            label: channel.label.to_string(),
+                                        })
+                                        .collect(),
+                                ),
+                                health: Some(WireHealth::from_health(&created.health())),
+                                ..WireReply::default()
+                            };
+                            driver = Some(created);
+                            reply
+                        }
+                        Err(err) => failure(id, err.to_string()),
+                    }
+                }
+            }
+            WireOp::Read { len } => match driver.as_mut() {
+                None => failure(id, "driver not initialized"),
+                Some(_) if len > MAX_IMAGE_LEN => {
+                    failure(id, format!("input image of {len} bytes is too large"))
+                }
+                Some(driver) => {
+                    let mut inputs = vec![0; len];
+                    match driver.read_inputs(&mut inputs) {
+                        Ok(()) => WireReply {
+                            id,
+                            ok: true,
+                            inputs: Some(inputs),
+                            health: Some(WireHealth::from_health(&driver.health())),
+                            ..WireReply::default()
+                        },
+                        Err(err) => failure(id, err.to_string()),
+                    }
+                }
+            },
+            WireOp::Write { outputs } => match driver.as_mut() {
+                None => failure(id, "driver not initialized"),
+                Some(driver) => match driver.write_outputs(&outputs) {
+                    Ok(()) => WireReply {
+                        id,
+                        ok: true,
+                        health: Some(WireHealth::from_health(&driver.health())),
+                        ..WireReply::default()
+                    },
+                    Err(err) => failure(id, err.to_string()),
+                },
+            },
+            WireOp::Shutdown => {
+                send_reply(
+                    &mut writer,
+                    WireReply {
+                        id,
+                        ok: true,
+                        ..WireReply::default()
+                    },
+                )?;
+                return Ok(());
+            }
+        };
+        send_reply(&mut writer, reply)?;
+    }
+    Ok(())
+}
+
+fn failure(id: u64, error: impl Into<String>) -> WireReply {
+    WireReply {
+        id,
+        ok: false,
+        error: Some(error.into()),
+        ..WireReply::default()
+    }
+}
+
+fn send_reply(writer: &mut impl Write, reply: WireReply) -> Result<(), RuntimeError> {
+    let mut line = serde_json::to_string(&reply)
+        .map_err(|err| RuntimeError::IoDriver(format!("encode reply: {err}").into()))?;
+    line.push('\n');
+    writer
+        .write_all(line.as_bytes())
+        .and_then(|()| writer.flush())
+        .map_err(|err| RuntimeError::IoDriver(format!("write reply: {err}").into()))
+}
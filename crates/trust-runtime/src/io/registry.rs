@@ -8,12 +8,13 @@ use smol_str::SmolStr;
 use crate::error::RuntimeError;
 
 use super::{
-    EthercatIoDriver, GpioDriver, IoDriver, LoopbackIoDriver, ModbusTcpDriver, MqttIoDriver,
-    SimulatedIoDriver,
+    EthercatIoDriver, GpioDriver, I2cIoDriver, IoDriver, LoopbackIoDriver, ModbusTcpDriver,
+    MqttIoDriver, ProcessDriverSpec, ProcessIoDriver, SimulatedIoDriver, SpiIoDriver,
 };
 
 pub struct IoDriverRegistry {
     entries: HashMap<SmolStr, IoDriverRegistryEntry>,
+    process: HashMap<SmolStr, ProcessDriverSpec>,
 }
 
 impl Default for IoDriverRegistry {
@@ -41,6 +42,7 @@ impl IoDriverRegistry {
     pub fn new() -> Self {
         Self {
             entries: HashMap::new(),
+            process: HashMap::new(),
         }
     }
 
@@ -52,6 +54,8 @@ impl IoDriverRegistry {
         registry.register("loopback", create_loopback, validate_simulated);
 
         registry.register("gpio", create_gpio, validate_gpio);
+        registry.register("i2c", create_i2c, validate_i2c);
+        registry.register("spi", create_spi, validate_spi);
 
         registry.register("modbus-tcp", create_modbus_tcp, validate_modbus_tcp);
         registry.register_alias("modbus_tcp", "modbus-tcp");
@@ -88,10 +92,35 @@ impl IoDriverRegistry {
         }
     }
 
+    /// Register an out-of-tree driver that runs as a subprocess.
+    pub fn register_process(&mut self, spec: ProcessDriverSpec) {
+        let name = normalize_name(spec.name.clone());
+        self.process.insert(name, spec);
+    }
+
+    /// Whether `name` resolves to a built-in driver or alias.
+    pub fn is_builtin(&self, name: &str) -> bool {
+        self.entries
+            .contains_key(&normalize_name(SmolStr::new(name)))
+    }
+
     pub fn validate(&self, driver: &str, params: &toml::Value) -> Result<(), RuntimeError> {
         if is_none_driver(driver) {
             return Ok(());
         }
+        if self
+            .process
+            .contains_key(&normalize_name(SmolStr::new(driver)))
+        {
+            // Parameters are checked by the driver itself during the init handshake.
+            return if params.is_table() {
+                Ok(())
+            } else {
+                Err(RuntimeError::InvalidConfig(
+                    format!("io.params for driver '{driver}' must be a table").into(),
+                ))
+            };
+        }
         let entry = self
             .entries
             .get(&normalize_name(SmolStr::new(driver)))
@@ -110,6 +139,13 @@ impl IoDriverRegistry {
         if is_none_driver(driver) {
             return Ok(None);
         }
+        if let Some(spec) = self.process.get(&normalize_name(SmolStr::new(driver))) {
+            let process = ProcessIoDriver::spawn(spec, params)?;
+            return Ok(Some(IoDriverSpec {
+                name: normalize_name(spec.name.clone()),
+                driver: Box::new(process),
+            }));
+        }
         let entry = self
             .entries
             .get(&normalize_name(SmolStr::new(driver)))
@@ -124,12 +160,15 @@ impl IoDriverRegistry {
         }))
     }
 
-    /// Return the canonical built-in driver names (stable sorted).
+    /// Return the canonical driver names, including process drivers (stable sorted).
     pub fn canonical_driver_names(&self) -> Vec<String> {
         let mut names = BTreeSet::new();
         for entry in self.entries.values() {
             names.insert(entry.canonical.to_string());
         }
+        for name in self.process.keys() {
+            names.insert(name.to_string());
+        }
         names.into_iter().collect()
     }
 }
@@ -164,6 +203,26 @@ fn create_gpio(params: &toml::Value) -> Result<Box<dyn IoDriver>, RuntimeError>
     Ok(Box::new(driver))
 }
 
+fn validate_i2c(params: &toml::Value) -> Result<(), RuntimeError> {
+    I2cIoDriver::validate_params(params)?;
+    Ok(())
+}
+
+fn create_i2c(params: &toml::Value) -> Result<Box<dyn IoDriver>, RuntimeError> {
+    let driver = I2cIoDriver::from_params(params)?;
+    Ok(Box::new(driver))
+}
+
+fn validate_spi(params: &toml::Value) -> Result<(), RuntimeError> {
+    SpiIoDriver::validate_params(params)?;
+    Ok(())
+}
+
+fn create_spi(params: &toml::Value) -> Result<Box<dyn IoDriver>, RuntimeError> {
+    let driver = SpiIoDriver::from_params(params)?;
+    Ok(Box::new(driver))
+}
+
 fn validate_modbus_tcp(params: &toml::Value) -> Result<(), RuntimeError> {
     let _ = ModbusTcpDriver::from_params(params)?;
     Ok(())
@@ -207,10 +266,12 @@ mod tests {
             vec![
                 "ethercat".to_string(),
                 "gpio".to_string(),
+                "i2c".to_string(),
                 "loopback".to_string(),
                 "modbus-tcp".to_string(),
                 "mqtt".to_string(),
                 "simulated".to_string(),
+                "spi".to_string(),
             ]
         );
     }
@@ -0,0 +1,342 @@
+//! Linux SPI frame I/O driver (`/dev/spidevB.C`).
+
+#![allow(missing_docs)]
+
+use serde::Deserialize;
+use smol_str::SmolStr;
+
+use crate::error::RuntimeError;
+use crate::io::{IoDriver, IoDriverErrorPolicy, IoDriverFallback, IoDriverHealth};
+
+/// Exchanges one full-duplex frame per cycle: the output image slice is
+/// shifted out while the input image slice is shifted in (e.g. 74HC595 /
+/// 74HC165 chains or SPI I/O expanders in streaming mode).
+pub struct SpiIoDriver {
+    bus: Box<dyn SpiBus>,
+    frame_bytes: usize,
+    input_start: usize,
+    output_start: usize,
+    tx: Vec<u8>,
+    rx: Vec<u8>,
+    last_written: Option<Vec<u8>>,
+    on_error: IoDriverErrorPolicy,
+    simulated: Option<SmolStr>,
+    health: IoDriverHealth,
+}
+
+impl std::fmt::Debug for SpiIoDriver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpiIoDriver")
+            .field("frame_bytes", &self.frame_bytes)
+            .field("input_start", &self.input_start)
+            .field("output_start", &self.output_start)
+            .field("simulated", &self.simulated.is_some())
+            .finish()
+    }
+}
+
+impl SpiIoDriver {
+    pub fn from_params(params: &toml::Value) -> Result<Self, RuntimeError> {
+        let config = SpiConfig::from_params(params)?;
+        let (bus, simulated) = match open_bus(&config) {
+            Ok(bus) => (bus, None),
+            Err(err) if config.fallback == IoDriverFallback::Simulated => {
+                let bus: Box<dyn SpiBus> = Box::new(SimulatedSpiBus);
+                (bus, Some(SmolStr::new(format!("simulated: {err}"))))
+            }
+            Err(err) => return Err(err),
+        };
+        let health = match &simulated {
+            Some(reason) => IoDriverHealth::Degraded {
+                error: reason.clone(),
+            },
+            None => IoDriverHealth::Ok,
+        };
+        Ok(Self {
+            bus,
+            frame_bytes: config.frame_bytes,
+            input_start: config.input_start,
+            output_start: config.output_start,
+            tx: vec![0; config.frame_bytes],
+            rx: vec![0; config.frame_bytes],
+            last_written: None,
+            on_error: config.on_error,
+            simulated,
+            health,
+        })
+    }
+
+    pub fn validate_params(params: &toml::Value) -> Result<(), RuntimeError> {
+        let _ = SpiConfig::from_params(params)?;
+        Ok(())
+    }
+
+    /// Shift out the last output frame and copy the received frame into the inputs.
+    fn exchange_inputs(&mut self, inputs: &mut [u8]) -> Result<(), RuntimeError> {
+        self.bus.transfer(&self.tx, &mut self.rx)?;
+        if let Some(target) = inputs.get_mut(self.input_start..) {
+            let len = target.len().min(self.frame_bytes);
+            target[..len].copy_from_slice(&self.rx[..len]);
+        }
+        Ok(())
+    }
+
+    fn exchange_outputs(&mut self, outputs: &[u8]) -> Result<(), RuntimeError> {
+        self.tx.fill(0);
+        if let Some(source) = outputs.get(self.output_start..) {
+            let len = source.len().min(self.frame_bytes);
+            self.tx[..len].copy_from_slice(&source[..len]);
+        }
+        if self.last_written.as_deref() == Some(self.tx.as_slice()) {
+            return Ok(());
+        }
+        self.bus.transfer(&self.tx, &mut self.rx)?;
+        self.last_written = Some(self.tx.clone());
+        Ok(())
+    }
+
+    fn handle_error(&mut self, err: RuntimeError) -> Result<(), RuntimeError> {
+        let message = SmolStr::new(err.to_string());
+        if matches!(self.on_error, IoDriverErrorPolicy::Fault) {
+            self.health = IoDriverHealth::Faulted { error: message };
+            return Err(err);
+        }
+        self.health = IoDriverHealth::Degraded { error: message };
+        Ok(())
+    }
+
+    fn mark_ok(&mut self) {
+        self.health = match &self.simulated {
+            Some(reason) => IoDriverHealth::Degraded {
+                error: reason.clone(),
+            },
+            None => IoDriverHealth::Ok,
+        };
+    }
+}
+
+impl IoDriver for SpiIoDriver {
+    fn read_inputs(&mut self, inputs: &mut [u8]) -> Result<(), RuntimeError> {
+        match self.exchange_inputs(inputs) {
+            Ok(()) => {
+                self.mark_ok();
+                Ok(())
+            }
+            Err(err) => self.handle_error(err),
+        }
+    }
+
+    fn write_outputs(&mut self, outputs: &[u8]) -> Result<(), RuntimeError> {
+        match self.exchange_outputs(outputs) {
+            Ok(()) => {
+                self.mark_ok();
+                Ok(())
+            }
+            Err(err) => {
+                self.last_written = None;
+                self.handle_error(err)
+            }
+        }
+    }
+
+    fn health(&self) -> IoDriverHealth {
+        self.health.clone()
+    }
+}
+
+#[derive(Debug)]
+struct SpiConfig {
+    device: String,
+    speed_hz: u32,
+    mode: u8,
+    frame_bytes: usize,
+    input_start: usize,
+    output_start: usize,
+    on_error: IoDriverErrorPolicy,
+    fallback: IoDriverFallback,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpiToml {
+    device: Option<String>,
+    speed_hz: Option<u32>,
+    mode: Option<u8>,
+    frame_bytes: Option<usize>,
+    input_start: Option<usize>,
+    output_start: Option<usize>,
+    on_error: Option<String>,
+    fallback: Option<String>,
+}
+
+impl SpiConfig {
+    fn from_params(value: &toml::Value) -> Result<Self, RuntimeError> {
+        let params: SpiToml = value
+            .clone()
+            .try_into()
+            .map_err(|err| RuntimeError::InvalidConfig(format!("io.params: {err}").into()))?;
+        let mode = params.mode.unwrap_or(0);
+        if mode > 3 {
+            return Err(RuntimeError::InvalidConfig(
+                format!("io.params.mode {mode} is out of range (0..=3)").into(),
+            ));
+        }
+        let frame_bytes = params.frame_bytes.unwrap_or(1);
+        if frame_bytes == 0 || frame_bytes > 4096 {
+            return Err(RuntimeError::InvalidConfig(
+                format!("io.params.frame_bytes {frame_bytes} is out of range (1..=4096)").into(),
+            ));
+        }
+        let speed_hz = params.speed_hz.unwrap_or(1_000_000);
+        if speed_hz == 0 {
+            return Err(RuntimeError::InvalidConfig(
+                "io.params.speed_hz must be greater than zero".into(),
+            ));
+        }
+        let on_error = params
+            .on_error
+            .as_deref()
+            .map(IoDriverErrorPolicy::parse)
+            .transpose()?
+            .unwrap_or(IoDriverErrorPolicy::Fault);
+        let fallback = params
+            .fallback
+            .as_deref()
+            .map(IoDriverFallback::parse)
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Self {
+            device: params
+                .device
+                .unwrap_or_else(|| "/dev/spidev0.0".to_string()),
+            speed_hz,
+            mode,
+            frame_bytes,
+            input_start: params.input_start.unwrap_or(0),
+            output_start: params.output_start.unwrap_or(0),
+            on_error,
+            fallback,
+        })
+    }
+}
+
+trait SpiBus: Send {
+    fn transfer(&mut self, tx: &[u8], rx: &mut [u8]) -> Result<(), RuntimeError>;
+}
+
+#[cfg(all(feature = "pi-wire", target_os = "linux"))]
+fn open_bus(config: &SpiConfig) -> Result<Box<dyn SpiBus>, RuntimeError> {
+    use spidev::{SpiModeFlags, Spidev, SpidevOptions};
+
+    let open_error = |err: std::io::Error| {
+        RuntimeError::IoDriver(SmolStr::new(format!(
+            "spi open {} failed: {err}",
+            config.device
+        )))
+    };
+    let mut spi = Spidev::open(&config.device).map_err(open_error)?;
+    let mode = match config.mode {
+        0 => SpiModeFlags::SPI_MODE_0,
+        1 => SpiModeFlags::SPI_MODE_1,
+        2 => SpiModeFlags::SPI_MODE_2,
+        _ => SpiModeFlags::SPI_MODE_3,
+    };
+    let options = SpidevOptions::new()
+        .bits_per_word(8)
+        .max_speed_hz(config.speed_hz)
+        .mode(mode)
+        .build();
+    spi.configure(&options).map_err(open_error)?;
+    Ok(Box::new(LinuxSpiBus {
+        spi,
+        device: config.device.clone(),
+    }))
+}
+
+#[cfg(not(all(feature = "pi-wire", target_os = "linux")))]
+fn open_bus(config: &SpiConfig) -> Result<Box<dyn SpiBus>, RuntimeError> {
+    Err(RuntimeError::InvalidConfig(SmolStr::new(format!(
+        "spi device {} (mode {}, {} Hz) requires feature 'pi-wire' on Linux",
+        config.device, config.mode, config.speed_hz
+    ))))
+}
+
+#[cfg(all(feature = "pi-wire", target_os = "linux"))]
+struct LinuxSpiBus {
+    spi: spidev::Spidev,
+    device: String,
+}
+
+#[cfg(all(feature = "pi-wire", target_os = "linux"))]
+impl SpiBus for LinuxSpiBus {
+    fn transfer(&mut self, tx: &[u8], rx: &mut [u8]) -> Result<(), RuntimeError> {
+        let mut transfer = spidev::SpidevTransfer::read_write(tx, rx);
+        self.spi.transfer(&mut transfer).map_err(|err| {
+            RuntimeError::IoDriver(SmolStr::new(format!(
+                "spi transfer on {} failed: {err}",
+                self.device
+            )))
+        })
+    }
+}
+
+/// Stand-in used when `fallback = "simulated"` and the device is unavailable;
+/// outputs are dropped and inputs read as zero.
+#[derive(Debug)]
+struct SimulatedSpiBus;
+
+impl SpiBus for SimulatedSpiBus {
+    fn transfer(&mut self, _tx: &[u8], rx: &mut [u8]) -> Result<(), RuntimeError> {
+        rx.fill(0);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_spi_config_defaults() {
+        let params: toml::Value = toml::from_str(r#"frame_bytes = 2"#).unwrap();
+        let config = SpiConfig::from_params(&params).expect("config");
+        assert_eq!(config.device, "/dev/spidev0.0");
+        assert_eq!(config.speed_hz, 1_000_000);
+        assert_eq!(config.mode, 0);
+        assert_eq!(config.frame_bytes, 2);
+        assert_eq!(config.fallback, IoDriverFallback::Fault);
+    }
+
+    #[test]
+    fn rejects_invalid_mode_and_frame() {
+        let mode: toml::Value = toml::from_str(r#"mode = 4"#).unwrap();
+        assert!(SpiConfig::from_params(&mode).is_err());
+        let frame: toml::Value = toml::from_str(r#"frame_bytes = 0"#).unwrap();
+        assert!(SpiConfig::from_params(&frame).is_err());
+    }
+
+    #[test]
+    fn missing_device_uses_simulated_fallback() {
+        let params: toml::Value = toml::from_str(
+            r#"
+device = "/nonexistent/spidev9.0"
+frame_bytes = 2
+input_start = 1
+fallback = "simulated"
+"#,
+        )
+        .unwrap();
+        let mut driver = SpiIoDriver::from_params(&params).expect("simulated spi");
+        assert!(matches!(
+            driver.health(),
+            IoDriverHealth::Degraded { ref error } if error.starts_with("simulated:")
+        ));
+        driver.write_outputs(&[0xAA, 0x55]).expect("write");
+        let mut inputs = [0xFF; 3];
+        driver.read_inputs(&mut inputs).expect("read");
+        assert_eq!(inputs, [0xFF, 0, 0]);
+
+        let strict: toml::Value = toml::from_str(r#"device = "/nonexistent/spidev9.0""#).unwrap();
+        assert!(SpiIoDriver::from_params(&strict).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+//! Symbolic signal names mapped to process-image addresses by io.toml.
+
+use smol_str::SmolStr;
+
+use crate::value::Value;
+use trust_hir::TypeId;
+
+use super::{IoAddress, IoSize};
+
+/// One `[io.map]` entry: a program variable and the address it is bound to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IoSymbol {
+    /// Global name, or `<program>.<variable>` for a program variable.
+    pub name: SmolStr,
+    pub address: IoAddress,
+}
+
+/// Symbol bindings loaded from `[io.map]`, applied when the bundle loads.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IoSymbolMap {
+    pub symbols: Vec<IoSymbol>,
+}
+
+impl IoSymbolMap {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+/// Elementary type of a symbol's current value, if it can sit on an address.
+pub(crate) fn symbol_value_type(value: &Value) -> Option<(TypeId, IoSize)> {
+    let type_id = match value {
+        Value::Bool(_) => TypeId::BOOL,
+        Value::SInt(_) => TypeId::SINT,
+        Value::USInt(_) => TypeId::USINT,
+        Value::Byte(_) => TypeId::BYTE,
+        Value::Char(_) => TypeId::CHAR,
+        Value::Int(_) => TypeId::INT,
+        Value::UInt(_) => TypeId::UINT,
+        Value::Word(_) => TypeId::WORD,
+        Value::WChar(_) => TypeId::WCHAR,
+        Value::DInt(_) => TypeId::DINT,
+        Value::UDInt(_) => TypeId::UDINT,
+        Value::DWord(_) => TypeId::DWORD,
+        Value::Real(_) => TypeId::REAL,
+        Value::LInt(_) => TypeId::LINT,
+        Value::ULInt(_) => TypeId::ULINT,
+        Value::LWord(_) => TypeId::LWORD,
+        Value::LReal(_) => TypeId::LREAL,
+        _ => return None,
+    };
+    super::expected_size_for_type(type_id).map(|size| (type_id, size))
+}
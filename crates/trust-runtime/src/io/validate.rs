@@ -0,0 +1,182 @@
+//! Load-time checks of the I/O map against itself and the driver channel maps.
+
+use smol_str::SmolStr;
+
+use crate::error::RuntimeError;
+use crate::memory::IoArea;
+
+use super::{IoAddress, IoBinding, IoSize, IoTarget};
+
+/// A process-image point that a driver reads or writes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IoChannel {
+    pub address: IoAddress,
+    /// Driver-side name used in messages, e.g. `line 17`.
+    pub label: SmolStr,
+}
+
+/// A driver channel tagged with the name of the driver that owns it.
+#[derive(Debug, Clone)]
+pub struct DriverChannel {
+    pub driver: SmolStr,
+    pub channel: IoChannel,
+}
+
+/// Reject I/O maps that would silently share process-image bits: outputs
+/// written by two variables, inputs filled by two driver channels, and
+/// variables whose size differs from the driver channel they sit on.
+pub fn validate_io_map(
+    bindings: &[IoBinding],
+    channels: &[DriverChannel],
+) -> Result<(), RuntimeError> {
+    let mut outputs = bindings
+        .iter()
+        .filter(|binding| binding.address.area == IoArea::Output)
+        .filter_map(|binding| span(&binding.address).map(|span| (span, binding)))
+        .collect::<Vec<_>>();
+    outputs.sort_by_key(|(span, _)| span.start);
+    for (idx, (span, binding)) in outputs.iter().enumerate() {
+        for (other_span, other) in &outputs[idx + 1..] {
+            if other_span.start >= span.end {
+                break;
+            }
+            if owner(binding) != owner(other) {
+                return Err(conflict(format!(
+                    "'{}' at {} and '{}' at {} write the same output bits",
+                    binding_label(binding),
+                    format_io_address(&binding.address),
+                    binding_label(other),
+                    format_io_address(&other.address),
+                )));
+            }
+        }
+    }
+
+    let inputs = channels
+        .iter()
+        .filter(|entry| entry.channel.address.area == IoArea::Input)
+        .filter_map(|entry| span(&entry.channel.address).map(|span| (span, entry)))
+        .collect::<Vec<_>>();
+    for (idx, (span, entry)) in inputs.iter().enumerate() {
+        if let Some((_, other)) = inputs[idx + 1..]
+            .iter()
+            .find(|(other_span, _)| other_span.overlaps(span))
+        {
+            return Err(conflict(format!(
+                "{} {} and {} {} both fill input {}",
+                entry.driver,
+                entry.channel.label,
+                other.driver,
+                other.channel.label,
+                format_io_address(&entry.channel.address),
+            )));
+        }
+    }
+
+    for entry in channels {
+        let Some(channel_span) = span(&entry.channel.address) else {
+            continue;
+        };
+        for binding in bindings {
+            if binding.address.area != entry.channel.address.area
+                || binding.address.size == entry.channel.address.size
+            {
+                continue;
+            }
+            if span(&binding.address).is_some_and(|span| span.overlaps(&channel_span)) {
+                return Err(conflict(format!(
+                    "'{}' at {} is a {} but {} {} maps {} as a {}",
+                    binding_label(binding),
+                    format_io_address(&binding.address),
+                    size_name(binding.address.size),
+                    entry.driver,
+                    entry.channel.label,
+                    format_io_address(&entry.channel.address),
+                    size_name(entry.channel.address.size),
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Bit range of a flat address; wildcards and hierarchical addresses have none.
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    start: u64,
+    end: u64,
+}
+
+impl Span {
+    fn overlaps(&self, other: &Span) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+fn span(address: &IoAddress) -> Option<Span> {
+    if address.wildcard || address.path.len() > 1 {
+        return None;
+    }
+    let start = u64::from(address.byte) * 8;
+    let (start, bits) = match address.size {
+        IoSize::Bit => (start + u64::from(address.bit), 1),
+        IoSize::Byte => (start, 8),
+        IoSize::Word => (start, 16),
+        IoSize::DWord => (start, 32),
+        IoSize::LWord => (start, 64),
+    };
+    Some(Span {
+        start,
+        end: start + bits,
+    })
+}
+
+/// Bindings of one declared variable (struct fields, union variants) may share bits.
+fn owner(binding: &IoBinding) -> String {
+    if let Some(name) = &binding.display_name {
+        return name.to_string();
+    }
+    match &binding.target {
+        IoTarget::Name(name) => name.to_string(),
+        IoTarget::Reference(reference) => {
+            format!("{:?}:{}", reference.location, reference.offset)
+        }
+    }
+}
+
+fn binding_label(binding: &IoBinding) -> String {
+    match (&binding.display_name, &binding.target) {
+        (Some(name), _) | (None, IoTarget::Name(name)) => name.to_string(),
+        (None, IoTarget::Reference(_)) => "<unnamed>".to_string(),
+    }
+}
+
+fn size_name(size: IoSize) -> &'static str {
+    match size {
+        IoSize::Bit => "BOOL",
+        IoSize::Byte => "BYTE",
+        IoSize::Word => "WORD",
+        IoSize::DWord => "DWORD",
+        IoSize::LWord => "LWORD",
+    }
+}
+
+/// Flat `%<area><size><byte>[.<bit>]` text of an address.
+pub fn format_io_address(address: &IoAddress) -> String {
+    let area = match address.area {
+        IoArea::Input => "I",
+        IoArea::Output => "Q",
+        IoArea::Memory => "M",
+    };
+    match address.size {
+        IoSize::Bit => format!("%{area}X{}.{}", address.byte, address.bit),
+        IoSize::Byte => format!("%{area}B{}", address.byte),
+        IoSize::Word => format!("%{area}W{}", address.byte),
+        IoSize::DWord => format!("%{area}D{}", address.byte),
+        IoSize::LWord => format!("%{area}L{}", address.byte),
+    }
+}
+
+fn conflict(message: String) -> RuntimeError {
+    RuntimeError::IoMapConflict(message.into())
+}
@@ -1,6 +1,8 @@
 //! `trust-runtime` - IEC 61131-3 Structured Text runtime interpreter.
 
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "jit"), forbid(unsafe_code))]
+// The native backend needs unsafe to call generated code; it opts in locally.
+#![cfg_attr(feature = "jit", deny(unsafe_code))]
 #![warn(missing_docs)]
 #![allow(clippy::module_name_repetitions)]
 #![recursion_limit = "512"]
@@ -16,45 +18,64 @@ pub mod bytecode;
 /// Runtime bundle configuration.
 pub mod config;
 /// Control server and protocol.
+#[cfg(feature = "services")]
 pub mod control;
 mod datetime;
 /// Debugging and tracing support.
 pub mod debug;
 /// Local discovery (mDNS) for runtimes.
+#[cfg(feature = "services")]
 pub mod discovery;
 /// Runtime errors and configuration.
 pub mod error;
 /// Expression and statement evaluation.
 pub mod eval;
+/// Persistent runtime event store with retention.
+pub mod event_store;
 /// Test harness for runtime execution.
 pub mod harness;
 /// Historian, alerts, and Prometheus observability helpers.
 pub mod historian;
 /// HMI schema and value contract helpers.
+#[cfg(feature = "services")]
 pub mod hmi;
 /// FB/Class instance management.
 pub mod instance;
 /// Direct I/O mapping.
 pub mod io;
+/// Runtime log records and sinks (stdout, syslog, journald).
+pub mod logging;
 /// Variable storage and instances.
 pub mod memory;
 /// Runtime-to-runtime mesh data sharing.
+#[cfg(feature = "services")]
 pub mod mesh;
 /// Runtime metrics collection.
 pub mod metrics;
+/// Fault, watchdog, and driver notifications (webhook, SMTP).
+pub mod notify;
 mod numeric;
 /// OPC UA profile and IEC-to-OPC UA mapping helpers.
 pub mod opcua;
 /// PLCopen XML import/export helpers (strict subset profile).
 pub mod plcopen;
+/// Real-time scheduling hooks for the resource thread.
+pub mod realtime;
+/// Hot-standby redundancy between two runtimes.
+#[cfg(feature = "services")]
+pub mod redundancy;
 /// Local package registry contracts and workflows.
+#[cfg(feature = "services")]
 pub mod registry;
 /// Retain storage support.
 pub mod retain;
 /// Resource scheduling helpers and clocks.
 pub mod scheduler;
 /// Security roles and authorization helpers.
+#[cfg(feature = "services")]
 pub mod security;
+/// Self-extracting runtime bundles (project appended to the executable).
+pub mod self_extracting;
 /// Runtime settings snapshot.
 pub mod settings;
 /// System setup helpers (writes system IO config).
@@ -63,18 +84,27 @@ pub mod setup;
 pub mod simulation;
 /// Standard library functions and FBs.
 pub mod stdlib;
+/// Precompiled library archives (`.stlib`).
+pub mod stlib;
 /// Task scheduling and cycle execution.
 pub mod task;
 /// Terminal UI for runtime monitoring.
+#[cfg(feature = "services")]
 pub mod ui;
 /// Value types and date/time profile.
 pub mod value;
 /// Watchdog and fault policies.
 pub mod watchdog;
 /// Embedded browser UI server.
+#[cfg(feature = "services")]
 pub mod web;
+/// Background worker pool for asynchronous function blocks.
+pub mod workers;
 
 mod runtime;
 
 pub(crate) use runtime::types::GlobalInitValue;
-pub use runtime::{RestartMode, RetainPolicy, RetainSnapshot, Runtime, RuntimeMetadata};
+pub use runtime::{
+    RestartMode, RestartPreviewEntry, RetainPolicy, RetainSnapshot, RetentionRules, Runtime,
+    RuntimeMetadata,
+};
@@ -0,0 +1,577 @@
+//! Runtime log records and their sinks (stdout, syslog, journald).
+
+#![allow(missing_docs)]
+
+use std::io::Write;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use smol_str::SmolStr;
+
+use crate::error::RuntimeError;
+
+/// Structured-data ID for syslog fields (RFC 5424 private enterprise form).
+const SYSLOG_SD_ID: &str = "trust@32473";
+const SYSLOG_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+#[cfg(target_os = "linux")]
+const JOURNALD_SOCKET: &str = "/run/systemd/journal/socket";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "error" => Self::Error,
+            "warn" | "warning" => Self::Warn,
+            "debug" => Self::Debug,
+            "trace" => Self::Trace,
+            _ => Self::Info,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+
+    /// Syslog severity (also used as the journald `PRIORITY`).
+    #[must_use]
+    pub fn severity(self) -> u8 {
+        match self {
+            Self::Error => 3,
+            Self::Warn => 4,
+            Self::Info => 6,
+            Self::Debug | Self::Trace => 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyslogTransport {
+    Udp,
+    Tcp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyslogSinkConfig {
+    pub transport: SyslogTransport,
+    /// `host:port` of the collector.
+    pub address: SmolStr,
+    pub facility: u8,
+    pub app_name: SmolStr,
+}
+
+impl SyslogSinkConfig {
+    /// Parses `udp://host:port` or `tcp://host:port`.
+    pub fn parse_address(text: &str) -> Result<(SyslogTransport, SmolStr), RuntimeError> {
+        let text = text.trim();
+        let (transport, rest) = if let Some(rest) = text.strip_prefix("udp://") {
+            (SyslogTransport::Udp, rest)
+        } else if let Some(rest) = text.strip_prefix("tcp://") {
+            (SyslogTransport::Tcp, rest)
+        } else {
+            return Err(RuntimeError::InvalidConfig(
+                format!("invalid runtime.log.syslog.address '{text}' (use udp:// or tcp://)")
+                    .into(),
+            ));
+        };
+        let valid = rest
+            .rsplit_once(':')
+            .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+        if !valid {
+            return Err(RuntimeError::InvalidConfig(
+                format!("invalid runtime.log.syslog.address '{text}' (missing host or port)")
+                    .into(),
+            ));
+        }
+        Ok((transport, SmolStr::new(rest)))
+    }
+
+    pub fn parse_facility(text: &str) -> Result<u8, RuntimeError> {
+        let facility = match text.trim().to_ascii_lowercase().as_str() {
+            "kern" => 0,
+            "user" => 1,
+            "daemon" => 3,
+            "auth" => 4,
+            "syslog" => 5,
+            "local0" => 16,
+            "local1" => 17,
+            "local2" => 18,
+            "local3" => 19,
+            "local4" => 20,
+            "local5" => 21,
+            "local6" => 22,
+            "local7" => 23,
+            _ => {
+                return Err(RuntimeError::InvalidConfig(
+                    format!("invalid runtime.log.syslog.facility '{text}'").into(),
+                ))
+            }
+        };
+        Ok(facility)
+    }
+}
+
+/// Where runtime log records go. Stdout is on unless disabled in `runtime.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogSinkConfig {
+    pub stdout: bool,
+    pub syslog: Option<SyslogSinkConfig>,
+    pub journald: bool,
+}
+
+impl Default for LogSinkConfig {
+    fn default() -> Self {
+        Self {
+            stdout: true,
+            syslog: None,
+            journald: false,
+        }
+    }
+}
+
+/// One structured runtime log record.
+#[derive(Debug, Clone)]
+pub struct LogRecord<'a> {
+    pub timestamp_ms: u128,
+    pub level: LogLevel,
+    pub event: &'a str,
+    pub data: &'a Value,
+}
+
+impl LogRecord<'_> {
+    /// The JSON line written to stdout.
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        json!({
+            "ts": self.timestamp_ms,
+            "level": self.level.as_str(),
+            "event": self.event,
+            "data": self.data,
+        })
+    }
+
+    /// Scalar `data` fields plus the resource name, used as structured fields
+    /// by syslog and journald.
+    fn fields(&self, resource: &str) -> Vec<(String, String)> {
+        let mut fields = vec![("resource".to_string(), resource.to_string())];
+        if let Some(data) = self.data.as_object() {
+            for (key, value) in data {
+                let text = match value {
+                    Value::Null | Value::Array(_) | Value::Object(_) => continue,
+                    Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                if key != "resource" {
+                    fields.push((key.clone(), text));
+                }
+            }
+        }
+        fields
+    }
+}
+
+/// Formats a record as an RFC 5424 syslog message.
+#[must_use]
+pub fn format_rfc5424(
+    record: &LogRecord<'_>,
+    config: &SyslogSinkConfig,
+    hostname: &str,
+    resource: &str,
+    pid: u32,
+) -> String {
+    let priority = u16::from(config.facility) * 8 + u16::from(record.level.severity());
+    let params = record
+        .fields(resource)
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let name = syslog_param_name(&key)?;
+            Some(format!(" {name}=\"{}\"", escape_sd_value(&value)))
+        })
+        .collect::<String>();
+    format!(
+        "<{priority}>1 {} {} {} {pid} {} [{SYSLOG_SD_ID}{params}] {}",
+        format_rfc3339_ms(record.timestamp_ms),
+        header_token(hostname, 255),
+        header_token(&config.app_name, 48),
+        header_token(record.event, 32),
+        record.to_json(),
+    )
+}
+
+/// Serializes a record for the journald native protocol.
+#[must_use]
+pub fn journald_payload(record: &LogRecord<'_>, identifier: &str, resource: &str) -> Vec<u8> {
+    let mut payload = Vec::new();
+    let message = format!("{} {}", record.event, record.data);
+    push_journald_field(&mut payload, "MESSAGE", &message);
+    push_journald_field(
+        &mut payload,
+        "PRIORITY",
+        &record.level.severity().to_string(),
+    );
+    push_journald_field(&mut payload, "SYSLOG_IDENTIFIER", identifier);
+    push_journald_field(&mut payload, "TRUST_EVENT", record.event);
+    for (key, value) in record.fields(resource) {
+        if let Some(name) = journald_field_name(&key) {
+            push_journald_field(&mut payload, &name, &value);
+        }
+    }
+    payload
+}
+
+/// Open log sinks. Write failures are dropped so logging never stalls the runtime.
+pub struct LogSinks {
+    stdout: bool,
+    resource: SmolStr,
+    hostname: String,
+    syslog: Option<SyslogWriter>,
+    #[cfg(target_os = "linux")]
+    journald: Option<std::os::unix::net::UnixDatagram>,
+}
+
+struct SyslogWriter {
+    config: SyslogSinkConfig,
+    target: SocketAddr,
+    udp: Option<UdpSocket>,
+    tcp: Mutex<Option<TcpStream>>,
+}
+
+impl LogSinks {
+    #[must_use]
+    pub fn stdout_only() -> Self {
+        Self {
+            stdout: true,
+            resource: SmolStr::default(),
+            hostname: local_hostname(),
+            syslog: None,
+            #[cfg(target_os = "linux")]
+            journald: None,
+        }
+    }
+
+    pub fn open(config: &LogSinkConfig, resource: &str) -> Result<Self, RuntimeError> {
+        let syslog = config.syslog.as_ref().map(SyslogWriter::open).transpose()?;
+        #[cfg(target_os = "linux")]
+        let journald = if config.journald {
+            Some(std::os::unix::net::UnixDatagram::unbound().map_err(|err| {
+                RuntimeError::ControlError(format!("journald socket: {err}").into())
+            })?)
+        } else {
+            None
+        };
+        #[cfg(not(target_os = "linux"))]
+        if config.journald {
+            return Err(RuntimeError::InvalidConfig(
+                "runtime.log.journald is only supported on Linux".into(),
+            ));
+        }
+        Ok(Self {
+            stdout: config.stdout,
+            resource: SmolStr::new(resource),
+            hostname: local_hostname(),
+            syslog,
+            #[cfg(target_os = "linux")]
+            journald,
+        })
+    }
+
+    pub fn write(&self, record: &LogRecord<'_>) {
+        if self.stdout {
+            println!("{}", record.to_json());
+        }
+        if let Some(syslog) = &self.syslog {
+            let message = format_rfc5424(
+                record,
+                &syslog.config,
+                &self.hostname,
+                &self.resource,
+                std::process::id(),
+            );
+            syslog.send(&message);
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(socket) = &self.journald {
+            let payload = journald_payload(record, "trust-runtime", &self.resource);
+            let _ = socket.send_to(&payload, JOURNALD_SOCKET);
+        }
+    }
+}
+
+impl SyslogWriter {
+    fn open(config: &SyslogSinkConfig) -> Result<Self, RuntimeError> {
+        let target = config
+            .address
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .ok_or_else(|| {
+                RuntimeError::ControlError(
+                    format!("syslog address '{}' did not resolve", config.address).into(),
+                )
+            })?;
+        let udp = match config.transport {
+            SyslogTransport::Udp => {
+                let bind = if target.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+                Some(UdpSocket::bind(bind).map_err(|err| {
+                    RuntimeError::ControlError(format!("syslog socket: {err}").into())
+                })?)
+            }
+            SyslogTransport::Tcp => None,
+        };
+        Ok(Self {
+            config: config.clone(),
+            target,
+            udp,
+            tcp: Mutex::new(None),
+        })
+    }
+
+    fn send(&self, message: &str) {
+        if let Some(socket) = &self.udp {
+            let _ = socket.send_to(message.as_bytes(), self.target);
+            return;
+        }
+        // RFC 6587 octet counting; reconnect once if the collector dropped us.
+        let frame = format!("{} {message}", message.len());
+        let Ok(mut guard) = self.tcp.lock() else {
+            return;
+        };
+        for _ in 0..2 {
+            if guard.is_none() {
+                *guard = TcpStream::connect_timeout(&self.target, SYSLOG_CONNECT_TIMEOUT).ok();
+            }
+            let Some(stream) = guard.as_mut() else {
+                return;
+            };
+            if stream.write_all(frame.as_bytes()).is_ok() {
+                return;
+            }
+            *guard = None;
+        }
+    }
+}
+
+fn local_hostname() -> String {
+    std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// RFC 5424 header fields are printable ASCII without spaces, or `-` when empty.
+fn header_token(text: &str, max: usize) -> String {
+    let token = text
+        .chars()
+        .filter(|ch| ch.is_ascii_graphic())
+        .take(max)
+        .collect::<String>();
+    if token.is_empty() {
+        "-".to_string()
+    } else {
+        token
+    }
+}
+
+fn syslog_param_name(key: &str) -> Option<String> {
+    let name = key
+        .chars()
+        .filter(|ch| ch.is_ascii_graphic() && !matches!(ch, '=' | ']' | '"'))
+        .take(32)
+        .collect::<String>();
+    (!name.is_empty()).then_some(name)
+}
+
+fn escape_sd_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '"' | '\\' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// Journald field names are uppercase ASCII letters, digits, and underscores.
+fn journald_field_name(key: &str) -> Option<String> {
+    let name = key
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() {
+                ch.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    let name = name.trim_matches('_');
+    (!name.is_empty()).then(|| format!("TRUST_{name}"))
+}
+
+fn push_journald_field(payload: &mut Vec<u8>, name: &str, value: &str) {
+    payload.extend_from_slice(name.as_bytes());
+    if value.contains('\n') {
+        payload.push(b'\n');
+        payload.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    } else {
+        payload.push(b'=');
+    }
+    payload.extend_from_slice(value.as_bytes());
+    payload.push(b'\n');
+}
+
+pub(crate) fn format_rfc3339_ms(timestamp_ms: u128) -> String {
+    let millis = (timestamp_ms % 1000) as u32;
+    let secs = (timestamp_ms / 1000) as i64;
+    let days = secs.div_euclid(86_400);
+    let seconds_of_day = secs.rem_euclid(86_400);
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{millis:03}Z",
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syslog_config() -> SyslogSinkConfig {
+        SyslogSinkConfig {
+            transport: SyslogTransport::Udp,
+            address: SmolStr::new("127.0.0.1:514"),
+            facility: 16,
+            app_name: SmolStr::new("trust-runtime"),
+        }
+    }
+
+    #[test]
+    fn rfc5424_message_carries_structured_fields() {
+        let data = json!({
+            "event_id": "TRUST-RT-OVERRUN-001",
+            "task": "Fast \"A\"",
+            "missed": 2,
+            "details": { "nested": true },
+        });
+        let record = LogRecord {
+            timestamp_ms: 1_760_616_000_123,
+            level: LogLevel::Warn,
+            event: "runtime_overrun",
+            data: &data,
+        };
+        let message = format_rfc5424(&record, &syslog_config(), "plc-01", "main", 42);
+        assert!(
+            message.starts_with(
+                "<132>1 2025-10-16T12:00:00.123Z plc-01 trust-runtime 42 runtime_overrun \
+                 [trust@32473 resource=\"main\""
+            ),
+            "{message}"
+        );
+        assert!(message.contains(" event_id=\"TRUST-RT-OVERRUN-001\""));
+        assert!(message.contains(" task=\"Fast \\\"A\\\"\""));
+        assert!(message.contains(" missed=\"2\""));
+        assert!(!message.contains(" details="));
+        assert!(message.ends_with(&record.to_json().to_string()));
+    }
+
+    #[test]
+    fn journald_payload_uses_prefixed_fields_and_binary_multiline_values() {
+        let data = json!({ "event_id": "TRUST-RT-FAULT-001", "error": "line 1\nline 2" });
+        let record = LogRecord {
+            timestamp_ms: 0,
+            level: LogLevel::Error,
+            event: "runtime_fault",
+            data: &data,
+        };
+        let payload = journald_payload(&record, "trust-runtime", "main");
+        let text = String::from_utf8_lossy(&payload);
+        assert!(text.contains("PRIORITY=3\n"));
+        assert!(text.contains("SYSLOG_IDENTIFIER=trust-runtime\n"));
+        assert!(text.contains("TRUST_EVENT=runtime_fault\n"));
+        assert!(text.contains("TRUST_RESOURCE=main\n"));
+        assert!(text.contains("TRUST_EVENT_ID=TRUST-RT-FAULT-001\n"));
+        let mut expected = b"TRUST_ERROR\n".to_vec();
+        expected.extend_from_slice(&13_u64.to_le_bytes());
+        expected.extend_from_slice(b"line 1\nline 2\n");
+        assert!(payload
+            .windows(expected.len())
+            .any(|window| window == expected.as_slice()));
+    }
+
+    #[test]
+    fn syslog_address_and_facility_parse() {
+        assert_eq!(
+            SyslogSinkConfig::parse_address("tcp://logs.local:601").unwrap(),
+            (SyslogTransport::Tcp, SmolStr::new("logs.local:601"))
+        );
+        assert!(SyslogSinkConfig::parse_address("logs.local:514").is_err());
+        assert!(SyslogSinkConfig::parse_address("udp://logs.local").is_err());
+        assert_eq!(SyslogSinkConfig::parse_facility("LOCAL3").unwrap(), 19);
+        assert!(SyslogSinkConfig::parse_facility("mail2").is_err());
+    }
+
+    #[test]
+    fn udp_syslog_sink_delivers_messages() {
+        let collector = UdpSocket::bind("127.0.0.1:0").unwrap();
+        collector
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let mut config = syslog_config();
+        config.address = SmolStr::new(collector.local_addr().unwrap().to_string());
+        let sinks = LogSinks::open(
+            &LogSinkConfig {
+                stdout: false,
+                syslog: Some(config),
+                journald: false,
+            },
+            "main",
+        )
+        .unwrap();
+        let data = json!({ "task": "Main" });
+        sinks.write(&LogRecord {
+            timestamp_ms: 0,
+            level: LogLevel::Info,
+            event: "runtime_start",
+            data: &data,
+        });
+        let mut buffer = [0_u8; 1024];
+        let (len, _) = collector.recv_from(&mut buffer).unwrap();
+        let message = String::from_utf8_lossy(&buffer[..len]);
+        assert!(message.starts_with("<134>1 1970-01-01T00:00:00.000Z "));
+        assert!(message.contains("task=\"Main\""));
+    }
+}
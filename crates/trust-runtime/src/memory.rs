@@ -50,6 +50,8 @@ pub struct LocalFrame {
     pub variables: IndexMap<SmolStr, Value>,
     pub return_value: Option<Value>,
     pub instance_id: Option<InstanceId>,
+    /// VAR_IN_OUT parameters bound to the caller's storage.
+    pub aliases: IndexMap<SmolStr, ValueRef>,
 }
 
 /// Data for a single FB/Class instance.
@@ -114,6 +116,7 @@ impl VariableStorage {
             variables: IndexMap::new(),
             return_value: None,
             instance_id: None,
+            aliases: IndexMap::new(),
         });
         id
     }
@@ -131,6 +134,7 @@ impl VariableStorage {
             variables: IndexMap::new(),
             return_value: None,
             instance_id: Some(instance_id),
+            aliases: IndexMap::new(),
         });
         id
     }
@@ -162,6 +166,22 @@ impl VariableStorage {
         }
     }
 
+    /// Binds a name in the current frame to another storage location.
+    pub fn bind_alias(&mut self, name: impl Into<SmolStr>, reference: ValueRef) -> bool {
+        if let Some(frame) = self.current_frame_mut() {
+            frame.aliases.insert(name.into(), reference);
+            true
+        } else {
+            false
+        }
+    }
+
+    #[must_use]
+    pub fn get_alias(&self, name: &str) -> Option<&ValueRef> {
+        self.current_frame()
+            .and_then(|frame| frame.aliases.get(name))
+    }
+
     #[must_use]
     pub fn get_local(&self, name: &str) -> Option<&Value> {
         self.current_frame()
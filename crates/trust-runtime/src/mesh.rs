@@ -3,22 +3,25 @@
 #![allow(missing_docs)]
 
 use std::collections::BTreeMap;
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader, Read, Write};
 use std::net::{SocketAddr, TcpListener, TcpStream};
 use std::sync::mpsc::{self, Receiver};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration as StdDuration;
+use std::time::{Duration as StdDuration, Instant};
 
 use indexmap::IndexMap;
 use rustls::{ClientConnection, ServerConnection, ServerName, StreamOwned};
 use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 
-use crate::config::MeshConfig;
+use crate::config::{MeshConfig, MeshPublishQos, MeshSubscribeQos, MeshTimeoutAction};
 use crate::discovery::DiscoveryState;
 use crate::error::RuntimeError;
-use crate::scheduler::{ResourceCommand, ResourceControl, StdClock};
+use crate::redundancy::{
+    decode_state, encode_state, parse_preference, PartnerHeartbeat, RedundancyPair, RedundancyRole,
+};
+use crate::scheduler::{ResourceCommand, ResourceControl, ResourceState, StdClock};
 use crate::security::{rustls_client_config, rustls_server_config, TlsMaterials};
 use crate::value::Value;
 
@@ -26,6 +29,13 @@ use crate::value::Value;
 const MESH_SNAPSHOT_TIMEOUT: StdDuration = StdDuration::from_millis(200);
 #[cfg(test)]
 const MESH_SNAPSHOT_TIMEOUT: StdDuration = StdDuration::from_millis(750);
+const MESH_PUBLISH_TICK: StdDuration = StdDuration::from_millis(100);
+/// Publishers send at least this often so subscribers can tell a quiet link from a lost one.
+const MESH_HEARTBEAT: StdDuration = StdDuration::from_millis(1_000);
+/// Unchanged values are re-sent at this interval for peers that joined late.
+const MESH_REFRESH: StdDuration = StdDuration::from_millis(5_000);
+const MESH_PROBE_INTERVAL: StdDuration = StdDuration::from_millis(5_000);
+const MESH_PROBE_TIMEOUT: StdDuration = StdDuration::from_millis(1_000);
 
 #[derive(Debug)]
 pub struct MeshService {
@@ -49,14 +59,507 @@ struct MeshState {
     discovery: Option<Arc<DiscoveryState>>,
     resource: ResourceControl<StdClock>,
     tls: Option<Arc<MeshTlsTransport>>,
+    links: Arc<MeshLinks>,
+    redundancy: Option<Arc<RedundancyPair>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Delivery state of every mesh link, shared with the control server for `mesh.status`.
+#[derive(Debug)]
+pub struct MeshLinks {
+    started: Instant,
+    inner: Mutex<MeshLinksInner>,
+}
+
+#[derive(Debug, Default)]
+struct MeshLinksInner {
+    peers: BTreeMap<SmolStr, PeerLink>,
+    probes: BTreeMap<SmolStr, PeerProbe>,
+    subscriptions: IndexMap<SmolStr, SubscriptionLink>,
+    publications: IndexMap<SmolStr, PublicationLink>,
+    last_message: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct PeerLink {
+    last_seen: Instant,
+    messages: u64,
+}
+
+/// Result of the last ping to a discovered peer.
+#[derive(Debug)]
+struct PeerProbe {
+    address: Option<SocketAddr>,
+    rtt_ms: Option<f64>,
+    error: Option<String>,
+    publish: Vec<SmolStr>,
+    subscribe: Vec<SmolStr>,
+}
+
+#[derive(Debug)]
+struct ProbeReply {
+    address: SocketAddr,
+    rtt_ms: f64,
+    publish: Vec<SmolStr>,
+    subscribe: Vec<SmolStr>,
+}
+
+#[derive(Debug)]
+struct SubscriptionLink {
+    peer: SmolStr,
+    local: SmolStr,
+    qos: MeshSubscribeQos,
+    last_update: Option<Instant>,
+    stale: bool,
+}
+
+#[derive(Debug)]
+struct PublicationLink {
+    qos: MeshPublishQos,
+    last_sent: Option<(serde_json::Value, Instant)>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeshStatus {
+    pub peers: Vec<MeshPeerStatus>,
+    pub subscriptions: Vec<MeshSubscriptionStatus>,
+    pub publications: Vec<MeshPublicationStatus>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeshPeerStatus {
+    pub name: String,
+    pub last_seen_ms: u64,
+    pub messages: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeshSubscriptionStatus {
+    pub remote: String,
+    pub local: String,
+    /// `waiting` until the first value arrives, then `ok` or `stale`.
+    pub state: &'static str,
+    pub age_ms: Option<u64>,
+    pub timeout_ms: Option<u64>,
+    pub on_timeout: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeshPublicationStatus {
+    pub name: String,
+    pub deadband: f64,
+    pub min_interval_ms: u64,
+    pub last_sent_ms: Option<u64>,
+}
+
+/// Graph of this runtime and the mesh peers it knows about. Edges point from
+/// publisher to subscriber and are built from every node's subscriptions.
+#[derive(Debug, Clone, Serialize)]
+pub struct MeshTopology {
+    pub nodes: Vec<MeshNode>,
+    pub edges: Vec<MeshEdge>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeshNode {
+    pub name: String,
+    pub local: bool,
+    pub address: Option<String>,
+    pub reachable: bool,
+    pub rtt_ms: Option<f64>,
+    pub last_seen_ms: Option<u64>,
+    pub error: Option<String>,
+    pub publish: Vec<String>,
+    pub subscribe: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MeshEdge {
+    pub from: String,
+    pub to: String,
+    pub variable: String,
+    /// Link state, known only for edges into this runtime.
+    pub state: Option<&'static str>,
+}
+
+impl MeshStatus {
+    #[must_use]
+    pub fn stale_count(&self) -> usize {
+        self.subscriptions
+            .iter()
+            .filter(|entry| entry.state == "stale")
+            .count()
+    }
+}
+
+impl MeshLinks {
+    #[must_use]
+    pub fn new(config: &MeshConfig) -> Self {
+        let subscriptions = config
+            .subscribe
+            .iter()
+            .filter_map(|(remote, local)| {
+                let (peer, _) = remote.split_once(':')?;
+                Some((
+                    remote.clone(),
+                    SubscriptionLink {
+                        peer: SmolStr::new(peer),
+                        local: local.clone(),
+                        qos: config
+                            .subscribe_qos
+                            .get(remote)
+                            .cloned()
+                            .unwrap_or_default(),
+                        last_update: None,
+                        stale: false,
+                    },
+                ))
+            })
+            .collect();
+        let publications = config
+            .publish
+            .iter()
+            .map(|name| {
+                (
+                    name.clone(),
+                    PublicationLink {
+                        qos: config.publish_qos.get(name).copied().unwrap_or_default(),
+                        last_sent: None,
+                    },
+                )
+            })
+            .collect();
+        Self {
+            started: Instant::now(),
+            inner: Mutex::new(MeshLinksInner {
+                subscriptions,
+                publications,
+                ..MeshLinksInner::default()
+            }),
+        }
+    }
+
+    #[must_use]
+    pub fn status(&self) -> MeshStatus {
+        let now = Instant::now();
+        let age = |at: Instant| now.saturating_duration_since(at).as_millis() as u64;
+        let Ok(inner) = self.inner.lock() else {
+            return MeshStatus {
+                peers: Vec::new(),
+                subscriptions: Vec::new(),
+                publications: Vec::new(),
+            };
+        };
+        MeshStatus {
+            peers: inner
+                .peers
+                .iter()
+                .map(|(name, peer)| MeshPeerStatus {
+                    name: name.to_string(),
+                    last_seen_ms: age(peer.last_seen),
+                    messages: peer.messages,
+                })
+                .collect(),
+            subscriptions: inner
+                .subscriptions
+                .iter()
+                .map(|(remote, link)| MeshSubscriptionStatus {
+                    remote: remote.to_string(),
+                    local: link.local.to_string(),
+                    state: link.state(),
+                    age_ms: link.last_update.map(age),
+                    timeout_ms: link.qos.timeout_ms,
+                    on_timeout: match link.qos.on_timeout {
+                        MeshTimeoutAction::HoldLast => "hold",
+                        MeshTimeoutAction::Substitute(_) => "substitute",
+                    },
+                })
+                .collect(),
+            publications: inner
+                .publications
+                .iter()
+                .map(|(name, link)| MeshPublicationStatus {
+                    name: name.to_string(),
+                    deadband: link.qos.deadband,
+                    min_interval_ms: link.qos.min_interval_ms,
+                    last_sent_ms: link.last_sent.as_ref().map(|(_, at)| age(*at)),
+                })
+                .collect(),
+        }
+    }
+
+    /// Picks the values due for sending; `None` means nothing to send, while an
+    /// empty map is a heartbeat.
+    fn select_publish(
+        &self,
+        data: BTreeMap<String, serde_json::Value>,
+        now: Instant,
+    ) -> Option<BTreeMap<String, serde_json::Value>> {
+        let mut inner = self.inner.lock().ok()?;
+        let mut selected = BTreeMap::new();
+        for (name, value) in data {
+            let Some(link) = inner.publications.get_mut(name.as_str()) else {
+                continue;
+            };
+            let due = match link.last_sent.as_ref() {
+                None => true,
+                Some((last, at)) => {
+                    let elapsed = now.saturating_duration_since(*at);
+                    elapsed >= MESH_REFRESH
+                        || (elapsed >= StdDuration::from_millis(link.qos.min_interval_ms)
+                            && exceeds_deadband(last, &value, link.qos.deadband))
+                }
+            };
+            if due {
+                link.last_sent = Some((value.clone(), now));
+                selected.insert(name, value);
+            }
+        }
+        let heartbeat_due = inner
+            .last_message
+            .is_none_or(|at| now.saturating_duration_since(at) >= MESH_HEARTBEAT);
+        if selected.is_empty() && !heartbeat_due {
+            return None;
+        }
+        inner.last_message = Some(now);
+        Some(selected)
+    }
+
+    fn record_peer<'a>(&self, peer: &str, keys: impl Iterator<Item = &'a String>, now: Instant) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        let entry = inner.peers.entry(SmolStr::new(peer)).or_insert(PeerLink {
+            last_seen: now,
+            messages: 0,
+        });
+        entry.last_seen = now;
+        entry.messages += 1;
+        for key in keys {
+            let remote = format!("{peer}:{key}");
+            if let Some(link) = inner.subscriptions.get_mut(remote.as_str()) {
+                link.last_update = Some(now);
+            }
+        }
+        for link in inner.subscriptions.values_mut() {
+            if link.peer == peer {
+                link.stale = false;
+            }
+        }
+    }
+
+    /// Marks subscriptions whose peer has been silent past their timeout as stale
+    /// and returns the substitute values to apply for newly stale ones.
+    fn expire(&self, now: Instant) -> Vec<(SmolStr, serde_json::Value)> {
+        let Ok(mut inner) = self.inner.lock() else {
+            return Vec::new();
+        };
+        let inner = &mut *inner;
+        let mut substitutes = Vec::new();
+        for link in inner.subscriptions.values_mut() {
+            let Some(timeout_ms) = link.qos.timeout_ms else {
+                continue;
+            };
+            if link.stale {
+                continue;
+            }
+            let last_seen = inner
+                .peers
+                .get(&link.peer)
+                .map_or(self.started, |peer| peer.last_seen);
+            if now.saturating_duration_since(last_seen) < StdDuration::from_millis(timeout_ms) {
+                continue;
+            }
+            link.stale = true;
+            if let MeshTimeoutAction::Substitute(value) = &link.qos.on_timeout {
+                substitutes.push((link.local.clone(), value.clone()));
+            }
+        }
+        substitutes
+    }
+
+    #[must_use]
+    pub fn topology(&self, own_name: &str) -> MeshTopology {
+        let now = Instant::now();
+        let Ok(inner) = self.inner.lock() else {
+            return MeshTopology {
+                nodes: Vec::new(),
+                edges: Vec::new(),
+            };
+        };
+        let mut nodes = vec![MeshNode {
+            name: own_name.to_string(),
+            local: true,
+            address: None,
+            reachable: true,
+            rtt_ms: None,
+            last_seen_ms: None,
+            error: None,
+            publish: inner.publications.keys().map(SmolStr::to_string).collect(),
+            subscribe: inner.subscriptions.keys().map(SmolStr::to_string).collect(),
+        }];
+        let mut names = inner
+            .probes
+            .keys()
+            .chain(inner.peers.keys())
+            .chain(inner.subscriptions.values().map(|link| &link.peer))
+            .filter(|name| name.as_str() != own_name)
+            .cloned()
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+        for name in names {
+            let probe = inner.probes.get(&name);
+            let last_seen = inner
+                .peers
+                .get(&name)
+                .map(|peer| now.saturating_duration_since(peer.last_seen).as_millis() as u64);
+            nodes.push(MeshNode {
+                reachable: probe.is_some_and(|probe| probe.rtt_ms.is_some())
+                    || last_seen.is_some_and(|age| age < MESH_PROBE_INTERVAL.as_millis() as u64),
+                address: probe
+                    .and_then(|probe| probe.address)
+                    .map(|address| address.to_string()),
+                rtt_ms: probe.and_then(|probe| probe.rtt_ms),
+                last_seen_ms: last_seen,
+                error: probe.and_then(|probe| probe.error.clone()),
+                publish: probe
+                    .map(|probe| probe.publish.iter().map(SmolStr::to_string).collect())
+                    .unwrap_or_default(),
+                subscribe: probe
+                    .map(|probe| probe.subscribe.iter().map(SmolStr::to_string).collect())
+                    .unwrap_or_default(),
+                local: false,
+                name: name.to_string(),
+            });
+        }
+        let mut edges = Vec::new();
+        for node in &nodes {
+            for remote in &node.subscribe {
+                let Some((publisher, variable)) = remote.split_once(':') else {
+                    continue;
+                };
+                let state = if node.local {
+                    inner
+                        .subscriptions
+                        .get(remote.as_str())
+                        .map(SubscriptionLink::state)
+                } else {
+                    None
+                };
+                let edge = MeshEdge {
+                    from: publisher.to_string(),
+                    to: node.name.clone(),
+                    variable: variable.to_string(),
+                    state,
+                };
+                if !edges.contains(&edge) {
+                    edges.push(edge);
+                }
+            }
+        }
+        // Peers only named in someone else's subscriptions are shown as unreachable.
+        for edge in &edges {
+            if !nodes.iter().any(|node| node.name == edge.from) {
+                nodes.push(MeshNode {
+                    name: edge.from.clone(),
+                    local: false,
+                    address: None,
+                    reachable: false,
+                    rtt_ms: None,
+                    last_seen_ms: None,
+                    error: None,
+                    publish: Vec::new(),
+                    subscribe: Vec::new(),
+                });
+            }
+        }
+        MeshTopology { nodes, edges }
+    }
+
+    fn record_probe(&self, peer: &str, result: Result<ProbeReply, String>) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        let probe = inner.probes.entry(SmolStr::new(peer)).or_insert(PeerProbe {
+            address: None,
+            rtt_ms: None,
+            error: None,
+            publish: Vec::new(),
+            subscribe: Vec::new(),
+        });
+        match result {
+            Ok(reply) => {
+                probe.address = Some(reply.address);
+                probe.rtt_ms = Some(reply.rtt_ms);
+                probe.error = None;
+                probe.publish = reply.publish;
+                probe.subscribe = reply.subscribe;
+            }
+            // Keep the last known variables so the graph survives a missed probe.
+            Err(error) => {
+                probe.rtt_ms = None;
+                probe.error = Some(error);
+            }
+        }
+    }
+
+    /// Drops probe results for peers that discovery no longer reports.
+    fn retain_probes(&self, peers: &[SmolStr]) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.probes.retain(|name, _| peers.contains(name));
+        }
+    }
+
+    fn has_timeouts(&self) -> bool {
+        self.inner.lock().is_ok_and(|inner| {
+            inner
+                .subscriptions
+                .values()
+                .any(|link| link.qos.timeout_ms.is_some())
+        })
+    }
+}
+
+impl SubscriptionLink {
+    fn state(&self) -> &'static str {
+        if self.stale {
+            "stale"
+        } else if self.last_update.is_some() {
+            "ok"
+        } else {
+            "waiting"
+        }
+    }
+}
+
+fn exceeds_deadband(last: &serde_json::Value, next: &serde_json::Value, deadband: f64) -> bool {
+    match (last.as_f64(), next.as_f64()) {
+        (Some(last), Some(next)) => (next - last).abs() > deadband,
+        _ => last != next,
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 struct MeshMessage {
     r#type: String,
     from: String,
     token: Option<String>,
     data: Option<BTreeMap<String, serde_json::Value>>,
+    /// Set on `pong` replies so peers can draw the topology.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    publish: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    subscribe: Option<Vec<String>>,
+    /// Redundancy heartbeats: the sender's role, preference, and fault state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    preferred: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    faulted: Option<bool>,
+    /// Synced state from the active side, encoded with the retain format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    state: Option<String>,
 }
 
 #[derive(Debug)]
@@ -71,6 +574,8 @@ pub fn start_mesh(
     resource: ResourceControl<StdClock>,
     discovery: Option<Arc<DiscoveryState>>,
     tls_materials: Option<Arc<TlsMaterials>>,
+    links: Arc<MeshLinks>,
+    redundancy: Option<Arc<RedundancyPair>>,
 ) -> Result<Option<MeshService>, RuntimeError> {
     if !config.enabled {
         return Ok(None);
@@ -97,6 +602,8 @@ pub fn start_mesh(
         discovery,
         resource,
         tls,
+        links,
+        redundancy,
     };
 
     let listener_state = state.clone();
@@ -118,6 +625,15 @@ pub fn start_mesh(
 
     let publisher_state = state.clone();
     let publisher = thread::spawn(move || publish_loop(publisher_state));
+    let probe_state = state.clone();
+    thread::spawn(move || probe_loop(probe_state));
+    if state.redundancy.is_some() {
+        let redundancy_state = state.clone();
+        thread::spawn(move || redundancy_loop(redundancy_state));
+    }
+    if state.links.has_timeouts() {
+        thread::spawn(move || supervise_loop(state));
+    }
 
     Ok(Some(MeshService {
         listen,
@@ -136,21 +652,219 @@ fn publish_loop(state: MeshState) {
             .iter()
             .filter_map(|(name, value)| value_to_json(value).map(|json| (name.to_string(), json)))
             .collect::<BTreeMap<_, _>>();
-        if let Some(discovery) = state.discovery.as_ref() {
-            for entry in discovery.snapshot() {
-                let Some(port) = entry.mesh_port else {
-                    continue;
-                };
-                if entry.name == state.name {
-                    continue;
+        if let Some(data) = state.links.select_publish(data, Instant::now()) {
+            if let Some(discovery) = state.discovery.as_ref() {
+                for entry in discovery.mesh_peers(state.name.as_str()) {
+                    let Some(port) = entry.mesh_port else {
+                        continue;
+                    };
+                    for addr in &entry.addresses {
+                        let target = SocketAddr::new(*addr, port);
+                        let _ = send_publish(&target, &state, &data);
+                    }
                 }
-                for addr in &entry.addresses {
-                    let target = SocketAddr::new(*addr, port);
-                    let _ = send_publish(&target, &state, &data);
+            }
+        }
+        thread::sleep(MESH_PUBLISH_TICK);
+    }
+}
+
+/// Pings discovered peers to measure round-trip time and learn what they share.
+fn probe_loop(state: MeshState) {
+    let Some(discovery) = state.discovery.clone() else {
+        return;
+    };
+    loop {
+        let peers = discovery.mesh_peers(state.name.as_str());
+        state.links.retain_probes(
+            &peers
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect::<Vec<_>>(),
+        );
+        for entry in peers {
+            let Some(port) = entry.mesh_port else {
+                continue;
+            };
+            let mut result = Err("no address".to_string());
+            for addr in &entry.addresses {
+                result = probe_peer(&SocketAddr::new(*addr, port), &state)
+                    .map_err(|err| err.to_string());
+                if result.is_ok() {
+                    break;
                 }
             }
+            state.links.record_probe(entry.name.as_str(), result);
+        }
+        thread::sleep(MESH_PROBE_INTERVAL);
+    }
+}
+
+fn probe_peer(target: &SocketAddr, state: &MeshState) -> Result<ProbeReply, RuntimeError> {
+    let stream = TcpStream::connect_timeout(target, MESH_PROBE_TIMEOUT).map_err(|err| {
+        RuntimeError::ControlError(format!("mesh connect {target}: {err}").into())
+    })?;
+    let _ = stream.set_read_timeout(Some(MESH_PROBE_TIMEOUT));
+    let ping = MeshMessage {
+        r#type: "ping".into(),
+        from: state.name.to_string(),
+        token: state.auth_token.as_ref().map(|t| t.to_string()),
+        ..MeshMessage::default()
+    };
+    let (rtt_ms, reply) = if let Some(tls) = state.tls.as_ref() {
+        let connection =
+            ClientConnection::new(tls.client_config.clone(), mesh_server_name(target)?).map_err(
+                |err| RuntimeError::ControlError(format!("mesh tls connect: {err}").into()),
+            )?;
+        let mut stream = StreamOwned::new(connection, stream);
+        stream.conn.complete_io(&mut stream.sock).map_err(|err| {
+            RuntimeError::ControlError(format!("mesh tls handshake: {err}").into())
+        })?;
+        exchange_line(&mut stream, &ping)?
+    } else {
+        let mut stream = stream;
+        exchange_line(&mut stream, &ping)?
+    };
+    if reply.r#type != "pong" {
+        return Err(RuntimeError::ControlError(
+            format!("mesh probe {target}: unexpected '{}' reply", reply.r#type).into(),
+        ));
+    }
+    let names = |list: Option<Vec<String>>| {
+        list.unwrap_or_default()
+            .into_iter()
+            .map(SmolStr::new)
+            .collect::<Vec<_>>()
+    };
+    Ok(ProbeReply {
+        address: *target,
+        rtt_ms,
+        publish: names(reply.publish),
+        subscribe: names(reply.subscribe),
+    })
+}
+
+/// Sends one message and reads one reply line, timing the round trip.
+fn exchange_line<S: Read + Write>(
+    stream: &mut S,
+    msg: &MeshMessage,
+) -> Result<(f64, MeshMessage), RuntimeError> {
+    let line = serde_json::to_string(msg).unwrap_or_default();
+    let started = Instant::now();
+    writeln!(stream, "{line}")
+        .and_then(|()| stream.flush())
+        .map_err(|err| RuntimeError::ControlError(format!("mesh send: {err}").into()))?;
+    let mut reply = String::new();
+    BufReader::new(&mut *stream)
+        .read_line(&mut reply)
+        .map_err(|err| RuntimeError::ControlError(format!("mesh probe reply: {err}").into()))?;
+    let rtt_ms = started.elapsed().as_secs_f64() * 1_000.0;
+    let reply = serde_json::from_str::<MeshMessage>(&reply)
+        .map_err(|err| RuntimeError::ControlError(format!("mesh probe reply: {err}").into()))?;
+    Ok((rtt_ms, reply))
+}
+
+/// Sends heartbeats (with state while active) to the redundancy partner and
+/// switches the resource between active and standby.
+fn redundancy_loop(state: MeshState) {
+    let Some(pair) = state.redundancy.clone() else {
+        return;
+    };
+    let Some(partner) = pair.partner_addr() else {
+        return;
+    };
+    loop {
+        let now = Instant::now();
+        let faulted = state.resource.state() == ResourceState::Faulted;
+        if let Some(role) = pair.evaluate(faulted, now) {
+            let _ = state
+                .resource
+                .send_command(ResourceCommand::SetStandby(role == RedundancyRole::Standby));
+        }
+        let role = pair.role();
+        let mut msg = MeshMessage {
+            r#type: "redundancy".into(),
+            from: state.name.to_string(),
+            token: state.auth_token.as_ref().map(|t| t.to_string()),
+            role: Some(role.as_str().to_string()),
+            preferred: Some(pair.preferred().as_str().to_string()),
+            faulted: Some(faulted),
+            ..MeshMessage::default()
+        };
+        if role == RedundancyRole::Active && pair.sync_due(now) {
+            let (tx, rx) = mpsc::channel();
+            let _ = state
+                .resource
+                .send_command(ResourceCommand::RedundancySnapshot {
+                    sync: pair.sync_names().to_vec(),
+                    respond_to: tx,
+                });
+            if let Ok(snapshot) = rx.recv_timeout(MESH_SNAPSHOT_TIMEOUT) {
+                msg.state = encode_state(&snapshot);
+                pair.record_sync(now);
+            }
         }
-        thread::sleep(StdDuration::from_millis(1000));
+        let _ = send_message(&partner, &state, &msg);
+        thread::sleep(pair.heartbeat());
+    }
+}
+
+/// Records a partner heartbeat and, on the standby, applies the synced state.
+fn receive_redundancy(state: &MeshState, msg: MeshMessage) {
+    let Some(pair) = state.redundancy.as_ref() else {
+        return;
+    };
+    let (Some(role), Some(preferred)) = (
+        msg.role.as_deref().and_then(RedundancyRole::parse),
+        msg.preferred.as_deref().and_then(parse_preference),
+    ) else {
+        return;
+    };
+    let now = Instant::now();
+    pair.record_partner(
+        PartnerHeartbeat {
+            name: SmolStr::new(&msg.from),
+            role,
+            preferred,
+            faulted: msg.faulted.unwrap_or(false),
+        },
+        now,
+    );
+    if role != RedundancyRole::Active || pair.role() != RedundancyRole::Standby {
+        return;
+    }
+    if let Some(snapshot) = msg.state.as_deref().and_then(decode_state) {
+        let _ = state
+            .resource
+            .send_command(ResourceCommand::RedundancyApply { snapshot });
+        pair.record_sync(now);
+    }
+}
+
+/// Applies timeout policies to subscriptions whose peer went silent.
+fn supervise_loop(state: MeshState) {
+    loop {
+        let substitutes = state.links.expire(Instant::now());
+        if !substitutes.is_empty() {
+            let names = substitutes
+                .iter()
+                .map(|(local, _)| local.clone())
+                .collect::<Vec<_>>();
+            let templates = snapshot_globals(&state.resource, &names);
+            let updates = substitutes
+                .iter()
+                .filter_map(|(local, json)| {
+                    let value = json_to_value(json, templates.get(local)?)?;
+                    Some((local.clone(), value))
+                })
+                .collect::<IndexMap<_, _>>();
+            if !updates.is_empty() {
+                let _ = state
+                    .resource
+                    .send_command(ResourceCommand::MeshApply { updates });
+            }
+        }
+        thread::sleep(MESH_PUBLISH_TICK);
     }
 }
 
@@ -159,19 +873,28 @@ fn send_publish(
     state: &MeshState,
     data: &BTreeMap<String, serde_json::Value>,
 ) -> Result<(), RuntimeError> {
-    if let Some(tls) = state.tls.as_ref() {
-        return send_publish_tls(target, state, data, tls.client_config.clone());
-    }
-    let mut stream = TcpStream::connect(target).map_err(|err| {
-        RuntimeError::ControlError(format!("mesh connect {target}: {err}").into())
-    })?;
     let msg = MeshMessage {
         r#type: "publish".into(),
         from: state.name.to_string(),
         token: state.auth_token.as_ref().map(|t| t.to_string()),
         data: Some(data.clone()),
+        ..MeshMessage::default()
     };
-    let line = serde_json::to_string(&msg).unwrap_or_default();
+    send_message(target, state, &msg)
+}
+
+fn send_message(
+    target: &SocketAddr,
+    state: &MeshState,
+    msg: &MeshMessage,
+) -> Result<(), RuntimeError> {
+    if let Some(tls) = state.tls.as_ref() {
+        return send_message_tls(target, msg, tls.client_config.clone());
+    }
+    let mut stream = TcpStream::connect(target).map_err(|err| {
+        RuntimeError::ControlError(format!("mesh connect {target}: {err}").into())
+    })?;
+    let line = serde_json::to_string(msg).unwrap_or_default();
     writeln!(stream, "{line}")
         .map_err(|err| RuntimeError::ControlError(format!("mesh send: {err}").into()))?;
     Ok(())
@@ -191,12 +914,18 @@ fn handle_peer_tls(stream: TcpStream, state: MeshState, server_config: Arc<rustl
     handle_peer_stream(reader, state);
 }
 
-fn handle_peer_stream<R: std::io::Read>(reader: BufReader<R>, state: MeshState) {
-    for line in reader.lines().map_while(Result::ok) {
+fn handle_peer_stream<S: Read + Write>(mut reader: BufReader<S>, state: MeshState) {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
         let Ok(msg) = serde_json::from_str::<MeshMessage>(&line) else {
             continue;
         };
-        if msg.r#type != "publish" {
+        if !matches!(msg.r#type.as_str(), "publish" | "ping" | "redundancy") {
             continue;
         }
         if let Some(expected) = state.auth_token.as_ref() {
@@ -204,7 +933,35 @@ fn handle_peer_stream<R: std::io::Read>(reader: BufReader<R>, state: MeshState)
                 continue;
             }
         }
+        if msg.r#type == "redundancy" {
+            receive_redundancy(&state, msg);
+            continue;
+        }
+        if msg.r#type == "ping" {
+            state
+                .links
+                .record_peer(msg.from.as_str(), std::iter::empty(), Instant::now());
+            let pong = MeshMessage {
+                r#type: "pong".into(),
+                from: state.name.to_string(),
+                publish: Some(state.publish.iter().map(ToString::to_string).collect()),
+                subscribe: Some(state.subscribe.keys().map(ToString::to_string).collect()),
+                ..MeshMessage::default()
+            };
+            let reply = serde_json::to_string(&pong).unwrap_or_default();
+            let stream = reader.get_mut();
+            if writeln!(stream, "{reply}")
+                .and_then(|()| stream.flush())
+                .is_err()
+            {
+                break;
+            }
+            continue;
+        }
         let data = msg.data.unwrap_or_default();
+        state
+            .links
+            .record_peer(msg.from.as_str(), data.keys(), Instant::now());
         let updates = map_subscribe(&state, msg.from.as_str(), &data);
         if updates.is_empty() {
             continue;
@@ -215,10 +972,9 @@ fn handle_peer_stream<R: std::io::Read>(reader: BufReader<R>, state: MeshState)
     }
 }
 
-fn send_publish_tls(
+fn send_message_tls(
     target: &SocketAddr,
-    state: &MeshState,
-    data: &BTreeMap<String, serde_json::Value>,
+    msg: &MeshMessage,
     client_config: Arc<rustls::ClientConfig>,
 ) -> Result<(), RuntimeError> {
     let stream = TcpStream::connect(target).map_err(|err| {
@@ -232,13 +988,7 @@ fn send_publish_tls(
         .conn
         .complete_io(&mut stream.sock)
         .map_err(|err| RuntimeError::ControlError(format!("mesh tls handshake: {err}").into()))?;
-    let msg = MeshMessage {
-        r#type: "publish".into(),
-        from: state.name.to_string(),
-        token: state.auth_token.as_ref().map(|t| t.to_string()),
-        data: Some(data.clone()),
-    };
-    let line = serde_json::to_string(&msg).unwrap_or_default();
+    let line = serde_json::to_string(msg).unwrap_or_default();
     writeln!(stream, "{line}")
         .map_err(|err| RuntimeError::ControlError(format!("mesh tls send: {err}").into()))?;
     stream
@@ -466,6 +1216,8 @@ mod tests {
             discovery: None,
             resource,
             tls: Some(tls.clone()),
+            links: test_links(),
+            redundancy: None,
         };
 
         let server_config = tls.server_config.clone();
@@ -483,6 +1235,8 @@ mod tests {
             discovery: None,
             resource: sender_resource,
             tls: Some(tls.clone()),
+            links: test_links(),
+            redundancy: None,
         };
         let mut data = BTreeMap::new();
         data.insert("temperature".to_string(), json!(42));
@@ -532,6 +1286,8 @@ mod tests {
             discovery: None,
             resource,
             tls: Some(tls.clone()),
+            links: test_links(),
+            redundancy: None,
         };
 
         let server_config = tls.server_config.clone();
@@ -562,6 +1318,247 @@ mod tests {
         listener_thread.join().expect("join mesh tls listener");
     }
 
+    fn mesh_config() -> MeshConfig {
+        MeshConfig {
+            enabled: true,
+            listen: SmolStr::new("127.0.0.1:0"),
+            tls: false,
+            auth_token: None,
+            publish: vec![SmolStr::new("speed"), SmolStr::new("mode")],
+            subscribe: IndexMap::from([
+                (SmolStr::new("peer:temperature"), SmolStr::new("temp")),
+                (SmolStr::new("peer:level"), SmolStr::new("level")),
+            ]),
+            subscribe_qos: IndexMap::from([
+                (
+                    SmolStr::new("peer:temperature"),
+                    MeshSubscribeQos {
+                        timeout_ms: Some(500),
+                        on_timeout: MeshTimeoutAction::Substitute(json!(-1)),
+                    },
+                ),
+                (
+                    SmolStr::new("peer:level"),
+                    MeshSubscribeQos {
+                        timeout_ms: Some(500),
+                        on_timeout: MeshTimeoutAction::HoldLast,
+                    },
+                ),
+            ]),
+            publish_qos: IndexMap::from([(
+                SmolStr::new("speed"),
+                MeshPublishQos {
+                    deadband: 0.5,
+                    min_interval_ms: 100,
+                },
+            )]),
+        }
+    }
+
+    fn test_links() -> Arc<MeshLinks> {
+        Arc::new(MeshLinks::new(&mesh_config()))
+    }
+
+    #[test]
+    fn mesh_publish_applies_deadband_min_interval_and_heartbeat() {
+        let links = MeshLinks::new(&mesh_config());
+        let start = Instant::now();
+        let at = |ms: u64| start + StdDuration::from_millis(ms);
+        let data = |speed: f64, mode: &str| {
+            BTreeMap::from([
+                ("speed".to_string(), json!(speed)),
+                ("mode".to_string(), json!(mode)),
+            ])
+        };
+
+        let first = links
+            .select_publish(data(10.0, "auto"), at(0))
+            .expect("first");
+        assert_eq!(first.len(), 2);
+        // Inside the deadband and before the default 1 s interval for `mode`.
+        assert!(links
+            .select_publish(data(10.3, "manual"), at(200))
+            .is_none());
+        let moved = links
+            .select_publish(data(10.6, "manual"), at(300))
+            .expect("speed left deadband");
+        assert_eq!(moved.keys().collect::<Vec<_>>(), vec!["speed"]);
+        let later = links
+            .select_publish(data(10.6, "manual"), at(1_100))
+            .expect("mode after min interval");
+        assert_eq!(later.keys().collect::<Vec<_>>(), vec!["mode"]);
+        let heartbeat = links
+            .select_publish(data(10.6, "manual"), at(2_200))
+            .expect("heartbeat");
+        assert!(heartbeat.is_empty());
+    }
+
+    #[test]
+    fn mesh_peer_timeout_marks_stale_and_substitutes() {
+        let links = MeshLinks::new(&mesh_config());
+        let now = links.started;
+        let keys = ["temperature".to_string(), "level".to_string()];
+        links.record_peer("peer", keys.iter(), now);
+        assert!(links.expire(now + StdDuration::from_millis(200)).is_empty());
+
+        let substitutes = links.expire(now + StdDuration::from_millis(600));
+        assert_eq!(substitutes, vec![(SmolStr::new("temp"), json!(-1))]);
+        let status = links.status();
+        assert_eq!(status.stale_count(), 2);
+        assert_eq!(status.subscriptions[1].on_timeout, "hold");
+        // Already stale links are not substituted again.
+        assert!(links.expire(now + StdDuration::from_millis(900)).is_empty());
+
+        links.record_peer(
+            "peer",
+            keys[..1].iter(),
+            now + StdDuration::from_millis(1_000),
+        );
+        let status = links.status();
+        assert_eq!(status.stale_count(), 0);
+        assert_eq!(status.peers[0].messages, 2);
+    }
+
+    #[test]
+    fn mesh_probe_reads_pong_with_shared_variables() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mesh listener");
+        let addr = listener.local_addr().expect("mesh addr");
+        let (resource, _cmd_rx) = ResourceControl::stub(StdClock::new());
+        let links = test_links();
+        let listener_state = MeshState {
+            name: SmolStr::new("line-2"),
+            auth_token: Some(SmolStr::new("mesh-token")),
+            publish: vec![SmolStr::new("speed")],
+            subscribe: IndexMap::from([(SmolStr::new("line-1:temperature"), SmolStr::new("t"))]),
+            discovery: None,
+            resource: resource.clone(),
+            tls: None,
+            links: links.clone(),
+            redundancy: None,
+        };
+        let listener_thread = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("accept mesh probe");
+            handle_peer(stream, listener_state);
+        });
+
+        let prober = MeshState {
+            name: SmolStr::new("line-1"),
+            auth_token: Some(SmolStr::new("mesh-token")),
+            publish: Vec::new(),
+            subscribe: IndexMap::new(),
+            discovery: None,
+            resource,
+            tls: None,
+            links: test_links(),
+            redundancy: None,
+        };
+        let reply = probe_peer(&addr, &prober).expect("probe peer");
+        assert_eq!(reply.address, addr);
+        assert!(reply.rtt_ms >= 0.0);
+        assert_eq!(reply.publish, vec![SmolStr::new("speed")]);
+        assert_eq!(reply.subscribe, vec![SmolStr::new("line-1:temperature")]);
+        listener_thread.join().expect("join mesh listener");
+        assert_eq!(links.status().peers[0].name, "line-1");
+    }
+
+    #[test]
+    fn mesh_topology_links_publishers_to_subscribers() {
+        let links = MeshLinks::new(&mesh_config());
+        links.record_probe(
+            "peer",
+            Ok(ProbeReply {
+                address: "10.0.0.2:5200".parse().expect("addr"),
+                rtt_ms: 1.5,
+                publish: vec![SmolStr::new("temperature"), SmolStr::new("level")],
+                subscribe: vec![SmolStr::new("self:speed"), SmolStr::new("other:flow")],
+            }),
+        );
+        links.record_peer("peer", ["temperature".to_string()].iter(), Instant::now());
+        let topology = links.topology("self");
+        let names = topology
+            .nodes
+            .iter()
+            .map(|node| node.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["self", "peer", "other"]);
+        assert!(!topology.nodes[2].reachable);
+        assert_eq!(topology.nodes[1].rtt_ms, Some(1.5));
+        assert!(topology.nodes[1].reachable);
+        let edge = |from: &str, to: &str, variable: &str, state: Option<&'static str>| MeshEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            variable: variable.to_string(),
+            state,
+        };
+        assert_eq!(
+            topology.edges,
+            vec![
+                edge("peer", "self", "temperature", Some("ok")),
+                edge("peer", "self", "level", Some("waiting")),
+                edge("self", "peer", "speed", None),
+                edge("other", "peer", "flow", None),
+            ]
+        );
+
+        links.record_probe("peer", Err("timed out".to_string()));
+        let topology = links.topology("self");
+        assert_eq!(topology.nodes[1].rtt_ms, None);
+        assert_eq!(topology.nodes[1].error.as_deref(), Some("timed out"));
+        assert_eq!(topology.nodes[1].publish.len(), 2);
+    }
+
+    #[test]
+    fn mesh_redundancy_standby_applies_state_from_active_partner() {
+        let (resource, cmd_rx) = ResourceControl::stub(StdClock::new());
+        let pair = Arc::new(RedundancyPair::new(
+            &crate::config::RedundancyConfig {
+                enabled: true,
+                role: crate::config::RedundancyPreference::Secondary,
+                partner: "127.0.0.1:5201".into(),
+                ..crate::config::RedundancyConfig::default()
+            },
+            SmolStr::new("plc-b"),
+        ));
+        let state = MeshState {
+            name: SmolStr::new("plc-b"),
+            auth_token: None,
+            publish: Vec::new(),
+            subscribe: IndexMap::new(),
+            discovery: None,
+            resource,
+            tls: None,
+            links: test_links(),
+            redundancy: Some(pair.clone()),
+        };
+        let mut snapshot = crate::RetainSnapshot::default();
+        snapshot.insert("Counter", Value::DInt(7));
+        let heartbeat = |state: Option<String>| MeshMessage {
+            r#type: "redundancy".into(),
+            from: "plc-a".into(),
+            role: Some("active".into()),
+            preferred: Some("primary".into()),
+            faulted: Some(false),
+            state,
+            ..MeshMessage::default()
+        };
+
+        receive_redundancy(&state, heartbeat(encode_state(&snapshot)));
+        match cmd_rx.try_recv() {
+            Ok(ResourceCommand::RedundancyApply { snapshot: applied }) => {
+                assert_eq!(applied, snapshot)
+            }
+            other => panic!("expected redundancy apply, got {other:?}"),
+        }
+        let status = pair.status();
+        assert_eq!(status.partner_name.as_deref(), Some("plc-a"));
+        assert_eq!(status.partner_role, Some("active"));
+        assert!(status.last_sync_ms.is_some());
+
+        // A heartbeat without state only refreshes the partner.
+        receive_redundancy(&state, heartbeat(None));
+        assert!(cmd_rx.try_recv().is_err());
+    }
+
     fn tls_test_transport() -> Arc<MeshTlsTransport> {
         let cert = include_bytes!("../tests/fixtures/tls/server-cert.pem").to_vec();
         let key = include_bytes!("../tests/fixtures/tls/server-key.pem").to_vec();
@@ -3,9 +3,11 @@
 #![allow(missing_docs)]
 
 use std::collections::HashMap;
-use std::time::Instant;
 
 use smol_str::SmolStr;
+use trust_ide::MeasuredTiming;
+
+use crate::scheduler::TimeBase;
 
 #[derive(Debug, Clone, Copy)]
 pub struct CycleStats {
@@ -57,7 +59,13 @@ pub struct TaskStats {
     pub avg_ms: f64,
     pub last_ms: f64,
     pub overruns: u64,
+    /// Release jitter: how late each periodic release started after its
+    /// scheduled time on the task's interval grid.
+    pub jitter_max_ms: f64,
+    pub jitter_avg_ms: f64,
+    pub jitter_last_ms: f64,
     samples: u64,
+    jitter_samples: u64,
 }
 
 impl TaskStats {
@@ -84,6 +92,17 @@ impl TaskStats {
     pub fn record_overrun(&mut self, missed: u64) {
         self.overruns = self.overruns.saturating_add(missed);
     }
+
+    pub fn record_jitter(&mut self, jitter: std::time::Duration) {
+        let ms = jitter.as_secs_f64() * 1000.0;
+        self.jitter_last_ms = ms;
+        if self.jitter_samples == 0 || ms > self.jitter_max_ms {
+            self.jitter_max_ms = ms;
+        }
+        let total = self.jitter_avg_ms * self.jitter_samples as f64 + ms;
+        self.jitter_avg_ms = total / (self.jitter_samples as f64 + 1.0);
+        self.jitter_samples = self.jitter_samples.saturating_add(1);
+    }
 }
 
 impl Default for TaskStats {
@@ -94,7 +113,11 @@ impl Default for TaskStats {
             avg_ms: 0.0,
             last_ms: 0.0,
             overruns: 0,
+            jitter_max_ms: 0.0,
+            jitter_avg_ms: 0.0,
+            jitter_last_ms: 0.0,
             samples: 0,
+            jitter_samples: 0,
         }
     }
 }
@@ -154,7 +177,7 @@ struct CallProfileEntry {
 
 #[derive(Debug, Clone)]
 pub struct RuntimeMetrics {
-    start: Instant,
+    time_base: TimeBase,
     pub cycle: CycleStats,
     pub tasks: HashMap<SmolStr, TaskStats>,
     pub profiling_enabled: bool,
@@ -167,7 +190,7 @@ impl RuntimeMetrics {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            start: Instant::now(),
+            time_base: TimeBase::new(),
             cycle: CycleStats::default(),
             tasks: HashMap::new(),
             profiling_enabled: true,
@@ -177,9 +200,20 @@ impl RuntimeMetrics {
         }
     }
 
+    /// Uptime on the resource clock, so a scaled simulation reports scaled uptime.
     #[must_use]
     pub fn uptime_ms(&self) -> u64 {
-        self.start.elapsed().as_millis() as u64
+        u64::try_from(self.time_base.uptime().as_millis()).unwrap_or(0)
+    }
+
+    /// Follow the resource clock of the runtime these metrics belong to.
+    pub fn set_time_base(&mut self, time_base: TimeBase) {
+        self.time_base = time_base;
+    }
+
+    #[must_use]
+    pub fn time_base(&self) -> &TimeBase {
+        &self.time_base
     }
 
     pub fn record_cycle(&mut self, duration: std::time::Duration) {
@@ -197,6 +231,11 @@ impl RuntimeMetrics {
         entry.record_overrun(missed);
     }
 
+    pub fn record_jitter(&mut self, name: &SmolStr, jitter: std::time::Duration) {
+        let entry = self.tasks.entry(name.clone()).or_default();
+        entry.record_jitter(jitter);
+    }
+
     pub fn record_fault(&mut self) {
         self.faults = self.faults.saturating_add(1);
     }
@@ -284,6 +323,9 @@ impl RuntimeMetrics {
                 avg_ms: stats.avg_ms,
                 last_ms: stats.last_ms,
                 overruns: stats.overruns,
+                jitter_max_ms: stats.jitter_max_ms,
+                jitter_avg_ms: stats.jitter_avg_ms,
+                jitter_last_ms: stats.jitter_last_ms,
             })
             .collect();
         RuntimeMetricsSnapshot {
@@ -315,6 +357,9 @@ pub struct TaskStatsSnapshot {
     pub avg_ms: f64,
     pub last_ms: f64,
     pub overruns: u64,
+    pub jitter_max_ms: f64,
+    pub jitter_avg_ms: f64,
+    pub jitter_last_ms: f64,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -358,6 +403,26 @@ pub struct ProfilingSnapshot {
     pub top_contributors: Vec<BudgetContributorSnapshot>,
 }
 
+/// Extracts the measured per-call maxima from a `tasks.stats` control result.
+///
+/// The timings feed the worst-case execution time estimation in `trust-ide`.
+#[must_use]
+pub fn measured_timings_from_task_stats(stats: &serde_json::Value) -> Vec<MeasuredTiming> {
+    stats
+        .get("calls")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            Some(MeasuredTiming {
+                kind: SmolStr::new(entry.get("kind")?.as_str()?),
+                name: SmolStr::new(entry.get("name")?.as_str()?),
+                max_ms: entry.get("max_ms")?.as_f64()?,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::RuntimeMetrics;
@@ -385,6 +450,22 @@ mod tests {
         assert!(main.avg_cycle_ms > 0.0);
     }
 
+    #[test]
+    fn measured_timings_parse_task_stats_calls() {
+        let stats = serde_json::json!({
+            "tasks": [],
+            "calls": [
+                { "kind": "program", "name": "MAIN", "max_ms": 1.5 },
+                { "kind": "fb", "name": "Worker" },
+            ],
+        });
+        let timings = super::measured_timings_from_task_stats(&stats);
+        assert_eq!(timings.len(), 1);
+        assert_eq!(timings[0].kind.as_str(), "program");
+        assert_eq!(timings[0].name.as_str(), "MAIN");
+        assert_eq!(timings[0].max_ms, 1.5);
+    }
+
     #[test]
     fn profiling_toggle_disables_and_reenables_collection() {
         let mut metrics = RuntimeMetrics::new();
@@ -0,0 +1,562 @@
+//! Fault, watchdog, and I/O driver notifications (webhook and SMTP).
+
+#![allow(missing_docs)]
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use smol_str::SmolStr;
+use tracing::warn;
+
+use crate::debug::RuntimeEvent;
+use crate::error::RuntimeError;
+use crate::io::{IoDriverHealth, IoDriverStatus};
+use crate::logging::format_rfc3339_ms;
+
+pub const DEFAULT_SUBJECT: &str = "[{resource}] {kind}: {message}";
+pub const DEFAULT_TEMPLATE: &str =
+    "{kind} on {resource} at {time}\n\n{message}\n\nRecent events:\n{recent}\n";
+const DRIVER_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotifyKind {
+    Fault,
+    Watchdog,
+    Driver,
+}
+
+impl NotifyKind {
+    pub const ALL: [Self; 3] = [Self::Fault, Self::Watchdog, Self::Driver];
+
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "fault" => Some(Self::Fault),
+            "watchdog" => Some(Self::Watchdog),
+            "driver" => Some(Self::Driver),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fault => "fault",
+            Self::Watchdog => "watchdog",
+            Self::Driver => "driver",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmtpConfig {
+    /// `host:port` of a plain SMTP relay.
+    pub server: SmolStr,
+    pub from: SmolStr,
+    pub to: Vec<SmolStr>,
+    pub username: Option<SmolStr>,
+    pub password: Option<SmolStr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationConfig {
+    pub enabled: bool,
+    pub events: Vec<NotifyKind>,
+    /// Minimum time between two notifications of the same kind.
+    pub min_interval: Duration,
+    pub recent_events: usize,
+    pub subject: SmolStr,
+    pub template: SmolStr,
+    pub webhook: Option<SmolStr>,
+    pub smtp: Option<SmtpConfig>,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            events: NotifyKind::ALL.to_vec(),
+            min_interval: Duration::from_secs(60),
+            recent_events: 5,
+            subject: SmolStr::new(DEFAULT_SUBJECT),
+            template: SmolStr::new(DEFAULT_TEMPLATE),
+            webhook: None,
+            smtp: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub kind: NotifyKind,
+    pub resource: SmolStr,
+    pub message: String,
+    pub timestamp_ms: u128,
+    pub recent: Vec<String>,
+    /// Notifications of this kind dropped by rate limiting since the last one sent.
+    pub suppressed: u64,
+}
+
+impl Notification {
+    /// Fills `{resource}`, `{kind}`, `{message}`, `{time}`, `{recent}`, and
+    /// `{suppressed}` in a template.
+    #[must_use]
+    pub fn render(&self, template: &str) -> String {
+        let recent = if self.recent.is_empty() {
+            "(none)".to_string()
+        } else {
+            self.recent
+                .iter()
+                .map(|line| format!("- {line}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        template
+            .replace("{resource}", &self.resource)
+            .replace("{kind}", self.kind.as_str())
+            .replace("{message}", &self.message)
+            .replace("{time}", &format_rfc3339_ms(self.timestamp_ms))
+            .replace("{recent}", &recent)
+            .replace("{suppressed}", &self.suppressed.to_string())
+    }
+}
+
+/// Rate limiting and the recent-event history attached to notifications.
+#[derive(Debug)]
+pub struct NotificationGate {
+    events: Vec<NotifyKind>,
+    min_interval_ms: u128,
+    capacity: usize,
+    recent: VecDeque<String>,
+    last_sent: HashMap<NotifyKind, u128>,
+    suppressed: HashMap<NotifyKind, u64>,
+}
+
+impl NotificationGate {
+    #[must_use]
+    pub fn new(config: &NotificationConfig) -> Self {
+        Self {
+            events: config.events.clone(),
+            min_interval_ms: config.min_interval.as_millis(),
+            capacity: config.recent_events,
+            recent: VecDeque::new(),
+            last_sent: HashMap::new(),
+            suppressed: HashMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, timestamp_ms: u128, line: impl Into<String>) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.recent.push_back(format!(
+            "{} {}",
+            format_rfc3339_ms(timestamp_ms),
+            line.into()
+        ));
+        while self.recent.len() > self.capacity {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Records the event and returns a notification unless the kind is not
+    /// subscribed or one of the same kind went out less than `min_interval` ago.
+    pub fn admit(
+        &mut self,
+        kind: NotifyKind,
+        resource: &str,
+        message: String,
+        timestamp_ms: u128,
+    ) -> Option<Notification> {
+        self.record(timestamp_ms, format!("{}: {message}", kind.as_str()));
+        if !self.events.contains(&kind) {
+            return None;
+        }
+        if let Some(last) = self.last_sent.get(&kind) {
+            if timestamp_ms.saturating_sub(*last) < self.min_interval_ms {
+                *self.suppressed.entry(kind).or_default() += 1;
+                return None;
+            }
+        }
+        self.last_sent.insert(kind, timestamp_ms);
+        Some(Notification {
+            kind,
+            resource: SmolStr::new(resource),
+            message,
+            timestamp_ms,
+            recent: self.recent.iter().cloned().collect(),
+            suppressed: self.suppressed.remove(&kind).unwrap_or(0),
+        })
+    }
+}
+
+/// Classifies runtime events and hands admitted notifications to a delivery thread.
+pub struct NotificationService {
+    resource: SmolStr,
+    gate: Mutex<NotificationGate>,
+    tx: Mutex<Sender<Notification>>,
+}
+
+impl NotificationService {
+    /// Starts the delivery thread; `None` when notifications are disabled.
+    #[must_use]
+    pub fn start(config: &NotificationConfig, resource: &str) -> Option<Arc<Self>> {
+        if !config.enabled || (config.webhook.is_none() && config.smtp.is_none()) {
+            return None;
+        }
+        let (tx, rx) = mpsc::channel::<Notification>();
+        let delivery = config.clone();
+        thread::spawn(move || {
+            for notification in rx {
+                deliver(&delivery, &notification);
+            }
+        });
+        Some(Arc::new(Self {
+            resource: SmolStr::new(resource),
+            gate: Mutex::new(NotificationGate::new(config)),
+            tx: Mutex::new(tx),
+        }))
+    }
+
+    pub fn observe_runtime_event(&self, event: &RuntimeEvent) {
+        let now = unix_ms();
+        match event {
+            RuntimeEvent::Fault { error, .. } => {
+                let kind = if error == &RuntimeError::WatchdogTimeout.to_string() {
+                    NotifyKind::Watchdog
+                } else {
+                    NotifyKind::Fault
+                };
+                self.notify(kind, error.clone(), now);
+            }
+            RuntimeEvent::TaskOverrun { name, missed, .. } => {
+                if let Ok(mut gate) = self.gate.lock() {
+                    gate.record(now, format!("overrun: task {name} missed {missed}"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Polls driver health and notifies when a driver becomes degraded or faulted.
+    pub fn watch_io_health(self: &Arc<Self>, health: Arc<Mutex<Vec<IoDriverStatus>>>) {
+        let service = Arc::clone(self);
+        thread::spawn(move || {
+            let mut previous: HashMap<SmolStr, IoDriverHealth> = HashMap::new();
+            loop {
+                let current = health.lock().map(|guard| guard.clone()).unwrap_or_default();
+                for status in current {
+                    let changed = previous.get(&status.name) != Some(&status.health);
+                    if changed {
+                        let message = match &status.health {
+                            IoDriverHealth::Ok => None,
+                            IoDriverHealth::Degraded { error } => {
+                                Some(format!("driver {} degraded: {error}", status.name))
+                            }
+                            IoDriverHealth::Faulted { error } => {
+                                Some(format!("driver {} faulted: {error}", status.name))
+                            }
+                        };
+                        if let Some(message) = message {
+                            service.notify(NotifyKind::Driver, message, unix_ms());
+                        }
+                    }
+                    previous.insert(status.name, status.health);
+                }
+                thread::sleep(DRIVER_POLL_INTERVAL);
+            }
+        });
+    }
+
+    fn notify(&self, kind: NotifyKind, message: String, timestamp_ms: u128) {
+        let notification = match self.gate.lock() {
+            Ok(mut gate) => gate.admit(kind, &self.resource, message, timestamp_ms),
+            Err(_) => None,
+        };
+        if let (Some(notification), Ok(tx)) = (notification, self.tx.lock()) {
+            let _ = tx.send(notification);
+        }
+    }
+}
+
+fn deliver(config: &NotificationConfig, notification: &Notification) {
+    let subject = notification.render(&config.subject);
+    let body = notification.render(&config.template);
+    if let Some(url) = config.webhook.as_ref() {
+        if let Err(err) = send_webhook(url, notification, &subject, &body) {
+            warn!("notification webhook delivery failed: {err}");
+        }
+    }
+    if let Some(smtp) = config.smtp.as_ref() {
+        if let Err(err) = send_smtp(smtp, &subject, &body) {
+            warn!("notification email delivery failed: {err}");
+        }
+    }
+}
+
+#[cfg(not(feature = "services"))]
+fn send_webhook(
+    _url: &str,
+    _notification: &Notification,
+    _subject: &str,
+    _body: &str,
+) -> Result<(), String> {
+    Err("webhooks require feature 'services'".to_string())
+}
+
+#[cfg(feature = "services")]
+fn send_webhook(
+    url: &str,
+    notification: &Notification,
+    subject: &str,
+    body: &str,
+) -> Result<(), String> {
+    let payload = serde_json::json!({
+        "resource": notification.resource.as_str(),
+        "kind": notification.kind.as_str(),
+        "message": notification.message,
+        "timestamp_ms": notification.timestamp_ms as u64,
+        "recent_events": notification.recent,
+        "suppressed": notification.suppressed,
+        "subject": subject,
+        "text": body,
+    });
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_millis(2_000))
+        .timeout_read(Duration::from_millis(5_000))
+        .build();
+    agent
+        .post(url)
+        .set("Content-Type", "application/json")
+        .send_string(&payload.to_string())
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(not(feature = "services"))]
+fn send_smtp(_config: &SmtpConfig, _subject: &str, _body: &str) -> Result<(), String> {
+    Err("email requires feature 'services'".to_string())
+}
+
+/// Minimal SMTP client for a plant relay: no TLS, optional AUTH PLAIN.
+#[cfg(feature = "services")]
+fn send_smtp(config: &SmtpConfig, subject: &str, body: &str) -> Result<(), String> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpStream, ToSocketAddrs};
+
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+
+    fn reply(reader: &mut impl BufRead, expected: u16) -> Result<(), String> {
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).map_err(|err| err.to_string())? == 0 {
+                return Err("connection closed".to_string());
+            }
+            let code = line.get(..3).and_then(|code| code.parse::<u16>().ok());
+            if line.as_bytes().get(3) == Some(&b'-') {
+                continue;
+            }
+            return match code {
+                Some(code) if code == expected => Ok(()),
+                _ => Err(format!("unexpected reply '{}'", line.trim_end())),
+            };
+        }
+    }
+
+    let address = config
+        .server
+        .to_socket_addrs()
+        .map_err(|err| err.to_string())?
+        .next()
+        .ok_or_else(|| format!("'{}' did not resolve", config.server))?;
+    let mut stream = TcpStream::connect_timeout(&address, Duration::from_secs(5))
+        .map_err(|err| err.to_string())?;
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+    let _ = stream.set_write_timeout(Some(Duration::from_secs(10)));
+    let mut reader = BufReader::new(stream.try_clone().map_err(|err| err.to_string())?);
+    reply(&mut reader, 220)?;
+    let mut command = |line: &str, expected: u16| -> Result<(), String> {
+        stream
+            .write_all(format!("{line}\r\n").as_bytes())
+            .map_err(|err| err.to_string())?;
+        reply(&mut reader, expected)
+    };
+
+    command("EHLO trust-runtime", 250)?;
+    if let (Some(user), Some(password)) = (&config.username, &config.password) {
+        let token = STANDARD.encode(format!("\0{user}\0{password}"));
+        command(&format!("AUTH PLAIN {token}"), 235)?;
+    }
+    command(&format!("MAIL FROM:<{}>", config.from), 250)?;
+    for to in &config.to {
+        command(&format!("RCPT TO:<{to}>"), 250)?;
+    }
+    command("DATA", 354)?;
+    let to = config
+        .to
+        .iter()
+        .map(|to| format!("<{to}>"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut message = format!(
+        "From: <{}>\r\nTo: {to}\r\nSubject: {}\r\n\
+         Content-Type: text/plain; charset=utf-8\r\n\r\n",
+        config.from,
+        subject.replace(['\r', '\n'], " "),
+    );
+    for line in body.lines() {
+        // Dot-stuffing keeps a body line of "." from ending the message.
+        if line.starts_with('.') {
+            message.push('.');
+        }
+        message.push_str(line);
+        message.push_str("\r\n");
+    }
+    message.push('.');
+    command(&message, 250)?;
+    let _ = command("QUIT", 221);
+    Ok(())
+}
+
+fn unix_ms() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> NotificationConfig {
+        NotificationConfig {
+            enabled: true,
+            events: vec![NotifyKind::Fault, NotifyKind::Watchdog],
+            min_interval: Duration::from_secs(10),
+            recent_events: 3,
+            ..NotificationConfig::default()
+        }
+    }
+
+    #[test]
+    fn gate_rate_limits_per_kind_and_counts_suppressed() {
+        let mut gate = NotificationGate::new(&config());
+        let first = gate.admit(NotifyKind::Fault, "plc", "div by zero".into(), 1_000);
+        assert!(first.is_some());
+        assert!(gate
+            .admit(NotifyKind::Fault, "plc", "div by zero".into(), 5_000)
+            .is_none());
+        assert!(gate
+            .admit(
+                NotifyKind::Watchdog,
+                "plc",
+                "watchdog timeout".into(),
+                6_000
+            )
+            .is_some());
+        assert!(gate
+            .admit(
+                NotifyKind::Driver,
+                "plc",
+                "driver modbus faulted".into(),
+                7_000
+            )
+            .is_none());
+        let next = gate
+            .admit(NotifyKind::Fault, "plc", "overflow".into(), 11_000)
+            .expect("interval elapsed");
+        assert_eq!(next.suppressed, 1);
+        assert_eq!(next.recent.len(), 3);
+        assert!(next.recent[0].ends_with("watchdog: watchdog timeout"));
+        assert!(next.recent[2].ends_with("fault: overflow"));
+    }
+
+    #[test]
+    fn render_fills_template_placeholders() {
+        let notification = Notification {
+            kind: NotifyKind::Driver,
+            resource: SmolStr::new("line-3"),
+            message: "driver modbus faulted: timeout".into(),
+            timestamp_ms: 0,
+            recent: vec!["a".into(), "b".into()],
+            suppressed: 2,
+        };
+        let text =
+            notification.render("[{resource}] {kind} {time} ({suppressed})\n{message}\n{recent}");
+        assert_eq!(
+            text,
+            "[line-3] driver 1970-01-01T00:00:00.000Z (2)\n\
+             driver modbus faulted: timeout\n- a\n- b"
+        );
+    }
+
+    #[cfg(feature = "services")]
+    #[test]
+    fn smtp_client_delivers_dot_stuffed_message() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind fake smtp server");
+        let server = listener.local_addr().expect("local addr").to_string();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("accept");
+            let mut writer = stream.try_clone().expect("clone");
+            let mut reader = BufReader::new(stream);
+            let mut transcript = Vec::new();
+            writer.write_all(b"220 relay ready\r\n").unwrap();
+            let mut in_data = false;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end().to_string();
+                transcript.push(line.clone());
+                let reply: &[u8] = if in_data {
+                    if line != "." {
+                        continue;
+                    }
+                    in_data = false;
+                    b"250 queued\r\n"
+                } else if line.starts_with("EHLO") {
+                    b"250-relay\r\n250 AUTH PLAIN\r\n"
+                } else if line.starts_with("AUTH") {
+                    b"235 ok\r\n"
+                } else if line == "DATA" {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                } else if line == "QUIT" {
+                    writer.write_all(b"221 bye\r\n").unwrap();
+                    break;
+                } else {
+                    b"250 ok\r\n"
+                };
+                writer.write_all(reply).unwrap();
+            }
+            transcript
+        });
+
+        let smtp = SmtpConfig {
+            server: server.into(),
+            from: "plc@example.com".into(),
+            to: vec!["ops@example.com".into()],
+            username: Some("plc".into()),
+            password: Some("secret".into()),
+        };
+        send_smtp(&smtp, "[plc] fault", "first\n.hidden\nlast").expect("send mail");
+        let transcript = handle.join().expect("server thread");
+        assert!(transcript.contains(&"MAIL FROM:<plc@example.com>".to_string()));
+        assert!(transcript.contains(&"RCPT TO:<ops@example.com>".to_string()));
+        assert!(transcript.contains(&"Subject: [plc] fault".to_string()));
+        assert!(transcript.contains(&"..hidden".to_string()));
+        assert!(transcript
+            .iter()
+            .any(|line| line.starts_with("AUTH PLAIN ")));
+        assert_eq!(transcript.last().map(String::as_str), Some("QUIT"));
+    }
+}
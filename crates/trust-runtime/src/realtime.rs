@@ -0,0 +1,107 @@
+//! Real-time scheduling hooks for the resource thread.
+//!
+//! `[runtime.realtime]` asks for a `SCHED_FIFO` priority, CPU pinning, and locked
+//! memory. Each setting is applied best effort: a missing privilege or an
+//! unsupported platform turns into a warning and the runtime keeps running with
+//! the default scheduler.
+
+use smol_str::SmolStr;
+
+use crate::config::RealtimeConfig;
+
+/// Apply `config` to the calling thread (and, for `lock_memory`, the process).
+///
+/// Returns one warning per setting that could not be applied.
+#[must_use]
+pub fn apply_current_thread(config: &RealtimeConfig) -> Vec<SmolStr> {
+    let mut warnings = Vec::new();
+    if config.lock_memory {
+        if let Err(err) = platform::lock_memory() {
+            warnings.push(SmolStr::new(format!("memory not locked: {err}")));
+        }
+    }
+    if !config.cpu_affinity.is_empty() {
+        if let Err(err) = platform::set_affinity(&config.cpu_affinity) {
+            warnings.push(SmolStr::new(format!(
+                "cpu affinity {:?} not applied: {err}",
+                config.cpu_affinity
+            )));
+        }
+    }
+    if let Some(priority) = config.priority {
+        if let Err(err) = platform::set_fifo_priority(priority) {
+            warnings.push(SmolStr::new(format!(
+                "SCHED_FIFO priority {priority} not applied: {err}"
+            )));
+        }
+    }
+    warnings
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use nix::errno::Errno;
+    use nix::sched::{sched_setaffinity, CpuSet};
+    use nix::sys::mman::{mlockall, MlockAllFlags};
+    use nix::unistd::Pid;
+    use thread_priority::unix::{
+        set_thread_priority_and_policy, thread_native_id, RealtimeThreadSchedulePolicy,
+        ThreadSchedulePolicy,
+    };
+    use thread_priority::{ThreadPriority, ThreadPriorityValue};
+
+    pub(super) fn lock_memory() -> Result<(), String> {
+        mlockall(MlockAllFlags::MCL_CURRENT | MlockAllFlags::MCL_FUTURE).map_err(|err| match err {
+            Errno::EPERM | Errno::ENOMEM => {
+                format!("{err} (needs CAP_IPC_LOCK or a larger RLIMIT_MEMLOCK)")
+            }
+            _ => err.to_string(),
+        })
+    }
+
+    pub(super) fn set_affinity(cpus: &[usize]) -> Result<(), String> {
+        let mut set = CpuSet::new();
+        for cpu in cpus {
+            set.set(*cpu).map_err(|err| format!("core {cpu}: {err}"))?;
+        }
+        // Pid 0 targets the calling thread.
+        sched_setaffinity(Pid::from_raw(0), &set).map_err(|err| err.to_string())
+    }
+
+    pub(super) fn set_fifo_priority(priority: u8) -> Result<(), String> {
+        let value = ThreadPriorityValue::try_from(priority).map_err(|err| err.to_string())?;
+        set_thread_priority_and_policy(
+            thread_native_id(),
+            ThreadPriority::Crossplatform(value),
+            ThreadSchedulePolicy::Realtime(RealtimeThreadSchedulePolicy::Fifo),
+        )
+        .map_err(|err| format!("{err:?} (needs CAP_SYS_NICE or an RLIMIT_RTPRIO grant)"))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    const UNSUPPORTED: &str = "only supported on Linux";
+
+    pub(super) fn lock_memory() -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub(super) fn set_affinity(_cpus: &[usize]) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub(super) fn set_fifo_priority(_priority: u8) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_applies_nothing() {
+        assert!(apply_current_thread(&RealtimeConfig::default()).is_empty());
+    }
+}
@@ -0,0 +1,377 @@
+//! Hot-standby redundancy between two runtimes sharing the mesh channel.
+
+#![allow(missing_docs)]
+
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde::Serialize;
+use smol_str::SmolStr;
+
+use crate::config::{RedundancyConfig, RedundancyPreference};
+use crate::retain::{decode_snapshot, encode_snapshot};
+use crate::RetainSnapshot;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedundancyRole {
+    Active,
+    Standby,
+}
+
+impl RedundancyRole {
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "active" => Some(Self::Active),
+            "standby" => Some(Self::Standby),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Standby => "standby",
+        }
+    }
+}
+
+#[must_use]
+pub fn parse_preference(text: &str) -> Option<RedundancyPreference> {
+    match text {
+        "primary" => Some(RedundancyPreference::Primary),
+        "secondary" => Some(RedundancyPreference::Secondary),
+        _ => None,
+    }
+}
+
+/// Last heartbeat received from the partner runtime.
+#[derive(Debug, Clone)]
+pub struct PartnerHeartbeat {
+    pub name: SmolStr,
+    pub role: RedundancyRole,
+    pub preferred: RedundancyPreference,
+    pub faulted: bool,
+}
+
+/// Role arbitration for one side of a redundant pair, shared with the control
+/// server for `status`.
+#[derive(Debug)]
+pub struct RedundancyPair {
+    config: RedundancyConfig,
+    name: SmolStr,
+    started: Instant,
+    inner: Mutex<PairInner>,
+}
+
+#[derive(Debug)]
+struct PairInner {
+    role: RedundancyRole,
+    local_faulted: bool,
+    partner: Option<(PartnerHeartbeat, Instant)>,
+    last_sync: Option<Instant>,
+    failovers: u64,
+    last_switch: Option<(&'static str, Instant)>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RedundancyStatus {
+    pub role: &'static str,
+    pub preferred: &'static str,
+    pub faulted: bool,
+    pub partner: String,
+    pub partner_name: Option<String>,
+    pub partner_role: Option<&'static str>,
+    pub partner_faulted: bool,
+    pub partner_seen_ms: Option<u64>,
+    /// Age of the last state sent (active) or applied (standby).
+    pub last_sync_ms: Option<u64>,
+    pub failovers: u64,
+    pub last_switch_reason: Option<&'static str>,
+    pub last_switch_ms: Option<u64>,
+}
+
+impl RedundancyPair {
+    /// Both sides start as standby so neither drives outputs before they agree.
+    #[must_use]
+    pub fn new(config: &RedundancyConfig, name: SmolStr) -> Self {
+        Self::starting_at(config, name, Instant::now())
+    }
+
+    fn starting_at(config: &RedundancyConfig, name: SmolStr, started: Instant) -> Self {
+        Self {
+            config: config.clone(),
+            name,
+            started,
+            inner: Mutex::new(PairInner {
+                role: RedundancyRole::Standby,
+                local_faulted: false,
+                partner: None,
+                last_sync: None,
+                failovers: 0,
+                last_switch: None,
+            }),
+        }
+    }
+
+    #[must_use]
+    pub fn role(&self) -> RedundancyRole {
+        self.lock().role
+    }
+
+    #[must_use]
+    pub fn preferred(&self) -> RedundancyPreference {
+        self.config.role
+    }
+
+    #[must_use]
+    pub fn partner_addr(&self) -> Option<SocketAddr> {
+        self.config.partner.parse().ok()
+    }
+
+    #[must_use]
+    pub fn heartbeat(&self) -> Duration {
+        Duration::from_millis(self.config.heartbeat_ms)
+    }
+
+    #[must_use]
+    pub fn sync_names(&self) -> &[SmolStr] {
+        &self.config.sync
+    }
+
+    pub fn record_partner(&self, heartbeat: PartnerHeartbeat, now: Instant) {
+        self.lock().partner = Some((heartbeat, now));
+    }
+
+    /// Active side: whether the next heartbeat should carry state.
+    #[must_use]
+    pub fn sync_due(&self, now: Instant) -> bool {
+        let interval = Duration::from_millis(self.config.sync_interval_ms);
+        self.lock()
+            .last_sync
+            .is_none_or(|at| now.saturating_duration_since(at) >= interval)
+    }
+
+    pub fn record_sync(&self, now: Instant) {
+        self.lock().last_sync = Some(now);
+    }
+
+    /// Applies the failover rules and returns the new role when it changed.
+    pub fn evaluate(&self, local_faulted: bool, now: Instant) -> Option<RedundancyRole> {
+        let timeout = Duration::from_millis(self.config.failover_timeout_ms);
+        let mut inner = self.lock();
+        inner.local_faulted = local_faulted;
+        let partner = inner
+            .partner
+            .as_ref()
+            .filter(|(_, seen)| now.saturating_duration_since(*seen) <= timeout)
+            .map(|(heartbeat, _)| heartbeat.clone());
+        let on_fault = self.config.failover_on_fault;
+        let switch = match (inner.role, partner) {
+            (RedundancyRole::Active, Some(partner)) => {
+                if local_faulted && on_fault && !partner.faulted {
+                    Some((RedundancyRole::Standby, "local fault"))
+                } else if partner.role == RedundancyRole::Active
+                    && (local_faulted || !partner.faulted)
+                    && !self.outranks(&partner)
+                {
+                    Some((RedundancyRole::Standby, "partner active"))
+                } else {
+                    None
+                }
+            }
+            (RedundancyRole::Active, None) => None,
+            (RedundancyRole::Standby, None) if !local_faulted => {
+                // A booting pair waits for a heartbeat first; the secondary waits
+                // longer so both do not go active when they start together.
+                let grace = match self.config.role {
+                    RedundancyPreference::Primary => timeout,
+                    RedundancyPreference::Secondary => timeout * 2,
+                };
+                if inner.partner.is_some() {
+                    Some((RedundancyRole::Active, "partner heartbeat lost"))
+                } else if now.saturating_duration_since(self.started) >= grace {
+                    Some((RedundancyRole::Active, "partner not found"))
+                } else {
+                    None
+                }
+            }
+            (RedundancyRole::Standby, None) => None,
+            (RedundancyRole::Standby, Some(_)) if local_faulted => None,
+            (RedundancyRole::Standby, Some(partner)) => match partner.role {
+                RedundancyRole::Active if partner.faulted && on_fault => {
+                    Some((RedundancyRole::Active, "partner fault"))
+                }
+                RedundancyRole::Active => None,
+                RedundancyRole::Standby if partner.faulted || self.outranks(&partner) => {
+                    Some((RedundancyRole::Active, "partner standby"))
+                }
+                RedundancyRole::Standby => None,
+            },
+        };
+        let (role, reason) = switch?;
+        if matches!(reason, "partner heartbeat lost" | "partner fault") {
+            inner.failovers = inner.failovers.saturating_add(1);
+        }
+        inner.role = role;
+        inner.last_switch = Some((reason, now));
+        Some(role)
+    }
+
+    #[must_use]
+    pub fn status(&self) -> RedundancyStatus {
+        let now = Instant::now();
+        let age = |at: Instant| now.saturating_duration_since(at).as_millis() as u64;
+        let inner = self.lock();
+        RedundancyStatus {
+            role: inner.role.as_str(),
+            preferred: self.config.role.as_str(),
+            faulted: inner.local_faulted,
+            partner: self.config.partner.to_string(),
+            partner_name: inner
+                .partner
+                .as_ref()
+                .map(|(heartbeat, _)| heartbeat.name.to_string()),
+            partner_role: inner
+                .partner
+                .as_ref()
+                .map(|(heartbeat, _)| heartbeat.role.as_str()),
+            partner_faulted: inner
+                .partner
+                .as_ref()
+                .is_some_and(|(heartbeat, _)| heartbeat.faulted),
+            partner_seen_ms: inner.partner.as_ref().map(|(_, seen)| age(*seen)),
+            last_sync_ms: inner.last_sync.map(age),
+            failovers: inner.failovers,
+            last_switch_reason: inner.last_switch.map(|(reason, _)| reason),
+            last_switch_ms: inner.last_switch.map(|(_, at)| age(at)),
+        }
+    }
+
+    /// Whether this side should be active when both sides are healthy.
+    fn outranks(&self, partner: &PartnerHeartbeat) -> bool {
+        match (self.config.role, partner.preferred) {
+            (RedundancyPreference::Primary, RedundancyPreference::Secondary) => true,
+            (RedundancyPreference::Secondary, RedundancyPreference::Primary) => false,
+            // Misconfigured pair (same preference): fall back to the resource name.
+            _ => self.name < partner.name,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, PairInner> {
+        self.inner.lock().expect("redundancy state poisoned")
+    }
+}
+
+/// Encodes synced state with the retain file format for the mesh message.
+#[must_use]
+pub fn encode_state(snapshot: &RetainSnapshot) -> Option<String> {
+    encode_snapshot(snapshot)
+        .ok()
+        .map(|bytes| BASE64_STANDARD.encode(bytes))
+}
+
+#[must_use]
+pub fn decode_state(text: &str) -> Option<RetainSnapshot> {
+    let bytes = BASE64_STANDARD.decode(text).ok()?;
+    decode_snapshot(&bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::Value;
+
+    fn config(role: RedundancyPreference) -> RedundancyConfig {
+        RedundancyConfig {
+            enabled: true,
+            role,
+            partner: "127.0.0.1:5201".into(),
+            ..RedundancyConfig::default()
+        }
+    }
+
+    fn heartbeat(role: RedundancyRole, faulted: bool) -> PartnerHeartbeat {
+        PartnerHeartbeat {
+            name: "plc-b".into(),
+            role,
+            preferred: RedundancyPreference::Secondary,
+            faulted,
+        }
+    }
+
+    #[test]
+    fn redundancy_primary_takes_over_on_startup_heartbeat_loss_and_fault() {
+        let start = Instant::now();
+        let pair = RedundancyPair::starting_at(
+            &config(RedundancyPreference::Primary),
+            "plc-a".into(),
+            start,
+        );
+        // Both sides come up as standby; the primary wins.
+        pair.record_partner(heartbeat(RedundancyRole::Standby, false), start);
+        assert_eq!(pair.evaluate(false, start), Some(RedundancyRole::Active));
+        assert_eq!(pair.status().failovers, 0);
+
+        // A faulted primary hands over once the partner is healthy.
+        pair.record_partner(heartbeat(RedundancyRole::Standby, false), start);
+        assert_eq!(pair.evaluate(true, start), Some(RedundancyRole::Standby));
+        assert_eq!(pair.status().last_switch_reason, Some("local fault"));
+
+        // The partner goes active; when it later falls silent this side takes over.
+        pair.record_partner(heartbeat(RedundancyRole::Active, false), start);
+        assert_eq!(pair.evaluate(false, start), None);
+        let later = start + Duration::from_millis(1_500);
+        assert_eq!(pair.evaluate(false, later), Some(RedundancyRole::Active));
+        let status = pair.status();
+        assert_eq!(status.role, "active");
+        assert_eq!(status.last_switch_reason, Some("partner heartbeat lost"));
+        assert_eq!(status.failovers, 1);
+    }
+
+    #[test]
+    fn redundancy_secondary_waits_for_faults_and_yields_when_both_active() {
+        let start = Instant::now();
+        let pair = RedundancyPair::starting_at(
+            &config(RedundancyPreference::Secondary),
+            "plc-b".into(),
+            start,
+        );
+        let primary = |role, faulted| PartnerHeartbeat {
+            name: "plc-a".into(),
+            role,
+            preferred: RedundancyPreference::Primary,
+            faulted,
+        };
+        // Alone at startup, the secondary waits twice the timeout.
+        assert_eq!(
+            pair.evaluate(false, start + Duration::from_millis(1_500)),
+            None
+        );
+
+        pair.record_partner(primary(RedundancyRole::Active, false), start);
+        assert_eq!(pair.evaluate(false, start), None);
+        pair.record_partner(primary(RedundancyRole::Active, true), start);
+        assert_eq!(pair.evaluate(false, start), Some(RedundancyRole::Active));
+        assert_eq!(pair.status().last_switch_reason, Some("partner fault"));
+
+        // Both active after the primary recovers: the secondary yields.
+        pair.record_partner(primary(RedundancyRole::Active, false), start);
+        assert_eq!(pair.evaluate(false, start), Some(RedundancyRole::Standby));
+        assert_eq!(pair.status().last_switch_reason, Some("partner active"));
+    }
+
+    #[test]
+    fn redundancy_state_roundtrips_through_mesh_encoding() {
+        let mut snapshot = RetainSnapshot::default();
+        snapshot.insert("Counter", Value::DInt(42));
+        snapshot.insert("Valve", Value::Bool(true));
+        let text = encode_state(&snapshot).expect("encode");
+        assert_eq!(decode_state(&text), Some(snapshot));
+        assert_eq!(decode_state("not base64!"), None);
+    }
+}
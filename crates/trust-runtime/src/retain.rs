@@ -132,6 +132,120 @@ impl RetainSnapshot {
     }
 }
 
+/// A difference between a stored retain snapshot and the program's retain layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetainLayoutIssue {
+    /// The stored value no longer matches the variable's type; loading it would put a
+    /// value of the wrong type into the variable.
+    TypeChanged {
+        name: SmolStr,
+        stored: String,
+        expected: String,
+    },
+    /// The stored variable is no longer retained by the program and is ignored.
+    Orphaned { name: SmolStr },
+    /// A retained variable has no stored value and starts from its initial value.
+    Missing { name: SmolStr },
+}
+
+impl RetainLayoutIssue {
+    /// Whether loading the snapshot would corrupt program state.
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::TypeChanged { .. })
+    }
+}
+
+impl std::fmt::Display for RetainLayoutIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TypeChanged {
+                name,
+                stored,
+                expected,
+            } => write!(f, "{name}: stored {stored}, program expects {expected}"),
+            Self::Orphaned { name } => write!(f, "{name}: stored but no longer retained"),
+            Self::Missing { name } => write!(f, "{name}: retained but not stored yet"),
+        }
+    }
+}
+
+/// Compare a stored snapshot with the retain layout of the program (`program` is the
+/// snapshot of a freshly initialized runtime).
+#[must_use]
+pub fn check_retain_layout(
+    program: &RetainSnapshot,
+    stored: &RetainSnapshot,
+) -> Vec<RetainLayoutIssue> {
+    let mut issues = Vec::new();
+    for (name, value) in &stored.values {
+        match program.values.get(name) {
+            Some(expected) if !same_layout(value, expected) => {
+                issues.push(RetainLayoutIssue::TypeChanged {
+                    name: name.clone(),
+                    stored: describe_layout(value),
+                    expected: describe_layout(expected),
+                });
+            }
+            Some(_) => {}
+            None => issues.push(RetainLayoutIssue::Orphaned { name: name.clone() }),
+        }
+    }
+    for name in program.values.keys() {
+        if !stored.values.contains_key(name) {
+            issues.push(RetainLayoutIssue::Missing { name: name.clone() });
+        }
+    }
+    issues
+}
+
+fn same_layout(stored: &Value, expected: &Value) -> bool {
+    match (stored, expected) {
+        (Value::Array(stored), Value::Array(expected)) => {
+            stored.dimensions == expected.dimensions
+                && stored.elements.len() == expected.elements.len()
+                && stored
+                    .elements
+                    .iter()
+                    .zip(&expected.elements)
+                    .all(|(stored, expected)| same_layout(stored, expected))
+        }
+        (Value::Struct(stored), Value::Struct(expected)) => {
+            stored.type_name.eq_ignore_ascii_case(&expected.type_name)
+                && stored.fields.len() == expected.fields.len()
+                && stored.fields.iter().zip(&expected.fields).all(
+                    |((stored_name, stored), (expected_name, expected))| {
+                        stored_name.eq_ignore_ascii_case(expected_name)
+                            && same_layout(stored, expected)
+                    },
+                )
+        }
+        (Value::Enum(stored), Value::Enum(expected)) => {
+            stored.type_name.eq_ignore_ascii_case(&expected.type_name)
+        }
+        _ => std::mem::discriminant(stored) == std::mem::discriminant(expected),
+    }
+}
+
+fn describe_layout(value: &Value) -> String {
+    match value {
+        Value::Array(array) => {
+            let dimensions = array
+                .dimensions
+                .iter()
+                .map(|(lower, upper)| format!("{lower}..{upper}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            let element = array
+                .elements
+                .first()
+                .map_or_else(|| "?".to_string(), describe_layout);
+            format!("ARRAY[{dimensions}] OF {element}")
+        }
+        _ => crate::debug::dap::value_type_name(value).unwrap_or_else(|| "?".to_string()),
+    }
+}
+
 /// File-based retain store.
 #[derive(Debug, Clone)]
 pub struct FileRetainStore {
@@ -176,7 +290,7 @@ impl RetainStore for FileRetainStore {
     }
 }
 
-fn encode_snapshot(snapshot: &RetainSnapshot) -> Result<Vec<u8>, RuntimeError> {
+pub(crate) fn encode_snapshot(snapshot: &RetainSnapshot) -> Result<Vec<u8>, RuntimeError> {
     let mut out = Vec::new();
     out.extend_from_slice(RETAIN_MAGIC);
     out.extend_from_slice(&RETAIN_VERSION.to_le_bytes());
@@ -188,7 +302,7 @@ fn encode_snapshot(snapshot: &RetainSnapshot) -> Result<Vec<u8>, RuntimeError> {
     Ok(out)
 }
 
-fn decode_snapshot(bytes: &[u8]) -> Result<RetainSnapshot, RuntimeError> {
+pub(crate) fn decode_snapshot(bytes: &[u8]) -> Result<RetainSnapshot, RuntimeError> {
     let mut reader = RetainReader::new(bytes);
     let magic = reader.read_bytes(4)?;
     if magic != RETAIN_MAGIC {
@@ -32,6 +32,7 @@ impl Runtime {
         }
         .ok_or_else(|| error::RuntimeError::InvalidBytecodeMetadata("resource".into()))?;
         self.apply_resource_metadata(resource)?;
+        self.build_info = None;
         Ok(())
     }
 
@@ -47,7 +48,9 @@ impl Runtime {
         let metadata = module
             .metadata()
             .map_err(|err| error::RuntimeError::InvalidBytecode(err.to_string().into()))?;
-        self.apply_bytecode_metadata(&metadata, resource_name)
+        self.apply_bytecode_metadata(&metadata, resource_name)?;
+        self.build_info = module.build_info();
+        Ok(())
     }
 
     /// Decode a bytecode container and apply its metadata.
@@ -4,15 +4,20 @@
 
 use crate::debug::DebugControl;
 use crate::eval::expr::Expr;
+use crate::eval::ops::OverflowPolicy;
 use crate::eval::{ClassDef, EvalContext, FunctionBlockDef, FunctionDef, InterfaceDef};
-use crate::io::{IoDriver, IoDriverStatus, IoInterface, IoSafeState};
+use crate::io::{IoDriver, IoDriverStatus, IoInterface, IoSafeState, OutputSafeState};
 use crate::memory::{AccessMap, FrameId, InstanceId, VariableStorage};
 use crate::metrics::RuntimeMetrics;
 use crate::retain::{RetainManager, RetainStore};
+use crate::scheduler::TimeBase;
 use crate::stdlib::StandardLibrary;
-use crate::task::{ProgramDef, TaskConfig, TaskState};
+use crate::task::{
+    BoundsPolicy, BoundsSettings, ProgramDef, SubrangePolicy, TaskCatchUp, TaskConfig, TaskState,
+};
 use crate::value::{DateTimeProfile, Duration, Value};
 use crate::watchdog::{FaultDecision, FaultPolicy, WatchdogPolicy};
+use crate::workers::WorkerPool;
 use crate::{error, eval, stdlib};
 use indexmap::IndexMap;
 use smol_str::SmolStr;
@@ -21,9 +26,10 @@ use trust_hir::Type;
 
 use super::faults::FaultSubsystem;
 use super::io_subsystem::IoSubsystem;
+use super::jit_subsystem::JitSubsystem;
 use super::metadata::{resolve_using_for_frame, RuntimeMetadata};
 use super::metrics_subsystem::MetricsSubsystem;
-use super::types::{GlobalInitValue, GlobalVarMeta, RetainPolicy};
+use super::types::{CachedProgram, GlobalInitValue, GlobalVarMeta, RetainPolicy, RetentionRules};
 use super::watchdog_subsystem::WatchdogSubsystem;
 
 /// Minimal runtime entry point (extended later).
@@ -41,19 +47,37 @@ pub struct Runtime {
     pub(super) classes: IndexMap<SmolStr, ClassDef>,
     pub(super) interfaces: IndexMap<SmolStr, InterfaceDef>,
     pub(super) programs: IndexMap<SmolStr, ProgramDef>,
+    pub(super) program_cache: IndexMap<SmolStr, CachedProgram>,
+    pub(super) execution_engine: eval::vm::ExecutionEngine,
+    pub(super) jit: JitSubsystem,
+    /// Compiled POU bodies, rebuilt on first use after a definition changes.
+    pub(super) pou_code: Option<eval::vm::PouCode>,
     pub(super) globals: IndexMap<SmolStr, GlobalVarMeta>,
     pub(super) tasks: Vec<TaskConfig>,
     pub(super) task_state: IndexMap<SmolStr, TaskState>,
+    pub(super) task_catch_up: TaskCatchUp,
+    pub(super) bounds: BoundsSettings,
+    /// Bounds policy of the task executing now.
+    pub(super) active_bounds: BoundsPolicy,
+    pub(super) overflow: OverflowPolicy,
+    pub(super) subrange: SubrangePolicy,
     pub(super) task_thread_ids: IndexMap<SmolStr, u32>,
     pub(super) next_thread_id: u32,
     pub(super) background_thread_id: Option<u32>,
     pub(super) current_time: Duration,
+    pub(super) time_base: TimeBase,
     pub(super) cycle_counter: u64,
     pub(super) retain: RetainManager,
     pub(super) metrics: MetricsSubsystem,
     pub(super) watchdog: WatchdogSubsystem,
     pub(super) faults: FaultSubsystem,
+    pub(super) workers: WorkerPool,
     pub(super) execution_deadline: Option<std::time::Instant>,
+    pub(super) standby: bool,
+    pub(super) shutdown_hook: Option<crate::watchdog::ShutdownHook>,
+    pub(super) shutting_down: bool,
+    pub(super) retention: RetentionRules,
+    pub(super) build_info: Option<crate::bytecode::BuildInfo>,
 }
 
 impl std::fmt::Debug for Runtime {
@@ -79,6 +103,10 @@ impl std::fmt::Debug for Runtime {
             .field("cycle_counter", &self.cycle_counter)
             .field("faulted", &self.faults.is_faulted())
             .field("last_fault", &self.faults.last_fault())
+            .field("standby", &self.standby)
+            .field("shutdown_hook", &self.shutdown_hook)
+            .field("shutting_down", &self.shutting_down)
+            .field("build_info", &self.build_info)
             .finish()
     }
 }
@@ -100,19 +128,35 @@ impl Runtime {
             classes: IndexMap::new(),
             interfaces: IndexMap::new(),
             programs: IndexMap::new(),
+            program_cache: IndexMap::new(),
+            execution_engine: eval::vm::ExecutionEngine::default(),
+            jit: JitSubsystem::new(),
+            pou_code: None,
             globals: IndexMap::new(),
             tasks: Vec::new(),
             task_state: IndexMap::new(),
             task_thread_ids: IndexMap::new(),
+            task_catch_up: TaskCatchUp::default(),
+            bounds: BoundsSettings::default(),
+            active_bounds: BoundsPolicy::default(),
+            overflow: OverflowPolicy::default(),
+            subrange: SubrangePolicy::default(),
             next_thread_id: 1,
             background_thread_id: None,
             current_time: Duration::ZERO,
+            time_base: TimeBase::new(),
             cycle_counter: 0,
             retain: RetainManager::default(),
             metrics: MetricsSubsystem::new(),
             watchdog: WatchdogSubsystem::new(),
             faults: FaultSubsystem::new(),
+            workers: WorkerPool::default(),
             execution_deadline: None,
+            standby: false,
+            shutdown_hook: None,
+            shutting_down: false,
+            retention: RetentionRules::default(),
+            build_info: None,
         };
         runtime.register_builtin_function_blocks();
         runtime
@@ -124,6 +168,47 @@ impl Runtime {
         self.profile
     }
 
+    /// Access the engine used to execute program bodies.
+    #[must_use]
+    pub fn execution_engine(&self) -> eval::vm::ExecutionEngine {
+        self.execution_engine
+    }
+
+    /// Select the engine used to execute program bodies.
+    pub fn set_execution_engine(&mut self, engine: eval::vm::ExecutionEngine) {
+        self.execution_engine = engine;
+    }
+
+    /// Access the native compilation settings.
+    #[must_use]
+    pub fn jit_settings(&self) -> eval::vm::JitSettings {
+        self.jit.settings()
+    }
+
+    /// Configure native compilation of hot programs and restart profiling.
+    ///
+    /// Has no effect unless the crate is built with the `jit` feature.
+    pub fn set_jit_settings(&mut self, settings: eval::vm::JitSettings) {
+        self.jit.configure(settings);
+        self.pou_code = None;
+    }
+
+    /// Programs currently running as native code.
+    #[must_use]
+    pub fn jit_programs(&self) -> Vec<SmolStr> {
+        self.jit.compiled_programs()
+    }
+
+    /// Functions, function blocks, and methods (`TYPE.METHOD`) currently
+    /// running as native code.
+    #[must_use]
+    pub fn jit_pous(&self) -> Vec<SmolStr> {
+        self.pou_code
+            .as_ref()
+            .map(eval::vm::PouCode::native_pous)
+            .unwrap_or_default()
+    }
+
     /// Enable debugging and return a shared control handle.
     #[must_use]
     pub fn enable_debug(&mut self) -> crate::debug::DebugControl {
@@ -163,6 +248,19 @@ impl Runtime {
                 .collect(),
             background_thread_id: self.background_thread_id,
             statement_index: self.statement_index.clone(),
+            build_info: self.build_info.clone(),
+            globals: self
+                .globals
+                .iter()
+                .map(|(name, meta)| (name.clone(), meta.retain))
+                .collect(),
+            global_types: self
+                .globals
+                .iter()
+                .map(|(name, meta)| (name.clone(), meta.type_id))
+                .collect(),
+            retention: self.retention,
+            subrange: self.subrange,
         }
     }
 
@@ -203,6 +301,51 @@ impl Runtime {
         self.faults.policy()
     }
 
+    /// Update how periodic tasks handle releases missed while running late.
+    pub fn set_task_catch_up(&mut self, policy: TaskCatchUp) {
+        self.task_catch_up = policy;
+    }
+
+    /// Current task catch-up policy.
+    #[must_use]
+    pub fn task_catch_up(&self) -> TaskCatchUp {
+        self.task_catch_up
+    }
+
+    /// Update what out-of-bounds array indices and string positions do per task.
+    pub fn set_bounds_settings(&mut self, settings: BoundsSettings) {
+        self.active_bounds = settings.default;
+        self.bounds = settings;
+    }
+
+    /// Current bounds policy and per-task overrides.
+    #[must_use]
+    pub fn bounds_settings(&self) -> &BoundsSettings {
+        &self.bounds
+    }
+
+    /// Update what integer arithmetic does on overflow.
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.overflow = policy;
+    }
+
+    /// Current integer overflow policy.
+    #[must_use]
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow
+    }
+
+    /// Update what writes outside a subrange type's range do.
+    pub fn set_subrange_policy(&mut self, policy: SubrangePolicy) {
+        self.subrange = policy;
+    }
+
+    /// Current subrange enforcement policy.
+    #[must_use]
+    pub fn subrange_policy(&self) -> SubrangePolicy {
+        self.subrange
+    }
+
     /// Set an optional execution deadline enforced by the evaluator.
     pub fn set_execution_deadline(&mut self, deadline: Option<std::time::Instant>) {
         self.execution_deadline = deadline;
@@ -214,13 +357,49 @@ impl Runtime {
         self.execution_deadline
     }
 
+    /// Confine file function blocks to `dir`; `None` leaves paths unrestricted.
+    pub fn set_worker_data_dir(&mut self, dir: Option<std::path::PathBuf>) {
+        self.workers.set_data_dir(dir);
+    }
+
+    /// Background worker pool used by asynchronous function blocks.
+    #[must_use]
+    pub fn workers(&self) -> &WorkerPool {
+        &self.workers
+    }
+
     /// Update configured safe-state outputs.
     pub fn set_io_safe_state(&mut self, safe_state: IoSafeState) {
         self.io.set_safe_state(safe_state);
     }
 
+    /// Output policy applied on pause, fault, and watchdog trip.
+    #[must_use]
+    pub fn output_safe_state(&self) -> &OutputSafeState {
+        self.io.output_safe_state()
+    }
+
+    /// Update the output policy applied on pause, fault, and watchdog trip.
+    pub fn set_output_safe_state(&mut self, safe_state: OutputSafeState) {
+        self.io.set_output_safe_state(safe_state);
+    }
+
+    /// Drive outputs to the configured safe state and flush them to the drivers.
+    pub fn apply_output_safe_state(&mut self) -> Result<(), error::RuntimeError> {
+        self.io.apply_output_safe_state()
+    }
+
+    /// Check the I/O bindings for overlapping outputs and for mismatches with
+    /// the channel maps of the attached drivers.
+    pub fn validate_io_map(&self) -> Result<(), error::RuntimeError> {
+        self.io.validate_map()
+    }
+
     /// Attach a metrics sink for runtime statistics.
     pub fn set_metrics_sink(&mut self, metrics: std::sync::Arc<std::sync::Mutex<RuntimeMetrics>>) {
+        if let Ok(mut guard) = metrics.lock() {
+            guard.set_time_base(self.time_base.clone());
+        }
         self.metrics.set_sink(metrics);
     }
 
@@ -254,6 +433,7 @@ impl Runtime {
         err: error::RuntimeError,
         decision: FaultDecision,
     ) -> error::RuntimeError {
+        let _ = self.io.apply_output_safe_state();
         if decision.apply_safe_state {
             let _ = self.io.apply_safe_state();
         }
@@ -262,6 +442,7 @@ impl Runtime {
         if let Some(debug) = &self.debug {
             debug.push_runtime_event(crate::debug::RuntimeEvent::Fault {
                 error: err.to_string(),
+                location: self.faults.last_location().cloned(),
                 time: self.current_time,
             });
         }
@@ -274,6 +455,11 @@ impl Runtime {
         self.debug.clone()
     }
 
+    /// Register the text of a source file so faults report their line.
+    pub fn register_source_text(&mut self, file_id: u32, path: Option<&str>, text: &str) {
+        self.faults.register_source(file_id, path, text);
+    }
+
     /// Register statement locations for a file id.
     pub fn register_statement_locations(
         &mut self,
@@ -382,18 +568,21 @@ impl Runtime {
     pub fn register_function(&mut self, function: FunctionDef) {
         let key = function.name.to_ascii_uppercase();
         self.functions.insert(key.into(), function);
+        self.pou_code = None;
     }
 
     /// Register a function block definition by name.
     pub fn register_function_block(&mut self, function_block: FunctionBlockDef) {
         let key = function_block.name.to_ascii_uppercase();
         self.function_blocks.insert(key.into(), function_block);
+        self.pou_code = None;
     }
 
     /// Register a class definition by name.
     pub fn register_class(&mut self, class_def: ClassDef) {
         let key = class_def.name.to_ascii_uppercase();
         self.classes.insert(key.into(), class_def);
+        self.pou_code = None;
     }
 
     /// Register an interface definition by name.
@@ -556,6 +745,9 @@ impl Runtime {
         let classes = &self.classes;
         let access = &self.access;
         let execution_deadline = self.execution_deadline;
+        let workers = &self.workers;
+        let overflow = self.overflow;
+        let subrange = self.subrange;
         let eval = |storage: &mut VariableStorage, instance_id: Option<InstanceId>| {
             let mut ctx = EvalContext {
                 storage,
@@ -575,6 +767,12 @@ impl Runtime {
                 loop_depth: 0,
                 pause_requested: false,
                 execution_deadline,
+                workers: Some(workers),
+                bounds: crate::task::BoundsPolicy::Fault,
+                overflow,
+                subrange,
+                fault_site: None,
+                pou_code: None,
             };
             eval::eval_expr(&mut ctx, expr)
         };
@@ -607,6 +805,9 @@ impl Runtime {
         let classes = &self.classes;
         let access = &self.access;
         let execution_deadline = self.execution_deadline;
+        let workers = &self.workers;
+        let overflow = self.overflow;
+        let subrange = self.subrange;
         let eval = |storage: &mut VariableStorage, instance_id: Option<InstanceId>| {
             let mut ctx = EvalContext {
                 storage,
@@ -626,6 +827,12 @@ impl Runtime {
                 loop_depth: 0,
                 pause_requested: false,
                 execution_deadline,
+                workers: Some(workers),
+                bounds: crate::task::BoundsPolicy::Fault,
+                overflow,
+                subrange,
+                fault_site: None,
+                pou_code: None,
             };
             f(&mut ctx)
         };
@@ -656,6 +863,15 @@ impl Runtime {
         )?;
         self.storage
             .set_global(program.name.clone(), Value::Instance(instance_id));
+        let body = eval::vm::compile_block(&program.body).map(std::sync::Arc::new);
+        self.jit.invalidate(&program.name);
+        self.program_cache.insert(
+            program.name.clone(),
+            CachedProgram {
+                def: std::sync::Arc::new(program.clone()),
+                body,
+            },
+        );
         self.programs.insert(program.name.clone(), program);
         Ok(())
     }
@@ -724,12 +940,15 @@ impl Runtime {
         }
         self.programs
             .keys()
-            .any(|name| !scheduled.contains_key(name))
+            .any(|name| !scheduled.contains_key(name) && !self.is_shutdown_program(name))
     }
 
     /// Advance the runtime clock by the given duration.
     pub fn advance_time(&mut self, delta: Duration) {
-        let next = self.current_time.as_nanos() + delta.as_nanos();
+        let next = self
+            .current_time
+            .as_nanos()
+            .saturating_add(delta.as_nanos());
         self.current_time = Duration::from_nanos(next);
     }
 
@@ -738,6 +957,12 @@ impl Runtime {
         self.current_time = time;
     }
 
+    /// Wall-clock view of the resource clock, for stamping samples and uptime.
+    #[must_use]
+    pub fn time_base(&self) -> TimeBase {
+        self.time_base.clone()
+    }
+
     /// Return whether the resource is currently faulted.
     #[must_use]
     pub fn faulted(&self) -> bool {
@@ -750,6 +975,12 @@ impl Runtime {
         self.faults.last_fault()
     }
 
+    /// Source location of the last recorded fault, when it was raised by a statement.
+    #[must_use]
+    pub fn last_fault_location(&self) -> Option<&error::FaultLocation> {
+        self.faults.last_location()
+    }
+
     /// Clear the faulted state (used by tests and tooling).
     pub fn clear_fault(&mut self) {
         self.faults.clear();
@@ -5,8 +5,9 @@
 use indexmap::IndexMap;
 use smol_str::SmolStr;
 
+use crate::eval::vm::{CompiledBlock, ExecutionEngine};
 use crate::eval::{self, EvalContext};
-use crate::task::{ProgramDef, TaskConfig};
+use crate::task::{ProgramDef, TaskCatchUp, TaskConfig, MAX_BURST_RELEASES};
 use crate::value::{Duration, Value};
 use crate::{error, stdlib};
 use trust_hir::symbols::ParamDirection;
@@ -21,6 +22,10 @@ impl Runtime {
         }
 
         let cycle_timer = self.metrics.start_timer();
+        self.time_base.observe(self.current_time);
+        self.faults.set_pending_location(None);
+        // Async FB completions change only at cycle boundaries.
+        self.workers.latch();
         let debug = self.debug.clone();
         if let Some(debug) = debug.as_ref() {
             for write in debug.drain_var_writes() {
@@ -50,6 +55,11 @@ impl Runtime {
         }
 
         if let Some(debug) = &self.debug {
+            let watchdog = self.watchdog.policy();
+            debug.begin_fault_cycle(
+                matches!(self.faults.policy(), crate::watchdog::FaultPolicy::Restart),
+                watchdog.enabled.then_some(watchdog.timeout),
+            );
             debug.push_runtime_event(crate::debug::RuntimeEvent::CycleStart {
                 cycle: self.cycle_counter,
                 time: self.current_time,
@@ -60,30 +70,19 @@ impl Runtime {
             return Err(self.record_fault(err));
         }
 
-        let mut ready = match self.collect_ready_tasks() {
-            Ok(ready) => ready,
-            Err(err) => return Err(self.record_fault(err)),
-        };
-        ready.sort_by_key(|entry| {
-            let task = &self.tasks[entry.index];
-            (task.priority, entry.due_at.as_nanos(), entry.index)
-        });
-        for entry in ready {
-            let task = self.tasks[entry.index].clone();
-            let task_timer = self.metrics.start_timer();
-            if let Err(err) = self.execute_task(&task) {
+        if self.standby {
+            // The active partner drives the outputs; keep the image in step with
+            // the synced state so a takeover starts from the same values.
+            if let Err(err) = self.io.interface_mut().write_outputs(&self.storage) {
                 return Err(self.record_fault(err));
             }
-            if let Some(start) = task_timer {
-                self.metrics.record_task(&task.name, start.elapsed());
+        } else {
+            if let Err(err) = self.execute_ready_tasks() {
+                return Err(self.record_fault(err));
+            }
+            if let Err(err) = self.write_cycle_outputs() {
+                return Err(self.record_fault(err));
             }
-        }
-        if let Err(err) = self.execute_background_programs() {
-            return Err(self.record_fault(err));
-        }
-
-        if let Err(err) = self.write_cycle_outputs() {
-            return Err(self.record_fault(err));
         }
 
         if self.retain.has_store() {
@@ -94,6 +93,7 @@ impl Runtime {
         }
 
         if let Some(debug) = &self.debug {
+            self.record_debug_history(debug);
             debug.push_runtime_event(crate::debug::RuntimeEvent::CycleEnd {
                 cycle: self.cycle_counter,
                 time: self.current_time,
@@ -102,10 +102,49 @@ impl Runtime {
         if let Some(start) = cycle_timer {
             self.metrics.record_cycle(start.elapsed());
         }
+        self.jit.end_cycle();
         self.cycle_counter = self.cycle_counter.saturating_add(1);
         Ok(())
     }
 
+    fn execute_ready_tasks(&mut self) -> Result<(), error::RuntimeError> {
+        if self.shutting_down {
+            return self.execute_shutdown_program();
+        }
+        let mut ready = self.collect_ready_tasks()?;
+        ready.sort_by_key(|entry| {
+            let task = &self.tasks[entry.index];
+            (task.priority, entry.due_at.as_nanos(), entry.index)
+        });
+        for entry in ready {
+            let task = self.tasks[entry.index].clone();
+            let task_timer = self.metrics.start_timer();
+            self.execute_task(&task)?;
+            if let Some(start) = task_timer {
+                self.metrics.record_task(&task.name, start.elapsed());
+            }
+        }
+        self.execute_background_programs()
+    }
+
+    /// Sample the variables watched through `debug.history`; names that do not
+    /// resolve (yet) are skipped.
+    fn record_debug_history(&self, debug: &crate::debug::DebugControl) {
+        let names = debug.history_names();
+        if names.is_empty() {
+            return;
+        }
+        let values = names
+            .into_iter()
+            .filter_map(|name| {
+                let reference = self.resolve_variable_path(name.as_str())?;
+                let value = self.storage.read_by_ref(reference)?.clone();
+                Some((name, value))
+            })
+            .collect();
+        debug.record_history(self.cycle_counter, self.current_time, values);
+    }
+
     fn apply_forced_values(
         &mut self,
         debug: &crate::debug::DebugControl,
@@ -132,7 +171,22 @@ impl Runtime {
 
     /// Execute a program body in the runtime context.
     pub fn execute_program(&mut self, program: &ProgramDef) -> Result<(), error::RuntimeError> {
+        self.execute_program_body(program, None)
+    }
+
+    fn execute_program_body(
+        &mut self,
+        program: &ProgramDef,
+        compiled: Option<&CompiledBlock>,
+    ) -> Result<(), error::RuntimeError> {
         let mut debug = self.debug.take();
+        // Statement hooks only matter while the debugger can stop execution.
+        let vm = self.execution_engine == ExecutionEngine::Vm
+            && debug.as_ref().is_none_or(|debug| debug.is_idle());
+        let compiled = compiled.filter(|_| vm);
+        if vm {
+            self.ensure_pou_code();
+        }
         let instance_id = match self.storage.get_global(program.name.as_ref()) {
             Some(Value::Instance(id)) => Some(*id),
             _ => None,
@@ -157,6 +211,12 @@ impl Runtime {
             loop_depth: 0,
             pause_requested: false,
             execution_deadline: self.execution_deadline,
+            workers: Some(&self.workers),
+            bounds: self.active_bounds,
+            overflow: self.overflow,
+            subrange: self.subrange,
+            fault_site: None,
+            pou_code: if vm { self.pou_code.as_ref() } else { None },
         };
         let mut has_frame = false;
         if instance_id.is_some() || !program.temps.is_empty() {
@@ -175,13 +235,29 @@ impl Runtime {
             }
             has_frame = true;
         }
-        let result = match eval::exec_block(&mut ctx, &program.body) {
+        let timer = compiled.and_then(|_| self.jit.start_timer());
+        let result = match compiled {
+            // Native code has no statement budget checks, so it only runs when
+            // no execution deadline is armed.
+            #[cfg(feature = "jit")]
+            Some(block) if ctx.execution_deadline.is_none() => {
+                self.jit.exec(&program.name, block, &mut ctx)
+            }
+            Some(block) => eval::vm::exec_compiled(&mut ctx, block),
+            None => eval::exec_block(&mut ctx, &program.body),
+        };
+        if let Some(start) = timer {
+            self.jit.record(&program.name, start.elapsed());
+        }
+        let result = match result {
             Ok(result) => result,
             Err(err) => {
+                let site = ctx.fault_site.take();
                 if has_frame {
                     ctx.storage.pop_frame();
                 }
                 self.debug = debug;
+                self.note_fault_site(site, &program.name);
                 return Err(err);
             }
         };
@@ -195,14 +271,17 @@ impl Runtime {
         }
     }
 
-    fn execute_program_by_name(&mut self, name: &SmolStr) -> Result<(), error::RuntimeError> {
+    pub(super) fn execute_program_by_name(
+        &mut self,
+        name: &SmolStr,
+    ) -> Result<(), error::RuntimeError> {
         let timer = self.metrics.start_timer();
         let program = self
-            .programs
+            .program_cache
             .get(name)
             .cloned()
             .ok_or_else(|| error::RuntimeError::UndefinedProgram(name.clone()))?;
-        let result = self.execute_program(&program);
+        let result = self.execute_program_body(&program.def, program.body.as_deref());
         if let Some(start) = timer {
             self.metrics
                 .record_profile_call("program", name, start.elapsed());
@@ -220,12 +299,10 @@ impl Runtime {
                 time: self.current_time,
             });
         }
-        for program in &task.programs {
-            self.execute_program_by_name(program)?;
-        }
-        for fb_ref in &task.fb_instances {
-            self.execute_function_block_ref(fb_ref)?;
-        }
+        self.active_bounds = self.bounds.for_task(&task.name);
+        let result = self.execute_task_body(task);
+        self.active_bounds = self.bounds.default;
+        result?;
         if let Some(debug) = &self.debug {
             debug.push_runtime_event(crate::debug::RuntimeEvent::TaskEnd {
                 name: task.name.clone(),
@@ -236,6 +313,16 @@ impl Runtime {
         Ok(())
     }
 
+    fn execute_task_body(&mut self, task: &TaskConfig) -> Result<(), error::RuntimeError> {
+        for program in &task.programs {
+            self.execute_program_by_name(program)?;
+        }
+        for fb_ref in &task.fb_instances {
+            self.execute_function_block_ref(fb_ref)?;
+        }
+        Ok(())
+    }
+
     fn execute_background_programs(&mut self) -> Result<(), error::RuntimeError> {
         let mut scheduled = IndexMap::new();
         for task in &self.tasks {
@@ -244,8 +331,8 @@ impl Runtime {
             }
         }
         let mut background = Vec::new();
-        for (name, program) in &self.programs {
-            if scheduled.contains_key(name) {
+        for (name, program) in &self.program_cache {
+            if scheduled.contains_key(name) || self.is_shutdown_program(name) {
                 continue;
             }
             background.push(program.clone());
@@ -259,7 +346,7 @@ impl Runtime {
             debug.set_current_thread(thread_id);
         }
         for program in background {
-            self.execute_program(&program)?;
+            self.execute_program_body(&program.def, program.body.as_deref())?;
         }
         Ok(())
     }
@@ -282,16 +369,24 @@ impl Runtime {
             };
             let event_due = !state.last_single && single_now;
             let interval_nanos = task.interval.as_nanos();
-            let elapsed = now.as_nanos().saturating_sub(state.last_run.as_nanos());
+            let elapsed = now.as_nanos().saturating_sub(state.last_release.as_nanos());
             let periodic_due = interval_nanos > 0 && !single_now && elapsed >= interval_nanos;
-            let mut due_at = None;
             if event_due {
-                due_at = Some(now);
+                ready.push(ReadyTask {
+                    index: idx,
+                    due_at: now,
+                });
             }
             if periodic_due {
+                // Releases stay on the absolute grid `last_release + n * interval`;
+                // how many of the elapsed ones run depends on the catch-up policy.
                 let intervals = elapsed / interval_nanos;
-                if intervals > 1 {
-                    let missed = (intervals - 1) as u64;
+                let releases = match self.task_catch_up {
+                    TaskCatchUp::Skip => 1,
+                    TaskCatchUp::Burst => intervals.min(MAX_BURST_RELEASES),
+                };
+                let missed = (intervals - releases) as u64;
+                if missed > 0 {
                     state.overrun_count = state.overrun_count.saturating_add(missed);
                     if let Some(debug) = &self.debug {
                         debug.push_runtime_event(crate::debug::RuntimeEvent::TaskOverrun {
@@ -302,27 +397,49 @@ impl Runtime {
                     }
                     self.metrics.record_overrun(&task.name, missed);
                 }
-                let due_time =
-                    Duration::from_nanos(state.last_run.as_nanos().saturating_add(interval_nanos));
-                due_at = Some(match due_at {
-                    Some(existing) if existing.as_nanos() <= due_time.as_nanos() => existing,
-                    _ => due_time,
-                });
-                state.last_run = now;
+                let base = state.last_release.as_nanos();
+                for release in (intervals - releases + 1)..=intervals {
+                    let release_at = base.saturating_add(release * interval_nanos);
+                    let jitter = now.as_nanos().saturating_sub(release_at).max(0) as u64;
+                    self.metrics
+                        .record_jitter(&task.name, std::time::Duration::from_nanos(jitter));
+                    // A skipped backlog keeps its place ahead of work that became
+                    // due later, so order it by the oldest pending release.
+                    let due_at = match self.task_catch_up {
+                        TaskCatchUp::Skip => base.saturating_add(interval_nanos),
+                        TaskCatchUp::Burst => release_at,
+                    };
+                    ready.push(ReadyTask {
+                        index: idx,
+                        due_at: Duration::from_nanos(due_at),
+                    });
+                }
+                state.last_release =
+                    Duration::from_nanos(base.saturating_add(intervals * interval_nanos));
             }
             state.last_single = single_now;
-            if let Some(due_at) = due_at {
-                ready.push(ReadyTask { index: idx, due_at });
-            }
         }
         Ok(ready)
     }
 
+    /// Compiles POU bodies again after a definition changed.
+    fn ensure_pou_code(&mut self) {
+        if self.pou_code.is_none() {
+            self.pou_code = Some(eval::vm::PouCode::build(
+                &self.functions,
+                &self.function_blocks,
+                &self.classes,
+                self.jit.settings(),
+            ));
+        }
+    }
+
     fn execute_function_block_ref(
         &mut self,
         reference: &crate::value::ValueRef,
     ) -> Result<(), error::RuntimeError> {
         let timer = self.metrics.start_timer();
+        self.ensure_pou_code();
         let instance_id = match self.storage.read_by_ref(reference.clone()) {
             Some(Value::Instance(id)) => *id,
             Some(_) => return Err(error::RuntimeError::TypeMismatch),
@@ -337,6 +454,8 @@ impl Runtime {
             error::RuntimeError::UndefinedFunctionBlock(instance.type_name.clone())
         })?;
         let mut debug = self.debug.take();
+        let vm = self.execution_engine == ExecutionEngine::Vm
+            && debug.as_ref().is_none_or(|debug| debug.is_idle());
         let mut ctx = EvalContext {
             storage: &mut self.storage,
             registry: &self.registry,
@@ -357,6 +476,12 @@ impl Runtime {
             loop_depth: 0,
             pause_requested: false,
             execution_deadline: self.execution_deadline,
+            workers: Some(&self.workers),
+            bounds: self.active_bounds,
+            overflow: self.overflow,
+            subrange: self.subrange,
+            fault_site: None,
+            pou_code: if vm { self.pou_code.as_ref() } else { None },
         };
         ctx.storage
             .push_frame_with_instance(fb.name.clone(), instance_id);
@@ -383,14 +508,20 @@ impl Runtime {
             stdlib::fbs::execute_builtin(&mut ctx, instance_id, kind)
         } else {
             crate::eval::init_locals_in_frame(&mut ctx, &fb.temps)?;
-            crate::eval::exec_block(&mut ctx, &fb.body).map(|_| ())
+            let compiled = ctx.pou_code.and_then(|code| code.function_block(&fb.name));
+            crate::eval::exec_pou_body(&mut ctx, compiled, &fb.body).map(|_| ())
         };
 
+        let site = ctx.fault_site.take();
         ctx.storage.pop_frame();
         self.debug = debug;
+        let fb_name = fb.name.clone();
         if let Some(start) = timer {
             self.metrics
-                .record_profile_call("fb", &fb.name, start.elapsed());
+                .record_profile_call("fb", &fb_name, start.elapsed());
+        }
+        if result.is_err() {
+            self.note_fault_site(site, &fb_name);
         }
         result
     }
@@ -432,6 +563,16 @@ impl Runtime {
         Ok(())
     }
 
+    /// Resolves where a failing POU raised its error, for the fault about to be
+    /// recorded. `pou` names the program or FB when the error left no frame.
+    fn note_fault_site(&mut self, site: Option<eval::FaultSite>, pou: &SmolStr) {
+        let location = site.and_then(|site| {
+            let pou = site.pou.unwrap_or_else(|| pou.clone());
+            self.faults.locate(pou, &site.location)
+        });
+        self.faults.set_pending_location(location);
+    }
+
     fn record_fault(&mut self, err: error::RuntimeError) -> error::RuntimeError {
         self.apply_fault(err, self.faults.decision())
     }
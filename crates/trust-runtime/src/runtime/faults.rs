@@ -1,12 +1,26 @@
 //! Fault state management.
 
-use crate::error::RuntimeError;
+use indexmap::IndexMap;
+use smol_str::SmolStr;
+
+use crate::debug::SourceLocation;
+use crate::error::{FaultLocation, RuntimeError};
 use crate::watchdog::{FaultDecision, FaultPolicy};
 
 pub(super) struct FaultSubsystem {
     policy: FaultPolicy,
     faulted: bool,
     last_fault: Option<RuntimeError>,
+    last_location: Option<FaultLocation>,
+    /// Location of the statement that raised the error being propagated.
+    pending_location: Option<FaultLocation>,
+    sources: IndexMap<u32, FaultSource>,
+}
+
+/// Line table of a source file, used to report fault locations.
+struct FaultSource {
+    path: Option<SmolStr>,
+    line_starts: Vec<u32>,
 }
 
 impl FaultSubsystem {
@@ -15,6 +29,9 @@ impl FaultSubsystem {
             policy: FaultPolicy::Halt,
             faulted: false,
             last_fault: None,
+            last_location: None,
+            pending_location: None,
+            sources: IndexMap::new(),
         }
     }
 
@@ -30,14 +47,55 @@ impl FaultSubsystem {
         FaultDecision::from_fault_policy(self.policy)
     }
 
+    pub(super) fn register_source(&mut self, file_id: u32, path: Option<&str>, text: &str) {
+        let line_starts = std::iter::once(0)
+            .chain(
+                text.bytes()
+                    .enumerate()
+                    .filter(|(_, byte)| *byte == b'\n')
+                    .map(|(idx, _)| idx as u32 + 1),
+            )
+            .collect();
+        self.sources.insert(
+            file_id,
+            FaultSource {
+                path: path.map(SmolStr::new),
+                line_starts,
+            },
+        );
+    }
+
+    /// Resolves a statement location in `pou` to a line and column, if its file
+    /// was registered.
+    pub(super) fn locate(&self, pou: SmolStr, location: &SourceLocation) -> Option<FaultLocation> {
+        let source = self.sources.get(&location.file_id)?;
+        let line = source
+            .line_starts
+            .partition_point(|start| *start <= location.start)
+            .max(1);
+        let line_start = source.line_starts[line - 1];
+        Some(FaultLocation {
+            pou,
+            file: source.path.clone(),
+            line: line as u32,
+            column: location.start - line_start + 1,
+        })
+    }
+
+    pub(super) fn set_pending_location(&mut self, location: Option<FaultLocation>) {
+        self.pending_location = location;
+    }
+
     pub(super) fn record(&mut self, err: RuntimeError) {
         self.faulted = true;
         self.last_fault = Some(err);
+        self.last_location = self.pending_location.take();
     }
 
     pub(super) fn clear(&mut self) {
         self.faulted = false;
         self.last_fault = None;
+        self.last_location = None;
     }
 
     pub(super) fn is_faulted(&self) -> bool {
@@ -47,4 +105,8 @@ impl FaultSubsystem {
     pub(super) fn last_fault(&self) -> Option<&RuntimeError> {
         self.last_fault.as_ref()
     }
+
+    pub(super) fn last_location(&self) -> Option<&FaultLocation> {
+        self.last_location.as_ref()
+    }
 }
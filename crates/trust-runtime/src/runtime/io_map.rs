@@ -0,0 +1,104 @@
+//! Binding of io.toml `[io.map]` symbols to program variables.
+
+#![allow(missing_docs)]
+
+use crate::error::RuntimeError;
+use crate::io::{format_io_address, symbol_value_type, IoSize, IoSymbolMap};
+use crate::value::{Value, ValueRef};
+
+use super::core::Runtime;
+
+impl Runtime {
+    /// Bind every `[io.map]` symbol to its address.
+    ///
+    /// All symbols that name no global or program variable are reported in one error.
+    pub fn bind_io_symbols(&mut self, map: &IoSymbolMap) -> Result<(), RuntimeError> {
+        let mut resolved = Vec::with_capacity(map.symbols.len());
+        let mut unbound = Vec::new();
+        for symbol in &map.symbols {
+            match self.resolve_variable_path(symbol.name.as_str()) {
+                Some(reference) => resolved.push((symbol, reference)),
+                None => unbound.push(symbol.name.to_string()),
+            }
+        }
+        if !unbound.is_empty() {
+            return Err(RuntimeError::UnboundIoSymbols(unbound.join(", ").into()));
+        }
+
+        for (symbol, reference) in resolved {
+            let value = self
+                .storage()
+                .read_by_ref(reference.clone())
+                .ok_or(RuntimeError::NullReference)?;
+            let Some((value_type, size)) = symbol_value_type(value) else {
+                return Err(RuntimeError::IoMapConflict(
+                    format!(
+                        "io.map '{}' is not an elementary variable and cannot be bound to {}",
+                        symbol.name,
+                        format_io_address(&symbol.address)
+                    )
+                    .into(),
+                ));
+            };
+            if size != symbol.address.size {
+                return Err(RuntimeError::IoMapConflict(
+                    format!(
+                        "io.map '{}' at {} is a {} address but the variable needs a {}",
+                        symbol.name,
+                        format_io_address(&symbol.address),
+                        size_name(symbol.address.size),
+                        size_name(size)
+                    )
+                    .into(),
+                ));
+            }
+            self.io_mut().bind_ref_named_typed(
+                reference,
+                symbol.address.clone(),
+                value_type,
+                symbol.name.clone(),
+            );
+        }
+        self.io_mut().fit_bindings();
+        Ok(())
+    }
+
+    /// Resolve a global name or `<program>.<variable>` path.
+    pub(super) fn resolve_variable_path(&self, name: &str) -> Option<ValueRef> {
+        let storage = self.storage();
+        if let Some(reference) = storage.ref_for_global(name) {
+            return Some(reference);
+        }
+        if let Some((key, _)) = storage
+            .globals()
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        {
+            return storage.ref_for_global(key.as_str());
+        }
+        let (program, variable) = name.split_once('.')?;
+        let program = self
+            .programs()
+            .values()
+            .find(|def| def.name.eq_ignore_ascii_case(program))?;
+        let Some(Value::Instance(id)) = storage.get_global(program.name.as_str()) else {
+            return None;
+        };
+        let instance = storage.get_instance(*id)?;
+        let (key, _) = instance
+            .variables
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(variable))?;
+        storage.ref_for_instance(*id, key.as_str())
+    }
+}
+
+fn size_name(size: IoSize) -> &'static str {
+    match size {
+        IoSize::Bit => "bit",
+        IoSize::Byte => "byte",
+        IoSize::Word => "word",
+        IoSize::DWord => "double word",
+        IoSize::LWord => "long word",
+    }
+}
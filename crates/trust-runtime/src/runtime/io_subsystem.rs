@@ -5,13 +5,17 @@ use std::sync::{Arc, Mutex};
 use smol_str::SmolStr;
 
 use crate::error::RuntimeError;
-use crate::io::{IoDriver, IoDriverStatus, IoInterface, IoSafeState, IoSnapshot};
+use crate::io::{
+    validate_io_map, DriverChannel, IoDriver, IoDriverStatus, IoInterface, IoSafeState,
+    OutputSafeState,
+};
 
 pub(super) struct IoSubsystem {
     interface: IoInterface,
     drivers: Vec<IoDriverEntry>,
     health_sink: Option<Arc<Mutex<Vec<IoDriverStatus>>>>,
     safe_state: IoSafeState,
+    output_safe_state: OutputSafeState,
 }
 
 pub(super) struct IoDriverEntry {
@@ -26,6 +30,7 @@ impl IoSubsystem {
             drivers: Vec::new(),
             health_sink: None,
             safe_state: IoSafeState::default(),
+            output_safe_state: OutputSafeState::default(),
         }
     }
 
@@ -46,6 +51,8 @@ impl IoSubsystem {
 
     pub(super) fn resize(&mut self, inputs: usize, outputs: usize, memory: usize) {
         self.interface.resize(inputs, outputs, memory);
+        // Symbols bound from io.toml are not part of the bytecode process image.
+        self.interface.fit_bindings();
     }
 
     pub(super) fn add_driver(&mut self, name: impl Into<SmolStr>, driver: Box<dyn IoDriver>) {
@@ -84,6 +91,26 @@ impl IoSubsystem {
 
     pub(super) fn apply_safe_state(&mut self) -> Result<(), RuntimeError> {
         self.safe_state.apply(&mut self.interface)?;
+        self.flush_outputs()
+    }
+
+    pub(super) fn output_safe_state(&self) -> &OutputSafeState {
+        &self.output_safe_state
+    }
+
+    pub(super) fn set_output_safe_state(&mut self, safe_state: OutputSafeState) {
+        self.output_safe_state = safe_state;
+    }
+
+    pub(super) fn apply_output_safe_state(&mut self) -> Result<(), RuntimeError> {
+        if self.output_safe_state.is_hold_all() {
+            return Ok(());
+        }
+        self.output_safe_state.apply(&mut self.interface)?;
+        self.flush_outputs()
+    }
+
+    fn flush_outputs(&mut self) -> Result<(), RuntimeError> {
         for entry in &mut self.drivers {
             entry.driver.write_outputs(self.interface.outputs())?;
         }
@@ -91,7 +118,26 @@ impl IoSubsystem {
         Ok(())
     }
 
-    pub(super) fn snapshot(&self) -> IoSnapshot {
+    pub(super) fn validate_map(&self) -> Result<(), RuntimeError> {
+        let channels = self
+            .drivers
+            .iter()
+            .flat_map(|entry| {
+                entry
+                    .driver
+                    .channels()
+                    .into_iter()
+                    .map(|channel| DriverChannel {
+                        driver: entry.name.clone(),
+                        channel,
+                    })
+            })
+            .collect::<Vec<_>>();
+        validate_io_map(self.interface.bindings(), &channels)
+    }
+
+    #[cfg(feature = "debug")]
+    pub(super) fn snapshot(&self) -> crate::io::IoSnapshot {
         self.interface.snapshot()
     }
 }
@@ -0,0 +1,131 @@
+//! Hot program selection for the native backend.
+
+use std::time::{Duration as StdDuration, Instant};
+
+use indexmap::IndexMap;
+use smol_str::SmolStr;
+
+#[cfg(feature = "jit")]
+use crate::error::RuntimeError;
+use crate::eval::vm::JitSettings;
+#[cfg(feature = "jit")]
+use crate::eval::{
+    stmt::StmtResult,
+    vm::{CompiledBlock, NativeBlock},
+    EvalContext,
+};
+
+pub(super) struct JitSubsystem {
+    settings: JitSettings,
+    cycles: u64,
+    samples: IndexMap<SmolStr, StdDuration>,
+    /// Programs compiled on their next run.
+    pending: Vec<SmolStr>,
+    selected: bool,
+    #[cfg(feature = "jit")]
+    native: IndexMap<SmolStr, NativeBlock>,
+}
+
+impl JitSubsystem {
+    pub(super) fn new() -> Self {
+        Self {
+            settings: JitSettings::default(),
+            cycles: 0,
+            samples: IndexMap::new(),
+            pending: Vec::new(),
+            selected: false,
+            #[cfg(feature = "jit")]
+            native: IndexMap::new(),
+        }
+    }
+
+    pub(super) fn settings(&self) -> JitSettings {
+        self.settings
+    }
+
+    /// Applies new settings and restarts profiling.
+    pub(super) fn configure(&mut self, settings: JitSettings) {
+        *self = Self::new();
+        self.settings = settings;
+    }
+
+    /// Starts timing a program body while hot programs are still unknown.
+    pub(super) fn start_timer(&self) -> Option<Instant> {
+        (crate::eval::vm::JIT_AVAILABLE && self.settings.enabled && !self.selected)
+            .then(Instant::now)
+    }
+
+    pub(super) fn record(&mut self, name: &SmolStr, duration: StdDuration) {
+        *self.samples.entry(name.clone()).or_default() += duration;
+    }
+
+    /// Picks the hot programs once the warmup period is over.
+    pub(super) fn end_cycle(&mut self) {
+        if !self.settings.enabled || self.selected {
+            return;
+        }
+        self.cycles += 1;
+        if self.cycles < self.settings.warmup_cycles {
+            return;
+        }
+        let mut samples = std::mem::take(&mut self.samples)
+            .into_iter()
+            .collect::<Vec<_>>();
+        samples.sort_by_key(|(_, duration)| std::cmp::Reverse(*duration));
+        self.pending = samples
+            .into_iter()
+            .take(self.settings.hot_programs)
+            .map(|(name, _)| name)
+            .collect();
+        self.selected = true;
+    }
+
+    /// Recompiles a program whose definition changed on its next run.
+    #[cfg(feature = "jit")]
+    pub(super) fn invalidate(&mut self, name: &SmolStr) {
+        if self.native.shift_remove(name).is_some() {
+            self.pending.push(name.clone());
+        }
+    }
+
+    #[cfg(not(feature = "jit"))]
+    pub(super) fn invalidate(&mut self, _name: &SmolStr) {}
+
+    /// Runs a program body, natively once it has been compiled.
+    ///
+    /// A program selected as hot runs once more on the VM while the result
+    /// kinds of its fallback expressions are recorded, and is compiled after.
+    #[cfg(feature = "jit")]
+    pub(super) fn exec(
+        &mut self,
+        name: &SmolStr,
+        block: &CompiledBlock,
+        ctx: &mut EvalContext<'_>,
+    ) -> Result<StmtResult, RuntimeError> {
+        if let Some(index) = self.pending.iter().position(|pending| pending == name) {
+            self.pending.swap_remove(index);
+            let (result, native) = crate::eval::vm::exec_and_compile(ctx, block);
+            match native {
+                Some(native) => {
+                    self.native.insert(name.clone(), native);
+                }
+                None => tracing::debug!("program {name} stays on the bytecode VM"),
+            }
+            return result;
+        }
+        match self.native.get(name) {
+            Some(native) => native.run(ctx, block),
+            None => crate::eval::vm::exec_compiled(ctx, block),
+        }
+    }
+
+    #[cfg(feature = "jit")]
+    pub(super) fn compiled_programs(&self) -> Vec<SmolStr> {
+        self.native.keys().cloned().collect()
+    }
+
+    #[cfg(not(feature = "jit"))]
+    pub(super) fn compiled_programs(&self) -> Vec<SmolStr> {
+        Vec::new()
+    }
+}
@@ -5,13 +5,18 @@
 use indexmap::IndexMap;
 use smol_str::SmolStr;
 
+use crate::bytecode::BuildInfo;
 use crate::debug::SourceLocation;
+use crate::error::RuntimeError;
 use crate::eval::{ClassDef, FunctionBlockDef, FunctionDef, InterfaceDef};
 use crate::memory::{AccessMap, FrameId, LocalFrame, VariableStorage};
 use crate::stdlib::StandardLibrary;
-use crate::task::{ProgramDef, TaskConfig};
-use crate::value::DateTimeProfile;
+use crate::task::{subrange_of_type, ProgramDef, SubrangePolicy, TaskConfig};
+use crate::value::{DateTimeProfile, Value};
+
+use super::types::{RestartMode, RestartPreviewEntry, RetainPolicy, RetentionRules};
 use trust_hir::types::TypeRegistry;
+use trust_hir::TypeId;
 
 /// Snapshot of runtime metadata needed by external tooling.
 #[derive(Debug, Clone)]
@@ -29,9 +34,72 @@ pub struct RuntimeMetadata {
     pub(super) task_thread_ids: IndexMap<SmolStr, u32>,
     pub(super) background_thread_id: Option<u32>,
     pub(super) statement_index: IndexMap<u32, Vec<SourceLocation>>,
+    pub(super) build_info: Option<BuildInfo>,
+    pub(super) globals: IndexMap<SmolStr, RetainPolicy>,
+    pub(super) global_types: IndexMap<SmolStr, TypeId>,
+    pub(super) retention: RetentionRules,
+    pub(super) subrange: SubrangePolicy,
 }
 
 impl RuntimeMetadata {
+    /// List which variables a `mode` restart keeps and which it re-initializes.
+    #[must_use]
+    pub fn restart_preview(&self, mode: RestartMode) -> Vec<RestartPreviewEntry> {
+        super::restart::restart_preview(
+            self.retention,
+            mode,
+            self.globals.iter().map(|(name, policy)| (name, *policy)),
+            self.programs.values(),
+            &self.registry,
+        )
+    }
+
+    /// Retention rules the restart preview is computed with.
+    #[must_use]
+    pub fn retention_rules(&self) -> RetentionRules {
+        self.retention
+    }
+
+    /// Build provenance of the loaded program, if it was recorded.
+    #[must_use]
+    pub fn build_info(&self) -> Option<&BuildInfo> {
+        self.build_info.as_ref()
+    }
+
+    /// Applies the subrange policy to a value written from outside the program,
+    /// to `program.name` or, without a program, to the global `name`.
+    ///
+    /// Under [`SubrangePolicy::Fault`] an out-of-range write is rejected with
+    /// `SubrangeViolation` rather than faulting the resource.
+    pub fn check_external_write(
+        &self,
+        program: Option<&str>,
+        name: &str,
+        value: Value,
+    ) -> Result<Value, RuntimeError> {
+        let type_id = match program {
+            Some(program) => self
+                .programs
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(program))
+                .and_then(|(_, def)| {
+                    def.vars
+                        .iter()
+                        .find(|var| var.name.eq_ignore_ascii_case(name))
+                })
+                .map(|var| var.type_id),
+            None => self
+                .global_types
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, type_id)| *type_id),
+        };
+        match type_id.and_then(|type_id| subrange_of_type(&self.registry, type_id)) {
+            Some((lower, upper)) => self.subrange.apply(value, lower, upper),
+            None => Ok(value),
+        }
+    }
+
     /// Access the type registry snapshot.
     #[must_use]
     pub fn registry(&self) -> &TypeRegistry {
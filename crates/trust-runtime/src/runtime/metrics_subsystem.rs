@@ -56,6 +56,14 @@ impl MetricsSubsystem {
         }
     }
 
+    pub(super) fn record_jitter(&self, name: &SmolStr, jitter: StdDuration) {
+        if let Some(metrics) = self.sink.as_ref() {
+            if let Ok(mut guard) = metrics.lock() {
+                guard.record_jitter(name, jitter);
+            }
+        }
+    }
+
     pub(super) fn record_fault(&self) {
         if let Some(metrics) = self.sink.as_ref() {
             if let Ok(mut guard) = metrics.lock() {
@@ -6,15 +6,19 @@ mod bytecode;
 mod core;
 mod cycle;
 mod faults;
+mod io_map;
 mod io_subsystem;
+mod jit_subsystem;
 mod mesh;
 mod metadata;
 mod metrics_subsystem;
+mod redundancy;
 mod restart;
 mod retain_store;
+mod shutdown;
 pub(crate) mod types;
 mod watchdog_subsystem;
 
 pub use core::Runtime;
 pub use metadata::RuntimeMetadata;
-pub use types::{RestartMode, RetainPolicy, RetainSnapshot};
+pub use types::{RestartMode, RestartPreviewEntry, RetainPolicy, RetainSnapshot, RetentionRules};
@@ -0,0 +1,70 @@
+//! Runtime hot-standby helpers (standby mode and state sync).
+
+#![allow(missing_docs)]
+
+use smol_str::SmolStr;
+
+use crate::io::IoTarget;
+use crate::memory::IoArea;
+use crate::RetainSnapshot;
+
+use super::core::Runtime;
+use super::restart::value_is_retainable;
+
+impl Runtime {
+    /// Whether the runtime is the standby of a redundant pair.
+    #[must_use]
+    pub fn is_standby(&self) -> bool {
+        self.standby
+    }
+
+    /// Switch between standby and active. A standby reads inputs but runs no
+    /// tasks and leaves the outputs to its partner.
+    pub fn set_standby(&mut self, standby: bool) {
+        if self.standby && !standby {
+            // Tasks were skipped on purpose; do not count the gap as overruns.
+            let now = self.current_time;
+            for state in self.task_state.values_mut() {
+                state.last_release = now;
+            }
+        }
+        self.standby = standby;
+    }
+
+    /// Capture the state an active runtime hands to its standby: RETAIN globals,
+    /// globals bound to outputs, and the extra names in `sync`.
+    #[must_use]
+    pub fn redundancy_snapshot(&self, sync: &[SmolStr]) -> RetainSnapshot {
+        let mut snapshot = self.retain_snapshot();
+        let outputs = self
+            .io
+            .interface()
+            .bindings()
+            .iter()
+            .filter(|binding| matches!(binding.address.area, IoArea::Output | IoArea::Memory))
+            .filter_map(|binding| match &binding.target {
+                IoTarget::Name(name) => Some(name.clone()),
+                IoTarget::Reference(_) => None,
+            });
+        for name in outputs.chain(sync.iter().cloned()) {
+            if snapshot.values.contains_key(&name) {
+                continue;
+            }
+            if let Some(value) = self.storage.get_global(name.as_str()) {
+                if value_is_retainable(value) {
+                    snapshot.values.insert(name, value.clone());
+                }
+            }
+        }
+        snapshot
+    }
+
+    /// Apply state received from the active partner (skips unknown names).
+    pub fn apply_redundancy_snapshot(&mut self, snapshot: &RetainSnapshot) {
+        for (name, value) in &snapshot.values {
+            if self.storage.get_global(name.as_str()).is_some() && value_is_retainable(value) {
+                self.storage.set_global(name.clone(), value.clone());
+            }
+        }
+    }
+}
@@ -4,54 +4,56 @@
 
 use indexmap::IndexMap;
 use smol_str::SmolStr;
+use trust_hir::types::TypeRegistry;
+use trust_hir::Type;
 
 use crate::error;
-use crate::task::TaskState;
+use crate::task::{ProgramDef, TaskState};
 use crate::value::{Duration, Value};
 
 use super::core::Runtime;
-use super::types::{GlobalInitValue, RestartMode, RetainPolicy, RetainSnapshot};
+use super::types::{
+    GlobalInitValue, RestartMode, RestartPreviewEntry, RetainPolicy, RetainSnapshot, RetentionRules,
+};
 
 impl Runtime {
-    /// Restart the runtime in the given mode (cold or warm).
+    /// Restart the runtime in the given mode (cold or warm). Which variables keep their
+    /// values follows the runtime's [`RetentionRules`].
     pub fn restart(&mut self, mode: RestartMode) -> Result<(), error::RuntimeError> {
         let globals = self.globals.clone();
+        let rules = self.retention;
         let mut retained = IndexMap::new();
         let mut retained_program_vars = Vec::new();
-        if matches!(mode, RestartMode::Warm) {
-            for (name, meta) in &globals {
-                if retain_on_warm(meta.retain) {
-                    if let Some(value) = self.storage.get_global(name.as_ref()) {
-                        retained.insert(name.clone(), value.clone());
-                    }
+        for (name, meta) in &globals {
+            if rules.keeps(meta.retain, mode) {
+                if let Some(value) = self.storage.get_global(name.as_ref()) {
+                    retained.insert(name.clone(), value.clone());
                 }
             }
-            for program in self.programs.values() {
-                let Some(Value::Instance(id)) = self.storage.get_global(program.name.as_ref())
-                else {
+        }
+        for program in self.programs.values() {
+            let Some(Value::Instance(id)) = self.storage.get_global(program.name.as_ref()) else {
+                continue;
+            };
+            for var in &program.vars {
+                if !rules.keeps(var.retain, mode) {
+                    continue;
+                }
+                let Some(value) = self.storage.get_instance_var(*id, var.name.as_ref()) else {
                     continue;
                 };
-                for var in &program.vars {
-                    if !retain_on_warm(var.retain) {
-                        continue;
-                    }
-                    let Some(value) = self.storage.get_instance_var(*id, var.name.as_ref()) else {
-                        continue;
-                    };
-                    if value_is_retainable(value) {
-                        retained_program_vars.push((
-                            program.name.clone(),
-                            var.name.clone(),
-                            value.clone(),
-                        ));
-                    }
+                if value_is_retainable(value) {
+                    retained_program_vars.push((
+                        program.name.clone(),
+                        var.name.clone(),
+                        value.clone(),
+                    ));
                 }
             }
         }
 
         for (name, meta) in globals {
-            let keep = matches!(mode, RestartMode::Warm) && retain_on_warm(meta.retain);
-            if keep {
+            if rules.keeps(meta.retain, mode) {
                 if let Some(value) = retained.get(&name) {
                     self.storage.set_global(name.clone(), value.clone());
                     continue;
@@ -131,15 +133,39 @@ impl Runtime {
         }
         self.faults.clear();
         self.cycle_counter = 0;
+        self.workers.reset();
         Ok(())
     }
 
+    /// Set which retention classes survive warm and cold restarts.
+    pub fn set_retention_rules(&mut self, rules: RetentionRules) {
+        self.retention = rules;
+    }
+
+    /// Current retention rules.
+    #[must_use]
+    pub fn retention_rules(&self) -> RetentionRules {
+        self.retention
+    }
+
+    /// List which variables a `mode` restart keeps and which it re-initializes.
+    #[must_use]
+    pub fn restart_preview(&self, mode: RestartMode) -> Vec<RestartPreviewEntry> {
+        restart_preview(
+            self.retention,
+            mode,
+            self.globals.iter().map(|(name, meta)| (name, meta.retain)),
+            self.programs.values(),
+            &self.registry,
+        )
+    }
+
     /// Capture retained global values that can be preserved across reloads.
     #[must_use]
     pub fn retain_snapshot(&self) -> RetainSnapshot {
         let mut snapshot = RetainSnapshot::default();
         for (name, meta) in &self.globals {
-            if !retain_on_warm(meta.retain) {
+            if !self.retention.keeps(meta.retain, RestartMode::Warm) {
                 continue;
             }
             let Some(value) = self.storage.get_global(name.as_ref()) else {
@@ -158,18 +184,14 @@ impl Runtime {
             let Some(meta) = self.globals.get(name) else {
                 continue;
             };
-            if retain_on_warm(meta.retain) && value_is_retainable(value) {
+            if self.retention.keeps(meta.retain, RestartMode::Warm) && value_is_retainable(value) {
                 self.storage.set_global(name.clone(), value.clone());
             }
         }
     }
 }
 
-fn retain_on_warm(policy: RetainPolicy) -> bool {
-    matches!(policy, RetainPolicy::Retain | RetainPolicy::Persistent)
-}
-
-fn value_is_retainable(value: &Value) -> bool {
+pub(super) fn value_is_retainable(value: &Value) -> bool {
     match value {
         Value::Array(array) => array.elements.iter().all(value_is_retainable),
         Value::Struct(value) => value.fields.values().all(value_is_retainable),
@@ -177,3 +199,41 @@ fn value_is_retainable(value: &Value) -> bool {
         _ => true,
     }
 }
+
+/// Classify globals and program variables for a `mode` restart. Program variables holding
+/// function block or class instances are always re-initialized.
+pub(super) fn restart_preview<'a>(
+    rules: RetentionRules,
+    mode: RestartMode,
+    globals: impl Iterator<Item = (&'a SmolStr, RetainPolicy)>,
+    programs: impl Iterator<Item = &'a ProgramDef>,
+    registry: &TypeRegistry,
+) -> Vec<RestartPreviewEntry> {
+    let mut entries = globals
+        .map(|(name, declared)| RestartPreviewEntry {
+            name: name.clone(),
+            declared,
+            class: rules.class_of(declared),
+            kept: rules.keeps(declared, mode),
+        })
+        .collect::<Vec<_>>();
+    for program in programs {
+        for var in program
+            .vars
+            .iter()
+            .filter(|var| !var.external && !var.constant)
+        {
+            let instance = matches!(
+                registry.get(var.type_id),
+                Some(Type::FunctionBlock { .. } | Type::Class { .. })
+            );
+            entries.push(RestartPreviewEntry {
+                name: SmolStr::new(format!("{}.{}", program.name, var.name)),
+                declared: var.retain,
+                class: rules.class_of(var.retain),
+                kept: !instance && rules.keeps(var.retain, mode),
+            });
+        }
+    }
+    entries
+}
@@ -0,0 +1,111 @@
+//! Shutdown hook sequencing (bounded exit cycles before stop/restart).
+
+#![allow(missing_docs)]
+
+use smol_str::SmolStr;
+
+use crate::error::RuntimeError;
+use crate::value::Value;
+use crate::watchdog::{ShutdownHook, ShutdownOutcome};
+
+use super::core::Runtime;
+
+impl Runtime {
+    /// Designate the program that runs while the resource shuts down. The program
+    /// must exist; it is then no longer run as a background program.
+    pub fn set_shutdown_hook(&mut self, hook: Option<ShutdownHook>) -> Result<(), RuntimeError> {
+        let Some(mut hook) = hook else {
+            self.shutdown_hook = None;
+            return Ok(());
+        };
+        let program = self
+            .program_cache
+            .keys()
+            .find(|name| name.eq_ignore_ascii_case(hook.program.as_str()))
+            .cloned()
+            .ok_or_else(|| RuntimeError::UndefinedProgram(hook.program.clone()))?;
+        if self
+            .tasks
+            .iter()
+            .any(|task| task.programs.iter().any(|name| name == &program))
+        {
+            return Err(RuntimeError::InvalidConfig(
+                format!("shutdown program '{program}' must not be assigned to a task").into(),
+            ));
+        }
+        if let Some(done) = hook.done.as_ref() {
+            if self.resolve_variable_path(done.as_str()).is_none() {
+                return Err(RuntimeError::UndefinedVariable(done.clone()));
+            }
+        }
+        hook.program = program;
+        self.shutdown_hook = Some(hook);
+        Ok(())
+    }
+
+    /// Configured shutdown hook, if any.
+    #[must_use]
+    pub fn shutdown_hook(&self) -> Option<&ShutdownHook> {
+        self.shutdown_hook.as_ref()
+    }
+
+    /// Whether the shutdown sequence is running.
+    #[must_use]
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down
+    }
+
+    /// Switch to shutdown cycles: from now on a cycle runs only the shutdown program.
+    /// Returns `false` when no hook is configured.
+    pub fn begin_shutdown(&mut self) -> bool {
+        self.shutting_down = self.shutdown_hook.is_some();
+        self.shutting_down
+    }
+
+    /// Whether the hook's `done` variable is TRUE.
+    #[must_use]
+    pub fn shutdown_done(&self) -> bool {
+        let Some(done) = self
+            .shutdown_hook
+            .as_ref()
+            .and_then(|hook| hook.done.as_ref())
+        else {
+            return false;
+        };
+        self.resolve_variable_path(done.as_str())
+            .and_then(|reference| self.storage.read_by_ref(reference))
+            .is_some_and(|value| matches!(value, Value::Bool(true)))
+    }
+
+    /// Leave shutdown mode and report how the sequence ended.
+    pub fn finish_shutdown(&mut self, cycles: u32, outcome: ShutdownOutcome) {
+        self.shutting_down = false;
+        let Some(program) = self.shutdown_hook.as_ref().map(|hook| hook.program.clone()) else {
+            return;
+        };
+        if let Some(debug) = &self.debug {
+            debug.push_runtime_event(crate::debug::RuntimeEvent::Shutdown {
+                program,
+                cycles,
+                outcome,
+                time: self.current_time,
+            });
+        }
+    }
+
+    pub(super) fn is_shutdown_program(&self, name: &SmolStr) -> bool {
+        self.shutdown_hook
+            .as_ref()
+            .is_some_and(|hook| &hook.program == name)
+    }
+
+    pub(super) fn execute_shutdown_program(&mut self) -> Result<(), RuntimeError> {
+        let Some(program) = self.shutdown_hook.as_ref().map(|hook| hook.program.clone()) else {
+            return Ok(());
+        };
+        if let Some(debug) = &self.debug {
+            debug.set_current_thread(self.background_thread_id);
+        }
+        self.execute_program_by_name(&program)
+    }
+}
@@ -5,6 +5,8 @@
 use indexmap::IndexMap;
 use smol_str::SmolStr;
 
+use crate::eval::vm::CompiledBlock;
+use crate::task::ProgramDef;
 use crate::value::{Duration, Value};
 
 #[derive(Debug, Clone)]
@@ -13,6 +15,14 @@ pub(super) struct ReadyTask {
     pub due_at: Duration,
 }
 
+/// Registered program shared with its compiled body, so cycles avoid cloning
+/// the statement tree.
+#[derive(Debug, Clone)]
+pub(super) struct CachedProgram {
+    pub def: std::sync::Arc<ProgramDef>,
+    pub body: Option<std::sync::Arc<CompiledBlock>>,
+}
+
 /// Retentive behavior for variables.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RetainPolicy {
@@ -20,22 +30,103 @@ pub enum RetainPolicy {
     Retain,
     /// Always reinitialized on restart.
     NonRetain,
-    /// No explicit qualifier; treat as non-retentive on warm restart.
+    /// No explicit qualifier; handled as [`RetentionRules::unqualified`].
     #[default]
     Unspecified,
-    /// Persistent across warm restarts.
+    /// Persistent across warm restarts and, by default, cold restarts.
     Persistent,
 }
 
+impl RetainPolicy {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Retain => "retain",
+            Self::NonRetain => "non_retain",
+            Self::Unspecified => "unspecified",
+            Self::Persistent => "persistent",
+        }
+    }
+}
+
 /// Restart mode for a resource/configuration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RestartMode {
-    /// Cold restart: reinitialize all variables.
+    /// Cold restart: reinitialize all variables except PERSISTENT ones.
     Cold,
     /// Warm restart: retain RETAIN/PERSISTENT variables.
     Warm,
 }
 
+impl RestartMode {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cold => "cold",
+            Self::Warm => "warm",
+        }
+    }
+}
+
+/// Which retention classes keep their values on each restart mode.
+///
+/// | class       | warm                 | cold                   |
+/// |-------------|----------------------|------------------------|
+/// | RETAIN      | kept                 | initialized            |
+/// | NON_RETAIN  | initialized          | initialized            |
+/// | PERSISTENT  | kept                 | `persistent_on_cold`   |
+/// | unqualified | as `unqualified`     | as `unqualified`       |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionRules {
+    /// Class of variables declared without a qualifier: `Retain` or `NonRetain`.
+    pub unqualified: RetainPolicy,
+    /// Keep PERSISTENT variables across cold restarts.
+    pub persistent_on_cold: bool,
+}
+
+impl Default for RetentionRules {
+    fn default() -> Self {
+        Self {
+            unqualified: RetainPolicy::NonRetain,
+            persistent_on_cold: true,
+        }
+    }
+}
+
+impl RetentionRules {
+    /// The class a declaration is handled as.
+    #[must_use]
+    pub fn class_of(self, policy: RetainPolicy) -> RetainPolicy {
+        match policy {
+            RetainPolicy::Unspecified => self.unqualified,
+            other => other,
+        }
+    }
+
+    /// Whether a variable of this class keeps its value on a `mode` restart.
+    #[must_use]
+    pub fn keeps(self, policy: RetainPolicy, mode: RestartMode) -> bool {
+        match (self.class_of(policy), mode) {
+            (RetainPolicy::Retain | RetainPolicy::Persistent, RestartMode::Warm) => true,
+            (RetainPolicy::Persistent, RestartMode::Cold) => self.persistent_on_cold,
+            _ => false,
+        }
+    }
+}
+
+/// One variable in a `restart` preview.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestartPreviewEntry {
+    /// Global name or `<program>.<variable>`.
+    pub name: SmolStr,
+    /// Declared retention class.
+    pub declared: RetainPolicy,
+    /// Class it is handled as (unqualified variables resolved).
+    pub class: RetainPolicy,
+    /// Whether the value survives the restart.
+    pub kept: bool,
+}
+
 /// Snapshot of retained global values for hot reload.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct RetainSnapshot {
@@ -2,7 +2,7 @@
 
 #![allow(missing_docs)]
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
 
@@ -194,6 +194,133 @@ impl Default for ManualClock {
     }
 }
 
+/// Wall-clock view of the resource clock, shared by everything that stamps
+/// runtime data: historian samples, HMI values, and uptime.
+///
+/// The first observed resource time is pinned to the current Unix time. After
+/// that, stamps advance with the resource clock, so under a simulation time scale
+/// they run exactly as fast as the timers do.
+#[derive(Debug, Clone)]
+pub struct TimeBase {
+    inner: Arc<TimeBaseInner>,
+}
+
+#[derive(Debug)]
+struct TimeBaseInner {
+    anchor: Mutex<Option<TimeAnchor>>,
+    scale: AtomicU32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TimeAnchor {
+    unix_ms: u128,
+    start: Duration,
+    latest: Duration,
+}
+
+impl TimeBase {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(TimeBaseInner {
+                anchor: Mutex::new(None),
+                scale: AtomicU32::new(1),
+            }),
+        }
+    }
+
+    /// Record the resource time of the current cycle. A clock that runs
+    /// backwards (e.g. a fresh clock after a restart) re-pins the anchor at the
+    /// last stamp handed out, so stamps never go backwards.
+    pub fn observe(&self, now: Duration) {
+        let mut anchor = self.lock();
+        match anchor.as_mut() {
+            Some(current) if now.as_nanos() >= current.latest.as_nanos() => current.latest = now,
+            Some(current) => {
+                *current = TimeAnchor {
+                    unix_ms: current.stamp(current.latest),
+                    start: now,
+                    latest: now,
+                };
+            }
+            None => {
+                *anchor = Some(TimeAnchor {
+                    unix_ms: system_unix_ms(),
+                    start: now,
+                    latest: now,
+                });
+            }
+        }
+    }
+
+    /// Unix milliseconds for resource time `now`; the system time until the
+    /// first cycle has been observed.
+    #[must_use]
+    pub fn unix_ms(&self, now: Duration) -> u128 {
+        self.lock()
+            .map_or_else(system_unix_ms, |anchor| anchor.stamp(now))
+    }
+
+    /// Unix milliseconds of the latest observed cycle.
+    #[must_use]
+    pub fn now_unix_ms(&self) -> u128 {
+        self.lock()
+            .map_or_else(system_unix_ms, |anchor| anchor.stamp(anchor.latest))
+    }
+
+    /// Resource time elapsed since the first observed cycle.
+    #[must_use]
+    pub fn uptime(&self) -> Duration {
+        self.lock().map_or(Duration::ZERO, |anchor| {
+            Duration::from_nanos(
+                anchor
+                    .latest
+                    .as_nanos()
+                    .saturating_sub(anchor.start.as_nanos()),
+            )
+        })
+    }
+
+    /// How many times faster than the wall clock the resource clock runs.
+    #[must_use]
+    pub fn time_scale(&self) -> u32 {
+        self.inner.scale.load(Ordering::Relaxed)
+    }
+
+    pub fn set_time_scale(&self, scale: u32) {
+        self.inner.scale.store(scale.max(1), Ordering::Relaxed);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<TimeAnchor>> {
+        self.inner.anchor.lock().expect("time base poisoned")
+    }
+}
+
+impl TimeAnchor {
+    fn stamp(&self, now: Duration) -> u128 {
+        let offset = now.as_millis().saturating_sub(self.start.as_millis());
+        let magnitude = u128::from(offset.unsigned_abs());
+        if offset >= 0 {
+            self.unix_ms.saturating_add(magnitude)
+        } else {
+            self.unix_ms.saturating_sub(magnitude)
+        }
+    }
+}
+
+impl Default for TimeBase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn system_unix_ms() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
 impl Clock for ManualClock {
     fn now(&self) -> Duration {
         self.current_time()
@@ -230,6 +357,12 @@ pub enum ResourceState {
 pub enum ResourceCommand {
     Pause,
     Resume,
+    /// Run `cycles` full scan cycles on a paused resource, then pause again.
+    /// Replies with the cycle counter after the last of them.
+    StepCycles {
+        cycles: u32,
+        respond_to: std::sync::mpsc::Sender<u64>,
+    },
     UpdateWatchdog(crate::watchdog::WatchdogPolicy),
     UpdateFaultPolicy(crate::watchdog::FaultPolicy),
     UpdateRetainSaveInterval(Option<Duration>),
@@ -245,9 +378,24 @@ pub enum ResourceCommand {
     MeshApply {
         updates: IndexMap<SmolStr, Value>,
     },
+    SetStandby(bool),
+    RedundancySnapshot {
+        sync: Vec<SmolStr>,
+        respond_to: std::sync::mpsc::Sender<crate::RetainSnapshot>,
+    },
+    RedundancyApply {
+        snapshot: crate::RetainSnapshot,
+    },
     Snapshot {
         respond_to: std::sync::mpsc::Sender<crate::debug::DebugSnapshot>,
     },
+    /// Change the parameters of a simulation channel model.
+    UpdateSimulationModel {
+        name: SmolStr,
+        update: crate::simulation::SimulationModelUpdate,
+        respond_to:
+            std::sync::mpsc::Sender<Result<crate::simulation::SimulationModelStatus, RuntimeError>>,
+    },
 }
 
 /// Gate that blocks resource execution until opened.
@@ -299,6 +447,7 @@ pub struct ResourceRunner<C: Clock + Clone> {
     start_gate: Option<Arc<StartGate>>,
     command_rx: Option<std::sync::mpsc::Receiver<ResourceCommand>>,
     simulation: Option<crate::simulation::SimulationController>,
+    realtime: Option<crate::config::RealtimeConfig>,
 }
 
 impl<C: Clock + Clone> ResourceRunner<C> {
@@ -313,6 +462,7 @@ impl<C: Clock + Clone> ResourceRunner<C> {
             start_gate: None,
             command_rx: None,
             simulation: None,
+            realtime: None,
         }
     }
 
@@ -334,6 +484,7 @@ impl<C: Clock + Clone> ResourceRunner<C> {
     #[must_use]
     pub fn with_time_scale(mut self, scale: u32) -> Self {
         self.time_scale = scale.max(1);
+        self.runtime.time_base().set_time_scale(self.time_scale);
         self
     }
 
@@ -344,6 +495,13 @@ impl<C: Clock + Clone> ResourceRunner<C> {
         self
     }
 
+    /// Apply real-time scheduling settings to the resource thread when spawned.
+    #[must_use]
+    pub fn with_realtime(mut self, realtime: crate::config::RealtimeConfig) -> Self {
+        self.realtime = Some(realtime);
+        self
+    }
+
     /// Access the underlying runtime.
     #[must_use]
     pub fn runtime(&self) -> &Runtime {
@@ -355,16 +513,17 @@ impl<C: Clock + Clone> ResourceRunner<C> {
         &mut self.runtime
     }
 
-    /// Execute one cycle using the current clock time.
+    /// Execute one cycle using the current clock time, scaled like the
+    /// spawned loop so manual ticks see the same time base.
     pub fn tick(&mut self) -> Result<(), RuntimeError> {
-        let now = self.clock.now();
+        let now = scaled_time(self.clock.now(), self.time_scale);
         self.runtime.set_current_time(now);
         self.runtime.execute_cycle()
     }
 
     /// Execute one cycle with shared global synchronization.
     pub fn tick_with_shared(&mut self, shared: &SharedGlobals) -> Result<(), RuntimeError> {
-        let now = self.clock.now();
+        let now = scaled_time(self.clock.now(), self.time_scale);
         self.runtime.set_current_time(now);
         shared.with_lock(|globals| {
             shared.sync_into_locked(globals, &mut self.runtime)?;
@@ -374,6 +533,58 @@ impl<C: Clock + Clone> ResourceRunner<C> {
         })
     }
 
+    /// Run the runtime's shutdown hook before a stop or restart: up to `cycles` cycles
+    /// that execute only the shutdown program, ended early by its `done` variable and
+    /// cut off at its timeout. Returns `None` when there is nothing to run (no hook, a
+    /// faulted runtime, or a standby that does not drive outputs).
+    pub fn run_shutdown_hook(
+        &mut self,
+        shared: Option<&SharedGlobals>,
+    ) -> Option<crate::watchdog::ShutdownOutcome> {
+        use crate::watchdog::ShutdownOutcome;
+
+        let hook = self.runtime.shutdown_hook()?.clone();
+        if self.runtime.faulted() || self.runtime.is_standby() || !self.runtime.begin_shutdown() {
+            return None;
+        }
+        let timeout =
+            std::time::Duration::from_nanos(u64::try_from(hook.timeout.as_nanos()).unwrap_or(0));
+        let started = std::time::Instant::now();
+        let previous_deadline = self.runtime.execution_deadline();
+        // A shutdown program stuck in a loop is stopped by the evaluator.
+        self.runtime.set_execution_deadline(Some(started + timeout));
+        let mut cycles = 0;
+        let outcome = loop {
+            if cycles >= hook.cycles {
+                break ShutdownOutcome::CyclesElapsed;
+            }
+            if started.elapsed() >= timeout {
+                break ShutdownOutcome::TimedOut;
+            }
+            let now_raw = self.clock.now();
+            let result = match shared {
+                Some(shared) => self.tick_with_shared(shared),
+                None => self.tick(),
+            };
+            cycles += 1;
+            match result {
+                Ok(()) if self.runtime.shutdown_done() => break ShutdownOutcome::Done,
+                Ok(()) => {}
+                Err(RuntimeError::ExecutionTimeout) => break ShutdownOutcome::TimedOut,
+                Err(_) => break ShutdownOutcome::Faulted,
+            }
+            if cycles < hook.cycles && self.cycle_interval.as_nanos() > 0 {
+                let interval = scaled_sleep_interval(self.cycle_interval, self.time_scale);
+                self.clock.sleep_until(Duration::from_nanos(
+                    now_raw.as_nanos().saturating_add(interval.as_nanos()),
+                ));
+            }
+        };
+        self.runtime.set_execution_deadline(previous_deadline);
+        self.runtime.finish_shutdown(cycles, outcome);
+        Some(outcome)
+    }
+
     /// Spawn the runner in a dedicated OS thread.
     pub fn spawn(self, name: impl Into<String>) -> Result<ResourceHandle<C>, RuntimeError> {
         let stop = Arc::new(AtomicBool::new(false));
@@ -392,18 +603,26 @@ impl<C: Clock + Clone> ResourceRunner<C> {
         let builder = thread::Builder::new().name(name.into());
         let join = builder
             .spawn(move || {
-                let _ = id_tx.send(thread::current().id());
+                let realtime_warnings = runner
+                    .realtime
+                    .as_ref()
+                    .map(crate::realtime::apply_current_thread)
+                    .unwrap_or_default();
+                let _ = id_tx.send((thread::current().id(), realtime_warnings));
                 run_resource_loop(runner, stop_thread, state_thread, last_error_thread);
             })
             .map_err(|err| RuntimeError::ThreadSpawn(err.to_string().into()))?;
 
-        let thread_id = id_rx.recv().unwrap_or_else(|_| join.thread().id());
+        let (thread_id, realtime_warnings) = id_rx
+            .recv()
+            .unwrap_or_else(|_| (join.thread().id(), Vec::new()));
 
         Ok(ResourceHandle {
             stop,
             state,
             last_error,
             thread_id,
+            realtime_warnings,
             clock,
             join: Some(join),
             cmd_tx: cmd_tx.clone(),
@@ -433,7 +652,12 @@ impl<C: Clock + Clone> ResourceRunner<C> {
         let builder = thread::Builder::new().name(name.into());
         let join = builder
             .spawn(move || {
-                let _ = id_tx.send(thread::current().id());
+                let realtime_warnings = runner
+                    .realtime
+                    .as_ref()
+                    .map(crate::realtime::apply_current_thread)
+                    .unwrap_or_default();
+                let _ = id_tx.send((thread::current().id(), realtime_warnings));
                 run_resource_loop_with_shared(
                     runner,
                     stop_thread,
@@ -444,13 +668,16 @@ impl<C: Clock + Clone> ResourceRunner<C> {
             })
             .map_err(|err| RuntimeError::ThreadSpawn(err.to_string().into()))?;
 
-        let thread_id = id_rx.recv().unwrap_or_else(|_| join.thread().id());
+        let (thread_id, realtime_warnings) = id_rx
+            .recv()
+            .unwrap_or_else(|_| (join.thread().id(), Vec::new()));
 
         Ok(ResourceHandle {
             stop,
             state,
             last_error,
             thread_id,
+            realtime_warnings,
             clock,
             join: Some(join),
             cmd_tx: cmd_tx.clone(),
@@ -478,6 +705,21 @@ fn scaled_sleep_interval(interval: Duration, scale: u32) -> Duration {
     Duration::from_nanos(scaled)
 }
 
+/// Start of the next cycle on the absolute grid `previous + interval`, so sleep
+/// latency does not accumulate. When the cycle itself started a whole slot late
+/// the grid is re-anchored at that start instead of firing catch-up cycles.
+fn next_cycle_deadline(
+    previous: Option<Duration>,
+    cycle_start: Duration,
+    interval: Duration,
+) -> Duration {
+    let interval = interval.as_nanos();
+    match previous.map(|previous| previous.as_nanos().saturating_add(interval)) {
+        Some(next) if next > cycle_start.as_nanos() => Duration::from_nanos(next),
+        _ => Duration::from_nanos(cycle_start.as_nanos().saturating_add(interval)),
+    }
+}
+
 fn run_resource_loop<C: Clock + Clone>(
     mut runner: ResourceRunner<C>,
     stop: Arc<AtomicBool>,
@@ -485,6 +727,8 @@ fn run_resource_loop<C: Clock + Clone>(
     last_error: Arc<Mutex<Option<RuntimeError>>>,
 ) {
     let mut paused = false;
+    let mut step: Option<CycleStep> = None;
+    let mut next_cycle = None;
     if let Some(gate) = runner.start_gate.as_ref() {
         *state.lock().expect("resource state poisoned") = ResourceState::Ready;
         if !gate.wait_open(&stop) {
@@ -495,6 +739,7 @@ fn run_resource_loop<C: Clock + Clone>(
     *state.lock().expect("resource state poisoned") = ResourceState::Running;
     loop {
         if stop.load(Ordering::SeqCst) {
+            let _ = runner.run_shutdown_hook(None);
             let _ = runner.runtime.save_retain_store();
             *state.lock().expect("resource state poisoned") = ResourceState::Stopped;
             break;
@@ -505,35 +750,61 @@ fn run_resource_loop<C: Clock + Clone>(
                 match command {
                     ResourceCommand::Pause => {
                         paused = true;
+                        let _ = runner.runtime.apply_output_safe_state();
                         *state.lock().expect("resource state poisoned") = ResourceState::Paused;
                     }
                     ResourceCommand::Resume => {
                         paused = false;
+                        finish_cycle_step(&mut step, runner.runtime.cycle_counter());
                         *state.lock().expect("resource state poisoned") = ResourceState::Running;
                     }
+                    ResourceCommand::StepCycles { cycles, respond_to } => queue_cycle_step(
+                        &mut step,
+                        paused,
+                        cycles,
+                        respond_to,
+                        runner.runtime.cycle_counter(),
+                    ),
+                    ResourceCommand::UpdateSimulationModel {
+                        name,
+                        update,
+                        respond_to,
+                    } => {
+                        let result = match runner.simulation.as_mut() {
+                            Some(simulation) => simulation.update_model(&name, &update),
+                            None => Err(simulation_models_inactive()),
+                        };
+                        let _ = respond_to.send(result);
+                    }
                     other => apply_resource_command(&mut runner.runtime, other),
                 }
             }
         }
 
-        if let Some(signal) = runner.restart_signal.as_ref() {
-            if let Ok(mut guard) = signal.lock() {
-                if let Some(mode) = guard.take() {
-                    if let Err(err) = runner.runtime.restart(mode) {
-                        *last_error.lock().expect("resource error poisoned") = Some(err);
-                        *state.lock().expect("resource state poisoned") = ResourceState::Faulted;
-                        break;
-                    }
-                    if let Err(err) = runner.runtime.load_retain_store() {
-                        *last_error.lock().expect("resource error poisoned") = Some(err);
-                        *state.lock().expect("resource state poisoned") = ResourceState::Faulted;
-                        break;
-                    }
+        let restart = runner
+            .restart_signal
+            .as_ref()
+            .and_then(|signal| signal.lock().ok().and_then(|mut guard| guard.take()));
+        if let Some(mode) = restart {
+            let _ = runner.run_shutdown_hook(None);
+            if let Err(err) = runner.runtime.restart(mode) {
+                *last_error.lock().expect("resource error poisoned") = Some(err);
+                *state.lock().expect("resource state poisoned") = ResourceState::Faulted;
+                break;
+            }
+            // A cold restart keeps only what the retention rules allow, so the stored
+            // RETAIN values are restored on warm restarts only.
+            if mode == crate::RestartMode::Warm {
+                if let Err(err) = runner.runtime.load_retain_store() {
+                    *last_error.lock().expect("resource error poisoned") = Some(err);
+                    *state.lock().expect("resource state poisoned") = ResourceState::Faulted;
+                    break;
                 }
             }
         }
 
-        if paused {
+        if paused && step.is_none() {
+            next_cycle = None;
             let now_raw = runner.clock.now();
             let interval = runner.cycle_interval.as_nanos();
             if interval <= 0 {
@@ -599,6 +870,7 @@ fn run_resource_loop<C: Clock + Clone>(
             let elapsed = i64::try_from(wall_start.elapsed().as_nanos()).unwrap_or(i64::MAX);
             if elapsed > watchdog.timeout.as_nanos() {
                 if matches!(watchdog.action, crate::watchdog::WatchdogAction::Restart) {
+                    let _ = runner.runtime.apply_output_safe_state();
                     if let Err(restart_err) = runner.runtime.restart(crate::RestartMode::Warm) {
                         *last_error.lock().expect("resource error poisoned") = Some(restart_err);
                         *state.lock().expect("resource state poisoned") = ResourceState::Faulted;
@@ -613,14 +885,22 @@ fn run_resource_loop<C: Clock + Clone>(
             }
         }
 
+        if let Some(active) = step.as_mut() {
+            active.remaining -= 1;
+            if active.remaining == 0 {
+                let _ = runner.runtime.apply_output_safe_state();
+                finish_cycle_step(&mut step, runner.runtime.cycle_counter());
+            }
+        }
+
         let interval = runner.cycle_interval.as_nanos();
         if interval <= 0 {
             thread::yield_now();
             continue;
         }
         let sleep_interval = scaled_sleep_interval(runner.cycle_interval, runner.time_scale);
-        let deadline =
-            Duration::from_nanos(now_raw.as_nanos().saturating_add(sleep_interval.as_nanos()));
+        let deadline = next_cycle_deadline(next_cycle, now_raw, sleep_interval);
+        next_cycle = Some(deadline);
         runner.clock.sleep_until(deadline);
     }
 }
@@ -633,6 +913,8 @@ fn run_resource_loop_with_shared<C: Clock + Clone>(
     shared: SharedGlobals,
 ) {
     let mut paused = false;
+    let mut step: Option<CycleStep> = None;
+    let mut next_cycle = None;
     if let Some(gate) = runner.start_gate.as_ref() {
         *state.lock().expect("resource state poisoned") = ResourceState::Ready;
         if !gate.wait_open(&stop) {
@@ -643,6 +925,7 @@ fn run_resource_loop_with_shared<C: Clock + Clone>(
     *state.lock().expect("resource state poisoned") = ResourceState::Running;
     loop {
         if stop.load(Ordering::SeqCst) {
+            let _ = runner.run_shutdown_hook(Some(&shared));
             let _ = runner.runtime.save_retain_store();
             *state.lock().expect("resource state poisoned") = ResourceState::Stopped;
             break;
@@ -653,35 +936,61 @@ fn run_resource_loop_with_shared<C: Clock + Clone>(
                 match command {
                     ResourceCommand::Pause => {
                         paused = true;
+                        let _ = runner.runtime.apply_output_safe_state();
                         *state.lock().expect("resource state poisoned") = ResourceState::Paused;
                     }
                     ResourceCommand::Resume => {
                         paused = false;
+                        finish_cycle_step(&mut step, runner.runtime.cycle_counter());
                         *state.lock().expect("resource state poisoned") = ResourceState::Running;
                     }
+                    ResourceCommand::StepCycles { cycles, respond_to } => queue_cycle_step(
+                        &mut step,
+                        paused,
+                        cycles,
+                        respond_to,
+                        runner.runtime.cycle_counter(),
+                    ),
+                    ResourceCommand::UpdateSimulationModel {
+                        name,
+                        update,
+                        respond_to,
+                    } => {
+                        let result = match runner.simulation.as_mut() {
+                            Some(simulation) => simulation.update_model(&name, &update),
+                            None => Err(simulation_models_inactive()),
+                        };
+                        let _ = respond_to.send(result);
+                    }
                     other => apply_resource_command(&mut runner.runtime, other),
                 }
             }
         }
 
-        if let Some(signal) = runner.restart_signal.as_ref() {
-            if let Ok(mut guard) = signal.lock() {
-                if let Some(mode) = guard.take() {
-                    if let Err(err) = runner.runtime.restart(mode) {
-                        *last_error.lock().expect("resource error poisoned") = Some(err);
-                        *state.lock().expect("resource state poisoned") = ResourceState::Faulted;
-                        break;
-                    }
-                    if let Err(err) = runner.runtime.load_retain_store() {
-                        *last_error.lock().expect("resource error poisoned") = Some(err);
-                        *state.lock().expect("resource state poisoned") = ResourceState::Faulted;
-                        break;
-                    }
+        let restart = runner
+            .restart_signal
+            .as_ref()
+            .and_then(|signal| signal.lock().ok().and_then(|mut guard| guard.take()));
+        if let Some(mode) = restart {
+            let _ = runner.run_shutdown_hook(Some(&shared));
+            if let Err(err) = runner.runtime.restart(mode) {
+                *last_error.lock().expect("resource error poisoned") = Some(err);
+                *state.lock().expect("resource state poisoned") = ResourceState::Faulted;
+                break;
+            }
+            // A cold restart keeps only what the retention rules allow, so the stored
+            // RETAIN values are restored on warm restarts only.
+            if mode == crate::RestartMode::Warm {
+                if let Err(err) = runner.runtime.load_retain_store() {
+                    *last_error.lock().expect("resource error poisoned") = Some(err);
+                    *state.lock().expect("resource state poisoned") = ResourceState::Faulted;
+                    break;
                 }
             }
         }
 
-        if paused {
+        if paused && step.is_none() {
+            next_cycle = None;
             let now_raw = runner.clock.now();
             let interval = runner.cycle_interval.as_nanos();
             if interval <= 0 {
@@ -751,6 +1060,7 @@ fn run_resource_loop_with_shared<C: Clock + Clone>(
             let elapsed = i64::try_from(wall_start.elapsed().as_nanos()).unwrap_or(i64::MAX);
             if elapsed > watchdog.timeout.as_nanos() {
                 if matches!(watchdog.action, crate::watchdog::WatchdogAction::Restart) {
+                    let _ = runner.runtime.apply_output_safe_state();
                     if let Err(restart_err) = runner.runtime.restart(crate::RestartMode::Warm) {
                         *last_error.lock().expect("resource error poisoned") = Some(restart_err);
                         *state.lock().expect("resource state poisoned") = ResourceState::Faulted;
@@ -765,21 +1075,70 @@ fn run_resource_loop_with_shared<C: Clock + Clone>(
             }
         }
 
+        if let Some(active) = step.as_mut() {
+            active.remaining -= 1;
+            if active.remaining == 0 {
+                let _ = runner.runtime.apply_output_safe_state();
+                finish_cycle_step(&mut step, runner.runtime.cycle_counter());
+            }
+        }
+
         let interval = runner.cycle_interval.as_nanos();
         if interval <= 0 {
             thread::yield_now();
             continue;
         }
         let sleep_interval = scaled_sleep_interval(runner.cycle_interval, runner.time_scale);
-        let deadline =
-            Duration::from_nanos(now_raw.as_nanos().saturating_add(sleep_interval.as_nanos()));
+        let deadline = next_cycle_deadline(next_cycle, now_raw, sleep_interval);
+        next_cycle = Some(deadline);
         runner.clock.sleep_until(deadline);
     }
 }
 
+/// Cycles still to run for a [`ResourceCommand::StepCycles`] request.
+struct CycleStep {
+    remaining: u32,
+    respond_to: std::sync::mpsc::Sender<u64>,
+}
+
+/// Steps only run on a paused resource; otherwise the request is answered
+/// right away. A new request adds to one that is still running.
+fn queue_cycle_step(
+    step: &mut Option<CycleStep>,
+    paused: bool,
+    cycles: u32,
+    respond_to: std::sync::mpsc::Sender<u64>,
+    cycle: u64,
+) {
+    if !paused || cycles == 0 {
+        let _ = respond_to.send(cycle);
+        return;
+    }
+    let remaining = match step.take() {
+        Some(previous) => {
+            let _ = previous.respond_to.send(cycle);
+            previous.remaining.saturating_add(cycles)
+        }
+        None => cycles,
+    };
+    *step = Some(CycleStep {
+        remaining,
+        respond_to,
+    });
+}
+
+fn finish_cycle_step(step: &mut Option<CycleStep>, cycle: u64) {
+    if let Some(done) = step.take() {
+        let _ = done.respond_to.send(cycle);
+    }
+}
+
 fn apply_resource_command(runtime: &mut Runtime, command: ResourceCommand) {
     match command {
         ResourceCommand::Pause | ResourceCommand::Resume => {}
+        ResourceCommand::StepCycles { respond_to, .. } => {
+            let _ = respond_to.send(runtime.cycle_counter());
+        }
         ResourceCommand::UpdateWatchdog(policy) => runtime.set_watchdog_policy(policy),
         ResourceCommand::UpdateFaultPolicy(policy) => runtime.set_fault_policy(policy),
         ResourceCommand::UpdateRetainSaveInterval(interval) => {
@@ -799,6 +1158,13 @@ fn apply_resource_command(runtime: &mut Runtime, command: ResourceCommand) {
             let _ = respond_to.send(snapshot);
         }
         ResourceCommand::MeshApply { updates } => runtime.apply_mesh_updates(&updates),
+        ResourceCommand::SetStandby(standby) => runtime.set_standby(standby),
+        ResourceCommand::RedundancySnapshot { sync, respond_to } => {
+            let _ = respond_to.send(runtime.redundancy_snapshot(&sync));
+        }
+        ResourceCommand::RedundancyApply { snapshot } => {
+            runtime.apply_redundancy_snapshot(&snapshot)
+        }
         ResourceCommand::Snapshot { respond_to } => {
             let snapshot = crate::debug::DebugSnapshot {
                 storage: runtime.storage().clone(),
@@ -806,9 +1172,16 @@ fn apply_resource_command(runtime: &mut Runtime, command: ResourceCommand) {
             };
             let _ = respond_to.send(snapshot);
         }
+        ResourceCommand::UpdateSimulationModel { respond_to, .. } => {
+            let _ = respond_to.send(Err(simulation_models_inactive()));
+        }
     }
 }
 
+fn simulation_models_inactive() -> RuntimeError {
+    RuntimeError::ControlError("simulation models are not active".into())
+}
+
 /// Handle to a running resource thread.
 #[derive(Debug)]
 pub struct ResourceHandle<C: Clock + Clone> {
@@ -816,6 +1189,7 @@ pub struct ResourceHandle<C: Clock + Clone> {
     state: Arc<Mutex<ResourceState>>,
     last_error: Arc<Mutex<Option<RuntimeError>>>,
     thread_id: thread::ThreadId,
+    realtime_warnings: Vec<SmolStr>,
     clock: C,
     join: Option<thread::JoinHandle<()>>,
     cmd_tx: std::sync::mpsc::Sender<ResourceCommand>,
@@ -860,6 +1234,12 @@ impl<C: Clock + Clone> ResourceHandle<C> {
         self.thread_id
     }
 
+    /// Real-time settings the resource thread could not apply.
+    #[must_use]
+    pub fn realtime_warnings(&self) -> &[SmolStr] {
+        &self.realtime_warnings
+    }
+
     /// Join the resource thread.
     pub fn join(&mut self) -> thread::Result<()> {
         if let Some(join) = self.join.take() {
@@ -913,6 +1293,34 @@ impl<C: Clock + Clone> ResourceControl<C> {
         Ok(())
     }
 
+    /// Run `cycles` scan cycles on the paused resource. The receiver yields the
+    /// cycle counter once they have completed.
+    pub fn step_cycles(&self, cycles: u32) -> Result<std::sync::mpsc::Receiver<u64>, RuntimeError> {
+        let (respond_to, rx) = std::sync::mpsc::channel();
+        self.send_command(ResourceCommand::StepCycles { cycles, respond_to })?;
+        self.clock.wake();
+        Ok(rx)
+    }
+
+    /// Change a simulation channel model. The receiver yields the updated model.
+    pub fn update_simulation_model(
+        &self,
+        name: SmolStr,
+        update: crate::simulation::SimulationModelUpdate,
+    ) -> Result<
+        std::sync::mpsc::Receiver<Result<crate::simulation::SimulationModelStatus, RuntimeError>>,
+        RuntimeError,
+    > {
+        let (respond_to, rx) = std::sync::mpsc::channel();
+        self.send_command(ResourceCommand::UpdateSimulationModel {
+            name,
+            update,
+            respond_to,
+        })?;
+        self.clock.wake();
+        Ok(rx)
+    }
+
     /// Signal the resource thread to stop.
     pub fn stop(&self) {
         self.stop.store(true, Ordering::SeqCst);
@@ -1011,7 +1419,7 @@ impl SharedGlobals {
 
 #[cfg(test)]
 mod tests {
-    use super::{Clock, ScaledClock, StdClock};
+    use super::{next_cycle_deadline, Clock, ScaledClock, StdClock, TimeBase};
     use crate::value::Duration;
 
     #[test]
@@ -1046,4 +1454,53 @@ mod tests {
         let second = clock.now();
         assert!(second.as_nanos() >= first.as_nanos());
     }
+
+    #[test]
+    fn cycle_deadline_stays_on_absolute_grid() {
+        let interval = Duration::from_millis(10);
+        // Waking 2 ms late does not push the next cycle out to 22 ms.
+        let next = next_cycle_deadline(
+            Some(Duration::from_millis(10)),
+            Duration::from_millis(12),
+            interval,
+        );
+        assert_eq!(next, Duration::from_millis(20));
+        // A cycle that starts a whole interval late re-anchors the grid at its start.
+        let next = next_cycle_deadline(
+            Some(Duration::from_millis(10)),
+            Duration::from_millis(27),
+            interval,
+        );
+        assert_eq!(next, Duration::from_millis(37));
+        // The first cycle has no previous deadline.
+        let next = next_cycle_deadline(None, Duration::from_millis(5), interval);
+        assert_eq!(next, Duration::from_millis(15));
+    }
+
+    #[test]
+    fn time_base_stamps_follow_resource_time() {
+        let time_base = TimeBase::new();
+        time_base.observe(Duration::from_secs(5));
+        let anchor = time_base.now_unix_ms();
+        time_base.observe(Duration::from_millis(5_250));
+        assert_eq!(time_base.now_unix_ms(), anchor + 250);
+        assert_eq!(
+            time_base.unix_ms(Duration::from_millis(4_900)),
+            anchor - 100
+        );
+        assert_eq!(time_base.uptime(), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn time_base_never_steps_backwards() {
+        let time_base = TimeBase::new();
+        time_base.observe(Duration::ZERO);
+        time_base.observe(Duration::from_millis(400));
+        let before = time_base.now_unix_ms();
+        // A restarted clock continues from the last stamp.
+        time_base.observe(Duration::ZERO);
+        assert_eq!(time_base.now_unix_ms(), before);
+        time_base.observe(Duration::from_millis(100));
+        assert_eq!(time_base.now_unix_ms(), before + 100);
+    }
 }
@@ -0,0 +1,391 @@
+//! Self-extracting runtime bundles (`trust-runtime build --self-extracting`).
+//!
+//! An image packs `runtime.toml`, `io.toml`, `program.stbc`, and the project
+//! sources into a payload appended to a copy of the runtime executable. At
+//! start-up the executable checks its own tail for the payload and, when one is
+//! present, extracts it and runs the embedded project on the bundled
+//! interpreter instead of the command line interface. The program is not
+//! compiled ahead of time.
+//!
+//! Layout: `section* payload_len:u64le MAGIC`, where each section is
+//! `tag:u8 len:u64le bytes`.
+
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::bundle_builder::{bundle_source_roots, collect_sources};
+
+const MAGIC: &[u8; 8] = b"TRUSTIM1";
+const TRAILER_LEN: u64 = 16;
+
+const TAG_RUNTIME: u8 = 1;
+const TAG_IO: u8 = 2;
+const TAG_BYTECODE: u8 = 3;
+const TAG_SOURCE: u8 = 4;
+const TAG_FLAGS: u8 = 5;
+
+const FLAG_CONTROL: u8 = 0x01;
+
+/// Project embedded in an executable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfExtractingImage {
+    /// `runtime.toml` contents.
+    pub runtime_toml: String,
+    /// `io.toml` contents, if the project has one.
+    pub io_toml: Option<String>,
+    /// `program.stbc` contents.
+    pub bytecode: Vec<u8>,
+    /// Source files as (`/`-separated path relative to `src/`, text).
+    pub sources: Vec<(String, String)>,
+    /// Start the control server and the network services configured in
+    /// `runtime.toml`. Without it the executable only runs the PLC cycle.
+    pub control: bool,
+}
+
+impl SelfExtractingImage {
+    /// Collect a built project (`program.stbc` must exist) into an image.
+    ///
+    /// Local dependency sources are stored under `src/deps/<n>/`.
+    pub fn from_project(
+        root: &Path,
+        sources_root: Option<&Path>,
+        control: bool,
+    ) -> anyhow::Result<Self> {
+        let runtime_path = root.join("runtime.toml");
+        let runtime_toml = fs::read_to_string(&runtime_path)
+            .with_context(|| format!("failed to read {}", runtime_path.display()))?;
+        crate::config::validate_runtime_toml_text(&runtime_toml)?;
+        let io_path = root.join("io.toml");
+        let io_toml = if io_path.is_file() {
+            Some(fs::read_to_string(&io_path)?)
+        } else {
+            None
+        };
+        let program_path = root.join("program.stbc");
+        let bytecode = fs::read(&program_path)
+            .with_context(|| format!("failed to read {}", program_path.display()))?;
+
+        let mut sources = Vec::new();
+        for (index, source_root) in bundle_source_roots(root, sources_root)?
+            .into_iter()
+            .enumerate()
+        {
+            let base = source_root
+                .canonicalize()
+                .unwrap_or_else(|_| source_root.clone());
+            let (files, paths) = collect_sources(std::slice::from_ref(&source_root))?;
+            for (file, path) in files.into_iter().zip(paths) {
+                let relative = path.strip_prefix(&base).unwrap_or(&path);
+                let mut name = relative
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                if index > 0 {
+                    name = format!("deps/{index}/{name}");
+                }
+                sources.push((name, file.text));
+            }
+        }
+        if sources.is_empty() {
+            anyhow::bail!("no source files found for {}", root.display());
+        }
+        Ok(Self {
+            runtime_toml,
+            io_toml,
+            bytecode,
+            sources,
+            control,
+        })
+    }
+
+    /// Serialize the image payload including its trailer.
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        push_section(&mut out, TAG_RUNTIME, self.runtime_toml.as_bytes());
+        if let Some(io) = &self.io_toml {
+            push_section(&mut out, TAG_IO, io.as_bytes());
+        }
+        push_section(&mut out, TAG_BYTECODE, &self.bytecode);
+        for (path, text) in &self.sources {
+            let mut entry = Vec::with_capacity(4 + path.len() + text.len());
+            entry.extend_from_slice(&(path.len() as u32).to_le_bytes());
+            entry.extend_from_slice(path.as_bytes());
+            entry.extend_from_slice(text.as_bytes());
+            push_section(&mut out, TAG_SOURCE, &entry);
+        }
+        let flags = if self.control { FLAG_CONTROL } else { 0 };
+        push_section(&mut out, TAG_FLAGS, &[flags]);
+        let payload_len = out.len() as u64;
+        out.extend_from_slice(&payload_len.to_le_bytes());
+        out.extend_from_slice(MAGIC);
+        out
+    }
+
+    /// Parse a payload produced by [`SelfExtractingImage::encode`].
+    pub fn decode(bytes: &[u8]) -> anyhow::Result<Self> {
+        let payload = split_trailer(bytes)
+            .ok_or_else(|| anyhow::anyhow!("self-extracting image trailer missing"))?;
+        let mut runtime_toml = None;
+        let mut io_toml = None;
+        let mut bytecode = None;
+        let mut sources = Vec::new();
+        let mut control = false;
+        let mut rest = payload;
+        while !rest.is_empty() {
+            let (tag, body, tail) = next_section(rest)
+                .ok_or_else(|| anyhow::anyhow!("truncated self-extracting image"))?;
+            rest = tail;
+            match tag {
+                TAG_RUNTIME => runtime_toml = Some(String::from_utf8(body.to_vec())?),
+                TAG_IO => io_toml = Some(String::from_utf8(body.to_vec())?),
+                TAG_BYTECODE => bytecode = Some(body.to_vec()),
+                TAG_SOURCE => {
+                    let (len, body) = body
+                        .split_first_chunk::<4>()
+                        .ok_or_else(|| anyhow::anyhow!("truncated self-extracting image source"))?;
+                    let len = u32::from_le_bytes(*len) as usize;
+                    if len > body.len() {
+                        anyhow::bail!("truncated self-extracting image source");
+                    }
+                    let (path, text) = body.split_at(len);
+                    sources.push((
+                        String::from_utf8(path.to_vec())?,
+                        String::from_utf8(text.to_vec())?,
+                    ));
+                }
+                TAG_FLAGS => control = body.first().is_some_and(|flags| flags & FLAG_CONTROL != 0),
+                other => anyhow::bail!("unknown self-extracting image section {other}"),
+            }
+        }
+        Ok(Self {
+            runtime_toml: runtime_toml
+                .ok_or_else(|| anyhow::anyhow!("self-extracting image missing runtime.toml"))?,
+            io_toml,
+            bytecode: bytecode
+                .ok_or_else(|| anyhow::anyhow!("self-extracting image missing program.stbc"))?,
+            sources,
+            control,
+        })
+    }
+
+    /// Write `runner` followed by the image to `output` and mark it executable.
+    ///
+    /// Returns the size of the written file.
+    pub fn write_executable(&self, runner: &Path, output: &Path) -> anyhow::Result<u64> {
+        let mut bytes = fs::read(runner)
+            .with_context(|| format!("failed to read runner {}", runner.display()))?;
+        // Re-packing an image executable replaces its payload.
+        if let Some(payload) = split_trailer(&bytes) {
+            let start = bytes.len() - payload.len() - TRAILER_LEN as usize;
+            bytes.truncate(start);
+        }
+        bytes.extend_from_slice(&self.encode());
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(output, &bytes)
+            .with_context(|| format!("failed to write {}", output.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(output, fs::Permissions::from_mode(0o755))?;
+        }
+        Ok(bytes.len() as u64)
+    }
+
+    /// Read the image appended to `executable`, if any.
+    pub fn read_embedded(executable: &Path) -> anyhow::Result<Option<Self>> {
+        let mut file = fs::File::open(executable)?;
+        let size = file.metadata()?.len();
+        if size < TRAILER_LEN {
+            return Ok(None);
+        }
+        let mut trailer = [0u8; TRAILER_LEN as usize];
+        file.seek(SeekFrom::End(-(TRAILER_LEN as i64)))?;
+        file.read_exact(&mut trailer)?;
+        if &trailer[8..] != MAGIC {
+            return Ok(None);
+        }
+        // The length comes from the file; check it against the file size
+        // before it sizes any allocation.
+        let payload_len = u64::from_le_bytes(trailer[..8].try_into()?);
+        let Some(image_len) = payload_len
+            .checked_add(TRAILER_LEN)
+            .filter(|&len| len <= size)
+        else {
+            anyhow::bail!("corrupt self-extracting image in {}", executable.display());
+        };
+        file.seek(SeekFrom::Start(size - image_len))?;
+        let mut bytes = Vec::with_capacity(usize::try_from(image_len)?);
+        file.read_to_end(&mut bytes)?;
+        Self::decode(&bytes).map(Some)
+    }
+
+    /// Write the project files to `root` so the regular loader can use them.
+    ///
+    /// Existing retain files and other runtime state in `root` are kept.
+    pub fn extract(&self, root: &Path) -> anyhow::Result<()> {
+        fs::create_dir_all(root)?;
+        fs::write(root.join("runtime.toml"), &self.runtime_toml)?;
+        if let Some(io) = &self.io_toml {
+            fs::write(root.join("io.toml"), io)?;
+        }
+        fs::write(root.join("program.stbc"), &self.bytecode)?;
+        let src = root.join("src");
+        if src.is_dir() {
+            fs::remove_dir_all(&src)?;
+        }
+        for (path, text) in &self.sources {
+            let target = source_path(&src, path)?;
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(target, text)?;
+        }
+        Ok(())
+    }
+}
+
+fn push_section(out: &mut Vec<u8>, tag: u8, body: &[u8]) {
+    out.push(tag);
+    out.extend_from_slice(&(body.len() as u64).to_le_bytes());
+    out.extend_from_slice(body);
+}
+
+fn next_section(bytes: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = bytes.split_first()?;
+    let (len, rest) = rest.split_first_chunk::<8>()?;
+    let len = usize::try_from(u64::from_le_bytes(*len)).ok()?;
+    (len <= rest.len()).then(|| {
+        let (body, tail) = rest.split_at(len);
+        (tag, body, tail)
+    })
+}
+
+/// Payload in front of a valid trailer at the end of `bytes`.
+fn split_trailer(bytes: &[u8]) -> Option<&[u8]> {
+    let (body, magic) = bytes.split_last_chunk::<8>()?;
+    if magic != MAGIC {
+        return None;
+    }
+    let (body, len) = body.split_last_chunk::<8>()?;
+    let len = usize::try_from(u64::from_le_bytes(*len)).ok()?;
+    body.len().checked_sub(len).map(|start| &body[start..])
+}
+
+/// Resolve an image source path below `src`, rejecting escapes.
+fn source_path(src: &Path, path: &str) -> anyhow::Result<PathBuf> {
+    let mut target = src.to_path_buf();
+    for part in path.split('/') {
+        if part.is_empty() || part == "." || part == ".." || part.contains('\\') {
+            anyhow::bail!("invalid source path in self-extracting image: {path}");
+        }
+        target.push(part);
+    }
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("{prefix}-{stamp}"));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    fn image() -> SelfExtractingImage {
+        SelfExtractingImage {
+            runtime_toml: "[bundle]\nversion = 1\n".into(),
+            io_toml: Some("[io]\ndriver = \"loopback\"\nparams = {}\n".into()),
+            bytecode: vec![0x53, 0x54, 0x42, 0x43, 0, 1],
+            sources: vec![
+                ("main.st".into(), "PROGRAM Main\nEND_PROGRAM\n".into()),
+                (
+                    "deps/1/lib.st".into(),
+                    "FUNCTION F : INT\nEND_FUNCTION\n".into(),
+                ),
+            ],
+            control: false,
+        }
+    }
+
+    #[test]
+    fn image_round_trips_through_executable() {
+        let dir = temp_dir("trust-runtime-self-extracting-image");
+        let runner = dir.join("runner");
+        fs::write(&runner, b"\x7fELF runner bytes").expect("write runner");
+        let output = dir.join("out/plc");
+
+        let first = image();
+        first
+            .write_executable(&runner, &output)
+            .expect("write image");
+        assert_eq!(
+            SelfExtractingImage::read_embedded(&output).expect("read image"),
+            Some(first)
+        );
+        assert_eq!(
+            SelfExtractingImage::read_embedded(&runner).expect("plain runner"),
+            None
+        );
+
+        // Packing from an image executable replaces the previous payload.
+        let mut second = image();
+        second.control = true;
+        second.sources.truncate(1);
+        let size = second.write_executable(&output, &output).expect("repack");
+        assert_eq!(
+            size,
+            (b"\x7fELF runner bytes".len() + second.encode().len()) as u64
+        );
+        assert_eq!(
+            SelfExtractingImage::read_embedded(&output).expect("read repacked"),
+            Some(second)
+        );
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn read_embedded_rejects_corrupt_trailer_length() {
+        let dir = temp_dir("trust-runtime-self-extracting-corrupt");
+        let path = dir.join("plc");
+        for payload_len in [u64::MAX, u64::MAX - TRAILER_LEN + 1, 64] {
+            let mut bytes = b"\x7fELF runner bytes".to_vec();
+            bytes.extend_from_slice(&payload_len.to_le_bytes());
+            bytes.extend_from_slice(MAGIC);
+            fs::write(&path, &bytes).expect("write executable");
+            let err = SelfExtractingImage::read_embedded(&path).expect_err("corrupt trailer");
+            assert!(
+                err.to_string().contains("corrupt self-extracting image"),
+                "{err}"
+            );
+        }
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn extract_rejects_paths_outside_src() {
+        let dir = temp_dir("trust-runtime-self-extracting-extract");
+        let mut image = image();
+        image.extract(&dir).expect("extract");
+        assert!(dir.join("src/deps/1/lib.st").is_file());
+        assert!(dir.join("program.stbc").is_file());
+
+        image.sources.push(("../escape.st".into(), String::new()));
+        assert!(image.extract(&dir).is_err());
+        assert!(!dir.join("escape.st").exists());
+        fs::remove_dir_all(dir).ok();
+    }
+}
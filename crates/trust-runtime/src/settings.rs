@@ -5,6 +5,8 @@
 use indexmap::IndexMap;
 use smol_str::SmolStr;
 
+use crate::config::PausePolicy;
+use crate::io::{IoSymbolMap, OutputSafeState};
 use crate::value::Duration;
 use crate::watchdog::{FaultPolicy, RetainMode, WatchdogPolicy};
 
@@ -20,6 +22,15 @@ pub struct RuntimeSettings {
     pub mesh: MeshSettings,
     pub opcua: OpcUaSettings,
     pub simulation: SimulationSettings,
+    pub realtime: RealtimeSettings,
+    /// Explicit pause policy; `None` follows the control mode.
+    pub pause_policy: Option<PausePolicy>,
+    /// Output policy applied on pause, fault, and watchdog trip.
+    pub safety_state: OutputSafeState,
+    /// Symbols bound from io.toml `[io.map]`.
+    pub io_map: IoSymbolMap,
+    /// Send control errors as plain strings instead of error objects.
+    pub legacy_errors: bool,
 }
 
 impl RuntimeSettings {
@@ -41,6 +52,11 @@ impl RuntimeSettings {
             mesh,
             opcua: OpcUaSettings::default(),
             simulation,
+            realtime: RealtimeSettings::default(),
+            pause_policy: None,
+            safety_state: OutputSafeState::default(),
+            io_map: IoSymbolMap::default(),
+            legacy_errors: false,
         }
     }
 }
@@ -120,3 +136,12 @@ pub struct SimulationSettings {
     pub mode_label: SmolStr,
     pub warning: SmolStr,
 }
+
+#[derive(Debug, Clone, Default)]
+pub struct RealtimeSettings {
+    pub priority: Option<u8>,
+    pub cpu_affinity: Vec<usize>,
+    pub lock_memory: bool,
+    /// Settings the resource thread could not apply (missing privileges etc.).
+    pub warnings: Vec<SmolStr>,
+}
@@ -49,6 +49,7 @@ pub fn run_setup(options: SetupOptions) -> Result<PathBuf, RuntimeError> {
             params,
         }],
         safe_state: crate::io::IoSafeState::default(),
+        map: crate::io::IoSymbolMap::default(),
     };
 
     write_system_io_config(&path, &io_config)?;
@@ -195,6 +196,17 @@ fn render_io_config(config: &IoConfig) -> Result<String, RuntimeError> {
             })
             .collect(),
         safe_state: Vec::new(),
+        map: config
+            .map
+            .symbols
+            .iter()
+            .map(|symbol| {
+                (
+                    symbol.name.to_string(),
+                    crate::io::format_io_address(&symbol.address),
+                )
+            })
+            .collect(),
     };
     Ok(crate::bundle_template::render_io_toml(&template))
 }
@@ -14,6 +14,14 @@ use crate::memory::IoArea;
 use crate::value::{Duration, Value};
 use crate::Runtime;
 
+mod models;
+
+use models::{ModelRuntime, ModelSection};
+pub use models::{
+    SimulationModel, SimulationModelKind, SimulationModelParams, SimulationModelStatus,
+    SimulationModelUpdate,
+};
+
 #[derive(Debug, Clone)]
 pub struct SimulationConfig {
     pub enabled: bool,
@@ -21,6 +29,7 @@ pub struct SimulationConfig {
     pub time_scale: u32,
     pub couplings: Vec<SignalCouplingRule>,
     pub disturbances: Vec<SimulationDisturbance>,
+    pub models: Vec<SimulationModel>,
 }
 
 impl Default for SimulationConfig {
@@ -31,6 +40,7 @@ impl Default for SimulationConfig {
             time_scale: 1,
             couplings: Vec::new(),
             disturbances: Vec::new(),
+            models: Vec::new(),
         }
     }
 }
@@ -102,18 +112,28 @@ pub struct SimulationController {
     pending_effects: VecDeque<PendingEffect>,
     next_sequence: u64,
     last_coupling_values: Vec<Option<Value>>,
+    models: Vec<ModelRuntime>,
+    noise_state: u64,
 }
 
 impl SimulationController {
     pub fn new(config: SimulationConfig) -> Self {
         let last_coupling_values = vec![None; config.couplings.len()];
+        let models = config
+            .models
+            .iter()
+            .cloned()
+            .map(ModelRuntime::new)
+            .collect();
         Self {
             disturbances: config.disturbances.clone(),
+            noise_state: config.seed,
             config,
             disturbance_cursor: 0,
             pending_effects: VecDeque::new(),
             next_sequence: 0,
             last_coupling_values,
+            models,
         }
     }
 
@@ -175,9 +195,53 @@ impl SimulationController {
             }
         }
 
+        for model in &mut self.models {
+            let source = match &model.model.source {
+                Some(address) => value_to_f64(&runtime.io().read(address)?).unwrap_or(0.0),
+                None => 0.0,
+            };
+            let noise = if model.model.params.noise > 0.0 {
+                models::next_noise(&mut self.noise_state)
+            } else {
+                0.0
+            };
+            let value = model.step(now, source, noise);
+            if let Err(err) = runtime.io_mut().write(&model.model.target, value) {
+                let msg = format!(
+                    "simulation model '{}' failed for {}: {err}",
+                    model.model.name,
+                    format_io(&model.model.target)
+                );
+                return Err(runtime.simulation_fault(msg));
+            }
+        }
+
         Ok(())
     }
 
+    /// Current definition and value of every channel model.
+    #[must_use]
+    pub fn models(&self) -> Vec<SimulationModelStatus> {
+        self.models.iter().map(ModelRuntime::status).collect()
+    }
+
+    /// Change model parameters while the simulation runs.
+    pub fn update_model(
+        &mut self,
+        name: &str,
+        update: &SimulationModelUpdate,
+    ) -> Result<SimulationModelStatus, RuntimeError> {
+        let model = self
+            .models
+            .iter_mut()
+            .find(|entry| entry.model.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                RuntimeError::ControlError(format!("unknown simulation model '{name}'").into())
+            })?;
+        model.update(update)?;
+        Ok(model.status())
+    }
+
     pub fn apply_post_cycle(
         &mut self,
         now: Duration,
@@ -221,6 +285,7 @@ struct SimulationToml {
     simulation: Option<SimulationSection>,
     couplings: Option<Vec<CouplingSection>>,
     disturbances: Option<Vec<DisturbanceSection>>,
+    models: Option<Vec<ModelSection>>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -265,8 +330,39 @@ impl SimulationToml {
             .map(DisturbanceSection::into_disturbance)
             .collect::<Result<Vec<_>, _>>()?;
         disturbances.sort_by_key(|entry| entry.at.as_nanos());
+        let models = self
+            .models
+            .unwrap_or_default()
+            .into_iter()
+            .map(ModelSection::into_model)
+            .collect::<Result<Vec<_>, _>>()?;
+        for (idx, model) in models.iter().enumerate() {
+            if models[..idx]
+                .iter()
+                .any(|other| other.name.eq_ignore_ascii_case(&model.name))
+            {
+                return Err(RuntimeError::InvalidConfig(
+                    format!("duplicate simulation model '{}'", model.name).into(),
+                ));
+            }
+            let driven_elsewhere = models[..idx]
+                .iter()
+                .map(|other| &other.target)
+                .chain(couplings.iter().map(|rule| &rule.target))
+                .any(|target| target == &model.target);
+            if driven_elsewhere {
+                return Err(RuntimeError::InvalidConfig(
+                    format!(
+                        "simulation model '{}' target {} is already driven by another model or coupling",
+                        model.name,
+                        format_io(&model.target)
+                    )
+                    .into(),
+                ));
+            }
+        }
 
-        let has_rules = !couplings.is_empty() || !disturbances.is_empty();
+        let has_rules = !couplings.is_empty() || !disturbances.is_empty() || !models.is_empty();
         let enabled = section.enabled.unwrap_or(has_rules);
         let time_scale = section.time_scale.unwrap_or(1);
         if time_scale == 0 {
@@ -281,6 +377,7 @@ impl SimulationToml {
             time_scale,
             couplings,
             disturbances,
+            models,
         })
     }
 }
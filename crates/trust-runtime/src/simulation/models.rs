@@ -0,0 +1,337 @@
+//! Per-channel behavior models that drive simulated inputs.
+
+use std::collections::VecDeque;
+
+use serde::Deserialize;
+use smol_str::SmolStr;
+
+use crate::error::RuntimeError;
+use crate::io::{IoAddress, IoSize};
+use crate::memory::IoArea;
+use crate::value::{Duration, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulationModelKind {
+    /// First-order lag: the input settles toward `gain * source + offset`
+    /// with time constant `time_constant`.
+    Lag,
+    /// Integrator: the input changes by `gain * source + offset` per second,
+    /// e.g. a tank level filled by a pump output and drained by a negative offset.
+    Integrator,
+}
+
+impl SimulationModelKind {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lag => "lag",
+            Self::Integrator => "integrator",
+        }
+    }
+}
+
+/// Tunable model parameters; `sim.model.set` changes them while running.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationModelParams {
+    pub gain: f64,
+    pub offset: f64,
+    pub time_constant: Duration,
+    /// Transport delay applied to the source signal.
+    pub delay: Duration,
+    /// Amplitude of uniform noise added to the written value.
+    pub noise: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimulationModel {
+    pub name: SmolStr,
+    pub kind: SimulationModelKind,
+    /// Output driving the model; without one the model only follows `offset`.
+    pub source: Option<IoAddress>,
+    /// Input written every cycle.
+    pub target: IoAddress,
+    pub initial: f64,
+    pub params: SimulationModelParams,
+}
+
+/// Parameter changes requested through `sim.model.set`. Unset fields keep their value.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SimulationModelUpdate {
+    pub gain: Option<f64>,
+    pub offset: Option<f64>,
+    pub time_constant_ms: Option<u64>,
+    pub delay_ms: Option<u64>,
+    pub noise: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Overwrite the model state, e.g. to refill a tank.
+    pub value: Option<f64>,
+}
+
+/// Model definition and current state, returned by `sim.model.set`.
+#[derive(Debug, Clone)]
+pub struct SimulationModelStatus {
+    pub model: SimulationModel,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct ModelRuntime {
+    pub(super) model: SimulationModel,
+    value: f64,
+    last: Option<Duration>,
+    samples: VecDeque<(i64, f64)>,
+}
+
+impl ModelRuntime {
+    pub(super) fn new(model: SimulationModel) -> Self {
+        Self {
+            value: model.initial,
+            model,
+            last: None,
+            samples: VecDeque::new(),
+        }
+    }
+
+    pub(super) fn status(&self) -> SimulationModelStatus {
+        SimulationModelStatus {
+            model: self.model.clone(),
+            value: self.value,
+        }
+    }
+
+    pub(super) fn update(&mut self, update: &SimulationModelUpdate) -> Result<(), RuntimeError> {
+        let mut params = self.model.params.clone();
+        if let Some(gain) = update.gain {
+            params.gain = gain;
+        }
+        if let Some(offset) = update.offset {
+            params.offset = offset;
+        }
+        if let Some(ms) = update.time_constant_ms {
+            params.time_constant = millis(ms);
+        }
+        if let Some(ms) = update.delay_ms {
+            params.delay = millis(ms);
+        }
+        if let Some(noise) = update.noise {
+            params.noise = noise;
+        }
+        if let Some(min) = update.min {
+            params.min = Some(min);
+        }
+        if let Some(max) = update.max {
+            params.max = Some(max);
+        }
+        check_params(&self.model.name, &params)?;
+        if let Some(value) = update.value {
+            if !value.is_finite() {
+                return Err(invalid(&self.model.name, "value must be finite"));
+            }
+            self.value = clamp(value, &params);
+        }
+        self.model.params = params;
+        Ok(())
+    }
+
+    /// Advance the model to `now` with the current `source` sample and
+    /// return the value to write, including noise.
+    pub(super) fn step(&mut self, now: Duration, source: f64, noise: f64) -> Value {
+        let params = &self.model.params;
+        let now_ns = now.as_nanos();
+        self.samples.push_back((now_ns, source));
+        let cutoff = now_ns.saturating_sub(params.delay.as_nanos());
+        while self.samples.len() > 1 && self.samples[1].0 <= cutoff {
+            self.samples.pop_front();
+        }
+        let delayed = match self.samples.front() {
+            Some((at, value)) if *at <= cutoff => *value,
+            _ => 0.0,
+        };
+
+        let dt = self
+            .last
+            .map_or(0.0, |last| (now_ns - last.as_nanos()).max(0) as f64 / 1e9);
+        self.last = Some(now);
+        let drive = params.gain * delayed + params.offset;
+        match self.model.kind {
+            SimulationModelKind::Lag => {
+                let tau = params.time_constant.as_nanos() as f64 / 1e9;
+                if tau <= 0.0 {
+                    self.value = drive;
+                } else if dt > 0.0 {
+                    self.value += (drive - self.value) * (1.0 - (-dt / tau).exp());
+                }
+            }
+            SimulationModelKind::Integrator => self.value += drive * dt,
+        }
+        self.value = clamp(self.value, params);
+        let written = clamp(self.value + params.noise * noise, params);
+        value_for_size(written, self.model.target.size)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ModelSection {
+    name: String,
+    kind: Option<String>,
+    source: Option<String>,
+    target: String,
+    initial: Option<f64>,
+    gain: Option<f64>,
+    offset: Option<f64>,
+    time_constant_ms: Option<u64>,
+    delay_ms: Option<u64>,
+    noise: Option<f64>,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl ModelSection {
+    pub(super) fn into_model(self) -> Result<SimulationModel, RuntimeError> {
+        let name = SmolStr::new(self.name.trim());
+        if name.is_empty() {
+            return Err(RuntimeError::InvalidConfig(
+                "simulation model name must not be empty".into(),
+            ));
+        }
+        let kind = match self
+            .kind
+            .as_deref()
+            .map(|text| text.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            None | Some("lag") => SimulationModelKind::Lag,
+            Some("integrator") => SimulationModelKind::Integrator,
+            Some(other) => {
+                return Err(invalid(
+                    &name,
+                    format!("unsupported kind '{other}' (expected lag/integrator)"),
+                ))
+            }
+        };
+        let target = IoAddress::parse(self.target.as_str())?;
+        if target.area != IoArea::Input || target.wildcard {
+            return Err(invalid(
+                &name,
+                format!("target must be a %I address, got {}", self.target),
+            ));
+        }
+        let source = self
+            .source
+            .as_deref()
+            .map(|text| {
+                let source = IoAddress::parse(text)?;
+                if source.area != IoArea::Output || source.wildcard {
+                    return Err(invalid(
+                        &name,
+                        format!("source must be a %Q address, got {text}"),
+                    ));
+                }
+                Ok(source)
+            })
+            .transpose()?;
+        let params = SimulationModelParams {
+            gain: self.gain.unwrap_or(1.0),
+            offset: self.offset.unwrap_or(0.0),
+            time_constant: millis(self.time_constant_ms.unwrap_or(0)),
+            delay: millis(self.delay_ms.unwrap_or(0)),
+            noise: self.noise.unwrap_or(0.0),
+            min: self.min,
+            max: self.max,
+        };
+        check_params(&name, &params)?;
+        let initial = self.initial.unwrap_or(0.0);
+        if !initial.is_finite() {
+            return Err(invalid(&name, "initial must be finite"));
+        }
+        Ok(SimulationModel {
+            name,
+            kind,
+            source,
+            target,
+            initial: clamp(initial, &params),
+            params,
+        })
+    }
+}
+
+fn check_params(name: &str, params: &SimulationModelParams) -> Result<(), RuntimeError> {
+    let numbers = [
+        ("gain", Some(params.gain)),
+        ("offset", Some(params.offset)),
+        ("noise", Some(params.noise)),
+        ("min", params.min),
+        ("max", params.max),
+    ];
+    for (field, value) in numbers {
+        if value.is_some_and(|value| !value.is_finite()) {
+            return Err(invalid(name, format!("{field} must be finite")));
+        }
+    }
+    if params.noise < 0.0 {
+        return Err(invalid(name, "noise must be >= 0"));
+    }
+    if let (Some(min), Some(max)) = (params.min, params.max) {
+        if min > max {
+            return Err(invalid(
+                name,
+                format!("min {min} is greater than max {max}"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn clamp(value: f64, params: &SimulationModelParams) -> f64 {
+    let value = params.min.map_or(value, |min| value.max(min));
+    params.max.map_or(value, |max| value.min(max))
+}
+
+/// Round to the target size; word-and-wider targets keep negative values in
+/// two's complement so signed variables read them back correctly.
+fn value_for_size(value: f64, size: IoSize) -> Value {
+    let rounded = value.round();
+    match size {
+        IoSize::Bit => Value::Bool(value >= 0.5),
+        IoSize::Byte if rounded < 0.0 => Value::Byte(rounded.max(f64::from(i8::MIN)) as i8 as u8),
+        IoSize::Byte => Value::Byte(rounded.min(f64::from(u8::MAX)) as u8),
+        IoSize::Word if rounded < 0.0 => {
+            Value::Word(rounded.max(f64::from(i16::MIN)) as i16 as u16)
+        }
+        IoSize::Word => Value::Word(rounded.min(f64::from(u16::MAX)) as u16),
+        IoSize::DWord if rounded < 0.0 => {
+            Value::DWord(rounded.max(f64::from(i32::MIN)) as i32 as u32)
+        }
+        IoSize::DWord => Value::DWord(rounded.min(f64::from(u32::MAX)) as u32),
+        IoSize::LWord if rounded < 0.0 => Value::LWord(rounded as i64 as u64),
+        IoSize::LWord => Value::LWord(rounded as u64),
+    }
+}
+
+fn millis(ms: u64) -> Duration {
+    Duration::from_nanos(
+        i64::try_from(ms)
+            .unwrap_or(i64::MAX)
+            .saturating_mul(1_000_000),
+    )
+}
+
+fn invalid(name: &str, message: impl std::fmt::Display) -> RuntimeError {
+    RuntimeError::InvalidConfig(format!("simulation model '{name}': {message}").into())
+}
+
+/// Uniform sample in `[-1, 1)` from a splitmix64 sequence, so runs with the
+/// same `simulation.seed` produce the same noise.
+pub(super) fn next_noise(state: &mut u64) -> f64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+}
@@ -0,0 +1,48 @@
+//! Array bound standard functions (LOWER_BOUND, UPPER_BOUND).
+//!
+//! Bounds are read from the array value itself, so the same functions serve
+//! fixed arrays and `ARRAY[*]` parameters.
+
+#![allow(missing_docs)]
+
+use crate::error::RuntimeError;
+use crate::stdlib::helpers::{require_arity, to_i64};
+use crate::stdlib::StandardLibrary;
+use crate::value::Value;
+
+pub fn register(lib: &mut StandardLibrary) {
+    lib.register("LOWER_BOUND", &["ARR", "DIM"], lower_bound);
+    lib.register("UPPER_BOUND", &["ARR", "DIM"], upper_bound);
+}
+
+/// Returns the `(lower, upper)` bounds of the 1-based dimension `DIM`.
+fn dimension(args: &[Value]) -> Result<(i64, i64), RuntimeError> {
+    require_arity(args, 2)?;
+    let Value::Array(array) = &args[0] else {
+        return Err(RuntimeError::TypeMismatch);
+    };
+    let dim = to_i64(&args[1])?;
+    dim.checked_sub(1)
+        .and_then(|index| usize::try_from(index).ok())
+        .and_then(|index| array.dimensions.get(index))
+        .copied()
+        .ok_or(RuntimeError::IndexOutOfBounds {
+            index: dim,
+            lower: 1,
+            upper: array.dimensions.len() as i64,
+        })
+}
+
+fn bound_value(bound: i64) -> Result<Value, RuntimeError> {
+    i32::try_from(bound)
+        .map(Value::DInt)
+        .map_err(|_| RuntimeError::Overflow)
+}
+
+fn lower_bound(args: &[Value]) -> Result<Value, RuntimeError> {
+    bound_value(dimension(args)?.0)
+}
+
+fn upper_bound(args: &[Value]) -> Result<Value, RuntimeError> {
+    bound_value(dimension(args)?.1)
+}
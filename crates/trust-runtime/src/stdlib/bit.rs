@@ -83,35 +83,36 @@ enum ShiftOp {
 fn shift(args: &[Value], op: ShiftOp) -> Result<Value, RuntimeError> {
     require_arity(args, 2)?;
     let (value, width) = bit_value(&args[0])?;
-    let count = to_i64(&args[1])?;
-    if count < 0 {
-        return Err(RuntimeError::TypeMismatch);
-    }
-    let count = count as u32;
+    let count = u64::try_from(to_i64(&args[1])?).map_err(|_| RuntimeError::TypeMismatch)?;
     let mask = mask_for(width);
     let result = match op {
         ShiftOp::Left => {
-            if count >= width {
+            if count >= u64::from(width) {
                 0
             } else {
                 (value << count) & mask
             }
         }
         ShiftOp::Right => {
-            if count >= width {
+            if count >= u64::from(width) {
                 0
             } else {
                 (value >> count) & mask
             }
         }
-        ShiftOp::RotateLeft => {
-            let shift = count % width;
-            ((value << shift) | (value >> (width - shift))) & mask
-        }
+        ShiftOp::RotateLeft => rotate_left(value, count % u64::from(width), width),
         ShiftOp::RotateRight => {
-            let shift = count % width;
-            ((value >> shift) | (value << (width - shift))) & mask
+            let count = count % u64::from(width);
+            rotate_left(value, (u64::from(width) - count) % u64::from(width), width)
         }
     };
     Ok(bit_value_to_result(result, width))
 }
+
+/// Rotates the low `width` bits of `value` left by `count < width` bits.
+fn rotate_left(value: u64, count: u64, width: u32) -> u64 {
+    if count == 0 {
+        return value;
+    }
+    ((value << count) | (value >> (u64::from(width) - count))) & mask_for(width)
+}
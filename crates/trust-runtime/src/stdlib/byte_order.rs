@@ -0,0 +1,58 @@
+//! Byte-order conversion functions for protocol data.
+//!
+//! Host order is the little-endian byte order used by UNION overlays, so the
+//! `LE` functions return their input and the `BE` functions reverse its bytes.
+
+#![allow(missing_docs)]
+
+use crate::error::RuntimeError;
+use crate::stdlib::helpers::require_arity;
+use crate::stdlib::StandardLibrary;
+use crate::value::Value;
+
+pub fn register(lib: &mut StandardLibrary) {
+    lib.register("BE_TO_HOST", &["IN"], swap_bytes);
+    lib.register("HOST_TO_BE", &["IN"], swap_bytes);
+    lib.register("LE_TO_HOST", &["IN"], keep_bytes);
+    lib.register("HOST_TO_LE", &["IN"], keep_bytes);
+}
+
+fn swap_bytes(args: &[Value]) -> Result<Value, RuntimeError> {
+    require_arity(args, 1)?;
+    let value = match &args[0] {
+        Value::Int(value) => Value::Int(value.swap_bytes()),
+        Value::DInt(value) => Value::DInt(value.swap_bytes()),
+        Value::LInt(value) => Value::LInt(value.swap_bytes()),
+        Value::UInt(value) => Value::UInt(value.swap_bytes()),
+        Value::UDInt(value) => Value::UDInt(value.swap_bytes()),
+        Value::ULInt(value) => Value::ULInt(value.swap_bytes()),
+        Value::Word(value) => Value::Word(value.swap_bytes()),
+        Value::DWord(value) => Value::DWord(value.swap_bytes()),
+        Value::LWord(value) => Value::LWord(value.swap_bytes()),
+        Value::Real(value) => Value::Real(f32::from_bits(value.to_bits().swap_bytes())),
+        Value::LReal(value) => Value::LReal(f64::from_bits(value.to_bits().swap_bytes())),
+        other => return keep_bytes(std::slice::from_ref(other)),
+    };
+    Ok(value)
+}
+
+fn keep_bytes(args: &[Value]) -> Result<Value, RuntimeError> {
+    require_arity(args, 1)?;
+    match &args[0] {
+        value @ (Value::SInt(_)
+        | Value::Int(_)
+        | Value::DInt(_)
+        | Value::LInt(_)
+        | Value::USInt(_)
+        | Value::UInt(_)
+        | Value::UDInt(_)
+        | Value::ULInt(_)
+        | Value::Byte(_)
+        | Value::Word(_)
+        | Value::DWord(_)
+        | Value::LWord(_)
+        | Value::Real(_)
+        | Value::LReal(_)) => Ok(value.clone()),
+        _ => Err(RuntimeError::TypeMismatch),
+    }
+}
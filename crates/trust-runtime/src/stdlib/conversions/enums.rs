@@ -0,0 +1,108 @@
+use smol_str::SmolStr;
+use trust_hir::types::TypeRegistry;
+use trust_hir::{Type, TypeId};
+
+use crate::error::RuntimeError;
+use crate::numeric::to_i64;
+use crate::stdlib::helpers::require_arity;
+use crate::value::{EnumValue, Value};
+
+use super::numeric::convert_to_int;
+use super::util::is_integer_type;
+use super::ConversionMode;
+
+/// `TO_<T>` / `<S>_TO_<T>` where `S` and `T` are elementary or enumerated types.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct EnumConversionSpec {
+    src: Option<TypeId>,
+    dst: TypeId,
+}
+
+pub(super) fn parse_enum_conversion_spec(
+    name: &str,
+    registry: &TypeRegistry,
+) -> Option<EnumConversionSpec> {
+    let upper = name.to_ascii_uppercase();
+    let (src, dst) = if let Some(dst_name) = upper.strip_prefix("TO_") {
+        (None, conversion_type_id(dst_name, registry)?)
+    } else {
+        let (src_name, dst_name) = upper.split_once("_TO_")?;
+        (
+            Some(conversion_type_id(src_name, registry)?),
+            conversion_type_id(dst_name, registry)?,
+        )
+    };
+    Some(EnumConversionSpec { src, dst })
+}
+
+/// Whether the function name itself refers to an enumerated type.
+pub(super) fn names_enum_type(spec: EnumConversionSpec, registry: &TypeRegistry) -> bool {
+    enum_type(spec.dst, registry).is_some()
+        || spec
+            .src
+            .is_some_and(|src| enum_type(src, registry).is_some())
+}
+
+pub(super) fn apply_enum_conversion(
+    spec: EnumConversionSpec,
+    args: &[Value],
+    registry: &TypeRegistry,
+) -> Result<Value, RuntimeError> {
+    require_arity(args, 1)?;
+    let value = &args[0];
+    if let Some(src) = spec.src {
+        if !value_matches_type(value, src, registry) {
+            return Err(RuntimeError::TypeMismatch);
+        }
+    }
+    match (value, enum_type(spec.dst, registry)) {
+        (Value::Enum(enum_value), None) if is_integer_type(spec.dst) => convert_to_int(
+            &Value::LInt(enum_value.numeric_value),
+            spec.dst,
+            ConversionMode::Round,
+        ),
+        (Value::Enum(enum_value), Some((name, _))) if enum_value.type_name == *name => {
+            Ok(value.clone())
+        }
+        (Value::Enum(_), _) => Err(RuntimeError::TypeMismatch),
+        (_, Some((name, values))) => {
+            let numeric_value = to_i64(value)?;
+            let (variant_name, _) = values
+                .iter()
+                .find(|(_, member)| *member == numeric_value)
+                .ok_or_else(|| RuntimeError::InvalidEnumValue {
+                    type_name: name.clone(),
+                    value: numeric_value,
+                })?;
+            Ok(Value::Enum(EnumValue {
+                type_name: name.clone(),
+                variant_name: variant_name.clone(),
+                numeric_value,
+            }))
+        }
+        (_, None) => Err(RuntimeError::TypeMismatch),
+    }
+}
+
+fn conversion_type_id(name: &str, registry: &TypeRegistry) -> Option<TypeId> {
+    TypeId::from_builtin_name(name).or_else(|| {
+        let type_id = registry.lookup(name)?;
+        enum_type(type_id, registry).map(|_| type_id)
+    })
+}
+
+fn enum_type(type_id: TypeId, registry: &TypeRegistry) -> Option<(&SmolStr, &[(SmolStr, i64)])> {
+    match registry.get(type_id)? {
+        Type::Enum { name, values, .. } => Some((name, values.as_slice())),
+        Type::Alias { target, .. } => enum_type(*target, registry),
+        _ => None,
+    }
+}
+
+fn value_matches_type(value: &Value, type_id: TypeId, registry: &TypeRegistry) -> bool {
+    match (value, enum_type(type_id, registry)) {
+        (Value::Enum(enum_value), Some((name, _))) => enum_value.type_name == *name,
+        (Value::Enum(_), None) | (_, Some(_)) => false,
+        (_, None) => super::util::value_type_id(value) == Some(type_id),
+    }
+}
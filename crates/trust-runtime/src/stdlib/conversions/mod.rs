@@ -5,6 +5,7 @@
 mod bcd;
 mod bitstring;
 mod dispatch;
+mod enums;
 mod numeric;
 mod spec;
 mod string;
@@ -14,6 +15,7 @@ mod util;
 use super::StandardLibrary;
 use crate::error::RuntimeError;
 use crate::value::Value;
+use trust_hir::types::TypeRegistry;
 
 #[derive(Debug, Clone, Copy)]
 enum ConversionMode {
@@ -31,3 +33,23 @@ pub fn call_conversion(name: &str, args: &[Value]) -> Option<Result<Value, Runti
     let spec = spec::parse_conversion_spec(name)?;
     Some(dispatch::apply_conversion(spec, args))
 }
+
+/// Whether `name` converts to or from an enumerated type declared in `registry`.
+pub fn is_enum_conversion_name(name: &str, registry: &TypeRegistry) -> bool {
+    enums::parse_enum_conversion_spec(name, registry)
+        .is_some_and(|spec| enums::names_enum_type(spec, registry))
+}
+
+/// Applies a conversion to or from an enumerated type. Returns `None` when neither
+/// the function name nor the argument involves an enum, e.g. `TO_INT(DINT#1)`.
+pub fn call_enum_conversion(
+    name: &str,
+    args: &[Value],
+    registry: &TypeRegistry,
+) -> Option<Result<Value, RuntimeError>> {
+    let spec = enums::parse_enum_conversion_spec(name, registry)?;
+    if !enums::names_enum_type(spec, registry) && !matches!(args.first(), Some(Value::Enum(_))) {
+        return None;
+    }
+    Some(enums::apply_enum_conversion(spec, args, registry))
+}
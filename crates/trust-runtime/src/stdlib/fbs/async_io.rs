@@ -0,0 +1,235 @@
+use std::path::PathBuf;
+
+use crate::error::RuntimeError;
+use crate::eval::EvalContext;
+use crate::memory::InstanceId;
+use crate::value::Value;
+use crate::workers::{FileMode, JobOutcome, JobRequest, DEFAULT_TCP_TIMEOUT};
+
+use super::instance::{
+    get_or_init_bool, read_bool, read_text, set_instance_value, write_bool, write_text,
+};
+use super::state::{STATE_JOB, STATE_PREV_EXECUTE};
+
+pub(super) fn exec_file_read_async(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+) -> Result<(), RuntimeError> {
+    let event = run_job(ctx, instance_id, |ctx| {
+        Ok(JobRequest::FileRead {
+            path: PathBuf::from(read_text(ctx, instance_id, "PATH")?),
+        })
+    })?;
+    write_result_text(ctx, instance_id, "DATA", event);
+    Ok(())
+}
+
+pub(super) fn exec_file_write_async(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+) -> Result<(), RuntimeError> {
+    run_job(ctx, instance_id, |ctx| {
+        Ok(JobRequest::FileWrite {
+            path: PathBuf::from(read_text(ctx, instance_id, "PATH")?),
+            data: read_text(ctx, instance_id, "DATA")?,
+            append: read_bool(ctx, instance_id, "APPEND")?,
+        })
+    })?;
+    Ok(())
+}
+
+pub(super) fn exec_tcp_client(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+) -> Result<(), RuntimeError> {
+    let event = run_job(ctx, instance_id, |ctx| {
+        let timeout = match ctx.storage.get_instance_var(instance_id, "TIMEOUT") {
+            Some(Value::Time(value)) if value.as_nanos() > 0 => {
+                std::time::Duration::from_nanos(value.as_nanos() as u64)
+            }
+            Some(Value::Time(_) | Value::Null) | None => DEFAULT_TCP_TIMEOUT,
+            Some(_) => return Err(RuntimeError::TypeMismatch),
+        };
+        Ok(JobRequest::TcpExchange {
+            address: read_text(ctx, instance_id, "HOST")?,
+            payload: read_text(ctx, instance_id, "SEND")?,
+            timeout,
+        })
+    })?;
+    write_result_text(ctx, instance_id, "RECEIVED", event);
+    Ok(())
+}
+
+pub(super) fn exec_file_open(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+) -> Result<(), RuntimeError> {
+    let event = run_job(ctx, instance_id, |ctx| {
+        let mode = if read_bool(ctx, instance_id, "APPEND")? {
+            FileMode::Append
+        } else if read_bool(ctx, instance_id, "WRITE")? {
+            FileMode::Write
+        } else {
+            FileMode::Read
+        };
+        Ok(JobRequest::FileOpen {
+            path: PathBuf::from(read_text(ctx, instance_id, "PATH")?),
+            mode,
+        })
+    })?;
+    match event {
+        JobEvent::Submitted => set_instance_value(ctx, instance_id, "HANDLE", Value::UDInt(0)),
+        JobEvent::Finished(JobOutcome::Opened(handle)) => {
+            set_instance_value(ctx, instance_id, "HANDLE", Value::UDInt(handle));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+pub(super) fn exec_file_read(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+) -> Result<(), RuntimeError> {
+    let event = run_job(ctx, instance_id, |ctx| {
+        Ok(JobRequest::FileReadLine {
+            handle: read_handle(ctx, instance_id)?,
+        })
+    })?;
+    match event {
+        JobEvent::Submitted => write_bool(ctx, instance_id, "EOF", false),
+        JobEvent::Finished(JobOutcome::EndOfFile) => write_bool(ctx, instance_id, "EOF", true),
+        _ => {}
+    }
+    write_result_text(ctx, instance_id, "DATA", event);
+    Ok(())
+}
+
+pub(super) fn exec_file_write(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+) -> Result<(), RuntimeError> {
+    run_job(ctx, instance_id, |ctx| {
+        Ok(JobRequest::FileWriteText {
+            handle: read_handle(ctx, instance_id)?,
+            data: read_text(ctx, instance_id, "DATA")?,
+        })
+    })?;
+    Ok(())
+}
+
+pub(super) fn exec_file_close(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+) -> Result<(), RuntimeError> {
+    run_job(ctx, instance_id, |ctx| {
+        Ok(JobRequest::FileClose {
+            handle: read_handle(ctx, instance_id)?,
+        })
+    })?;
+    Ok(())
+}
+
+/// What a call of [`run_job`] did, for the block-specific outputs.
+enum JobEvent {
+    /// Nothing changed: idle or still busy.
+    Idle,
+    /// A new job was queued on this call.
+    Submitted,
+    /// The job finished on this call.
+    Finished(JobOutcome),
+}
+
+/// Shared EXECUTE/DONE/BUSY/ERROR handshake of the async function blocks.
+///
+/// A rising edge on EXECUTE submits the job; later calls poll for the outcome.
+/// DONE and ERROR stay set while EXECUTE is TRUE, or for one call if EXECUTE
+/// already dropped when the job finished.
+fn run_job(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+    request: impl FnOnce(&EvalContext<'_>) -> Result<JobRequest, RuntimeError>,
+) -> Result<JobEvent, RuntimeError> {
+    let execute = read_bool(ctx, instance_id, "EXECUTE")?;
+    let prev_execute = get_or_init_bool(ctx, instance_id, STATE_PREV_EXECUTE, false)?;
+    let mut job = read_job(ctx, instance_id)?;
+    let mut done = read_bool(ctx, instance_id, "DONE")?;
+    let mut error = read_bool(ctx, instance_id, "ERROR")?;
+    let mut event = JobEvent::Idle;
+
+    if job == 0 && !execute {
+        done = false;
+        error = false;
+    }
+    if job == 0 && execute && !prev_execute {
+        done = false;
+        error = false;
+        write_text(ctx, instance_id, "STATUS", "");
+        event = JobEvent::Submitted;
+        let request = request(&*ctx)?;
+        match ctx.workers {
+            Some(workers) => job = workers.submit(request),
+            None => {
+                error = true;
+                write_text(
+                    ctx,
+                    instance_id,
+                    "STATUS",
+                    "no background workers available",
+                );
+            }
+        }
+    }
+    if job != 0 {
+        if let Some(outcome) = ctx.workers.and_then(|workers| workers.take(job)) {
+            job = 0;
+            match &outcome {
+                JobOutcome::Failed(reason) => {
+                    error = true;
+                    write_text(ctx, instance_id, "STATUS", reason);
+                }
+                _ => done = true,
+            }
+            event = JobEvent::Finished(outcome);
+        }
+    }
+
+    write_bool(ctx, instance_id, "DONE", done);
+    write_bool(ctx, instance_id, "BUSY", job != 0);
+    write_bool(ctx, instance_id, "ERROR", error);
+    write_bool(ctx, instance_id, STATE_PREV_EXECUTE, execute);
+    set_instance_value(ctx, instance_id, STATE_JOB, Value::ULInt(job));
+    Ok(event)
+}
+
+/// Clear a text result when a job starts and fill it in when it succeeds.
+fn write_result_text(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+    name: &str,
+    event: JobEvent,
+) {
+    match event {
+        JobEvent::Submitted | JobEvent::Finished(JobOutcome::EndOfFile) => {
+            write_text(ctx, instance_id, name, "");
+        }
+        JobEvent::Finished(JobOutcome::Done(text)) => write_text(ctx, instance_id, name, &text),
+        _ => {}
+    }
+}
+
+fn read_handle(ctx: &EvalContext<'_>, instance_id: InstanceId) -> Result<u32, RuntimeError> {
+    match ctx.storage.get_instance_var(instance_id, "HANDLE") {
+        Some(Value::UDInt(handle)) => Ok(*handle),
+        Some(Value::Null) | None => Ok(0),
+        _ => Err(RuntimeError::TypeMismatch),
+    }
+}
+
+fn read_job(ctx: &EvalContext<'_>, instance_id: InstanceId) -> Result<u64, RuntimeError> {
+    match ctx.storage.get_instance_var(instance_id, STATE_JOB) {
+        Some(Value::ULInt(value)) => Ok(*value),
+        Some(Value::Null) | None => Ok(0),
+        _ => Err(RuntimeError::TypeMismatch),
+    }
+}
@@ -2,7 +2,8 @@ use crate::error::RuntimeError;
 use crate::eval::EvalContext;
 use crate::memory::InstanceId;
 
-use super::instance::{read_bool, write_bool};
+use super::instance::{get_or_init_bool, read_bool, write_bool};
+use super::state::STATE_CLAIMED;
 
 #[derive(Debug, Clone)]
 pub struct Sr {
@@ -58,6 +59,37 @@ impl Default for Rs {
     }
 }
 
+/// Semaphore: BUSY reports whether the semaphore was already claimed before
+/// this call, so exactly one claimant sees BUSY = FALSE. CLAIM takes priority
+/// over a simultaneous RELEASE.
+#[derive(Debug, Clone)]
+pub struct Sema {
+    claimed: bool,
+}
+
+impl Sema {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { claimed: false }
+    }
+
+    pub fn step(&mut self, claim: bool, release: bool) -> bool {
+        let busy = self.claimed;
+        if claim {
+            self.claimed = true;
+        } else if release {
+            self.claimed = false;
+        }
+        busy
+    }
+}
+
+impl Default for Sema {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub(super) fn exec_rs(
     ctx: &mut EvalContext<'_>,
     instance_id: InstanceId,
@@ -89,3 +121,17 @@ pub(super) fn exec_sr(
     write_bool(ctx, instance_id, "Q1", q);
     Ok(())
 }
+
+pub(super) fn exec_sema(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+) -> Result<(), RuntimeError> {
+    let claim = read_bool(ctx, instance_id, "CLAIM")?;
+    let release = read_bool(ctx, instance_id, "RELEASE")?;
+    let claimed = get_or_init_bool(ctx, instance_id, STATE_CLAIMED, false)?;
+    let mut sema = Sema { claimed };
+    let busy = sema.step(claim, release);
+    write_bool(ctx, instance_id, "BUSY", busy);
+    write_bool(ctx, instance_id, STATE_CLAIMED, sema.claimed);
+    Ok(())
+}
@@ -0,0 +1,214 @@
+use crate::error::RuntimeError;
+use crate::eval::EvalContext;
+use crate::memory::InstanceId;
+use crate::numeric::to_u64;
+use crate::value::{ArrayValue, Value};
+use crate::workers::ChannelSpec;
+
+use super::instance::{
+    get_or_init_bool, read_bool, read_text, set_instance_value, write_bool, write_text,
+};
+use super::state::{STATE_CHANNEL, STATE_PREV_SEND};
+
+/// Baud rate used when `SERIAL_PORT.BAUD` is zero.
+const DEFAULT_BAUD: u32 = 9600;
+
+pub(super) fn exec_tcp_stream_client(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+) -> Result<(), RuntimeError> {
+    run_channel(ctx, instance_id, "CONNECTED", None, |ctx| {
+        Ok(ChannelSpec::TcpClient {
+            address: read_text(ctx, instance_id, "HOST")?,
+        })
+    })
+}
+
+pub(super) fn exec_tcp_stream_server(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+) -> Result<(), RuntimeError> {
+    run_channel(ctx, instance_id, "CONNECTED", Some("PEER"), |ctx| {
+        Ok(ChannelSpec::TcpServer {
+            port: read_unsigned(ctx, instance_id, "PORT")?,
+        })
+    })
+}
+
+pub(super) fn exec_udp_socket(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+) -> Result<(), RuntimeError> {
+    run_channel(ctx, instance_id, "OPEN", Some("PEER"), |ctx| {
+        Ok(ChannelSpec::Udp {
+            port: read_unsigned(ctx, instance_id, "PORT")?,
+            remote: read_text(ctx, instance_id, "REMOTE")?,
+        })
+    })
+}
+
+pub(super) fn exec_serial_port(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+) -> Result<(), RuntimeError> {
+    run_channel(ctx, instance_id, "OPEN", None, |ctx| {
+        let baud = read_unsigned(ctx, instance_id, "BAUD")?;
+        Ok(ChannelSpec::Serial {
+            device: read_text(ctx, instance_id, "DEVICE")?,
+            baud: if baud == 0 { DEFAULT_BAUD } else { baud },
+        })
+    })
+}
+
+/// Shared ENABLE/SEND handshake of the communication function blocks.
+///
+/// A TRUE ENABLE opens the channel with the parameters of that call and keeps it
+/// open; FALSE closes it. A rising edge on SEND queues the first TX_LEN bytes of
+/// TX_BUFFER. Every call copies whatever has arrived (one datagram for UDP) into
+/// RX_BUFFER and reports the count in RX_LEN, so RX_LEN is zero when nothing new
+/// came in.
+fn run_channel(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+    open_output: &str,
+    peer_output: Option<&str>,
+    spec: impl FnOnce(&EvalContext<'_>) -> Result<ChannelSpec, RuntimeError>,
+) -> Result<(), RuntimeError> {
+    let enable = read_bool(ctx, instance_id, "ENABLE")?;
+    let send = read_bool(ctx, instance_id, "SEND")?;
+    let prev_send = get_or_init_bool(ctx, instance_id, STATE_PREV_SEND, false)?;
+    let mut channel = read_unsigned::<u64>(ctx, instance_id, STATE_CHANNEL)?;
+    write_bool(ctx, instance_id, STATE_PREV_SEND, send);
+    set_instance_value(ctx, instance_id, "RX_LEN", Value::UInt(0));
+
+    let workers = ctx.workers.filter(|_| enable);
+    let Some(workers) = workers else {
+        if let Some(workers) = ctx.workers.filter(|_| channel != 0) {
+            workers.close_channel(channel);
+        }
+        set_instance_value(ctx, instance_id, STATE_CHANNEL, Value::ULInt(0));
+        write_bool(ctx, instance_id, open_output, false);
+        write_bool(ctx, instance_id, "ERROR", enable);
+        let status = if enable {
+            "no background workers available"
+        } else {
+            ""
+        };
+        write_text(ctx, instance_id, "STATUS", status);
+        return Ok(());
+    };
+
+    if channel == 0 {
+        channel = workers.open_channel(spec(&*ctx)?);
+        set_instance_value(ctx, instance_id, STATE_CHANNEL, Value::ULInt(channel));
+    }
+    let mut failure = None;
+    if send && !prev_send {
+        let bytes = read_tx_bytes(ctx, instance_id)?;
+        if let Err(reason) = workers.channel_send(channel, bytes) {
+            failure = Some(reason);
+        }
+    }
+    let capacity = match buffer_value(ctx, instance_id, "RX_BUFFER") {
+        Some(Value::Array(array)) => array.elements.len(),
+        _ => 0,
+    };
+    let received = workers.channel_receive(channel, capacity);
+    let status = workers.channel_status(channel).unwrap_or_default();
+
+    let mut received_peer = None;
+    if let Some(data) = received {
+        write_rx_bytes(ctx, instance_id, &data.bytes)?;
+        let count = u16::try_from(data.bytes.len()).unwrap_or(u16::MAX);
+        set_instance_value(ctx, instance_id, "RX_LEN", Value::UInt(count));
+        received_peer = data.peer;
+    }
+    if let Some(output) = peer_output {
+        // UDP reports the sender of the latest datagram, TCP the connected peer.
+        match received_peer.or(status.peer) {
+            Some(peer) => write_text(ctx, instance_id, output, &peer),
+            None if !status.open => write_text(ctx, instance_id, output, ""),
+            None => {}
+        }
+    }
+    let error = failure.or(status.error);
+    write_bool(ctx, instance_id, open_output, status.open);
+    write_bool(ctx, instance_id, "ERROR", error.is_some());
+    write_text(ctx, instance_id, "STATUS", error.as_deref().unwrap_or(""));
+    Ok(())
+}
+
+fn read_unsigned<T: TryFrom<u64>>(
+    ctx: &EvalContext<'_>,
+    instance_id: InstanceId,
+    name: &str,
+) -> Result<T, RuntimeError> {
+    let value = match ctx.storage.get_instance_var(instance_id, name) {
+        Some(Value::Null) | None => 0,
+        Some(value) => to_u64(value)?,
+    };
+    T::try_from(value).map_err(|_| RuntimeError::Overflow)
+}
+
+fn read_tx_bytes(ctx: &EvalContext<'_>, instance_id: InstanceId) -> Result<Vec<u8>, RuntimeError> {
+    let len = read_unsigned::<usize>(ctx, instance_id, "TX_LEN")?;
+    let Some(array) = read_buffer(ctx, instance_id, "TX_BUFFER")? else {
+        return Ok(Vec::new());
+    };
+    array
+        .elements
+        .iter()
+        .take(len)
+        .map(|element| match element {
+            Value::Byte(byte) | Value::USInt(byte) => Ok(*byte),
+            _ => Err(RuntimeError::TypeMismatch),
+        })
+        .collect()
+}
+
+fn write_rx_bytes(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+    bytes: &[u8],
+) -> Result<(), RuntimeError> {
+    let Some(mut array) = read_buffer(ctx, instance_id, "RX_BUFFER")? else {
+        return Ok(());
+    };
+    for (element, byte) in array.elements.iter_mut().zip(bytes) {
+        *element = Value::Byte(*byte);
+    }
+    let value = Value::Array(array);
+    match ctx.storage.get_alias("RX_BUFFER").cloned() {
+        Some(reference) => {
+            if !ctx.storage.write_by_ref(reference, value) {
+                return Err(RuntimeError::NullReference);
+            }
+        }
+        None => set_instance_value(ctx, instance_id, "RX_BUFFER", value),
+    }
+    Ok(())
+}
+
+/// In-out byte buffer, through the caller's alias when bound by reference.
+fn buffer_value<'a>(
+    ctx: &'a EvalContext<'_>,
+    instance_id: InstanceId,
+    name: &str,
+) -> Option<&'a Value> {
+    match ctx.storage.get_alias(name) {
+        Some(reference) => ctx.storage.read_by_ref(reference.clone()),
+        None => ctx.storage.get_instance_var(instance_id, name),
+    }
+}
+
+fn read_buffer(
+    ctx: &EvalContext<'_>,
+    instance_id: InstanceId,
+    name: &str,
+) -> Result<Option<ArrayValue>, RuntimeError> {
+    match buffer_value(ctx, instance_id, name) {
+        Some(Value::Array(array)) => Ok(Some(array.clone())),
+        Some(Value::Null) | None => Ok(None),
+        Some(_) => Err(RuntimeError::TypeMismatch),
+    }
+}
@@ -1,3 +1,5 @@
+use smol_str::SmolStr;
+
 use crate::error::RuntimeError;
 use crate::eval::EvalContext;
 use crate::memory::InstanceId;
@@ -72,3 +74,25 @@ pub(super) fn set_instance_value(
 ) {
     ctx.storage.set_instance_var(instance_id, name, value);
 }
+
+pub(super) fn read_text(
+    ctx: &EvalContext<'_>,
+    instance_id: InstanceId,
+    name: &str,
+) -> Result<String, RuntimeError> {
+    match ctx.storage.get_instance_var(instance_id, name) {
+        Some(Value::String(value)) => Ok(value.to_string()),
+        Some(Value::WString(value)) => Ok(value.clone()),
+        Some(Value::Null) | None => Ok(String::new()),
+        _ => Err(RuntimeError::TypeMismatch),
+    }
+}
+
+pub(super) fn write_text(
+    ctx: &mut EvalContext<'_>,
+    instance_id: InstanceId,
+    name: &str,
+    text: &str,
+) {
+    set_instance_value(ctx, instance_id, name, Value::String(SmolStr::new(text)));
+}
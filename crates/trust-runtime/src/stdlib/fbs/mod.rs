@@ -1,8 +1,10 @@
-//! Standard function blocks (TON, CTU, etc.).
+//! Standard function blocks (TON, CTU, etc.), async I/O, and communication blocks.
 
 #![allow(missing_docs)]
 
+mod async_io;
 mod bistable;
+mod comm;
 mod counters;
 mod instance;
 mod registry;
@@ -10,7 +12,7 @@ mod state;
 mod timers;
 mod triggers;
 
-pub use bistable::{Rs, Sr};
+pub use bistable::{Rs, Sema, Sr};
 pub use counters::{CounterOutput, CounterUpDownOutput, Ctd, Ctu, Ctud};
 pub use registry::{builtin_kind, standard_function_blocks, BuiltinFbKind};
 pub use timers::{TimerOutput, Tof, Ton, Tp};
@@ -28,6 +30,7 @@ pub fn execute_builtin(
     match kind {
         BuiltinFbKind::Rs => bistable::exec_rs(ctx, instance_id),
         BuiltinFbKind::Sr => bistable::exec_sr(ctx, instance_id),
+        BuiltinFbKind::Sema => bistable::exec_sema(ctx, instance_id),
         BuiltinFbKind::RTrig => triggers::exec_r_trig(ctx, instance_id),
         BuiltinFbKind::FTrig => triggers::exec_f_trig(ctx, instance_id),
         BuiltinFbKind::Ctu => counters::exec_ctu(ctx, instance_id),
@@ -36,5 +39,16 @@ pub fn execute_builtin(
         BuiltinFbKind::Tp => timers::exec_tp(ctx, instance_id),
         BuiltinFbKind::Ton => timers::exec_ton(ctx, instance_id),
         BuiltinFbKind::Tof => timers::exec_tof(ctx, instance_id),
+        BuiltinFbKind::FileReadAsync => async_io::exec_file_read_async(ctx, instance_id),
+        BuiltinFbKind::FileWriteAsync => async_io::exec_file_write_async(ctx, instance_id),
+        BuiltinFbKind::TcpClient => async_io::exec_tcp_client(ctx, instance_id),
+        BuiltinFbKind::FileOpen => async_io::exec_file_open(ctx, instance_id),
+        BuiltinFbKind::FileRead => async_io::exec_file_read(ctx, instance_id),
+        BuiltinFbKind::FileWrite => async_io::exec_file_write(ctx, instance_id),
+        BuiltinFbKind::FileClose => async_io::exec_file_close(ctx, instance_id),
+        BuiltinFbKind::TcpStreamClient => comm::exec_tcp_stream_client(ctx, instance_id),
+        BuiltinFbKind::TcpStreamServer => comm::exec_tcp_stream_server(ctx, instance_id),
+        BuiltinFbKind::UdpSocket => comm::exec_udp_socket(ctx, instance_id),
+        BuiltinFbKind::SerialPort => comm::exec_serial_port(ctx, instance_id),
     }
 }
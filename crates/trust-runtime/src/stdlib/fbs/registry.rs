@@ -6,6 +6,7 @@ use trust_hir::TypeId;
 pub enum BuiltinFbKind {
     Rs,
     Sr,
+    Sema,
     RTrig,
     FTrig,
     Ctu,
@@ -14,6 +15,17 @@ pub enum BuiltinFbKind {
     Tp,
     Ton,
     Tof,
+    FileReadAsync,
+    FileWriteAsync,
+    TcpClient,
+    FileOpen,
+    FileRead,
+    FileWrite,
+    FileClose,
+    TcpStreamClient,
+    TcpStreamServer,
+    UdpSocket,
+    SerialPort,
 }
 
 pub fn builtin_kind(name: &str) -> Option<BuiltinFbKind> {
@@ -21,6 +33,7 @@ pub fn builtin_kind(name: &str) -> Option<BuiltinFbKind> {
     match upper.as_str() {
         "RS" => Some(BuiltinFbKind::Rs),
         "SR" => Some(BuiltinFbKind::Sr),
+        "SEMA" => Some(BuiltinFbKind::Sema),
         "R_TRIG" | "DIFU" => Some(BuiltinFbKind::RTrig),
         "F_TRIG" | "DIFD" => Some(BuiltinFbKind::FTrig),
         "CTU" | "CTU_INT" | "CTU_DINT" | "CTU_LINT" | "CTU_UDINT" | "CTU_ULINT" => {
@@ -35,6 +48,17 @@ pub fn builtin_kind(name: &str) -> Option<BuiltinFbKind> {
         "TP" | "TP_LTIME" => Some(BuiltinFbKind::Tp),
         "TON" | "TON_LTIME" => Some(BuiltinFbKind::Ton),
         "TOF" | "TOF_LTIME" => Some(BuiltinFbKind::Tof),
+        "FILE_READ_ASYNC" => Some(BuiltinFbKind::FileReadAsync),
+        "FILE_WRITE_ASYNC" => Some(BuiltinFbKind::FileWriteAsync),
+        "TCP_CLIENT" => Some(BuiltinFbKind::TcpClient),
+        "FILE_OPEN" => Some(BuiltinFbKind::FileOpen),
+        "FILE_READ" => Some(BuiltinFbKind::FileRead),
+        "FILE_WRITE" => Some(BuiltinFbKind::FileWrite),
+        "FILE_CLOSE" => Some(BuiltinFbKind::FileClose),
+        "TCP_STREAM_CLIENT" => Some(BuiltinFbKind::TcpStreamClient),
+        "TCP_STREAM_SERVER" => Some(BuiltinFbKind::TcpStreamServer),
+        "UDP_SOCKET" => Some(BuiltinFbKind::UdpSocket),
+        "SERIAL_PORT" => Some(BuiltinFbKind::SerialPort),
         _ => None,
     }
 }
@@ -79,6 +103,14 @@ pub fn standard_function_blocks() -> Vec<FunctionBlockDef> {
                 ("Q1", TypeId::BOOL, ParamDirection::Out),
             ],
         ),
+        fb(
+            "SEMA",
+            &[
+                ("CLAIM", TypeId::BOOL, ParamDirection::In),
+                ("RELEASE", TypeId::BOOL, ParamDirection::In),
+                ("BUSY", TypeId::BOOL, ParamDirection::Out),
+            ],
+        ),
         fb(
             "R_TRIG",
             &[
@@ -175,5 +207,147 @@ pub fn standard_function_blocks() -> Vec<FunctionBlockDef> {
         ));
     }
 
+    defs.push(fb(
+        "FILE_READ_ASYNC",
+        &[
+            ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+            ("PATH", TypeId::STRING, ParamDirection::In),
+            ("DONE", TypeId::BOOL, ParamDirection::Out),
+            ("BUSY", TypeId::BOOL, ParamDirection::Out),
+            ("ERROR", TypeId::BOOL, ParamDirection::Out),
+            ("STATUS", TypeId::STRING, ParamDirection::Out),
+            ("DATA", TypeId::STRING, ParamDirection::Out),
+        ],
+    ));
+    defs.push(fb(
+        "FILE_WRITE_ASYNC",
+        &[
+            ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+            ("PATH", TypeId::STRING, ParamDirection::In),
+            ("DATA", TypeId::STRING, ParamDirection::In),
+            ("APPEND", TypeId::BOOL, ParamDirection::In),
+            ("DONE", TypeId::BOOL, ParamDirection::Out),
+            ("BUSY", TypeId::BOOL, ParamDirection::Out),
+            ("ERROR", TypeId::BOOL, ParamDirection::Out),
+            ("STATUS", TypeId::STRING, ParamDirection::Out),
+        ],
+    ));
+    defs.push(fb(
+        "TCP_CLIENT",
+        &[
+            ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+            ("HOST", TypeId::STRING, ParamDirection::In),
+            ("SEND", TypeId::STRING, ParamDirection::In),
+            ("TIMEOUT", TypeId::TIME, ParamDirection::In),
+            ("DONE", TypeId::BOOL, ParamDirection::Out),
+            ("BUSY", TypeId::BOOL, ParamDirection::Out),
+            ("ERROR", TypeId::BOOL, ParamDirection::Out),
+            ("STATUS", TypeId::STRING, ParamDirection::Out),
+            ("RECEIVED", TypeId::STRING, ParamDirection::Out),
+        ],
+    ));
+
+    defs.push(fb(
+        "FILE_OPEN",
+        &[
+            ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+            ("PATH", TypeId::STRING, ParamDirection::In),
+            ("WRITE", TypeId::BOOL, ParamDirection::In),
+            ("APPEND", TypeId::BOOL, ParamDirection::In),
+            ("DONE", TypeId::BOOL, ParamDirection::Out),
+            ("BUSY", TypeId::BOOL, ParamDirection::Out),
+            ("ERROR", TypeId::BOOL, ParamDirection::Out),
+            ("STATUS", TypeId::STRING, ParamDirection::Out),
+            ("HANDLE", TypeId::UDINT, ParamDirection::Out),
+        ],
+    ));
+    defs.push(fb(
+        "FILE_READ",
+        &[
+            ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+            ("HANDLE", TypeId::UDINT, ParamDirection::In),
+            ("DONE", TypeId::BOOL, ParamDirection::Out),
+            ("BUSY", TypeId::BOOL, ParamDirection::Out),
+            ("ERROR", TypeId::BOOL, ParamDirection::Out),
+            ("STATUS", TypeId::STRING, ParamDirection::Out),
+            ("DATA", TypeId::STRING, ParamDirection::Out),
+            ("EOF", TypeId::BOOL, ParamDirection::Out),
+        ],
+    ));
+    defs.push(fb(
+        "FILE_WRITE",
+        &[
+            ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+            ("HANDLE", TypeId::UDINT, ParamDirection::In),
+            ("DATA", TypeId::STRING, ParamDirection::In),
+            ("DONE", TypeId::BOOL, ParamDirection::Out),
+            ("BUSY", TypeId::BOOL, ParamDirection::Out),
+            ("ERROR", TypeId::BOOL, ParamDirection::Out),
+            ("STATUS", TypeId::STRING, ParamDirection::Out),
+        ],
+    ));
+    defs.push(fb(
+        "FILE_CLOSE",
+        &[
+            ("EXECUTE", TypeId::BOOL, ParamDirection::In),
+            ("HANDLE", TypeId::UDINT, ParamDirection::In),
+            ("DONE", TypeId::BOOL, ParamDirection::Out),
+            ("BUSY", TypeId::BOOL, ParamDirection::Out),
+            ("ERROR", TypeId::BOOL, ParamDirection::Out),
+            ("STATUS", TypeId::STRING, ParamDirection::Out),
+        ],
+    ));
+
+    // Communication blocks. The byte buffers are `ARRAY[*] OF BYTE` in-outs; the
+    // semantic layer checks them, so the runtime only needs a placeholder type.
+    type Fields<'a> = &'a [(&'a str, TypeId)];
+    let comm_blocks: [(&str, Fields, Fields); 4] = [
+        (
+            "TCP_STREAM_CLIENT",
+            &[("HOST", TypeId::STRING)],
+            &[("CONNECTED", TypeId::BOOL)],
+        ),
+        (
+            "TCP_STREAM_SERVER",
+            &[("PORT", TypeId::UINT)],
+            &[("CONNECTED", TypeId::BOOL), ("PEER", TypeId::STRING)],
+        ),
+        (
+            "UDP_SOCKET",
+            &[("PORT", TypeId::UINT), ("REMOTE", TypeId::STRING)],
+            &[("OPEN", TypeId::BOOL), ("PEER", TypeId::STRING)],
+        ),
+        (
+            "SERIAL_PORT",
+            &[("DEVICE", TypeId::STRING), ("BAUD", TypeId::UDINT)],
+            &[("OPEN", TypeId::BOOL)],
+        ),
+    ];
+    for (name, inputs, outputs) in comm_blocks {
+        let mut params = vec![("ENABLE", TypeId::BOOL, ParamDirection::In)];
+        params.extend(
+            inputs
+                .iter()
+                .map(|(param, type_id)| (*param, *type_id, ParamDirection::In)),
+        );
+        params.extend([
+            ("SEND", TypeId::BOOL, ParamDirection::In),
+            ("TX_LEN", TypeId::UINT, ParamDirection::In),
+            ("TX_BUFFER", TypeId::ANY, ParamDirection::InOut),
+            ("RX_BUFFER", TypeId::ANY, ParamDirection::InOut),
+        ]);
+        params.extend(
+            outputs
+                .iter()
+                .map(|(param, type_id)| (*param, *type_id, ParamDirection::Out)),
+        );
+        params.extend([
+            ("RX_LEN", TypeId::UINT, ParamDirection::Out),
+            ("ERROR", TypeId::BOOL, ParamDirection::Out),
+            ("STATUS", TypeId::STRING, ParamDirection::Out),
+        ]);
+        defs.push(fb(name, &params));
+    }
+
     defs
 }
@@ -1,7 +1,12 @@
 pub(super) const STATE_PREV_CU: &str = "__ST_PREV_CU";
 pub(super) const STATE_PREV_CD: &str = "__ST_PREV_CD";
+pub(super) const STATE_CLAIMED: &str = "__ST_CLAIMED";
 pub(super) const STATE_TRIG_M: &str = "__ST_TRIG_M";
 pub(super) const STATE_LAST_TIME: &str = "__ST_LAST_TIME";
 pub(super) const STATE_PREV_IN: &str = "__ST_PREV_IN";
 pub(super) const STATE_TIMING: &str = "__ST_TIMING";
 pub(super) const STATE_ACTIVE: &str = "__ST_ACTIVE";
+pub(super) const STATE_PREV_EXECUTE: &str = "__ST_PREV_EXECUTE";
+pub(super) const STATE_JOB: &str = "__ST_JOB";
+pub(super) const STATE_PREV_SEND: &str = "__ST_PREV_SEND";
+pub(super) const STATE_CHANNEL: &str = "__ST_CHANNEL";
@@ -33,8 +33,7 @@ impl Ton {
             self.et = Duration::ZERO;
             self.q = false;
         } else {
-            let next = self.et.as_nanos() + delta.as_nanos();
-            self.et = Duration::from_nanos(next);
+            self.et = advance(self.et, delta);
             self.q = self.et.as_nanos() >= pt.as_nanos();
         }
         let et = if self.et.as_nanos() >= pt.as_nanos() {
@@ -83,8 +82,7 @@ impl Tof {
                 self.et = Duration::ZERO;
             }
             if self.timing {
-                let next = self.et.as_nanos() + delta.as_nanos();
-                self.et = Duration::from_nanos(next);
+                self.et = advance(self.et, delta);
                 if self.et.as_nanos() >= pt.as_nanos() {
                     self.q = false;
                     self.timing = false;
@@ -139,8 +137,7 @@ impl Tp {
             self.et = Duration::ZERO;
         }
         if self.active {
-            let next = self.et.as_nanos() + delta.as_nanos();
-            self.et = Duration::from_nanos(next);
+            self.et = advance(self.et, delta);
             if self.et.as_nanos() >= pt.as_nanos() {
                 self.active = false;
                 self.et = pt;
@@ -225,6 +222,12 @@ pub(super) fn exec_tp(
     Ok(())
 }
 
+/// Accumulate elapsed time; saturates so a large clock jump (e.g. under a high
+/// simulation time scale) cannot wrap ET around to a negative value.
+fn advance(et: Duration, delta: Duration) -> Duration {
+    Duration::from_nanos(et.as_nanos().saturating_add(delta.as_nanos()))
+}
+
 fn normalize_duration(value: Duration) -> Duration {
     if value.as_nanos() < 0 {
         Duration::ZERO
@@ -278,7 +281,9 @@ fn elapsed_since(
     instance_id: InstanceId,
 ) -> Result<Duration, RuntimeError> {
     let last = get_or_init_duration(ctx, instance_id, STATE_LAST_TIME, ctx.now)?;
-    let delta_nanos = ctx.now.as_nanos() - last.as_nanos();
+    // A clock that stepped backwards (restart, reset simulation time) counts as
+    // no elapsed time rather than a huge or negative delta.
+    let delta_nanos = ctx.now.as_nanos().saturating_sub(last.as_nanos());
     let delta = if delta_nanos <= 0 {
         Duration::ZERO
     } else {
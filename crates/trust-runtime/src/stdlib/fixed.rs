@@ -0,0 +1,93 @@
+//! Q16.16 fixed-point standard functions.
+//!
+//! Fixed-point values travel through ST as their raw DINT representation.
+
+#![allow(missing_docs)]
+
+use crate::error::RuntimeError;
+use crate::stdlib::helpers::{require_arity, to_f64, to_i64};
+use crate::stdlib::StandardLibrary;
+use crate::value::{Fixed, Value};
+
+pub fn register(lib: &mut StandardLibrary) {
+    lib.register("TO_FIX", &["IN"], to_fix);
+    lib.register("FIX_TO_DINT", &["IN"], fix_to_dint);
+    lib.register("FIX_TO_REAL", &["IN"], fix_to_real);
+    lib.register("FIX_TO_LREAL", &["IN"], fix_to_lreal);
+    lib.register("FIX_ADD", &["IN1", "IN2"], fix_add);
+    lib.register("FIX_SUB", &["IN1", "IN2"], fix_sub);
+    lib.register("FIX_MUL", &["IN1", "IN2"], fix_mul);
+    lib.register("FIX_DIV", &["IN1", "IN2"], fix_div);
+    lib.register("FIX_ABS", &["IN"], fix_abs);
+    lib.register("FIX_SQRT", &["IN"], fix_sqrt);
+}
+
+fn fixed_arg(value: &Value) -> Result<Fixed, RuntimeError> {
+    let raw = i32::try_from(to_i64(value)?).map_err(|_| RuntimeError::Overflow)?;
+    Ok(Fixed::from_raw(raw))
+}
+
+fn fixed_value(value: Fixed) -> Value {
+    Value::DInt(value.raw())
+}
+
+fn to_fix(args: &[Value]) -> Result<Value, RuntimeError> {
+    require_arity(args, 1)?;
+    let fixed = match &args[0] {
+        Value::Real(_) | Value::LReal(_) => {
+            Fixed::from_f64(to_f64(&args[0])?).ok_or(RuntimeError::Overflow)?
+        }
+        other => Fixed::from_int(to_i64(other)?),
+    };
+    Ok(fixed_value(fixed))
+}
+
+fn fix_to_dint(args: &[Value]) -> Result<Value, RuntimeError> {
+    require_arity(args, 1)?;
+    Ok(Value::DInt(fixed_arg(&args[0])?.round_to_int()))
+}
+
+fn fix_to_real(args: &[Value]) -> Result<Value, RuntimeError> {
+    require_arity(args, 1)?;
+    Ok(Value::Real(fixed_arg(&args[0])?.to_f64() as f32))
+}
+
+fn fix_to_lreal(args: &[Value]) -> Result<Value, RuntimeError> {
+    require_arity(args, 1)?;
+    Ok(Value::LReal(fixed_arg(&args[0])?.to_f64()))
+}
+
+fn fix_binary(args: &[Value], op: fn(Fixed, Fixed) -> Fixed) -> Result<Value, RuntimeError> {
+    require_arity(args, 2)?;
+    Ok(fixed_value(op(fixed_arg(&args[0])?, fixed_arg(&args[1])?)))
+}
+
+fn fix_add(args: &[Value]) -> Result<Value, RuntimeError> {
+    fix_binary(args, Fixed::saturating_add)
+}
+
+fn fix_sub(args: &[Value]) -> Result<Value, RuntimeError> {
+    fix_binary(args, Fixed::saturating_sub)
+}
+
+fn fix_mul(args: &[Value]) -> Result<Value, RuntimeError> {
+    fix_binary(args, Fixed::saturating_mul)
+}
+
+fn fix_div(args: &[Value]) -> Result<Value, RuntimeError> {
+    require_arity(args, 2)?;
+    let quotient = fixed_arg(&args[0])?
+        .checked_div(fixed_arg(&args[1])?)
+        .ok_or(RuntimeError::DivisionByZero)?;
+    Ok(fixed_value(quotient))
+}
+
+fn fix_abs(args: &[Value]) -> Result<Value, RuntimeError> {
+    require_arity(args, 1)?;
+    Ok(fixed_value(fixed_arg(&args[0])?.saturating_abs()))
+}
+
+fn fix_sqrt(args: &[Value]) -> Result<Value, RuntimeError> {
+    require_arity(args, 1)?;
+    Ok(fixed_value(fixed_arg(&args[0])?.sqrt()))
+}
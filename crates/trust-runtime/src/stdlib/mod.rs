@@ -1,10 +1,13 @@
 //! Standard library registry.
 
+pub mod array;
 pub mod assertions;
 pub mod bit;
+pub mod byte_order;
 pub mod comparison;
 pub mod conversions;
 pub mod fbs;
+pub mod fixed;
 pub mod helpers;
 pub mod numeric;
 pub mod selection;
@@ -63,8 +66,11 @@ impl StandardLibrary {
         };
         assertions::register(&mut lib);
         numeric::register(&mut lib);
+        fixed::register(&mut lib);
         bit::register(&mut lib);
+        byte_order::register(&mut lib);
         selection::register(&mut lib);
+        array::register(&mut lib);
         comparison::register(&mut lib);
         string::register(&mut lib);
         time::register(&mut lib);
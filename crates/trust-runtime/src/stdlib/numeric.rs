@@ -3,7 +3,7 @@
 #![allow(missing_docs)]
 
 use crate::error::RuntimeError;
-use crate::eval::ops::{apply_binary, BinaryOp};
+use crate::eval::ops::{apply_binary, apply_binary_with, BinaryOp, OverflowPolicy};
 use crate::stdlib::helpers::{
     require_arity, require_min, scale_time, signed_from_i128, to_f64, to_i64, to_u64,
     unsigned_from_u128, wider_numeric, NumericKind,
@@ -135,6 +135,33 @@ fn unary_real(args: &[Value], f: impl Fn(f64) -> f64) -> Result<Value, RuntimeEr
     }
 }
 
+/// Calls `ADD`, `SUB` or `MUL` on numeric operands with the given integer overflow
+/// policy. Returns `None` for other functions and for time operands.
+pub fn call_with_overflow(
+    name: &str,
+    args: &[Value],
+    overflow: OverflowPolicy,
+) -> Option<Result<Value, RuntimeError>> {
+    let op = match name.to_ascii_uppercase().as_str() {
+        "ADD" => BinaryOp::Add,
+        "SUB" if args.len() == 2 => BinaryOp::Sub,
+        "MUL" => BinaryOp::Mul,
+        _ => return None,
+    };
+    if args.len() < 2 || args.iter().any(is_time_related) {
+        return None;
+    }
+    let profile = DateTimeProfile::default();
+    let mut acc = args[0].clone();
+    for value in &args[1..] {
+        acc = match apply_binary_with(op, acc, value.clone(), &profile, overflow) {
+            Ok(value) => value,
+            Err(err) => return Some(Err(err)),
+        };
+    }
+    Some(Ok(acc))
+}
+
 fn add(args: &[Value]) -> Result<Value, RuntimeError> {
     require_min(args, 2)?;
     let profile = DateTimeProfile::default();
@@ -1,4 +1,9 @@
 //! String standard functions.
+//!
+//! STRING and WSTRING share one implementation. Lengths and positions count
+//! characters, so no operation can split a multi-byte character. A position
+//! outside the string is a runtime fault unless the task clamps out-of-bounds
+//! accesses (see [`call_clamped`]).
 
 #![allow(missing_docs)]
 
@@ -20,313 +25,227 @@ pub fn register(lib: &mut StandardLibrary) {
     lib.register("FIND", &["IN1", "IN2"], find);
 }
 
-fn len(args: &[Value]) -> Result<Value, RuntimeError> {
-    require_arity(args, 1)?;
-    let length = match &args[0] {
-        Value::String(value) => value.len(),
-        Value::WString(value) => value.chars().count(),
-        _ => return Err(RuntimeError::TypeMismatch),
+/// Calls a string function that takes a position with the position clamped into
+/// the string instead of faulting. Returns `None` for other functions.
+pub fn call_clamped(name: &str, args: &[Value]) -> Option<Result<Value, RuntimeError>> {
+    let result = match name.to_ascii_uppercase().as_str() {
+        "MID" => mid_with(args, true),
+        "INSERT" => insert_with(args, true),
+        "DELETE" => delete_with(args, true),
+        "REPLACE" => replace_with(args, true),
+        _ => return None,
     };
-    if length > i16::MAX as usize {
-        return Err(RuntimeError::Overflow);
+    Some(result)
+}
+
+/// Truncates a string value to `max_len` characters.
+///
+/// Used when assigning to a `STRING[n]`/`WSTRING[n]` target.
+#[must_use]
+pub fn truncate_string(value: Value, max_len: usize) -> Value {
+    match value {
+        Value::String(text) if text.chars().count() > max_len => {
+            Value::String(text.chars().take(max_len).collect())
+        }
+        Value::WString(text) if text.chars().count() > max_len => {
+            Value::WString(text.chars().take(max_len).collect())
+        }
+        other => other,
     }
-    Ok(Value::Int(length as i16))
 }
 
-fn left(args: &[Value]) -> Result<Value, RuntimeError> {
-    require_arity(args, 2)?;
-    let count = to_i64(&args[1])?;
-    match &args[0] {
-        Value::String(value) => {
-            let bytes = value.as_bytes();
-            let len = bytes.len();
-            let take = if count <= 0 {
-                0
-            } else {
-                count.min(len as i64) as usize
-            };
-            let result =
-                std::str::from_utf8(&bytes[..take]).map_err(|_| RuntimeError::TypeMismatch)?;
-            Ok(Value::String(SmolStr::new(result)))
-        }
-        Value::WString(value) => {
-            let take = if count <= 0 { 0 } else { count as usize };
-            let result: String = value.chars().take(take).collect();
-            Ok(Value::WString(result))
-        }
+/// Returns the text of a STRING/WSTRING argument and whether it is wide.
+fn text_arg(value: &Value) -> Result<(&str, bool), RuntimeError> {
+    match value {
+        Value::String(text) => Ok((text.as_str(), false)),
+        Value::WString(text) => Ok((text.as_str(), true)),
         _ => Err(RuntimeError::TypeMismatch),
     }
 }
 
+/// Returns the text of two arguments that must share the same string type.
+fn text_pair<'a>(in1: &'a Value, in2: &'a Value) -> Result<(&'a str, &'a str, bool), RuntimeError> {
+    let (in1, wide) = text_arg(in1)?;
+    let (in2, other_wide) = text_arg(in2)?;
+    if wide != other_wide {
+        return Err(RuntimeError::TypeMismatch);
+    }
+    Ok((in1, in2, wide))
+}
+
+fn text_value(text: String, wide: bool) -> Value {
+    if wide {
+        Value::WString(text)
+    } else {
+        Value::String(SmolStr::new(text))
+    }
+}
+
+/// Converts a character count to `INT`.
+fn int_result(count: usize) -> Result<Value, RuntimeError> {
+    i16::try_from(count)
+        .map(Value::Int)
+        .map_err(|_| RuntimeError::Overflow)
+}
+
+/// Clamps a 1-based position to a 0-based character index.
+fn start_index(position: i64) -> usize {
+    if position <= 1 {
+        0
+    } else {
+        usize::try_from(position - 1).unwrap_or(usize::MAX)
+    }
+}
+
+/// Checks a 1-based position, which may point just past the last character, and
+/// converts it to a 0-based character index.
+fn position_index(position: i64, total: usize, clamp: bool) -> Result<usize, RuntimeError> {
+    let upper = i64::try_from(total).unwrap_or(i64::MAX).saturating_add(1);
+    if !clamp && !(1..=upper).contains(&position) {
+        return Err(RuntimeError::StringIndexOutOfBounds {
+            position,
+            lower: 1,
+            upper,
+        });
+    }
+    Ok(start_index(position))
+}
+
+/// Clamps a character count to `0..=limit`.
+fn clamp_count(count: i64, limit: usize) -> usize {
+    if count <= 0 {
+        0
+    } else {
+        usize::try_from(count).map_or(limit, |count| count.min(limit))
+    }
+}
+
+/// Returns `text` with the characters in `start..end` replaced by `insert`.
+fn splice(text: &str, start: usize, end: usize, insert: &str) -> String {
+    let mut result = String::with_capacity(text.len() + insert.len());
+    result.extend(text.chars().take(start));
+    result.push_str(insert);
+    result.extend(text.chars().skip(end));
+    result
+}
+
+fn len(args: &[Value]) -> Result<Value, RuntimeError> {
+    require_arity(args, 1)?;
+    let (text, _) = text_arg(&args[0])?;
+    int_result(text.chars().count())
+}
+
+fn left(args: &[Value]) -> Result<Value, RuntimeError> {
+    require_arity(args, 2)?;
+    let (text, wide) = text_arg(&args[0])?;
+    let take = clamp_count(to_i64(&args[1])?, text.chars().count());
+    Ok(text_value(text.chars().take(take).collect(), wide))
+}
+
 fn right(args: &[Value]) -> Result<Value, RuntimeError> {
     require_arity(args, 2)?;
-    let count = to_i64(&args[1])?;
-    match &args[0] {
-        Value::String(value) => {
-            let bytes = value.as_bytes();
-            let len = bytes.len();
-            let take = if count <= 0 {
-                0
-            } else {
-                count.min(len as i64) as usize
-            };
-            let start = len - take;
-            let result =
-                std::str::from_utf8(&bytes[start..]).map_err(|_| RuntimeError::TypeMismatch)?;
-            Ok(Value::String(SmolStr::new(result)))
-        }
-        Value::WString(value) => {
-            let total = value.chars().count();
-            let take = if count <= 0 {
-                0
-            } else {
-                count.min(total as i64) as usize
-            };
-            let start = total - take;
-            let result: String = value.chars().skip(start).collect();
-            Ok(Value::WString(result))
-        }
-        _ => Err(RuntimeError::TypeMismatch),
-    }
+    let (text, wide) = text_arg(&args[0])?;
+    let total = text.chars().count();
+    let take = clamp_count(to_i64(&args[1])?, total);
+    Ok(text_value(text.chars().skip(total - take).collect(), wide))
 }
 
 fn mid(args: &[Value]) -> Result<Value, RuntimeError> {
+    mid_with(args, false)
+}
+
+fn mid_with(args: &[Value], clamp: bool) -> Result<Value, RuntimeError> {
     require_arity(args, 3)?;
+    let (text, wide) = text_arg(&args[0])?;
     let length = to_i64(&args[1])?;
-    let position = to_i64(&args[2])?;
-    match &args[0] {
-        Value::String(value) => {
-            let bytes = value.as_bytes();
-            let len = bytes.len();
-            let start = if position <= 1 {
-                0
-            } else {
-                position as usize - 1
-            };
-            if start >= len || length <= 0 {
-                return Ok(Value::String(SmolStr::new("")));
-            }
-            let end = (start as i64 + length).min(len as i64) as usize;
-            let result =
-                std::str::from_utf8(&bytes[start..end]).map_err(|_| RuntimeError::TypeMismatch)?;
-            Ok(Value::String(SmolStr::new(result)))
-        }
-        Value::WString(value) => {
-            let total = value.chars().count();
-            let start = if position <= 1 {
-                0
-            } else {
-                position as usize - 1
-            };
-            if start >= total || length <= 0 {
-                return Ok(Value::WString(String::new()));
-            }
-            let end = (start as i64 + length).min(total as i64) as usize;
-            let result: String = value.chars().skip(start).take(end - start).collect();
-            Ok(Value::WString(result))
-        }
-        _ => Err(RuntimeError::TypeMismatch),
+    let total = text.chars().count();
+    let start = position_index(to_i64(&args[2])?, total, clamp)?;
+    if start >= total {
+        return Ok(text_value(String::new(), wide));
     }
+    let take = clamp_count(length, total - start);
+    Ok(text_value(
+        text.chars().skip(start).take(take).collect(),
+        wide,
+    ))
 }
 
 fn concat(args: &[Value]) -> Result<Value, RuntimeError> {
     require_min(args, 2)?;
-    let is_wide = match &args[0] {
-        Value::String(_) => false,
-        Value::WString(_) => true,
-        _ => return Err(RuntimeError::TypeMismatch),
-    };
-    if is_wide {
-        let mut result = String::new();
-        for value in args {
-            match value {
-                Value::WString(s) => result.push_str(s),
-                _ => return Err(RuntimeError::TypeMismatch),
-            }
-        }
-        Ok(Value::WString(result))
-    } else {
-        let mut result = String::new();
-        for value in args {
-            match value {
-                Value::String(s) => result.push_str(s.as_str()),
-                _ => return Err(RuntimeError::TypeMismatch),
-            }
-        }
-        Ok(Value::String(SmolStr::new(result)))
+    let (_, wide) = text_arg(&args[0])?;
+    let mut result = String::new();
+    for value in args {
+        let (text, other_wide) = text_arg(value)?;
+        if other_wide != wide {
+            return Err(RuntimeError::TypeMismatch);
+        }
+        result.push_str(text);
     }
+    Ok(text_value(result, wide))
 }
 
 fn insert(args: &[Value]) -> Result<Value, RuntimeError> {
+    insert_with(args, false)
+}
+
+/// `P` counts the characters before the insertion point, so it ranges over `0..=LEN`.
+fn insert_with(args: &[Value], clamp: bool) -> Result<Value, RuntimeError> {
     require_arity(args, 3)?;
+    let (in1, in2, wide) = text_pair(&args[0], &args[1])?;
+    let total = in1.chars().count();
     let position = to_i64(&args[2])?;
-    match (&args[0], &args[1]) {
-        (Value::String(in1), Value::String(in2)) => {
-            let bytes = in1.as_bytes();
-            let len = bytes.len();
-            let idx = if position <= 0 {
-                0
-            } else if position as usize >= len {
-                len
-            } else {
-                position as usize
-            };
-            let mut result = Vec::with_capacity(len + in2.len());
-            result.extend_from_slice(&bytes[..idx]);
-            result.extend_from_slice(in2.as_bytes());
-            result.extend_from_slice(&bytes[idx..]);
-            let result = String::from_utf8(result).map_err(|_| RuntimeError::TypeMismatch)?;
-            Ok(Value::String(SmolStr::new(result)))
-        }
-        (Value::WString(in1), Value::WString(in2)) => {
-            let total = in1.chars().count();
-            let idx = if position <= 0 {
-                0
-            } else if position as usize >= total {
-                total
-            } else {
-                position as usize
-            };
-            let mut result = String::new();
-            result.extend(in1.chars().take(idx));
-            result.push_str(in2);
-            result.extend(in1.chars().skip(idx));
-            Ok(Value::WString(result))
-        }
-        _ => Err(RuntimeError::TypeMismatch),
+    let upper = i64::try_from(total).unwrap_or(i64::MAX);
+    if !clamp && !(0..=upper).contains(&position) {
+        return Err(RuntimeError::StringIndexOutOfBounds {
+            position,
+            lower: 0,
+            upper,
+        });
     }
+    let idx = clamp_count(position, total);
+    Ok(text_value(splice(in1, idx, idx, in2), wide))
 }
 
 fn delete(args: &[Value]) -> Result<Value, RuntimeError> {
+    delete_with(args, false)
+}
+
+fn delete_with(args: &[Value], clamp: bool) -> Result<Value, RuntimeError> {
     require_arity(args, 3)?;
+    let (text, wide) = text_arg(&args[0])?;
     let length = to_i64(&args[1])?;
-    let position = to_i64(&args[2])?;
-    match &args[0] {
-        Value::String(input) => {
-            if length <= 0 {
-                return Ok(Value::String(input.clone()));
-            }
-            let bytes = input.as_bytes();
-            let len = bytes.len();
-            let start = if position <= 1 {
-                0
-            } else {
-                position as usize - 1
-            };
-            if start >= len {
-                return Ok(Value::String(input.clone()));
-            }
-            let end = (start as i64 + length).min(len as i64) as usize;
-            let mut result = Vec::with_capacity(len - (end - start));
-            result.extend_from_slice(&bytes[..start]);
-            result.extend_from_slice(&bytes[end..]);
-            let result = String::from_utf8(result).map_err(|_| RuntimeError::TypeMismatch)?;
-            Ok(Value::String(SmolStr::new(result)))
-        }
-        Value::WString(input) => {
-            if length <= 0 {
-                return Ok(Value::WString(input.clone()));
-            }
-            let total = input.chars().count();
-            let start = if position <= 1 {
-                0
-            } else {
-                position as usize - 1
-            };
-            if start >= total {
-                return Ok(Value::WString(input.clone()));
-            }
-            let end = (start as i64 + length).min(total as i64) as usize;
-            let mut result = String::new();
-            result.extend(input.chars().take(start));
-            result.extend(input.chars().skip(end));
-            Ok(Value::WString(result))
-        }
-        _ => Err(RuntimeError::TypeMismatch),
+    let total = text.chars().count();
+    let start = position_index(to_i64(&args[2])?, total, clamp)?;
+    if length <= 0 || start >= total {
+        return Ok(args[0].clone());
     }
+    let end = start + clamp_count(length, total - start);
+    Ok(text_value(splice(text, start, end, ""), wide))
 }
 
 fn replace(args: &[Value]) -> Result<Value, RuntimeError> {
+    replace_with(args, false)
+}
+
+fn replace_with(args: &[Value], clamp: bool) -> Result<Value, RuntimeError> {
     require_arity(args, 4)?;
+    let (input, repl, wide) = text_pair(&args[0], &args[1])?;
     let length = to_i64(&args[2])?;
-    let position = to_i64(&args[3])?;
-    match (&args[0], &args[1]) {
-        (Value::String(input), Value::String(repl)) => {
-            let bytes = input.as_bytes();
-            let len = bytes.len();
-            let start = if position <= 1 {
-                0
-            } else {
-                position as usize - 1
-            };
-            if start >= len {
-                return Ok(Value::String(input.clone()));
-            }
-            let end = if length <= 0 {
-                start
-            } else {
-                (start as i64 + length).min(len as i64) as usize
-            };
-            let mut result = Vec::with_capacity(len - (end - start) + repl.len());
-            result.extend_from_slice(&bytes[..start]);
-            result.extend_from_slice(repl.as_bytes());
-            result.extend_from_slice(&bytes[end..]);
-            let result = String::from_utf8(result).map_err(|_| RuntimeError::TypeMismatch)?;
-            Ok(Value::String(SmolStr::new(result)))
-        }
-        (Value::WString(input), Value::WString(repl)) => {
-            let total = input.chars().count();
-            let start = if position <= 1 {
-                0
-            } else {
-                position as usize - 1
-            };
-            if start >= total {
-                return Ok(Value::WString(input.clone()));
-            }
-            let end = if length <= 0 {
-                start
-            } else {
-                (start as i64 + length).min(total as i64) as usize
-            };
-            let mut result = String::new();
-            result.extend(input.chars().take(start));
-            result.push_str(repl);
-            result.extend(input.chars().skip(end));
-            Ok(Value::WString(result))
-        }
-        _ => Err(RuntimeError::TypeMismatch),
+    let total = input.chars().count();
+    let start = position_index(to_i64(&args[3])?, total, clamp)?;
+    if start >= total {
+        return Ok(args[0].clone());
     }
+    let end = start + clamp_count(length, total - start);
+    Ok(text_value(splice(input, start, end, repl), wide))
 }
 
 fn find(args: &[Value]) -> Result<Value, RuntimeError> {
     require_arity(args, 2)?;
-    match (&args[0], &args[1]) {
-        (Value::String(in1), Value::String(in2)) => {
-            let pos = in1
-                .as_str()
-                .find(in2.as_str())
-                .map(|idx| idx + 1)
-                .unwrap_or(0);
-            if pos > i16::MAX as usize {
-                return Err(RuntimeError::Overflow);
-            }
-            Ok(Value::Int(pos as i16))
-        }
-        (Value::WString(in1), Value::WString(in2)) => {
-            if in2.is_empty() {
-                return Ok(Value::Int(1));
-            }
-            let byte_idx = in1.find(in2);
-            let pos = match byte_idx {
-                Some(idx) => in1[..idx].chars().count() + 1,
-                None => 0,
-            };
-            if pos > i16::MAX as usize {
-                return Err(RuntimeError::Overflow);
-            }
-            Ok(Value::Int(pos as i16))
-        }
-        _ => Err(RuntimeError::TypeMismatch),
-    }
+    let (in1, in2, _) = text_pair(&args[0], &args[1])?;
+    let pos = match in1.find(in2) {
+        Some(idx) => in1[..idx].chars().count() + 1,
+        None => 0,
+    };
+    int_result(pos)
 }
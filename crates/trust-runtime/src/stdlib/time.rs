@@ -3,7 +3,8 @@
 #![allow(missing_docs)]
 
 use crate::datetime::{
-    days_from_civil, days_to_ticks, nanos_to_ticks, ticks_per_day, DivisionMode, NANOS_PER_DAY,
+    civil_from_days, days_from_civil, days_to_ticks, nanos_to_ticks, ticks_per_day, DivisionMode,
+    NANOS_PER_DAY,
 };
 use crate::error::RuntimeError;
 use crate::eval::ops::{apply_binary, BinaryOp};
@@ -464,17 +465,3 @@ fn tod_from_nanos(nanos: i64) -> (i64, i64, i64, i64) {
     let millis = remainder / 1_000_000;
     (hours, minutes, seconds, millis)
 }
-
-fn civil_from_days(days: i64) -> (i64, i64, i64) {
-    let z = days + 719468;
-    let era = if z >= 0 { z } else { z - 146096 } / 146097;
-    let doe = z - era * 146097;
-    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
-    let y = yoe + era * 400;
-    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
-    let mp = (5 * doy + 2) / 153;
-    let d = doy - (153 * mp + 2) / 5 + 1;
-    let m = mp + if mp < 10 { 3 } else { -9 };
-    let year = y + if m <= 2 { 1 } else { 0 };
-    (year, m, d)
-}
@@ -0,0 +1,537 @@
+//! Precompiled library archives (`.stlib`).
+//!
+//! An archive carries compiled bytecode plus a symbol-only interface so a
+//! library can be distributed without its source. The interface is plain ST
+//! containing every public declaration (types, functions, function blocks,
+//! classes, interfaces, and their parameter blocks) with all statement bodies,
+//! actions, and private `VAR`/`VAR_TEMP` blocks removed, so editors can index
+//! it like any other source file.
+//!
+//! Layout (little-endian):
+//!
+//! ```text
+//! magic "STLIB\0\0\0" | format u16 | reserved u16
+//! manifest_len u32 | interface_len u32 | bytecode_len u32
+//! manifest (JSON) | interface (UTF-8 ST) | bytecode (STBC container)
+//! crc32 u32 over all preceding bytes
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use trust_hir::doc_comments::leading_comment_lines;
+use trust_syntax::parser;
+use trust_syntax::{SyntaxKind, SyntaxNode};
+
+use crate::bytecode::BytecodeModule;
+use crate::harness::{CompileSession, SourceFile};
+
+/// File extension used for library archives.
+pub const LIBRARY_ARCHIVE_EXTENSION: &str = "stlib";
+
+const MAGIC: &[u8; 8] = b"STLIB\0\0\0";
+const FORMAT_VERSION: u16 = 1;
+const HEADER_LEN: usize = 8 + 2 + 2 + 4 * 3;
+
+/// Kind of a public POU exposed by a library archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LibraryPouKind {
+    /// `FUNCTION`.
+    Function,
+    /// `FUNCTION_BLOCK`.
+    FunctionBlock,
+    /// `CLASS`.
+    Class,
+    /// `INTERFACE`.
+    Interface,
+}
+
+/// Public POU listed in a library manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibraryPou {
+    /// Declared name.
+    pub name: String,
+    /// POU kind.
+    pub kind: LibraryPouKind,
+}
+
+/// Interface manifest stored in a library archive.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibraryManifest {
+    /// Library name.
+    pub name: String,
+    /// Library version (from `[package].version` when packed from a project).
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Public POUs.
+    #[serde(default)]
+    pub pous: Vec<LibraryPou>,
+    /// Public data type names.
+    #[serde(default)]
+    pub types: Vec<String>,
+    /// Documentation comments keyed by declaration name.
+    #[serde(default)]
+    pub docs: BTreeMap<String, String>,
+}
+
+/// A decoded `.stlib` archive.
+#[derive(Debug, Clone)]
+pub struct LibraryArchive {
+    /// Interface manifest.
+    pub manifest: LibraryManifest,
+    /// Declaration-only ST interface.
+    pub interface: String,
+    /// Encoded bytecode container.
+    pub bytecode: Vec<u8>,
+}
+
+/// Summary returned by [`pack_library`].
+#[derive(Debug, Clone)]
+pub struct LibraryPackReport {
+    /// Written archive path.
+    pub archive_path: PathBuf,
+    /// Archive manifest.
+    pub manifest: LibraryManifest,
+    /// Source files compiled into the archive.
+    pub sources: Vec<PathBuf>,
+}
+
+impl LibraryArchive {
+    /// Compile `sources` and build an archive exposing their public interface.
+    pub fn from_sources(
+        name: impl Into<String>,
+        version: Option<String>,
+        sources: Vec<SourceFile>,
+    ) -> anyhow::Result<Self> {
+        let interface = extract_interface(&sources);
+        let bytecode = CompileSession::from_sources(sources)
+            .library(true)
+            .build_bytecode_bytes()?;
+        Ok(Self {
+            manifest: LibraryManifest {
+                name: name.into(),
+                version,
+                pous: interface.pous,
+                types: interface.types,
+                docs: interface.docs,
+            },
+            interface: interface.text,
+            bytecode,
+        })
+    }
+
+    /// Serialize the archive.
+    pub fn encode(&self) -> anyhow::Result<Vec<u8>> {
+        let manifest = serde_json::to_vec(&self.manifest)?;
+        let interface = self.interface.as_bytes();
+        let mut out = Vec::with_capacity(
+            HEADER_LEN + manifest.len() + interface.len() + self.bytecode.len() + 4,
+        );
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());
+        for len in [manifest.len(), interface.len(), self.bytecode.len()] {
+            let len = u32::try_from(len).context("library archive section exceeds 4 GiB")?;
+            out.extend_from_slice(&len.to_le_bytes());
+        }
+        out.extend_from_slice(&manifest);
+        out.extend_from_slice(interface);
+        out.extend_from_slice(&self.bytecode);
+        let crc = crc32fast::hash(&out);
+        out.extend_from_slice(&crc.to_le_bytes());
+        Ok(out)
+    }
+
+    /// Decode and validate an archive, including its bytecode container.
+    pub fn decode(bytes: &[u8]) -> anyhow::Result<Self> {
+        if bytes.len() < HEADER_LEN + 4 || &bytes[..8] != MAGIC {
+            anyhow::bail!("not a .stlib library archive");
+        }
+        let (body, crc_bytes) = bytes.split_at(bytes.len() - 4);
+        let expected = u32::from_le_bytes(crc_bytes.try_into().expect("4-byte crc"));
+        if crc32fast::hash(body) != expected {
+            anyhow::bail!("library archive checksum mismatch");
+        }
+        let format = u16::from_le_bytes([body[8], body[9]]);
+        if format != FORMAT_VERSION {
+            anyhow::bail!("unsupported library archive format {format}");
+        }
+        let read_len = |offset: usize| {
+            u32::from_le_bytes(body[offset..offset + 4].try_into().expect("4-byte length")) as usize
+        };
+        let lengths = [read_len(12), read_len(16), read_len(20)];
+        if HEADER_LEN + lengths.iter().sum::<usize>() != body.len() {
+            anyhow::bail!("library archive section lengths do not match file size");
+        }
+        let manifest_end = HEADER_LEN + lengths[0];
+        let interface_end = manifest_end + lengths[1];
+        let manifest: LibraryManifest = serde_json::from_slice(&body[HEADER_LEN..manifest_end])
+            .context("invalid library manifest")?;
+        let interface = String::from_utf8(body[manifest_end..interface_end].to_vec())
+            .context("library interface is not valid UTF-8")?;
+        let bytecode = body[interface_end..].to_vec();
+        BytecodeModule::decode(&bytecode)
+            .and_then(|module| module.validate())
+            .context("invalid library bytecode")?;
+        Ok(Self {
+            manifest,
+            interface,
+            bytecode,
+        })
+    }
+
+    /// Read and validate an archive from disk.
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        Self::decode(&bytes).with_context(|| format!("invalid library archive {}", path.display()))
+    }
+
+    /// Decode the archived bytecode container.
+    pub fn bytecode_module(&self) -> anyhow::Result<BytecodeModule> {
+        Ok(BytecodeModule::decode(&self.bytecode)?)
+    }
+}
+
+/// Returns `true` if `path` names a `.stlib` archive.
+#[must_use]
+pub fn is_library_archive(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(LIBRARY_ARCHIVE_EXTENSION))
+}
+
+/// Compile a library project into a `.stlib` archive.
+///
+/// The archive name and version come from `[package]` in the project manifest
+/// when present, falling back to the project folder name.
+pub fn pack_library(
+    project_root: &Path,
+    sources_root: Option<&Path>,
+    output: Option<&Path>,
+) -> anyhow::Result<LibraryPackReport> {
+    let sources_root = crate::bundle_builder::resolve_sources_root(project_root, sources_root)?;
+    let (sources, source_paths) =
+        crate::bundle_builder::collect_sources(std::slice::from_ref(&sources_root))?;
+    if sources.is_empty() {
+        anyhow::bail!(
+            "no source files found in {} (expected .st/.pou files)",
+            sources_root.display()
+        );
+    }
+    let package = crate::bundle_builder::load_package_info(project_root)?;
+    let name = package.name.unwrap_or_else(|| {
+        project_root
+            .canonicalize()
+            .unwrap_or_else(|_| project_root.to_path_buf())
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "library".to_string())
+    });
+    let archive = LibraryArchive::from_sources(name.clone(), package.version, sources)?;
+    let archive_path = output
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| project_root.join(format!("{name}.{LIBRARY_ARCHIVE_EXTENSION}")));
+    if let Some(parent) = archive_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&archive_path, archive.encode()?)
+        .with_context(|| format!("failed to write {}", archive_path.display()))?;
+    Ok(LibraryPackReport {
+        archive_path,
+        manifest: archive.manifest,
+        sources: source_paths,
+    })
+}
+
+struct ExtractedInterface {
+    text: String,
+    pous: Vec<LibraryPou>,
+    types: Vec<String>,
+    docs: BTreeMap<String, String>,
+}
+
+fn extract_interface(sources: &[SourceFile]) -> ExtractedInterface {
+    let mut interface = ExtractedInterface {
+        text: String::new(),
+        pous: Vec::new(),
+        types: Vec::new(),
+        docs: BTreeMap::new(),
+    };
+    for source in sources {
+        let parse = parser::parse(&source.text);
+        emit_container(&parse.syntax(), &mut interface);
+    }
+    interface.text = collapse_blank_lines(&interface.text);
+    interface
+}
+
+/// Emits the public declarations directly below a source file or namespace.
+fn emit_container(node: &SyntaxNode, out: &mut ExtractedInterface) {
+    let in_namespace = node.kind() == SyntaxKind::Namespace;
+    let mut pending_docs: Vec<String> = Vec::new();
+    for element in node.children_with_tokens() {
+        if let Some(token) = element.as_token() {
+            match token.kind() {
+                SyntaxKind::LineComment | SyntaxKind::BlockComment => {
+                    pending_docs.push(token.text().to_string());
+                }
+                SyntaxKind::Whitespace => {
+                    if token.text().matches('\n').count() > 1 {
+                        pending_docs.clear();
+                    }
+                    if in_namespace {
+                        out.text.push_str(token.text());
+                    }
+                }
+                _ => {
+                    if in_namespace {
+                        out.text.push_str(token.text());
+                    }
+                    pending_docs.clear();
+                }
+            }
+            continue;
+        }
+        let Some(child) = element.into_node() else {
+            continue;
+        };
+        if in_namespace && child.kind() == SyntaxKind::Name {
+            out.text.push_str(&child.text().to_string());
+            continue;
+        }
+        let docs = std::mem::take(&mut pending_docs);
+        if child.kind() == SyntaxKind::Namespace {
+            emit_container(&child, out);
+            out.text.push('\n');
+            continue;
+        }
+        if !record_declaration(&child, &docs, out) {
+            continue;
+        }
+        for doc in &docs {
+            out.text.push_str(doc);
+            out.text.push('\n');
+        }
+        emit_declaration(&child, &mut out.text);
+        out.text.push_str("\n\n");
+    }
+}
+
+/// Records a public top-level declaration; returns `false` for nodes that are
+/// not part of the interface.
+fn record_declaration(node: &SyntaxNode, docs: &[String], out: &mut ExtractedInterface) -> bool {
+    let kind = match node.kind() {
+        SyntaxKind::Function => LibraryPouKind::Function,
+        SyntaxKind::FunctionBlock => LibraryPouKind::FunctionBlock,
+        SyntaxKind::Class => LibraryPouKind::Class,
+        SyntaxKind::Interface => LibraryPouKind::Interface,
+        SyntaxKind::TypeDecl => {
+            for name in node
+                .children()
+                .filter(|child| child.kind() == SyntaxKind::Name)
+            {
+                out.types.push(name.text().to_string().trim().to_string());
+            }
+            return true;
+        }
+        SyntaxKind::UsingDirective => return true,
+        _ => return false,
+    };
+    let Some(name) = node
+        .children()
+        .find(|child| child.kind() == SyntaxKind::Name)
+        .map(|name| name.text().to_string().trim().to_string())
+    else {
+        return false;
+    };
+    // Leading comments are attached inside the declaration node unless a blank
+    // line or other code separates them from the previous declaration.
+    let doc = if docs.is_empty() {
+        leading_comment_lines(node).join("\n")
+    } else {
+        doc_text(docs)
+    };
+    if !doc.is_empty() {
+        out.docs.insert(name.clone(), doc);
+    }
+    out.pous.push(LibraryPou { name, kind });
+    true
+}
+
+/// Emits a declaration without statement bodies, actions, or private blocks.
+fn emit_declaration(node: &SyntaxNode, out: &mut String) {
+    for element in node.children_with_tokens() {
+        if let Some(token) = element.as_token() {
+            out.push_str(token.text());
+            continue;
+        }
+        let Some(child) = element.into_node() else {
+            continue;
+        };
+        if child.kind() == SyntaxKind::StmtList
+            || child.kind() == SyntaxKind::Action
+            || is_private_var_block(&child)
+        {
+            continue;
+        }
+        emit_declaration(&child, out);
+    }
+}
+
+fn is_private_var_block(node: &SyntaxNode) -> bool {
+    node.kind() == SyntaxKind::VarBlock
+        && node
+            .children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .find(|token| !token.kind().is_trivia())
+            .is_some_and(|token| {
+                matches!(
+                    token.kind(),
+                    SyntaxKind::KwVar | SyntaxKind::KwVarTemp | SyntaxKind::KwVarStat
+                )
+            })
+}
+
+fn doc_text(comments: &[String]) -> String {
+    comments
+        .iter()
+        .map(|comment| {
+            let text = comment.trim();
+            let text = text
+                .strip_prefix("(*")
+                .and_then(|inner| inner.strip_suffix("*)"))
+                .or_else(|| {
+                    text.strip_prefix("/*")
+                        .and_then(|inner| inner.strip_suffix("*/"))
+                })
+                .or_else(|| text.strip_prefix("//"))
+                .unwrap_or(text);
+            text.lines()
+                .map(|line| line.trim().trim_start_matches('*').trim())
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim()
+                .to_string()
+        })
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn collapse_blank_lines(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank_run = 0usize;
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            blank_run += 1;
+            if blank_run > 1 {
+                continue;
+            }
+            out.push('\n');
+            continue;
+        }
+        blank_run = 0;
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out.trim_start_matches('\n').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIBRARY: &str = r#"
+(* Scales a raw analog value. *)
+FUNCTION ScaleRaw : REAL
+VAR_INPUT
+    Raw : INT;
+END_VAR
+VAR
+    SecretGain : REAL := 0.5;
+END_VAR
+ScaleRaw := INT_TO_REAL(Raw) * SecretGain;
+END_FUNCTION
+
+TYPE MotorState : (Idle, Running); END_TYPE
+
+FUNCTION_BLOCK Motor
+VAR_INPUT
+    Enable : BOOL;
+END_VAR
+VAR_OUTPUT
+    State : MotorState;
+END_VAR
+IF Enable THEN
+    State := MotorState#Running;
+END_IF;
+END_FUNCTION_BLOCK
+
+PROGRAM Demo
+END_PROGRAM
+"#;
+
+    fn archive() -> LibraryArchive {
+        LibraryArchive::from_sources(
+            "Motion",
+            Some("1.0.0".to_string()),
+            vec![SourceFile::with_path("lib.st", LIBRARY)],
+        )
+        .expect("build archive")
+    }
+
+    #[test]
+    fn interface_keeps_signatures_and_drops_bodies() {
+        let archive = archive();
+        let interface = &archive.interface;
+        assert!(interface.contains("FUNCTION ScaleRaw : REAL"));
+        assert!(interface.contains("Raw : INT;"));
+        assert!(interface.contains("FUNCTION_BLOCK Motor"));
+        assert!(interface.contains("MotorState"));
+        assert!(!interface.contains("SecretGain"));
+        assert!(!interface.contains("INT_TO_REAL"));
+        assert!(!interface.contains("MotorState#Running"));
+        assert!(!interface.contains("PROGRAM Demo"));
+        assert!(trust_syntax::parser::parse(interface).ok(), "{interface}");
+
+        let manifest = &archive.manifest;
+        assert_eq!(
+            manifest.pous,
+            vec![
+                LibraryPou {
+                    name: "ScaleRaw".to_string(),
+                    kind: LibraryPouKind::Function,
+                },
+                LibraryPou {
+                    name: "Motor".to_string(),
+                    kind: LibraryPouKind::FunctionBlock,
+                },
+            ]
+        );
+        assert_eq!(manifest.types, vec!["MotorState".to_string()]);
+        assert_eq!(
+            manifest.docs.get("ScaleRaw").map(String::as_str),
+            Some("Scales a raw analog value.")
+        );
+    }
+
+    #[test]
+    fn archive_round_trips_and_detects_corruption() {
+        let archive = archive();
+        let bytes = archive.encode().expect("encode");
+        let decoded = LibraryArchive::decode(&bytes).expect("decode");
+        assert_eq!(decoded.manifest, archive.manifest);
+        assert_eq!(decoded.interface, archive.interface);
+        assert!(decoded.bytecode_module().is_ok());
+
+        let mut corrupted = bytes.clone();
+        let middle = corrupted.len() / 2;
+        corrupted[middle] ^= 0xFF;
+        assert!(LibraryArchive::decode(&corrupted).is_err());
+        assert!(LibraryArchive::decode(b"not an archive").is_err());
+    }
+}
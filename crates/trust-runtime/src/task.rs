@@ -2,10 +2,14 @@
 
 #![allow(missing_docs)]
 
+use indexmap::IndexMap;
 use smol_str::SmolStr;
 
+use crate::error::RuntimeError;
 use crate::eval::stmt::Stmt;
-use crate::value::{Duration, ValueRef};
+use crate::value::{Duration, Value, ValueRef};
+use trust_hir::types::TypeRegistry;
+use trust_hir::{Type, TypeId};
 
 /// Program definition for execution.
 #[derive(Debug, Clone)]
@@ -28,11 +32,181 @@ pub struct TaskConfig {
     pub fb_instances: Vec<ValueRef>,
 }
 
+/// How a periodic task handles releases it missed while the resource ran late.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaskCatchUp {
+    /// Run once for the most recent release and count the rest as overruns.
+    #[default]
+    Skip,
+    /// Run every missed release back to back in the same cycle (bounded by
+    /// [`MAX_BURST_RELEASES`]; older releases beyond the bound are overruns).
+    Burst,
+}
+
+/// Upper bound on the releases a [`TaskCatchUp::Burst`] task runs in one cycle.
+pub const MAX_BURST_RELEASES: i64 = 16;
+
+impl TaskCatchUp {
+    pub fn parse(text: &str) -> Result<Self, RuntimeError> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "skip" => Ok(Self::Skip),
+            "burst" => Ok(Self::Burst),
+            _ => Err(RuntimeError::InvalidConfig(
+                format!("invalid task catch-up policy '{text}'").into(),
+            )),
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Skip => "skip",
+            Self::Burst => "burst",
+        }
+    }
+}
+
+/// What an out-of-bounds array index or string position does in a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoundsPolicy {
+    /// Raise a runtime fault; the resource fault policy decides what happens next.
+    #[default]
+    Fault,
+    /// Move the index to the nearest bound and continue.
+    Clamp,
+}
+
+impl BoundsPolicy {
+    pub fn parse(text: &str) -> Result<Self, RuntimeError> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "fault" => Ok(Self::Fault),
+            "clamp" => Ok(Self::Clamp),
+            _ => Err(RuntimeError::InvalidConfig(
+                format!("invalid bounds policy '{text}'").into(),
+            )),
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fault => "fault",
+            Self::Clamp => "clamp",
+        }
+    }
+}
+
+/// Bounds policy of the resource with per-task overrides.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoundsSettings {
+    /// Policy of tasks without an override and of programs outside any task.
+    pub default: BoundsPolicy,
+    /// Overrides by task name.
+    pub tasks: IndexMap<SmolStr, BoundsPolicy>,
+}
+
+impl BoundsSettings {
+    /// Policy for the task `name` (case-insensitive).
+    #[must_use]
+    pub fn for_task(&self, name: &str) -> BoundsPolicy {
+        self.tasks
+            .iter()
+            .find(|(task, _)| task.eq_ignore_ascii_case(name))
+            .map_or(self.default, |(_, policy)| *policy)
+    }
+}
+
+/// What a write outside the range of a subrange type (`INT (0..100)`) does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SubrangePolicy {
+    /// Store the value unchecked.
+    #[default]
+    Off,
+    /// Raise a runtime fault; writes from the control protocol are rejected.
+    Fault,
+    /// Store the nearest bound instead.
+    Clamp,
+}
+
+impl SubrangePolicy {
+    pub fn parse(text: &str) -> Result<Self, RuntimeError> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "fault" => Ok(Self::Fault),
+            "clamp" => Ok(Self::Clamp),
+            _ => Err(RuntimeError::InvalidConfig(
+                format!("invalid subrange policy '{text}'").into(),
+            )),
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Fault => "fault",
+            Self::Clamp => "clamp",
+        }
+    }
+
+    /// Applies the policy to an integer `value` written to a variable of subrange
+    /// `lower..upper`. Non-integer values pass through.
+    pub fn apply(self, value: Value, lower: i64, upper: i64) -> Result<Value, RuntimeError> {
+        if self == Self::Off {
+            return Ok(value);
+        }
+        let current = match value {
+            Value::SInt(v) => i128::from(v),
+            Value::Int(v) => i128::from(v),
+            Value::DInt(v) => i128::from(v),
+            Value::LInt(v) => i128::from(v),
+            Value::USInt(v) => i128::from(v),
+            Value::UInt(v) => i128::from(v),
+            Value::UDInt(v) => i128::from(v),
+            Value::ULInt(v) => i128::from(v),
+            _ => return Ok(value),
+        };
+        if (i128::from(lower)..=i128::from(upper)).contains(&current) {
+            return Ok(value);
+        }
+        if self == Self::Fault {
+            return Err(RuntimeError::SubrangeViolation {
+                value: i64::try_from(current).unwrap_or(i64::MAX),
+                lower,
+                upper,
+            });
+        }
+        let clamped = current.clamp(i128::from(lower), i128::from(upper));
+        Ok(match value {
+            Value::SInt(_) => Value::SInt(clamped as i8),
+            Value::Int(_) => Value::Int(clamped as i16),
+            Value::DInt(_) => Value::DInt(clamped as i32),
+            Value::USInt(_) => Value::USInt(clamped as u8),
+            Value::UInt(_) => Value::UInt(clamped as u16),
+            Value::UDInt(_) => Value::UDInt(clamped as u32),
+            Value::ULInt(_) => Value::ULInt(clamped as u64),
+            _ => Value::LInt(clamped as i64),
+        })
+    }
+}
+
+/// Range of a subrange type, looking through aliases.
+#[must_use]
+pub fn subrange_of_type(registry: &TypeRegistry, type_id: TypeId) -> Option<(i64, i64)> {
+    match registry.get(type_id)? {
+        Type::Subrange { lower, upper, .. } => Some((*lower, *upper)),
+        Type::Alias { target, .. } => subrange_of_type(registry, *target),
+        _ => None,
+    }
+}
+
 /// Scheduling state for a task.
 #[derive(Debug, Clone)]
 pub struct TaskState {
     pub last_single: bool,
-    pub last_run: Duration,
+    /// Absolute time of the latest periodic release. Releases sit on the grid
+    /// `last_release + n * interval`, so late cycles never shift later ones.
+    pub last_release: Duration,
     pub overrun_count: u64,
 }
 
@@ -41,7 +215,7 @@ impl TaskState {
     pub fn new(current_time: Duration) -> Self {
         Self {
             last_single: false,
-            last_run: current_time,
+            last_release: current_time,
             overrun_count: 0,
         }
     }
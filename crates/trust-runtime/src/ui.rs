@@ -2,14 +2,17 @@
 
 #![allow(missing_docs)]
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::io::{self, BufRead, Read, Write};
 use std::path::{Path, PathBuf};
-use std::time::{Duration as StdDuration, Instant};
+use std::time::{Duration as StdDuration, Instant, SystemTime, UNIX_EPOCH};
 
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -25,14 +28,16 @@ use ratatui::{
 use crate::bundle::detect_bundle_path;
 use crate::bundle_builder::build_program_stbc;
 use crate::config::RuntimeBundle;
-use crate::control::ControlEndpoint;
+use crate::control::{control_error_message, ControlEndpoint};
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
 use serde_json::json;
 
 mod client;
 mod commands;
+mod fleet;
 mod input;
+mod mouse;
 mod parsing;
 mod render;
 mod state;
@@ -46,6 +51,10 @@ const COLOR_YELLOW: Color = Color::Rgb(245, 196, 66);
 const COLOR_CYAN: Color = Color::Rgb(64, 212, 255);
 const COLOR_MAGENTA: Color = Color::Rgb(191, 90, 242);
 const COLOR_PROMPT_BG: Color = Color::Rgb(24, 24, 24);
+/// Samples kept per watched variable for the Trends panel and CSV export.
+const TREND_CAPACITY: usize = 600;
+/// Lines kept by the Logs panel.
+const LOG_CAPACITY: usize = 500;
 
 #[derive(Default, Clone)]
 struct UiData {
@@ -53,6 +62,7 @@ struct UiData {
     tasks: Vec<TaskSnapshot>,
     io: Vec<IoEntry>,
     events: Vec<EventSnapshot>,
+    alarms: Vec<AlarmSnapshot>,
     settings: Option<SettingsSnapshot>,
 }
 
@@ -74,6 +84,10 @@ struct StatusSnapshot {
     simulation_mode: String,
     simulation_time_scale: u32,
     simulation_warning: String,
+    redundancy_role: Option<String>,
+    program: Option<String>,
+    built: Option<String>,
+    safe_state: Option<String>,
 }
 
 #[derive(Default, Clone)]
@@ -100,6 +114,25 @@ struct IoEntry {
     direction: String,
 }
 
+/// An active HMI alarm as reported by `hmi.alarms.get`.
+#[derive(Default, Clone)]
+struct AlarmSnapshot {
+    id: String,
+    label: String,
+    acknowledged: bool,
+    value: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+/// One buffered reading of a watched variable.
+#[derive(Debug, Clone, PartialEq)]
+struct TrendSample {
+    timestamp_ms: u64,
+    value: String,
+    numeric: Option<f64>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum EventKind {
     Info,
@@ -164,6 +197,9 @@ enum PanelKind {
     Events,
     Tasks,
     Watch,
+    Trends,
+    Logs,
+    Alarms,
 }
 
 impl PanelKind {
@@ -175,6 +211,9 @@ impl PanelKind {
             PanelKind::Events => "Events",
             PanelKind::Tasks => "Tasks",
             PanelKind::Watch => "Watch",
+            PanelKind::Trends => "Trends",
+            PanelKind::Logs => "Logs",
+            PanelKind::Alarms => "Alarms",
         }
     }
 
@@ -186,6 +225,9 @@ impl PanelKind {
             "events" => Some(Self::Events),
             "tasks" => Some(Self::Tasks),
             "watch" => Some(Self::Watch),
+            "trends" => Some(Self::Trends),
+            "logs" => Some(Self::Logs),
+            "alarms" => Some(Self::Alarms),
             _ => None,
         }
     }
@@ -397,6 +439,9 @@ struct UiState {
     prompt: PromptState,
     layout: Vec<PanelKind>,
     focus: Option<PanelKind>,
+    selected_panel: Option<PanelKind>,
+    row_split: u16,
+    dragging_split: bool,
     panel_page: usize,
     settings_index: usize,
     menu_index: usize,
@@ -407,6 +452,13 @@ struct UiState {
     cycle_history: VecDeque<u64>,
     watch_list: Vec<String>,
     watch_values: Vec<(String, String)>,
+    watch_trends: HashMap<String, VecDeque<TrendSample>>,
+    log_lines: VecDeque<EventSnapshot>,
+    log_last_key: Option<String>,
+    log_filter: Option<String>,
+    log_follow: bool,
+    log_scroll: usize,
+    events_scroll: usize,
     forced_io: HashSet<String>,
     alerts: VecDeque<PromptLine>,
     seen_events: HashSet<String>,
@@ -423,6 +475,85 @@ pub fn run_ui(
     beginner: bool,
 ) -> anyhow::Result<()> {
     let (endpoint, auth_token, bundle_root) = resolve_endpoint(bundle, endpoint, token)?;
+    let client = ControlClient::connect(endpoint.clone(), auth_token.clone())?;
+    let mouse = bundle_root
+        .as_ref()
+        .and_then(|root| load_console_config(root).mouse)
+        .unwrap_or(true);
+
+    let mut terminal = enter_terminal(mouse)?;
+
+    let result = run_console(
+        &mut terminal,
+        client,
+        ConsoleTarget {
+            endpoint,
+            auth_token,
+            bundle_root,
+        },
+        refresh_ms,
+        no_input,
+        beginner,
+    );
+
+    leave_terminal(&mut terminal, mouse)?;
+    result
+}
+
+/// Multi-PLC dashboard over the endpoints in a fleet file and/or found via discovery.
+pub fn run_fleet_ui(
+    fleet: Option<PathBuf>,
+    discover: bool,
+    token: Option<String>,
+    refresh_ms: u64,
+    no_input: bool,
+    beginner: bool,
+) -> anyhow::Result<()> {
+    fleet::run_fleet_ui(fleet, discover, token, refresh_ms, no_input, beginner)
+}
+
+type ConsoleTerminal = Terminal<CrosstermBackend<io::Stdout>>;
+
+/// Mouse capture is optional because it takes over the terminal's own text selection.
+fn enter_terminal(mouse: bool) -> anyhow::Result<ConsoleTerminal> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    if mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
+    Ok(Terminal::new(CrosstermBackend::new(stdout))?)
+}
+
+fn leave_terminal(terminal: &mut ConsoleTerminal, mouse: bool) -> anyhow::Result<()> {
+    disable_raw_mode()?;
+    if mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+struct ConsoleTarget {
+    endpoint: ControlEndpoint,
+    auth_token: Option<String>,
+    bundle_root: Option<PathBuf>,
+}
+
+fn run_console(
+    terminal: &mut ConsoleTerminal,
+    mut client: ControlClient,
+    target: ConsoleTarget,
+    refresh_ms: u64,
+    no_input: bool,
+    beginner: bool,
+) -> anyhow::Result<()> {
+    let ConsoleTarget {
+        endpoint,
+        auth_token,
+        bundle_root,
+    } = target;
     let console_config = bundle_root
         .as_ref()
         .map(|root| load_console_config(root))
@@ -448,6 +579,9 @@ pub fn run_ui(
         prompt: PromptState::new(),
         layout,
         focus: None,
+        selected_panel: None,
+        row_split: 50,
+        dragging_split: false,
         panel_page: 0,
         settings_index: 0,
         menu_index: 0,
@@ -458,79 +592,83 @@ pub fn run_ui(
         cycle_history: VecDeque::with_capacity(120),
         watch_list: Vec::new(),
         watch_values: Vec::new(),
+        watch_trends: HashMap::new(),
+        log_lines: VecDeque::new(),
+        log_last_key: None,
+        log_filter: None,
+        log_follow: true,
+        log_scroll: 0,
+        events_scroll: 0,
         forced_io: HashSet::new(),
         alerts: VecDeque::with_capacity(6),
         seen_events: HashSet::new(),
         connected: true,
         bundle_root,
     };
-    let mut client = ControlClient::connect(endpoint.clone(), auth_token.clone())?;
     let mut last_refresh = Instant::now();
     let refresh = StdDuration::from_millis(refresh_ms);
 
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-
-    let result = (|| {
-        loop {
-            if last_refresh.elapsed() >= refresh {
-                match fetch_data(&mut client) {
-                    Ok(data) => {
-                        if !state.connected {
-                            push_alert(
-                                &mut state,
-                                "CONNECTED Control restored.",
-                                Style::default().fg(COLOR_GREEN),
-                            );
-                        }
-                        state.connected = true;
-                        state.data = data;
-                        if let Some(status) = state.data.status.as_ref() {
-                            state.debug_controls = !state.beginner_mode && status.debug_enabled;
-                        }
-                        update_cycle_history(&mut state);
-                        update_watch_values(&mut client, &mut state);
-                        update_event_alerts(&mut state);
+    loop {
+        if last_refresh.elapsed() >= refresh {
+            match fetch_data(&mut client) {
+                Ok(data) => {
+                    if !state.connected {
+                        push_alert(
+                            &mut state,
+                            "CONNECTED Control restored.",
+                            Style::default().fg(COLOR_GREEN),
+                        );
                     }
-                    Err(_) => {
-                        if state.connected {
-                            push_alert(
-                                &mut state,
-                                "DISCONNECTED Reconnecting...",
-                                Style::default().fg(COLOR_AMBER),
-                            );
-                        }
-                        state.connected = false;
-                        if let Ok(new_client) =
-                            ControlClient::connect(endpoint.clone(), auth_token.clone())
-                        {
-                            client = new_client;
-                        }
+                    state.connected = true;
+                    state.data = data;
+                    if let Some(status) = state.data.status.as_ref() {
+                        state.debug_controls = !state.beginner_mode && status.debug_enabled;
                     }
+                    update_cycle_history(&mut state);
+                    update_watch_values(&mut client, &mut state);
+                    update_event_alerts(&mut state);
+                    update_log_lines(&mut state);
                 }
-                last_refresh = Instant::now();
-            }
-
-            terminal.draw(|frame| render_ui(frame.size(), frame, &state, no_input))?;
-
-            if event::poll(StdDuration::from_millis(50))? {
-                if let Event::Key(key) = event::read()? {
-                    if handle_key(key, &mut client, &mut state, no_input)? {
-                        break;
+                Err(_) => {
+                    if state.connected {
+                        push_alert(
+                            &mut state,
+                            "DISCONNECTED Reconnecting...",
+                            Style::default().fg(COLOR_AMBER),
+                        );
+                    }
+                    state.connected = false;
+                    if let Ok(new_client) =
+                        ControlClient::connect(endpoint.clone(), auth_token.clone())
+                    {
+                        client = new_client;
                     }
                 }
             }
+            last_refresh = Instant::now();
         }
-        Ok(())
-    })();
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
-    result
+        terminal.draw(|frame| render_ui(frame.size(), frame, &state, no_input))?;
+
+        if event::poll(StdDuration::from_millis(50))? {
+            match event::read()? {
+                Event::Key(key) if handle_key(key, &mut client, &mut state, no_input)? => break,
+                Event::Mouse(mouse)
+                    if handle_mouse(
+                        mouse,
+                        terminal.size()?,
+                        &mut client,
+                        &mut state,
+                        no_input,
+                    )? =>
+                {
+                    break
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(())
 }
 
 fn resolve_endpoint(
@@ -545,6 +683,7 @@ fn resolve_endpoint(
 struct ConsoleConfig {
     layout: Option<Vec<PanelKind>>,
     refresh_ms: Option<u64>,
+    mouse: Option<bool>,
 }
 
 fn load_console_config(root: &Path) -> ConsoleConfig {
@@ -571,6 +710,10 @@ fn parse_events(response: &serde_json::Value) -> Vec<EventSnapshot> {
     parsing::parse_events(response)
 }
 
+fn parse_alarms(response: &serde_json::Value) -> Vec<AlarmSnapshot> {
+    parsing::parse_alarms(response)
+}
+
 fn parse_settings(response: &serde_json::Value) -> Option<SettingsSnapshot> {
     parsing::parse_settings(response)
 }
@@ -584,6 +727,16 @@ fn handle_key(
     input::handle_key(key, client, state, no_input)
 }
 
+fn handle_mouse(
+    event: MouseEvent,
+    area: Rect,
+    client: &mut ControlClient,
+    state: &mut UiState,
+    no_input: bool,
+) -> anyhow::Result<bool> {
+    mouse::handle_mouse(event, area, client, state, no_input)
+}
+
 fn handle_settings_select(
     input: &str,
     _client: &mut ControlClient,
@@ -687,6 +840,9 @@ fn render_panel(
         PanelKind::Events => render_events_panel(area, frame, state, focused),
         PanelKind::Tasks => render_tasks_panel(area, frame, state, focused),
         PanelKind::Watch => render_watch_panel(area, frame, state, focused),
+        PanelKind::Trends => render_trends_panel(area, frame, state, focused),
+        PanelKind::Logs => render_logs_panel(area, frame, state, focused),
+        PanelKind::Alarms => render_alarms_panel(area, frame, state, focused),
     }
 }
 
@@ -871,6 +1027,28 @@ fn render_status_panel(area: Rect, frame: &mut ratatui::Frame<'_>, state: &UiSta
         format!("http://{}", settings.web_listen)
     };
     lines.push(label_value_line("Web", &web, 12, value_style()));
+    if let Some(program) = status.program.as_ref() {
+        lines.push(label_value_line("Program", program, 12, value_style()));
+    }
+    if let Some(built) = status.built.as_ref() {
+        lines.push(label_value_line("Built", built, 12, value_style()));
+    }
+    if let Some(role) = status.redundancy_role.as_ref() {
+        let style = if role == "active" {
+            Style::default().fg(COLOR_GREEN)
+        } else {
+            Style::default().fg(COLOR_AMBER)
+        };
+        lines.push(label_value_line("Redundancy", role, 12, style));
+    }
+    if let Some(safe_state) = status.safe_state.as_ref() {
+        let style = if safe_state.ends_with("(applied)") {
+            Style::default().fg(COLOR_AMBER)
+        } else {
+            value_style()
+        };
+        lines.push(label_value_line("Safe state", safe_state, 12, style));
+    }
     if !status.fault.is_empty() && status.fault != "none" {
         lines.push(label_value_line(
             "Fault",
@@ -935,37 +1113,10 @@ fn render_events_panel(area: Rect, frame: &mut ratatui::Frame<'_>, state: &UiSta
         .data
         .events
         .iter()
+        .skip(state.events_scroll)
         .take(area.height.saturating_sub(2) as usize)
     {
-        let (tag, tag_style, msg_style) = match event.kind {
-            EventKind::Fault => (
-                "[FAULT]",
-                Style::default().fg(COLOR_RED).add_modifier(Modifier::BOLD),
-                Style::default().fg(Color::White),
-            ),
-            EventKind::Warn => (
-                "[WARN]",
-                Style::default()
-                    .fg(COLOR_AMBER)
-                    .add_modifier(Modifier::BOLD),
-                Style::default().fg(Color::White),
-            ),
-            EventKind::Info => (
-                "[INFO]",
-                Style::default().fg(COLOR_CYAN),
-                Style::default().fg(Color::White),
-            ),
-        };
-        let mut spans = Vec::new();
-        if let Some(ts) = event.timestamp.as_ref() {
-            spans.push(Span::styled(
-                format!("{ts} "),
-                Style::default().fg(COLOR_INFO).add_modifier(Modifier::DIM),
-            ));
-        }
-        spans.push(Span::styled(format!("{tag} "), tag_style));
-        spans.push(Span::styled(event.message.clone(), msg_style));
-        lines.push(Line::from(spans));
+        lines.push(event_line(event));
     }
     let block = panel_block(PanelKind::Events, focused);
     frame.render_widget(
@@ -974,6 +1125,166 @@ fn render_events_panel(area: Rect, frame: &mut ratatui::Frame<'_>, state: &UiSta
     );
 }
 
+fn event_line(event: &EventSnapshot) -> Line<'static> {
+    let (tag, tag_style) = match event.kind {
+        EventKind::Fault => (
+            "[FAULT]",
+            Style::default().fg(COLOR_RED).add_modifier(Modifier::BOLD),
+        ),
+        EventKind::Warn => (
+            "[WARN]",
+            Style::default()
+                .fg(COLOR_AMBER)
+                .add_modifier(Modifier::BOLD),
+        ),
+        EventKind::Info => ("[INFO]", Style::default().fg(COLOR_CYAN)),
+    };
+    let mut spans = Vec::new();
+    if let Some(ts) = event.timestamp.as_ref() {
+        spans.push(Span::styled(
+            format!("{ts} "),
+            Style::default().fg(COLOR_INFO).add_modifier(Modifier::DIM),
+        ));
+    }
+    spans.push(Span::styled(format!("{tag} "), tag_style));
+    spans.push(Span::styled(
+        event.message.clone(),
+        Style::default().fg(Color::White),
+    ));
+    Line::from(spans)
+}
+
+fn render_logs_panel(area: Rect, frame: &mut ratatui::Frame<'_>, state: &UiState, focused: bool) {
+    let block = panel_block(PanelKind::Logs, focused);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut status = Vec::new();
+    if !state.log_follow {
+        status.push("paused".to_string());
+    }
+    if let Some(filter) = state.log_filter.as_ref() {
+        status.push(format!("filter: {filter}"));
+    }
+    let mut lines = Vec::new();
+    if !status.is_empty() {
+        lines.push(Line::from(Span::styled(
+            status.join(" | "),
+            Style::default().fg(COLOR_AMBER),
+        )));
+    }
+    let visible = filtered_log_lines(state);
+    let rows = (inner.height as usize).saturating_sub(lines.len());
+    let end = if state.log_follow {
+        visible.len()
+    } else {
+        visible.len().saturating_sub(state.log_scroll)
+    };
+    let start = end.saturating_sub(rows);
+    if visible.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No log lines.",
+            Style::default().fg(COLOR_INFO),
+        )));
+    }
+    lines.extend(visible[start..end].iter().map(|event| event_line(event)));
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn filtered_log_lines(state: &UiState) -> Vec<&EventSnapshot> {
+    let filter = state
+        .log_filter
+        .as_ref()
+        .map(|filter| filter.to_ascii_lowercase());
+    state
+        .log_lines
+        .iter()
+        .filter(|event| log_line_matches(event, filter.as_deref()))
+        .collect()
+}
+
+/// Case-insensitive match against an already lowercased filter.
+fn log_line_matches(event: &EventSnapshot, filter: Option<&str>) -> bool {
+    filter.is_none_or(|filter| {
+        event.message.to_ascii_lowercase().contains(filter)
+            || event.label.to_ascii_lowercase().contains(filter)
+    })
+}
+
+fn scroll_events(state: &mut UiState, delta: isize) {
+    state.events_scroll = state
+        .events_scroll
+        .saturating_add_signed(delta)
+        .min(state.data.events.len().saturating_sub(1));
+}
+
+fn scroll_logs(state: &mut UiState, delta: isize) {
+    let visible = filtered_log_lines(state).len();
+    state.log_scroll = state
+        .log_scroll
+        .saturating_add_signed(delta)
+        .min(visible.saturating_sub(1));
+    state.log_follow = false;
+}
+
+fn render_alarms_panel(area: Rect, frame: &mut ratatui::Frame<'_>, state: &UiState, focused: bool) {
+    let mut lines = Vec::new();
+    if state.data.alarms.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No active alarms.",
+            Style::default().fg(COLOR_GREEN),
+        )));
+    } else {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<8}", "STATE"), header_style()),
+            Span::raw(" "),
+            Span::styled(format!("{:<18}", "ALARM"), header_style()),
+            Span::raw(" "),
+            Span::styled(format!("{:>10}", "VALUE"), header_style()),
+            Span::raw(" "),
+            Span::styled("LIMITS", header_style()),
+        ]));
+    }
+    for alarm in state
+        .data
+        .alarms
+        .iter()
+        .take(area.height.saturating_sub(3) as usize)
+    {
+        // Unacknowledged alarms need operator attention; acknowledged ones stay visible until cleared.
+        let (tag, tag_style) = if alarm.acknowledged {
+            ("ACKED", Style::default().fg(COLOR_AMBER))
+        } else {
+            (
+                "RAISED",
+                Style::default().fg(COLOR_RED).add_modifier(Modifier::BOLD),
+            )
+        };
+        let limits = match (alarm.min, alarm.max) {
+            (Some(min), Some(max)) => format!("{min}..{max}"),
+            (Some(min), None) => format!(">= {min}"),
+            (None, Some(max)) => format!("<= {max}"),
+            (None, None) => "-".to_string(),
+        };
+        let label = if alarm.label.is_empty() {
+            alarm.id.as_str()
+        } else {
+            alarm.label.as_str()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{tag:<8}"), tag_style),
+            Span::raw(" "),
+            Span::styled(format!("{label:<18.18}"), label_style()),
+            Span::raw(" "),
+            Span::styled(format!("{:>10.2}", alarm.value), value_style()),
+            Span::raw(" "),
+            Span::styled(limits, Style::default().fg(COLOR_INFO)),
+        ]));
+    }
+    let block = panel_block(PanelKind::Alarms, focused);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 fn render_tasks_panel(area: Rect, frame: &mut ratatui::Frame<'_>, state: &UiState, focused: bool) {
     let mut lines = Vec::new();
     lines.push(Line::from(vec![
@@ -1029,6 +1340,95 @@ fn render_watch_panel(area: Rect, frame: &mut ratatui::Frame<'_>, state: &UiStat
     frame.render_widget(Paragraph::new(lines).block(block), area);
 }
 
+fn render_trends_panel(area: Rect, frame: &mut ratatui::Frame<'_>, state: &UiState, focused: bool) {
+    let block = panel_block(PanelKind::Trends, focused);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if state.watch_list.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No watches configured.",
+                Style::default().fg(COLOR_INFO),
+            ))),
+            inner,
+        );
+        return;
+    }
+
+    let label_width = 14.min(inner.width);
+    let value_width = 12.min(inner.width.saturating_sub(label_width));
+    let spark_width = inner
+        .width
+        .saturating_sub(label_width + value_width)
+        .saturating_sub(2);
+    for (row, name) in state
+        .watch_list
+        .iter()
+        .take(inner.height as usize)
+        .enumerate()
+    {
+        let y = inner.y + row as u16;
+        let samples = state.watch_trends.get(name);
+        let label: String = name.chars().take(label_width as usize).collect();
+        frame.render_widget(
+            Paragraph::new(Span::styled(label, label_style())),
+            Rect {
+                x: inner.x,
+                y,
+                width: label_width,
+                height: 1,
+            },
+        );
+        let data = trend_sparkline_data(samples, spark_width as usize);
+        frame.render_widget(
+            Sparkline::default()
+                .data(&data)
+                .max(8)
+                .style(Style::default().fg(COLOR_TEAL)),
+            Rect {
+                x: inner.x + label_width + 1,
+                y,
+                width: spark_width,
+                height: 1,
+            },
+        );
+        let last = samples
+            .and_then(|samples| samples.back())
+            .map_or("-", |sample| sample.value.as_str());
+        frame.render_widget(
+            Paragraph::new(Span::styled(last.to_string(), value_style())),
+            Rect {
+                x: inner.x + label_width + spark_width + 2,
+                y,
+                width: value_width,
+                height: 1,
+            },
+        );
+    }
+}
+
+/// Scales the latest numeric samples to sparkline levels 1..=8.
+fn trend_sparkline_data(samples: Option<&VecDeque<TrendSample>>, width: usize) -> Vec<u64> {
+    let Some(samples) = samples else {
+        return Vec::new();
+    };
+    let values: Vec<f64> = samples.iter().filter_map(|sample| sample.numeric).collect();
+    let values = &values[values.len().saturating_sub(width)..];
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = max - min;
+    values
+        .iter()
+        .map(|value| {
+            if span > 0.0 {
+                ((value - min) / span * 7.0).round() as u64 + 1
+            } else {
+                4
+            }
+        })
+        .collect()
+}
+
 fn render_prompt(area: Rect, frame: &mut ratatui::Frame<'_>, state: &UiState, no_input: bool) {
     let mut lines: Vec<Line> = Vec::new();
     for alert in state.alerts.iter().take(3) {
@@ -1213,6 +1613,14 @@ fn update_watch_values(client: &mut ControlClient, state: &mut UiState) {
     state::update_watch_values(client, state);
 }
 
+fn update_log_lines(state: &mut UiState) {
+    state::update_log_lines(state);
+}
+
+fn export_watch_trends(state: &UiState, path: &Path) -> anyhow::Result<usize> {
+    state::export_watch_trends(state, path)
+}
+
 fn update_event_alerts(state: &mut UiState) {
     state::update_event_alerts(state);
 }
@@ -1484,6 +1892,11 @@ fn command_catalog(beginner: bool) -> Vec<CommandHelp> {
             desc: "Show version, uptime",
             beginner: true,
         },
+        CommandHelp {
+            cmd: "alarm",
+            desc: "Acknowledge alarms (/alarm ack <id|all>)",
+            beginner: true,
+        },
         CommandHelp {
             cmd: "exit",
             desc: "Leave console",
@@ -1501,12 +1914,12 @@ fn command_catalog(beginner: bool) -> Vec<CommandHelp> {
         },
         CommandHelp {
             cmd: "watch",
-            desc: "Watch variable",
+            desc: "Watch variable (/watch export <file> for CSV)",
             beginner: false,
         },
         CommandHelp {
             cmd: "log",
-            desc: "Show/set log level",
+            desc: "Show/set log level, /log filter|pause|follow",
             beginner: false,
         },
         CommandHelp {
@@ -1549,7 +1962,7 @@ fn command_catalog(beginner: bool) -> Vec<CommandHelp> {
 fn is_beginner_command(head: &str) -> bool {
     matches!(
         head,
-        "help" | "status" | "settings" | "io" | "control" | "info" | "exit"
+        "help" | "status" | "settings" | "io" | "control" | "alarm" | "info" | "exit"
     )
 }
 
@@ -1872,6 +2285,11 @@ fn menu_entries(kind: MenuKind) -> Vec<MenuEntry> {
                 command: "/control step-out",
                 needs_input: false,
             },
+            MenuEntry {
+                label: "Step cycles",
+                command: "/control cycle",
+                needs_input: true,
+            },
             MenuEntry {
                 label: "Restart (warm/cold)",
                 command: "/control restart",
@@ -1951,6 +2369,11 @@ fn menu_entries(kind: MenuKind) -> Vec<MenuEntry> {
                 command: "/linking subscribe",
                 needs_input: true,
             },
+            MenuEntry {
+                label: "Show topology",
+                command: "/linking topology",
+                needs_input: false,
+            },
             MenuEntry {
                 label: "Back",
                 command: "",
@@ -1983,6 +2406,11 @@ fn menu_entries(kind: MenuKind) -> Vec<MenuEntry> {
                 command: "/log tail",
                 needs_input: true,
             },
+            MenuEntry {
+                label: "Filter Logs panel",
+                command: "/log filter",
+                needs_input: true,
+            },
             MenuEntry {
                 label: "Back",
                 command: "",
@@ -2619,6 +3047,30 @@ fn handle_control_command(
             let response = client.request(json!({"id": 1, "type": "step_out"}));
             set_simple_response(state, response, "Step out.");
         }
+        "cycle" => {
+            let cycles = match args.get(1).map(|value| value.parse::<u32>()) {
+                None => 1,
+                Some(Ok(cycles)) if cycles > 0 => cycles,
+                Some(_) => {
+                    state.prompt.set_output(vec![PromptLine::plain(
+                        "Usage: /control cycle [n]  (n >= 1)",
+                        Style::default().fg(COLOR_RED),
+                    )]);
+                    return Ok(());
+                }
+            };
+            let response = client.request(json!({
+                "id": 1,
+                "type": "cycle.step",
+                "params": { "cycles": cycles }
+            }));
+            let message = if cycles == 1 {
+                "Ran 1 cycle; paused.".to_string()
+            } else {
+                format!("Ran {cycles} cycles; paused.")
+            };
+            set_simple_response(state, response, &message);
+        }
         "restart" => {
             if args.len() < 2 {
                 open_menu(MenuKind::Restart, state);
@@ -2654,7 +3106,7 @@ fn handle_control_command(
             let response = client.request(json!({"id": 1, "type": "breakpoints.list"}));
             match response {
                 Ok(value) => {
-                    if let Some(err) = value.get("error").and_then(|v| v.as_str()) {
+                    if let Some(err) = value.get("error").and_then(control_error_message) {
                         state.prompt.set_output(vec![PromptLine::plain(
                             err.to_string(),
                             Style::default().fg(COLOR_RED),
@@ -2839,6 +3291,25 @@ fn handle_linking_command(
                 set_config_response(state, response, "Saved.");
             }
         }
+        "topology" => match client.request(json!({"id": 1, "type": "mesh.topology"})) {
+            Ok(response) => {
+                if let Some(err) = response.get("error").and_then(control_error_message) {
+                    state.prompt.set_output(vec![PromptLine::plain(
+                        err.to_string(),
+                        Style::default().fg(COLOR_RED),
+                    )]);
+                } else {
+                    let result = response.get("result").cloned().unwrap_or_default();
+                    state.prompt.set_output(mesh_topology_lines(&result));
+                }
+            }
+            Err(err) => {
+                state.prompt.set_output(vec![PromptLine::plain(
+                    format!("Error: {err}"),
+                    Style::default().fg(COLOR_RED),
+                )]);
+            }
+        },
         _ => {
             state.prompt.set_output(vec![PromptLine::plain(
                 "Unknown /linking command.",
@@ -2849,6 +3320,151 @@ fn handle_linking_command(
     Ok(())
 }
 
+/// One line per PLC with address and round-trip time, then one per shared variable.
+fn mesh_topology_lines(topology: &serde_json::Value) -> Vec<PromptLine> {
+    let empty = Vec::new();
+    let nodes = topology
+        .get("nodes")
+        .and_then(|v| v.as_array())
+        .unwrap_or(&empty);
+    let edges = topology
+        .get("edges")
+        .and_then(|v| v.as_array())
+        .unwrap_or(&empty);
+    let text = |value: &serde_json::Value, key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    let mut lines = vec![PromptLine::plain(
+        format!("Mesh topology: {} PLCs, {} links", nodes.len(), edges.len()),
+        Style::default().fg(COLOR_INFO),
+    )];
+    for node in nodes {
+        let name = text(node, "name");
+        if node.get("local").and_then(|v| v.as_bool()) == Some(true) {
+            lines.push(PromptLine::plain(
+                format!("  {name} (this PLC)"),
+                Style::default().fg(COLOR_GREEN),
+            ));
+            continue;
+        }
+        let address = node.get("address").and_then(|v| v.as_str()).unwrap_or("-");
+        let (detail, color) = match node.get("rtt_ms").and_then(|v| v.as_f64()) {
+            Some(rtt) => (format!("rtt {rtt:.1} ms"), COLOR_GREEN),
+            None if node.get("reachable").and_then(|v| v.as_bool()) == Some(true) => {
+                ("reachable".to_string(), COLOR_AMBER)
+            }
+            None => (
+                node.get("error").and_then(|v| v.as_str()).map_or_else(
+                    || "unreachable".to_string(),
+                    |err| format!("unreachable: {err}"),
+                ),
+                COLOR_RED,
+            ),
+        };
+        lines.push(PromptLine::from_segments(vec![
+            (
+                format!("  {name:<16} {address:<22} "),
+                Style::default().fg(COLOR_INFO),
+            ),
+            (detail, Style::default().fg(color)),
+        ]));
+    }
+    for edge in edges {
+        let state = edge.get("state").and_then(|v| v.as_str());
+        let color = match state {
+            Some("stale") => COLOR_RED,
+            Some("waiting") => COLOR_AMBER,
+            _ => COLOR_INFO,
+        };
+        lines.push(PromptLine::plain(
+            format!(
+                "  {} -> {}  {}{}",
+                text(edge, "from"),
+                text(edge, "to"),
+                text(edge, "variable"),
+                state
+                    .map(|state| format!("  [{state}]"))
+                    .unwrap_or_default(),
+            ),
+            Style::default().fg(color),
+        ));
+    }
+    lines
+}
+
+fn handle_alarm_command(
+    args: Vec<&str>,
+    client: &mut ControlClient,
+    state: &mut UiState,
+) -> anyhow::Result<()> {
+    let (Some(&"ack"), Some(&target)) = (args.first(), args.get(1)) else {
+        state.prompt.set_output(vec![PromptLine::plain(
+            "Usage: /alarm ack <id|all>",
+            Style::default().fg(COLOR_INFO),
+        )]);
+        return Ok(());
+    };
+    let ids = if target == "all" {
+        state
+            .data
+            .alarms
+            .iter()
+            .filter(|alarm| !alarm.acknowledged)
+            .map(|alarm| alarm.id.clone())
+            .collect::<Vec<_>>()
+    } else {
+        vec![target.to_string()]
+    };
+    if ids.is_empty() {
+        state.prompt.set_output(vec![PromptLine::plain(
+            "No unacknowledged alarms.",
+            Style::default().fg(COLOR_INFO),
+        )]);
+        return Ok(());
+    }
+    let mut acknowledged = 0;
+    for id in &ids {
+        let response =
+            client.request(json!({"id": 1, "type": "hmi.alarm.ack", "params": { "id": id }}));
+        let error = match response {
+            Ok(value) => match value.get("error").and_then(control_error_message) {
+                Some(err) => Some(err.to_string()),
+                None => {
+                    if value.get("result").and_then(|v| v.get("active")).is_some() {
+                        state.data.alarms = parse_alarms(&value);
+                    }
+                    None
+                }
+            },
+            Err(err) => Some(format!("Error: {err}")),
+        };
+        if let Some(err) = error {
+            state.prompt.set_output(vec![PromptLine::plain(
+                format!("{id}: {err}"),
+                Style::default().fg(COLOR_RED),
+            )]);
+            return Ok(());
+        }
+        acknowledged += 1;
+        if let Some(alarm) = state.data.alarms.iter_mut().find(|alarm| &alarm.id == id) {
+            alarm.acknowledged = true;
+        }
+    }
+    state.prompt.set_output(vec![PromptLine::plain(
+        if acknowledged == 1 {
+            format!("Acknowledged {}.", ids[0])
+        } else {
+            format!("Acknowledged {acknowledged} alarms.")
+        },
+        Style::default().fg(COLOR_GREEN),
+    )]);
+    Ok(())
+}
+
 fn handle_log_command(
     args: Vec<&str>,
     client: &mut ControlClient,
@@ -2858,6 +3474,52 @@ fn handle_log_command(
         open_menu(MenuKind::Log, state);
         return Ok(());
     }
+    match args[0] {
+        "filter" => {
+            let pattern = args[1..].join(" ");
+            let message = if pattern.is_empty() {
+                state.log_filter = None;
+                "Log filter cleared.".to_string()
+            } else {
+                let message = format!("Filtering logs by '{pattern}'.");
+                state.log_filter = Some(pattern);
+                message
+            };
+            state.log_scroll = 0;
+            state.prompt.set_output(vec![PromptLine::plain(
+                message,
+                Style::default().fg(COLOR_INFO),
+            )]);
+            return Ok(());
+        }
+        "pause" => {
+            state.log_follow = false;
+            state.prompt.set_output(vec![PromptLine::plain(
+                "Logs paused. PgUp/PgDn scroll, End follows.",
+                Style::default().fg(COLOR_INFO),
+            )]);
+            return Ok(());
+        }
+        "follow" => {
+            state.log_follow = true;
+            state.log_scroll = 0;
+            state.prompt.set_output(vec![PromptLine::plain(
+                "Following logs.",
+                Style::default().fg(COLOR_INFO),
+            )]);
+            return Ok(());
+        }
+        "clear" => {
+            state.log_lines.clear();
+            state.log_scroll = 0;
+            state.prompt.set_output(vec![PromptLine::plain(
+                "Logs cleared.",
+                Style::default().fg(COLOR_INFO),
+            )]);
+            return Ok(());
+        }
+        _ => {}
+    }
     if args[0] == "tail" {
         let limit = args
             .get(1)
@@ -2867,7 +3529,7 @@ fn handle_log_command(
             client.request(json!({"id": 1, "type": "events.tail", "params": { "limit": limit }}));
         match response {
             Ok(value) => {
-                if let Some(err) = value.get("error").and_then(|v| v.as_str()) {
+                if let Some(err) = value.get("error").and_then(control_error_message) {
                     state.prompt.set_output(vec![PromptLine::plain(
                         err.to_string(),
                         Style::default().fg(COLOR_RED),
@@ -2883,7 +3545,14 @@ fn handle_log_command(
                         let lines = events
                             .into_iter()
                             .map(|event| {
-                                PromptLine::plain(event.label, Style::default().fg(COLOR_INFO))
+                                PromptLine::plain(
+                                    if event.message.is_empty() {
+                                        event.label
+                                    } else {
+                                        format!("{} {}", event.label, event.message)
+                                    },
+                                    Style::default().fg(COLOR_INFO),
+                                )
                             })
                             .collect();
                         state.prompt.set_output(lines);
@@ -3007,7 +3676,7 @@ struct ConfigSetResult {
 fn config_set(client: &mut ControlClient, params: serde_json::Value) -> ConfigSetResult {
     let response = client.request(json!({"id": 1, "type": "config.set", "params": params}));
     if let Ok(value) = response {
-        if let Some(err) = value.get("error").and_then(|v| v.as_str()) {
+        if let Some(err) = value.get("error").and_then(control_error_message) {
             return ConfigSetResult {
                 ok: false,
                 restart_required: false,
@@ -3058,7 +3727,7 @@ fn set_simple_response(
 ) {
     match response {
         Ok(value) => {
-            if let Some(err) = value.get("error").and_then(|v| v.as_str()) {
+            if let Some(err) = value.get("error").and_then(control_error_message) {
                 state.prompt.set_output(vec![PromptLine::plain(
                     err.to_string(),
                     Style::default().fg(COLOR_RED),
@@ -3197,6 +3866,33 @@ impl ControlClient {
         })
     }
 
+    /// Like [`Self::connect`], but bounded so one unreachable PLC cannot stall the fleet view.
+    fn connect_with_timeout(
+        endpoint: ControlEndpoint,
+        token: Option<String>,
+        timeout: StdDuration,
+    ) -> anyhow::Result<Self> {
+        let stream = match &endpoint {
+            ControlEndpoint::Tcp(addr) => {
+                let stream = std::net::TcpStream::connect_timeout(addr, timeout)?;
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                ControlStream::Tcp(stream)
+            }
+            #[cfg(unix)]
+            ControlEndpoint::Unix(path) => {
+                let stream = std::os::unix::net::UnixStream::connect(path)?;
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                ControlStream::Unix(stream)
+            }
+        };
+        Ok(Self {
+            token,
+            reader: io::BufReader::new(stream),
+        })
+    }
+
     fn request(&mut self, mut payload: serde_json::Value) -> anyhow::Result<serde_json::Value> {
         if let Some(token) = self.token.as_deref() {
             payload["auth"] = json!(token);
@@ -3290,6 +3986,10 @@ mod tests {
                     simulation_mode: "production".to_string(),
                     simulation_time_scale: 1,
                     simulation_warning: String::new(),
+                    redundancy_role: None,
+                    program: None,
+                    built: None,
+                    safe_state: None,
                 }),
                 tasks: vec![TaskSnapshot {
                     name: "MainTask".to_string(),
@@ -3318,6 +4018,7 @@ mod tests {
                     timestamp: Some("2026-01-01T00:00:00Z".to_string()),
                     message: "Started".to_string(),
                 }],
+                alarms: Vec::new(),
                 settings: Some(SettingsSnapshot {
                     log_level: "info".to_string(),
                     watchdog_enabled: true,
@@ -3350,6 +4051,9 @@ mod tests {
                 PanelKind::Events,
             ],
             focus: None,
+            selected_panel: None,
+            row_split: 50,
+            dragging_split: false,
             panel_page: 0,
             settings_index: 0,
             menu_index: 0,
@@ -3360,6 +4064,13 @@ mod tests {
             cycle_history: VecDeque::from([2, 4, 6, 8]),
             watch_list: vec!["Main.counter".to_string()],
             watch_values: vec![("Main.counter".to_string(), "42".to_string())],
+            watch_trends: HashMap::new(),
+            log_lines: VecDeque::new(),
+            log_last_key: None,
+            log_filter: None,
+            log_follow: true,
+            log_scroll: 0,
+            events_scroll: 0,
             forced_io,
             alerts: VecDeque::new(),
             seen_events: HashSet::new(),
@@ -3408,6 +4119,18 @@ mod tests {
                 "simulation_mode": "simulation",
                 "simulation_time_scale": 12,
                 "simulation_warning": "Simulation mode active (time scale x12). Not for live hardware.",
+                "redundancy_role": "standby",
+                "safety": {
+                    "default": "zero",
+                    "outputs": [{ "address": "%QX0.1", "action": "hold" }],
+                    "active": true
+                },
+                "program": {
+                    "version": "2.1.0",
+                    "git_hash": "3f2a9c41d0be",
+                    "built_at": "2026-10-16T08:30:00.000Z",
+                    "author": "Line Team"
+                },
                 "metrics": {
                     "cycle_ms": {
                         "min": 0.1,
@@ -3425,6 +4148,16 @@ mod tests {
         assert_eq!(status.simulation_mode, "simulation");
         assert_eq!(status.simulation_time_scale, 12);
         assert!(status.simulation_warning.contains("Not for live hardware"));
+        assert_eq!(status.redundancy_role.as_deref(), Some("standby"));
+        assert_eq!(status.program.as_deref(), Some("2.1.0 (3f2a9c41d0be)"));
+        assert_eq!(
+            status.built.as_deref(),
+            Some("2026-10-16 08:30:00 by Line Team")
+        );
+        assert_eq!(
+            status.safe_state.as_deref(),
+            Some("zero, 1 override (applied)")
+        );
     }
 
     #[test]
@@ -3646,4 +4379,348 @@ settings log=debug web=127.0.0.1:8080 control=rw publish=Main.speed subscribe=li
         execute_command("/p", &mut client, &mut state).expect("pause shortcut");
         assert!(prompt_output_text(&state).contains("Paused."));
     }
+
+    #[test]
+    fn trend_values_parse_runtime_debug_values() {
+        assert_eq!(state::trend_value("\"DInt(7)\""), Some(7.0));
+        assert_eq!(state::trend_value("LReal(-1.5)"), Some(-1.5));
+        assert_eq!(state::trend_value("Bool(true)"), Some(1.0));
+        assert_eq!(state::trend_value("FALSE"), Some(0.0));
+        assert_eq!(state::trend_value("String(\"idle\")"), None);
+        assert_eq!(state::trend_value("unavailable"), None);
+        assert_eq!(PanelKind::parse("trends"), Some(PanelKind::Trends));
+    }
+
+    #[test]
+    fn watch_export_writes_buffered_samples_as_csv() {
+        let mut client = test_client();
+        let mut state = sample_state();
+        state.watch_trends.insert(
+            "Main.counter".to_string(),
+            VecDeque::from(vec![
+                TrendSample {
+                    timestamp_ms: 1000,
+                    value: "DInt(41)".to_string(),
+                    numeric: Some(41.0),
+                },
+                TrendSample {
+                    timestamp_ms: 2000,
+                    value: "DInt(42)".to_string(),
+                    numeric: Some(42.0),
+                },
+            ]),
+        );
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("trust-ui-trends-{stamp}.csv"));
+
+        execute_command(
+            &format!("/watch export {}", path.display()),
+            &mut client,
+            &mut state,
+        )
+        .expect("watch export");
+        assert!(prompt_output_text(&state).contains("Exported 2 samples"));
+        let csv = std::fs::read_to_string(&path).expect("read csv");
+        assert_eq!(
+            csv,
+            "timestamp_ms,variable,value,raw\n\
+             1000,Main.counter,41,DInt(41)\n\
+             2000,Main.counter,42,DInt(42)\n"
+        );
+        let _ = std::fs::remove_file(&path);
+
+        execute_command("/unwatch Main.counter", &mut client, &mut state).expect("unwatch");
+        assert!(state.watch_trends.is_empty());
+    }
+
+    #[test]
+    fn logs_panel_tails_new_events_with_filter_and_pause() {
+        let mut client = test_client();
+        let mut state = sample_state();
+        let response = |time_ns: &[u64]| {
+            let events = time_ns
+                .iter()
+                .rev()
+                .map(|time_ns| {
+                    json!({ "type": "fault", "error": format!("div by zero {time_ns}"), "time_ns": time_ns })
+                })
+                .chain(std::iter::once(json!({ "type": "cycle_end", "cycle": 9, "time_ns": 1 })))
+                .collect::<Vec<_>>();
+            json!({ "ok": true, "result": { "events": events } })
+        };
+
+        state.data.events = parse_events(&response(&[1_000_000_000, 2_000_000_000]));
+        assert_eq!(state.data.events.len(), 2);
+        assert_eq!(state.data.events[0].kind, EventKind::Fault);
+        assert_eq!(state.data.events[0].timestamp.as_deref(), Some("2.000s"));
+        update_log_lines(&mut state);
+        update_log_lines(&mut state);
+        assert_eq!(state.log_lines.len(), 2);
+        assert_eq!(state.log_lines[0].message, "div by zero 1000000000");
+
+        execute_command("/log pause", &mut client, &mut state).expect("log pause");
+        state.data.events = parse_events(&response(&[2_000_000_000, 3_000_000_000]));
+        update_log_lines(&mut state);
+        assert_eq!(state.log_lines.len(), 3);
+        assert_eq!(state.log_scroll, 1);
+
+        execute_command("/log filter ZERO 3", &mut client, &mut state).expect("log filter");
+        let visible = filtered_log_lines(&state);
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].message, "div by zero 3000000000");
+
+        execute_command("/log follow", &mut client, &mut state).expect("log follow");
+        assert!(state.log_follow);
+        assert_eq!(state.log_scroll, 0);
+        assert_eq!(PanelKind::parse("logs"), Some(PanelKind::Logs));
+    }
+
+    #[test]
+    fn alarm_ack_all_acknowledges_raised_alarms() {
+        let mut client = test_client();
+        let mut state = sample_state();
+        state.data.alarms = parse_alarms(&json!({
+            "ok": true,
+            "result": {
+                "connected": true,
+                "active": [
+                    { "id": "alarm:Main.level", "label": "Tank level", "state": "raised", "acknowledged": false, "value": 97.5, "min": 0.0, "max": 90.0 },
+                    { "id": "alarm:Main.temp", "label": "Temperature", "state": "acknowledged", "acknowledged": true, "value": 81.0, "max": 80.0 }
+                ],
+                "history": []
+            }
+        }));
+        assert_eq!(state.data.alarms.len(), 2);
+        assert_eq!(state.data.alarms[1].min, None);
+
+        execute_command("/alarm", &mut client, &mut state).expect("alarm usage");
+        assert!(prompt_output_text(&state).contains("Usage: /alarm ack <id|all>"));
+
+        execute_command("/alarm ack all", &mut client, &mut state).expect("alarm ack all");
+        assert!(prompt_output_text(&state).contains("Acknowledged alarm:Main.level."));
+        assert!(state.data.alarms.iter().all(|alarm| alarm.acknowledged));
+
+        execute_command("/alarm ack all", &mut client, &mut state).expect("alarm ack none");
+        assert!(prompt_output_text(&state).contains("No unacknowledged alarms."));
+        assert_eq!(PanelKind::parse("alarms"), Some(PanelKind::Alarms));
+    }
+
+    #[test]
+    fn linking_topology_lists_peers_and_links() {
+        let lines = mesh_topology_lines(&json!({
+            "nodes": [
+                { "name": "line-1", "local": true },
+                { "name": "line-2", "local": false, "address": "10.0.0.2:5200", "rtt_ms": 1.24 },
+                { "name": "line-3", "local": false, "reachable": false, "error": "timed out" }
+            ],
+            "edges": [
+                { "from": "line-2", "to": "line-1", "variable": "Temp", "state": "stale" },
+                { "from": "line-1", "to": "line-2", "variable": "Speed", "state": null }
+            ]
+        }));
+        let text = lines
+            .iter()
+            .map(|line| {
+                line.segments
+                    .iter()
+                    .map(|(text, _)| text.as_str())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(text[0], "Mesh topology: 3 PLCs, 2 links");
+        assert_eq!(text[1], "  line-1 (this PLC)");
+        assert!(text[2].contains("10.0.0.2:5200") && text[2].ends_with("rtt 1.2 ms"));
+        assert!(text[3].ends_with("unreachable: timed out"));
+        assert_eq!(text[4], "  line-2 -> line-1  Temp  [stale]");
+        assert_eq!(text[5], "  line-1 -> line-2  Speed");
+        assert_eq!(
+            menu_entries(MenuKind::Linking)
+                .iter()
+                .filter(|entry| entry.command == "/linking topology")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn mouse_clicks_select_panels_menu_entries_and_drag_divider() {
+        let mut client = test_client();
+        let mut state = sample_state();
+        let event = state.data.events[0].clone();
+        state.data.events = vec![event; 6];
+        let screen = Rect::new(0, 0, 120, 40);
+        let at = |kind, column, row| MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        let left = MouseButton::Left;
+
+        let (panels_area, _) = render::ui_areas(screen, &state);
+        let (_, events_rect) = render::panel_areas(panels_area, &state)
+            .into_iter()
+            .find(|(panel, _)| *panel == PanelKind::Events)
+            .expect("events panel");
+        let (column, row) = (events_rect.x + 2, events_rect.y + 2);
+        handle_mouse(
+            at(MouseEventKind::Down(left), column, row),
+            screen,
+            &mut client,
+            &mut state,
+            false,
+        )
+        .expect("click panel");
+        assert_eq!(state.selected_panel, Some(PanelKind::Events));
+        assert_eq!(state.focus, None);
+        handle_mouse(
+            at(MouseEventKind::ScrollDown, column, row),
+            screen,
+            &mut client,
+            &mut state,
+            false,
+        )
+        .expect("wheel");
+        assert_eq!(state.events_scroll, 3);
+
+        let divider = mouse::row_divider(panels_area, &state).expect("two panel rows");
+        handle_mouse(
+            at(MouseEventKind::Down(left), 5, divider),
+            screen,
+            &mut client,
+            &mut state,
+            false,
+        )
+        .expect("grab divider");
+        assert!(state.dragging_split);
+        handle_mouse(
+            at(MouseEventKind::Drag(left), 5, panels_area.bottom() - 1),
+            screen,
+            &mut client,
+            &mut state,
+            false,
+        )
+        .expect("drag divider");
+        handle_mouse(
+            at(MouseEventKind::Up(left), 5, panels_area.bottom() - 1),
+            screen,
+            &mut client,
+            &mut state,
+            false,
+        )
+        .expect("release divider");
+        assert!(!state.dragging_split);
+        assert_eq!(state.row_split, 80);
+
+        open_menu(MenuKind::Log, &mut state);
+        let (_, prompt_area) = render::ui_areas(screen, &state);
+        handle_mouse(
+            at(MouseEventKind::ScrollDown, 2, prompt_area.y + 1),
+            screen,
+            &mut client,
+            &mut state,
+            false,
+        )
+        .expect("wheel menu");
+        assert_eq!(state.menu_index, 1);
+        handle_mouse(
+            at(MouseEventKind::Down(left), 2, prompt_area.y + 3),
+            screen,
+            &mut client,
+            &mut state,
+            true,
+        )
+        .expect("read-only click");
+        assert_eq!(state.prompt.mode, PromptMode::Menu(MenuKind::Log));
+        handle_mouse(
+            at(MouseEventKind::Down(left), 2, prompt_area.y + 3),
+            screen,
+            &mut client,
+            &mut state,
+            false,
+        )
+        .expect("click entry");
+        assert_eq!(state.prompt.mode, PromptMode::Normal);
+    }
+
+    #[test]
+    fn fleet_file_and_discovery_build_dashboard_rows() {
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("trust-ui-fleet-{stamp}.toml"));
+        std::fs::write(
+            &path,
+            r#"
+[[plc]]
+name = "line-01"
+endpoint = "tcp://127.0.0.1:9101"
+
+[[plc]]
+name = "line-02"
+endpoint = "tcp://127.0.0.1:9102"
+token = "secret"
+"#,
+        )
+        .expect("write fleet file");
+        let mut members = fleet::load_fleet_file(&path).expect("load fleet file");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[1].token.as_deref(), Some("secret"));
+
+        let discovered = |name: &str, control: &str| crate::discovery::DiscoveryEntry {
+            id: name.into(),
+            name: name.into(),
+            addresses: Vec::new(),
+            web_port: None,
+            mesh_port: None,
+            control: Some(control.into()),
+        };
+        fleet::merge_discovered(
+            &mut members,
+            &[
+                discovered("line-02", "tcp://127.0.0.1:9102"),
+                discovered("line-03", "tcp://127.0.0.1:9103"),
+                discovered("remote", "tcp://10.0.0.5:9000"),
+            ],
+        );
+        let names = members
+            .iter()
+            .map(|member| member.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["line-01", "line-02", "line-03"]);
+        assert_eq!(members[2].expected_resource.as_deref(), Some("line-03"));
+
+        let mut state = fleet::fleet_state(members);
+        assert_eq!(
+            fleet::handle_fleet_key(KeyEvent::from(KeyCode::Up), &mut state),
+            fleet::FleetAction::None
+        );
+        assert_eq!(state.selected, 2);
+        fleet::handle_fleet_key(KeyEvent::from(KeyCode::Down), &mut state);
+        assert_eq!(state.selected, 0);
+        assert_eq!(
+            fleet::handle_fleet_key(KeyEvent::from(KeyCode::Enter), &mut state),
+            fleet::FleetAction::Open
+        );
+        assert_eq!(
+            fleet::handle_fleet_key(KeyEvent::from(KeyCode::Char('q')), &mut state),
+            fleet::FleetAction::Quit
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 8)).expect("create test terminal");
+        terminal
+            .draw(|frame| fleet::render_fleet(frame.size(), frame, &state))
+            .expect("draw fleet");
+        let buffer = terminal.backend().buffer();
+        let row = (0..100)
+            .map(|x| buffer.get(x, 2).symbol())
+            .collect::<String>();
+        assert!(row.contains("> line-01"), "{row}");
+        assert!(row.contains("[OFFLINE]"), "{row}");
+    }
 }
@@ -52,7 +52,12 @@ pub(super) fn load_console_config(root: &Path) -> ConsoleConfig {
         .get("refresh_ms")
         .and_then(|value| value.as_integer())
         .and_then(|value| u64::try_from(value).ok());
-    ConsoleConfig { layout, refresh_ms }
+    let mouse = console.get("mouse").and_then(|value| value.as_bool());
+    ConsoleConfig {
+        layout,
+        refresh_ms,
+        mouse,
+    }
 }
 
 pub(super) fn fetch_data(client: &mut ControlClient) -> anyhow::Result<UiData> {
@@ -60,13 +65,16 @@ pub(super) fn fetch_data(client: &mut ControlClient) -> anyhow::Result<UiData> {
     let tasks = client.request(json!({"id": 2, "type": "tasks.stats"}))?;
     let io = client.request(json!({"id": 3, "type": "io.list"}))?;
     let events =
-        client.request(json!({"id": 4, "type": "events.tail", "params": { "limit": 20 }}))?;
+        client.request(json!({"id": 4, "type": "events.tail", "params": { "limit": 200 }}))?;
     let settings = client.request(json!({"id": 5, "type": "config.get"}))?;
+    let alarms =
+        client.request(json!({"id": 6, "type": "hmi.alarms.get", "params": { "limit": 1 }}))?;
     Ok(UiData {
         status: parse_status(&status),
         tasks: parse_tasks(&tasks),
         io: parse_io(&io),
         events: parse_events(&events),
+        alarms: parse_alarms(&alarms),
         settings: parse_settings(&settings),
     })
 }
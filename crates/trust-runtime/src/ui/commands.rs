@@ -44,7 +44,7 @@ pub(super) fn execute_command(
 
     if state.beginner_mode && !is_beginner_command(head) {
         state.prompt.set_output(vec![PromptLine::plain(
-            "Beginner mode: use /help, /status, /settings, /io, /control, /info, /exit.",
+            "Beginner mode: use /help, /status, /settings, /io, /control, /alarm, /info, /exit.",
             Style::default().fg(COLOR_AMBER),
         )]);
         return Ok(false);
@@ -79,6 +79,9 @@ pub(super) fn execute_command(
         "control" => {
             handle_control_command(parts.collect::<Vec<_>>(), client, state)?;
         }
+        "alarm" => {
+            handle_alarm_command(parts.collect::<Vec<_>>(), client, state)?;
+        }
         "access" => {
             handle_access_command(parts.collect::<Vec<_>>(), client, state)?;
         }
@@ -91,8 +94,30 @@ pub(super) fn execute_command(
         "reload" => {
             handle_reload_command(client, state)?;
         }
-        "watch" => {
-            if let Some(name) = parts.next() {
+        "watch" => match parts.next() {
+            Some("export") => match parts.next() {
+                Some(file) => {
+                    let path = PathBuf::from(file);
+                    let line = match export_watch_trends(state, &path) {
+                        Ok(count) => PromptLine::plain(
+                            format!("Exported {count} samples to {}.", path.display()),
+                            Style::default().fg(COLOR_GREEN),
+                        ),
+                        Err(err) => PromptLine::plain(
+                            format!("Export failed: {err}"),
+                            Style::default().fg(COLOR_RED),
+                        ),
+                    };
+                    state.prompt.set_output(vec![line]);
+                }
+                None => {
+                    state.prompt.set_output(vec![PromptLine::plain(
+                        "Usage: /watch export <file>",
+                        Style::default().fg(COLOR_INFO),
+                    )]);
+                }
+            },
+            Some(name) => {
                 if !state.watch_list.iter().any(|v| v == name) {
                     state.watch_list.push(name.to_string());
                 }
@@ -101,11 +126,18 @@ pub(super) fn execute_command(
                     Style::default().fg(COLOR_GREEN),
                 )]);
             }
-        }
+            None => {
+                state.prompt.set_output(vec![PromptLine::plain(
+                    "Usage: /watch <name> | /watch export <file>",
+                    Style::default().fg(COLOR_INFO),
+                )]);
+            }
+        },
         "unwatch" => match parts.next() {
             Some("all") => {
                 state.watch_list.clear();
                 state.watch_values.clear();
+                state.watch_trends.clear();
                 state.prompt.set_output(vec![PromptLine::plain(
                     "Watches cleared.",
                     Style::default().fg(COLOR_INFO),
@@ -113,6 +145,7 @@ pub(super) fn execute_command(
             }
             Some(name) => {
                 state.watch_list.retain(|v| v != name);
+                state.watch_trends.remove(name);
                 state.prompt.set_output(vec![PromptLine::plain(
                     format!("Stopped watching {name}."),
                     Style::default().fg(COLOR_INFO),
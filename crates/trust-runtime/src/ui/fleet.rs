@@ -0,0 +1,375 @@
+use super::*;
+
+use crate::discovery::{browse_runtimes, DiscoveryEntry};
+
+const FLEET_CONNECT_TIMEOUT: StdDuration = StdDuration::from_millis(500);
+const FLEET_DISCOVERY_WINDOW: StdDuration = StdDuration::from_secs(2);
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub(super) struct FleetMember {
+    pub(super) name: String,
+    pub(super) endpoint: String,
+    #[serde(default)]
+    pub(super) token: Option<String>,
+    /// Resource name announced over discovery. Control endpoints are local
+    /// (loopback or unix socket), so a runtime answering under another name
+    /// means the announced endpoint does not reach that PLC from here.
+    #[serde(skip)]
+    pub(super) expected_resource: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct FleetFile {
+    #[serde(default)]
+    plc: Vec<FleetMember>,
+}
+
+struct FleetRow {
+    member: FleetMember,
+    client: Option<ControlClient>,
+    status: Option<StatusSnapshot>,
+    error: Option<String>,
+}
+
+pub(super) struct FleetState {
+    rows: Vec<FleetRow>,
+    pub(super) selected: usize,
+    message: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FleetAction {
+    None,
+    Open,
+    Quit,
+}
+
+pub(super) fn run_fleet_ui(
+    fleet: Option<PathBuf>,
+    discover: bool,
+    token: Option<String>,
+    refresh_ms: u64,
+    no_input: bool,
+    beginner: bool,
+) -> anyhow::Result<()> {
+    let token = token.or_else(|| std::env::var("TRUST_CTL_TOKEN").ok());
+    let mut members = match fleet.as_deref() {
+        Some(path) => load_fleet_file(path)?,
+        None => Vec::new(),
+    };
+    if discover {
+        let entries = browse_runtimes(FLEET_DISCOVERY_WINDOW).map_err(anyhow::Error::from)?;
+        merge_discovered(&mut members, &entries);
+    }
+    if members.is_empty() {
+        anyhow::bail!("no PLCs to show; list them in a fleet file or use --discover");
+    }
+    for member in &mut members {
+        if member.token.is_none() {
+            member.token = token.clone();
+        }
+    }
+    let mut state = fleet_state(members);
+
+    let mut terminal = enter_terminal(true)?;
+    let result = run_fleet_loop(&mut terminal, &mut state, refresh_ms, no_input, beginner);
+    leave_terminal(&mut terminal, true)?;
+    result
+}
+
+pub(super) fn load_fleet_file(path: &Path) -> anyhow::Result<Vec<FleetMember>> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| anyhow::anyhow!("failed to read '{}': {err}", path.display()))?;
+    let file: FleetFile = toml::from_str(&text)
+        .map_err(|err| anyhow::anyhow!("invalid fleet file '{}': {err}", path.display()))?;
+    for member in &file.plc {
+        ControlEndpoint::parse(&member.endpoint)
+            .map_err(|err| anyhow::anyhow!("PLC '{}': {err}", member.name))?;
+    }
+    Ok(file.plc)
+}
+
+/// Adds discovered runtimes whose control endpoint is not already listed.
+pub(super) fn merge_discovered(members: &mut Vec<FleetMember>, entries: &[DiscoveryEntry]) {
+    for entry in entries {
+        let Some(endpoint) = entry.control.as_ref() else {
+            continue;
+        };
+        if ControlEndpoint::parse(endpoint).is_err()
+            || members
+                .iter()
+                .any(|member| member.endpoint == endpoint.as_str())
+        {
+            continue;
+        }
+        members.push(FleetMember {
+            name: entry.name.to_string(),
+            endpoint: endpoint.to_string(),
+            token: None,
+            expected_resource: Some(entry.name.to_string()),
+        });
+    }
+}
+
+pub(super) fn fleet_state(members: Vec<FleetMember>) -> FleetState {
+    FleetState {
+        rows: members
+            .into_iter()
+            .map(|member| FleetRow {
+                member,
+                client: None,
+                status: None,
+                error: None,
+            })
+            .collect(),
+        selected: 0,
+        message: None,
+    }
+}
+
+fn run_fleet_loop(
+    terminal: &mut ConsoleTerminal,
+    state: &mut FleetState,
+    refresh_ms: u64,
+    no_input: bool,
+    beginner: bool,
+) -> anyhow::Result<()> {
+    let refresh = StdDuration::from_millis(refresh_ms);
+    let mut last_refresh: Option<Instant> = None;
+    loop {
+        if last_refresh.is_none_or(|at| at.elapsed() >= refresh) {
+            for row in &mut state.rows {
+                poll_member(row);
+            }
+            last_refresh = Some(Instant::now());
+        }
+
+        terminal.draw(|frame| render_fleet(frame.size(), frame, state))?;
+
+        if event::poll(StdDuration::from_millis(50))? {
+            let action = match event::read()? {
+                Event::Key(key) => handle_fleet_key(key, state),
+                Event::Mouse(mouse) => handle_fleet_mouse(mouse, state),
+                _ => FleetAction::None,
+            };
+            match action {
+                FleetAction::Quit => break,
+                FleetAction::Open => {
+                    open_member(terminal, state, refresh_ms, no_input, beginner)?;
+                    last_refresh = None;
+                }
+                FleetAction::None => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+fn poll_member(row: &mut FleetRow) {
+    if row.client.is_none() {
+        let connected = ControlEndpoint::parse(&row.member.endpoint)
+            .map_err(anyhow::Error::from)
+            .and_then(|endpoint| {
+                ControlClient::connect_with_timeout(
+                    endpoint,
+                    row.member.token.clone(),
+                    FLEET_CONNECT_TIMEOUT,
+                )
+            });
+        match connected {
+            Ok(client) => row.client = Some(client),
+            Err(err) => {
+                row.status = None;
+                row.error = Some(err.to_string());
+                return;
+            }
+        }
+    }
+    let Some(client) = row.client.as_mut() else {
+        return;
+    };
+    match client.request(json!({"id": 1, "type": "status"})) {
+        Ok(response) => {
+            if let Some(err) = response.get("error").and_then(control_error_message) {
+                row.status = None;
+                row.error = Some(err.to_string());
+                return;
+            }
+            let status = parse_status(&response);
+            match (row.member.expected_resource.as_ref(), status) {
+                (Some(expected), Some(status)) if status.resource != *expected => {
+                    row.status = None;
+                    row.error = Some(format!("endpoint answers as {}", status.resource));
+                }
+                (_, status) => {
+                    row.status = status;
+                    row.error = None;
+                }
+            }
+        }
+        Err(err) => {
+            row.client = None;
+            row.status = None;
+            row.error = Some(err.to_string());
+        }
+    }
+}
+
+pub(super) fn handle_fleet_key(key: KeyEvent, state: &mut FleetState) -> FleetAction {
+    let count = state.rows.len().max(1);
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.selected = (state.selected + count - 1) % count;
+            FleetAction::None
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.selected = (state.selected + 1) % count;
+            FleetAction::None
+        }
+        KeyCode::Enter => FleetAction::Open,
+        KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => FleetAction::Quit,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => FleetAction::Quit,
+        _ => FleetAction::None,
+    }
+}
+
+/// Rows start below the border and header; clicking the selected row opens it.
+fn handle_fleet_mouse(event: MouseEvent, state: &mut FleetState) -> FleetAction {
+    if event.kind != MouseEventKind::Down(MouseButton::Left) {
+        return FleetAction::None;
+    }
+    let Some(index) = (event.row as usize).checked_sub(2) else {
+        return FleetAction::None;
+    };
+    if index >= state.rows.len() {
+        return FleetAction::None;
+    }
+    if index == state.selected {
+        return FleetAction::Open;
+    }
+    state.selected = index;
+    FleetAction::None
+}
+
+/// Switches into the single-PLC console for the selected row until it exits.
+fn open_member(
+    terminal: &mut ConsoleTerminal,
+    state: &mut FleetState,
+    refresh_ms: u64,
+    no_input: bool,
+    beginner: bool,
+) -> anyhow::Result<()> {
+    let Some(row) = state.rows.get(state.selected) else {
+        return Ok(());
+    };
+    let member = row.member.clone();
+    let connected = ControlEndpoint::parse(&member.endpoint)
+        .map_err(anyhow::Error::from)
+        .and_then(|endpoint| {
+            ControlClient::connect(endpoint.clone(), member.token.clone())
+                .map(|client| (endpoint, client))
+        });
+    let (endpoint, client) = match connected {
+        Ok(connected) => connected,
+        Err(err) => {
+            state.message = Some(format!("{}: {err}", member.name));
+            return Ok(());
+        }
+    };
+    state.message = None;
+    terminal.clear()?;
+    run_console(
+        terminal,
+        client,
+        ConsoleTarget {
+            endpoint,
+            auth_token: member.token,
+            bundle_root: None,
+        },
+        refresh_ms,
+        no_input,
+        beginner,
+    )?;
+    terminal.clear()?;
+    Ok(())
+}
+
+pub(super) fn render_fleet(area: Rect, frame: &mut ratatui::Frame<'_>, state: &FleetState) {
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!("  {:<16}", "PLC"), header_style()),
+        Span::styled(format!("{:<11}", "STATE"), header_style()),
+        Span::styled(format!("{:>8}", "CYCLE"), header_style()),
+        Span::styled(format!("{:>8}", "AVG"), header_style()),
+        Span::styled(format!("{:>7}", "FAULTS"), header_style()),
+        Span::styled(format!("{:>5}", "OVR"), header_style()),
+        Span::raw("  "),
+        Span::styled("ENDPOINT", header_style()),
+    ])];
+    for (index, row) in state.rows.iter().enumerate() {
+        let selected = index == state.selected;
+        let name_style = if selected {
+            label_style().add_modifier(Modifier::REVERSED)
+        } else {
+            label_style()
+        };
+        let (chip, chip_style) = match row.status.as_ref() {
+            Some(status) => status_chip(status.state.as_str()),
+            None => (
+                "[OFFLINE]".to_string(),
+                Style::default().fg(COLOR_RED).add_modifier(Modifier::BOLD),
+            ),
+        };
+        let (last, avg, faults, overruns) = match row.status.as_ref() {
+            Some(status) => (
+                format!("{:.1}ms", status.cycle_last),
+                format!("{:.1}ms", status.cycle_avg),
+                status.faults.to_string(),
+                status.overruns.to_string(),
+            ),
+            None => ("-".into(), "-".into(), "-".into(), "-".into()),
+        };
+        let fault_style = if row.status.as_ref().is_some_and(|status| status.faults > 0) {
+            Style::default().fg(COLOR_RED)
+        } else {
+            value_style()
+        };
+        let mut spans = vec![
+            Span::styled(if selected { "> " } else { "  " }, label_style()),
+            Span::styled(format!("{:<16.16}", row.member.name), name_style),
+            Span::styled(format!("{chip:<11}"), chip_style),
+            Span::styled(format!("{last:>8}"), value_style()),
+            Span::styled(format!("{avg:>8}"), value_style()),
+            Span::styled(format!("{faults:>7}"), fault_style),
+            Span::styled(format!("{overruns:>5}"), value_style()),
+            Span::raw("  "),
+            Span::styled(row.member.endpoint.clone(), Style::default().fg(COLOR_INFO)),
+        ];
+        if let Some(error) = row.error.as_ref() {
+            spans.push(Span::styled(
+                format!("  {error}"),
+                Style::default().fg(COLOR_AMBER),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        state
+            .message
+            .clone()
+            .unwrap_or_else(|| "Up/Down select   Enter open console   q quit".to_string()),
+        Style::default().fg(COLOR_INFO),
+    )));
+    let online = state.rows.iter().filter(|row| row.status.is_some()).count();
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" Fleet {online}/{} online ", state.rows.len()),
+            Style::default()
+                .fg(COLOR_YELLOW)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Plain)
+        .border_style(Style::default().fg(COLOR_TEAL));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
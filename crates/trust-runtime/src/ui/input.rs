@@ -41,6 +41,23 @@ pub(super) fn handle_key(
         return Ok(false);
     }
 
+    match key.code {
+        KeyCode::PageUp => {
+            scroll_logs(state, 10);
+            return Ok(false);
+        }
+        KeyCode::PageDown => {
+            scroll_logs(state, -10);
+            return Ok(false);
+        }
+        KeyCode::End => {
+            state.log_follow = true;
+            state.log_scroll = 0;
+            return Ok(false);
+        }
+        _ => {}
+    }
+
     let action = match key.code {
         KeyCode::Char('p') | KeyCode::Char('P') => Some("pause"),
         KeyCode::Char('r') | KeyCode::Char('R') => Some("resume"),
@@ -0,0 +1,124 @@
+use super::*;
+
+/// Smallest share of the panel area either row can be dragged down to.
+const MIN_ROW_SPLIT: u16 = 20;
+const WHEEL_STEP: isize = 3;
+
+pub(super) fn handle_mouse(
+    event: MouseEvent,
+    area: Rect,
+    client: &mut ControlClient,
+    state: &mut UiState,
+    no_input: bool,
+) -> anyhow::Result<bool> {
+    let (panels_area, prompt_area) = render::ui_areas(area, state);
+    let (column, row) = (event.column, event.row);
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if row_divider(panels_area, state) == Some(row) {
+                state.dragging_split = true;
+                return Ok(false);
+            }
+            if contains(prompt_area, column, row) {
+                if no_input {
+                    return Ok(false);
+                }
+                return click_prompt_entry(prompt_area, row, client, state);
+            }
+            if let Some((panel, rect)) = panel_at(panels_area, state, column, row) {
+                // The title bar toggles between the grid and the zoomed panel.
+                if row == rect.y {
+                    state.focus = match state.focus {
+                        Some(_) => None,
+                        None => Some(panel),
+                    };
+                }
+                state.selected_panel = Some(panel);
+            }
+        }
+        MouseEventKind::Drag(MouseButton::Left) if state.dragging_split => {
+            let offset = u32::from(row.saturating_sub(panels_area.y)) + 1;
+            let percent = (offset * 100 / u32::from(panels_area.height.max(1))) as u16;
+            state.row_split = percent.clamp(MIN_ROW_SPLIT, 100 - MIN_ROW_SPLIT);
+        }
+        MouseEventKind::Up(MouseButton::Left) => {
+            state.dragging_split = false;
+        }
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+            let up = event.kind == MouseEventKind::ScrollUp;
+            if contains(prompt_area, column, row) {
+                scroll_prompt_selection(state, if up { -1 } else { 1 });
+                return Ok(false);
+            }
+            let delta = if up { WHEEL_STEP } else { -WHEEL_STEP };
+            match panel_at(panels_area, state, column, row) {
+                Some((PanelKind::Logs, _)) => scroll_logs(state, delta),
+                // Events are listed newest first, so scrolling down moves to older ones.
+                Some((PanelKind::Events, _)) => scroll_events(state, -delta),
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.right() && row >= area.y && row < area.bottom()
+}
+
+fn panel_at(area: Rect, state: &UiState, column: u16, row: u16) -> Option<(PanelKind, Rect)> {
+    render::panel_areas(area, state)
+        .into_iter()
+        .find(|(_, rect)| contains(*rect, column, row))
+}
+
+/// The bottom border of the upper panel row doubles as the drag handle.
+pub(super) fn row_divider(area: Rect, state: &UiState) -> Option<u16> {
+    let areas = render::panel_areas(area, state);
+    let first = areas.first()?.1;
+    areas
+        .iter()
+        .any(|(_, rect)| rect.y == first.bottom())
+        .then(|| first.bottom() - 1)
+}
+
+/// Maps a click on a menu line to its 1-based choice and submits it like typed input.
+fn click_prompt_entry(
+    prompt_area: Rect,
+    row: u16,
+    client: &mut ControlClient,
+    state: &mut UiState,
+) -> anyhow::Result<bool> {
+    let (header, count) = match state.prompt.mode {
+        PromptMode::Menu(kind) => (
+            if kind == MenuKind::Restart { 2 } else { 1 },
+            menu_entries(kind).len(),
+        ),
+        PromptMode::SettingsSelect => (1, settings_menu_entries(state).len()),
+        PromptMode::IoSelect(action) => (2, io_entries_for_action(state, action).len()),
+        PromptMode::IoValueSelect => (2, 3),
+        _ => return Ok(false),
+    };
+    let line = usize::from(row - prompt_area.y);
+    let shown_alerts = state.alerts.len().min(3);
+    let Some(index) = line.checked_sub(shown_alerts + header) else {
+        return Ok(false);
+    };
+    if index >= count {
+        return Ok(false);
+    }
+    state.prompt.deactivate();
+    state.prompt.clear_suggestions();
+    input::handle_prompt_submit(&(index + 1).to_string(), client, state)
+}
+
+fn scroll_prompt_selection(state: &mut UiState, delta: i32) {
+    match state.prompt.mode {
+        PromptMode::SettingsSelect => move_settings_selection(state, delta),
+        PromptMode::Menu(kind) => move_menu_selection(state, kind, delta),
+        PromptMode::IoSelect(action) => move_io_selection(state, action, delta),
+        PromptMode::IoValueSelect => move_io_value_selection(state, delta),
+        _ => {}
+    }
+}
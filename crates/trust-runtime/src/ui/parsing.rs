@@ -100,9 +100,61 @@ pub(super) fn parse_status(response: &serde_json::Value) -> Option<StatusSnapsho
             .and_then(|v| v.as_str())
             .unwrap_or("")
             .to_string(),
+        redundancy_role: result
+            .get("redundancy_role")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        program: result.get("program").and_then(program_label),
+        built: result.get("program").and_then(built_label),
+        safe_state: result.get("safety").and_then(safe_state_label),
     })
 }
 
+/// "<default>, <n> overrides (applied)" from the status `safety` object.
+fn safe_state_label(safety: &serde_json::Value) -> Option<String> {
+    let default = safety.get("default")?.as_str()?;
+    let overrides = safety
+        .get("outputs")
+        .and_then(|v| v.as_array())
+        .map_or(0, Vec::len);
+    let mut label = match overrides {
+        0 => default.to_string(),
+        1 => format!("{default}, 1 override"),
+        n => format!("{default}, {n} overrides"),
+    };
+    if safety.get("active").and_then(|v| v.as_bool()) == Some(true) {
+        label.push_str(" (applied)");
+    }
+    Some(label)
+}
+
+/// "<version> (<git hash>)" from the status `program` object.
+fn program_label(program: &serde_json::Value) -> Option<String> {
+    let version = program.get("version").and_then(|v| v.as_str());
+    let git_hash = program.get("git_hash").and_then(|v| v.as_str());
+    match (version, git_hash) {
+        (Some(version), Some(hash)) => Some(format!("{version} ({hash})")),
+        (Some(version), None) => Some(version.to_string()),
+        (None, Some(hash)) => Some(hash.to_string()),
+        (None, None) => None,
+    }
+}
+
+/// "<date> <time> by <author>" from the status `program` object.
+fn built_label(program: &serde_json::Value) -> Option<String> {
+    let built_at = program
+        .get("built_at")
+        .and_then(|v| v.as_str())
+        .map(|text| text.get(..19).unwrap_or(text).replace('T', " "));
+    let author = program.get("author").and_then(|v| v.as_str());
+    match (built_at, author) {
+        (Some(built_at), Some(author)) => Some(format!("{built_at} by {author}")),
+        (Some(built_at), None) => Some(built_at),
+        (None, Some(author)) => Some(format!("by {author}")),
+        (None, None) => None,
+    }
+}
+
 pub(super) fn parse_tasks(response: &serde_json::Value) -> Vec<TaskSnapshot> {
     response
         .get("result")
@@ -176,6 +228,13 @@ pub(super) fn parse_io(response: &serde_json::Value) -> Vec<IoEntry> {
 }
 
 pub(super) fn parse_events(response: &serde_json::Value) -> Vec<EventSnapshot> {
+    if let Some(events) = response
+        .get("result")
+        .and_then(|v| v.get("events"))
+        .and_then(|v| v.as_array())
+    {
+        return events.iter().filter_map(parse_runtime_event).collect();
+    }
     response
         .get("result")
         .and_then(|v| v.as_array())
@@ -217,6 +276,84 @@ pub(super) fn parse_events(response: &serde_json::Value) -> Vec<EventSnapshot> {
         .unwrap_or_default()
 }
 
+/// Maps a control-server runtime event; per-cycle and per-task markers are skipped.
+fn parse_runtime_event(entry: &serde_json::Value) -> Option<EventSnapshot> {
+    let kind = entry.get("type").and_then(|v| v.as_str())?;
+    let name = entry
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    let (kind, message) = match kind {
+        "fault" => (
+            EventKind::Fault,
+            entry
+                .get("error")
+                .and_then(|v| v.as_str())
+                .unwrap_or("fault")
+                .to_string(),
+        ),
+        "task_overrun" => (
+            EventKind::Warn,
+            format!(
+                "Task {name} overrun ({} missed)",
+                entry
+                    .get("missed")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or_default()
+            ),
+        ),
+        "cycle_start" | "cycle_end" | "task_start" | "task_end" => return None,
+        other => (EventKind::Info, other.to_string()),
+    };
+    Some(EventSnapshot {
+        label: entry
+            .get("type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("event")
+            .to_ascii_uppercase(),
+        kind,
+        timestamp: entry
+            .get("time_ns")
+            .and_then(|v| v.as_u64())
+            .map(|ns| format!("{:.3}s", ns as f64 / 1_000_000_000.0)),
+        message,
+    })
+}
+
+pub(super) fn parse_alarms(response: &serde_json::Value) -> Vec<AlarmSnapshot> {
+    response
+        .get("result")
+        .and_then(|v| v.get("active"))
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .map(|entry| AlarmSnapshot {
+                    id: entry
+                        .get("id")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    label: entry
+                        .get("label")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    acknowledged: entry
+                        .get("acknowledged")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                    value: entry
+                        .get("value")
+                        .and_then(|v| v.as_f64())
+                        .unwrap_or_default(),
+                    min: entry.get("min").and_then(|v| v.as_f64()),
+                    max: entry.get("max").and_then(|v| v.as_f64()),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 pub(super) fn parse_settings(response: &serde_json::Value) -> Option<SettingsSnapshot> {
     let result = response.get("result")?;
     Some(SettingsSnapshot {
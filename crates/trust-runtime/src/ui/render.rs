@@ -6,6 +6,13 @@ pub(super) fn render_ui(
     state: &UiState,
     no_input: bool,
 ) {
+    let (panels_area, prompt_area) = ui_areas(area, state);
+    render_panels(panels_area, frame, state);
+    render_prompt(prompt_area, frame, state, no_input);
+}
+
+/// Splits the screen into the panel grid and the prompt below it.
+pub(super) fn ui_areas(area: Rect, state: &UiState) -> (Rect, Rect) {
     let mut prompt_height = (state.prompt.output.len() + state.alerts.len() + 1) as u16;
     let is_menu = matches!(
         state.prompt.mode,
@@ -32,17 +39,27 @@ pub(super) fn render_ui(
             Constraint::Length(prompt_height),
         ])
         .split(area);
-    render_panels(layout[0], frame, state);
-    render_prompt(layout[1], frame, state, no_input);
+    (layout[0], layout[1])
 }
 
 pub(super) fn render_panels(area: Rect, frame: &mut ratatui::Frame<'_>, state: &UiState) {
+    for (panel, rect) in panel_areas(area, state) {
+        let focused = state.focus.is_some() || state.selected_panel == Some(panel);
+        render_panel(rect, frame, state, panel, focused);
+    }
+}
+
+/// Where each visible panel is drawn; shared by rendering and mouse hit-testing.
+pub(super) fn panel_areas(area: Rect, state: &UiState) -> Vec<(PanelKind, Rect)> {
     if let Some(panel) = state.focus {
-        render_panel(area, frame, state, panel, true);
-        return;
+        return vec![(panel, area)];
     }
     let width = area.width;
     let panels = state.layout.as_slice();
+    let rows = [
+        Constraint::Percentage(state.row_split),
+        Constraint::Percentage(100 - state.row_split),
+    ];
     if width >= 120 && panels.len() >= 4 {
         let cols = Layout::default()
             .direction(Direction::Horizontal)
@@ -50,17 +67,18 @@ pub(super) fn render_panels(area: Rect, frame: &mut ratatui::Frame<'_>, state: &
             .split(area);
         let left = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .constraints(rows)
             .split(cols[0]);
         let right = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .constraints(rows)
             .split(cols[1]);
-        render_panel(left[0], frame, state, panels[0], false);
-        render_panel(right[0], frame, state, panels[1], false);
-        render_panel(left[1], frame, state, panels[2], false);
-        render_panel(right[1], frame, state, panels[3], false);
-        return;
+        return vec![
+            (panels[0], left[0]),
+            (panels[1], right[0]),
+            (panels[2], left[1]),
+            (panels[3], right[1]),
+        ];
     }
 
     if width >= 80 {
@@ -69,20 +87,21 @@ pub(super) fn render_panels(area: Rect, frame: &mut ratatui::Frame<'_>, state: &
         let start = page * 2;
         let stack = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .constraints(rows)
             .split(area);
+        let mut areas = Vec::new();
         if let Some(panel) = panels.get(start) {
-            render_panel(stack[0], frame, state, *panel, false);
+            areas.push((*panel, stack[0]));
         }
         if let Some(panel) = panels.get(start + 1) {
-            render_panel(stack[1], frame, state, *panel, false);
+            areas.push((*panel, stack[1]));
         }
-        return;
+        return areas;
     }
 
     let panel = panels
         .get(state.panel_page % panels.len().max(1))
         .copied()
         .unwrap_or(PanelKind::Status);
-    render_panel(area, frame, state, panel, false);
+    vec![(panel, area)]
 }
@@ -24,6 +24,7 @@ pub(super) fn update_cycle_history(state: &mut UiState) {
 pub(super) fn update_watch_values(client: &mut ControlClient, state: &mut UiState) {
     if state.watch_list.is_empty() {
         state.watch_values.clear();
+        state.watch_trends.clear();
         return;
     }
     let mut out = Vec::new();
@@ -37,7 +38,7 @@ pub(super) fn update_watch_values(client: &mut ControlClient, state: &mut UiStat
             Ok(value) => {
                 if let Some(result) = value.get("result").and_then(|r| r.get("value")) {
                     out.push((name.clone(), result.to_string()));
-                } else if let Some(err) = value.get("error").and_then(|e| e.as_str()) {
+                } else if let Some(err) = value.get("error").and_then(control_error_message) {
                     out.push((name.clone(), format!("error: {err}")));
                 } else {
                     out.push((name.clone(), "unknown".to_string()));
@@ -46,16 +47,137 @@ pub(super) fn update_watch_values(client: &mut ControlClient, state: &mut UiStat
             Err(_) => out.push((name.clone(), "unavailable".to_string())),
         }
     }
+    record_watch_trends(state, &out);
     state.watch_values = out;
 }
 
+fn record_watch_trends(state: &mut UiState, values: &[(String, String)]) {
+    let timestamp_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default();
+    let watch_list = &state.watch_list;
+    state
+        .watch_trends
+        .retain(|name, _| watch_list.iter().any(|watched| watched == name));
+    for (name, raw) in values {
+        let value = raw.trim_matches('"').to_string();
+        let numeric = trend_value(&value);
+        let samples = state.watch_trends.entry(name.clone()).or_default();
+        while samples.len() >= TREND_CAPACITY {
+            samples.pop_front();
+        }
+        samples.push_back(TrendSample {
+            timestamp_ms,
+            value,
+            numeric,
+        });
+    }
+}
+
+/// Extracts a plottable number from a watch value such as `DInt(7)` or `TRUE`.
+pub(super) fn trend_value(text: &str) -> Option<f64> {
+    let text = text.trim().trim_matches('"');
+    let inner = match (text.find('('), text.strip_suffix(')')) {
+        (Some(open), Some(body)) => &body[open + 1..],
+        _ => text,
+    };
+    match inner.trim().to_ascii_lowercase().as_str() {
+        "true" => Some(1.0),
+        "false" => Some(0.0),
+        other => other.parse::<f64>().ok().filter(|value| value.is_finite()),
+    }
+}
+
+/// Writes buffered watch samples as CSV and returns the number of rows.
+pub(super) fn export_watch_trends(state: &UiState, path: &Path) -> anyhow::Result<usize> {
+    let mut rows = Vec::new();
+    for name in &state.watch_list {
+        let Some(samples) = state.watch_trends.get(name) else {
+            continue;
+        };
+        for sample in samples {
+            rows.push((sample.timestamp_ms, name.as_str(), sample));
+        }
+    }
+    rows.sort_by_key(|(timestamp_ms, _, _)| *timestamp_ms);
+
+    let mut csv = String::from("timestamp_ms,variable,value,raw\n");
+    for (timestamp_ms, name, sample) in &rows {
+        let numeric = sample
+            .numeric
+            .map_or_else(String::new, |value| value.to_string());
+        csv.push_str(&format!(
+            "{timestamp_ms},{},{numeric},{}\n",
+            csv_field(name),
+            csv_field(&sample.value)
+        ));
+    }
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, csv)?;
+    Ok(rows.len())
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Appends events that arrived since the last refresh to the Logs panel.
+pub(super) fn update_log_lines(state: &mut UiState) {
+    let key = |event: &EventSnapshot| {
+        format!(
+            "{}|{}|{}",
+            event.timestamp.as_deref().unwrap_or_default(),
+            event.label,
+            event.message
+        )
+    };
+    // `events.tail` lists the newest event first.
+    let mut fresh = Vec::new();
+    for event in &state.data.events {
+        if state.log_last_key.as_deref() == Some(key(event).as_str()) {
+            break;
+        }
+        fresh.push(event.clone());
+    }
+    if let Some(newest) = state.data.events.first() {
+        state.log_last_key = Some(key(newest));
+    }
+    let filter = state
+        .log_filter
+        .as_ref()
+        .map(|filter| filter.to_ascii_lowercase());
+    for event in fresh.into_iter().rev() {
+        // Keep the paused view anchored while new matching lines arrive below it.
+        if !state.log_follow && log_line_matches(&event, filter.as_deref()) {
+            state.log_scroll += 1;
+        }
+        if state.log_lines.len() >= LOG_CAPACITY {
+            state.log_lines.pop_front();
+        }
+        state.log_lines.push_back(event);
+    }
+}
+
 pub(super) fn update_event_alerts(state: &mut UiState) {
     let events = state.data.events.clone();
     for event in events {
-        if state.seen_events.contains(&event.label) {
+        let key = match event.timestamp.as_deref() {
+            Some(timestamp) => format!("{}@{timestamp}", event.label),
+            None => event.label.clone(),
+        };
+        if !state.seen_events.insert(key) {
             continue;
         }
-        state.seen_events.insert(event.label.clone());
         match event.kind {
             EventKind::Fault => push_alert(
                 state,
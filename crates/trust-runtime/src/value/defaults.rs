@@ -142,7 +142,8 @@ fn default_value_for_type(
 fn array_len(dimensions: &[(i64, i64)]) -> Result<usize, DefaultValueError> {
     let mut total: i128 = 1;
     for (lower, upper) in dimensions {
-        if upper < lower {
+        // ARRAY[*] has no storage of its own; its bounds come from the caller.
+        if upper < lower || (*lower == 0 && *upper == i64::MAX) {
             return Err(DefaultValueError::InvalidArrayBounds);
         }
         let len = (*upper as i128) - (*lower as i128) + 1;
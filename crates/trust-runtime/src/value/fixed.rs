@@ -0,0 +1,136 @@
+//! Q16.16 fixed-point arithmetic.
+//!
+//! All operations are integer-only so results are bit-identical on every
+//! target, unlike REAL/LREAL math whose rounding can differ between x86 and
+//! ARM builds. Out-of-range results saturate at [`Fixed::MIN`]/[`Fixed::MAX`].
+
+/// Signed Q16.16 number stored in a DINT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed(i32);
+
+impl Fixed {
+    /// Number of fractional bits.
+    pub const FRAC_BITS: u32 = 16;
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1 << Self::FRAC_BITS);
+    pub const MIN: Self = Self(i32::MIN);
+    pub const MAX: Self = Self(i32::MAX);
+
+    #[must_use]
+    pub const fn from_raw(raw: i32) -> Self {
+        Self(raw)
+    }
+
+    #[must_use]
+    pub const fn raw(self) -> i32 {
+        self.0
+    }
+
+    /// Converts an integer, saturating outside -32768..=32767.
+    #[must_use]
+    pub fn from_int(value: i64) -> Self {
+        Self::saturate(i128::from(value) << Self::FRAC_BITS)
+    }
+
+    /// Converts a float rounded to the nearest step; `None` for NaN.
+    #[must_use]
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if value.is_nan() {
+            return None;
+        }
+        let scaled = (value * f64::from(Self::ONE.0)).round();
+        Some(if scaled >= f64::from(i32::MAX) {
+            Self::MAX
+        } else if scaled <= f64::from(i32::MIN) {
+            Self::MIN
+        } else {
+            Self(scaled as i32)
+        })
+    }
+
+    /// Exact conversion to LREAL.
+    #[must_use]
+    pub fn to_f64(self) -> f64 {
+        f64::from(self.0) / f64::from(Self::ONE.0)
+    }
+
+    /// Rounds to the nearest integer, ties away from zero.
+    #[must_use]
+    pub fn round_to_int(self) -> i32 {
+        let half = i64::from(Self::ONE.0 / 2);
+        let raw = i64::from(self.0);
+        let rounded = if raw >= 0 {
+            (raw + half) >> Self::FRAC_BITS
+        } else {
+            -((-raw + half) >> Self::FRAC_BITS)
+        };
+        rounded as i32
+    }
+
+    #[must_use]
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+
+    #[must_use]
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    /// Product rounded to the nearest step.
+    #[must_use]
+    pub fn saturating_mul(self, other: Self) -> Self {
+        let product = i128::from(self.0) * i128::from(other.0);
+        Self::saturate((product + (1 << (Self::FRAC_BITS - 1))) >> Self::FRAC_BITS)
+    }
+
+    /// Quotient truncated toward zero; `None` when dividing by zero.
+    #[must_use]
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.0 == 0 {
+            return None;
+        }
+        Some(Self::saturate(
+            (i128::from(self.0) << Self::FRAC_BITS) / i128::from(other.0),
+        ))
+    }
+
+    #[must_use]
+    pub fn saturating_abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+
+    /// Square root truncated to the step below; negative inputs yield zero.
+    #[must_use]
+    pub fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Self::ZERO;
+        }
+        let radicand = (self.0 as u64) << Self::FRAC_BITS;
+        Self(isqrt(radicand) as i32)
+    }
+
+    fn saturate(value: i128) -> Self {
+        Self(value.clamp(i128::from(i32::MIN), i128::from(i32::MAX)) as i32)
+    }
+}
+
+/// Bitwise integer square root (floor).
+fn isqrt(value: u64) -> u64 {
+    let mut remainder = value;
+    let mut root = 0u64;
+    let mut bit = 1u64 << 62;
+    while bit > remainder {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if remainder >= root + bit {
+            remainder -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root
+}
@@ -4,14 +4,20 @@
 
 mod datetime;
 mod defaults;
+mod fixed;
+mod overlay;
 mod partial_access;
 mod reference;
 mod size;
+mod time_literal;
 mod types;
 
 pub use datetime::*;
 pub use defaults::*;
+pub use fixed::*;
+pub use overlay::*;
 pub use partial_access::*;
 pub use reference::*;
 pub use size::*;
+pub use time_literal::*;
 pub use types::*;
@@ -0,0 +1,210 @@
+//! Byte overlays between UNION variants.
+//!
+//! All variants of a UNION share storage starting at byte 0. Overlay bytes are
+//! little-endian and packed without padding: BOOL and CHAR take one byte, enums
+//! take the size of their base type, and arrays and structs place their elements
+//! back to back. Strings, date/time values, references and instances have no
+//! overlay layout.
+
+use trust_hir::types::TypeRegistry;
+use trust_hir::{Type, TypeId};
+
+use super::{size_of_type, EnumValue, StructValue, Value};
+
+/// Encodes a value into its overlay bytes, or `None` when the value has no fixed
+/// byte layout.
+pub fn encode_overlay(value: &Value, registry: &TypeRegistry) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    encode_into(value, registry, &mut bytes)?;
+    Some(bytes)
+}
+
+/// Decodes overlay bytes into a value shaped like `template`. Returns `None` when
+/// the bytes are too short or do not form a valid value (e.g. an enum number
+/// without a member).
+pub fn decode_overlay(template: &Value, bytes: &[u8], registry: &TypeRegistry) -> Option<Value> {
+    let mut cursor = bytes;
+    decode_from(template, &mut cursor, registry)
+}
+
+/// Propagates a write to variant `written` of a UNION value to the other variants.
+///
+/// Each other variant keeps its trailing bytes when it is larger than the written
+/// one. Variants without a byte layout are left unchanged. Values that are not
+/// UNIONs are ignored.
+pub fn sync_union_variants(union: &mut StructValue, written: &str, registry: &TypeRegistry) {
+    if !is_union(&union.type_name, registry) {
+        return;
+    }
+    let Some(bytes) = union
+        .fields
+        .get(written)
+        .and_then(|value| encode_overlay(value, registry))
+    else {
+        return;
+    };
+    for (name, variant) in union.fields.iter_mut() {
+        if name == written {
+            continue;
+        }
+        let Some(mut current) = encode_overlay(variant, registry) else {
+            continue;
+        };
+        let shared = current.len().min(bytes.len());
+        current[..shared].copy_from_slice(&bytes[..shared]);
+        if let Some(updated) = decode_overlay(variant, &current, registry) {
+            *variant = updated;
+        }
+    }
+}
+
+fn is_union(type_name: &str, registry: &TypeRegistry) -> bool {
+    let mut type_id = registry.lookup(type_name);
+    while let Some(id) = type_id {
+        match registry.get(id) {
+            Some(Type::Union { .. }) => return true,
+            Some(Type::Alias { target, .. }) => type_id = Some(*target),
+            _ => return false,
+        }
+    }
+    false
+}
+
+fn encode_into(value: &Value, registry: &TypeRegistry, out: &mut Vec<u8>) -> Option<()> {
+    match value {
+        Value::Bool(value) => out.push(u8::from(*value)),
+        Value::SInt(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::Int(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::DInt(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::LInt(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::USInt(value) | Value::Byte(value) | Value::Char(value) => out.push(*value),
+        Value::UInt(value) | Value::Word(value) | Value::WChar(value) => {
+            out.extend_from_slice(&value.to_le_bytes())
+        }
+        Value::UDInt(value) | Value::DWord(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::ULInt(value) | Value::LWord(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::Real(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::LReal(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::Enum(value) => {
+            let (size, _) = enum_layout(value, registry)?;
+            out.extend_from_slice(&value.numeric_value.to_le_bytes()[..size]);
+        }
+        Value::Array(array) => {
+            for element in &array.elements {
+                encode_into(element, registry, out)?;
+            }
+        }
+        Value::Struct(struct_value) if is_union(&struct_value.type_name, registry) => {
+            // Variants are kept in sync, so the largest one holds every byte.
+            let mut largest = Vec::new();
+            for variant in struct_value.fields.values() {
+                let bytes = encode_overlay(variant, registry)?;
+                if bytes.len() > largest.len() {
+                    largest = bytes;
+                }
+            }
+            out.extend_from_slice(&largest);
+        }
+        Value::Struct(struct_value) => {
+            for field in struct_value.fields.values() {
+                encode_into(field, registry, out)?;
+            }
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+fn decode_from(template: &Value, cursor: &mut &[u8], registry: &TypeRegistry) -> Option<Value> {
+    let value = match template {
+        Value::Bool(_) => Value::Bool(take::<1>(cursor)?[0] != 0),
+        Value::SInt(_) => Value::SInt(i8::from_le_bytes(take(cursor)?)),
+        Value::Int(_) => Value::Int(i16::from_le_bytes(take(cursor)?)),
+        Value::DInt(_) => Value::DInt(i32::from_le_bytes(take(cursor)?)),
+        Value::LInt(_) => Value::LInt(i64::from_le_bytes(take(cursor)?)),
+        Value::USInt(_) => Value::USInt(take::<1>(cursor)?[0]),
+        Value::Byte(_) => Value::Byte(take::<1>(cursor)?[0]),
+        Value::Char(_) => Value::Char(take::<1>(cursor)?[0]),
+        Value::UInt(_) => Value::UInt(u16::from_le_bytes(take(cursor)?)),
+        Value::Word(_) => Value::Word(u16::from_le_bytes(take(cursor)?)),
+        Value::WChar(_) => Value::WChar(u16::from_le_bytes(take(cursor)?)),
+        Value::UDInt(_) => Value::UDInt(u32::from_le_bytes(take(cursor)?)),
+        Value::DWord(_) => Value::DWord(u32::from_le_bytes(take(cursor)?)),
+        Value::ULInt(_) => Value::ULInt(u64::from_le_bytes(take(cursor)?)),
+        Value::LWord(_) => Value::LWord(u64::from_le_bytes(take(cursor)?)),
+        Value::Real(_) => Value::Real(f32::from_le_bytes(take(cursor)?)),
+        Value::LReal(_) => Value::LReal(f64::from_le_bytes(take(cursor)?)),
+        Value::Enum(value) => decode_enum(value, cursor, registry)?,
+        Value::Array(array) => {
+            let mut decoded = array.clone();
+            for element in &mut decoded.elements {
+                *element = decode_from(element, cursor, registry)?;
+            }
+            Value::Array(decoded)
+        }
+        Value::Struct(struct_value) if is_union(&struct_value.type_name, registry) => {
+            let start = *cursor;
+            let mut decoded = struct_value.clone();
+            let mut consumed = 0;
+            for variant in decoded.fields.values_mut() {
+                let mut variant_cursor = start;
+                *variant = decode_from(variant, &mut variant_cursor, registry)?;
+                consumed = consumed.max(start.len() - variant_cursor.len());
+            }
+            *cursor = &start[consumed..];
+            Value::Struct(decoded)
+        }
+        Value::Struct(struct_value) => {
+            let mut decoded = struct_value.clone();
+            for field in decoded.fields.values_mut() {
+                *field = decode_from(field, cursor, registry)?;
+            }
+            Value::Struct(decoded)
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn decode_enum(value: &EnumValue, cursor: &mut &[u8], registry: &TypeRegistry) -> Option<Value> {
+    let (size, signed) = enum_layout(value, registry)?;
+    let bytes = cursor.get(..size)?;
+    *cursor = &cursor[size..];
+    let negative = signed && bytes.last().is_some_and(|byte| byte & 0x80 != 0);
+    let mut raw = if negative { [0xFF; 8] } else { [0; 8] };
+    raw[..size].copy_from_slice(bytes);
+    let numeric_value = i64::from_le_bytes(raw);
+    let type_id = registry.lookup(&value.type_name)?;
+    let Some(Type::Enum { values, .. }) = registry.get(type_id) else {
+        return None;
+    };
+    let (variant_name, _) = values.iter().find(|(_, member)| *member == numeric_value)?;
+    Some(Value::Enum(EnumValue {
+        type_name: value.type_name.clone(),
+        variant_name: variant_name.clone(),
+        numeric_value,
+    }))
+}
+
+/// Byte size and signedness of an enum's base type.
+fn enum_layout(value: &EnumValue, registry: &TypeRegistry) -> Option<(usize, bool)> {
+    let type_id = registry.lookup(&value.type_name)?;
+    let Some(Type::Enum { base, .. }) = registry.get(type_id) else {
+        return None;
+    };
+    let size = usize::try_from(size_of_type(*base, registry).ok()?).ok()?;
+    if size == 0 || size > 8 {
+        return None;
+    }
+    let signed = matches!(
+        *base,
+        TypeId::SINT | TypeId::INT | TypeId::DINT | TypeId::LINT
+    );
+    Some((size, signed))
+}
+
+fn take<const N: usize>(cursor: &mut &[u8]) -> Option<[u8; N]> {
+    let (head, rest) = cursor.split_first_chunk::<N>()?;
+    *cursor = rest;
+    Some(*head)
+}
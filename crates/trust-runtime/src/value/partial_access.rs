@@ -111,6 +111,11 @@ pub fn read_partial_access(
             let dword = ((value >> (index * 32)) & 0xFFFF_FFFF) as u32;
             Ok(Value::DWord(dword))
         }
+        (target, PartialAccess::Bit(index)) => {
+            let (bits, width) = integer_bits(target)?;
+            check_bit_index(index, width)?;
+            Ok(Value::Bool(((bits >> index) & 1) == 1))
+        }
         _ => Err(PartialAccessError::TypeMismatch),
     }
 }
@@ -259,6 +264,56 @@ pub fn write_partial_access(
             word |= (u64::from(val)) << shift;
             Ok(Value::LWord(word))
         }
+        (target, PartialAccess::Bit(index), Value::Bool(bit)) => {
+            let (mut bits, width) = integer_bits(&target)?;
+            check_bit_index(index, width)?;
+            if bit {
+                bits |= 1 << index;
+            } else {
+                bits &= !(1 << index);
+            }
+            integer_from_bits(&target, bits)
+        }
+        _ => Err(PartialAccessError::TypeMismatch),
+    }
+}
+
+/// Two's complement bits and bit width of an integer value (CODESYS-style `i.3`).
+fn integer_bits(value: &Value) -> Result<(u64, u8), PartialAccessError> {
+    match value {
+        Value::SInt(value) => Ok((u64::from(*value as u8), 8)),
+        Value::Int(value) => Ok((u64::from(*value as u16), 16)),
+        Value::DInt(value) => Ok((u64::from(*value as u32), 32)),
+        Value::LInt(value) => Ok((*value as u64, 64)),
+        Value::USInt(value) => Ok((u64::from(*value), 8)),
+        Value::UInt(value) => Ok((u64::from(*value), 16)),
+        Value::UDInt(value) => Ok((u64::from(*value), 32)),
+        Value::ULInt(value) => Ok((*value, 64)),
+        _ => Err(PartialAccessError::TypeMismatch),
+    }
+}
+
+fn integer_from_bits(template: &Value, bits: u64) -> Result<Value, PartialAccessError> {
+    match template {
+        Value::SInt(_) => Ok(Value::SInt(bits as u8 as i8)),
+        Value::Int(_) => Ok(Value::Int(bits as u16 as i16)),
+        Value::DInt(_) => Ok(Value::DInt(bits as u32 as i32)),
+        Value::LInt(_) => Ok(Value::LInt(bits as i64)),
+        Value::USInt(_) => Ok(Value::USInt(bits as u8)),
+        Value::UInt(_) => Ok(Value::UInt(bits as u16)),
+        Value::UDInt(_) => Ok(Value::UDInt(bits as u32)),
+        Value::ULInt(_) => Ok(Value::ULInt(bits)),
         _ => Err(PartialAccessError::TypeMismatch),
     }
 }
+
+fn check_bit_index(index: u8, width: u8) -> Result<(), PartialAccessError> {
+    if index >= width {
+        return Err(PartialAccessError::IndexOutOfBounds {
+            index: index as i64,
+            lower: 0,
+            upper: i64::from(width) - 1,
+        });
+    }
+    Ok(())
+}
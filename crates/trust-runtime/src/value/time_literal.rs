@@ -0,0 +1,394 @@
+//! IEC 61131-3 date/time literal parsing and formatting.
+
+use crate::datetime::{
+    civil_from_days, days_from_civil, days_to_ticks, nanos_to_ticks, ticks_per_day,
+    DateTimeCalcError, DivisionMode, NANOS_PER_DAY,
+};
+
+use super::{
+    DateTimeProfile, DateTimeValue, DateValue, Duration, LDateTimeValue, LDateValue,
+    LTimeOfDayValue, TimeOfDayValue, Value,
+};
+
+/// Malformed or out-of-range date/time literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("{0}")]
+pub struct TimeLiteralError(pub &'static str);
+
+/// Parses any TIME, DATE, TOD, or DT literal (including the long forms).
+pub fn parse_time_literal(text: &str, profile: DateTimeProfile) -> Result<Value, TimeLiteralError> {
+    let prefix = text
+        .split_once('#')
+        .map(|(prefix, _)| prefix.trim().to_ascii_uppercase())
+        .ok_or(TimeLiteralError("missing literal prefix"))?;
+    match prefix.as_str() {
+        "T" | "TIME" | "LT" | "LTIME" => parse_duration_literal(text),
+        "D" | "DATE" | "LD" | "LDATE" => parse_date_literal(text, profile),
+        "TOD" | "TIME_OF_DAY" | "LTOD" | "LTIME_OF_DAY" => parse_tod_literal(text, profile),
+        "DT" | "DATE_AND_TIME" | "LDT" | "LDATE_AND_TIME" => parse_dt_literal(text, profile),
+        _ => Err(TimeLiteralError("unknown date/time literal prefix")),
+    }
+}
+
+/// Formats a date/time value as an IEC literal; `None` for other values.
+///
+/// The output parses back to the same value with [`parse_time_literal`].
+#[must_use]
+pub fn format_time_literal(value: &Value, profile: DateTimeProfile) -> Option<String> {
+    let resolution = profile.resolution.as_nanos();
+    let text = match value {
+        Value::Time(duration) => format!("T#{}", format_duration(duration.as_nanos())),
+        Value::LTime(duration) => format!("LTIME#{}", format_duration(duration.as_nanos())),
+        Value::Date(date) => {
+            let ticks = date.ticks().checked_sub(profile.epoch.ticks())?;
+            let days = ticks.div_euclid(ticks_per_day(profile).ok()?);
+            format!("D#{}", format_date(days))
+        }
+        Value::LDate(date) => format!("LD#{}", format_date(date.nanos().div_euclid(NANOS_PER_DAY))),
+        Value::Tod(tod) => format!("TOD#{}", format_tod(tod.ticks().checked_mul(resolution)?)),
+        Value::LTod(tod) => format!("LTOD#{}", format_tod(tod.nanos())),
+        Value::Dt(dt) => {
+            let ticks = dt.ticks().checked_sub(profile.epoch.ticks())?;
+            let per_day = ticks_per_day(profile).ok()?;
+            let nanos = ticks.rem_euclid(per_day).checked_mul(resolution)?;
+            format!(
+                "DT#{}-{}",
+                format_date(ticks.div_euclid(per_day)),
+                format_tod(nanos)
+            )
+        }
+        Value::Ldt(dt) => format!(
+            "LDT#{}-{}",
+            format_date(dt.nanos().div_euclid(NANOS_PER_DAY)),
+            format_tod(dt.nanos().rem_euclid(NANOS_PER_DAY))
+        ),
+        _ => return None,
+    };
+    Some(text)
+}
+
+fn format_duration(nanos: i64) -> String {
+    const UNITS: [(&str, u64); 7] = [
+        ("d", 86_400_000_000_000),
+        ("h", 3_600_000_000_000),
+        ("m", 60_000_000_000),
+        ("s", 1_000_000_000),
+        ("ms", 1_000_000),
+        ("us", 1_000),
+        ("ns", 1),
+    ];
+    if nanos == 0 {
+        return "0s".to_string();
+    }
+    let mut text = String::new();
+    if nanos < 0 {
+        text.push('-');
+    }
+    let mut remainder = nanos.unsigned_abs();
+    for (unit, size) in UNITS {
+        let count = remainder / size;
+        remainder %= size;
+        if count > 0 {
+            text.push_str(&format!("{count}{unit}"));
+        }
+    }
+    text
+}
+
+fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn format_tod(nanos: i64) -> String {
+    let seconds = nanos.div_euclid(1_000_000_000);
+    let fraction = nanos.rem_euclid(1_000_000_000);
+    let mut text = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    if fraction > 0 {
+        let digits = format!("{fraction:09}");
+        text.push('.');
+        text.push_str(digits.trim_end_matches('0'));
+    }
+    text
+}
+
+pub fn parse_duration_literal(text: &str) -> Result<Value, TimeLiteralError> {
+    let is_long = is_long_time_literal(text);
+    let nanos = parse_duration_nanos(text)?;
+    let duration = Duration::from_nanos(nanos);
+    Ok(if is_long {
+        Value::LTime(duration)
+    } else {
+        Value::Time(duration)
+    })
+}
+
+pub fn parse_date_literal(text: &str, profile: DateTimeProfile) -> Result<Value, TimeLiteralError> {
+    let is_long = is_long_date_literal(text);
+    let (year, month, day) = parse_date_parts(text)?;
+    let days = days_from_civil_checked(year, month, day)?;
+    if is_long {
+        let nanos = days
+            .checked_mul(NANOS_PER_DAY)
+            .ok_or(TimeLiteralError("date out of range"))?;
+        return Ok(Value::LDate(LDateValue::new(nanos)));
+    }
+    let ticks = days_to_ticks_checked(days, profile)?;
+    Ok(Value::Date(DateValue::new(ticks)))
+}
+
+pub fn parse_tod_literal(text: &str, profile: DateTimeProfile) -> Result<Value, TimeLiteralError> {
+    let is_long = is_long_tod_literal(text);
+    let nanos = parse_time_of_day_nanos(text)?;
+    if is_long {
+        return Ok(Value::LTod(LTimeOfDayValue::new(nanos)));
+    }
+    let ticks = nanos_to_ticks_checked(nanos, profile)?;
+    Ok(Value::Tod(TimeOfDayValue::new(ticks)))
+}
+
+pub fn parse_dt_literal(text: &str, profile: DateTimeProfile) -> Result<Value, TimeLiteralError> {
+    let is_long = is_long_dt_literal(text);
+    let (date_part, tod_part) = parse_dt_parts(text)?;
+    let (year, month, day) = parse_date_parts(date_part)?;
+    let days = days_from_civil_checked(year, month, day)?;
+    let nanos_tod = parse_time_of_day_nanos(tod_part)?;
+    if is_long {
+        let date_nanos = days
+            .checked_mul(NANOS_PER_DAY)
+            .ok_or(TimeLiteralError("date out of range"))?;
+        let nanos = date_nanos
+            .checked_add(nanos_tod)
+            .ok_or(TimeLiteralError("date/time out of range"))?;
+        return Ok(Value::Ldt(LDateTimeValue::new(nanos)));
+    }
+    let date_ticks = days_to_ticks_checked(days, profile)?;
+    let tod_ticks = nanos_to_ticks_checked(nanos_tod, profile)?;
+    let ticks = date_ticks
+        .checked_add(tod_ticks)
+        .ok_or(TimeLiteralError("date/time out of range"))?;
+    Ok(Value::Dt(DateTimeValue::new(ticks)))
+}
+
+fn days_from_civil_checked(year: i64, month: i64, day: i64) -> Result<i64, TimeLiteralError> {
+    match days_from_civil(year, month, day) {
+        // Days past the end of the month (`2023-02-29`) roll over; reject them.
+        Ok(days) if civil_from_days(days) == (year, month, day) => Ok(days),
+        Ok(_) | Err(DateTimeCalcError::InvalidDate) => Err(TimeLiteralError("invalid date")),
+        Err(_) => Err(TimeLiteralError("invalid date")),
+    }
+}
+
+fn days_to_ticks_checked(days: i64, profile: DateTimeProfile) -> Result<i64, TimeLiteralError> {
+    match days_to_ticks(days, profile) {
+        Ok(ticks) => Ok(ticks),
+        Err(DateTimeCalcError::InvalidResolution) => {
+            Err(TimeLiteralError("invalid time resolution"))
+        }
+        Err(DateTimeCalcError::Overflow) => Err(TimeLiteralError("date out of range")),
+        Err(DateTimeCalcError::InvalidDate) => Err(TimeLiteralError("invalid date")),
+    }
+}
+
+fn nanos_to_ticks_checked(nanos: i64, profile: DateTimeProfile) -> Result<i64, TimeLiteralError> {
+    match nanos_to_ticks(nanos, profile, DivisionMode::Trunc) {
+        Ok(ticks) => Ok(ticks),
+        Err(DateTimeCalcError::InvalidResolution) => {
+            Err(TimeLiteralError("invalid time resolution"))
+        }
+        Err(_) => Err(TimeLiteralError("invalid time resolution")),
+    }
+}
+
+fn parse_duration_nanos(text: &str) -> Result<i64, TimeLiteralError> {
+    let upper = text.to_ascii_uppercase();
+    let (_, raw) = upper
+        .split_once('#')
+        .ok_or(TimeLiteralError("invalid TIME literal"))?;
+    let mut rest = raw.trim();
+    let mut negative = false;
+    if let Some(stripped) = rest.strip_prefix('-') {
+        negative = true;
+        rest = stripped;
+    } else if let Some(stripped) = rest.strip_prefix('+') {
+        rest = stripped;
+    }
+
+    // Integer accumulation keeps LTIME literals exact down to the nanosecond.
+    let bytes = rest.as_bytes();
+    let mut idx = 0usize;
+    let mut total: i128 = 0;
+    while idx < bytes.len() {
+        let start = idx;
+        while idx < bytes.len()
+            && (bytes[idx].is_ascii_digit() || bytes[idx] == b'_' || bytes[idx] == b'.')
+        {
+            idx += 1;
+        }
+        if start == idx {
+            return Err(TimeLiteralError("invalid TIME literal"));
+        }
+        let num_str: String = rest[start..idx].chars().filter(|c| *c != '_').collect();
+        let unit_start = idx;
+        while idx < bytes.len() && bytes[idx].is_ascii_alphabetic() {
+            idx += 1;
+        }
+        let nanos_per: i128 = match &rest[unit_start..idx] {
+            "D" => 86_400_000_000_000,
+            "H" => 3_600_000_000_000,
+            "M" => 60_000_000_000,
+            "S" => 1_000_000_000,
+            "MS" => 1_000_000,
+            "US" => 1_000,
+            "NS" => 1,
+            _ => return Err(TimeLiteralError("invalid TIME literal unit")),
+        };
+        total = total
+            .checked_add(scaled_decimal(&num_str, nanos_per)?)
+            .ok_or(TimeLiteralError("TIME literal out of range"))?;
+        while idx < bytes.len() && bytes[idx] == b'_' {
+            idx += 1;
+        }
+    }
+    if negative {
+        total = -total;
+    }
+    i64::try_from(total).map_err(|_| TimeLiteralError("TIME literal out of range"))
+}
+
+/// Multiplies a decimal number by `scale`, rounding the fraction to nearest.
+fn scaled_decimal(text: &str, scale: i128) -> Result<i128, TimeLiteralError> {
+    let invalid = TimeLiteralError("invalid TIME literal");
+    let (whole, frac) = text.split_once('.').unwrap_or((text, ""));
+    if whole.is_empty() || frac.contains('.') || frac.len() > 18 {
+        return Err(invalid);
+    }
+    let whole = whole.parse::<i128>().map_err(|_| invalid)?;
+    let mut value = whole.checked_mul(scale).ok_or(invalid)?;
+    if !frac.is_empty() {
+        let digits = frac.parse::<i128>().map_err(|_| invalid)?;
+        let denominator = 10i128.pow(frac.len() as u32);
+        value += (digits * scale + denominator / 2) / denominator;
+    }
+    Ok(value)
+}
+
+fn parse_date_parts(text: &str) -> Result<(i64, i64, i64), TimeLiteralError> {
+    let rest = match text.split_once('#') {
+        Some((_, rest)) => rest,
+        None => text,
+    };
+    let mut parts = rest.split('-');
+    let year = parts
+        .next()
+        .ok_or(TimeLiteralError("invalid DATE literal"))?
+        .parse::<i64>()
+        .map_err(|_| TimeLiteralError("invalid DATE literal"))?;
+    let month = parts
+        .next()
+        .ok_or(TimeLiteralError("invalid DATE literal"))?
+        .parse::<i64>()
+        .map_err(|_| TimeLiteralError("invalid DATE literal"))?;
+    let day = parts
+        .next()
+        .ok_or(TimeLiteralError("invalid DATE literal"))?
+        .parse::<i64>()
+        .map_err(|_| TimeLiteralError("invalid DATE literal"))?;
+    Ok((year, month, day))
+}
+
+fn parse_time_of_day_nanos(text: &str) -> Result<i64, TimeLiteralError> {
+    let rest = match text.split_once('#') {
+        Some((_, rest)) => rest,
+        None => text,
+    };
+    let mut parts = rest.split(':');
+    let hours = parts
+        .next()
+        .ok_or(TimeLiteralError("invalid TOD literal"))?
+        .parse::<i64>()
+        .map_err(|_| TimeLiteralError("invalid TOD literal"))?;
+    let minutes = parts
+        .next()
+        .ok_or(TimeLiteralError("invalid TOD literal"))?
+        .parse::<i64>()
+        .map_err(|_| TimeLiteralError("invalid TOD literal"))?;
+    let seconds_part = parts
+        .next()
+        .ok_or(TimeLiteralError("invalid TOD literal"))?;
+    let (seconds, nanos) = parse_seconds_fraction(seconds_part)?;
+    let total = hours
+        .checked_mul(3_600)
+        .and_then(|v| v.checked_add(minutes.checked_mul(60)?))
+        .and_then(|v| v.checked_add(seconds))
+        .ok_or(TimeLiteralError("invalid TOD literal"))?;
+    let total_nanos = total
+        .checked_mul(1_000_000_000)
+        .and_then(|v| v.checked_add(nanos))
+        .ok_or(TimeLiteralError("invalid TOD literal"))?;
+    Ok(total_nanos)
+}
+
+fn parse_dt_parts(text: &str) -> Result<(&str, &str), TimeLiteralError> {
+    let (_, rest) = text
+        .split_once('#')
+        .ok_or(TimeLiteralError("invalid DT literal"))?;
+    let (date_part, time_part) = rest
+        .rsplit_once('-')
+        .ok_or(TimeLiteralError("invalid DT literal"))?;
+    Ok((date_part, time_part))
+}
+
+fn parse_seconds_fraction(text: &str) -> Result<(i64, i64), TimeLiteralError> {
+    let mut parts = text.split('.');
+    let secs = parts
+        .next()
+        .ok_or(TimeLiteralError("invalid time literal"))?
+        .parse::<i64>()
+        .map_err(|_| TimeLiteralError("invalid time literal"))?;
+    let nanos = if let Some(frac) = parts.next() {
+        let digits: String = frac.chars().filter(|c| *c != '_').collect();
+        if digits.is_empty() {
+            0
+        } else {
+            let mut padded = digits;
+            if padded.len() > 9 {
+                padded.truncate(9);
+            }
+            while padded.len() < 9 {
+                padded.push('0');
+            }
+            padded
+                .parse::<i64>()
+                .map_err(|_| TimeLiteralError("invalid time fraction"))?
+        }
+    } else {
+        0
+    };
+    Ok((secs, nanos))
+}
+
+fn is_long_time_literal(text: &str) -> bool {
+    let upper = text.to_ascii_uppercase();
+    upper.starts_with("LT#") || upper.starts_with("LTIME#")
+}
+
+fn is_long_date_literal(text: &str) -> bool {
+    let upper = text.to_ascii_uppercase();
+    upper.starts_with("LDATE#") || upper.starts_with("LD#")
+}
+
+fn is_long_tod_literal(text: &str) -> bool {
+    let upper = text.to_ascii_uppercase();
+    upper.starts_with("LTOD#") || upper.starts_with("LTIME_OF_DAY#")
+}
+
+fn is_long_dt_literal(text: &str) -> bool {
+    let upper = text.to_ascii_uppercase();
+    upper.starts_with("LDT#") || upper.starts_with("LDATE_AND_TIME#")
+}
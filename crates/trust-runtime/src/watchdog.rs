@@ -72,6 +72,45 @@ pub struct WatchdogPolicy {
     pub action: WatchdogAction,
 }
 
+/// PROGRAM run for a bounded number of cycles before the resource stops or restarts,
+/// so the application can park axes and close valves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShutdownHook {
+    /// Program instance to run. It is not scheduled during normal operation.
+    pub program: SmolStr,
+    /// Maximum number of shutdown cycles.
+    pub cycles: u32,
+    /// Wall-clock limit for the whole sequence, enforced inside the program too.
+    pub timeout: Duration,
+    /// BOOL variable that ends the sequence early once it is TRUE.
+    pub done: Option<SmolStr>,
+}
+
+/// How a shutdown hook ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownOutcome {
+    /// The `done` variable became TRUE.
+    Done,
+    /// All configured cycles ran.
+    CyclesElapsed,
+    /// The timeout expired first.
+    TimedOut,
+    /// A shutdown cycle faulted.
+    Faulted,
+}
+
+impl ShutdownOutcome {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Done => "done",
+            Self::CyclesElapsed => "cycles_elapsed",
+            Self::TimedOut => "timed_out",
+            Self::Faulted => "faulted",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FaultAction {
     Halt,
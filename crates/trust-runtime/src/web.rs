@@ -3,7 +3,7 @@
 #![allow(missing_docs)]
 
 use std::collections::HashMap;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::net::ToSocketAddrs;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
@@ -12,6 +12,8 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant, SystemTime};
 
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
 use qrcode::{render::svg, QrCode};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -22,22 +24,33 @@ use crate::bundle_template::{IoConfigTemplate, IoDriverTemplate};
 use crate::config::{
     load_system_io_config, IoConfig, IoDriverConfig, RuntimeConfig, WebAuthMode, WebConfig,
 };
-use crate::control::{handle_request_value, ControlState};
+use crate::control::limits::{ConnectionPermit, LimitGuard};
+use crate::control::{
+    control_error_message, handle_request_value, handle_session_request_value, record_limit_audit,
+    ControlSession, ControlState,
+};
 use crate::debug::dap::format_value;
 use crate::discovery::DiscoveryState;
 use crate::error::RuntimeError;
 use crate::io::{IoAddress, IoDriverRegistry, IoSize};
 use crate::memory::IoArea;
+use crate::scheduler::ResourceState;
 use crate::security::{AccessRole, TlsMaterials};
 use crate::setup::SetupOptions;
 
 mod deploy;
 pub mod ide;
 pub mod pairing;
+mod rest;
 
-use deploy::{apply_deploy, apply_rollback, DeployRequest};
+use deploy::{
+    activate_deploy, apply_deploy, apply_rollback, apply_runtime_toml_edit, list_bundle_files,
+    probation_active, read_runtime_toml, watch_probation, DeployTracker, RuntimeTomlEdit,
+};
+pub use deploy::{sign_deploy_request, DeployActivation, DeployPhase, DeployRequest, DeploySource};
 use ide::{IdeError, IdeRole, WebIdeFrontendTelemetry, WebIdeState};
 use pairing::PairingStore;
+use rest::{openapi_spec, resolve_rest_request, status_for_control_error};
 
 #[derive(Debug, Deserialize)]
 struct SetupApplyRequest {
@@ -248,6 +261,10 @@ const HMI_WS_ROUTE: &str = "/ws/hmi";
 const HMI_WS_VALUES_POLL_INTERVAL: Duration = Duration::from_millis(100);
 const HMI_WS_SCHEMA_POLL_INTERVAL: Duration = Duration::from_millis(500);
 const HMI_WS_ALARMS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const HMI_WS_PING_INTERVAL: Duration = Duration::from_secs(5);
+/// How long a websocket upgrade over `max_connections` waits for probed
+/// sessions to free a slot.
+const HMI_WS_RECLAIM_WAIT: Duration = Duration::from_millis(500);
 
 fn default_bundle_root(bundle_root: &Option<PathBuf>) -> PathBuf {
     bundle_root
@@ -420,7 +437,11 @@ fn load_io_config(bundle_root: &Option<PathBuf>) -> Result<IoConfigResponse, Run
     })
 }
 
-fn render_io_toml(drivers: Vec<IoDriverConfig>, safe_state: Vec<IoSafeStateEntry>) -> String {
+fn render_io_toml(
+    drivers: Vec<IoDriverConfig>,
+    safe_state: Vec<IoSafeStateEntry>,
+    map: Vec<(String, String)>,
+) -> String {
     let template = IoConfigTemplate {
         drivers: drivers
             .into_iter()
@@ -433,10 +454,35 @@ fn render_io_toml(drivers: Vec<IoDriverConfig>, safe_state: Vec<IoSafeStateEntry
             .into_iter()
             .map(|entry| (entry.address, entry.value))
             .collect(),
+        map,
     };
     crate::bundle_template::render_io_toml(&template)
 }
 
+fn existing_io_map(io_path: &Path) -> Vec<(String, String)> {
+    let Ok(text) = std::fs::read_to_string(io_path) else {
+        return Vec::new();
+    };
+    let Ok(value) = text.parse::<toml::Value>() else {
+        return Vec::new();
+    };
+    value
+        .get("io")
+        .and_then(|io| io.get("map"))
+        .and_then(toml::Value::as_table)
+        .map(|table| {
+            table
+                .iter()
+                .filter_map(|(name, address)| {
+                    address
+                        .as_str()
+                        .map(|address| (name.clone(), address.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn driver_configs_from_payload(
     payload: &IoConfigRequest,
 ) -> Result<Vec<IoDriverConfig>, RuntimeError> {
@@ -675,6 +721,10 @@ pub fn start_web_server(
     let auth = config.auth;
     let web_url = format_web_url(&listen, config.tls);
     let auth_token = control_state.auth_token.clone();
+    // The web UI shares one control session across its HTTP requests.
+    let web_session = control_state
+        .sessions
+        .open("web", None, control_state.debug.stop_cursor());
     let discovery = discovery.unwrap_or_else(|| Arc::new(DiscoveryState::new()));
     let pairing = pairing.or_else(|| {
         bundle_root
@@ -684,12 +734,27 @@ pub fn start_web_server(
     let ide_state = Arc::new(WebIdeState::new(bundle_root.clone()));
     let ide_task_store: Arc<Mutex<HashMap<u64, IdeTaskJob>>> = Arc::new(Mutex::new(HashMap::new()));
     let ide_task_seq = Arc::new(AtomicU64::new(1));
+    let deploy_tracker = DeployTracker::default();
     let bundle_root = bundle_root.clone();
+    let limits = Arc::new(LimitGuard::new(config.limits));
+    let hmi_ws_probe = Arc::new(AtomicU64::new(0));
     let handle = thread::spawn(move || {
         for mut request in server.incoming_requests() {
             let method = request.method().clone();
             let url = request.url().to_string();
             let url_path = url.split('?').next().unwrap_or(url.as_str());
+            if let Some((status, kind, error)) = web_limit_violation(&request, url_path, &limits) {
+                let client = request.remote_addr().map(ToString::to_string);
+                record_limit_audit(&control_state, kind, &error, client.as_deref());
+                let response =
+                    Response::from_string(json!({ "ok": false, "error": error }).to_string())
+                        .with_status_code(StatusCode(status))
+                        .with_header(
+                            Header::from_bytes("Content-Type", "application/json").unwrap(),
+                        );
+                let _ = request.respond(response);
+                continue;
+            }
             if method == Method::Get && (url == "/" || url == "/setup") {
                 let response = Response::from_string(INDEX_HTML)
                     .with_header(Header::from_bytes("Content-Type", "text/html").unwrap());
@@ -774,11 +839,28 @@ pub fn start_web_server(
                         continue;
                     }
                 };
-                let response = Response::empty(StatusCode(101)).with_header(
-                    Header::from_bytes("Sec-WebSocket-Accept", accept_key.as_bytes()).unwrap(),
-                );
-                let stream = request.upgrade("websocket", response);
-                spawn_hmi_websocket_session(stream, control_state.clone(), request_token);
+                if let Some(permit) = limits.try_connect() {
+                    let session = HmiWsSession {
+                        control_state: control_state.clone(),
+                        request_token,
+                        probe: Arc::clone(&hmi_ws_probe),
+                    };
+                    accept_hmi_websocket(request, &accept_key, session, permit);
+                    continue;
+                }
+                // Idle sessions only notice a closed client when they ping it, so
+                // ask them to ping now and wait for a freed slot off this loop.
+                hmi_ws_probe.fetch_add(1, Ordering::Relaxed);
+                let session = HmiWsSession {
+                    control_state: control_state.clone(),
+                    request_token,
+                    probe: Arc::clone(&hmi_ws_probe),
+                };
+                let limits = Arc::clone(&limits);
+                thread::spawn(move || match reclaim_connection(&limits) {
+                    Some(permit) => accept_hmi_websocket(request, &accept_key, session, permit),
+                    None => refuse_connection(request, &session.control_state),
+                });
                 continue;
             }
             if method == Method::Get && (url == "/ide" || url == "/ide/") {
@@ -1178,7 +1260,7 @@ pub fn start_web_server(
                     }
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string("invalid body").with_status_code(400);
                     let _ = request.respond(response);
                     continue;
@@ -1227,7 +1309,7 @@ pub fn start_web_server(
                     }
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string("invalid body").with_status_code(400);
                     let _ = request.respond(response);
                     continue;
@@ -1257,7 +1339,9 @@ pub fn start_web_server(
                     match driver_configs_from_payload(&payload) {
                         Ok(drivers) => {
                             let safe_state = payload.safe_state.clone().unwrap_or_default();
-                            let io_text = render_io_toml(drivers, safe_state);
+                            // The page does not edit `[io.map]`, so keep the project's symbols.
+                            let map = existing_io_map(&io_path);
+                            let io_text = render_io_toml(drivers, safe_state, map);
                             match crate::config::validate_io_toml_text(&io_text) {
                                 Ok(()) => match std::fs::write(&io_path, io_text) {
                                     Ok(_) => "✓ I/O config saved. Restart the runtime to apply."
@@ -1290,7 +1374,7 @@ pub fn start_web_server(
                     }
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string("invalid body").with_status_code(400);
                     let _ = request.respond(response);
                     continue;
@@ -1434,7 +1518,7 @@ pub fn start_web_server(
                     }
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -1526,7 +1610,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -1693,7 +1777,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -1744,7 +1828,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -1799,7 +1883,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -1849,7 +1933,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -1949,7 +2033,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -2028,7 +2112,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -2077,7 +2161,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -2131,7 +2215,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -2186,7 +2270,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -2240,7 +2324,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -2295,7 +2379,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -2473,7 +2557,7 @@ pub fn start_web_server(
                     continue;
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -2621,7 +2705,7 @@ pub fn start_web_server(
             }
             if method == Method::Post && url == "/api/pair/claim" {
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -2757,7 +2841,7 @@ pub fn start_web_server(
                     }
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -2784,9 +2868,33 @@ pub fn start_web_server(
                     let _ = request.respond(response);
                     continue;
                 };
-                let result = apply_deploy(bundle_root, payload);
+                let result = if payload.activate && probation_active(&deploy_tracker) {
+                    Err(RuntimeError::ControlError(
+                        "previous deploy is still in probation".into(),
+                    ))
+                } else {
+                    apply_deploy(bundle_root, payload)
+                };
+                let result = result.and_then(|mut result| {
+                    let Some(staged) = result.activation.take() else {
+                        return Ok((result, None));
+                    };
+                    let activation =
+                        activate_deploy(bundle_root, staged, &deploy_tracker, |program| {
+                            reload_program(&control_state, request_token.as_deref(), program)
+                        })?;
+                    if activation.phase == DeployPhase::Probation {
+                        spawn_deploy_probation(
+                            control_state.clone(),
+                            bundle_root.clone(),
+                            deploy_tracker.clone(),
+                            request_token.clone(),
+                        );
+                    }
+                    Ok((result, Some(activation)))
+                });
                 let body = match result {
-                    Ok(result) => {
+                    Ok((result, activation)) => {
                         if let Some(restart) = result.restart.as_ref() {
                             let _ = dispatch_control_request(
                                 json!({ "id": 1, "type": "restart", "params": { "mode": restart } }),
@@ -2795,7 +2903,12 @@ pub fn start_web_server(
                                 request_token.as_deref(),
                             );
                         }
-                        json!({ "ok": true, "written": result.written, "restart": result.restart })
+                        json!({
+                            "ok": true,
+                            "written": result.written,
+                            "restart": result.restart,
+                            "activation": activation,
+                        })
                     }
                     Err(err) => json!({ "ok": false, "error": err.to_string() }),
                 };
@@ -2804,6 +2917,125 @@ pub fn start_web_server(
                 let _ = request.respond(response);
                 continue;
             }
+            if method == Method::Get && url == "/api/deploy/status" {
+                if let Err(error) = check_auth(
+                    &request,
+                    auth,
+                    &auth_token,
+                    pairing.as_deref(),
+                    AccessRole::Viewer,
+                ) {
+                    let _ = request.respond(auth_error_response(error));
+                    continue;
+                }
+                let activation = deploy_tracker.lock().ok().and_then(|guard| guard.clone());
+                let body = json!({ "ok": true, "activation": activation });
+                let response = Response::from_string(body.to_string())
+                    .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+                let _ = request.respond(response);
+                continue;
+            }
+            if method == Method::Get
+                && (url == "/api/bundle/files" || url == "/api/bundle/runtime-toml")
+            {
+                if let Err(error) = check_auth(
+                    &request,
+                    auth,
+                    &auth_token,
+                    pairing.as_deref(),
+                    AccessRole::Admin,
+                ) {
+                    let _ = request.respond(auth_error_response(error));
+                    continue;
+                }
+                let Some(bundle_root) = bundle_root.as_ref() else {
+                    let response = Response::from_string(
+                        json!({ "ok": false, "error": "project folder unavailable" }).to_string(),
+                    )
+                    .with_status_code(StatusCode(400));
+                    let _ = request.respond(response);
+                    continue;
+                };
+                let body = if url == "/api/bundle/files" {
+                    json!({ "ok": true, "files": list_bundle_files(bundle_root) })
+                } else {
+                    match read_runtime_toml(bundle_root) {
+                        Ok(text) => json!({ "ok": true, "text": text }),
+                        Err(err) => json!({ "ok": false, "error": err.to_string() }),
+                    }
+                };
+                let response = Response::from_string(body.to_string())
+                    .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+                let _ = request.respond(response);
+                continue;
+            }
+            if method == Method::Post && url == "/api/bundle/runtime-toml" {
+                let request_token = match check_auth(
+                    &request,
+                    auth,
+                    &auth_token,
+                    pairing.as_deref(),
+                    AccessRole::Admin,
+                ) {
+                    Ok(token) => token,
+                    Err(error) => {
+                        let _ = request.respond(auth_error_response(error));
+                        continue;
+                    }
+                };
+                let mut body = String::new();
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
+                    let response = Response::from_string(
+                        json!({ "ok": false, "error": "invalid body" }).to_string(),
+                    )
+                    .with_status_code(StatusCode(400));
+                    let _ = request.respond(response);
+                    continue;
+                }
+                let payload: RuntimeTomlEdit = match serde_json::from_str(&body) {
+                    Ok(value) => value,
+                    Err(_) => {
+                        let response = Response::from_string(
+                            json!({ "ok": false, "error": "invalid json" }).to_string(),
+                        )
+                        .with_status_code(StatusCode(400));
+                        let _ = request.respond(response);
+                        continue;
+                    }
+                };
+                let Some(bundle_root) = bundle_root.as_ref() else {
+                    let response = Response::from_string(
+                        json!({ "ok": false, "error": "project folder unavailable" }).to_string(),
+                    )
+                    .with_status_code(StatusCode(400));
+                    let _ = request.respond(response);
+                    continue;
+                };
+                let body = match apply_runtime_toml_edit(bundle_root, payload) {
+                    Ok(None) => json!({ "ok": true, "valid": true }),
+                    Ok(Some(result)) => {
+                        if let Some(restart) = result.restart.as_ref() {
+                            let _ = dispatch_control_request(
+                                json!({ "id": 1, "type": "restart", "params": { "mode": restart } }),
+                                &control_state,
+                                Some("web"),
+                                request_token.as_deref(),
+                            );
+                        }
+                        json!({
+                            "ok": true,
+                            "valid": true,
+                            "written": result.written,
+                            "restart": result.restart,
+                        })
+                    }
+                    Err(err) => json!({ "ok": false, "valid": false, "error": err.to_string() }),
+                };
+                let response = Response::from_string(body.to_string())
+                    .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+                let _ = request.respond(response);
+                continue;
+            }
             if method == Method::Post && url == "/api/rollback" {
                 let request_token = match check_auth(
                     &request,
@@ -2819,7 +3051,7 @@ pub fn start_web_server(
                     }
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -2865,6 +3097,79 @@ pub fn start_web_server(
                 let _ = request.respond(response);
                 continue;
             }
+            if method == Method::Get && url == rest::OPENAPI_PATH {
+                if let Err(error) = check_auth(
+                    &request,
+                    auth,
+                    &auth_token,
+                    pairing.as_deref(),
+                    AccessRole::Viewer,
+                ) {
+                    let _ = request.respond(auth_error_response(error));
+                    continue;
+                }
+                let response = Response::from_string(openapi_spec().to_string())
+                    .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+                let _ = request.respond(response);
+                continue;
+            }
+            if url.starts_with(rest::REST_PREFIX) {
+                let mut body = String::new();
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
+                    let response = Response::from_string(
+                        json!({ "ok": false, "error": "invalid body" }).to_string(),
+                    )
+                    .with_status_code(StatusCode(400));
+                    let _ = request.respond(response);
+                    continue;
+                }
+                let call = match resolve_rest_request(method.as_str(), &url, &body) {
+                    Ok(call) => call,
+                    Err(error) => {
+                        let response = Response::from_string(
+                            json!({ "ok": false, "error": error.message() }).to_string(),
+                        )
+                        .with_status_code(StatusCode(error.status()))
+                        .with_header(
+                            Header::from_bytes("Content-Type", "application/json").unwrap(),
+                        );
+                        let _ = request.respond(response);
+                        continue;
+                    }
+                };
+                let request_token =
+                    match check_auth(&request, auth, &auth_token, pairing.as_deref(), call.role) {
+                        Ok(token) => token,
+                        Err(error) => {
+                            let _ = request.respond(auth_error_response(error));
+                            continue;
+                        }
+                    };
+                let mut payload = json!({ "id": 1, "type": call.control });
+                if let Some(params) = call.params {
+                    payload["params"] = params;
+                }
+                let response = dispatch_control_request(
+                    payload,
+                    &control_state,
+                    Some("web"),
+                    request_token.as_deref(),
+                );
+                let status = if response.is_ok() {
+                    200
+                } else {
+                    status_for_control_error(response.code())
+                };
+                let mut body = serde_json::to_value(&response).unwrap_or_else(|_| json!({}));
+                if let Some(fields) = body.as_object_mut() {
+                    fields.remove("id");
+                }
+                let response = Response::from_string(body.to_string())
+                    .with_status_code(StatusCode(status))
+                    .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+                let _ = request.respond(response);
+                continue;
+            }
             if method == Method::Post && url == "/api/control" {
                 let request_token = match check_auth(
                     &request,
@@ -2880,7 +3185,7 @@ pub fn start_web_server(
                     }
                 };
                 let mut body = String::new();
-                if request.as_reader().read_to_string(&mut body).is_err() {
+                if read_request_body(&mut request, &limits, &control_state, &mut body).is_err() {
                     let response = Response::from_string(
                         json!({ "ok": false, "error": "invalid body" }).to_string(),
                     )
@@ -2899,9 +3204,10 @@ pub fn start_web_server(
                         continue;
                     }
                 };
-                let response = dispatch_control_request(
+                let response = dispatch_session_control_request(
                     payload,
                     &control_state,
+                    Some(&web_session),
                     Some("web"),
                     request_token.as_deref(),
                 );
@@ -2940,22 +3246,59 @@ fn header_value(request: &tiny_http::Request, key: &str) -> Option<String> {
         .map(|header| header.value.as_str().trim().to_string())
 }
 
-fn spawn_hmi_websocket_session(
-    stream: Box<dyn tiny_http::ReadWrite + Send>,
+/// What an HMI websocket session needs besides its stream.
+struct HmiWsSession {
     control_state: Arc<ControlState>,
     request_token: Option<String>,
+    /// Bumped when the web server is out of connection slots; sessions ping
+    /// their client right away so closed ones give their slot back.
+    probe: Arc<AtomicU64>,
+}
+
+fn accept_hmi_websocket(
+    request: tiny_http::Request,
+    accept_key: &str,
+    session: HmiWsSession,
+    permit: ConnectionPermit,
 ) {
+    let response = Response::empty(StatusCode(101))
+        .with_header(Header::from_bytes("Sec-WebSocket-Accept", accept_key.as_bytes()).unwrap());
+    let stream = request.upgrade("websocket", response);
     thread::spawn(move || {
-        if let Err(err) = run_hmi_websocket_session(stream, control_state, request_token) {
+        let _permit = permit;
+        if let Err(err) = run_hmi_websocket_session(stream, session) {
             tracing::debug!("hmi websocket session closed: {err}");
         }
     });
 }
 
+/// Waits up to [`HMI_WS_RECLAIM_WAIT`] for a connection slot to free up.
+fn reclaim_connection(limits: &LimitGuard) -> Option<ConnectionPermit> {
+    let deadline = Instant::now() + HMI_WS_RECLAIM_WAIT;
+    loop {
+        if let Some(permit) = limits.try_connect() {
+            return Some(permit);
+        }
+        if Instant::now() >= deadline {
+            return None;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+fn refuse_connection(request: tiny_http::Request, control_state: &ControlState) {
+    let error = "too many connections";
+    let client = request.remote_addr().map(ToString::to_string);
+    record_limit_audit(control_state, "limit.connections", error, client.as_deref());
+    let response = Response::from_string(json!({ "ok": false, "error": error }).to_string())
+        .with_status_code(StatusCode(503))
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    let _ = request.respond(response);
+}
+
 fn run_hmi_websocket_session(
     stream: Box<dyn tiny_http::ReadWrite + Send>,
-    control_state: Arc<ControlState>,
-    request_token: Option<String>,
+    session: HmiWsSession,
 ) -> Result<(), String> {
     use tungstenite::protocol::Role;
 
@@ -2967,6 +3310,13 @@ fn run_hmi_websocket_session(
     let mut last_alarm_payload: Option<serde_json::Value> = None;
     let mut next_schema_poll = Instant::now();
     let mut next_alarm_poll = Instant::now();
+    let mut next_ping = Instant::now() + HMI_WS_PING_INTERVAL;
+    let HmiWsSession {
+        control_state,
+        request_token,
+        probe,
+    } = session;
+    let mut probe_seen = probe.load(Ordering::Relaxed);
 
     if let Some(schema_result) = hmi_control_result(
         control_state.as_ref(),
@@ -3057,10 +3407,48 @@ fn run_hmi_websocket_session(
             }
         }
 
+        let probed = probe.load(Ordering::Relaxed);
+        if now >= next_ping || probed != probe_seen {
+            probe_seen = probed;
+            next_ping = now + HMI_WS_PING_INTERVAL;
+            if !hmi_ws_ping(&mut socket)? {
+                return Ok(());
+            }
+        }
         std::thread::sleep(HMI_WS_VALUES_POLL_INTERVAL);
     }
 }
 
+/// Pings the client and reads its frames up to the pong; returns `false` once
+/// the client has closed the session.
+///
+/// The upgraded tiny_http stream can neither be split nor made non-blocking,
+/// so client frames are only read here, where a live client answers within a
+/// round trip.
+fn hmi_ws_ping<S>(socket: &mut tungstenite::protocol::WebSocket<S>) -> Result<bool, String>
+where
+    S: std::io::Read + std::io::Write,
+{
+    socket
+        .send(tungstenite::Message::Ping(Vec::new()))
+        .map_err(|err| err.to_string())?;
+    loop {
+        match socket.read() {
+            Ok(tungstenite::Message::Pong(_)) => return Ok(true),
+            Ok(tungstenite::Message::Close(_)) => {
+                // Sends the close reply tungstenite queued while reading.
+                let _ = socket.flush();
+                return Ok(false);
+            }
+            Ok(_) => {}
+            Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => {
+                return Ok(false)
+            }
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+}
+
 fn hmi_control_result(
     control_state: &ControlState,
     request_id: &mut u64,
@@ -3206,9 +3594,71 @@ fn auth_error_response(error: &str) -> Response<std::io::Cursor<Vec<u8>>> {
         .with_status_code(status)
 }
 
+/// Status, audit kind, and message for a request refused by `runtime.web` limits.
+///
+/// Only `/api/` routes draw from the rate limit; pages, assets, and websocket
+/// upgrades (bounded by `max_connections`) do not. Chunked bodies have no declared
+/// size and are capped by [`read_request_body`] instead.
+fn web_limit_violation(
+    request: &tiny_http::Request,
+    url_path: &str,
+    limits: &LimitGuard,
+) -> Option<(u16, &'static str, String)> {
+    if url_path.starts_with("/api/") {
+        let rate_key = request
+            .remote_addr()
+            .map_or_else(|| "web".to_string(), |addr| addr.ip().to_string());
+        if !limits.allow_request(&rate_key) {
+            return Some((429, "limit.rate", "rate limit exceeded".to_string()));
+        }
+    }
+    let max = limits.max_request_bytes();
+    if request.body_length().is_some_and(|length| length > max) {
+        return Some((
+            413,
+            "limit.request_size",
+            format!("request too large (max {max} bytes)"),
+        ));
+    }
+    None
+}
+
+/// Read the request body as text, failing once it grows past `max_request_bytes`.
+fn read_request_body(
+    request: &mut tiny_http::Request,
+    limits: &LimitGuard,
+    control_state: &ControlState,
+    body: &mut String,
+) -> std::io::Result<usize> {
+    let max = limits.max_request_bytes();
+    let read = Read::take(request.as_reader(), max as u64 + 1).read_to_string(body)?;
+    if read > max {
+        let error = format!("request too large (max {max} bytes)");
+        let client = request.remote_addr().map(ToString::to_string);
+        record_limit_audit(
+            control_state,
+            "limit.request_size",
+            &error,
+            client.as_deref(),
+        );
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error));
+    }
+    Ok(read)
+}
+
 fn dispatch_control_request(
+    payload: serde_json::Value,
+    control_state: &ControlState,
+    client: Option<&str>,
+    request_token: Option<&str>,
+) -> crate::control::ControlResponse {
+    dispatch_session_control_request(payload, control_state, None, client, request_token)
+}
+
+fn dispatch_session_control_request(
     mut payload: serde_json::Value,
     control_state: &ControlState,
+    session: Option<&ControlSession>,
     client: Option<&str>,
     request_token: Option<&str>,
 ) -> crate::control::ControlResponse {
@@ -3217,7 +3667,72 @@ fn dispatch_control_request(
             payload["auth"] = serde_json::Value::String(token.to_string());
         }
     }
-    handle_request_value(payload, control_state, client)
+    handle_session_request_value(payload, control_state, client, session)
+}
+
+/// Hot-swaps `program` through the control `bytecode.reload` request.
+fn reload_program(
+    control_state: &ControlState,
+    request_token: Option<&str>,
+    program: &[u8],
+) -> Result<(), RuntimeError> {
+    let response = dispatch_control_request(
+        json!({
+            "id": 1,
+            "type": "bytecode.reload",
+            "params": { "bytes": BASE64_STANDARD.encode(program) },
+        }),
+        control_state,
+        Some("web"),
+        request_token,
+    );
+    if response.is_ok() {
+        return Ok(());
+    }
+    let error = response.error_message().unwrap_or("bytecode reload failed");
+    Err(RuntimeError::ControlError(error.to_string().into()))
+}
+
+/// Watches an activated deploy in the background and rolls it back if the
+/// resource faults or records new faults before the probation window ends.
+fn spawn_deploy_probation(
+    control_state: Arc<ControlState>,
+    bundle_root: PathBuf,
+    tracker: DeployTracker,
+    request_token: Option<String>,
+) {
+    let baseline = control_state
+        .metrics
+        .lock()
+        .map(|metrics| metrics.faults)
+        .unwrap_or(0);
+    thread::spawn(move || {
+        let faulted = || {
+            if control_state.resource.state() == ResourceState::Faulted {
+                let reason = control_state
+                    .resource
+                    .last_error()
+                    .map(|err| err.to_string())
+                    .unwrap_or_else(|| "resource faulted".to_string());
+                return Some(reason);
+            }
+            let faults = control_state
+                .metrics
+                .lock()
+                .map(|metrics| metrics.faults)
+                .unwrap_or(baseline);
+            (faults > baseline).then(|| format!("{} fault(s) during probation", faults - baseline))
+        };
+        let reload =
+            |program: &[u8]| reload_program(&control_state, request_token.as_deref(), program);
+        watch_probation(
+            &bundle_root,
+            &tracker,
+            Duration::from_millis(200),
+            faulted,
+            reload,
+        );
+    });
 }
 
 fn ide_session_token(request: &tiny_http::Request) -> Option<String> {
@@ -3561,7 +4076,7 @@ fn parse_probe_response(text: &str) -> Value {
     if !ok {
         let error = value
             .get("error")
-            .and_then(|v| v.as_str())
+            .and_then(control_error_message)
             .unwrap_or("unreachable");
         return json!({ "ok": false, "error": error });
     }
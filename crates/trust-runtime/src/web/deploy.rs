@@ -4,17 +4,26 @@
 
 use std::fs;
 use std::path::{Component, Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+use crate::bytecode::BytecodeModule;
 use crate::config::{validate_io_toml_text, validate_runtime_toml_text};
 use crate::error::RuntimeError;
 
-#[derive(Debug, Deserialize)]
+/// Project files managed by deploys, relative to the project folder.
+const BUNDLE_FILES: [&str; 3] = ["runtime.toml", "io.toml", "program.stbc"];
+/// Copy of the project taken before an activated deploy overwrites it.
+const PREVIOUS_DIR: &str = ".deploy/previous";
+const DEFAULT_PROBATION_S: u64 = 30;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct DeployRequest {
     pub runtime_toml: Option<String>,
     pub io_toml: Option<String>,
@@ -22,15 +31,22 @@ pub struct DeployRequest {
     pub sources: Option<Vec<DeploySource>>,
     pub signature: Option<DeploySignature>,
     pub restart: Option<String>,
+    /// Hot-swap the uploaded program and watch it for the probation window,
+    /// restoring the previous project if it faults.
+    #[serde(default)]
+    pub activate: bool,
+    /// Overrides `runtime.deploy.probation_s` for an activated deploy.
+    pub probation_s: Option<u64>,
+    pub label: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeploySource {
     pub path: String,
     pub content: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeploySignature {
     pub key_id: String,
     pub payload_sha256: String,
@@ -41,6 +57,46 @@ pub struct DeploySignature {
 pub struct DeployResult {
     pub written: Vec<String>,
     pub restart: Option<String>,
+    /// Set for `activate` requests; the caller still has to load the program.
+    pub activation: Option<DeployActivation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeployPhase {
+    Staged,
+    Probation,
+    Committed,
+    RolledBack,
+}
+
+/// Progress of the last activated deploy, reported by `/api/deploy/status`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployActivation {
+    pub phase: DeployPhase,
+    pub label: Option<String>,
+    pub program_sha256: String,
+    pub probation_s: u64,
+    pub started_ms: u128,
+    pub reason: Option<String>,
+}
+
+pub type DeployTracker = Arc<Mutex<Option<DeployActivation>>>;
+
+/// Edit of the project runtime.toml from the web UI.
+#[derive(Debug, Deserialize)]
+pub struct RuntimeTomlEdit {
+    pub text: String,
+    #[serde(default)]
+    pub validate_only: bool,
+    pub restart: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BundleFileEntry {
+    pub name: String,
+    pub size: u64,
+    pub updated_ms: Option<u128>,
 }
 
 #[derive(Debug)]
@@ -63,6 +119,7 @@ struct RuntimeDeployPolicyRuntime {
 struct RuntimeDeployPolicy {
     require_signed: Option<bool>,
     keyring_path: Option<String>,
+    probation_s: Option<u64>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -90,17 +147,22 @@ pub fn apply_deploy(
         ));
     }
     preflight_deploy(bundle_root, &request)?;
+    let activation = if request.activate {
+        let activation = prepare_activation(bundle_root, &request)?;
+        snapshot_previous(bundle_root)?;
+        Some(activation)
+    } else {
+        None
+    };
     let mut written = Vec::new();
     if let Some(runtime_toml) = request.runtime_toml {
-        let path = bundle_root.join("runtime.toml");
-        fs::write(&path, runtime_toml).map_err(|err| {
-            RuntimeError::ControlError(format!("write runtime.toml: {err}").into())
-        })?;
+        write_atomic(&bundle_root.join("runtime.toml"), runtime_toml.as_bytes()).map_err(
+            |err| RuntimeError::ControlError(format!("write runtime.toml: {err}").into()),
+        )?;
         written.push("runtime.toml".to_string());
     }
     if let Some(io_toml) = request.io_toml {
-        let path = bundle_root.join("io.toml");
-        fs::write(&path, io_toml)
+        write_atomic(&bundle_root.join("io.toml"), io_toml.as_bytes())
             .map_err(|err| RuntimeError::ControlError(format!("write io.toml: {err}").into()))?;
         written.push("io.toml".to_string());
     }
@@ -108,8 +170,7 @@ pub fn apply_deploy(
         let bytes = STANDARD.decode(program_b64.trim()).map_err(|err| {
             RuntimeError::ControlError(format!("decode program.stbc: {err}").into())
         })?;
-        let path = bundle_root.join("program.stbc");
-        fs::write(&path, bytes).map_err(|err| {
+        write_atomic(&bundle_root.join("program.stbc"), &bytes).map_err(|err| {
             RuntimeError::ControlError(format!("write program.stbc: {err}").into())
         })?;
         written.push("program.stbc".to_string());
@@ -140,9 +201,143 @@ pub fn apply_deploy(
     Ok(DeployResult {
         written,
         restart: request.restart,
+        activation,
     })
 }
 
+/// Loads the freshly written program into the runtime through `reload`.
+///
+/// When the program cannot be loaded the previous project is restored on
+/// disk before the error is returned, so the files match what is running.
+pub fn activate_deploy(
+    bundle_root: &Path,
+    mut activation: DeployActivation,
+    tracker: &DeployTracker,
+    reload: impl Fn(&[u8]) -> Result<(), RuntimeError>,
+) -> Result<DeployActivation, RuntimeError> {
+    let program = fs::read(bundle_root.join("program.stbc"))
+        .map_err(|err| RuntimeError::ControlError(format!("read program.stbc: {err}").into()))?;
+    if let Err(err) = reload(&program) {
+        restore_previous(bundle_root)?;
+        return Err(RuntimeError::ControlError(
+            format!("activate program: {err}; previous project restored").into(),
+        ));
+    }
+    activation.phase = if activation.probation_s == 0 {
+        DeployPhase::Committed
+    } else {
+        DeployPhase::Probation
+    };
+    activation.started_ms = now_ms();
+    if let Ok(mut guard) = tracker.lock() {
+        *guard = Some(activation.clone());
+    }
+    Ok(activation)
+}
+
+/// Returns true while an activated deploy is still inside its probation window.
+pub fn probation_active(tracker: &DeployTracker) -> bool {
+    tracker.lock().ok().is_some_and(|guard| {
+        guard
+            .as_ref()
+            .is_some_and(|activation| activation.phase == DeployPhase::Probation)
+    })
+}
+
+/// Watches an activated deploy until its probation window ends.
+///
+/// `faulted` reports why the new program is considered faulted, if it is.
+/// On the first fault the previous project is restored and reloaded through
+/// `reload`; otherwise the deploy is committed once the window has passed.
+pub fn watch_probation(
+    bundle_root: &Path,
+    tracker: &DeployTracker,
+    poll: Duration,
+    faulted: impl Fn() -> Option<String>,
+    reload: impl Fn(&[u8]) -> Result<(), RuntimeError>,
+) {
+    let Some(activation) = tracker.lock().ok().and_then(|guard| guard.clone()) else {
+        return;
+    };
+    if activation.phase != DeployPhase::Probation {
+        return;
+    }
+    let deadline = Instant::now() + Duration::from_secs(activation.probation_s);
+    let (phase, reason) = loop {
+        if let Some(reason) = faulted() {
+            let restored = restore_previous(bundle_root).and_then(|program| match program {
+                Some(program) => reload(&program),
+                None => Ok(()),
+            });
+            let reason = match restored {
+                Ok(()) => reason,
+                Err(err) => format!("{reason}; rollback incomplete: {err}"),
+            };
+            break (DeployPhase::RolledBack, Some(reason));
+        }
+        if Instant::now() >= deadline {
+            break (DeployPhase::Committed, None);
+        }
+        thread::sleep(poll);
+    };
+    if let Ok(mut guard) = tracker.lock() {
+        if let Some(current) = guard.as_mut() {
+            if current.started_ms == activation.started_ms {
+                current.phase = phase;
+                current.reason = reason;
+            }
+        }
+    }
+}
+
+/// Top-level project files the deploy page manages, with size and mtime.
+pub fn list_bundle_files(bundle_root: &Path) -> Vec<BundleFileEntry> {
+    BUNDLE_FILES
+        .into_iter()
+        .filter_map(|name| {
+            let meta = fs::metadata(bundle_root.join(name)).ok()?;
+            let updated_ms = meta
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_millis());
+            Some(BundleFileEntry {
+                name: name.to_string(),
+                size: meta.len(),
+                updated_ms,
+            })
+        })
+        .collect()
+}
+
+pub fn read_runtime_toml(bundle_root: &Path) -> Result<String, RuntimeError> {
+    let path = bundle_root.join("runtime.toml");
+    fs::read_to_string(&path)
+        .map_err(|err| RuntimeError::ControlError(format!("read runtime.toml: {err}").into()))
+}
+
+/// Validates an edited runtime.toml and, unless `validate_only` is set, writes
+/// it through the regular deploy path so the same preflight and signing
+/// policy apply. Returns `None` for a validation-only request.
+pub fn apply_runtime_toml_edit(
+    bundle_root: &Path,
+    edit: RuntimeTomlEdit,
+) -> Result<Option<DeployResult>, RuntimeError> {
+    if edit.validate_only {
+        validate_runtime_toml_text(&edit.text)?;
+        return Ok(None);
+    }
+    apply_deploy(
+        bundle_root,
+        DeployRequest {
+            runtime_toml: Some(edit.text),
+            restart: edit.restart,
+            ..DeployRequest::default()
+        },
+    )
+    .map(Some)
+}
+
 fn preflight_deploy(bundle_root: &Path, request: &DeployRequest) -> Result<(), RuntimeError> {
     let runtime_text = if let Some(text) = request.runtime_toml.as_deref() {
         Some(text.to_string())
@@ -253,6 +448,18 @@ fn load_deploy_key(path: &Path, key_id: &str) -> Result<DeployKeyEntry, RuntimeE
         .ok_or_else(|| RuntimeError::ControlError("unknown deploy signing key".into()))
 }
 
+/// Signs `request` for the keyring entry `key_id`, as checked when
+/// `runtime.deploy.require_signed` is set on the target.
+pub fn sign_deploy_request(request: &mut DeployRequest, key_id: &str, secret: &str) {
+    let payload_sha256 = deploy_payload_sha256(request);
+    let signature = deploy_signature_digest(secret.trim(), &payload_sha256);
+    request.signature = Some(DeploySignature {
+        key_id: key_id.to_string(),
+        payload_sha256,
+        signature,
+    });
+}
+
 fn deploy_payload_sha256(request: &DeployRequest) -> String {
     let mut hasher = Sha256::new();
     hash_field(&mut hasher, "runtime_toml", request.runtime_toml.as_deref());
@@ -308,6 +515,130 @@ fn now_secs() -> u64 {
         .as_secs()
 }
 
+fn now_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+/// Checks that an activated deploy carries a program this runtime can load
+/// and resolves its probation window.
+fn prepare_activation(
+    bundle_root: &Path,
+    request: &DeployRequest,
+) -> Result<DeployActivation, RuntimeError> {
+    let program_b64 = request.program_stbc_b64.as_deref().ok_or_else(|| {
+        RuntimeError::ControlError("activated deploy requires program.stbc".into())
+    })?;
+    let program = STANDARD
+        .decode(program_b64.trim())
+        .map_err(|err| RuntimeError::ControlError(format!("decode program.stbc: {err}").into()))?;
+    BytecodeModule::decode(&program)
+        .map_err(|err| RuntimeError::ControlError(format!("program.stbc: {err}").into()))?;
+    let probation_s = match request.probation_s {
+        Some(value) => value,
+        None => {
+            let runtime_text = match request.runtime_toml.as_deref() {
+                Some(text) => text.to_string(),
+                None => read_runtime_toml(bundle_root)?,
+            };
+            parse_runtime_deploy_policy(&runtime_text)?
+                .probation_s
+                .unwrap_or(DEFAULT_PROBATION_S)
+        }
+    };
+    Ok(DeployActivation {
+        phase: DeployPhase::Staged,
+        label: request.label.clone(),
+        program_sha256: hex_string(&Sha256::digest(&program)),
+        probation_s,
+        started_ms: 0,
+        reason: None,
+    })
+}
+
+fn snapshot_previous(bundle_root: &Path) -> Result<(), RuntimeError> {
+    let previous = bundle_root.join(PREVIOUS_DIR);
+    let snapshot = || -> std::io::Result<()> {
+        if previous.exists() {
+            fs::remove_dir_all(&previous)?;
+        }
+        fs::create_dir_all(&previous)?;
+        for name in BUNDLE_FILES {
+            let live = bundle_root.join(name);
+            if live.is_file() {
+                fs::copy(&live, previous.join(name))?;
+            }
+        }
+        let sources = bundle_root.join("src");
+        if sources.is_dir() {
+            copy_dir(&sources, &previous.join("src"))?;
+        }
+        Ok(())
+    };
+    snapshot().map_err(|err| RuntimeError::ControlError(format!("snapshot project: {err}").into()))
+}
+
+/// Puts the snapshot taken by the last activated deploy back in place and
+/// returns its program, if it had one.
+fn restore_previous(bundle_root: &Path) -> Result<Option<Vec<u8>>, RuntimeError> {
+    let previous = bundle_root.join(PREVIOUS_DIR);
+    if !previous.is_dir() {
+        return Err(RuntimeError::ControlError(
+            format!("no previous project snapshot at {}", previous.display()).into(),
+        ));
+    }
+    let restore = || -> std::io::Result<Option<Vec<u8>>> {
+        for name in BUNDLE_FILES {
+            let saved = previous.join(name);
+            let live = bundle_root.join(name);
+            if saved.is_file() {
+                write_atomic(&live, &fs::read(&saved)?)?;
+            } else if live.is_file() {
+                fs::remove_file(&live)?;
+            }
+        }
+        let sources = bundle_root.join("src");
+        if sources.is_dir() {
+            fs::remove_dir_all(&sources)?;
+        }
+        if previous.join("src").is_dir() {
+            copy_dir(&previous.join("src"), &sources)?;
+        }
+        let program = previous.join("program.stbc");
+        if program.is_file() {
+            fs::read(program).map(Some)
+        } else {
+            Ok(None)
+        }
+    };
+    restore().map_err(|err| RuntimeError::ControlError(format!("restore project: {err}").into()))
+}
+
+/// Writes next to `path` and renames over it so readers never see a partial file.
+fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut staged = path.as_os_str().to_owned();
+    staged.push(".staged");
+    let staged = PathBuf::from(staged);
+    fs::write(&staged, bytes)?;
+    fs::rename(&staged, path)
+}
+
+fn copy_dir(source: &Path, dest: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        if entry.path().is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn apply_rollback(root: &Path) -> Result<RollbackResult, RuntimeError> {
     let current_link = root.join("current");
     let previous_link = root.join("previous");
@@ -433,6 +764,7 @@ policy = "halt"
             }]),
             signature: None,
             restart: None,
+            ..DeployRequest::default()
         };
         let result = apply_deploy(&root, request).unwrap();
         assert!(result.written.contains(&"runtime.toml".to_string()));
@@ -482,6 +814,7 @@ policy = "halt"
             sources: None,
             signature: None,
             restart: None,
+            ..DeployRequest::default()
         };
         let err = apply_deploy(&root, request).expect_err("schema should fail");
         assert!(err
@@ -490,6 +823,72 @@ policy = "halt"
         let _ = fs::remove_dir_all(root);
     }
 
+    #[test]
+    fn runtime_toml_edit_validates_before_writing() {
+        let mut root = std::env::temp_dir();
+        root.push(format!("trust-deploy-edit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let valid =
+            runtime_with_signed_policy().replace("require_signed = true", "require_signed = false");
+        fs::write(root.join("runtime.toml"), &valid).unwrap();
+
+        let invalid = valid.replace("cycle_interval_ms = 100", "cycle_interval_ms = 0");
+        let check = apply_runtime_toml_edit(
+            &root,
+            RuntimeTomlEdit {
+                text: invalid.clone(),
+                validate_only: true,
+                restart: None,
+            },
+        );
+        assert!(check
+            .expect_err("validation should fail")
+            .to_string()
+            .contains("resource.cycle_interval_ms must be >= 1"));
+        let save = apply_runtime_toml_edit(
+            &root,
+            RuntimeTomlEdit {
+                text: invalid,
+                validate_only: false,
+                restart: Some("warm".to_string()),
+            },
+        );
+        assert!(save.is_err());
+        assert_eq!(read_runtime_toml(&root).unwrap(), valid);
+
+        let edited = valid.replace("cycle_interval_ms = 100", "cycle_interval_ms = 50");
+        let validated = apply_runtime_toml_edit(
+            &root,
+            RuntimeTomlEdit {
+                text: edited.clone(),
+                validate_only: true,
+                restart: None,
+            },
+        )
+        .unwrap();
+        assert!(validated.is_none());
+        assert_eq!(read_runtime_toml(&root).unwrap(), valid);
+        let saved = apply_runtime_toml_edit(
+            &root,
+            RuntimeTomlEdit {
+                text: edited.clone(),
+                validate_only: false,
+                restart: Some("warm".to_string()),
+            },
+        )
+        .unwrap()
+        .expect("deploy result");
+        assert_eq!(saved.written, vec!["runtime.toml".to_string()]);
+        assert_eq!(saved.restart.as_deref(), Some("warm"));
+        assert_eq!(read_runtime_toml(&root).unwrap(), edited);
+        let files = list_bundle_files(&root);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "runtime.toml");
+        assert_eq!(files[0].size, edited.len() as u64);
+        let _ = fs::remove_dir_all(root);
+    }
+
     fn runtime_with_signed_policy() -> String {
         r#"
 [bundle]
@@ -537,6 +936,7 @@ policy = "halt"
             }]),
             signature: None,
             restart: None,
+            ..DeployRequest::default()
         };
         let key_dir = root.join("security");
         fs::create_dir_all(&key_dir).expect("security dir");
@@ -640,4 +1040,96 @@ not_after_unix = 100
         assert!(!text.contains(secret), "error leaked secret: {text}");
         let _ = fs::remove_dir_all(root);
     }
+
+    fn loadable_program() -> Vec<u8> {
+        BytecodeModule::new(crate::bytecode::BytecodeVersion::new(1, 1))
+            .encode()
+            .expect("encode program")
+    }
+
+    #[test]
+    fn activated_deploy_rejects_unloadable_program() {
+        let mut root = std::env::temp_dir();
+        root.push(format!(
+            "trust-deploy-activate-invalid-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).expect("create root");
+        let runtime =
+            runtime_with_signed_policy().replace("require_signed = true", "require_signed = false");
+        let request = DeployRequest {
+            runtime_toml: Some(runtime),
+            program_stbc_b64: Some(STANDARD.encode([1u8, 2, 3])),
+            activate: true,
+            ..DeployRequest::default()
+        };
+        let err = apply_deploy(&root, request).expect_err("program should be rejected");
+        assert!(err.to_string().contains("program.stbc"));
+        assert!(!root.join("runtime.toml").exists());
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn activated_deploy_rolls_back_when_program_faults_during_probation() {
+        let mut root = std::env::temp_dir();
+        root.push(format!("trust-deploy-activate-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).expect("create root");
+        let runtime =
+            runtime_with_signed_policy().replace("require_signed = true", "require_signed = false");
+        fs::write(root.join("runtime.toml"), &runtime).unwrap();
+        fs::write(root.join("program.stbc"), [7u8, 7, 7]).unwrap();
+        fs::write(root.join("src/main.st"), "PROGRAM Old\nEND_PROGRAM\n").unwrap();
+
+        let program = loadable_program();
+        let request = DeployRequest {
+            program_stbc_b64: Some(STANDARD.encode(&program)),
+            sources: Some(vec![DeploySource {
+                path: "main.st".to_string(),
+                content: "PROGRAM New\nEND_PROGRAM\n".to_string(),
+            }]),
+            activate: true,
+            probation_s: Some(5),
+            label: Some("v2".to_string()),
+            ..DeployRequest::default()
+        };
+        let result = apply_deploy(&root, request).expect("deploy");
+        let staged = result.activation.expect("activation");
+        assert_eq!(staged.phase, DeployPhase::Staged);
+        assert_eq!(staged.probation_s, 5);
+        assert_eq!(fs::read(root.join("program.stbc")).unwrap(), program);
+
+        let tracker = DeployTracker::default();
+        let loaded = Mutex::new(Vec::new());
+        let reload = |bytes: &[u8]| -> Result<(), RuntimeError> {
+            loaded.lock().unwrap().push(bytes.to_vec());
+            Ok(())
+        };
+        let active = activate_deploy(&root, staged, &tracker, reload).expect("activate");
+        assert_eq!(active.phase, DeployPhase::Probation);
+        assert!(probation_active(&tracker));
+
+        watch_probation(
+            &root,
+            &tracker,
+            Duration::from_millis(1),
+            || Some("resource faulted".to_string()),
+            reload,
+        );
+        let outcome = tracker.lock().unwrap().clone().expect("outcome");
+        assert_eq!(outcome.phase, DeployPhase::RolledBack);
+        assert_eq!(outcome.reason.as_deref(), Some("resource faulted"));
+        assert_eq!(
+            fs::read(root.join("program.stbc")).unwrap(),
+            vec![7u8, 7, 7]
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/main.st")).unwrap(),
+            "PROGRAM Old\nEND_PROGRAM\n"
+        );
+        let loaded = loaded.into_inner().unwrap();
+        assert_eq!(loaded, vec![program, vec![7u8, 7, 7]]);
+        let _ = fs::remove_dir_all(root);
+    }
 }
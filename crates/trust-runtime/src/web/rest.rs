@@ -0,0 +1,760 @@
+//! Versioned REST API (`/api/v1/...`) over the control protocol.
+//!
+//! Each route maps an HTTP method and path template onto one control request
+//! type. The same table drives request routing and the OpenAPI document, so
+//! the two cannot drift apart.
+
+use serde_json::{json, Value};
+
+use crate::control::{required_role_for_control_request, ControlErrorCode};
+use crate::security::AccessRole;
+
+use super::{decode_url_component, query_value};
+
+pub const REST_PREFIX: &str = "/api/v1/";
+pub const OPENAPI_PATH: &str = "/api/v1/openapi.json";
+
+type ParamsFn = fn(&RestArgs<'_>) -> Result<Option<Value>, String>;
+
+pub struct RestRoute {
+    pub method: &'static str,
+    pub path: &'static str,
+    pub control: &'static str,
+    pub summary: &'static str,
+    /// Query parameters and JSON body fields documented for the route.
+    pub inputs: &'static [RestInput],
+    params: ParamsFn,
+}
+
+#[derive(Clone, Copy)]
+pub enum RestInput {
+    Query(&'static str, &'static str),
+    /// Body field: name, description, and whether it is required.
+    Body(&'static str, &'static str, bool),
+    /// Free-form JSON object body.
+    Object(&'static str),
+}
+
+struct RestArgs<'a> {
+    url: &'a str,
+    captures: Vec<String>,
+    body: Option<Value>,
+}
+
+/// A REST call resolved to the control request it stands for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestCall {
+    pub control: &'static str,
+    pub params: Option<Value>,
+    pub role: AccessRole,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestError {
+    NotFound,
+    MethodNotAllowed,
+    BadRequest(String),
+}
+
+impl RestError {
+    pub fn status(&self) -> u16 {
+        match self {
+            Self::NotFound => 404,
+            Self::MethodNotAllowed => 405,
+            Self::BadRequest(_) => 400,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Self::NotFound => "unknown route".to_string(),
+            Self::MethodNotAllowed => "method not allowed".to_string(),
+            Self::BadRequest(message) => message.clone(),
+        }
+    }
+}
+
+const VALUE_BODY: &[RestInput] = &[RestInput::Body(
+    "value",
+    "New value, for example TRUE, FALSE, or 42.",
+    true,
+)];
+const LIMIT_QUERY: &[RestInput] = &[RestInput::Query("limit", "Maximum entries (default 50).")];
+
+pub const ROUTES: &[RestRoute] = &[
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/status",
+        control: "status",
+        summary: "Runtime state, cycle timing, and fault counters.",
+        inputs: &[],
+        params: no_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/health",
+        control: "health",
+        summary: "Health summary for probes.",
+        inputs: &[],
+        params: no_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/tasks",
+        control: "tasks.stats",
+        summary: "Per-task cycle statistics.",
+        inputs: &[],
+        params: no_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/events",
+        control: "events.tail",
+        summary: "Most recent runtime events, newest first.",
+        inputs: &[
+            RestInput::Query("limit", "Maximum entries (default 50)."),
+            RestInput::Query(
+                "min_severity",
+                "Skip events below debug, info, warning, or error.",
+            ),
+        ],
+        params: events_tail_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/events/history",
+        control: "events.query",
+        summary: "Persisted runtime events, newest first.",
+        inputs: &[
+            RestInput::Query("since_ms", "Only events stored at or after this time."),
+            RestInput::Query("until_ms", "Only events stored at or before this time."),
+            RestInput::Query("code", "Comma-separated event types, e.g. fault."),
+            RestInput::Query(
+                "min_severity",
+                "Skip events below debug, info, warning, or error.",
+            ),
+            RestInput::Query("limit", "Maximum events (default 200)."),
+        ],
+        params: events_query_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/faults",
+        control: "faults",
+        summary: "Most recent faults, newest first.",
+        inputs: LIMIT_QUERY,
+        params: limit_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/config",
+        control: "config.get",
+        summary: "Effective runtime configuration.",
+        inputs: &[],
+        params: no_params,
+    },
+    RestRoute {
+        method: "PATCH",
+        path: "/api/v1/config",
+        control: "config.set",
+        summary: "Update runtime configuration keys.",
+        inputs: &[RestInput::Object("Configuration keys to set.")],
+        params: body_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/io",
+        control: "io.list",
+        summary: "Configured inputs and outputs with current values.",
+        inputs: &[],
+        params: no_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/io/snapshot",
+        control: "io.read",
+        summary: "Last I/O image captured by the scan cycle.",
+        inputs: &[],
+        params: no_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/io/map",
+        control: "io.map.get",
+        summary: "Symbols bound by io.toml [io.map] with their addresses and values.",
+        inputs: &[],
+        params: no_params,
+    },
+    RestRoute {
+        method: "PUT",
+        path: "/api/v1/io/{address}",
+        control: "io.write",
+        summary: "Queue a write to an I/O address.",
+        inputs: VALUE_BODY,
+        params: io_value_params,
+    },
+    RestRoute {
+        method: "PUT",
+        path: "/api/v1/io/{address}/force",
+        control: "io.force",
+        summary: "Force an I/O address to a value.",
+        inputs: VALUE_BODY,
+        params: io_value_params,
+    },
+    RestRoute {
+        method: "DELETE",
+        path: "/api/v1/io/{address}/force",
+        control: "io.unforce",
+        summary: "Release a forced I/O address.",
+        inputs: &[],
+        params: io_address_params,
+    },
+    RestRoute {
+        method: "PATCH",
+        path: "/api/v1/sim/models/{name}",
+        control: "sim.model.set",
+        summary: "Change simulation channel model parameters.",
+        inputs: &[RestInput::Object(
+            "Parameters to change (gain, offset, time_constant_ms, delay_ms, noise, min, max, value).",
+        )],
+        params: sim_model_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/vars/forced",
+        control: "var.forced",
+        summary: "Variables that are currently forced.",
+        inputs: &[],
+        params: no_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/vars/{name}",
+        control: "eval",
+        summary: "Read a global or retained variable.",
+        inputs: &[],
+        params: var_read_params,
+    },
+    RestRoute {
+        method: "PUT",
+        path: "/api/v1/vars/{name}",
+        control: "set",
+        summary: "Queue a write to a variable (`global:` is assumed without a prefix).",
+        inputs: VALUE_BODY,
+        params: var_value_params,
+    },
+    RestRoute {
+        method: "PUT",
+        path: "/api/v1/vars/{name}/force",
+        control: "var.force",
+        summary: "Force a variable to a value.",
+        inputs: VALUE_BODY,
+        params: var_value_params,
+    },
+    RestRoute {
+        method: "DELETE",
+        path: "/api/v1/vars/{name}/force",
+        control: "var.unforce",
+        summary: "Release a forced variable.",
+        inputs: &[],
+        params: var_target_params,
+    },
+    RestRoute {
+        method: "POST",
+        path: "/api/v1/pause",
+        control: "pause",
+        summary: "Pause the scan cycle.",
+        inputs: &[],
+        params: no_params,
+    },
+    RestRoute {
+        method: "POST",
+        path: "/api/v1/resume",
+        control: "resume",
+        summary: "Resume the scan cycle.",
+        inputs: &[],
+        params: no_params,
+    },
+    RestRoute {
+        method: "POST",
+        path: "/api/v1/cycle/step",
+        control: "cycle.step",
+        summary: "Run scan cycles on a paused resource, then pause again.",
+        inputs: &[RestInput::Body(
+            "cycles",
+            "Number of cycles (default 1).",
+            false,
+        )],
+        params: cycle_step_params,
+    },
+    RestRoute {
+        method: "POST",
+        path: "/api/v1/restart",
+        control: "restart",
+        summary: "Restart the runtime.",
+        inputs: &[RestInput::Body("mode", "warm (default) or cold.", false)],
+        params: restart_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/historian",
+        control: "historian.query",
+        summary: "Recorded historian samples.",
+        inputs: &[
+            RestInput::Query("variable", "Variable name to filter on."),
+            RestInput::Query("since_ms", "Only samples at or after this time."),
+            RestInput::Query("until_ms", "Only samples at or before this time."),
+            RestInput::Query("limit", "Maximum samples (default 250)."),
+        ],
+        params: historian_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/mesh",
+        control: "mesh.status",
+        summary: "Mesh peer and link health.",
+        inputs: &[],
+        params: no_params,
+    },
+    RestRoute {
+        method: "GET",
+        path: "/api/v1/mesh/topology",
+        control: "mesh.topology",
+        summary: "Mesh peers, shared variables, and round-trip times.",
+        inputs: &[],
+        params: no_params,
+    },
+];
+
+/// Resolves a `/api/v1/...` request to a control request type and params.
+pub fn resolve_rest_request(method: &str, url: &str, body: &str) -> Result<RestCall, RestError> {
+    let path = url.split('?').next().unwrap_or(url);
+    let segments = path_segments(path);
+    let mut path_matched = false;
+    for route in ROUTES {
+        let Some(captures) = match_path(route.path, &segments) else {
+            continue;
+        };
+        if !route.method.eq_ignore_ascii_case(method) {
+            path_matched = true;
+            continue;
+        }
+        let body = if body.trim().is_empty() {
+            None
+        } else {
+            Some(
+                serde_json::from_str::<Value>(body)
+                    .map_err(|_| RestError::BadRequest("invalid json".to_string()))?,
+            )
+        };
+        let args = RestArgs {
+            url,
+            captures,
+            body,
+        };
+        let params = (route.params)(&args).map_err(RestError::BadRequest)?;
+        return Ok(RestCall {
+            control: route.control,
+            role: required_role_for_control_request(route.control, params.as_ref()),
+            params,
+        });
+    }
+    Err(if path_matched {
+        RestError::MethodNotAllowed
+    } else {
+        RestError::NotFound
+    })
+}
+
+/// HTTP status for a failed control response.
+pub fn status_for_control_error(code: Option<ControlErrorCode>) -> u16 {
+    code.map_or(400, ControlErrorCode::http_status)
+}
+
+/// OpenAPI 3 document generated from [`ROUTES`].
+pub fn openapi_spec() -> Value {
+    let mut paths = serde_json::Map::new();
+    for route in ROUTES {
+        let mut parameters = path_segments(route.path)
+            .into_iter()
+            .filter_map(|segment| {
+                let name = segment.strip_prefix('{')?.strip_suffix('}')?;
+                Some(json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string" },
+                }))
+            })
+            .collect::<Vec<_>>();
+        let mut body_fields = serde_json::Map::new();
+        let mut body_required = false;
+        let mut open_body = None;
+        for input in route.inputs {
+            match *input {
+                RestInput::Query(name, description) => parameters.push(json!({
+                    "name": name,
+                    "in": "query",
+                    "required": false,
+                    "description": description,
+                    "schema": { "type": "string" },
+                })),
+                RestInput::Object(description) => {
+                    open_body = Some(description);
+                    body_required = true;
+                }
+                RestInput::Body(name, description, required) => {
+                    body_required |= required;
+                    body_fields.insert(name.to_string(), json!({ "description": description }));
+                }
+            }
+        }
+        let role = required_role_for_control_request(route.control, None);
+        let mut operation = json!({
+            "summary": route.summary,
+            "operationId": route.control,
+            "x-control-request": route.control,
+            "x-required-role": role.as_str(),
+            "parameters": parameters,
+            "responses": {
+                "200": { "description": "Control request succeeded; see `result`." },
+                "400": { "description": "Invalid request or control error." },
+                "401": { "description": "Missing or invalid token." },
+                "403": { "description": "Token role is not allowed to call this route." },
+            },
+        });
+        if open_body.is_some() || !body_fields.is_empty() {
+            let schema = if let Some(description) = open_body {
+                json!({
+                    "type": "object",
+                    "description": description,
+                    "additionalProperties": true,
+                })
+            } else {
+                json!({ "type": "object", "properties": body_fields })
+            };
+            operation["requestBody"] = json!({
+                "required": body_required,
+                "content": { "application/json": { "schema": schema } },
+            });
+        }
+        let key = route.path.to_string();
+        let entry = paths.entry(key).or_insert_with(|| json!({}));
+        entry[route.method.to_ascii_lowercase()] = operation;
+    }
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "truST runtime REST API",
+            "version": "1",
+            "description": "Plain HTTP view of the runtime control protocol. \
+                Send the web token as `X-Trust-Token`.",
+        },
+        "components": {
+            "securitySchemes": {
+                "trustToken": { "type": "apiKey", "in": "header", "name": "X-Trust-Token" },
+            },
+        },
+        "security": [{ "trustToken": [] }],
+        "paths": paths,
+    })
+}
+
+fn path_segments(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+fn match_path(template: &str, segments: &[&str]) -> Option<Vec<String>> {
+    let parts = path_segments(template);
+    if parts.len() != segments.len() {
+        return None;
+    }
+    let mut captures = Vec::new();
+    for (part, segment) in parts.iter().zip(segments) {
+        if part.starts_with('{') {
+            captures.push(decode_url_component(segment));
+        } else if part != segment {
+            return None;
+        }
+    }
+    Some(captures)
+}
+
+fn body_value(args: &RestArgs<'_>) -> Result<String, String> {
+    match args.body.as_ref().and_then(|body| body.get("value")) {
+        Some(Value::String(text)) => Ok(text.clone()),
+        Some(Value::Bool(true)) => Ok("TRUE".to_string()),
+        Some(Value::Bool(false)) => Ok("FALSE".to_string()),
+        Some(Value::Number(number)) => Ok(number.to_string()),
+        _ => Err("missing value".to_string()),
+    }
+}
+
+fn var_target(name: &str) -> String {
+    if name.contains(':') {
+        name.to_string()
+    } else {
+        format!("global:{name}")
+    }
+}
+
+fn no_params(_args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    Ok(None)
+}
+
+fn body_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    match args.body.as_ref() {
+        Some(body) if body.is_object() => Ok(Some(body.clone())),
+        _ => Err("expected a JSON object body".to_string()),
+    }
+}
+
+fn limit_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    Ok(query_u64(args.url, "limit")?.map(|limit| json!({ "limit": limit })))
+}
+
+fn events_tail_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    let mut params = serde_json::Map::new();
+    if let Some(limit) = query_u64(args.url, "limit")? {
+        params.insert("limit".to_string(), json!(limit));
+    }
+    if let Some(severity) = query_value(args.url, "min_severity") {
+        params.insert("min_severity".to_string(), json!(severity));
+    }
+    Ok((!params.is_empty()).then_some(Value::Object(params)))
+}
+
+fn events_query_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    let mut params = serde_json::Map::new();
+    for key in ["since_ms", "until_ms", "limit"] {
+        if let Some(value) = query_u64(args.url, key)? {
+            params.insert(key.to_string(), json!(value));
+        }
+    }
+    if let Some(codes) = query_value(args.url, "code") {
+        let codes = codes
+            .split(',')
+            .map(str::trim)
+            .filter(|code| !code.is_empty())
+            .collect::<Vec<_>>();
+        params.insert("code".to_string(), json!(codes));
+    }
+    if let Some(severity) = query_value(args.url, "min_severity") {
+        params.insert("min_severity".to_string(), json!(severity));
+    }
+    Ok(Some(Value::Object(params)))
+}
+
+fn io_address_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    Ok(Some(json!({ "address": args.captures[0] })))
+}
+
+fn io_value_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    Ok(Some(
+        json!({ "address": args.captures[0], "value": body_value(args)? }),
+    ))
+}
+
+fn sim_model_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    let mut params = match args.body.as_ref() {
+        Some(Value::Object(body)) => body.clone(),
+        _ => return Err("expected a JSON object body".to_string()),
+    };
+    params.insert("name".to_string(), json!(args.captures[0]));
+    Ok(Some(Value::Object(params)))
+}
+
+fn var_read_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    let name = args.captures[0].as_str();
+    let name = name
+        .strip_prefix("global:")
+        .or_else(|| name.strip_prefix("retain:"))
+        .unwrap_or(name);
+    Ok(Some(json!({ "expr": name })))
+}
+
+fn var_target_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    Ok(Some(json!({ "target": var_target(&args.captures[0]) })))
+}
+
+fn var_value_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    Ok(Some(json!({
+        "target": var_target(&args.captures[0]),
+        "value": body_value(args)?,
+    })))
+}
+
+fn restart_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    let mode = args
+        .body
+        .as_ref()
+        .and_then(|body| body.get("mode"))
+        .and_then(Value::as_str)
+        .unwrap_or("warm");
+    Ok(Some(json!({ "mode": mode })))
+}
+
+fn cycle_step_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    let cycles = match args.body.as_ref().and_then(|body| body.get("cycles")) {
+        None => 1,
+        Some(value) => value
+            .as_u64()
+            .filter(|cycles| (1..=u64::from(u32::MAX)).contains(cycles))
+            .ok_or_else(|| "invalid cycles".to_string())?,
+    };
+    Ok(Some(json!({ "cycles": cycles })))
+}
+
+fn historian_params(args: &RestArgs<'_>) -> Result<Option<Value>, String> {
+    let mut params = serde_json::Map::new();
+    if let Some(variable) = query_value(args.url, "variable") {
+        params.insert("variable".to_string(), json!(variable));
+    }
+    for key in ["since_ms", "until_ms", "limit"] {
+        if let Some(value) = query_u64(args.url, key)? {
+            params.insert(key.to_string(), json!(value));
+        }
+    }
+    Ok(Some(Value::Object(params)))
+}
+
+fn query_u64(url: &str, key: &str) -> Result<Option<u64>, String> {
+    query_value(url, key)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| format!("invalid {key} '{value}'"))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rest_routes_map_onto_control_requests() {
+        let status = resolve_rest_request("GET", "/api/v1/status", "").unwrap();
+        assert_eq!(status.control, "status");
+        assert_eq!(status.params, None);
+        assert_eq!(status.role, AccessRole::Viewer);
+
+        let events = resolve_rest_request("GET", "/api/v1/events?limit=5", "").unwrap();
+        assert_eq!(events.control, "events.tail");
+        assert_eq!(events.params, Some(json!({ "limit": 5 })));
+        let events =
+            resolve_rest_request("GET", "/api/v1/events?min_severity=warning", "").unwrap();
+        assert_eq!(events.params, Some(json!({ "min_severity": "warning" })));
+
+        let history = resolve_rest_request(
+            "GET",
+            "/api/v1/events/history?since_ms=10&code=fault,task_overrun",
+            "",
+        )
+        .unwrap();
+        assert_eq!(history.control, "events.query");
+        assert_eq!(history.role, AccessRole::Viewer);
+        assert_eq!(
+            history.params,
+            Some(json!({ "since_ms": 10, "code": ["fault", "task_overrun"] }))
+        );
+
+        let forced = resolve_rest_request("GET", "/api/v1/vars/forced", "").unwrap();
+        assert_eq!(forced.control, "var.forced");
+
+        let read = resolve_rest_request("GET", "/api/v1/vars/global%3ASpeed", "").unwrap();
+        assert_eq!(read.control, "eval");
+        assert_eq!(read.params, Some(json!({ "expr": "Speed" })));
+
+        let write = resolve_rest_request("PUT", "/api/v1/vars/Speed", r#"{"value": 42}"#).unwrap();
+        assert_eq!(write.control, "set");
+        assert_eq!(
+            write.params,
+            Some(json!({ "target": "global:Speed", "value": "42" }))
+        );
+        assert_eq!(write.role, AccessRole::Engineer);
+
+        let force =
+            resolve_rest_request("PUT", "/api/v1/io/%25QX0.1/force", r#"{"value": true}"#).unwrap();
+        assert_eq!(force.control, "io.force");
+        assert_eq!(
+            force.params,
+            Some(json!({ "address": "%QX0.1", "value": "TRUE" }))
+        );
+
+        let step = resolve_rest_request("POST", "/api/v1/cycle/step", r#"{"cycles": 3}"#).unwrap();
+        assert_eq!(step.control, "cycle.step");
+        assert_eq!(step.params, Some(json!({ "cycles": 3 })));
+        assert_eq!(step.role, AccessRole::Operator);
+
+        let model =
+            resolve_rest_request("PATCH", "/api/v1/sim/models/tank", r#"{"gain": 0.5}"#).unwrap();
+        assert_eq!(model.control, "sim.model.set");
+        assert_eq!(model.params, Some(json!({ "gain": 0.5, "name": "tank" })));
+        assert_eq!(model.role, AccessRole::Engineer);
+
+        let restart = resolve_rest_request("POST", "/api/v1/restart", "").unwrap();
+        assert_eq!(restart.params, Some(json!({ "mode": "warm" })));
+        assert_eq!(restart.role, AccessRole::Operator);
+
+        let config = resolve_rest_request(
+            "PATCH",
+            "/api/v1/config",
+            r#"{"control.auth_token": "secret"}"#,
+        )
+        .unwrap();
+        assert_eq!(config.role, AccessRole::Admin);
+    }
+
+    #[test]
+    fn rest_routes_reject_unknown_paths_methods_and_bad_input() {
+        assert_eq!(
+            resolve_rest_request("GET", "/api/v1/nope", ""),
+            Err(RestError::NotFound)
+        );
+        assert_eq!(
+            resolve_rest_request("DELETE", "/api/v1/status", ""),
+            Err(RestError::MethodNotAllowed)
+        );
+        assert_eq!(
+            resolve_rest_request("PUT", "/api/v1/vars/Speed", "{}"),
+            Err(RestError::BadRequest("missing value".to_string()))
+        );
+        assert_eq!(
+            resolve_rest_request("GET", "/api/v1/faults?limit=x", ""),
+            Err(RestError::BadRequest("invalid limit 'x'".to_string()))
+        );
+        assert_eq!(
+            status_for_control_error(Some(ControlErrorCode::Forbidden)),
+            403
+        );
+        assert_eq!(
+            status_for_control_error(Some(ControlErrorCode::NotFound)),
+            404
+        );
+        assert_eq!(status_for_control_error(None), 400);
+    }
+
+    #[test]
+    fn openapi_spec_lists_every_route_with_its_role() {
+        let spec = openapi_spec();
+        for route in ROUTES {
+            let operation = &spec["paths"][route.path][route.method.to_ascii_lowercase()];
+            assert_eq!(operation["x-control-request"], route.control);
+            assert!(operation["x-required-role"].is_string());
+        }
+        let force = &spec["paths"]["/api/v1/vars/{name}/force"]["put"];
+        assert_eq!(force["x-required-role"], "engineer");
+        assert_eq!(force["parameters"][0]["name"], "name");
+        assert!(force["requestBody"]["required"].as_bool().unwrap());
+        assert_eq!(
+            spec["paths"]["/api/v1/historian"]["get"]["parameters"]
+                .as_array()
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+}
@@ -37,6 +37,7 @@ let variableTrendSamples = {};
 let selectedTrendVar = '';
 let eventHistory = [];
 let meshValueCache = {};
+let meshTopology = null;
 let lastFaultText = null;
 let currentSourceName = '';
 let currentPcLine = null;
@@ -44,7 +45,7 @@ let refreshTimer = null;
 let discoveryTimer = null;
 let initialLoad = true;
 
-const fallbackSupportedIoDrivers = ['ethercat', 'gpio', 'loopback', 'modbus-tcp', 'simulated', 'mqtt'];
+const fallbackSupportedIoDrivers = ['ethercat', 'gpio', 'i2c', 'loopback', 'modbus-tcp', 'simulated', 'spi', 'mqtt'];
 
 const pageTitles = {
   overview: 'PLC Overview',
@@ -115,7 +116,7 @@ function driverParamsText(params) {
 }
 
 function isKnownIoDriver(name) {
-  return ['ethercat', 'gpio', 'loopback', 'modbus-tcp', 'simulated', 'mqtt'].includes(String(name || '').trim());
+  return ['ethercat', 'gpio', 'i2c', 'loopback', 'modbus-tcp', 'simulated', 'spi', 'mqtt'].includes(String(name || '').trim());
 }
 
 function defaultIoDriverParams(name) {
@@ -147,6 +148,27 @@ function defaultIoDriverParams(name) {
       outputs: [],
     };
   }
+  if (driver === 'i2c') {
+    return {
+      bus: '/dev/i2c-1',
+      on_error: 'fault',
+      fallback: 'fault',
+      init: [],
+      channels: [],
+    };
+  }
+  if (driver === 'spi') {
+    return {
+      device: '/dev/spidev0.0',
+      speed_hz: 1000000,
+      mode: 0,
+      frame_bytes: 1,
+      input_start: 0,
+      output_start: 0,
+      on_error: 'fault',
+      fallback: 'fault',
+    };
+  }
   if (driver === 'ethercat') {
     return {
       adapter: 'mock',
@@ -533,6 +555,16 @@ function setLoading(id, loading, label) {
   }
 }
 
+// Control errors arrive as { code, name, message }; keep `error` a display string and
+// expose the symbolic name as `errorCode`. Runtimes with legacy_errors send a string.
+function normalizeControlResponse(body) {
+  if (body && body.error && typeof body.error === 'object') {
+    body.errorCode = body.error.name;
+    body.error = body.error.message;
+  }
+  return body;
+}
+
 async function apiRequest(type, params) {
   const payload = { id: 1, type, params };
   try {
@@ -547,7 +579,7 @@ async function apiRequest(type, params) {
     if (res.status === 401) {
       return { ok: false, error: 'unauthorized' };
     }
-    return res.json();
+    return normalizeControlResponse(await res.json());
   } catch (err) {
     return { ok: false, error: 'offline' };
   }
@@ -871,6 +903,44 @@ function calculateHealthScore(statusResult) {
   return Math.max(0, Math.min(100, score));
 }
 
+function redundancyRow(result) {
+  const role = result.redundancy_role;
+  if (!role) return '';
+  const detail = result.redundancy || {};
+  const partner = detail.partner_name || detail.partner || 'partner';
+  const partnerRole = detail.partner_role
+    ? `${partner} ${detail.partner_role}${detail.partner_faulted ? ' (faulted)' : ''}`
+    : `${partner} not seen`;
+  return `<div class="row"><span>Redundancy</span><span><span class="stat">${escapeHtml(role)}</span> | ${escapeHtml(partnerRole)}</span></div>`;
+}
+
+function safetyRow(result) {
+  const safety = result.safety;
+  if (!safety) return '';
+  const outputs = Array.isArray(safety.outputs) ? safety.outputs : [];
+  const overrides = outputs
+    .map(entry => `${entry.address}=${entry.action}`)
+    .join(', ');
+  const policy = overrides ? `${safety.default} | ${overrides}` : safety.default;
+  const applied = safety.active ? ' <span class="stat">applied</span>' : '';
+  return `<div class="row"><span>Safe state</span><span>${escapeHtml(policy)}${applied}</span></div>`;
+}
+
+function programRows(result) {
+  const program = result.program;
+  if (!program) return '';
+  const version = [program.version, program.git_hash ? `(${program.git_hash})` : '']
+    .filter(Boolean)
+    .join(' ');
+  const builtAt = program.built_at ? program.built_at.slice(0, 19).replace('T', ' ') : '';
+  const built = [builtAt, program.author ? `by ${program.author}` : '']
+    .filter(Boolean)
+    .join(' ');
+  return `
+    ${version ? `<div class="row"><span>Program</span><span class="stat">${escapeHtml(version)}</span></div>` : ''}
+    ${built ? `<div class="row"><span>Built</span><span>${escapeHtml(built)}</span></div>` : ''}`;
+}
+
 async function refreshStatus() {
   const status = await apiRequest('status');
   if (!status.ok && status.error === 'offline') {
@@ -924,6 +994,9 @@ async function refreshStatus() {
     <div class="row"><span>Uptime</span><span>${formatDuration(result.uptime_ms || 0)}</span></div>
     <div class="row"><span>Mode</span><span>${escapeHtml(simulationMode)} (x${simulationScale})</span></div>
     <div class="row"><span>Fault</span><span>${fault || 'none'}</span></div>
+    ${programRows(result)}
+    ${redundancyRow(result)}
+    ${safetyRow(result)}
     <div class="row"><span>I/O drivers</span><span>${okDrivers} ok | ${degraded} degraded | ${faulted} faulted</span></div>
     <div class="row"><span>CPU / memory</span><span>${cpuLabel} / ${memLabel}</span></div>
     ${simulationMode === 'simulation' && simulationWarning ? `<div class="row"><span>Warning</span><span>${escapeHtml(simulationWarning)}</span></div>` : ''}
@@ -1910,6 +1983,21 @@ function renderMeshConnections() {
     target.innerHTML = '<div class="empty">No mesh connections configured.</div>';
     return;
   }
+  const local = meshTopology?.nodes?.find(node => node.local);
+  if (local) {
+    const edges = meshTopology.edges || [];
+    target.innerHTML = edges
+      .filter(edge => edge.from === local.name || edge.to === local.name)
+      .map(edge => {
+        const inbound = edge.to === local.name;
+        const label = inbound
+          ? `Subscribe ${edge.from}:${edge.variable}`
+          : `Publish ${edge.variable} → ${edge.to}`;
+        const status = inbound ? (edge.state || 'configured') : 'consumed';
+        return `<div class="row"><span>${escapeHtml(label)}</span><span class="stat mesh-${escapeHtml(status)}">${escapeHtml(status)}</span></div>`;
+      }).join('') || '<div class="empty">No peers are exchanging data yet.</div>';
+    return;
+  }
   const publishRows = meshPublishState.map(value => {
     const status = connectionOk ? (debugEnabled ? 'live' : 'configured') : 'stale';
     return `<div class="row"><span>Publish ${escapeHtml(value || '-')}</span><span class="stat">${status}</span></div>`;
@@ -1921,6 +2009,104 @@ function renderMeshConnections() {
   target.innerHTML = publishRows + subscribeRows;
 }
 
+async function refreshMeshTopology() {
+  const res = await apiRequest('mesh.topology');
+  meshTopology = res.ok ? res.result : null;
+  renderMeshGraph(res.ok ? '' : res.error);
+  renderMeshConnections();
+}
+
+function renderMeshGraph(error) {
+  const target = document.getElementById('meshGraph');
+  const peers = document.getElementById('meshPeers');
+  if (!target || !peers) return;
+  const nodes = meshTopology?.nodes || [];
+  if (!nodes.length) {
+    const message = error === 'mesh disabled' || !error
+      ? 'Mesh is not enabled on this PLC.'
+      : `Mesh topology unavailable (${error}).`;
+    target.innerHTML = `<div class="empty">${escapeHtml(message)}</div>`;
+    peers.innerHTML = '';
+    return;
+  }
+  // This PLC sits in the middle with peers on a circle around it.
+  const width = 560;
+  const height = 320;
+  const cx = width / 2;
+  const cy = height / 2;
+  const radius = Math.min(width, height) / 2 - 48;
+  const others = nodes.filter(node => !node.local);
+  const positions = new Map();
+  nodes.filter(node => node.local).forEach(node => positions.set(node.name, { x: cx, y: cy }));
+  others.forEach((node, idx) => {
+    const angle = (2 * Math.PI * idx) / Math.max(others.length, 1) - Math.PI / 2;
+    positions.set(node.name, { x: cx + radius * Math.cos(angle), y: cy + radius * Math.sin(angle) });
+  });
+  const grouped = new Map();
+  (meshTopology.edges || []).forEach(edge => {
+    const key = `${edge.from}\u0000${edge.to}`;
+    if (!grouped.has(key)) grouped.set(key, { from: edge.from, to: edge.to, variables: [], stale: false });
+    const entry = grouped.get(key);
+    entry.variables.push(edge.variable);
+    entry.stale = entry.stale || edge.state === 'stale';
+  });
+  const edgeSvg = Array.from(grouped.values()).map(edge => {
+    const from = positions.get(edge.from);
+    const to = positions.get(edge.to);
+    if (!from || !to) return '';
+    // Offset both directions of a pair so they do not overlap.
+    const dx = to.x - from.x;
+    const dy = to.y - from.y;
+    const length = Math.hypot(dx, dy) || 1;
+    const ox = (-dy / length) * 6;
+    const oy = (dx / length) * 6;
+    const x1 = from.x + (dx / length) * 26 + ox;
+    const y1 = from.y + (dy / length) * 26 + oy;
+    const x2 = to.x - (dx / length) * 26 + ox;
+    const y2 = to.y - (dy / length) * 26 + oy;
+    const label = edge.variables.length > 2
+      ? `${edge.variables.slice(0, 2).join(', ')} +${edge.variables.length - 2}`
+      : edge.variables.join(', ');
+    return `
+      <line class="mesh-edge${edge.stale ? ' stale' : ''}" x1="${x1}" y1="${y1}" x2="${x2}" y2="${y2}" marker-end="url(#meshArrow)">
+        <title>${escapeHtml(`${edge.from} → ${edge.to}: ${edge.variables.join(', ')}`)}</title>
+      </line>
+      <text class="mesh-edge-label" x="${(x1 + x2) / 2 + ox * 2}" y="${(y1 + y2) / 2 + oy * 2}">${escapeHtml(label)}</text>`;
+  }).join('');
+  const nodeSvg = nodes.map(node => {
+    const pos = positions.get(node.name);
+    const status = node.local ? 'local' : (node.reachable ? 'online' : 'offline');
+    const detail = node.local
+      ? 'this PLC'
+      : (node.rtt_ms != null ? `${node.rtt_ms.toFixed(1)} ms` : (node.reachable ? 'online' : 'unreachable'));
+    return `
+      <g class="mesh-node ${status}">
+        <circle cx="${pos.x}" cy="${pos.y}" r="22"/>
+        <text class="mesh-node-name" x="${pos.x}" y="${pos.y - 28}">${escapeHtml(node.name)}</text>
+        <text class="mesh-node-detail" x="${pos.x}" y="${pos.y + 38}">${escapeHtml(detail)}</text>
+      </g>`;
+  }).join('');
+  target.innerHTML = `
+    <svg viewBox="0 0 ${width} ${height}" role="img" aria-label="Mesh topology">
+      <defs>
+        <marker id="meshArrow" viewBox="0 0 10 10" refX="9" refY="5" markerWidth="7" markerHeight="7" orient="auto-start-reverse">
+          <path d="M 0 0 L 10 5 L 0 10 z"/>
+        </marker>
+      </defs>
+      ${edgeSvg}
+      ${nodeSvg}
+    </svg>`;
+  peers.innerHTML = others.map(node => {
+    const detail = node.rtt_ms != null
+      ? `rtt ${node.rtt_ms.toFixed(1)} ms`
+      : (node.error ? `unreachable: ${node.error}` : (node.reachable ? 'online' : 'unreachable'));
+    const shares = (node.publish || []).length
+      ? `publishes ${(node.publish || []).join(', ')}`
+      : 'publishes nothing';
+    return `<div class="row"><span>${escapeHtml(node.name)} <span class="muted">${escapeHtml(node.address || '')} · ${escapeHtml(shares)}</span></span><span class="stat">${escapeHtml(detail)}</span></div>`;
+  }).join('') || '<div class="empty">No mesh peers discovered yet.</div>';
+}
+
 function renderSourceViewer(text) {
   const target = document.getElementById('sourceViewer');
   if (!target) return;
@@ -2186,6 +2372,7 @@ async function refreshDiscovery() {
     }
   }
   renderTopology(list.concat(probedManual));
+  await refreshMeshTopology();
 }
 
 async function refreshDiscoveryWithFeedback() {
@@ -2478,6 +2665,91 @@ async function deployBundle() {
   });
 }
 
+function bundleRequestHeaders() {
+  return Object.assign(
+    { 'Content-Type': 'application/json' },
+    authToken ? { 'X-Trust-Token': authToken } : {},
+  );
+}
+
+function formatBytes(size) {
+  const value = Number(size) || 0;
+  if (value >= 1024 * 1024) return `${(value / (1024 * 1024)).toFixed(1)} MB`;
+  if (value >= 1024) return `${(value / 1024).toFixed(1)} KB`;
+  return `${value} B`;
+}
+
+async function loadBundleFiles() {
+  try {
+    const [filesRes, tomlRes] = await Promise.all([
+      fetch('/api/bundle/files', { headers: bundleRequestHeaders() }),
+      fetch('/api/bundle/runtime-toml', { headers: bundleRequestHeaders() }),
+    ]);
+    const files = await filesRes.json();
+    if (files.ok && files.files.length) {
+      setHtml('bundleFiles', files.files.map(file => {
+        const updated = file.updated_ms ? new Date(Number(file.updated_ms)).toLocaleString() : '-';
+        return `<div class="row"><span>${escapeHtml(file.name)}</span><span>${formatBytes(file.size)}</span><span class="muted">${updated}</span></div>`;
+      }).join(''));
+    } else {
+      setHtml('bundleFiles', `<div class="empty">${escapeHtml(files.error || 'No project files found.')}</div>`);
+    }
+    const toml = await tomlRes.json();
+    const editor = document.getElementById('runtimeTomlEditor');
+    if (editor && toml.ok) {
+      editor.value = toml.text;
+    }
+    setStatus('runtimeTomlStatus', toml.ok ? '' : (toml.error || 'Unable to load runtime.toml.'), toml.ok ? '' : 'error');
+  } catch (err) {
+    setHtml('bundleFiles', '<div class="empty">Project files unavailable.</div>');
+  }
+}
+
+async function submitRuntimeToml(validateOnly) {
+  const editor = document.getElementById('runtimeTomlEditor');
+  if (!editor) return null;
+  const restart = validateOnly ? null : (document.getElementById('runtimeTomlRestart').value || null);
+  try {
+    const res = await fetch('/api/bundle/runtime-toml', {
+      method: 'POST',
+      headers: bundleRequestHeaders(),
+      body: JSON.stringify({ text: editor.value, validate_only: validateOnly, restart }),
+    });
+    return await res.json();
+  } catch (err) {
+    return { ok: false, error: 'PLC unreachable (offline).' };
+  }
+}
+
+async function validateRuntimeToml() {
+  await withLoadingState('runtimeTomlValidate', 'runtimeTomlStatus', 'Validating...', async () => {
+    const data = await submitRuntimeToml(true);
+    if (data && data.ok) {
+      setStatus('runtimeTomlStatus', 'runtime.toml is valid.', 'success');
+    } else {
+      setStatus('runtimeTomlStatus', (data && data.error) || 'Validation failed.', 'error');
+    }
+  });
+}
+
+async function saveRuntimeToml() {
+  const restart = document.getElementById('runtimeTomlRestart').value || null;
+  if (restart === 'cold' && !confirm('Save runtime.toml and restart cold?')) {
+    return;
+  }
+  await withLoadingState('runtimeTomlSave', 'runtimeTomlStatus', 'Saving...', async () => {
+    const data = await submitRuntimeToml(false);
+    if (data && data.ok) {
+      setStatus('runtimeTomlStatus', restart ? `Saved. Restarting ${restart}.` : 'Saved. Restart to apply.', 'success');
+      addDeployHistory({ ts: Date.now(), restart, written: data.written || [] });
+      showToast('runtime.toml saved.', 'success');
+      await loadBundleFiles();
+    } else {
+      setStatus('runtimeTomlStatus', (data && data.error) || 'Save failed.', 'error');
+    }
+  });
+}
+
 async function readBase64(file) {
   const buffer = await file.arrayBuffer();
   const bytes = new Uint8Array(buffer);
@@ -2666,6 +2938,9 @@ function setPage(page) {
   if (page === 'program' && !programLoaded) {
     loadProgram();
   }
+  if (page === 'deploy') {
+    loadBundleFiles();
+  }
   const group = tabGroups.get(page);
   if (group && group.active) {
     group.activate(group.active);
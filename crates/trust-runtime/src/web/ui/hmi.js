@@ -34,6 +34,9 @@ const state = {
   routeFocus: null,
   routeTarget: null,
   trendDurationMs: null,
+  trendUntilMs: null,
+  trendHidden: new Set(),
+  trendChart: null,
   processBindingMisses: 0,
   presentationMode: 'operator',
   layoutEditMode: false,
@@ -505,6 +508,16 @@ function initModeControls() {
   });
 }
 
+// Control errors arrive as { code, name, message }; keep `error` a display string and
+// expose the symbolic name as `errorCode`. Runtimes with legacy_errors send a string.
+function normalizeControlResponse(body) {
+  if (body && body.error && typeof body.error === 'object') {
+    body.errorCode = body.error.name;
+    body.error = body.error.message;
+  }
+  return body;
+}
+
 async function apiControl(type, params) {
   const payload = { id: Date.now(), type };
   if (params !== undefined) {
@@ -518,7 +531,7 @@ async function apiControl(type, params) {
   if (!response.ok) {
     throw new Error(`HTTP ${response.status}`);
   }
-  return response.json();
+  return normalizeControlResponse(await response.json());
 }
 
 function ensurePollingLoop() {
@@ -745,6 +758,9 @@ function formatProcessRawValue(value) {
   if (typeof value === 'string') {
     return value;
   }
+  if (typeof value === 'object' && typeof value.variant === 'string') {
+    return value.variant;
+  }
   try {
     return JSON.stringify(value);
   } catch (_error) {
@@ -895,6 +911,9 @@ function formatValue(value) {
   if (typeof value === 'string') {
     return value;
   }
+  if (typeof value === 'object' && typeof value.variant === 'string') {
+    return value.variant;
+  }
   try {
     return JSON.stringify(value);
   } catch (_error) {
@@ -1539,6 +1558,7 @@ function hideContentPanels() {
     trend.classList.add('hidden');
     trend.innerHTML = '';
   }
+  state.trendChart = null;
   if (alarm) {
     alarm.classList.add('hidden');
     alarm.innerHTML = '';
@@ -2157,6 +2177,7 @@ function navigateToPage(pageId, route = {}, replace = false) {
     return;
   }
   state.currentPage = pageId;
+  state.trendUntilMs = null;
   applyRoute(
     {
       page: pageId,
@@ -2613,51 +2634,128 @@ function resolveTrendIds(page) {
   return ids.length ? ids : undefined;
 }
 
-function trendSvg(points) {
-  if (!Array.isArray(points) || !points.length) {
-    return '<svg class="trend-svg" viewBox="0 0 300 92"></svg>';
+const TREND_MIN_WINDOW_MS = 5 * 1000;
+const TREND_MAX_WINDOW_MS = 7 * 24 * 60 * 60 * 1000;
+const TREND_CHART_WIDTH = 800;
+const TREND_CHART_HEIGHT = 260;
+const TREND_COLORS = ['var(--accent)', '#60a5fa', '#f59e0b', '#a78bfa', '#f472b6', '#34d399', '#f87171', '#facc15'];
+
+function trendView(result) {
+  const duration = Number(result?.duration_ms) || 10 * 60 * 1000;
+  const end = Number(result?.until_ms) || Number(result?.timestamp_ms) || Date.now();
+  return { start: end - duration, end, duration };
+}
+
+function trendWindowFor(end, duration, nowMs) {
+  const span = Math.min(TREND_MAX_WINDOW_MS, Math.max(TREND_MIN_WINDOW_MS, Math.round(duration)));
+  // Windows reaching the present follow live samples again.
+  if (!Number.isFinite(end) || end >= nowMs) {
+    return { untilMs: null, durationMs: span };
   }
-  const width = 300;
-  const height = 92;
-  const values = points.flatMap((point) => [Number(point.min), Number(point.max), Number(point.value)]);
-  const min = Math.min(...values);
-  const max = Math.max(...values);
-  const span = Math.max(1e-9, max - min);
-  const toY = (value) => {
-    const normalized = (value - min) / span;
-    return Math.round((height - 6) - normalized * (height - 14));
-  };
-  const toX = (index) => {
-    if (points.length <= 1) {
-      return 0;
+  return { untilMs: Math.round(end), durationMs: span };
+}
+
+function zoomTrendWindow(view, factor, anchorRatio, nowMs) {
+  const ratio = Math.min(1, Math.max(0, Number(anchorRatio)));
+  const duration = Math.min(TREND_MAX_WINDOW_MS, Math.max(TREND_MIN_WINDOW_MS, view.duration * factor));
+  const anchor = view.start + view.duration * ratio;
+  return trendWindowFor(anchor + (1 - ratio) * duration, duration, nowMs);
+}
+
+function panTrendWindow(view, deltaRatio, nowMs) {
+  return trendWindowFor(view.end - deltaRatio * view.duration, view.duration, nowMs);
+}
+
+function trendReadoutAt(series, timestampMs) {
+  return series
+    .map((entry) => {
+      const points = Array.isArray(entry.points) ? entry.points : [];
+      let nearest = null;
+      for (const point of points) {
+        const distance = Math.abs(Number(point.ts_ms) - timestampMs);
+        if (!nearest || distance < nearest.distance) {
+          nearest = { point, distance };
+        }
+      }
+      if (!nearest) {
+        return null;
+      }
+      return {
+        id: entry.id,
+        label: entry.label || entry.id,
+        unit: entry.unit || '',
+        ts_ms: Number(nearest.point.ts_ms),
+        value: Number(nearest.point.value),
+      };
+    })
+    .filter((entry) => entry !== null);
+}
+
+function formatTrendTime(timestampMs, duration) {
+  const date = new Date(Number(timestampMs));
+  const pad = (value) => String(value).padStart(2, '0');
+  const time = `${pad(date.getHours())}:${pad(date.getMinutes())}:${pad(date.getSeconds())}`;
+  if (duration > 24 * 60 * 60 * 1000) {
+    return `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())} ${time}`;
+  }
+  return time;
+}
+
+function trendColor(index) {
+  return TREND_COLORS[index % TREND_COLORS.length];
+}
+
+function trendChartSvg(series, view, cursorMs) {
+  const width = TREND_CHART_WIDTH;
+  const height = TREND_CHART_HEIGHT;
+  const grid = [0.25, 0.5, 0.75]
+    .map((ratio) => `<line class="trend-grid-line" x1="0" x2="${width}" y1="${ratio * height}" y2="${ratio * height}"></line>`)
+    .join('');
+  const values = series.flatMap(({ entry }) => (entry.points || [])
+    .flatMap((point) => [Number(point.min), Number(point.max), Number(point.value)]))
+    .filter(Number.isFinite);
+  let body = '';
+  let range = null;
+  if (values.length) {
+    let min = Math.min(...values);
+    let max = Math.max(...values);
+    if (max - min < 1e-9) {
+      min -= 1;
+      max += 1;
+    }
+    const pad = (max - min) * 0.06;
+    range = { min: min - pad, max: max + pad };
+    const toX = (ts) => (((Number(ts) - view.start) / view.duration) * width).toFixed(1);
+    const toY = (value) => (height - ((value - range.min) / (range.max - range.min)) * height).toFixed(1);
+    for (const { entry, color } of series) {
+      const points = (entry.points || []).filter((point) => Number.isFinite(Number(point.value)));
+      if (!points.length) {
+        continue;
+      }
+      const upper = points.map((point) => `${toX(point.ts_ms)},${toY(Number(point.max))}`);
+      const lower = [...points].reverse().map((point) => `${toX(point.ts_ms)},${toY(Number(point.min))}`);
+      const line = points.map((point) => `${toX(point.ts_ms)},${toY(Number(point.value))}`).join(' ');
+      body += `<polygon class="trend-band" style="fill:${color}" points="${[...upper, ...lower].join(' ')}"></polygon>`;
+      body += `<polyline class="trend-line" style="stroke:${color}" points="${line}"></polyline>`;
     }
-    return Math.round((index / (points.length - 1)) * width);
+  }
+  if (Number.isFinite(cursorMs)) {
+    const x = (((cursorMs - view.start) / view.duration) * width).toFixed(1);
+    body += `<line class="trend-cursor" x1="${x}" x2="${x}" y1="0" y2="${height}"></line>`;
+  }
+  return {
+    svg: `<svg class="trend-chart-svg" viewBox="0 0 ${width} ${height}" preserveAspectRatio="none">${grid}${body}</svg>`,
+    range,
   };
+}
 
-  const avgPoints = points.map((point, idx) => ({
-    x: toX(idx),
-    y: toY(Number(point.value)),
-  }));
-  const avg = avgPoints.map((point) => `${point.x},${point.y}`).join(' ');
-  const upper = points
-    .map((point, idx) => `${toX(idx)},${toY(Number(point.max))}`)
-    .join(' ');
-  const lower = [...points]
-    .reverse()
-    .map((point, idx) => {
-      const x = toX(points.length - 1 - idx);
-      return `${x},${toY(Number(point.min))}`;
-    })
-    .join(' ');
-  const band = `${upper} ${lower}`;
-  return `<svg class="trend-svg" viewBox="0 0 ${width} ${height}" preserveAspectRatio="none"><polygon class="trend-band" points="${band}"></polygon><polyline class="trend-line" points="${avg}"></polyline></svg>`;
+function applyTrendWindow(page, next) {
+  state.trendDurationMs = next.durationMs;
+  state.trendUntilMs = next.untilMs;
+  void refreshTrends(page);
 }
 
-function renderTrends(page, result) {
-  const panel = byId('trendPanel');
-  if (!panel) {
-    return;
-  }
+function buildTrendChart(panel, page) {
   panel.classList.remove('hidden');
   panel.innerHTML = '';
 
@@ -2666,74 +2764,212 @@ function renderTrends(page, result) {
   title.textContent = page?.title || 'Trends';
   panel.appendChild(title);
 
-  const presetWrap = document.createElement('div');
-  presetWrap.className = 'trend-presets';
-  for (const preset of [
-    { label: '1m', ms: 60 * 1000 },
-    { label: '10m', ms: 10 * 60 * 1000 },
-    { label: '1h', ms: 60 * 60 * 1000 },
-  ]) {
+  const toolbar = document.createElement('div');
+  toolbar.className = 'trend-presets';
+  const addButton = (label, titleText, onClick) => {
     const button = document.createElement('button');
     button.type = 'button';
     button.className = 'trend-preset';
-    button.textContent = preset.label;
-    const activeDuration = Number.isFinite(state.trendDurationMs) && state.trendDurationMs > 0
-      ? state.trendDurationMs
-      : (Number(page?.duration_ms) || 10 * 60 * 1000);
-    if (activeDuration === preset.ms) {
-      button.classList.add('active');
+    button.textContent = label;
+    button.title = titleText;
+    button.addEventListener('click', onClick);
+    toolbar.appendChild(button);
+    return button;
+  };
+  const presets = [
+    { label: '1m', ms: 60 * 1000 },
+    { label: '10m', ms: 10 * 60 * 1000 },
+    { label: '1h', ms: 60 * 60 * 1000 },
+    { label: '8h', ms: 8 * 60 * 60 * 1000 },
+  ].map((preset) => ({
+    ms: preset.ms,
+    button: addButton(preset.label, `Show the last ${preset.label}`, () => {
+      applyTrendWindow(page, { untilMs: null, durationMs: preset.ms });
+    }),
+  }));
+  const zoom = (factor) => {
+    const view = trendView(state.trendChart?.result);
+    const now = Number(state.trendChart?.result?.timestamp_ms) || Date.now();
+    applyTrendWindow(page, zoomTrendWindow(view, factor, state.trendUntilMs === null ? 1 : 0.5, now));
+  };
+  addButton('-', 'Zoom out', () => zoom(2));
+  addButton('+', 'Zoom in', () => zoom(0.5));
+  const live = addButton('Live', 'Follow live values', () => {
+    applyTrendWindow(page, { untilMs: null, durationMs: trendView(state.trendChart?.result).duration });
+  });
+  panel.appendChild(toolbar);
+
+  const chart = document.createElement('div');
+  chart.className = 'trend-chart';
+  const plot = document.createElement('div');
+  plot.className = 'trend-chart-plot';
+  const axisMax = document.createElement('span');
+  axisMax.className = 'trend-axis trend-axis-max';
+  const axisMin = document.createElement('span');
+  axisMin.className = 'trend-axis trend-axis-min';
+  const axisStart = document.createElement('span');
+  axisStart.className = 'trend-axis trend-axis-start';
+  const axisEnd = document.createElement('span');
+  axisEnd.className = 'trend-axis trend-axis-end';
+  chart.appendChild(plot);
+  chart.appendChild(axisMax);
+  chart.appendChild(axisMin);
+  chart.appendChild(axisStart);
+  chart.appendChild(axisEnd);
+  panel.appendChild(chart);
+
+  const readout = document.createElement('div');
+  readout.className = 'trend-readout';
+  panel.appendChild(readout);
+  const legend = document.createElement('div');
+  legend.className = 'trend-legend';
+  panel.appendChild(legend);
+
+  const ratioAt = (event) => {
+    const rect = plot.getBoundingClientRect();
+    return rect.width > 0 ? (event.clientX - rect.left) / rect.width : 1;
+  };
+  plot.addEventListener('wheel', (event) => {
+    event.preventDefault();
+    const view = trendView(state.trendChart?.result);
+    const now = Number(state.trendChart?.result?.timestamp_ms) || Date.now();
+    applyTrendWindow(page, zoomTrendWindow(view, event.deltaY > 0 ? 1.25 : 0.8, ratioAt(event), now));
+  }, { passive: false });
+  plot.addEventListener('pointerdown', (event) => {
+    if (!state.trendChart) {
+      return;
     }
-    button.addEventListener('click', () => {
-      state.trendDurationMs = preset.ms;
-      void refreshTrends(page);
-    });
-    presetWrap.appendChild(button);
-  }
-  panel.appendChild(presetWrap);
+    state.trendChart.drag = { startRatio: ratioAt(event) };
+    plot.setPointerCapture?.(event.pointerId);
+  });
+  plot.addEventListener('pointermove', (event) => {
+    const chartState = state.trendChart;
+    if (!chartState) {
+      return;
+    }
+    const ratio = ratioAt(event);
+    if (chartState.drag) {
+      plot.style.transform = `translateX(${((ratio - chartState.drag.startRatio) * 100).toFixed(1)}%)`;
+      return;
+    }
+    const view = trendView(chartState.result);
+    chartState.cursorMs = view.start + view.duration * Math.min(1, Math.max(0, ratio));
+    drawTrendChart();
+  });
+  plot.addEventListener('pointerup', (event) => {
+    const chartState = state.trendChart;
+    if (!chartState?.drag) {
+      return;
+    }
+    const delta = ratioAt(event) - chartState.drag.startRatio;
+    chartState.drag = null;
+    plot.style.transform = '';
+    if (Math.abs(delta) < 0.005) {
+      return;
+    }
+    const now = Number(chartState.result?.timestamp_ms) || Date.now();
+    applyTrendWindow(page, panTrendWindow(trendView(chartState.result), delta, now));
+  });
+  plot.addEventListener('pointerleave', () => {
+    if (state.trendChart && !state.trendChart.drag) {
+      state.trendChart.cursorMs = null;
+      drawTrendChart();
+    }
+  });
 
-  const series = Array.isArray(result?.series) ? result.series : [];
-  if (!series.length) {
-    const empty = document.createElement('div');
-    empty.className = 'empty';
-    empty.textContent = 'No numeric signals available for trend visualization.';
-    panel.appendChild(empty);
+  state.trendChart = {
+    pageId: page?.id || null,
+    result: null,
+    cursorMs: null,
+    drag: null,
+    refs: { presets, live, plot, axisMax, axisMin, axisStart, axisEnd, readout, legend },
+  };
+}
+
+function drawTrendChart() {
+  const chartState = state.trendChart;
+  if (!chartState) {
     return;
   }
+  const { refs, result } = chartState;
+  const view = trendView(result);
+  for (const preset of refs.presets) {
+    preset.button.classList.remove('active');
+    if (state.trendUntilMs === null && preset.ms === view.duration) {
+      preset.button.classList.add('active');
+    }
+  }
+  refs.live.classList.remove('active');
+  if (state.trendUntilMs === null) {
+    refs.live.classList.add('active');
+  }
 
-  const grid = document.createElement('div');
-  grid.className = 'trend-grid';
+  const all = Array.isArray(result?.series) ? result.series : [];
   const focusedSignal = state.routeSignal;
   const focusedWidgetId = focusedSignal
     ? (state.schema?.widgets || []).find((widget) => widget.path === focusedSignal || widget.id === focusedSignal)?.id || focusedSignal
     : null;
-
-  for (const entry of series) {
-    const card = document.createElement('article');
-    card.className = 'trend-card';
-    if (focusedWidgetId && entry.id === focusedWidgetId) {
-      card.classList.add('focused');
+  const visible = all
+    .map((entry, index) => ({ entry, color: trendColor(index) }))
+    .filter(({ entry }) => !state.trendHidden.has(entry.id));
+
+  const { svg, range } = trendChartSvg(visible, view, chartState.cursorMs);
+  refs.plot.innerHTML = all.length
+    ? svg
+    : `${svg}<div class="trend-chart-empty">${result?.historian ? 'No samples in this window.' : 'No samples in this window. Enable the historian to pan past the live buffer.'}</div>`;
+  refs.axisMax.textContent = range ? formatValue(range.max) : '';
+  refs.axisMin.textContent = range ? formatValue(range.min) : '';
+  refs.axisStart.textContent = formatTrendTime(view.start, view.duration);
+  refs.axisEnd.textContent = state.trendUntilMs === null ? 'now' : formatTrendTime(view.end, view.duration);
+
+  const readoutTime = Number.isFinite(chartState.cursorMs) ? chartState.cursorMs : view.end;
+  const readout = trendReadoutAt(visible.map(({ entry }) => entry), readoutTime);
+  refs.readout.textContent = readout.length
+    ? `${Number.isFinite(chartState.cursorMs) ? formatTrendTime(readoutTime, view.duration) : 'last'}  ${readout
+      .map((item) => `${item.label}: ${formatValue(item.value)}${item.unit ? ` ${item.unit}` : ''}`)
+      .join('   ')}`
+    : '';
+
+  refs.legend.innerHTML = '';
+  all.forEach((entry, index) => {
+    const chip = document.createElement('button');
+    chip.type = 'button';
+    chip.className = 'trend-legend-item';
+    if (state.trendHidden.has(entry.id)) {
+      chip.classList.add('off');
     }
+    if (focusedWidgetId && entry.id === focusedWidgetId) {
+      chip.classList.add('focused');
+    }
+    const swatch = document.createElement('span');
+    swatch.className = 'trend-legend-swatch';
+    swatch.style.background = trendColor(index);
+    const label = document.createElement('span');
+    label.textContent = entry.unit ? `${entry.label || entry.id} (${entry.unit})` : entry.label || entry.id;
+    chip.appendChild(swatch);
+    chip.appendChild(label);
+    chip.addEventListener('click', () => {
+      if (state.trendHidden.has(entry.id)) {
+        state.trendHidden.delete(entry.id);
+      } else {
+        state.trendHidden.add(entry.id);
+      }
+      drawTrendChart();
+    });
+    refs.legend.appendChild(chip);
+  });
+}
 
-    const heading = document.createElement('h3');
-    heading.textContent = entry.label || entry.id;
-
-    const meta = document.createElement('p');
-    meta.className = 'trend-meta';
-    const last = Array.isArray(entry.points) && entry.points.length
-      ? Number(entry.points[entry.points.length - 1].value)
-      : undefined;
-    meta.textContent = `last: ${last === undefined ? '--' : formatValue(last)}${entry.unit ? ` ${entry.unit}` : ''}`;
-
-    const svgHost = document.createElement('div');
-    svgHost.innerHTML = trendSvg(Array.isArray(entry.points) ? entry.points : []);
-
-    card.appendChild(heading);
-    card.appendChild(meta);
-    card.appendChild(svgHost);
-    grid.appendChild(card);
+function renderTrends(page, result) {
+  const panel = byId('trendPanel');
+  if (!panel) {
+    return;
   }
-
-  panel.appendChild(grid);
+  if (!state.trendChart || state.trendChart.pageId !== (page?.id || null)) {
+    buildTrendChart(panel, page);
+  }
+  state.trendChart.result = result;
+  drawTrendChart();
 }
 
 async function refreshTrends(page) {
@@ -2742,8 +2978,11 @@ async function refreshTrends(page) {
     : (Number(page?.duration_ms) || 10 * 60 * 1000);
   const params = {
     duration_ms: selectedDuration,
-    buckets: 120,
+    buckets: 240,
   };
+  if (Number.isFinite(state.trendUntilMs)) {
+    params.until_ms = state.trendUntilMs;
+  }
   const ids = resolveTrendIds(page);
   if (ids) {
     params.ids = ids;
@@ -0,0 +1,664 @@
+//! Background worker pool for asynchronous function blocks.
+//!
+//! Blocking work requested by the file function blocks and `TCP_CLIENT` runs
+//! on a small pool of threads so the scan never waits on I/O.
+//! Finished jobs are latched once at the start of each cycle, which keeps every
+//! POU in a cycle looking at the same set of completions.
+//!
+//! Once a data directory is set, every file path from ST is resolved inside it
+//! and paths that would leave it are rejected.
+//!
+//! The pool also owns the long-lived socket and serial channels behind the
+//! communication function blocks.
+
+mod channels;
+
+pub use channels::{ChannelData, ChannelSpec, ChannelStatus};
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use channels::Channel;
+
+/// Number of worker threads spawned on first use.
+pub const DEFAULT_WORKER_THREADS: usize = 2;
+
+/// Socket timeout used when `TCP_CLIENT.TIMEOUT` is zero.
+pub const DEFAULT_TCP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Largest file or socket reply returned to a STRING output.
+pub const MAX_TEXT_BYTES: usize = 64 * 1024;
+
+/// How `FILE_OPEN` opens a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileMode {
+    /// Read lines from an existing file.
+    Read,
+    /// Create or truncate the file for writing.
+    Write,
+    /// Create the file if needed and write at its end.
+    Append,
+}
+
+/// Blocking operation executed off the scan thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobRequest {
+    /// Read a file as UTF-8 text.
+    FileRead {
+        /// File to read; resolved inside the pool's data directory.
+        path: PathBuf,
+    },
+    /// Write text to a file, replacing or appending to its contents.
+    FileWrite {
+        /// File to write; resolved inside the pool's data directory.
+        path: PathBuf,
+        /// Text to write.
+        data: String,
+        /// Append instead of truncating.
+        append: bool,
+    },
+    /// Connect, send `payload`, and read the reply until the peer closes.
+    TcpExchange {
+        /// Peer as `host:port`.
+        address: String,
+        /// Text sent after connecting.
+        payload: String,
+        /// Connect, send, and receive timeout.
+        timeout: Duration,
+    },
+    /// Open a file and keep it open under a new handle.
+    FileOpen {
+        /// File to open; resolved inside the pool's data directory.
+        path: PathBuf,
+        /// Read, write, or append.
+        mode: FileMode,
+    },
+    /// Read the next line from an open file.
+    FileReadLine {
+        /// Handle returned by [`JobRequest::FileOpen`].
+        handle: u32,
+    },
+    /// Write text to a file opened for writing or appending.
+    FileWriteText {
+        /// Handle returned by [`JobRequest::FileOpen`].
+        handle: u32,
+        /// Text to write, as is.
+        data: String,
+    },
+    /// Close an open file.
+    FileClose {
+        /// Handle returned by [`JobRequest::FileOpen`].
+        handle: u32,
+    },
+}
+
+/// Result of a finished job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobOutcome {
+    /// The job succeeded; carries the text it produced (empty for writes).
+    Done(String),
+    /// The file was opened under this handle.
+    Opened(u32),
+    /// A line read found no more data.
+    EndOfFile,
+    /// The job failed with a readable reason.
+    Failed(String),
+}
+
+enum OpenFile {
+    Reader(BufReader<File>),
+    Writer(File),
+}
+
+struct Job {
+    id: u64,
+    generation: u64,
+    request: JobRequest,
+}
+
+struct Shared {
+    generation: AtomicU64,
+    completed: Mutex<HashMap<u64, JobOutcome>>,
+    files: Mutex<HashMap<u32, Arc<Mutex<OpenFile>>>>,
+    next_handle: AtomicU32,
+}
+
+impl Default for Shared {
+    fn default() -> Self {
+        Self {
+            generation: AtomicU64::new(0),
+            completed: Mutex::new(HashMap::new()),
+            files: Mutex::new(HashMap::new()),
+            next_handle: AtomicU32::new(1),
+        }
+    }
+}
+
+/// Thread pool that runs [`JobRequest`]s for the cyclic program.
+///
+/// Threads are spawned on the first submission, so programs without async
+/// function blocks pay nothing. Dropping the pool lets the threads exit once
+/// their current job finishes.
+pub struct WorkerPool {
+    threads: usize,
+    data_dir: Option<PathBuf>,
+    shared: Arc<Shared>,
+    sender: Mutex<Option<Sender<Job>>>,
+    next_id: AtomicU64,
+    latched: Mutex<HashMap<u64, JobOutcome>>,
+    channels: Mutex<HashMap<u64, Channel>>,
+}
+
+impl std::fmt::Debug for WorkerPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerPool")
+            .field("threads", &self.threads)
+            .field("data_dir", &self.data_dir)
+            .field("started", &lock(&self.sender).is_some())
+            .field("open_files", &lock(&self.shared.files).len())
+            .field("channels", &lock(&self.channels).len())
+            .finish()
+    }
+}
+
+impl Default for WorkerPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_WORKER_THREADS)
+    }
+}
+
+impl WorkerPool {
+    /// Create a pool that spawns `threads` workers (at least one) on first use.
+    #[must_use]
+    pub fn new(threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+            data_dir: None,
+            shared: Arc::new(Shared::default()),
+            sender: Mutex::new(None),
+            next_id: AtomicU64::new(1),
+            latched: Mutex::new(HashMap::new()),
+            channels: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Confine file jobs to `dir`; `None` uses paths as given.
+    pub fn set_data_dir(&mut self, dir: Option<PathBuf>) {
+        self.data_dir = dir;
+    }
+
+    /// Current data directory, if any.
+    #[must_use]
+    pub fn data_dir(&self) -> Option<&Path> {
+        self.data_dir.as_deref()
+    }
+
+    /// Queue `request` and return its job id (never zero).
+    pub fn submit(&self, request: JobRequest) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let request = match self.resolve(request) {
+            Ok(request) => request,
+            Err(reason) => {
+                lock(&self.shared.completed).insert(id, JobOutcome::Failed(reason));
+                return id;
+            }
+        };
+        let job = Job {
+            id,
+            generation: self.shared.generation.load(Ordering::Acquire),
+            request,
+        };
+        let mut sender = lock(&self.sender);
+        if sender.is_none() {
+            *sender = self.spawn_workers();
+        }
+        let failed = match sender.as_ref() {
+            Some(tx) => tx
+                .send(job)
+                .err()
+                .map(|_| "worker pool stopped".to_string()),
+            None => Some("failed to start worker threads".to_string()),
+        };
+        if let Some(reason) = failed {
+            lock(&self.shared.completed).insert(id, JobOutcome::Failed(reason));
+        }
+        id
+    }
+
+    /// Publish jobs finished since the previous call to [`WorkerPool::take`].
+    ///
+    /// The runtime calls this once at the start of every cycle.
+    pub fn latch(&self) {
+        let mut completed = lock(&self.shared.completed);
+        if completed.is_empty() {
+            return;
+        }
+        lock(&self.latched).extend(completed.drain());
+    }
+
+    /// Remove and return the latched outcome for `id`, if it has finished.
+    #[must_use]
+    pub fn take(&self, id: u64) -> Option<JobOutcome> {
+        lock(&self.latched).remove(&id)
+    }
+
+    /// Forget every queued, running, and finished job and close all files and channels.
+    ///
+    /// Jobs already running still complete, but their outcomes are discarded.
+    pub fn reset(&self) {
+        self.shared.generation.fetch_add(1, Ordering::AcqRel);
+        lock(&self.shared.completed).clear();
+        lock(&self.shared.files).clear();
+        lock(&self.latched).clear();
+        lock(&self.channels).clear();
+    }
+
+    /// Open a channel on its own thread and return its id (never zero).
+    ///
+    /// Connecting happens in the background; poll [`WorkerPool::channel_status`].
+    pub fn open_channel(&self, spec: ChannelSpec) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        lock(&self.channels).insert(id, Channel::open(spec));
+        id
+    }
+
+    /// Close channel `id`; its thread exits shortly after.
+    pub fn close_channel(&self, id: u64) {
+        lock(&self.channels).remove(&id);
+    }
+
+    /// Current state of channel `id`, or `None` if it is not open.
+    #[must_use]
+    pub fn channel_status(&self, id: u64) -> Option<ChannelStatus> {
+        lock(&self.channels).get(&id).map(Channel::status)
+    }
+
+    /// Queue `bytes` for transmission on channel `id`.
+    pub fn channel_send(&self, id: u64, bytes: Vec<u8>) -> Result<(), String> {
+        match lock(&self.channels).get(&id) {
+            Some(channel) => channel.send(bytes),
+            None => Err("channel closed".to_string()),
+        }
+    }
+
+    /// Take up to `max` received bytes (one datagram for UDP) from channel `id`.
+    #[must_use]
+    pub fn channel_receive(&self, id: u64, max: usize) -> Option<ChannelData> {
+        lock(&self.channels)
+            .get_mut(&id)
+            .and_then(|channel| channel.receive(max))
+    }
+
+    fn resolve(&self, request: JobRequest) -> Result<JobRequest, String> {
+        let Some(root) = self.data_dir.as_deref() else {
+            return Ok(request);
+        };
+        Ok(match request {
+            JobRequest::FileRead { path } => JobRequest::FileRead {
+                path: sandboxed(root, path)?,
+            },
+            JobRequest::FileWrite { path, data, append } => JobRequest::FileWrite {
+                path: sandboxed(root, path)?,
+                data,
+                append,
+            },
+            JobRequest::FileOpen { path, mode } => JobRequest::FileOpen {
+                path: sandboxed(root, path)?,
+                mode,
+            },
+            other => other,
+        })
+    }
+
+    fn spawn_workers(&self) -> Option<Sender<Job>> {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let rx = Arc::new(Mutex::new(rx));
+        let mut spawned = 0;
+        for index in 0..self.threads {
+            let rx = Arc::clone(&rx);
+            let shared = Arc::clone(&self.shared);
+            let builder = std::thread::Builder::new().name(format!("trust-worker-{index}"));
+            if builder.spawn(move || worker_loop(&rx, &shared)).is_ok() {
+                spawned += 1;
+            }
+        }
+        (spawned > 0).then_some(tx)
+    }
+}
+
+fn worker_loop(rx: &Mutex<Receiver<Job>>, shared: &Shared) {
+    loop {
+        let job = match lock(rx).recv() {
+            Ok(job) => job,
+            Err(_) => return,
+        };
+        let outcome = run_job(job.request, shared).unwrap_or_else(JobOutcome::Failed);
+        let mut completed = lock(&shared.completed);
+        if shared.generation.load(Ordering::Acquire) == job.generation {
+            completed.insert(job.id, outcome);
+        } else if let JobOutcome::Opened(handle) = outcome {
+            // Opened after a reset: nobody will ever close it.
+            lock(&shared.files).remove(&handle);
+        }
+    }
+}
+
+fn run_job(request: JobRequest, shared: &Shared) -> Result<JobOutcome, String> {
+    match request {
+        JobRequest::FileRead { path } => read_file(&path).map(JobOutcome::Done),
+        JobRequest::FileWrite { path, data, append } => {
+            write_file(&path, &data, append).map(|()| JobOutcome::Done(String::new()))
+        }
+        JobRequest::TcpExchange {
+            address,
+            payload,
+            timeout,
+        } => tcp_exchange(&address, &payload, timeout).map(JobOutcome::Done),
+        JobRequest::FileOpen { path, mode } => {
+            let file = open_file(&path, mode)?;
+            let handle = shared.next_handle.fetch_add(1, Ordering::Relaxed);
+            lock(&shared.files).insert(handle, Arc::new(Mutex::new(file)));
+            Ok(JobOutcome::Opened(handle))
+        }
+        JobRequest::FileReadLine { handle } => {
+            let file = open_handle(shared, handle)?;
+            let mut file = lock(&file);
+            let OpenFile::Reader(reader) = &mut *file else {
+                return Err(format!("handle {handle} is not open for reading"));
+            };
+            read_line(reader).map(|line| line.map_or(JobOutcome::EndOfFile, JobOutcome::Done))
+        }
+        JobRequest::FileWriteText { handle, data } => {
+            let file = open_handle(shared, handle)?;
+            let mut file = lock(&file);
+            let OpenFile::Writer(writer) = &mut *file else {
+                return Err(format!("handle {handle} is not open for writing"));
+            };
+            writer
+                .write_all(data.as_bytes())
+                .map(|()| JobOutcome::Done(String::new()))
+                .map_err(|err| format!("handle {handle}: {err}"))
+        }
+        JobRequest::FileClose { handle } => {
+            let file = lock(&shared.files)
+                .remove(&handle)
+                .ok_or_else(|| format!("handle {handle} is not open"))?;
+            // A running read or write on another worker finishes first.
+            let file = lock(&file);
+            if let OpenFile::Writer(writer) = &*file {
+                writer
+                    .sync_data()
+                    .map_err(|err| format!("handle {handle}: {err}"))?;
+            }
+            Ok(JobOutcome::Done(String::new()))
+        }
+    }
+}
+
+/// Resolve `path` inside `root`, rejecting absolute paths and `..` segments.
+fn sandboxed(root: &Path, path: PathBuf) -> Result<PathBuf, String> {
+    if path.as_os_str().is_empty() {
+        return Ok(path);
+    }
+    let inside = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if !inside {
+        return Err(format!(
+            "{}: path is outside the data directory",
+            path.display()
+        ));
+    }
+    Ok(root.join(path))
+}
+
+fn open_handle(shared: &Shared, handle: u32) -> Result<Arc<Mutex<OpenFile>>, String> {
+    lock(&shared.files)
+        .get(&handle)
+        .cloned()
+        .ok_or_else(|| format!("handle {handle} is not open"))
+}
+
+fn open_file(path: &Path, mode: FileMode) -> Result<OpenFile, String> {
+    if path.as_os_str().is_empty() {
+        return Err("empty path".to_string());
+    }
+    let describe = |err: std::io::Error| format!("{}: {err}", path.display());
+    if mode == FileMode::Read {
+        return File::open(path)
+            .map(|file| OpenFile::Reader(BufReader::new(file)))
+            .map_err(describe);
+    }
+    create_parent(path)?;
+    OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(mode == FileMode::Append)
+        .truncate(mode == FileMode::Write)
+        .open(path)
+        .map(OpenFile::Writer)
+        .map_err(describe)
+}
+
+/// Next line without its terminator, or `None` at end of file.
+fn read_line(reader: &mut BufReader<File>) -> Result<Option<String>, String> {
+    let mut line = Vec::new();
+    let read = reader
+        .by_ref()
+        .take(MAX_TEXT_BYTES as u64 + 1)
+        .read_until(b'\n', &mut line)
+        .map_err(|err| err.to_string())?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+    } else if line.len() > MAX_TEXT_BYTES {
+        return Err(format!("line exceeds {MAX_TEXT_BYTES} bytes"));
+    }
+    String::from_utf8(line)
+        .map(Some)
+        .map_err(|_| "line is not valid UTF-8".to_string())
+}
+
+fn create_parent(path: &Path) -> Result<(), String> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => {
+            std::fs::create_dir_all(parent).map_err(|err| format!("{}: {err}", parent.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    if path.as_os_str().is_empty() {
+        return Err("empty path".to_string());
+    }
+    let bytes = std::fs::read(path).map_err(|err| format!("{}: {err}", path.display()))?;
+    if bytes.len() > MAX_TEXT_BYTES {
+        return Err(format!(
+            "{}: file exceeds {MAX_TEXT_BYTES} bytes",
+            path.display()
+        ));
+    }
+    String::from_utf8(bytes).map_err(|_| format!("{}: not valid UTF-8", path.display()))
+}
+
+fn write_file(path: &Path, data: &str, append: bool) -> Result<(), String> {
+    if path.as_os_str().is_empty() {
+        return Err("empty path".to_string());
+    }
+    create_parent(path)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .map_err(|err| format!("{}: {err}", path.display()))?;
+    file.write_all(data.as_bytes())
+        .map_err(|err| format!("{}: {err}", path.display()))
+}
+
+fn tcp_exchange(address: &str, payload: &str, timeout: Duration) -> Result<String, String> {
+    let addr = address
+        .to_socket_addrs()
+        .map_err(|err| format!("{address}: {err}"))?
+        .next()
+        .ok_or_else(|| format!("{address}: no address resolved"))?;
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)
+        .map_err(|err| format!("connect {address}: {err}"))?;
+    stream
+        .set_read_timeout(Some(timeout))
+        .and_then(|()| stream.set_write_timeout(Some(timeout)))
+        .map_err(|err| format!("{address}: {err}"))?;
+    stream
+        .write_all(payload.as_bytes())
+        .map_err(|err| format!("send to {address}: {err}"))?;
+    // Signal end of request so request/response peers can close their side.
+    let _ = stream.shutdown(Shutdown::Write);
+
+    let mut received = Vec::new();
+    let mut buf = [0_u8; 4096];
+    while received.len() < MAX_TEXT_BYTES {
+        match stream.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => received.extend_from_slice(&buf[..read]),
+            // A peer that keeps the connection open ends the reply by going quiet.
+            Err(err)
+                if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+                    && !received.is_empty() =>
+            {
+                break
+            }
+            Err(err) => return Err(format!("receive from {address}: {err}")),
+        }
+    }
+    received.truncate(MAX_TEXT_BYTES);
+    Ok(String::from_utf8_lossy(&received).into_owned())
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poison| poison.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn wait_for(pool: &WorkerPool, id: u64) -> JobOutcome {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            pool.latch();
+            if let Some(outcome) = pool.take(id) {
+                return outcome;
+            }
+            assert!(Instant::now() < deadline, "job {id} did not finish");
+            std::thread::sleep(Duration::from_millis(2));
+        }
+    }
+
+    #[test]
+    fn outcomes_only_become_visible_after_latch() {
+        let pool = WorkerPool::new(1);
+        let id = pool.submit(JobRequest::FileRead {
+            path: PathBuf::new(),
+        });
+        assert_eq!(pool.take(id), None);
+        assert_eq!(
+            wait_for(&pool, id),
+            JobOutcome::Failed("empty path".to_string())
+        );
+        assert_eq!(pool.take(id), None);
+    }
+
+    #[test]
+    fn file_paths_stay_inside_the_data_dir() {
+        let root = std::env::temp_dir().join(format!("trust_workers_{}", std::process::id()));
+        let mut pool = WorkerPool::default();
+        pool.set_data_dir(Some(root.clone()));
+
+        let write = pool.submit(JobRequest::FileWrite {
+            path: PathBuf::from("logs/note.txt"),
+            data: "hello".to_string(),
+            append: false,
+        });
+        assert_eq!(wait_for(&pool, write), JobOutcome::Done(String::new()));
+        assert_eq!(
+            std::fs::read_to_string(root.join("logs/note.txt")).unwrap(),
+            "hello"
+        );
+        for path in ["../escape.txt", "/etc/hostname"] {
+            let read = pool.submit(JobRequest::FileRead {
+                path: PathBuf::from(path),
+            });
+            let JobOutcome::Failed(reason) = wait_for(&pool, read) else {
+                panic!("{path} should be rejected");
+            };
+            assert!(reason.contains("outside the data directory"), "{reason}");
+        }
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn file_handles_read_lines_until_end_of_file() {
+        let root = std::env::temp_dir().join(format!("trust_handles_{}", std::process::id()));
+        let mut pool = WorkerPool::new(1);
+        pool.set_data_dir(Some(root.clone()));
+
+        let open = |path: &str, mode| {
+            let id = pool.submit(JobRequest::FileOpen {
+                path: PathBuf::from(path),
+                mode,
+            });
+            match wait_for(&pool, id) {
+                JobOutcome::Opened(handle) => handle,
+                other => panic!("open failed: {other:?}"),
+            }
+        };
+        let writer = open("recipe.csv", FileMode::Write);
+        let write = pool.submit(JobRequest::FileWriteText {
+            handle: writer,
+            data: "a;1\r\nb;2\n".to_string(),
+        });
+        assert_eq!(wait_for(&pool, write), JobOutcome::Done(String::new()));
+        let close = pool.submit(JobRequest::FileClose { handle: writer });
+        assert_eq!(wait_for(&pool, close), JobOutcome::Done(String::new()));
+
+        let reader = open("recipe.csv", FileMode::Read);
+        let mut lines = Vec::new();
+        loop {
+            let id = pool.submit(JobRequest::FileReadLine { handle: reader });
+            match wait_for(&pool, id) {
+                JobOutcome::Done(line) => lines.push(line),
+                JobOutcome::EndOfFile => break,
+                other => panic!("read failed: {other:?}"),
+            }
+        }
+        assert_eq!(lines, vec!["a;1".to_string(), "b;2".to_string()]);
+
+        let write = pool.submit(JobRequest::FileWriteText {
+            handle: reader,
+            data: String::new(),
+        });
+        assert!(matches!(wait_for(&pool, write), JobOutcome::Failed(_)));
+        pool.reset();
+        let read = pool.submit(JobRequest::FileReadLine { handle: reader });
+        assert_eq!(
+            wait_for(&pool, read),
+            JobOutcome::Failed(format!("handle {reader} is not open"))
+        );
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,438 @@
+//! Long-lived socket and serial endpoints for the communication function blocks.
+//!
+//! Every open channel owns one thread doing blocking I/O with short timeouts.
+//! The scan only swaps byte buffers with it through queues, so
+//! `TCP_STREAM_CLIENT` and friends never stall a cycle.
+
+use std::collections::VecDeque;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::lock;
+
+/// Read timeout of channel threads; bounds how long a close takes to land.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Delay before a failed connect, bind, or open is retried.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Largest UDP payload over IPv4.
+const MAX_DATAGRAM: usize = 65_507;
+
+/// Endpoint opened by a communication function block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChannelSpec {
+    /// TCP connection to `host:port`, re-established while the channel is open.
+    TcpClient {
+        /// Peer as `host:port`.
+        address: String,
+    },
+    /// TCP listener serving one peer at a time.
+    TcpServer {
+        /// Local port to listen on.
+        port: u16,
+    },
+    /// UDP socket sending to a fixed remote, or replying to the last sender.
+    Udp {
+        /// Local port to bind; zero picks a free port.
+        port: u16,
+        /// Destination as `host:port`; empty replies to the last sender.
+        remote: String,
+    },
+    /// Serial device with 8 data bits, no parity, and one stop bit.
+    Serial {
+        /// Device path such as `/dev/ttyUSB0` or `COM3`.
+        device: String,
+        /// Baud rate.
+        baud: u32,
+    },
+}
+
+/// Connection state reported to the function block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChannelStatus {
+    /// The endpoint can send; for TCP, a peer is connected.
+    pub open: bool,
+    /// Last failure, cleared when the endpoint opens again.
+    pub error: Option<String>,
+    /// Peer of the current TCP connection.
+    pub peer: Option<String>,
+}
+
+/// Bytes handed to the scan by [`super::WorkerPool::channel_receive`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelData {
+    /// Received bytes, at most the requested length.
+    pub bytes: Vec<u8>,
+    /// Sender of a UDP datagram.
+    pub peer: Option<String>,
+}
+
+enum Incoming {
+    Stream(Vec<u8>),
+    Datagram(Vec<u8>, String),
+}
+
+/// Scan-side handle of an open channel. Dropping it stops the thread.
+pub(super) struct Channel {
+    outbound: Sender<Vec<u8>>,
+    inbound: Receiver<Incoming>,
+    pending: VecDeque<u8>,
+    status: Arc<Mutex<ChannelStatus>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Channel {
+    pub(super) fn open(spec: ChannelSpec) -> Self {
+        let (outbound, outbound_rx) = mpsc::channel();
+        let (inbound_tx, inbound) = mpsc::channel();
+        let status = Arc::new(Mutex::new(ChannelStatus::default()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let link = Link {
+            outbound: outbound_rx,
+            inbound: inbound_tx,
+            status: Arc::clone(&status),
+            stop: Arc::clone(&stop),
+        };
+        let spawned = std::thread::Builder::new()
+            .name("trust-channel".to_string())
+            .spawn(move || link.run(&spec));
+        if let Err(err) = spawned {
+            lock(&status).error = Some(format!("failed to start channel thread: {err}"));
+        }
+        Self {
+            outbound,
+            inbound,
+            pending: VecDeque::new(),
+            status,
+            stop,
+        }
+    }
+
+    pub(super) fn status(&self) -> ChannelStatus {
+        lock(&self.status).clone()
+    }
+
+    pub(super) fn send(&self, bytes: Vec<u8>) -> Result<(), String> {
+        if !lock(&self.status).open {
+            return Err("not connected".to_string());
+        }
+        self.outbound
+            .send(bytes)
+            .map_err(|_| "channel closed".to_string())
+    }
+
+    /// Up to `max` buffered stream bytes, or the next datagram truncated to `max`.
+    pub(super) fn receive(&mut self, max: usize) -> Option<ChannelData> {
+        if max == 0 {
+            return None;
+        }
+        while let Ok(incoming) = self.inbound.try_recv() {
+            match incoming {
+                Incoming::Stream(bytes) => self.pending.extend(bytes),
+                Incoming::Datagram(mut bytes, peer) => {
+                    bytes.truncate(max);
+                    return Some(ChannelData {
+                        bytes,
+                        peer: Some(peer),
+                    });
+                }
+            }
+        }
+        if self.pending.is_empty() {
+            return None;
+        }
+        let count = max.min(self.pending.len());
+        Some(ChannelData {
+            bytes: self.pending.drain(..count).collect(),
+            peer: None,
+        })
+    }
+}
+
+impl Drop for Channel {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+    }
+}
+
+/// Thread side of a channel.
+struct Link {
+    outbound: Receiver<Vec<u8>>,
+    inbound: Sender<Incoming>,
+    status: Arc<Mutex<ChannelStatus>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Link {
+    fn run(self, spec: &ChannelSpec) {
+        while !self.stopped() {
+            let result = match spec {
+                ChannelSpec::TcpClient { address } => self.run_tcp_client(address),
+                ChannelSpec::TcpServer { port } => self.run_tcp_server(*port),
+                ChannelSpec::Udp { port, remote } => self.run_udp(*port, remote),
+                ChannelSpec::Serial { device, baud } => self.run_serial(device, *baud),
+            };
+            self.mark_closed(result.err());
+            self.pause(RETRY_DELAY);
+        }
+    }
+
+    fn run_tcp_client(&self, address: &str) -> Result<(), String> {
+        let addr = resolve(address)?;
+        let mut stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)
+            .map_err(|err| format!("connect {address}: {err}"))?;
+        configure_stream(&stream).map_err(|err| format!("{address}: {err}"))?;
+        self.pump_stream(&mut stream, address)
+    }
+
+    fn run_tcp_server(&self, port: u16) -> Result<(), String> {
+        let listener = TcpListener::bind(("0.0.0.0", port))
+            .and_then(|listener| listener.set_nonblocking(true).map(|()| listener))
+            .map_err(|err| format!("listen on port {port}: {err}"))?;
+        while !self.stopped() {
+            match listener.accept() {
+                Ok((mut stream, peer)) => {
+                    let peer = peer.to_string();
+                    let served = stream
+                        .set_nonblocking(false)
+                        .and_then(|()| configure_stream(&stream))
+                        .map_err(|err| format!("{peer}: {err}"))
+                        .and_then(|()| self.pump_stream(&mut stream, &peer));
+                    // Serve the next peer; only listener failures restart the channel.
+                    self.mark_closed(served.err());
+                }
+                Err(err) if is_timeout(&err) => std::thread::sleep(POLL_INTERVAL),
+                Err(err) => return Err(format!("accept on port {port}: {err}")),
+            }
+        }
+        Ok(())
+    }
+
+    fn run_udp(&self, port: u16, remote: &str) -> Result<(), String> {
+        let socket = UdpSocket::bind(("0.0.0.0", port))
+            .and_then(|socket| {
+                socket
+                    .set_read_timeout(Some(POLL_INTERVAL))
+                    .map(|()| socket)
+            })
+            .map_err(|err| format!("bind port {port}: {err}"))?;
+        let mut target = if remote.is_empty() {
+            None
+        } else {
+            Some(resolve(remote)?)
+        };
+        let fixed_target = target.is_some();
+        self.mark_open(None);
+        let mut buf = vec![0_u8; MAX_DATAGRAM];
+        while !self.stopped() {
+            while let Some(bytes) = self.next_outbound()? {
+                let Some(addr) = target else {
+                    lock(&self.status).error = Some("no remote address to send to".to_string());
+                    continue;
+                };
+                socket
+                    .send_to(&bytes, addr)
+                    .map_err(|err| format!("send to {addr}: {err}"))?;
+            }
+            match socket.recv_from(&mut buf) {
+                Ok((read, peer)) => {
+                    if !fixed_target {
+                        target = Some(peer);
+                    }
+                    let _ = self
+                        .inbound
+                        .send(Incoming::Datagram(buf[..read].to_vec(), peer.to_string()));
+                }
+                Err(err) if is_timeout(&err) => {}
+                Err(err) => return Err(format!("receive on port {port}: {err}")),
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "serial-wire")]
+    fn run_serial(&self, device: &str, baud: u32) -> Result<(), String> {
+        let mut port = serialport::new(device, baud)
+            .timeout(POLL_INTERVAL)
+            .open()
+            .map_err(|err| format!("open {device}: {err}"))?;
+        self.pump_stream(&mut port, device)
+    }
+
+    #[cfg(not(feature = "serial-wire"))]
+    fn run_serial(&self, device: &str, _baud: u32) -> Result<(), String> {
+        Err(format!(
+            "{device}: serial support not built (enable the `serial-wire` feature)"
+        ))
+    }
+
+    /// Shuttle bytes between the queues and a connected stream until it closes.
+    fn pump_stream<S: Read + Write>(&self, stream: &mut S, peer: &str) -> Result<(), String> {
+        self.mark_open(Some(peer));
+        let mut buf = [0_u8; 4096];
+        while !self.stopped() {
+            while let Some(bytes) = self.next_outbound()? {
+                stream
+                    .write_all(&bytes)
+                    .and_then(|()| stream.flush())
+                    .map_err(|err| format!("send to {peer}: {err}"))?;
+            }
+            match stream.read(&mut buf) {
+                // An orderly hang-up only drops the connection, it is not a failure.
+                Ok(0) => return Ok(()),
+                Ok(read) => {
+                    let _ = self.inbound.send(Incoming::Stream(buf[..read].to_vec()));
+                }
+                Err(err) if is_timeout(&err) => {}
+                Err(err) => return Err(format!("receive from {peer}: {err}")),
+            }
+        }
+        Ok(())
+    }
+
+    fn next_outbound(&self) -> Result<Option<Vec<u8>>, String> {
+        match self.outbound.try_recv() {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => {
+                self.stop.store(true, Ordering::Release);
+                Ok(None)
+            }
+        }
+    }
+
+    fn mark_open(&self, peer: Option<&str>) {
+        let mut status = lock(&self.status);
+        status.open = true;
+        status.error = None;
+        status.peer = peer.map(str::to_string);
+    }
+
+    /// Record a closed endpoint and drop bytes queued for it.
+    fn mark_closed(&self, error: Option<String>) {
+        {
+            let mut status = lock(&self.status);
+            status.open = false;
+            status.peer = None;
+            if error.is_some() {
+                status.error = error;
+            }
+        }
+        while let Ok(Some(_)) = self.next_outbound() {}
+    }
+
+    fn pause(&self, duration: Duration) {
+        let deadline = Instant::now() + duration;
+        while !self.stopped() && Instant::now() < deadline {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Acquire)
+    }
+}
+
+fn resolve(address: &str) -> Result<SocketAddr, String> {
+    address
+        .to_socket_addrs()
+        .map_err(|err| format!("{address}: {err}"))?
+        .next()
+        .ok_or_else(|| format!("{address}: no address resolved"))
+}
+
+fn configure_stream(stream: &TcpStream) -> std::io::Result<()> {
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(POLL_INTERVAL))
+}
+
+fn is_timeout(err: &std::io::Error) -> bool {
+    matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wait_until(mut ready: impl FnMut() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !ready() {
+            assert!(Instant::now() < deadline, "channel did not become ready");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    fn receive_all(channel: &mut Channel, expected: usize) -> Vec<u8> {
+        let mut received = Vec::new();
+        wait_until(|| {
+            if let Some(data) = channel.receive(64) {
+                received.extend(data.bytes);
+            }
+            received.len() >= expected
+        });
+        received
+    }
+
+    #[test]
+    fn tcp_client_and_server_exchange_bytes() {
+        let probe = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = probe.local_addr().unwrap().port();
+        drop(probe);
+
+        let mut server = Channel::open(ChannelSpec::TcpServer { port });
+        let mut client = Channel::open(ChannelSpec::TcpClient {
+            address: format!("127.0.0.1:{port}"),
+        });
+        wait_until(|| client.status().open && server.status().open);
+        assert!(server.status().peer.is_some());
+
+        client.send(vec![1, 2, 3]).unwrap();
+        assert_eq!(receive_all(&mut server, 3), vec![1, 2, 3]);
+        server.send(vec![9, 8]).unwrap();
+        assert_eq!(receive_all(&mut client, 2), vec![9, 8]);
+
+        drop(client);
+        wait_until(|| !server.status().open);
+        assert_eq!(server.status().error, None);
+        assert_eq!(server.send(vec![0]), Err("not connected".to_string()));
+    }
+
+    #[test]
+    fn udp_receive_returns_one_datagram_and_its_sender() {
+        let peer = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let probe = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let port = probe.local_addr().unwrap().port();
+        drop(probe);
+
+        let mut channel = Channel::open(ChannelSpec::Udp {
+            port,
+            remote: String::new(),
+        });
+        wait_until(|| channel.status().open);
+        peer.send_to(&[1, 2, 3, 4], ("127.0.0.1", port)).unwrap();
+        peer.send_to(&[5], ("127.0.0.1", port)).unwrap();
+
+        let mut first = None;
+        wait_until(|| {
+            first = channel.receive(2);
+            first.is_some()
+        });
+        let first = first.unwrap();
+        assert_eq!(first.bytes, vec![1, 2]);
+        assert_eq!(first.peer, Some(peer.local_addr().unwrap().to_string()));
+
+        // Without a fixed REMOTE the channel answers the last sender.
+        channel.send(vec![7]).unwrap();
+        let mut buf = [0_u8; 4];
+        peer.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let (read, _) = peer.recv_from(&mut buf).unwrap();
+        assert_eq!(&buf[..read], &[7]);
+    }
+}
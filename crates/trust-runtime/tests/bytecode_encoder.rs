@@ -489,6 +489,73 @@ END_PROGRAM
     assert_eq!(second.code_offset, 16);
 }
 
+#[test]
+fn disassembler_maps_instructions_to_source_lines() {
+    let source = r#"
+PROGRAM Main
+VAR
+    counter : INT := 0;
+END_VAR
+counter := counter + 1;
+IF counter > 5 THEN
+    counter := 0;
+END_IF;
+END_PROGRAM
+"#;
+
+    let path = "/tmp/main.st";
+    let module = bytecode_module_from_source_with_path(source, path).unwrap();
+    let pous = module.disassemble().expect("disassemble");
+    let main = pous
+        .iter()
+        .find(|pou| pou.kind == PouKind::Program && pou.name.eq_ignore_ascii_case("Main"))
+        .expect("program body");
+
+    let first = &main.instructions[..4];
+    assert_eq!(
+        first
+            .iter()
+            .map(|instruction| instruction.mnemonic)
+            .collect::<Vec<_>>(),
+        vec!["LOAD_REF", "CONST", "ADD", "STORE_REF"]
+    );
+    assert!(first[0].statement_start);
+    assert!(!first[1].statement_start);
+    assert_eq!(first[0].bytes.len(), 5);
+    for instruction in first {
+        let source = instruction.source.as_ref().expect("source mapping");
+        assert_eq!(source.path, path);
+        assert_eq!(source.line, 6);
+    }
+
+    let jump = main
+        .instructions
+        .iter()
+        .find(|instruction| instruction.mnemonic == "JMP_FALSE")
+        .expect("IF condition jump");
+    assert_eq!(jump.source.as_ref().map(|source| source.line), Some(7));
+    let target = u32::from_str_radix(jump.operands.trim_start_matches("0x"), 16).unwrap();
+    assert!(
+        main.instructions
+            .iter()
+            .any(|instruction| instruction.offset == target)
+            || target
+                == main
+                    .instructions
+                    .last()
+                    .map_or(0, |last| last.offset + last.bytes.len() as u32)
+    );
+    let reset = main
+        .instructions
+        .iter()
+        .find(|instruction| {
+            instruction.statement_start
+                && instruction.source.as_ref().map(|source| source.line) == Some(8)
+        })
+        .expect("nested statement start");
+    assert!(reset.offset > jump.offset);
+}
+
 #[test]
 fn encoder_emits_param_defaults() {
     let source = r#"
@@ -573,6 +640,51 @@ END_CONFIGURATION
         .any(|retain| retain.ref_idx == entry.ref_idx));
 }
 
+#[test]
+fn encoder_emits_aggregate_init_constants() {
+    let source = r#"
+TYPE
+    Position : STRUCT
+        x : REAL;
+        y : REAL;
+    END_STRUCT;
+END_TYPE
+
+PROGRAM Main
+END_PROGRAM
+
+CONFIGURATION C
+RESOURCE R ON CPU
+VAR_GLOBAL
+    g_home : Position := (x := 1.0, y := 2.0);
+    g_path : ARRAY[1..3] OF Position := [(x := 1.0), 2((y := 3.0))];
+END_VAR
+TASK T (INTERVAL := T#10ms, PRIORITY := 0);
+PROGRAM Main WITH T : Main;
+END_RESOURCE
+END_CONFIGURATION
+"#;
+
+    let module = bytecode_module_from_source(source).unwrap();
+    module.validate().unwrap();
+    let strings = match module.section(SectionId::StringTable) {
+        Some(SectionData::StringTable(table)) => table,
+        other => panic!("expected STRING_TABLE, got {other:?}"),
+    };
+    let var_meta = match module.section(SectionId::VarMeta) {
+        Some(SectionData::VarMeta(meta)) => meta,
+        other => panic!("expected VAR_META, got {other:?}"),
+    };
+    for name in ["g_home", "g_path"] {
+        let entry = var_meta
+            .entries
+            .iter()
+            .find(|entry| lookup_string(strings, entry.name_idx) == name)
+            .unwrap_or_else(|| panic!("{name} meta"));
+        assert!(entry.init_const_idx.is_some(), "{name} init constant");
+    }
+}
+
 #[test]
 fn encoder_emits_local_refs_for_functions_and_methods() {
     let source = r#"
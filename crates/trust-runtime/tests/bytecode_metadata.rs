@@ -88,6 +88,49 @@ END_CONFIGURATION
     ));
 }
 
+#[test]
+fn build_info_reaches_metadata_snapshot() {
+    let source = r#"
+PROGRAM Main
+VAR
+    counter : INT := 0;
+END_VAR
+counter := counter + 1;
+END_PROGRAM
+
+CONFIGURATION C
+PROGRAM Main : Main;
+END_CONFIGURATION
+"#;
+
+    let mut runtime = TestHarness::from_source(source).unwrap().into_runtime();
+    let mut module = bytecode_helpers::base_module();
+    module.sections.push(Section {
+        id: SectionId::BuildInfo.as_raw(),
+        flags: 0,
+        data: SectionData::BuildInfo(BuildInfo {
+            version: Some(SmolStr::new("2.1.0")),
+            git_hash: Some(SmolStr::new("3f2a9c41d0be")),
+            built_at: None,
+            author: Some(SmolStr::new("Line Team")),
+        }),
+    });
+    let bytes = module.encode().unwrap();
+
+    runtime.apply_bytecode_bytes(&bytes, None).unwrap();
+    let snapshot = runtime.metadata_snapshot();
+    let info = snapshot.build_info().expect("build info");
+    assert_eq!(info.version.as_deref(), Some("2.1.0"));
+    assert_eq!(info.git_hash.as_deref(), Some("3f2a9c41d0be"));
+    assert_eq!(info.built_at, None);
+    assert_eq!(info.author.as_deref(), Some("Line Team"));
+
+    runtime
+        .apply_bytecode_bytes(&bytecode_helpers::base_module().encode().unwrap(), None)
+        .unwrap();
+    assert!(runtime.metadata_snapshot().build_info().is_none());
+}
+
 #[test]
 fn version_gate() {
     let source = r#"
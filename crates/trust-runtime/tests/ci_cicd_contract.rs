@@ -1,3 +1,5 @@
+#![cfg(feature = "services")]
+
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -25,5 +25,11 @@ pub fn make_context<'a>(
         loop_depth: 0,
         pause_requested: false,
         execution_deadline: None,
+        workers: None,
+        bounds: trust_runtime::task::BoundsPolicy::Fault,
+        overflow: trust_runtime::eval::ops::OverflowPolicy::Wrap,
+        subrange: trust_runtime::task::SubrangePolicy::Off,
+        fault_site: None,
+        pou_code: None,
     }
 }
@@ -1,3 +1,5 @@
+#![cfg(feature = "services")]
+
 use std::process::{Command, Output};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
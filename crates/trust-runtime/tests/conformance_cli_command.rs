@@ -1,3 +1,5 @@
+#![cfg(feature = "services")]
+
 use std::path::PathBuf;
 use std::process::Command;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -0,0 +1,72 @@
+use trust_runtime::harness::TestHarness;
+use trust_runtime::value::{
+    format_time_literal, parse_time_literal, DateTimeProfile, Duration, Value,
+};
+
+#[test]
+fn literals_round_trip_through_formatter() {
+    let profile = DateTimeProfile::default();
+    for text in [
+        "T#1h2m3s4ms",
+        "T#-250ms",
+        "T#0s",
+        "LTIME#1d2h3m4s5ms6us7ns",
+        "D#2024-02-29",
+        "LD#1969-12-31",
+        "TOD#23:59:59.5",
+        "LTOD#00:00:00.000000001",
+        "DT#2024-03-01-08:30:00",
+        "LDT#2000-01-01-12:00:00.123456789",
+    ] {
+        let value = parse_time_literal(text, profile).unwrap();
+        assert_eq!(format_time_literal(&value, profile).as_deref(), Some(text));
+    }
+    assert_eq!(format_time_literal(&Value::DInt(1), profile), None);
+    assert!(parse_time_literal("D#2023-02-29", profile).is_err());
+}
+
+#[test]
+fn ltime_literal_is_nanosecond_exact() {
+    let profile = DateTimeProfile::default();
+    assert_eq!(
+        parse_time_literal("LTIME#106751d23h47m16s854ms775us807ns", profile).unwrap(),
+        Value::LTime(Duration::from_nanos(i64::MAX))
+    );
+    assert_eq!(
+        parse_time_literal("LT#1.000000001s", profile).unwrap(),
+        Value::LTime(Duration::from_nanos(1_000_000_001))
+    );
+    assert!(parse_time_literal("LTIME#106752d", profile).is_err());
+}
+
+#[test]
+fn date_time_arithmetic_in_program() {
+    let source = r#"
+PROGRAM Main
+VAR
+    start : DT := DT#2024-02-28-23:00:00;
+    later : DT;
+    day : DATE;
+    clock : TOD;
+    joined : DT;
+    elapsed : TIME;
+END_VAR
+later := ADD_DT_TIME(start, T#2h);
+day := DT_TO_DATE(later);
+clock := DT_TO_TOD(later);
+joined := CONCAT_DATE_TOD(day, clock);
+elapsed := SUB_DT_DT(joined, start);
+END_PROGRAM
+"#;
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+    let profile = DateTimeProfile::default();
+    let literal = |name: &str| format_time_literal(&harness.get_output(name).unwrap(), profile);
+    assert_eq!(literal("later").as_deref(), Some("DT#2024-02-29-01:00:00"));
+    assert_eq!(literal("day").as_deref(), Some("D#2024-02-29"));
+    assert_eq!(literal("clock").as_deref(), Some("TOD#01:00:00"));
+    assert_eq!(literal("joined").as_deref(), Some("DT#2024-02-29-01:00:00"));
+    assert_eq!(literal("elapsed").as_deref(), Some("T#2h"));
+}
@@ -7,7 +7,7 @@ use std::time::Duration;
 use trust_hir::types::TypeRegistry;
 use trust_runtime::debug::{
     offset_to_line_col, resolve_breakpoint_location, DebugBreakpoint, DebugControl, DebugHook,
-    DebugStopReason, HitCondition, LogFragment, SourceLocation,
+    DebugStopReason, FaultBreakpoints, HitCondition, LogFragment, SourceLocation,
 };
 use trust_runtime::eval::expr::Expr;
 use trust_runtime::eval::stmt::{exec_stmt, Stmt};
@@ -15,6 +15,7 @@ use trust_runtime::harness::parse_debug_expression;
 use trust_runtime::harness::TestHarness;
 use trust_runtime::memory::VariableStorage;
 use trust_runtime::value::{DateTimeProfile, Value};
+use trust_runtime::watchdog::FaultPolicy;
 use trust_runtime::Runtime;
 
 #[test]
@@ -412,6 +413,91 @@ END_PROGRAM
     assert_eq!(runtime.storage().get_global("trace"), Some(&Value::Int(12)));
 }
 
+const FAULT_SOURCE: &str = r#"
+CONFIGURATION Conf
+VAR_GLOBAL
+    divisor : INT := 0;
+    ratio : INT := 0;
+END_VAR
+PROGRAM P1 : Prog1;
+END_CONFIGURATION
+
+PROGRAM Prog1
+IF divisor = 0 THEN
+    ratio := 100 / divisor;
+END_IF;
+END_PROGRAM
+"#;
+
+#[test]
+fn fault_breakpoint_stops_on_faulting_statement() {
+    let mut harness = TestHarness::from_source(FAULT_SOURCE).unwrap();
+    let control = harness.runtime_mut().enable_debug();
+    let (stop_tx, stop_rx) = channel();
+    control.set_stop_sender(stop_tx);
+    control.set_fault_breakpoints(FaultBreakpoints {
+        unhandled_faults: true,
+        ..FaultBreakpoints::default()
+    });
+
+    let runtime = std::sync::Arc::new(std::sync::Mutex::new(harness.into_runtime()));
+    let runtime_thread = runtime.clone();
+    let handle = thread::spawn(move || {
+        let mut runtime = runtime_thread.lock().expect("runtime lock poisoned");
+        runtime.execute_cycle()
+    });
+
+    let stop = stop_rx.recv_timeout(Duration::from_millis(500)).unwrap();
+    assert_eq!(stop.reason, DebugStopReason::Exception);
+    assert_eq!(stop.fault.as_deref(), Some("division by zero"));
+    let location = stop.location.expect("fault location");
+    let line = FAULT_SOURCE
+        .lines()
+        .position(|line| line.contains("ratio := 100 / divisor;"))
+        .unwrap() as u32;
+    assert_eq!(offset_to_line_col(FAULT_SOURCE, location.start).0, line);
+    // Enclosing statements do not report the same fault again.
+    assert!(stop_rx.recv_timeout(Duration::from_millis(100)).is_err());
+    assert!(control.is_paused());
+
+    control.continue_run();
+    assert!(handle.join().unwrap().is_err());
+    assert!(stop_rx.try_recv().is_err());
+}
+
+#[test]
+fn unhandled_fault_filter_skips_faults_recovered_by_restart() {
+    let mut harness = TestHarness::from_source(FAULT_SOURCE).unwrap();
+    harness.runtime_mut().set_fault_policy(FaultPolicy::Restart);
+    let control = harness.runtime_mut().enable_debug();
+    let (stop_tx, stop_rx) = channel();
+    control.set_stop_sender(stop_tx);
+    control.set_fault_breakpoints(FaultBreakpoints {
+        unhandled_faults: true,
+        ..FaultBreakpoints::default()
+    });
+
+    let mut runtime = harness.into_runtime();
+    assert!(runtime.execute_cycle().is_err());
+    assert!(stop_rx.try_recv().is_err());
+
+    control.set_fault_breakpoints(FaultBreakpoints {
+        all_faults: true,
+        ..FaultBreakpoints::default()
+    });
+    let runtime = std::sync::Arc::new(std::sync::Mutex::new(runtime));
+    let runtime_thread = runtime.clone();
+    let handle = thread::spawn(move || {
+        let mut runtime = runtime_thread.lock().expect("runtime lock poisoned");
+        runtime.restart(trust_runtime::RestartMode::Warm).unwrap();
+        runtime.execute_cycle()
+    });
+    let stop = stop_rx.recv_timeout(Duration::from_millis(500)).unwrap();
+    assert_eq!(stop.reason, DebugStopReason::Exception);
+    control.continue_run();
+    assert!(handle.join().unwrap().is_err());
+}
+
 #[test]
 fn conditional_breakpoint_skips_when_false() {
     let control = DebugControl::new();
@@ -28,6 +28,86 @@ fn namespace_resolution() {
     harness.assert_eq("count", 1i32);
 }
 
+#[test]
+fn nested_namespace_declarations() {
+    let library = r#"
+        NAMESPACE Company.Motion
+        FUNCTION Speed : INT
+        VAR_INPUT
+            x: INT;
+        END_VAR
+        Speed := x * 2;
+        END_FUNCTION
+        END_NAMESPACE
+    "#;
+
+    let program = r#"
+        USING Company.Motion;
+        PROGRAM Multi
+        VAR
+            qualified: DINT := 0;
+            imported: DINT := 0;
+        END_VAR
+        qualified := Company.Motion.Speed(3);
+        imported := Speed(4);
+        END_PROGRAM
+    "#;
+
+    let mut harness = TestHarness::from_sources(&[library, program]).unwrap();
+    harness.cycle();
+    harness.assert_eq("qualified", 6i32);
+    harness.assert_eq("imported", 8i32);
+}
+
+#[test]
+fn namespace_reexport_facade() {
+    let library = r#"
+        NAMESPACE Impl
+        FUNCTION Scale : INT
+        VAR_INPUT
+            x: INT;
+        END_VAR
+        Scale := x * 10;
+        END_FUNCTION
+
+        FUNCTION_BLOCK Counter
+        VAR_OUTPUT
+            count: INT;
+        END_VAR
+        count := count + 1;
+        END_FUNCTION_BLOCK
+        END_NAMESPACE
+
+        NAMESPACE Facade
+        USING PUBLIC Impl;
+        END_NAMESPACE
+    "#;
+
+    let program = r#"
+        PROGRAM Multi
+        USING Facade;
+        VAR
+            exported: Facade.Counter;
+            other: Counter;
+            qualified: DINT := 0;
+            imported: DINT := 0;
+            counts: DINT := 0;
+        END_VAR
+        exported();
+        other();
+        counts := exported.count + other.count;
+        qualified := Facade.Scale(2);
+        imported := Scale(3);
+        END_PROGRAM
+    "#;
+
+    let mut harness = TestHarness::from_sources(&[library, program]).unwrap();
+    harness.cycle();
+    harness.assert_eq("qualified", 20i32);
+    harness.assert_eq("imported", 30i32);
+    harness.assert_eq("counts", 2i32);
+}
+
 #[test]
 fn duplicate_program_name_errors() {
     let first = r#"
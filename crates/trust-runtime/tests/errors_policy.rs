@@ -1,5 +1,8 @@
 use trust_runtime::error::RuntimeError;
+use trust_runtime::eval::ops::OverflowPolicy;
 use trust_runtime::harness::TestHarness;
+use trust_runtime::task::{BoundsPolicy, BoundsSettings, SubrangePolicy};
+use trust_runtime::value::{ArrayValue, Value};
 
 #[test]
 fn error_policy() {
@@ -16,3 +19,171 @@ END_PROGRAM
     let result = harness.cycle();
     assert!(result.errors.contains(&RuntimeError::DivisionByZero));
 }
+
+#[test]
+fn bounds_fault_reports_statement_location() {
+    let source = r#"
+PROGRAM Main
+VAR
+    arr : ARRAY[1..3] OF INT;
+    i : INT := 4;
+END_VAR
+arr[1] := 1;
+arr[i] := 2;
+END_PROGRAM
+"#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(matches!(
+        result.errors.as_slice(),
+        [RuntimeError::IndexOutOfBounds { index: 4, .. }]
+    ));
+    let location = harness.runtime().last_fault_location().unwrap();
+    assert_eq!(location.pou.as_str(), "Main");
+    assert_eq!((location.line, location.column), (8, 1));
+}
+
+#[test]
+fn null_dereference_reports_statement_location() {
+    let source = r#"
+PROGRAM Main
+VAR
+    r : REF_TO INT;
+    x : INT;
+END_VAR
+x := 1;
+x := r^;
+END_PROGRAM
+"#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert_eq!(result.errors, vec![RuntimeError::NullReference]);
+    let location = harness.runtime().last_fault_location().unwrap();
+    assert_eq!(location.pou.as_str(), "Main");
+    assert_eq!((location.line, location.column), (8, 1));
+}
+
+#[test]
+fn bounds_clamp_policy_saturates_indices_and_positions() {
+    let source = r#"
+PROGRAM Main
+VAR
+    arr : ARRAY[1..3] OF INT;
+    i : INT := 7;
+    j : INT := 0;
+    x : INT;
+    s : STRING := 'abc';
+    m : STRING;
+END_VAR
+arr[i] := INT#5;
+x := arr[j];
+m := MID(s, 2, 0);
+END_PROGRAM
+"#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    harness.runtime_mut().set_bounds_settings(BoundsSettings {
+        default: BoundsPolicy::Clamp,
+        ..BoundsSettings::default()
+    });
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    harness.assert_eq("x", 0i16);
+    harness.assert_eq("m", Value::String("ab".into()));
+    assert_eq!(
+        harness.get_output("arr"),
+        Some(Value::Array(ArrayValue {
+            elements: vec![Value::Int(0), Value::Int(0), Value::Int(5)],
+            dimensions: vec![(1, 3)],
+        }))
+    );
+}
+
+#[test]
+fn subrange_policy_checks_assignments() {
+    let source = r#"
+TYPE
+    Percent : INT (0..100);
+END_TYPE
+
+PROGRAM Main
+VAR
+    raw : INT := 150;
+    level : Percent;
+    low : INT (-10..10);
+END_VAR
+level := raw;
+low := -raw;
+END_PROGRAM
+"#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    harness.assert_eq("level", 150i16);
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    harness
+        .runtime_mut()
+        .set_subrange_policy(SubrangePolicy::Clamp);
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    harness.assert_eq("level", 100i16);
+    harness.assert_eq("low", -10i16);
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    harness
+        .runtime_mut()
+        .set_subrange_policy(SubrangePolicy::Fault);
+    let result = harness.cycle();
+    assert!(result.errors.contains(&RuntimeError::SubrangeViolation {
+        value: 150,
+        lower: 0,
+        upper: 100,
+    }));
+}
+
+#[test]
+fn overflow_policy_applies_when_storing_into_narrower_integers() {
+    let source = r#"
+PROGRAM Main
+VAR
+    c : INT := 32767;
+    arr : ARRAY[1..2] OF SINT := [127, 0];
+END_VAR
+c := c + 1;
+arr[1] := arr[1] + 1;
+END_PROGRAM
+"#;
+    let arr = |first: i8| {
+        Some(Value::Array(ArrayValue {
+            elements: vec![Value::SInt(first), Value::SInt(0)],
+            dimensions: vec![(1, 2)],
+        }))
+    };
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    harness.assert_eq("c", i16::MIN);
+    assert_eq!(harness.get_output("arr"), arr(i8::MIN));
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    harness
+        .runtime_mut()
+        .set_overflow_policy(OverflowPolicy::Saturate);
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    harness.assert_eq("c", i16::MAX);
+    assert_eq!(harness.get_output("arr"), arr(i8::MAX));
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    harness
+        .runtime_mut()
+        .set_overflow_policy(OverflowPolicy::Fault);
+    let result = harness.cycle();
+    assert_eq!(result.errors, vec![RuntimeError::Overflow]);
+    harness.assert_eq("c", i16::MAX);
+}
@@ -1,7 +1,9 @@
 mod common;
 
 use trust_hir::types::TypeRegistry;
-use trust_runtime::eval::{eval_expr, expr::Expr, ops::BinaryOp};
+use trust_runtime::error::RuntimeError;
+use trust_runtime::eval::ops::{BinaryOp, OverflowPolicy, UnaryOp};
+use trust_runtime::eval::{eval_expr, expr::Expr};
 use trust_runtime::memory::VariableStorage;
 use trust_runtime::value::Value;
 
@@ -93,3 +95,38 @@ fn string_and_bool_compare() {
     };
     assert_eq!(eval_expr(&mut ctx, &expr).unwrap(), Value::Bool(true));
 }
+
+#[test]
+fn integer_overflow_policy() {
+    let mut storage = VariableStorage::new();
+    let registry = TypeRegistry::new();
+    let mut ctx = common::make_context(&mut storage, &registry);
+    let add = Expr::Binary {
+        op: BinaryOp::Add,
+        left: Box::new(Expr::Literal(Value::Int(i16::MAX))),
+        right: Box::new(Expr::Literal(Value::Int(1))),
+    };
+    let sub = Expr::Binary {
+        op: BinaryOp::Sub,
+        left: Box::new(Expr::Literal(Value::USInt(0))),
+        right: Box::new(Expr::Literal(Value::USInt(1))),
+    };
+    let neg = Expr::Unary {
+        op: UnaryOp::Neg,
+        expr: Box::new(Expr::Literal(Value::SInt(i8::MIN))),
+    };
+
+    assert_eq!(eval_expr(&mut ctx, &add).unwrap(), Value::Int(i16::MIN));
+    assert_eq!(eval_expr(&mut ctx, &sub).unwrap(), Value::USInt(u8::MAX));
+    assert_eq!(eval_expr(&mut ctx, &neg).unwrap(), Value::SInt(i8::MIN));
+
+    ctx.overflow = OverflowPolicy::Saturate;
+    assert_eq!(eval_expr(&mut ctx, &add).unwrap(), Value::Int(i16::MAX));
+    assert_eq!(eval_expr(&mut ctx, &sub).unwrap(), Value::USInt(0));
+    assert_eq!(eval_expr(&mut ctx, &neg).unwrap(), Value::SInt(i8::MAX));
+
+    ctx.overflow = OverflowPolicy::Fault;
+    assert_eq!(eval_expr(&mut ctx, &add), Err(RuntimeError::Overflow));
+    assert_eq!(eval_expr(&mut ctx, &sub), Err(RuntimeError::Overflow));
+    assert_eq!(eval_expr(&mut ctx, &neg), Err(RuntimeError::Overflow));
+}
@@ -0,0 +1,177 @@
+use std::env;
+use std::time::{Duration as StdDuration, Instant};
+
+use smol_str::SmolStr;
+use trust_runtime::harness::TestHarness;
+use trust_runtime::value::Value;
+
+const READER: &str = r#"
+    PROGRAM Test
+    VAR
+        reader : FILE_READ_ASYNC;
+        start : BOOL;
+        path : STRING;
+        busy : BOOL;
+        done : BOOL;
+        failed : BOOL;
+        status : STRING;
+        text : STRING;
+    END_VAR
+    reader(EXECUTE := start, PATH := path, DONE => done, BUSY => busy,
+           ERROR => failed, STATUS => status, DATA => text);
+    END_PROGRAM
+"#;
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    let mut path = env::temp_dir();
+    let pid = std::process::id();
+    path.push(format!("trust_runtime_fb_async_{pid}_{name}.txt"));
+    path
+}
+
+fn text(value: &str) -> Value {
+    Value::String(SmolStr::new(value))
+}
+
+fn cycle_until_idle(harness: &mut TestHarness) {
+    let deadline = Instant::now() + StdDuration::from_secs(5);
+    loop {
+        harness.cycle();
+        if harness.get_output("busy") == Some(Value::Bool(false)) {
+            return;
+        }
+        assert!(Instant::now() < deadline, "async job did not finish");
+        std::thread::sleep(StdDuration::from_millis(2));
+    }
+}
+
+#[test]
+fn file_read_async_is_busy_until_a_later_cycle() {
+    let path = temp_path("read");
+    std::fs::write(&path, "hello async").unwrap();
+    let mut harness = TestHarness::from_source(READER).unwrap();
+    harness.set_input("path", text(path.to_str().unwrap()));
+    harness.set_input("start", true);
+
+    // The scan never waits: the result cannot be visible in the submitting cycle.
+    harness.cycle();
+    harness.assert_eq("busy", Value::Bool(true));
+    harness.assert_eq("done", Value::Bool(false));
+
+    cycle_until_idle(&mut harness);
+    harness.assert_eq("done", Value::Bool(true));
+    harness.assert_eq("failed", Value::Bool(false));
+    harness.assert_eq("text", text("hello async"));
+
+    // DONE holds while EXECUTE stays TRUE and clears once it drops.
+    harness.cycle();
+    harness.assert_eq("done", Value::Bool(true));
+    harness.set_input("start", false);
+    harness.cycle();
+    harness.assert_eq("done", Value::Bool(false));
+    harness.assert_eq("text", text("hello async"));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn file_read_async_reports_missing_file_as_error() {
+    let path = temp_path("missing");
+    let _ = std::fs::remove_file(&path);
+    let mut harness = TestHarness::from_source(READER).unwrap();
+    harness.set_input("path", text(path.to_str().unwrap()));
+    harness.set_input("start", true);
+
+    cycle_until_idle(&mut harness);
+    harness.assert_eq("done", Value::Bool(false));
+    harness.assert_eq("failed", Value::Bool(true));
+    match harness.get_output("status") {
+        Some(Value::String(status)) => assert!(!status.is_empty()),
+        other => panic!("expected status text, got {other:?}"),
+    }
+}
+
+#[test]
+fn file_write_async_writes_in_the_background() {
+    let source = r#"
+        PROGRAM Test
+        VAR
+            writer : FILE_WRITE_ASYNC;
+            start : BOOL;
+            path : STRING;
+            busy : BOOL;
+            done : BOOL;
+        END_VAR
+        writer(EXECUTE := start, PATH := path, DATA := 'line', APPEND := TRUE,
+               DONE => done, BUSY => busy);
+        END_PROGRAM
+    "#;
+    let path = temp_path("write");
+    std::fs::write(&path, "first ").unwrap();
+    let mut harness = TestHarness::from_source(source).unwrap();
+    harness.set_input("path", text(path.to_str().unwrap()));
+    harness.set_input("start", true);
+
+    cycle_until_idle(&mut harness);
+    harness.assert_eq("done", Value::Bool(true));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "first line");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn file_handles_write_then_read_lines() {
+    let source = r#"
+        PROGRAM Test
+        VAR
+            opener : FILE_OPEN;
+            writer : FILE_WRITE;
+            reader : FILE_READ;
+            closer : FILE_CLOSE;
+            do_open : BOOL;
+            do_write : BOOL;
+            do_read : BOOL;
+            do_close : BOOL;
+            write_mode : BOOL;
+            path : STRING;
+            handle : UDINT;
+            line : STRING;
+            eof : BOOL;
+            busy : BOOL;
+            failed : BOOL;
+        END_VAR
+        opener(EXECUTE := do_open, PATH := path, WRITE := write_mode, HANDLE => handle);
+        writer(EXECUTE := do_write, HANDLE := handle, DATA := 'a;1$N');
+        reader(EXECUTE := do_read, HANDLE := handle, DATA => line, EOF => eof);
+        closer(EXECUTE := do_close, HANDLE := handle);
+        busy := opener.BUSY OR writer.BUSY OR reader.BUSY OR closer.BUSY;
+        failed := opener.ERROR OR writer.ERROR OR reader.ERROR OR closer.ERROR;
+        END_PROGRAM
+    "#;
+    let pulse = |harness: &mut TestHarness, input: &str| {
+        harness.set_input(input, true);
+        cycle_until_idle(harness);
+        harness.assert_eq("failed", Value::Bool(false));
+        harness.set_input(input, false);
+        harness.cycle();
+    };
+    let path = temp_path("handles");
+    let mut harness = TestHarness::from_source(source).unwrap();
+    harness.set_input("path", text(path.to_str().unwrap()));
+    harness.set_input("write_mode", true);
+
+    pulse(&mut harness, "do_open");
+    assert_ne!(harness.get_output("handle"), Some(Value::UDInt(0)));
+    pulse(&mut harness, "do_write");
+    pulse(&mut harness, "do_close");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "a;1\n");
+
+    harness.set_input("write_mode", false);
+    pulse(&mut harness, "do_open");
+    pulse(&mut harness, "do_read");
+    harness.assert_eq("line", text("a;1"));
+    harness.assert_eq("eof", Value::Bool(false));
+    pulse(&mut harness, "do_read");
+    harness.assert_eq("line", text(""));
+    harness.assert_eq("eof", Value::Bool(true));
+    pulse(&mut harness, "do_close");
+    let _ = std::fs::remove_file(path);
+}
@@ -50,3 +50,81 @@ fn sr_rs() {
     harness.assert_eq("q_sr", Value::Bool(true));
     harness.assert_eq("q_rs", Value::Bool(false));
 }
+
+#[test]
+fn sema_grants_one_claimant_at_a_time() {
+    let source = r#"
+        PROGRAM Test
+        VAR
+            sema : SEMA;
+            want_a : BOOL;
+            done_a : BOOL;
+            want_b : BOOL;
+            busy_a : BOOL;
+            busy_b : BOOL;
+        END_VAR
+        sema(CLAIM := want_a, RELEASE := done_a, BUSY => busy_a);
+        sema(CLAIM := want_b, RELEASE := FALSE, BUSY => busy_b);
+        END_PROGRAM
+    "#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+
+    // Both claim in the same scan: the first caller gets it.
+    harness.set_input("want_a", Value::Bool(true));
+    harness.set_input("want_b", Value::Bool(true));
+    harness.cycle();
+    harness.assert_eq("busy_a", Value::Bool(false));
+    harness.assert_eq("busy_b", Value::Bool(true));
+
+    harness.set_input("want_a", Value::Bool(false));
+    harness.cycle();
+    harness.assert_eq("busy_a", Value::Bool(true));
+    harness.assert_eq("busy_b", Value::Bool(true));
+
+    // RELEASE frees it for the next claimant within the same scan.
+    harness.set_input("done_a", Value::Bool(true));
+    harness.cycle();
+    harness.assert_eq("busy_a", Value::Bool(true));
+    harness.assert_eq("busy_b", Value::Bool(false));
+
+    harness.set_input("done_a", Value::Bool(false));
+    harness.set_input("want_b", Value::Bool(false));
+    harness.cycle();
+    harness.assert_eq("busy_a", Value::Bool(true));
+    harness.assert_eq("busy_b", Value::Bool(true));
+}
+
+#[test]
+fn sema_claim_wins_over_simultaneous_release() {
+    let source = r#"
+        PROGRAM Test
+        VAR
+            sema : SEMA;
+            claim_in : BOOL;
+            release_in : BOOL;
+            busy : BOOL;
+        END_VAR
+        sema(CLAIM := claim_in, RELEASE := release_in, BUSY => busy);
+        END_PROGRAM
+    "#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+
+    // CLAIM and RELEASE in the same scan: the semaphore ends up claimed.
+    harness.set_input("claim_in", Value::Bool(true));
+    harness.set_input("release_in", Value::Bool(true));
+    harness.cycle();
+    harness.assert_eq("busy", Value::Bool(false));
+
+    harness.cycle();
+    harness.assert_eq("busy", Value::Bool(true));
+
+    // RELEASE alone frees it.
+    harness.set_input("claim_in", Value::Bool(false));
+    harness.cycle();
+    harness.assert_eq("busy", Value::Bool(true));
+
+    harness.cycle();
+    harness.assert_eq("busy", Value::Bool(false));
+}
@@ -0,0 +1,107 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::time::{Duration as StdDuration, Instant};
+
+use smol_str::SmolStr;
+use trust_runtime::harness::TestHarness;
+use trust_runtime::value::Value;
+
+const CLIENT: &str = r#"
+    PROGRAM Test
+    VAR
+        link : TCP_STREAM_CLIENT;
+        enable : BOOL;
+        host : STRING;
+        send : BOOL;
+        tx : ARRAY[0..3] OF BYTE;
+        rx : ARRAY[0..7] OF BYTE;
+        connected : BOOL;
+        rx_len : UINT;
+        failed : BOOL;
+        status : STRING;
+    END_VAR
+    tx[0] := BYTE#16#01;
+    tx[1] := BYTE#16#02;
+    tx[2] := BYTE#16#03;
+    tx[3] := BYTE#16#04;
+    link(ENABLE := enable, HOST := host, SEND := send, TX_LEN := 3,
+         TX_BUFFER := tx, RX_BUFFER := rx, CONNECTED => connected,
+         RX_LEN => rx_len, ERROR => failed, STATUS => status);
+    END_PROGRAM
+"#;
+
+fn text(value: &str) -> Value {
+    Value::String(SmolStr::new(value))
+}
+
+fn cycle_until(harness: &mut TestHarness, name: &str, expected: impl Fn(&Value) -> bool) {
+    let deadline = Instant::now() + StdDuration::from_secs(5);
+    loop {
+        let result = harness.cycle();
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        if harness.get_output(name).as_ref().is_some_and(&expected) {
+            return;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "{name} did not reach the expected value"
+        );
+        std::thread::sleep(StdDuration::from_millis(5));
+    }
+}
+
+#[test]
+fn tcp_stream_client_exchanges_byte_buffers() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let mut harness = TestHarness::from_source(CLIENT).unwrap();
+    harness.set_input("host", text(&address));
+    harness.set_input("enable", true);
+
+    cycle_until(&mut harness, "connected", |value| {
+        *value == Value::Bool(true)
+    });
+    let (mut peer, _) = listener.accept().unwrap();
+    peer.set_read_timeout(Some(StdDuration::from_secs(5)))
+        .unwrap();
+
+    // A rising SEND edge transmits the first TX_LEN bytes.
+    harness.set_input("send", true);
+    harness.cycle();
+    let mut sent = [0_u8; 3];
+    peer.read_exact(&mut sent).unwrap();
+    assert_eq!(sent, [1, 2, 3]);
+
+    peer.write_all(&[9, 8]).unwrap();
+    cycle_until(&mut harness, "rx_len", |value| *value != Value::UInt(0));
+    harness.assert_eq("rx_len", Value::UInt(2));
+    let Some(Value::Array(rx)) = harness.get_output("rx") else {
+        panic!("expected array");
+    };
+    assert_eq!(rx.elements[..2], [Value::Byte(9), Value::Byte(8)]);
+
+    // Nothing new arrived, so the next call reports zero bytes.
+    harness.cycle();
+    harness.assert_eq("rx_len", Value::UInt(0));
+
+    // Dropping ENABLE closes the connection.
+    harness.set_input("enable", false);
+    harness.cycle();
+    harness.assert_eq("connected", Value::Bool(false));
+    let mut buf = [0_u8; 1];
+    assert_eq!(peer.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn tcp_stream_client_reports_connect_failures() {
+    let mut harness = TestHarness::from_source(CLIENT).unwrap();
+    harness.set_input("host", text("not an address"));
+    harness.set_input("enable", true);
+
+    cycle_until(&mut harness, "failed", |value| *value == Value::Bool(true));
+    harness.assert_eq("connected", Value::Bool(false));
+    match harness.get_output("status") {
+        Some(Value::String(status)) => assert!(status.contains("not an address")),
+        other => panic!("expected status text, got {other:?}"),
+    }
+}
@@ -0,0 +1,211 @@
+use std::sync::{Arc, Mutex};
+
+use trust_runtime::harness::TestHarness;
+use trust_runtime::metrics::RuntimeMetrics;
+use trust_runtime::scheduler::{ManualClock, ResourceRunner};
+use trust_runtime::value::{Duration, Value};
+
+// Every standard block, stimulated only from the scan count, so the same scan
+// must produce the same outputs whatever the clock scale.
+const PLANT: &str = r#"
+    PROGRAM Test
+    VAR
+        ton : TON; tof : TOF; tp : TP; blink : TON;
+        up : CTU; down : CTD; both : CTUD;
+        rise : R_TRIG; fall : F_TRIG;
+        rs : RS; sr : SR; sema : SEMA;
+        scan : INT;
+        pv : INT := 5;
+        start : BOOL;
+        tick : BOOL;
+        ton_q : BOOL; ton_et : TIME;
+        tof_q : BOOL; tof_et : TIME;
+        tp_q : BOOL; tp_et : TIME;
+        up_q : BOOL; up_cv : INT;
+        down_q : BOOL; down_cv : INT;
+        both_cv : INT;
+        rises : INT; falls : INT;
+        rs_q : BOOL; sr_q : BOOL; busy : BOOL;
+    END_VAR
+    scan := scan + INT#1;
+    start := scan >= 5 AND scan < 40;
+    ton(IN := start, PT := T#100ms, Q => ton_q, ET => ton_et);
+    tof(IN := start, PT := T#100ms, Q => tof_q, ET => tof_et);
+    tp(IN := start, PT := T#50ms, Q => tp_q, ET => tp_et);
+    blink(IN := NOT tick, PT := T#30ms, Q => tick);
+    up(CU := tick, R := scan = 60, PV := pv, Q => up_q, CV => up_cv);
+    down(CD := tick, LD := scan = 1, PV := pv, Q => down_q, CV => down_cv);
+    both(CU := tick, CD := start, R := FALSE, LD := FALSE, PV := pv, CV => both_cv);
+    rise(CLK := start);
+    fall(CLK := start);
+    IF rise.Q THEN
+        rises := rises + INT#1;
+    END_IF;
+    IF fall.Q THEN
+        falls := falls + INT#1;
+    END_IF;
+    rs(S := ton_q, R1 := tp_q, Q1 => rs_q);
+    sr(S1 := ton_q, R := NOT start, Q1 => sr_q);
+    sema(CLAIM := start, RELEASE := NOT tof_q, BUSY => busy);
+    END_PROGRAM
+"#;
+
+const OUTPUTS: &[&str] = &[
+    "ton_q", "ton_et", "tof_q", "tof_et", "tp_q", "tp_et", "tick", "up_q", "up_cv", "down_q",
+    "down_cv", "both_cv", "rises", "falls", "rs_q", "sr_q", "busy",
+];
+
+const CYCLE_MS: i64 = 10;
+const CYCLES: usize = 80;
+
+/// Run the plant on a resource whose clock runs `scale` times faster than the
+/// manual wall clock, and record the outputs after every scan.
+fn trace(scale: u32) -> Vec<Vec<Value>> {
+    let runtime = TestHarness::from_source(PLANT).unwrap().into_runtime();
+    let clock = ManualClock::new();
+    let cycle = Duration::from_millis(CYCLE_MS);
+    let mut runner = ResourceRunner::new(runtime, clock.clone(), cycle).with_time_scale(scale);
+    let program = match runner.runtime().storage().get_global("Test") {
+        Some(Value::Instance(id)) => *id,
+        other => panic!("expected program instance, got {other:?}"),
+    };
+    // One scan per cycle interval of resource time, i.e. interval / scale of wall time.
+    let wall_step = Duration::from_nanos(cycle.as_nanos() / i64::from(scale));
+    (0..CYCLES)
+        .map(|_| {
+            runner.tick().unwrap();
+            clock.advance(wall_step);
+            OUTPUTS
+                .iter()
+                .map(|name| {
+                    runner
+                        .runtime()
+                        .storage()
+                        .get_instance_var(program, name)
+                        .cloned()
+                        .unwrap_or_else(|| panic!("missing output {name}"))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn column(trace: &[Vec<Value>], name: &str) -> Vec<Value> {
+    let index = OUTPUTS.iter().position(|output| *output == name).unwrap();
+    trace.iter().map(|row| row[index].clone()).collect()
+}
+
+fn first_cycle(trace: &[Vec<Value>], name: &str, value: &Value) -> usize {
+    column(trace, name)
+        .iter()
+        .position(|observed| observed == value)
+        .unwrap_or_else(|| panic!("{name} never became {value:?}"))
+}
+
+#[test]
+fn standard_blocks_match_across_time_scales() {
+    let reference = trace(1);
+    for scale in [2, 10, 100, 1000] {
+        let scaled = trace(scale);
+        for (cycle, (expected, observed)) in reference.iter().zip(&scaled).enumerate() {
+            assert_eq!(observed, expected, "scale {scale}, cycle {cycle}");
+        }
+    }
+}
+
+#[test]
+fn standard_blocks_follow_resource_time() {
+    let trace = trace(10);
+    let on = Value::Bool(true);
+    // START rises on the fifth scan (index 4). Each scan is 10 ms of resource
+    // time, so a 100 ms TON elapses nine scans after the edge scan.
+    let start = 4;
+    assert_eq!(first_cycle(&trace, "ton_q", &on), start + 9);
+    assert_eq!(
+        trace[start + 9][1],
+        Value::Time(Duration::from_millis(100)),
+        "ET stops at PT"
+    );
+    assert_eq!(first_cycle(&trace, "tp_q", &on), start);
+    assert_eq!(column(&trace, "tp_q")[start + 5], Value::Bool(false));
+
+    // R_TRIG fires once for the single START pulse; F_TRIG also fires on its
+    // first call because CLK starts FALSE (IEC 61131-3 Table 44).
+    assert_eq!(column(&trace, "rises").last(), Some(&Value::Int(1)));
+    assert_eq!(column(&trace, "falls")[0], Value::Int(1));
+    assert_eq!(column(&trace, "falls").last(), Some(&Value::Int(2)));
+
+    // The blinker produces one single-scan TICK every four scans, and CTU counts
+    // each of them exactly once up to its reset on scan 60.
+    let ticks = column(&trace, "tick")
+        .iter()
+        .take(59)
+        .filter(|tick| **tick == on)
+        .count();
+    assert_eq!(column(&trace, "up_cv")[58], Value::Int(ticks as i16));
+    assert_eq!(column(&trace, "up_cv")[59], Value::Int(0));
+}
+
+#[test]
+fn timers_tolerate_clock_jumps() {
+    let source = r#"
+        PROGRAM Test
+        VAR
+            ton : TON; tp : TP;
+            run : BOOL;
+            ton_q : BOOL; ton_et : TIME;
+            tp_q : BOOL; tp_et : TIME;
+        END_VAR
+        ton(IN := run, PT := T#100ms, Q => ton_q, ET => ton_et);
+        tp(IN := run, PT := T#100ms, Q => tp_q, ET => tp_et);
+        END_PROGRAM
+    "#;
+    let mut harness = TestHarness::from_source(source).unwrap();
+    harness.set_input("run", true);
+    harness.cycle();
+    harness.advance_time(Duration::from_millis(10));
+    harness.cycle();
+    harness.assert_eq("ton_et", Value::Time(Duration::from_millis(10)));
+
+    // A clock that steps backwards adds no time instead of a negative delta.
+    harness
+        .runtime_mut()
+        .set_current_time(Duration::from_millis(1));
+    harness.cycle();
+    harness.assert_eq("ton_q", Value::Bool(false));
+    harness.assert_eq("ton_et", Value::Time(Duration::from_millis(10)));
+    harness.assert_eq("tp_q", Value::Bool(true));
+    harness.assert_eq("tp_et", Value::Time(Duration::from_millis(10)));
+
+    // A huge jump forward completes the timers without overflowing ET.
+    harness.advance_time(Duration::from_nanos(i64::MAX));
+    harness.cycle();
+    harness.assert_eq("ton_q", Value::Bool(true));
+    harness.assert_eq("ton_et", Value::Time(Duration::from_millis(100)));
+    harness.assert_eq("tp_q", Value::Bool(false));
+    harness.advance_time(Duration::from_millis(10));
+    harness.cycle();
+    harness.assert_eq("ton_et", Value::Time(Duration::from_millis(100)));
+}
+
+#[test]
+fn stamps_and_uptime_follow_scaled_time() {
+    let mut runtime = TestHarness::from_source(PLANT).unwrap().into_runtime();
+    let metrics = Arc::new(Mutex::new(RuntimeMetrics::new()));
+    runtime.set_metrics_sink(metrics.clone());
+    let time_base = runtime.time_base();
+    let clock = ManualClock::new();
+    let mut runner = ResourceRunner::new(runtime, clock.clone(), Duration::from_millis(CYCLE_MS))
+        .with_time_scale(10);
+    assert_eq!(time_base.time_scale(), 10);
+
+    runner.tick().unwrap();
+    let first = time_base.now_unix_ms();
+    for _ in 0..9 {
+        clock.advance(Duration::from_millis(1));
+        runner.tick().unwrap();
+    }
+    // 9 ms of wall time at scale 10 is 90 ms of resource time.
+    assert_eq!(time_base.now_unix_ms(), first + 90);
+    assert_eq!(metrics.lock().unwrap().uptime_ms(), 90);
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "services")]
+
 use std::collections::{BTreeSet, VecDeque};
 use std::fs;
 use std::io::ErrorKind;
@@ -14,8 +16,8 @@ use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use indexmap::IndexMap;
 use serde_json::json;
 use smol_str::SmolStr;
-use trust_runtime::config::{ControlMode, WebAuthMode, WebConfig};
-use trust_runtime::control::{ControlState, HmiRuntimeDescriptor, SourceRegistry};
+use trust_runtime::config::{ControlMode, ServerLimits, WebAuthMode, WebConfig};
+use trust_runtime::control::{ControlSessions, ControlState, HmiRuntimeDescriptor, SourceRegistry};
 use trust_runtime::debug::DebugVariableHandles;
 use trust_runtime::error::RuntimeError;
 use trust_runtime::harness::TestHarness;
@@ -121,10 +123,14 @@ fn hmi_control_state_with_root(source: &str, project_root: Option<PathBuf>) -> A
         io_health: Arc::new(Mutex::new(Vec::new())),
         debug_enabled: Arc::new(AtomicBool::new(true)),
         debug_variables: Arc::new(Mutex::new(DebugVariableHandles::new())),
+        sessions: Arc::new(ControlSessions::default()),
         hmi_live: Arc::new(Mutex::new(trust_runtime::hmi::HmiLiveState::default())),
         hmi_descriptor,
         historian: None,
+        event_store: None,
         pairing: None,
+        mesh: None,
+        redundancy: None,
     })
 }
 
@@ -164,6 +170,7 @@ fn start_test_server(state: Arc<ControlState>) -> String {
         listen: SmolStr::new(listen.clone()),
         auth: WebAuthMode::Local,
         tls: false,
+        limits: ServerLimits::default(),
     };
     let _server =
         start_web_server(&config, state, None, None, None, None).expect("start web server");
@@ -1390,6 +1397,84 @@ console.log('ok');
     run_node_hmi_script(&js_path, script, "process transform");
 }
 
+#[test]
+fn hmi_trend_chart_zoom_pan_and_cursor_readout() {
+    let js_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/web/ui/hmi.js");
+    let script = r#"
+const fs = require('fs');
+const vm = require('vm');
+const assert = require('assert');
+
+const sourcePath = process.env.HMI_JS_PATH;
+const source = fs.readFileSync(sourcePath, 'utf8')
+  + '\n;globalThis.__hmi_test__ = { trendView, zoomTrendWindow, panTrendWindow, trendReadoutAt, trendChartSvg };';
+const noop = () => {};
+const context = {
+  console,
+  URLSearchParams,
+  window: {
+    location: { protocol: 'http:', host: '127.0.0.1:7777', search: '' },
+    addEventListener: noop,
+    setInterval: () => 1,
+    clearInterval: noop,
+    setTimeout: () => 1,
+    clearTimeout: noop,
+    innerWidth: 1280,
+  },
+  document: {
+    getElementById: () => null,
+    body: { classList: { add: noop, remove: noop } },
+    documentElement: { style: { setProperty: noop } },
+  },
+  fetch: async () => { throw new Error('unexpected fetch'); },
+  DOMParser: class {
+    parseFromString() {
+      return { querySelector: () => null, documentElement: null };
+    }
+  },
+};
+vm.createContext(context);
+vm.runInContext(source, context, { filename: 'hmi.js' });
+
+const test = context.__hmi_test__;
+const now = 1_000_000;
+const live = test.trendView({ timestamp_ms: now, until_ms: now, duration_ms: 60_000 });
+assert.deepStrictEqual({ ...live }, { start: 940_000, end: now, duration: 60_000 });
+
+const zoomedLive = test.zoomTrendWindow(live, 0.5, 1, now);
+assert.deepStrictEqual({ ...zoomedLive }, { untilMs: null, durationMs: 30_000 });
+const zoomedMiddle = test.zoomTrendWindow(live, 0.5, 0.5, now);
+assert.deepStrictEqual({ ...zoomedMiddle }, { untilMs: 985_000, durationMs: 30_000 });
+const clamped = test.zoomTrendWindow(live, 0.001, 1, now);
+assert.strictEqual(clamped.durationMs, 5_000);
+
+const panned = test.panTrendWindow(live, 0.25, now);
+assert.deepStrictEqual({ ...panned }, { untilMs: 985_000, durationMs: 60_000 });
+const backToLive = test.panTrendWindow(test.trendView({ timestamp_ms: now, until_ms: 985_000, duration_ms: 60_000 }), -0.5, now);
+assert.strictEqual(backToLive.untilMs, null);
+
+const series = [
+  { id: 'speed', label: 'Speed', unit: 'rpm', points: [
+    { ts_ms: 950_000, value: 10, min: 10, max: 10 },
+    { ts_ms: 990_000, value: 20, min: 18, max: 22 },
+  ] },
+  { id: 'temp', label: 'Temp', points: [{ ts_ms: 960_000, value: 70, min: 70, max: 70 }] },
+];
+const readout = test.trendReadoutAt(series, 985_000);
+assert.strictEqual(readout.length, 2);
+assert.strictEqual(readout[0].value, 20);
+assert.strictEqual(readout[0].unit, 'rpm');
+assert.strictEqual(readout[1].value, 70);
+
+const chart = test.trendChartSvg(series.map((entry, index) => ({ entry, color: `c${index}` })), live, 985_000);
+assert.strictEqual(chart.svg.match(/<polyline/g).length, 2);
+assert(chart.svg.includes('trend-cursor'));
+assert(chart.range.min < 10 && chart.range.max > 70);
+console.log('ok');
+"#;
+    run_node_hmi_script(&js_path, script, "trend chart");
+}
+
 #[test]
 fn hmi_process_renderer_handles_malformed_svg_without_crash() {
     let js_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/web/ui/hmi.js");
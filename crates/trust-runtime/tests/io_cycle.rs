@@ -144,3 +144,69 @@ fn io_snapshots_emitted_at_cycle_bounds() {
         other => panic!("unexpected output snapshot after exec: {other:?}"),
     }
 }
+
+#[test]
+fn standby_skips_tasks_and_mirrors_synced_outputs() {
+    let mut runtime = Runtime::new();
+    runtime.storage_mut().set_global("in", Value::Bool(false));
+    runtime.storage_mut().set_global("out", Value::Bool(false));
+    runtime
+        .storage_mut()
+        .set_global("trigger", Value::Bool(false));
+
+    let program = ProgramDef {
+        name: "P".into(),
+        vars: Vec::new(),
+        temps: Vec::new(),
+        using: Vec::new(),
+        body: vec![Stmt::Assign {
+            target: LValue::Name("out".into()),
+            value: Expr::Name("in".into()),
+            location: None,
+        }],
+    };
+    runtime.register_program(program).unwrap();
+
+    let input_addr = IoAddress::parse("%IX0.0").unwrap();
+    let output_addr = IoAddress::parse("%QX0.1").unwrap();
+    runtime.io_mut().bind("in", input_addr.clone());
+    runtime.io_mut().bind("out", output_addr.clone());
+
+    runtime.register_task(TaskConfig {
+        name: "T".into(),
+        interval: Duration::ZERO,
+        single: Some("trigger".into()),
+        priority: 0,
+        programs: vec!["P".into()],
+        fb_instances: Vec::new(),
+    });
+
+    runtime.set_standby(true);
+    runtime
+        .io_mut()
+        .write(&input_addr, Value::Bool(true))
+        .unwrap();
+    runtime
+        .storage_mut()
+        .set_global("trigger", Value::Bool(true));
+    runtime.execute_cycle().unwrap();
+    // The program did not run; the output image still follows storage.
+    assert_eq!(runtime.io().read(&output_addr).unwrap(), Value::Bool(false));
+
+    let snapshot = runtime.redundancy_snapshot(&[]);
+    assert!(snapshot.values().contains_key("out"));
+    let mut synced = trust_runtime::RetainSnapshot::default();
+    synced.insert("out", Value::Bool(true));
+    runtime.apply_redundancy_snapshot(&synced);
+    runtime.execute_cycle().unwrap();
+    assert_eq!(runtime.io().read(&output_addr).unwrap(), Value::Bool(true));
+
+    // Once active, the program drives the output again.
+    runtime.set_standby(false);
+    runtime
+        .io_mut()
+        .write(&input_addr, Value::Bool(false))
+        .unwrap();
+    runtime.execute_cycle().unwrap();
+    assert_eq!(runtime.io().read(&output_addr).unwrap(), Value::Bool(false));
+}
@@ -0,0 +1,128 @@
+use trust_runtime::error::RuntimeError;
+use trust_runtime::harness::TestHarness;
+use trust_runtime::io::{IoAddress, IoChannel, IoDriver};
+use trust_runtime::Runtime;
+
+/// Driver that only advertises a channel map.
+struct MappedDriver {
+    channels: Vec<(&'static str, &'static str)>,
+}
+
+impl IoDriver for MappedDriver {
+    fn read_inputs(&mut self, _inputs: &mut [u8]) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+
+    fn write_outputs(&mut self, _outputs: &[u8]) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+
+    fn channels(&self) -> Vec<IoChannel> {
+        self.channels
+            .iter()
+            .map(|(address, label)| IoChannel {
+                address: IoAddress::parse(address).unwrap(),
+                label: (*label).into(),
+            })
+            .collect()
+    }
+}
+
+fn runtime(source: &str) -> Runtime {
+    TestHarness::from_source(source).unwrap().into_runtime()
+}
+
+fn conflict(runtime: &Runtime) -> String {
+    match runtime.validate_io_map() {
+        Err(RuntimeError::IoMapConflict(message)) => message.to_string(),
+        other => panic!("expected an I/O map conflict, got {other:?}"),
+    }
+}
+
+#[test]
+fn overlapping_outputs_are_rejected() {
+    let runtime = runtime(
+        r#"
+PROGRAM Main
+VAR
+    speed AT %QW0 : INT;
+    lamp AT %QX1.0 : BOOL;
+END_VAR
+END_PROGRAM
+"#,
+    );
+
+    let message = conflict(&runtime);
+    assert!(message.contains("%QW0"), "{message}");
+    assert!(message.contains("%QX1.0"), "{message}");
+}
+
+#[test]
+fn struct_fields_and_neighbours_are_accepted() {
+    let runtime = runtime(
+        r#"
+TYPE Rel :
+STRUCT
+    a AT %B0 : BYTE;
+    b AT %B1 : BYTE;
+END_STRUCT
+END_TYPE
+
+PROGRAM Main
+VAR
+    rel AT %QB0 : Rel;
+    lamp AT %QX2.0 : BOOL;
+    horn AT %QX2.1 : BOOL;
+    start AT %IX0.0 : BOOL;
+    start_copy AT %IX0.0 : BOOL;
+END_VAR
+END_PROGRAM
+"#,
+    );
+
+    runtime.validate_io_map().unwrap();
+}
+
+#[test]
+fn channel_size_mismatch_is_rejected() {
+    let mut runtime = runtime(
+        r#"
+PROGRAM Main
+VAR
+    speed AT %QW0 : INT;
+END_VAR
+END_PROGRAM
+"#,
+    );
+    runtime.add_io_driver(
+        "gpio",
+        Box::new(MappedDriver {
+            channels: vec![("%QX0.3", "line 17")],
+        }),
+    );
+
+    let message = conflict(&runtime);
+    assert!(message.contains("is a WORD"), "{message}");
+    assert!(message.contains("gpio line 17"), "{message}");
+}
+
+#[test]
+fn duplicate_input_channels_are_rejected() {
+    let mut runtime = Runtime::new();
+    runtime.io_mut().resize(1, 0, 0);
+    runtime.add_io_driver(
+        "gpio",
+        Box::new(MappedDriver {
+            channels: vec![("%IX0.0", "line 4")],
+        }),
+    );
+    runtime.add_io_driver(
+        "modbus",
+        Box::new(MappedDriver {
+            channels: vec![("%IB0", "input 0")],
+        }),
+    );
+
+    let message = conflict(&runtime);
+    assert!(message.contains("both fill input"), "{message}");
+}
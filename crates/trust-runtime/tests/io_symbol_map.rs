@@ -0,0 +1,120 @@
+use trust_runtime::error::RuntimeError;
+use trust_runtime::harness::TestHarness;
+use trust_runtime::io::{IoAddress, IoSymbol, IoSymbolMap};
+use trust_runtime::value::Value;
+
+const SOURCE: &str = r#"
+CONFIGURATION Conf
+VAR_GLOBAL
+    StartButton : BOOL;
+    MotorRun : BOOL;
+END_VAR
+PROGRAM P1 : Main;
+END_CONFIGURATION
+
+PROGRAM Main
+VAR_EXTERNAL
+    StartButton : BOOL;
+    MotorRun : BOOL;
+END_VAR
+VAR
+    Speed : INT;
+END_VAR
+MotorRun := StartButton;
+Speed := 1200;
+END_PROGRAM
+"#;
+
+fn symbols(entries: &[(&str, &str)]) -> IoSymbolMap {
+    IoSymbolMap {
+        symbols: entries
+            .iter()
+            .map(|(name, address)| IoSymbol {
+                name: (*name).into(),
+                address: IoAddress::parse(address).unwrap(),
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn symbols_bind_globals_and_program_variables() {
+    let mut harness = TestHarness::from_source(SOURCE).unwrap();
+    harness
+        .runtime_mut()
+        .bind_io_symbols(&symbols(&[
+            ("StartButton", "%IX0.0"),
+            ("MotorRun", "%QX0.0"),
+            ("P1.Speed", "%QW2"),
+        ]))
+        .unwrap();
+
+    harness
+        .set_direct_input("%IX0.0", Value::Bool(true))
+        .unwrap();
+    harness.cycle();
+
+    assert_eq!(
+        harness.get_direct_output("%QX0.0").unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        harness.get_direct_output("%QW2").unwrap(),
+        Value::Word(1200)
+    );
+}
+
+#[test]
+fn remapping_moves_a_symbol_without_recompiling() {
+    let mut harness = TestHarness::from_source(SOURCE).unwrap();
+    harness
+        .runtime_mut()
+        .bind_io_symbols(&symbols(&[
+            ("StartButton", "%IX1.3"),
+            ("MotorRun", "%QX4.1"),
+        ]))
+        .unwrap();
+
+    harness
+        .set_direct_input("%IX1.3", Value::Bool(true))
+        .unwrap();
+    harness.cycle();
+
+    assert_eq!(
+        harness.get_direct_output("%QX4.1").unwrap(),
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn unbound_symbols_are_reported_together() {
+    let mut harness = TestHarness::from_source(SOURCE).unwrap();
+    let err = harness
+        .runtime_mut()
+        .bind_io_symbols(&symbols(&[
+            ("StartButton", "%IX0.0"),
+            ("StopButton", "%IX0.1"),
+            ("P1.Missing", "%QX0.1"),
+        ]))
+        .unwrap_err();
+
+    match err {
+        RuntimeError::UnboundIoSymbols(names) => assert_eq!(names, "StopButton, P1.Missing"),
+        other => panic!("expected unbound symbols, got {other:?}"),
+    }
+    assert!(harness.runtime().io().bindings().is_empty());
+}
+
+#[test]
+fn symbol_size_must_match_variable() {
+    let mut harness = TestHarness::from_source(SOURCE).unwrap();
+    let err = harness
+        .runtime_mut()
+        .bind_io_symbols(&symbols(&[("P1.Speed", "%QX0.0")]))
+        .unwrap_err();
+
+    assert!(
+        err.to_string().contains("needs a word"),
+        "unexpected error: {err}"
+    );
+}
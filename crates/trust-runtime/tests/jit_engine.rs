@@ -0,0 +1,164 @@
+#![cfg(feature = "jit")]
+
+use trust_runtime::eval::ops::OverflowPolicy;
+use trust_runtime::eval::vm::JitSettings;
+use trust_runtime::harness::TestHarness;
+use trust_runtime::value::Value;
+
+const HOT_LOOP: &str = r#"
+    PROGRAM Main
+    VAR
+        i : INT;
+        acc : LREAL := 0.0;
+        state : DINT := 1;
+        hits : DINT := 0;
+        selector : DINT := 0;
+        branch : DINT := 0;
+        toggle : BOOL := FALSE;
+    END_VAR
+    FOR i := 1 TO 50 DO
+        acc := acc * 0.5 + 2.0 / (1.0 + i);
+        state := (state * 1103 + 12345) MOD 65536;
+        IF state > 32768 AND NOT toggle THEN
+            hits := hits + 1;
+        ELSIF state < 1024 OR toggle THEN
+            hits := hits - 1;
+        END_IF;
+    END_FOR;
+    selector := selector + 1;
+    CASE selector OF
+        1: branch := 10;
+        2..4: branch := 20 + hits;
+    ELSE
+        branch := -1;
+    END_CASE;
+    toggle := NOT toggle;
+    END_PROGRAM
+"#;
+
+const OVERFLOW: &str = r#"
+    PROGRAM Main
+    VAR
+        count : DINT := 2147483640;
+    END_VAR
+    count := count + 1;
+    END_PROGRAM
+"#;
+
+const CALLS: &str = r#"
+    FUNCTION Scale : DINT
+    VAR_INPUT
+        value : DINT;
+        factor : DINT;
+    END_VAR
+    VAR
+        k : DINT;
+    END_VAR
+    Scale := 0;
+    FOR k := 1 TO factor DO
+        Scale := Scale + value;
+    END_FOR;
+    END_FUNCTION
+
+    FUNCTION_BLOCK Filter
+    VAR_INPUT
+        input : LREAL;
+    END_VAR
+    VAR_OUTPUT
+        output : LREAL;
+    END_VAR
+    VAR
+        samples : DINT;
+    END_VAR
+    output := output * 0.75 + input * 0.25;
+    samples := samples + 1;
+
+    METHOD PUBLIC Reset : BOOL
+    output := 0.0;
+    samples := 0;
+    Reset := TRUE;
+    END_METHOD
+    END_FUNCTION_BLOCK
+
+    PROGRAM Main
+    VAR
+        i : DINT;
+        total : DINT := 0;
+        smooth : Filter;
+        level : LREAL;
+        resets : DINT := 0;
+        values : ARRAY[1..4] OF DINT;
+        trail : STRING := '';
+    END_VAR
+    FOR i := 1 TO 4 DO
+        total := (total + Scale(value := i, factor := 3)) MOD 100000;
+        values[i] := total;
+        smooth(input := TO_LREAL(i));
+        IF i = 3 THEN
+            trail := CONCAT(trail, 'x');
+        END_IF;
+    END_FOR;
+    level := smooth.output;
+    IF total MOD 7 = 0 AND smooth.Reset() THEN
+        resets := resets + 1;
+    END_IF;
+    END_PROGRAM
+"#;
+
+fn jit_settings() -> JitSettings {
+    JitSettings {
+        enabled: true,
+        warmup_cycles: 2,
+        hot_programs: 1,
+    }
+}
+
+#[test]
+fn jit_matches_vm_after_warmup() {
+    let mut vm = TestHarness::from_source(HOT_LOOP).unwrap();
+    let mut jit = TestHarness::from_source(HOT_LOOP).unwrap();
+    jit.runtime_mut().set_jit_settings(jit_settings());
+    for result in vm.run_cycles(6).into_iter().chain(jit.run_cycles(6)) {
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+    }
+    assert_eq!(jit.runtime_mut().jit_programs().len(), 1);
+    for name in ["i", "acc", "state", "hits", "selector", "branch", "toggle"] {
+        assert_eq!(jit.get_output(name), vm.get_output(name), "{name}");
+    }
+    assert_eq!(jit.get_output("branch"), Some(Value::DInt(-1)));
+}
+
+#[test]
+fn jit_falls_back_to_vm_for_runtime_errors() {
+    let mut harness = TestHarness::from_source(OVERFLOW).unwrap();
+    harness
+        .runtime_mut()
+        .set_overflow_policy(OverflowPolicy::Fault);
+    harness.runtime_mut().set_jit_settings(jit_settings());
+    for result in harness.run_cycles(7) {
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+    }
+    assert_eq!(harness.runtime_mut().jit_programs().len(), 1);
+    assert_eq!(harness.get_output("count"), Some(Value::DInt(2147483647)));
+    let result = harness.cycle();
+    assert!(!result.errors.is_empty());
+    assert_eq!(harness.get_output("count"), Some(Value::DInt(2147483647)));
+}
+
+#[test]
+fn jit_compiles_called_pous_and_calls_back_into_the_vm() {
+    let mut vm = TestHarness::from_source(CALLS).unwrap();
+    let mut jit = TestHarness::from_source(CALLS).unwrap();
+    jit.runtime_mut().set_jit_settings(jit_settings());
+    for result in vm.run_cycles(12).into_iter().chain(jit.run_cycles(12)) {
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+    }
+    assert_eq!(jit.runtime_mut().jit_programs().len(), 1);
+    let pous = jit.runtime_mut().jit_pous();
+    assert!(pous.iter().any(|pou| pou == "SCALE"), "{pous:?}");
+    assert!(pous.iter().any(|pou| pou == "FILTER"), "{pous:?}");
+    for name in ["i", "total", "level", "resets", "values", "trail"] {
+        assert_eq!(jit.get_output(name), vm.get_output(name), "{name}");
+    }
+    assert_ne!(jit.get_output("resets"), Some(Value::DInt(0)));
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "services")]
+
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use trust_runtime::stlib::LibraryArchive;
+
+fn unique_temp_dir(prefix: &str) -> std::path::PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before unix epoch")
+        .as_nanos();
+    std::env::temp_dir().join(format!(
+        "trust-runtime-{prefix}-{}-{nanos}",
+        std::process::id()
+    ))
+}
+
+#[test]
+fn library_pack_writes_archive_without_source_bodies() {
+    let project = unique_temp_dir("library-pack");
+    std::fs::create_dir_all(project.join("src")).expect("create source root");
+    std::fs::write(
+        project.join("trust-lsp.toml"),
+        "[package]\nname = \"Valves\"\nversion = \"0.3.0\"\n",
+    )
+    .expect("write manifest");
+    std::fs::write(
+        project.join("src/valve.st"),
+        r#"
+// Opens a valve after a debounce delay.
+FUNCTION_BLOCK ValveCtrl
+VAR_INPUT
+    Open : BOOL;
+END_VAR
+VAR_OUTPUT
+    IsOpen : BOOL;
+END_VAR
+VAR
+    ProprietaryCounter : INT;
+END_VAR
+ProprietaryCounter := ProprietaryCounter + 1;
+IsOpen := Open AND ProprietaryCounter > 3;
+END_FUNCTION_BLOCK
+"#,
+    )
+    .expect("write library source");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_trust-runtime"))
+        .args(["library", "pack", "--project"])
+        .arg(&project)
+        .arg("--json")
+        .output()
+        .expect("run trust-runtime library pack");
+    assert!(
+        output.status.success(),
+        "expected library pack success, stderr was:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("parse pack JSON");
+    assert_eq!(report["manifest"]["name"], "Valves");
+    assert_eq!(report["manifest"]["version"], "0.3.0");
+
+    let archive_path = project.join("Valves.stlib");
+    let archive = LibraryArchive::read(&archive_path).expect("read archive");
+    assert!(archive.interface.contains("FUNCTION_BLOCK ValveCtrl"));
+    assert!(archive.interface.contains("IsOpen : BOOL;"));
+    assert!(!archive.interface.contains("ProprietaryCounter"));
+    assert_eq!(
+        archive.manifest.docs.get("ValveCtrl").map(String::as_str),
+        Some("Opens a valve after a debounce delay.")
+    );
+    assert!(archive.bytecode_module().is_ok());
+
+    let inspect = Command::new(env!("CARGO_BIN_EXE_trust-runtime"))
+        .args(["library", "inspect", "--archive"])
+        .arg(&archive_path)
+        .arg("--json")
+        .output()
+        .expect("run trust-runtime library inspect");
+    assert!(inspect.status.success());
+    let value: serde_json::Value =
+        serde_json::from_slice(&inspect.stdout).expect("parse inspect JSON");
+    assert_eq!(value["manifest"]["pous"][0]["kind"], "function_block");
+
+    std::fs::remove_dir_all(project).ok();
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "services")]
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn unique_temp_dir(prefix: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before unix epoch")
+        .as_nanos();
+    std::env::temp_dir().join(format!(
+        "trust-runtime-{prefix}-{}-{nanos}",
+        std::process::id()
+    ))
+}
+
+fn toml_string(path: &std::path::Path, table: &str, key: &str) -> String {
+    let text = std::fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("read {}: {err}", path.display()));
+    let value: toml::Value = toml::from_str(&text).expect("parse toml");
+    table
+        .split('.')
+        .fold(&value, |value, part| &value[part])
+        .get(key)
+        .and_then(toml::Value::as_str)
+        .unwrap_or_else(|| panic!("{table}.{key} missing in {}", path.display()))
+        .to_string()
+}
+
+#[test]
+fn new_templates_produce_valid_projects() {
+    for template in ["counter", "traffic-light", "modbus-io"] {
+        let project = unique_temp_dir(&format!("new-{template}"));
+        let output = Command::new(env!("CARGO_BIN_EXE_trust-runtime"))
+            .arg("new")
+            .arg(&project)
+            .args(["--template", template, "--no-git"])
+            .output()
+            .expect("run trust-runtime new");
+        assert!(
+            output.status.success(),
+            "expected new --template {template} success, stderr was:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        for file in [
+            "src/main.st",
+            "src/configuration.st",
+            "runtime.toml",
+            "io.toml",
+            "trust-lsp.toml",
+            ".vscode/launch.json",
+            "program.stbc",
+        ] {
+            assert!(project.join(file).is_file(), "{template}: missing {file}");
+        }
+        assert_eq!(
+            toml_string(
+                &project.join("trust-lsp.toml"),
+                "runtime",
+                "control_endpoint"
+            ),
+            toml_string(&project.join("runtime.toml"), "runtime.control", "endpoint"),
+            "{template}: editor and runtime control endpoints differ"
+        );
+
+        let validate = Command::new(env!("CARGO_BIN_EXE_trust-runtime"))
+            .arg("validate")
+            .arg("--project")
+            .arg(&project)
+            .output()
+            .expect("run trust-runtime validate");
+        assert!(
+            validate.status.success(),
+            "{template}: validate failed, stderr was:\n{}",
+            String::from_utf8_lossy(&validate.stderr)
+        );
+
+        let _ = std::fs::remove_dir_all(&project);
+    }
+}
+
+#[test]
+fn new_rejects_non_empty_directory_without_force() {
+    let project = unique_temp_dir("new-non-empty");
+    std::fs::create_dir_all(&project).expect("create project dir");
+    std::fs::write(project.join("notes.txt"), "keep me").expect("write marker");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_trust-runtime"))
+        .arg("new")
+        .arg(&project)
+        .arg("--no-git")
+        .output()
+        .expect("run trust-runtime new");
+    assert!(!output.status.success(), "expected non-empty dir failure");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--force"), "stderr was:\n{stderr}");
+    assert!(!project.join("runtime.toml").exists());
+
+    let _ = std::fs::remove_dir_all(&project);
+}
@@ -0,0 +1,126 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration as StdDuration, Instant};
+
+use trust_runtime::error::RuntimeError;
+use trust_runtime::harness::TestHarness;
+use trust_runtime::io::{IoAddress, IoDriver, OutputSafeState, SafeOutput};
+use trust_runtime::scheduler::{ResourceRunner, ResourceState, StdClock};
+use trust_runtime::value::{Duration, Value};
+use trust_runtime::Runtime;
+
+/// Driver that remembers the last output image it was given.
+struct RecordingDriver {
+    outputs: Arc<Mutex<Vec<u8>>>,
+}
+
+impl IoDriver for RecordingDriver {
+    fn read_inputs(&mut self, _inputs: &mut [u8]) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+
+    fn write_outputs(&mut self, outputs: &[u8]) -> Result<(), RuntimeError> {
+        *self.outputs.lock().unwrap() = outputs.to_vec();
+        Ok(())
+    }
+}
+
+fn address(text: &str) -> IoAddress {
+    IoAddress::parse(text).unwrap()
+}
+
+fn wait_until(what: &str, condition: impl Fn() -> bool) {
+    let start = Instant::now();
+    while !condition() {
+        assert!(
+            start.elapsed() < StdDuration::from_secs(1),
+            "{what} timed out"
+        );
+        std::thread::sleep(StdDuration::from_millis(1));
+    }
+}
+
+#[test]
+fn watchdog_trip_applies_output_policy() {
+    let outputs = Arc::new(Mutex::new(Vec::new()));
+    let mut runtime = Runtime::new();
+    runtime.io_mut().resize(0, 3, 0);
+    runtime.add_io_driver(
+        "recorder",
+        Box::new(RecordingDriver {
+            outputs: outputs.clone(),
+        }),
+    );
+    runtime
+        .io_mut()
+        .outputs_mut()
+        .copy_from_slice(&[0b11, 7, 9]);
+    runtime.set_output_safe_state(OutputSafeState {
+        default: SafeOutput::Zero,
+        outputs: vec![
+            (address("%QX0.1"), SafeOutput::Hold),
+            (address("%QB1"), SafeOutput::Value(Value::Byte(42))),
+        ],
+    });
+
+    let _ = runtime.watchdog_timeout();
+
+    // %QX0.0 and %QB2 fall back to zero, %QX0.1 holds, %QB1 takes its value.
+    assert_eq!(*outputs.lock().unwrap(), vec![0b10, 42, 0]);
+    assert_eq!(runtime.io().outputs(), &[0b10, 42, 0]);
+}
+
+#[test]
+fn hold_policy_leaves_outputs_alone() {
+    let mut runtime = Runtime::new();
+    runtime.io_mut().resize(0, 1, 0);
+    runtime.io_mut().outputs_mut()[0] = 5;
+
+    let _ = runtime.watchdog_timeout();
+
+    assert_eq!(runtime.io().outputs(), &[5]);
+}
+
+#[test]
+fn pause_drives_outputs_to_safe_state_until_resume() {
+    let source = r#"
+PROGRAM Main
+VAR
+    motor AT %QX0.0 : BOOL;
+END_VAR
+motor := TRUE;
+END_PROGRAM
+"#;
+
+    let outputs = Arc::new(Mutex::new(Vec::new()));
+    let mut runtime = TestHarness::from_source(source).unwrap().into_runtime();
+    runtime.add_io_driver(
+        "recorder",
+        Box::new(RecordingDriver {
+            outputs: outputs.clone(),
+        }),
+    );
+    runtime.set_output_safe_state(OutputSafeState {
+        default: SafeOutput::Zero,
+        outputs: Vec::new(),
+    });
+    let runner = ResourceRunner::new(runtime, StdClock::new(), Duration::from_millis(1));
+    let mut handle = runner.spawn("safe-state-resource").unwrap();
+    let control = handle.control();
+    let motor = || outputs.lock().unwrap().first().copied();
+
+    wait_until("first cycle", || motor() == Some(1));
+    control.pause().unwrap();
+    wait_until("pause", || handle.state() == ResourceState::Paused);
+    assert_eq!(motor(), Some(0));
+
+    // A stepped cycle drives the program outputs, then returns to the safe state.
+    let stepped = control.step_cycles(1).unwrap();
+    stepped.recv_timeout(StdDuration::from_secs(1)).unwrap();
+    assert_eq!(motor(), Some(0));
+
+    control.resume().unwrap();
+    wait_until("resume", || motor() == Some(1));
+
+    handle.stop();
+    handle.join().unwrap();
+}
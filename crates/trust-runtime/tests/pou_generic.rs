@@ -0,0 +1,63 @@
+use trust_runtime::harness::TestHarness;
+use trust_runtime::value::Value;
+
+#[test]
+fn generic_function_runs_for_each_instantiation() {
+    let source = r#"
+FUNCTION MAX2 : ANY_NUM
+VAR_INPUT
+    a : ANY_NUM;
+    b : ANY_NUM;
+END_VAR
+IF a > b THEN
+    MAX2 := a;
+ELSE
+    MAX2 := b;
+END_IF
+END_FUNCTION
+
+PROGRAM Main
+VAR
+    i1 : INT := 4;
+    i2 : INT := 9;
+    d1 : DINT := -70000;
+    d2 : DINT := -80000;
+    r1 : REAL := 1.5;
+    r2 : REAL := 0.25;
+    imax : INT;
+    dmax : DINT;
+    rmax : REAL;
+END_VAR
+imax := MAX2(i1, i2);
+dmax := MAX2(d1, d2);
+rmax := MAX2(r1, r2);
+END_PROGRAM
+"#;
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(harness.get_output("imax"), Some(Value::Int(9)));
+    assert_eq!(harness.get_output("dmax"), Some(Value::DInt(-70000)));
+    assert_eq!(harness.get_output("rmax"), Some(Value::Real(1.5)));
+}
+
+#[test]
+fn generic_function_rejects_unsupported_instantiation() {
+    let source = r#"
+FUNCTION MAX2 : ANY_NUM
+VAR_INPUT
+    a : ANY_NUM;
+    b : ANY_NUM;
+END_VAR
+MAX2 := a;
+END_FUNCTION
+
+PROGRAM Main
+VAR
+    flag : BOOL;
+END_VAR
+flag := MAX2(flag, flag);
+END_PROGRAM
+"#;
+    assert!(TestHarness::from_source(source).is_err());
+}
@@ -43,3 +43,65 @@ END_PROGRAM
     harness.assert_eq("out1", 1i16);
     harness.assert_eq("out2", 3i16);
 }
+
+#[test]
+fn interface_default_method_and_extends() {
+    let source = r#"
+INTERFACE ICounter
+METHOD Inc : INT
+VAR_INPUT
+    delta : INT;
+END_VAR
+END_METHOD
+
+METHOD Twice : INT
+VAR_INPUT
+    delta : INT;
+END_VAR
+Twice := THIS.Inc(delta) + THIS.Inc(delta);
+END_METHOD
+END_INTERFACE
+
+INTERFACE IResettable EXTENDS ICounter
+METHOD Reset : INT
+Reset := INT#-1;
+END_METHOD
+END_INTERFACE
+
+CLASS Counter IMPLEMENTS IResettable
+VAR PUBLIC
+    value : INT := INT#0;
+END_VAR
+METHOD PUBLIC Inc : INT
+VAR_INPUT
+    delta : INT;
+END_VAR
+value := value + delta;
+Inc := value;
+END_METHOD
+
+METHOD PUBLIC Reset : INT
+value := INT#0;
+Reset := value;
+END_METHOD
+END_CLASS
+
+PROGRAM Main
+VAR
+    c : Counter;
+    i : IResettable;
+    out1 : INT := INT#0;
+    out2 : INT := INT#0;
+END_VAR
+i := c;
+out1 := i.Twice(INT#2);
+out2 := c.Reset();
+END_PROGRAM
+"#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    harness.assert_eq("out1", 6i16);
+    harness.assert_eq("out2", 0i16);
+}
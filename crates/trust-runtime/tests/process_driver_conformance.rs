@@ -0,0 +1,207 @@
+use std::io::{BufRead, BufReader, Cursor, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use smol_str::SmolStr;
+use trust_runtime::error::RuntimeError;
+use trust_runtime::io::{
+    serve_process_driver_io, IoAddress, IoChannel, IoDriver, IoDriverHealth, IoDriverRegistry,
+    ProcessDriverSpec, ProcessIoDriver, PROCESS_DRIVER_PROTOCOL_VERSION,
+};
+
+struct EchoDriver {
+    last_outputs: Vec<u8>,
+    health: IoDriverHealth,
+}
+
+impl IoDriver for EchoDriver {
+    fn read_inputs(&mut self, inputs: &mut [u8]) -> Result<(), RuntimeError> {
+        let len = inputs.len().min(self.last_outputs.len());
+        inputs[..len].copy_from_slice(&self.last_outputs[..len]);
+        Ok(())
+    }
+
+    fn write_outputs(&mut self, outputs: &[u8]) -> Result<(), RuntimeError> {
+        self.last_outputs = outputs.to_vec();
+        Ok(())
+    }
+
+    fn health(&self) -> IoDriverHealth {
+        self.health.clone()
+    }
+
+    fn channels(&self) -> Vec<IoChannel> {
+        vec![IoChannel {
+            address: IoAddress::parse("%IB0").expect("address"),
+            label: SmolStr::new("echo byte 0"),
+        }]
+    }
+}
+
+fn echo(params: &toml::Value) -> Result<Box<dyn IoDriver>, RuntimeError> {
+    let health = match params.get("health").and_then(toml::Value::as_str) {
+        Some("degraded") => IoDriverHealth::Degraded {
+            error: "link noisy".into(),
+        },
+        Some("faulted") => IoDriverHealth::Faulted {
+            error: "link down".into(),
+        },
+        _ => IoDriverHealth::Ok,
+    };
+    if params.get("fail").is_some() {
+        return Err(RuntimeError::InvalidConfig("bad vendor param".into()));
+    }
+    Ok(Box::new(EchoDriver {
+        last_outputs: Vec::new(),
+        health,
+    }))
+}
+
+fn stream_pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let host = TcpStream::connect(listener.local_addr().expect("addr")).expect("connect");
+    let (peer, _) = listener.accept().expect("accept");
+    (host, peer)
+}
+
+fn connect(
+    peer: impl FnOnce(TcpStream) + Send + 'static,
+    params: &str,
+) -> Result<ProcessIoDriver, RuntimeError> {
+    let (host, driver_side) = stream_pair();
+    thread::spawn(move || peer(driver_side));
+    let params: toml::Value = toml::from_str(params).expect("params");
+    ProcessIoDriver::from_io(
+        "vendor",
+        host.try_clone().expect("clone"),
+        host,
+        Duration::from_millis(500),
+        &params,
+    )
+}
+
+fn serve_echo(stream: TcpStream) {
+    let reader = BufReader::new(stream.try_clone().expect("clone"));
+    let _ = serve_process_driver_io(echo, reader, stream);
+}
+
+#[test]
+fn handshake_reports_channels_and_exchanges_images() {
+    let mut driver = connect(serve_echo, "").expect("driver");
+    let channels = driver.channels();
+    assert_eq!(channels.len(), 1);
+    assert_eq!(channels[0].label.as_str(), "echo byte 0");
+    assert_eq!(channels[0].address, IoAddress::parse("%IB0").unwrap());
+
+    driver.write_outputs(&[0x12, 0x34]).expect("write");
+    let mut inputs = [0; 3];
+    driver.read_inputs(&mut inputs).expect("read");
+    assert_eq!(inputs, [0x12, 0x34, 0]);
+    assert_eq!(driver.health(), IoDriverHealth::Ok);
+}
+
+#[test]
+fn init_errors_fail_the_load() {
+    let err = connect(serve_echo, "fail = true").unwrap_err();
+    assert!(err.to_string().contains("bad vendor param"), "{err}");
+}
+
+#[test]
+fn driver_health_is_forwarded() {
+    let mut degraded = connect(serve_echo, "health = \"degraded\"").expect("driver");
+    degraded
+        .read_inputs(&mut [0; 1])
+        .expect("degraded still reads");
+    assert!(matches!(
+        degraded.health(),
+        IoDriverHealth::Degraded { ref error } if error == "link noisy"
+    ));
+
+    let mut faulted = connect(serve_echo, "health = \"faulted\"").expect("driver");
+    let err = faulted.read_inputs(&mut [0; 1]).unwrap_err();
+    assert!(err.to_string().contains("link down"), "{err}");
+    assert!(matches!(faulted.health(), IoDriverHealth::Faulted { .. }));
+}
+
+#[test]
+fn silent_driver_times_out() {
+    let err = connect(
+        |stream| {
+            let mut line = String::new();
+            let _ = BufReader::new(&stream).read_line(&mut line);
+            thread::sleep(Duration::from_secs(2));
+        },
+        "",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("no reply within 500 ms"), "{err}");
+}
+
+#[test]
+fn protocol_version_mismatch_is_rejected() {
+    let err = connect(
+        |mut stream| {
+            let mut line = String::new();
+            let _ = BufReader::new(&stream).read_line(&mut line);
+            let _ = writeln!(stream, r#"{{"id":1,"ok":true,"version":99}}"#);
+            thread::sleep(Duration::from_millis(200));
+        },
+        "",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("protocol version 99"), "{err}");
+}
+
+#[test]
+fn serve_answers_each_request_on_its_own_line() {
+    let requests = [
+        r#"{"id":1,"op":"read","len":2}"#.to_string(),
+        format!(
+            r#"{{"id":2,"op":"init","version":{PROCESS_DRIVER_PROTOCOL_VERSION},"params":{{}}}}"#
+        ),
+        r#"{"id":3,"op":"write","outputs":[7,8]}"#.to_string(),
+        r#"{"id":4,"op":"read","len":2}"#.to_string(),
+        r#"{"id":5,"op":"shutdown"}"#.to_string(),
+        r#"{"id":6,"op":"read","len":2}"#.to_string(),
+    ]
+    .join("\n");
+    let mut output = Vec::new();
+    serve_process_driver_io(echo, Cursor::new(requests), &mut output).expect("serve");
+
+    let replies = String::from_utf8(output)
+        .expect("utf8")
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("json reply"))
+        .collect::<Vec<_>>();
+    assert_eq!(replies.len(), 5, "serve stops after shutdown");
+    assert_eq!(replies[0]["id"], 1);
+    assert_eq!(replies[0]["ok"], false);
+    assert_eq!(replies[1]["version"], PROCESS_DRIVER_PROTOCOL_VERSION);
+    assert_eq!(replies[1]["channels"][0]["address"], "%IB0");
+    assert_eq!(replies[1]["health"]["state"], "ok");
+    assert_eq!(replies[2]["ok"], true);
+    assert_eq!(replies[3]["inputs"], serde_json::json!([7, 8]));
+    assert_eq!(replies[4]["id"], 5);
+}
+
+#[test]
+fn registry_builds_declared_process_drivers() {
+    let mut registry = IoDriverRegistry::default_registry();
+    registry.register_process(ProcessDriverSpec {
+        name: "Acme-IO".into(),
+        command: "/nonexistent/acme-io".into(),
+        args: Vec::new(),
+        timeout: Duration::from_millis(100),
+    });
+    assert!(registry
+        .canonical_driver_names()
+        .contains(&"acme-io".to_string()));
+    let params = toml::Value::Table(toml::map::Map::new());
+    registry.validate("acme-io", &params).expect("validate");
+    let err = registry
+        .build("ACME-IO", &params)
+        .err()
+        .expect("spawn fails");
+    assert!(err.to_string().contains("failed to start"), "{err}");
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "services")]
+
 use std::collections::VecDeque;
 use std::net::TcpListener;
 use std::path::PathBuf;
@@ -8,8 +10,10 @@ use std::time::Duration;
 
 use indexmap::IndexMap;
 use smol_str::SmolStr;
-use trust_runtime::config::{ControlMode, WebAuthMode, WebConfig};
-use trust_runtime::control::{ControlState, HmiRuntimeDescriptor, SourceFile, SourceRegistry};
+use trust_runtime::config::{ControlMode, ServerLimits, WebAuthMode, WebConfig};
+use trust_runtime::control::{
+    ControlSessions, ControlState, HmiRuntimeDescriptor, SourceFile, SourceRegistry,
+};
 use trust_runtime::debug::DebugVariableHandles;
 use trust_runtime::error::RuntimeError;
 use trust_runtime::harness::TestHarness;
@@ -151,10 +155,14 @@ fn control_state(
         io_health: Arc::new(Mutex::new(Vec::new())),
         debug_enabled: Arc::new(AtomicBool::new(true)),
         debug_variables: Arc::new(Mutex::new(DebugVariableHandles::new())),
+        sessions: Arc::new(ControlSessions::default()),
         hmi_live: Arc::new(Mutex::new(trust_runtime::hmi::HmiLiveState::default())),
         hmi_descriptor,
         historian,
+        event_store: None,
         pairing: None,
+        mesh: None,
+        redundancy: None,
     })
 }
 
@@ -173,6 +181,7 @@ fn start_test_server(state: Arc<ControlState>, auth: WebAuthMode) -> String {
         listen: SmolStr::new(listen.clone()),
         auth,
         tls: false,
+        limits: ServerLimits::default(),
     };
     let _server =
         start_web_server(&config, state, None, None, None, None).expect("start web server");
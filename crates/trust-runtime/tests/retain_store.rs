@@ -1,7 +1,7 @@
 use std::env;
 
 use smol_str::SmolStr;
-use trust_runtime::retain::{FileRetainStore, RetainStore};
+use trust_runtime::retain::{check_retain_layout, FileRetainStore, RetainLayoutIssue, RetainStore};
 use trust_runtime::value::{ArrayValue, StructValue, Value};
 use trust_runtime::RetainSnapshot;
 
@@ -52,3 +52,61 @@ fn retain_store_missing_file_returns_default() {
     let snapshot = store.load().expect("load missing retain snapshot");
     assert!(snapshot.values().is_empty());
 }
+
+#[test]
+fn retain_layout_check_reports_type_changes_and_orphans() {
+    let mut program = RetainSnapshot::default();
+    program.insert("Count", Value::DInt(0));
+    program.insert(
+        "Levels",
+        Value::Array(ArrayValue {
+            elements: vec![Value::Real(0.0); 3],
+            dimensions: vec![(1, 3)],
+        }),
+    );
+    program.insert("Fresh", Value::Bool(false));
+
+    let mut stored = RetainSnapshot::default();
+    stored.insert("Count", Value::Int(7));
+    stored.insert(
+        "Levels",
+        Value::Array(ArrayValue {
+            elements: vec![Value::Real(1.5); 3],
+            dimensions: vec![(1, 3)],
+        }),
+    );
+    stored.insert("Removed", Value::Bool(true));
+
+    let issues = check_retain_layout(&program, &stored);
+    assert_eq!(
+        issues,
+        vec![
+            RetainLayoutIssue::TypeChanged {
+                name: SmolStr::new("Count"),
+                stored: "INT".to_string(),
+                expected: "DINT".to_string(),
+            },
+            RetainLayoutIssue::Orphaned {
+                name: SmolStr::new("Removed"),
+            },
+            RetainLayoutIssue::Missing {
+                name: SmolStr::new("Fresh"),
+            },
+        ]
+    );
+    assert_eq!(issues.iter().filter(|issue| issue.is_error()).count(), 1);
+
+    stored.insert(
+        "Levels",
+        Value::Array(ArrayValue {
+            elements: vec![Value::Real(1.5); 2],
+            dimensions: vec![(1, 2)],
+        }),
+    );
+    let issues = check_retain_layout(&program, &stored);
+    assert!(issues.contains(&RetainLayoutIssue::TypeChanged {
+        name: SmolStr::new("Levels"),
+        stored: "ARRAY[1..2] OF REAL".to_string(),
+        expected: "ARRAY[1..3] OF REAL".to_string(),
+    }));
+}
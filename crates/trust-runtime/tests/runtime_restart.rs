@@ -3,7 +3,7 @@ use std::env;
 use trust_runtime::harness::TestHarness;
 use trust_runtime::retain::FileRetainStore;
 use trust_runtime::value::{Duration, Value};
-use trust_runtime::RestartMode;
+use trust_runtime::{RestartMode, RetainPolicy, RetentionRules};
 
 fn temp_path(name: &str) -> std::path::PathBuf {
     let mut path = env::temp_dir();
@@ -65,3 +65,66 @@ END_PROGRAM
 
     let _ = std::fs::remove_file(path);
 }
+
+#[test]
+fn retention_classes_follow_restart_matrix() {
+    let source = r#"
+PROGRAM Main
+VAR RETAIN
+    r : INT := 1;
+END_VAR
+VAR PERSISTENT
+    p : INT := 2;
+END_VAR
+VAR NON_RETAIN
+    n : INT := 3;
+END_VAR
+VAR
+    u : INT := 4;
+END_VAR
+END_PROGRAM
+"#;
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let set_all = |harness: &mut TestHarness| {
+        for name in ["r", "p", "n", "u"] {
+            harness.set_input(name, Value::Int(40));
+        }
+    };
+
+    set_all(&mut harness);
+    harness.restart(RestartMode::Warm).unwrap();
+    assert_eq!(harness.get_output("r"), Some(Value::Int(40)));
+    assert_eq!(harness.get_output("p"), Some(Value::Int(40)));
+    assert_eq!(harness.get_output("n"), Some(Value::Int(3)));
+    assert_eq!(harness.get_output("u"), Some(Value::Int(4)));
+
+    set_all(&mut harness);
+    harness.restart(RestartMode::Cold).unwrap();
+    assert_eq!(harness.get_output("r"), Some(Value::Int(1)));
+    assert_eq!(harness.get_output("p"), Some(Value::Int(40)));
+    assert_eq!(harness.get_output("n"), Some(Value::Int(3)));
+    assert_eq!(harness.get_output("u"), Some(Value::Int(4)));
+
+    harness.runtime_mut().set_retention_rules(RetentionRules {
+        unqualified: RetainPolicy::Retain,
+        persistent_on_cold: false,
+    });
+    set_all(&mut harness);
+    harness.restart(RestartMode::Warm).unwrap();
+    assert_eq!(harness.get_output("u"), Some(Value::Int(40)));
+    assert_eq!(harness.get_output("n"), Some(Value::Int(3)));
+    harness.restart(RestartMode::Cold).unwrap();
+    assert_eq!(harness.get_output("p"), Some(Value::Int(2)));
+    assert_eq!(harness.get_output("u"), Some(Value::Int(4)));
+
+    let preview = harness.runtime().restart_preview(RestartMode::Warm);
+    let kept = preview
+        .iter()
+        .filter(|entry| entry.kept)
+        .map(|entry| entry.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(kept, ["Main.r", "Main.p", "Main.u"]);
+    let unqualified = preview.iter().find(|entry| entry.name == "Main.u").unwrap();
+    assert_eq!(unqualified.declared, RetainPolicy::Unspecified);
+    assert_eq!(unqualified.class, RetainPolicy::Retain);
+}
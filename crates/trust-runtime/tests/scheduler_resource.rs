@@ -3,7 +3,8 @@ use std::time::Duration as StdDuration;
 use trust_runtime::debug::RuntimeEvent;
 use trust_runtime::harness::TestHarness;
 use trust_runtime::scheduler::{ManualClock, ResourceRunner};
-use trust_runtime::value::Duration;
+use trust_runtime::value::{Duration, Value};
+use trust_runtime::watchdog::{ShutdownHook, ShutdownOutcome};
 
 #[test]
 fn resource_runs_in_thread() {
@@ -36,3 +37,123 @@ END_PROGRAM
     handle.stop();
     handle.join().unwrap();
 }
+
+fn program_var(runtime: &trust_runtime::Runtime, program: &str, name: &str) -> Option<Value> {
+    let Some(Value::Instance(id)) = runtime.storage().get_global(program) else {
+        return None;
+    };
+    runtime.storage().get_instance_var(*id, name).cloned()
+}
+
+#[test]
+fn shutdown_hook_runs_only_the_shutdown_program_until_done() {
+    let source = r#"
+PROGRAM Main
+VAR
+    counter : INT := 0;
+END_VAR
+counter := counter + INT#1;
+END_PROGRAM
+
+PROGRAM Park
+VAR
+    steps : INT := 0;
+    Done : BOOL := FALSE;
+END_VAR
+steps := steps + INT#1;
+Done := steps >= 3;
+END_PROGRAM
+"#;
+
+    let mut runtime = TestHarness::from_source(source).unwrap().into_runtime();
+    let debug = runtime.enable_debug();
+    let (tx, rx) = std::sync::mpsc::channel();
+    debug.set_runtime_sender(tx);
+    runtime
+        .set_shutdown_hook(Some(ShutdownHook {
+            program: "park".into(),
+            cycles: 10,
+            timeout: Duration::from_millis(1_000),
+            done: Some("Park.Done".into()),
+        }))
+        .unwrap();
+    assert_eq!(runtime.shutdown_hook().unwrap().program, "Park");
+
+    let mut runner = ResourceRunner::new(runtime, ManualClock::new(), Duration::ZERO);
+    runner.tick().unwrap();
+    runner.tick().unwrap();
+    assert_eq!(
+        program_var(runner.runtime(), "Park", "steps"),
+        Some(Value::Int(0))
+    );
+
+    assert_eq!(runner.run_shutdown_hook(None), Some(ShutdownOutcome::Done));
+    assert!(!runner.runtime().is_shutting_down());
+    assert_eq!(
+        program_var(runner.runtime(), "Park", "steps"),
+        Some(Value::Int(3))
+    );
+    assert_eq!(
+        program_var(runner.runtime(), "Main", "counter"),
+        Some(Value::Int(2))
+    );
+    let shutdown = rx
+        .try_iter()
+        .find(|event| matches!(event, RuntimeEvent::Shutdown { .. }));
+    assert!(matches!(
+        shutdown,
+        Some(RuntimeEvent::Shutdown {
+            cycles: 3,
+            outcome: ShutdownOutcome::Done,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn shutdown_hook_is_cut_off_at_its_timeout() {
+    let source = r#"
+PROGRAM Main
+VAR
+    counter : INT := 0;
+END_VAR
+counter := counter + INT#1;
+END_PROGRAM
+
+PROGRAM Park
+VAR
+    spin : DINT := 0;
+END_VAR
+WHILE TRUE DO
+    spin := spin + 1;
+END_WHILE;
+END_PROGRAM
+"#;
+
+    let mut runtime = TestHarness::from_source(source).unwrap().into_runtime();
+    runtime
+        .set_shutdown_hook(Some(ShutdownHook {
+            program: "Park".into(),
+            cycles: 5,
+            timeout: Duration::from_millis(50),
+            done: None,
+        }))
+        .unwrap();
+    let mut runner = ResourceRunner::new(runtime, ManualClock::new(), Duration::ZERO);
+    assert_eq!(
+        runner.run_shutdown_hook(None),
+        Some(ShutdownOutcome::TimedOut)
+    );
+    assert_eq!(runner.runtime().execution_deadline(), None);
+
+    let mut runtime = TestHarness::from_source(source).unwrap().into_runtime();
+    let err = runtime
+        .set_shutdown_hook(Some(ShutdownHook {
+            program: "Missing".into(),
+            cycles: 5,
+            timeout: Duration::from_millis(50),
+            done: None,
+        }))
+        .unwrap_err();
+    assert!(err.to_string().contains("Missing"));
+}
@@ -2,7 +2,7 @@ use std::time::Duration as StdDuration;
 
 use trust_runtime::error::RuntimeError;
 use trust_runtime::harness::TestHarness;
-use trust_runtime::scheduler::{ManualClock, ResourceRunner, ResourceState};
+use trust_runtime::scheduler::{ManualClock, ResourceRunner, ResourceState, StdClock};
 use trust_runtime::value::Duration;
 
 #[test]
@@ -40,3 +40,47 @@ END_PROGRAM
     handle.stop();
     handle.join().unwrap();
 }
+
+#[test]
+fn paused_resource_runs_requested_cycles() {
+    let source = r#"
+PROGRAM Main
+VAR
+    counter : INT := 0;
+END_VAR
+counter := counter + 1;
+END_PROGRAM
+"#;
+
+    let runtime = TestHarness::from_source(source).unwrap().into_runtime();
+    let runner = ResourceRunner::new(runtime, StdClock::new(), Duration::from_millis(1));
+    let mut handle = runner.spawn("stepped-resource").unwrap();
+    let control = handle.control();
+
+    control.pause().unwrap();
+    let start = std::time::Instant::now();
+    while handle.state() != ResourceState::Paused {
+        assert!(
+            start.elapsed() < StdDuration::from_secs(1),
+            "resource did not pause in time"
+        );
+        std::thread::yield_now();
+    }
+
+    let step = |cycles| {
+        control
+            .step_cycles(cycles)
+            .unwrap()
+            .recv_timeout(StdDuration::from_secs(1))
+            .unwrap()
+    };
+    let first = step(1);
+    // No cycles run between steps while the resource stays paused.
+    std::thread::sleep(StdDuration::from_millis(20));
+    assert_eq!(step(3), first + 3);
+    assert_eq!(step(1), first + 4);
+    assert_eq!(handle.state(), ResourceState::Paused);
+
+    handle.stop();
+    handle.join().unwrap();
+}
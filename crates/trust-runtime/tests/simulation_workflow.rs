@@ -3,7 +3,7 @@ use trust_runtime::io::IoAddress;
 use trust_runtime::scheduler::{ResourceRunner, ResourceState, ScaledClock};
 use trust_runtime::simulation::{
     SignalCouplingRule, SimulationConfig, SimulationController, SimulationDisturbance,
-    SimulationDisturbanceKind,
+    SimulationDisturbanceKind, SimulationModelKind, SimulationModelUpdate,
 };
 use trust_runtime::value::{Duration, Value};
 use trust_runtime::watchdog::{WatchdogAction, WatchdogPolicy};
@@ -86,6 +86,7 @@ fn coupling_applies_threshold_with_delay() {
             on_false: Some(Value::Bool(false)),
         }],
         disturbances: Vec::new(),
+        models: Vec::new(),
     });
 
     runtime
@@ -137,6 +138,7 @@ fn scripted_fault_disturbance_faults_runtime() {
                 message: "inject-fault".into(),
             },
         }],
+        models: Vec::new(),
     });
 
     simulation
@@ -151,6 +153,195 @@ fn scripted_fault_disturbance_faults_runtime() {
     assert!(runtime.faulted());
 }
 
+#[test]
+fn simulation_toml_parses_channel_models() {
+    let config = load_simulation_toml(
+        "models",
+        r#"
+[[models]]
+name = "tank"
+kind = "integrator"
+source = "%QW0"
+target = "%IW0"
+gain = 0.5
+offset = -1.0
+delay_ms = 100
+min = 0
+max = 1000
+
+[[models]]
+name = "temperature"
+target = "%IW2"
+time_constant_ms = 2000
+noise = 0.5
+initial = 20
+"#,
+    )
+    .expect("load models");
+    assert!(config.enabled, "models enable simulation by default");
+    assert_eq!(config.models.len(), 2);
+    assert_eq!(config.models[0].kind, SimulationModelKind::Integrator);
+    assert_eq!(config.models[0].params.delay, Duration::from_millis(100));
+    assert_eq!(config.models[1].kind, SimulationModelKind::Lag);
+    assert_eq!(config.models[1].initial, 20.0);
+
+    let output_target = load_simulation_toml(
+        "models-output-target",
+        "[[models]]\nname = \"bad\"\ntarget = \"%QW0\"\n",
+    )
+    .unwrap_err();
+    assert!(output_target.to_string().contains("%I address"));
+    let duplicate = load_simulation_toml(
+        "models-duplicate",
+        "[[models]]\nname = \"a\"\ntarget = \"%IW0\"\n\n[[models]]\nname = \"A\"\ntarget = \"%IW2\"\n",
+    )
+    .unwrap_err();
+    assert!(duplicate.to_string().contains("duplicate"));
+    let shared_target = load_simulation_toml(
+        "models-shared-target",
+        "[[models]]\nname = \"a\"\ntarget = \"%IW0\"\n\n[[models]]\nname = \"b\"\ntarget = \"%IW0\"\n",
+    )
+    .unwrap_err();
+    assert!(shared_target.to_string().contains("already driven"));
+}
+
+#[test]
+fn integrator_model_follows_delayed_source_within_limits() {
+    let config = load_simulation_toml(
+        "integrator",
+        r#"
+[[models]]
+name = "tank"
+kind = "integrator"
+source = "%QW0"
+target = "%IW0"
+gain = 10.0
+delay_ms = 100
+max = 25
+"#,
+    )
+    .expect("load models");
+    let source = IoAddress::parse("%QW0").expect("source address");
+    let target = IoAddress::parse("%IW0").expect("target address");
+    let mut runtime = Runtime::new();
+    let mut simulation = SimulationController::new(config);
+    let mut levels = Vec::new();
+    for ms in [0, 100, 200, 300, 400, 500] {
+        if ms == 200 {
+            runtime
+                .io_mut()
+                .write(&source, Value::Word(1))
+                .expect("pump on");
+        }
+        simulation
+            .apply_pre_cycle(Duration::from_millis(ms), &mut runtime)
+            .expect("pre cycle");
+        levels.push(read_input_word(&runtime, &target));
+    }
+    // The pump switched on at 200 ms reaches the tank after the 100 ms
+    // transport delay, then fills it by 1 unit per 100 ms.
+    assert_eq!(levels, vec![0, 0, 0, 1, 2, 3]);
+
+    for ms in (600..=3500).step_by(100) {
+        simulation
+            .apply_pre_cycle(Duration::from_millis(ms), &mut runtime)
+            .expect("pre cycle");
+    }
+    assert_eq!(read_input_word(&runtime, &target), 25);
+}
+
+#[test]
+fn lag_model_settles_and_accepts_runtime_updates() {
+    let config = load_simulation_toml(
+        "lag",
+        r#"
+[[models]]
+name = "temperature"
+source = "%QW0"
+target = "%IW0"
+time_constant_ms = 1000
+offset = 20
+"#,
+    )
+    .expect("load models");
+    let source = IoAddress::parse("%QW0").expect("source address");
+    let target = IoAddress::parse("%IW0").expect("target address");
+    let mut runtime = Runtime::new();
+    let mut simulation = SimulationController::new(config);
+    runtime
+        .io_mut()
+        .write(&source, Value::Word(80))
+        .expect("heater setpoint");
+
+    simulation
+        .apply_pre_cycle(Duration::from_millis(0), &mut runtime)
+        .expect("pre cycle");
+    simulation
+        .apply_pre_cycle(Duration::from_millis(1000), &mut runtime)
+        .expect("pre cycle");
+    // One time constant covers ~63% of the step from 0 to 100.
+    assert_eq!(read_input_word(&runtime, &target), 63);
+    simulation
+        .apply_pre_cycle(Duration::from_millis(10_000), &mut runtime)
+        .expect("pre cycle");
+    assert_eq!(read_input_word(&runtime, &target), 100);
+
+    let status = simulation
+        .update_model(
+            "Temperature",
+            &SimulationModelUpdate {
+                time_constant_ms: Some(0),
+                offset: Some(0.0),
+                ..SimulationModelUpdate::default()
+            },
+        )
+        .expect("update model");
+    assert_eq!(status.model.params.offset, 0.0);
+    simulation
+        .apply_pre_cycle(Duration::from_millis(10_010), &mut runtime)
+        .expect("pre cycle");
+    assert_eq!(read_input_word(&runtime, &target), 80);
+
+    let invalid = SimulationModelUpdate {
+        min: Some(10.0),
+        max: Some(5.0),
+        ..SimulationModelUpdate::default()
+    };
+    assert!(simulation.update_model("temperature", &invalid).is_err());
+    assert!(simulation
+        .update_model("missing", &SimulationModelUpdate::default())
+        .is_err());
+}
+
+#[test]
+fn model_noise_is_bounded_and_repeats_for_a_seed() {
+    let trace = |seed: u64| {
+        let config = load_simulation_toml(
+            &format!("noise-{seed}"),
+            &format!(
+                "[simulation]\nseed = {seed}\n\n[[models]]\nname = \"level\"\ntarget = \"%IW0\"\noffset = 500\nnoise = 20\n"
+            ),
+        )
+        .expect("load models");
+        let target = IoAddress::parse("%IW0").expect("target address");
+        let mut runtime = Runtime::new();
+        let mut simulation = SimulationController::new(config);
+        (0..20)
+            .map(|cycle| {
+                simulation
+                    .apply_pre_cycle(Duration::from_millis(cycle * 10), &mut runtime)
+                    .expect("pre cycle");
+                read_input_word(&runtime, &target)
+            })
+            .collect::<Vec<_>>()
+    };
+    let first = trace(7);
+    assert_eq!(first, trace(7));
+    assert_ne!(first, trace(8));
+    assert!(first.iter().all(|value| (480..=520).contains(value)));
+    assert!(first.iter().any(|value| *value != 500));
+}
+
 #[test]
 fn accelerated_clock_keeps_watchdog_semantics() {
     let mut runtime = Runtime::new();
@@ -192,6 +383,7 @@ fn run_simulation_trace() -> Vec<bool> {
                 value: Value::Bool(true),
             },
         }],
+        models: Vec::new(),
     });
 
     let output_pattern = [1u16, 12u16, 4u16, 18u16, 0u16, 25u16];
@@ -213,6 +405,26 @@ fn run_simulation_trace() -> Vec<bool> {
     trace
 }
 
+fn load_simulation_toml(name: &str, text: &str) -> Result<SimulationConfig, RuntimeError> {
+    let root = std::env::temp_dir().join(format!(
+        "trust-runtime-simulation-{name}-{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&root).expect("create temp dir");
+    let path = root.join("simulation.toml");
+    std::fs::write(&path, text).expect("write simulation.toml");
+    let config = SimulationConfig::load(&path);
+    let _ = std::fs::remove_dir_all(&root);
+    config
+}
+
+fn read_input_word(runtime: &Runtime, address: &IoAddress) -> u16 {
+    match runtime.io().read(address).expect("read input word") {
+        Value::Word(value) => value,
+        other => panic!("expected word input, got {other:?}"),
+    }
+}
+
 fn read_input_bit(runtime: &Runtime, address: &IoAddress) -> bool {
     match runtime.io().read(address).expect("read input bit") {
         Value::Bool(value) => value,
@@ -58,3 +58,51 @@ fn bit_full() {
         Value::Word(0x00FF)
     );
 }
+
+#[test]
+fn shifts_cover_every_width() {
+    let lib = StandardLibrary::new();
+    let call = |name: &str, value: Value, count: i16| lib.call(name, &[value, Value::Int(count)]);
+
+    assert_eq!(
+        call("SHL", Value::Byte(0x81), 1).unwrap(),
+        Value::Byte(0x02)
+    );
+    assert_eq!(
+        call("SHR", Value::DWord(0x8000_0000), 31).unwrap(),
+        Value::DWord(0x0000_0001)
+    );
+    assert_eq!(
+        call("SHL", Value::LWord(0x1), 63).unwrap(),
+        Value::LWord(0x8000_0000_0000_0000)
+    );
+    assert_eq!(
+        call("SHL", Value::LWord(u64::MAX), 64).unwrap(),
+        Value::LWord(0)
+    );
+    assert_eq!(
+        call("ROL", Value::Word(0x8001), 4).unwrap(),
+        Value::Word(0x0018)
+    );
+    assert_eq!(
+        call("ROR", Value::DWord(0x0000_0001), 1).unwrap(),
+        Value::DWord(0x8000_0000)
+    );
+    assert_eq!(
+        call("ROL", Value::LWord(0x8000_0000_0000_0001), 1).unwrap(),
+        Value::LWord(0x3)
+    );
+    assert_eq!(
+        call("ROR", Value::LWord(0x3), 1).unwrap(),
+        Value::LWord(0x8000_0000_0000_0001)
+    );
+    assert_eq!(
+        call("ROL", Value::LWord(0x1234), 64).unwrap(),
+        Value::LWord(0x1234)
+    );
+    assert_eq!(
+        call("ROR", Value::LWord(0x1234), 0).unwrap(),
+        Value::LWord(0x1234)
+    );
+    assert!(call("SHL", Value::Word(0x1), -1).is_err());
+}
@@ -0,0 +1,44 @@
+use trust_runtime::stdlib::StandardLibrary;
+use trust_runtime::value::Value;
+
+#[test]
+fn byte_order_functions() {
+    let lib = StandardLibrary::new();
+
+    assert_eq!(
+        lib.call("BE_TO_HOST", &[Value::DWord(0x1234_5678)])
+            .unwrap(),
+        Value::DWord(0x7856_3412)
+    );
+    assert_eq!(
+        lib.call("HOST_TO_BE", &[Value::Int(0x0102)]).unwrap(),
+        Value::Int(0x0201)
+    );
+    assert_eq!(
+        lib.call("BE_TO_HOST", &[Value::LWord(0x0102_0304_0506_0708)])
+            .unwrap(),
+        Value::LWord(0x0807_0605_0403_0201)
+    );
+    assert_eq!(
+        lib.call("BE_TO_HOST", &[Value::Byte(0xAB)]).unwrap(),
+        Value::Byte(0xAB)
+    );
+    assert_eq!(
+        lib.call(
+            "BE_TO_HOST",
+            &[Value::Real(f32::from_bits(1.5f32.to_bits().swap_bytes()))]
+        )
+        .unwrap(),
+        Value::Real(1.5)
+    );
+    assert_eq!(
+        lib.call("LE_TO_HOST", &[Value::UDInt(0x1234_5678)])
+            .unwrap(),
+        Value::UDInt(0x1234_5678)
+    );
+    assert_eq!(
+        lib.call("HOST_TO_LE", &[Value::Word(0xBEEF)]).unwrap(),
+        Value::Word(0xBEEF)
+    );
+    assert!(lib.call("BE_TO_HOST", &[Value::Bool(true)]).is_err());
+}
@@ -0,0 +1,128 @@
+use trust_runtime::harness::TestHarness;
+use trust_runtime::stdlib::StandardLibrary;
+use trust_runtime::value::{Fixed, Value};
+
+const ONE: i32 = 1 << 16;
+
+#[test]
+fn fixed_conversions() {
+    let lib = StandardLibrary::new();
+
+    assert_eq!(
+        lib.call("TO_FIX", &[Value::Int(3)]).unwrap(),
+        Value::DInt(3 * ONE)
+    );
+    assert_eq!(
+        lib.call("TO_FIX", &[Value::LReal(-1.5)]).unwrap(),
+        Value::DInt(-ONE - ONE / 2)
+    );
+    assert_eq!(
+        lib.call("TO_FIX", &[Value::DInt(40_000)]).unwrap(),
+        Value::DInt(i32::MAX)
+    );
+    assert_eq!(
+        lib.call("TO_FIX", &[Value::Real(-1.0e9)]).unwrap(),
+        Value::DInt(i32::MIN)
+    );
+    assert!(lib.call("TO_FIX", &[Value::LReal(f64::NAN)]).is_err());
+
+    assert_eq!(
+        lib.call("FIX_TO_DINT", &[Value::DInt(2 * ONE + ONE / 2)])
+            .unwrap(),
+        Value::DInt(3)
+    );
+    assert_eq!(
+        lib.call("FIX_TO_DINT", &[Value::DInt(-2 * ONE - ONE / 2)])
+            .unwrap(),
+        Value::DInt(-3)
+    );
+    assert_eq!(
+        lib.call("FIX_TO_LREAL", &[Value::DInt(ONE / 4)]).unwrap(),
+        Value::LReal(0.25)
+    );
+    assert_eq!(
+        lib.call("FIX_TO_REAL", &[Value::DInt(-ONE / 2)]).unwrap(),
+        Value::Real(-0.5)
+    );
+}
+
+#[test]
+fn fixed_arithmetic_saturates() {
+    let lib = StandardLibrary::new();
+    let fix = |value: f64| Value::DInt(Fixed::from_f64(value).unwrap().raw());
+
+    assert_eq!(
+        lib.call("FIX_ADD", &[fix(1.25), fix(2.5)]).unwrap(),
+        fix(3.75)
+    );
+    assert_eq!(
+        lib.call("FIX_ADD", &[fix(30_000.0), fix(30_000.0)])
+            .unwrap(),
+        Value::DInt(i32::MAX)
+    );
+    assert_eq!(
+        lib.call("FIX_SUB", &[fix(-30_000.0), fix(30_000.0)])
+            .unwrap(),
+        Value::DInt(i32::MIN)
+    );
+    assert_eq!(
+        lib.call("FIX_MUL", &[fix(-1.5), fix(2.25)]).unwrap(),
+        fix(-3.375)
+    );
+    assert_eq!(
+        lib.call("FIX_MUL", &[fix(300.0), fix(300.0)]).unwrap(),
+        Value::DInt(i32::MAX)
+    );
+    assert_eq!(
+        lib.call("FIX_DIV", &[fix(1.0), fix(4.0)]).unwrap(),
+        fix(0.25)
+    );
+    assert_eq!(
+        lib.call("FIX_DIV", &[fix(1000.0), fix(0.001)]).unwrap(),
+        Value::DInt(i32::MAX)
+    );
+    assert!(lib.call("FIX_DIV", &[fix(1.0), fix(0.0)]).is_err());
+    assert_eq!(
+        lib.call("FIX_ABS", &[Value::DInt(i32::MIN)]).unwrap(),
+        Value::DInt(i32::MAX)
+    );
+    assert_eq!(lib.call("FIX_SQRT", &[fix(6.25)]).unwrap(), fix(2.5));
+    assert_eq!(lib.call("FIX_SQRT", &[fix(-4.0)]).unwrap(), fix(0.0));
+    assert!(lib
+        .call("FIX_ADD", &[Value::LInt(i64::MAX), fix(0.0)])
+        .is_err());
+}
+
+#[test]
+fn fixed_filter_is_reproducible() {
+    let source = r#"
+TYPE
+    Q16_16 : DINT;
+END_TYPE
+
+PROGRAM Main
+VAR
+    alpha : Q16_16 := 16384;
+    sample : Q16_16;
+    state : Q16_16;
+    rounded : DINT;
+END_VAR
+sample := TO_FIX(10);
+state := FIX_ADD(state, FIX_MUL(alpha, FIX_SUB(sample, state)));
+rounded := FIX_TO_DINT(state);
+END_PROGRAM
+"#;
+    let mut harness = TestHarness::from_source(source).unwrap();
+    for result in harness.run_cycles(20) {
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+    }
+
+    let alpha = Fixed::from_raw(16384);
+    let input = Fixed::from_int(10);
+    let mut state = Fixed::ZERO;
+    for _ in 0..20 {
+        state = state.saturating_add(alpha.saturating_mul(input.saturating_sub(state)));
+    }
+    assert_eq!(harness.get_output("state"), Some(Value::DInt(state.raw())));
+    assert_eq!(harness.get_output("rounded"), Some(Value::DInt(10)));
+}
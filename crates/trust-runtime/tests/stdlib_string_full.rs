@@ -1,3 +1,4 @@
+use trust_runtime::harness::TestHarness;
 use trust_runtime::stdlib::StandardLibrary;
 use trust_runtime::value::Value;
 
@@ -84,3 +85,80 @@ fn string_full() {
         Value::Int(2)
     );
 }
+
+#[test]
+fn string_functions_count_characters() {
+    let lib = StandardLibrary::new();
+
+    assert_eq!(
+        lib.call("LEN", &[Value::String("Grüße".into())]).unwrap(),
+        Value::Int(5)
+    );
+    assert_eq!(
+        lib.call("LEFT", &[Value::String("Grüße".into()), Value::Int(3)])
+            .unwrap(),
+        Value::String("Grü".into())
+    );
+    assert_eq!(
+        lib.call(
+            "MID",
+            &[Value::WString("Ωmega".into()), Value::Int(2), Value::Int(1)]
+        )
+        .unwrap(),
+        Value::WString("Ωm".into())
+    );
+    assert_eq!(
+        lib.call(
+            "REPLACE",
+            &[
+                Value::String("a€b".into()),
+                Value::String("x".into()),
+                Value::Int(1),
+                Value::Int(2)
+            ]
+        )
+        .unwrap(),
+        Value::String("axb".into())
+    );
+    assert_eq!(
+        lib.call(
+            "FIND",
+            &[Value::WString("äöüö".into()), Value::WString("ö".into())]
+        )
+        .unwrap(),
+        Value::Int(2)
+    );
+    assert!(lib
+        .call(
+            "CONCAT",
+            &[Value::String("a".into()), Value::WString("b".into())]
+        )
+        .is_err());
+}
+
+#[test]
+fn bounded_string_assignment_truncates() {
+    let source = r#"
+PROGRAM Main
+VAR
+    short : STRING[4];
+    wide : WSTRING[2];
+    source : STRING := 'ABCDEFG';
+    text : WSTRING := "äöü";
+END_VAR
+short := CONCAT(source, 'XYZ');
+wide := text;
+END_PROGRAM
+"#;
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(
+        harness.get_output("short"),
+        Some(Value::String("ABCD".into()))
+    );
+    assert_eq!(
+        harness.get_output("wide"),
+        Some(Value::WString("äö".into()))
+    );
+}
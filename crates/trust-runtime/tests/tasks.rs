@@ -1,7 +1,10 @@
+use std::sync::{Arc, Mutex};
+
 use trust_runtime::eval::expr::{Expr, LValue};
 use trust_runtime::eval::ops::BinaryOp;
 use trust_runtime::eval::stmt::Stmt;
-use trust_runtime::task::{ProgramDef, TaskConfig};
+use trust_runtime::metrics::RuntimeMetrics;
+use trust_runtime::task::{ProgramDef, TaskCatchUp, TaskConfig};
 use trust_runtime::value::{Duration, Value};
 use trust_runtime::Runtime;
 
@@ -400,3 +403,80 @@ fn task_overrun_drops_missed_intervals() {
     );
     assert_eq!(runtime.task_overrun_count("T"), Some(2));
 }
+
+fn periodic_runtime(interval: Duration) -> Runtime {
+    let mut runtime = Runtime::new();
+    runtime.storage_mut().set_global("count", Value::Int(0));
+    runtime.register_program(inc_program("P", "count")).unwrap();
+    runtime.register_task(TaskConfig {
+        name: "T".into(),
+        interval,
+        single: None,
+        priority: 0,
+        programs: vec!["P".into()],
+        fb_instances: Vec::new(),
+    });
+    runtime
+}
+
+#[test]
+fn periodic_releases_do_not_drift_with_cycle_quantization() {
+    let mut runtime = periodic_runtime(Duration::from_millis(10));
+
+    // A 3 ms cycle sees the 10 ms releases at 12, 21, 30, ... ms. Scheduling from
+    // the observed start instead of the release grid would only fire every 12 ms.
+    for _ in 0..30 {
+        runtime.advance_time(Duration::from_millis(3));
+        runtime.execute_cycle().unwrap();
+    }
+
+    assert_eq!(
+        runtime.storage_mut().get_global("count"),
+        Some(&Value::Int(9))
+    );
+    assert_eq!(runtime.task_overrun_count("T"), Some(0));
+}
+
+#[test]
+fn task_burst_catch_up_runs_missed_intervals() {
+    let mut runtime = periodic_runtime(Duration::from_millis(10));
+    runtime.set_task_catch_up(TaskCatchUp::Burst);
+
+    runtime.advance_time(Duration::from_millis(35));
+    runtime.execute_cycle().unwrap();
+    assert_eq!(
+        runtime.storage_mut().get_global("count"),
+        Some(&Value::Int(3))
+    );
+    assert_eq!(runtime.task_overrun_count("T"), Some(0));
+
+    // The grid is unchanged: the next release is at 40 ms, not 45 ms.
+    runtime.advance_time(Duration::from_millis(5));
+    runtime.execute_cycle().unwrap();
+    assert_eq!(
+        runtime.storage_mut().get_global("count"),
+        Some(&Value::Int(4))
+    );
+}
+
+#[test]
+fn task_stats_report_release_jitter() {
+    let metrics = Arc::new(Mutex::new(RuntimeMetrics::new()));
+    let mut runtime = periodic_runtime(Duration::from_millis(10));
+    runtime.set_metrics_sink(metrics.clone());
+
+    runtime.advance_time(Duration::from_millis(12));
+    runtime.execute_cycle().unwrap();
+    runtime.advance_time(Duration::from_millis(8));
+    runtime.execute_cycle().unwrap();
+
+    let snapshot = metrics.lock().unwrap().snapshot();
+    let task = snapshot
+        .tasks
+        .iter()
+        .find(|task| task.name == "T")
+        .expect("task stats");
+    assert_eq!(task.jitter_last_ms, 0.0);
+    assert_eq!(task.jitter_max_ms, 2.0);
+    assert_eq!(task.jitter_avg_ms, 1.0);
+}
@@ -38,3 +38,33 @@ END_PROGRAM
     assert_eq!(harness.get_output("b"), Some(Value::Byte(0x08)));
     assert_eq!(harness.get_output("w"), Some(Value::Word(0x12FF)));
 }
+
+#[test]
+fn integer_bit_access() {
+    let source = r#"
+PROGRAM Main
+VAR
+    code : INT := -1;
+    count : UDINT := UDINT#0;
+    big : LINT := LINT#0;
+    sign_bit : BOOL;
+    low_bit : BOOL;
+END_VAR
+code.15 := FALSE;
+count.31 := TRUE;
+count.%X0 := TRUE;
+big.63 := TRUE;
+sign_bit := code.15;
+low_bit := code.0;
+END_PROGRAM
+"#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    harness.cycle();
+
+    assert_eq!(harness.get_output("code"), Some(Value::Int(0x7FFF)));
+    assert_eq!(harness.get_output("count"), Some(Value::UDInt(0x8000_0001)));
+    assert_eq!(harness.get_output("big"), Some(Value::LInt(i64::MIN)));
+    assert_eq!(harness.get_output("sign_bit"), Some(Value::Bool(false)));
+    assert_eq!(harness.get_output("low_bit"), Some(Value::Bool(true)));
+}
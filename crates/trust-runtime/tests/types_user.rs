@@ -1,3 +1,4 @@
+use trust_runtime::error::RuntimeError;
 use trust_runtime::harness::TestHarness;
 use trust_runtime::value::{EnumValue, Value};
 
@@ -58,3 +59,229 @@ END_PROGRAM
     assert_eq!(array_value.elements[1], Value::Int(20));
     assert_eq!(array_value.elements[2], Value::Int(30));
 }
+
+#[test]
+fn structured_initializers() {
+    let source = r#"
+TYPE
+    Position : STRUCT
+        x : REAL;
+        y : REAL;
+    END_STRUCT;
+    Segment : STRUCT
+        start : Position;
+        stop : Position;
+        speed : INT;
+    END_STRUCT;
+END_TYPE
+
+PROGRAM Main
+VAR
+    scale : REAL := 2.0;
+    pos : Position := (x := 1.0, y := 2.0);
+    seg : Segment := (stop := (x := scale * 5.0), speed := 3);
+    path : ARRAY[1..4] OF Position := [(x := 1.0), 2((y := 4.0))];
+    grid : ARRAY[0..1, 0..2] OF INT := [2(1, 2(0))];
+END_VAR
+END_PROGRAM
+"#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    harness.cycle();
+
+    let position = |value: &Value| {
+        let Value::Struct(struct_value) = value else {
+            panic!("expected struct value");
+        };
+        (
+            struct_value.fields.get("x").cloned(),
+            struct_value.fields.get("y").cloned(),
+        )
+    };
+
+    let pos = harness.get_output("pos").unwrap();
+    assert_eq!(
+        position(&pos),
+        (Some(Value::Real(1.0)), Some(Value::Real(2.0)))
+    );
+
+    let Value::Struct(seg) = harness.get_output("seg").unwrap() else {
+        panic!("expected struct value");
+    };
+    assert_eq!(
+        position(seg.fields.get("start").unwrap()),
+        (Some(Value::Real(0.0)), Some(Value::Real(0.0)))
+    );
+    assert_eq!(
+        position(seg.fields.get("stop").unwrap()),
+        (Some(Value::Real(10.0)), Some(Value::Real(0.0)))
+    );
+    assert_eq!(seg.fields.get("speed"), Some(&Value::Int(3)));
+
+    let Value::Array(path) = harness.get_output("path").unwrap() else {
+        panic!("expected array value");
+    };
+    let points: Vec<_> = path.elements.iter().map(position).collect();
+    assert_eq!(
+        points,
+        vec![
+            (Some(Value::Real(1.0)), Some(Value::Real(0.0))),
+            (Some(Value::Real(0.0)), Some(Value::Real(4.0))),
+            (Some(Value::Real(0.0)), Some(Value::Real(4.0))),
+            (Some(Value::Real(0.0)), Some(Value::Real(0.0))),
+        ]
+    );
+
+    let Value::Array(grid) = harness.get_output("grid").unwrap() else {
+        panic!("expected array value");
+    };
+    assert_eq!(
+        grid.elements,
+        vec![
+            Value::Int(1),
+            Value::Int(0),
+            Value::Int(0),
+            Value::Int(1),
+            Value::Int(0),
+            Value::Int(0),
+        ]
+    );
+}
+
+#[test]
+fn structured_initializer_rejects_unknown_field() {
+    let source = r#"
+TYPE
+    Position : STRUCT
+        x : REAL;
+    END_STRUCT;
+END_TYPE
+
+PROGRAM Main
+VAR
+    pos : Position := (z := 1.0);
+END_VAR
+END_PROGRAM
+"#;
+
+    let err = TestHarness::from_source(source)
+        .err()
+        .expect("compile error");
+    assert!(err.to_string().contains("field 'z'"));
+}
+
+#[test]
+fn enum_conversion_functions() {
+    let source = r#"
+TYPE
+    Color : (Red := 1, Green := 2, Blue := 4);
+END_TYPE
+
+PROGRAM Main
+VAR
+    raw : INT := INT#4;
+    c : Color;
+    back : DINT;
+    from_name : UINT;
+END_VAR
+c := TO_Color(raw);
+back := COLOR_TO_DINT(c);
+from_name := TO_UINT(Color#Green);
+END_PROGRAM
+"#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    match harness.get_output("c").unwrap() {
+        Value::Enum(EnumValue {
+            variant_name,
+            numeric_value,
+            ..
+        }) => {
+            assert_eq!(variant_name.as_str(), "Blue");
+            assert_eq!(numeric_value, 4);
+        }
+        other => panic!("expected enum value, got {other:?}"),
+    }
+    assert_eq!(harness.get_output("back"), Some(Value::DInt(4)));
+    assert_eq!(harness.get_output("from_name"), Some(Value::UInt(2)));
+}
+
+#[test]
+fn enum_conversion_rejects_unknown_member_value() {
+    let source = r#"
+TYPE
+    Color : (Red := 1, Green := 2, Blue := 4);
+END_TYPE
+
+PROGRAM Main
+VAR
+    raw : INT := INT#3;
+    c : Color;
+END_VAR
+c := INT_TO_COLOR(raw);
+END_PROGRAM
+"#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(matches!(
+        result.errors.as_slice(),
+        [RuntimeError::InvalidEnumValue { value: 3, .. }]
+    ));
+}
+
+#[test]
+fn union_variants_share_bytes() {
+    let source = r#"
+TYPE
+    Header : STRUCT
+        id : BYTE;
+        flags : BYTE;
+        length : WORD;
+    END_STRUCT;
+    Telegram : UNION
+        raw : DWORD;
+        bytes : ARRAY[0..3] OF BYTE;
+        header : Header;
+    END_UNION;
+    Word16 : UNION
+        w : WORD;
+        lo : BYTE;
+    END_UNION;
+END_TYPE
+
+PROGRAM Main
+VAR
+    t : Telegram;
+    p : Word16;
+    first : BYTE;
+    length : WORD;
+    ready : BOOL;
+    host : DWORD;
+END_VAR
+t.raw := DWORD#16#01020304;
+first := t.bytes[0];
+length := t.header.length;
+ready := t.raw.%X2;
+host := BE_TO_HOST(t.raw);
+p.w := WORD#16#1234;
+p.lo := BYTE#16#FF;
+END_PROGRAM
+"#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(harness.get_output("first"), Some(Value::Byte(0x04)));
+    assert_eq!(harness.get_output("length"), Some(Value::Word(0x0102)));
+    assert_eq!(harness.get_output("ready"), Some(Value::Bool(true)));
+    assert_eq!(harness.get_output("host"), Some(Value::DWord(0x0403_0201)));
+
+    let Value::Struct(p) = harness.get_output("p").unwrap() else {
+        panic!("expected union value");
+    };
+    assert_eq!(p.fields.get("w"), Some(&Value::Word(0x12FF)));
+    assert_eq!(p.fields.get("lo"), Some(&Value::Byte(0xFF)));
+}
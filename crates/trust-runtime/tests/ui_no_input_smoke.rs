@@ -1,3 +1,5 @@
+#![cfg(feature = "services")]
+
 use std::net::TcpListener;
 use std::process::Command;
 
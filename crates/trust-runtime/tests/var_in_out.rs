@@ -0,0 +1,97 @@
+use trust_runtime::harness::TestHarness;
+use trust_runtime::value::Value;
+
+#[test]
+fn in_out_aliases_caller_variable_during_call() {
+    let source = r#"
+FUNCTION_BLOCK Probe
+VAR_IN_OUT
+    a : DINT;
+    b : DINT;
+END_VAR
+VAR_OUTPUT
+    seen : DINT;
+END_VAR
+a := a + 1;
+seen := b;
+END_FUNCTION_BLOCK
+
+PROGRAM Main
+VAR
+    probe : Probe;
+    shared : DINT := 10;
+    seen : DINT;
+END_VAR
+probe(a := shared, b := shared, seen => seen);
+END_PROGRAM
+"#;
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(harness.get_output("shared"), Some(Value::DInt(11)));
+    assert_eq!(harness.get_output("seen"), Some(Value::DInt(11)));
+}
+
+#[test]
+fn in_out_passes_through_nested_calls_and_methods() {
+    let source = r#"
+TYPE
+    Point : STRUCT
+        x : DINT;
+        y : DINT;
+    END_STRUCT;
+END_TYPE
+
+FUNCTION_BLOCK Inner
+VAR_IN_OUT
+    value : DINT;
+END_VAR
+value := value * 2;
+END_FUNCTION_BLOCK
+
+FUNCTION_BLOCK Outer
+VAR_IN_OUT
+    value : DINT;
+END_VAR
+VAR
+    inner : Inner;
+END_VAR
+value := value + 1;
+inner(value := value);
+
+METHOD PUBLIC Bump
+VAR_IN_OUT
+    target : DINT;
+END_VAR
+target := target + 100;
+END_METHOD
+END_FUNCTION_BLOCK
+
+PROGRAM Main
+VAR
+    outer : Outer;
+    counter : DINT := 4;
+    values : ARRAY[1..3] OF DINT;
+    point : Point;
+END_VAR
+outer(value := counter);
+outer(value := values[2]);
+outer.Bump(target := point.y);
+outer.Bump(target := values[2]);
+END_PROGRAM
+"#;
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(harness.get_output("counter"), Some(Value::DInt(10)));
+
+    let Some(Value::Array(values)) = harness.get_output("values") else {
+        panic!("expected array");
+    };
+    assert_eq!(values.elements[1], Value::DInt(102));
+
+    let Some(Value::Struct(point)) = harness.get_output("point") else {
+        panic!("expected struct");
+    };
+    assert_eq!(point.fields.get("y"), Some(&Value::DInt(100)));
+}
@@ -39,3 +39,88 @@ END_PROGRAM
     };
     assert_eq!(arr2.elements[0], Value::Int(6));
 }
+
+#[test]
+fn input_array_bounds_follow_argument() {
+    let source = r#"
+FUNCTION Average : REAL
+VAR_INPUT
+    values : ARRAY[*] OF REAL;
+END_VAR
+VAR
+    i : DINT;
+    total : REAL;
+END_VAR
+FOR i := LOWER_BOUND(values, 1) TO UPPER_BOUND(values, 1) DO
+    total := total + values[i];
+END_FOR;
+Average := total / DINT_TO_REAL(UPPER_BOUND(values, 1) - LOWER_BOUND(values, 1) + 1);
+END_FUNCTION
+
+CLASS Stats
+METHOD PUBLIC Span : DINT
+VAR_INPUT
+    grid : ARRAY[*, *] OF INT;
+END_VAR
+Span := (UPPER_BOUND(grid, 1) - LOWER_BOUND(grid, 1) + 1)
+    * (UPPER_BOUND(grid, 2) - LOWER_BOUND(grid, 2) + 1);
+END_METHOD
+END_CLASS
+
+PROGRAM Main
+VAR
+    short : ARRAY[1..2] OF REAL;
+    long : ARRAY[-2..2] OF REAL;
+    grid : ARRAY[0..1, 5..7] OF INT;
+    stats : Stats;
+    avg_short : REAL;
+    avg_long : REAL;
+    lower : DINT;
+    cells : DINT;
+    i : DINT;
+END_VAR
+short[1] := REAL#1.0;
+short[2] := REAL#3.0;
+FOR i := -2 TO 2 DO
+    long[i] := DINT_TO_REAL(i + 3);
+END_FOR;
+avg_short := Average(short);
+avg_long := Average(values := long);
+lower := LOWER_BOUND(long, 1);
+cells := stats.Span(grid);
+END_PROGRAM
+"#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(harness.get_output("avg_short"), Some(Value::Real(2.0)));
+    assert_eq!(harness.get_output("avg_long"), Some(Value::Real(3.0)));
+    assert_eq!(harness.get_output("lower"), Some(Value::DInt(-2)));
+    assert_eq!(harness.get_output("cells"), Some(Value::DInt(6)));
+}
+
+#[test]
+fn bound_dimension_out_of_range_is_error() {
+    let source = r#"
+FUNCTION Upper2 : DINT
+VAR_INPUT
+    values : ARRAY[*] OF INT;
+    dim : INT;
+END_VAR
+Upper2 := UPPER_BOUND(values, dim);
+END_FUNCTION
+
+PROGRAM Main
+VAR
+    data : ARRAY[0..3] OF INT;
+    upper : DINT;
+END_VAR
+upper := Upper2(data, 2);
+END_PROGRAM
+"#;
+
+    let mut harness = TestHarness::from_source(source).unwrap();
+    let result = harness.cycle();
+    assert!(!result.errors.is_empty());
+}
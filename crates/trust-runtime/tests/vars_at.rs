@@ -22,3 +22,21 @@ END_PROGRAM
     let out = harness.get_direct_output("%QX0.1").unwrap();
     assert_eq!(out, Value::Bool(true));
 }
+
+#[test]
+fn at_address_size_must_match_type() {
+    let source = r#"
+PROGRAM Main
+VAR
+    speed AT %QB0 : INT;
+END_VAR
+END_PROGRAM
+"#;
+
+    let Err(err) = TestHarness::from_source(source) else {
+        panic!("a BYTE address for an INT should be rejected");
+    };
+    let message = err.to_string();
+    assert!(message.contains("speed"), "{message}");
+    assert!(message.contains("needs a WORD"), "{message}");
+}
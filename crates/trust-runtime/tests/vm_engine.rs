@@ -0,0 +1,205 @@
+mod common;
+
+use trust_hir::types::TypeRegistry;
+use trust_runtime::error::RuntimeError;
+use trust_runtime::eval::expr::{Expr, LValue};
+use trust_runtime::eval::ops::BinaryOp;
+use trust_runtime::eval::stmt::{exec_block, Stmt, StmtResult};
+use trust_runtime::eval::vm::{compile_block, exec_compiled, ExecutionEngine};
+use trust_runtime::harness::TestHarness;
+use trust_runtime::memory::VariableStorage;
+use trust_runtime::value::Value;
+
+const CONTROL_FLOW: &str = r#"
+    FUNCTION Scale : DINT
+    VAR_INPUT
+        value : DINT;
+    END_VAR
+    Scale := value * 3;
+    END_FUNCTION
+
+    PROGRAM Main
+    VAR
+        i : INT;
+        sum : DINT := 0;
+        evens : DINT := 0;
+        w : DINT := 0;
+        r : DINT := 0;
+        selector : INT := 0;
+        branch : DINT := 0;
+        flag : BOOL := FALSE;
+        ratio : LREAL := 0.0;
+    END_VAR
+    FOR i := 1 TO 20 BY 1 DO
+        IF i = 3 THEN
+            CONTINUE;
+        END_IF;
+        IF i > 15 THEN
+            EXIT;
+        END_IF;
+        sum := sum + i * (2 + 3);
+        IF (i MOD 2 = 0) AND (i > 4 OR flag) THEN
+            evens := evens + 1;
+        END_IF;
+    END_FOR;
+    WHILE w < 10 DO
+        w := w + 2;
+        IF w = 6 THEN
+            CONTINUE;
+        END_IF;
+        r := r + Scale(w);
+    END_WHILE;
+    REPEAT
+        r := r - 1;
+    UNTIL r < 40
+    END_REPEAT;
+    selector := selector + 1;
+    CASE selector OF
+        1: branch := 10;
+        2..3: branch := 20 + sum;
+    ELSE
+        branch := -1;
+    END_CASE;
+    flag := NOT flag;
+    ratio := ratio + 1.5 * 2.0;
+    END_PROGRAM
+"#;
+
+fn run(engine: ExecutionEngine, cycles: u32) -> TestHarness {
+    let mut harness = TestHarness::from_source(CONTROL_FLOW).unwrap();
+    harness.runtime_mut().set_execution_engine(engine);
+    for result in harness.run_cycles(cycles) {
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+    }
+    harness
+}
+
+fn add(left: Expr, right: Expr) -> Expr {
+    Expr::Binary {
+        op: BinaryOp::Add,
+        left: Box::new(left),
+        right: Box::new(right),
+    }
+}
+
+#[test]
+fn vm_matches_interpreter_across_cycles() {
+    let vm = run(ExecutionEngine::Vm, 4);
+    let interpreter = run(ExecutionEngine::Interpreter, 4);
+    for name in [
+        "i", "sum", "evens", "w", "r", "selector", "branch", "flag", "ratio",
+    ] {
+        assert_eq!(vm.get_output(name), interpreter.get_output(name), "{name}");
+    }
+    assert_eq!(vm.get_output("branch"), Some(Value::DInt(-1)));
+}
+
+#[test]
+fn compiled_block_folds_constants_and_reuses_loads() {
+    let stmts = vec![Stmt::Assign {
+        target: LValue::Name("x".into()),
+        value: add(
+            add(
+                Expr::Binary {
+                    op: BinaryOp::Mul,
+                    left: Box::new(Expr::Literal(Value::DInt(2))),
+                    right: Box::new(Expr::Literal(Value::DInt(3))),
+                },
+                Expr::Name("y".into()),
+            ),
+            Expr::Name("y".into()),
+        ),
+        location: None,
+    }];
+    let block = compile_block(&stmts).expect("compiled");
+    // Budget check, folded constant, one load, a move, two adds, and the store.
+    assert_eq!(block.instruction_count(), 7);
+
+    let mut storage = VariableStorage::new();
+    storage.set_global("x", Value::DInt(0));
+    storage.set_global("y", Value::DInt(4));
+    let registry = TypeRegistry::new();
+    let mut ctx = common::make_context(&mut storage, &registry);
+    assert_eq!(
+        exec_compiled(&mut ctx, &block).unwrap(),
+        StmtResult::Continue
+    );
+    assert_eq!(ctx.storage.get_global("x"), Some(&Value::DInt(14)));
+}
+
+#[test]
+fn compiled_block_keeps_interpreter_control_flow_errors() {
+    let exit = vec![Stmt::Exit { location: None }];
+    let block = compile_block(&exit).expect("compiled");
+    let mut storage = VariableStorage::new();
+    let registry = TypeRegistry::new();
+    let mut ctx = common::make_context(&mut storage, &registry);
+    assert!(matches!(
+        exec_compiled(&mut ctx, &block),
+        Err(RuntimeError::InvalidControlFlow)
+    ));
+    assert!(matches!(
+        exec_block(&mut ctx, &exit),
+        Err(RuntimeError::InvalidControlFlow)
+    ));
+
+    let condition = vec![Stmt::While {
+        condition: Expr::Literal(Value::Int(1)),
+        body: Vec::new(),
+        location: None,
+    }];
+    let block = compile_block(&condition).expect("compiled");
+    assert!(matches!(
+        exec_compiled(&mut ctx, &block),
+        Err(RuntimeError::ConditionNotBool)
+    ));
+
+    let labelled = vec![Stmt::Jmp {
+        target: "done".into(),
+        location: None,
+    }];
+    assert!(compile_block(&labelled).is_none());
+}
+
+#[test]
+fn compiled_binary_matches_interpreter_at_boundaries() {
+    let cases = [
+        (BinaryOp::Add, Value::DInt(i32::MAX), Value::DInt(1)),
+        (BinaryOp::Div, Value::DInt(i32::MIN), Value::DInt(-1)),
+        (BinaryOp::Div, Value::DInt(7), Value::DInt(0)),
+        (BinaryOp::Mod, Value::Int(-7), Value::Int(2)),
+        (BinaryOp::Mul, Value::LInt(i64::MAX), Value::LInt(2)),
+        (BinaryOp::Div, Value::LReal(1.0), Value::LReal(0.0)),
+        (BinaryOp::Mul, Value::Real(f32::MAX), Value::Real(2.0)),
+        (BinaryOp::Sub, Value::Real(0.1), Value::Real(0.2)),
+        (BinaryOp::Lt, Value::SInt(-1), Value::SInt(1)),
+        (BinaryOp::Xor, Value::Bool(true), Value::Bool(true)),
+    ];
+    for (op, left, right) in cases {
+        let stmts = vec![Stmt::Assign {
+            target: LValue::Name("x".into()),
+            value: Expr::Binary {
+                op,
+                left: Box::new(Expr::Name("a".into())),
+                right: Box::new(Expr::Name("b".into())),
+            },
+            location: None,
+        }];
+        let block = compile_block(&stmts).expect("compiled");
+        let run = |compiled: bool| {
+            let mut storage = VariableStorage::new();
+            storage.set_global("x", left.clone());
+            storage.set_global("a", left.clone());
+            storage.set_global("b", right.clone());
+            let registry = TypeRegistry::new();
+            let mut ctx = common::make_context(&mut storage, &registry);
+            let result = if compiled {
+                exec_compiled(&mut ctx, &block)
+            } else {
+                exec_block(&mut ctx, &stmts)
+            };
+            format!("{result:?} {:?}", ctx.storage.get_global("x"))
+        };
+        assert_eq!(run(true), run(false), "{op:?} {left:?} {right:?}");
+    }
+}
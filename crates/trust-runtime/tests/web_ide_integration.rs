@@ -1,3 +1,5 @@
+#![cfg(feature = "services")]
+
 use std::collections::VecDeque;
 use std::net::TcpListener;
 use std::path::PathBuf;
@@ -9,8 +11,10 @@ use std::time::{Duration, Instant};
 use indexmap::IndexMap;
 use serde_json::{json, Value};
 use smol_str::SmolStr;
-use trust_runtime::config::{ControlMode, WebAuthMode, WebConfig};
-use trust_runtime::control::{ControlState, HmiRuntimeDescriptor, SourceFile, SourceRegistry};
+use trust_runtime::config::{ControlMode, ServerLimits, WebAuthMode, WebConfig};
+use trust_runtime::control::{
+    ControlSessions, ControlState, HmiRuntimeDescriptor, SourceFile, SourceRegistry,
+};
 use trust_runtime::debug::DebugVariableHandles;
 use trust_runtime::error::RuntimeError;
 use trust_runtime::harness::TestHarness;
@@ -117,10 +121,14 @@ fn control_state(source: &str, mode: ControlMode, auth_token: Option<&str>) -> A
         io_health: Arc::new(Mutex::new(Vec::new())),
         debug_enabled: Arc::new(AtomicBool::new(true)),
         debug_variables: Arc::new(Mutex::new(DebugVariableHandles::new())),
+        sessions: Arc::new(ControlSessions::default()),
         hmi_live: Arc::new(Mutex::new(trust_runtime::hmi::HmiLiveState::default())),
         hmi_descriptor,
         historian: None,
+        event_store: None,
         pairing: None,
+        mesh: None,
+        redundancy: None,
     })
 }
 
@@ -143,6 +151,7 @@ fn start_test_server_with_root(
         listen: SmolStr::new(listen.clone()),
         auth,
         tls: false,
+        limits: ServerLimits::default(),
     };
     let _server =
         start_web_server(&config, state, None, None, project_root, None).expect("start server");
@@ -1,3 +1,5 @@
+#![cfg(feature = "services")]
+
 use std::collections::VecDeque;
 use std::net::TcpListener;
 use std::path::PathBuf;
@@ -9,8 +11,10 @@ use std::time::Duration;
 use indexmap::IndexMap;
 use serde_json::{json, Value};
 use smol_str::SmolStr;
-use trust_runtime::config::{ControlMode, WebAuthMode, WebConfig};
-use trust_runtime::control::{ControlState, HmiRuntimeDescriptor, SourceFile, SourceRegistry};
+use trust_runtime::config::{ControlMode, ServerLimits, WebAuthMode, WebConfig};
+use trust_runtime::control::{
+    ControlSessions, ControlState, HmiRuntimeDescriptor, SourceFile, SourceRegistry,
+};
 use trust_runtime::debug::DebugVariableHandles;
 use trust_runtime::error::RuntimeError;
 use trust_runtime::harness::TestHarness;
@@ -121,10 +125,14 @@ fn control_state(source: &str) -> Arc<ControlState> {
         io_health: Arc::new(Mutex::new(Vec::new())),
         debug_enabled: Arc::new(AtomicBool::new(true)),
         debug_variables: Arc::new(Mutex::new(DebugVariableHandles::new())),
+        sessions: Arc::new(ControlSessions::default()),
         hmi_live: Arc::new(Mutex::new(trust_runtime::hmi::HmiLiveState::default())),
         hmi_descriptor,
         historian: None,
+        event_store: None,
         pairing: None,
+        mesh: None,
+        redundancy: None,
     })
 }
 
@@ -143,6 +151,7 @@ fn start_test_server(state: Arc<ControlState>, project_root: PathBuf) -> String
         listen: SmolStr::new(listen.clone()),
         auth: WebAuthMode::Local,
         tls: false,
+        limits: ServerLimits::default(),
     };
     let _server = start_web_server(&config, state, None, None, Some(project_root), None)
         .expect("start web server");
@@ -0,0 +1,293 @@
+#![cfg(feature = "services")]
+
+use std::collections::VecDeque;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+use smol_str::SmolStr;
+use trust_runtime::config::{ControlMode, ServerLimits, WebAuthMode, WebConfig};
+use trust_runtime::control::{
+    ControlSessions, ControlState, HmiRuntimeDescriptor, SourceFile, SourceRegistry,
+};
+use trust_runtime::debug::DebugVariableHandles;
+use trust_runtime::error::RuntimeError;
+use trust_runtime::harness::TestHarness;
+use trust_runtime::metrics::RuntimeMetrics;
+use trust_runtime::scheduler::{ResourceCommand, ResourceControl, StdClock};
+use trust_runtime::settings::{
+    BaseSettings, DiscoverySettings, MeshSettings, RuntimeSettings, SimulationSettings, WebSettings,
+};
+use trust_runtime::watchdog::{FaultPolicy, RetainMode, WatchdogPolicy};
+use trust_runtime::web::start_web_server;
+
+fn runtime_settings() -> RuntimeSettings {
+    RuntimeSettings::new(
+        BaseSettings {
+            log_level: SmolStr::new("info"),
+            watchdog: WatchdogPolicy::default(),
+            fault_policy: FaultPolicy::SafeHalt,
+            retain_mode: RetainMode::None,
+            retain_save_interval: None,
+        },
+        WebSettings {
+            enabled: true,
+            listen: SmolStr::new("127.0.0.1:0"),
+            auth: SmolStr::new("local"),
+            tls: false,
+        },
+        DiscoverySettings {
+            enabled: false,
+            service_name: SmolStr::new("truST"),
+            advertise: false,
+            interfaces: Vec::new(),
+        },
+        MeshSettings {
+            enabled: false,
+            listen: SmolStr::new("127.0.0.1:0"),
+            tls: false,
+            auth_token: None,
+            publish: Vec::new(),
+            subscribe: IndexMap::new(),
+        },
+        SimulationSettings {
+            enabled: false,
+            time_scale: 1,
+            mode_label: SmolStr::new("production"),
+            warning: SmolStr::new(""),
+        },
+    )
+}
+
+fn source_fixture() -> &'static str {
+    "PROGRAM Main\nEND_PROGRAM\n"
+}
+
+fn control_state(source: &str) -> Arc<ControlState> {
+    let mut harness = TestHarness::from_source(source).expect("build test harness");
+    let debug = harness.runtime_mut().enable_debug();
+    harness.cycle();
+
+    let snapshot = trust_runtime::debug::DebugSnapshot {
+        storage: harness.runtime().storage().clone(),
+        now: harness.runtime().current_time(),
+    };
+
+    let (resource, cmd_rx) = ResourceControl::stub(StdClock::new());
+    thread::spawn(move || {
+        while let Ok(command) = cmd_rx.recv() {
+            match command {
+                ResourceCommand::ReloadBytecode { respond_to, .. } => {
+                    let _ = respond_to
+                        .send(Err(RuntimeError::ControlError(SmolStr::new("unsupported"))));
+                }
+                ResourceCommand::MeshSnapshot { respond_to, .. } => {
+                    let _ = respond_to.send(IndexMap::new());
+                }
+                ResourceCommand::Snapshot { respond_to } => {
+                    let _ = respond_to.send(snapshot.clone());
+                }
+                _ => {}
+            }
+        }
+    });
+
+    let sources = SourceRegistry::new(vec![SourceFile {
+        id: 1,
+        path: PathBuf::from("main.st"),
+        text: source.to_string(),
+    }]);
+    let hmi_descriptor = Arc::new(Mutex::new(HmiRuntimeDescriptor::from_sources(
+        None, &sources,
+    )));
+
+    Arc::new(ControlState {
+        debug,
+        resource,
+        metadata: Arc::new(Mutex::new(harness.runtime().metadata_snapshot())),
+        sources,
+        io_snapshot: Arc::new(Mutex::new(None)),
+        pending_restart: Arc::new(Mutex::new(None)),
+        auth_token: Arc::new(Mutex::new(None)),
+        control_requires_auth: false,
+        control_mode: Arc::new(Mutex::new(ControlMode::Debug)),
+        audit_tx: None,
+        metrics: Arc::new(Mutex::new(RuntimeMetrics::default())),
+        events: Arc::new(Mutex::new(VecDeque::new())),
+        settings: Arc::new(Mutex::new(runtime_settings())),
+        project_root: None,
+        resource_name: SmolStr::new("RESOURCE"),
+        io_health: Arc::new(Mutex::new(Vec::new())),
+        debug_enabled: Arc::new(AtomicBool::new(true)),
+        debug_variables: Arc::new(Mutex::new(DebugVariableHandles::new())),
+        sessions: Arc::new(ControlSessions::default()),
+        hmi_live: Arc::new(Mutex::new(trust_runtime::hmi::HmiLiveState::default())),
+        hmi_descriptor,
+        historian: None,
+        event_store: None,
+        pairing: None,
+        mesh: None,
+        redundancy: None,
+    })
+}
+
+fn reserve_loopback_port() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind local port");
+    let port = listener.local_addr().expect("read local addr").port();
+    drop(listener);
+    port
+}
+
+fn start_test_server(state: Arc<ControlState>) -> String {
+    start_test_server_with_limits(state, ServerLimits::default())
+}
+
+fn start_test_server_with_limits(state: Arc<ControlState>, limits: ServerLimits) -> String {
+    let port = reserve_loopback_port();
+    let listen = format!("127.0.0.1:{port}");
+    let config = WebConfig {
+        enabled: true,
+        listen: SmolStr::new(listen.clone()),
+        auth: WebAuthMode::Local,
+        tls: false,
+        limits,
+    };
+    let _server =
+        start_web_server(&config, state, None, None, None, None).expect("start web server");
+    let base = format!("http://{listen}");
+    wait_for_server(&base);
+    base
+}
+
+fn wait_for_server(base: &str) {
+    for _ in 0..80 {
+        if ureq::get(&format!("{base}/api/v1/health")).call().is_ok() {
+            return;
+        }
+        thread::sleep(Duration::from_millis(25));
+    }
+    panic!("web server did not become reachable at {base}");
+}
+
+fn rest_request(method: &str, url: &str, body: Option<Value>) -> (u16, Value) {
+    let request = ureq::request(method, url);
+    let result = match body {
+        Some(body) => request
+            .set("Content-Type", "application/json")
+            .send_string(&body.to_string()),
+        None => request.call(),
+    };
+    match result {
+        Ok(response) => {
+            let status = response.status();
+            let body = response.into_string().expect("read success body");
+            (
+                status,
+                serde_json::from_str(&body).unwrap_or_else(|_| json!({})),
+            )
+        }
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().expect("read error body");
+            (
+                status,
+                serde_json::from_str(&body).unwrap_or_else(|_| json!({})),
+            )
+        }
+        Err(err) => panic!("request failed: {err}"),
+    }
+}
+
+#[test]
+fn rest_api_mirrors_control_requests_with_http_status_codes() {
+    let state = control_state(source_fixture());
+    let base = start_test_server(state);
+
+    let (status, body) = rest_request("GET", &format!("{base}/api/v1/status"), None);
+    assert_eq!(status, 200);
+    assert_eq!(body["ok"], json!(true));
+    assert!(body["result"]["state"].is_string(), "status result: {body}");
+    assert!(body.get("id").is_none());
+
+    let (status, body) = rest_request("GET", &format!("{base}/api/v1/events?limit=5"), None);
+    assert_eq!(status, 200);
+    assert!(body["result"]["events"].is_array());
+
+    let (status, body) = rest_request("GET", &format!("{base}/api/v1/vars/forced"), None);
+    assert_eq!(status, 200);
+    assert_eq!(body["ok"], json!(true));
+
+    let (status, body) = rest_request(
+        "PUT",
+        &format!("{base}/api/v1/io/not-an-address"),
+        Some(json!({ "value": 1 })),
+    );
+    assert_eq!(status, 400);
+    assert_eq!(body["ok"], json!(false));
+
+    let (status, body) = rest_request(
+        "PUT",
+        &format!("{base}/api/v1/vars/Missing"),
+        Some(json!({})),
+    );
+    assert_eq!(status, 400);
+    assert_eq!(body["error"], json!("missing value"));
+
+    let (status, _) = rest_request("DELETE", &format!("{base}/api/v1/status"), None);
+    assert_eq!(status, 405);
+    let (status, _) = rest_request("GET", &format!("{base}/api/v1/unknown"), None);
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn rest_api_serves_openapi_spec_for_every_route() {
+    let state = control_state(source_fixture());
+    let base = start_test_server(state);
+
+    let (status, spec) = rest_request("GET", &format!("{base}/api/v1/openapi.json"), None);
+    assert_eq!(status, 200);
+    assert_eq!(spec["openapi"], json!("3.0.3"));
+    assert_eq!(
+        spec["paths"]["/api/v1/status"]["get"]["x-control-request"],
+        json!("status")
+    );
+    assert_eq!(
+        spec["paths"]["/api/v1/io/{address}"]["put"]["x-required-role"],
+        json!("engineer")
+    );
+    assert_eq!(
+        spec["paths"]["/api/v1/restart"]["post"]["x-required-role"],
+        json!("operator")
+    );
+}
+
+#[test]
+fn web_server_caps_chunked_request_bodies() {
+    let state = control_state(source_fixture());
+    let base = start_test_server_with_limits(
+        state,
+        ServerLimits {
+            max_request_bytes: 256,
+            ..ServerLimits::default()
+        },
+    );
+    // A reader body makes ureq send `Transfer-Encoding: chunked`.
+    let post_chunked = |body: String| match ureq::post(&format!("{base}/api/control"))
+        .set("Content-Type", "application/json")
+        .send(std::io::Cursor::new(body.into_bytes()))
+    {
+        Ok(response) => response.status(),
+        Err(ureq::Error::Status(status, _)) => status,
+        Err(err) => panic!("request failed: {err}"),
+    };
+
+    let small = json!({ "id": 1, "type": "status" }).to_string();
+    assert_eq!(post_chunked(small), 200);
+
+    let large = json!({ "id": 2, "type": "status", "pad": "x".repeat(1024) }).to_string();
+    assert_eq!(post_chunked(large), 400);
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "services")]
+
 use std::collections::VecDeque;
 use std::io::{Read, Write};
 use std::net::{TcpListener, TcpStream};
@@ -8,8 +10,10 @@ use std::time::Duration;
 
 use indexmap::IndexMap;
 use smol_str::SmolStr;
-use trust_runtime::config::{ControlMode, WebAuthMode, WebConfig};
-use trust_runtime::control::{ControlState, HmiRuntimeDescriptor, SourceFile, SourceRegistry};
+use trust_runtime::config::{ControlMode, ServerLimits, WebAuthMode, WebConfig};
+use trust_runtime::control::{
+    ControlSessions, ControlState, HmiRuntimeDescriptor, SourceFile, SourceRegistry,
+};
 use trust_runtime::debug::DebugVariableHandles;
 use trust_runtime::error::RuntimeError;
 use trust_runtime::harness::TestHarness;
@@ -115,10 +119,14 @@ fn control_state(source: &str) -> Arc<ControlState> {
         io_health: Arc::new(Mutex::new(Vec::new())),
         debug_enabled: Arc::new(AtomicBool::new(true)),
         debug_variables: Arc::new(Mutex::new(DebugVariableHandles::new())),
+        sessions: Arc::new(ControlSessions::default()),
         hmi_live: Arc::new(Mutex::new(trust_runtime::hmi::HmiLiveState::default())),
         hmi_descriptor,
         historian: None,
+        event_store: None,
         pairing: None,
+        mesh: None,
+        redundancy: None,
     })
 }
 
@@ -171,6 +179,7 @@ fn web_tls_handshake_and_downgrade_prevention() {
         listen: SmolStr::new(listen.clone()),
         auth: WebAuthMode::Local,
         tls: true,
+        limits: ServerLimits::default(),
     };
     let tls = tls_materials();
     let _server = start_web_server(&config, state, None, None, None, Some(tls.clone()))
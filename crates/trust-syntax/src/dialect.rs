@@ -0,0 +1,151 @@
+//! Vendor dialect profiles.
+//!
+//! The lexer and parser accept the union of supported vendor syntax so every
+//! consumer sees the same tree. A [`Dialect`] records which of those
+//! extensions a workspace actually allows; later stages (diagnostics,
+//! completion, formatting) consult it to accept, reject, or lay out the
+//! extension. New vendors are added by registering another profile in
+//! [`DIALECTS`].
+
+/// A syntax extension outside IEC 61131-3 that some vendors accept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DialectExtension {
+    /// `REGION name` ... `END_REGION` folding blocks (Siemens TIA Portal SCL).
+    Regions,
+    /// `POINTER TO` types and the `ADR` operator (CODESYS).
+    Pointers,
+    /// `.n` bit access on signed and unsigned integers (CODESYS).
+    IntegerBitAccess,
+}
+
+impl DialectExtension {
+    /// Returns a short user-facing label for the extension.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Regions => "REGION blocks",
+            Self::Pointers => "POINTER TO types and ADR",
+            Self::IntegerBitAccess => "bit access on integers",
+        }
+    }
+}
+
+/// A vendor dialect profile selected through `vendor_profile`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Dialect {
+    /// Canonical profile name.
+    pub name: &'static str,
+    /// Additional `vendor_profile` values that select this dialect.
+    pub aliases: &'static [&'static str],
+    /// Extensions accepted by this dialect.
+    pub extensions: &'static [DialectExtension],
+}
+
+impl Dialect {
+    /// Returns `true` if the dialect accepts `extension`.
+    #[must_use]
+    pub fn supports(&self, extension: DialectExtension) -> bool {
+        self.extensions.contains(&extension)
+    }
+
+    /// Returns the name of the first registered dialect that accepts `extension`.
+    #[must_use]
+    pub fn provider_of(extension: DialectExtension) -> Option<&'static str> {
+        DIALECTS
+            .iter()
+            .find(|dialect| dialect.supports(extension))
+            .map(|dialect| dialect.name)
+    }
+
+    /// Resolves a `vendor_profile` value to its dialect, defaulting to plain IEC.
+    #[must_use]
+    pub fn for_vendor_profile(profile: Option<&str>) -> &'static Dialect {
+        let Some(profile) = profile.map(str::trim).filter(|profile| !profile.is_empty()) else {
+            return &IEC;
+        };
+        DIALECTS
+            .iter()
+            .copied()
+            .find(|dialect| {
+                dialect.name.eq_ignore_ascii_case(profile)
+                    || dialect
+                        .aliases
+                        .iter()
+                        .any(|alias| alias.eq_ignore_ascii_case(profile))
+            })
+            .unwrap_or(&IEC)
+    }
+}
+
+/// Strict IEC 61131-3 (no vendor extensions).
+pub static IEC: Dialect = Dialect {
+    name: "iec",
+    aliases: &[],
+    extensions: &[],
+};
+
+/// CODESYS and TwinCAT 3.
+pub static CODESYS: Dialect = Dialect {
+    name: "codesys",
+    aliases: &["beckhoff", "twincat"],
+    extensions: &[
+        DialectExtension::Pointers,
+        DialectExtension::IntegerBitAccess,
+    ],
+};
+
+/// Siemens TIA Portal SCL.
+pub static SIEMENS: Dialect = Dialect {
+    name: "siemens",
+    aliases: &[],
+    extensions: &[DialectExtension::Regions],
+};
+
+/// Mitsubishi GX Works3.
+pub static MITSUBISHI: Dialect = Dialect {
+    name: "mitsubishi",
+    aliases: &["gxworks3"],
+    extensions: &[],
+};
+
+/// Registered dialect profiles, searched by [`Dialect::for_vendor_profile`].
+pub static DIALECTS: &[&Dialect] = &[&IEC, &CODESYS, &SIEMENS, &MITSUBISHI];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_vendor_profiles_and_aliases() {
+        assert_eq!(Dialect::for_vendor_profile(None).name, "iec");
+        assert_eq!(Dialect::for_vendor_profile(Some("Siemens")).name, "siemens");
+        assert_eq!(Dialect::for_vendor_profile(Some("twincat")).name, "codesys");
+        assert_eq!(
+            Dialect::for_vendor_profile(Some("gxworks3")).name,
+            "mitsubishi"
+        );
+        assert_eq!(Dialect::for_vendor_profile(Some("unknown")).name, "iec");
+    }
+
+    #[test]
+    fn only_siemens_accepts_regions() {
+        assert!(SIEMENS.supports(DialectExtension::Regions));
+        assert!(!IEC.supports(DialectExtension::Regions));
+        assert!(!CODESYS.supports(DialectExtension::Regions));
+        assert_eq!(
+            Dialect::provider_of(DialectExtension::Regions),
+            Some("siemens")
+        );
+    }
+
+    #[test]
+    fn only_codesys_accepts_pointers() {
+        assert!(CODESYS.supports(DialectExtension::Pointers));
+        assert!(!IEC.supports(DialectExtension::Pointers));
+        assert!(!SIEMENS.supports(DialectExtension::Pointers));
+        assert_eq!(
+            Dialect::provider_of(DialectExtension::Pointers),
+            Some("codesys")
+        );
+    }
+}
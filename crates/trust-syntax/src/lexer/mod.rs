@@ -138,10 +138,64 @@ impl<'src> Iterator for Lexer<'src> {
             }
         }
 
+        if kind == TokenKind::Ident {
+            if let Some(end) = region_marker_end(self.source, span.start, span.end) {
+                self.inner.bump(end - span.end);
+                let range = TextRange::new(
+                    TextSize::from(span.start as u32),
+                    TextSize::from(end as u32),
+                );
+                return Some(Token::new(TokenKind::RegionMarker, range));
+            }
+        }
+
         Some(Token::new(kind, range))
     }
 }
 
+/// Returns the end offset of a `REGION name` / `END_REGION` marker starting at
+/// `start`, or `None` when the identifier is an ordinary name.
+///
+/// Markers must start their line. `REGION` takes the rest of the line as its
+/// title unless that text continues an expression (`region := ...`,
+/// `region.x`, `region(...)`); `END_REGION` may only be followed by `;` or a comment.
+fn region_marker_end(source: &str, start: usize, end: usize) -> Option<usize> {
+    let word = &source[start..end];
+    let is_start = word.eq_ignore_ascii_case("REGION");
+    if !is_start && !word.eq_ignore_ascii_case("END_REGION") {
+        return None;
+    }
+    let line_start = source[..start].rfind('\n').map_or(0, |idx| idx + 1);
+    if !source[line_start..start]
+        .chars()
+        .all(|ch| ch == ' ' || ch == '\t')
+    {
+        return None;
+    }
+    let line_end = source[end..]
+        .find('\n')
+        .map_or(source.len(), |idx| end + idx);
+    let rest = &source[end..line_end];
+    let title = rest.trim_start();
+    if is_start {
+        if title.starts_with(|ch: char| ":=.([^;,)=+-*/<>&".contains(ch)) {
+            return None;
+        }
+        return Some(end + rest.trim_end().len());
+    }
+    if title.is_empty()
+        || title.starts_with("//")
+        || title.starts_with("(*")
+        || title.starts_with("/*")
+    {
+        return Some(end);
+    }
+    if title.starts_with(';') {
+        return Some(end + (rest.len() - title.len()) + 1);
+    }
+    None
+}
+
 /// Lex the entire source and return all tokens.
 ///
 /// This is a convenience function for testing and simple use cases.
@@ -206,6 +260,28 @@ mod tests {
         assert_eq!(non_trivia[2].1, "42");
     }
 
+    #[test]
+    fn test_region_markers_are_trivia() {
+        let source = "REGION Init motors\n    x := 1;\nEND_REGION\nregion := 2;\n";
+        let tokens = lex_with_text(source);
+        let markers: Vec<_> = tokens
+            .iter()
+            .filter(|(t, _)| t.kind == TokenKind::RegionMarker)
+            .map(|(_, text)| *text)
+            .collect();
+        assert_eq!(markers, vec!["REGION Init motors", "END_REGION"]);
+
+        let non_trivia: Vec<_> = tokens
+            .iter()
+            .filter(|(t, _)| !t.kind.is_trivia())
+            .map(|(_, text)| *text)
+            .collect();
+        assert_eq!(
+            non_trivia,
+            vec!["x", ":=", "1", ";", "region", ":=", "2", ";"]
+        );
+    }
+
     #[test]
     fn test_full_function_block() {
         let source = r#"
@@ -76,6 +76,10 @@ pub enum TokenKind {
     #[regex(r"\{[^}]*\}")]
     Pragma,
 
+    /// Vendor region marker: `REGION name` or `END_REGION` on its own line.
+    /// Produced by the lexer wrapper (not logos); treated as trivia.
+    RegionMarker,
+
     // =========================================================================
     // PUNCTUATION
     // =========================================================================
@@ -1003,12 +1007,16 @@ pub enum TokenKind {
 }
 
 impl TokenKind {
-    /// Returns `true` if this token is trivia (whitespace, comment, or pragma).
+    /// Returns `true` if this token is trivia (whitespace, comment, pragma, or region marker).
     #[inline]
     pub fn is_trivia(self) -> bool {
         matches!(
             self,
-            Self::Whitespace | Self::LineComment | Self::BlockComment | Self::Pragma
+            Self::Whitespace
+                | Self::LineComment
+                | Self::BlockComment
+                | Self::Pragma
+                | Self::RegionMarker
         )
     }
 
@@ -5,6 +5,7 @@
 //! - **Lexer**: Tokenizes source text into a stream of tokens
 //! - **Parser**: Builds a concrete syntax tree (CST) from tokens
 //! - **Syntax Tree**: Lossless representation of the source code
+//! - **Dialects**: Vendor profiles that gate non-IEC syntax extensions
 //!
 //! # Design Principles
 //!
@@ -37,10 +38,12 @@
 #![warn(missing_docs)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod dialect;
 pub mod lexer;
 pub mod parser;
 pub mod syntax;
 mod token_kinds;
 
+pub use dialect::{Dialect, DialectExtension};
 pub use lexer::{lex, Lexer, Token, TokenKind};
 pub use syntax::{StLanguage, SyntaxKind, SyntaxNode, SyntaxToken};
@@ -34,7 +34,7 @@ impl Parser<'_, '_> {
                 self.parse_type_def();
                 if self.at(TokenKind::Assign) {
                     self.bump();
-                    self.parse_expression();
+                    self.parse_initializer();
                 }
             } else {
                 self.error("expected ':' after type name");
@@ -366,7 +366,7 @@ impl Parser<'_, '_> {
         // Parse initializer
         if self.at(TokenKind::Assign) {
             self.bump();
-            self.parse_expression();
+            self.parse_initializer();
         }
 
         if self.at(TokenKind::Semicolon) {
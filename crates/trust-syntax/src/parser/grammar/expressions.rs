@@ -340,4 +340,108 @@ impl Parser<'_, '_> {
 
         self.finish_node();
     }
+
+    /// Parse a declaration initializer: an initializer list `(a := 1, b := 2)`, an
+    /// array initializer `[1, 2, 3(0)]`, or an expression.
+    pub(crate) fn parse_initializer(&mut self) {
+        let aggregate = self.at(TokenKind::LBracket)
+            || (self.at(TokenKind::LParen)
+                && self.peek_kind_n(1) == TokenKind::Ident
+                && self.peek_kind_n(2) == TokenKind::Assign);
+        if !aggregate || self.expr_depth >= MAX_EXPRESSION_DEPTH {
+            self.parse_expression();
+            return;
+        }
+
+        self.expr_depth += 1;
+        if self.at(TokenKind::LBracket) {
+            self.parse_array_initializer();
+        } else {
+            self.parse_initializer_list();
+        }
+        self.expr_depth -= 1;
+    }
+
+    fn parse_initializer_list(&mut self) {
+        self.start_node(SyntaxKind::InitializerList);
+        self.bump(); // (
+
+        loop {
+            self.start_node(SyntaxKind::FieldInitializer);
+            if self.at(TokenKind::Ident) {
+                self.parse_name();
+            } else {
+                self.error("expected field name");
+            }
+            if self.at(TokenKind::Assign) {
+                self.bump();
+                self.parse_initializer();
+            } else {
+                self.error("expected :=");
+            }
+            self.finish_node();
+
+            if self.at(TokenKind::Comma) {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        if self.at(TokenKind::RParen) {
+            self.bump();
+        } else {
+            self.error("expected )");
+        }
+
+        self.finish_node();
+    }
+
+    fn parse_array_initializer(&mut self) {
+        self.start_node(SyntaxKind::ArrayInitializer);
+        self.bump(); // [
+
+        self.parse_array_initializer_elements(TokenKind::RBracket);
+        if self.at(TokenKind::RBracket) {
+            self.bump();
+        } else {
+            self.error("expected ]");
+        }
+
+        self.finish_node();
+    }
+
+    /// Parse comma-separated array elements up to `end`, where `n(...)` repeats the
+    /// enclosed elements `n` times.
+    fn parse_array_initializer_elements(&mut self, end: TokenKind) {
+        while !self.at(end) && !self.at_end() {
+            if self.at(TokenKind::IntLiteral)
+                && self.peek_kind_n(1) == TokenKind::LParen
+                && self.expr_depth < MAX_EXPRESSION_DEPTH
+            {
+                self.start_node(SyntaxKind::RepeatInitializer);
+                self.start_node(SyntaxKind::Literal);
+                self.bump();
+                self.finish_node();
+                self.bump(); // (
+                self.expr_depth += 1;
+                self.parse_array_initializer_elements(TokenKind::RParen);
+                self.expr_depth -= 1;
+                if self.at(TokenKind::RParen) {
+                    self.bump();
+                } else {
+                    self.error("expected )");
+                }
+                self.finish_node();
+            } else {
+                self.parse_initializer();
+            }
+
+            if self.at(TokenKind::Comma) {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
 }
@@ -167,8 +167,22 @@ impl Parser<'_, '_> {
             self.parse_var_block();
         }
 
-        // Parse methods, properties, actions, and statements
+        // Parse methods, properties, actions, and statements. Each run of body
+        // statements goes into a statement list, as for PROGRAM and FUNCTION.
+        let mut in_body = false;
         loop {
+            if self.current().can_start_statement() {
+                if !in_body {
+                    self.start_node(SyntaxKind::StmtList);
+                    in_body = true;
+                }
+                self.parse_statement();
+                continue;
+            }
+            if in_body {
+                self.finish_node();
+                in_body = false;
+            }
             if self.at(TokenKind::KwMethod) {
                 self.parse_method();
             } else if self.at(TokenKind::KwProperty) {
@@ -187,10 +201,6 @@ impl Parser<'_, '_> {
                 }
             } else if self.at(TokenKind::KwAction) {
                 self.parse_action();
-            } else if self.at(expected_end) || self.at(alternate_end) || self.at_end() {
-                break;
-            } else if self.current().can_start_statement() {
-                self.parse_statement();
             } else {
                 break;
             }
@@ -624,7 +634,7 @@ impl Parser<'_, '_> {
 
         if self.at(TokenKind::Assign) {
             self.bump();
-            self.parse_expression();
+            self.parse_initializer();
         }
 
         self.finish_node();
@@ -932,6 +942,15 @@ impl Parser<'_, '_> {
             self.parse_var_block();
         }
 
+        // Optional default body
+        if self.current().can_start_statement() {
+            self.start_node(SyntaxKind::StmtList);
+            while !self.at(TokenKind::KwEndMethod) && !self.at_end() && !self.at_stmt_list_end() {
+                self.parse_statement();
+            }
+            self.finish_node();
+        }
+
         if self.at(TokenKind::KwEndMethod) {
             self.bump();
         } else {
@@ -1112,6 +1131,11 @@ impl Parser<'_, '_> {
         self.start_node(SyntaxKind::UsingDirective);
         self.bump(); // USING
 
+        // `USING PUBLIC` re-exports the namespace from the enclosing namespace.
+        if self.at(TokenKind::KwPublic) {
+            self.bump();
+        }
+
         if self.at(TokenKind::Ident) {
             self.parse_qualified_name();
         } else {
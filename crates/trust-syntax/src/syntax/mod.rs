@@ -265,6 +265,12 @@ macro_rules! define_syntax_kind {
             /// Array initializer: `[1, 2, 3]`
             ArrayInitializer,
 
+            /// Field initializer inside an initializer list: `a := 1`
+            FieldInitializer,
+
+            /// Repeated array initializer elements: `3(0)`
+            RepeatInitializer,
+
             /// Condition expression (for IF, WHILE, etc.)
             Condition,
         }
@@ -279,7 +285,11 @@ impl SyntaxKind {
     pub fn is_trivia(self) -> bool {
         matches!(
             self,
-            Self::Whitespace | Self::LineComment | Self::BlockComment | Self::Pragma
+            Self::Whitespace
+                | Self::LineComment
+                | Self::BlockComment
+                | Self::Pragma
+                | Self::RegionMarker
         )
     }
 
@@ -404,6 +414,8 @@ macro_rules! define_syntax_kinds {
             SyntaxKind::SuperExpr,
             SyntaxKind::InitializerList,
             SyntaxKind::ArrayInitializer,
+            SyntaxKind::FieldInitializer,
+            SyntaxKind::RepeatInitializer,
             SyntaxKind::Condition,
         ];
     };
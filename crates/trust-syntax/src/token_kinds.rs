@@ -7,6 +7,7 @@ macro_rules! for_each_token_kind {
             LineComment,
             BlockComment,
             Pragma,
+            RegionMarker,
             Semicolon,
             Colon,
             Comma,
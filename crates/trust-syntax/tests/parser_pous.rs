@@ -131,6 +131,41 @@ END_INTERFACE"#
     ));
 }
 
+#[test]
+fn test_interface_default_method_and_extends() {
+    let parsed = parse(
+        r#"INTERFACE IDerived EXTENDS IBase
+    METHOD Scale : INT
+    VAR_INPUT
+        value : INT;
+    END_VAR
+        Scale := value * 2;
+    END_METHOD
+    METHOD Stop
+    END_METHOD
+END_INTERFACE"#,
+    );
+    assert!(parsed.ok(), "{:?}", parsed.errors());
+    let interface = parsed
+        .syntax()
+        .descendants()
+        .find(|node| node.kind() == SyntaxKind::Interface)
+        .expect("interface");
+    assert!(interface
+        .children()
+        .any(|child| child.kind() == SyntaxKind::ExtendsClause));
+    let bodies: Vec<bool> = interface
+        .children()
+        .filter(|child| child.kind() == SyntaxKind::Method)
+        .map(|method| {
+            method
+                .children()
+                .any(|child| child.kind() == SyntaxKind::StmtList)
+        })
+        .collect();
+    assert_eq!(bodies, vec![true, false]);
+}
+
 #[test]
 fn test_method_with_body() {
     insta::assert_snapshot!(snapshot_parse(
@@ -200,6 +235,27 @@ END_PROGRAM"#
     ));
 }
 
+#[test]
+fn test_using_public_directive() {
+    let parsed = parse(
+        r#"NAMESPACE Facade
+    USING PUBLIC Impl.Core;
+END_NAMESPACE"#,
+    );
+    assert!(parsed.ok(), "{:?}", parsed.errors());
+    let using = parsed
+        .syntax()
+        .descendants()
+        .find(|node| node.kind() == SyntaxKind::UsingDirective)
+        .expect("using directive");
+    assert!(using
+        .children_with_tokens()
+        .any(|child| child.kind() == SyntaxKind::KwPublic));
+    assert!(using
+        .children()
+        .any(|child| child.kind() == SyntaxKind::QualifiedName));
+}
+
 #[test]
 fn test_namespace_qualified_name() {
     insta::assert_snapshot!(snapshot_parse(
@@ -81,3 +81,17 @@ END_VAR
 END_PROGRAM"#
     ));
 }
+
+#[test]
+// IEC 61131-3 Ed.3 Tables 11-12 (structured and array initialization)
+fn test_var_with_structured_initializer() {
+    insta::assert_snapshot!(snapshot_parse(
+        r#"PROGRAM Test
+VAR
+    pos : Position := (x := 1.0, y := 2.0);
+    path : ARRAY[0..3] OF Position := [(x := 1.0), 2((y := -1.0)), (x := 0.0, y := 0.0)];
+    flags : ARRAY[1..5] OF BOOL := [TRUE, 3(FALSE), TRUE];
+END_VAR
+END_PROGRAM"#
+    ));
+}
@@ -0,0 +1,26 @@
+/**
+ * Control responses report failures as `{ code, name, category, message }`.
+ * Runtimes configured with `legacy_errors` send the message as a plain string.
+ */
+export type ControlErrorDetail = {
+  message?: string;
+  /** Symbolic code such as `UNAUTHORIZED`; absent for legacy string errors. */
+  code?: string;
+};
+
+export function controlErrorDetail(error: unknown): ControlErrorDetail {
+  if (typeof error === "string") {
+    return { message: error.trim() || undefined };
+  }
+  if (error && typeof error === "object") {
+    const fields = error as { message?: unknown; name?: unknown };
+    return {
+      message:
+        typeof fields.message === "string" && fields.message.trim()
+          ? fields.message.trim()
+          : undefined,
+      code: typeof fields.name === "string" ? fields.name : undefined,
+    };
+  }
+  return {};
+}
@@ -0,0 +1,102 @@
+import * as vscode from "vscode";
+import {
+  ExecuteCommandRequest,
+  LanguageClient,
+} from "vscode-languageclient/node";
+
+const EVALUATE_SELECTION_UI_COMMAND = "trust-lsp.runtime.evaluateSelection";
+
+type EvaluateSelectionResult = {
+  ok?: boolean;
+  expression?: string;
+  result?: string;
+  type?: string | null;
+  markdown?: string;
+  error?: string;
+};
+
+export function registerEvaluateSelectionCommand(
+  context: vscode.ExtensionContext,
+  client: LanguageClient
+): void {
+  const decoration = vscode.window.createTextEditorDecorationType({
+    after: {
+      margin: "0 0 0 1em",
+      color: new vscode.ThemeColor("editorCodeLens.foreground"),
+      fontStyle: "italic",
+    },
+  });
+  context.subscriptions.push(decoration);
+
+  // Inline results are stale as soon as the document changes.
+  context.subscriptions.push(
+    vscode.workspace.onDidChangeTextDocument((event) => {
+      for (const editor of vscode.window.visibleTextEditors) {
+        if (editor.document === event.document) {
+          editor.setDecorations(decoration, []);
+        }
+      }
+    })
+  );
+
+  context.subscriptions.push(
+    vscode.commands.registerCommand(EVALUATE_SELECTION_UI_COMMAND, async () => {
+      const editor = vscode.window.activeTextEditor;
+      if (!editor || editor.document.languageId !== "structured-text") {
+        vscode.window.showErrorMessage(
+          "Evaluate selection requires an active Structured Text file."
+        );
+        return undefined;
+      }
+      let selection: vscode.Range = editor.selection;
+      if (selection.isEmpty) {
+        const word = editor.document.getWordRangeAtPosition(selection.start);
+        if (!word) {
+          vscode.window.showErrorMessage("Select an expression to evaluate.");
+          return undefined;
+        }
+        selection = word;
+      }
+
+      await client.start();
+      const result = (await client.sendRequest(ExecuteCommandRequest.type, {
+        command: "trust-lsp.evaluateSelection",
+        arguments: [
+          {
+            text_document: { uri: editor.document.uri.toString() },
+            range: {
+              start: {
+                line: selection.start.line,
+                character: selection.start.character,
+              },
+              end: {
+                line: selection.end.line,
+                character: selection.end.character,
+              },
+            },
+          },
+        ],
+      })) as EvaluateSelectionResult | null;
+
+      if (!result?.ok) {
+        vscode.window.showErrorMessage(
+          `Evaluate selection failed: ${result?.error ?? "no response"}`
+        );
+        return result ?? undefined;
+      }
+      const value = result.type
+        ? `${result.result} : ${result.type}`
+        : `${result.result}`;
+      const hover = new vscode.MarkdownString(result.markdown ?? "");
+      const lineEnd = editor.document.lineAt(selection.end.line).range.end;
+      editor.setDecorations(decoration, [
+        {
+          range: new vscode.Range(lineEnd, lineEnd),
+          hoverMessage: hover,
+          renderOptions: { after: { contentText: `= ${value}` } },
+        },
+      ]);
+      return result;
+    })
+  );
+}
@@ -23,6 +23,9 @@ import {
   registerNamespaceMoveCodeActions,
   registerNamespaceMoveContext,
 } from "./namespaceMove";
+import { registerEvaluateSelectionCommand } from "./evaluateSelection";
+import { registerForceVariableCommands } from "./forceVariables";
+import { registerLiveValues } from "./liveValues";
 import { StateChartEditorProvider } from "./statechart/stateChartEditor";
 
 let client: LanguageClient | undefined;
@@ -196,7 +199,11 @@ export async function activate(context: vscode.ExtensionContext) {
   };
 
   const clientOptions: LanguageClientOptions = {
-    documentSelector: [{ scheme: "file", language: "structured-text" }],
+    documentSelector: [
+      { scheme: "file", language: "structured-text" },
+      // Project configuration: key completion, hovers, and runtime validation.
+      { scheme: "file", pattern: "**/{runtime,hmi}.toml" },
+    ],
     synchronize: {
       fileEvents: vscode.workspace.createFileSystemWatcher(
         "**/*.{st,ST,pou,POU}"
@@ -230,6 +237,9 @@ export async function activate(context: vscode.ExtensionContext) {
   registerNamespaceMoveCommand(context, client);
   registerNamespaceMoveCodeActions(context);
   registerNamespaceMoveContext(context);
+  registerEvaluateSelectionCommand(context, client);
+  registerLiveValues(context, client);
+  registerForceVariableCommands(context, client);
   context.subscriptions.push(
     vscode.commands.registerCommand(
       "trust-lsp.hmi.init",
@@ -0,0 +1,189 @@
+import * as vscode from "vscode";
+import {
+  ExecuteCommandRequest,
+  LanguageClient,
+} from "vscode-languageclient/node";
+
+const FORCE_VARIABLE_UI_COMMAND = "trust-lsp.runtime.forceVariable";
+const UNFORCE_VARIABLE_UI_COMMAND = "trust-lsp.runtime.unforceVariable";
+const SHOW_FORCED_UI_COMMAND = "trust-lsp.runtime.showForced";
+
+type PositionLike = { line: number; character: number };
+
+type VariableArgs = {
+  text_document?: { uri: string };
+  position?: PositionLike;
+};
+
+type ForceResult = {
+  ok?: boolean;
+  name?: string;
+  target?: string;
+  error?: string;
+};
+
+type ForcedResult = {
+  ok?: boolean;
+  forced?: { target: string; value: string }[];
+  declarations?: {
+    name: string;
+    target: string;
+    value: string;
+    range: { start: PositionLike; end: PositionLike };
+  }[];
+  error?: string;
+};
+
+/**
+ * Registers the commands behind the "Force…", "Unforce", and "Show forced"
+ * code lenses and marks forced variable declarations in the editor.
+ */
+export function registerForceVariableCommands(
+  context: vscode.ExtensionContext,
+  client: LanguageClient
+): void {
+  const decoration = vscode.window.createTextEditorDecorationType({
+    after: {
+      margin: "0 0 0 1em",
+      color: new vscode.ThemeColor("editorWarning.foreground"),
+      fontWeight: "bold",
+    },
+    overviewRulerColor: new vscode.ThemeColor("editorWarning.foreground"),
+    overviewRulerLane: vscode.OverviewRulerLane.Right,
+  });
+  context.subscriptions.push(decoration);
+
+  const fetchForced = async (
+    uri: vscode.Uri
+  ): Promise<ForcedResult | null> => {
+    return (await client.sendRequest(ExecuteCommandRequest.type, {
+      command: "trust-lsp.forcedVariables",
+      arguments: [{ text_document: { uri: uri.toString() } }],
+    })) as ForcedResult | null;
+  };
+
+  const refreshDecorations = async (editor?: vscode.TextEditor) => {
+    if (
+      !editor ||
+      editor.document.languageId !== "structured-text" ||
+      !client.isRunning()
+    ) {
+      return;
+    }
+    let result: ForcedResult | null;
+    try {
+      result = await fetchForced(editor.document.uri);
+    } catch {
+      result = null;
+    }
+    if (!result?.ok) {
+      editor.setDecorations(decoration, []);
+      return;
+    }
+    editor.setDecorations(
+      decoration,
+      (result.declarations ?? []).map((entry) => {
+        const end = new vscode.Position(
+          entry.range.end.line,
+          entry.range.end.character
+        );
+        return {
+          range: new vscode.Range(end, end),
+          hoverMessage: `Forced on the runtime (\`${entry.target}\`)`,
+          renderOptions: {
+            after: { contentText: `forced = ${entry.value}` },
+          },
+        };
+      })
+    );
+  };
+
+  const runForce = async (
+    command: string,
+    args: VariableArgs | undefined,
+    value?: string
+  ) => {
+    const editor = vscode.window.activeTextEditor;
+    const uri = args?.text_document?.uri ?? editor?.document.uri.toString();
+    const position = args?.position ?? editor?.selection.active;
+    if (!uri || !position) {
+      vscode.window.showErrorMessage(
+        "Forcing requires a variable declaration in a Structured Text file."
+      );
+      return undefined;
+    }
+    await client.start();
+    const result = (await client.sendRequest(ExecuteCommandRequest.type, {
+      command,
+      arguments: [
+        {
+          text_document: { uri },
+          position: { line: position.line, character: position.character },
+          ...(value !== undefined ? { value } : {}),
+        },
+      ],
+    })) as ForceResult | null;
+    if (!result?.ok) {
+      vscode.window.showErrorMessage(
+        `${command === "trust-lsp.forceVariable" ? "Force" : "Unforce"} failed: ${
+          result?.error ?? "no response"
+        }`
+      );
+    }
+    await refreshDecorations(vscode.window.activeTextEditor);
+    return result ?? undefined;
+  };
+
+  context.subscriptions.push(
+    vscode.commands.registerCommand(
+      FORCE_VARIABLE_UI_COMMAND,
+      async (args?: VariableArgs) => {
+        const value = await vscode.window.showInputBox({
+          prompt: "Value to force (for example TRUE, FALSE, or 42)",
+          ignoreFocusOut: true,
+          validateInput: (text) =>
+            text.trim().length === 0 ? "Enter a value." : undefined,
+        });
+        if (value === undefined) {
+          return undefined;
+        }
+        return runForce("trust-lsp.forceVariable", args, value.trim());
+      }
+    ),
+    vscode.commands.registerCommand(
+      UNFORCE_VARIABLE_UI_COMMAND,
+      async (args?: VariableArgs) => runForce("trust-lsp.unforceVariable", args)
+    ),
+    vscode.commands.registerCommand(SHOW_FORCED_UI_COMMAND, async () => {
+      const editor = vscode.window.activeTextEditor;
+      if (!editor) {
+        return undefined;
+      }
+      await client.start();
+      const result = await fetchForced(editor.document.uri);
+      await refreshDecorations(editor);
+      if (!result?.ok) {
+        vscode.window.showErrorMessage(
+          `Show forced failed: ${result?.error ?? "no response"}`
+        );
+        return result ?? undefined;
+      }
+      const forced = result.forced ?? [];
+      if (forced.length === 0) {
+        vscode.window.showInformationMessage("No variables are forced.");
+        return result;
+      }
+      await vscode.window.showQuickPick(
+        forced.map((entry) => ({
+          label: entry.target,
+          description: entry.value,
+        })),
+        { title: "Forced variables", canPickMany: false }
+      );
+      return result;
+    }),
+    vscode.window.onDidChangeActiveTextEditor((editor) => {
+      void refreshDecorations(editor);
+    })
+  );
+}
@@ -2,6 +2,7 @@ import * as net from "net";
 import * as path from "path";
 import * as vscode from "vscode";
 
+import { controlErrorDetail } from "./controlErrors";
 import { defaultRuntimeControlEndpoint } from "./runtimeDefaults";
 
 const HMI_PANEL_VIEW_TYPE = "trust-hmi-preview";
@@ -612,15 +613,14 @@ async function sendControlRequest(
             const parsedLine = JSON.parse(line) as {
               ok?: boolean;
               result?: unknown;
-              error?: string;
+              error?: unknown;
             };
             if (parsedLine.ok) {
               finish(() => resolve(parsedLine.result));
             } else {
               const errorText =
-                typeof parsedLine.error === "string" && parsedLine.error.length > 0
-                  ? parsedLine.error
-                  : "control request rejected";
+                controlErrorDetail(parsedLine.error).message ??
+                "control request rejected";
               finish(() => reject(new Error(errorText)));
             }
             return;
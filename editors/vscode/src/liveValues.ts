@@ -0,0 +1,159 @@
+import * as vscode from "vscode";
+import {
+  ExecuteCommandRequest,
+  LanguageClient,
+} from "vscode-languageclient/node";
+
+const TOGGLE_LIVE_VALUES_COMMAND = "trust-lsp.runtime.toggleLiveValues";
+const MIN_REFRESH_MS = 200;
+
+type LiveValue = {
+  range: {
+    start: { line: number; character: number };
+    end: { line: number; character: number };
+  };
+  text: string;
+};
+
+type LiveValuesResult = {
+  ok?: boolean;
+  values?: LiveValue[];
+  error?: string;
+};
+
+function liveValuesConfig(): { enabled: boolean; refreshMs: number } {
+  const config = vscode.workspace.getConfiguration("trust-lsp");
+  const enabled = config.get<boolean>("runtime.liveValues.enabled") ?? false;
+  const refreshMs = config.get<number>("runtime.liveValues.refreshMs") ?? 1000;
+  return { enabled, refreshMs: Math.max(MIN_REFRESH_MS, refreshMs) };
+}
+
+/**
+ * Shows runtime values next to variables while monitoring a running PLC.
+ *
+ * Values are read through the language server's `trust-lsp.liveValues`
+ * command, which only issues read-only runtime requests. Polling pauses
+ * while a debug session is active, where inline values take over.
+ */
+export function registerLiveValues(
+  context: vscode.ExtensionContext,
+  client: LanguageClient
+): void {
+  const decoration = vscode.window.createTextEditorDecorationType({
+    after: {
+      margin: "0 0 0 0.5em",
+      color: new vscode.ThemeColor("editorInlayHint.foreground"),
+      backgroundColor: new vscode.ThemeColor("editorInlayHint.background"),
+    },
+  });
+  context.subscriptions.push(decoration);
+
+  let timer: NodeJS.Timeout | undefined;
+  let inFlight = false;
+
+  const clearAll = () => {
+    for (const editor of vscode.window.visibleTextEditors) {
+      editor.setDecorations(decoration, []);
+    }
+  };
+
+  const refreshEditor = async (editor: vscode.TextEditor) => {
+    const visible = editor.visibleRanges;
+    if (visible.length === 0) {
+      return;
+    }
+    const first = visible[0];
+    const last = visible[visible.length - 1];
+    const result = (await client.sendRequest(ExecuteCommandRequest.type, {
+      command: "trust-lsp.liveValues",
+      arguments: [
+        {
+          text_document: { uri: editor.document.uri.toString() },
+          range: {
+            start: { line: first.start.line, character: 0 },
+            end: {
+              line: last.end.line,
+              character: last.end.character,
+            },
+          },
+        },
+      ],
+    })) as LiveValuesResult | null;
+    if (!result?.ok) {
+      editor.setDecorations(decoration, []);
+      return;
+    }
+    editor.setDecorations(
+      decoration,
+      (result.values ?? []).map((value) => {
+        const end = new vscode.Position(
+          value.range.end.line,
+          value.range.end.character
+        );
+        return {
+          range: new vscode.Range(end, end),
+          renderOptions: { after: { contentText: value.text.trim() } },
+        };
+      })
+    );
+  };
+
+  const refresh = async () => {
+    if (inFlight || !client.isRunning()) {
+      return;
+    }
+    if (vscode.debug.activeDebugSession) {
+      clearAll();
+      return;
+    }
+    inFlight = true;
+    try {
+      for (const editor of vscode.window.visibleTextEditors) {
+        if (editor.document.languageId === "structured-text") {
+          await refreshEditor(editor);
+        }
+      }
+    } catch {
+      clearAll();
+    } finally {
+      inFlight = false;
+    }
+  };
+
+  const restart = () => {
+    if (timer) {
+      clearInterval(timer);
+      timer = undefined;
+    }
+    const { enabled, refreshMs } = liveValuesConfig();
+    if (!enabled) {
+      clearAll();
+      return;
+    }
+    timer = setInterval(() => void refresh(), refreshMs);
+    void refresh();
+  };
+
+  context.subscriptions.push(
+    { dispose: () => timer && clearInterval(timer) },
+    vscode.workspace.onDidChangeConfiguration((event) => {
+      if (
+        event.affectsConfiguration("trust-lsp.runtime.liveValues") ||
+        event.affectsConfiguration("trust-lsp.runtime.controlEndpoint")
+      ) {
+        restart();
+      }
+    }),
+    vscode.commands.registerCommand(TOGGLE_LIVE_VALUES_COMMAND, async () => {
+      const config = vscode.workspace.getConfiguration("trust-lsp");
+      const enabled = config.get<boolean>("runtime.liveValues.enabled") ?? false;
+      await config.update(
+        "runtime.liveValues.enabled",
+        !enabled,
+        vscode.ConfigurationTarget.Workspace
+      );
+    })
+  );
+
+  restart();
+}
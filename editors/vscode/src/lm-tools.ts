@@ -4,6 +4,7 @@ import * as net from "net";
 import { TextDecoder } from "util";
 import * as vscode from "vscode";
 import type { LanguageClient } from "vscode-languageclient/node";
+import { controlErrorDetail } from "./controlErrors";
 import { defaultRuntimeControlEndpoint } from "./runtimeDefaults";
 
 type LmApi = {
@@ -710,19 +711,12 @@ async function sendRuntimeControlRequest(
               ok?: boolean;
               result?: unknown;
               error?: unknown;
-              code?: unknown;
             };
             if (parsedLine.ok) {
               finish(() => resolve(parsedLine.result));
             } else {
-              const code =
-                typeof parsedLine.code === "string" && parsedLine.code.trim()
-                  ? parsedLine.code.trim()
-                  : undefined;
-              const detail =
-                typeof parsedLine.error === "string" && parsedLine.error.trim()
-                  ? parsedLine.error.trim()
-                  : "control request rejected";
+              const { code, message } = controlErrorDetail(parsedLine.error);
+              const detail = message ?? "control request rejected";
               finish(() =>
                 reject(new Error(code ? `${code}: ${detail}` : detail)),
               );
@@ -5,6 +5,7 @@
 
 import * as net from "net";
 import * as vscode from "vscode";
+import { controlErrorDetail } from "../controlErrors";
 
 export interface RuntimeConfig {
   controlEndpoint: string; // e.g., "unix:///tmp/trust-debug.sock" or "tcp://127.0.0.1:9000"
@@ -29,6 +30,8 @@ export interface ControlResponse {
   ok: boolean;
   result?: any;
   error?: string;
+  /** Symbolic error code such as `INVALID_PARAMS`. */
+  errorCode?: string;
 }
 
 /**
@@ -151,7 +154,13 @@ export class RuntimeClient {
           if (!line.trim()) continue;
 
           try {
-            const response: ControlResponse = JSON.parse(line);
+            const parsed = JSON.parse(line);
+            const { message, code } = controlErrorDetail(parsed.error);
+            const response: ControlResponse = {
+              ...parsed,
+              error: message,
+              errorCode: code,
+            };
             if (response.id === id) {
               settle(() => resolve(response));
             }